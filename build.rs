@@ -0,0 +1,16 @@
+//! Generates the gRPC node API bindings when the `grpc` feature is enabled.
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    #[cfg(feature = "grpc")]
+    {
+        println!("cargo:rerun-if-changed=proto/powerhouse_node.proto");
+        let protoc = protoc_bin_vendored::protoc_bin_path().expect("vendored protoc");
+        std::env::set_var("PROTOC", protoc);
+        tonic_build::configure()
+            .build_client(true)
+            .build_server(true)
+            .compile_protos(&["proto/powerhouse_node.proto"], &["proto"])
+            .expect("compile proto/powerhouse_node.proto");
+    }
+}
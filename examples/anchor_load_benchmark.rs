@@ -0,0 +1,294 @@
+//! Measures `load_anchor_from_logs` against the number of transcript logs.
+//!
+//! ```text
+//! cargo run --release --features net --example anchor_load_benchmark -- \
+//!     [--counts 1000,10000,100000] [--layout files|journal] [--warm-runs 3] \
+//!     [--drop-caches] [--json results.json] \
+//!     [--baseline results.json --max-regression-pct 25]
+//! ```
+//!
+//! For each count a fresh directory of synthetic records is generated and
+//! loaded once cold and `--warm-runs` times warm.  The warm pass is then
+//! split into its phases: listing the directory, reading the records,
+//! parsing them, and recomputing their digests.  `--drop-caches` evicts the
+//! page cache before the cold load (root only); without it the cold load
+//! reads freshly written files.  With `--baseline`, the run fails if any
+//! warm load is more than `--max-regression-pct` slower than the baseline.
+
+use power_house::net::load_anchor_from_logs;
+use power_house::{
+    iter_ledger_logs, parse_transcript_record, profile, transcript_digest, write_transcript_record,
+    FsyncPolicy, JournalConfig, TranscriptJournal,
+};
+use serde::{Deserialize, Serialize};
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use std::process::exit;
+use std::time::{Duration, Instant};
+use std::{env, fs};
+
+const SCHEMA: &str = "mfenx.powerhouse.anchor_load_bench.v1";
+const ROUNDS: u64 = 8;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Report {
+    schema: String,
+    layout: String,
+    results: Vec<Sample>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Sample {
+    logs: usize,
+    cold_ms: f64,
+    warm_ms: f64,
+    list_ms: f64,
+    io_ms: f64,
+    parse_ms: f64,
+    digest_ms: f64,
+    warm_us_per_log: f64,
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("anchor_load_benchmark: {message}");
+    exit(2);
+}
+
+fn record_lines(index: u64) -> Vec<String> {
+    let challenges: Vec<u64> = (0..ROUNDS).map(|round| index * 31 + round).collect();
+    let round_sums: Vec<u64> = (0..ROUNDS).map(|round| index * 17 + round * 3).collect();
+    let mut lines = vec![format!("statement:bench statement {index}")];
+    write_transcript_record(
+        |line| {
+            lines.push(line.to_string());
+            Ok(())
+        },
+        &challenges,
+        &round_sums,
+        index + 1,
+    )
+    .expect("writing to memory cannot fail");
+    lines
+}
+
+fn generate(dir: &Path, count: usize, journal: bool) {
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap_or_else(|err| fail(&format!("{}: {err}", dir.display())));
+    if journal {
+        let config = JournalConfig {
+            fsync: FsyncPolicy::Never,
+            ..JournalConfig::default()
+        };
+        let mut journal =
+            TranscriptJournal::open(dir, config).unwrap_or_else(|err| fail(&err.to_string()));
+        for index in 0..count as u64 {
+            journal
+                .append(&record_lines(index))
+                .unwrap_or_else(|err| fail(&err.to_string()));
+        }
+        journal.sync().unwrap_or_else(|err| fail(&err.to_string()));
+    } else {
+        for index in 0..count as u64 {
+            let path = dir.join(format!("ledger_{index:08}.txt"));
+            fs::write(&path, record_lines(index).join("\n"))
+                .unwrap_or_else(|err| fail(&format!("{}: {err}", path.display())));
+        }
+    }
+}
+
+fn drop_caches() {
+    if let Err(err) = fs::write("/proc/sys/vm/drop_caches", "3") {
+        eprintln!("warning: could not drop the page cache ({err}); cold load reads cached files");
+    }
+}
+
+fn timed<T>(run: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let value = run();
+    (value, start.elapsed())
+}
+
+fn load(dir: &Path) -> Duration {
+    let (anchor, elapsed) = timed(|| load_anchor_from_logs(dir));
+    black_box(anchor.unwrap_or_else(|err| fail(&err.to_string())));
+    elapsed
+}
+
+/// Repeats the loader's per-record work one phase at a time.
+fn phases(dir: &Path) -> (Duration, Duration, Duration, Duration) {
+    let (records, list) = timed(|| {
+        iter_ledger_logs(dir)
+            .unwrap_or_else(|err| fail(&err))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_or_else(|err| fail(&err))
+    });
+    let (contents, io) = timed(|| {
+        records
+            .iter()
+            .map(|record| record.bytes().unwrap_or_else(|err| fail(&err)))
+            .collect::<Vec<_>>()
+    });
+    let (parsed, parse) = timed(|| {
+        contents
+            .iter()
+            .map(|bytes| {
+                let text = std::str::from_utf8(bytes).unwrap_or_else(|err| fail(&err.to_string()));
+                parse_transcript_record(text.lines().skip(1).map(str::trim))
+                    .unwrap_or_else(|err| fail(&err))
+            })
+            .collect::<Vec<_>>()
+    });
+    let ((), digest) = timed(|| {
+        for (challenges, round_sums, final_value, stored) in &parsed {
+            assert_eq!(
+                &transcript_digest(challenges, round_sums, *final_value),
+                stored
+            );
+        }
+    });
+    (list, io, parse, digest)
+}
+
+fn main() {
+    let mut counts = vec![1_000, 10_000, 100_000];
+    let mut journal = false;
+    let mut warm_runs = 3usize;
+    let mut evict = false;
+    let mut json_out: Option<PathBuf> = None;
+    let mut baseline: Option<PathBuf> = None;
+    let mut max_regression_pct = 25.0f64;
+    let mut root = env::temp_dir().join(format!("anchor_load_bench_{}", std::process::id()));
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .unwrap_or_else(|| fail(&format!("{arg} expects a value")))
+        };
+        match arg.as_str() {
+            "--counts" => {
+                counts = value()
+                    .split(',')
+                    .map(|count| {
+                        count
+                            .trim()
+                            .parse()
+                            .unwrap_or_else(|_| fail("bad --counts"))
+                    })
+                    .collect();
+            }
+            "--layout" => match value().as_str() {
+                "files" => journal = false,
+                "journal" => journal = true,
+                other => fail(&format!("unknown layout {other}")),
+            },
+            "--warm-runs" => {
+                warm_runs = value()
+                    .parse::<usize>()
+                    .unwrap_or_else(|_| fail("bad --warm-runs"))
+                    .max(1);
+            }
+            "--drop-caches" => evict = true,
+            "--json" => json_out = Some(PathBuf::from(value())),
+            "--baseline" => baseline = Some(PathBuf::from(value())),
+            "--max-regression-pct" => {
+                max_regression_pct = value()
+                    .parse()
+                    .unwrap_or_else(|_| fail("bad --max-regression-pct"));
+            }
+            "--dir" => root = PathBuf::from(value()),
+            other => fail(&format!("unknown argument {other}")),
+        }
+    }
+
+    profile::enable();
+    let layout = if journal { "journal" } else { "files" };
+    println!(
+        "{:>8} {:>10} {:>10} {:>9} {:>9} {:>9} {:>9} {:>10}",
+        "logs", "cold_ms", "warm_ms", "list_ms", "io_ms", "parse_ms", "digest_ms", "us/log"
+    );
+    let mut report = Report {
+        schema: SCHEMA.to_string(),
+        layout: layout.to_string(),
+        results: Vec::new(),
+    };
+    for &count in &counts {
+        let dir = root.join(format!("{layout}_{count}"));
+        generate(&dir, count, journal);
+        if evict {
+            drop_caches();
+        }
+        let cold = load(&dir);
+        let mut warm: Vec<Duration> = (0..warm_runs).map(|_| load(&dir)).collect();
+        warm.sort();
+        let warm = warm[warm.len() / 2];
+        let (list, io, parse, digest) = phases(&dir);
+        let sample = Sample {
+            logs: count,
+            cold_ms: millis(cold),
+            warm_ms: millis(warm),
+            list_ms: millis(list),
+            io_ms: millis(io),
+            parse_ms: millis(parse),
+            digest_ms: millis(digest),
+            warm_us_per_log: millis(warm) * 1000.0 / count.max(1) as f64,
+        };
+        println!(
+            "{:>8} {:>10.1} {:>10.1} {:>9.1} {:>9.1} {:>9.1} {:>9.1} {:>10.2}",
+            sample.logs,
+            sample.cold_ms,
+            sample.warm_ms,
+            sample.list_ms,
+            sample.io_ms,
+            sample.parse_ms,
+            sample.digest_ms,
+            sample.warm_us_per_log
+        );
+        report.results.push(sample);
+        let _ = fs::remove_dir_all(&dir);
+    }
+    let _ = fs::remove_dir(&root);
+    println!();
+    print!("{}", profile::render_table(&profile::summary()));
+
+    if let Some(path) = json_out {
+        let encoded = serde_json::to_string_pretty(&report).expect("report serializes");
+        fs::write(&path, encoded).unwrap_or_else(|err| fail(&format!("{}: {err}", path.display())));
+    }
+    if let Some(path) = baseline {
+        let bytes =
+            fs::read(&path).unwrap_or_else(|err| fail(&format!("{}: {err}", path.display())));
+        let baseline: Report =
+            serde_json::from_slice(&bytes).unwrap_or_else(|err| fail(&err.to_string()));
+        if baseline.schema != SCHEMA || baseline.layout != report.layout {
+            fail("baseline was recorded with another schema or layout");
+        }
+        let mut regressed = false;
+        for sample in &report.results {
+            let Some(base) = baseline
+                .results
+                .iter()
+                .find(|base| base.logs == sample.logs)
+            else {
+                continue;
+            };
+            let change = (sample.warm_ms / base.warm_ms.max(f64::EPSILON) - 1.0) * 100.0;
+            let verdict = if change > max_regression_pct {
+                regressed = true;
+                "REGRESSED"
+            } else {
+                "ok"
+            };
+            println!(
+                "{} logs: warm {:.1} ms vs baseline {:.1} ms ({change:+.1}%) {verdict}",
+                sample.logs, sample.warm_ms, base.warm_ms
+            );
+        }
+        if regressed {
+            exit(1);
+        }
+    }
+}
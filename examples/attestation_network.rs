@@ -0,0 +1,256 @@
+//! Distributed data-integrity attestation across three in-process nodes.
+//!
+//! ```text
+//! cargo run --example attestation_network -- [data.csv] [column] [--tamper]
+//! ```
+//!
+//! Every node holds its own copy of a CSV dataset.  The first node proves the
+//! sum of one column with a multilinear sum-check and gossips the proof; each
+//! node rebuilds the column polynomial from its local copy, verifies the proof
+//! into its own ledger, and gossips the resulting anchor.  Nodes then check
+//! quorum agreement over the anchors they received and the first node prints a
+//! Merkle receipt tying the column-sum transcript to the ledger root.
+//!
+//! `--tamper` edits one cell of the third node's copy: its verification fails,
+//! its anchor diverges, and the two honest nodes still reach a 2-of-3 quorum.
+
+use blake2::digest::{consts::U32, Digest};
+use power_house::{
+    build_merkle_proof, reconcile_anchors_with_quorum, transcript_digest_to_hex,
+    verify_merkle_proof, AnchorVote, Field, GeneralSumProof, LedgerAnchor, MultilinearPolynomial,
+    Proof, ProofKind, ProofLedger, Statement, TranscriptDigest,
+};
+use std::{fs, sync::mpsc, thread};
+
+type Blake2b256 = blake2::Blake2b<U32>;
+
+/// Goldilocks prime: column sums below it are proven exactly.
+const MODULUS: u64 = 0xFFFF_FFFF_0000_0001;
+const NODES: [&str; 3] = ["node-a", "node-b", "node-c"];
+const QUORUM: usize = 2;
+
+const SAMPLE_CSV: &str = "\
+sensor,reading,flow
+s-01,412,17
+s-02,398,22
+s-03,405,19
+s-04,421,25
+s-05,390,18
+";
+
+/// Messages exchanged over the in-process gossip mesh.
+#[derive(Clone)]
+enum Gossip {
+    Proof {
+        statement: Statement,
+        proof: GeneralSumProof,
+    },
+    Anchor {
+        node: usize,
+        anchor: LedgerAnchor,
+    },
+}
+
+/// Reads `column` from a headed CSV as field elements.
+fn column_values(csv: &str, column: &str) -> Result<Vec<u64>, String> {
+    let mut lines = csv.lines().filter(|line| !line.trim().is_empty());
+    let header = lines.next().ok_or("dataset is empty")?;
+    let index = header
+        .split(',')
+        .position(|name| name.trim() == column)
+        .ok_or_else(|| format!("column `{column}` not found"))?;
+    lines
+        .enumerate()
+        .map(|(row, line)| {
+            let cell = line.split(',').nth(index).unwrap_or("").trim();
+            match cell.parse::<u64>() {
+                Ok(value) if value < MODULUS => Ok(value),
+                _ => Err(format!("row {}: `{cell}` is not a field element", row + 1)),
+            }
+        })
+        .collect()
+}
+
+/// Flips the low bit of the first value in `column`.
+fn tamper_first_cell(csv: &str, column: &str) -> String {
+    let mut lines: Vec<String> = csv.lines().map(str::to_string).collect();
+    let index = lines[0]
+        .split(',')
+        .position(|name| name.trim() == column)
+        .expect("column exists");
+    let mut cells: Vec<String> = lines[1].split(',').map(str::to_string).collect();
+    let value: u64 = cells[index].trim().parse().expect("numeric cell");
+    cells[index] = (value ^ 1).to_string();
+    lines[1] = cells.join(",");
+    lines.join("\n") + "\n"
+}
+
+/// Zero-pads the column to a power of two and lifts it to a multilinear table.
+fn column_polynomial(values: &[u64]) -> MultilinearPolynomial {
+    let size = values.len().max(2).next_power_of_two();
+    let mut evaluations = values.to_vec();
+    evaluations.resize(size, 0);
+    MultilinearPolynomial::from_evaluations(size.trailing_zeros() as usize, evaluations)
+}
+
+fn dataset_digest(csv: &str) -> String {
+    let digest: TranscriptDigest = Blake2b256::digest(csv.as_bytes()).into();
+    transcript_digest_to_hex(&digest)
+}
+
+fn run_node(
+    id: usize,
+    csv: String,
+    column: String,
+    inbox: mpsc::Receiver<Gossip>,
+    peers: Vec<mpsc::Sender<Gossip>>,
+) -> (ProofLedger, bool) {
+    let field = Field::new(MODULUS);
+    let broadcast = |message: Gossip| {
+        for peer in &peers {
+            let _ = peer.send(message.clone());
+        }
+    };
+    let mut ledger = ProofLedger::new();
+    let values = column_values(&csv, &column).expect("dataset column");
+    let polynomial = column_polynomial(&values);
+
+    // The first node proves the column sum; everyone verifies against local data.
+    let mut anchors = Vec::new();
+    let (statement, proof) = if id == 0 {
+        let proof = GeneralSumProof::prove(&polynomial, &field);
+        let statement = Statement::structured("power_house.attestation", "column_sum")
+            .with_parameter("dataset", dataset_digest(&csv))
+            .with_parameter("column", column.as_str())
+            .with_parameter("rows", values.len().to_string())
+            .with_parameter("sum", proof.claim.claimed_sum.to_string());
+        println!(
+            "[{}] proved sum({column}) = {} over {} rows",
+            NODES[id],
+            proof.claim.claimed_sum,
+            values.len()
+        );
+        broadcast(Gossip::Proof {
+            statement: statement.clone(),
+            proof: proof.clone(),
+        });
+        (statement, proof)
+    } else {
+        loop {
+            match inbox.recv().expect("gossip mesh open") {
+                Gossip::Proof { statement, proof } => break (statement, proof),
+                // A fast peer's anchor can overtake the proof; keep it for later.
+                Gossip::Anchor { node, anchor } => anchors.push((node, anchor)),
+            }
+        }
+    };
+    ledger.submit(
+        statement,
+        Proof {
+            kind: ProofKind::General { polynomial, proof },
+            data: Vec::new(),
+        },
+    );
+    let accepted = ledger.entries().last().is_some_and(|entry| entry.accepted);
+    println!(
+        "[{}] verified against local dataset {}: {}",
+        NODES[id],
+        &dataset_digest(&csv)[..16],
+        if accepted { "accepted" } else { "REJECTED" }
+    );
+
+    // Gossip our anchor, then collect one from every peer.
+    let own = ledger.anchor();
+    broadcast(Gossip::Anchor {
+        node: id,
+        anchor: own.clone(),
+    });
+    anchors.push((id, own));
+    while anchors.len() < NODES.len() {
+        if let Gossip::Anchor { node, anchor } = inbox.recv().expect("gossip mesh open") {
+            anchors.push((node, anchor));
+        }
+    }
+    anchors.sort_by_key(|(node, _)| *node);
+    let votes: Vec<AnchorVote<'_>> = anchors
+        .iter()
+        .map(|(node, anchor)| AnchorVote {
+            anchor,
+            public_key: NODES[*node].as_bytes(),
+        })
+        .collect();
+    match reconcile_anchors_with_quorum(&votes, QUORUM) {
+        Ok(()) => println!("[{}] quorum {QUORUM}/{} reached", NODES[id], NODES.len()),
+        Err(err) => println!("[{}] no quorum: {err}", NODES[id]),
+    }
+    (ledger, accepted)
+}
+
+fn main() {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let tamper = args.iter().any(|arg| arg == "--tamper");
+    args.retain(|arg| arg != "--tamper");
+    let csv = match args.first() {
+        Some(path) => fs::read_to_string(path).expect("read dataset"),
+        None => SAMPLE_CSV.to_string(),
+    };
+    let column = args
+        .get(1)
+        .cloned()
+        .unwrap_or_else(|| "reading".to_string());
+
+    let (senders, inboxes): (Vec<_>, Vec<_>) = NODES.iter().map(|_| mpsc::channel()).unzip();
+    let handles: Vec<_> = inboxes
+        .into_iter()
+        .enumerate()
+        .map(|(id, inbox)| {
+            let peers = senders
+                .iter()
+                .enumerate()
+                .filter(|(peer, _)| *peer != id)
+                .map(|(_, sender)| sender.clone())
+                .collect();
+            let local = if tamper && id == 2 {
+                tamper_first_cell(&csv, &column)
+            } else {
+                csv.clone()
+            };
+            let column = column.clone();
+            thread::spawn(move || run_node(id, local, column, inbox, peers))
+        })
+        .collect();
+    drop(senders);
+    let results: Vec<(ProofLedger, bool)> = handles
+        .into_iter()
+        .map(|handle| handle.join().expect("node thread"))
+        .collect();
+
+    // Merkle receipt from the proving node: transcript hash -> ledger root.
+    let (ledger, accepted) = &results[0];
+    if !accepted {
+        println!("proving node rejected its own proof; no receipt");
+        return;
+    }
+    let hashes: Vec<TranscriptDigest> = ledger
+        .entries()
+        .iter()
+        .flat_map(|entry| entry.hashes.iter().copied())
+        .collect();
+    let index = hashes.len() - 1;
+    let receipt = build_merkle_proof(&hashes, index).expect("leaf in ledger");
+    assert!(verify_merkle_proof(&receipt));
+    assert_eq!(receipt.root, ledger.ledger_root());
+    let entry = ledger.entries().last().expect("attested entry");
+    println!("receipt:");
+    println!("  statement   {}", entry.statement.canonical());
+    println!("  transcript  {}", transcript_digest_to_hex(&receipt.leaf));
+    println!("  leaf index  {}", receipt.index);
+    for node in &receipt.path {
+        let side = if node.left { "left " } else { "right" };
+        println!(
+            "  sibling     {side} {}",
+            transcript_digest_to_hex(&node.sibling)
+        );
+    }
+    println!("  ledger root {}", transcript_digest_to_hex(&receipt.root));
+}
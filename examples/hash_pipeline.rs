@@ -77,9 +77,7 @@ fn main() {
 
     let mut ledger_a = ProofLedger::new();
     ledger_a.enable_logging(&dir_a);
-    let base_statement = Statement {
-        description: "Dense polynomial proof".into(),
-    };
+    let base_statement = Statement::new("Dense polynomial proof");
     ledger_a.submit(
         base_statement.clone(),
         Proof {
@@ -124,9 +122,7 @@ fn main() {
     );
 
     ledger_a.submit(
-        Statement {
-            description: "Hash anchor proof".into(),
-        },
+        Statement::new("Hash anchor proof"),
         Proof {
             kind: ProofKind::StreamingGeneral {
                 polynomial: anchor_poly.clone(),
@@ -149,9 +145,7 @@ fn main() {
         },
     );
     ledger_b.submit(
-        Statement {
-            description: "Hash anchor proof".into(),
-        },
+        Statement::new("Hash anchor proof"),
         Proof {
             kind: ProofKind::StreamingGeneral {
                 polynomial: anchor_poly,
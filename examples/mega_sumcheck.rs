@@ -50,11 +50,12 @@ fn main() {
         GeneralSumProof::prove_with_stats(&mega_poly, &field);
 
     println!(
-        "Mega sum-check: variables={}, sum={}, final={}, total={:.3} ms",
+        "Mega sum-check: variables={}, sum={}, final={}, total={:.3} ms, peak={} B",
         mega_proof.claim.num_vars,
         mega_proof.claim.claimed_sum,
         mega_proof.final_evaluation,
-        ms(&stats.total_duration)
+        ms(&stats.total_duration),
+        stats.peak_memory_bytes
     );
     for (round, duration) in stats.round_durations.iter().enumerate() {
         println!("  round {:02}: {:.3} ms", round, ms(duration));
@@ -98,12 +99,7 @@ fn main() {
         },
         data: Vec::new(),
     };
-    ledger.submit(
-        Statement {
-            description: "10-variable mega polynomial".into(),
-        },
-        general_entry,
-    );
+    ledger.submit(Statement::new("10-variable mega polynomial"), general_entry);
 
     let chain_entry = Proof {
         kind: ProofKind::Chain {
@@ -112,12 +108,7 @@ fn main() {
         },
         data: Vec::new(),
     };
-    ledger.submit(
-        Statement {
-            description: "Chained mega transcript".into(),
-        },
-        chain_entry,
-    );
+    ledger.submit(Statement::new("Chained mega transcript"), chain_entry);
 
     for (idx, entry) in ledger.entries().iter().enumerate() {
         println!(
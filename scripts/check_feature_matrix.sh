@@ -0,0 +1,31 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+ROOT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")"/.. && pwd)"
+cd "$ROOT_DIR"
+
+CARGO_BIN="${CARGO_BIN:-cargo}"
+
+# Crates that must never reach the verify-only dependency graph.
+HEAVY_CRATES=(
+  ark-bn254 ark-ec ark-ff ark-groth16 ark-r1cs-std ark-relations
+  ed25519-dalek flate2 k256 libp2p prost rayon reqwest rusqlite sha3 tokio tonic
+)
+
+VERIFY_ONLY=(--no-default-features --features verify-only)
+
+"$CARGO_BIN" check --locked --lib "${VERIFY_ONLY[@]}"
+for features in "" "memory" "parallel" "rollup" "sfcs" "sfcs-zk" "net" "sqlite" "keychain" "grpc"; do
+  echo "feature matrix: [${features:-none}]"
+  "$CARGO_BIN" check --locked --lib --no-default-features --features "verify-only $features"
+done
+
+deps=$("$CARGO_BIN" tree --locked "${VERIFY_ONLY[@]}" -e normal --prefix none | sed 's/ v.*//' | sort -u)
+status=0
+for crate in "${HEAVY_CRATES[@]}"; do
+  if grep -qx "$crate" <<<"$deps"; then
+    echo "verify-only build depends on $crate" >&2
+    status=1
+  fi
+done
+exit "$status"
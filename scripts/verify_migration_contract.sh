@@ -61,7 +61,7 @@ net_help="$(cargo run --features net --bin julian --quiet -- net --help 2>&1 ||
 check_contains "$net_help" "Usage: julian net <start|anchor|verify-envelope>" "net help"
 
 migration_help="$(cargo run --features net --bin julian --quiet -- migration --help 2>&1 || true)"
-check_contains "$migration_help" "Usage: julian migration <finalize|verify-state|execute-burn-intents>" "migration help"
+check_contains "$migration_help" "Usage: julian migration <finalize|finalize-status|verify-state|execute-burn-intents|reconcile-burns|sweep-claims|reserve-report|verify-reserve-report>" "migration help"
 
 echo "[5/12] verify migration command help"
 snap_help="$(cargo run --features net --bin julian --quiet -- stake snapshot --help 2>&1 || true)"
@@ -74,7 +74,7 @@ proposal_help="$(cargo run --features net --bin julian --quiet -- governance pro
 check_contains "$proposal_help" "Usage: julian governance propose-migration" "governance propose-migration help"
 
 finalize_help="$(cargo run --features net --bin julian --quiet -- migration finalize --help 2>&1 || true)"
-check_contains "$finalize_help" "Usage: julian migration <finalize|verify-state|execute-burn-intents>" "migration finalize help"
+check_contains "$finalize_help" "Usage: julian migration <finalize|finalize-status|verify-state|execute-burn-intents|reconcile-burns|sweep-claims|reserve-report|verify-reserve-report>" "migration finalize help"
 
 echo "[6/12] verify net anchor compatibility"
 TMP_DIR="$(mktemp -d)"
@@ -0,0 +1,562 @@
+//! Epoch-level aggregation of sum-check proofs.
+//!
+//! Anchors list one transcript digest per accepted proof, so a verifier that
+//! wants to trust an epoch must re-run every sum-check.  [`EpochProof`] folds
+//! all accepted proofs of an epoch into a single statement built from the
+//! existing primitives:
+//!
+//! 1. Every polynomial `P_i` is zero-padded to the widest arity `m` of the
+//!    epoch, and Fiat–Shamir batching coefficients `ρ_i` are derived from the
+//!    epoch number, the transcript digests and the claimed sums `S_i`.
+//! 2. A *batch* sum-check proves `Σ_x Q(x) = Σ_i ρ_i·S_i` for
+//!    `Q = Σ_i ρ_i·P_i`, ending at a random point `r`.
+//! 3. An *openings* sum-check over the table `T(i) = ρ_i·P_i(r)` proves that
+//!    the per-statement openings add up to the batch sum-check's final
+//!    evaluation `Q(r)`.
+//!
+//! [`EpochProof::verify`] replays the batching and both round sequences, then
+//! checks each opening against its polynomial, which costs one multilinear
+//! evaluation per statement instead of a full sum-check.  The openings are
+//! prover-supplied and bound to no commitment, so the composition alone
+//! proves nothing: the per-polynomial check is what makes a false claimed sum
+//! fail, and there is no verification mode without the polynomials.
+
+use crate::{
+    julian::{LedgerEntry, ProofKind, ProofLedger},
+    merkle_root,
+    sumcheck::GENERAL_SUMCHECK_DOMAIN,
+    transcript_digest, transcript_digest_from_hex, transcript_digest_to_hex, Field,
+    GeneralSumProof, MultilinearPolynomial, Transcript, TranscriptDigest,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Schema identifier of an epoch proof.
+pub const EPOCH_PROOF_SCHEMA: &str = "power_house.epoch_proof.v1";
+
+const AGGREGATE_DOMAIN: &[u8] = b"power_house:v1:epoch-aggregate";
+
+/// Errors returned while building or verifying an epoch proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AggregateError {
+    /// No proofs were supplied for the epoch.
+    Empty,
+    /// A proof or polynomial uses a different field than the epoch.
+    FieldMismatch,
+    /// An input proof failed verification before aggregation.
+    InvalidInput(usize),
+    /// The proof document is malformed.
+    Malformed(&'static str),
+    /// The batch sum-check's claimed sum disagrees with the statements.
+    BatchSumMismatch,
+    /// A round of the batch or openings sum-check is inconsistent.
+    RoundMismatch(&'static str, usize),
+    /// A sum-check's final evaluation is inconsistent with its rounds.
+    FinalEvaluationMismatch(&'static str),
+    /// The openings do not add up to the batch final evaluation.
+    OpeningSumMismatch,
+    /// The statement Merkle root does not match the statements.
+    RootMismatch,
+    /// An opening does not match its polynomial.
+    OpeningMismatch(usize),
+}
+
+impl fmt::Display for AggregateError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(formatter, "no proofs to aggregate"),
+            Self::FieldMismatch => write!(formatter, "field modulus mismatch"),
+            Self::InvalidInput(index) => write!(formatter, "input proof {index} does not verify"),
+            Self::Malformed(reason) => write!(formatter, "malformed epoch proof: {reason}"),
+            Self::BatchSumMismatch => write!(formatter, "batch claimed sum mismatch"),
+            Self::RoundMismatch(layer, round) => {
+                write!(formatter, "{layer} sum-check round {round} mismatch")
+            }
+            Self::FinalEvaluationMismatch(layer) => {
+                write!(formatter, "{layer} sum-check final evaluation mismatch")
+            }
+            Self::OpeningSumMismatch => write!(formatter, "openings do not match batch evaluation"),
+            Self::RootMismatch => write!(formatter, "statement root mismatch"),
+            Self::OpeningMismatch(index) => write!(formatter, "opening {index} mismatch"),
+        }
+    }
+}
+
+impl std::error::Error for AggregateError {}
+
+/// One accepted proof folded into an epoch proof.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AggregatedStatement {
+    /// Hex transcript digest of the original proof, as anchored.
+    pub transcript_digest: String,
+    /// Arity of the original polynomial.
+    pub num_vars: usize,
+    /// Sum the original proof claimed.
+    pub claimed_sum: u64,
+    /// Zero-padded polynomial evaluated at the batch challenge point.
+    pub opening: u64,
+}
+
+/// Serializable round data of one sum-check layer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochSumCheck {
+    /// Number of variables of the layer polynomial.
+    pub num_vars: usize,
+    /// Claimed sum over the Boolean hypercube.
+    pub claimed_sum: u64,
+    /// Linear round polynomials `(a_i, b_i)`.
+    pub rounds: Vec<(u64, u64)>,
+    /// Evaluation at the layer's challenge point.
+    pub final_evaluation: u64,
+}
+
+impl EpochSumCheck {
+    fn from_proof(proof: &GeneralSumProof) -> Self {
+        Self {
+            num_vars: proof.claim.num_vars,
+            claimed_sum: proof.claim.claimed_sum,
+            rounds: proof.claim.rounds.clone(),
+            final_evaluation: proof.final_evaluation,
+        }
+    }
+
+    /// Replays the Fiat–Shamir rounds and returns the challenge point.
+    fn replay(&self, field: &Field, layer: &'static str) -> Result<Vec<u64>, AggregateError> {
+        if self.rounds.len() != self.num_vars || self.num_vars == 0 {
+            return Err(AggregateError::Malformed("round count"));
+        }
+        let mut transcript = Transcript::new(GENERAL_SUMCHECK_DOMAIN);
+        transcript.append(field.modulus());
+        transcript.append(self.num_vars as u64);
+        transcript.append(self.claimed_sum);
+        let mut running = self.claimed_sum;
+        let mut challenges = Vec::with_capacity(self.num_vars);
+        for (round, &(a, b)) in self.rounds.iter().enumerate() {
+            if field.add(b, field.add(a, b)) != running {
+                return Err(AggregateError::RoundMismatch(layer, round));
+            }
+            transcript.append(a);
+            transcript.append(b);
+            let r = transcript.challenge(field);
+            running = field.add(field.mul(a, r), b);
+            challenges.push(r);
+        }
+        if running != self.final_evaluation {
+            return Err(AggregateError::FinalEvaluationMismatch(layer));
+        }
+        Ok(challenges)
+    }
+}
+
+/// Single proof attesting to every accepted sum-check of an epoch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochProof {
+    /// Schema identifier (`power_house.epoch_proof.v1`).
+    pub schema: String,
+    /// Epoch the proof covers.
+    pub epoch: u64,
+    /// Prime modulus shared by every aggregated proof.
+    pub modulus: u64,
+    /// Aggregated statements in ledger order.
+    pub statements: Vec<AggregatedStatement>,
+    /// Hex Merkle root over the statements' transcript digests.
+    pub statements_root: String,
+    /// Sum-check over the batched polynomial `Σ ρ_i·P_i`.
+    pub batch: EpochSumCheck,
+    /// Sum-check over the opening table `ρ_i·P_i(r)`.
+    pub openings: EpochSumCheck,
+}
+
+impl EpochProof {
+    /// Aggregates every accepted sum-check proof recorded in `ledger`.
+    pub fn from_ledger(ledger: &ProofLedger, epoch: u64) -> Result<Self, AggregateError> {
+        let mut aggregator: Option<EpochAggregator> = None;
+        for entry in ledger.entries() {
+            let Some(modulus) = entry_modulus(entry) else {
+                continue;
+            };
+            aggregator
+                .get_or_insert_with(|| EpochAggregator::new(Field::new(modulus), epoch))
+                .push_entry(entry)?;
+        }
+        aggregator.ok_or(AggregateError::Empty)?.prove()
+    }
+
+    /// Transcript digests of the aggregated statements.
+    pub fn digests(&self) -> Result<Vec<TranscriptDigest>, AggregateError> {
+        self.statements
+            .iter()
+            .map(|statement| {
+                transcript_digest_from_hex(&statement.transcript_digest)
+                    .map_err(|_| AggregateError::Malformed("transcript digest"))
+            })
+            .collect()
+    }
+
+    /// Checks the batching and both sum-check layers, taking the openings on
+    /// trust.  Returns the batch challenge point at which the openings were
+    /// taken; [`Self::verify`] must still check them.
+    fn check_composition(&self) -> Result<Vec<u64>, AggregateError> {
+        if self.schema != EPOCH_PROOF_SCHEMA {
+            return Err(AggregateError::Malformed("schema"));
+        }
+        if self.statements.is_empty() {
+            return Err(AggregateError::Empty);
+        }
+        if self.modulus < 3 || self.modulus.is_multiple_of(2) {
+            return Err(AggregateError::Malformed("modulus"));
+        }
+        let field = Field::new(self.modulus);
+        let digests = self.digests()?;
+        if transcript_digest_to_hex(&merkle_root(&digests)) != self.statements_root {
+            return Err(AggregateError::RootMismatch);
+        }
+        let width = self
+            .statements
+            .iter()
+            .map(|statement| statement.num_vars)
+            .max()
+            .unwrap_or(0);
+        if width != self.batch.num_vars {
+            return Err(AggregateError::Malformed("batch arity"));
+        }
+        if self.openings.num_vars != table_vars(self.statements.len()) {
+            return Err(AggregateError::Malformed("openings arity"));
+        }
+
+        let weights = batching_weights(&field, self.epoch, width, &self.statements, &digests);
+        let batch_sum = self
+            .statements
+            .iter()
+            .zip(&weights)
+            .fold(0, |acc, (statement, &rho)| {
+                field.add(acc, field.mul(rho, statement.claimed_sum))
+            });
+        if batch_sum != self.batch.claimed_sum {
+            return Err(AggregateError::BatchSumMismatch);
+        }
+        let point = self.batch.replay(&field, "batch")?;
+
+        if self.openings.claimed_sum != self.batch.final_evaluation {
+            return Err(AggregateError::OpeningSumMismatch);
+        }
+        let table = opening_table(&field, &self.statements, &weights);
+        let opening_point = self.openings.replay(&field, "openings")?;
+        if table.evaluate(&field, &opening_point) != self.openings.final_evaluation {
+            return Err(AggregateError::FinalEvaluationMismatch("openings"));
+        }
+        Ok(point)
+    }
+
+    /// Checks the composition and every opening against its polynomial.
+    ///
+    /// `polynomials` must be given in statement order.
+    pub fn verify(&self, polynomials: &[MultilinearPolynomial]) -> Result<(), AggregateError> {
+        let point = self.check_composition()?;
+        if polynomials.len() != self.statements.len() {
+            return Err(AggregateError::Malformed("polynomial count"));
+        }
+        let field = Field::new(self.modulus);
+        for (index, (statement, polynomial)) in self.statements.iter().zip(polynomials).enumerate()
+        {
+            if polynomial.num_vars() != statement.num_vars {
+                return Err(AggregateError::OpeningMismatch(index));
+            }
+            if padded_evaluation(&field, polynomial, &point) != statement.opening {
+                return Err(AggregateError::OpeningMismatch(index));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Collects verified sum-check proofs of one epoch before folding them.
+#[derive(Debug, Clone)]
+pub struct EpochAggregator {
+    field: Field,
+    epoch: u64,
+    items: Vec<(MultilinearPolynomial, GeneralSumProof, TranscriptDigest)>,
+}
+
+impl EpochAggregator {
+    /// Starts an empty aggregation for `epoch` over `field`.
+    pub fn new(field: Field, epoch: u64) -> Self {
+        Self {
+            field,
+            epoch,
+            items: Vec::new(),
+        }
+    }
+
+    /// Number of proofs collected so far.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true if no proof has been collected.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Verifies `proof` against `polynomial` and adds it to the epoch.
+    pub fn push(
+        &mut self,
+        polynomial: MultilinearPolynomial,
+        proof: GeneralSumProof,
+    ) -> Result<(), AggregateError> {
+        if proof.claim.p != self.field.modulus() {
+            return Err(AggregateError::FieldMismatch);
+        }
+        let trace = proof
+            .verify_with_trace(&polynomial, &self.field)
+            .ok_or(AggregateError::InvalidInput(self.items.len()))?;
+        let digest =
+            transcript_digest(&trace.challenges, &trace.round_sums, trace.final_evaluation);
+        self.items.push((polynomial, proof, digest));
+        Ok(())
+    }
+
+    /// Adds every sum-check proof of an accepted ledger entry.
+    ///
+    /// Returns the number of proofs added; rejected entries and entries
+    /// without a multilinear sum-check (demo, replayed, genesis) add none.
+    pub fn push_entry(&mut self, entry: &LedgerEntry) -> Result<usize, AggregateError> {
+        if !entry.accepted {
+            return Ok(0);
+        }
+        match &entry.proof.kind {
+            ProofKind::General { polynomial, proof } => {
+                self.push(polynomial.clone(), proof.clone())?;
+                Ok(1)
+            }
+            ProofKind::StreamingGeneral { polynomial, proof } => {
+                let evaluations = (0..1usize << polynomial.num_vars())
+                    .map(|idx| polynomial.evaluate(idx))
+                    .collect();
+                let dense =
+                    MultilinearPolynomial::from_evaluations(polynomial.num_vars(), evaluations);
+                self.push(dense, proof.clone())?;
+                Ok(1)
+            }
+            ProofKind::Range(range) => {
+                let table = range
+                    .polynomial(&self.field)
+                    .ok_or(AggregateError::InvalidInput(self.items.len()))?;
+                self.push(table, range.proof.clone())?;
+                Ok(1)
+            }
+            ProofKind::Chain { polynomials, proof } => {
+                for (polynomial, link) in polynomials.iter().zip(proof.links()) {
+                    self.push(polynomial.clone(), link.proof.clone())?;
+                }
+                Ok(proof.len())
+            }
+            ProofKind::Demo(_) | ProofKind::Replayed | ProofKind::Genesis => Ok(0),
+        }
+    }
+
+    /// Folds the collected proofs into one [`EpochProof`].
+    pub fn prove(&self) -> Result<EpochProof, AggregateError> {
+        if self.items.is_empty() {
+            return Err(AggregateError::Empty);
+        }
+        let field = &self.field;
+        let width = self
+            .items
+            .iter()
+            .map(|(polynomial, _, _)| polynomial.num_vars())
+            .max()
+            .unwrap_or(0);
+        let digests: Vec<TranscriptDigest> =
+            self.items.iter().map(|(_, _, digest)| *digest).collect();
+        let mut statements: Vec<AggregatedStatement> = self
+            .items
+            .iter()
+            .map(|(polynomial, proof, digest)| AggregatedStatement {
+                transcript_digest: transcript_digest_to_hex(digest),
+                num_vars: polynomial.num_vars(),
+                claimed_sum: proof.claim.claimed_sum,
+                opening: 0,
+            })
+            .collect();
+        let weights = batching_weights(field, self.epoch, width, &statements, &digests);
+
+        let mut batched = vec![0u64; 1 << width];
+        for ((polynomial, _, _), &rho) in self.items.iter().zip(&weights) {
+            for (slot, value) in batched.iter_mut().zip(polynomial.evaluations_mod_p(field)) {
+                *slot = field.add(*slot, field.mul(rho, value));
+            }
+        }
+        let batched = MultilinearPolynomial::from_evaluations(width, batched);
+        let batch = GeneralSumProof::prove(&batched, field);
+
+        for (statement, (polynomial, _, _)) in statements.iter_mut().zip(&self.items) {
+            statement.opening = padded_evaluation(field, polynomial, &batch.challenges);
+        }
+        let table = opening_table(field, &statements, &weights);
+        let openings = GeneralSumProof::prove(&table, field);
+
+        Ok(EpochProof {
+            schema: EPOCH_PROOF_SCHEMA.to_string(),
+            epoch: self.epoch,
+            modulus: field.modulus(),
+            statements,
+            statements_root: transcript_digest_to_hex(&merkle_root(&digests)),
+            batch: EpochSumCheck::from_proof(&batch),
+            openings: EpochSumCheck::from_proof(&openings),
+        })
+    }
+}
+
+fn entry_modulus(entry: &LedgerEntry) -> Option<u64> {
+    if !entry.accepted {
+        return None;
+    }
+    match &entry.proof.kind {
+        ProofKind::General { proof, .. } | ProofKind::StreamingGeneral { proof, .. } => {
+            Some(proof.claim.p)
+        }
+        ProofKind::Range(range) => Some(range.proof.claim.p),
+        ProofKind::Chain { proof, .. } => proof.links().first().map(|link| link.proof.claim.p),
+        ProofKind::Demo(_) | ProofKind::Replayed | ProofKind::Genesis => None,
+    }
+}
+
+/// Fiat–Shamir batching coefficients bound to every statement.
+fn batching_weights(
+    field: &Field,
+    epoch: u64,
+    width: usize,
+    statements: &[AggregatedStatement],
+    digests: &[TranscriptDigest],
+) -> Vec<u64> {
+    let mut transcript = Transcript::new(AGGREGATE_DOMAIN);
+    transcript.append(field.modulus());
+    transcript.append(epoch);
+    transcript.append(width as u64);
+    transcript.append(statements.len() as u64);
+    for (statement, digest) in statements.iter().zip(digests) {
+        transcript.append_message(b"transcript-digest", digest);
+        transcript.append(statement.num_vars as u64);
+        transcript.append(statement.claimed_sum);
+    }
+    statements
+        .iter()
+        .map(|_| transcript.challenge(field))
+        .collect()
+}
+
+/// Arity of the opening table for `count` statements (at least one variable).
+fn table_vars(count: usize) -> usize {
+    (count.max(2).next_power_of_two()).trailing_zeros() as usize
+}
+
+fn opening_table(
+    field: &Field,
+    statements: &[AggregatedStatement],
+    weights: &[u64],
+) -> MultilinearPolynomial {
+    let vars = table_vars(statements.len());
+    let mut table = vec![0u64; 1 << vars];
+    for ((slot, statement), &rho) in table.iter_mut().zip(statements).zip(weights) {
+        *slot = field.mul(rho, statement.opening % field.modulus());
+    }
+    MultilinearPolynomial::from_evaluations(vars, table)
+}
+
+/// Evaluates `polynomial`, zero-padded to `point.len()` variables, at `point`.
+fn padded_evaluation(field: &Field, polynomial: &MultilinearPolynomial, point: &[u64]) -> u64 {
+    let (own, padding) = point.split_at(polynomial.num_vars());
+    padding
+        .iter()
+        .fold(polynomial.evaluate(field, own), |acc, &r| {
+            field.mul(acc, field.sub(1, r % field.modulus()))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::julian::{Proof, Statement};
+
+    fn polynomial(field: &Field, num_vars: usize, seed: u64) -> MultilinearPolynomial {
+        let evaluations = (0..1u64 << num_vars)
+            .map(|x| field.add(field.mul(seed, x), x * x))
+            .collect();
+        MultilinearPolynomial::from_evaluations(num_vars, evaluations)
+    }
+
+    #[test]
+    fn epoch_proof_folds_mixed_arity_proofs() {
+        let field = Field::new(1_000_000_007);
+        let polynomials = vec![
+            polynomial(&field, 3, 5),
+            polynomial(&field, 2, 11),
+            polynomial(&field, 4, 2),
+        ];
+        let mut aggregator = EpochAggregator::new(field, 7);
+        for poly in &polynomials {
+            aggregator
+                .push(poly.clone(), GeneralSumProof::prove(poly, &field))
+                .unwrap();
+        }
+        let proof = aggregator.prove().unwrap();
+        assert_eq!(proof.batch.num_vars, 4);
+        assert_eq!(proof.openings.num_vars, 2);
+        proof.verify(&polynomials).unwrap();
+
+        let json = serde_json::to_string(&proof).unwrap();
+        let decoded: EpochProof = serde_json::from_str(&json).unwrap();
+        decoded.verify(&polynomials).unwrap();
+
+        let mut false_claim = proof.clone();
+        false_claim.statements[1].claimed_sum = field.add(false_claim.statements[1].claimed_sum, 1);
+        assert_eq!(
+            false_claim.verify(&polynomials),
+            Err(AggregateError::BatchSumMismatch)
+        );
+
+        let mut forged_opening = proof.clone();
+        forged_opening.statements[0].opening = field.add(forged_opening.statements[0].opening, 1);
+        assert!(forged_opening.verify(&polynomials).is_err());
+
+        let mut swapped = polynomials.clone();
+        swapped[2] = polynomial(&field, 4, 3);
+        assert_eq!(
+            proof.verify(&swapped),
+            Err(AggregateError::OpeningMismatch(2))
+        );
+
+        let mut wrong_epoch = proof;
+        wrong_epoch.epoch += 1;
+        assert!(wrong_epoch.verify(&polynomials).is_err());
+    }
+
+    #[test]
+    fn epoch_proof_from_ledger_skips_unprovable_entries() {
+        let field = Field::new(101);
+        let mut ledger = ProofLedger::new();
+        let poly = polynomial(&field, 3, 4);
+        let proof = GeneralSumProof::prove(&poly, &field);
+        ledger.submit(
+            Statement::new("epoch-aggregate"),
+            Proof {
+                kind: ProofKind::General {
+                    polynomial: poly.clone(),
+                    proof,
+                },
+                data: Vec::new(),
+            },
+        );
+        let epoch = EpochProof::from_ledger(&ledger, 1).unwrap();
+        assert_eq!(epoch.statements.len(), 1);
+        let anchored = ledger.entries().last().unwrap().hashes[0];
+        assert_eq!(epoch.digests().unwrap(), vec![anchored]);
+        epoch.verify(&[poly]).unwrap();
+
+        assert_eq!(
+            EpochProof::from_ledger(&ProofLedger::new(), 1),
+            Err(AggregateError::Empty)
+        );
+    }
+}
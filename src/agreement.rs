@@ -0,0 +1,455 @@
+//! Equality of committed tables without exchanging them.
+//!
+//! Two nodes holding large evaluation tables (for example replicated
+//! datasets) can show that the tables agree while exchanging only
+//! commitments, one field element and a few chunk openings each:
+//!
+//! 1. Each node publishes a [`TableCommitment`]: a Merkle root over the
+//!    table split into fixed-size chunks.
+//! 2. [`AgreementChallenge::derive`] feeds both commitments into a shared
+//!    Fiat–Shamir transcript and draws a random point `r` and a few chunk
+//!    indices.  Neither node can influence them after committing.
+//! 3. Each node answers with [`AgreementResponse::respond`]: the multilinear
+//!    extension of its table at `r`, the requested chunks with Merkle paths,
+//!    and a sum-check proof of the evaluation over `eq(r, x)·P(x)` that its
+//!    own ledger can record.
+//! 4. [`check_agreement`] accepts when both openings verify against their
+//!    roots, the opened chunks coincide and both evaluations are equal.
+//!    Distinct tables have distinct multilinear extensions, so equal
+//!    evaluations at a random point imply equality except with probability
+//!    `num_vars / p`.
+//!
+//! The resulting [`AgreementRecord`] yields a structured [`Statement`] for
+//! the ledger.
+
+use crate::{
+    build_merkle_proof,
+    julian::{Proof, ProofKind, Statement},
+    merkle_root, transcript_digest_to_hex, verify_merkle_proof, Field, GeneralSumProof,
+    MerkleProof, MultilinearPolynomial, Transcript, TranscriptDigest,
+};
+use blake2::digest::{consts::U32, Digest};
+use std::fmt;
+
+type Blake2b256 = blake2::Blake2b<U32>;
+
+/// Application id of agreement statements.
+pub const AGREEMENT_APPLICATION_ID: &str = "power_house.agreement";
+
+const CHUNK_DOMAIN: &[u8] = b"power_house:v1:table-chunk";
+const AGREEMENT_DOMAIN: &[u8] = b"power_house:v1:table-agreement";
+
+/// Errors returned by the table agreement protocol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AgreementError {
+    /// Chunk size is zero, not a power of two, or larger than the table.
+    InvalidChunkSize(usize),
+    /// The two commitments describe differently shaped tables.
+    ShapeMismatch,
+    /// The table does not match the commitment it is answered under.
+    CommitmentMismatch,
+    /// A chunk opening does not verify against its commitment.
+    InvalidOpening(usize),
+    /// The evaluation proof does not verify.
+    InvalidEvaluation,
+    /// The tables differ.
+    Disagreement,
+}
+
+impl fmt::Display for AgreementError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidChunkSize(size) => write!(formatter, "invalid chunk size {size}"),
+            Self::ShapeMismatch => write!(formatter, "commitments describe different tables"),
+            Self::CommitmentMismatch => write!(formatter, "table does not match commitment"),
+            Self::InvalidOpening(index) => write!(formatter, "chunk {index} opening is invalid"),
+            Self::InvalidEvaluation => write!(formatter, "evaluation proof does not verify"),
+            Self::Disagreement => write!(formatter, "tables disagree"),
+        }
+    }
+}
+
+impl std::error::Error for AgreementError {}
+
+/// Merkle commitment to an evaluation table split into chunks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableCommitment {
+    /// Prime modulus of the table entries.
+    pub modulus: u64,
+    /// Number of variables; the table has `2^num_vars` entries.
+    pub num_vars: usize,
+    /// Entries per chunk (a power of two).
+    pub chunk_size: usize,
+    /// Merkle root over the chunk digests.
+    pub root: TranscriptDigest,
+}
+
+impl TableCommitment {
+    /// Commits to `table` in chunks of `chunk_size` entries.
+    pub fn commit(
+        table: &MultilinearPolynomial,
+        field: &Field,
+        chunk_size: usize,
+    ) -> Result<Self, AgreementError> {
+        Ok(chunk_leaves(table, field, chunk_size)?.0)
+    }
+
+    fn chunk_count(&self) -> usize {
+        (1usize << self.num_vars) / self.chunk_size
+    }
+}
+
+/// Shared random point and chunk queries for one agreement run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgreementChallenge {
+    /// Evaluation point for the multilinear extensions.
+    pub point: Vec<u64>,
+    /// Chunk indices both parties must open.
+    pub chunks: Vec<usize>,
+}
+
+impl AgreementChallenge {
+    /// Derives the challenge from both commitments, in either order.
+    pub fn derive(
+        left: &TableCommitment,
+        right: &TableCommitment,
+        queries: usize,
+    ) -> Result<Self, AgreementError> {
+        if left.modulus != right.modulus
+            || left.num_vars != right.num_vars
+            || left.chunk_size != right.chunk_size
+        {
+            return Err(AgreementError::ShapeMismatch);
+        }
+        let field = Field::new(left.modulus);
+        let (first, second) = if left.root <= right.root {
+            (left, right)
+        } else {
+            (right, left)
+        };
+        let mut transcript = Transcript::new(AGREEMENT_DOMAIN);
+        transcript.append(left.modulus);
+        transcript.append(left.num_vars as u64);
+        transcript.append(left.chunk_size as u64);
+        transcript.append_message(b"first-root", &first.root);
+        transcript.append_message(b"second-root", &second.root);
+        let point = (0..left.num_vars)
+            .map(|_| transcript.challenge_scalar(b"point", &field))
+            .collect();
+        let chunk_count = left.chunk_count() as u64;
+        let mut chunks: Vec<usize> = (0..queries.min(left.chunk_count()))
+            .map(|_| (transcript.challenge_scalar(b"chunk", &field) % chunk_count) as usize)
+            .collect();
+        chunks.sort_unstable();
+        chunks.dedup();
+        Ok(Self { point, chunks })
+    }
+}
+
+/// Chunk of a committed table with its Merkle path.
+#[derive(Debug, Clone)]
+pub struct ChunkOpening {
+    /// Chunk index.
+    pub index: usize,
+    /// Table entries of the chunk.
+    pub values: Vec<u64>,
+    /// Inclusion proof against the commitment root.
+    pub proof: MerkleProof,
+}
+
+/// One party's answer to an [`AgreementChallenge`].
+#[derive(Debug, Clone)]
+pub struct AgreementResponse {
+    /// Commitment the response is given under.
+    pub commitment: TableCommitment,
+    /// Multilinear extension of the table at the challenge point.
+    pub evaluation: u64,
+    /// Openings of the challenged chunks.
+    pub openings: Vec<ChunkOpening>,
+    /// Sum-check proof that `Σ_x eq(r, x)·P(x)` equals `evaluation`.
+    pub proof: GeneralSumProof,
+}
+
+impl AgreementResponse {
+    /// Answers `challenge` for the committed `table`.
+    pub fn respond(
+        table: &MultilinearPolynomial,
+        commitment: &TableCommitment,
+        challenge: &AgreementChallenge,
+        field: &Field,
+    ) -> Result<Self, AgreementError> {
+        let (recomputed, leaves) = chunk_leaves(table, field, commitment.chunk_size)?;
+        if recomputed != *commitment || challenge.point.len() != commitment.num_vars {
+            return Err(AgreementError::CommitmentMismatch);
+        }
+        let evaluations = table.evaluations_mod_p(field);
+        let openings = challenge
+            .chunks
+            .iter()
+            .map(|&index| {
+                let start = index * commitment.chunk_size;
+                Ok(ChunkOpening {
+                    index,
+                    values: evaluations[start..start + commitment.chunk_size].to_vec(),
+                    proof: build_merkle_proof(&leaves, index)
+                        .ok_or(AgreementError::InvalidOpening(index))?,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        let weighted = eq_weighted(table, &challenge.point, field);
+        let proof = GeneralSumProof::prove(&weighted, field);
+        Ok(Self {
+            commitment: commitment.clone(),
+            evaluation: proof.claim.claimed_sum,
+            openings,
+            proof,
+        })
+    }
+
+    /// Checks the evaluation proof against the responder's own table.
+    ///
+    /// This is what the responder's ledger verifies when it records the
+    /// agreement; the counterparty only sees the openings.
+    pub fn verify_evaluation(
+        &self,
+        table: &MultilinearPolynomial,
+        challenge: &AgreementChallenge,
+        field: &Field,
+    ) -> Result<(), AgreementError> {
+        if self.proof.claim.claimed_sum != self.evaluation
+            || !self
+                .proof
+                .verify(&eq_weighted(table, &challenge.point, field), field)
+        {
+            return Err(AgreementError::InvalidEvaluation);
+        }
+        Ok(())
+    }
+
+    /// Statement and proof for the responder's ledger.
+    pub fn ledger_entry(
+        &self,
+        table: &MultilinearPolynomial,
+        record: &AgreementRecord,
+        field: &Field,
+    ) -> Result<(Statement, Proof), AgreementError> {
+        self.verify_evaluation(table, &record.challenge, field)?;
+        let proof = Proof {
+            kind: ProofKind::General {
+                polynomial: eq_weighted(table, &record.challenge.point, field),
+                proof: self.proof.clone(),
+            },
+            data: Vec::new(),
+        };
+        Ok((record.statement(), proof))
+    }
+}
+
+/// Outcome of a successful agreement run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgreementRecord {
+    /// Commitments of both parties, ordered by root.
+    pub roots: [TranscriptDigest; 2],
+    /// Shared shape of the tables.
+    pub num_vars: usize,
+    /// Field modulus.
+    pub modulus: u64,
+    /// Challenge both parties answered.
+    pub challenge: AgreementChallenge,
+    /// Common evaluation at the challenge point.
+    pub evaluation: u64,
+}
+
+impl AgreementRecord {
+    /// Structured statement recording the agreement.
+    pub fn statement(&self) -> Statement {
+        Statement::structured(AGREEMENT_APPLICATION_ID, "table_equality")
+            .with_parameter("modulus", self.modulus.to_string())
+            .with_parameter("num_vars", self.num_vars.to_string())
+            .with_parameter("root_a", transcript_digest_to_hex(&self.roots[0]))
+            .with_parameter("root_b", transcript_digest_to_hex(&self.roots[1]))
+            .with_parameter("evaluation", self.evaluation.to_string())
+    }
+}
+
+/// Checks two responses to the same challenge and records the agreement.
+pub fn check_agreement(
+    left: &AgreementResponse,
+    right: &AgreementResponse,
+    queries: usize,
+) -> Result<AgreementRecord, AgreementError> {
+    let challenge = AgreementChallenge::derive(&left.commitment, &right.commitment, queries)?;
+    for response in [left, right] {
+        check_openings(response, &challenge)?;
+    }
+    let same_chunks = left
+        .openings
+        .iter()
+        .zip(&right.openings)
+        .all(|(a, b)| a.values == b.values);
+    if !same_chunks || left.evaluation != right.evaluation {
+        return Err(AgreementError::Disagreement);
+    }
+    let mut roots = [left.commitment.root, right.commitment.root];
+    roots.sort();
+    Ok(AgreementRecord {
+        roots,
+        num_vars: left.commitment.num_vars,
+        modulus: left.commitment.modulus,
+        challenge,
+        evaluation: left.evaluation,
+    })
+}
+
+fn check_openings(
+    response: &AgreementResponse,
+    challenge: &AgreementChallenge,
+) -> Result<(), AgreementError> {
+    let commitment = &response.commitment;
+    if response.openings.len() != challenge.chunks.len() {
+        return Err(AgreementError::InvalidOpening(response.openings.len()));
+    }
+    for (opening, &index) in response.openings.iter().zip(&challenge.chunks) {
+        let valid = opening.index == index
+            && opening.values.len() == commitment.chunk_size
+            && opening.proof.index == index
+            && opening.proof.root == commitment.root
+            && opening.proof.leaf == chunk_digest(index, &opening.values)
+            && verify_merkle_proof(&opening.proof);
+        if !valid {
+            return Err(AgreementError::InvalidOpening(index));
+        }
+    }
+    Ok(())
+}
+
+fn chunk_digest(index: usize, values: &[u64]) -> TranscriptDigest {
+    let mut hasher = Blake2b256::new();
+    hasher.update(CHUNK_DOMAIN);
+    hasher.update((index as u64).to_be_bytes());
+    for value in values {
+        hasher.update(value.to_be_bytes());
+    }
+    hasher.finalize().into()
+}
+
+fn chunk_leaves(
+    table: &MultilinearPolynomial,
+    field: &Field,
+    chunk_size: usize,
+) -> Result<(TableCommitment, Vec<TranscriptDigest>), AgreementError> {
+    let evaluations = table.evaluations_mod_p(field);
+    if chunk_size == 0 || !chunk_size.is_power_of_two() || chunk_size > evaluations.len() {
+        return Err(AgreementError::InvalidChunkSize(chunk_size));
+    }
+    let leaves: Vec<TranscriptDigest> = evaluations
+        .chunks_exact(chunk_size)
+        .enumerate()
+        .map(|(index, chunk)| chunk_digest(index, chunk))
+        .collect();
+    let commitment = TableCommitment {
+        modulus: field.modulus(),
+        num_vars: table.num_vars(),
+        chunk_size,
+        root: merkle_root(&leaves),
+    };
+    Ok((commitment, leaves))
+}
+
+/// Table `eq(r, x)·P(x)`, whose hypercube sum is the extension `P̃(r)`.
+fn eq_weighted(
+    table: &MultilinearPolynomial,
+    point: &[u64],
+    field: &Field,
+) -> MultilinearPolynomial {
+    let mut eq = vec![1u64];
+    for &r in point {
+        let r = r % field.modulus();
+        let one_minus = field.sub(1, r);
+        let low: Vec<u64> = eq.iter().map(|&w| field.mul(w, one_minus)).collect();
+        let high: Vec<u64> = eq.iter().map(|&w| field.mul(w, r)).collect();
+        eq = low;
+        eq.extend(high);
+    }
+    let weighted = table
+        .evaluations_mod_p(field)
+        .iter()
+        .zip(&eq)
+        .map(|(&value, &weight)| field.mul(value, weight))
+        .collect();
+    MultilinearPolynomial::from_evaluations(table.num_vars(), weighted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProofLedger;
+
+    fn table(field: &Field, tweak: Option<usize>) -> MultilinearPolynomial {
+        let mut evaluations: Vec<u64> = (0..64u64).map(|x| field.mul(x, x + 3)).collect();
+        if let Some(index) = tweak {
+            evaluations[index] = field.add(evaluations[index], 1);
+        }
+        MultilinearPolynomial::from_evaluations(6, evaluations)
+    }
+
+    #[test]
+    fn agreeing_tables_produce_a_ledger_record() {
+        let field = Field::new(1_000_000_007);
+        let ours = table(&field, None);
+        let theirs = table(&field, None);
+        let our_commitment = TableCommitment::commit(&ours, &field, 8).unwrap();
+        let their_commitment = TableCommitment::commit(&theirs, &field, 8).unwrap();
+        let challenge = AgreementChallenge::derive(&our_commitment, &their_commitment, 3).unwrap();
+        assert_eq!(
+            challenge,
+            AgreementChallenge::derive(&their_commitment, &our_commitment, 3).unwrap()
+        );
+
+        let our_response =
+            AgreementResponse::respond(&ours, &our_commitment, &challenge, &field).unwrap();
+        let their_response =
+            AgreementResponse::respond(&theirs, &their_commitment, &challenge, &field).unwrap();
+        assert_eq!(
+            our_response.evaluation,
+            ours.evaluate(&field, &challenge.point)
+        );
+        let record = check_agreement(&our_response, &their_response, 3).unwrap();
+
+        let (statement, proof) = our_response.ledger_entry(&ours, &record, &field).unwrap();
+        assert_eq!(statement.application_id(), Some(AGREEMENT_APPLICATION_ID));
+        let mut ledger = ProofLedger::new();
+        ledger.submit(statement, proof);
+        assert!(ledger.entries().last().unwrap().accepted);
+    }
+
+    #[test]
+    fn differing_tables_are_detected() {
+        let field = Field::new(1_000_000_007);
+        let ours = table(&field, None);
+        let theirs = table(&field, Some(41));
+        let our_commitment = TableCommitment::commit(&ours, &field, 8).unwrap();
+        let their_commitment = TableCommitment::commit(&theirs, &field, 8).unwrap();
+        let challenge = AgreementChallenge::derive(&our_commitment, &their_commitment, 2).unwrap();
+        let our_response =
+            AgreementResponse::respond(&ours, &our_commitment, &challenge, &field).unwrap();
+        let mut their_response =
+            AgreementResponse::respond(&theirs, &their_commitment, &challenge, &field).unwrap();
+        assert_eq!(
+            check_agreement(&our_response, &their_response, 2),
+            Err(AgreementError::Disagreement)
+        );
+
+        // Claiming our evaluation does not help without a matching table.
+        their_response.evaluation = our_response.evaluation;
+        assert_eq!(
+            their_response.verify_evaluation(&theirs, &challenge, &field),
+            Err(AgreementError::InvalidEvaluation)
+        );
+
+        assert_eq!(
+            AgreementResponse::respond(&theirs, &our_commitment, &challenge, &field).unwrap_err(),
+            AgreementError::CommitmentMismatch
+        );
+    }
+}
@@ -4,24 +4,66 @@
 //! deriving ledger anchors, and reconciling anchors with a quorum using the
 //! crate's domain-separated hashing and signature utilities.
 
+#[cfg(feature = "net")]
+use power_house::artifact_clock;
 #[cfg(feature = "net")]
 use power_house::commands::{
-    migration_apply_claims::{run_apply_claims, ApplyClaimsOptions},
-    migration_burn_executor::{run_execute_burn_intents, ExecuteBurnOptions},
-    migration_claims::{run_build_claims, BuildClaimsOptions},
-    migration_finalize::{run_finalize_migration, FinalizeMigrationOptions},
-    migration_proposal::{run_propose_migration, ProposeMigrationOptions},
+    migration_apply_claims::{
+        run_apply_claims, run_sweep_claims, ApplyClaimsOptions, SweepClaimsOptions,
+    },
+    migration_burn_executor::{
+        run_execute_burn_intents, run_reconcile_burns, ExecuteBurnOptions, ReconcileBurnOptions,
+    },
+    migration_claims::{run_build_claims_with_clock, BuildClaimsOptions},
+    migration_finalize::{
+        plan_finalize_migration, run_finalize_migration, FinalizeMigrationOptions, FinalizeState,
+        FinalizeStep, StepPlan, StepStatus,
+    },
+    migration_proposal::{run_propose_migration_with_clock, ProposeMigrationOptions},
+    migration_reserve::{
+        run_reserve_report_with_clock, verify_reserve_report, ReserveReportOptions,
+    },
     migration_verify_state::{run_verify_state, VerifyStateOptions},
-    stake_snapshot::run_snapshot,
+    stake_snapshot::{
+        run_paged_snapshot_with_clock, run_snapshot_with_clock, verify_paged_snapshot,
+    },
+};
+#[cfg(feature = "net")]
+use power_house::economics::{FeeSinkPolicy, NATIVE_ASSET};
+#[cfg(feature = "net")]
+use power_house::net::{
+    apply_key_edit, clean_devnet, create_backup, follow_primary_state, generate_devnet,
+    load_policy_history, restore_backup, run_evm_rpc_server, verify_backup, BackupManifest,
+    BackupSources, ConfigFile, Devnet, DevnetSpec, EvmRpcConfig, FreezeOrder, GovernanceUpdate,
+    GuardLimits, GuardOverride, KeyEdit, KeyEditAction, NodeClock, PolicyKind, PolicySnapshot,
+    ReplicaMode, ReplicaStatus, RestoreTargets, RpcTierLimits, WallClock, BLOCK_ARCHIVE_DIR,
+    DEFAULT_REPLICA_POLL,
 };
 #[cfg(feature = "net")]
 use power_house::net::{
-    decode_public_key_base64, encrypt_identity_base64, load_encrypted_identity,
-    load_or_derive_keypair, refresh_migration_mode_from_env, run_network, verify_signature_base64,
-    AnchorEnvelope, AnchorJson, Ed25519KeySource, MembershipPolicy, MultisigPolicy, NamespaceRule,
-    NetConfig, ObserverRegistration, ObserverRegistry, StakePolicy, StakeRegistry, StaticPolicy,
-    ValidatorRegistration, ValidatorRegistry, OBSERVER_REGISTRY_SCHEMA, VALIDATOR_REGISTRY_SCHEMA,
+    collect_expired_entries, confirm_pins, decode_public_key_base64, derive_mnemonic_key,
+    digest_input_path, encrypt_identity_base64, enqueue_direct, enqueue_governance, export_archive,
+    export_attestations, export_content_bundle, find_archived_block, find_archived_transaction,
+    generate_mnemonic, import_archived_entries, inspect_segment, keychain_account, list_segments,
+    load_beacon, load_checkpoints, load_encrypted_identity, load_or_derive_keypair,
+    migrate_chain_state, migrate_registry, mnemonic_key_file, network_topic, parse_evm_signing_key,
+    pin_latest_checkpoint, read_fork_evidence, read_passphrase, record_proposal,
+    refresh_migration_mode_from_env, resolve_fork, run_evm_pinner, run_log_aggregator,
+    run_log_shipper, run_network, ship_pending_logs, validate_asset_id, verify_archive,
+    verify_attestation_bundle, verify_content_bundle, verify_signature_base64, AddressIndex,
+    AlertConfig, AnchorArchive, AnchorEnvelope, AnchorHistory, AnchorJson, AttestationBundle,
+    Ballot, ChaosConfig, CheckReceipt, CorsPolicy, DirectPayload, Ed25519KeySource, EvmPinConfig,
+    GovernanceBook, GovernanceMessage, JoinRequest, LogAggregatorConfig, LogShipperConfig,
+    MembershipPolicy, MetricsPushConfig, MultisigPolicy, NamespaceRule, NativeChainState,
+    NetConfig, ObserverRegistration, ObserverRegistry, PassphraseSource, ProofSubmission, Proposal,
+    ProposalAction, ProposalVote, RewardConfig, RewardWeighting, ShippedRecord, StakePolicy,
+    StakeRegistry, StaticPolicy, StorageBackend, SupplyCause, ValidatorRegistration,
+    ValidatorRegistry, WebhookConfig, ADDRESS_DERIVATION, DEFAULT_CHUNK_SIZE,
+    DEFAULT_MNEMONIC_PATH, OBSERVER_REGISTRY_SCHEMA, VALIDATOR_REGISTRY_SCHEMA,
 };
+#[cfg(feature = "grpc")]
+use power_house::net::{run_grpc_server, GrpcConfig};
+use power_house::profile;
 use power_house::provenance::{ExternalProofAttachment, PhaArtifact, Rootprint};
 #[cfg(feature = "sfcs")]
 use power_house::{
@@ -38,12 +80,13 @@ use power_house::{
     SfcsZkPrivateAddWitness, SfcsZkPrivateVmProof, SfcsZkPrivateVmWitness,
 };
 use power_house::{
-    compute_fold_digest, identity::Identity, julian_genesis_anchor, parse_log_file,
+    compute_fold_digest, identity::Identity, iter_ledger_logs, julian_genesis_anchor,
     read_fold_digest_hint, reconcile_anchors_with_quorum, AnchorMetadata, AnchorVote,
-    ChallengeSuite, EntryAnchor, Field, GeneralSumProof, LedgerAnchor, MemoryCapsule,
-    MemoryCapsuleBuilder, MemoryError, MemoryVerificationPolicy, ObservatorySidecar, ProofStats,
+    ChallengeSuite, EntryAnchor, EntrySubmission, Field, GeneralSumProof, KvReceipt, KvStore,
+    LedgerAnchor, LogFault, LogFaultKind, MemoryCapsule, MemoryCapsuleBuilder, MemoryError,
+    MemoryVerificationPolicy, ObservatorySidecar, ProofStats, Statement,
 };
-#[cfg(feature = "sfcs")]
+#[cfg(any(feature = "sfcs", feature = "net"))]
 use std::collections::BTreeMap;
 #[cfg(feature = "net")]
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, TcpStream, UdpSocket};
@@ -55,6 +98,7 @@ use std::{
     env, fs,
     io::{self, Read},
     path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
 };
 
 #[cfg(feature = "net")]
@@ -63,7 +107,7 @@ use std::io::Write;
 #[cfg(feature = "net")]
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 #[cfg(feature = "net")]
-use libp2p::Multiaddr;
+use libp2p::{Multiaddr, PeerId};
 #[cfg(feature = "net")]
 use rand::RngCore;
 #[cfg(feature = "net")]
@@ -73,7 +117,10 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "net")]
 use std::collections::{HashMap, HashSet};
 
-const NETWORK_ID: &str = "MFENX-POWERHOUSE";
+fn network_id() -> &'static str {
+    &power_house::active_genesis().network_id
+}
+
 #[cfg(feature = "net")]
 const DEFAULT_OBSERVER_BOOTSTRAPS: &[&str] = &[
     "/ip4/159.203.109.128/tcp/7002/p2p/12D3KooWMCyR9gXPXCGAMNCVJDKbisohRRq8oaTHNiR91HZ67cSR",
@@ -81,16 +128,60 @@ const DEFAULT_OBSERVER_BOOTSTRAPS: &[&str] = &[
     "/ip4/164.92.150.22/tcp/7002/p2p/12D3KooWFNv4sZfDKypMeWqRetghHxXzkhPTc4PvynDZKSETJqd8",
 ];
 
+fn install_genesis_file(path: &str) {
+    let config =
+        power_house::GenesisConfig::load(Path::new(path)).unwrap_or_else(|err| fatal(&err));
+    power_house::install_genesis(config).unwrap_or_else(|err| fatal(&err));
+}
+
+static PROFILE_FOLDED: AtomicBool = AtomicBool::new(false);
+
 fn fatal(message: &str) -> ! {
     eprintln!("{message}");
-    std::process::exit(1);
+    exit(1);
 }
 
 fn fatal_code(code: i32, message: &str) -> ! {
     eprintln!("{message}");
+    exit(code);
+}
+
+/// Exits with `code` after printing any `--profile` summary.
+fn exit(code: i32) -> ! {
+    print_profile_summary();
     std::process::exit(code);
 }
 
+/// Strips `--profile[=table|folded]` from `args`, enabling span collection.
+fn take_profile_flag(args: Vec<String>) -> Vec<String> {
+    args.into_iter()
+        .filter(|arg| {
+            let format = match arg.as_str() {
+                "--profile" | "--profile=table" => false,
+                "--profile=folded" => true,
+                _ => return true,
+            };
+            PROFILE_FOLDED.store(format, Ordering::Relaxed);
+            profile::enable();
+            false
+        })
+        .collect()
+}
+
+/// Prints the spans collected under `--profile` to stderr.
+fn print_profile_summary() {
+    if !profile::is_enabled() {
+        return;
+    }
+    let stats = profile::summary();
+    if PROFILE_FOLDED.load(Ordering::Relaxed) {
+        eprint!("{}", profile::render_folded(&stats));
+    } else {
+        eprintln!("profile:");
+        eprint!("{}", profile::render_table(&stats));
+    }
+}
+
 fn print_cli_help() {
     println!("Power-House JULIAN {}", env!("CARGO_PKG_VERSION"));
     println!("Usage: julian <command> [options]");
@@ -102,6 +193,7 @@ fn print_cli_help() {
     #[cfg(feature = "sfcs")]
     println!("  sfcs             Parse, execute, and verify SFCS computational fractals");
     println!("  node             Replay logs, derive anchors, and verify Merkle proofs");
+    println!("  kv               Put, get, and prove anchored key-value entries");
     println!("  scale_sumcheck   Benchmark streaming sum-check verification");
     println!();
     println!("Optional external integration:");
@@ -116,23 +208,49 @@ fn print_cli_help() {
         println!("  governance       Build governance proposals");
         println!("  migration        Finalize and verify migrations");
         println!("  rollup           Settle rollup requests");
+        println!("  storage          Migrate registry and native-chain state between backends");
+        println!("  backup           Create, verify, and restore signed backups of node state");
+        println!("  beacon           Read the random beacon of a finalized checkpoint epoch");
+        println!("  policy           Show and edit allowlist and multisig membership policies");
+        println!("  tx               Trace the account mutations of a finalized transaction");
         println!("  keygen           Create an encrypted network identity");
         println!("  key-info         Inspect a network identity without exposing its secret");
+        println!("  key              Generate mnemonics and derive node keys from them");
+        println!("  receipt          Verify signed receipts of reconcile and verify checks");
         println!("  observer         Diagnose, set up, register, and package public observers");
         println!("  validator-registry  Sign, assemble, and verify validator registrations");
         println!("  observer-registry   Sign, assemble, and verify public observer registrations");
     }
     println!();
+    println!("Global options:");
+    println!("  --profile[=table|folded]  Print hot-path timings to stderr at exit");
+    println!();
     println!("Use 'julian <command> --help' for command details.");
 }
 
 fn print_node_help() {
-    println!("Usage: julian node <run|anchor|reconcile|prove|verify-proof> ...");
+    println!("Usage: julian node <run|anchor|fsck|archive|gc|reconcile|prove|verify-proof> ...");
     println!("  run <node_id> <log_dir> <output_anchor>");
-    println!("  anchor <log_dir>");
-    println!("  reconcile <log_dir> <peer_anchor> <quorum>");
+    println!("  anchor <log_dir> [--app <application_id>]");
+    #[cfg(feature = "net")]
+    println!("  anchor <log_dir> (--at-epoch <n> | --digest <hex>) [--archive <archive.json>]...");
+    println!("  fsck <log_dir> [--modulus <p>] [--quarantine]");
+    #[cfg(feature = "net")]
+    {
+        println!("  archive export <log_dir> --node-id <id> --output <file> [--key <spec>]");
+        println!("  archive verify <archive.json>");
+        println!(
+            "  archive export-cids <log_dir> --node-id <id> --output <dir> [--key <spec>] [--chunk-size <bytes>]"
+        );
+        println!("  archive verify-cids <dir>");
+        println!("  archive import <log_dir> [--digest <hex>]...");
+        println!("  gc <log_dir> --expiry-epochs <N> [--epoch <n>] [--dry-run]");
+    }
+    println!("  reconcile <log_dir> <peer_anchor> <quorum> [receipt options]");
     println!("  prove <log_dir> <entry_index> <leaf_index> [output.json]");
     println!("  verify-proof <anchor_file> <proof_file>");
+    println!("  genesis [genesis.json]");
+    println!("  (set PH_GENESIS_CONFIG=<genesis.json> to run any command on that network)");
 }
 
 fn print_scale_help() {
@@ -206,6 +324,17 @@ fn print_memory_help() {
     println!("Memory verification runs offline and verifies core truth before semantic bindings.");
 }
 
+fn print_kv_help() {
+    println!("Usage: julian kv <put|get|prove|verify> ...");
+    println!("  put <log_dir> <key> (<value> | --file <path>)");
+    println!("  get <log_dir> <key> [--digest]");
+    println!("  prove <log_dir> <key> [output.json]");
+    println!("  verify <receipt.json> [--anchor <anchor_file>] [--value-file <path>]");
+    println!();
+    println!("Writes are appended to the log directory's transcript journal; receipts");
+    println!("tie the latest version of a key to the ledger root.");
+}
+
 #[cfg(feature = "sfcs")]
 fn print_sfcs_help() {
     println!("Usage: julian sfcs <source|eval|inspect|verify-pha|vm-run|verify-vm-pha> ...");
@@ -263,17 +392,87 @@ fn print_sfcs_help() {
 
 #[cfg(feature = "net")]
 fn print_stake_help() {
-    println!("Usage: julian stake <show|fund|bond|snapshot|claims|apply-claims|unbond|reward> ...");
+    println!(
+        "Usage: julian stake <show|fund|bond|snapshot|verify-snapshot|claims|apply-claims|unbond|withdraw|unbonding-period|guards|guard-override|fee-sink|freeze-order|freeze|frozen|reward|audit|address-index|prove> ..."
+    );
     println!("  show <stake_registry.json>");
-    println!("  fund <registry.json> <pubkey_b64> <amount>");
+    println!("  fund <registry.json> <pubkey_b64> <amount> [--asset <id>]");
+    println!("       [--override <update.json> --policy <multisig.json>]");
     println!("  bond <registry.json> <pubkey_b64> <amount>");
     println!("  snapshot --registry <path> --height <N> --output <file>");
+    println!("  snapshot --registry <path> --height <N> --output <dir> --page-size <n>");
+    println!("  verify-snapshot <dir>");
     println!(
         "  claims --snapshot <file> --output <file> [--mode native|erc20] [--amount-source stake|balance|total]"
     );
     println!("  apply-claims --registry <file> --claims <file> [--state <file>] [--dry-run]");
     println!("  unbond <registry.json> <pubkey_b64> <amount>");
+    println!("  withdraw <registry.json> <pubkey_b64>");
+    println!("  unbonding-period <registry.json> <epochs>");
+    println!("  guards <registry.json> [--max-single-credit <n|off>]");
+    println!("         [--max-account-daily-delta <n|off>] [--max-daily-mint <n|off>]");
+    println!(
+        "  guard-override --policy <multisig.json> --out <update.json> [--approve <key-spec>]..."
+    );
+    println!("                 (--update <update.json> | --account <pubkey_b64> --amount <n>");
+    println!("                  --expires-at-epoch <N>)");
+    println!("  fee-sink <registry.json> [--burn | --sink <pubkey_b64>");
+    println!("           | --split <pubkey_b64> --burn-bps <n>]");
+    println!(
+        "  freeze-order --policy <multisig.json> --out <update.json> [--approve <key-spec>]..."
+    );
+    println!("               (--update <update.json> | --account <pubkey_b64>");
+    println!("                (--freeze --reason <text> | --release))");
+    println!("  freeze <registry.json> --order <update.json> --policy <multisig.json>");
+    println!("  frozen <registry.json>");
     println!("  reward <registry.json> <pubkey_b64> <amount>");
+    println!("  audit <registry.json>");
+    println!("  address-index <registry.json> [--output <file>]");
+    println!("  prove <registry.json> <pubkey_b64>");
+}
+
+#[cfg(feature = "net")]
+fn print_storage_help() {
+    println!("Usage: julian storage migrate <registry|native-state> <input.json> <output.sqlite>");
+    println!("  Copies a JSON store into an empty SQLite database (requires the sqlite feature).");
+}
+
+#[cfg(feature = "net")]
+fn print_backup_help() {
+    println!("Usage: julian backup <create|verify|restore> ...");
+    println!("  create --log-dir <dir> [--state-dir <dir>] [--apply-state <file>]");
+    println!("         [--config <file>]... [--key <spec>] --out <backup.tar.gz>");
+    println!("  verify <backup.tar.gz> [--trusted-key <pubkey_b64>]");
+    println!("  restore <backup.tar.gz> --log-dir <dir> [--state-dir <dir>] [--config-dir <dir>]");
+    println!("          [--trusted-key <pubkey_b64>] [--force]");
+    println!("  Backups never contain private keys; restore writes every file or none.");
+}
+
+#[cfg(feature = "net")]
+fn print_policy_help() {
+    println!("Usage: julian policy <show|add-key|remove-key> ...");
+    println!("  show <policy.json> [--history] [--json]");
+    println!("  add-key|remove-key <policy.json> <pubkey_b64> [--note <text>]");
+    println!(
+        "                     [--update <edit.json>] [--approve <key-spec>]... [--out <edit.json>]"
+    );
+    println!("  Allowlists are edited in place. Multisig edits apply once the update carries");
+    println!(
+        "  threshold signer approvals; until then it is written to --out for the next signer."
+    );
+    println!("  Compare the printed policy digest across nodes.");
+}
+
+#[cfg(feature = "net")]
+fn print_beacon_help() {
+    println!("Usage: julian beacon get <epoch> --log-dir <dir> [--threshold <N>]");
+    println!("  Derives the epoch beacon from a checkpoint with at least N verified signers.");
+}
+
+#[cfg(feature = "net")]
+fn print_tx_help() {
+    println!("Usage: julian tx trace <hash> --rpc <url> [--json]");
+    println!("  Lists the debits, credits, fee and registry keys of a finalized transaction.");
 }
 
 #[cfg(feature = "net")]
@@ -286,29 +485,85 @@ fn print_governance_help() {
 
 #[cfg(feature = "net")]
 fn print_migration_help() {
-    println!("Usage: julian migration <finalize|verify-state|execute-burn-intents> ...");
+    println!(
+        "Usage: julian migration <finalize|finalize-status|verify-state|execute-burn-intents|reconcile-burns|sweep-claims|reserve-report|verify-reserve-report> ..."
+    );
     println!("  finalize --registry <file> --height <N> --log-dir <dir> --output-dir <dir>");
     println!(
         "           [--token-contract <id>] [--conversion-ratio <u64>] [--treasury-mint <u64>]"
     );
     println!("           [--amount-source stake|balance|total] [--include-slashed]");
     println!("           [--claim-id-salt <text>] [--node-id <id>] [--quorum <N>]");
-    println!("           [--apply-state <file>] [--allow-unfrozen] [--force]");
+    println!("           [--apply-state <file>] [--allow-unfrozen] [--force] [--deterministic]");
+    println!("           [--until snapshot|claims|apply_claims|proposal] [--dry-run]");
+    println!("  finalize-status --output-dir <dir>");
     println!("  verify-state --registry <file> --claims <file> --state <file>");
-    println!("               [--require-complete] [--skip-balance-floor]");
+    println!("               [--require-complete] [--skip-balance-floor] [receipt options]");
     println!(
         "  execute-burn-intents --registry <file> [--outbox <file>] [--state <file>] [--dry-run]"
     );
+    println!(
+        "                       [--key <spec>] [--receipts <file>] [--intent-signer <b64>]..."
+    );
+    println!("  reconcile-burns --outbox <file> [--receipts <file>]");
+    println!("                  [--intent-signer <b64>]... [--executor <b64>]...");
+    println!("  sweep-claims --registry <file> --claims <file> --to <pubkey_b64> --key <spec>");
+    println!("               --output <file> [--state <file>] [--dry-run]");
+    println!("  reserve-report --registry <file> --claims <file> --log-dir <dir> --key <spec>");
+    println!("                 [--state <file>] [--outbox <file>] [--receipts <file>]");
+    println!("                 [--intent-signer <b64>]... [--executor <b64>]...");
+    println!(
+        "                 [--node-id <id>] [--quorum <N>] [--output <file>] [--deterministic]"
+    );
+    println!("  verify-reserve-report <file>");
 }
 
 #[cfg(feature = "net")]
 fn print_net_help() {
-    println!("Usage: julian net <start|anchor|verify-envelope> ...");
+    println!(
+        "Usage: julian net <start|anchor|verify-envelope|ship-logs|pin-anchor|log-aggregator|grpc|attestations|direct|block-archive|resolve-fork|proposal|join-request|verify-rpc-state|rpc-replica|dev> ..."
+    );
     println!("  start --node-id <id> --log-dir <dir> --listen <multiaddr> [flags]");
     println!("        [--evm-rpc-listen <host:port>] [--evm-chain-id <u64>]");
     println!("  anchor --log-dir <dir> [--node-id <id>] [--quorum <N>]");
     println!("         (compat: julian net anchor <log_dir>)");
     println!("  verify-envelope --file <anchor.json> --log-dir <dir> [--quorum <N>]");
+    println!("                  [receipt options]");
+    println!("  ship-logs --endpoint <url> --node-id <id> --log-dir <dir> [--key <spec>]");
+    println!("            [--interval <secs>] [--once]");
+    println!("  pin-anchor --log-dir <dir> --rpc <url> --contract <0x..> --chain-id <N>");
+    println!("             --evm-key <file> [--confirmations <N>] [--gas-limit <N>]");
+    println!(
+        "             [--max-fee-wei <N>] [--priority-fee-wei <N>] [--interval <secs>] [--once]"
+    );
+    println!("  log-aggregator --listen <host:port> --store <dir> [--cors-origins <list>]");
+    println!("  grpc --listen <host:port> --log-dir <dir> [--node-id <id>] [--quorum <N>]");
+    println!("       [--registry <path>]   (requires the `grpc` feature)");
+    println!("  attestations export --log-dir <dir> --output <file> [--since <unix_ms>]");
+    println!("  attestations verify <bundle.json> [--trusted-key <b64>]...");
+    println!("  direct send --log-dir <dir> --peer <peer_id> [--key <spec>]");
+    println!("              (--proof <statement> <proof-file> | --record <file> --node-id <id>)");
+    println!("  block-archive <dir> [--tx <hash> | --block <N>]");
+    println!("  resolve-fork --log-dir <dir> [--choose <digest>]");
+    println!("  proposal create --log-dir <dir> [--key <spec>] (--parameter <name>=<value> |");
+    println!("                  --members <file> | --fee-schedule <file> | --join-request <file>)");
+    println!("                  [--description <text>]");
+    println!("                  [--voting-period <secs>]");
+    println!("  proposal vote --log-dir <dir> [--key <spec>] <proposal_id> <yes|no>");
+    println!("  proposal status --log-dir <dir> [<proposal_id>]");
+    println!(
+        "  join-request --node-id <id> [--key <spec>] [--address <multiaddr>]... [--out <file>]"
+    );
+    println!("  verify-rpc-state --log-dir <dir> --epoch <N> <native_chain_state file>");
+    println!("  rpc-replica --state <native_chain_state file> --rpc-listen <host:port>");
+    println!("              [--primary-rpc <url>] [--registry <file>] [--log-dir <dir>]");
+    println!("              [--poll-ms <ms>] [--rpc-tier <tier>=<N>:<queue>]...");
+    println!("              [--gas-margin <percent>]");
+    println!("              [--config <file.toml>]");
+    println!(
+        "  dev [--nodes <N>] [--workspace <dir>] [--base-port <port>] [--rpc-base-port <port>]"
+    );
+    println!("      [--chain-id <N>] [--seed <text>] [--generate-only | --clean]");
 }
 
 #[cfg(feature = "net")]
@@ -318,7 +573,14 @@ fn print_net_start_help() {
     println!("Identity and peers:");
     println!("  --key <spec>                     Seed, file, or key specification");
     println!("  --identity <file>                Encrypted identity file");
+    println!("  --passphrase-fd <N>              Read the identity passphrase from descriptor N");
+    println!("  --passphrase-env <VAR>           Read the identity passphrase from $VAR");
+    println!("  --passphrase-file <path>         Read the identity passphrase from a file");
+    println!(
+        "  --passphrase-keychain            Read the identity passphrase from the OS keychain"
+    );
     println!("  --bootstrap <multiaddr>          Bootstrap peer; repeatable");
+    println!("  --config <file.toml>             Read defaults from the [net] table; flags win");
     println!("  --bootnodes <csv>                Comma-separated bootstrap peers");
     println!();
     println!("Consensus and gossip:");
@@ -336,6 +598,9 @@ fn print_net_start_help() {
     println!("  --policy <file>                  Membership policy");
     println!("  --policy-allowlist <file>        Static peer allowlist");
     println!("  --metrics <host:port>            Prometheus listener");
+    println!("  --metrics-push <url>             POST the metrics exposition to a collector");
+    println!("  --metrics-push-interval <secs>   Push interval (default 15)");
+    println!("  --metrics-push-auth <value>      Authorization header for pushes");
     println!("  --blob-dir <dir>                 Blob data directory");
     println!("  --blob-listen <host:port>        Blob HTTP listener");
     println!("  --blob-policy <file>             Namespace policy file");
@@ -348,14 +613,67 @@ fn print_net_start_help() {
     println!("  --token-mode <native|TOKEN_ID>   Settlement token mode");
     println!("  --token-oracle <RPC_URL>         Token oracle endpoint");
     println!("  --evm-chain-id <u64>             Enable native-chain finality");
+    println!(
+        "  --native-max-block-interval <s>  Seal an empty block after N idle seconds (default 12)"
+    );
+    println!("  --native-prune-keep <N>          Archive finalized blocks older than the last N");
+    println!("  --entry-gc-epochs <N>            Archive entries not finalized within N checkpoint epochs");
+    println!("  --native-pending-ttl <s>         Expire pending transactions after N seconds (default 600)");
+    println!("  --native-max-pending <N>         Pending transactions held before eviction (default 4096)");
     println!("  --evm-rpc-listen <host:port>     Serve finalized wallet JSON-RPC");
+    println!(
+        "  --rpc-cors-origins <list>        Comma-separated CORS origins (default *, none = off)"
+    );
+    println!("  --rpc-cors-headers <list>        Comma-separated CORS request headers");
+    println!("  --rpc-no-preflight               Reject OPTIONS preflights with 405");
+    println!("  --max-http-connections <N>       Concurrent metrics/RPC connections (default 256)");
+    println!("  --rpc-tier <tier>=<N>:<queue>    RPC concurrency and queue for write, read, or expensive");
+    println!("  --evm-gas-margin <percent>       Margin on calldata gas estimates (default 20)");
+    println!("  --max-anchors-per-minute <N>     Anchors accepted per identity per minute (default 60, 0 = off)");
+    println!("  --log-ship-endpoint <url>        Ship signed transcript records to an aggregator");
+    println!("  --find-checkpoint <hex>          Locate and dial DHT providers of a checkpoint");
+    println!(
+        "  --epoch-reward-pool <N>          Reward checkpoint signers from the stake registry"
+    );
+    println!("  --reward-weighting <equal|stake> Split rule for the epoch reward pool");
+    println!("  --reward-asset <id>              Pay the epoch reward pool in a secondary asset");
+    println!("  --storage <json|sqlite>          Backend for registry and native-chain state");
+    println!("  --governance-fee-schedule <file> Fee schedule replaced by passed proposals");
+    println!("  --governance-multisig <file>     Multisig policy rotated by passed proposals");
+    println!("  --governance-allowlist <file>    Allowlist extended by passed admissions");
+    println!("  --webhooks <file>                POST signed ledger and finality events");
+    println!("  --alerts <file>                  Alert sinks and thresholds (log, webhook, SMTP, Matrix)");
+    println!("  --genesis <file>                 Join the network described by a genesis config");
+    if cfg!(debug_assertions) {
+        println!();
+        println!("Stress rehearsal (debug builds only):");
+        println!("  --inject-latency-ms <ms>         Delay every outgoing gossip message");
+        println!("  --drop-rate <0..1>               Drop this fraction of outgoing gossip");
+        println!(
+            "  --duplicate-rate <0..1>          Publish this fraction of outgoing gossip twice"
+        );
+    }
 }
 
 #[cfg(feature = "net")]
 fn print_rollup_help() {
-    println!("Usage: julian rollup <settle|settle-file> ...");
+    println!("Usage: julian rollup <submit|prove|settle|challenge|settle-file|commitments|transfer-setup|batch-setup> ...");
+    println!("  submit <namespace> <batch-file> [--prev=<hex>] [--share=<idx>] [--hash=pedersen|poseidon] [--node=<url>] [--out=<batch.json>]");
+    println!("  prove <batch.json> --pk=<proving-key> [--out=<proof.json>]");
     println!("  settle <registry.json> <namespace> <share_root> <payer_b64> <fee> [options]");
-    println!("  settle-file <registry.json> <request.json> [--outbox <path>]");
+    println!("         [--zk=<proof.json>] [--transfer-vk=<file>] [--batch-vk=<file>] [--out=<receipt.json>]");
+    println!("  challenge <registry.json> <batch.json> <reason> [--proof=<proof.json> --transfer-vk=<file>] [--evidence=<file>] [--out=<fault.json>]");
+    println!("  settle-file <registry.json> <request.json> [--outbox <path>] [--store <path>] [--transfer-vk=<file>] [--batch-vk=<file>]");
+    println!("  commitments <store.json> <namespace> [sequence]");
+    println!(
+        "  transfer-setup <pedersen|poseidon> <path-depth> <proving-key-out> <verifying-key-out>"
+    );
+    println!("  batch-setup <proving-key-out> <verifying-key-out>");
+    println!("  Proofs verify only against a key from transfer-setup or batch-setup");
+    println!("  (--transfer-vk / --batch-vk, or rollup_transfer.vk / rollup_batch.vk in a");
+    println!("  node's blob directory).");
+    println!("  Settlements and faults are recorded in rollup_commitments.json beside the");
+    println!("  registry unless --store names another file.");
 }
 
 #[cfg(feature = "net")]
@@ -370,6 +688,33 @@ fn print_key_info_help() {
     println!("  Prints the Ed25519 public key and libp2p peer ID for a key source.");
 }
 
+#[cfg(feature = "net")]
+fn print_receipt_help() {
+    println!("Usage: julian receipt verify <receipt.json> [--trusted-key <b64>]...");
+    println!("                             [--input <name>=<path>]...");
+    println!("  Checks the signature of a check receipt. --input re-digests a file or");
+    println!("  directory and requires it to match the digest recorded under <name>.");
+    println!();
+    println!("Receipt options of node reconcile, net verify-envelope, migration verify-state:");
+    println!("  --sign-receipt <file>   Write a signed receipt of the inputs and verdict");
+    println!("  --key <spec>            Key that signs the receipt (required with --sign-receipt)");
+    println!("  --node-id <id>          Node identifier recorded in the receipt");
+}
+
+#[cfg(feature = "net")]
+fn print_key_help() {
+    println!("Usage: julian key <mnemonic|from-mnemonic> ...");
+    println!("  mnemonic [--words <12|15|18|21|24>]");
+    println!("      Prints a new BIP-39 English mnemonic (24 words by default).");
+    println!("  from-mnemonic --out <key-file> [--mnemonic-file <file>] [--path <path>]");
+    println!("                [--passphrase]");
+    println!("      Derives an Ed25519 node key along a hardened SLIP-0010 path");
+    println!("      (default {DEFAULT_MNEMONIC_PATH}) and writes it with a derivation header.");
+    println!("  keychain-store --identity <file>");
+    println!("      Saves the identity passphrase in the OS keychain for --passphrase-keychain");
+    println!("      (requires the `keychain` feature).");
+}
+
 #[cfg(feature = "net")]
 fn print_observer_help() {
     println!("Usage: julian observer <doctor|setup|register|submit|status> ...");
@@ -461,8 +806,34 @@ fn append_rollup_fault(path: &Path, ev: &power_house::rollup::RollupFaultEvidenc
     }
 }
 
+#[cfg(feature = "net")]
+fn rollup_store_path(registry: &Path, store: Option<String>) -> PathBuf {
+    store.map(PathBuf::from).unwrap_or_else(|| {
+        power_house::net::RollupCommitmentStore::path_for(
+            registry.parent().unwrap_or(Path::new(".")),
+        )
+    })
+}
+
+#[cfg(feature = "net")]
+fn record_rollup_outcome(
+    path: &Path,
+    commitment: &power_house::rollup::RollupCommitment,
+    outcome: power_house::net::RollupOutcome,
+) -> power_house::net::StoredCommitment {
+    power_house::net::append_rollup_record(path, commitment, outcome).unwrap_or_else(|err| {
+        fatal(&format!(
+            "failed to record commitment in {}: {err}",
+            path.display()
+        ))
+    })
+}
+
 fn main() {
-    let mut args = env::args().skip(1);
+    if let Ok(path) = env::var("PH_GENESIS_CONFIG") {
+        install_genesis_file(&path);
+    }
+    let mut args = take_profile_flag(env::args().skip(1).collect()).into_iter();
     let command = args.next();
     match command.as_deref() {
         None | Some("-h") | Some("--help") | Some("help") => print_cli_help(),
@@ -476,6 +847,13 @@ fn main() {
                 print_node_help();
             }
         }
+        Some("kv") => {
+            if let Some(sub) = args.next() {
+                handle_kv(&sub, args.collect());
+            } else {
+                print_kv_help();
+            }
+        }
         Some("scale_sumcheck") => {
             cmd_scale_sumcheck(args.collect());
         }
@@ -527,6 +905,22 @@ fn main() {
             cmd_key_info(args.collect());
         }
         #[cfg(feature = "net")]
+        Some("receipt") => {
+            if let Some(sub) = args.next() {
+                handle_receipt(&sub, args.collect());
+            } else {
+                print_receipt_help();
+            }
+        }
+        #[cfg(feature = "net")]
+        Some("key") => {
+            if let Some(sub) = args.next() {
+                handle_key(&sub, args.collect());
+            } else {
+                print_key_help();
+            }
+        }
+        #[cfg(feature = "net")]
         Some("observer") => {
             if let Some(sub) = args.next() {
                 handle_observer(&sub, args.collect());
@@ -590,12 +984,53 @@ fn main() {
                 print_rollup_help();
             }
         }
+        #[cfg(feature = "net")]
+        Some("storage") => {
+            if let Some(sub) = args.next() {
+                handle_storage(&sub, args.collect());
+            } else {
+                print_storage_help();
+            }
+        }
+        #[cfg(feature = "net")]
+        Some("backup") => {
+            if let Some(sub) = args.next() {
+                handle_backup(&sub, args.collect());
+            } else {
+                print_backup_help();
+            }
+        }
+        #[cfg(feature = "net")]
+        Some("policy") => {
+            if let Some(sub) = args.next() {
+                handle_policy(&sub, args.collect());
+            } else {
+                print_policy_help();
+            }
+        }
+        #[cfg(feature = "net")]
+        Some("tx") => {
+            if let Some(sub) = args.next() {
+                handle_tx(&sub, args.collect());
+            } else {
+                print_tx_help();
+            }
+        }
+        #[cfg(feature = "net")]
+        Some("beacon") => {
+            if let Some(sub) = args.next() {
+                handle_beacon(&sub, args.collect());
+            } else {
+                print_beacon_help();
+            }
+        }
         _ => {
             eprintln!("Unknown command: {}", command.unwrap_or_default());
             eprintln!("Run 'julian --help' for usage.");
-            std::process::exit(1);
+            exit(1);
         }
     }
+    print_profile_summary();
 }
 
 fn handle_rootprint(sub: &str, tail: Vec<String>) {
@@ -646,6 +1081,138 @@ fn handle_memory(sub: &str, tail: Vec<String>) {
     }
 }
 
+fn handle_kv(sub: &str, tail: Vec<String>) {
+    match sub {
+        "-h" | "--help" => print_kv_help(),
+        "put" => cmd_kv_put(tail),
+        "get" => cmd_kv_get(tail),
+        "prove" => cmd_kv_prove(tail),
+        "verify" => cmd_kv_verify(tail),
+        _ => fatal(&format!("unknown kv subcommand: {sub}")),
+    }
+}
+
+fn cmd_kv_put(args: Vec<String>) {
+    let value = match args.as_slice() {
+        [_, _, flag, path] if flag == "--file" => {
+            fs::read(path).unwrap_or_else(|err| fatal(&format!("failed to read {path}: {err}")))
+        }
+        [_, _, value] => value.clone().into_bytes(),
+        _ => fatal("Usage: julian kv put <log_dir> <key> (<value> | --file <path>)"),
+    };
+    let mut store = KvStore::open(Path::new(&args[0])).unwrap_or_else(|err| fatal(&err));
+    let receipt = store
+        .put(&args[1], &value)
+        .unwrap_or_else(|err| fatal(&err));
+    println!(
+        "{} v{} digest={} entry={} root={}",
+        receipt.key,
+        receipt.version,
+        receipt.value_digest,
+        receipt.entry_index,
+        receipt.ledger_root
+    );
+}
+
+fn cmd_kv_get(args: Vec<String>) {
+    let digest_only = args.iter().any(|arg| arg == "--digest");
+    let positional: Vec<&String> = args.iter().filter(|arg| *arg != "--digest").collect();
+    let [log_dir, key] = positional.as_slice() else {
+        fatal("Usage: julian kv get <log_dir> <key> [--digest]");
+    };
+    let store = KvStore::load(Path::new(log_dir.as_str())).unwrap_or_else(|err| fatal(&err));
+    let record = store
+        .get(key)
+        .unwrap_or_else(|| fatal_code(2, &format!("key not found: {key}")));
+    let digest = power_house::transcript_digest_to_hex(&record.value_digest);
+    if digest_only {
+        println!("{digest}");
+        return;
+    }
+    match store.value(record) {
+        Some(value) => match String::from_utf8(value) {
+            Ok(text) => print!("{text}"),
+            Err(err) => println!("hex:{}", hex::encode(err.into_bytes())),
+        },
+        None => fatal(&format!(
+            "{key} v{} is anchored ({digest}) but its value is not stored locally",
+            record.version
+        )),
+    }
+}
+
+fn cmd_kv_prove(args: Vec<String>) {
+    if args.len() < 2 || args.len() > 3 {
+        fatal("Usage: julian kv prove <log_dir> <key> [output.json]");
+    }
+    let store = KvStore::load(Path::new(&args[0])).unwrap_or_else(|err| fatal(&err));
+    let receipt = store
+        .prove(&args[1])
+        .unwrap_or_else(|| fatal_code(2, &format!("key not found: {}", args[1])));
+    receipt
+        .verify_against(&store.ledger().anchor())
+        .unwrap_or_else(|err| fatal(&format!("receipt does not verify: {err}")));
+    let document = serde_json::to_string_pretty(&receipt).unwrap();
+    if let Some(path) = args.get(2) {
+        if let Err(err) = fs::write(path, document) {
+            fatal(&format!("failed to write receipt: {err}"));
+        }
+    } else {
+        println!("{document}");
+    }
+}
+
+fn cmd_kv_verify(args: Vec<String>) {
+    let mut receipt_path = None;
+    let mut anchor_path = None;
+    let mut value_path = None;
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--anchor" => anchor_path = iter.next(),
+            "--value-file" => value_path = iter.next(),
+            _ if receipt_path.is_none() => receipt_path = Some(arg),
+            _ => fatal(&format!("unexpected argument: {arg}")),
+        }
+    }
+    let receipt_path = receipt_path.unwrap_or_else(|| {
+        fatal(
+            "Usage: julian kv verify <receipt.json> [--anchor <anchor_file>] [--value-file <path>]",
+        )
+    });
+    let text = fs::read_to_string(&receipt_path)
+        .unwrap_or_else(|err| fatal(&format!("failed to read receipt: {err}")));
+    let receipt: KvReceipt = serde_json::from_str(&text)
+        .unwrap_or_else(|err| fatal(&format!("invalid receipt JSON: {err}")));
+    let outcome = match &anchor_path {
+        Some(path) => {
+            let anchor = read_anchor(Path::new(path))
+                .unwrap_or_else(|err| fatal(&format!("failed to read anchor: {err}")));
+            receipt.verify_against(&anchor)
+        }
+        None => receipt.verify(),
+    };
+    if let Err(err) = outcome {
+        fatal(&format!("receipt rejected: {err}"));
+    }
+    if let Some(path) = value_path {
+        let value =
+            fs::read(&path).unwrap_or_else(|err| fatal(&format!("failed to read {path}: {err}")));
+        if !receipt.matches_value(&value) {
+            fatal("value does not match the receipt digest");
+        }
+    }
+    let scope = if anchor_path.is_some() {
+        "latest in anchor"
+    } else {
+        "included in root"
+    };
+    println!(
+        "{} v{}: {scope} {}",
+        receipt.key, receipt.version, receipt.ledger_root
+    );
+}
+
 #[cfg(feature = "sfcs")]
 fn handle_sfcs(sub: &str, tail: Vec<String>) {
     match sub {
@@ -3186,3289 +3753,6949 @@ fn handle_stake(sub: &str, tail: Vec<String>) {
         "fund" => cmd_stake_fund(tail),
         "bond" => cmd_stake_bond(tail),
         "snapshot" => cmd_stake_snapshot(tail),
+        "verify-snapshot" => cmd_stake_verify_snapshot(tail),
         "claims" => cmd_stake_claims(tail),
         "apply-claims" => cmd_stake_apply_claims(tail),
         "unbond" => cmd_stake_unbond(tail),
+        "withdraw" => cmd_stake_withdraw(tail),
+        "audit" => cmd_stake_audit(tail),
+        "address-index" => cmd_stake_address_index(tail),
+        "prove" => cmd_stake_prove(tail),
+        "unbonding-period" => cmd_stake_unbonding_period(tail),
+        "guards" => cmd_stake_guards(tail),
+        "guard-override" => cmd_stake_guard_override(tail),
+        "fee-sink" => cmd_stake_fee_sink(tail),
+        "freeze-order" => cmd_stake_freeze_order(tail),
+        "freeze" => cmd_stake_freeze(tail),
+        "frozen" => cmd_stake_frozen(tail),
         "reward" => cmd_stake_reward(tail),
         _ => {
             eprintln!("Unknown stake subcommand: {sub}");
-            std::process::exit(1);
+            exit(1);
         }
     }
 }
 
 #[cfg(feature = "net")]
-fn handle_governance(sub: &str, tail: Vec<String>) {
+fn handle_storage(sub: &str, tail: Vec<String>) {
     match sub {
-        "-h" | "--help" => print_governance_help(),
-        "propose-migration" => cmd_governance_propose_migration(tail),
+        "-h" | "--help" => print_storage_help(),
+        "migrate" => cmd_storage_migrate(tail),
         _ => {
-            eprintln!("Unknown governance subcommand: {sub}");
-            std::process::exit(1);
+            eprintln!("Unknown storage subcommand: {sub}");
+            exit(1);
         }
     }
 }
 
 #[cfg(feature = "net")]
-fn handle_migration(sub: &str, tail: Vec<String>) {
+fn handle_backup(sub: &str, tail: Vec<String>) {
     match sub {
-        "-h" | "--help" => print_migration_help(),
-        "finalize" => cmd_migration_finalize(tail),
-        "verify-state" => cmd_migration_verify_state(tail),
-        "execute-burn-intents" => cmd_migration_execute_burn_intents(tail),
+        "-h" | "--help" => print_backup_help(),
+        "create" => cmd_backup_create(tail),
+        "verify" => cmd_backup_verify(tail),
+        "restore" => cmd_backup_restore(tail),
         _ => {
-            eprintln!("Unknown migration subcommand: {sub}");
-            std::process::exit(1);
+            eprintln!("Unknown backup subcommand: {sub}");
+            exit(1);
         }
     }
 }
 
 #[cfg(feature = "net")]
-fn handle_rollup(sub: &str, tail: Vec<String>) {
-    match sub {
-        "-h" | "--help" => print_rollup_help(),
-        "settle" => cmd_rollup_settle(tail),
-        "settle-file" => cmd_rollup_settle_file(tail),
-        _ => {
-            eprintln!("Unknown rollup subcommand: {sub}");
-            std::process::exit(1);
-        }
-    }
-}
-
-fn handle_node(sub: &str, tail: Vec<String>) {
-    match sub {
-        "-h" | "--help" => print_node_help(),
-        "run" => cmd_node_run(tail),
-        "anchor" => cmd_node_anchor(tail),
-        "reconcile" => cmd_node_reconcile(tail),
-        "prove" => cmd_node_prove(tail),
-        "verify-proof" => cmd_node_verify_proof(tail),
-        _ => {
-            eprintln!("Unknown subcommand: {}", sub);
-            std::process::exit(1);
-        }
+fn print_backup_manifest(manifest: &BackupManifest) {
+    for file in &manifest.files {
+        println!("  {:<48} {:>10} {}", file.path, file.size, file.digest);
     }
+    println!(
+        "{} files, network {}, signed by {}",
+        manifest.files.len(),
+        manifest.network,
+        manifest.public_key
+    );
 }
 
-fn cmd_scale_sumcheck(args: Vec<String>) {
-    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
-        print_scale_help();
-        return;
-    }
-
-    let mut max_vars: Option<usize> = None;
+#[cfg(feature = "net")]
+fn cmd_backup_create(args: Vec<String>) {
+    let mut sources = BackupSources::default();
+    let mut log_dir = None;
+    let mut key_spec = None;
+    let mut out = None;
     let mut iter = args.into_iter();
     while let Some(arg) = iter.next() {
+        let mut path = |flag: &str| {
+            PathBuf::from(
+                iter.next()
+                    .unwrap_or_else(|| fatal(&format!("{flag} expects a path"))),
+            )
+        };
         match arg.as_str() {
-            "--vars" | "--max-vars" => {
-                let value = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--vars expects a value"));
-                max_vars = Some(
-                    value
-                        .parse()
-                        .unwrap_or_else(|_| fatal("invalid --vars value")),
+            "--log-dir" => log_dir = Some(path("--log-dir")),
+            "--state-dir" => sources.state_dir = Some(path("--state-dir")),
+            "--apply-state" => sources.apply_state = Some(path("--apply-state")),
+            "--config" => sources.config.push(path("--config")),
+            "--out" => out = Some(path("--out")),
+            "--key" => {
+                key_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--key expects a value")),
                 );
             }
-            other => fatal(&format!("unknown argument: {other}")),
+            other => fatal(&format!("unexpected argument: {other}")),
         }
     }
-    run_scale_sumcheck(max_vars);
-}
-
-fn run_scale_sumcheck(max_vars: Option<usize>) {
-    let field = Field::new(257);
-    let default_dims = [8usize, 10, 12, 14, 16, 18];
-    let dimensions: Vec<usize> = match max_vars {
-        Some(m) => (8..=m).step_by(2).collect(),
-        None => default_dims.to_vec(),
+    let (Some(log_dir), Some(out)) = (log_dir, out) else {
+        print_backup_help();
+        exit(1);
     };
-    if dimensions.is_empty() {
-        fatal("No dimensions selected; provide --vars >= 8.");
-    }
-    let mut rows = Vec::new();
-    println!(
-        "{:>5} | {:>10} | {:>10} | {:>10} | {:>12} | {:>12}",
-        "vars", "2^vars", "total(ms)", "avg(ms)", "max_round(ms)", "final_eval"
-    );
-    println!("{}", "-".repeat(70));
-    for &vars in &dimensions {
-        let evaluator = make_scale_evaluator(vars, field.modulus());
-        let (proof, stats) = GeneralSumProof::prove_streaming_with_stats(vars, &field, evaluator);
-        let (total_ms, avg_ms, max_round_ms) = summarize_stats(&stats);
-        let size = 1usize << vars;
-        rows.push((
-            vars,
-            size,
-            total_ms,
-            avg_ms,
-            max_round_ms,
-            proof.final_evaluation,
-        ));
-        println!(
-            "{:>5} | {:>10} | {:>10.3} | {:>10.3} | {:>12.3} | {:>12}",
-            vars, size, total_ms, avg_ms, max_round_ms, proof.final_evaluation
-        );
-    }
-
-    if let Ok(path) = std::env::var("POWER_HOUSE_SCALE_OUT") {
-        let mut file = fs::File::create(&path).expect("create csv output");
-        use std::io::Write;
-        writeln!(
-            file,
-            "vars,size,total_ms,avg_ms,max_round_ms,final_evaluation"
-        )
-        .expect("write csv header");
-        for (vars, size, total_ms, avg_ms, max_round_ms, final_eval) in rows {
-            writeln!(
-                file,
-                "{vars},{size},{total_ms:.6},{avg_ms:.6},{max_round_ms:.6},{final_eval}"
-            )
-            .expect("write csv row");
-        }
-        println!("CSV exported to {path}");
-    }
+    sources.log_dir = log_dir;
+    let key = load_or_derive_keypair(&Ed25519KeySource::from_spec(key_spec.as_deref()))
+        .unwrap_or_else(|err| fatal(&format!("failed to load key: {err}")));
+    let manifest = create_backup(&sources, &out, &key, now_millis())
+        .unwrap_or_else(|err| fatal(&err.to_string()));
+    print_backup_manifest(&manifest);
+    println!("wrote {}", out.display());
 }
 
-fn make_scale_evaluator(
-    num_vars: usize,
-    modulus: u64,
-) -> impl Fn(usize) -> u64 + Send + Sync + 'static {
-    move |idx: usize| {
-        let mut acc = (idx as u64) % modulus;
-        for bit in 0..num_vars {
-            let bit_value = ((idx >> bit) & 1) as u64;
-            if bit_value == 0 {
-                continue;
-            }
-            let coef = ((bit as u64 + 3).pow(2)) % modulus;
-            acc = (acc + coef) % modulus;
-        }
-        for bit in 0..num_vars.saturating_sub(1) {
-            let a = ((idx >> bit) & 1) as u64;
-            let b = ((idx >> (bit + 1)) & 1) as u64;
-            if a == 0 || b == 0 {
-                continue;
-            }
-            let coef = (17 + (bit as u64 * 5)) % modulus;
-            acc = (acc + coef) % modulus;
-        }
-        if num_vars >= 3 {
-            let a = (idx & 1) as u64;
-            let b = ((idx >> 1) & 1) as u64;
-            let c = ((idx >> 2) & 1) as u64;
-            if a == 1 && b == 1 && c == 1 {
-                acc = (acc + 29) % modulus;
+#[cfg(feature = "net")]
+fn cmd_backup_verify(args: Vec<String>) {
+    let mut archive = None;
+    let mut trusted_key = None;
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--trusted-key" => {
+                trusted_key = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--trusted-key expects a public key")),
+                );
             }
+            other if archive.is_none() => archive = Some(PathBuf::from(other)),
+            other => fatal(&format!("unexpected argument: {other}")),
         }
-        acc % modulus
     }
+    let Some(archive) = archive else {
+        print_backup_help();
+        exit(1);
+    };
+    let manifest = verify_backup(&archive, trusted_key.as_deref())
+        .unwrap_or_else(|err| fatal(&err.to_string()));
+    print_backup_manifest(&manifest);
+    println!("backup verified");
 }
 
-fn ms(duration: &std::time::Duration) -> f64 {
-    duration.as_secs_f64() * 1_000.0
-}
-
-fn summarize_stats(stats: &ProofStats) -> (f64, f64, f64) {
-    if stats.round_durations.is_empty() {
-        return (0.0, 0.0, 0.0);
-    }
-    let total = ms(&stats.total_duration);
-    let max = stats.round_durations.iter().map(ms).fold(0.0f64, f64::max);
-    let mean = total / (stats.round_durations.len() as f64);
-    (total, mean, max)
+#[cfg(feature = "net")]
+fn cmd_backup_restore(args: Vec<String>) {
+    let mut archive = None;
+    let mut targets = RestoreTargets::default();
+    let mut log_dir = None;
+    let mut trusted_key = None;
+    let mut force = false;
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        let mut value = |flag: &str| {
+            iter.next()
+                .unwrap_or_else(|| fatal(&format!("{flag} expects a value")))
+        };
+        match arg.as_str() {
+            "--log-dir" => log_dir = Some(PathBuf::from(value("--log-dir"))),
+            "--state-dir" => targets.state_dir = Some(PathBuf::from(value("--state-dir"))),
+            "--config-dir" => targets.config_dir = Some(PathBuf::from(value("--config-dir"))),
+            "--trusted-key" => trusted_key = Some(value("--trusted-key")),
+            "--force" => force = true,
+            other if archive.is_none() => archive = Some(PathBuf::from(other)),
+            other => fatal(&format!("unexpected argument: {other}")),
+        }
+    }
+    let (Some(archive), Some(log_dir)) = (archive, log_dir) else {
+        print_backup_help();
+        exit(1);
+    };
+    targets.log_dir = log_dir;
+    let manifest = restore_backup(&archive, &targets, trusted_key.as_deref(), force)
+        .unwrap_or_else(|err| fatal(&err.to_string()));
+    print_backup_manifest(&manifest);
+    println!("restored {} files", manifest.files.len());
 }
 
 #[cfg(feature = "net")]
-fn handle_net(sub: &str, tail: Vec<String>) {
+fn handle_policy(sub: &str, tail: Vec<String>) {
     match sub {
-        "-h" | "--help" => print_net_help(),
-        "start" => cmd_net_start(tail),
-        "anchor" => cmd_net_anchor(tail),
-        "verify-envelope" => cmd_net_verify_envelope(tail),
+        "-h" | "--help" => print_policy_help(),
+        "show" => cmd_policy_show(tail),
+        "add-key" => cmd_policy_edit(KeyEditAction::AddKey, tail),
+        "remove-key" => cmd_policy_edit(KeyEditAction::RemoveKey, tail),
         _ => {
-            eprintln!("Unknown net subcommand: {sub}");
-            std::process::exit(1);
+            eprintln!("Unknown policy subcommand: {sub}");
+            exit(1);
         }
     }
 }
 
 #[cfg(feature = "net")]
-fn cmd_keygen(args: Vec<String>) {
-    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
-        print_keygen_help();
+fn cmd_policy_show(args: Vec<String>) {
+    let mut path = None;
+    let mut history = false;
+    let mut json_output = false;
+    for arg in args {
+        match arg.as_str() {
+            "--history" => history = true,
+            "--json" => json_output = true,
+            other if other.starts_with("--") => fatal(&format!("unknown argument: {other}")),
+            other if path.is_none() => path = Some(PathBuf::from(other)),
+            other => fatal(&format!("unexpected argument: {other}")),
+        }
+    }
+    let Some(path) = path else {
+        print_policy_help();
+        exit(1);
+    };
+    let policy = PolicySnapshot::load(&path).unwrap_or_else(|err| fatal(&err.to_string()));
+    let changes = load_policy_history(&path).unwrap_or_else(|err| fatal(&err.to_string()));
+    if json_output {
+        let mut value = serde_json::json!({
+            "policy": policy.kind.label(),
+            "digest": policy.digest_hex(),
+            "members": policy.members,
+            "threshold": policy.threshold,
+            "signers": policy.signers,
+            "edits": changes.len(),
+        });
+        if history {
+            value["history"] = serde_json::json!(changes);
+        }
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&value).expect("policy serializes")
+        );
         return;
     }
+    println!("policy: {}", policy.kind.label());
+    println!("digest: {}", policy.digest_hex());
+    println!("members ({}):", policy.members.len());
+    for member in &policy.members {
+        println!("  {member}");
+    }
+    if let Some(threshold) = policy.threshold {
+        println!(
+            "signers ({threshold} of {} required):",
+            policy.signers.len()
+        );
+        for signer in &policy.signers {
+            println!("  {signer}");
+        }
+    }
+    println!("edits: {}", changes.len());
+    if history {
+        for change in &changes {
+            println!(
+                "  {} {} {} {} -> {}{}",
+                change.changed_ms,
+                change.action.label(),
+                change.key,
+                change.digest_before,
+                change.digest_after,
+                change
+                    .note
+                    .as_deref()
+                    .map(|note| format!(" ({note})"))
+                    .unwrap_or_default()
+            );
+        }
+    }
+}
 
-    let mut key_spec: Option<String> = None;
-    let mut out_path: Option<PathBuf> = None;
+#[cfg(feature = "net")]
+fn cmd_policy_edit(action: KeyEditAction, args: Vec<String>) {
+    let mut positional = Vec::new();
+    let mut note = None;
+    let mut update_path: Option<PathBuf> = None;
+    let mut approvers = Vec::new();
+    let mut out: Option<PathBuf> = None;
     let mut iter = args.into_iter();
     while let Some(arg) = iter.next() {
+        let mut value = |flag: &str| {
+            iter.next()
+                .unwrap_or_else(|| fatal(&format!("{flag} expects a value")))
+        };
         match arg.as_str() {
-            "--out" => {
-                out_path = Some(PathBuf::from(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--out expects a value")),
-                ));
+            "--note" => note = Some(value("--note")),
+            "--update" => update_path = Some(PathBuf::from(value("--update"))),
+            "--approve" => approvers.push(value("--approve")),
+            "--out" => out = Some(PathBuf::from(value("--out"))),
+            other if other.starts_with("--") => fatal(&format!("unknown argument: {other}")),
+            other => positional.push(other.to_string()),
+        }
+    }
+    let [path, key] = positional.as_slice() else {
+        print_policy_help();
+        exit(1);
+    };
+    let path = PathBuf::from(path);
+    let edit = KeyEdit::new(action, key).unwrap_or_else(|err| fatal(&err.to_string()));
+    let policy = PolicySnapshot::load(&path).unwrap_or_else(|err| fatal(&err.to_string()));
+    let update = match policy.kind {
+        PolicyKind::Allowlist => {
+            if update_path.is_some() || !approvers.is_empty() {
+                fatal("--update and --approve apply to multisig policies only");
             }
-            value => {
-                if key_spec.is_none() {
-                    key_spec = Some(value.to_string());
-                } else {
-                    fatal(&format!("unknown argument: {value}"));
+            None
+        }
+        PolicyKind::Multisig => {
+            let mut update = match &update_path {
+                Some(update_path) => {
+                    if note.is_some() {
+                        fatal("--note is fixed by the update being approved");
+                    }
+                    let bytes = fs::read(update_path).unwrap_or_else(|err| {
+                        fatal(&format!("failed to read {}: {err}", update_path.display()))
+                    });
+                    let update: GovernanceUpdate =
+                        serde_json::from_slice(&bytes).unwrap_or_else(|err| {
+                            fatal(&format!("invalid update {}: {err}", update_path.display()))
+                        });
+                    edit.check_update(&policy, &update)
+                        .unwrap_or_else(|err| fatal(&err.to_string()));
+                    update
+                }
+                None => edit
+                    .update(&policy, note.as_deref())
+                    .unwrap_or_else(|err| fatal(&err.to_string())),
+            };
+            for spec in &approvers {
+                let signer = load_or_derive_keypair(&Ed25519KeySource::from_spec(Some(spec)))
+                    .unwrap_or_else(|err| fatal(&format!("failed to load key: {err}")));
+                let signer_b64 = power_house::net::encode_public_key_base64(&signer.verifying);
+                if !policy.signers.contains(&signer_b64) {
+                    fatal(&format!("{signer_b64} is not a signer of this policy"));
                 }
+                update
+                    .approve(&signer)
+                    .unwrap_or_else(|err| fatal(&err.to_string()));
+            }
+            let approvals = policy.approvals(&update);
+            let threshold = policy.threshold.unwrap_or(0);
+            if approvals < threshold {
+                let Some(out) = out.or(update_path) else {
+                    fatal(&format!(
+                        "{approvals} of {threshold} approvals; pass --out to hand the update to the next signer"
+                    ));
+                };
+                let encoded = serde_json::to_vec_pretty(&update).expect("update serializes");
+                fs::write(&out, encoded).unwrap_or_else(|err| {
+                    fatal(&format!("failed to write {}: {err}", out.display()))
+                });
+                println!(
+                    "QSYS|mod=POLICY|evt=APPROVAL_RECORDED|approvals={approvals}|threshold={threshold}|update={}",
+                    out.display()
+                );
+                return;
             }
+            Some(update)
         }
-    }
-
-    let key_source = Ed25519KeySource::from_spec(key_spec.as_deref());
-    let passphrase = prompt_password("Identity passphrase: ")
-        .unwrap_or_else(|err| fatal(&format!("failed to read passphrase: {err}")));
-    let material = load_or_derive_keypair(&key_source)
-        .unwrap_or_else(|err| fatal(&format!("failed to derive key: {err}")));
-    let encoded = encrypt_identity_base64(&material.signing, &passphrase);
-    let out_path = out_path.unwrap_or_else(|| PathBuf::from("julian.identity"));
-    if let Some(parent) = out_path.parent() {
-        let _ = fs::create_dir_all(parent);
-    }
-    fs::write(&out_path, format!("{encoded}\n"))
-        .unwrap_or_else(|err| fatal(&format!("failed to write identity: {err}")));
+    };
+    let change = apply_key_edit(&path, &edit, update.as_ref(), now_millis())
+        .unwrap_or_else(|err| fatal(&err.to_string()));
     println!(
-        "public_key_b64: {}",
-        power_house::net::encode_public_key_base64(&material.verifying)
+        "QSYS|mod=POLICY|evt=KEY_EDIT|action={}|key={}|policy={}|digest={}",
+        change.action.label(),
+        change.key,
+        change.policy.label(),
+        change.digest_after
     );
-    println!("identity_path: {}", out_path.display());
 }
 
 #[cfg(feature = "net")]
-fn cmd_key_info(args: Vec<String>) {
-    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
-        print_key_info_help();
-        return;
+fn handle_beacon(sub: &str, tail: Vec<String>) {
+    match sub {
+        "-h" | "--help" => print_beacon_help(),
+        "get" => cmd_beacon_get(tail),
+        _ => {
+            eprintln!("Unknown beacon subcommand: {sub}");
+            exit(1);
+        }
+    }
+}
+
+#[cfg(feature = "net")]
+fn handle_tx(sub: &str, tail: Vec<String>) {
+    match sub {
+        "-h" | "--help" => print_tx_help(),
+        "trace" => cmd_tx_trace(tail),
+        _ => {
+            eprintln!("Unknown tx subcommand: {sub}");
+            exit(1);
+        }
     }
+}
 
-    let mut key_spec: Option<String> = None;
-    let mut json = false;
-    for arg in args {
+#[cfg(feature = "net")]
+fn cmd_tx_trace(args: Vec<String>) {
+    let mut hash = None;
+    let mut rpc_url = None;
+    let mut json_output = false;
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
         match arg.as_str() {
-            "--json" => json = true,
-            value if key_spec.is_none() => key_spec = Some(value.to_string()),
-            value => fatal(&format!("unknown argument: {value}")),
+            "--rpc" => rpc_url = Some(take_option(&mut iter, "--rpc")),
+            "--json" => json_output = true,
+            "-h" | "--help" => {
+                print_tx_help();
+                return;
+            }
+            other if other.starts_with("--") => fatal(&format!("unknown argument: {other}")),
+            other if hash.is_none() => hash = Some(other.to_string()),
+            other => fatal(&format!("unexpected argument: {other}")),
         }
     }
-    let key_spec = key_spec.unwrap_or_else(|| fatal("key-info requires a key specification"));
-    let material = load_or_derive_keypair(&Ed25519KeySource::from_spec(Some(&key_spec)))
-        .unwrap_or_else(|err| fatal(&format!("failed to load key: {err}")));
-    let public_key = power_house::net::encode_public_key_base64(&material.verifying);
-    let peer_id = material.libp2p.public().to_peer_id().to_string();
-
-    if json {
+    let (Some(hash), Some(rpc_url)) = (hash, rpc_url) else {
+        print_tx_help();
+        exit(1);
+    };
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap_or_else(|err| fatal(&format!("failed to start runtime: {err}")));
+    let response: serde_json::Value = runtime
+        .block_on(async {
+            reqwest::Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()?
+                .post(&rpc_url)
+                .json(&serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "method": "ph_traceTransaction",
+                    "params": [hash],
+                }))
+                .send()
+                .await?
+                .json()
+                .await
+        })
+        .unwrap_or_else(|err| fatal(&format!("trace request to {rpc_url} failed: {err}")));
+    if let Some(error) = response.get("error") {
+        fatal(&format!(
+            "trace failed: {}",
+            error["message"].as_str().unwrap_or("unknown error")
+        ));
+    }
+    let trace = &response["result"];
+    if trace.is_null() {
+        fatal(&format!("transaction {hash} not found"));
+    }
+    if json_output {
         println!(
             "{}",
-            serde_json::json!({
-                "peer_id": peer_id,
-                "public_key_b64": public_key,
-            })
+            serde_json::to_string_pretty(trace).unwrap_or_else(|err| fatal(&err.to_string()))
+        );
+        return;
+    }
+    let quantity = |value: &serde_json::Value| {
+        value
+            .as_str()
+            .and_then(|text| u64::from_str_radix(text.trim_start_matches("0x"), 16).ok())
+            .unwrap_or_default()
+    };
+    let text = |value: &serde_json::Value| value.as_str().unwrap_or("-").to_string();
+    println!(
+        "transaction {} (block {}, index {})",
+        text(&trace["transactionHash"]),
+        quantity(&trace["blockNumber"]),
+        quantity(&trace["transactionIndex"])
+    );
+    println!(
+        "  {} -> {}  value {}",
+        text(&trace["from"]),
+        text(&trace["to"]),
+        quantity(&trace["value"])
+    );
+    for change in trace["changes"].as_array().into_iter().flatten() {
+        let registry_key = change["registryKey"]
+            .as_str()
+            .map(|key| format!("  registry {key}"))
+            .unwrap_or_default();
+        println!(
+            "  {:<6} {}  {}  balance {} -> {}  nonce {} -> {}{registry_key}",
+            text(&change["kind"]),
+            text(&change["address"]),
+            quantity(&change["amount"]),
+            quantity(&change["balanceBefore"]),
+            quantity(&change["balanceAfter"]),
+            quantity(&change["nonceBefore"]),
+            quantity(&change["nonceAfter"]),
+        );
+    }
+    let fee = &trace["fee"];
+    println!(
+        "  fee    {} (gas {} at {}) -> {}",
+        quantity(&fee["amount"]),
+        quantity(&fee["gasUsed"]),
+        quantity(&fee["gasPrice"]),
+        text(&fee["sink"])
+    );
+    if quantity(&trace["input"]["bytes"]) > 0 {
+        let handler = if trace["input"]["handler"].as_bool() == Some(true) {
+            "handled"
+        } else {
+            "no handler"
+        };
+        println!(
+            "  input  {} byte(s), {handler}",
+            quantity(&trace["input"]["bytes"])
         );
-    } else {
-        println!("public_key_b64: {public_key}");
-        println!("peer_id: {peer_id}");
     }
 }
 
 #[cfg(feature = "net")]
-fn handle_observer(sub: &str, tail: Vec<String>) {
-    match sub {
-        "-h" | "--help" => print_observer_help(),
-        "doctor" => cmd_observer_doctor(tail),
-        "setup" => cmd_observer_setup(tail),
-        "register" => cmd_observer_register(tail),
-        "submit" => cmd_observer_submit(tail),
-        "status" => cmd_observer_status(tail),
-        _ => fatal(&format!("unknown observer subcommand: {sub}")),
+fn cmd_beacon_get(args: Vec<String>) {
+    let mut epoch: Option<u64> = None;
+    let mut log_dir: Option<PathBuf> = None;
+    let mut threshold = 1usize;
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--log-dir" => {
+                log_dir = Some(PathBuf::from(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--log-dir expects a directory")),
+                ));
+            }
+            "--threshold" => {
+                threshold = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--threshold expects a value"))
+                    .parse()
+                    .unwrap_or_else(|_| fatal("invalid --threshold"));
+            }
+            other if epoch.is_none() => {
+                epoch = Some(
+                    other
+                        .parse()
+                        .unwrap_or_else(|_| fatal("epoch must be an integer")),
+                );
+            }
+            other => fatal(&format!("unexpected argument: {other}")),
+        }
+    }
+    let (Some(epoch), Some(log_dir)) = (epoch, log_dir) else {
+        print_beacon_help();
+        exit(1);
+    };
+    match load_beacon(&log_dir, epoch, threshold) {
+        Ok(value) => println!(
+            "{}",
+            serde_json::to_string_pretty(&value).expect("beacon serializes")
+        ),
+        Err(err) => fatal(&format!("beacon unavailable: {err}")),
     }
 }
 
 #[cfg(feature = "net")]
-#[derive(Debug, Clone)]
-struct ObserverCliOptions {
-    node_id: String,
-    operator: String,
-    region: String,
-    key_spec: String,
-    p2p_port: u16,
-    metrics_port: u16,
-    public_host: Option<String>,
-    probe_url: Option<String>,
-    output: PathBuf,
-    json: bool,
+fn cmd_storage_migrate(args: Vec<String>) {
+    if args.len() < 3 {
+        eprintln!(
+            "Usage: julian storage migrate <registry|native-state> <input.json> <output.sqlite>"
+        );
+        exit(1);
+    }
+    let (input, output) = (Path::new(&args[1]), Path::new(&args[2]));
+    if StorageBackend::for_path(output) != StorageBackend::Sqlite {
+        fatal("migration output must end in .sqlite, .sqlite3 or .db");
+    }
+    match args[0].as_str() {
+        "registry" => {
+            let accounts = migrate_registry(input, output).unwrap_or_else(|err| fatal(&err));
+            println!(
+                "migrated {accounts} registry accounts to {}",
+                output.display()
+            );
+        }
+        "native-state" => {
+            let blocks = migrate_chain_state(input, output, WallClock.now().as_secs())
+                .unwrap_or_else(|err| fatal(&err));
+            println!(
+                "migrated {blocks} native-chain blocks to {}",
+                output.display()
+            );
+        }
+        other => fatal(&format!(
+            "unknown storage kind '{other}' (expected registry or native-state)"
+        )),
+    }
 }
 
 #[cfg(feature = "net")]
-#[derive(Debug, Clone, Serialize)]
-struct ObserverCheck {
-    name: String,
-    status: String,
-    detail: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    fix: Option<String>,
+fn handle_governance(sub: &str, tail: Vec<String>) {
+    match sub {
+        "-h" | "--help" => print_governance_help(),
+        "propose-migration" => cmd_governance_propose_migration(tail),
+        _ => {
+            eprintln!("Unknown governance subcommand: {sub}");
+            exit(1);
+        }
+    }
 }
 
 #[cfg(feature = "net")]
-#[derive(Debug, Clone, Serialize)]
-struct ObserverDiagnosis {
-    schema: String,
-    node_id: String,
-    key_spec: String,
-    local_ip: Option<String>,
-    public_host: Option<String>,
-    p2p_port: u16,
-    metrics_port: u16,
-    peer_id: Option<String>,
-    public_key_b64: Option<String>,
-    checks: Vec<ObserverCheck>,
+fn handle_migration(sub: &str, tail: Vec<String>) {
+    match sub {
+        "-h" | "--help" => print_migration_help(),
+        "finalize" => cmd_migration_finalize(tail),
+        "finalize-status" => cmd_migration_finalize_status(tail),
+        "verify-state" => cmd_migration_verify_state(tail),
+        "execute-burn-intents" => cmd_migration_execute_burn_intents(tail),
+        "reconcile-burns" => cmd_migration_reconcile_burns(tail),
+        "sweep-claims" => cmd_migration_sweep_claims(tail),
+        "reserve-report" => cmd_migration_reserve_report(tail),
+        "verify-reserve-report" => cmd_migration_verify_reserve_report(tail),
+        _ => {
+            eprintln!("Unknown migration subcommand: {sub}");
+            exit(1);
+        }
+    }
 }
 
 #[cfg(feature = "net")]
-fn cmd_observer_doctor(args: Vec<String>) {
-    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
-        print_observer_help();
-        return;
+fn handle_rollup(sub: &str, tail: Vec<String>) {
+    match sub {
+        "-h" | "--help" => print_rollup_help(),
+        "submit" => cmd_rollup_submit(tail),
+        "prove" => cmd_rollup_prove(tail),
+        "settle" => cmd_rollup_settle(tail),
+        "challenge" => cmd_rollup_challenge(tail),
+        "settle-file" => cmd_rollup_settle_file(tail),
+        "commitments" => cmd_rollup_commitments(tail),
+        "transfer-setup" => cmd_rollup_transfer_setup(tail),
+        "batch-setup" => cmd_rollup_batch_setup(tail),
+        _ => {
+            eprintln!("Unknown rollup subcommand: {sub}");
+            exit(1);
+        }
     }
-    let (values, switches) = parse_observer_options(
-        args,
-        &[
-            "--node-id",
-            "--key",
-            "--operator",
-            "--region",
-            "--public-host",
-            "--p2p-port",
-            "--metrics-port",
-            "--probe-url",
-        ],
-        &["--json", "--no-probe"],
-    );
-    let options = observer_cli_options(&values, &switches, false);
-    let diagnosis = diagnose_observer(&options);
-    print_observer_diagnosis(&diagnosis, options.json);
 }
 
-#[cfg(feature = "net")]
-fn cmd_observer_setup(args: Vec<String>) {
-    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
-        print_observer_help();
-        return;
-    }
-    let (values, switches) = parse_observer_options(
-        args,
-        &[
-            "--node-id",
-            "--key",
-            "--operator",
-            "--region",
-            "--public-host",
-            "--p2p-port",
-            "--metrics-port",
-            "--probe-url",
-            "--output",
-        ],
-        &["--json", "--no-probe"],
-    );
-    let options = observer_cli_options(&values, &switches, true);
-    let created_key = ensure_default_node_key(&options.key_spec);
-    let registration = observer_registration_from_options(&options);
-    write_json_file(&options.output, &registration, "observer registration");
-    let diagnosis = diagnose_observer(&options);
-    if options.json {
+fn cmd_node_genesis(args: Vec<String>) {
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        println!("Usage: julian node genesis [genesis.json]");
         println!(
-            "{}",
-            serde_json::to_string_pretty(&serde_json::json!({
-                "schema": "power-house-observer-setup-v1",
-                "created_node_key": created_key.as_ref().map(|path| path.display().to_string()),
-                "registration_output": options.output,
-                "diagnosis": diagnosis,
-                "start_command": observer_start_command(&options),
-            }))
-            .unwrap_or_else(|err| fatal(&format!("failed to encode setup JSON: {err}")))
+            "  Prints the network id, chain id and genesis digest (active genesis by default)."
         );
-    } else {
-        if let Some(path) = created_key {
-            println!("created node key: {}", path.display());
-        }
-        println!("observer registration: {}", options.output.display());
-        println!();
-        println!("Start the observer with:");
-        println!("{}", observer_start_command(&options));
-        println!();
-        print_observer_diagnosis(&diagnosis, false);
-        println!();
-        println!("Submit/check the signed JSON at https://mfenx.com/register.html");
+        return;
     }
+    let config = match args.first() {
+        Some(path) => {
+            power_house::GenesisConfig::load(Path::new(path)).unwrap_or_else(|err| fatal(&err))
+        }
+        None => power_house::active_genesis().clone(),
+    };
+    println!("network_id: {}", config.network_id);
+    println!("chain_id: {}", config.chain_id);
+    println!("genesis_statement: {}", config.genesis_statement);
+    println!("allocations: {}", config.allocations.len());
+    println!("validators: {}", config.validators.len());
+    println!("genesis_digest: {}", hex::encode(config.digest()));
 }
 
-#[cfg(feature = "net")]
-fn cmd_observer_register(args: Vec<String>) {
-    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
-        print_observer_help();
-        return;
-    }
-    let (values, switches) = parse_observer_options(
-        args,
-        &[
-            "--node-id",
-            "--key",
-            "--operator",
-            "--region",
-            "--public-host",
-            "--p2p-port",
-            "--metrics-port",
-            "--output",
-        ],
-        &["--json"],
-    );
-    let options = observer_cli_options(&values, &switches, true);
-    let registration = observer_registration_from_options(&options);
-    write_json_file(&options.output, &registration, "observer registration");
-    if options.json {
-        println!(
-            "{}",
-            serde_json::to_string_pretty(&registration)
-                .unwrap_or_else(|err| fatal(&format!("failed to encode registration: {err}")))
-        );
-    } else {
-        print_registration_summary(
-            "observer",
-            &options.output,
-            &registration.node_id,
-            &registration.peer_id,
-            &registration.p2p_address,
-            &registration.metrics_url,
-        );
-        println!("next: run `julian observer doctor` or upload the signed JSON at https://mfenx.com/register.html");
+fn handle_node(sub: &str, tail: Vec<String>) {
+    match sub {
+        "-h" | "--help" => print_node_help(),
+        "run" => cmd_node_run(tail),
+        "anchor" => cmd_node_anchor(tail),
+        "fsck" => cmd_node_fsck(tail),
+        #[cfg(feature = "net")]
+        "archive" => cmd_node_archive(tail),
+        #[cfg(feature = "net")]
+        "gc" => cmd_node_gc(tail),
+        "reconcile" => cmd_node_reconcile(tail),
+        "prove" => cmd_node_prove(tail),
+        "verify-proof" => cmd_node_verify_proof(tail),
+        "genesis" => cmd_node_genesis(tail),
+        _ => {
+            eprintln!("Unknown subcommand: {}", sub);
+            exit(1);
+        }
     }
 }
 
-#[cfg(feature = "net")]
-fn cmd_observer_submit(args: Vec<String>) {
+fn cmd_scale_sumcheck(args: Vec<String>) {
     if args.iter().any(|arg| arg == "-h" || arg == "--help") {
-        print_observer_help();
+        print_scale_help();
         return;
     }
-    let mut input = None;
-    let mut output = None;
-    let mut json = false;
-    let mut probe_url = Some("https://rpc.mfenx.com/observer-probe".to_string());
-    let mut intake_url = Some("https://rpc.mfenx.com/observer-registrations".to_string());
+
+    let mut max_vars: Option<usize> = None;
     let mut iter = args.into_iter();
     while let Some(arg) = iter.next() {
         match arg.as_str() {
-            "--output" => {
-                output = Some(PathBuf::from(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--output expects a value")),
-                ))
-            }
-            "--probe-url" => {
-                probe_url = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--probe-url expects a value")),
-                )
+            "--vars" | "--max-vars" => {
+                let value = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--vars expects a value"));
+                max_vars = Some(
+                    value
+                        .parse()
+                        .unwrap_or_else(|_| fatal("invalid --vars value")),
+                );
             }
-            "--intake-url" => {
-                intake_url = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--intake-url expects a value")),
-                )
-            }
-            "--no-probe" => probe_url = None,
-            "--no-upload" => intake_url = None,
-            "--json" => json = true,
-            value if !value.starts_with("--") && input.is_none() => {
-                input = Some(PathBuf::from(value))
-            }
-            value => fatal(&format!("unknown argument: {value}")),
+            other => fatal(&format!("unknown argument: {other}")),
         }
     }
-    let input = input.unwrap_or_else(|| fatal("submit requires <observer-registration.json>"));
-    let registration = read_observer_registration_or_package(&input);
-    registration
-        .verify(177155, unix_seconds())
-        .unwrap_or_else(|err| fatal(&format!("observer registration verification failed: {err}")));
-    let probe = probe_url
-        .as_deref()
-        .and_then(|url| observer_probe_for_registration(url, &registration).ok());
-    let admission = intake_url.as_deref().map(|url| {
-        observer_intake_request("POST", url, Some(&registration))
-            .unwrap_or_else(|err| fatal(&format!("observer registration submission failed: {err}")))
-    });
-    let package = serde_json::json!({
-        "schema": "mfenx-node-registration-submission-v1",
-        "created_at_unix": unix_seconds(),
-        "registration_type": "observer",
-        "client_side_status": "ready",
-        "client_side_errors": [],
-        "client_side_warnings": [],
-        "probe": probe,
-        "registration": registration,
-        "admission": admission,
-    });
-    if let Some(path) = output {
-        write_json_file(&path, &package, "observer submission package");
-        if !json {
-            println!("submission package: {}", path.display());
-        }
+    run_scale_sumcheck(max_vars);
+}
+
+fn run_scale_sumcheck(max_vars: Option<usize>) {
+    let field = Field::new(257);
+    let default_dims = [8usize, 10, 12, 14, 16, 18];
+    let dimensions: Vec<usize> = match max_vars {
+        Some(m) => (8..=m).step_by(2).collect(),
+        None => default_dims.to_vec(),
+    };
+    if dimensions.is_empty() {
+        fatal("No dimensions selected; provide --vars >= 8.");
     }
-    if json {
+    let mut rows = Vec::new();
+    println!(
+        "{:>5} | {:>10} | {:>10} | {:>10} | {:>12} | {:>12}",
+        "vars", "2^vars", "total(ms)", "avg(ms)", "max_round(ms)", "final_eval"
+    );
+    println!("{}", "-".repeat(70));
+    for &vars in &dimensions {
+        let evaluator = make_scale_evaluator(vars, field.modulus());
+        let (proof, stats) = GeneralSumProof::prove_streaming_with_stats(vars, &field, evaluator);
+        let (total_ms, avg_ms, max_round_ms) = summarize_stats(&stats);
+        let size = 1usize << vars;
+        rows.push((
+            vars,
+            size,
+            total_ms,
+            avg_ms,
+            max_round_ms,
+            proof.final_evaluation,
+        ));
         println!(
-            "{}",
-            serde_json::to_string_pretty(&package)
-                .unwrap_or_else(|err| fatal(&format!("failed to encode package: {err}")))
+            "{:>5} | {:>10} | {:>10.3} | {:>10.3} | {:>12.3} | {:>12}",
+            vars, size, total_ms, avg_ms, max_round_ms, proof.final_evaluation
         );
-    } else {
-        println!("observer registration verified locally");
-        if !package["probe"].is_null() {
-            println!("external probe: {}", package["probe"]);
-        } else {
-            println!("external probe: skipped or unavailable");
+    }
+
+    if let Ok(path) = std::env::var("POWER_HOUSE_SCALE_OUT") {
+        let mut file = fs::File::create(&path).expect("create csv output");
+        use std::io::Write;
+        writeln!(
+            file,
+            "vars,size,total_ms,avg_ms,max_round_ms,final_evaluation"
+        )
+        .expect("write csv header");
+        for (vars, size, total_ms, avg_ms, max_round_ms, final_eval) in rows {
+            writeln!(
+                file,
+                "{vars},{size},{total_ms:.6},{avg_ms:.6},{max_round_ms:.6},{final_eval}"
+            )
+            .expect("write csv row");
         }
-        if let Some(admission) = package.get("admission").filter(|value| !value.is_null()) {
-            println!(
-                "tracking id: {}",
-                admission["tracking_id"].as_str().unwrap_or("unavailable")
-            );
-            println!(
-                "admission status: {}",
-                admission["status"].as_str().unwrap_or("unknown")
-            );
-            println!(
-                "next: julian observer status {}",
-                admission["tracking_id"].as_str().unwrap_or("<tracking-id>")
-            );
-        } else {
-            println!("upload skipped; package is ready for https://mfenx.com/register.html");
+        println!("CSV exported to {path}");
+    }
+}
+
+fn make_scale_evaluator(
+    num_vars: usize,
+    modulus: u64,
+) -> impl Fn(usize) -> u64 + Send + Sync + 'static {
+    move |idx: usize| {
+        let mut acc = (idx as u64) % modulus;
+        for bit in 0..num_vars {
+            let bit_value = ((idx >> bit) & 1) as u64;
+            if bit_value == 0 {
+                continue;
+            }
+            let coef = ((bit as u64 + 3).pow(2)) % modulus;
+            acc = (acc + coef) % modulus;
+        }
+        for bit in 0..num_vars.saturating_sub(1) {
+            let a = ((idx >> bit) & 1) as u64;
+            let b = ((idx >> (bit + 1)) & 1) as u64;
+            if a == 0 || b == 0 {
+                continue;
+            }
+            let coef = (17 + (bit as u64 * 5)) % modulus;
+            acc = (acc + coef) % modulus;
+        }
+        if num_vars >= 3 {
+            let a = (idx & 1) as u64;
+            let b = ((idx >> 1) & 1) as u64;
+            let c = ((idx >> 2) & 1) as u64;
+            if a == 1 && b == 1 && c == 1 {
+                acc = (acc + 29) % modulus;
+            }
         }
+        acc % modulus
+    }
+}
+
+fn ms(duration: &std::time::Duration) -> f64 {
+    duration.as_secs_f64() * 1_000.0
+}
+
+fn summarize_stats(stats: &ProofStats) -> (f64, f64, f64) {
+    if stats.round_durations.is_empty() {
+        return (0.0, 0.0, 0.0);
     }
+    let total = ms(&stats.total_duration);
+    let max = stats.round_durations.iter().map(ms).fold(0.0f64, f64::max);
+    let mean = total / (stats.round_durations.len() as f64);
+    (total, mean, max)
 }
 
 #[cfg(feature = "net")]
-fn cmd_observer_status(args: Vec<String>) {
+fn handle_net(sub: &str, tail: Vec<String>) {
+    match sub {
+        "-h" | "--help" => print_net_help(),
+        "start" => cmd_net_start(tail),
+        "anchor" => cmd_net_anchor(tail),
+        "verify-envelope" => cmd_net_verify_envelope(tail),
+        "ship-logs" => cmd_net_ship_logs(tail),
+        "pin-anchor" => cmd_net_pin_anchor(tail),
+        "log-aggregator" => cmd_net_log_aggregator(tail),
+        #[cfg(feature = "grpc")]
+        "grpc" => cmd_net_grpc(tail),
+        #[cfg(not(feature = "grpc"))]
+        "grpc" => fatal("julian was built without the `grpc` feature"),
+        "attestations" => cmd_net_attestations(tail),
+        "direct" => cmd_net_direct(tail),
+        "block-archive" => cmd_net_block_archive(tail),
+        "resolve-fork" => cmd_net_resolve_fork(tail),
+        "proposal" => cmd_net_proposal(tail),
+        "join-request" => cmd_net_join_request(tail),
+        "verify-rpc-state" => cmd_net_verify_rpc_state(tail),
+        "rpc-replica" => cmd_net_rpc_replica(tail),
+        "dev" => cmd_net_dev(tail),
+        _ => {
+            eprintln!("Unknown net subcommand: {sub}");
+            exit(1);
+        }
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_keygen(args: Vec<String>) {
     if args.iter().any(|arg| arg == "-h" || arg == "--help") {
-        print_observer_help();
+        print_keygen_help();
         return;
     }
-    let mut tracking_id = None;
-    let mut intake_url = "https://rpc.mfenx.com/observer-registrations".to_string();
-    let mut json = false;
+
+    let mut key_spec: Option<String> = None;
+    let mut out_path: Option<PathBuf> = None;
     let mut iter = args.into_iter();
     while let Some(arg) = iter.next() {
         match arg.as_str() {
-            "--intake-url" => {
-                intake_url = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--intake-url expects a value"))
+            "--out" => {
+                out_path = Some(PathBuf::from(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--out expects a value")),
+                ));
             }
-            "--json" => json = true,
-            value if !value.starts_with("--") && tracking_id.is_none() => {
-                tracking_id = Some(value.to_string())
+            value => {
+                if key_spec.is_none() {
+                    key_spec = Some(value.to_string());
+                } else {
+                    fatal(&format!("unknown argument: {value}"));
+                }
             }
+        }
+    }
+
+    let key_source = Ed25519KeySource::from_spec(key_spec.as_deref());
+    let passphrase = prompt_password("Identity passphrase: ")
+        .unwrap_or_else(|err| fatal(&format!("failed to read passphrase: {err}")));
+    let material = load_or_derive_keypair(&key_source)
+        .unwrap_or_else(|err| fatal(&format!("failed to derive key: {err}")));
+    let encoded = encrypt_identity_base64(&material.signing, &passphrase);
+    let out_path = out_path.unwrap_or_else(|| PathBuf::from("julian.identity"));
+    if let Some(parent) = out_path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    fs::write(&out_path, format!("{encoded}\n"))
+        .unwrap_or_else(|err| fatal(&format!("failed to write identity: {err}")));
+    println!(
+        "public_key_b64: {}",
+        power_house::net::encode_public_key_base64(&material.verifying)
+    );
+    println!("identity_path: {}", out_path.display());
+}
+
+#[cfg(feature = "net")]
+fn cmd_key_info(args: Vec<String>) {
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        print_key_info_help();
+        return;
+    }
+
+    let mut key_spec: Option<String> = None;
+    let mut json = false;
+    for arg in args {
+        match arg.as_str() {
+            "--json" => json = true,
+            value if key_spec.is_none() => key_spec = Some(value.to_string()),
             value => fatal(&format!("unknown argument: {value}")),
         }
     }
-    let tracking_id = tracking_id.unwrap_or_else(|| fatal("status requires <tracking-id>"));
-    let url = format!("{}/{}", intake_url.trim_end_matches('/'), tracking_id);
-    let status = observer_intake_request::<serde_json::Value>("GET", &url, None)
-        .unwrap_or_else(|err| fatal(&format!("observer admission status failed: {err}")));
+    let key_spec = key_spec.unwrap_or_else(|| fatal("key-info requires a key specification"));
+    let material = load_or_derive_keypair(&Ed25519KeySource::from_spec(Some(&key_spec)))
+        .unwrap_or_else(|err| fatal(&format!("failed to load key: {err}")));
+    let public_key = power_house::net::encode_public_key_base64(&material.verifying);
+    let peer_id = material.libp2p.public().to_peer_id().to_string();
+
     if json {
         println!(
             "{}",
-            serde_json::to_string_pretty(&status)
-                .unwrap_or_else(|err| fatal(&format!("failed to encode status: {err}")))
+            serde_json::json!({
+                "peer_id": peer_id,
+                "public_key_b64": public_key,
+            })
         );
     } else {
-        println!("tracking id: {tracking_id}");
-        println!(
-            "admission status: {}",
-            status["status"].as_str().unwrap_or("unknown")
-        );
-        if let Some(revision) = status["registry_revision"].as_str() {
-            println!("registry revision: {revision}");
-        }
-        if let Some(message) = status
-            .pointer("/error/message")
-            .and_then(|value| value.as_str())
-        {
-            println!("error: {message}");
-        }
+        println!("public_key_b64: {public_key}");
+        println!("peer_id: {peer_id}");
     }
 }
 
 #[cfg(feature = "net")]
-fn parse_observer_options(
-    args: Vec<String>,
-    value_flags: &[&str],
-    switches: &[&str],
-) -> (HashMap<String, String>, HashSet<String>) {
-    let mut values = HashMap::new();
-    let mut present = HashSet::new();
+fn handle_receipt(sub: &str, tail: Vec<String>) {
+    match sub {
+        "-h" | "--help" => print_receipt_help(),
+        "verify" => cmd_receipt_verify(tail),
+        _ => fatal(&format!("unknown receipt subcommand: {sub}")),
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_receipt_verify(args: Vec<String>) {
+    let mut path = None;
+    let mut trusted_keys = Vec::new();
+    let mut inputs = Vec::new();
     let mut iter = args.into_iter();
     while let Some(arg) = iter.next() {
-        if switches.contains(&arg.as_str()) {
-            present.insert(arg);
-            continue;
-        }
-        if !value_flags.contains(&arg.as_str()) {
-            fatal(&format!("unknown argument: {arg}"));
+        match arg.as_str() {
+            "--trusted-key" => trusted_keys.push(
+                iter.next()
+                    .unwrap_or_else(|| fatal("--trusted-key expects a value")),
+            ),
+            "--input" => {
+                let spec = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--input expects <name>=<path>"));
+                let (name, file) = spec
+                    .split_once('=')
+                    .unwrap_or_else(|| fatal("--input expects <name>=<path>"));
+                inputs.push((name.to_string(), PathBuf::from(file)));
+            }
+            "-h" | "--help" => {
+                print_receipt_help();
+                return;
+            }
+            other if other.starts_with("--") => fatal(&format!("unknown argument: {other}")),
+            other if path.is_none() => path = Some(PathBuf::from(other)),
+            other => fatal(&format!("unexpected positional argument: {other}")),
+        }
+    }
+    let path = path.unwrap_or_else(|| fatal("receipt verify requires a receipt file"));
+    let contents = fs::read_to_string(&path)
+        .unwrap_or_else(|err| fatal(&format!("FAIL: failed to read receipt: {err}")));
+    let receipt: CheckReceipt = serde_json::from_str(&contents)
+        .unwrap_or_else(|err| fatal(&format!("FAIL: invalid receipt JSON: {err}")));
+    receipt
+        .verify(&trusted_keys)
+        .unwrap_or_else(|err| fatal(&format!("FAIL: {err}")));
+    for (name, file) in &inputs {
+        let digest = digest_input_path(file).unwrap_or_else(|err| fatal(&format!("FAIL: {err}")));
+        match receipt.inputs.get(name) {
+            Some(recorded) if *recorded == digest => {}
+            Some(_) => fatal(&format!(
+                "FAIL: input {name} does not match {}",
+                file.display()
+            )),
+            None => fatal(&format!("FAIL: receipt has no input named {name}")),
         }
-        let value = iter
-            .next()
-            .unwrap_or_else(|| fatal(&format!("{arg} expects a value")));
-        if values.insert(arg.clone(), value).is_some() {
-            fatal(&format!("duplicate argument: {arg}"));
+    }
+    println!(
+        "PASS: {} receipt signed by {} ({})",
+        receipt.command,
+        receipt.public_key,
+        if receipt.passed {
+            "check passed"
+        } else {
+            "check failed"
         }
+    );
+    println!("result: {}", receipt.result);
+    println!("issued_ms: {}", receipt.issued_ms);
+    for (name, value) in &receipt.inputs {
+        println!("  input {name} {value}");
     }
-    (values, present)
 }
 
 #[cfg(feature = "net")]
-fn observer_cli_options(
-    values: &HashMap<String, String>,
-    switches: &HashSet<String>,
-    require_public_host: bool,
-) -> ObserverCliOptions {
-    let node_id = values
-        .get("--node-id")
-        .cloned()
-        .unwrap_or_else(|| "mynode".to_string());
-    let operator = values
-        .get("--operator")
-        .cloned()
-        .unwrap_or_else(|| node_id.clone());
-    let region = values
-        .get("--region")
-        .cloned()
-        .unwrap_or_else(|| "self-hosted".to_string());
-    let key_spec = values
-        .get("--key")
-        .cloned()
-        .unwrap_or_else(default_node_key_spec);
-    let p2p_port = parse_port_option(values.get("--p2p-port"), 7001, "--p2p-port");
-    let metrics_port = parse_port_option(values.get("--metrics-port"), 9102, "--metrics-port");
-    let public_host = values
-        .get("--public-host")
-        .map(|host| normalize_public_host(host))
-        .or_else(|| detect_public_host().ok());
-    if require_public_host && public_host.is_none() {
-        fatal("--public-host is required because public IP auto-detection failed");
-    }
-    let probe_url = if switches.contains("--no-probe") {
-        None
-    } else {
-        Some(
-            values
-                .get("--probe-url")
-                .cloned()
-                .unwrap_or_else(|| "https://rpc.mfenx.com/observer-probe".to_string()),
-        )
-    };
-    let output = values
-        .get("--output")
-        .map(PathBuf::from)
-        .unwrap_or_else(|| default_registration_output(&node_id, "observer"));
-    ObserverCliOptions {
-        node_id,
-        operator,
-        region,
-        key_spec,
-        p2p_port,
-        metrics_port,
-        public_host,
-        probe_url,
-        output,
-        json: switches.contains("--json"),
+fn handle_key(sub: &str, tail: Vec<String>) {
+    match sub {
+        "-h" | "--help" => print_key_help(),
+        "mnemonic" => cmd_key_mnemonic(tail),
+        "from-mnemonic" => cmd_key_from_mnemonic(tail),
+        #[cfg(feature = "keychain")]
+        "keychain-store" => cmd_key_keychain_store(tail),
+        #[cfg(not(feature = "keychain"))]
+        "keychain-store" => fatal("julian was built without the `keychain` feature"),
+        _ => fatal(&format!("unknown key subcommand: {sub}")),
     }
 }
 
 #[cfg(feature = "net")]
-fn observer_registration_from_options(options: &ObserverCliOptions) -> ObserverRegistration {
-    let mut values = HashMap::new();
-    values.insert("--key".to_string(), options.key_spec.clone());
-    values.insert("--node-id".to_string(), options.node_id.clone());
-    values.insert("--operator".to_string(), options.operator.clone());
-    values.insert("--region".to_string(), options.region.clone());
-    values.insert(
-        "--public-host".to_string(),
-        options
-            .public_host
-            .clone()
-            .unwrap_or_else(|| fatal("public host is required")),
-    );
-    values.insert("--p2p-port".to_string(), options.p2p_port.to_string());
-    values.insert(
-        "--metrics-port".to_string(),
-        options.metrics_port.to_string(),
-    );
-    build_observer_registration(&values)
+fn cmd_key_mnemonic(args: Vec<String>) {
+    let mut words = 24;
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--words" => {
+                words = iter
+                    .next()
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or_else(|| fatal("--words expects 12, 15, 18, 21, or 24"));
+            }
+            "-h" | "--help" => {
+                print_key_help();
+                return;
+            }
+            other => fatal(&format!("unknown argument: {other}")),
+        }
+    }
+    let mnemonic = generate_mnemonic(words).unwrap_or_else(|err| fatal(&err.to_string()));
+    println!("{mnemonic}");
 }
 
 #[cfg(feature = "net")]
-fn diagnose_observer(options: &ObserverCliOptions) -> ObserverDiagnosis {
-    let mut checks = Vec::new();
-    let local_ip = local_ipv4_guess();
-    let key_result = load_or_derive_keypair(&Ed25519KeySource::from_spec(Some(&options.key_spec)));
-    let (peer_id, public_key_b64) = match key_result {
-        Ok(material) => {
-            let public_key = power_house::net::encode_public_key_base64(&material.verifying);
-            let peer_id = material.libp2p.public().to_peer_id().to_string();
-            checks.push(observer_check(
-                "node key",
-                "OK",
-                format!("loaded identity {peer_id}"),
-                None,
-            ));
-            (Some(peer_id), Some(public_key))
-        }
-        Err(err) => {
-            checks.push(observer_check(
-                "node key",
-                "FAIL",
-                format!("failed to load {}: {err}", options.key_spec),
-                Some("run `julian observer setup` to create the default key".to_string()),
-            ));
-            (None, None)
-        }
-    };
-    checks.push(observer_check(
-        "local ip",
-        if local_ip.is_some() { "OK" } else { "WARN" },
-        local_ip
-            .clone()
-            .unwrap_or_else(|| "could not infer local LAN IP".to_string()),
-        None,
-    ));
-    checks.push(port_check("p2p port", options.p2p_port));
-    checks.push(port_check("metrics port", options.metrics_port));
-    match fetch_local_metrics(options.metrics_port) {
-        Ok(metrics) => {
-            if let (Some(peer_id), Some(public_key)) = (&peer_id, &public_key_b64) {
-                if metrics_contains_identity(&metrics, &options.node_id, peer_id, public_key) {
-                    checks.push(observer_check(
-                        "local metrics identity",
-                        "OK",
-                        "metrics exposes matching node_id, peer_id, public key, and chain"
-                            .to_string(),
-                        None,
-                    ));
-                } else {
-                    checks.push(observer_check(
-                        "local metrics identity",
-                        "FAIL",
-                        "metrics endpoint is reachable but does not match this node identity".to_string(),
-                        Some("restart the observer with the same --node-id and --key used for registration".to_string()),
-                    ));
-                }
-            } else {
-                checks.push(observer_check(
-                    "local metrics",
-                    "OK",
-                    "metrics endpoint is reachable".to_string(),
-                    None,
+fn cmd_key_from_mnemonic(args: Vec<String>) {
+    let mut out_path: Option<PathBuf> = None;
+    let mut mnemonic_file: Option<PathBuf> = None;
+    let mut path = DEFAULT_MNEMONIC_PATH.to_string();
+    let mut with_passphrase = false;
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--out" => {
+                out_path = Some(PathBuf::from(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--out expects a value")),
+                ));
+            }
+            "--mnemonic-file" => {
+                mnemonic_file = Some(PathBuf::from(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--mnemonic-file expects a value")),
                 ));
             }
+            "--path" => {
+                path = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--path expects a derivation path"));
+            }
+            "--passphrase" => with_passphrase = true,
+            "-h" | "--help" => {
+                print_key_help();
+                return;
+            }
+            other => fatal(&format!("unknown argument: {other}")),
         }
-        Err(err) => checks.push(observer_check(
-            "local metrics",
-            "WARN",
-            format!("not reachable at 127.0.0.1:{}: {err}", options.metrics_port),
-            Some(observer_start_command(options)),
-        )),
     }
-    if let (Some(host), Some(probe_url)) = (&options.public_host, &options.probe_url) {
-        match fetch_observer_probe(probe_url, host, options.metrics_port, options.p2p_port) {
-            Ok(probe) => add_probe_checks(&mut checks, &probe),
-            Err(err) => checks.push(observer_check(
-                "external probe",
-                "WARN",
-                format!("probe unavailable: {err}"),
-                Some("verify from phone cellular data or retry with --probe-url".to_string()),
-            )),
-        }
-    } else {
-        checks.push(observer_check(
-            "external probe",
-            "WARN",
-            "public host or probe URL unavailable".to_string(),
-            Some(
-                "pass --public-host <public-ip-or-dns> or run after internet access is available"
-                    .to_string(),
-            ),
-        ));
+    let out_path = out_path.unwrap_or_else(|| fatal("from-mnemonic requires --out <key-file>"));
+    if out_path.exists() {
+        fatal(&format!("refusing to overwrite {}", out_path.display()));
     }
-    ObserverDiagnosis {
-        schema: "power-house-observer-doctor-v1".to_string(),
-        node_id: options.node_id.clone(),
-        key_spec: options.key_spec.clone(),
-        local_ip,
-        public_host: options.public_host.clone(),
-        p2p_port: options.p2p_port,
-        metrics_port: options.metrics_port,
-        peer_id,
-        public_key_b64,
-        checks,
+    let mnemonic = match mnemonic_file {
+        Some(file) => fs::read_to_string(&file)
+            .unwrap_or_else(|err| fatal(&format!("failed to read {}: {err}", file.display()))),
+        None => prompt_password("Mnemonic: ")
+            .unwrap_or_else(|err| fatal(&format!("failed to read mnemonic: {err}"))),
+    };
+    let passphrase = if with_passphrase {
+        prompt_password("Mnemonic passphrase: ")
+            .unwrap_or_else(|err| fatal(&format!("failed to read passphrase: {err}")))
+    } else {
+        String::new()
+    };
+    let material = derive_mnemonic_key(&mnemonic, &passphrase, &path)
+        .unwrap_or_else(|err| fatal(&format!("failed to derive key: {err}")));
+    if let Some(parent) = out_path.parent() {
+        fs::create_dir_all(parent)
+            .unwrap_or_else(|err| fatal(&format!("failed to create {}: {err}", parent.display())));
     }
-}
-
-#[cfg(feature = "net")]
-fn observer_check(
-    name: impl Into<String>,
-    status: impl Into<String>,
-    detail: impl Into<String>,
-    fix: Option<String>,
-) -> ObserverCheck {
-    ObserverCheck {
-        name: name.into(),
-        status: status.into(),
-        detail: detail.into(),
-        fix,
+    fs::write(
+        &out_path,
+        mnemonic_key_file(&material.signing, &path, with_passphrase),
+    )
+    .unwrap_or_else(|err| fatal(&format!("failed to write key: {err}")));
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&out_path, fs::Permissions::from_mode(0o600)).unwrap_or_else(|err| {
+            fatal(&format!(
+                "failed to set key permissions {}: {err}",
+                out_path.display()
+            ))
+        });
     }
+    println!(
+        "public_key_b64: {}",
+        power_house::net::encode_public_key_base64(&material.verifying)
+    );
+    println!("peer_id: {}", material.libp2p.public().to_peer_id());
+    println!("derivation_path: {path}");
+    println!("key_path: {}", out_path.display());
 }
 
-#[cfg(feature = "net")]
-fn port_check(name: &str, port: u16) -> ObserverCheck {
-    match TcpListener::bind(("0.0.0.0", port)) {
-        Ok(listener) => {
-            drop(listener);
-            observer_check(
-                name,
-                "OK",
-                format!("tcp/{port} is available"),
-                None,
-            )
+#[cfg(feature = "keychain")]
+fn cmd_key_keychain_store(args: Vec<String>) {
+    let mut identity: Option<PathBuf> = None;
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--identity" => {
+                identity = Some(PathBuf::from(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--identity expects a value")),
+                ));
+            }
+            other => fatal(&format!("unknown argument: {other}")),
         }
-        Err(err) => observer_check(
-            name,
-            "INFO",
-            format!("tcp/{port} is already in use: {err}"),
-            Some("this is expected when the observer is already running; otherwise choose another port".to_string()),
-        ),
     }
+    let identity = identity.unwrap_or_else(|| fatal("keychain-store requires --identity <file>"));
+    let passphrase = prompt_password("Identity passphrase: ")
+        .unwrap_or_else(|err| fatal(&format!("failed to read passphrase: {err}")));
+    let material = load_encrypted_identity(&identity, &passphrase)
+        .unwrap_or_else(|err| fatal(&format!("failed to load identity: {err}")));
+    let account = keychain_account(&identity);
+    power_house::net::keychain_store(&account, &passphrase)
+        .unwrap_or_else(|err| fatal(&err.to_string()));
+    println!(
+        "public_key_b64: {}",
+        power_house::net::encode_public_key_base64(&material.verifying)
+    );
+    println!(
+        "keychain_entry: {}/{account}",
+        power_house::net::KEYCHAIN_SERVICE
+    );
 }
 
 #[cfg(feature = "net")]
-fn print_observer_diagnosis(diagnosis: &ObserverDiagnosis, json: bool) {
-    if json {
-        println!(
-            "{}",
-            serde_json::to_string_pretty(diagnosis)
-                .unwrap_or_else(|err| fatal(&format!("failed to encode diagnosis: {err}")))
-        );
-        return;
-    }
-    println!("Power House Observer Doctor");
-    println!("node_id: {}", diagnosis.node_id);
-    if let Some(host) = &diagnosis.public_host {
-        println!("public_host: {host}");
-    }
-    for check in &diagnosis.checks {
-        println!("[{}] {}: {}", check.status, check.name, check.detail);
-        if let Some(fix) = &check.fix {
-            println!("    fix: {fix}");
-        }
+fn handle_observer(sub: &str, tail: Vec<String>) {
+    match sub {
+        "-h" | "--help" => print_observer_help(),
+        "doctor" => cmd_observer_doctor(tail),
+        "setup" => cmd_observer_setup(tail),
+        "register" => cmd_observer_register(tail),
+        "submit" => cmd_observer_submit(tail),
+        "status" => cmd_observer_status(tail),
+        _ => fatal(&format!("unknown observer subcommand: {sub}")),
     }
 }
 
 #[cfg(feature = "net")]
-fn ensure_default_node_key(key_spec: &str) -> Option<PathBuf> {
-    if key_spec.contains("://") {
-        return None;
-    }
-    let path = Path::new(key_spec);
-    if path.exists() {
-        return None;
-    }
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)
-            .unwrap_or_else(|err| fatal(&format!("failed to create {}: {err}", parent.display())));
-    }
-    let mut seed = [0u8; 32];
-    rand::rngs::OsRng.fill_bytes(&mut seed);
-    fs::write(path, seed)
-        .unwrap_or_else(|err| fatal(&format!("failed to write key {}: {err}", path.display())));
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        fs::set_permissions(path, fs::Permissions::from_mode(0o600)).unwrap_or_else(|err| {
-            fatal(&format!(
-                "failed to set key permissions {}: {err}",
-                path.display()
-            ))
-        });
-    }
-    Some(path.to_path_buf())
+#[derive(Debug, Clone)]
+struct ObserverCliOptions {
+    node_id: String,
+    operator: String,
+    region: String,
+    key_spec: String,
+    p2p_port: u16,
+    metrics_port: u16,
+    public_host: Option<String>,
+    probe_url: Option<String>,
+    output: PathBuf,
+    json: bool,
 }
 
 #[cfg(feature = "net")]
-fn observer_start_command(options: &ObserverCliOptions) -> String {
-    let bootstrap_lines = DEFAULT_OBSERVER_BOOTSTRAPS
-        .iter()
-        .map(|addr| format!("  --bootstrap {} \\", shell_word(addr)))
-        .collect::<Vec<_>>()
-        .join("\n");
-    let bootstrap_section = if bootstrap_lines.is_empty() {
-        String::new()
-    } else {
-        format!("{bootstrap_lines}\n")
-    };
-    format!(
-        "julian net start \\\n  --node-id {} \\\n  --log-dir ./logs/{}-observer \\\n  --blob-dir ./data/{}-observer \\\n  --listen /ip4/0.0.0.0/tcp/{} \\\n{}  --key \"{}\" \\\n  --metrics 0.0.0.0:{}",
-        shell_word(&options.node_id),
-        options.node_id,
-        options.node_id,
-        options.p2p_port,
-        bootstrap_section,
-        options.key_spec,
-        options.metrics_port
-    )
+#[derive(Debug, Clone, Serialize)]
+struct ObserverCheck {
+    name: String,
+    status: String,
+    detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fix: Option<String>,
 }
 
 #[cfg(feature = "net")]
-fn shell_word(value: &str) -> String {
-    if value
-        .chars()
-        .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-' | '.' | '/' | ':'))
-    {
-        value.to_string()
-    } else {
-        format!("'{}'", value.replace('\'', "'\\''"))
-    }
+#[derive(Debug, Clone, Serialize)]
+struct ObserverDiagnosis {
+    schema: String,
+    node_id: String,
+    key_spec: String,
+    local_ip: Option<String>,
+    public_host: Option<String>,
+    p2p_port: u16,
+    metrics_port: u16,
+    peer_id: Option<String>,
+    public_key_b64: Option<String>,
+    checks: Vec<ObserverCheck>,
 }
 
 #[cfg(feature = "net")]
-fn local_ipv4_guess() -> Option<String> {
-    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
-    socket.connect("1.1.1.1:80").ok()?;
-    match socket.local_addr().ok()?.ip() {
-        IpAddr::V4(value) => Some(value.to_string()),
-        IpAddr::V6(_) => None,
+fn cmd_observer_doctor(args: Vec<String>) {
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        print_observer_help();
+        return;
     }
+    let (values, switches) = parse_observer_options(
+        args,
+        &[
+            "--node-id",
+            "--key",
+            "--operator",
+            "--region",
+            "--public-host",
+            "--p2p-port",
+            "--metrics-port",
+            "--probe-url",
+        ],
+        &["--json", "--no-probe"],
+    );
+    let options = observer_cli_options(&values, &switches, false);
+    let diagnosis = diagnose_observer(&options);
+    print_observer_diagnosis(&diagnosis, options.json);
 }
 
 #[cfg(feature = "net")]
-fn detect_public_host() -> Result<String, String> {
-    let text = https_get_text("https://api.ipify.org", 4)?;
-    Ok(normalize_public_host(text.trim()))
+fn cmd_observer_setup(args: Vec<String>) {
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        print_observer_help();
+        return;
+    }
+    let (values, switches) = parse_observer_options(
+        args,
+        &[
+            "--node-id",
+            "--key",
+            "--operator",
+            "--region",
+            "--public-host",
+            "--p2p-port",
+            "--metrics-port",
+            "--probe-url",
+            "--output",
+        ],
+        &["--json", "--no-probe"],
+    );
+    let options = observer_cli_options(&values, &switches, true);
+    let created_key = ensure_default_node_key(&options.key_spec);
+    let registration = observer_registration_from_options(&options);
+    write_json_file(&options.output, &registration, "observer registration");
+    let diagnosis = diagnose_observer(&options);
+    if options.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "schema": "power-house-observer-setup-v1",
+                "created_node_key": created_key.as_ref().map(|path| path.display().to_string()),
+                "registration_output": options.output,
+                "diagnosis": diagnosis,
+                "start_command": observer_start_command(&options),
+            }))
+            .unwrap_or_else(|err| fatal(&format!("failed to encode setup JSON: {err}")))
+        );
+    } else {
+        if let Some(path) = created_key {
+            println!("created node key: {}", path.display());
+        }
+        println!("observer registration: {}", options.output.display());
+        println!();
+        println!("Start the observer with:");
+        println!("{}", observer_start_command(&options));
+        println!();
+        print_observer_diagnosis(&diagnosis, false);
+        println!();
+        println!("Submit/check the signed JSON at https://mfenx.com/register.html");
+    }
 }
 
 #[cfg(feature = "net")]
-fn https_get_text(url: &str, timeout_secs: u64) -> Result<String, String> {
-    let runtime = tokio::runtime::Builder::new_current_thread()
-        .enable_all()
-        .build()
-        .map_err(|err| err.to_string())?;
-    runtime.block_on(async {
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(timeout_secs))
-            .redirect(reqwest::redirect::Policy::none())
-            .build()
-            .map_err(|err| err.to_string())?;
-        let response = client
-            .get(url)
-            .send()
-            .await
-            .map_err(|err| err.to_string())?
-            .error_for_status()
-            .map_err(|err| err.to_string())?;
-        response.text().await.map_err(|err| err.to_string())
-    })
+fn cmd_observer_register(args: Vec<String>) {
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        print_observer_help();
+        return;
+    }
+    let (values, switches) = parse_observer_options(
+        args,
+        &[
+            "--node-id",
+            "--key",
+            "--operator",
+            "--region",
+            "--public-host",
+            "--p2p-port",
+            "--metrics-port",
+            "--output",
+        ],
+        &["--json"],
+    );
+    let options = observer_cli_options(&values, &switches, true);
+    let registration = observer_registration_from_options(&options);
+    write_json_file(&options.output, &registration, "observer registration");
+    if options.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&registration)
+                .unwrap_or_else(|err| fatal(&format!("failed to encode registration: {err}")))
+        );
+    } else {
+        print_registration_summary(
+            "observer",
+            &options.output,
+            &registration.node_id,
+            &registration.peer_id,
+            &registration.p2p_address,
+            &registration.metrics_url,
+        );
+        println!("next: run `julian observer doctor` or upload the signed JSON at https://mfenx.com/register.html");
+    }
 }
 
 #[cfg(feature = "net")]
-fn observer_intake_request<T: Serialize>(
-    method: &str,
-    url: &str,
-    body: Option<&T>,
-) -> Result<serde_json::Value, String> {
-    let runtime = tokio::runtime::Builder::new_current_thread()
-        .enable_all()
-        .build()
-        .map_err(|err| err.to_string())?;
-    runtime.block_on(async {
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(15))
-            .redirect(reqwest::redirect::Policy::none())
-            .build()
-            .map_err(|err| err.to_string())?;
-        let method =
-            reqwest::Method::from_bytes(method.as_bytes()).map_err(|err| err.to_string())?;
-        let mut request = client.request(method, url);
-        if let Some(value) = body {
-            request = request.json(value);
+fn cmd_observer_submit(args: Vec<String>) {
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        print_observer_help();
+        return;
+    }
+    let mut input = None;
+    let mut output = None;
+    let mut json = false;
+    let mut probe_url = Some("https://rpc.mfenx.com/observer-probe".to_string());
+    let mut intake_url = Some("https://rpc.mfenx.com/observer-registrations".to_string());
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--output" => {
+                output = Some(PathBuf::from(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--output expects a value")),
+                ))
+            }
+            "--probe-url" => {
+                probe_url = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--probe-url expects a value")),
+                )
+            }
+            "--intake-url" => {
+                intake_url = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--intake-url expects a value")),
+                )
+            }
+            "--no-probe" => probe_url = None,
+            "--no-upload" => intake_url = None,
+            "--json" => json = true,
+            value if !value.starts_with("--") && input.is_none() => {
+                input = Some(PathBuf::from(value))
+            }
+            value => fatal(&format!("unknown argument: {value}")),
         }
-        let response = request.send().await.map_err(|err| err.to_string())?;
-        let status = response.status();
-        let text = response.text().await.map_err(|err| err.to_string())?;
-        let value: serde_json::Value =
-            serde_json::from_str(&text).map_err(|err| format!("invalid intake response: {err}"))?;
-        if !status.is_success() {
-            let message = value
-                .pointer("/error/message")
-                .and_then(|item| item.as_str())
-                .unwrap_or("registration intake rejected the request");
-            return Err(format!("HTTP {status}: {message}"));
+    }
+    let input = input.unwrap_or_else(|| fatal("submit requires <observer-registration.json>"));
+    let registration = read_observer_registration_or_package(&input);
+    registration
+        .verify(power_house::active_genesis().chain_id, unix_seconds())
+        .unwrap_or_else(|err| fatal(&format!("observer registration verification failed: {err}")));
+    let probe = probe_url
+        .as_deref()
+        .and_then(|url| observer_probe_for_registration(url, &registration).ok());
+    let admission = intake_url.as_deref().map(|url| {
+        observer_intake_request("POST", url, Some(&registration))
+            .unwrap_or_else(|err| fatal(&format!("observer registration submission failed: {err}")))
+    });
+    let package = serde_json::json!({
+        "schema": "mfenx-node-registration-submission-v1",
+        "created_at_unix": unix_seconds(),
+        "registration_type": "observer",
+        "client_side_status": "ready",
+        "client_side_errors": [],
+        "client_side_warnings": [],
+        "probe": probe,
+        "registration": registration,
+        "admission": admission,
+    });
+    if let Some(path) = output {
+        write_json_file(&path, &package, "observer submission package");
+        if !json {
+            println!("submission package: {}", path.display());
         }
-        Ok(value)
-    })
-}
-
-#[cfg(feature = "net")]
-fn fetch_local_metrics(port: u16) -> Result<String, String> {
-    let mut stream = TcpStream::connect(("127.0.0.1", port)).map_err(|err| err.to_string())?;
-    stream
-        .set_read_timeout(Some(Duration::from_secs(3)))
-        .map_err(|err| err.to_string())?;
-    stream
-        .set_write_timeout(Some(Duration::from_secs(3)))
-        .map_err(|err| err.to_string())?;
-    stream
-        .write_all(b"GET /metrics HTTP/1.1\r\nHost: 127.0.0.1\r\nConnection: close\r\n\r\n")
-        .map_err(|err| err.to_string())?;
-    let mut response = String::new();
-    stream
-        .read_to_string(&mut response)
-        .map_err(|err| err.to_string())?;
-    if !response.starts_with("HTTP/1.1 200") && !response.starts_with("HTTP/1.0 200") {
-        return Err(response.lines().next().unwrap_or("HTTP error").to_string());
     }
-    Ok(response
-        .split("\r\n\r\n")
-        .nth(1)
-        .unwrap_or(response.as_str())
-        .to_string())
-}
-
-#[cfg(feature = "net")]
-fn metrics_contains_identity(
-    body: &str,
-    node_id: &str,
-    peer_id: &str,
-    public_key_b64: &str,
-) -> bool {
-    body.lines().any(|line| {
-        line.starts_with("powerhouse_node_identity{")
-            && line.contains(&format!("node_id=\"{node_id}\""))
-            && line.contains(&format!("peer_id=\"{peer_id}\""))
-            && line.contains(&format!("public_key_b64=\"{public_key_b64}\""))
-            && line.contains("chain_id=\"177155\"")
-    })
-}
-
-#[cfg(feature = "net")]
-fn fetch_observer_probe(
-    probe_url: &str,
-    host: &str,
-    metrics_port: u16,
-    p2p_port: u16,
-) -> Result<serde_json::Value, String> {
-    let separator = if probe_url.contains('?') { '&' } else { '?' };
-    let url = format!(
-        "{probe_url}{separator}host={}&metrics_port={metrics_port}&p2p_port={p2p_port}",
-        percent_encode_query(host)
-    );
-    let text = https_get_text(&url, 8)?;
-    serde_json::from_str(&text).map_err(|err| err.to_string())
-}
-
-#[cfg(feature = "net")]
-fn percent_encode_query(value: &str) -> String {
-    let mut encoded = String::new();
-    for byte in value.bytes() {
-        if byte.is_ascii_alphanumeric() || matches!(byte, b'.' | b'-' | b'_') {
-            encoded.push(byte as char);
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&package)
+                .unwrap_or_else(|err| fatal(&format!("failed to encode package: {err}")))
+        );
+    } else {
+        println!("observer registration verified locally");
+        if !package["probe"].is_null() {
+            println!("external probe: {}", package["probe"]);
         } else {
-            encoded.push_str(&format!("%{byte:02X}"));
+            println!("external probe: skipped or unavailable");
+        }
+        if let Some(admission) = package.get("admission").filter(|value| !value.is_null()) {
+            println!(
+                "tracking id: {}",
+                admission["tracking_id"].as_str().unwrap_or("unavailable")
+            );
+            println!(
+                "admission status: {}",
+                admission["status"].as_str().unwrap_or("unknown")
+            );
+            println!(
+                "next: julian observer status {}",
+                admission["tracking_id"].as_str().unwrap_or("<tracking-id>")
+            );
+        } else {
+            println!("upload skipped; package is ready for https://mfenx.com/register.html");
         }
     }
-    encoded
 }
 
 #[cfg(feature = "net")]
-fn add_probe_checks(checks: &mut Vec<ObserverCheck>, probe: &serde_json::Value) {
-    let metrics_ok = probe
-        .pointer("/metrics/reachable")
-        .and_then(|value| value.as_bool())
-        .unwrap_or(false);
-    let identity_ok = probe
-        .get("registration_identity_matches")
-        .and_then(|value| value.as_bool())
-        .unwrap_or(false);
-    let p2p_ok = probe
-        .pointer("/p2p/reachable")
-        .and_then(|value| value.as_bool())
-        .unwrap_or(false);
-    checks.push(observer_check(
-        "external metrics",
-        if metrics_ok && identity_ok {
-            "OK"
-        } else {
-            "FAIL"
-        },
-        if metrics_ok && identity_ok {
-            "public metrics endpoint is reachable and exposes identity".to_string()
-        } else {
-            probe
-                .pointer("/metrics/error")
-                .and_then(|value| value.as_str())
-                .unwrap_or("metrics identity not reachable from external probe")
-                .to_string()
-        },
-        if metrics_ok && identity_ok {
-            None
-        } else {
-            Some(
-                "forward TCP metrics port to this machine and keep the observer running"
-                    .to_string(),
-            )
-        },
-    ));
-    checks.push(observer_check(
-        "external p2p",
-        if p2p_ok { "OK" } else { "FAIL" },
-        if p2p_ok {
-            "public p2p port accepts TCP connections".to_string()
-        } else {
-            probe
-                .pointer("/p2p/error")
-                .and_then(|value| value.as_str())
-                .unwrap_or("p2p port not reachable from external probe")
-                .to_string()
-        },
-        if p2p_ok {
-            None
-        } else {
-            Some("forward TCP p2p port to this machine".to_string())
-        },
-    ));
-}
-
-#[cfg(feature = "net")]
-fn read_observer_registration_or_package(path: &Path) -> ObserverRegistration {
-    let value: serde_json::Value = read_json_file(path, "observer registration package");
-    if value.get("schema").and_then(|schema| schema.as_str())
-        == Some("power-house-observer-registration-v1")
-    {
-        return serde_json::from_value(value)
-            .unwrap_or_else(|err| fatal(&format!("invalid observer registration: {err}")));
+fn cmd_observer_status(args: Vec<String>) {
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        print_observer_help();
+        return;
     }
-    if let Some(registration) = value.get("registration") {
-        return serde_json::from_value(registration.clone()).unwrap_or_else(|err| {
-            fatal(&format!("invalid packaged observer registration: {err}"))
-        });
+    let mut tracking_id = None;
+    let mut intake_url = "https://rpc.mfenx.com/observer-registrations".to_string();
+    let mut json = false;
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--intake-url" => {
+                intake_url = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--intake-url expects a value"))
+            }
+            "--json" => json = true,
+            value if !value.starts_with("--") && tracking_id.is_none() => {
+                tracking_id = Some(value.to_string())
+            }
+            value => fatal(&format!("unknown argument: {value}")),
+        }
+    }
+    let tracking_id = tracking_id.unwrap_or_else(|| fatal("status requires <tracking-id>"));
+    let url = format!("{}/{}", intake_url.trim_end_matches('/'), tracking_id);
+    let status = observer_intake_request::<serde_json::Value>("GET", &url, None)
+        .unwrap_or_else(|err| fatal(&format!("observer admission status failed: {err}")));
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&status)
+                .unwrap_or_else(|err| fatal(&format!("failed to encode status: {err}")))
+        );
+    } else {
+        println!("tracking id: {tracking_id}");
+        println!(
+            "admission status: {}",
+            status["status"].as_str().unwrap_or("unknown")
+        );
+        if let Some(revision) = status["registry_revision"].as_str() {
+            println!("registry revision: {revision}");
+        }
+        if let Some(message) = status
+            .pointer("/error/message")
+            .and_then(|value| value.as_str())
+        {
+            println!("error: {message}");
+        }
     }
-    fatal("input must be an observer registration or mfenx submission package")
-}
-
-#[cfg(feature = "net")]
-fn observer_probe_for_registration(
-    probe_url: &str,
-    registration: &ObserverRegistration,
-) -> Result<serde_json::Value, String> {
-    let host = metrics_host_from_url(&registration.metrics_url)?;
-    let metrics_port = metrics_port_from_url(&registration.metrics_url).unwrap_or(80);
-    let p2p_port = p2p_port_from_multiaddr(&registration.p2p_address).unwrap_or(7001);
-    let mut probe = fetch_observer_probe(probe_url, &host, metrics_port, p2p_port)?;
-    let identity = probe.pointer("/metrics/identity");
-    let identity_matches = identity.is_some_and(|value| {
-        value.get("node_id").and_then(|item| item.as_str()) == Some(&registration.node_id)
-            && value.get("peer_id").and_then(|item| item.as_str()) == Some(&registration.peer_id)
-            && value.get("public_key_b64").and_then(|item| item.as_str())
-                == Some(&registration.public_key_b64)
-            && value
-                .get("chain_id")
-                .and_then(|item| item.as_str())
-                .and_then(|item| item.parse::<u64>().ok())
-                == Some(registration.chain_id)
-    });
-    probe["registration_identity_matches"] = serde_json::Value::Bool(identity_matches);
-    let probe_ok = probe
-        .get("ok")
-        .and_then(|value| value.as_bool())
-        .unwrap_or(false);
-    probe["ok"] = serde_json::Value::Bool(probe_ok && identity_matches);
-    Ok(probe)
-}
-
-#[cfg(feature = "net")]
-fn metrics_host_from_url(url: &str) -> Result<String, String> {
-    let parsed = reqwest::Url::parse(url).map_err(|err| err.to_string())?;
-    parsed
-        .host_str()
-        .map(|host| host.to_string())
-        .ok_or_else(|| "metrics URL has no host".to_string())
-}
-
-#[cfg(feature = "net")]
-fn metrics_port_from_url(url: &str) -> Option<u16> {
-    reqwest::Url::parse(url)
-        .ok()
-        .and_then(|parsed| parsed.port_or_known_default())
 }
 
 #[cfg(feature = "net")]
-fn p2p_port_from_multiaddr(address: &str) -> Option<u16> {
-    let mut parts = address.split('/');
-    while let Some(part) = parts.next() {
-        if part == "tcp" {
-            return parts.next().and_then(|port| port.parse().ok());
+fn parse_observer_options(
+    args: Vec<String>,
+    value_flags: &[&str],
+    switches: &[&str],
+) -> (HashMap<String, String>, HashSet<String>) {
+    let mut values = HashMap::new();
+    let mut present = HashSet::new();
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        if switches.contains(&arg.as_str()) {
+            present.insert(arg);
+            continue;
+        }
+        if !value_flags.contains(&arg.as_str()) {
+            fatal(&format!("unknown argument: {arg}"));
+        }
+        let value = iter
+            .next()
+            .unwrap_or_else(|| fatal(&format!("{arg} expects a value")));
+        if values.insert(arg.clone(), value).is_some() {
+            fatal(&format!("duplicate argument: {arg}"));
         }
     }
-    None
+    (values, present)
 }
 
 #[cfg(feature = "net")]
-fn handle_validator_registry(sub: &str, tail: Vec<String>) {
-    match sub {
-        "-h" | "--help" => print_validator_registry_help(),
-        "register" => cmd_validator_registry_register(tail),
-        "create" => cmd_validator_registry_create(tail),
-        "assemble" => cmd_validator_registry_assemble(tail),
-        "verify" => cmd_validator_registry_verify(tail),
-        _ => fatal(&format!("unknown validator-registry subcommand: {sub}")),
+fn observer_cli_options(
+    values: &HashMap<String, String>,
+    switches: &HashSet<String>,
+    require_public_host: bool,
+) -> ObserverCliOptions {
+    let node_id = values
+        .get("--node-id")
+        .cloned()
+        .unwrap_or_else(|| "mynode".to_string());
+    let operator = values
+        .get("--operator")
+        .cloned()
+        .unwrap_or_else(|| node_id.clone());
+    let region = values
+        .get("--region")
+        .cloned()
+        .unwrap_or_else(|| "self-hosted".to_string());
+    let key_spec = values
+        .get("--key")
+        .cloned()
+        .unwrap_or_else(default_node_key_spec);
+    let p2p_port = parse_port_option(values.get("--p2p-port"), 7001, "--p2p-port");
+    let metrics_port = parse_port_option(values.get("--metrics-port"), 9102, "--metrics-port");
+    let public_host = values
+        .get("--public-host")
+        .map(|host| normalize_public_host(host))
+        .or_else(|| detect_public_host().ok());
+    if require_public_host && public_host.is_none() {
+        fatal("--public-host is required because public IP auto-detection failed");
+    }
+    let probe_url = if switches.contains("--no-probe") {
+        None
+    } else {
+        Some(
+            values
+                .get("--probe-url")
+                .cloned()
+                .unwrap_or_else(|| "https://rpc.mfenx.com/observer-probe".to_string()),
+        )
+    };
+    let output = values
+        .get("--output")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| default_registration_output(&node_id, "observer"));
+    ObserverCliOptions {
+        node_id,
+        operator,
+        region,
+        key_spec,
+        p2p_port,
+        metrics_port,
+        public_host,
+        probe_url,
+        output,
+        json: switches.contains("--json"),
     }
 }
 
 #[cfg(feature = "net")]
-fn cmd_validator_registry_register(args: Vec<String>) {
-    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
-        print_validator_registry_help();
-        return;
-    }
-    let values = parse_registry_options(
-        args,
-        &[
-            "--key",
-            "--node-id",
-            "--operator",
-            "--region",
-            "--public-host",
-            "--host",
-            "--p2p-port",
-            "--metrics-port",
-            "--p2p-address",
-            "--metrics-url",
-            "--system-metrics-port",
-            "--system-metrics-url",
-            "--chain-id",
-            "--issued-at",
-            "--valid-until",
-            "--output",
-            "--policy",
-            "--registry",
-            "--registry-output",
-        ],
+fn observer_registration_from_options(options: &ObserverCliOptions) -> ObserverRegistration {
+    let mut values = HashMap::new();
+    values.insert("--key".to_string(), options.key_spec.clone());
+    values.insert("--node-id".to_string(), options.node_id.clone());
+    values.insert("--operator".to_string(), options.operator.clone());
+    values.insert("--region".to_string(), options.region.clone());
+    values.insert(
+        "--public-host".to_string(),
+        options
+            .public_host
+            .clone()
+            .unwrap_or_else(|| fatal("public host is required")),
     );
-    let registration = build_validator_registration(&values);
-    let output = values
-        .get("--output")
-        .map(PathBuf::from)
-        .unwrap_or_else(|| default_registration_output(&registration.node_id, "validator"));
-    write_json_file(&output, &registration, "validator registration");
-    print_registration_summary(
-        "validator",
-        &output,
-        &registration.node_id,
-        &registration.peer_id,
-        &registration.p2p_address,
-        &registration.metrics_url,
+    values.insert("--p2p-port".to_string(), options.p2p_port.to_string());
+    values.insert(
+        "--metrics-port".to_string(),
+        options.metrics_port.to_string(),
     );
+    build_observer_registration(&values)
+}
 
-    if values.contains_key("--registry") && !values.contains_key("--registry-output") {
-        fatal("--registry requires --registry-output so the assembled registry is explicit");
-    }
-
-    if let Some(registry_output) = values.get("--registry-output") {
-        let policy = read_validator_policy(Path::new(
-            values
-                .get("--policy")
-                .map(String::as_str)
-                .unwrap_or_else(|| fatal("--registry-output requires --policy")),
-        ));
-        let mut registrations = values
-            .get("--registry")
-            .map(|path| read_json_file::<ValidatorRegistry>(Path::new(path), "validator registry"))
-            .map(|registry| {
-                if registry.chain_id != registration.chain_id {
-                    fatal("existing registry chain ID differs from the new registration");
+#[cfg(feature = "net")]
+fn diagnose_observer(options: &ObserverCliOptions) -> ObserverDiagnosis {
+    let mut checks = Vec::new();
+    let local_ip = local_ipv4_guess();
+    let key_result = load_or_derive_keypair(&Ed25519KeySource::from_spec(Some(&options.key_spec)));
+    let (peer_id, public_key_b64) = match key_result {
+        Ok(material) => {
+            let public_key = power_house::net::encode_public_key_base64(&material.verifying);
+            let peer_id = material.libp2p.public().to_peer_id().to_string();
+            checks.push(observer_check(
+                "node key",
+                "OK",
+                format!("loaded identity {peer_id}"),
+                None,
+            ));
+            (Some(peer_id), Some(public_key))
+        }
+        Err(err) => {
+            checks.push(observer_check(
+                "node key",
+                "FAIL",
+                format!("failed to load {}: {err}", options.key_spec),
+                Some("run `julian observer setup` to create the default key".to_string()),
+            ));
+            (None, None)
+        }
+    };
+    checks.push(observer_check(
+        "local ip",
+        if local_ip.is_some() { "OK" } else { "WARN" },
+        local_ip
+            .clone()
+            .unwrap_or_else(|| "could not infer local LAN IP".to_string()),
+        None,
+    ));
+    checks.push(port_check("p2p port", options.p2p_port));
+    checks.push(port_check("metrics port", options.metrics_port));
+    match fetch_local_metrics(options.metrics_port) {
+        Ok(metrics) => {
+            if let (Some(peer_id), Some(public_key)) = (&peer_id, &public_key_b64) {
+                if metrics_contains_identity(&metrics, &options.node_id, peer_id, public_key) {
+                    checks.push(observer_check(
+                        "local metrics identity",
+                        "OK",
+                        "metrics exposes matching node_id, peer_id, public key, and chain"
+                            .to_string(),
+                        None,
+                    ));
+                } else {
+                    checks.push(observer_check(
+                        "local metrics identity",
+                        "FAIL",
+                        "metrics endpoint is reachable but does not match this node identity".to_string(),
+                        Some("restart the observer with the same --node-id and --key used for registration".to_string()),
+                    ));
                 }
-                registry.registrations
-            })
-            .unwrap_or_default();
-        registrations.retain(|entry| {
-            entry.node_id != registration.node_id
-                && entry.public_key_b64 != registration.public_key_b64
-                && entry.peer_id != registration.peer_id
-        });
-        registrations.push(registration.clone());
-        let registry = ValidatorRegistry {
-            schema: VALIDATOR_REGISTRY_SCHEMA.to_string(),
-            chain_id: registration.chain_id,
-            registrations,
-        };
-        registry
-            .verify(&policy, unix_seconds())
-            .unwrap_or_else(|err| fatal(&format!("validator registry verification failed: {err}")));
-        write_json_file(Path::new(registry_output), &registry, "validator registry");
-        println!("validator registry: {registry_output}");
-        println!(
-            "verified validator identities: {}",
-            registry.registrations.len()
-        );
+            } else {
+                checks.push(observer_check(
+                    "local metrics",
+                    "OK",
+                    "metrics endpoint is reachable".to_string(),
+                    None,
+                ));
+            }
+        }
+        Err(err) => checks.push(observer_check(
+            "local metrics",
+            "WARN",
+            format!("not reachable at 127.0.0.1:{}: {err}", options.metrics_port),
+            Some(observer_start_command(options)),
+        )),
+    }
+    if let (Some(host), Some(probe_url)) = (&options.public_host, &options.probe_url) {
+        match fetch_observer_probe(probe_url, host, options.metrics_port, options.p2p_port) {
+            Ok(probe) => add_probe_checks(&mut checks, &probe),
+            Err(err) => checks.push(observer_check(
+                "external probe",
+                "WARN",
+                format!("probe unavailable: {err}"),
+                Some("verify from phone cellular data or retry with --probe-url".to_string()),
+            )),
+        }
     } else {
-        println!("next: submit the signed registration for policy admission and registry assembly");
+        checks.push(observer_check(
+            "external probe",
+            "WARN",
+            "public host or probe URL unavailable".to_string(),
+            Some(
+                "pass --public-host <public-ip-or-dns> or run after internet access is available"
+                    .to_string(),
+            ),
+        ));
+    }
+    ObserverDiagnosis {
+        schema: "power-house-observer-doctor-v1".to_string(),
+        node_id: options.node_id.clone(),
+        key_spec: options.key_spec.clone(),
+        local_ip,
+        public_host: options.public_host.clone(),
+        p2p_port: options.p2p_port,
+        metrics_port: options.metrics_port,
+        peer_id,
+        public_key_b64,
+        checks,
     }
 }
 
 #[cfg(feature = "net")]
-fn cmd_validator_registry_create(args: Vec<String>) {
-    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
-        print_validator_registry_help();
-        return;
+fn observer_check(
+    name: impl Into<String>,
+    status: impl Into<String>,
+    detail: impl Into<String>,
+    fix: Option<String>,
+) -> ObserverCheck {
+    ObserverCheck {
+        name: name.into(),
+        status: status.into(),
+        detail: detail.into(),
+        fix,
     }
-    let mut values = HashMap::new();
-    let mut iter = args.into_iter();
-    while let Some(flag) = iter.next() {
-        let value = iter
-            .next()
-            .unwrap_or_else(|| fatal(&format!("{flag} expects a value")));
-        if !matches!(
-            flag.as_str(),
-            "--key"
-                | "--node-id"
-                | "--operator"
-                | "--region"
-                | "--p2p-address"
-                | "--metrics-url"
-                | "--system-metrics-url"
-                | "--chain-id"
-                | "--issued-at"
-                | "--valid-until"
-                | "--output"
-        ) {
-            fatal(&format!("unknown argument: {flag}"));
-        }
-        if values.insert(flag.clone(), value).is_some() {
-            fatal(&format!("duplicate argument: {flag}"));
+}
+
+#[cfg(feature = "net")]
+fn port_check(name: &str, port: u16) -> ObserverCheck {
+    match TcpListener::bind(("0.0.0.0", port)) {
+        Ok(listener) => {
+            drop(listener);
+            observer_check(
+                name,
+                "OK",
+                format!("tcp/{port} is available"),
+                None,
+            )
         }
+        Err(err) => observer_check(
+            name,
+            "INFO",
+            format!("tcp/{port} is already in use: {err}"),
+            Some("this is expected when the observer is already running; otherwise choose another port".to_string()),
+        ),
     }
-
-    let now = unix_seconds();
-    let chain_id = parse_u64_option(values.get("--chain-id"), 177155, "--chain-id");
-    let issued_at = parse_u64_option(values.get("--issued-at"), now, "--issued-at");
-    let valid_until = parse_u64_option(
-        values.get("--valid-until"),
-        issued_at.saturating_add(365 * 24 * 60 * 60),
-        "--valid-until",
-    );
-    let key = required_option(&values, "--key");
-    let material = load_or_derive_keypair(&Ed25519KeySource::from_spec(Some(key)))
-        .unwrap_or_else(|err| fatal(&format!("failed to load validator key: {err}")));
-    let registration = ValidatorRegistration::sign(
-        chain_id,
-        required_option(&values, "--node-id").to_string(),
-        required_option(&values, "--operator").to_string(),
-        required_option(&values, "--region").to_string(),
-        required_option(&values, "--p2p-address").to_string(),
-        required_option(&values, "--metrics-url").to_string(),
-        values.get("--system-metrics-url").cloned(),
-        issued_at,
-        valid_until,
-        &material,
-    )
-    .unwrap_or_else(|err| fatal(&format!("failed to create validator registration: {err}")));
-    write_json_file(
-        Path::new(required_option(&values, "--output")),
-        &registration,
-        "validator registration",
-    );
-}
+}
 
 #[cfg(feature = "net")]
-fn cmd_validator_registry_assemble(args: Vec<String>) {
-    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
-        print_validator_registry_help();
+fn print_observer_diagnosis(diagnosis: &ObserverDiagnosis, json: bool) {
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(diagnosis)
+                .unwrap_or_else(|err| fatal(&format!("failed to encode diagnosis: {err}")))
+        );
         return;
     }
-    let mut registrations = Vec::new();
-    let mut policy = None;
-    let mut output = None;
-    let mut chain_id = 177155;
-    let mut iter = args.into_iter();
-    while let Some(arg) = iter.next() {
-        match arg.as_str() {
-            "--registration" => registrations.push(PathBuf::from(
-                iter.next()
-                    .unwrap_or_else(|| fatal("--registration expects a value")),
-            )),
-            "--policy" => {
-                policy = Some(PathBuf::from(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--policy expects a value")),
-                ))
-            }
-            "--output" => {
-                output = Some(PathBuf::from(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--output expects a value")),
-                ))
-            }
-            "--chain-id" => {
-                chain_id = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--chain-id expects a value"))
-                    .parse()
-                    .unwrap_or_else(|_| fatal("invalid --chain-id"))
-            }
-            value => fatal(&format!("unknown argument: {value}")),
+    println!("Power House Observer Doctor");
+    println!("node_id: {}", diagnosis.node_id);
+    if let Some(host) = &diagnosis.public_host {
+        println!("public_host: {host}");
+    }
+    for check in &diagnosis.checks {
+        println!("[{}] {}: {}", check.status, check.name, check.detail);
+        if let Some(fix) = &check.fix {
+            println!("    fix: {fix}");
         }
     }
-    if registrations.is_empty() {
-        fatal("assemble requires at least one --registration");
+}
+
+#[cfg(feature = "net")]
+fn ensure_default_node_key(key_spec: &str) -> Option<PathBuf> {
+    if key_spec.contains("://") {
+        return None;
     }
-    let entries = registrations
+    let path = Path::new(key_spec);
+    if path.exists() {
+        return None;
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .unwrap_or_else(|err| fatal(&format!("failed to create {}: {err}", parent.display())));
+    }
+    let mut seed = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut seed);
+    fs::write(path, seed)
+        .unwrap_or_else(|err| fatal(&format!("failed to write key {}: {err}", path.display())));
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600)).unwrap_or_else(|err| {
+            fatal(&format!(
+                "failed to set key permissions {}: {err}",
+                path.display()
+            ))
+        });
+    }
+    Some(path.to_path_buf())
+}
+
+#[cfg(feature = "net")]
+fn observer_start_command(options: &ObserverCliOptions) -> String {
+    let bootstrap_lines = DEFAULT_OBSERVER_BOOTSTRAPS
         .iter()
-        .map(|path| read_json_file::<ValidatorRegistration>(path, "validator registration"))
-        .collect();
-    let registry = ValidatorRegistry {
-        schema: VALIDATOR_REGISTRY_SCHEMA.to_string(),
-        chain_id,
-        registrations: entries,
+        .map(|addr| format!("  --bootstrap {} \\", shell_word(addr)))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let bootstrap_section = if bootstrap_lines.is_empty() {
+        String::new()
+    } else {
+        format!("{bootstrap_lines}\n")
     };
-    let admitted = read_validator_policy(
-        policy
-            .as_deref()
-            .unwrap_or_else(|| fatal("assemble requires --policy")),
-    );
-    registry
-        .verify(&admitted, unix_seconds())
-        .unwrap_or_else(|err| fatal(&format!("validator registry verification failed: {err}")));
-    write_json_file(
-        output
-            .as_deref()
-            .unwrap_or_else(|| fatal("assemble requires --output")),
-        &registry,
-        "validator registry",
-    );
+    format!(
+        "julian net start \\\n  --node-id {} \\\n  --log-dir ./logs/{}-observer \\\n  --blob-dir ./data/{}-observer \\\n  --listen /ip4/0.0.0.0/tcp/{} \\\n{}  --key \"{}\" \\\n  --metrics 0.0.0.0:{}",
+        shell_word(&options.node_id),
+        options.node_id,
+        options.node_id,
+        options.p2p_port,
+        bootstrap_section,
+        options.key_spec,
+        options.metrics_port
+    )
 }
 
 #[cfg(feature = "net")]
-fn cmd_validator_registry_verify(args: Vec<String>) {
-    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
-        print_validator_registry_help();
-        return;
-    }
-    let mut registry_path = None;
-    let mut policy_path = None;
-    let mut now = unix_seconds();
-    let mut json = false;
-    let mut iter = args.into_iter();
-    while let Some(arg) = iter.next() {
-        match arg.as_str() {
-            "--policy" => {
-                policy_path = Some(PathBuf::from(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--policy expects a value")),
-                ))
-            }
-            "--now" => {
-                now = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--now expects a value"))
-                    .parse()
-                    .unwrap_or_else(|_| fatal("invalid --now"))
-            }
-            "--json" => json = true,
-            value if registry_path.is_none() => registry_path = Some(PathBuf::from(value)),
-            value => fatal(&format!("unknown argument: {value}")),
-        }
-    }
-    let registry = read_json_file::<ValidatorRegistry>(
-        registry_path
-            .as_deref()
-            .unwrap_or_else(|| fatal("verify requires a registry path")),
-        "validator registry",
-    );
-    let admitted = read_validator_policy(
-        policy_path
-            .as_deref()
-            .unwrap_or_else(|| fatal("verify requires --policy")),
-    );
-    registry
-        .verify(&admitted, now)
-        .unwrap_or_else(|err| fatal(&format!("validator registry verification failed: {err}")));
-    if json {
-        println!(
-            "{}",
-            serde_json::json!({
-                "chain_id": registry.chain_id,
-                "registrations": registry.registrations,
-                "schema": registry.schema,
-                "validators_verified": registry.registrations.len(),
-                "verified": true,
-            })
-        );
+fn shell_word(value: &str) -> String {
+    if value
+        .chars()
+        .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-' | '.' | '/' | ':'))
+    {
+        value.to_string()
     } else {
-        println!(
-            "validator registry verified: {} admitted identities on chain {}",
-            registry.registrations.len(),
-            registry.chain_id
-        );
+        format!("'{}'", value.replace('\'', "'\\''"))
     }
 }
 
 #[cfg(feature = "net")]
-fn handle_observer_registry(sub: &str, tail: Vec<String>) {
-    match sub {
-        "-h" | "--help" => print_observer_registry_help(),
-        "register" => cmd_observer_registry_register(tail),
-        "create" => cmd_observer_registry_create(tail),
-        "assemble" => cmd_observer_registry_assemble(tail),
-        "verify" => cmd_observer_registry_verify(tail),
-        _ => fatal(&format!("unknown observer-registry subcommand: {sub}")),
+fn local_ipv4_guess() -> Option<String> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("1.1.1.1:80").ok()?;
+    match socket.local_addr().ok()?.ip() {
+        IpAddr::V4(value) => Some(value.to_string()),
+        IpAddr::V6(_) => None,
     }
 }
 
 #[cfg(feature = "net")]
-fn cmd_observer_registry_register(args: Vec<String>) {
-    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
-        print_observer_registry_help();
-        return;
-    }
-    let values = parse_registry_options(
-        args,
-        &[
-            "--key",
-            "--node-id",
-            "--operator",
-            "--region",
-            "--public-host",
-            "--host",
-            "--p2p-port",
-            "--metrics-port",
-            "--p2p-address",
-            "--metrics-url",
-            "--system-metrics-port",
-            "--system-metrics-url",
-            "--chain-id",
-            "--issued-at",
-            "--valid-until",
-            "--output",
-            "--registry",
-            "--registry-output",
-        ],
-    );
-    let registration = build_observer_registration(&values);
-    let output = values
-        .get("--output")
-        .map(PathBuf::from)
-        .unwrap_or_else(|| default_registration_output(&registration.node_id, "observer"));
-    write_json_file(&output, &registration, "observer registration");
-    print_registration_summary(
-        "observer",
-        &output,
-        &registration.node_id,
-        &registration.peer_id,
-        &registration.p2p_address,
-        &registration.metrics_url,
-    );
-
-    if values.contains_key("--registry") && !values.contains_key("--registry-output") {
-        fatal("--registry requires --registry-output so the assembled registry is explicit");
-    }
+fn detect_public_host() -> Result<String, String> {
+    let text = https_get_text("https://api.ipify.org", 4)?;
+    Ok(normalize_public_host(text.trim()))
+}
 
-    if let Some(registry_output) = values.get("--registry-output") {
-        let mut registrations = values
-            .get("--registry")
-            .map(|path| read_json_file::<ObserverRegistry>(Path::new(path), "observer registry"))
-            .map(|registry| {
-                if registry.chain_id != registration.chain_id {
-                    fatal("existing registry chain ID differs from the new registration");
-                }
-                registry.registrations
-            })
-            .unwrap_or_default();
-        registrations.retain(|entry| {
-            entry.node_id != registration.node_id
-                && entry.public_key_b64 != registration.public_key_b64
-                && entry.peer_id != registration.peer_id
-        });
-        registrations.push(registration.clone());
-        let registry = ObserverRegistry {
-            schema: OBSERVER_REGISTRY_SCHEMA.to_string(),
-            chain_id: registration.chain_id,
-            registrations,
-        };
-        registry
-            .verify(unix_seconds())
-            .unwrap_or_else(|err| fatal(&format!("observer registry verification failed: {err}")));
-        write_json_file(Path::new(registry_output), &registry, "observer registry");
-        println!("observer registry: {registry_output}");
-        println!(
-            "verified observer identities: {}",
-            registry.registrations.len()
-        );
-    } else {
-        println!("next: upload the signed registration on mfenx.com/register.html or send it for observer registry assembly");
-    }
+#[cfg(feature = "net")]
+fn https_get_text(url: &str, timeout_secs: u64) -> Result<String, String> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|err| err.to_string())?;
+    runtime.block_on(async {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(timeout_secs))
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .map_err(|err| err.to_string())?;
+        let response = client
+            .get(url)
+            .send()
+            .await
+            .map_err(|err| err.to_string())?
+            .error_for_status()
+            .map_err(|err| err.to_string())?;
+        response.text().await.map_err(|err| err.to_string())
+    })
 }
 
 #[cfg(feature = "net")]
-fn cmd_observer_registry_create(args: Vec<String>) {
-    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
-        print_observer_registry_help();
-        return;
-    }
-    let mut values = HashMap::new();
-    let mut iter = args.into_iter();
-    while let Some(flag) = iter.next() {
-        let value = iter
-            .next()
-            .unwrap_or_else(|| fatal(&format!("{flag} expects a value")));
-        if !matches!(
-            flag.as_str(),
-            "--key"
-                | "--node-id"
-                | "--operator"
-                | "--region"
-                | "--p2p-address"
-                | "--metrics-url"
-                | "--system-metrics-url"
-                | "--chain-id"
-                | "--issued-at"
-                | "--valid-until"
-                | "--output"
-        ) {
-            fatal(&format!("unknown argument: {flag}"));
+fn observer_intake_request<T: Serialize>(
+    method: &str,
+    url: &str,
+    body: Option<&T>,
+) -> Result<serde_json::Value, String> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|err| err.to_string())?;
+    runtime.block_on(async {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(15))
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .map_err(|err| err.to_string())?;
+        let method =
+            reqwest::Method::from_bytes(method.as_bytes()).map_err(|err| err.to_string())?;
+        let mut request = client.request(method, url);
+        if let Some(value) = body {
+            request = request.json(value);
         }
-        if values.insert(flag.clone(), value).is_some() {
-            fatal(&format!("duplicate argument: {flag}"));
+        let response = request.send().await.map_err(|err| err.to_string())?;
+        let status = response.status();
+        let text = response.text().await.map_err(|err| err.to_string())?;
+        let value: serde_json::Value =
+            serde_json::from_str(&text).map_err(|err| format!("invalid intake response: {err}"))?;
+        if !status.is_success() {
+            let message = value
+                .pointer("/error/message")
+                .and_then(|item| item.as_str())
+                .unwrap_or("registration intake rejected the request");
+            return Err(format!("HTTP {status}: {message}"));
         }
+        Ok(value)
+    })
+}
+
+#[cfg(feature = "net")]
+fn fetch_local_metrics(port: u16) -> Result<String, String> {
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).map_err(|err| err.to_string())?;
+    stream
+        .set_read_timeout(Some(Duration::from_secs(3)))
+        .map_err(|err| err.to_string())?;
+    stream
+        .set_write_timeout(Some(Duration::from_secs(3)))
+        .map_err(|err| err.to_string())?;
+    stream
+        .write_all(b"GET /metrics HTTP/1.1\r\nHost: 127.0.0.1\r\nConnection: close\r\n\r\n")
+        .map_err(|err| err.to_string())?;
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .map_err(|err| err.to_string())?;
+    if !response.starts_with("HTTP/1.1 200") && !response.starts_with("HTTP/1.0 200") {
+        return Err(response.lines().next().unwrap_or("HTTP error").to_string());
     }
+    Ok(response
+        .split("\r\n\r\n")
+        .nth(1)
+        .unwrap_or(response.as_str())
+        .to_string())
+}
 
-    let now = unix_seconds();
-    let chain_id = parse_u64_option(values.get("--chain-id"), 177155, "--chain-id");
-    let issued_at = parse_u64_option(values.get("--issued-at"), now, "--issued-at");
-    let valid_until = parse_u64_option(
-        values.get("--valid-until"),
-        issued_at.saturating_add(365 * 24 * 60 * 60),
-        "--valid-until",
-    );
-    let key = required_option(&values, "--key");
-    let material = load_or_derive_keypair(&Ed25519KeySource::from_spec(Some(key)))
-        .unwrap_or_else(|err| fatal(&format!("failed to load observer key: {err}")));
-    let registration = ObserverRegistration::sign(
-        chain_id,
-        required_option(&values, "--node-id").to_string(),
-        required_option(&values, "--operator").to_string(),
-        required_option(&values, "--region").to_string(),
-        required_option(&values, "--p2p-address").to_string(),
-        required_option(&values, "--metrics-url").to_string(),
-        values.get("--system-metrics-url").cloned(),
-        issued_at,
-        valid_until,
-        &material,
-    )
-    .unwrap_or_else(|err| fatal(&format!("failed to create observer registration: {err}")));
-    write_json_file(
-        Path::new(required_option(&values, "--output")),
-        &registration,
-        "observer registration",
+#[cfg(feature = "net")]
+fn metrics_contains_identity(
+    body: &str,
+    node_id: &str,
+    peer_id: &str,
+    public_key_b64: &str,
+) -> bool {
+    body.lines().any(|line| {
+        line.starts_with("powerhouse_node_identity{")
+            && line.contains(&format!("node_id=\"{node_id}\""))
+            && line.contains(&format!("peer_id=\"{peer_id}\""))
+            && line.contains(&format!("public_key_b64=\"{public_key_b64}\""))
+            && line.contains(&format!(
+                "chain_id=\"{}\"",
+                power_house::active_genesis().chain_id
+            ))
+    })
+}
+
+#[cfg(feature = "net")]
+fn fetch_observer_probe(
+    probe_url: &str,
+    host: &str,
+    metrics_port: u16,
+    p2p_port: u16,
+) -> Result<serde_json::Value, String> {
+    let separator = if probe_url.contains('?') { '&' } else { '?' };
+    let url = format!(
+        "{probe_url}{separator}host={}&metrics_port={metrics_port}&p2p_port={p2p_port}",
+        percent_encode_query(host)
     );
+    let text = https_get_text(&url, 8)?;
+    serde_json::from_str(&text).map_err(|err| err.to_string())
 }
 
 #[cfg(feature = "net")]
-fn cmd_observer_registry_assemble(args: Vec<String>) {
-    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
-        print_observer_registry_help();
-        return;
-    }
-    let mut registrations = Vec::new();
-    let mut output = None;
-    let mut chain_id = 177155;
-    let mut iter = args.into_iter();
-    while let Some(arg) = iter.next() {
-        match arg.as_str() {
-            "--registration" => registrations.push(PathBuf::from(
-                iter.next()
-                    .unwrap_or_else(|| fatal("--registration expects a value")),
-            )),
-            "--output" => {
-                output = Some(PathBuf::from(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--output expects a value")),
-                ))
-            }
-            "--chain-id" => {
-                chain_id = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--chain-id expects a value"))
-                    .parse()
-                    .unwrap_or_else(|_| fatal("invalid --chain-id"))
-            }
-            value => fatal(&format!("unknown argument: {value}")),
+fn percent_encode_query(value: &str) -> String {
+    let mut encoded = String::new();
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'.' | b'-' | b'_') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
         }
     }
-    if registrations.is_empty() {
-        fatal("assemble requires at least one --registration");
-    }
-    let entries = registrations
-        .iter()
-        .map(|path| read_json_file::<ObserverRegistration>(path, "observer registration"))
-        .collect();
-    let registry = ObserverRegistry {
-        schema: OBSERVER_REGISTRY_SCHEMA.to_string(),
-        chain_id,
-        registrations: entries,
-    };
-    registry
-        .verify(unix_seconds())
-        .unwrap_or_else(|err| fatal(&format!("observer registry verification failed: {err}")));
-    write_json_file(
-        output
-            .as_deref()
-            .unwrap_or_else(|| fatal("assemble requires --output")),
-        &registry,
-        "observer registry",
-    );
+    encoded
 }
 
 #[cfg(feature = "net")]
-fn cmd_observer_registry_verify(args: Vec<String>) {
-    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
-        print_observer_registry_help();
-        return;
-    }
-    let mut registry_path = None;
-    let mut now = unix_seconds();
-    let mut json = false;
-    let mut iter = args.into_iter();
-    while let Some(arg) = iter.next() {
-        match arg.as_str() {
-            "--now" => {
-                now = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--now expects a value"))
-                    .parse()
-                    .unwrap_or_else(|_| fatal("invalid --now"))
-            }
-            "--json" => json = true,
-            value if registry_path.is_none() => registry_path = Some(PathBuf::from(value)),
-            value => fatal(&format!("unknown argument: {value}")),
-        }
+fn add_probe_checks(checks: &mut Vec<ObserverCheck>, probe: &serde_json::Value) {
+    let metrics_ok = probe
+        .pointer("/metrics/reachable")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false);
+    let identity_ok = probe
+        .get("registration_identity_matches")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false);
+    let p2p_ok = probe
+        .pointer("/p2p/reachable")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false);
+    checks.push(observer_check(
+        "external metrics",
+        if metrics_ok && identity_ok {
+            "OK"
+        } else {
+            "FAIL"
+        },
+        if metrics_ok && identity_ok {
+            "public metrics endpoint is reachable and exposes identity".to_string()
+        } else {
+            probe
+                .pointer("/metrics/error")
+                .and_then(|value| value.as_str())
+                .unwrap_or("metrics identity not reachable from external probe")
+                .to_string()
+        },
+        if metrics_ok && identity_ok {
+            None
+        } else {
+            Some(
+                "forward TCP metrics port to this machine and keep the observer running"
+                    .to_string(),
+            )
+        },
+    ));
+    checks.push(observer_check(
+        "external p2p",
+        if p2p_ok { "OK" } else { "FAIL" },
+        if p2p_ok {
+            "public p2p port accepts TCP connections".to_string()
+        } else {
+            probe
+                .pointer("/p2p/error")
+                .and_then(|value| value.as_str())
+                .unwrap_or("p2p port not reachable from external probe")
+                .to_string()
+        },
+        if p2p_ok {
+            None
+        } else {
+            Some("forward TCP p2p port to this machine".to_string())
+        },
+    ));
+}
+
+#[cfg(feature = "net")]
+fn read_observer_registration_or_package(path: &Path) -> ObserverRegistration {
+    let value: serde_json::Value = read_json_file(path, "observer registration package");
+    if value.get("schema").and_then(|schema| schema.as_str())
+        == Some("power-house-observer-registration-v1")
+    {
+        return serde_json::from_value(value)
+            .unwrap_or_else(|err| fatal(&format!("invalid observer registration: {err}")));
     }
-    let registry = read_json_file::<ObserverRegistry>(
-        registry_path
-            .as_deref()
-            .unwrap_or_else(|| fatal("verify requires a registry path")),
-        "observer registry",
-    );
-    registry
-        .verify(now)
-        .unwrap_or_else(|err| fatal(&format!("observer registry verification failed: {err}")));
-    if json {
-        println!(
-            "{}",
-            serde_json::json!({
-                "chain_id": registry.chain_id,
-                "registrations": registry.registrations,
-                "schema": registry.schema,
-                "observers_verified": registry.registrations.len(),
-                "verified": true,
-            })
-        );
-    } else {
-        println!(
-            "observer registry verified: {} signed identities on chain {}",
-            registry.registrations.len(),
-            registry.chain_id
-        );
+    if let Some(registration) = value.get("registration") {
+        return serde_json::from_value(registration.clone()).unwrap_or_else(|err| {
+            fatal(&format!("invalid packaged observer registration: {err}"))
+        });
     }
+    fatal("input must be an observer registration or mfenx submission package")
 }
 
 #[cfg(feature = "net")]
-fn parse_registry_options(args: Vec<String>, allowed: &[&str]) -> HashMap<String, String> {
-    let mut values = HashMap::new();
-    let mut iter = args.into_iter();
-    while let Some(flag) = iter.next() {
-        if !flag.starts_with("--") {
-            fatal(&format!("unexpected positional argument: {flag}"));
-        }
-        if !allowed.contains(&flag.as_str()) {
-            fatal(&format!("unknown argument: {flag}"));
-        }
-        let value = iter
-            .next()
-            .unwrap_or_else(|| fatal(&format!("{flag} expects a value")));
-        if value.starts_with("--") {
-            fatal(&format!("{flag} expects a value"));
-        }
-        if values.insert(flag.clone(), value).is_some() {
-            fatal(&format!("duplicate argument: {flag}"));
-        }
-    }
-    values
+fn observer_probe_for_registration(
+    probe_url: &str,
+    registration: &ObserverRegistration,
+) -> Result<serde_json::Value, String> {
+    let host = metrics_host_from_url(&registration.metrics_url)?;
+    let metrics_port = metrics_port_from_url(&registration.metrics_url).unwrap_or(80);
+    let p2p_port = p2p_port_from_multiaddr(&registration.p2p_address).unwrap_or(7001);
+    let mut probe = fetch_observer_probe(probe_url, &host, metrics_port, p2p_port)?;
+    let identity = probe.pointer("/metrics/identity");
+    let identity_matches = identity.is_some_and(|value| {
+        value.get("node_id").and_then(|item| item.as_str()) == Some(&registration.node_id)
+            && value.get("peer_id").and_then(|item| item.as_str()) == Some(&registration.peer_id)
+            && value.get("public_key_b64").and_then(|item| item.as_str())
+                == Some(&registration.public_key_b64)
+            && value
+                .get("chain_id")
+                .and_then(|item| item.as_str())
+                .and_then(|item| item.parse::<u64>().ok())
+                == Some(registration.chain_id)
+    });
+    probe["registration_identity_matches"] = serde_json::Value::Bool(identity_matches);
+    let probe_ok = probe
+        .get("ok")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false);
+    probe["ok"] = serde_json::Value::Bool(probe_ok && identity_matches);
+    Ok(probe)
 }
 
 #[cfg(feature = "net")]
-fn build_validator_registration(values: &HashMap<String, String>) -> ValidatorRegistration {
-    let input = registration_input(values, "validator");
-    ValidatorRegistration::sign(
-        input.chain_id,
-        input.node_id,
-        input.operator,
-        input.region,
-        input.p2p_address,
-        input.metrics_url,
-        input.system_metrics_url,
-        input.issued_at,
-        input.valid_until,
-        &input.material,
-    )
-    .unwrap_or_else(|err| fatal(&format!("failed to create validator registration: {err}")))
+fn metrics_host_from_url(url: &str) -> Result<String, String> {
+    let parsed = reqwest::Url::parse(url).map_err(|err| err.to_string())?;
+    parsed
+        .host_str()
+        .map(|host| host.to_string())
+        .ok_or_else(|| "metrics URL has no host".to_string())
 }
 
 #[cfg(feature = "net")]
-fn build_observer_registration(values: &HashMap<String, String>) -> ObserverRegistration {
-    let input = registration_input(values, "observer");
-    ObserverRegistration::sign(
-        input.chain_id,
-        input.node_id,
-        input.operator,
-        input.region,
-        input.p2p_address,
-        input.metrics_url,
-        input.system_metrics_url,
-        input.issued_at,
-        input.valid_until,
-        &input.material,
-    )
-    .unwrap_or_else(|err| fatal(&format!("failed to create observer registration: {err}")))
+fn metrics_port_from_url(url: &str) -> Option<u16> {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|parsed| parsed.port_or_known_default())
 }
 
 #[cfg(feature = "net")]
-struct RegistrationInput {
-    chain_id: u64,
-    node_id: String,
-    operator: String,
-    region: String,
-    p2p_address: String,
-    metrics_url: String,
-    system_metrics_url: Option<String>,
-    issued_at: u64,
-    valid_until: u64,
-    material: power_house::net::KeyMaterial,
+fn p2p_port_from_multiaddr(address: &str) -> Option<u16> {
+    let mut parts = address.split('/');
+    while let Some(part) = parts.next() {
+        if part == "tcp" {
+            return parts.next().and_then(|port| port.parse().ok());
+        }
+    }
+    None
 }
 
 #[cfg(feature = "net")]
-fn registration_input(values: &HashMap<String, String>, label: &str) -> RegistrationInput {
-    let now = unix_seconds();
-    let chain_id = parse_u64_option(values.get("--chain-id"), 177155, "--chain-id");
-    let issued_at = parse_u64_option(values.get("--issued-at"), now, "--issued-at");
-    let valid_until = parse_u64_option(
-        values.get("--valid-until"),
-        issued_at.saturating_add(365 * 24 * 60 * 60),
-        "--valid-until",
-    );
-    let node_id = required_option(values, "--node-id").to_string();
-    let operator = values
-        .get("--operator")
-        .cloned()
-        .unwrap_or_else(|| node_id.clone());
-    let region = values
-        .get("--region")
-        .cloned()
-        .unwrap_or_else(|| "self-hosted".to_string());
-    let key_spec = values
-        .get("--key")
-        .cloned()
-        .unwrap_or_else(default_node_key_spec);
-    let material = load_or_derive_keypair(&Ed25519KeySource::from_spec(Some(&key_spec)))
-        .unwrap_or_else(|err| fatal(&format!("failed to load {label} key {key_spec}: {err}")));
-    let peer_id = material.libp2p.public().to_peer_id().to_string();
-
-    let public_host = registry_public_host(values);
-    let p2p_address = values.get("--p2p-address").cloned().unwrap_or_else(|| {
-        let host = public_host
-            .clone()
-            .unwrap_or_else(|| fatal("--public-host is required unless --p2p-address is provided"));
-        build_p2p_address(
-            &host,
-            parse_port_option(values.get("--p2p-port"), 7001, "--p2p-port"),
-            &peer_id,
-        )
-    });
-    let metrics_url = values.get("--metrics-url").cloned().unwrap_or_else(|| {
-        let host = public_host
-            .as_deref()
-            .unwrap_or_else(|| fatal("--public-host is required unless --metrics-url is provided"));
-        build_metrics_url(
-            host,
-            parse_port_option(values.get("--metrics-port"), 9100, "--metrics-port"),
-        )
-    });
-    let system_metrics_url = values.get("--system-metrics-url").cloned().or_else(|| {
-        values.get("--system-metrics-port").map(|_| {
-            let host = public_host.as_deref().unwrap_or_else(|| {
-                fatal("--public-host is required when --system-metrics-port is used")
-            });
-            build_metrics_url(
-                host,
-                parse_port_option(
-                    values.get("--system-metrics-port"),
-                    9101,
-                    "--system-metrics-port",
-                ),
-            )
-        })
-    });
-
-    RegistrationInput {
-        chain_id,
-        node_id,
-        operator,
-        region,
-        p2p_address,
-        metrics_url,
-        system_metrics_url,
-        issued_at,
-        valid_until,
-        material,
+fn handle_validator_registry(sub: &str, tail: Vec<String>) {
+    match sub {
+        "-h" | "--help" => print_validator_registry_help(),
+        "register" => cmd_validator_registry_register(tail),
+        "create" => cmd_validator_registry_create(tail),
+        "assemble" => cmd_validator_registry_assemble(tail),
+        "verify" => cmd_validator_registry_verify(tail),
+        _ => fatal(&format!("unknown validator-registry subcommand: {sub}")),
     }
 }
 
 #[cfg(feature = "net")]
-fn registry_public_host(values: &HashMap<String, String>) -> Option<String> {
-    values
-        .get("--public-host")
-        .or_else(|| values.get("--host"))
-        .map(|host| normalize_public_host(host))
-}
+fn cmd_validator_registry_register(args: Vec<String>) {
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        print_validator_registry_help();
+        return;
+    }
+    let values = parse_registry_options(
+        args,
+        &[
+            "--key",
+            "--node-id",
+            "--operator",
+            "--region",
+            "--public-host",
+            "--host",
+            "--p2p-port",
+            "--metrics-port",
+            "--p2p-address",
+            "--metrics-url",
+            "--system-metrics-port",
+            "--system-metrics-url",
+            "--chain-id",
+            "--issued-at",
+            "--valid-until",
+            "--output",
+            "--policy",
+            "--registry",
+            "--registry-output",
+        ],
+    );
+    let registration = build_validator_registration(&values);
+    let output = values
+        .get("--output")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| default_registration_output(&registration.node_id, "validator"));
+    write_json_file(&output, &registration, "validator registration");
+    print_registration_summary(
+        "validator",
+        &output,
+        &registration.node_id,
+        &registration.peer_id,
+        &registration.p2p_address,
+        &registration.metrics_url,
+    );
 
-#[cfg(feature = "net")]
-fn default_node_key_spec() -> String {
-    env::var("POWERHOUSE_NODE_KEY").unwrap_or_else(|_| {
-        env::var("HOME")
-            .map(|home| format!("{home}/.powerhouse/node.key"))
-            .unwrap_or_else(|_| "$HOME/.powerhouse/node.key".to_string())
-    })
-}
+    if values.contains_key("--registry") && !values.contains_key("--registry-output") {
+        fatal("--registry requires --registry-output so the assembled registry is explicit");
+    }
 
-#[cfg(feature = "net")]
-fn default_registration_output(node_id: &str, kind: &str) -> PathBuf {
-    PathBuf::from(format!("{node_id}.{kind}.registration.json"))
+    if let Some(registry_output) = values.get("--registry-output") {
+        let policy = read_validator_policy(Path::new(
+            values
+                .get("--policy")
+                .map(String::as_str)
+                .unwrap_or_else(|| fatal("--registry-output requires --policy")),
+        ));
+        let mut registrations = values
+            .get("--registry")
+            .map(|path| read_json_file::<ValidatorRegistry>(Path::new(path), "validator registry"))
+            .map(|registry| {
+                if registry.chain_id != registration.chain_id {
+                    fatal("existing registry chain ID differs from the new registration");
+                }
+                registry.registrations
+            })
+            .unwrap_or_default();
+        registrations.retain(|entry| {
+            entry.node_id != registration.node_id
+                && entry.public_key_b64 != registration.public_key_b64
+                && entry.peer_id != registration.peer_id
+        });
+        registrations.push(registration.clone());
+        let registry = ValidatorRegistry {
+            schema: VALIDATOR_REGISTRY_SCHEMA.to_string(),
+            chain_id: registration.chain_id,
+            registrations,
+        };
+        registry
+            .verify(&policy, unix_seconds())
+            .unwrap_or_else(|err| fatal(&format!("validator registry verification failed: {err}")));
+        write_json_file(Path::new(registry_output), &registry, "validator registry");
+        println!("validator registry: {registry_output}");
+        println!(
+            "verified validator identities: {}",
+            registry.registrations.len()
+        );
+    } else {
+        println!("next: submit the signed registration for policy admission and registry assembly");
+    }
 }
 
 #[cfg(feature = "net")]
-fn parse_port_option(value: Option<&String>, default: u16, name: &str) -> u16 {
-    value.map_or(default, |raw| {
-        let port: u16 = raw
-            .parse()
-            .unwrap_or_else(|_| fatal(&format!("invalid {name}")));
-        if port == 0 {
-            fatal(&format!("{name} must be between 1 and 65535"));
+fn cmd_validator_registry_create(args: Vec<String>) {
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        print_validator_registry_help();
+        return;
+    }
+    let mut values = HashMap::new();
+    let mut iter = args.into_iter();
+    while let Some(flag) = iter.next() {
+        let value = iter
+            .next()
+            .unwrap_or_else(|| fatal(&format!("{flag} expects a value")));
+        if !matches!(
+            flag.as_str(),
+            "--key"
+                | "--node-id"
+                | "--operator"
+                | "--region"
+                | "--p2p-address"
+                | "--metrics-url"
+                | "--system-metrics-url"
+                | "--chain-id"
+                | "--issued-at"
+                | "--valid-until"
+                | "--output"
+        ) {
+            fatal(&format!("unknown argument: {flag}"));
         }
-        port
-    })
+        if values.insert(flag.clone(), value).is_some() {
+            fatal(&format!("duplicate argument: {flag}"));
+        }
+    }
+
+    let now = unix_seconds();
+    let chain_id = parse_u64_option(
+        values.get("--chain-id"),
+        power_house::active_genesis().chain_id,
+        "--chain-id",
+    );
+    let issued_at = parse_u64_option(values.get("--issued-at"), now, "--issued-at");
+    let valid_until = parse_u64_option(
+        values.get("--valid-until"),
+        issued_at.saturating_add(365 * 24 * 60 * 60),
+        "--valid-until",
+    );
+    let key = required_option(&values, "--key");
+    let material = load_or_derive_keypair(&Ed25519KeySource::from_spec(Some(key)))
+        .unwrap_or_else(|err| fatal(&format!("failed to load validator key: {err}")));
+    let registration = ValidatorRegistration::sign(
+        chain_id,
+        required_option(&values, "--node-id").to_string(),
+        required_option(&values, "--operator").to_string(),
+        required_option(&values, "--region").to_string(),
+        required_option(&values, "--p2p-address").to_string(),
+        required_option(&values, "--metrics-url").to_string(),
+        values.get("--system-metrics-url").cloned(),
+        issued_at,
+        valid_until,
+        &material,
+    )
+    .unwrap_or_else(|err| fatal(&format!("failed to create validator registration: {err}")));
+    write_json_file(
+        Path::new(required_option(&values, "--output")),
+        &registration,
+        "validator registration",
+    );
 }
 
 #[cfg(feature = "net")]
-fn normalize_public_host(host: &str) -> String {
-    let trimmed = host
-        .trim()
-        .trim_start_matches('[')
-        .trim_end_matches(']')
-        .trim_end_matches('.');
-    if trimmed.is_empty() {
-        fatal("--public-host cannot be empty");
-    }
-    if trimmed.contains("://") || trimmed.contains('/') {
-        fatal("--public-host must be a bare DNS name, IPv4 address, or IPv6 address");
+fn cmd_validator_registry_assemble(args: Vec<String>) {
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        print_validator_registry_help();
+        return;
     }
-    if trimmed.parse::<Ipv4Addr>().is_ok() || trimmed.parse::<Ipv6Addr>().is_ok() {
-        trimmed.to_string()
-    } else {
-        trimmed.to_ascii_lowercase()
+    let mut registrations = Vec::new();
+    let mut policy = None;
+    let mut output = None;
+    let mut chain_id = power_house::active_genesis().chain_id;
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--registration" => registrations.push(PathBuf::from(
+                iter.next()
+                    .unwrap_or_else(|| fatal("--registration expects a value")),
+            )),
+            "--policy" => {
+                policy = Some(PathBuf::from(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--policy expects a value")),
+                ))
+            }
+            "--output" => {
+                output = Some(PathBuf::from(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--output expects a value")),
+                ))
+            }
+            "--chain-id" => {
+                chain_id = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--chain-id expects a value"))
+                    .parse()
+                    .unwrap_or_else(|_| fatal("invalid --chain-id"))
+            }
+            value => fatal(&format!("unknown argument: {value}")),
+        }
     }
-}
-
-#[cfg(feature = "net")]
-fn build_p2p_address(host: &str, port: u16, peer_id: &str) -> String {
-    let protocol = if host.parse::<Ipv4Addr>().is_ok() {
-        format!("/ip4/{host}")
-    } else if host.parse::<Ipv6Addr>().is_ok() {
-        format!("/ip6/{host}")
-    } else {
-        format!("/dns4/{host}")
-    };
-    format!("{protocol}/tcp/{port}/p2p/{peer_id}")
-}
-
-#[cfg(feature = "net")]
-fn build_metrics_url(host: &str, port: u16) -> String {
-    let host_for_url = if host.parse::<Ipv6Addr>().is_ok() {
-        format!("[{host}]")
-    } else {
-        host.to_string()
+    if registrations.is_empty() {
+        fatal("assemble requires at least one --registration");
+    }
+    let entries = registrations
+        .iter()
+        .map(|path| read_json_file::<ValidatorRegistration>(path, "validator registration"))
+        .collect();
+    let registry = ValidatorRegistry {
+        schema: VALIDATOR_REGISTRY_SCHEMA.to_string(),
+        chain_id,
+        registrations: entries,
     };
-    format!("http://{host_for_url}:{port}/metrics")
-}
-
-#[cfg(feature = "net")]
-fn print_registration_summary(
-    kind: &str,
-    output: &Path,
-    node_id: &str,
-    peer_id: &str,
-    p2p_address: &str,
-    metrics_url: &str,
-) {
-    println!("{kind} registration: {}", output.display());
-    println!("node_id: {node_id}");
-    println!("peer_id: {peer_id}");
-    println!("p2p_address: {p2p_address}");
-    println!("metrics_url: {metrics_url}");
-}
-
-#[cfg(feature = "net")]
-fn required_option<'a>(values: &'a HashMap<String, String>, key: &str) -> &'a str {
-    values
-        .get(key)
-        .map(String::as_str)
-        .unwrap_or_else(|| fatal(&format!("{key} is required")))
-}
-
-#[cfg(feature = "net")]
-fn parse_u64_option(value: Option<&String>, default: u64, name: &str) -> u64 {
-    value.map_or(default, |raw| {
-        raw.parse()
-            .unwrap_or_else(|_| fatal(&format!("invalid {name}")))
-    })
-}
-
-#[cfg(feature = "net")]
-fn unix_seconds() -> u64 {
-    use std::time::{SystemTime, UNIX_EPOCH};
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs()
-}
-
-#[cfg(feature = "net")]
-fn read_json_file<T: serde::de::DeserializeOwned>(path: &Path, label: &str) -> T {
-    let contents = fs::read_to_string(path)
-        .unwrap_or_else(|err| fatal(&format!("failed to read {label} {}: {err}", path.display())));
-    serde_json::from_str(&contents)
-        .unwrap_or_else(|err| fatal(&format!("invalid {label} {}: {err}", path.display())))
+    let admitted = read_validator_policy(
+        policy
+            .as_deref()
+            .unwrap_or_else(|| fatal("assemble requires --policy")),
+    );
+    registry
+        .verify(&admitted, unix_seconds())
+        .unwrap_or_else(|err| fatal(&format!("validator registry verification failed: {err}")));
+    write_json_file(
+        output
+            .as_deref()
+            .unwrap_or_else(|| fatal("assemble requires --output")),
+        &registry,
+        "validator registry",
+    );
 }
 
 #[cfg(feature = "net")]
-fn write_json_file<T: serde::Serialize>(path: &Path, value: &T, label: &str) {
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).unwrap_or_else(|err| {
-            fatal(&format!(
-                "failed to create {} directory {}: {err}",
-                label,
-                parent.display()
-            ))
-        });
+fn cmd_validator_registry_verify(args: Vec<String>) {
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        print_validator_registry_help();
+        return;
     }
-    let bytes = serde_json::to_vec_pretty(value)
-        .unwrap_or_else(|err| fatal(&format!("failed to encode {label}: {err}")));
-    fs::write(path, [bytes, b"\n".to_vec()].concat()).unwrap_or_else(|err| {
-        fatal(&format!(
-            "failed to write {label} {}: {err}",
-            path.display()
-        ))
-    });
-}
-
-#[cfg(feature = "net")]
-fn read_validator_policy(path: &Path) -> std::collections::HashSet<String> {
-    #[derive(Deserialize)]
-    struct ValidatorPolicy {
-        allowlist: Vec<String>,
+    let mut registry_path = None;
+    let mut policy_path = None;
+    let mut now = unix_seconds();
+    let mut json = false;
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--policy" => {
+                policy_path = Some(PathBuf::from(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--policy expects a value")),
+                ))
+            }
+            "--now" => {
+                now = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--now expects a value"))
+                    .parse()
+                    .unwrap_or_else(|_| fatal("invalid --now"))
+            }
+            "--json" => json = true,
+            value if registry_path.is_none() => registry_path = Some(PathBuf::from(value)),
+            value => fatal(&format!("unknown argument: {value}")),
+        }
     }
-    let policy: ValidatorPolicy = read_json_file(path, "validator policy");
-    if policy.allowlist.is_empty() {
-        fatal("validator policy allowlist cannot be empty");
+    let registry = read_json_file::<ValidatorRegistry>(
+        registry_path
+            .as_deref()
+            .unwrap_or_else(|| fatal("verify requires a registry path")),
+        "validator registry",
+    );
+    let admitted = read_validator_policy(
+        policy_path
+            .as_deref()
+            .unwrap_or_else(|| fatal("verify requires --policy")),
+    );
+    registry
+        .verify(&admitted, now)
+        .unwrap_or_else(|err| fatal(&format!("validator registry verification failed: {err}")));
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "chain_id": registry.chain_id,
+                "registrations": registry.registrations,
+                "schema": registry.schema,
+                "validators_verified": registry.registrations.len(),
+                "verified": true,
+            })
+        );
+    } else {
+        println!(
+            "validator registry verified: {} admitted identities on chain {}",
+            registry.registrations.len(),
+            registry.chain_id
+        );
     }
-    policy.allowlist.into_iter().collect()
-}
-
-#[cfg(feature = "net")]
-fn load_registry(path: &Path) -> StakeRegistry {
-    StakeRegistry::load(path).unwrap_or_else(|err| {
-        fatal(&format!(
-            "failed to load stake registry {}: {err}",
-            path.display()
-        ))
-    })
 }
 
 #[cfg(feature = "net")]
-fn save_registry(path: &Path, reg: &StakeRegistry) {
-    reg.save(path).unwrap_or_else(|err| {
-        fatal(&format!(
-            "failed to save stake registry {}: {err}",
-            path.display()
-        ))
-    });
+fn handle_observer_registry(sub: &str, tail: Vec<String>) {
+    match sub {
+        "-h" | "--help" => print_observer_registry_help(),
+        "register" => cmd_observer_registry_register(tail),
+        "create" => cmd_observer_registry_create(tail),
+        "assemble" => cmd_observer_registry_assemble(tail),
+        "verify" => cmd_observer_registry_verify(tail),
+        _ => fatal(&format!("unknown observer-registry subcommand: {sub}")),
+    }
 }
 
 #[cfg(feature = "net")]
-fn cmd_stake_show(args: Vec<String>) {
-    if args.is_empty() {
-        eprintln!("Usage: julian stake show <stake_registry.json>");
-        std::process::exit(1);
+fn cmd_observer_registry_register(args: Vec<String>) {
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        print_observer_registry_help();
+        return;
     }
-    let path = Path::new(&args[0]);
-    match StakeRegistry::load(path) {
-        Ok(reg) => {
-            let pretty = serde_json::to_string_pretty(&reg)
-                .unwrap_or_else(|e| format!("{{\"error\":\"{e}\"}}"));
-            println!("{pretty}");
-        }
-        Err(err) => fatal(&format!(
-            "failed to load stake registry {}: {err}",
-            path.display()
-        )),
-    }
-}
+    let values = parse_registry_options(
+        args,
+        &[
+            "--key",
+            "--node-id",
+            "--operator",
+            "--region",
+            "--public-host",
+            "--host",
+            "--p2p-port",
+            "--metrics-port",
+            "--p2p-address",
+            "--metrics-url",
+            "--system-metrics-port",
+            "--system-metrics-url",
+            "--chain-id",
+            "--issued-at",
+            "--valid-until",
+            "--output",
+            "--registry",
+            "--registry-output",
+        ],
+    );
+    let registration = build_observer_registration(&values);
+    let output = values
+        .get("--output")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| default_registration_output(&registration.node_id, "observer"));
+    write_json_file(&output, &registration, "observer registration");
+    print_registration_summary(
+        "observer",
+        &output,
+        &registration.node_id,
+        &registration.peer_id,
+        &registration.p2p_address,
+        &registration.metrics_url,
+    );
 
-#[cfg(feature = "net")]
-fn cmd_stake_fund(args: Vec<String>) {
-    if args.len() < 3 {
-        eprintln!("Usage: julian stake fund <registry.json> <pubkey_b64> <amount>");
-        std::process::exit(1);
-    }
-    let path = Path::new(&args[0]);
-    let pk = &args[1];
-    let amount: u64 = args[2].parse().unwrap_or_else(|_| fatal("invalid amount"));
-    let mut reg = load_registry(path);
-    reg.fund_balance(pk, amount);
-    save_registry(path, &reg);
-    if let Some(acct) = reg.account(pk) {
-        println!(
-            "funded {pk} by {amount}, balance={} stake={}",
-            acct.balance, acct.stake
-        );
+    if values.contains_key("--registry") && !values.contains_key("--registry-output") {
+        fatal("--registry requires --registry-output so the assembled registry is explicit");
     }
-}
 
-#[cfg(feature = "net")]
-fn cmd_stake_bond(args: Vec<String>) {
-    refresh_migration_mode_from_env();
-    if power_house::net::migration_mode_frozen() {
-        fatal("migration freeze active: stake bonding is disabled");
-    }
-    if args.len() < 3 {
-        eprintln!("Usage: julian stake bond <registry.json> <pubkey_b64> <amount>");
-        std::process::exit(1);
-    }
-    let path = Path::new(&args[0]);
-    let pk = &args[1];
-    let amount: u64 = args[2].parse().unwrap_or_else(|_| fatal("invalid amount"));
-    let mut reg = load_registry(path);
-    reg.bond_from_balance(pk, amount)
-        .unwrap_or_else(|err| fatal(&err));
-    save_registry(path, &reg);
-    if let Some(acct) = reg.account(pk) {
+    if let Some(registry_output) = values.get("--registry-output") {
+        let mut registrations = values
+            .get("--registry")
+            .map(|path| read_json_file::<ObserverRegistry>(Path::new(path), "observer registry"))
+            .map(|registry| {
+                if registry.chain_id != registration.chain_id {
+                    fatal("existing registry chain ID differs from the new registration");
+                }
+                registry.registrations
+            })
+            .unwrap_or_default();
+        registrations.retain(|entry| {
+            entry.node_id != registration.node_id
+                && entry.public_key_b64 != registration.public_key_b64
+                && entry.peer_id != registration.peer_id
+        });
+        registrations.push(registration.clone());
+        let registry = ObserverRegistry {
+            schema: OBSERVER_REGISTRY_SCHEMA.to_string(),
+            chain_id: registration.chain_id,
+            registrations,
+        };
+        registry
+            .verify(unix_seconds())
+            .unwrap_or_else(|err| fatal(&format!("observer registry verification failed: {err}")));
+        write_json_file(Path::new(registry_output), &registry, "observer registry");
+        println!("observer registry: {registry_output}");
         println!(
-            "bonded {amount} for {pk}, balance={} stake={}",
-            acct.balance, acct.stake
+            "verified observer identities: {}",
+            registry.registrations.len()
         );
+    } else {
+        println!("next: upload the signed registration on mfenx.com/register.html or send it for observer registry assembly");
     }
 }
 
 #[cfg(feature = "net")]
-fn cmd_stake_snapshot(args: Vec<String>) {
-    if args.iter().any(|a| a == "-h" || a == "--help") {
-        println!("Usage: julian stake snapshot --registry <path> --height <N> --output <file>");
+fn cmd_observer_registry_create(args: Vec<String>) {
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        print_observer_registry_help();
         return;
     }
-
-    let mut registry_path: Option<String> = None;
-    let mut height: Option<u64> = None;
-    let mut output: Option<String> = None;
-
+    let mut values = HashMap::new();
     let mut iter = args.into_iter();
-    while let Some(arg) = iter.next() {
-        match arg.as_str() {
-            "--registry" => {
-                registry_path = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--registry expects a value")),
-                );
-            }
-            "--height" => {
-                let raw = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--height expects a value"));
-                height = Some(
-                    raw.parse::<u64>()
-                        .unwrap_or_else(|_| fatal("invalid --height")),
-                );
-            }
-            "--output" => {
-                output = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--output expects a value")),
-                );
-            }
-            other => fatal(&format!("unknown argument: {other}")),
+    while let Some(flag) = iter.next() {
+        let value = iter
+            .next()
+            .unwrap_or_else(|| fatal(&format!("{flag} expects a value")));
+        if !matches!(
+            flag.as_str(),
+            "--key"
+                | "--node-id"
+                | "--operator"
+                | "--region"
+                | "--p2p-address"
+                | "--metrics-url"
+                | "--system-metrics-url"
+                | "--chain-id"
+                | "--issued-at"
+                | "--valid-until"
+                | "--output"
+        ) {
+            fatal(&format!("unknown argument: {flag}"));
+        }
+        if values.insert(flag.clone(), value).is_some() {
+            fatal(&format!("duplicate argument: {flag}"));
         }
     }
 
-    let registry_path = registry_path.unwrap_or_else(|| fatal("--registry is required"));
-    let height = height.unwrap_or_else(|| fatal("--height is required"));
-    let output = output.unwrap_or_else(|| fatal("--output is required"));
-
-    let root = run_snapshot(&registry_path, height, &output)
-        .unwrap_or_else(|err| fatal(&format!("snapshot failed: {err}")));
-    println!("snapshot root: {root}");
-    println!("artifact: {output}");
+    let now = unix_seconds();
+    let chain_id = parse_u64_option(
+        values.get("--chain-id"),
+        power_house::active_genesis().chain_id,
+        "--chain-id",
+    );
+    let issued_at = parse_u64_option(values.get("--issued-at"), now, "--issued-at");
+    let valid_until = parse_u64_option(
+        values.get("--valid-until"),
+        issued_at.saturating_add(365 * 24 * 60 * 60),
+        "--valid-until",
+    );
+    let key = required_option(&values, "--key");
+    let material = load_or_derive_keypair(&Ed25519KeySource::from_spec(Some(key)))
+        .unwrap_or_else(|err| fatal(&format!("failed to load observer key: {err}")));
+    let registration = ObserverRegistration::sign(
+        chain_id,
+        required_option(&values, "--node-id").to_string(),
+        required_option(&values, "--operator").to_string(),
+        required_option(&values, "--region").to_string(),
+        required_option(&values, "--p2p-address").to_string(),
+        required_option(&values, "--metrics-url").to_string(),
+        values.get("--system-metrics-url").cloned(),
+        issued_at,
+        valid_until,
+        &material,
+    )
+    .unwrap_or_else(|err| fatal(&format!("failed to create observer registration: {err}")));
+    write_json_file(
+        Path::new(required_option(&values, "--output")),
+        &registration,
+        "observer registration",
+    );
 }
 
 #[cfg(feature = "net")]
-fn cmd_stake_claims(args: Vec<String>) {
-    if args.iter().any(|a| a == "-h" || a == "--help") {
-        println!("Usage: julian stake claims --snapshot <file> --output <file> [options]");
-        println!("  [--mode native|erc20]");
-        println!("  [--amount-source stake|balance|total] [--include-slashed]");
-        println!("  [--conversion-ratio <u64>] [--claim-id-salt <text>]");
-        println!("  [--token-contract <id>] [--snapshot-height <u64>]");
+fn cmd_observer_registry_assemble(args: Vec<String>) {
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        print_observer_registry_help();
         return;
     }
-
-    let mut snapshot: Option<String> = None;
-    let mut output: Option<String> = None;
-    let mut claim_mode = String::from("native");
-    let mut amount_source = String::from("total");
-    let mut include_slashed = false;
-    let mut conversion_ratio: u64 = 1;
-    let mut claim_id_salt = String::from("mfenx-migration-claim-v1");
-    let mut token_contract: Option<String> = None;
-    let mut snapshot_height_override: Option<u64> = None;
-
+    let mut registrations = Vec::new();
+    let mut output = None;
+    let mut chain_id = power_house::active_genesis().chain_id;
     let mut iter = args.into_iter();
     while let Some(arg) = iter.next() {
         match arg.as_str() {
-            "--snapshot" => {
-                snapshot = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--snapshot expects a value")),
-                );
-            }
+            "--registration" => registrations.push(PathBuf::from(
+                iter.next()
+                    .unwrap_or_else(|| fatal("--registration expects a value")),
+            )),
             "--output" => {
-                output = Some(
+                output = Some(PathBuf::from(
                     iter.next()
                         .unwrap_or_else(|| fatal("--output expects a value")),
-                );
+                ))
             }
-            "--mode" => {
-                claim_mode = iter
+            "--chain-id" => {
+                chain_id = iter
                     .next()
-                    .unwrap_or_else(|| fatal("--mode expects a value"));
+                    .unwrap_or_else(|| fatal("--chain-id expects a value"))
+                    .parse()
+                    .unwrap_or_else(|_| fatal("invalid --chain-id"))
             }
-            "--amount-source" => {
-                amount_source = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--amount-source expects a value"));
-            }
-            "--include-slashed" => {
-                include_slashed = true;
-            }
-            "--conversion-ratio" => {
-                let raw = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--conversion-ratio expects a value"));
-                conversion_ratio = raw
-                    .parse::<u64>()
-                    .unwrap_or_else(|_| fatal("invalid --conversion-ratio"));
-            }
-            "--claim-id-salt" => {
-                claim_id_salt = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--claim-id-salt expects a value"));
-            }
-            "--token-contract" => {
-                token_contract = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--token-contract expects a value")),
-                );
-            }
-            "--snapshot-height" => {
-                let raw = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--snapshot-height expects a value"));
-                snapshot_height_override = Some(
-                    raw.parse::<u64>()
-                        .unwrap_or_else(|_| fatal("invalid --snapshot-height")),
-                );
-            }
-            other => fatal(&format!("unknown argument: {other}")),
+            value => fatal(&format!("unknown argument: {value}")),
         }
     }
-
-    let snapshot = snapshot.unwrap_or_else(|| fatal("--snapshot is required"));
-    let output = output.unwrap_or_else(|| fatal("--output is required"));
-    let opts = BuildClaimsOptions {
-        claim_mode,
-        amount_source,
-        include_slashed,
-        conversion_ratio,
-        claim_id_salt,
-        token_contract,
-        snapshot_height_override,
+    if registrations.is_empty() {
+        fatal("assemble requires at least one --registration");
+    }
+    let entries = registrations
+        .iter()
+        .map(|path| read_json_file::<ObserverRegistration>(path, "observer registration"))
+        .collect();
+    let registry = ObserverRegistry {
+        schema: OBSERVER_REGISTRY_SCHEMA.to_string(),
+        chain_id,
+        registrations: entries,
     };
-
-    let root = run_build_claims(&snapshot, &output, &opts)
-        .unwrap_or_else(|err| fatal(&format!("claim build failed: {err}")));
-    println!("claims root: {root}");
-    println!("artifact: {output}");
+    registry
+        .verify(unix_seconds())
+        .unwrap_or_else(|err| fatal(&format!("observer registry verification failed: {err}")));
+    write_json_file(
+        output
+            .as_deref()
+            .unwrap_or_else(|| fatal("assemble requires --output")),
+        &registry,
+        "observer registry",
+    );
 }
 
 #[cfg(feature = "net")]
-fn cmd_stake_apply_claims(args: Vec<String>) {
-    if args.iter().any(|a| a == "-h" || a == "--help") {
-        println!("Usage: julian stake apply-claims --registry <file> --claims <file> [options]");
-        println!("  [--state <file>] [--dry-run]");
+fn cmd_observer_registry_verify(args: Vec<String>) {
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        print_observer_registry_help();
         return;
     }
-
-    let mut registry: Option<String> = None;
-    let mut claims: Option<String> = None;
-    let mut state_path: Option<String> = None;
-    let mut dry_run = false;
-
+    let mut registry_path = None;
+    let mut now = unix_seconds();
+    let mut json = false;
     let mut iter = args.into_iter();
     while let Some(arg) = iter.next() {
         match arg.as_str() {
-            "--registry" => {
-                registry = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--registry expects a value")),
-                );
-            }
-            "--claims" => {
-                claims = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--claims expects a value")),
-                );
-            }
-            "--state" => {
-                state_path = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--state expects a value")),
-                );
-            }
-            "--dry-run" => {
-                dry_run = true;
+            "--now" => {
+                now = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--now expects a value"))
+                    .parse()
+                    .unwrap_or_else(|_| fatal("invalid --now"))
             }
-            other => fatal(&format!("unknown argument: {other}")),
+            "--json" => json = true,
+            value if registry_path.is_none() => registry_path = Some(PathBuf::from(value)),
+            value => fatal(&format!("unknown argument: {value}")),
         }
     }
-
-    let registry = registry.unwrap_or_else(|| fatal("--registry is required"));
-    let claims = claims.unwrap_or_else(|| fatal("--claims is required"));
-    let opts = ApplyClaimsOptions {
-        state_path,
-        dry_run,
-    };
-
-    let summary = run_apply_claims(&registry, &claims, &opts)
-        .unwrap_or_else(|err| fatal(&format!("apply-claims failed: {err}")));
-    println!("applied: {}", summary.applied);
-    println!("skipped: {}", summary.skipped);
-    println!("total_mint_amount: {}", summary.total_mint_amount);
-    println!("state: {}", summary.state_path);
-    if dry_run {
-        println!("dry_run: true");
+    let registry = read_json_file::<ObserverRegistry>(
+        registry_path
+            .as_deref()
+            .unwrap_or_else(|| fatal("verify requires a registry path")),
+        "observer registry",
+    );
+    registry
+        .verify(now)
+        .unwrap_or_else(|err| fatal(&format!("observer registry verification failed: {err}")));
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "chain_id": registry.chain_id,
+                "registrations": registry.registrations,
+                "schema": registry.schema,
+                "observers_verified": registry.registrations.len(),
+                "verified": true,
+            })
+        );
+    } else {
+        println!(
+            "observer registry verified: {} signed identities on chain {}",
+            registry.registrations.len(),
+            registry.chain_id
+        );
     }
 }
 
 #[cfg(feature = "net")]
-fn cmd_migration_finalize(args: Vec<String>) {
-    if args.iter().any(|a| a == "-h" || a == "--help") {
-        print_migration_help();
-        return;
+fn parse_registry_options(args: Vec<String>, allowed: &[&str]) -> HashMap<String, String> {
+    let mut values = HashMap::new();
+    let mut iter = args.into_iter();
+    while let Some(flag) = iter.next() {
+        if !flag.starts_with("--") {
+            fatal(&format!("unexpected positional argument: {flag}"));
+        }
+        if !allowed.contains(&flag.as_str()) {
+            fatal(&format!("unknown argument: {flag}"));
+        }
+        let value = iter
+            .next()
+            .unwrap_or_else(|| fatal(&format!("{flag} expects a value")));
+        if value.starts_with("--") {
+            fatal(&format!("{flag} expects a value"));
+        }
+        if values.insert(flag.clone(), value).is_some() {
+            fatal(&format!("duplicate argument: {flag}"));
+        }
     }
+    values
+}
 
-    let mut registry_path: Option<String> = None;
-    let mut snapshot_height: Option<u64> = None;
-    let mut log_dir: Option<String> = None;
-    let mut output_dir: Option<String> = None;
-    let mut token_contract = String::from("native://julian");
-    let mut conversion_ratio: u64 = 1;
-    let mut treasury_mint: u64 = 0;
-    let mut amount_source = String::from("total");
-    let mut include_slashed = false;
-    let mut claim_id_salt = String::from("mfenx-migration-claim-v1");
-    let mut node_id = String::from("migration-finalize");
-    let mut quorum: usize = 1;
-    let mut apply_state_path: Option<String> = None;
-    let mut allow_unfrozen = false;
-    let mut force = false;
+#[cfg(feature = "net")]
+fn build_validator_registration(values: &HashMap<String, String>) -> ValidatorRegistration {
+    let input = registration_input(values, "validator");
+    ValidatorRegistration::sign(
+        input.chain_id,
+        input.node_id,
+        input.operator,
+        input.region,
+        input.p2p_address,
+        input.metrics_url,
+        input.system_metrics_url,
+        input.issued_at,
+        input.valid_until,
+        &input.material,
+    )
+    .unwrap_or_else(|err| fatal(&format!("failed to create validator registration: {err}")))
+}
 
-    let mut iter = args.into_iter();
-    while let Some(arg) = iter.next() {
-        match arg.as_str() {
-            "--registry" => {
-                registry_path = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--registry expects a value")),
-                );
-            }
-            "--height" => {
-                let raw = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--height expects a value"));
-                snapshot_height = Some(raw.parse().unwrap_or_else(|_| fatal("invalid --height")));
-            }
-            "--log-dir" => {
-                log_dir = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--log-dir expects a value")),
-                );
-            }
-            "--output-dir" => {
-                output_dir = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--output-dir expects a value")),
-                );
-            }
-            "--token-contract" => {
-                token_contract = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--token-contract expects a value"));
-            }
-            "--conversion-ratio" => {
-                let raw = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--conversion-ratio expects a value"));
-                conversion_ratio = raw
-                    .parse::<u64>()
-                    .unwrap_or_else(|_| fatal("invalid --conversion-ratio"));
-            }
-            "--treasury-mint" => {
-                let raw = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--treasury-mint expects a value"));
-                treasury_mint = raw
-                    .parse::<u64>()
-                    .unwrap_or_else(|_| fatal("invalid --treasury-mint"));
-            }
-            "--amount-source" => {
-                amount_source = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--amount-source expects a value"));
-            }
-            "--include-slashed" => {
-                include_slashed = true;
-            }
-            "--claim-id-salt" => {
-                claim_id_salt = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--claim-id-salt expects a value"));
-            }
-            "--node-id" => {
-                node_id = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--node-id expects a value"));
-            }
-            "--quorum" => {
-                let raw = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--quorum expects a value"));
-                quorum = raw
-                    .parse::<usize>()
-                    .unwrap_or_else(|_| fatal("invalid --quorum"));
-            }
-            "--apply-state" => {
-                apply_state_path = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--apply-state expects a value")),
-                );
-            }
-            "--allow-unfrozen" => {
-                allow_unfrozen = true;
-            }
-            "--force" => {
-                force = true;
-            }
-            other => fatal(&format!("unknown argument: {other}")),
-        }
-    }
-
-    let opts = FinalizeMigrationOptions {
-        registry_path: registry_path.unwrap_or_else(|| fatal("--registry is required")),
-        snapshot_height: snapshot_height.unwrap_or_else(|| fatal("--height is required")),
-        log_dir: log_dir.unwrap_or_else(|| fatal("--log-dir is required")),
-        output_dir: output_dir.unwrap_or_else(|| fatal("--output-dir is required")),
-        token_contract,
-        conversion_ratio,
-        treasury_mint,
-        amount_source,
-        include_slashed,
-        claim_id_salt,
-        node_id,
-        quorum,
-        apply_state_path,
-        allow_unfrozen,
-        force,
-    };
+#[cfg(feature = "net")]
+fn build_observer_registration(values: &HashMap<String, String>) -> ObserverRegistration {
+    let input = registration_input(values, "observer");
+    ObserverRegistration::sign(
+        input.chain_id,
+        input.node_id,
+        input.operator,
+        input.region,
+        input.p2p_address,
+        input.metrics_url,
+        input.system_metrics_url,
+        input.issued_at,
+        input.valid_until,
+        &input.material,
+    )
+    .unwrap_or_else(|err| fatal(&format!("failed to create observer registration: {err}")))
+}
 
-    let summary = run_finalize_migration(&opts)
-        .unwrap_or_else(|err| fatal(&format!("migration finalize failed: {err}")));
-    println!("snapshot_root: {}", summary.snapshot_root);
-    println!("claims_root: {}", summary.claims_root);
-    println!("applied_claims: {}", summary.applied_claims);
-    println!("skipped_claims: {}", summary.skipped_claims);
-    println!("snapshot: {}", summary.snapshot_path);
-    println!("claims: {}", summary.claims_path);
-    println!("apply_state: {}", summary.apply_state_path);
-    println!("proposal: {}", summary.proposal_path);
+#[cfg(feature = "net")]
+struct RegistrationInput {
+    chain_id: u64,
+    node_id: String,
+    operator: String,
+    region: String,
+    p2p_address: String,
+    metrics_url: String,
+    system_metrics_url: Option<String>,
+    issued_at: u64,
+    valid_until: u64,
+    material: power_house::net::KeyMaterial,
 }
 
 #[cfg(feature = "net")]
-fn cmd_migration_verify_state(args: Vec<String>) {
-    if args.iter().any(|a| a == "-h" || a == "--help") {
-        print_migration_help();
-        return;
-    }
+fn registration_input(values: &HashMap<String, String>, label: &str) -> RegistrationInput {
+    let now = unix_seconds();
+    let chain_id = parse_u64_option(
+        values.get("--chain-id"),
+        power_house::active_genesis().chain_id,
+        "--chain-id",
+    );
+    let issued_at = parse_u64_option(values.get("--issued-at"), now, "--issued-at");
+    let valid_until = parse_u64_option(
+        values.get("--valid-until"),
+        issued_at.saturating_add(365 * 24 * 60 * 60),
+        "--valid-until",
+    );
+    let node_id = required_option(values, "--node-id").to_string();
+    let operator = values
+        .get("--operator")
+        .cloned()
+        .unwrap_or_else(|| node_id.clone());
+    let region = values
+        .get("--region")
+        .cloned()
+        .unwrap_or_else(|| "self-hosted".to_string());
+    let key_spec = values
+        .get("--key")
+        .cloned()
+        .unwrap_or_else(default_node_key_spec);
+    let material = load_or_derive_keypair(&Ed25519KeySource::from_spec(Some(&key_spec)))
+        .unwrap_or_else(|err| fatal(&format!("failed to load {label} key {key_spec}: {err}")));
+    let peer_id = material.libp2p.public().to_peer_id().to_string();
 
-    let mut registry: Option<String> = None;
-    let mut claims: Option<String> = None;
-    let mut state: Option<String> = None;
-    let mut require_complete = false;
-    let mut enforce_balance_floor = true;
+    let public_host = registry_public_host(values);
+    let p2p_address = values.get("--p2p-address").cloned().unwrap_or_else(|| {
+        let host = public_host
+            .clone()
+            .unwrap_or_else(|| fatal("--public-host is required unless --p2p-address is provided"));
+        build_p2p_address(
+            &host,
+            parse_port_option(values.get("--p2p-port"), 7001, "--p2p-port"),
+            &peer_id,
+        )
+    });
+    let metrics_url = values.get("--metrics-url").cloned().unwrap_or_else(|| {
+        let host = public_host
+            .as_deref()
+            .unwrap_or_else(|| fatal("--public-host is required unless --metrics-url is provided"));
+        build_metrics_url(
+            host,
+            parse_port_option(values.get("--metrics-port"), 9100, "--metrics-port"),
+        )
+    });
+    let system_metrics_url = values.get("--system-metrics-url").cloned().or_else(|| {
+        values.get("--system-metrics-port").map(|_| {
+            let host = public_host.as_deref().unwrap_or_else(|| {
+                fatal("--public-host is required when --system-metrics-port is used")
+            });
+            build_metrics_url(
+                host,
+                parse_port_option(
+                    values.get("--system-metrics-port"),
+                    9101,
+                    "--system-metrics-port",
+                ),
+            )
+        })
+    });
 
-    let mut iter = args.into_iter();
-    while let Some(arg) = iter.next() {
-        match arg.as_str() {
-            "--registry" => {
-                registry = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--registry expects a value")),
-                );
-            }
-            "--claims" => {
-                claims = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--claims expects a value")),
-                );
-            }
-            "--state" => {
-                state = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--state expects a value")),
-                );
-            }
-            "--require-complete" => {
-                require_complete = true;
-            }
-            "--skip-balance-floor" => {
-                enforce_balance_floor = false;
-            }
-            other => fatal(&format!("unknown argument: {other}")),
-        }
+    RegistrationInput {
+        chain_id,
+        node_id,
+        operator,
+        region,
+        p2p_address,
+        metrics_url,
+        system_metrics_url,
+        issued_at,
+        valid_until,
+        material,
     }
+}
 
-    let summary = run_verify_state(
-        &registry.unwrap_or_else(|| fatal("--registry is required")),
-        &claims.unwrap_or_else(|| fatal("--claims is required")),
-        &state.unwrap_or_else(|| fatal("--state is required")),
-        &VerifyStateOptions {
-            require_complete,
-            enforce_balance_floor,
-        },
-    )
-    .unwrap_or_else(|err| fatal(&format!("migration verify-state failed: {err}")));
-
-    println!("claim_count: {}", summary.claim_count);
-    println!("applied_count: {}", summary.applied_count);
-    println!("missing_count: {}", summary.missing_count);
-    println!("unknown_count: {}", summary.unknown_count);
-    println!("applied_total_mint: {}", summary.applied_total_mint);
+#[cfg(feature = "net")]
+fn registry_public_host(values: &HashMap<String, String>) -> Option<String> {
+    values
+        .get("--public-host")
+        .or_else(|| values.get("--host"))
+        .map(|host| normalize_public_host(host))
 }
 
 #[cfg(feature = "net")]
-fn cmd_migration_execute_burn_intents(args: Vec<String>) {
-    if args.iter().any(|a| a == "-h" || a == "--help") {
-        print_migration_help();
-        return;
-    }
+fn default_node_key_spec() -> String {
+    env::var("POWERHOUSE_NODE_KEY").unwrap_or_else(|_| {
+        env::var("HOME")
+            .map(|home| format!("{home}/.powerhouse/node.key"))
+            .unwrap_or_else(|_| "$HOME/.powerhouse/node.key".to_string())
+    })
+}
 
-    let mut registry: Option<String> = None;
-    let mut outbox: Option<String> = None;
-    let mut state: Option<String> = None;
-    let mut dry_run = false;
+#[cfg(feature = "net")]
+fn default_registration_output(node_id: &str, kind: &str) -> PathBuf {
+    PathBuf::from(format!("{node_id}.{kind}.registration.json"))
+}
 
-    let mut iter = args.into_iter();
-    while let Some(arg) = iter.next() {
-        match arg.as_str() {
-            "--registry" => {
-                registry = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--registry expects a value")),
-                );
-            }
-            "--outbox" => {
-                outbox = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--outbox expects a value")),
-                );
-            }
-            "--state" => {
-                state = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--state expects a value")),
-                );
-            }
-            "--dry-run" => {
-                dry_run = true;
-            }
-            other => fatal(&format!("unknown argument: {other}")),
+#[cfg(feature = "net")]
+fn parse_port_option(value: Option<&String>, default: u16, name: &str) -> u16 {
+    value.map_or(default, |raw| {
+        let port: u16 = raw
+            .parse()
+            .unwrap_or_else(|_| fatal(&format!("invalid {name}")));
+        if port == 0 {
+            fatal(&format!("{name} must be between 1 and 65535"));
         }
-    }
-
-    let registry = registry.unwrap_or_else(|| fatal("--registry is required"));
-    let outbox = outbox.unwrap_or_else(|| {
-        let parent = Path::new(&registry)
-            .parent()
-            .unwrap_or_else(|| Path::new("."));
-        parent.join("token_burn_outbox.jsonl").display().to_string()
-    });
-
-    let summary = run_execute_burn_intents(
-        &registry,
-        &outbox,
-        &ExecuteBurnOptions {
-            state_path: state,
-            dry_run,
-        },
-    )
-    .unwrap_or_else(|err| fatal(&format!("migration execute-burn-intents failed: {err}")));
-
-    println!("processed: {}", summary.processed);
-    println!("skipped: {}", summary.skipped);
-    println!("native_executed: {}", summary.native_executed);
-    println!("unsupported_mode: {}", summary.unsupported_mode);
-    println!("state: {}", summary.state_path);
-    if dry_run {
-        println!("dry_run: true");
-    }
+        port
+    })
 }
 
 #[cfg(feature = "net")]
-fn cmd_governance_propose_migration(args: Vec<String>) {
-    if args.iter().any(|a| a == "-h" || a == "--help") {
-        println!("Usage: julian governance propose-migration \\");
-        println!("  --snapshot-height <N> [--token-contract <id>] \\");
-        println!("  [--conversion-ratio <u64>] [--treasury-mint <u64>] \\");
-        println!("  --log-dir <dir> [--node-id <id>] [--quorum <N>] [--output <file>]");
-        return;
+fn normalize_public_host(host: &str) -> String {
+    let trimmed = host
+        .trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .trim_end_matches('.');
+    if trimmed.is_empty() {
+        fatal("--public-host cannot be empty");
     }
-
-    let mut snapshot_height: Option<u64> = None;
-    let mut token_contract: Option<String> = None;
-    let mut conversion_ratio: u64 = 1;
-    let mut treasury_mint: u64 = 0;
-    let mut log_dir: Option<String> = None;
-    let mut node_id: String = "migration-governance".to_string();
-    let mut quorum: usize = 1;
-    let mut output: Option<String> = None;
-
-    let mut iter = args.into_iter();
-    while let Some(arg) = iter.next() {
-        match arg.as_str() {
-            "--snapshot-height" => {
-                let raw = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--snapshot-height expects a value"));
-                snapshot_height = Some(
-                    raw.parse::<u64>()
-                        .unwrap_or_else(|_| fatal("invalid --snapshot-height")),
-                );
-            }
-            "--token-contract" => {
-                token_contract = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--token-contract expects a value")),
-                );
-            }
-            "--conversion-ratio" => {
-                let raw = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--conversion-ratio expects a value"));
-                conversion_ratio = raw
-                    .parse::<u64>()
-                    .unwrap_or_else(|_| fatal("invalid --conversion-ratio"));
-            }
-            "--treasury-mint" => {
-                let raw = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--treasury-mint expects a value"));
-                treasury_mint = raw
-                    .parse::<u64>()
-                    .unwrap_or_else(|_| fatal("invalid --treasury-mint"));
-            }
-            "--log-dir" => {
-                log_dir = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--log-dir expects a value")),
-                );
-            }
-            "--node-id" => {
-                node_id = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--node-id expects a value"));
-            }
-            "--quorum" => {
-                let raw = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--quorum expects a value"));
-                quorum = raw
-                    .parse::<usize>()
-                    .unwrap_or_else(|_| fatal("invalid --quorum"));
-            }
-            "--output" => {
-                output = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--output expects a value")),
-                );
-            }
-            other => fatal(&format!("unknown argument: {other}")),
-        }
+    if trimmed.contains("://") || trimmed.contains('/') {
+        fatal("--public-host must be a bare DNS name, IPv4 address, or IPv6 address");
     }
-
-    let snapshot_height = snapshot_height.unwrap_or_else(|| fatal("--snapshot-height is required"));
-    let token_contract = token_contract
-        .or_else(|| std::env::var("PH_MIGRATION_TOKEN_ID").ok())
-        .unwrap_or_else(|| "native://julian".to_string());
-    let log_dir = log_dir.unwrap_or_else(|| fatal("--log-dir is required"));
-
-    let encoded = run_propose_migration(&ProposeMigrationOptions {
-        snapshot_height,
-        token_contract,
-        conversion_ratio,
-        treasury_mint,
-        log_dir,
-        node_id,
-        quorum,
-        output: output.clone(),
-    })
-    .unwrap_or_else(|err| fatal(&format!("propose-migration failed: {err}")));
-
-    if let Some(path) = output {
-        println!("wrote migration proposal artifact to {path}");
+    if trimmed.parse::<Ipv4Addr>().is_ok() || trimmed.parse::<Ipv6Addr>().is_ok() {
+        trimmed.to_string()
     } else {
-        println!("{encoded}");
+        trimmed.to_ascii_lowercase()
     }
 }
 
 #[cfg(feature = "net")]
-fn cmd_stake_unbond(args: Vec<String>) {
-    if args.len() < 3 {
-        eprintln!("Usage: julian stake unbond <registry.json> <pubkey_b64> <amount>");
-        std::process::exit(1);
-    }
-    let path = Path::new(&args[0]);
-    let pk = &args[1];
-    let amount: u64 = args[2].parse().unwrap_or_else(|_| fatal("invalid amount"));
-    let mut reg = load_registry(path);
-    reg.unbond(pk, amount).unwrap_or_else(|err| fatal(&err));
-    save_registry(path, &reg);
-    if let Some(acct) = reg.account(pk) {
-        println!(
-            "unbonded {amount} for {pk}, balance={} stake={}",
-            acct.balance, acct.stake
-        );
-    }
+fn build_p2p_address(host: &str, port: u16, peer_id: &str) -> String {
+    let protocol = if host.parse::<Ipv4Addr>().is_ok() {
+        format!("/ip4/{host}")
+    } else if host.parse::<Ipv6Addr>().is_ok() {
+        format!("/ip6/{host}")
+    } else {
+        format!("/dns4/{host}")
+    };
+    format!("{protocol}/tcp/{port}/p2p/{peer_id}")
 }
 
 #[cfg(feature = "net")]
-fn cmd_stake_reward(args: Vec<String>) {
-    if args.len() < 3 {
-        eprintln!("Usage: julian stake reward <registry.json> <pubkey_b64> <amount>");
-        std::process::exit(1);
-    }
-    let path = Path::new(&args[0]);
-    let pk = &args[1];
-    let amount: u64 = args[2].parse().unwrap_or_else(|_| fatal("invalid amount"));
-    let mut reg = load_registry(path);
-    reg.credit_reward(pk, amount);
-    save_registry(path, &reg);
-    if let Some(acct) = reg.account(pk) {
-        println!(
-            "rewarded {pk} by {amount}, balance={} stake={}",
-            acct.balance, acct.stake
-        );
-    }
+fn build_metrics_url(host: &str, port: u16) -> String {
+    let host_for_url = if host.parse::<Ipv6Addr>().is_ok() {
+        format!("[{host}]")
+    } else {
+        host.to_string()
+    };
+    format!("http://{host_for_url}:{port}/metrics")
 }
 
 #[cfg(feature = "net")]
-fn cmd_rollup_settle(args: Vec<String>) {
-    if args.len() < 5 {
-        eprintln!("Usage: julian rollup settle <registry.json> <namespace> <share_root> <payer_b64> <fee> [zk|optimistic] [operator_b64] [attesters_csv] [--proof file] [--public-inputs file] [--merkle-path file] [--outbox path]");
-        std::process::exit(1);
-    }
-    let registry = Path::new(&args[0]);
-    let namespace = args[1].clone();
-    let share_root = args[2].clone();
-    let payer = args[3].clone();
-    let fee: u64 = args[4].parse().unwrap_or_else(|_| fatal("invalid fee"));
+fn print_registration_summary(
+    kind: &str,
+    output: &Path,
+    node_id: &str,
+    peer_id: &str,
+    p2p_address: &str,
+    metrics_url: &str,
+) {
+    println!("{kind} registration: {}", output.display());
+    println!("node_id: {node_id}");
+    println!("peer_id: {peer_id}");
+    println!("p2p_address: {p2p_address}");
+    println!("metrics_url: {metrics_url}");
+}
 
-    let mut mode = "optimistic".to_string();
-    let mut operator_pk: Option<String> = None;
-    let mut attesters: Vec<String> = Vec::new();
-    let mut proof_path: Option<String> = None;
-    let mut public_inputs_path: Option<String> = None;
-    let mut merkle_path_file: Option<String> = None;
-    let mut outbox: Option<String> = None;
+#[cfg(feature = "net")]
+fn required_option<'a>(values: &'a HashMap<String, String>, key: &str) -> &'a str {
+    values
+        .get(key)
+        .map(String::as_str)
+        .unwrap_or_else(|| fatal(&format!("{key} is required")))
+}
 
-    for arg in args.iter().skip(5) {
-        if arg.starts_with("--proof=") {
-            proof_path = Some(arg.trim_start_matches("--proof=").to_string());
-        } else if arg.starts_with("--public-inputs=") {
-            public_inputs_path = Some(arg.trim_start_matches("--public-inputs=").to_string());
-        } else if arg.starts_with("--merkle-path=") {
-            merkle_path_file = Some(arg.trim_start_matches("--merkle-path=").to_string());
-        } else if arg.starts_with("--outbox=") {
-            outbox = Some(arg.trim_start_matches("--outbox=").to_string());
-        } else if mode == "optimistic" && (arg == "zk" || arg == "optimistic") {
-            mode = arg.clone();
-        } else if operator_pk.is_none() {
-            operator_pk = Some(arg.clone());
-        } else if attesters.is_empty() {
-            attesters = arg
-                .split(',')
-                .filter(|v| !v.is_empty())
-                .map(|v| v.to_string())
-                .collect();
-        }
+#[cfg(feature = "net")]
+fn parse_u64_option(value: Option<&String>, default: u64, name: &str) -> u64 {
+    value.map_or(default, |raw| {
+        raw.parse()
+            .unwrap_or_else(|_| fatal(&format!("invalid {name}")))
+    })
+}
+
+#[cfg(feature = "net")]
+fn unix_seconds() -> u64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(feature = "net")]
+fn read_json_file<T: serde::de::DeserializeOwned>(path: &Path, label: &str) -> T {
+    let contents = fs::read_to_string(path)
+        .unwrap_or_else(|err| fatal(&format!("failed to read {label} {}: {err}", path.display())));
+    serde_json::from_str(&contents)
+        .unwrap_or_else(|err| fatal(&format!("invalid {label} {}: {err}", path.display())))
+}
+
+#[cfg(feature = "net")]
+fn write_json_file<T: serde::Serialize>(path: &Path, value: &T, label: &str) {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).unwrap_or_else(|err| {
+            fatal(&format!(
+                "failed to create {} directory {}: {err}",
+                label,
+                parent.display()
+            ))
+        });
     }
+    let bytes = serde_json::to_vec_pretty(value)
+        .unwrap_or_else(|err| fatal(&format!("failed to encode {label}: {err}")));
+    fs::write(path, [bytes, b"\n".to_vec()].concat()).unwrap_or_else(|err| {
+        fatal(&format!(
+            "failed to write {label} {}: {err}",
+            path.display()
+        ))
+    });
+}
 
-    let operator_pk = operator_pk.unwrap_or_else(|| payer.clone());
-    let commitment = power_house::rollup::RollupCommitment {
-        namespace,
-        share_root,
-        pedersen_root: None,
-        settlement_slot: None,
-    };
+#[cfg(feature = "net")]
+fn read_validator_policy(path: &Path) -> std::collections::HashSet<String> {
+    #[derive(Deserialize)]
+    struct ValidatorPolicy {
+        allowlist: Vec<String>,
+    }
+    let policy: ValidatorPolicy = read_json_file(path, "validator policy");
+    if policy.allowlist.is_empty() {
+        fatal("validator policy allowlist cannot be empty");
+    }
+    policy.allowlist.into_iter().collect()
+}
 
-    let zk_proof = if let (Some(pp), Some(pi_path), Some(mp_path)) =
-        (proof_path, public_inputs_path, merkle_path_file)
-    {
-        let proof_bytes = std::fs::read(&pp).unwrap_or_else(|_| fatal("failed to read proof file"));
-        let public_inputs =
-            std::fs::read(&pi_path).unwrap_or_else(|_| fatal("failed to read public inputs file"));
-        let merkle_path =
-            std::fs::read(&mp_path).unwrap_or_else(|_| fatal("failed to read merkle path file"));
-        power_house::rollup::ZkRollupProof {
-            proof: proof_bytes,
-            public_inputs,
-            merkle_path,
-        }
-    } else {
-        power_house::rollup::ZkRollupProof {
-            proof: Vec::new(),
-            public_inputs: Vec::new(),
-            merkle_path: Vec::new(),
-        }
-    };
+#[cfg(feature = "net")]
+fn load_registry(path: &Path) -> StakeRegistry {
+    StakeRegistry::load(path).unwrap_or_else(|err| {
+        fatal(&format!(
+            "failed to load stake registry {}: {err}",
+            path.display()
+        ))
+    })
+}
 
-    let result = match mode.as_str() {
-        "zk" => power_house::rollup::settle_rollup_with_rewards(
-            registry,
-            commitment.clone(),
-            &payer,
-            &operator_pk,
-            &attesters,
-            fee,
-            power_house::rollup::RollupSettlementMode::Zk(zk_proof),
-        ),
-        _ => power_house::rollup::settle_rollup_with_rewards(
-            registry,
-            commitment.clone(),
-            &payer,
-            &operator_pk,
-            &attesters,
-            fee,
-            power_house::rollup::RollupSettlementMode::Optimistic(Vec::new()),
-        ),
-    };
-    match result {
-        Ok(receipt) => {
-            println!(
-                "settled rollup for {payer} fee={fee} commitment={}",
-                receipt.commitment.share_root
-            );
-        }
-        Err(err) => {
-            let outbox_path: PathBuf = outbox.map(PathBuf::from).unwrap_or_else(|| {
-                registry
-                    .parent()
-                    .unwrap_or(Path::new("."))
-                    .join("evidence_outbox.jsonl")
-            });
-            append_rollup_fault(&outbox_path, &err);
-            eprintln!("fault evidence written to {}", outbox_path.display());
-            fatal(&format!("settlement failed: {}", err.reason));
+#[cfg(feature = "net")]
+fn save_registry(path: &Path, reg: &StakeRegistry) {
+    reg.save(path).unwrap_or_else(|err| {
+        fatal(&format!(
+            "failed to save stake registry {}: {err}",
+            path.display()
+        ))
+    });
+}
+
+#[cfg(feature = "net")]
+fn cmd_stake_show(args: Vec<String>) {
+    if args.is_empty() {
+        eprintln!("Usage: julian stake show <stake_registry.json>");
+        exit(1);
+    }
+    let path = Path::new(&args[0]);
+    match StakeRegistry::load(path) {
+        Ok(reg) => {
+            let pretty = serde_json::to_string_pretty(&reg)
+                .unwrap_or_else(|e| format!("{{\"error\":\"{e}\"}}"));
+            println!("{pretty}");
         }
+        Err(err) => fatal(&format!(
+            "failed to load stake registry {}: {err}",
+            path.display()
+        )),
     }
 }
 
 #[cfg(feature = "net")]
-fn cmd_rollup_settle_file(args: Vec<String>) {
-    if args.len() < 2 {
-        eprintln!(
-            "Usage: julian rollup settle-file <registry.json> <request.json> [--outbox path]"
+fn cmd_stake_fund(args: Vec<String>) {
+    if args.len() < 3 {
+        eprintln!("Usage: julian stake fund <registry.json> <pubkey_b64> <amount> [--asset <id>]");
+        exit(1);
+    }
+    let path = Path::new(&args[0]);
+    let pk = &args[1];
+    let amount: u64 = args[2].parse().unwrap_or_else(|_| fatal("invalid amount"));
+    let mut asset = NATIVE_ASSET.to_string();
+    let mut override_path: Option<String> = None;
+    let mut policy_path: Option<String> = None;
+    let mut iter = args[3..].iter();
+    while let Some(arg) = iter.next() {
+        let mut value = |flag: &str| {
+            iter.next()
+                .cloned()
+                .unwrap_or_else(|| fatal(&format!("{flag} expects a value")))
+        };
+        match arg.as_str() {
+            "--asset" => asset = value("--asset"),
+            "--override" => override_path = Some(value("--override")),
+            "--policy" => policy_path = Some(value("--policy")),
+            other => fatal(&format!("unknown fund option: {other}")),
+        }
+    }
+    let mut reg = load_registry(path);
+    if let Some(override_path) = override_path {
+        let policy_path = policy_path.unwrap_or_else(|| fatal("--override requires --policy"));
+        let (update, policy) = load_guard_override(&override_path, &policy_path);
+        reg.apply_override_update(&update, &policy)
+            .unwrap_or_else(|err| fatal(&err));
+    }
+    reg.mint_asset(pk, &asset, amount, SupplyCause::Funding)
+        .unwrap_or_else(|err| fatal(&err));
+    save_registry(path, &reg);
+    if asset != NATIVE_ASSET {
+        println!(
+            "funded {pk} by {amount} {asset}, balance={}",
+            reg.balance_of(pk, &asset)
+        );
+    } else if let Some(acct) = reg.account(pk) {
+        println!(
+            "funded {pk} by {amount}, balance={} stake={}",
+            acct.balance, acct.stake
         );
-        std::process::exit(1);
     }
-    #[derive(serde::Deserialize)]
-    struct RollupSettleRequest {
-        namespace: String,
-        share_root: String,
-        #[serde(default)]
-        pedersen_root: Option<String>,
-        payer_pk: String,
-        #[serde(default)]
-        operator_pk: Option<String>,
-        #[serde(default)]
-        attesters: Option<Vec<String>>,
-        fee: u64,
-        #[serde(default)]
-        mode: Option<String>,
-        #[serde(default)]
-        proof_b64: Option<String>,
-        #[serde(default)]
-        public_inputs_b64: Option<String>,
-        #[serde(default)]
-        merkle_path_b64: Option<String>,
+}
+
+#[cfg(feature = "net")]
+fn cmd_stake_bond(args: Vec<String>) {
+    refresh_migration_mode_from_env();
+    if power_house::net::migration_mode_frozen() {
+        fatal("migration freeze active: stake bonding is disabled");
     }
-    let registry = Path::new(&args[0]);
-    let req_bytes =
-        std::fs::read(&args[1]).unwrap_or_else(|_| fatal("failed to read request file"));
-    let mut outbox: Option<String> = None;
-    for arg in args.iter().skip(2) {
-        if arg.starts_with("--outbox=") {
-            outbox = Some(arg.trim_start_matches("--outbox=").to_string());
-        }
+    if args.len() < 3 {
+        eprintln!("Usage: julian stake bond <registry.json> <pubkey_b64> <amount>");
+        exit(1);
     }
-    let req: RollupSettleRequest =
-        serde_json::from_slice(&req_bytes).unwrap_or_else(|_| fatal("invalid request JSON"));
-    let pedersen_root = req
-        .pedersen_root
-        .clone()
-        .unwrap_or_else(|| req.share_root.clone());
-    let commitment = power_house::rollup::RollupCommitment {
-        namespace: req.namespace.clone(),
-        share_root: req.share_root.clone(),
-        pedersen_root: Some(pedersen_root),
-        settlement_slot: None,
-    };
-    let operator_pk = req
-        .operator_pk
-        .clone()
-        .unwrap_or_else(|| req.payer_pk.clone());
-    let attesters = req.attesters.clone().unwrap_or_default();
-    let mode = req.mode.clone().unwrap_or_else(|| "optimistic".to_string());
-    let zk_proof = if let (Some(p_b64), Some(pi_b64), Some(mp_b64)) = (
-        req.proof_b64.as_ref(),
-        req.public_inputs_b64.as_ref(),
-        req.merkle_path_b64.as_ref(),
-    ) {
-        let proof = BASE64
-            .decode(p_b64.as_bytes())
-            .unwrap_or_else(|_| fatal("proof decode failed"));
-        let public_inputs = BASE64
-            .decode(pi_b64.as_bytes())
-            .unwrap_or_else(|_| fatal("public inputs decode failed"));
-        let merkle_path = BASE64
-            .decode(mp_b64.as_bytes())
-            .unwrap_or_else(|_| fatal("merkle path decode failed"));
-        power_house::rollup::ZkRollupProof {
-            proof,
-            public_inputs,
-            merkle_path,
-        }
-    } else {
-        power_house::rollup::ZkRollupProof {
-            proof: Vec::new(),
-            public_inputs: Vec::new(),
-            merkle_path: Vec::new(),
+    let path = Path::new(&args[0]);
+    let pk = &args[1];
+    let amount: u64 = args[2].parse().unwrap_or_else(|_| fatal("invalid amount"));
+    let mut reg = load_registry(path);
+    reg.bond(pk, amount).unwrap_or_else(|err| fatal(&err));
+    save_registry(path, &reg);
+    if let Some(acct) = reg.account(pk) {
+        println!(
+            "bonded {amount} for {pk}, balance={} stake={}",
+            acct.balance, acct.stake
+        );
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_stake_snapshot(args: Vec<String>) {
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        println!("Usage: julian stake snapshot --registry <path> --height <N> --output <file>");
+        println!("  [--deterministic] [--page-size <n>]");
+        println!("  --page-size streams the registry into a directory of page files");
+        return;
+    }
+
+    let mut registry_path: Option<String> = None;
+    let mut height: Option<u64> = None;
+    let mut output: Option<String> = None;
+    let mut deterministic = false;
+    let mut page_size: Option<usize> = None;
+
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--registry" => {
+                registry_path = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--registry expects a value")),
+                );
+            }
+            "--height" => {
+                let raw = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--height expects a value"));
+                height = Some(
+                    raw.parse::<u64>()
+                        .unwrap_or_else(|_| fatal("invalid --height")),
+                );
+            }
+            "--output" => {
+                output = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--output expects a value")),
+                );
+            }
+            "--deterministic" => {
+                deterministic = true;
+            }
+            "--page-size" => {
+                page_size = Some(
+                    iter.next()
+                        .and_then(|value| value.parse().ok())
+                        .filter(|&size: &usize| size > 0)
+                        .unwrap_or_else(|| fatal("--page-size expects a positive integer")),
+                );
+            }
+            other => fatal(&format!("unknown argument: {other}")),
         }
+    }
+
+    let registry_path = registry_path.unwrap_or_else(|| fatal("--registry is required"));
+    let height = height.unwrap_or_else(|| fatal("--height is required"));
+    let output = output.unwrap_or_else(|| fatal("--output is required"));
+
+    if let Some(page_size) = page_size {
+        let manifest = run_paged_snapshot_with_clock(
+            &registry_path,
+            height,
+            page_size,
+            Path::new(&output),
+            artifact_clock(deterministic),
+        )
+        .unwrap_or_else(|err| fatal(&format!("snapshot failed: {err}")));
+        println!("snapshot root: {}", manifest.snapshot_root);
+        println!(
+            "pages: {} ({} account(s)) in {output}",
+            manifest.pages.len(),
+            manifest.accounts
+        );
+        return;
+    }
+
+    let root = run_snapshot_with_clock(
+        &registry_path,
+        height,
+        &output,
+        artifact_clock(deterministic),
+    )
+    .unwrap_or_else(|err| fatal(&format!("snapshot failed: {err}")));
+    println!("snapshot root: {root}");
+    println!("artifact: {output}");
+}
+
+#[cfg(feature = "net")]
+fn cmd_stake_verify_snapshot(args: Vec<String>) {
+    let [dir] = args.as_slice() else {
+        fatal("Usage: julian stake verify-snapshot <dir>");
     };
-    let mode_enum = if mode == "zk" {
-        power_house::rollup::RollupSettlementMode::Zk(zk_proof)
-    } else {
-        power_house::rollup::RollupSettlementMode::Optimistic(Vec::new())
-    };
-    match power_house::rollup::settle_rollup_with_rewards(
-        registry,
-        commitment.clone(),
-        &req.payer_pk,
-        &operator_pk,
-        &attesters,
-        req.fee,
-        mode_enum,
-    ) {
-        Ok(receipt) => println!(
-            "settled rollup fee={} commitment={}",
-            receipt.fee, receipt.commitment.share_root
+    match verify_paged_snapshot(Path::new(dir)) {
+        Ok(manifest) => println!(
+            "PASS: {} page(s), {} account(s) at height {} chain to root {}",
+            manifest.pages.len(),
+            manifest.accounts,
+            manifest.snapshot_height,
+            manifest.snapshot_root
         ),
-        Err(fault) => {
-            let outbox_path: PathBuf = outbox.map(PathBuf::from).unwrap_or_else(|| {
-                registry
-                    .parent()
-                    .unwrap_or(Path::new("."))
-                    .join("evidence_outbox.jsonl")
-            });
-            append_rollup_fault(&outbox_path, &fault);
-            eprintln!("fault evidence written to {}", outbox_path.display());
-            fatal(&format!("settlement failed: {}", fault.reason));
+        Err(err) => fatal(&format!("FAIL: {err}")),
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_stake_claims(args: Vec<String>) {
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        println!("Usage: julian stake claims --snapshot <file> --output <file> [options]");
+        println!("  [--mode native|erc20]");
+        println!("  [--amount-source stake|balance|total] [--include-slashed]");
+        println!("  [--conversion-ratio <u64>] [--claim-id-salt <text>]");
+        println!("  [--token-contract <id>] [--snapshot-height <u64>]");
+        println!("  [--address-index <address_index.json>] [--expires-at-epoch <u64>]");
+        println!("  [--deterministic]");
+        return;
+    }
+
+    let mut snapshot: Option<String> = None;
+    let mut output: Option<String> = None;
+    let mut claim_mode = String::from("native");
+    let mut amount_source = String::from("total");
+    let mut include_slashed = false;
+    let mut conversion_ratio: u64 = 1;
+    let mut claim_id_salt = String::from("mfenx-migration-claim-v1");
+    let mut token_contract: Option<String> = None;
+    let mut snapshot_height_override: Option<u64> = None;
+    let mut address_index: Option<PathBuf> = None;
+    let mut expires_at_epoch: Option<u64> = None;
+    let mut deterministic = false;
+
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--deterministic" => {
+                deterministic = true;
+            }
+            "--address-index" => {
+                address_index = Some(PathBuf::from(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--address-index expects a path")),
+                ));
+            }
+            "--snapshot" => {
+                snapshot = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--snapshot expects a value")),
+                );
+            }
+            "--output" => {
+                output = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--output expects a value")),
+                );
+            }
+            "--mode" => {
+                claim_mode = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--mode expects a value"));
+            }
+            "--amount-source" => {
+                amount_source = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--amount-source expects a value"));
+            }
+            "--include-slashed" => {
+                include_slashed = true;
+            }
+            "--conversion-ratio" => {
+                let raw = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--conversion-ratio expects a value"));
+                conversion_ratio = raw
+                    .parse::<u64>()
+                    .unwrap_or_else(|_| fatal("invalid --conversion-ratio"));
+            }
+            "--claim-id-salt" => {
+                claim_id_salt = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--claim-id-salt expects a value"));
+            }
+            "--token-contract" => {
+                token_contract = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--token-contract expects a value")),
+                );
+            }
+            "--snapshot-height" => {
+                let raw = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--snapshot-height expects a value"));
+                snapshot_height_override = Some(
+                    raw.parse::<u64>()
+                        .unwrap_or_else(|_| fatal("invalid --snapshot-height")),
+                );
+            }
+            "--expires-at-epoch" => {
+                let raw = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--expires-at-epoch expects a value"));
+                expires_at_epoch = Some(
+                    raw.parse::<u64>()
+                        .unwrap_or_else(|_| fatal("invalid --expires-at-epoch")),
+                );
+            }
+            other => fatal(&format!("unknown argument: {other}")),
         }
     }
+
+    let snapshot = snapshot.unwrap_or_else(|| fatal("--snapshot is required"));
+    let output = output.unwrap_or_else(|| fatal("--output is required"));
+    let opts = BuildClaimsOptions {
+        claim_mode,
+        amount_source,
+        include_slashed,
+        conversion_ratio,
+        claim_id_salt,
+        token_contract,
+        snapshot_height_override,
+        address_index,
+        expires_at_epoch,
+    };
+
+    let root =
+        run_build_claims_with_clock(&snapshot, &output, &opts, artifact_clock(deterministic))
+            .unwrap_or_else(|err| fatal(&format!("claim build failed: {err}")));
+    println!("claims root: {root}");
+    println!("artifact: {output}");
 }
 
-fn cmd_node_run(args: Vec<String>) {
-    if args.len() < 3 {
-        eprintln!("Usage: julian node run <node_id> <log_dir> <output_anchor>");
-        std::process::exit(1);
+#[cfg(feature = "net")]
+fn cmd_stake_apply_claims(args: Vec<String>) {
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        println!("Usage: julian stake apply-claims --registry <file> --claims <file> [options]");
+        println!("  [--state <file>] [--dry-run]");
+        return;
     }
-    let node_id = &args[0];
-    println!("{NETWORK_ID} node {node_id} starting...");
-    let log_dir = Path::new(&args[1]);
-    let output = Path::new(&args[2]);
-    let anchor = match load_anchor_from_logs(log_dir) {
-        Ok(anchor) => anchor,
-        Err(err) => {
-            eprintln!("error: {err}");
-            std::process::exit(1);
+
+    let mut registry: Option<String> = None;
+    let mut claims: Option<String> = None;
+    let mut state_path: Option<String> = None;
+    let mut dry_run = false;
+
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--registry" => {
+                registry = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--registry expects a value")),
+                );
+            }
+            "--claims" => {
+                claims = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--claims expects a value")),
+                );
+            }
+            "--state" => {
+                state_path = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--state expects a value")),
+                );
+            }
+            "--dry-run" => {
+                dry_run = true;
+            }
+            other => fatal(&format!("unknown argument: {other}")),
+        }
+    }
+
+    let registry = registry.unwrap_or_else(|| fatal("--registry is required"));
+    let claims = claims.unwrap_or_else(|| fatal("--claims is required"));
+    let opts = ApplyClaimsOptions {
+        state_path,
+        dry_run,
+    };
+
+    let summary = run_apply_claims(&registry, &claims, &opts)
+        .unwrap_or_else(|err| fatal(&format!("apply-claims failed: {err}")));
+    println!("applied: {}", summary.applied);
+    println!("skipped: {}", summary.skipped);
+    if summary.held > 0 {
+        println!("held_frozen: {}", summary.held);
+    }
+    println!("total_mint_amount: {}", summary.total_mint_amount);
+    println!("state: {}", summary.state_path);
+    if dry_run {
+        println!("dry_run: true");
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_migration_finalize(args: Vec<String>) {
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        print_migration_help();
+        return;
+    }
+
+    let mut registry_path: Option<String> = None;
+    let mut snapshot_height: Option<u64> = None;
+    let mut log_dir: Option<String> = None;
+    let mut output_dir: Option<String> = None;
+    let mut token_contract = String::from("native://julian");
+    let mut conversion_ratio: u64 = 1;
+    let mut treasury_mint: u64 = 0;
+    let mut amount_source = String::from("total");
+    let mut include_slashed = false;
+    let mut claim_id_salt = String::from("mfenx-migration-claim-v1");
+    let mut node_id = String::from("migration-finalize");
+    let mut quorum: usize = 1;
+    let mut apply_state_path: Option<String> = None;
+    let mut allow_unfrozen = false;
+    let mut force = false;
+    let mut deterministic = false;
+    let mut until = None;
+    let mut dry_run = false;
+
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--registry" => {
+                registry_path = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--registry expects a value")),
+                );
+            }
+            "--height" => {
+                let raw = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--height expects a value"));
+                snapshot_height = Some(raw.parse().unwrap_or_else(|_| fatal("invalid --height")));
+            }
+            "--log-dir" => {
+                log_dir = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--log-dir expects a value")),
+                );
+            }
+            "--output-dir" => {
+                output_dir = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--output-dir expects a value")),
+                );
+            }
+            "--token-contract" => {
+                token_contract = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--token-contract expects a value"));
+            }
+            "--conversion-ratio" => {
+                let raw = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--conversion-ratio expects a value"));
+                conversion_ratio = raw
+                    .parse::<u64>()
+                    .unwrap_or_else(|_| fatal("invalid --conversion-ratio"));
+            }
+            "--treasury-mint" => {
+                let raw = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--treasury-mint expects a value"));
+                treasury_mint = raw
+                    .parse::<u64>()
+                    .unwrap_or_else(|_| fatal("invalid --treasury-mint"));
+            }
+            "--amount-source" => {
+                amount_source = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--amount-source expects a value"));
+            }
+            "--include-slashed" => {
+                include_slashed = true;
+            }
+            "--claim-id-salt" => {
+                claim_id_salt = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--claim-id-salt expects a value"));
+            }
+            "--node-id" => {
+                node_id = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--node-id expects a value"));
+            }
+            "--quorum" => {
+                let raw = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--quorum expects a value"));
+                quorum = raw
+                    .parse::<usize>()
+                    .unwrap_or_else(|_| fatal("invalid --quorum"));
+            }
+            "--apply-state" => {
+                apply_state_path = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--apply-state expects a value")),
+                );
+            }
+            "--allow-unfrozen" => {
+                allow_unfrozen = true;
+            }
+            "--force" => {
+                force = true;
+            }
+            "--deterministic" => {
+                deterministic = true;
+            }
+            "--until" => {
+                let raw = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--until expects a value"));
+                until = Some(FinalizeStep::parse(&raw).unwrap_or_else(|err| fatal(&err)));
+            }
+            "--dry-run" => {
+                dry_run = true;
+            }
+            other => fatal(&format!("unknown argument: {other}")),
+        }
+    }
+
+    let opts = FinalizeMigrationOptions {
+        registry_path: registry_path.unwrap_or_else(|| fatal("--registry is required")),
+        snapshot_height: snapshot_height.unwrap_or_else(|| fatal("--height is required")),
+        log_dir: log_dir.unwrap_or_else(|| fatal("--log-dir is required")),
+        output_dir: output_dir.unwrap_or_else(|| fatal("--output-dir is required")),
+        token_contract,
+        conversion_ratio,
+        treasury_mint,
+        amount_source,
+        include_slashed,
+        claim_id_salt,
+        node_id,
+        quorum,
+        apply_state_path,
+        allow_unfrozen,
+        force,
+        deterministic,
+        until,
+    };
+
+    if dry_run {
+        let plan = plan_finalize_migration(&opts)
+            .unwrap_or_else(|err| fatal(&format!("migration finalize failed: {err}")));
+        for (step, entry) in plan {
+            match entry {
+                StepPlan::Skip => println!("{step}: done, would skip"),
+                StepPlan::Run => println!("{step}: would run"),
+                StepPlan::Stop => println!("{step}: would stay pending"),
+                StepPlan::Blocked(reason) => println!("{step}: blocked: {reason}"),
+            }
+        }
+        println!("dry_run: true");
+        return;
+    }
+
+    let summary = run_finalize_migration(&opts)
+        .unwrap_or_else(|err| fatal(&format!("migration finalize failed: {err}")));
+    println!("snapshot_root: {}", summary.snapshot_root);
+    println!("claims_root: {}", summary.claims_root);
+    println!("applied_claims: {}", summary.applied_claims);
+    println!("skipped_claims: {}", summary.skipped_claims);
+    println!("snapshot: {}", summary.snapshot_path);
+    println!("claims: {}", summary.claims_path);
+    println!("apply_state: {}", summary.apply_state_path);
+    println!("proposal: {}", summary.proposal_path);
+    let names = |steps: &[FinalizeStep]| {
+        steps
+            .iter()
+            .map(|step| step.as_str())
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    println!("resumed: {}", names(&summary.resumed));
+    println!("executed: {}", names(&summary.executed));
+}
+
+#[cfg(feature = "net")]
+fn cmd_migration_finalize_status(args: Vec<String>) {
+    let output_dir = match args.as_slice() {
+        [flag, dir] if flag == "--output-dir" => dir.clone(),
+        _ => fatal("Usage: julian migration finalize-status --output-dir <dir>"),
+    };
+    let state = FinalizeState::load(Path::new(&output_dir))
+        .unwrap_or_else(|err| fatal(&err))
+        .unwrap_or_else(|| fatal(&format!("no finalize run recorded in {output_dir}")));
+    for record in &state.steps {
+        println!("{}: {}", record.step, record.status.as_str());
+        for (key, value) in &record.outputs {
+            println!("  {key}: {value}");
+        }
+        if let Some(err) = &record.error {
+            println!("  error: {err}");
+        }
+    }
+    let touched = state
+        .steps
+        .iter()
+        .rev()
+        .filter(|record| record.status != StepStatus::Pending)
+        .collect::<Vec<_>>();
+    if !touched.is_empty() {
+        println!("rollback (newest first):");
+        for record in touched {
+            println!("  {}: {}", record.step, record.step.rollback());
+        }
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_migration_verify_state(args: Vec<String>) {
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        print_migration_help();
+        return;
+    }
+
+    let mut registry: Option<String> = None;
+    let mut claims: Option<String> = None;
+    let mut state: Option<String> = None;
+    let mut require_complete = false;
+    let mut enforce_balance_floor = true;
+    let mut receipt = ReceiptOptions::default();
+
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        if receipt.parse(&arg, &mut iter) {
+            continue;
+        }
+        match arg.as_str() {
+            "--registry" => {
+                registry = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--registry expects a value")),
+                );
+            }
+            "--claims" => {
+                claims = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--claims expects a value")),
+                );
+            }
+            "--state" => {
+                state = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--state expects a value")),
+                );
+            }
+            "--require-complete" => {
+                require_complete = true;
+            }
+            "--skip-balance-floor" => {
+                enforce_balance_floor = false;
+            }
+            other => fatal(&format!("unknown argument: {other}")),
+        }
+    }
+
+    let registry = registry.unwrap_or_else(|| fatal("--registry is required"));
+    let claims = claims.unwrap_or_else(|| fatal("--claims is required"));
+    let state = state.unwrap_or_else(|| fatal("--state is required"));
+    let result = run_verify_state(
+        &registry,
+        &claims,
+        &state,
+        &VerifyStateOptions {
+            require_complete,
+            enforce_balance_floor,
+        },
+    );
+    receipt.emit(
+        "migration verify-state",
+        &[
+            ("registry", Path::new(&registry)),
+            ("claims", Path::new(&claims)),
+            ("state", Path::new(&state)),
+        ],
+        &[
+            ("require_complete", require_complete.to_string()),
+            ("enforce_balance_floor", enforce_balance_floor.to_string()),
+        ],
+        &result
+            .as_ref()
+            .map(|summary| {
+                format!(
+                    "claim_count={} applied_count={} swept_count={} missing_count={} unknown_count={} applied_total_mint={}",
+                    summary.claim_count,
+                    summary.applied_count,
+                    summary.swept_count,
+                    summary.missing_count,
+                    summary.unknown_count,
+                    summary.applied_total_mint
+                )
+            })
+            .map_err(|err| err.to_string()),
+    );
+    let summary =
+        result.unwrap_or_else(|err| fatal(&format!("migration verify-state failed: {err}")));
+
+    println!("claim_count: {}", summary.claim_count);
+    println!("applied_count: {}", summary.applied_count);
+    println!("swept_count: {}", summary.swept_count);
+    println!("missing_count: {}", summary.missing_count);
+    println!("unknown_count: {}", summary.unknown_count);
+    println!("applied_total_mint: {}", summary.applied_total_mint);
+}
+
+#[cfg(feature = "net")]
+fn cmd_migration_execute_burn_intents(args: Vec<String>) {
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        print_migration_help();
+        return;
+    }
+
+    let mut registry: Option<String> = None;
+    let mut outbox: Option<String> = None;
+    let mut state: Option<String> = None;
+    let mut dry_run = false;
+    let mut key_spec: Option<String> = None;
+    let mut receipts: Option<String> = None;
+    let mut trusted_signers = Vec::new();
+
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--registry" => {
+                registry = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--registry expects a value")),
+                );
+            }
+            "--outbox" => {
+                outbox = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--outbox expects a value")),
+                );
+            }
+            "--state" => {
+                state = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--state expects a value")),
+                );
+            }
+            "--dry-run" => {
+                dry_run = true;
+            }
+            "--key" => {
+                key_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--key expects a value")),
+                );
+            }
+            "--receipts" => {
+                receipts = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--receipts expects a value")),
+                );
+            }
+            "--intent-signer" => {
+                trusted_signers.push(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--intent-signer expects a value")),
+                );
+            }
+            other => fatal(&format!("unknown argument: {other}")),
+        }
+    }
+
+    let registry = registry.unwrap_or_else(|| fatal("--registry is required"));
+    let executor = key_spec.map(|spec| {
+        load_or_derive_keypair(&Ed25519KeySource::from_spec(Some(&spec)))
+            .unwrap_or_else(|err| fatal(&format!("failed to load key: {err}")))
+            .signing
+    });
+    let outbox = outbox.unwrap_or_else(|| {
+        let parent = Path::new(&registry)
+            .parent()
+            .unwrap_or_else(|| Path::new("."));
+        parent.join("token_burn_outbox.jsonl").display().to_string()
+    });
+
+    let summary = run_execute_burn_intents(
+        &registry,
+        &outbox,
+        &ExecuteBurnOptions {
+            state_path: state,
+            dry_run,
+            receipts_path: receipts,
+            executor,
+            trusted_signers,
+        },
+    )
+    .unwrap_or_else(|err| fatal(&format!("migration execute-burn-intents failed: {err}")));
+
+    println!("processed: {}", summary.processed);
+    println!("skipped: {}", summary.skipped);
+    println!("native_executed: {}", summary.native_executed);
+    println!("unsupported_mode: {}", summary.unsupported_mode);
+    println!("rejected: {}", summary.rejected);
+    println!("state: {}", summary.state_path);
+    if let Some(path) = &summary.receipts_path {
+        println!("receipts: {} ({path})", summary.receipts);
+    }
+    if dry_run {
+        println!("dry_run: true");
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_migration_reconcile_burns(args: Vec<String>) {
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        print_migration_help();
+        return;
+    }
+
+    let mut outbox: Option<String> = None;
+    let mut opts = ReconcileBurnOptions::default();
+
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--outbox" => {
+                outbox = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--outbox expects a value")),
+                );
+            }
+            "--receipts" => {
+                opts.receipts_path = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--receipts expects a value")),
+                );
+            }
+            "--intent-signer" => {
+                opts.trusted_signers.push(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--intent-signer expects a value")),
+                );
+            }
+            "--executor" => {
+                opts.trusted_executors.push(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--executor expects a value")),
+                );
+            }
+            other => fatal(&format!("unknown argument: {other}")),
+        }
+    }
+
+    let outbox = outbox.unwrap_or_else(|| fatal("--outbox is required"));
+    let report = run_reconcile_burns(&outbox, &opts)
+        .unwrap_or_else(|err| fatal(&format!("migration reconcile-burns failed: {err}")));
+
+    println!("intents: {}", report.intents);
+    println!("matched: {}", report.matched);
+    for id in &report.unexecuted {
+        println!("unexecuted: {id}");
+    }
+    for id in &report.double_executed {
+        println!("double_executed: {id}");
+    }
+    for id in &report.invalid_intents {
+        println!("invalid_intent: {id}");
+    }
+    for line in &report.invalid_receipts {
+        println!("invalid_receipt: line {line}");
+    }
+    for id in &report.orphan_receipts {
+        println!("orphan_receipt: {id}");
+    }
+    if !report.is_clean() {
+        fatal("burn reconciliation found discrepancies");
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_migration_sweep_claims(args: Vec<String>) {
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        print_migration_help();
+        return;
+    }
+
+    let mut registry: Option<String> = None;
+    let mut claims: Option<String> = None;
+    let mut sweep_account: Option<String> = None;
+    let mut key_spec: Option<String> = None;
+    let mut output: Option<String> = None;
+    let mut state_path: Option<String> = None;
+    let mut dry_run = false;
+
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--registry" => {
+                registry = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--registry expects a value")),
+                );
+            }
+            "--claims" => {
+                claims = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--claims expects a value")),
+                );
+            }
+            "--to" => {
+                sweep_account = Some(iter.next().unwrap_or_else(|| fatal("--to expects a value")));
+            }
+            "--key" => {
+                key_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--key expects a value")),
+                );
+            }
+            "--output" => {
+                output = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--output expects a value")),
+                );
+            }
+            "--state" => {
+                state_path = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--state expects a value")),
+                );
+            }
+            "--dry-run" => {
+                dry_run = true;
+            }
+            other => fatal(&format!("unknown argument: {other}")),
+        }
+    }
+
+    let registry = registry.unwrap_or_else(|| fatal("--registry is required"));
+    let claims = claims.unwrap_or_else(|| fatal("--claims is required"));
+    let key_spec = key_spec.unwrap_or_else(|| fatal("--key is required"));
+    let signing = load_or_derive_keypair(&Ed25519KeySource::from_spec(Some(&key_spec)))
+        .unwrap_or_else(|err| fatal(&format!("failed to load key: {err}")))
+        .signing;
+    let opts = SweepClaimsOptions {
+        state_path,
+        sweep_account: sweep_account.unwrap_or_else(|| fatal("--to is required")),
+        signing,
+        output: output.unwrap_or_else(|| fatal("--output is required")),
+        dry_run,
+    };
+
+    let sweep = run_sweep_claims(&registry, &claims, &opts)
+        .unwrap_or_else(|err| fatal(&format!("migration sweep-claims failed: {err}")));
+    println!("swept: {}", sweep.swept_claim_ids.len());
+    println!("total_amount: {}", sweep.total_amount);
+    println!("sweep_account: {}", sweep.sweep_account);
+    println!("registry_epoch: {}", sweep.registry_epoch);
+    if dry_run {
+        println!("dry_run: true");
+    } else {
+        println!("record: {}", opts.output);
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_migration_reserve_report(args: Vec<String>) {
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        print_migration_help();
+        return;
+    }
+
+    let mut registry: Option<String> = None;
+    let mut claims: Option<String> = None;
+    let mut log_dir: Option<String> = None;
+    let mut key_spec: Option<String> = None;
+    let mut state_path: Option<String> = None;
+    let mut outbox_path: Option<String> = None;
+    let mut burns = ReconcileBurnOptions::default();
+    let mut node_id = "migration-reserve".to_string();
+    let mut quorum: usize = 1;
+    let mut output: Option<String> = None;
+    let mut deterministic = false;
+
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        let mut value = |flag: &str| {
+            iter.next()
+                .unwrap_or_else(|| fatal(&format!("{flag} expects a value")))
+        };
+        match arg.as_str() {
+            "--registry" => registry = Some(value("--registry")),
+            "--claims" => claims = Some(value("--claims")),
+            "--log-dir" => log_dir = Some(value("--log-dir")),
+            "--key" => key_spec = Some(value("--key")),
+            "--state" => state_path = Some(value("--state")),
+            "--outbox" => outbox_path = Some(value("--outbox")),
+            "--receipts" => burns.receipts_path = Some(value("--receipts")),
+            "--intent-signer" => burns.trusted_signers.push(value("--intent-signer")),
+            "--executor" => burns.trusted_executors.push(value("--executor")),
+            "--node-id" => node_id = value("--node-id"),
+            "--quorum" => {
+                quorum = value("--quorum")
+                    .parse()
+                    .unwrap_or_else(|_| fatal("invalid --quorum"));
+            }
+            "--output" => output = Some(value("--output")),
+            "--deterministic" => deterministic = true,
+            other => fatal(&format!("unknown argument: {other}")),
+        }
+    }
+
+    let key_spec = key_spec.unwrap_or_else(|| fatal("--key is required"));
+    let signing = load_or_derive_keypair(&Ed25519KeySource::from_spec(Some(&key_spec)))
+        .unwrap_or_else(|err| fatal(&format!("failed to load key: {err}")))
+        .signing;
+    let opts = ReserveReportOptions {
+        registry_path: registry.unwrap_or_else(|| fatal("--registry is required")),
+        claims_path: claims.unwrap_or_else(|| fatal("--claims is required")),
+        state_path,
+        outbox_path,
+        burns,
+        log_dir: log_dir.unwrap_or_else(|| fatal("--log-dir is required")),
+        node_id,
+        quorum,
+        signing,
+        output: output.clone(),
+    };
+    let artifact = run_reserve_report_with_clock(&opts, artifact_clock(deterministic))
+        .unwrap_or_else(|err| fatal(&format!("migration reserve-report failed: {err}")));
+
+    let report = &artifact.report;
+    println!("allocation: {}", report.allocation);
+    println!("applied_amount: {}", report.applied_amount);
+    println!("swept_amount: {}", report.swept_amount);
+    println!("outstanding_amount: {}", report.outstanding_amount);
+    println!("total_supply: {}", report.total_supply);
+    println!("report_digest: {}", artifact.report_digest);
+    if let Some(path) = output {
+        println!("wrote reserve report to {path}");
+    } else {
+        let encoded = serde_json::to_string_pretty(&artifact)
+            .unwrap_or_else(|err| fatal(&format!("failed to encode reserve report: {err}")));
+        println!("{encoded}");
+    }
+    for discrepancy in &report.discrepancies {
+        println!("discrepancy: {discrepancy}");
+    }
+    if !report.discrepancies.is_empty() {
+        fatal(&format!(
+            "reserve report found {} discrepancies",
+            report.discrepancies.len()
+        ));
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_migration_verify_reserve_report(args: Vec<String>) {
+    let path = match args.as_slice() {
+        [path] if path != "-h" && path != "--help" => path,
+        _ => {
+            print_migration_help();
+            return;
+        }
+    };
+    let artifact = verify_reserve_report(path)
+        .unwrap_or_else(|err| fatal(&format!("migration verify-reserve-report failed: {err}")));
+    println!("signer: {}", artifact.report.signer_b64);
+    println!("report_digest: {}", artifact.report_digest);
+    println!("discrepancies: {}", artifact.report.discrepancies.len());
+}
+
+#[cfg(feature = "net")]
+fn cmd_governance_propose_migration(args: Vec<String>) {
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        println!("Usage: julian governance propose-migration \\");
+        println!("  --snapshot-height <N> [--token-contract <id>] \\");
+        println!("  [--conversion-ratio <u64>] [--treasury-mint <u64>] \\");
+        println!("  --log-dir <dir> [--node-id <id>] [--quorum <N>] [--output <file>] \\");
+        println!("  [--deterministic]");
+        return;
+    }
+
+    let mut snapshot_height: Option<u64> = None;
+    let mut token_contract: Option<String> = None;
+    let mut conversion_ratio: u64 = 1;
+    let mut treasury_mint: u64 = 0;
+    let mut log_dir: Option<String> = None;
+    let mut node_id: String = "migration-governance".to_string();
+    let mut quorum: usize = 1;
+    let mut output: Option<String> = None;
+    let mut deterministic = false;
+
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--snapshot-height" => {
+                let raw = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--snapshot-height expects a value"));
+                snapshot_height = Some(
+                    raw.parse::<u64>()
+                        .unwrap_or_else(|_| fatal("invalid --snapshot-height")),
+                );
+            }
+            "--token-contract" => {
+                token_contract = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--token-contract expects a value")),
+                );
+            }
+            "--conversion-ratio" => {
+                let raw = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--conversion-ratio expects a value"));
+                conversion_ratio = raw
+                    .parse::<u64>()
+                    .unwrap_or_else(|_| fatal("invalid --conversion-ratio"));
+            }
+            "--treasury-mint" => {
+                let raw = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--treasury-mint expects a value"));
+                treasury_mint = raw
+                    .parse::<u64>()
+                    .unwrap_or_else(|_| fatal("invalid --treasury-mint"));
+            }
+            "--log-dir" => {
+                log_dir = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--log-dir expects a value")),
+                );
+            }
+            "--node-id" => {
+                node_id = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--node-id expects a value"));
+            }
+            "--quorum" => {
+                let raw = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--quorum expects a value"));
+                quorum = raw
+                    .parse::<usize>()
+                    .unwrap_or_else(|_| fatal("invalid --quorum"));
+            }
+            "--output" => {
+                output = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--output expects a value")),
+                );
+            }
+            "--deterministic" => {
+                deterministic = true;
+            }
+            other => fatal(&format!("unknown argument: {other}")),
+        }
+    }
+
+    let snapshot_height = snapshot_height.unwrap_or_else(|| fatal("--snapshot-height is required"));
+    let token_contract = token_contract
+        .or_else(|| std::env::var("PH_MIGRATION_TOKEN_ID").ok())
+        .unwrap_or_else(|| "native://julian".to_string());
+    let log_dir = log_dir.unwrap_or_else(|| fatal("--log-dir is required"));
+
+    let encoded = run_propose_migration_with_clock(
+        &ProposeMigrationOptions {
+            snapshot_height,
+            token_contract,
+            conversion_ratio,
+            treasury_mint,
+            log_dir,
+            node_id,
+            quorum,
+            output: output.clone(),
+        },
+        artifact_clock(deterministic),
+    )
+    .unwrap_or_else(|err| fatal(&format!("propose-migration failed: {err}")));
+
+    if let Some(path) = output {
+        println!("wrote migration proposal artifact to {path}");
+    } else {
+        println!("{encoded}");
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_stake_unbond(args: Vec<String>) {
+    if args.len() < 3 {
+        eprintln!("Usage: julian stake unbond <registry.json> <pubkey_b64> <amount>");
+        exit(1);
+    }
+    let path = Path::new(&args[0]);
+    let pk = &args[1];
+    let amount: u64 = args[2].parse().unwrap_or_else(|_| fatal("invalid amount"));
+    let mut reg = load_registry(path);
+    let release_epoch = reg
+        .begin_unbond(pk, amount)
+        .unwrap_or_else(|err| fatal(&err));
+    save_registry(path, &reg);
+    if let Some(acct) = reg.account(pk) {
+        println!(
+            "unbonding {amount} for {pk} until epoch {release_epoch}, stake={} unbonding={}",
+            acct.stake,
+            acct.unbonding_total()
+        );
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_stake_withdraw(args: Vec<String>) {
+    if args.len() < 2 {
+        eprintln!("Usage: julian stake withdraw <registry.json> <pubkey_b64>");
+        exit(1);
+    }
+    let path = Path::new(&args[0]);
+    let pk = &args[1];
+    let mut reg = load_registry(path);
+    let amount = reg.withdraw(pk).unwrap_or_else(|err| fatal(&err));
+    save_registry(path, &reg);
+    if let Some(acct) = reg.account(pk) {
+        println!(
+            "withdrew {amount} for {pk}, balance={} unbonding={}",
+            acct.balance,
+            acct.unbonding_total()
+        );
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_stake_audit(args: Vec<String>) {
+    if args.is_empty() {
+        eprintln!("Usage: julian stake audit <registry.json>");
+        exit(1);
+    }
+    let reg = load_registry(Path::new(&args[0]));
+    let supply = reg.supply();
+    println!("journal_entries: {}", supply.entries.len());
+    for asset in supply.asset_ids() {
+        println!("asset: {asset}");
+        println!("  total_supply: {}", supply.supply_of(&asset));
+        println!("  holdings: {}", reg.holdings_of(&asset));
+        for (cause, amount) in supply.minted_by_cause(&asset) {
+            println!("  minted[{}]: {amount}", supply_cause_label(cause));
+        }
+        for (cause, amount) in supply.burned_by_cause(&asset) {
+            println!("  burned[{}]: {amount}", supply_cause_label(cause));
+        }
+    }
+    println!("PASS: supply invariant holds");
+}
+
+#[cfg(feature = "net")]
+fn cmd_stake_address_index(args: Vec<String>) {
+    if args.is_empty() || args.iter().any(|a| a == "-h" || a == "--help") {
+        eprintln!("Usage: julian stake address-index <registry.json> [--output <file>]");
+        exit(1);
+    }
+    let registry_path = PathBuf::from(&args[0]);
+    let mut output = AddressIndex::path_for(&registry_path);
+    let mut iter = args.into_iter().skip(1);
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--output" => {
+                output = PathBuf::from(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--output expects a path")),
+                );
+            }
+            other => fatal(&format!("unknown argument: {other}")),
+        }
+    }
+    let reg = load_registry(&registry_path);
+    let index = AddressIndex::build(&reg);
+    index.save(&output).unwrap_or_else(|err| fatal(&err));
+    println!(
+        "indexed {} of {} accounts ({ADDRESS_DERIVATION}) -> {}",
+        index.len(),
+        reg.accounts().len(),
+        output.display()
+    );
+}
+
+#[cfg(feature = "net")]
+fn cmd_stake_prove(args: Vec<String>) {
+    if args.len() < 2 {
+        eprintln!("Usage: julian stake prove <registry.json> <pubkey_b64>");
+        exit(1);
+    }
+    let reg = load_registry(Path::new(&args[0]));
+    let report = serde_json::json!({
+        "epoch": reg.epoch(),
+        "registry_root": hex::encode(reg.state_root()),
+        "account": reg.account(&args[1]),
+        "proof": reg.account_proof(&args[1]),
+    });
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&report).unwrap_or_else(|err| fatal(&err.to_string()))
+    );
+}
+
+#[cfg(feature = "net")]
+fn supply_cause_label(cause: SupplyCause) -> String {
+    serde_json::to_value(cause)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_else(|| format!("{cause:?}"))
+}
+
+#[cfg(feature = "net")]
+fn cmd_stake_unbonding_period(args: Vec<String>) {
+    if args.len() < 2 {
+        eprintln!("Usage: julian stake unbonding-period <registry.json> <epochs>");
+        exit(1);
+    }
+    let path = Path::new(&args[0]);
+    let epochs: u64 = args[1].parse().unwrap_or_else(|_| fatal("invalid epochs"));
+    let mut reg = load_registry(path);
+    reg.set_unbonding_epochs(epochs);
+    save_registry(path, &reg);
+    println!(
+        "unbonding period set to {epochs} epochs (current epoch {})",
+        reg.epoch()
+    );
+}
+
+#[cfg(feature = "net")]
+fn load_guard_override(update_path: &str, policy_path: &str) -> (GovernanceUpdate, MultisigPolicy) {
+    let update: GovernanceUpdate = read_json_file(Path::new(update_path), "guard override");
+    let policy = MultisigPolicy::load(Path::new(policy_path))
+        .unwrap_or_else(|err| fatal(&format!("failed to load policy {policy_path}: {err}")));
+    (update, policy)
+}
+
+#[cfg(feature = "net")]
+fn cmd_stake_guards(args: Vec<String>) {
+    let Some((path, flags)) = args.split_first() else {
+        print_stake_help();
+        exit(1);
+    };
+    let path = Path::new(path);
+    let mut reg = load_registry(path);
+    let mut limits = reg.guard_limits();
+    let mut iter = flags.iter();
+    while let Some(arg) = iter.next() {
+        let raw = iter
+            .next()
+            .unwrap_or_else(|| fatal(&format!("{arg} expects a value or 'off'")));
+        let limit = match raw.as_str() {
+            "off" => None,
+            raw => Some(
+                raw.parse::<u64>()
+                    .unwrap_or_else(|_| fatal(&format!("invalid {arg}"))),
+            ),
+        };
+        match arg.as_str() {
+            "--max-single-credit" => limits.max_single_credit = limit,
+            "--max-account-daily-delta" => limits.max_account_daily_delta = limit,
+            "--max-daily-mint" => limits.max_daily_mint = limit,
+            other => fatal(&format!("unknown argument: {other}")),
+        }
+    }
+    if !flags.is_empty() {
+        reg.set_guard_limits(limits);
+        save_registry(path, &reg);
+    }
+    let show = |limit: Option<u64>| limit.map_or_else(|| "off".to_string(), |v| v.to_string());
+    let GuardLimits {
+        max_single_credit,
+        max_account_daily_delta,
+        max_daily_mint,
+    } = limits;
+    println!("max_single_credit: {}", show(max_single_credit));
+    println!("max_account_daily_delta: {}", show(max_account_daily_delta));
+    println!("max_daily_mint: {}", show(max_daily_mint));
+}
+
+#[cfg(feature = "net")]
+fn cmd_stake_guard_override(args: Vec<String>) {
+    let mut policy_path: Option<String> = None;
+    let mut update_path: Option<String> = None;
+    let mut out: Option<String> = None;
+    let mut account: Option<String> = None;
+    let mut amount: Option<u64> = None;
+    let mut expires_at_epoch: Option<u64> = None;
+    let mut approvers = Vec::new();
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        let mut value = |flag: &str| {
+            iter.next()
+                .unwrap_or_else(|| fatal(&format!("{flag} expects a value")))
+        };
+        match arg.as_str() {
+            "--policy" => policy_path = Some(value("--policy")),
+            "--update" => update_path = Some(value("--update")),
+            "--out" => out = Some(value("--out")),
+            "--account" => account = Some(value("--account")),
+            "--amount" => {
+                amount = Some(
+                    value("--amount")
+                        .parse()
+                        .unwrap_or_else(|_| fatal("invalid --amount")),
+                );
+            }
+            "--expires-at-epoch" => {
+                expires_at_epoch = Some(
+                    value("--expires-at-epoch")
+                        .parse()
+                        .unwrap_or_else(|_| fatal("invalid --expires-at-epoch")),
+                );
+            }
+            "--approve" => approvers.push(value("--approve")),
+            "-h" | "--help" => {
+                print_stake_help();
+                return;
+            }
+            other => fatal(&format!("unknown argument: {other}")),
+        }
+    }
+    let policy_path = policy_path.unwrap_or_else(|| fatal("--policy is required"));
+    let out = out.unwrap_or_else(|| fatal("--out is required"));
+    let policy = MultisigPolicy::load(Path::new(&policy_path))
+        .unwrap_or_else(|err| fatal(&format!("failed to load policy {policy_path}: {err}")));
+    let mut update = match &update_path {
+        Some(path) => read_json_file::<GovernanceUpdate>(Path::new(path), "guard override"),
+        None => GuardOverride {
+            account: account.unwrap_or_else(|| fatal("--account is required")),
+            amount: amount.unwrap_or_else(|| fatal("--amount is required")),
+            expires_at_epoch: expires_at_epoch
+                .unwrap_or_else(|| fatal("--expires-at-epoch is required")),
+            nonce: now_millis(),
+        }
+        .to_update(),
+    };
+    for spec in &approvers {
+        let signer = load_or_derive_keypair(&Ed25519KeySource::from_spec(Some(spec)))
+            .unwrap_or_else(|err| fatal(&format!("failed to load key: {err}")));
+        let signer_b64 = power_house::net::encode_public_key_base64(&signer.verifying);
+        if !policy.signers().contains(&signer_b64) {
+            fatal(&format!("{signer_b64} is not a signer of this policy"));
+        }
+        update
+            .approve(&signer)
+            .unwrap_or_else(|err| fatal(&err.to_string()));
+    }
+    write_json_file(Path::new(&out), &update, "guard override");
+    let approvals = update.signatures.len();
+    let threshold = policy.threshold();
+    match GuardOverride::from_update(&update, &policy) {
+        Ok(grant) => println!(
+            "override {} approved ({approvals} of {threshold}); pass --override {out} to the credit",
+            grant.id()
+        ),
+        Err(_) => println!("{approvals} of {threshold} approvals recorded in {out}"),
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_stake_fee_sink(args: Vec<String>) {
+    let Some((path, flags)) = args.split_first() else {
+        print_stake_help();
+        exit(1);
+    };
+    let path = Path::new(path);
+    let mut reg = load_registry(path);
+    let mut policy: Option<FeeSinkPolicy> = None;
+    let mut burn_bps: Option<u16> = None;
+    let mut iter = flags.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = |flag: &str| {
+            iter.next()
+                .cloned()
+                .unwrap_or_else(|| fatal(&format!("{flag} expects a value")))
+        };
+        match arg.as_str() {
+            "--burn" => policy = Some(FeeSinkPolicy::Burn),
+            "--sink" => {
+                policy = Some(FeeSinkPolicy::Sink {
+                    account: value("--sink"),
+                })
+            }
+            "--split" => {
+                policy = Some(FeeSinkPolicy::Split {
+                    account: value("--split"),
+                    burn_bps: 0,
+                })
+            }
+            "--burn-bps" => {
+                burn_bps = Some(
+                    value("--burn-bps")
+                        .parse()
+                        .unwrap_or_else(|_| fatal("invalid --burn-bps")),
+                );
+            }
+            other => fatal(&format!("unknown argument: {other}")),
+        }
+    }
+    match (policy, burn_bps) {
+        (Some(FeeSinkPolicy::Split { account, .. }), Some(burn_bps)) => {
+            policy = Some(FeeSinkPolicy::Split { account, burn_bps });
+        }
+        (Some(FeeSinkPolicy::Split { .. }), None) => fatal("--split requires --burn-bps"),
+        (_, Some(_)) => fatal("--burn-bps applies only to --split"),
+        (other, None) => policy = other,
+    }
+    if let Some(policy) = policy {
+        reg.set_fee_sink(policy).unwrap_or_else(|err| fatal(&err));
+        save_registry(path, &reg);
+    }
+    match reg.fee_sink() {
+        FeeSinkPolicy::Burn => println!("fee policy: burn"),
+        FeeSinkPolicy::Sink { account } => println!("fee policy: sink -> {account}"),
+        FeeSinkPolicy::Split { account, burn_bps } => {
+            println!("fee policy: split, burn {burn_bps} bps, rest -> {account}")
+        }
+    }
+    let fees = reg.supply().fees_in_epoch(reg.epoch());
+    println!(
+        "epoch {}: burned={} sunk={} refunded={}",
+        fees.epoch, fees.burned, fees.sunk, fees.refunded
+    );
+}
+
+#[cfg(feature = "net")]
+fn cmd_stake_freeze_order(args: Vec<String>) {
+    let mut policy_path: Option<String> = None;
+    let mut update_path: Option<String> = None;
+    let mut out: Option<String> = None;
+    let mut account: Option<String> = None;
+    let mut freeze: Option<bool> = None;
+    let mut reason = String::new();
+    let mut approvers = Vec::new();
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        let mut value = |flag: &str| {
+            iter.next()
+                .unwrap_or_else(|| fatal(&format!("{flag} expects a value")))
+        };
+        match arg.as_str() {
+            "--policy" => policy_path = Some(value("--policy")),
+            "--update" => update_path = Some(value("--update")),
+            "--out" => out = Some(value("--out")),
+            "--account" => account = Some(value("--account")),
+            "--freeze" => freeze = Some(true),
+            "--release" => freeze = Some(false),
+            "--reason" => reason = value("--reason"),
+            "--approve" => approvers.push(value("--approve")),
+            "-h" | "--help" => {
+                print_stake_help();
+                return;
+            }
+            other => fatal(&format!("unknown argument: {other}")),
+        }
+    }
+    let policy_path = policy_path.unwrap_or_else(|| fatal("--policy is required"));
+    let out = out.unwrap_or_else(|| fatal("--out is required"));
+    let policy = MultisigPolicy::load(Path::new(&policy_path))
+        .unwrap_or_else(|err| fatal(&format!("failed to load policy {policy_path}: {err}")));
+    let mut update = match &update_path {
+        Some(path) => read_json_file::<GovernanceUpdate>(Path::new(path), "freeze order"),
+        None => {
+            let freeze = freeze.unwrap_or_else(|| fatal("--freeze or --release is required"));
+            if freeze && reason.trim().is_empty() {
+                fatal("--reason is required when freezing");
+            }
+            FreezeOrder {
+                account: account.unwrap_or_else(|| fatal("--account is required")),
+                freeze,
+                reason,
+                nonce: now_millis(),
+            }
+            .to_update()
+        }
+    };
+    for spec in &approvers {
+        let signer = load_or_derive_keypair(&Ed25519KeySource::from_spec(Some(spec)))
+            .unwrap_or_else(|err| fatal(&format!("failed to load key: {err}")));
+        let signer_b64 = power_house::net::encode_public_key_base64(&signer.verifying);
+        if !policy.signers().contains(&signer_b64) {
+            fatal(&format!("{signer_b64} is not a signer of this policy"));
+        }
+        update
+            .approve(&signer)
+            .unwrap_or_else(|err| fatal(&err.to_string()));
+    }
+    write_json_file(Path::new(&out), &update, "freeze order");
+    let approvals = update.signatures.len();
+    let threshold = policy.threshold();
+    if policy.verify_update(&update).is_ok() {
+        println!("freeze order approved ({approvals} of {threshold}); apply with stake freeze --order {out}");
+    } else {
+        println!("{approvals} of {threshold} approvals recorded in {out}");
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_stake_freeze(args: Vec<String>) {
+    let Some((path, flags)) = args.split_first() else {
+        print_stake_help();
+        exit(1);
+    };
+    let mut order_path: Option<String> = None;
+    let mut policy_path: Option<String> = None;
+    let mut iter = flags.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = |flag: &str| {
+            iter.next()
+                .cloned()
+                .unwrap_or_else(|| fatal(&format!("{flag} expects a value")))
+        };
+        match arg.as_str() {
+            "--order" => order_path = Some(value("--order")),
+            "--policy" => policy_path = Some(value("--policy")),
+            other => fatal(&format!("unknown argument: {other}")),
+        }
+    }
+    let order_path = order_path.unwrap_or_else(|| fatal("--order is required"));
+    let policy_path = policy_path.unwrap_or_else(|| fatal("--policy is required"));
+    let update: GovernanceUpdate = read_json_file(Path::new(&order_path), "freeze order");
+    let policy = MultisigPolicy::load(Path::new(&policy_path))
+        .unwrap_or_else(|err| fatal(&format!("failed to load policy {policy_path}: {err}")));
+    let path = Path::new(path);
+    let mut reg = load_registry(path);
+    let order = reg
+        .apply_freeze_update(&update, &policy)
+        .unwrap_or_else(|err| fatal(&err));
+    save_registry(path, &reg);
+    if order.freeze {
+        println!("frozen {}: {}", order.account, order.reason);
+    } else {
+        println!("released {}", order.account);
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_stake_frozen(args: Vec<String>) {
+    let Some(path) = args.first() else {
+        eprintln!("Usage: julian stake frozen <registry.json>");
+        exit(1);
+    };
+    let reg = load_registry(Path::new(path));
+    let frozen = reg.frozen_accounts();
+    if frozen.is_empty() {
+        println!("no frozen accounts");
+        return;
+    }
+    for (pk, hold) in frozen {
+        println!(
+            "{pk} since_epoch={} authority={} reason={}",
+            hold.since_epoch,
+            hold.authority.join(","),
+            hold.reason
+        );
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_stake_reward(args: Vec<String>) {
+    if args.len() < 3 {
+        eprintln!("Usage: julian stake reward <registry.json> <pubkey_b64> <amount>");
+        exit(1);
+    }
+    let path = Path::new(&args[0]);
+    let pk = &args[1];
+    let amount: u64 = args[2].parse().unwrap_or_else(|_| fatal("invalid amount"));
+    let mut reg = load_registry(path);
+    reg.mint(pk, amount, SupplyCause::Reward)
+        .unwrap_or_else(|err| fatal(&err));
+    save_registry(path, &reg);
+    if let Some(acct) = reg.account(pk) {
+        println!(
+            "rewarded {pk} by {amount}, balance={} stake={}",
+            acct.balance, acct.stake
+        );
+    }
+}
+
+#[cfg(feature = "net")]
+const SCHEMA_ROLLUP_BATCH: &str = "mfenx.powerhouse.rollup_batch.v1";
+#[cfg(feature = "net")]
+const SCHEMA_ROLLUP_PROOF: &str = "mfenx.powerhouse.rollup_proof.v1";
+#[cfg(feature = "net")]
+const SCHEMA_ROLLUP_RECEIPT: &str = "mfenx.powerhouse.rollup_receipt.v1";
+
+/// Output of `julian rollup submit`: the batch's DA commitment and the share
+/// it proves against.
+#[cfg(feature = "net")]
+#[derive(Debug, Serialize, Deserialize)]
+struct RollupBatchArtifact {
+    schema: String,
+    namespace: String,
+    size: usize,
+    data_shards: u8,
+    parity_shards: u8,
+    share_root: String,
+    pedersen_root: String,
+    #[serde(default)]
+    path_hash: power_house::rollup::RollupPathHash,
+    share_index: usize,
+    prev_state: String,
+    tx_root: String,
+    merkle_path: Vec<power_house::rollup::MerkleSibling>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    da_status: Option<String>,
+}
+
+/// Output of `julian rollup prove`, consumed by `settle --zk=` and `challenge --proof=`.
+#[cfg(feature = "net")]
+#[derive(Debug, Serialize, Deserialize)]
+struct RollupProofArtifact {
+    schema: String,
+    namespace: String,
+    share_root: String,
+    pedersen_root: String,
+    #[serde(default)]
+    path_hash: power_house::rollup::RollupPathHash,
+    prev_state: String,
+    next_state: String,
+    tx_root: String,
+    proof_b64: String,
+    public_inputs_b64: String,
+    merkle_path_b64: String,
+}
+
+#[cfg(feature = "net")]
+impl RollupProofArtifact {
+    fn commitment(&self) -> power_house::rollup::RollupCommitment {
+        power_house::rollup::RollupCommitment {
+            namespace: self.namespace.clone(),
+            share_root: self.share_root.clone(),
+            pedersen_root: Some(self.pedersen_root.clone()),
+            settlement_slot: None,
+            path_hash: self.path_hash,
+        }
+    }
+
+    fn proof(&self) -> power_house::rollup::ZkRollupProof {
+        let decode = |value: &str, label: &str| {
+            BASE64
+                .decode(value.as_bytes())
+                .unwrap_or_else(|_| fatal(&format!("{label} decode failed")))
+        };
+        power_house::rollup::ZkRollupProof {
+            proof: decode(&self.proof_b64, "proof"),
+            public_inputs: decode(&self.public_inputs_b64, "public inputs"),
+            merkle_path: decode(&self.merkle_path_b64, "merkle path"),
+        }
+    }
+}
+
+#[cfg(feature = "net")]
+fn rollup_hex32(value: &str, name: &str) -> [u8; 32] {
+    let bytes = hex::decode(value).unwrap_or_else(|err| fatal(&format!("{name}: bad hex: {err}")));
+    bytes
+        .try_into()
+        .unwrap_or_else(|_| fatal(&format!("{name} expects 32 bytes / 64 hex characters")))
+}
+
+#[cfg(feature = "net")]
+fn post_rollup_blob(
+    node: &str,
+    namespace: &str,
+    body: Vec<u8>,
+) -> Result<serde_json::Value, String> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|err| err.to_string())?;
+    runtime.block_on(async {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .map_err(|err| err.to_string())?;
+        let response = client
+            .post(format!("{}/submit_blob", node.trim_end_matches('/')))
+            .header("x-namespace", namespace)
+            .body(body)
+            .send()
+            .await
+            .map_err(|err| err.to_string())?;
+        let status = response.status();
+        let text = response.text().await.map_err(|err| err.to_string())?;
+        if !status.is_success() {
+            return Err(format!("HTTP {status}: {}", text.trim()));
+        }
+        serde_json::from_str(&text).map_err(|err| format!("invalid blob response: {err}"))
+    })
+}
+
+#[cfg(feature = "net")]
+fn cmd_rollup_submit(args: Vec<String>) {
+    if args.len() < 2 || args.iter().any(|a| a == "-h" || a == "--help") {
+        eprintln!("Usage: julian rollup submit <namespace> <batch-file> [--prev=<hex>] [--share=<idx>] [--hash=pedersen|poseidon] [--node=<url>] [--out=<batch.json>]");
+        exit(1);
+    }
+    let namespace = args[0].clone();
+    let batch = fs::read(&args[1])
+        .unwrap_or_else(|err| fatal(&format!("failed to read batch {}: {err}", args[1])));
+    let mut prev = [0u8; 32];
+    let mut share_index = 0usize;
+    let mut path_hash = power_house::rollup::RollupPathHash::Pedersen;
+    let mut node: Option<String> = None;
+    let mut out: Option<String> = None;
+    for arg in args.iter().skip(2) {
+        if let Some(value) = arg.strip_prefix("--prev=") {
+            prev = rollup_hex32(value, "--prev");
+        } else if let Some(value) = arg.strip_prefix("--share=") {
+            share_index = value
+                .parse()
+                .unwrap_or_else(|_| fatal("--share expects a share index"));
+        } else if let Some(value) = arg.strip_prefix("--hash=") {
+            path_hash = serde_json::from_value(serde_json::json!(value))
+                .unwrap_or_else(|_| fatal("--hash expects pedersen or poseidon"));
+        } else if let Some(value) = arg.strip_prefix("--node=") {
+            node = Some(value.to_string());
+        } else if let Some(value) = arg.strip_prefix("--out=") {
+            out = Some(value.to_string());
+        } else {
+            fatal(&format!("unknown option {arg}"));
+        }
+    }
+
+    // Same shard layout as the blob service, so the roots match what a node commits.
+    let (data_shards, parity_shards) = (4u8, 2u8);
+    let (_, shares) = power_house::net::encode_shares(&batch, data_shards, parity_shards)
+        .unwrap_or_else(|err| fatal(&format!("failed to encode batch: {err}")));
+    let (path_root, merkle_path) = match path_hash {
+        power_house::rollup::RollupPathHash::Pedersen => {
+            let proof = power_house::net::pedersen_share_proof(&shares.share_hashes, share_index)
+                .unwrap_or_else(|err| fatal(&format!("share {share_index}: {err}")));
+            let path = proof
+                .path
+                .iter()
+                .map(|node| power_house::rollup::MerkleSibling {
+                    left: node.left,
+                    hash: hex::encode(node.sibling),
+                })
+                .collect();
+            (shares.pedersen_root.clone(), path)
+        }
+        power_house::rollup::RollupPathHash::Poseidon => {
+            let path = power_house::rollup::poseidon_merkle_path(&shares.share_hashes, share_index)
+                .unwrap_or_else(|err| fatal(&format!("share {share_index}: {err}")));
+            let root = power_house::rollup::poseidon_merkle_root(&shares.share_hashes);
+            (hex::encode(root), path)
+        }
+    };
+    let da_status = node.map(|node| {
+        let response = post_rollup_blob(&node, &namespace, batch.clone())
+            .unwrap_or_else(|err| fatal(&format!("blob submission failed: {err}")));
+        if response["share_root"].as_str() != Some(shares.share_root.as_str()) {
+            fatal("node committed a different share_root than the local encoding");
+        }
+        response["da_status"].as_str().unwrap_or("ok").to_string()
+    });
+
+    let artifact = RollupBatchArtifact {
+        schema: SCHEMA_ROLLUP_BATCH.to_string(),
+        namespace,
+        size: batch.len(),
+        data_shards,
+        parity_shards,
+        share_root: shares.share_root,
+        pedersen_root: path_root,
+        path_hash,
+        share_index,
+        prev_state: hex::encode(prev),
+        tx_root: hex::encode(shares.share_hashes[share_index]),
+        merkle_path,
+        da_status,
+    };
+    match out {
+        Some(path) => {
+            write_json_file(Path::new(&path), &artifact, "rollup batch");
+            println!(
+                "batch {} share_root={} written to {path}",
+                artifact.namespace, artifact.share_root
+            );
+        }
+        None => println!(
+            "{}",
+            serde_json::to_string_pretty(&artifact).expect("batch JSON")
+        ),
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_rollup_prove(args: Vec<String>) {
+    if args.is_empty() || args.iter().any(|a| a == "-h" || a == "--help") {
+        eprintln!(
+            "Usage: julian rollup prove <batch.json> --pk=<proving-key> [--out=<proof.json>]"
+        );
+        exit(1);
+    }
+    let batch: RollupBatchArtifact = read_json_file(Path::new(&args[0]), "rollup batch");
+    if batch.schema != SCHEMA_ROLLUP_BATCH {
+        fatal(&format!("unexpected batch schema {}", batch.schema));
+    }
+    let mut out: Option<String> = None;
+    let mut key_path: Option<String> = None;
+    for arg in args.iter().skip(1) {
+        if let Some(value) = arg.strip_prefix("--out=") {
+            out = Some(value.to_string());
+        } else if let Some(value) = arg.strip_prefix("--pk=") {
+            key_path = Some(value.to_string());
+        } else {
+            fatal(&format!("unknown option {arg}"));
+        }
+    }
+    let key_path = key_path.unwrap_or_else(|| fatal("rollup prove needs --pk=<proving-key>"));
+    let key = power_house::rollup::CircuitProvingKey::load(Path::new(&key_path))
+        .unwrap_or_else(|err| fatal(&err));
+    let commitment = power_house::rollup::RollupCommitment {
+        namespace: batch.namespace.clone(),
+        share_root: batch.share_root.clone(),
+        pedersen_root: Some(batch.pedersen_root.clone()),
+        settlement_slot: None,
+        path_hash: batch.path_hash,
+    };
+    let public_inputs = power_house::rollup::rollup_public_inputs(
+        &rollup_hex32(&batch.prev_state, "prev_state"),
+        &rollup_hex32(&batch.tx_root, "tx_root"),
+        &rollup_hex32(&batch.pedersen_root, "pedersen_root"),
+    );
+    let merkle_path = serde_json::to_vec(&batch.merkle_path).expect("merkle path JSON");
+    let proof = power_house::rollup::prove_zk_rollup(
+        &commitment,
+        public_inputs,
+        merkle_path,
+        &key,
+        &mut rand_core::OsRng,
+    )
+    .unwrap_or_else(|err| fatal(&format!("proof generation failed: {err}")));
+    let artifact = RollupProofArtifact {
+        schema: SCHEMA_ROLLUP_PROOF.to_string(),
+        namespace: batch.namespace,
+        share_root: batch.share_root,
+        pedersen_root: batch.pedersen_root,
+        path_hash: batch.path_hash,
+        prev_state: batch.prev_state,
+        next_state: hex::encode(&proof.public_inputs[32..64]),
+        tx_root: batch.tx_root,
+        proof_b64: BASE64.encode(&proof.proof),
+        public_inputs_b64: BASE64.encode(&proof.public_inputs),
+        merkle_path_b64: BASE64.encode(&proof.merkle_path),
+    };
+    match out {
+        Some(path) => {
+            write_json_file(Path::new(&path), &artifact, "rollup proof");
+            println!(
+                "proved {} next_state={} written to {path}",
+                artifact.namespace, artifact.next_state
+            );
+        }
+        None => println!(
+            "{}",
+            serde_json::to_string_pretty(&artifact).expect("proof JSON")
+        ),
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_rollup_challenge(args: Vec<String>) {
+    if args.len() < 3 || args.iter().any(|a| a == "-h" || a == "--help") {
+        eprintln!("Usage: julian rollup challenge <registry.json> <batch.json> <reason> [--proof=<proof.json> --transfer-vk=<file>] [--evidence=<file>] [--out=<fault.json>] [--outbox=<path>] [--store=<path>]");
+        exit(1);
+    }
+    let registry = Path::new(&args[0]);
+    let batch: RollupBatchArtifact = read_json_file(Path::new(&args[1]), "rollup batch");
+    let mut reason = args[2].clone();
+    let mut proof_path: Option<String> = None;
+    let mut transfer_vk: Option<String> = None;
+    let mut evidence: Option<String> = None;
+    let mut out: Option<String> = None;
+    let mut outbox: Option<String> = None;
+    let mut store: Option<String> = None;
+    for arg in args.iter().skip(3) {
+        if let Some(value) = arg.strip_prefix("--proof=") {
+            proof_path = Some(value.to_string());
+        } else if let Some(value) = arg.strip_prefix("--transfer-vk=") {
+            transfer_vk = Some(value.to_string());
+        } else if let Some(value) = arg.strip_prefix("--evidence=") {
+            let bytes = fs::read(value)
+                .unwrap_or_else(|err| fatal(&format!("failed to read evidence {value}: {err}")));
+            evidence = Some(BASE64.encode(bytes));
+        } else if let Some(value) = arg.strip_prefix("--out=") {
+            out = Some(value.to_string());
+        } else if let Some(value) = arg.strip_prefix("--outbox=") {
+            outbox = Some(value.to_string());
+        } else if let Some(value) = arg.strip_prefix("--store=") {
+            store = Some(value.to_string());
+        } else {
+            fatal(&format!("unknown option {arg}"));
+        }
+    }
+    let commitment = power_house::rollup::RollupCommitment {
+        namespace: batch.namespace.clone(),
+        share_root: batch.share_root.clone(),
+        pedersen_root: Some(batch.pedersen_root.clone()),
+        settlement_slot: None,
+        path_hash: batch.path_hash,
+    };
+    if let Some(path) = proof_path {
+        let proof: RollupProofArtifact = read_json_file(Path::new(&path), "rollup proof");
+        if proof.share_root != batch.share_root || proof.namespace != batch.namespace {
+            fatal("proof does not belong to this batch");
+        }
+        let key_path = transfer_vk
+            .as_deref()
+            .unwrap_or_else(|| fatal("--proof needs --transfer-vk=<file>"));
+        let key = power_house::rollup::CircuitVerifyingKey::load(Path::new(key_path))
+            .unwrap_or_else(|err| fatal(&err));
+        match power_house::rollup::verify_zk_rollup(&commitment, &proof.proof(), &key) {
+            Ok(()) => fatal_code(2, "challenge rejected: the proof verifies"),
+            Err(err) => reason = format!("{reason}: {err}"),
+        }
+    }
+
+    let fault = power_house::rollup::build_rollup_fault(&commitment, &reason, evidence);
+    let store_path = rollup_store_path(registry, store);
+    let stored = record_rollup_outcome(&store_path, &commitment, (&fault).into());
+    let outbox_path: PathBuf = outbox.map(PathBuf::from).unwrap_or_else(|| {
+        registry
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join("evidence_outbox.jsonl")
+    });
+    append_rollup_fault(&outbox_path, &fault);
+    if let Some(path) = out {
+        write_json_file(Path::new(&path), &fault, "rollup fault");
+    }
+    println!(
+        "challenged rollup {} commitment={} sequence={}; evidence written to {}",
+        fault.namespace,
+        fault.commitment,
+        stored.sequence,
+        outbox_path.display()
+    );
+}
+
+#[cfg(feature = "net")]
+fn cmd_rollup_settle(args: Vec<String>) {
+    if args.len() < 5 {
+        eprintln!("Usage: julian rollup settle <registry.json> <namespace> <share_root> <payer_b64> <fee> [zk|optimistic] [operator_b64] [attesters_csv] [--proof file] [--public-inputs file] [--merkle-path file] [--outbox path] [--store path] [--zk=proof.json] [--transfer-vk=<file>] [--batch-vk=<file>] [--out=receipt.json]");
+        exit(1);
+    }
+    let registry = Path::new(&args[0]);
+    let namespace = args[1].clone();
+    let share_root = args[2].clone();
+    let payer = args[3].clone();
+    let fee: u64 = args[4].parse().unwrap_or_else(|_| fatal("invalid fee"));
+
+    let mut mode = "optimistic".to_string();
+    let mut operator_pk: Option<String> = None;
+    let mut attesters: Vec<String> = Vec::new();
+    let mut proof_path: Option<String> = None;
+    let mut public_inputs_path: Option<String> = None;
+    let mut merkle_path_file: Option<String> = None;
+    let mut outbox: Option<String> = None;
+    let mut store: Option<String> = None;
+    let mut zk_artifact: Option<RollupProofArtifact> = None;
+    let mut transfer_vk: Option<String> = None;
+    let mut batch_vk: Option<String> = None;
+    let mut out: Option<String> = None;
+
+    for arg in args.iter().skip(5) {
+        if arg.starts_with("--proof=") {
+            proof_path = Some(arg.trim_start_matches("--proof=").to_string());
+        } else if arg.starts_with("--public-inputs=") {
+            public_inputs_path = Some(arg.trim_start_matches("--public-inputs=").to_string());
+        } else if arg.starts_with("--merkle-path=") {
+            merkle_path_file = Some(arg.trim_start_matches("--merkle-path=").to_string());
+        } else if arg.starts_with("--outbox=") {
+            outbox = Some(arg.trim_start_matches("--outbox=").to_string());
+        } else if arg.starts_with("--store=") {
+            store = Some(arg.trim_start_matches("--store=").to_string());
+        } else if let Some(path) = arg.strip_prefix("--zk=") {
+            zk_artifact = Some(read_json_file(Path::new(path), "rollup proof"));
+            mode = "zk".to_string();
+        } else if let Some(path) = arg.strip_prefix("--transfer-vk=") {
+            transfer_vk = Some(path.to_string());
+        } else if let Some(path) = arg.strip_prefix("--batch-vk=") {
+            batch_vk = Some(path.to_string());
+        } else if let Some(path) = arg.strip_prefix("--out=") {
+            out = Some(path.to_string());
+        } else if mode == "optimistic" && (arg == "zk" || arg == "optimistic") {
+            mode = arg.clone();
+        } else if operator_pk.is_none() {
+            operator_pk = Some(arg.clone());
+        } else if attesters.is_empty() {
+            attesters = arg
+                .split(',')
+                .filter(|v| !v.is_empty())
+                .map(|v| v.to_string())
+                .collect();
+        }
+    }
+
+    let operator_pk = operator_pk.unwrap_or_else(|| payer.clone());
+    let commitment = match &zk_artifact {
+        Some(artifact) => {
+            if artifact.namespace != namespace || artifact.share_root != share_root {
+                fatal("proof artifact does not match namespace and share_root");
+            }
+            artifact.commitment()
+        }
+        None => power_house::rollup::RollupCommitment {
+            namespace,
+            share_root,
+            pedersen_root: None,
+            settlement_slot: None,
+            path_hash: power_house::rollup::RollupPathHash::Pedersen,
+        },
+    };
+
+    let zk_proof = if let Some(artifact) = &zk_artifact {
+        artifact.proof()
+    } else if let (Some(pp), Some(pi_path), Some(mp_path)) =
+        (proof_path, public_inputs_path, merkle_path_file)
+    {
+        let proof_bytes = std::fs::read(&pp).unwrap_or_else(|_| fatal("failed to read proof file"));
+        let public_inputs =
+            std::fs::read(&pi_path).unwrap_or_else(|_| fatal("failed to read public inputs file"));
+        let merkle_path =
+            std::fs::read(&mp_path).unwrap_or_else(|_| fatal("failed to read merkle path file"));
+        power_house::rollup::ZkRollupProof {
+            proof: proof_bytes,
+            public_inputs,
+            merkle_path,
+        }
+    } else {
+        power_house::rollup::ZkRollupProof {
+            proof: Vec::new(),
+            public_inputs: Vec::new(),
+            merkle_path: Vec::new(),
+        }
+    };
+
+    let result = match mode.as_str() {
+        "zk" => power_house::rollup::settle_rollup_with_rewards(
+            registry,
+            commitment.clone(),
+            &payer,
+            &operator_pk,
+            &attesters,
+            fee,
+            rollup_zk_mode(zk_proof, transfer_vk.as_deref(), batch_vk.as_deref()),
+        ),
+        _ => power_house::rollup::settle_rollup_with_rewards(
+            registry,
+            commitment.clone(),
+            &payer,
+            &operator_pk,
+            &attesters,
+            fee,
+            power_house::rollup::RollupSettlementMode::Optimistic(Vec::new()),
+        ),
+    };
+    let store_path = rollup_store_path(registry, store);
+    match result {
+        Ok(receipt) => {
+            let stored = record_rollup_outcome(&store_path, &commitment, (&receipt).into());
+            println!(
+                "settled rollup for {payer} fee={fee} commitment={} sequence={}",
+                receipt.commitment.share_root, stored.sequence
+            );
+            if let Some(path) = out {
+                let document = serde_json::json!({
+                    "schema": SCHEMA_ROLLUP_RECEIPT,
+                    "namespace": receipt.commitment.namespace,
+                    "share_root": receipt.commitment.share_root,
+                    "pedersen_root": receipt.commitment.pedersen_root,
+                    "mode": mode,
+                    "payer": receipt.payer,
+                    "operator": operator_pk,
+                    "attesters": attesters,
+                    "fee": receipt.fee,
+                    "sequence": stored.sequence,
+                    "recorded_at_ms": stored.recorded_at_ms,
+                });
+                write_json_file(Path::new(&path), &document, "rollup receipt");
+            }
+        }
+        Err(err) => {
+            record_rollup_outcome(&store_path, &commitment, (&err).into());
+            let outbox_path: PathBuf = outbox.map(PathBuf::from).unwrap_or_else(|| {
+                registry
+                    .parent()
+                    .unwrap_or(Path::new("."))
+                    .join("evidence_outbox.jsonl")
+            });
+            append_rollup_fault(&outbox_path, &err);
+            eprintln!("fault evidence written to {}", outbox_path.display());
+            fatal(&format!("settlement failed: {}", err.reason));
+        }
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_rollup_settle_file(args: Vec<String>) {
+    if args.len() < 2 {
+        eprintln!(
+            "Usage: julian rollup settle-file <registry.json> <request.json> [--outbox path] [--transfer-vk=<file>] [--batch-vk=<file>]"
+        );
+        exit(1);
+    }
+    #[derive(serde::Deserialize)]
+    struct RollupSettleRequest {
+        namespace: String,
+        share_root: String,
+        #[serde(default)]
+        pedersen_root: Option<String>,
+        payer_pk: String,
+        #[serde(default)]
+        operator_pk: Option<String>,
+        #[serde(default)]
+        attesters: Option<Vec<String>>,
+        fee: u64,
+        #[serde(default)]
+        mode: Option<String>,
+        #[serde(default)]
+        proof_b64: Option<String>,
+        #[serde(default)]
+        public_inputs_b64: Option<String>,
+        #[serde(default)]
+        merkle_path_b64: Option<String>,
+        #[serde(default)]
+        path_hash: power_house::rollup::RollupPathHash,
+    }
+    let registry = Path::new(&args[0]);
+    let req_bytes =
+        std::fs::read(&args[1]).unwrap_or_else(|_| fatal("failed to read request file"));
+    let mut outbox: Option<String> = None;
+    let mut store: Option<String> = None;
+    let mut transfer_vk: Option<String> = None;
+    let mut batch_vk: Option<String> = None;
+    for arg in args.iter().skip(2) {
+        if arg.starts_with("--outbox=") {
+            outbox = Some(arg.trim_start_matches("--outbox=").to_string());
+        } else if arg.starts_with("--store=") {
+            store = Some(arg.trim_start_matches("--store=").to_string());
+        } else if let Some(path) = arg.strip_prefix("--transfer-vk=") {
+            transfer_vk = Some(path.to_string());
+        } else if let Some(path) = arg.strip_prefix("--batch-vk=") {
+            batch_vk = Some(path.to_string());
+        }
+    }
+    let req: RollupSettleRequest =
+        serde_json::from_slice(&req_bytes).unwrap_or_else(|_| fatal("invalid request JSON"));
+    let pedersen_root = req
+        .pedersen_root
+        .clone()
+        .unwrap_or_else(|| req.share_root.clone());
+    let commitment = power_house::rollup::RollupCommitment {
+        namespace: req.namespace.clone(),
+        share_root: req.share_root.clone(),
+        pedersen_root: Some(pedersen_root),
+        settlement_slot: None,
+        path_hash: req.path_hash,
+    };
+    let operator_pk = req
+        .operator_pk
+        .clone()
+        .unwrap_or_else(|| req.payer_pk.clone());
+    let attesters = req.attesters.clone().unwrap_or_default();
+    let mode = req.mode.clone().unwrap_or_else(|| "optimistic".to_string());
+    let zk_proof = if let (Some(p_b64), Some(pi_b64), Some(mp_b64)) = (
+        req.proof_b64.as_ref(),
+        req.public_inputs_b64.as_ref(),
+        req.merkle_path_b64.as_ref(),
+    ) {
+        let proof = BASE64
+            .decode(p_b64.as_bytes())
+            .unwrap_or_else(|_| fatal("proof decode failed"));
+        let public_inputs = BASE64
+            .decode(pi_b64.as_bytes())
+            .unwrap_or_else(|_| fatal("public inputs decode failed"));
+        let merkle_path = BASE64
+            .decode(mp_b64.as_bytes())
+            .unwrap_or_else(|_| fatal("merkle path decode failed"));
+        power_house::rollup::ZkRollupProof {
+            proof,
+            public_inputs,
+            merkle_path,
+        }
+    } else {
+        power_house::rollup::ZkRollupProof {
+            proof: Vec::new(),
+            public_inputs: Vec::new(),
+            merkle_path: Vec::new(),
+        }
+    };
+    let mode_enum = if mode == "zk" {
+        rollup_zk_mode(zk_proof, transfer_vk.as_deref(), batch_vk.as_deref())
+    } else {
+        power_house::rollup::RollupSettlementMode::Optimistic(Vec::new())
+    };
+    let store_path = rollup_store_path(registry, store);
+    match power_house::rollup::settle_rollup_with_rewards(
+        registry,
+        commitment.clone(),
+        &req.payer_pk,
+        &operator_pk,
+        &attesters,
+        req.fee,
+        mode_enum,
+    ) {
+        Ok(receipt) => {
+            let stored = record_rollup_outcome(&store_path, &commitment, (&receipt).into());
+            println!(
+                "settled rollup fee={} commitment={} sequence={}",
+                receipt.fee, receipt.commitment.share_root, stored.sequence
+            );
+        }
+        Err(fault) => {
+            record_rollup_outcome(&store_path, &commitment, (&fault).into());
+            let outbox_path: PathBuf = outbox.map(PathBuf::from).unwrap_or_else(|| {
+                registry
+                    .parent()
+                    .unwrap_or(Path::new("."))
+                    .join("evidence_outbox.jsonl")
+            });
+            append_rollup_fault(&outbox_path, &fault);
+            eprintln!("fault evidence written to {}", outbox_path.display());
+            fatal(&format!("settlement failed: {}", fault.reason));
+        }
+    }
+}
+
+/// Zk settlement mode for `proof`; a batch proof needs `--batch-vk` and a
+/// single-transfer proof `--transfer-vk`.
+#[cfg(feature = "net")]
+fn rollup_zk_mode(
+    proof: power_house::rollup::ZkRollupProof,
+    transfer_vk: Option<&str>,
+    batch_vk: Option<&str>,
+) -> power_house::rollup::RollupSettlementMode {
+    let load = |path: &str| {
+        Box::new(
+            power_house::rollup::CircuitVerifyingKey::load(Path::new(path))
+                .unwrap_or_else(|err| fatal(&err)),
+        )
+    };
+    if proof.is_batch() {
+        let path = batch_vk.unwrap_or_else(|| fatal("batch proofs need --batch-vk=<file>"));
+        power_house::rollup::RollupSettlementMode::ZkBatch(proof, load(path))
+    } else {
+        let path = transfer_vk
+            .unwrap_or_else(|| fatal("single-transfer proofs need --transfer-vk=<file>"));
+        power_house::rollup::RollupSettlementMode::Zk(proof, load(path))
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_rollup_transfer_setup(args: Vec<String>) {
+    if args.len() != 4 || args.iter().any(|a| a == "-h" || a == "--help") {
+        eprintln!("Usage: julian rollup transfer-setup <pedersen|poseidon> <path-depth> <proving-key-out> <verifying-key-out>");
+        exit(1);
+    }
+    let path_hash = match args[0].as_str() {
+        "pedersen" => power_house::rollup::RollupPathHash::Pedersen,
+        "poseidon" => power_house::rollup::RollupPathHash::Poseidon,
+        other => fatal(&format!("unknown path hash {other}")),
+    };
+    let depth: usize = args[1]
+        .parse()
+        .unwrap_or_else(|_| fatal("invalid path depth"));
+    let key = power_house::rollup::rollup_transfer_setup(path_hash, depth, &mut rand_core::OsRng)
+        .unwrap_or_else(|err| fatal(&err));
+    key.save(Path::new(&args[2]))
+        .unwrap_or_else(|err| fatal(&err));
+    key.verifying_key()
+        .save(Path::new(&args[3]))
+        .unwrap_or_else(|err| fatal(&err));
+    println!(
+        "wrote {} proving key to {} and verifying key to {}",
+        key.circuit(),
+        args[2],
+        args[3]
+    );
+}
+
+#[cfg(feature = "net")]
+fn cmd_rollup_batch_setup(args: Vec<String>) {
+    if args.len() != 2 || args.iter().any(|a| a == "-h" || a == "--help") {
+        eprintln!("Usage: julian rollup batch-setup <proving-key-out> <verifying-key-out>");
+        exit(1);
+    }
+    let key = power_house::rollup::rollup_batch_setup(&mut rand_core::OsRng)
+        .unwrap_or_else(|err| fatal(&err));
+    key.save(Path::new(&args[0]))
+        .unwrap_or_else(|err| fatal(&err));
+    key.verifying_key()
+        .save(Path::new(&args[1]))
+        .unwrap_or_else(|err| fatal(&err));
+    println!(
+        "wrote {} proving key to {} and verifying key to {}",
+        key.circuit(),
+        args[0],
+        args[1]
+    );
+}
+
+#[cfg(feature = "net")]
+fn cmd_rollup_commitments(args: Vec<String>) {
+    if args.len() < 2 || args.iter().any(|a| a == "-h" || a == "--help") {
+        eprintln!("Usage: julian rollup commitments <store.json> <namespace> [sequence]");
+        exit(1);
+    }
+    let store = power_house::net::RollupCommitmentStore::load(Path::new(&args[0]))
+        .unwrap_or_else(|err| fatal(&err.to_string()));
+    let namespace = &args[1];
+    let root = store.root().map(hex::encode);
+    let body = match args.get(2) {
+        None => serde_json::json!({
+            "namespace": namespace,
+            "root": root,
+            "commitments": store.namespace(namespace),
+        }),
+        Some(sequence) => {
+            let sequence: u64 = sequence
+                .parse()
+                .unwrap_or_else(|_| fatal("sequence must be an integer"));
+            let record = store
+                .get(namespace, sequence)
+                .unwrap_or_else(|| fatal(&format!("no commitment {namespace}/{sequence}")));
+            let proof = store
+                .prove(namespace, sequence)
+                .expect("stored commitment has a proof");
+            serde_json::json!({
+                "commitment": record,
+                "leaf": hex::encode(record.leaf()),
+                "root": root,
+                "proof": serde_json::from_str::<serde_json::Value>(&proof.to_json_string())
+                    .expect("proof JSON"),
+            })
+        }
+    };
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&body).expect("commitment JSON")
+    );
+}
+
+fn cmd_node_run(args: Vec<String>) {
+    if args.len() < 3 {
+        eprintln!("Usage: julian node run <node_id> <log_dir> <output_anchor>");
+        exit(1);
+    }
+    let node_id = &args[0];
+    println!("{} node {node_id} starting...", network_id());
+    let log_dir = Path::new(&args[1]);
+    let output = Path::new(&args[2]);
+    let anchor = match load_anchor_from_logs(log_dir) {
+        Ok(anchor) => anchor,
+        Err(err) => {
+            eprintln!("error: {err}");
+            exit(1);
+        }
+    };
+    if let Err(err) = write_anchor(output, &anchor) {
+        eprintln!("error writing anchor: {err}");
+        exit(1);
+    }
+    println!(
+        "{} node {node_id} anchor written to {}",
+        network_id(),
+        output.display()
+    );
+    println!("anchor summary:\n{}", format_anchor(&anchor));
+}
+
+fn cmd_node_anchor(args: Vec<String>) {
+    const USAGE: &str = "Usage: julian node anchor <log_dir> [--app <application_id>]";
+    let mut log_dir: Option<PathBuf> = None;
+    let mut application_id: Option<String> = None;
+    #[cfg(feature = "net")]
+    let mut lookup: Option<AnchorLookup> = None;
+    #[cfg(feature = "net")]
+    let mut archives: Vec<PathBuf> = Vec::new();
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--app" => {
+                application_id = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--app expects a value")),
+                );
+            }
+            #[cfg(feature = "net")]
+            "--at-epoch" => {
+                let value = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--at-epoch expects a value"));
+                let epoch = value
+                    .parse()
+                    .unwrap_or_else(|_| fatal(&format!("invalid epoch: {value}")));
+                lookup = Some(AnchorLookup::Epoch(epoch));
+            }
+            #[cfg(feature = "net")]
+            "--digest" => {
+                let value = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--digest expects a value"));
+                let digest = power_house::transcript_digest_from_hex(
+                    value.strip_prefix("0x").unwrap_or(&value),
+                )
+                .unwrap_or_else(|err| fatal(&format!("invalid digest {value}: {err}")));
+                lookup = Some(AnchorLookup::Digest(digest));
+            }
+            #[cfg(feature = "net")]
+            "--archive" => archives.push(PathBuf::from(
+                iter.next()
+                    .unwrap_or_else(|| fatal("--archive expects a file")),
+            )),
+            other if other.starts_with("--") => fatal(&format!("unknown argument: {other}")),
+            other if log_dir.is_none() => log_dir = Some(PathBuf::from(other)),
+            _ => fatal(USAGE),
+        }
+    }
+    let log_dir = log_dir.unwrap_or_else(|| fatal(USAGE));
+    #[cfg(feature = "net")]
+    if let Some(lookup) = lookup {
+        if application_id.is_some() {
+            fatal("--app cannot be combined with --at-epoch or --digest");
+        }
+        cmd_node_anchor_history(&log_dir, &archives, lookup);
+        return;
+    }
+    match load_anchor_from_logs(&log_dir) {
+        Ok(mut anchor) => {
+            if let Some(application_id) = &application_id {
+                anchor.entries = anchor
+                    .entries_for_application(application_id)
+                    .cloned()
+                    .collect();
+            }
+            println!("{}", format_anchor(&anchor));
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            exit(1);
+        }
+    }
+}
+
+fn cmd_node_fsck(args: Vec<String>) {
+    const USAGE: &str = "Usage: julian node fsck <log_dir> [--modulus <p>] [--quarantine]";
+    let mut log_dir: Option<PathBuf> = None;
+    let mut modulus: Option<u64> = None;
+    let mut quarantine = false;
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return;
+            }
+            "--modulus" => {
+                let value = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--modulus expects a value"));
+                modulus = Some(
+                    value
+                        .parse()
+                        .unwrap_or_else(|_| fatal("invalid --modulus value")),
+                );
+            }
+            "--quarantine" => quarantine = true,
+            other if other.starts_with("--") => fatal(&format!("unknown argument: {other}")),
+            other if log_dir.is_none() => log_dir = Some(PathBuf::from(other)),
+            _ => fatal(USAGE),
+        }
+    }
+    let log_dir = log_dir.unwrap_or_else(|| fatal(USAGE));
+    let before = load_anchor_from_logs(&log_dir);
+    let records = iter_ledger_logs(&log_dir).unwrap_or_else(|err| fatal(&format!("error: {err}")));
+
+    let mut checked = 0usize;
+    let mut faults: Vec<(String, Option<PathBuf>, LogFault)> = Vec::new();
+    for record in records {
+        checked += 1;
+        match record {
+            Ok(record) => {
+                if let Err(fault) = record.check(modulus) {
+                    let path = record.path().map(Path::to_path_buf);
+                    faults.push((record.name, path, fault));
+                }
+            }
+            Err(message) => {
+                let kind = if message.contains("checksum mismatch") {
+                    LogFaultKind::BadDigest
+                } else {
+                    LogFaultKind::ParseError
+                };
+                faults.push(("journal".to_string(), None, LogFault { kind, message }));
+            }
+        }
+    }
+
+    println!(
+        "fsck {}: {checked} record(s), {} fault(s)",
+        log_dir.display(),
+        faults.len()
+    );
+    let quarantine_dir = log_dir.join("quarantine");
+    let mut unresolved = 0usize;
+    for (name, path, fault) in &faults {
+        println!("  {:<17} {}", fault.kind.label(), fault.message);
+        println!("  {:<17} suggestion: {}", "", fsck_suggestion(fault.kind));
+        match (quarantine, path) {
+            (true, Some(path)) => {
+                let target = quarantine_dir.join(name);
+                if let Err(err) =
+                    fs::create_dir_all(&quarantine_dir).and_then(|_| fs::rename(path, &target))
+                {
+                    fatal(&format!("failed to quarantine {name}: {err}"));
+                }
+                println!("  {:<17} quarantined to {}", "", target.display());
+            }
+            (true, None) => {
+                unresolved += 1;
+                println!(
+                    "  {:<17} journal records cannot be quarantined individually",
+                    ""
+                );
+            }
+            (false, _) => unresolved += 1,
+        }
+    }
+
+    let after = load_anchor_from_logs_with(&log_dir, modulus, true);
+    println!("anchor delta:");
+    println!("  before: {}", fsck_anchor_summary(&before));
+    println!("  after:  {}", fsck_anchor_summary(&after));
+    if let (Ok(before), Ok(after)) = (&before, &after) {
+        for entry in &before.entries {
+            if !after.entries.contains(entry) {
+                println!("  - {}", entry.statement);
+            }
+        }
+    } else {
+        for (name, _, _) in &faults {
+            println!("  - {name}");
+        }
+    }
+    if unresolved > 0 {
+        exit(1);
+    }
+}
+
+fn fsck_suggestion(kind: LogFaultKind) -> &'static str {
+    match kind {
+        LogFaultKind::BadDigest => {
+            "contents were altered after writing; restore the record from a peer or backup"
+        }
+        LogFaultKind::MissingStatement => {
+            "restore the leading `statement:` line or drop the record"
+        }
+        LogFaultKind::ParseError => {
+            "record is truncated or hand-edited; restore it from a peer or backup"
+        }
+        LogFaultKind::StaleModulus => {
+            "record was produced under another field; move it to that ledger's log directory"
+        }
+    }
+}
+
+fn fsck_anchor_summary(anchor: &Result<LedgerAnchor, String>) -> String {
+    match anchor {
+        Ok(anchor) => format!(
+            "{} entries, fold_digest={}",
+            anchor.entries.len(),
+            anchor
+                .metadata
+                .fold_digest
+                .as_ref()
+                .map(power_house::transcript_digest_to_hex)
+                .unwrap_or_else(|| "-".to_string())
+        ),
+        Err(err) => format!("unavailable ({err})"),
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_node_gc(args: Vec<String>) {
+    const USAGE: &str =
+        "Usage: julian node gc <log_dir> --expiry-epochs <N> [--epoch <n>] [--dry-run]";
+    let mut log_dir = None;
+    let mut expiry_epochs: Option<u64> = None;
+    let mut epoch: Option<u64> = None;
+    let mut dry_run = false;
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--expiry-epochs" => {
+                expiry_epochs = Some(
+                    iter.next()
+                        .and_then(|value| value.parse().ok())
+                        .unwrap_or_else(|| fatal("--expiry-epochs expects an epoch count")),
+                );
+            }
+            "--epoch" => {
+                epoch = Some(
+                    iter.next()
+                        .and_then(|value| value.parse().ok())
+                        .unwrap_or_else(|| fatal("--epoch expects a checkpoint epoch")),
+                );
+            }
+            "--dry-run" => dry_run = true,
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return;
+            }
+            other if other.starts_with("--") => fatal(&format!("unknown argument: {other}")),
+            other if log_dir.is_none() => log_dir = Some(other.to_string()),
+            other => fatal(&format!("unexpected positional argument: {other}")),
+        }
+    }
+    let log_dir = PathBuf::from(log_dir.unwrap_or_else(|| fatal(USAGE)));
+    let expiry_epochs = expiry_epochs.unwrap_or_else(|| fatal(USAGE));
+    let epoch = epoch.unwrap_or_else(|| {
+        power_house::net::load_latest_checkpoint(&log_dir)
+            .unwrap_or_else(|err| fatal(&err.to_string()))
+            .map_or(0, |checkpoint| checkpoint.epoch)
+    });
+    let report = collect_expired_entries(&log_dir, expiry_epochs, epoch, dry_run)
+        .unwrap_or_else(|err| fatal(&err.to_string()));
+    let verb = if dry_run { "would archive" } else { "archived" };
+    for entry in &report.archived {
+        println!(
+            "{verb} {} {} (first seen epoch {})",
+            entry.name, entry.digest, entry.first_seen_epoch
+        );
+    }
+    println!(
+        "epoch {}: finalized={} pending={} {verb}={}",
+        report.epoch,
+        report.finalized,
+        report.pending,
+        report.archived.len()
+    );
+}
+
+#[cfg(feature = "net")]
+fn cmd_node_archive(args: Vec<String>) {
+    const USAGE: &str = "Usage: julian node archive <export|verify|export-cids|verify-cids|import> ...\n  export <log_dir> --node-id <id> --output <file> [--key <spec>]\n  verify <archive.json>\n  export-cids <log_dir> --node-id <id> --output <dir> [--key <spec>] [--chunk-size <bytes>]\n  verify-cids <dir>\n  import <log_dir> [--digest <hex>]...";
+    let mut iter = args.into_iter();
+    match iter.next().as_deref() {
+        Some(mode @ ("export" | "export-cids")) => {
+            let content = mode == "export-cids";
+            let mut log_dir = None;
+            let mut node_id = None;
+            let mut output = None;
+            let mut key_spec: Option<String> = None;
+            let mut chunk_size = DEFAULT_CHUNK_SIZE;
+            while let Some(arg) = iter.next() {
+                match arg.as_str() {
+                    "--node-id" => {
+                        node_id = Some(
+                            iter.next()
+                                .unwrap_or_else(|| fatal("--node-id expects a value")),
+                        );
+                    }
+                    "--output" => {
+                        output = Some(
+                            iter.next()
+                                .unwrap_or_else(|| fatal("--output expects a value")),
+                        );
+                    }
+                    "--key" => {
+                        key_spec = Some(
+                            iter.next()
+                                .unwrap_or_else(|| fatal("--key expects a value")),
+                        );
+                    }
+                    "--chunk-size" if content => {
+                        chunk_size = iter
+                            .next()
+                            .and_then(|value| value.parse().ok())
+                            .filter(|&size: &usize| size > 0)
+                            .unwrap_or_else(|| fatal("--chunk-size expects a positive integer"));
+                    }
+                    other if other.starts_with("--") => {
+                        fatal(&format!("unknown argument: {other}"))
+                    }
+                    other if log_dir.is_none() => log_dir = Some(other.to_string()),
+                    other => fatal(&format!("unexpected positional argument: {other}")),
+                }
+            }
+            let log_dir = PathBuf::from(log_dir.unwrap_or_else(|| fatal(USAGE)));
+            let node_id = node_id.unwrap_or_else(|| fatal(USAGE));
+            let output = PathBuf::from(output.unwrap_or_else(|| fatal(USAGE)));
+            let key_material =
+                load_or_derive_keypair(&Ed25519KeySource::from_spec(key_spec.as_deref()))
+                    .unwrap_or_else(|err| fatal(&format!("failed to load key: {err}")));
+            let head = load_anchor_from_logs(&log_dir)
+                .unwrap_or_else(|err| fatal(&format!("failed to load anchor: {err}")));
+            let head =
+                AnchorJson::from_ledger(node_id.clone(), 1, &head, now_millis(), Vec::new(), None)
+                    .unwrap_or_else(|err| fatal(&format!("anchor conversion failed: {err}")));
+            let archive =
+                export_archive(&log_dir, &node_id, &key_material, Some(head), now_millis())
+                    .unwrap_or_else(|err| fatal(&err.to_string()));
+            if content {
+                let manifest = export_content_bundle(&log_dir, &archive, &output, chunk_size)
+                    .unwrap_or_else(|err| fatal(&err.to_string()));
+                println!(
+                    "content bundle written to {} (archive {}, {} anchor(s), {} transcript(s))",
+                    output.display(),
+                    manifest.archive.cid,
+                    manifest.anchors.len(),
+                    manifest.transcripts.len()
+                );
+                return;
+            }
+            archive
+                .write(&output)
+                .unwrap_or_else(|err| fatal(&err.to_string()));
+            println!(
+                "archive written to {} ({} epoch(s), head link {})",
+                output.display(),
+                archive.entries.len(),
+                archive
+                    .entries
+                    .last()
+                    .map(|entry| entry.link.as_str())
+                    .unwrap_or("-")
+            );
+        }
+        Some("verify") => {
+            let path = iter.next().unwrap_or_else(|| fatal(USAGE));
+            let archive = AnchorArchive::read(Path::new(&path))
+                .unwrap_or_else(|err| fatal(&format!("FAIL: {err}")));
+            match verify_archive(&archive) {
+                Ok(summary) => println!(
+                    "PASS: archive for {} verified ({} epoch(s), {} checkpoint signature(s), head link {})",
+                    archive.node_id,
+                    summary.entries,
+                    summary.checkpoint_signatures,
+                    summary.head_link
+                ),
+                Err(err) => fatal(&format!("FAIL: {err}")),
+            }
+        }
+        Some("verify-cids") => {
+            let path = iter.next().unwrap_or_else(|| fatal(USAGE));
+            match verify_content_bundle(Path::new(&path)) {
+                Ok(summary) => println!(
+                    "PASS: content bundle verified ({} block(s), {} anchor(s), {} transcript(s), head link {})",
+                    summary.blocks,
+                    summary.anchors,
+                    summary.transcripts,
+                    summary.archive.head_link
+                ),
+                Err(err) => fatal(&format!("FAIL: {err}")),
+            }
+        }
+        Some("import") => {
+            let mut log_dir = None;
+            let mut digests = Vec::new();
+            while let Some(arg) = iter.next() {
+                match arg.as_str() {
+                    "--digest" => digests.push(
+                        iter.next()
+                            .unwrap_or_else(|| fatal("--digest expects a value")),
+                    ),
+                    other if other.starts_with("--") => {
+                        fatal(&format!("unknown argument: {other}"))
+                    }
+                    other if log_dir.is_none() => log_dir = Some(other.to_string()),
+                    other => fatal(&format!("unexpected positional argument: {other}")),
+                }
+            }
+            let log_dir = PathBuf::from(log_dir.unwrap_or_else(|| fatal(USAGE)));
+            let restored = import_archived_entries(&log_dir, &digests)
+                .unwrap_or_else(|err| fatal(&err.to_string()));
+            for entry in &restored {
+                println!(
+                    "restored {} {} ({})",
+                    entry.name, entry.digest, entry.statement
+                );
+            }
+            println!(
+                "{} entr(ies) restored to {}",
+                restored.len(),
+                log_dir.display()
+            );
+        }
+        Some("-h") | Some("--help") => println!("{USAGE}"),
+        _ => fatal(USAGE),
+    }
+}
+
+fn cmd_node_reconcile(args: Vec<String>) {
+    let mut receipt = ReceiptOptions::default();
+    let mut positional = Vec::new();
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        if !receipt.parse(&arg, &mut iter) {
+            positional.push(arg);
+        }
+    }
+    if positional.len() < 3 {
+        eprintln!("Usage: julian node reconcile <log_dir> <peer_anchor> <quorum> [--sign-receipt <file> --key <spec>]");
+        exit(1);
+    }
+    let log_dir = Path::new(&positional[0]);
+    let peer_path = Path::new(&positional[1]);
+    let quorum: usize = positional[2].parse().unwrap_or_else(|_| {
+        eprintln!("Invalid quorum value: {}", positional[2]);
+        exit(1);
+    });
+
+    let outcome = reconcile_anchor_files(log_dir, peer_path, quorum);
+    receipt.emit(
+        "node reconcile",
+        &[("log_dir", log_dir), ("peer_anchor", peer_path)],
+        &[("quorum", quorum.to_string())],
+        &outcome
+            .as_ref()
+            .map(|_| format!("Finality reached with quorum {quorum}."))
+            .map_err(Clone::clone),
+    );
+    match outcome {
+        Ok((local, peer)) => {
+            println!("Finality reached with quorum {quorum}.");
+            println!("Local anchor:\n{}", format_anchor(&local));
+            println!("Peer anchor:\n{}", format_anchor(&peer));
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            exit(1);
+        }
+    }
+}
+
+fn reconcile_anchor_files(
+    log_dir: &Path,
+    peer_path: &Path,
+    quorum: usize,
+) -> Result<(LedgerAnchor, LedgerAnchor), String> {
+    let local = load_anchor_from_logs(log_dir).map_err(|err| format!("error: {err}"))?;
+    let peer = read_anchor(peer_path).map_err(|err| format!("error reading peer anchor: {err}"))?;
+    let votes = [
+        AnchorVote {
+            anchor: &local,
+            public_key: b"LOCAL_OFFLINE",
+        },
+        AnchorVote {
+            anchor: &peer,
+            public_key: b"PEER_FILE",
+        },
+    ];
+    reconcile_anchors_with_quorum(&votes, quorum)
+        .map_err(|err| format!("Quorum check failed: {err}"))?;
+    Ok((local, peer))
+}
+
+#[cfg(feature = "net")]
+enum AnchorLookup {
+    Epoch(u64),
+    Digest([u8; 32]),
+}
+
+#[cfg(feature = "net")]
+fn cmd_node_anchor_history(log_dir: &Path, archives: &[PathBuf], lookup: AnchorLookup) {
+    let mut history = AnchorHistory::new();
+    history
+        .index_checkpoints(log_dir)
+        .unwrap_or_else(|err| fatal(&err.to_string()));
+    for path in archives {
+        let archive = AnchorArchive::read(path)
+            .unwrap_or_else(|err| fatal(&format!("{}: {err}", path.display())));
+        history
+            .index_archive(&archive)
+            .unwrap_or_else(|err| fatal(&format!("{}: {err}", path.display())));
+    }
+    let found = match &lookup {
+        AnchorLookup::Epoch(epoch) => history.at_epoch(*epoch),
+        AnchorLookup::Digest(digest) => history.by_digest(digest),
+    };
+    match found {
+        Some(anchor) => println!("{}", serde_json::to_string_pretty(anchor).unwrap()),
+        None => {
+            let wanted = match lookup {
+                AnchorLookup::Epoch(epoch) => format!("epoch {epoch}"),
+                AnchorLookup::Digest(digest) => format!("digest {}", hex::encode(digest)),
+            };
+            fatal_code(
+                2,
+                &format!(
+                    "no anchor for {wanted} among {} indexed epochs",
+                    history.len()
+                ),
+            );
+        }
+    }
+}
+
+fn cmd_node_prove(args: Vec<String>) {
+    if args.len() < 3 {
+        eprintln!("Usage: julian node prove <log_dir> <entry_index> <leaf_index> [output.json]");
+        exit(1);
+    }
+    let log_dir = Path::new(&args[0]);
+    let entry_index: usize = args[1]
+        .parse()
+        .unwrap_or_else(|_| fatal("invalid entry index"));
+    let leaf_index: usize = args[2]
+        .parse()
+        .unwrap_or_else(|_| fatal("invalid leaf index"));
+    let anchor = load_anchor_from_logs(log_dir).unwrap_or_else(|err| fatal(&err.to_string()));
+    let entry = anchor
+        .entries
+        .get(entry_index)
+        .unwrap_or_else(|| fatal("entry index out of bounds"));
+    if leaf_index >= entry.hashes.len() {
+        fatal("leaf index out of bounds");
+    }
+    let proof = power_house::build_merkle_proof(&entry.hashes, leaf_index)
+        .ok_or_else(|| "unable to build proof".to_string())
+        .unwrap_or_else(|err| fatal(&err));
+    if proof.root != entry.merkle_root {
+        fatal("computed proof root does not match entry merkle root");
+    }
+    let proof_json: serde_json::Value = serde_json::from_str(&proof.to_json_string()).unwrap();
+    let document = serde_json::json!({
+        "entry_index": entry_index,
+        "statement": entry.statement,
+        "leaf_index": leaf_index,
+        "leaf": power_house::transcript_digest_to_hex(&entry.hashes[leaf_index]),
+        "merkle_root": power_house::transcript_digest_to_hex(&entry.merkle_root),
+        "proof": proof_json
+    });
+    if let Some(path) = args.get(3) {
+        if let Err(err) = fs::write(path, serde_json::to_string_pretty(&document).unwrap()) {
+            fatal(&format!("failed to write proof: {err}"));
+        }
+    } else {
+        println!("{}", serde_json::to_string_pretty(&document).unwrap());
+    }
+}
+
+fn cmd_node_verify_proof(args: Vec<String>) {
+    if args.len() != 2 {
+        eprintln!("Usage: julian node verify-proof <anchor_file> <proof_file>");
+        exit(1);
+    }
+    let anchor = read_anchor(Path::new(&args[0]))
+        .unwrap_or_else(|err| fatal(&format!("failed to read anchor: {err}")));
+    let proof_text = fs::read_to_string(&args[1])
+        .unwrap_or_else(|err| fatal(&format!("failed to read proof file: {err}")));
+    let document: serde_json::Value = serde_json::from_str(&proof_text)
+        .unwrap_or_else(|err| fatal(&format!("invalid proof JSON: {err}")));
+    let entry_index = document
+        .get("entry_index")
+        .and_then(|v| v.as_u64())
+        .unwrap_or_else(|| fatal("proof missing entry_index")) as usize;
+    let leaf_index = document
+        .get("leaf_index")
+        .and_then(|v| v.as_u64())
+        .unwrap_or_else(|| fatal("proof missing leaf_index")) as usize;
+    let proof_value = document
+        .get("proof")
+        .unwrap_or_else(|| fatal("proof missing inner proof object"));
+    let proof_json = serde_json::to_string(proof_value).unwrap();
+    let proof = power_house::MerkleProof::from_json_str(&proof_json)
+        .unwrap_or_else(|err| fatal(&format!("invalid proof: {err}")));
+    if entry_index >= anchor.entries.len() {
+        fatal("entry index out of bounds");
+    }
+    let entry = &anchor.entries[entry_index];
+    if leaf_index >= entry.hashes.len() {
+        fatal("leaf index out of bounds");
+    }
+    if proof.root != entry.merkle_root {
+        fatal("proof root does not match anchor merkle root");
+    }
+    if proof.leaf != entry.hashes[leaf_index] {
+        fatal("proof leaf does not match anchor digest");
+    }
+    if !power_house::verify_merkle_proof(&proof) {
+        fatal("invalid Merkle proof");
+    }
+    println!(
+        "Proof verified for statement '{}' (entry {}, leaf {}).",
+        entry.statement, entry_index, leaf_index
+    );
+}
+
+#[cfg(feature = "net")]
+fn cmd_net_start(mut args: Vec<String>) {
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        print_net_start_help();
+        return;
+    }
+
+    refresh_migration_mode_from_env();
+    // Config file values are defaults; flags given on the command line win.
+    let file = take_config_file(&mut args).net;
+    let parse_listen = |raw: &str, what: &str| {
+        raw.parse::<Multiaddr>()
+            .unwrap_or_else(|_| fatal(&format!("invalid multiaddr for {what}")))
+    };
+    let mut node_id = file.node_id;
+    let mut log_dir = file.log_dir.map(|dir| dir.display().to_string());
+    let mut listen = file.listen.map(|raw| parse_listen(&raw, "listen"));
+    let mut bootstraps: Vec<Multiaddr> = file
+        .bootstraps
+        .iter()
+        .map(|raw| parse_listen(raw, "bootstraps"))
+        .collect();
+    let mut quorum: usize = file.quorum.unwrap_or(1);
+    let mut broadcast_ms: u64 = file.broadcast_interval_ms.unwrap_or(5_000);
+    let mut key_spec: Option<String> = file.key;
+    let mut identity_path: Option<String> = None;
+    let mut passphrase_source: Option<PassphraseSource> = None;
+    let mut anchor_topic_spec: Option<String> = file.anchor_topic;
+    let mut gossip_shard_spec: Option<String> = None;
+    let mut gossip_bridge_topics_spec: Option<String> =
+        (!file.bridge_topics.is_empty()).then(|| file.bridge_topics.join(","));
+    let mut bft_enabled = file.bft.unwrap_or(false);
+    let mut bft_round_ms_spec: Option<String> = file.bft_round_ms.map(|ms| ms.to_string());
+    let mut metrics_addr_spec: Option<String> = file.metrics.map(|addr| addr.to_string());
+    let mut policy_allowlist_spec: Option<String> = None;
+    let mut policy_spec: Option<String> = None;
+    let mut checkpoint_interval_spec: Option<String> =
+        file.checkpoint_interval.map(|n| n.to_string());
+    let mut blob_dir_spec: Option<String> = file.blob_dir.map(|dir| dir.display().to_string());
+    let mut blob_listen_spec: Option<String> = file.blob_listen.map(|addr| addr.to_string());
+    let mut max_blob_bytes_spec: Option<String> = None;
+    let mut blob_retention_days_spec: Option<String> = None;
+    let mut blob_policy_spec: Option<String> = None;
+    let mut blob_auth_token_spec: Option<String> = None;
+    let mut blob_max_concurrency_spec: Option<String> = None;
+    let mut blob_request_timeout_ms_spec: Option<String> = None;
+    let mut attestation_quorum_spec: Option<String> =
+        file.attestation_quorum.map(|n| n.to_string());
+    let mut tokio_threads_spec: Option<String> = None;
+    let mut token_mode_contract_spec: Option<String> = None;
+    let mut token_oracle_rpc_spec: Option<String> = None;
+    let mut evm_rpc_listen_spec: Option<String> = file.evm_rpc_listen.map(|addr| addr.to_string());
+    let mut evm_chain_id_spec: Option<String> = file.evm_chain_id.map(|id| id.to_string());
+    let mut rpc_cors = CorsPolicy::default();
+    let mut native_max_block_interval: Option<u64> = file.native_max_block_interval_secs;
+    let mut native_prune_keep: Option<u64> = file.native_prune_keep;
+    let mut entry_gc_epochs: Option<u64> = file.entry_gc_epochs;
+    let mut native_pending_ttl: Option<u64> = file.native_pending_ttl_secs;
+    let mut native_max_pending: Option<usize> = file.native_max_pending;
+    let mut genesis_spec: Option<String> = None;
+    let mut log_ship_endpoint: Option<String> = None;
+    let mut metrics_push_endpoint: Option<String> = None;
+    let mut metrics_push_interval: Option<u64> = None;
+    let mut metrics_push_auth: Option<String> = None;
+    let mut checkpoint_queries: Vec<[u8; 32]> = Vec::new();
+    let mut max_http_connections: Option<usize> = file.max_http_connections;
+    let mut rpc_tier_limits = RpcTierLimits::default();
+    for spec in &file.rpc_tiers {
+        rpc_tier_limits
+            .apply_spec(spec)
+            .unwrap_or_else(|err| fatal(&format!("invalid rpc_tiers in --config: {err}")));
+    }
+    let mut max_anchors_per_minute: Option<u32> = file.max_anchors_per_minute;
+    let mut evm_gas_margin: Option<u64> = file.evm_gas_margin_percent;
+    let mut governance_fee_schedule: Option<PathBuf> = None;
+    let mut governance_multisig: Option<PathBuf> = None;
+    let mut governance_allowlist: Option<PathBuf> = None;
+    let mut webhooks: Option<WebhookConfig> = None;
+    let mut alerts: Option<AlertConfig> = None;
+    let mut epoch_reward_pool: Option<u64> = None;
+    let mut reward_weighting = RewardWeighting::Equal;
+    let mut reward_asset: Option<String> = None;
+    let mut storage_backend = StorageBackend::Json;
+    #[cfg_attr(not(debug_assertions), allow(unused_mut))]
+    let mut chaos = ChaosConfig::default();
+
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--node-id" => {
+                node_id = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--node-id expects a value")),
+                );
+            }
+            "--log-dir" => {
+                log_dir = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--log-dir expects a value")),
+                );
+            }
+            "--listen" => {
+                let value = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--listen expects a value"));
+                let addr: Multiaddr = value
+                    .parse()
+                    .unwrap_or_else(|_| fatal("invalid multiaddr for --listen"));
+                listen = Some(addr);
+            }
+            "--bootstrap" => {
+                let value = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--bootstrap expects a value"));
+                let addr: Multiaddr = value
+                    .parse()
+                    .unwrap_or_else(|_| fatal("invalid multiaddr for --bootstrap"));
+                bootstraps.push(addr);
+            }
+            "--bootnodes" => {
+                let value = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--bootnodes expects a value"));
+                for raw in value.split(',') {
+                    let trimmed = raw.trim();
+                    if trimmed.is_empty() {
+                        continue;
+                    }
+                    let addr: Multiaddr = trimmed
+                        .parse()
+                        .unwrap_or_else(|_| fatal("invalid multiaddr for --bootnodes"));
+                    bootstraps.push(addr);
+                }
+            }
+            "--broadcast-interval" => {
+                let value = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--broadcast-interval expects a value"));
+                broadcast_ms = value
+                    .parse()
+                    .unwrap_or_else(|_| fatal("invalid --broadcast-interval"));
+            }
+            "--quorum" => {
+                let value = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--quorum expects a value"));
+                quorum = value.parse().unwrap_or_else(|_| fatal("invalid --quorum"));
+            }
+            "--key" => {
+                key_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--key expects a value")),
+                );
+            }
+            "--passphrase-fd"
+            | "--passphrase-env"
+            | "--passphrase-file"
+            | "--passphrase-keychain" => {
+                if passphrase_source.is_some() {
+                    fatal("use only one --passphrase-* source");
+                }
+                let mut value = || {
+                    iter.next()
+                        .unwrap_or_else(|| fatal(&format!("{arg} expects a value")))
+                };
+                passphrase_source = Some(match arg.as_str() {
+                    "--passphrase-fd" => PassphraseSource::Fd(
+                        value()
+                            .parse()
+                            .unwrap_or_else(|_| fatal("--passphrase-fd expects a descriptor")),
+                    ),
+                    "--passphrase-env" => PassphraseSource::Env(value()),
+                    "--passphrase-file" => PassphraseSource::File(PathBuf::from(value())),
+                    // The account is resolved from --identity once parsing is done.
+                    _ => PassphraseSource::Keychain(String::new()),
+                });
+            }
+            "--identity" => {
+                identity_path = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--identity expects a value")),
+                );
+            }
+            "--anchor-topic" => {
+                anchor_topic_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--anchor-topic expects a value")),
+                );
+            }
+            "--gossip-shard" => {
+                gossip_shard_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--gossip-shard expects a value")),
+                );
+            }
+            "--gossip-bridge-topics" => {
+                gossip_bridge_topics_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--gossip-bridge-topics expects a value")),
+                );
+            }
+            "--bft" => {
+                bft_enabled = true;
+            }
+            "--bft-round-ms" => {
+                bft_round_ms_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--bft-round-ms expects a value")),
+                );
+            }
+            "--metrics" => {
+                metrics_addr_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--metrics expects a value")),
+                );
+            }
+            "--policy-allowlist" => {
+                policy_allowlist_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--policy-allowlist expects a value")),
+                );
+            }
+            "--policy" => {
+                policy_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--policy expects a value")),
+                );
+            }
+            "--checkpoint-interval" => {
+                checkpoint_interval_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--checkpoint-interval expects a value")),
+                );
+            }
+            "--blob-dir" => {
+                blob_dir_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--blob-dir expects a value")),
+                );
+            }
+            "--blob-listen" => {
+                blob_listen_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--blob-listen expects a value")),
+                );
+            }
+            "--max-blob-bytes" => {
+                max_blob_bytes_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--max-blob-bytes expects a value")),
+                );
+            }
+            "--blob-retention-days" => {
+                blob_retention_days_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--blob-retention-days expects a value")),
+                );
+            }
+            "--blob-policy" => {
+                blob_policy_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--blob-policy expects a value")),
+                );
+            }
+            "--blob-auth-token" => {
+                blob_auth_token_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--blob-auth-token expects a value")),
+                );
+            }
+            "--blob-max-concurrency" => {
+                blob_max_concurrency_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--blob-max-concurrency expects a value")),
+                );
+            }
+            "--blob-request-timeout-ms" => {
+                blob_request_timeout_ms_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--blob-request-timeout-ms expects a value")),
+                );
+            }
+            "--attestation-quorum" => {
+                attestation_quorum_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--attestation-quorum expects a value")),
+                );
+            }
+            "--tokio-threads" => {
+                tokio_threads_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--tokio-threads expects a value")),
+                );
+            }
+            "--token-mode" => {
+                token_mode_contract_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--token-mode expects a value")),
+                );
+            }
+            "--token-oracle" => {
+                token_oracle_rpc_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--token-oracle expects a value")),
+                );
+            }
+            "--evm-rpc-listen" => {
+                evm_rpc_listen_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--evm-rpc-listen expects a value")),
+                );
+            }
+            "--rpc-cors-origins" => {
+                let list = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--rpc-cors-origins expects a value"));
+                rpc_cors.allowed_origins = if list.eq_ignore_ascii_case("none") {
+                    Vec::new()
+                } else {
+                    parse_topic_list(&list)
+                };
+            }
+            "--rpc-cors-headers" => {
+                rpc_cors.allowed_headers = parse_topic_list(
+                    &iter
+                        .next()
+                        .unwrap_or_else(|| fatal("--rpc-cors-headers expects a value")),
+                );
+            }
+            "--rpc-no-preflight" => rpc_cors.serve_preflight = false,
+            "--native-max-block-interval" => {
+                native_max_block_interval = Some(
+                    iter.next()
+                        .and_then(|value| value.parse().ok())
+                        .filter(|&secs: &u64| secs > 0)
+                        .unwrap_or_else(|| {
+                            fatal("--native-max-block-interval expects seconds > 0")
+                        }),
+                );
+            }
+            "--entry-gc-epochs" => {
+                entry_gc_epochs = Some(
+                    iter.next()
+                        .and_then(|value| value.parse().ok())
+                        .unwrap_or_else(|| fatal("--entry-gc-epochs expects an epoch count")),
+                );
+            }
+            "--native-prune-keep" => {
+                native_prune_keep = Some(
+                    iter.next()
+                        .and_then(|value| value.parse().ok())
+                        .unwrap_or_else(|| fatal("--native-prune-keep expects a block count")),
+                );
+            }
+            "--native-pending-ttl" => {
+                native_pending_ttl = Some(
+                    iter.next()
+                        .and_then(|value| value.parse().ok())
+                        .filter(|&secs: &u64| secs > 0)
+                        .unwrap_or_else(|| fatal("--native-pending-ttl expects seconds > 0")),
+                );
+            }
+            "--native-max-pending" => {
+                native_max_pending = Some(
+                    iter.next()
+                        .and_then(|value| value.parse().ok())
+                        .filter(|&max: &usize| max > 0)
+                        .unwrap_or_else(|| fatal("--native-max-pending expects a count > 0")),
+                );
+            }
+            "--evm-chain-id" => {
+                evm_chain_id_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--evm-chain-id expects a value")),
+                );
+            }
+            "--genesis" => {
+                genesis_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--genesis expects a value")),
+                );
+            }
+            "--metrics-push" => {
+                metrics_push_endpoint = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--metrics-push expects a value")),
+                );
+            }
+            "--metrics-push-interval" => {
+                metrics_push_interval = Some(
+                    iter.next()
+                        .and_then(|value| value.parse().ok())
+                        .filter(|&secs: &u64| secs > 0)
+                        .unwrap_or_else(|| fatal("--metrics-push-interval expects seconds > 0")),
+                );
+            }
+            "--metrics-push-auth" => {
+                metrics_push_auth = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--metrics-push-auth expects a value")),
+                );
+            }
+            "--log-ship-endpoint" => {
+                log_ship_endpoint = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--log-ship-endpoint expects a value")),
+                );
+            }
+            "--max-http-connections" => {
+                let raw = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--max-http-connections expects a value"));
+                let value: usize = raw
+                    .parse()
+                    .unwrap_or_else(|_| fatal("invalid --max-http-connections"));
+                if value == 0 {
+                    fatal("--max-http-connections must be at least 1");
+                }
+                max_http_connections = Some(value);
+            }
+            "--rpc-tier" => {
+                let spec = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--rpc-tier expects <tier>=<concurrency>:<queue>"));
+                rpc_tier_limits
+                    .apply_spec(&spec)
+                    .unwrap_or_else(|err| fatal(&format!("invalid --rpc-tier: {err}")));
+            }
+            "--evm-gas-margin" => {
+                let raw = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--evm-gas-margin expects a percentage"));
+                evm_gas_margin = Some(
+                    raw.parse()
+                        .unwrap_or_else(|_| fatal("invalid --evm-gas-margin")),
+                );
+            }
+            "--max-anchors-per-minute" => {
+                let raw = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--max-anchors-per-minute expects a value"));
+                max_anchors_per_minute = Some(
+                    raw.parse()
+                        .unwrap_or_else(|_| fatal("invalid --max-anchors-per-minute")),
+                );
+            }
+            "--governance-fee-schedule" => {
+                governance_fee_schedule =
+                    Some(PathBuf::from(iter.next().unwrap_or_else(|| {
+                        fatal("--governance-fee-schedule expects a path")
+                    })));
+            }
+            "--governance-multisig" => {
+                governance_multisig =
+                    Some(PathBuf::from(iter.next().unwrap_or_else(|| {
+                        fatal("--governance-multisig expects a path")
+                    })));
+            }
+            "--governance-allowlist" => {
+                governance_allowlist =
+                    Some(PathBuf::from(iter.next().unwrap_or_else(|| {
+                        fatal("--governance-allowlist expects a path")
+                    })));
+            }
+            "--alerts" => {
+                let path = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--alerts expects a path"));
+                alerts = Some(
+                    AlertConfig::load(Path::new(&path))
+                        .unwrap_or_else(|err| fatal(&err.to_string())),
+                );
+            }
+            "--webhooks" => {
+                let path = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--webhooks expects a path"));
+                webhooks = Some(
+                    WebhookConfig::load(Path::new(&path))
+                        .unwrap_or_else(|err| fatal(&err.to_string())),
+                );
+            }
+            "--epoch-reward-pool" => {
+                let raw = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--epoch-reward-pool expects a value"));
+                epoch_reward_pool = Some(
+                    raw.parse()
+                        .unwrap_or_else(|_| fatal("invalid --epoch-reward-pool")),
+                );
+            }
+            "--reward-weighting" => {
+                let raw = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--reward-weighting expects a value"));
+                reward_weighting = match raw.as_str() {
+                    "equal" => RewardWeighting::Equal,
+                    "stake" => RewardWeighting::Stake,
+                    _ => fatal("--reward-weighting expects equal or stake"),
+                };
+            }
+            "--storage" => {
+                let raw = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--storage expects json or sqlite"));
+                storage_backend = StorageBackend::parse(&raw).unwrap_or_else(|err| fatal(&err));
+            }
+            "--reward-asset" => {
+                let raw = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--reward-asset expects an asset id"));
+                validate_asset_id(&raw).unwrap_or_else(|err| fatal(&err));
+                reward_asset = Some(raw);
+            }
+            "--find-checkpoint" => {
+                let raw = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--find-checkpoint expects a value"));
+                let digest = hex::decode(raw.trim())
+                    .ok()
+                    .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+                    .unwrap_or_else(|| fatal("--find-checkpoint expects a 32-byte hex digest"));
+                checkpoint_queries.push(digest);
+            }
+            #[cfg(debug_assertions)]
+            "--inject-latency-ms" => {
+                chaos.latency_ms = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--inject-latency-ms expects a value"))
+                    .parse()
+                    .unwrap_or_else(|_| fatal("invalid --inject-latency-ms"));
+            }
+            #[cfg(debug_assertions)]
+            "--drop-rate" => {
+                chaos.drop_rate = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--drop-rate expects a value"))
+                    .parse()
+                    .unwrap_or_else(|_| fatal("invalid --drop-rate"));
+            }
+            #[cfg(debug_assertions)]
+            "--duplicate-rate" => {
+                chaos.duplicate_rate = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--duplicate-rate expects a value"))
+                    .parse()
+                    .unwrap_or_else(|_| fatal("invalid --duplicate-rate"));
+            }
+            other => fatal(&format!("unknown argument: {other}")),
         }
-    };
-    if let Err(err) = write_anchor(output, &anchor) {
-        eprintln!("error writing anchor: {err}");
-        std::process::exit(1);
     }
-    println!(
-        "{NETWORK_ID} node {node_id} anchor written to {}",
-        output.display()
-    );
-    println!("anchor summary:\n{}", format_anchor(&anchor));
-}
 
-fn cmd_node_anchor(args: Vec<String>) {
-    if args.len() != 1 {
-        eprintln!("Usage: julian node anchor <log_dir>");
-        std::process::exit(1);
-    }
-    let log_dir = Path::new(&args[0]);
-    match load_anchor_from_logs(log_dir) {
-        Ok(anchor) => println!("{}", format_anchor(&anchor)),
-        Err(err) => {
-            eprintln!("error: {err}");
-            std::process::exit(1);
-        }
+    let node_id = node_id.unwrap_or_else(|| fatal("--node-id is required"));
+    let log_dir = log_dir.unwrap_or_else(|| fatal("--log-dir is required"));
+    let listen_addr = listen.unwrap_or_else(|| fatal("--listen is required"));
+    if identity_path.is_some() && key_spec.is_some() {
+        fatal("use either --key or --identity, not both");
     }
-}
 
-fn cmd_node_reconcile(args: Vec<String>) {
-    if args.len() < 3 {
-        eprintln!("Usage: julian node reconcile <log_dir> <peer_anchor> <quorum>");
-        std::process::exit(1);
+    if passphrase_source.is_some() && identity_path.is_none() {
+        fatal("--passphrase-* sources require --identity");
     }
-    let log_dir = Path::new(&args[0]);
-    let peer_path = Path::new(&args[1]);
-    let quorum: usize = args[2].parse().unwrap_or_else(|_| {
-        eprintln!("Invalid quorum value: {}", args[2]);
-        std::process::exit(1);
-    });
 
-    let local = match load_anchor_from_logs(log_dir) {
-        Ok(anchor) => anchor,
-        Err(err) => {
-            eprintln!("error: {err}");
-            std::process::exit(1);
+    let key_material = if let Some(path) = identity_path {
+        let source = match passphrase_source.unwrap_or(PassphraseSource::Prompt) {
+            PassphraseSource::Keychain(_) => {
+                PassphraseSource::Keychain(keychain_account(Path::new(&path)))
+            }
+            source => source,
+        };
+        println!(
+            "QSYS|mod=IDENTITY|evt=PASSPHRASE_SOURCE|source={}",
+            source.label()
+        );
+        let passphrase = match source {
+            PassphraseSource::Prompt => prompt_password("Identity passphrase: ")
+                .unwrap_or_else(|err| fatal(&format!("failed to read passphrase: {err}"))),
+            source => read_passphrase(&source)
+                .unwrap_or_else(|err| fatal(&format!("failed to read passphrase: {err}"))),
+        };
+        match load_encrypted_identity(Path::new(&path), &passphrase) {
+            Ok(material) => material,
+            Err(err) => fatal(&format!("failed to load identity: {err}")),
         }
-    };
-    let peer = match read_anchor(peer_path) {
-        Ok(anchor) => anchor,
-        Err(err) => {
-            eprintln!("error reading peer anchor: {err}");
-            std::process::exit(1);
+    } else {
+        let key_source = Ed25519KeySource::from_spec(key_spec.as_deref());
+        match load_or_derive_keypair(&key_source) {
+            Ok(material) => material,
+            Err(err) => fatal(&format!("failed to load key: {err}")),
         }
     };
 
-    let votes = [
-        AnchorVote {
-            anchor: &local,
-            public_key: b"LOCAL_OFFLINE",
-        },
-        AnchorVote {
-            anchor: &peer,
-            public_key: b"PEER_FILE",
-        },
-    ];
-    match reconcile_anchors_with_quorum(&votes, quorum) {
-        Ok(()) => {
-            println!("Finality reached with quorum {quorum}.");
-            println!("Local anchor:\n{}", format_anchor(&local));
-            println!("Peer anchor:\n{}", format_anchor(&peer));
-        }
-        Err(err) => {
-            eprintln!("Quorum check failed: {err}");
-            std::process::exit(1);
+    let metrics_addr = metrics_addr_spec
+        .as_deref()
+        .map(parse_metrics_addr)
+        .unwrap_or(None);
+    let membership_policy =
+        load_membership_policy(policy_spec.as_deref(), policy_allowlist_spec.as_deref());
+    let checkpoint_interval = checkpoint_interval_spec.map(|value| {
+        value
+            .parse()
+            .unwrap_or_else(|_| fatal("invalid --checkpoint-interval"))
+    });
+    let blob_dir = blob_dir_spec.map(PathBuf::from);
+    let blob_listen = blob_listen_spec
+        .as_deref()
+        .map(parse_metrics_addr)
+        .unwrap_or(None);
+    let max_blob_bytes = max_blob_bytes_spec.map(|v| {
+        v.parse::<usize>()
+            .unwrap_or_else(|_| fatal("invalid --max-blob-bytes"))
+    });
+    let blob_retention_days = blob_retention_days_spec.map(|v| {
+        v.parse::<u64>()
+            .unwrap_or_else(|_| fatal("invalid --blob-retention-days"))
+    });
+    let blob_policies = blob_policy_spec
+        .as_deref()
+        .map(|path| load_blob_policies(Path::new(path)));
+    let blob_auth_token = blob_auth_token_spec;
+    let blob_max_concurrency = blob_max_concurrency_spec.map(|v| {
+        v.parse::<usize>()
+            .unwrap_or_else(|_| fatal("invalid --blob-max-concurrency"))
+    });
+    let blob_request_timeout_ms = blob_request_timeout_ms_spec.map(|v| {
+        v.parse::<u64>()
+            .unwrap_or_else(|_| fatal("invalid --blob-request-timeout-ms"))
+    });
+    let attestation_quorum = attestation_quorum_spec.map(|v| {
+        v.parse::<usize>()
+            .unwrap_or_else(|_| fatal("invalid --attestation-quorum"))
+    });
+    if let Some(path) = genesis_spec.as_deref() {
+        install_genesis_file(path);
+    }
+    let anchor_topic = anchor_topic_spec.or_else(|| {
+        gossip_shard_spec
+            .map(|shard| network_topic(&format!("mfenx/powerhouse/anchors/v1/shard/{shard}")))
+    });
+    let gossip_bridge_topics = gossip_bridge_topics_spec.as_deref().map(parse_topic_list);
+    let bft_round_ms = bft_round_ms_spec.map(|v| {
+        v.parse::<u64>()
+            .unwrap_or_else(|_| fatal("invalid --bft-round-ms"))
+    });
+    let tokio_threads = tokio_threads_spec.map(|v| {
+        v.parse::<usize>()
+            .unwrap_or_else(|_| fatal("invalid --tokio-threads"))
+    });
+    let evm_rpc_listen = evm_rpc_listen_spec
+        .as_deref()
+        .map(parse_metrics_addr)
+        .unwrap_or(None);
+    let evm_chain_id = evm_chain_id_spec.map(|v| {
+        v.parse::<u64>()
+            .unwrap_or_else(|_| fatal("invalid --evm-chain-id"))
+    });
+    let genesis = power_house::active_genesis();
+    if let Some(chain_id) = evm_chain_id {
+        if !genesis.is_default_network() && chain_id != genesis.chain_id {
+            fatal(&format!(
+                "--evm-chain-id {chain_id} conflicts with genesis chain id {}",
+                genesis.chain_id
+            ));
         }
     }
-}
 
-fn cmd_node_prove(args: Vec<String>) {
-    if args.len() < 3 {
-        eprintln!("Usage: julian node prove <log_dir> <entry_index> <leaf_index> [output.json]");
-        std::process::exit(1);
+    let log_shipping = log_ship_endpoint
+        .map(|endpoint| LogShipperConfig::new(endpoint, node_id.clone(), PathBuf::from(&log_dir)));
+    let mut builder = NetConfig::builder(node_id, listen_addr, log_dir, key_material)
+        .bootstraps(bootstraps)
+        .quorum(quorum)
+        .broadcast_interval(Duration::from_millis(broadcast_ms))
+        .membership_policy(membership_policy.clone())
+        .rpc_tier_limits(rpc_tier_limits);
+    if let Some(topic) = anchor_topic {
+        builder = builder.anchor_topic(topic);
     }
-    let log_dir = Path::new(&args[0]);
-    let entry_index: usize = args[1]
-        .parse()
-        .unwrap_or_else(|_| fatal("invalid entry index"));
-    let leaf_index: usize = args[2]
-        .parse()
-        .unwrap_or_else(|_| fatal("invalid leaf index"));
-    let anchor = load_anchor_from_logs(log_dir).unwrap_or_else(|err| fatal(&err.to_string()));
-    let entry = anchor
-        .entries
-        .get(entry_index)
-        .unwrap_or_else(|| fatal("entry index out of bounds"));
-    if leaf_index >= entry.hashes.len() {
-        fatal("leaf index out of bounds");
+    if let Some(topics) = gossip_bridge_topics {
+        builder = builder.bridge_topics(topics);
     }
-    let proof = power_house::build_merkle_proof(&entry.hashes, leaf_index)
-        .ok_or_else(|| "unable to build proof".to_string())
-        .unwrap_or_else(|err| fatal(&err));
-    if proof.root != entry.merkle_root {
-        fatal("computed proof root does not match entry merkle root");
+    if bft_enabled {
+        builder = builder.bft(bft_round_ms.map(Duration::from_millis));
     }
-    let proof_json: serde_json::Value = serde_json::from_str(&proof.to_json_string()).unwrap();
-    let document = serde_json::json!({
-        "entry_index": entry_index,
-        "statement": entry.statement,
-        "leaf_index": leaf_index,
-        "leaf": power_house::transcript_digest_to_hex(&entry.hashes[leaf_index]),
-        "merkle_root": power_house::transcript_digest_to_hex(&entry.merkle_root),
-        "proof": proof_json
+    if let Some(addr) = metrics_addr {
+        builder = builder.metrics_addr(addr);
+    }
+    if let Some(interval) = checkpoint_interval {
+        builder = builder.checkpoint_interval(interval);
+    }
+    if let Some(dir) = blob_dir {
+        builder = builder.blob_dir(dir);
+    }
+    if let Some(addr) = blob_listen {
+        builder = builder.blob_listen(addr);
+    }
+    if let Some(quorum) = attestation_quorum {
+        builder = builder.attestation_quorum(quorum);
+    }
+    if let Some(addr) = evm_rpc_listen {
+        builder = builder.evm_rpc_listen(addr);
+    }
+    if let Some(chain_id) = evm_chain_id {
+        builder = builder.evm_chain_id(chain_id);
+    }
+    if let Some(secs) = native_max_block_interval {
+        builder = builder.native_max_block_interval(Duration::from_secs(secs));
+    }
+    if let Some(keep) = native_prune_keep {
+        builder = builder.native_prune_keep(keep);
+    }
+    if let Some(epochs) = entry_gc_epochs {
+        builder = builder.entry_gc_epochs(epochs);
+    }
+    if let Some(secs) = native_pending_ttl {
+        builder = builder.native_pending_ttl(Duration::from_secs(secs));
+    }
+    if let Some(max) = native_max_pending {
+        builder = builder.native_max_pending(max);
+    }
+    if let Some(max) = max_http_connections {
+        builder = builder.max_http_connections(max);
+    }
+    if let Some(max) = max_anchors_per_minute {
+        builder = builder.max_anchors_per_minute(max);
+    }
+    if let Some(percent) = evm_gas_margin {
+        builder = builder.evm_gas_margin_percent(percent);
+    }
+    let mut config = builder
+        .build()
+        .unwrap_or_else(|err| fatal(&err.to_string()));
+    config.max_blob_bytes = max_blob_bytes;
+    config.blob_retention_days = blob_retention_days;
+    config.blob_policies = blob_policies;
+    config.blob_auth_token = blob_auth_token;
+    if let Some(max) = blob_max_concurrency {
+        config.blob_max_concurrency = max;
+    }
+    if let Some(ms) = blob_request_timeout_ms {
+        config.blob_request_timeout = Duration::from_millis(ms);
+    }
+    config.token_mode_contract = token_mode_contract_spec;
+    config.token_oracle_rpc = token_oracle_rpc_spec;
+    config.log_shipping = log_shipping;
+    config.evm_rpc_cors = rpc_cors;
+    config.metrics_push = metrics_push_endpoint.map(|endpoint| {
+        let mut push = MetricsPushConfig::new(endpoint);
+        push.authorization = metrics_push_auth;
+        if let Some(secs) = metrics_push_interval {
+            push.interval = Duration::from_secs(secs);
+        }
+        push
     });
-    if let Some(path) = args.get(3) {
-        if let Err(err) = fs::write(path, serde_json::to_string_pretty(&document).unwrap()) {
-            fatal(&format!("failed to write proof: {err}"));
-        }
-    } else {
-        println!("{}", serde_json::to_string_pretty(&document).unwrap());
+    config.checkpoint_queries = checkpoint_queries;
+    config.set_storage_backend(storage_backend);
+    if let Some(pool_per_epoch) = epoch_reward_pool {
+        if config.checkpoint_interval.is_none() || config.stake_registry_path.is_none() {
+            fatal("--epoch-reward-pool requires --checkpoint-interval and --blob-dir");
+        }
+        config.reward_config = Some(RewardConfig {
+            pool_per_epoch,
+            weighting: reward_weighting,
+            asset: reward_asset,
+        });
     }
-}
+    config.governance_fee_schedule = governance_fee_schedule;
+    config.governance_multisig = governance_multisig;
+    config.governance_allowlist = governance_allowlist;
+    config.webhooks = webhooks;
+    config.alerts = alerts;
+    config.set_chaos(chaos).unwrap_or_else(|err| fatal(&err));
 
-fn cmd_node_verify_proof(args: Vec<String>) {
-    if args.len() != 2 {
-        eprintln!("Usage: julian node verify-proof <anchor_file> <proof_file>");
-        std::process::exit(1);
-    }
-    let anchor = read_anchor(Path::new(&args[0]))
-        .unwrap_or_else(|err| fatal(&format!("failed to read anchor: {err}")));
-    let proof_text = fs::read_to_string(&args[1])
-        .unwrap_or_else(|err| fatal(&format!("failed to read proof file: {err}")));
-    let document: serde_json::Value = serde_json::from_str(&proof_text)
-        .unwrap_or_else(|err| fatal(&format!("invalid proof JSON: {err}")));
-    let entry_index = document
-        .get("entry_index")
-        .and_then(|v| v.as_u64())
-        .unwrap_or_else(|| fatal("proof missing entry_index")) as usize;
-    let leaf_index = document
-        .get("leaf_index")
-        .and_then(|v| v.as_u64())
-        .unwrap_or_else(|| fatal("proof missing leaf_index")) as usize;
-    let proof_value = document
-        .get("proof")
-        .unwrap_or_else(|| fatal("proof missing inner proof object"));
-    let proof_json = serde_json::to_string(proof_value).unwrap();
-    let proof = power_house::MerkleProof::from_json_str(&proof_json)
-        .unwrap_or_else(|err| fatal(&format!("invalid proof: {err}")));
-    if entry_index >= anchor.entries.len() {
-        fatal("entry index out of bounds");
+    let mut builder = tokio::runtime::Builder::new_multi_thread();
+    builder.enable_all();
+    if let Some(threads) = tokio_threads {
+        builder.worker_threads(threads);
     }
-    let entry = &anchor.entries[entry_index];
-    if leaf_index >= entry.hashes.len() {
-        fatal("leaf index out of bounds");
+    let runtime = builder
+        .build()
+        .unwrap_or_else(|err| fatal(&format!("failed to start runtime: {err}")));
+    if let Err(err) = runtime.block_on(run_network(config)) {
+        fatal(&format!("network error: {err}"));
     }
-    if proof.root != entry.merkle_root {
-        fatal("proof root does not match anchor merkle root");
+}
+
+#[cfg(feature = "net")]
+fn cmd_net_anchor(args: Vec<String>) {
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        println!("Usage: julian net anchor --log-dir <dir> [--node-id <id>] [--quorum <N>] [--deterministic]");
+        println!("Compat: julian net anchor <log_dir> [--node-id <id>] [--quorum <N>]");
+        return;
     }
-    if proof.leaf != entry.hashes[leaf_index] {
-        fatal("proof leaf does not match anchor digest");
+
+    let mut log_dir = None;
+    let mut node_id = String::from("unknown-node");
+    let mut quorum: usize = 1;
+    let mut deterministic = false;
+
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--log-dir" => {
+                log_dir = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--log-dir expects a value")),
+                );
+            }
+            "--node-id" => {
+                node_id = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--node-id expects a value"));
+            }
+            "--quorum" => {
+                let value = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--quorum expects a value"));
+                quorum = value.parse().unwrap_or_else(|_| fatal("invalid --quorum"));
+            }
+            "--deterministic" => {
+                deterministic = true;
+            }
+            other => {
+                if other.starts_with("--") {
+                    fatal(&format!("unknown argument: {other}"));
+                }
+                if log_dir.is_none() {
+                    log_dir = Some(other.to_string());
+                } else {
+                    fatal(&format!("unexpected positional argument: {other}"));
+                }
+            }
+        }
     }
-    if !power_house::verify_merkle_proof(&proof) {
-        fatal("invalid Merkle proof");
+
+    let log_dir = log_dir.unwrap_or_else(|| fatal("--log-dir is required"));
+    let ledger = load_anchor_from_logs(Path::new(&log_dir)).unwrap_or_else(|err| fatal(&err));
+    let timestamp_ms = artifact_clock(deterministic).timestamp_ms(&[&compute_fold_digest(&ledger)]);
+    let anchor_json =
+        AnchorJson::from_ledger(node_id, quorum, &ledger, timestamp_ms, Vec::new(), None)
+            .unwrap_or_else(|err| fatal(&format!("anchor conversion failed: {err}")));
+    match anchor_json.to_json_string() {
+        Ok(text) => println!("{text}"),
+        Err(err) => fatal(&format!("FAIL: failed to encode anchor: {err}")),
     }
-    println!(
-        "Proof verified for statement '{}' (entry {}, leaf {}).",
-        entry.statement, entry_index, leaf_index
-    );
 }
 
 #[cfg(feature = "net")]
-fn cmd_net_start(args: Vec<String>) {
+fn cmd_net_ship_logs(args: Vec<String>) {
+    const USAGE: &str = "Usage: julian net ship-logs --endpoint <url> --node-id <id> --log-dir <dir> [--key <spec>] [--interval <secs>] [--once]";
     if args.iter().any(|a| a == "-h" || a == "--help") {
-        print_net_start_help();
+        println!("{USAGE}");
         return;
     }
 
-    refresh_migration_mode_from_env();
+    let mut endpoint = None;
     let mut node_id = None;
     let mut log_dir = None;
-    let mut listen = None;
-    let mut bootstraps = Vec::new();
-    let mut quorum: usize = 1;
-    let mut broadcast_ms: u64 = 5_000;
     let mut key_spec: Option<String> = None;
-    let mut identity_path: Option<String> = None;
-    let mut anchor_topic_spec: Option<String> = None;
-    let mut gossip_shard_spec: Option<String> = None;
-    let mut gossip_bridge_topics_spec: Option<String> = None;
-    let mut bft_enabled = false;
-    let mut bft_round_ms_spec: Option<String> = None;
-    let mut metrics_addr_spec: Option<String> = None;
-    let mut policy_allowlist_spec: Option<String> = None;
-    let mut policy_spec: Option<String> = None;
-    let mut checkpoint_interval_spec: Option<String> = None;
-    let mut blob_dir_spec: Option<String> = None;
-    let mut blob_listen_spec: Option<String> = None;
-    let mut max_blob_bytes_spec: Option<String> = None;
-    let mut blob_retention_days_spec: Option<String> = None;
-    let mut blob_policy_spec: Option<String> = None;
-    let mut blob_auth_token_spec: Option<String> = None;
-    let mut blob_max_concurrency_spec: Option<String> = None;
-    let mut blob_request_timeout_ms_spec: Option<String> = None;
-    let mut attestation_quorum_spec: Option<String> = None;
-    let mut tokio_threads_spec: Option<String> = None;
-    let mut token_mode_contract_spec: Option<String> = None;
-    let mut token_oracle_rpc_spec: Option<String> = None;
-    let mut evm_rpc_listen_spec: Option<String> = None;
-    let mut evm_chain_id_spec: Option<String> = None;
-
+    let mut interval: Option<u64> = None;
+    let mut once = false;
     let mut iter = args.into_iter();
     while let Some(arg) = iter.next() {
         match arg.as_str() {
+            "--endpoint" => {
+                endpoint = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--endpoint expects a value")),
+                );
+            }
             "--node-id" => {
                 node_id = Some(
                     iter.next()
@@ -6481,345 +10708,1164 @@ fn cmd_net_start(args: Vec<String>) {
                         .unwrap_or_else(|| fatal("--log-dir expects a value")),
                 );
             }
-            "--listen" => {
-                let value = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--listen expects a value"));
-                let addr: Multiaddr = value
-                    .parse()
-                    .unwrap_or_else(|_| fatal("invalid multiaddr for --listen"));
-                listen = Some(addr);
+            "--key" => {
+                key_spec = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--key expects a value")),
+                );
             }
-            "--bootstrap" => {
+            "--interval" => {
                 let value = iter
                     .next()
-                    .unwrap_or_else(|| fatal("--bootstrap expects a value"));
-                let addr: Multiaddr = value
-                    .parse()
-                    .unwrap_or_else(|_| fatal("invalid multiaddr for --bootstrap"));
-                bootstraps.push(addr);
+                    .unwrap_or_else(|| fatal("--interval expects a value"));
+                interval = Some(
+                    value
+                        .parse()
+                        .unwrap_or_else(|_| fatal("invalid --interval")),
+                );
             }
-            "--bootnodes" => {
-                let value = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--bootnodes expects a value"));
-                for raw in value.split(',') {
-                    let trimmed = raw.trim();
-                    if trimmed.is_empty() {
-                        continue;
-                    }
-                    let addr: Multiaddr = trimmed
+            "--once" => once = true,
+            other => fatal(&format!("unknown argument: {other}")),
+        }
+    }
+
+    let endpoint = endpoint.unwrap_or_else(|| fatal(USAGE));
+    let node_id = node_id.unwrap_or_else(|| fatal(USAGE));
+    let log_dir = log_dir.unwrap_or_else(|| fatal(USAGE));
+    let key_material = load_or_derive_keypair(&Ed25519KeySource::from_spec(key_spec.as_deref()))
+        .unwrap_or_else(|err| fatal(&format!("failed to load key: {err}")));
+    let mut cfg = LogShipperConfig::new(endpoint, node_id, PathBuf::from(log_dir));
+    if let Some(secs) = interval {
+        cfg.poll_interval = Duration::from_secs(secs);
+    }
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap_or_else(|err| fatal(&format!("failed to start runtime: {err}")));
+    if once {
+        let client = reqwest::Client::builder()
+            .timeout(cfg.request_timeout)
+            .build()
+            .unwrap_or_else(|err| fatal(&format!("failed to build HTTP client: {err}")));
+        match runtime.block_on(ship_pending_logs(&cfg, &key_material, &client)) {
+            Ok(count) => println!("shipped {count} record(s)"),
+            Err(err) => fatal(&format!("log shipping failed: {err}")),
+        }
+    } else if let Err(err) = runtime.block_on(run_log_shipper(cfg, key_material)) {
+        fatal(&format!("log shipping failed: {err}"));
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_net_pin_anchor(args: Vec<String>) {
+    const USAGE: &str = "Usage: julian net pin-anchor --log-dir <dir> --rpc <url> --contract <0x..> --chain-id <N> --evm-key <file> [--confirmations <N>] [--gas-limit <N>] [--max-fee-wei <N>] [--priority-fee-wei <N>] [--interval <secs>] [--once]";
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        println!("{USAGE}");
+        return;
+    }
+
+    let mut log_dir = None;
+    let mut rpc_url = None;
+    let mut contract = None;
+    let mut chain_id: Option<u64> = None;
+    let mut key_path = None;
+    let mut confirmations: Option<u64> = None;
+    let mut gas_limit: Option<u64> = None;
+    let mut max_fee: Option<u128> = None;
+    let mut priority_fee: Option<u128> = None;
+    let mut interval: Option<u64> = None;
+    let mut once = false;
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        let mut value = |flag: &str| {
+            iter.next()
+                .unwrap_or_else(|| fatal(&format!("{flag} expects a value")))
+        };
+        match arg.as_str() {
+            "--log-dir" => log_dir = Some(value("--log-dir")),
+            "--rpc" => rpc_url = Some(value("--rpc")),
+            "--contract" => contract = Some(value("--contract")),
+            "--evm-key" => key_path = Some(value("--evm-key")),
+            "--chain-id" => {
+                chain_id = Some(
+                    value("--chain-id")
                         .parse()
-                        .unwrap_or_else(|_| fatal("invalid multiaddr for --bootnodes"));
-                    bootstraps.push(addr);
+                        .unwrap_or_else(|_| fatal("invalid --chain-id")),
+                )
+            }
+            "--confirmations" => {
+                confirmations = Some(
+                    value("--confirmations")
+                        .parse()
+                        .unwrap_or_else(|_| fatal("invalid --confirmations")),
+                )
+            }
+            "--gas-limit" => {
+                gas_limit = Some(
+                    value("--gas-limit")
+                        .parse()
+                        .unwrap_or_else(|_| fatal("invalid --gas-limit")),
+                )
+            }
+            "--max-fee-wei" => {
+                max_fee = Some(
+                    value("--max-fee-wei")
+                        .parse()
+                        .unwrap_or_else(|_| fatal("invalid --max-fee-wei")),
+                )
+            }
+            "--priority-fee-wei" => {
+                priority_fee = Some(
+                    value("--priority-fee-wei")
+                        .parse()
+                        .unwrap_or_else(|_| fatal("invalid --priority-fee-wei")),
+                )
+            }
+            "--interval" => {
+                interval = Some(
+                    value("--interval")
+                        .parse()
+                        .unwrap_or_else(|_| fatal("invalid --interval")),
+                )
+            }
+            "--once" => once = true,
+            other => fatal(&format!("unknown argument: {other}")),
+        }
+    }
+
+    let log_dir = log_dir.unwrap_or_else(|| fatal(USAGE));
+    let rpc_url = rpc_url.unwrap_or_else(|| fatal(USAGE));
+    let contract = contract.unwrap_or_else(|| fatal(USAGE));
+    let chain_id = chain_id.unwrap_or_else(|| fatal(USAGE));
+    let key_path = key_path.unwrap_or_else(|| fatal(USAGE));
+    let key_text = fs::read_to_string(&key_path)
+        .unwrap_or_else(|err| fatal(&format!("failed to read {key_path}: {err}")));
+    let key = parse_evm_signing_key(&key_text).unwrap_or_else(|err| fatal(&err.to_string()));
+    let mut cfg = EvmPinConfig::new(rpc_url, contract, chain_id, PathBuf::from(log_dir));
+    if let Some(value) = confirmations {
+        cfg.confirmations = value;
+    }
+    if let Some(value) = gas_limit {
+        cfg.gas_limit = value;
+    }
+    if let Some(value) = max_fee {
+        cfg.max_fee_per_gas = value;
+    }
+    if let Some(value) = priority_fee {
+        cfg.max_priority_fee_per_gas = value;
+    }
+    if let Some(secs) = interval {
+        cfg.poll_interval = Duration::from_secs(secs);
+    }
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap_or_else(|err| fatal(&format!("failed to start runtime: {err}")));
+    if once {
+        let client = reqwest::Client::builder()
+            .timeout(cfg.request_timeout)
+            .build()
+            .unwrap_or_else(|err| fatal(&format!("failed to build HTTP client: {err}")));
+        let settled = runtime
+            .block_on(confirm_pins(&cfg, &client))
+            .unwrap_or_else(|err| fatal(&format!("pin confirmation failed: {err}")));
+        println!("settled {settled} pin(s)");
+        match runtime.block_on(pin_latest_checkpoint(&cfg, &key, &client)) {
+            Ok(Some(record)) => println!(
+                "pinned epoch {} fold_digest {} in {}",
+                record.epoch, record.fold_digest, record.tx_hash
+            ),
+            Ok(None) => {
+                println!("nothing to pin: no checkpoint or latest fold digest already pinned")
+            }
+            Err(err) => fatal(&format!("anchor pinning failed: {err}")),
+        }
+    } else if let Err(err) = runtime.block_on(run_evm_pinner(cfg, key)) {
+        fatal(&format!("anchor pinning failed: {err}"));
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_net_attestations(args: Vec<String>) {
+    const USAGE: &str = "Usage: julian net attestations <export|verify> ...\n  export --log-dir <dir> --output <file> [--since <unix_ms>]\n  verify <bundle.json> [--trusted-key <b64>]...";
+    let mut iter = args.into_iter();
+    match iter.next().as_deref() {
+        Some("export") => {
+            let mut log_dir = None;
+            let mut output = None;
+            let mut since_ms: u64 = 0;
+            while let Some(arg) = iter.next() {
+                match arg.as_str() {
+                    "--log-dir" => {
+                        log_dir = Some(
+                            iter.next()
+                                .unwrap_or_else(|| fatal("--log-dir expects a value")),
+                        );
+                    }
+                    "--output" => {
+                        output = Some(
+                            iter.next()
+                                .unwrap_or_else(|| fatal("--output expects a value")),
+                        );
+                    }
+                    "--since" => {
+                        since_ms = iter
+                            .next()
+                            .unwrap_or_else(|| fatal("--since expects a value"))
+                            .parse()
+                            .unwrap_or_else(|_| fatal("--since expects a unix timestamp in ms"));
+                    }
+                    other => fatal(&format!("unknown argument: {other}")),
                 }
             }
-            "--broadcast-interval" => {
-                let value = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--broadcast-interval expects a value"));
-                broadcast_ms = value
+            let log_dir = PathBuf::from(log_dir.unwrap_or_else(|| fatal(USAGE)));
+            let output = PathBuf::from(output.unwrap_or_else(|| fatal(USAGE)));
+            let bundle = export_attestations(&log_dir, since_ms, now_millis())
+                .unwrap_or_else(|err| fatal(&err.to_string()));
+            bundle
+                .write(&output)
+                .unwrap_or_else(|err| fatal(&err.to_string()));
+            println!(
+                "attestation bundle written to {} ({} attestation(s) since {})",
+                output.display(),
+                bundle.attestations.len(),
+                since_ms
+            );
+        }
+        Some("verify") => {
+            let mut path = None;
+            let mut trusted_keys = Vec::new();
+            while let Some(arg) = iter.next() {
+                match arg.as_str() {
+                    "--trusted-key" => trusted_keys.push(
+                        iter.next()
+                            .unwrap_or_else(|| fatal("--trusted-key expects a value")),
+                    ),
+                    other if other.starts_with("--") => {
+                        fatal(&format!("unknown argument: {other}"))
+                    }
+                    other if path.is_none() => path = Some(other.to_string()),
+                    other => fatal(&format!("unexpected positional argument: {other}")),
+                }
+            }
+            let path = path.unwrap_or_else(|| fatal(USAGE));
+            let bundle = AttestationBundle::read(Path::new(&path))
+                .unwrap_or_else(|err| fatal(&format!("FAIL: {err}")));
+            match verify_attestation_bundle(&bundle, &trusted_keys) {
+                Ok(summary) => {
+                    println!(
+                        "PASS: {} attestation(s) over {} digest(s) verified",
+                        summary.attestations, summary.digests
+                    );
+                    for (node_id, public_key) in &summary.signers {
+                        println!("  signer {node_id} {public_key}");
+                    }
+                }
+                Err(err) => fatal(&format!("FAIL: {err}")),
+            }
+        }
+        Some("-h") | Some("--help") => println!("{USAGE}"),
+        _ => fatal(USAGE),
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_net_block_archive(args: Vec<String>) {
+    const USAGE: &str = "Usage: julian net block-archive <dir> [--tx <hash> | --block <N>]";
+    let mut dir = None;
+    let mut tx_hash = None;
+    let mut block_number = None;
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--tx" => {
+                tx_hash = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--tx expects a transaction hash")),
+                );
+            }
+            "--block" => {
+                block_number = Some(
+                    iter.next()
+                        .and_then(|value| value.parse::<u64>().ok())
+                        .unwrap_or_else(|| fatal("--block expects a block number")),
+                );
+            }
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return;
+            }
+            other if other.starts_with("--") => fatal(&format!("unknown argument: {other}")),
+            other if dir.is_none() => dir = Some(PathBuf::from(other)),
+            other => fatal(&format!("unexpected positional argument: {other}")),
+        }
+    }
+    let dir = dir.unwrap_or_else(|| fatal(USAGE));
+    let value = if let Some(hash) = tx_hash {
+        let (block, index) = find_archived_transaction(&dir, &hash)
+            .unwrap_or_else(|err| fatal(&err))
+            .unwrap_or_else(|| fatal(&format!("transaction {hash} is not archived")));
+        serde_json::json!({
+            "block": block.proposal.number,
+            "blockHash": block.proposal.hash,
+            "index": index,
+            "transaction": block.proposal.transactions[index],
+        })
+    } else if let Some(number) = block_number {
+        let block = find_archived_block(&dir, number)
+            .unwrap_or_else(|err| fatal(&err))
+            .unwrap_or_else(|| fatal(&format!("block {number} is not archived")));
+        serde_json::to_value(block).unwrap_or_else(|err| fatal(&err.to_string()))
+    } else {
+        let summaries = list_segments(&dir)
+            .unwrap_or_else(|err| fatal(&err))
+            .iter()
+            .map(|segment| inspect_segment(segment).unwrap_or_else(|err| fatal(&err)))
+            .collect::<Vec<_>>();
+        serde_json::to_value(summaries).unwrap_or_else(|err| fatal(&err.to_string()))
+    };
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&value).expect("archive output serializes")
+    );
+}
+
+#[cfg(feature = "net")]
+fn cmd_net_rpc_replica(mut args: Vec<String>) {
+    const USAGE: &str = "Usage: julian net rpc-replica --state <native_chain_state file> --rpc-listen <host:port> [--primary-rpc <url>] [--registry <file>] [--log-dir <dir>] [--poll-ms <ms>] [--rpc-tier <tier>=<N>:<queue>]... [--gas-margin <percent>] [--config <file.toml>]\n  Serves the wallet RPC read-only from a primary node's state, refusing transactions.";
+    let file = take_config_file(&mut args).rpc;
+    let mut state_path = file.state.clone();
+    let mut listen = file.listen;
+    let mut primary_rpc = file.primary_rpc.clone();
+    let mut registry = file.registry.clone();
+    let mut log_dir = file.log_dir.clone();
+    let mut poll = file
+        .poll_ms
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_REPLICA_POLL);
+    let mut gas_margin = None;
+    let mut tier_limits = RpcTierLimits::default();
+    for spec in &file.tiers {
+        tier_limits
+            .apply_spec(spec)
+            .unwrap_or_else(|err| fatal(&format!("invalid tiers in --config: {err}")));
+    }
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        let mut value = |flag: &str| {
+            iter.next()
+                .unwrap_or_else(|| fatal(&format!("{flag} expects a value")))
+        };
+        match arg.as_str() {
+            "--state" => state_path = Some(PathBuf::from(value("--state"))),
+            "--rpc-listen" => {
+                listen = Some(
+                    value("--rpc-listen")
+                        .parse::<SocketAddr>()
+                        .unwrap_or_else(|_| fatal("--rpc-listen expects <host:port>")),
+                );
+            }
+            "--primary-rpc" => primary_rpc = Some(value("--primary-rpc")),
+            "--registry" => registry = Some(PathBuf::from(value("--registry"))),
+            "--log-dir" => log_dir = Some(PathBuf::from(value("--log-dir"))),
+            "--rpc-tier" => tier_limits
+                .apply_spec(&value("--rpc-tier"))
+                .unwrap_or_else(|err| fatal(&format!("invalid --rpc-tier: {err}"))),
+            "--gas-margin" => {
+                gas_margin = Some(
+                    value("--gas-margin")
+                        .parse()
+                        .unwrap_or_else(|_| fatal("--gas-margin expects a percentage")),
+                );
+            }
+            "--poll-ms" => {
+                poll = Duration::from_millis(
+                    value("--poll-ms")
+                        .parse()
+                        .unwrap_or_else(|_| fatal("--poll-ms expects milliseconds")),
+                );
+            }
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return;
+            }
+            other => fatal(&format!("unknown argument: {other}\n{USAGE}")),
+        }
+    }
+    let (Some(state_path), Some(listen)) = (state_path, listen) else {
+        fatal(USAGE);
+    };
+    let state = NativeChainState::load(&state_path, WallClock.now().as_secs())
+        .unwrap_or_else(|err| fatal(&err));
+    let chain_id = state.chain_id;
+    let state = Arc::new(tokio::sync::RwLock::new(state));
+    let status = ReplicaStatus::new(WallClock::shared());
+    // Writes are refused before they reach consensus, so nothing consumes
+    // the command queue.
+    let (command_sender, _commands) = tokio::sync::mpsc::channel(1);
+    let mut builder = EvmRpcConfig::builder(listen, chain_id, state.clone(), command_sender)
+        .settings(&file)
+        .unwrap_or_else(|err| fatal(&err.to_string()))
+        .tier_limits(tier_limits)
+        .replica(ReplicaMode {
+            primary_rpc,
+            status: status.clone(),
+        });
+    if let Some(path) = registry {
+        builder = builder.asset_registry(path);
+    }
+    if let Some(dir) = log_dir {
+        builder = builder.beacon_log_dir(dir);
+    }
+    if let Some(percent) = gas_margin {
+        builder = builder.gas_margin_percent(percent);
+    }
+    if let Some(dir) = state_path.parent() {
+        builder = builder.block_archive(dir.join(BLOCK_ARCHIVE_DIR));
+    }
+    let rpc_cfg = builder
+        .build()
+        .unwrap_or_else(|err| fatal(&err.to_string()));
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap_or_else(|err| fatal(&format!("failed to start runtime: {err}")));
+    runtime.spawn(follow_primary_state(state_path, state, status, poll));
+    if let Err(err) = runtime.block_on(run_evm_rpc_server(rpc_cfg)) {
+        fatal(&format!("replica rpc failed: {err}"));
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_net_dev(args: Vec<String>) {
+    const USAGE: &str = "Usage: julian net dev [--nodes <N>] [--workspace <dir>] [--base-port <port>] [--rpc-base-port <port>] [--chain-id <N>] [--seed <text>] [--generate-only | --clean]\n  Generates keys, genesis and configs for a local test network, runs one `julian net start` per node until Ctrl+C, then stops them.";
+    let mut spec = DevnetSpec::default();
+    let mut workspace = PathBuf::from("devnet");
+    let mut generate_only = false;
+    let mut clean = false;
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        let mut value = |flag: &str| {
+            iter.next()
+                .unwrap_or_else(|| fatal(&format!("{flag} expects a value")))
+        };
+        match arg.as_str() {
+            "--nodes" => {
+                spec.nodes = value("--nodes")
                     .parse()
-                    .unwrap_or_else(|_| fatal("invalid --broadcast-interval"));
+                    .unwrap_or_else(|_| fatal("--nodes expects a node count"));
             }
-            "--quorum" => {
-                let value = iter
-                    .next()
-                    .unwrap_or_else(|| fatal("--quorum expects a value"));
-                quorum = value.parse().unwrap_or_else(|_| fatal("invalid --quorum"));
+            "--workspace" => workspace = PathBuf::from(value("--workspace")),
+            "--base-port" => {
+                spec.p2p_base_port = value("--base-port")
+                    .parse()
+                    .unwrap_or_else(|_| fatal("--base-port expects a port"));
+            }
+            "--rpc-base-port" => {
+                spec.rpc_base_port = value("--rpc-base-port")
+                    .parse()
+                    .unwrap_or_else(|_| fatal("--rpc-base-port expects a port"));
             }
-            "--key" => {
-                key_spec = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--key expects a value")),
-                );
+            "--chain-id" => {
+                spec.chain_id = value("--chain-id")
+                    .parse()
+                    .unwrap_or_else(|_| fatal("--chain-id expects a chain id"));
             }
-            "--identity" => {
-                identity_path = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--identity expects a value")),
-                );
+            "--seed" => spec.seed = value("--seed"),
+            "--generate-only" => generate_only = true,
+            "--clean" => clean = true,
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return;
             }
-            "--anchor-topic" => {
-                anchor_topic_spec = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--anchor-topic expects a value")),
-                );
+            other => fatal(&format!("unknown argument: {other}\n{USAGE}")),
+        }
+    }
+    if clean {
+        clean_devnet(&workspace).unwrap_or_else(|err| fatal(&err.to_string()));
+        println!("removed devnet workspace {}", workspace.display());
+        return;
+    }
+
+    let devnet = generate_devnet(&workspace, &spec).unwrap_or_else(|err| fatal(&err.to_string()));
+    println!(
+        "devnet: {} node(s), network {}, chain id {}, quorum {}",
+        devnet.nodes.len(),
+        devnet.network_id,
+        devnet.chain_id,
+        devnet.quorum
+    );
+    println!("workspace: {}", devnet.workspace.display());
+    if generate_only {
+        print_devnet_endpoints(&devnet);
+        return;
+    }
+    for node in &devnet.nodes {
+        for addr in node.p2p_addr().into_iter().chain([node.rpc]) {
+            if let Err(err) = TcpListener::bind(addr) {
+                fatal(&format!("{} cannot use {addr}: {err}", node.node_id));
             }
-            "--gossip-shard" => {
-                gossip_shard_spec = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--gossip-shard expects a value")),
-                );
+        }
+    }
+
+    let exe = std::env::current_exe()
+        .unwrap_or_else(|err| fatal(&format!("cannot locate julian binary: {err}")));
+    let mut children: Vec<(String, std::process::Child)> = Vec::new();
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap_or_else(|err| fatal(&format!("failed to start runtime: {err}")));
+    let outcome = runtime.block_on(async {
+        for (index, node) in devnet.nodes.iter().enumerate() {
+            match spawn_devnet_node(&exe, &devnet, node) {
+                Ok(child) => children.push((node.node_id.clone(), child)),
+                Err(err) => return Err(err),
+            }
+            // Peers bootstrap from node 1, so it must be listening first.
+            if index == 0 {
+                if let Some(addr) = node.p2p_addr() {
+                    wait_for_devnet_port(addr, &mut children).await?;
+                }
             }
-            "--gossip-bridge-topics" => {
-                gossip_bridge_topics_spec = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--gossip-bridge-topics expects a value")),
-                );
+        }
+        for node in &devnet.nodes {
+            wait_for_devnet_port(node.rpc, &mut children).await?;
+        }
+        print_devnet_endpoints(&devnet);
+        println!("press Ctrl+C to stop the devnet");
+        loop {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => return Ok(()),
+                _ = tokio::time::sleep(Duration::from_millis(500)) => {
+                    if let Some(exited) = exited_devnet_node(&mut children) {
+                        return Err(exited);
+                    }
+                }
             }
-            "--bft" => {
-                bft_enabled = true;
+        }
+    });
+    // On Ctrl+C the nodes received the signal too and shut themselves down.
+    let grace = if outcome.is_ok() {
+        Duration::from_secs(5)
+    } else {
+        Duration::ZERO
+    };
+    stop_devnet_nodes(&mut children, grace);
+    match outcome {
+        Ok(()) => println!(
+            "devnet stopped; workspace kept at {}",
+            devnet.workspace.display()
+        ),
+        Err(err) => fatal(&format!("devnet stopped: {err}")),
+    }
+}
+
+#[cfg(feature = "net")]
+fn spawn_devnet_node(
+    exe: &Path,
+    devnet: &Devnet,
+    node: &power_house::net::DevnetNode,
+) -> Result<std::process::Child, String> {
+    let output = fs::File::create(&node.output_path)
+        .map_err(|err| format!("{}: {err}", node.output_path.display()))?;
+    let errors = output
+        .try_clone()
+        .map_err(|err| format!("{}: {err}", node.output_path.display()))?;
+    std::process::Command::new(exe)
+        .arg("net")
+        .arg("start")
+        .arg("--config")
+        .arg(&node.config_path)
+        .arg("--genesis")
+        .arg(&devnet.genesis_path)
+        .arg("--policy-allowlist")
+        .arg(&devnet.allowlist_path)
+        .stdin(std::process::Stdio::null())
+        .stdout(output)
+        .stderr(errors)
+        .spawn()
+        .map_err(|err| format!("failed to start {}: {err}", node.node_id))
+}
+
+/// Waits until `addr` accepts connections, failing early if a node exits.
+#[cfg(feature = "net")]
+async fn wait_for_devnet_port(
+    addr: SocketAddr,
+    children: &mut [(String, std::process::Child)],
+) -> Result<(), String> {
+    for _ in 0..300 {
+        if tokio::net::TcpStream::connect(addr).await.is_ok() {
+            return Ok(());
+        }
+        if let Some(exited) = exited_devnet_node(children) {
+            return Err(exited);
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    Err(format!("timed out waiting for {addr}"))
+}
+
+#[cfg(feature = "net")]
+fn exited_devnet_node(children: &mut [(String, std::process::Child)]) -> Option<String> {
+    children.iter_mut().find_map(|(node_id, child)| {
+        child
+            .try_wait()
+            .ok()
+            .flatten()
+            .map(|status| format!("{node_id} exited with {status}"))
+    })
+}
+
+/// Gives nodes `grace` to finish their own shutdown, then kills whatever is
+/// still running.
+#[cfg(feature = "net")]
+fn stop_devnet_nodes(children: &mut [(String, std::process::Child)], grace: Duration) {
+    let deadline = std::time::Instant::now() + grace;
+    for (node_id, child) in children.iter_mut() {
+        while std::time::Instant::now() < deadline {
+            if !matches!(child.try_wait(), Ok(None)) {
+                break;
             }
-            "--bft-round-ms" => {
-                bft_round_ms_spec = Some(
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        if matches!(child.try_wait(), Ok(None)) {
+            let _ = child.kill();
+        }
+        let _ = child.wait();
+        println!("QSYS|mod=DEVNET|evt=STOPPED|node={node_id}");
+    }
+}
+
+#[cfg(feature = "net")]
+fn print_devnet_endpoints(devnet: &Devnet) {
+    for node in &devnet.nodes {
+        println!("{}:", node.node_id);
+        println!("  multiaddr: {}", node.multiaddr);
+        println!("  rpc:       {}", node.rpc_url());
+        println!("  address:   {}", node.address);
+        println!("  output:    {}", node.output_path.display());
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_net_verify_rpc_state(args: Vec<String>) {
+    const USAGE: &str =
+        "Usage: julian net verify-rpc-state --log-dir <dir> --epoch <N> <native_chain_state file>";
+    let mut log_dir = None;
+    let mut epoch = None;
+    let mut state_path = None;
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--log-dir" => {
+                log_dir = Some(PathBuf::from(
                     iter.next()
-                        .unwrap_or_else(|| fatal("--bft-round-ms expects a value")),
-                );
+                        .unwrap_or_else(|| fatal("--log-dir expects a directory")),
+                ));
             }
-            "--metrics" => {
-                metrics_addr_spec = Some(
+            "--epoch" => {
+                epoch = Some(
                     iter.next()
-                        .unwrap_or_else(|| fatal("--metrics expects a value")),
+                        .and_then(|value| value.parse::<u64>().ok())
+                        .unwrap_or_else(|| fatal("--epoch expects a checkpoint epoch")),
                 );
             }
-            "--policy-allowlist" => {
-                policy_allowlist_spec = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--policy-allowlist expects a value")),
-                );
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return;
             }
-            "--policy" => {
-                policy_spec = Some(
+            other if other.starts_with("--") => fatal(&format!("unknown argument: {other}")),
+            other if state_path.is_none() => state_path = Some(PathBuf::from(other)),
+            other => fatal(&format!("unexpected positional argument: {other}")),
+        }
+    }
+    let (Some(log_dir), Some(epoch), Some(state_path)) = (log_dir, epoch, state_path) else {
+        fatal(USAGE);
+    };
+    let checkpoint = load_checkpoints(&log_dir)
+        .unwrap_or_else(|err| fatal(&err.to_string()))
+        .into_iter()
+        .find(|checkpoint| checkpoint.epoch == epoch)
+        .unwrap_or_else(|| fatal(&format!("no checkpoint at epoch {epoch}")));
+    let state = NativeChainState::load(&state_path, WallClock.now().as_secs())
+        .unwrap_or_else(|err| fatal(&err));
+    checkpoint
+        .verify_rpc_state(&state)
+        .unwrap_or_else(|err| fatal(&err.to_string()));
+    println!(
+        "{} matches the RPC state committed at epoch {epoch} (block {}, digest {})",
+        state_path.display(),
+        state.latest_number(),
+        hex::encode(state.rpc_state_digest())
+    );
+}
+
+#[cfg(feature = "net")]
+fn cmd_net_resolve_fork(args: Vec<String>) {
+    const USAGE: &str = "Usage: julian net resolve-fork --log-dir <dir> [--choose <digest>]";
+    let mut log_dir = None;
+    let mut chosen = None;
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--log-dir" => {
+                log_dir = Some(PathBuf::from(
                     iter.next()
-                        .unwrap_or_else(|| fatal("--policy expects a value")),
-                );
+                        .unwrap_or_else(|| fatal("--log-dir expects a directory")),
+                ));
             }
-            "--checkpoint-interval" => {
-                checkpoint_interval_spec = Some(
+            "--choose" => {
+                chosen = Some(
                     iter.next()
-                        .unwrap_or_else(|| fatal("--checkpoint-interval expects a value")),
+                        .unwrap_or_else(|| fatal("--choose expects an anchor digest")),
                 );
             }
-            "--blob-dir" => {
-                blob_dir_spec = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--blob-dir expects a value")),
-                );
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return;
             }
-            "--blob-listen" => {
-                blob_listen_spec = Some(
+            other => fatal(&format!("unknown argument: {other}")),
+        }
+    }
+    let log_dir = log_dir.unwrap_or_else(|| fatal(USAGE));
+    let Some(chosen) = chosen else {
+        let evidence = read_fork_evidence(&log_dir)
+            .unwrap_or_else(|err| fatal(&err.to_string()))
+            .unwrap_or_else(|| {
+                println!("{} is not in safe mode", log_dir.display());
+                exit(0);
+            });
+        println!("fork at epoch {}:", evidence.epoch);
+        for branch in &evidence.branches {
+            let signers: Vec<&str> = branch
+                .checkpoints
+                .iter()
+                .flat_map(|checkpoint| &checkpoint.signatures)
+                .map(|signature| signature.node_id.as_str())
+                .collect();
+            println!(
+                "  0x{}  signers={} [{}]",
+                branch.anchor_digest,
+                branch.signers.len(),
+                signers.join(", ")
+            );
+        }
+        fatal_code(
+            2,
+            "choose a branch explicitly with --choose <digest>; the node stays halted until then",
+        );
+    };
+    let resolution =
+        resolve_fork(&log_dir, &chosen, now_millis()).unwrap_or_else(|err| fatal(&err.to_string()));
+    println!(
+        "epoch {} resolved to 0x{}; abandoned {}",
+        resolution.epoch,
+        resolution.chosen,
+        resolution
+            .abandoned
+            .iter()
+            .map(|digest| format!("0x{digest}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    println!("safe mode cleared; restart the node to resume broadcasting");
+}
+
+#[cfg(feature = "net")]
+fn cmd_net_join_request(args: Vec<String>) {
+    const USAGE: &str = "Usage: julian net join-request --node-id <id> [--key <spec>] [--address <multiaddr>]... [--out <file>]\n  Signs a membership application with the key the node will run under.\n  A member submits it with `julian net proposal create --join-request <file>`.";
+    let mut node_id = None;
+    let mut key_spec = None;
+    let mut addresses = Vec::new();
+    let mut out = None;
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--node-id" => {
+                node_id = Some(
                     iter.next()
-                        .unwrap_or_else(|| fatal("--blob-listen expects a value")),
+                        .unwrap_or_else(|| fatal("--node-id expects a value")),
                 );
             }
-            "--max-blob-bytes" => {
-                max_blob_bytes_spec = Some(
+            "--key" => {
+                key_spec = Some(
                     iter.next()
-                        .unwrap_or_else(|| fatal("--max-blob-bytes expects a value")),
+                        .unwrap_or_else(|| fatal("--key expects a value")),
                 );
             }
-            "--blob-retention-days" => {
-                blob_retention_days_spec = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--blob-retention-days expects a value")),
-                );
+            "--address" => addresses.push(
+                iter.next()
+                    .unwrap_or_else(|| fatal("--address expects a multiaddr")),
+            ),
+            "--out" => {
+                out = Some(PathBuf::from(
+                    iter.next().unwrap_or_else(|| fatal("--out expects a path")),
+                ));
             }
-            "--blob-policy" => {
-                blob_policy_spec = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--blob-policy expects a value")),
-                );
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return;
             }
-            "--blob-auth-token" => {
-                blob_auth_token_spec = Some(
+            other => fatal(&format!("unknown argument: {other}\n{USAGE}")),
+        }
+    }
+    let node_id = node_id.unwrap_or_else(|| fatal(USAGE));
+    let key = load_or_derive_keypair(&Ed25519KeySource::from_spec(key_spec.as_deref()))
+        .unwrap_or_else(|err| fatal(&format!("failed to load key: {err}")));
+    let request = JoinRequest::new(node_id, addresses, now_millis(), &key);
+    request
+        .verify()
+        .unwrap_or_else(|err| fatal(&err.to_string()));
+    let encoded = serde_json::to_string_pretty(&request).expect("join request serializes");
+    match out {
+        Some(path) => {
+            fs::write(&path, format!("{encoded}\n"))
+                .unwrap_or_else(|err| fatal(&format!("failed to write {}: {err}", path.display())));
+            println!(
+                "join request for {} (key {}) written to {}",
+                request.node_id,
+                request.public_key,
+                path.display()
+            );
+        }
+        None => println!("{encoded}"),
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_net_proposal(args: Vec<String>) {
+    const USAGE: &str = "Usage: julian net proposal <create|vote|status> --log-dir <dir> ...\n  create [--key <spec>] (--parameter <name>=<value> | --members <file> |\n         --fee-schedule <file> | --join-request <file>) [--description <text>] [--voting-period <secs>]\n  vote [--key <spec>] <proposal_id> <yes|no>\n  status [<proposal_id>]\n  Proposals and votes are queued for the node running on <dir>, which gossips\n  them, tallies votes against its membership policy, and executes passed\n  proposals.";
+    let mut iter = args.into_iter();
+    let action = match iter.next() {
+        Some(action) if matches!(action.as_str(), "create" | "vote" | "status") => action,
+        Some(flag) if flag == "-h" || flag == "--help" => {
+            println!("{USAGE}");
+            return;
+        }
+        _ => fatal(USAGE),
+    };
+    let mut log_dir = None;
+    let mut key_spec = None;
+    let mut change = None;
+    let mut description = String::new();
+    let mut voting_period_secs: u64 = 7 * 24 * 60 * 60;
+    let mut positional = Vec::new();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--log-dir" => {
+                log_dir = Some(PathBuf::from(
                     iter.next()
-                        .unwrap_or_else(|| fatal("--blob-auth-token expects a value")),
-                );
+                        .unwrap_or_else(|| fatal("--log-dir expects a value")),
+                ));
             }
-            "--blob-max-concurrency" => {
-                blob_max_concurrency_spec = Some(
+            "--key" => {
+                key_spec = Some(
                     iter.next()
-                        .unwrap_or_else(|| fatal("--blob-max-concurrency expects a value")),
+                        .unwrap_or_else(|| fatal("--key expects a value")),
                 );
             }
-            "--blob-request-timeout-ms" => {
-                blob_request_timeout_ms_spec = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--blob-request-timeout-ms expects a value")),
-                );
+            "--parameter" | "--members" | "--fee-schedule" | "--join-request" => {
+                if change.is_some() {
+                    fatal("a proposal carries exactly one change");
+                }
+                let value = iter
+                    .next()
+                    .unwrap_or_else(|| fatal(&format!("{arg} expects a value")));
+                change = Some((arg, value));
             }
-            "--attestation-quorum" => {
-                attestation_quorum_spec = Some(
-                    iter.next()
-                        .unwrap_or_else(|| fatal("--attestation-quorum expects a value")),
-                );
+            "--description" => {
+                description = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--description expects a value"));
             }
-            "--tokio-threads" => {
-                tokio_threads_spec = Some(
+            "--voting-period" => {
+                voting_period_secs = iter
+                    .next()
+                    .and_then(|value| value.parse().ok())
+                    .filter(|secs| *secs > 0)
+                    .unwrap_or_else(|| fatal("--voting-period expects a positive number"));
+            }
+            other if other.starts_with("--") => fatal(&format!("unknown argument: {other}")),
+            other => positional.push(other.to_string()),
+        }
+    }
+    let log_dir = log_dir.unwrap_or_else(|| fatal(USAGE));
+    let key = || {
+        load_or_derive_keypair(&Ed25519KeySource::from_spec(key_spec.as_deref()))
+            .unwrap_or_else(|err| fatal(&format!("failed to load key: {err}")))
+    };
+    match action.as_str() {
+        "create" => {
+            if !positional.is_empty() {
+                fatal(USAGE);
+            }
+            let read = |path: &str| {
+                fs::read_to_string(path)
+                    .unwrap_or_else(|err| fatal(&format!("failed to read {path}: {err}")))
+            };
+            let change = match change {
+                Some((flag, value)) if flag == "--parameter" => {
+                    let (name, value) = value
+                        .split_once('=')
+                        .unwrap_or_else(|| fatal("--parameter expects <name>=<value>"));
+                    ProposalAction::ParameterChange {
+                        name: name.to_string(),
+                        value: value.to_string(),
+                    }
+                }
+                Some((flag, path)) if flag == "--members" => ProposalAction::MembershipRotation {
+                    new_members: serde_json::from_str(&read(&path)).unwrap_or_else(|err| {
+                        fatal(&format!("{path} must be a JSON array of keys: {err}"))
+                    }),
+                },
+                Some((flag, path)) if flag == "--fee-schedule" => ProposalAction::FeeSchedule {
+                    schedule: serde_json::from_str(&read(&path))
+                        .unwrap_or_else(|err| fatal(&format!("invalid fee schedule: {err}"))),
+                },
+                Some((_, path)) => ProposalAction::AdmitMember {
+                    request: JoinRequest::load(Path::new(&path))
+                        .unwrap_or_else(|err| fatal(&err.to_string())),
+                },
+                None => fatal(
+                    "create expects --parameter, --members, --fee-schedule, or --join-request",
+                ),
+            };
+            let created_ms = now_millis();
+            let proposal = Proposal::new(
+                change,
+                description,
+                created_ms,
+                created_ms.saturating_add(voting_period_secs.saturating_mul(1000)),
+                &key(),
+            );
+            proposal
+                .verify()
+                .unwrap_or_else(|err| fatal(&err.to_string()));
+            let entry =
+                record_proposal(&log_dir, &proposal).unwrap_or_else(|err| fatal(&err.to_string()));
+            let id = proposal.id_hex();
+            enqueue_governance(&log_dir, &GovernanceMessage::Proposal(proposal))
+                .unwrap_or_else(|err| fatal(&format!("failed to queue proposal: {err}")));
+            println!("proposal 0x{id} recorded at ledger entry {entry} and queued");
+        }
+        "vote" => {
+            let [id, ballot] = positional.as_slice() else {
+                fatal(USAGE);
+            };
+            let ballot = match ballot.as_str() {
+                "yes" => Ballot::Yes,
+                "no" => Ballot::No,
+                _ => fatal("ballot must be yes or no"),
+            };
+            let id = id.strip_prefix("0x").unwrap_or(id);
+            let vote = ProposalVote::new(id, ballot, &key());
+            enqueue_governance(&log_dir, &GovernanceMessage::Vote(vote))
+                .unwrap_or_else(|err| fatal(&format!("failed to queue vote: {err}")));
+            println!("vote queued for proposal 0x{}", id.to_lowercase());
+        }
+        _ => {
+            let book = GovernanceBook::open(&log_dir).unwrap_or_else(|err| fatal(&err.to_string()));
+            let value = match positional.as_slice() {
+                [] => serde_json::to_value(book.iter().collect::<Vec<_>>()),
+                [id] => serde_json::to_value(
+                    book.get(id)
+                        .unwrap_or_else(|| fatal_code(2, &format!("unknown proposal {id}"))),
+                ),
+                _ => fatal(USAGE),
+            }
+            .unwrap_or_else(|err| fatal(&err.to_string()));
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&value).expect("proposal records serialize")
+            );
+        }
+    }
+}
+
+#[cfg(feature = "net")]
+fn cmd_net_direct(args: Vec<String>) {
+    const USAGE: &str = "Usage: julian net direct send --log-dir <dir> --peer <peer_id> [--key <spec>]\n         (--proof <statement> <proof-file> | --record <file> --node-id <id>)\n  Queues the payload for the node running on <dir>; the node sends it to\n  <peer_id> over the encrypted direct channel and records the signed receipt\n  in direct_receipts.jsonl.";
+    let mut iter = args.into_iter();
+    match iter.next().as_deref() {
+        Some("send") => {}
+        Some("-h") | Some("--help") => {
+            println!("{USAGE}");
+            return;
+        }
+        _ => fatal(USAGE),
+    }
+    let mut log_dir = None;
+    let mut peer = None;
+    let mut key_spec = None;
+    let mut node_id = None;
+    let mut proof = None;
+    let mut record = None;
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--log-dir" => {
+                log_dir = Some(
                     iter.next()
-                        .unwrap_or_else(|| fatal("--tokio-threads expects a value")),
+                        .unwrap_or_else(|| fatal("--log-dir expects a value")),
                 );
             }
-            "--token-mode" => {
-                token_mode_contract_spec = Some(
+            "--peer" => {
+                peer = Some(
                     iter.next()
-                        .unwrap_or_else(|| fatal("--token-mode expects a value")),
+                        .unwrap_or_else(|| fatal("--peer expects a value")),
                 );
             }
-            "--token-oracle" => {
-                token_oracle_rpc_spec = Some(
+            "--key" => {
+                key_spec = Some(
                     iter.next()
-                        .unwrap_or_else(|| fatal("--token-oracle expects a value")),
+                        .unwrap_or_else(|| fatal("--key expects a value")),
                 );
             }
-            "--evm-rpc-listen" => {
-                evm_rpc_listen_spec = Some(
+            "--node-id" => {
+                node_id = Some(
                     iter.next()
-                        .unwrap_or_else(|| fatal("--evm-rpc-listen expects a value")),
+                        .unwrap_or_else(|| fatal("--node-id expects a value")),
                 );
             }
-            "--evm-chain-id" => {
-                evm_chain_id_spec = Some(
+            "--proof" => {
+                let statement = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--proof expects <statement> <proof-file>"));
+                let file = iter
+                    .next()
+                    .unwrap_or_else(|| fatal("--proof expects <statement> <proof-file>"));
+                proof = Some((statement, file));
+            }
+            "--record" => {
+                record = Some(
                     iter.next()
-                        .unwrap_or_else(|| fatal("--evm-chain-id expects a value")),
+                        .unwrap_or_else(|| fatal("--record expects a value")),
                 );
             }
             other => fatal(&format!("unknown argument: {other}")),
         }
     }
-
-    let node_id = node_id.unwrap_or_else(|| fatal("--node-id is required"));
-    let log_dir = log_dir.unwrap_or_else(|| fatal("--log-dir is required"));
-    let listen_addr = listen.unwrap_or_else(|| fatal("--listen is required"));
-    if identity_path.is_some() && key_spec.is_some() {
-        fatal("use either --key or --identity, not both");
-    }
-
-    let key_material = if let Some(path) = identity_path {
-        let passphrase = prompt_password("Identity passphrase: ")
-            .unwrap_or_else(|err| fatal(&format!("failed to read passphrase: {err}")));
-        match load_encrypted_identity(Path::new(&path), &passphrase) {
-            Ok(material) => material,
-            Err(err) => fatal(&format!("failed to load identity: {err}")),
+    let log_dir = PathBuf::from(log_dir.unwrap_or_else(|| fatal(USAGE)));
+    let peer: PeerId = peer
+        .unwrap_or_else(|| fatal(USAGE))
+        .parse()
+        .unwrap_or_else(|err| fatal(&format!("invalid --peer: {err}")));
+    let key_material = load_or_derive_keypair(&Ed25519KeySource::from_spec(key_spec.as_deref()))
+        .unwrap_or_else(|err| fatal(&format!("failed to load key: {err}")));
+    let payload = match (proof, record) {
+        (Some((statement, file)), None) => {
+            let bytes = fs::read(&file)
+                .unwrap_or_else(|err| fatal(&format!("failed to read {file}: {err}")));
+            DirectPayload::ProofSubmission(ProofSubmission::sign(
+                &statement,
+                &bytes,
+                &key_material,
+                now_millis(),
+            ))
         }
-    } else {
-        let key_source = Ed25519KeySource::from_spec(key_spec.as_deref());
-        match load_or_derive_keypair(&key_source) {
-            Ok(material) => material,
-            Err(err) => fatal(&format!("failed to load key: {err}")),
+        (None, Some(file)) => {
+            let node_id = node_id.unwrap_or_else(|| fatal("--record requires --node-id"));
+            let path = PathBuf::from(&file);
+            let bytes = fs::read(&path)
+                .unwrap_or_else(|err| fatal(&format!("failed to read {file}: {err}")));
+            let name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_else(|| fatal(&format!("invalid record path: {file}")));
+            DirectPayload::TranscriptRecord(ShippedRecord::sign(
+                &node_id,
+                name,
+                &bytes,
+                &key_material,
+            ))
         }
+        _ => fatal("exactly one of --proof or --record is required"),
     };
+    if let Err(err) = payload.verify() {
+        fatal(&format!("payload does not verify: {err}"));
+    }
+    let kind = payload.kind();
+    enqueue_direct(&log_dir, &peer, payload)
+        .unwrap_or_else(|err| fatal(&format!("failed to queue message: {err}")));
+    println!("queued {kind} for {peer} in {}", log_dir.display());
+}
 
-    let metrics_addr = metrics_addr_spec
-        .as_deref()
-        .map(parse_metrics_addr)
-        .unwrap_or(None);
-    let membership_policy =
-        load_membership_policy(policy_spec.as_deref(), policy_allowlist_spec.as_deref());
-    let checkpoint_interval = checkpoint_interval_spec.map(|value| {
-        value
-            .parse()
-            .unwrap_or_else(|_| fatal("invalid --checkpoint-interval"))
-    });
-    let blob_dir = blob_dir_spec.map(PathBuf::from);
-    let blob_listen = blob_listen_spec
-        .as_deref()
-        .map(parse_metrics_addr)
-        .unwrap_or(None);
-    let max_blob_bytes = max_blob_bytes_spec.map(|v| {
-        v.parse::<usize>()
-            .unwrap_or_else(|_| fatal("invalid --max-blob-bytes"))
-    });
-    let blob_retention_days = blob_retention_days_spec.map(|v| {
-        v.parse::<u64>()
-            .unwrap_or_else(|_| fatal("invalid --blob-retention-days"))
-    });
-    let blob_policies = blob_policy_spec
-        .as_deref()
-        .map(|path| load_blob_policies(Path::new(path)));
-    let blob_auth_token = blob_auth_token_spec;
-    let blob_max_concurrency = blob_max_concurrency_spec.map(|v| {
-        v.parse::<usize>()
-            .unwrap_or_else(|_| fatal("invalid --blob-max-concurrency"))
-    });
-    let blob_request_timeout_ms = blob_request_timeout_ms_spec.map(|v| {
-        v.parse::<u64>()
-            .unwrap_or_else(|_| fatal("invalid --blob-request-timeout-ms"))
-    });
-    let attestation_quorum = attestation_quorum_spec.map(|v| {
-        v.parse::<usize>()
-            .unwrap_or_else(|_| fatal("invalid --attestation-quorum"))
-    });
-    let anchor_topic = anchor_topic_spec.or_else(|| {
-        gossip_shard_spec.map(|shard| format!("mfenx/powerhouse/anchors/v1/shard/{shard}"))
-    });
-    let gossip_bridge_topics = gossip_bridge_topics_spec.as_deref().map(parse_topic_list);
-    let bft_round_ms = bft_round_ms_spec.map(|v| {
-        v.parse::<u64>()
-            .unwrap_or_else(|_| fatal("invalid --bft-round-ms"))
-    });
-    let tokio_threads = tokio_threads_spec.map(|v| {
-        v.parse::<usize>()
-            .unwrap_or_else(|_| fatal("invalid --tokio-threads"))
-    });
-    let evm_rpc_listen = evm_rpc_listen_spec
-        .as_deref()
-        .map(parse_metrics_addr)
-        .unwrap_or(None);
-    let evm_chain_id = evm_chain_id_spec.map(|v| {
-        v.parse::<u64>()
-            .unwrap_or_else(|_| fatal("invalid --evm-chain-id"))
-    });
-
-    let config = NetConfig::new(
-        node_id,
-        listen_addr,
-        bootstraps,
-        PathBuf::from(log_dir),
-        quorum,
-        Duration::from_millis(broadcast_ms),
-        key_material,
-        anchor_topic,
-        gossip_bridge_topics,
-        bft_enabled,
-        bft_round_ms,
-        metrics_addr,
-        membership_policy.clone(),
-        checkpoint_interval,
-        blob_dir,
-        blob_listen,
-        max_blob_bytes,
-        blob_retention_days,
-        blob_policies,
-        blob_auth_token,
-        blob_max_concurrency,
-        blob_request_timeout_ms,
-        attestation_quorum,
-        token_mode_contract_spec,
-        token_oracle_rpc_spec,
-        evm_rpc_listen,
-        evm_chain_id,
-    );
+#[cfg(feature = "net")]
+fn cmd_net_log_aggregator(args: Vec<String>) {
+    const USAGE: &str = "Usage: julian net log-aggregator --listen <host:port> --store <dir> [--cors-origins <list>]";
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        println!("{USAGE}");
+        return;
+    }
 
-    let mut builder = tokio::runtime::Builder::new_multi_thread();
-    builder.enable_all();
-    if let Some(threads) = tokio_threads {
-        builder.worker_threads(threads);
+    let mut listen: Option<String> = None;
+    let mut store = None;
+    let mut cors_origins = Vec::new();
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--listen" => {
+                listen = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--listen expects a value")),
+                );
+            }
+            "--store" => {
+                store = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--store expects a value")),
+                );
+            }
+            "--cors-origins" => {
+                cors_origins = parse_topic_list(
+                    &iter
+                        .next()
+                        .unwrap_or_else(|| fatal("--cors-origins expects a value")),
+                );
+            }
+            other => fatal(&format!("unknown argument: {other}")),
+        }
     }
-    let runtime = builder
+
+    let listen = listen
+        .as_deref()
+        .and_then(parse_metrics_addr)
+        .unwrap_or_else(|| fatal(USAGE));
+    let store = store.unwrap_or_else(|| fatal(USAGE));
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
         .build()
         .unwrap_or_else(|err| fatal(&format!("failed to start runtime: {err}")));
-    if let Err(err) = runtime.block_on(run_network(config)) {
-        fatal(&format!("network error: {err}"));
+    let mut config = LogAggregatorConfig::new(listen, PathBuf::from(store));
+    config.cors.allowed_origins = cors_origins;
+    if let Err(err) = runtime.block_on(run_log_aggregator(config)) {
+        fatal(&format!("log aggregator failed: {err}"));
     }
 }
 
-#[cfg(feature = "net")]
-fn cmd_net_anchor(args: Vec<String>) {
+#[cfg(feature = "grpc")]
+fn cmd_net_grpc(args: Vec<String>) {
+    const USAGE: &str = "Usage: julian net grpc --listen <host:port> --log-dir <dir> [--node-id <id>] [--quorum <N>] [--registry <path>]";
     if args.iter().any(|a| a == "-h" || a == "--help") {
-        println!("Usage: julian net anchor --log-dir <dir> [--node-id <id>] [--quorum <N>]");
-        println!("Compat: julian net anchor <log_dir> [--node-id <id>] [--quorum <N>]");
+        println!("{USAGE}");
         return;
     }
 
+    let mut listen: Option<String> = None;
     let mut log_dir = None;
-    let mut node_id = String::from("unknown-node");
-    let mut quorum: usize = 1;
-
+    let mut node_id = "local".to_string();
+    let mut quorum = 1usize;
+    let mut registry = None;
     let mut iter = args.into_iter();
     while let Some(arg) = iter.next() {
         match arg.as_str() {
+            "--listen" => {
+                listen = Some(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--listen expects a value")),
+                );
+            }
             "--log-dir" => {
                 log_dir = Some(
                     iter.next()
@@ -6832,32 +11878,35 @@ fn cmd_net_anchor(args: Vec<String>) {
                     .unwrap_or_else(|| fatal("--node-id expects a value"));
             }
             "--quorum" => {
-                let value = iter
+                quorum = iter
                     .next()
-                    .unwrap_or_else(|| fatal("--quorum expects a value"));
-                quorum = value.parse().unwrap_or_else(|_| fatal("invalid --quorum"));
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or_else(|| fatal("--quorum expects an integer"));
             }
-            other => {
-                if other.starts_with("--") {
-                    fatal(&format!("unknown argument: {other}"));
-                }
-                if log_dir.is_none() {
-                    log_dir = Some(other.to_string());
-                } else {
-                    fatal(&format!("unexpected positional argument: {other}"));
-                }
+            "--registry" => {
+                registry = Some(PathBuf::from(
+                    iter.next()
+                        .unwrap_or_else(|| fatal("--registry expects a value")),
+                ));
             }
+            other => fatal(&format!("unknown argument: {other}")),
         }
     }
 
-    let log_dir = log_dir.unwrap_or_else(|| fatal("--log-dir is required"));
-    let ledger = load_anchor_from_logs(Path::new(&log_dir)).unwrap_or_else(|err| fatal(&err));
-    let anchor_json =
-        AnchorJson::from_ledger(node_id, quorum, &ledger, now_millis(), Vec::new(), None)
-            .unwrap_or_else(|err| fatal(&format!("anchor conversion failed: {err}")));
-    match anchor_json.to_json_string() {
-        Ok(text) => println!("{text}"),
-        Err(err) => fatal(&format!("FAIL: failed to encode anchor: {err}")),
+    let listen = listen
+        .as_deref()
+        .and_then(parse_metrics_addr)
+        .unwrap_or_else(|| fatal(USAGE));
+    let log_dir = PathBuf::from(log_dir.unwrap_or_else(|| fatal(USAGE)));
+    let mut cfg = GrpcConfig::new(listen, log_dir, node_id);
+    cfg.quorum = quorum;
+    cfg.registry_path = registry;
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap_or_else(|err| fatal(&format!("failed to start runtime: {err}")));
+    if let Err(err) = runtime.block_on(run_grpc_server(cfg)) {
+        fatal(&format!("gRPC server failed: {err}"));
     }
 }
 
@@ -6867,15 +11916,20 @@ fn cmd_net_verify_envelope(args: Vec<String>) {
         println!(
             "Usage: julian net verify-envelope --file <anchor.json> --log-dir <dir> [--quorum <N>]"
         );
+        println!("           [--sign-receipt <file> --key <spec> [--node-id <id>]]");
         return;
     }
 
     let mut file = None;
     let mut log_dir = None;
     let mut quorum: usize = 1;
+    let mut receipt = ReceiptOptions::default();
 
     let mut iter = args.into_iter();
     while let Some(arg) = iter.next() {
+        if receipt.parse(&arg, &mut iter) {
+            continue;
+        }
         match arg.as_str() {
             "--file" => {
                 file = Some(
@@ -6901,31 +11955,50 @@ fn cmd_net_verify_envelope(args: Vec<String>) {
 
     let file = file.unwrap_or_else(|| fatal("--file is required"));
     let log_dir = log_dir.unwrap_or_else(|| fatal("--log-dir is required"));
-    let contents = fs::read_to_string(&file)
-        .unwrap_or_else(|err| fatal(&format!("FAIL: failed to read envelope: {err}")));
-    let envelope: AnchorEnvelope = serde_json::from_str(&contents)
-        .unwrap_or_else(|err| fatal(&format!("FAIL: invalid envelope JSON: {err}")));
-    if let Err(err) = envelope.validate() {
-        fatal(&format!("FAIL: invalid envelope: {err}"));
+    let outcome = verify_envelope_file(Path::new(&file), Path::new(&log_dir), quorum)
+        .map(|()| "envelope verified and quorum satisfied.".to_string());
+    receipt.emit(
+        "net verify-envelope",
+        &[
+            ("envelope", Path::new(&file)),
+            ("log_dir", Path::new(&log_dir)),
+        ],
+        &[("quorum", quorum.to_string())],
+        &outcome,
+    );
+    match outcome {
+        Ok(summary) => println!("PASS: {summary}"),
+        Err(err) => fatal(&format!("FAIL: {err}")),
     }
+}
+
+#[cfg(feature = "net")]
+fn verify_envelope_file(file: &Path, log_dir: &Path, quorum: usize) -> Result<(), String> {
+    let contents =
+        fs::read_to_string(file).map_err(|err| format!("failed to read envelope: {err}"))?;
+    let envelope = serde_json::from_str::<AnchorEnvelope>(&contents)
+        .map_err(|err| format!("invalid envelope JSON: {err}"))?
+        .upgrade()
+        .map_err(|err| format!("invalid envelope: {err}"))?;
+    envelope
+        .validate()
+        .map_err(|err| format!("invalid envelope: {err}"))?;
     let payload = BASE64
         .decode(envelope.payload.as_bytes())
-        .unwrap_or_else(|err| fatal(&format!("FAIL: payload decode failed: {err}")));
+        .map_err(|err| format!("payload decode failed: {err}"))?;
     verify_signature_base64(&envelope.public_key, &payload, &envelope.signature)
-        .unwrap_or_else(|err| fatal(&format!("FAIL: signature verification failed: {err}")));
+        .map_err(|err| format!("signature verification failed: {err}"))?;
     let remote_verifying = decode_public_key_base64(&envelope.public_key)
-        .unwrap_or_else(|err| fatal(&format!("FAIL: invalid public key: {err}")));
+        .map_err(|err| format!("invalid public key: {err}"))?;
     let remote_key_bytes = remote_verifying.to_bytes();
-    let payload_str = std::str::from_utf8(&payload)
-        .unwrap_or_else(|err| fatal(&format!("FAIL: payload is not UTF-8: {err}")));
+    let payload_str =
+        std::str::from_utf8(&payload).map_err(|err| format!("payload is not UTF-8: {err}"))?;
     let anchor_json = AnchorJson::from_json_str(payload_str)
-        .unwrap_or_else(|err| fatal(&format!("FAIL: invalid anchor payload: {err}")));
+        .map_err(|err| format!("invalid anchor payload: {err}"))?;
     let remote_ledger = anchor_json
-        .clone()
         .into_ledger()
-        .unwrap_or_else(|err| fatal(&format!("FAIL: anchor decode error: {err}")));
-    let local = load_anchor_from_logs(Path::new(&log_dir))
-        .unwrap_or_else(|err| fatal(&format!("FAIL: {err}")));
+        .map_err(|err| format!("anchor decode error: {err}"))?;
+    let local = load_anchor_from_logs(log_dir)?;
     let votes = [
         AnchorVote {
             anchor: &local,
@@ -6936,13 +12009,22 @@ fn cmd_net_verify_envelope(args: Vec<String>) {
             public_key: &remote_key_bytes,
         },
     ];
-    match reconcile_anchors_with_quorum(&votes, quorum) {
-        Ok(()) => println!("PASS: envelope verified and quorum satisfied."),
-        Err(err) => fatal(&format!("FAIL: quorum check failed: {err}")),
-    }
+    reconcile_anchors_with_quorum(&votes, quorum)
+        .map_err(|err| format!("quorum check failed: {err}"))
 }
 
 fn load_anchor_from_logs(path: &Path) -> Result<LedgerAnchor, String> {
+    load_anchor_from_logs_with(path, None, false)
+}
+
+/// Builds the anchor from `path`, optionally skipping records that fail
+/// verification instead of aborting (used to preview `node fsck` repairs).
+fn load_anchor_from_logs_with(
+    path: &Path,
+    modulus: Option<u64>,
+    skip_faulty: bool,
+) -> Result<LedgerAnchor, String> {
+    let _span = profile::span("load_anchor_from_logs");
     #[cfg(feature = "net")]
     let mut cutoff: Option<String> = None;
     #[cfg(not(feature = "net"))]
@@ -6981,30 +12063,30 @@ fn load_anchor_from_logs(path: &Path) -> Result<LedgerAnchor, String> {
     metadata
         .crate_version
         .get_or_insert_with(|| env!("CARGO_PKG_VERSION").to_string());
-    let mut files: Vec<PathBuf> = fs::read_dir(path)
-        .map_err(|err| format!("failed to read directory {}: {err}", path.display()))?
-        .filter_map(|entry| entry.ok().map(|e| e.path()))
-        .filter(|p| p.is_file() && is_ledger_file(p))
-        .collect();
-    files.sort();
-    for file in files {
+    for record in iter_ledger_logs(path)? {
+        let record = match record {
+            Ok(record) => record,
+            Err(_) if skip_faulty => continue,
+            Err(err) => return Err(err),
+        };
+        let name = record.name.as_str();
         if let Some(ref cutoff_name) = cutoff {
-            if let Some(name) = file.file_name().and_then(|n| n.to_str()) {
-                if name <= cutoff_name.as_str() {
-                    continue;
-                }
+            if name <= cutoff_name.as_str() {
+                continue;
             }
         }
-        let parsed = parse_log_file(&file)?;
+        let parsed = match record.check(modulus) {
+            Ok(parsed) => parsed,
+            Err(_) if skip_faulty => continue,
+            Err(fault) => return Err(fault.message),
+        };
         if let Some(mode) = parsed.metadata.challenge_mode {
             match &mut metadata.challenge_mode {
                 None => metadata.challenge_mode = Some(mode),
                 Some(existing) if existing != &mode => {
                     return Err(format!(
                         "{} challenge_mode {} conflicts with existing {}",
-                        file.display(),
-                        mode,
-                        existing
+                        name, mode, existing
                     ));
                 }
                 _ => {}
@@ -7015,7 +12097,7 @@ fn load_anchor_from_logs(path: &Path) -> Result<LedgerAnchor, String> {
                 if existing != &digest && anchor_from_checkpoint {
                     return Err(format!(
                         "{} fold_digest conflicts with existing value",
-                        file.display()
+                        name
                     ));
                 }
             }
@@ -7026,6 +12108,7 @@ fn load_anchor_from_logs(path: &Path) -> Result<LedgerAnchor, String> {
             statement: parsed.statement,
             merkle_root: power_house::merkle_root(&entry_hashes),
             hashes: entry_hashes,
+            submission: parsed.metadata.submission,
         });
     }
     if entries.is_empty() {
@@ -7046,13 +12129,6 @@ fn load_anchor_from_logs(path: &Path) -> Result<LedgerAnchor, String> {
     Ok(anchor)
 }
 
-fn is_ledger_file(path: &Path) -> bool {
-    match path.file_name().and_then(|n| n.to_str()) {
-        Some(name) => name.starts_with("ledger_") && name.ends_with(".txt"),
-        None => false,
-    }
-}
-
 fn write_anchor(path: &Path, anchor: &LedgerAnchor) -> io::Result<()> {
     fs::write(path, anchor_to_string(anchor))
 }
@@ -7080,6 +12156,12 @@ fn anchor_to_string(anchor: &LedgerAnchor) -> String {
     if let Some(version) = &anchor.metadata.crate_version {
         lines.push(format!("# crate_version: {version}"));
     }
+    if let Some(root) = &anchor.metadata.registry_root {
+        lines.push(format!(
+            "# registry_root: {}",
+            power_house::transcript_digest_to_hex(root)
+        ));
+    }
     for entry in &anchor.entries {
         let hash_list = entry
             .hashes
@@ -7087,17 +12169,98 @@ fn anchor_to_string(anchor: &LedgerAnchor) -> String {
             .map(power_house::transcript_digest_to_hex)
             .collect::<Vec<_>>()
             .join(",");
-        lines.push(format!(
+        let mut line = format!(
             "{}|{}|{}|root={}",
-            NETWORK_ID,
+            network_id(),
             entry.statement,
             hash_list,
             power_house::transcript_digest_to_hex(&entry.merkle_root)
-        ));
+        );
+        if let Some(submission) = &entry.submission {
+            line.push('|');
+            line.push_str(&format_anchor_submission(submission));
+        }
+        lines.push(line);
     }
     lines.join("\n")
 }
 
+/// Raw `|`-separated fields of one anchor text line.
+struct AnchorLineFields<'a> {
+    statement: &'a str,
+    hashes: &'a str,
+    root: Option<&'a str>,
+    submission: Option<&'a str>,
+}
+
+/// Splits an anchor line into its fields.
+///
+/// Structured statements contain `|`, so the fixed fields are peeled off the right.
+fn split_anchor_line(line: &str) -> Result<AnchorLineFields<'_>, String> {
+    let mut rest = line;
+    let mut submission = None;
+    let mut root = None;
+    if let Some((head, tail)) = rest.rsplit_once('|') {
+        if tail.starts_with("submitter=") {
+            submission = Some(tail);
+            rest = head;
+        }
+    }
+    if let Some((head, tail)) = rest.rsplit_once('|') {
+        if tail.starts_with("root=") {
+            root = Some(tail);
+            rest = head;
+        }
+    }
+    let (prefix, hashes) = rest
+        .rsplit_once('|')
+        .ok_or_else(|| format!("invalid anchor line: {line}"))?;
+    let statement = match prefix.split_once('|') {
+        Some((network, statement)) if network == network_id() => statement,
+        Some((network, _)) if !Statement::parse(prefix).is_structured() => {
+            return Err(format!(
+                "anchor network mismatch: expected {}, found {network}",
+                network_id()
+            ));
+        }
+        _ => prefix,
+    };
+    Ok(AnchorLineFields {
+        statement,
+        hashes,
+        root,
+        submission,
+    })
+}
+
+fn format_anchor_submission(submission: &EntrySubmission) -> String {
+    format!(
+        "submitter={}:{}:{}",
+        hex::encode(&submission.public_key),
+        hex::encode(&submission.signature),
+        submission.submitted_ms
+    )
+}
+
+fn parse_anchor_submission(field: &str) -> Result<EntrySubmission, String> {
+    let value = field.strip_prefix("submitter=").unwrap_or(field);
+    let mut parts = value.splitn(3, ':');
+    let (Some(public_key), Some(signature), Some(submitted_ms)) =
+        (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(format!("invalid submitter field: {field}"));
+    };
+    Ok(EntrySubmission {
+        public_key: hex::decode(public_key)
+            .map_err(|err| format!("invalid submitter key: {err}"))?,
+        signature: hex::decode(signature)
+            .map_err(|err| format!("invalid submitter signature: {err}"))?,
+        submitted_ms: submitted_ms
+            .parse()
+            .map_err(|err| format!("invalid submitted_ms: {err}"))?,
+    })
+}
+
 fn anchor_from_string(input: &str) -> Result<LedgerAnchor, String> {
     let mut entries = Vec::new();
     let mut metadata = AnchorMetadata::default();
@@ -7123,30 +12286,23 @@ fn anchor_from_string(input: &str) -> Result<LedgerAnchor, String> {
                     "crate_version" if !value.is_empty() => {
                         metadata.crate_version = Some(value.to_string())
                     }
+                    "registry_root" if !value.is_empty() => {
+                        metadata.registry_root =
+                            Some(power_house::transcript_digest_from_hex(value).map_err(
+                                |err| format!("invalid registry_root value {value}: {err}"),
+                            )?);
+                    }
                     _ => {}
                 }
             }
             continue;
         }
-        let segments: Vec<&str> = trimmed.split('|').collect();
-        let (statement, hashes_str, root_part) = match segments.as_slice() {
-            [network, statement, hashes, root] if *network == NETWORK_ID => {
-                (*statement, *hashes, Some(*root))
-            }
-            [network, statement, hashes] if *network == NETWORK_ID => (*statement, *hashes, None),
-            [statement, hashes, root] => (*statement, *hashes, Some(*root)),
-            [statement, hashes] => (*statement, *hashes, None),
-            _ => return Err(format!("invalid anchor line: {trimmed}")),
-        };
-        if segments.len() >= 3 && segments[0] != NETWORK_ID {
-            // Ensure lines with an unexpected network identifier are rejected explicitly.
-            if segments.len() == 4 {
-                return Err(format!(
-                    "anchor network mismatch: expected {NETWORK_ID}, found {}",
-                    segments[0]
-                ));
-            }
-        }
+        let AnchorLineFields {
+            statement,
+            hashes: hashes_str,
+            root: root_part,
+            submission: submission_part,
+        } = split_anchor_line(trimmed)?;
         let mut hashes = Vec::new();
         if !hashes_str.is_empty() {
             for part in hashes_str.split(',') {
@@ -7172,6 +12328,7 @@ fn anchor_from_string(input: &str) -> Result<LedgerAnchor, String> {
             statement: statement.to_string(),
             hashes,
             merkle_root,
+            submission: submission_part.map(parse_anchor_submission).transpose()?,
         });
     }
     if entries.is_empty() {
@@ -7204,6 +12361,12 @@ fn format_anchor(anchor: &LedgerAnchor) -> String {
     if let Some(version) = &anchor.metadata.crate_version {
         lines.push(format!("crate_version: {version}"));
     }
+    if let Some(root) = &anchor.metadata.registry_root {
+        lines.push(format!(
+            "registry_root: {}",
+            power_house::transcript_digest_to_hex(root)
+        ));
+    }
     for entry in &anchor.entries {
         let hashes = entry
             .hashes
@@ -7212,7 +12375,8 @@ fn format_anchor(anchor: &LedgerAnchor) -> String {
             .collect::<Vec<_>>()
             .join(", ");
         lines.push(format!(
-            "{NETWORK_ID} :: {} -> [{}] :: root={}",
+            "{} :: {} -> [{}] :: root={}",
+            network_id(),
             entry.statement,
             hashes,
             power_house::transcript_digest_to_hex(&entry.merkle_root)
@@ -7221,6 +12385,88 @@ fn format_anchor(anchor: &LedgerAnchor) -> String {
     lines.join("\n")
 }
 
+/// `--sign-receipt` options shared by the offline check commands.
+#[derive(Debug, Default)]
+struct ReceiptOptions {
+    out: Option<PathBuf>,
+    key_spec: Option<String>,
+    node_id: Option<String>,
+}
+
+impl ReceiptOptions {
+    /// Consumes `arg` and its value if it is a receipt option.
+    fn parse(&mut self, arg: &str, iter: &mut impl Iterator<Item = String>) -> bool {
+        let mut value = || {
+            iter.next()
+                .unwrap_or_else(|| fatal(&format!("{arg} expects a value")))
+        };
+        match arg {
+            "--sign-receipt" => self.out = Some(PathBuf::from(value())),
+            "--key" => self.key_spec = Some(value()),
+            "--node-id" => self.node_id = Some(value()),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Writes a signed receipt for `outcome` when `--sign-receipt` was given.
+    #[cfg(feature = "net")]
+    fn emit(
+        &self,
+        command: &str,
+        paths: &[(&str, &Path)],
+        params: &[(&str, String)],
+        outcome: &Result<String, String>,
+    ) {
+        let Some(out) = &self.out else {
+            if self.key_spec.is_some() || self.node_id.is_some() {
+                fatal("--key and --node-id require --sign-receipt");
+            }
+            return;
+        };
+        let key_spec = self
+            .key_spec
+            .as_deref()
+            .unwrap_or_else(|| fatal("--sign-receipt requires --key <spec>"));
+        let key = load_or_derive_keypair(&Ed25519KeySource::from_spec(Some(key_spec)))
+            .unwrap_or_else(|err| fatal(&format!("failed to load key: {err}")));
+        let mut inputs = BTreeMap::new();
+        for (name, path) in paths {
+            // Missing inputs are part of a failed check, not a reason to skip the receipt.
+            let digest = digest_input_path(path).unwrap_or_else(|err| format!("unreadable: {err}"));
+            inputs.insert(name.to_string(), digest);
+        }
+        for (name, value) in params {
+            inputs.insert(name.to_string(), value.clone());
+        }
+        let receipt = CheckReceipt::sign(
+            command,
+            inputs,
+            outcome,
+            now_millis(),
+            self.node_id.clone(),
+            &key,
+        );
+        let json = serde_json::to_string_pretty(&receipt).expect("receipt serializes");
+        fs::write(out, format!("{json}\n"))
+            .unwrap_or_else(|err| fatal(&format!("failed to write receipt: {err}")));
+        println!("receipt: {}", out.display());
+    }
+
+    #[cfg(not(feature = "net"))]
+    fn emit(
+        &self,
+        _command: &str,
+        _paths: &[(&str, &Path)],
+        _params: &[(&str, String)],
+        _outcome: &Result<String, String>,
+    ) {
+        if self.out.is_some() {
+            fatal("--sign-receipt requires the `net` feature");
+        }
+    }
+}
+
 #[cfg(feature = "net")]
 fn now_millis() -> u64 {
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -7285,6 +12531,21 @@ enum GovernanceDescriptor {
     },
 }
 
+/// Removes `--config <file>` from `args` and loads the file; without the flag
+/// every setting keeps its default.
+#[cfg(feature = "net")]
+fn take_config_file(args: &mut Vec<String>) -> ConfigFile {
+    let Some(index) = args.iter().position(|arg| arg == "--config") else {
+        return ConfigFile::default();
+    };
+    if index + 1 >= args.len() {
+        fatal("--config expects a path");
+    }
+    let path = args.remove(index + 1);
+    args.remove(index);
+    ConfigFile::load(Path::new(&path)).unwrap_or_else(|err| fatal(&err.to_string()))
+}
+
 #[cfg(feature = "net")]
 fn load_membership_policy(
     policy_spec: Option<&str>,
@@ -0,0 +1,100 @@
+//! Injectable clocks for artifact timestamps.
+//!
+//! Artifacts such as migration snapshots, claim manifests and anchors embed a
+//! millisecond timestamp.  Reading the wall clock makes two otherwise
+//! identical runs differ byte for byte, so producers take a [`Clock`] instead:
+//! [`SystemClock`] keeps the historical behaviour, [`FixedClock`] pins a value
+//! for tests, and [`InputClock`] derives the timestamp from the artifact's own
+//! inputs so independent operators reproduce identical files.
+
+use blake2::digest::{consts::U32, Digest};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+type Blake2b256 = blake2::Blake2b<U32>;
+
+const INPUT_CLOCK_DOMAIN: &[u8] = b"power_house:v1:input-clock";
+
+/// Upper bound (exclusive) of [`InputClock`] timestamps: `2^40` ms, late 2004.
+///
+/// Derived values therefore never pass for a recent wall-clock reading.
+pub const INPUT_CLOCK_SPAN_MS: u64 = 1 << 40;
+
+/// Source of millisecond timestamps embedded in artifacts.
+pub trait Clock {
+    /// Timestamp for an artifact derived from `inputs`.
+    ///
+    /// Wall-clock implementations ignore `inputs`.
+    fn timestamp_ms(&self, inputs: &[&[u8]]) -> u64;
+}
+
+/// Reads the system wall clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn timestamp_ms(&self, _inputs: &[&[u8]]) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64
+    }
+}
+
+/// Returns the same timestamp for every artifact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedClock(pub u64);
+
+impl Clock for FixedClock {
+    fn timestamp_ms(&self, _inputs: &[&[u8]]) -> u64 {
+        self.0
+    }
+}
+
+/// Hashes the artifact inputs into a timestamp below [`INPUT_CLOCK_SPAN_MS`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InputClock;
+
+impl Clock for InputClock {
+    fn timestamp_ms(&self, inputs: &[&[u8]]) -> u64 {
+        let mut hasher = Blake2b256::new();
+        hasher.update(INPUT_CLOCK_DOMAIN);
+        hasher.update((inputs.len() as u64).to_be_bytes());
+        for input in inputs {
+            hasher.update((input.len() as u64).to_be_bytes());
+            hasher.update(input);
+        }
+        let digest = hasher.finalize();
+        let mut word = [0u8; 8];
+        word.copy_from_slice(&digest[..8]);
+        u64::from_be_bytes(word) % INPUT_CLOCK_SPAN_MS
+    }
+}
+
+/// Clock used by CLI commands: [`InputClock`] in `--deterministic` mode,
+/// [`SystemClock`] otherwise.
+pub fn artifact_clock(deterministic: bool) -> &'static dyn Clock {
+    if deterministic {
+        &InputClock
+    } else {
+        &SystemClock
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_clock_depends_only_on_inputs() {
+        let a = InputClock.timestamp_ms(&[b"root", &7u64.to_be_bytes()]);
+        assert_eq!(a, InputClock.timestamp_ms(&[b"root", &7u64.to_be_bytes()]));
+        assert_ne!(a, InputClock.timestamp_ms(&[b"root", &8u64.to_be_bytes()]));
+        assert_ne!(
+            InputClock.timestamp_ms(&[b"ab", b"c"]),
+            InputClock.timestamp_ms(&[b"a", b"bc"])
+        );
+        assert!(a < INPUT_CLOCK_SPAN_MS);
+        assert_eq!(FixedClock(5).timestamp_ms(&[b"anything"]), 5);
+        assert!(artifact_clock(false).timestamp_ms(&[]) >= INPUT_CLOCK_SPAN_MS);
+    }
+}
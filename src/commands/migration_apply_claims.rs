@@ -1,6 +1,10 @@
 #![cfg(feature = "net")]
 
-use crate::net::StakeRegistry;
+use crate::net::{
+    decode_public_key_base64, encode_public_key_base64, encode_signature_base64, sign_payload,
+    verify_signature_base64, StakeRegistry, SupplyCause,
+};
+use ed25519_dalek::SigningKey;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
@@ -8,6 +12,9 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 const APPLY_STATE_SCHEMA: &str = "mfenx.powerhouse.migration-apply-state.v1";
 
+/// Schema of the signed record written by a sweep of expired claims.
+pub const CLAIM_SWEEP_SCHEMA: &str = "mfenx.powerhouse.migration-sweep.v1";
+
 /// Options for applying native migration claims into the stake registry.
 #[derive(Debug, Clone)]
 pub struct ApplyClaimsOptions {
@@ -24,31 +31,102 @@ pub struct ApplyClaimsSummary {
     pub applied: usize,
     /// Number of claims skipped because they were already applied.
     pub skipped: usize,
+    /// Number of claims left unapplied because their account is frozen.
+    pub held: usize,
     /// Aggregate minted amount for newly-applied claims.
     pub total_mint_amount: String,
     /// Resolved state file path.
     pub state_path: String,
 }
 
+/// Options for sweeping expired, unapplied claims into a governance account.
+#[derive(Debug, Clone)]
+pub struct SweepClaimsOptions {
+    /// Optional path to the apply-state file shared with `apply-claims`.
+    pub state_path: Option<String>,
+    /// Base64 public key of the governance-controlled account credited by the sweep.
+    pub sweep_account: String,
+    /// Identity signing the sweep record.
+    pub signing: SigningKey,
+    /// Path the signed sweep record is written to.
+    pub output: String,
+    /// Dry-run mode returns the signed record without writing any file.
+    pub dry_run: bool,
+}
+
+/// Signed record of unapplied claims credited to the sweep account after expiry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClaimSweep {
+    /// Schema identifier (`mfenx.powerhouse.migration-sweep.v1`).
+    pub schema: String,
+    /// Merkle root of the swept claims artifact.
+    pub claims_root: String,
+    /// Expiry epoch recorded in the claims artifact.
+    pub expires_at_epoch: u64,
+    /// Registry epoch at which the sweep ran.
+    pub registry_epoch: u64,
+    /// Account credited with the swept amount.
+    pub sweep_account: String,
+    /// Claims swept by this run, sorted.
+    pub swept_claim_ids: Vec<String>,
+    /// Sum of the swept `mint_amount` values.
+    pub total_amount: String,
+    /// Millisecond timestamp of the sweep.
+    pub swept_at_ms: u64,
+    /// Base64 public key of the signing identity.
+    pub signer_b64: String,
+    /// Base64 ed25519 signature over the record with this field empty.
+    pub signature_b64: String,
+}
+
+impl ClaimSweep {
+    fn signing_payload(&self) -> Vec<u8> {
+        let unsigned = Self {
+            signature_b64: String::new(),
+            ..self.clone()
+        };
+        serde_json::to_vec(&unsigned).expect("sweep record encodes")
+    }
+
+    /// Checks the schema and the signer's signature.
+    pub fn verify(&self) -> Result<(), String> {
+        if self.schema != CLAIM_SWEEP_SCHEMA {
+            return Err(format!("unexpected sweep schema: {}", self.schema));
+        }
+        verify_signature_base64(
+            &self.signer_b64,
+            &self.signing_payload(),
+            &self.signature_b64,
+        )
+        .map_err(|err| format!("sweep signature invalid: {err}"))
+    }
+}
+
 #[derive(Debug, Deserialize)]
-struct ClaimsArtifact {
+pub(crate) struct ClaimsArtifact {
     claim_mode: String,
-    claims: Vec<ClaimEntry>,
+    #[serde(default)]
+    pub(crate) merkle_root: String,
+    #[serde(default)]
+    expires_at_epoch: Option<u64>,
+    pub(crate) claims: Vec<ClaimEntry>,
 }
 
 #[derive(Debug, Deserialize)]
-struct ClaimEntry {
+pub(crate) struct ClaimEntry {
     pubkey_b64: String,
     account: String,
-    claim_id: String,
+    pub(crate) claim_id: String,
     mint_amount: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
-struct ApplyState {
+pub(crate) struct ApplyState {
     schema: String,
     updated_at_ms: u64,
-    applied_claim_ids: Vec<String>,
+    pub(crate) applied_claim_ids: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) swept_claim_ids: Vec<String>,
 }
 
 fn now_millis() -> u64 {
@@ -58,7 +136,9 @@ fn now_millis() -> u64 {
         .as_millis() as u64
 }
 
-fn resolve_state_path(registry_path: &Path, explicit: Option<&str>) -> PathBuf {
+/// Apply-state file used for `registry_path`: `explicit` if given, otherwise
+/// `<registry stem>.migration_apply_state.json` beside the registry.
+pub fn resolve_state_path(registry_path: &Path, explicit: Option<&str>) -> PathBuf {
     if let Some(path) = explicit {
         return PathBuf::from(path);
     }
@@ -70,12 +150,13 @@ fn resolve_state_path(registry_path: &Path, explicit: Option<&str>) -> PathBuf {
     registry_path.with_file_name(default_name)
 }
 
-fn load_apply_state(path: &Path) -> Result<ApplyState, String> {
+pub(crate) fn load_apply_state(path: &Path) -> Result<ApplyState, String> {
     if !path.exists() {
         return Ok(ApplyState {
             schema: APPLY_STATE_SCHEMA.to_string(),
             updated_at_ms: now_millis(),
             applied_claim_ids: Vec::new(),
+            swept_claim_ids: Vec::new(),
         });
     }
     let bytes = std::fs::read(path)
@@ -88,6 +169,16 @@ fn load_apply_state(path: &Path) -> Result<ApplyState, String> {
     Ok(state)
 }
 
+/// Returns whether `claim_id` has been applied according to the apply-state file.
+///
+/// A missing file means no claim has been applied yet.
+pub fn claim_applied(state_path: &Path, claim_id: &str) -> Result<bool, String> {
+    Ok(load_apply_state(state_path)?
+        .applied_claim_ids
+        .iter()
+        .any(|applied| applied == claim_id))
+}
+
 fn save_apply_state(path: &Path, state: &ApplyState) -> Result<(), String> {
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent)
@@ -99,18 +190,29 @@ fn save_apply_state(path: &Path, state: &ApplyState) -> Result<(), String> {
         .map_err(|err| format!("failed to write apply state {}: {err}", path.display()))
 }
 
-/// Applies native claim artifacts into the stake registry with idempotent state tracking.
-///
-/// Only artifacts with `claim_mode == "native"` are accepted.
-pub fn run_apply_claims(
-    registry_path: &str,
-    claims_path: &str,
-    opts: &ApplyClaimsOptions,
-) -> Result<ApplyClaimsSummary, String> {
-    let registry_path = Path::new(registry_path);
-    let claims_path = Path::new(claims_path);
-    let state_path = resolve_state_path(registry_path, opts.state_path.as_deref());
+impl ClaimEntry {
+    pub(crate) fn checked_mint_amount(&self) -> Result<u128, String> {
+        if self.account != self.pubkey_b64 {
+            return Err(format!(
+                "native claim account mismatch for claim_id {} (account='{}', pubkey='{}')",
+                self.claim_id, self.account, self.pubkey_b64
+            ));
+        }
+        let mint_amount = self
+            .mint_amount
+            .parse::<u128>()
+            .map_err(|err| format!("invalid mint_amount for claim {}: {err}", self.claim_id))?;
+        if mint_amount > u64::MAX as u128 {
+            return Err(format!(
+                "mint_amount overflow for claim {}: {} > u64::MAX",
+                self.claim_id, mint_amount
+            ));
+        }
+        Ok(mint_amount)
+    }
+}
 
+pub(crate) fn load_native_claims(claims_path: &Path) -> Result<ClaimsArtifact, String> {
     let claims_bytes = std::fs::read(claims_path)
         .map_err(|err| format!("failed to read claims {}: {err}", claims_path.display()))?;
     let artifact: ClaimsArtifact = serde_json::from_slice(&claims_bytes)
@@ -122,6 +224,25 @@ pub fn run_apply_claims(
             artifact.claim_mode
         ));
     }
+    Ok(artifact)
+}
+
+/// Applies native claim artifacts into the stake registry with idempotent state tracking.
+///
+/// Only artifacts with `claim_mode == "native"` are accepted, and an artifact
+/// carrying `expires_at_epoch` is refused once the registry epoch reaches it.
+/// Claims for frozen accounts are held: left unapplied until the account is
+/// released.
+pub fn run_apply_claims(
+    registry_path: &str,
+    claims_path: &str,
+    opts: &ApplyClaimsOptions,
+) -> Result<ApplyClaimsSummary, String> {
+    let registry_path = Path::new(registry_path);
+    let claims_path = Path::new(claims_path);
+    let state_path = resolve_state_path(registry_path, opts.state_path.as_deref());
+
+    let artifact = load_native_claims(claims_path)?;
 
     let mut state = load_apply_state(&state_path)?;
     let mut applied_set = state
@@ -131,36 +252,41 @@ pub fn run_apply_claims(
         .collect::<HashSet<String>>();
 
     let mut registry = StakeRegistry::load(registry_path)?;
+    if let Some(expiry) = artifact.expires_at_epoch {
+        if registry.epoch() >= expiry {
+            return Err(format!(
+                "claims expired at epoch {expiry} (registry epoch {}); unapplied claims can only be swept",
+                registry.epoch()
+            ));
+        }
+    }
 
     let mut applied = 0usize;
     let mut skipped = 0usize;
+    let mut held = 0usize;
     let mut total_mint_amount: u128 = 0;
 
     for claim in artifact.claims {
-        if claim.account != claim.pubkey_b64 {
-            return Err(format!(
-                "native claim account mismatch for claim_id {} (account='{}', pubkey='{}')",
-                claim.claim_id, claim.account, claim.pubkey_b64
-            ));
-        }
+        let mint_amount = claim.checked_mint_amount()?;
 
-        let mint_amount = claim
-            .mint_amount
-            .parse::<u128>()
-            .map_err(|err| format!("invalid mint_amount for claim {}: {err}", claim.claim_id))?;
-        if mint_amount > u64::MAX as u128 {
-            return Err(format!(
-                "mint_amount overflow for claim {}: {} > u64::MAX",
-                claim.claim_id, mint_amount
-            ));
-        }
-
-        if !applied_set.insert(claim.claim_id.clone()) {
+        if applied_set.contains(&claim.claim_id) {
             skipped += 1;
             continue;
         }
+        if registry
+            .account(&claim.pubkey_b64)
+            .is_some_and(|acct| acct.frozen.is_some())
+        {
+            held += 1;
+            continue;
+        }
+        applied_set.insert(claim.claim_id.clone());
 
-        registry.fund_balance(&claim.pubkey_b64, mint_amount as u64);
+        registry.mint(
+            &claim.pubkey_b64,
+            mint_amount as u64,
+            SupplyCause::MigrationClaim,
+        )?;
         applied += 1;
         total_mint_amount = total_mint_amount.saturating_add(mint_amount);
     }
@@ -178,15 +304,110 @@ pub fn run_apply_claims(
     Ok(ApplyClaimsSummary {
         applied,
         skipped,
+        held,
         total_mint_amount: total_mint_amount.to_string(),
         state_path: state_path.display().to_string(),
     })
 }
 
+/// Credits every claim that was neither applied nor swept to
+/// `opts.sweep_account` once the artifact's `expires_at_epoch` has passed,
+/// records the swept ids in the apply state and writes a signed [`ClaimSweep`].
+pub fn run_sweep_claims(
+    registry_path: &str,
+    claims_path: &str,
+    opts: &SweepClaimsOptions,
+) -> Result<ClaimSweep, String> {
+    let registry_path = Path::new(registry_path);
+    let state_path = resolve_state_path(registry_path, opts.state_path.as_deref());
+    decode_public_key_base64(&opts.sweep_account)
+        .map_err(|err| format!("invalid sweep account: {err}"))?;
+
+    let artifact = load_native_claims(Path::new(claims_path))?;
+    let expiry = artifact.expires_at_epoch.ok_or_else(|| {
+        "claims artifact has no expires_at_epoch; nothing can be swept".to_string()
+    })?;
+    let mut registry = StakeRegistry::load(registry_path)?;
+    if registry.epoch() < expiry {
+        return Err(format!(
+            "claims expire at epoch {expiry}; registry is at epoch {}",
+            registry.epoch()
+        ));
+    }
+
+    let mut state = load_apply_state(&state_path)?;
+    let applied = state
+        .applied_claim_ids
+        .iter()
+        .cloned()
+        .collect::<HashSet<String>>();
+    let mut swept_set = state
+        .swept_claim_ids
+        .iter()
+        .cloned()
+        .collect::<HashSet<String>>();
+
+    let mut swept_claim_ids = Vec::new();
+    let mut total_amount: u128 = 0;
+    for claim in &artifact.claims {
+        let mint_amount = claim.checked_mint_amount()?;
+        if applied.contains(&claim.claim_id) || !swept_set.insert(claim.claim_id.clone()) {
+            continue;
+        }
+        swept_claim_ids.push(claim.claim_id.clone());
+        total_amount = total_amount.saturating_add(mint_amount);
+    }
+    if swept_claim_ids.is_empty() {
+        return Err("no unapplied claims left to sweep".to_string());
+    }
+    let total = u64::try_from(total_amount)
+        .map_err(|_| format!("sweep total overflow: {total_amount} > u64::MAX"))?;
+    registry.mint(&opts.sweep_account, total, SupplyCause::MigrationSweep)?;
+    swept_claim_ids.sort();
+
+    let mut sweep = ClaimSweep {
+        schema: CLAIM_SWEEP_SCHEMA.to_string(),
+        claims_root: artifact.merkle_root,
+        expires_at_epoch: expiry,
+        registry_epoch: registry.epoch(),
+        sweep_account: opts.sweep_account.clone(),
+        swept_claim_ids,
+        total_amount: total_amount.to_string(),
+        swept_at_ms: now_millis(),
+        signer_b64: encode_public_key_base64(&opts.signing.verifying_key()),
+        signature_b64: String::new(),
+    };
+    sweep.signature_b64 =
+        encode_signature_base64(&sign_payload(&opts.signing, &sweep.signing_payload()));
+
+    if !opts.dry_run {
+        registry.save(registry_path)?;
+        let mut swept_claim_ids = swept_set.into_iter().collect::<Vec<_>>();
+        swept_claim_ids.sort();
+        state.schema = APPLY_STATE_SCHEMA.to_string();
+        state.updated_at_ms = now_millis();
+        state.swept_claim_ids = swept_claim_ids;
+        save_apply_state(&state_path, &state)?;
+
+        let output = Path::new(&opts.output);
+        if let Some(parent) = output.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|err| format!("failed to create {}: {err}", parent.display()))?;
+        }
+        let bytes = serde_json::to_vec_pretty(&sweep)
+            .map_err(|err| format!("failed to encode sweep record: {err}"))?;
+        std::fs::write(output, bytes)
+            .map_err(|err| format!("failed to write sweep record {}: {err}", output.display()))?;
+    }
+    Ok(sweep)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{run_apply_claims, ApplyClaimsOptions};
-    use crate::net::StakeRegistry;
+    use super::{run_apply_claims, run_sweep_claims, ApplyClaimsOptions, SweepClaimsOptions};
+    use crate::net::{
+        encode_public_key_base64, load_or_derive_keypair, Ed25519KeySource, StakeRegistry,
+    };
     use serde_json::json;
     use std::fs;
 
@@ -262,6 +483,59 @@ mod tests {
         let _ = fs::remove_file(state);
     }
 
+    #[test]
+    fn apply_holds_claims_for_frozen_accounts() {
+        let registry = temp_path("registry_apply_frozen.json");
+        let claims = temp_path("claims_apply_frozen.json");
+        let state = temp_path("apply_state_frozen.json");
+
+        let registry_payload = json!({
+            "accounts": {
+                "aKey": {
+                    "balance": 0, "stake": 0, "slashed": false, "freeze_nonce": 1,
+                    "frozen": {"reason": "hold", "authority": ["gov"], "since_epoch": 0}
+                }
+            }
+        });
+        fs::write(&registry, serde_json::to_vec(&registry_payload).unwrap()).unwrap();
+        let claims_payload = json!({
+            "claim_mode": "native",
+            "claims": [
+                {"pubkey_b64": "aKey", "account": "aKey", "claim_id": "c1", "mint_amount": "10"},
+                {"pubkey_b64": "bKey", "account": "bKey", "claim_id": "c2", "mint_amount": "20"}
+            ]
+        });
+        fs::write(&claims, serde_json::to_vec(&claims_payload).unwrap()).unwrap();
+        let opts = ApplyClaimsOptions {
+            state_path: Some(state.display().to_string()),
+            dry_run: false,
+        };
+
+        let first =
+            run_apply_claims(registry.to_str().unwrap(), claims.to_str().unwrap(), &opts).unwrap();
+        assert_eq!((first.applied, first.held), (1, 1));
+
+        let reg = StakeRegistry::load(&registry).unwrap();
+        assert_eq!(reg.account("aKey").unwrap().balance, 0);
+        let mut saved: serde_json::Value =
+            serde_json::from_slice(&fs::read(&registry).unwrap()).unwrap();
+        saved["accounts"]["aKey"]
+            .as_object_mut()
+            .unwrap()
+            .remove("frozen");
+        fs::write(&registry, serde_json::to_vec(&saved).unwrap()).unwrap();
+
+        let second =
+            run_apply_claims(registry.to_str().unwrap(), claims.to_str().unwrap(), &opts).unwrap();
+        assert_eq!((second.applied, second.skipped, second.held), (1, 1, 0));
+        let reg = StakeRegistry::load(&registry).unwrap();
+        assert_eq!(reg.account("aKey").unwrap().balance, 10);
+
+        let _ = fs::remove_file(registry);
+        let _ = fs::remove_file(claims);
+        let _ = fs::remove_file(state);
+    }
+
     #[test]
     fn reject_non_native_claims() {
         let registry = temp_path("registry_apply_erc20.json");
@@ -288,4 +562,85 @@ mod tests {
         let _ = fs::remove_file(registry);
         let _ = fs::remove_file(claims);
     }
+
+    #[test]
+    fn expired_claims_are_swept_once() {
+        let registry = temp_path("registry_sweep.json");
+        let partial = temp_path("claims_sweep_partial.json");
+        let claims = temp_path("claims_sweep.json");
+        let state = temp_path("apply_state_sweep.json");
+        let record = temp_path("sweep_record.json");
+        let key = load_or_derive_keypair(&Ed25519KeySource::Seed("sweep-gov".into())).unwrap();
+        let treasury = encode_public_key_base64(&key.verifying);
+
+        fs::write(&registry, br#"{"accounts": {}}"#).unwrap();
+        let claim = |id: &str, pk: &str, amount: &str| json!({"pubkey_b64": pk, "account": pk, "claim_id": id, "mint_amount": amount});
+        let artifact = |entries: Vec<serde_json::Value>| {
+            serde_json::to_vec(&json!({
+                "claim_mode": "native",
+                "merkle_root": "0xabc",
+                "expires_at_epoch": 3,
+                "claims": entries
+            }))
+            .unwrap()
+        };
+        fs::write(&partial, artifact(vec![claim("c1", "aKey", "10")])).unwrap();
+        fs::write(
+            &claims,
+            artifact(vec![claim("c1", "aKey", "10"), claim("c2", "bKey", "20")]),
+        )
+        .unwrap();
+
+        let apply = ApplyClaimsOptions {
+            state_path: Some(state.display().to_string()),
+            dry_run: false,
+        };
+        run_apply_claims(
+            registry.to_str().unwrap(),
+            partial.to_str().unwrap(),
+            &apply,
+        )
+        .unwrap();
+
+        let sweep = SweepClaimsOptions {
+            state_path: Some(state.display().to_string()),
+            sweep_account: treasury.clone(),
+            signing: key.signing.clone(),
+            output: record.display().to_string(),
+            dry_run: false,
+        };
+        let early = run_sweep_claims(registry.to_str().unwrap(), claims.to_str().unwrap(), &sweep)
+            .unwrap_err();
+        assert!(early.contains("expire at epoch 3"));
+
+        let mut reg = StakeRegistry::load(&registry).unwrap();
+        reg.advance_epoch(3);
+        reg.save(&registry).unwrap();
+        let late = run_apply_claims(registry.to_str().unwrap(), claims.to_str().unwrap(), &apply)
+            .unwrap_err();
+        assert!(late.contains("can only be swept"));
+
+        let swept =
+            run_sweep_claims(registry.to_str().unwrap(), claims.to_str().unwrap(), &sweep).unwrap();
+        assert_eq!(swept.swept_claim_ids, vec!["c2".to_string()]);
+        assert_eq!(swept.total_amount, "20");
+        let written: super::ClaimSweep =
+            serde_json::from_slice(&fs::read(&record).unwrap()).unwrap();
+        assert_eq!(written, swept);
+        written.verify().unwrap();
+        let mut forged = written.clone();
+        forged.total_amount = "21".into();
+        assert!(forged.verify().is_err());
+
+        let reg = StakeRegistry::load(&registry).unwrap();
+        assert_eq!(reg.account(&treasury).unwrap().balance, 20);
+        assert!(reg.account("bKey").is_none());
+        assert!(
+            run_sweep_claims(registry.to_str().unwrap(), claims.to_str().unwrap(), &sweep).is_err()
+        );
+
+        for path in [registry, partial, claims, state, record] {
+            let _ = fs::remove_file(path);
+        }
+    }
 }
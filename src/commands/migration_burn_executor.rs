@@ -1,9 +1,15 @@
 #![cfg(feature = "net")]
 
-use crate::net::StakeRegistry;
+use crate::net::{
+    encode_public_key_base64, encode_signature_base64, sign_payload, verify_signature_base64,
+    StakeRegistry,
+};
 use blake2::digest::{consts::U32, Digest};
+use ed25519_dalek::SigningKey;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -11,13 +17,27 @@ type Blake2b256 = blake2::Blake2b<U32>;
 
 const EXEC_STATE_SCHEMA: &str = "mfenx.powerhouse.migration-burn-exec-state.v1";
 
+/// Schema of a burn intent record in the outbox.
+pub const BURN_INTENT_SCHEMA: &str = "mfenx.powerhouse.token-burn-intent.v1";
+
+/// Schema of an executor receipt acknowledging one burn intent.
+pub const BURN_RECEIPT_SCHEMA: &str = "mfenx.powerhouse.token-burn-receipt.v1";
+
+const SIGNATURE_FIELDS: [&str; 2] = ["signer_b64", "signature_b64"];
+
 /// Options for executing migration burn intents.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct ExecuteBurnOptions {
     /// Optional path to outbox state file.
     pub state_path: Option<String>,
     /// Dry-run mode computes actions without writing registry/state changes.
     pub dry_run: bool,
+    /// Optional receipts file; defaults to `token_burn_receipts.jsonl` beside the outbox.
+    pub receipts_path: Option<String>,
+    /// Executor identity signing one receipt per executed intent.
+    pub executor: Option<SigningKey>,
+    /// Base64 keys allowed to sign intents; when non-empty, unsigned intents are rejected.
+    pub trusted_signers: Vec<String>,
 }
 
 /// Summary returned after executing burn intents.
@@ -31,8 +51,14 @@ pub struct ExecuteBurnSummary {
     pub native_executed: usize,
     /// Number of non-native intents left untouched.
     pub unsupported_mode: usize,
+    /// Number of intents left unprocessed because their signature was missing or invalid.
+    pub rejected: usize,
+    /// Number of executor receipts appended.
+    pub receipts: usize,
     /// State file path used for idempotency.
     pub state_path: String,
+    /// Receipts file path, when an executor key was supplied.
+    pub receipts_path: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -43,6 +69,112 @@ struct BurnIntent {
     pubkey_b64: Option<String>,
 }
 
+/// Signed acknowledgment that an executor processed one burn intent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BurnReceipt {
+    /// Schema identifier (`mfenx.powerhouse.token-burn-receipt.v1`).
+    pub schema: String,
+    /// Identifier of the acknowledged intent (hash of its outbox line).
+    pub intent_id: String,
+    /// Account named by the intent.
+    pub pubkey_b64: String,
+    /// `slashed` for executed native burns, `unsupported` for other modes.
+    pub outcome: String,
+    /// Millisecond timestamp of execution.
+    pub executed_at_ms: u64,
+    /// Base64 public key of the executing identity.
+    pub executor_b64: String,
+    /// Base64 ed25519 signature over the receipt with this field empty.
+    pub signature_b64: String,
+}
+
+impl BurnReceipt {
+    fn signing_payload(&self) -> Vec<u8> {
+        let unsigned = Self {
+            signature_b64: String::new(),
+            ..self.clone()
+        };
+        serde_json::to_vec(&unsigned).expect("receipt encodes")
+    }
+
+    fn issue(
+        intent_id: String,
+        pubkey_b64: String,
+        outcome: &str,
+        executed_at_ms: u64,
+        signing: &SigningKey,
+    ) -> Self {
+        let mut receipt = Self {
+            schema: BURN_RECEIPT_SCHEMA.to_string(),
+            intent_id,
+            pubkey_b64,
+            outcome: outcome.to_string(),
+            executed_at_ms,
+            executor_b64: encode_public_key_base64(&signing.verifying_key()),
+            signature_b64: String::new(),
+        };
+        receipt.signature_b64 =
+            encode_signature_base64(&sign_payload(signing, &receipt.signing_payload()));
+        receipt
+    }
+
+    /// Checks the schema and the executor signature.
+    pub fn verify(&self) -> Result<(), String> {
+        if self.schema != BURN_RECEIPT_SCHEMA {
+            return Err(format!("unexpected burn receipt schema: {}", self.schema));
+        }
+        verify_signature_base64(
+            &self.executor_b64,
+            &self.signing_payload(),
+            &self.signature_b64,
+        )
+        .map_err(|err| format!("receipt signature invalid: {err}"))
+    }
+}
+
+fn intent_signing_payload(record: &Value) -> Result<Vec<u8>, String> {
+    let fields = record
+        .as_object()
+        .ok_or_else(|| "burn intent must be a JSON object".to_string())?
+        .iter()
+        .filter(|(key, _)| !SIGNATURE_FIELDS.contains(&key.as_str()))
+        .collect::<BTreeMap<_, _>>();
+    serde_json::to_vec(&fields).map_err(|err| format!("failed to encode burn intent: {err}"))
+}
+
+/// Adds `signer_b64` and `signature_b64` to an outbox intent, signing every
+/// other field in key order.
+pub fn sign_burn_intent(record: &mut Value, signing: &SigningKey) -> Result<(), String> {
+    let payload = intent_signing_payload(record)?;
+    let fields = record
+        .as_object_mut()
+        .ok_or_else(|| "burn intent must be a JSON object".to_string())?;
+    fields.insert(
+        "signer_b64".into(),
+        encode_public_key_base64(&signing.verifying_key()).into(),
+    );
+    fields.insert(
+        "signature_b64".into(),
+        encode_signature_base64(&sign_payload(signing, &payload)).into(),
+    );
+    Ok(())
+}
+
+/// Returns the intent signer, `None` for an unsigned intent, or an error when
+/// the signature does not verify.
+fn intent_signer(record: &Value) -> Result<Option<String>, String> {
+    let field = |name: &str| record.get(name).and_then(Value::as_str);
+    match (field("signer_b64"), field("signature_b64")) {
+        (None, None) => Ok(None),
+        (Some(signer), Some(signature)) => {
+            verify_signature_base64(signer, &intent_signing_payload(record)?, signature)
+                .map_err(|err| format!("intent signature invalid: {err}"))?;
+            Ok(Some(signer.to_string()))
+        }
+        _ => Err("intent carries an incomplete signature".into()),
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 struct ExecuteState {
     schema: String,
@@ -69,6 +201,41 @@ fn resolve_state_path(outbox_path: &Path, explicit: Option<&str>) -> PathBuf {
     outbox_path.with_file_name("token_burn_exec_state.json")
 }
 
+pub(crate) fn resolve_receipts_path(outbox_path: &Path, explicit: Option<&str>) -> PathBuf {
+    if let Some(path) = explicit {
+        return PathBuf::from(path);
+    }
+    outbox_path.with_file_name("token_burn_receipts.jsonl")
+}
+
+pub(crate) fn read_lines(path: &Path, label: &str) -> Result<String, String> {
+    if !path.exists() {
+        return Ok(String::new());
+    }
+    std::fs::read_to_string(path)
+        .map_err(|err| format!("failed to read {label} {}: {err}", path.display()))
+}
+
+fn append_receipts(path: &Path, receipts: &[BurnReceipt]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|err| format!("failed to create {}: {err}", parent.display()))?;
+    }
+    let mut lines = String::new();
+    for receipt in receipts {
+        let line = serde_json::to_string(receipt)
+            .map_err(|err| format!("failed to encode burn receipt: {err}"))?;
+        lines.push_str(&line);
+        lines.push('\n');
+    }
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(lines.as_bytes()))
+        .map_err(|err| format!("failed to append receipts {}: {err}", path.display()))
+}
+
 fn intent_id(raw_line: &str) -> String {
     let mut hasher = Blake2b256::new();
     hasher.update(b"mfenx-migration-burn-intent-id-v1");
@@ -107,7 +274,10 @@ fn save_state(path: &Path, state: &ExecuteState) -> Result<(), String> {
 
 /// Execute native burn intents by slashing corresponding stake registry accounts.
 ///
-/// Intents are consumed idempotently using a persistent state file.
+/// Intents are consumed idempotently using a persistent state file. Signed
+/// intents must verify, and with `trusted_signers` set every intent must be
+/// signed by one of them; rejected intents stay unprocessed. With an
+/// `executor` key, each processed intent gets a signed [`BurnReceipt`].
 pub fn run_execute_burn_intents(
     registry_path: &str,
     outbox_path: &str,
@@ -116,13 +286,9 @@ pub fn run_execute_burn_intents(
     let registry_path = Path::new(registry_path);
     let outbox_path = Path::new(outbox_path);
     let state_path = resolve_state_path(outbox_path, opts.state_path.as_deref());
+    let receipts_path = resolve_receipts_path(outbox_path, opts.receipts_path.as_deref());
 
-    let outbox = if outbox_path.exists() {
-        std::fs::read_to_string(outbox_path)
-            .map_err(|err| format!("failed to read outbox {}: {err}", outbox_path.display()))?
-    } else {
-        String::new()
-    };
+    let outbox = read_lines(outbox_path, "outbox")?;
 
     let mut state = load_state(&state_path)?;
     let mut seen = state
@@ -138,6 +304,9 @@ pub fn run_execute_burn_intents(
     let mut skipped = 0usize;
     let mut native_executed = 0usize;
     let mut unsupported_mode = 0usize;
+    let mut rejected = 0usize;
+    let mut receipts = Vec::new();
+    let executed_at_ms = now_millis();
 
     for raw in outbox.lines() {
         let line = raw.trim();
@@ -146,37 +315,65 @@ pub fn run_execute_burn_intents(
         }
 
         let id = intent_id(line);
-        if !seen.insert(id) {
+        if seen.contains(&id) {
             skipped += 1;
             continue;
         }
 
-        let intent: BurnIntent = serde_json::from_str(line)
+        let record: Value = serde_json::from_str(line)
+            .map_err(|err| format!("invalid burn intent record: {err}"))?;
+        let intent: BurnIntent = serde_json::from_value(record.clone())
             .map_err(|err| format!("invalid burn intent record: {err}"))?;
 
-        if !intent.schema.is_empty() && intent.schema != "mfenx.powerhouse.token-burn-intent.v1" {
+        if !intent.schema.is_empty() && intent.schema != BURN_INTENT_SCHEMA {
             return Err(format!("unexpected burn intent schema: {}", intent.schema));
         }
 
-        let mode = intent.token_contract.unwrap_or_default();
-        if !token_mode_is_native(&mode) {
-            unsupported_mode += 1;
-            processed += 1;
+        let trusted = match intent_signer(&record) {
+            Ok(Some(signer)) => {
+                opts.trusted_signers.is_empty() || opts.trusted_signers.contains(&signer)
+            }
+            Ok(None) => opts.trusted_signers.is_empty(),
+            Err(_) => false,
+        };
+        if !trusted {
+            rejected += 1;
             continue;
         }
+        seen.insert(id.clone());
 
-        let pk = intent
-            .pubkey_b64
-            .ok_or_else(|| "burn intent missing pubkey_b64".to_string())?;
-        registry.slash(&pk);
-        native_executed += 1;
+        let pk = intent.pubkey_b64.unwrap_or_default();
+        let mode = intent.token_contract.unwrap_or_default();
+        let outcome = if !token_mode_is_native(&mode) {
+            unsupported_mode += 1;
+            "unsupported"
+        } else {
+            if pk.is_empty() {
+                return Err("burn intent missing pubkey_b64".to_string());
+            }
+            registry.slash(&pk);
+            native_executed += 1;
+            "slashed"
+        };
         processed += 1;
+        if let Some(executor) = &opts.executor {
+            receipts.push(BurnReceipt::issue(
+                id,
+                pk,
+                outcome,
+                executed_at_ms,
+                executor,
+            ));
+        }
     }
 
     if !opts.dry_run {
         registry
             .save(registry_path)
             .map_err(|err| format!("failed to save registry {}: {err}", registry_path.display()))?;
+        if !receipts.is_empty() {
+            append_receipts(&receipts_path, &receipts)?;
+        }
         let mut processed_ids = seen.into_iter().collect::<Vec<_>>();
         processed_ids.sort();
         state.schema = EXEC_STATE_SCHEMA.to_string();
@@ -190,14 +387,141 @@ pub fn run_execute_burn_intents(
         skipped,
         native_executed,
         unsupported_mode,
+        rejected,
+        receipts: receipts.len(),
         state_path: state_path.display().to_string(),
+        receipts_path: opts
+            .executor
+            .as_ref()
+            .map(|_| receipts_path.display().to_string()),
     })
 }
 
+/// Options for reconciling burn intents against executor receipts.
+#[derive(Debug, Clone, Default)]
+pub struct ReconcileBurnOptions {
+    /// Optional receipts file; defaults to `token_burn_receipts.jsonl` beside the outbox.
+    pub receipts_path: Option<String>,
+    /// Base64 keys allowed to sign intents; when non-empty, unsigned intents are invalid.
+    pub trusted_signers: Vec<String>,
+    /// Base64 keys allowed to sign receipts; when empty any valid signature counts.
+    pub trusted_executors: Vec<String>,
+}
+
+/// Outcome of matching burn intents to executor receipts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BurnReconciliation {
+    /// Distinct intents in the outbox.
+    pub intents: usize,
+    /// Intents acknowledged by exactly one valid receipt.
+    pub matched: usize,
+    /// Intent ids with no valid receipt.
+    pub unexecuted: Vec<String>,
+    /// Intent ids acknowledged by more than one valid receipt.
+    pub double_executed: Vec<String>,
+    /// Intent ids whose signature is missing, untrusted or invalid.
+    pub invalid_intents: Vec<String>,
+    /// Receipt lines (1-based) that fail to parse, verify, or match their intent's account.
+    pub invalid_receipts: Vec<usize>,
+    /// Intent ids named by valid receipts but absent from the outbox.
+    pub orphan_receipts: Vec<String>,
+}
+
+impl BurnReconciliation {
+    /// True when every intent was executed exactly once and nothing else was found.
+    pub fn is_clean(&self) -> bool {
+        self.unexecuted.is_empty()
+            && self.double_executed.is_empty()
+            && self.invalid_intents.is_empty()
+            && self.invalid_receipts.is_empty()
+            && self.orphan_receipts.is_empty()
+    }
+}
+
+/// Matches every outbox intent to the executor receipts and flags intents
+/// that were never executed, executed more than once, or improperly signed.
+pub fn run_reconcile_burns(
+    outbox_path: &str,
+    opts: &ReconcileBurnOptions,
+) -> Result<BurnReconciliation, String> {
+    let outbox_path = Path::new(outbox_path);
+    let receipts_path = resolve_receipts_path(outbox_path, opts.receipts_path.as_deref());
+    let mut report = BurnReconciliation::default();
+
+    let mut intents: BTreeMap<String, String> = BTreeMap::new();
+    for raw in read_lines(outbox_path, "outbox")?.lines() {
+        let line = raw.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let id = intent_id(line);
+        if intents.contains_key(&id) {
+            continue;
+        }
+        let record: Value = serde_json::from_str(line)
+            .map_err(|err| format!("invalid burn intent record: {err}"))?;
+        let trusted = match intent_signer(&record) {
+            Ok(Some(signer)) => {
+                opts.trusted_signers.is_empty() || opts.trusted_signers.contains(&signer)
+            }
+            Ok(None) => opts.trusted_signers.is_empty(),
+            Err(_) => false,
+        };
+        if !trusted {
+            report.invalid_intents.push(id.clone());
+        }
+        let pk = record
+            .get("pubkey_b64")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        intents.insert(id, pk);
+    }
+    report.intents = intents.len();
+
+    let mut acknowledged: HashMap<String, usize> = HashMap::new();
+    for (number, raw) in read_lines(&receipts_path, "receipts")?.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let valid = serde_json::from_str::<BurnReceipt>(line)
+            .ok()
+            .filter(|receipt| receipt.verify().is_ok())
+            .filter(|receipt| {
+                opts.trusted_executors.is_empty()
+                    || opts.trusted_executors.contains(&receipt.executor_b64)
+            });
+        let Some(receipt) = valid else {
+            report.invalid_receipts.push(number + 1);
+            continue;
+        };
+        match intents.get(&receipt.intent_id) {
+            Some(pk) if *pk != receipt.pubkey_b64 => report.invalid_receipts.push(number + 1),
+            Some(_) => *acknowledged.entry(receipt.intent_id).or_default() += 1,
+            None => report.orphan_receipts.push(receipt.intent_id),
+        }
+    }
+
+    for id in intents.keys() {
+        match acknowledged.get(id).copied().unwrap_or(0) {
+            0 => report.unexecuted.push(id.clone()),
+            1 => report.matched += 1,
+            _ => report.double_executed.push(id.clone()),
+        }
+    }
+    Ok(report)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{run_execute_burn_intents, ExecuteBurnOptions};
-    use crate::net::StakeRegistry;
+    use super::{
+        run_execute_burn_intents, run_reconcile_burns, sign_burn_intent, ExecuteBurnOptions,
+        ReconcileBurnOptions,
+    };
+    use crate::net::{
+        encode_public_key_base64, load_or_derive_keypair, Ed25519KeySource, StakeRegistry,
+    };
     use serde_json::json;
     use std::fs;
 
@@ -235,7 +559,7 @@ mod tests {
 
         let opts = ExecuteBurnOptions {
             state_path: Some(state.display().to_string()),
-            dry_run: false,
+            ..Default::default()
         };
 
         let first =
@@ -256,4 +580,80 @@ mod tests {
         let _ = fs::remove_file(outbox);
         let _ = fs::remove_file(state);
     }
+
+    #[test]
+    fn signed_intents_reconcile_against_executor_receipts() {
+        let registry = temp_path("burn_recon_registry.json");
+        let outbox = temp_path("burn_recon_outbox.jsonl");
+        let state = temp_path("burn_recon_state.json");
+        let receipts = temp_path("burn_recon_receipts.jsonl");
+        let key =
+            |seed: &str| load_or_derive_keypair(&Ed25519KeySource::Seed(seed.to_string())).unwrap();
+        let (node, executor, stranger) = (key("burn-node"), key("burn-exec"), key("burn-other"));
+        fs::write(
+            &registry,
+            serde_json::to_vec(&json!({
+                "accounts": {
+                    "pk1": {"balance": 1, "stake": 5, "slashed": false},
+                    "pk2": {"balance": 1, "stake": 6, "slashed": false}
+                }
+            }))
+            .unwrap(),
+        )
+        .unwrap();
+        let intent = |pk: &str, signer: &crate::net::KeyMaterial| {
+            let mut record = json!({
+                "schema": "mfenx.powerhouse.token-burn-intent.v1",
+                "token_contract": "native://julian",
+                "pubkey_b64": pk,
+                "reason": "test"
+            });
+            sign_burn_intent(&mut record, &signer.signing).unwrap();
+            record.to_string()
+        };
+        let signed = intent("pk1", &node);
+        let forged = intent("pk2", &stranger);
+        fs::write(&outbox, format!("{signed}\n{forged}\n")).unwrap();
+
+        let trusted = vec![encode_public_key_base64(&node.verifying)];
+        let opts = ExecuteBurnOptions {
+            state_path: Some(state.display().to_string()),
+            receipts_path: Some(receipts.display().to_string()),
+            executor: Some(executor.signing.clone()),
+            trusted_signers: trusted.clone(),
+            ..Default::default()
+        };
+        let outbox_path = outbox.to_str().unwrap();
+        let summary =
+            run_execute_burn_intents(registry.to_str().unwrap(), outbox_path, &opts).unwrap();
+        assert_eq!((summary.native_executed, summary.rejected), (1, 1));
+        assert!(
+            !StakeRegistry::load(&registry)
+                .unwrap()
+                .account("pk2")
+                .unwrap()
+                .slashed
+        );
+
+        let recon = ReconcileBurnOptions {
+            receipts_path: Some(receipts.display().to_string()),
+            trusted_signers: trusted,
+            trusted_executors: vec![encode_public_key_base64(&executor.verifying)],
+        };
+        let report = run_reconcile_burns(outbox_path, &recon).unwrap();
+        assert_eq!((report.intents, report.matched), (2, 1));
+        assert_eq!(report.unexecuted, report.invalid_intents);
+        assert!(!report.is_clean());
+
+        let receipt_line = fs::read_to_string(&receipts).unwrap();
+        fs::write(&receipts, receipt_line.repeat(2)).unwrap();
+        fs::write(&outbox, format!("{signed}\n")).unwrap();
+        let report = run_reconcile_burns(outbox_path, &recon).unwrap();
+        assert_eq!(report.double_executed.len(), 1);
+        assert_eq!(report.matched, 0);
+
+        for path in [registry, outbox, state, receipts] {
+            let _ = fs::remove_file(path);
+        }
+    }
 }
@@ -1,12 +1,13 @@
 #![cfg(feature = "net")]
 
 use crate::commands::stake_snapshot::{StakeSnapshotArtifact, StakeSnapshotEntry};
+use crate::net::address_index::{derive_evm_address, AddressIndex, ADDRESS_DERIVATION};
+use crate::{Clock, SystemClock};
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use blake2::digest::{consts::U32, Digest as BlakeDigest};
 use serde::{Deserialize, Serialize};
 use sha3::Keccak256;
-use std::path::Path;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::path::{Path, PathBuf};
 
 /// Options for building a deterministic migration claim manifest.
 #[derive(Debug, Clone)]
@@ -25,6 +26,10 @@ pub struct BuildClaimsOptions {
     pub snapshot_height_override: Option<u64>,
     /// Claim mode: `native` (default) or `erc20`.
     pub claim_mode: String,
+    /// Optional address index; erc20 accounts must then be present in it.
+    pub address_index: Option<PathBuf>,
+    /// Registry epoch from which unapplied claims may no longer be applied.
+    pub expires_at_epoch: Option<u64>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -131,10 +136,14 @@ struct MigrationClaimsArtifact {
     include_slashed: bool,
     conversion_ratio: u64,
     token_contract: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    address_derivation: Option<String>,
     claim_id_format: String,
     leaf_format: String,
     pair_hash_format: String,
     merkle_root: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expires_at_epoch: Option<u64>,
     claim_count: usize,
     excluded: ExcludedCounts,
     claims: Vec<MigrationClaimEntry>,
@@ -174,13 +183,6 @@ type HashPairFn = fn([u8; 32], [u8; 32]) -> [u8; 32];
 
 type Blake2b256 = blake2::Blake2b<U32>;
 
-fn now_millis() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as u64
-}
-
 fn u256_from_u64(value: u64) -> [u8; 32] {
     let mut out = [0u8; 32];
     out[24..].copy_from_slice(&value.to_be_bytes());
@@ -199,14 +201,6 @@ fn blake2b256(bytes: &[u8]) -> [u8; 32] {
     hasher.finalize().into()
 }
 
-fn derive_account_from_pubkey(pubkey_bytes: &[u8]) -> String {
-    let mut payload = Vec::with_capacity(32 + pubkey_bytes.len());
-    payload.extend_from_slice(b"mfenx-migration-address-v1");
-    payload.extend_from_slice(pubkey_bytes);
-    let digest = blake2b256(&payload);
-    format!("0x{}", hex::encode(&digest[12..]))
-}
-
 fn derive_claim_id_erc20(pubkey_bytes: &[u8], snapshot_height: u64, salt: &str) -> [u8; 32] {
     let mut data = Vec::with_capacity(salt.len() + 32 + pubkey_bytes.len());
     data.extend_from_slice(salt.as_bytes());
@@ -354,6 +348,18 @@ pub fn run_build_claims(
     snapshot_path: &str,
     output: &str,
     opts: &BuildClaimsOptions,
+) -> Result<String, String> {
+    run_build_claims_with_clock(snapshot_path, output, opts, &SystemClock)
+}
+
+/// [`run_build_claims`] with an explicit timestamp source.
+///
+/// The clock sees the claims root and the snapshot bytes.
+pub fn run_build_claims_with_clock(
+    snapshot_path: &str,
+    output: &str,
+    opts: &BuildClaimsOptions,
+    clock: &dyn Clock,
 ) -> Result<String, String> {
     let source = Path::new(snapshot_path);
     let bytes = std::fs::read(source)
@@ -367,6 +373,13 @@ pub fn run_build_claims(
     let snapshot_height = opts
         .snapshot_height_override
         .unwrap_or(snapshot.snapshot_height);
+    let address_index = match &opts.address_index {
+        Some(path) => Some(
+            AddressIndex::load(path)?
+                .ok_or_else(|| format!("address index {} does not exist", path.display()))?,
+        ),
+        None => None,
+    };
 
     let mut entries = snapshot.entries.clone();
     entries.sort_by(|a, b| a.pubkey_b64.cmp(&b.pubkey_b64));
@@ -400,7 +413,15 @@ pub fn run_build_claims(
                 (entry.pubkey_b64.clone(), claim_id, leaf)
             }
             ClaimMode::Erc20 => {
-                let account = derive_account_from_pubkey(&pubkey_bytes);
+                let account = match &address_index {
+                    Some(index) => index
+                        .address_of(&entry.pubkey_b64)
+                        .map(str::to_string)
+                        .ok_or_else(|| {
+                            format!("{} is missing from the address index", entry.pubkey_b64)
+                        })?,
+                    None => derive_evm_address(&pubkey_bytes),
+                };
                 let claim_id =
                     derive_claim_id_erc20(&pubkey_bytes, snapshot_height, &opts.claim_id_salt);
                 let leaf = encode_leaf_erc20(snapshot_height, claim_id, &account, raw_amount)?;
@@ -461,7 +482,7 @@ pub fn run_build_claims(
 
     let artifact = MigrationClaimsArtifact {
         schema: "mfenx.powerhouse.migration-claims.v2".to_string(),
-        generated_at_ms: now_millis(),
+        generated_at_ms: clock.timestamp_ms(&[&root, &bytes]),
         source_snapshot: snapshot_path.to_string(),
         snapshot_height,
         claim_mode: claim_mode.as_str().to_string(),
@@ -469,10 +490,13 @@ pub fn run_build_claims(
         include_slashed: opts.include_slashed,
         conversion_ratio: opts.conversion_ratio,
         token_contract: opts.token_contract.clone(),
+        address_derivation: matches!(claim_mode, ClaimMode::Erc20)
+            .then(|| ADDRESS_DERIVATION.to_string()),
         claim_id_format: claim_mode.claim_id_format().to_string(),
         leaf_format: claim_mode.leaf_format().to_string(),
         pair_hash_format: claim_mode.pair_hash_format().to_string(),
         merkle_root: format!("0x{}", hex::encode(root)),
+        expires_at_epoch: opts.expires_at_epoch,
         claim_count: claims.len(),
         excluded,
         claims,
@@ -493,7 +517,7 @@ pub fn run_build_claims(
 
 #[cfg(test)]
 mod tests {
-    use super::{run_build_claims, BuildClaimsOptions};
+    use super::{run_build_claims, AddressIndex, BuildClaimsOptions, ADDRESS_DERIVATION};
     use crate::commands::stake_snapshot::run_snapshot;
     use serde_json::json;
     use std::fs;
@@ -544,6 +568,8 @@ mod tests {
             token_contract: None,
             snapshot_height_override: None,
             claim_mode: "native".to_string(),
+            address_index: None,
+            expires_at_epoch: None,
         };
 
         let root_a =
@@ -597,6 +623,8 @@ mod tests {
             token_contract: Some("0x0000000000000000000000000000000000000001".to_string()),
             snapshot_height_override: None,
             claim_mode: "erc20".to_string(),
+            address_index: None,
+            expires_at_epoch: None,
         };
 
         let root = run_build_claims(snap.to_str().unwrap(), out.to_str().unwrap(), &opts).unwrap();
@@ -607,6 +635,32 @@ mod tests {
         let account = artifact["claims"][0]["account"].as_str().unwrap_or("");
         assert!(account.starts_with("0x"));
         assert_eq!(account.len(), 42);
+        assert_eq!(artifact["address_derivation"], ADDRESS_DERIVATION);
+
+        let index_path = temp_path("claims_address_index_erc20.json");
+        let registry = crate::net::StakeRegistry::load(&reg).unwrap();
+        let index = AddressIndex::build(&registry);
+        index.save(&index_path).unwrap();
+        let indexed = BuildClaimsOptions {
+            address_index: Some(index_path.clone()),
+            ..opts
+        };
+        match index.address_of("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=") {
+            Some(address) => {
+                run_build_claims(snap.to_str().unwrap(), out.to_str().unwrap(), &indexed).unwrap();
+                let artifact: serde_json::Value =
+                    serde_json::from_slice(&fs::read(&out).unwrap()).unwrap();
+                assert_eq!(artifact["claims"][0]["account"], address);
+            }
+            None => {
+                assert!(
+                    run_build_claims(snap.to_str().unwrap(), out.to_str().unwrap(), &indexed)
+                        .unwrap_err()
+                        .contains("missing from the address index")
+                )
+            }
+        }
+        let _ = fs::remove_file(index_path);
 
         let _ = fs::remove_file(reg);
         let _ = fs::remove_file(snap);
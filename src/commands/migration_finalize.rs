@@ -1,9 +1,230 @@
 #![cfg(feature = "net")]
 
+//! Resumable migration finalize workflow.
+//!
+//! Finalize runs four steps in order: snapshot, claims, apply-claims and
+//! proposal. Progress is persisted after every transition in
+//! `migration_finalize_state.json` inside the output directory, together with
+//! a digest of each step's output files. A rerun with the same options skips
+//! completed steps whose outputs are unchanged and resumes at the first step
+//! that is pending or failed. Each
+//! [`FinalizeStep`](crate::commands::migration_finalize::FinalizeStep) also
+//! carries the manual rollback procedure for its effects, printed by
+//! `julian migration finalize-status`.
+
+use crate::artifact_clock;
 use crate::commands::migration_apply_claims::{run_apply_claims, ApplyClaimsOptions};
-use crate::commands::migration_claims::{run_build_claims, BuildClaimsOptions};
-use crate::commands::migration_proposal::{run_propose_migration, ProposeMigrationOptions};
-use crate::commands::stake_snapshot::run_snapshot;
+use crate::commands::migration_claims::{run_build_claims_with_clock, BuildClaimsOptions};
+use crate::commands::migration_proposal::{
+    run_propose_migration_with_clock, ProposeMigrationOptions,
+};
+use crate::commands::stake_snapshot::run_snapshot_with_clock;
+use crate::net::storage::write_json_atomic;
+use crate::net::StakeRegistry;
+use blake2::digest::{consts::U32, Digest};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+type Blake2b256 = blake2::Blake2b<U32>;
+
+/// Schema identifier of the persisted finalize progress file.
+pub const FINALIZE_STATE_SCHEMA: &str = "mfenx.powerhouse.migration-finalize-state.v1";
+
+/// File name of the finalize progress file inside the output directory.
+pub const FINALIZE_STATE_FILE: &str = "migration_finalize_state.json";
+
+/// One step of the finalize workflow, in execution order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FinalizeStep {
+    /// Write the stake snapshot artifact.
+    Snapshot,
+    /// Build the claims manifest from the snapshot.
+    Claims,
+    /// Apply claims to the stake registry.
+    ApplyClaims,
+    /// Write the anchored migration proposal.
+    Proposal,
+}
+
+impl FinalizeStep {
+    /// Every step in execution order.
+    pub const ALL: [Self; 4] = [
+        Self::Snapshot,
+        Self::Claims,
+        Self::ApplyClaims,
+        Self::Proposal,
+    ];
+
+    /// Stable step name used in the state file and on the command line.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Snapshot => "snapshot",
+            Self::Claims => "claims",
+            Self::ApplyClaims => "apply_claims",
+            Self::Proposal => "proposal",
+        }
+    }
+
+    /// Parses a step name; `apply-claims` is accepted for `apply_claims`.
+    pub fn parse(name: &str) -> Result<Self, String> {
+        Self::ALL
+            .into_iter()
+            .find(|step| step.as_str() == name.replace('-', "_"))
+            .ok_or_else(|| {
+                format!("unknown finalize step '{name}' (snapshot|claims|apply_claims|proposal)")
+            })
+    }
+
+    /// Manual procedure that undoes this step's effects.
+    pub fn rollback(self) -> &'static str {
+        match self {
+            Self::Snapshot => {
+                "delete migration_snapshot.json from the output directory; the registry is untouched"
+            }
+            Self::Claims => {
+                "delete migration_claims.json from the output directory; the registry is untouched"
+            }
+            Self::ApplyClaims => {
+                "stop every node using the registry, restore it from registry_before_apply.json \
+                 in the output directory (the registry before the first apply attempt; for SQLite \
+                 run `julian storage migrate` into a fresh database), then delete the apply-state \
+                 file"
+            }
+            Self::Proposal => {
+                "delete migration_anchor.json; if it was already broadcast, submit a governance \
+                 proposal cancelling the migration"
+            }
+        }
+    }
+}
+
+impl fmt::Display for FinalizeStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Progress of one finalize step.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StepStatus {
+    /// Not started yet.
+    #[default]
+    Pending,
+    /// Started; the run stopped before the step finished.
+    Running,
+    /// Finished; outputs are recorded.
+    Done,
+    /// Returned an error; the step reruns on the next invocation.
+    Failed,
+}
+
+impl StepStatus {
+    /// Stable status name used in the state file.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Running => "running",
+            Self::Done => "done",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// Persisted record of one finalize step.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FinalizeStepRecord {
+    /// Step this record describes.
+    pub step: FinalizeStep,
+    /// Current progress.
+    pub status: StepStatus,
+    /// Named outputs (roots, counts, paths).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub outputs: BTreeMap<String, String>,
+    /// BLAKE2b-256 digests of the files the step wrote, keyed by path.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub files: BTreeMap<String, String>,
+    /// Millisecond timestamp of the last transition.
+    pub updated_at_ms: u64,
+    /// Error returned by the last failed attempt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Persisted progress of a finalize run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FinalizeState {
+    /// Schema identifier (`mfenx.powerhouse.migration-finalize-state.v1`).
+    pub schema: String,
+    /// Digest of the options the run was started with.
+    pub options_digest: String,
+    /// One record per step, in execution order.
+    pub steps: Vec<FinalizeStepRecord>,
+}
+
+impl FinalizeState {
+    fn new(options_digest: String) -> Self {
+        Self {
+            schema: FINALIZE_STATE_SCHEMA.to_string(),
+            options_digest,
+            steps: FinalizeStep::ALL
+                .into_iter()
+                .map(|step| FinalizeStepRecord {
+                    step,
+                    status: StepStatus::Pending,
+                    outputs: BTreeMap::new(),
+                    files: BTreeMap::new(),
+                    updated_at_ms: 0,
+                    error: None,
+                })
+                .collect(),
+        }
+    }
+
+    /// Loads the progress file of `output_dir`, if one exists.
+    pub fn load(output_dir: &Path) -> Result<Option<Self>, String> {
+        let path = output_dir.join(FINALIZE_STATE_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let bytes = std::fs::read(&path)
+            .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+        let state: Self = serde_json::from_slice(&bytes)
+            .map_err(|err| format!("invalid finalize state {}: {err}", path.display()))?;
+        if state.schema != FINALIZE_STATE_SCHEMA {
+            return Err(format!(
+                "unexpected finalize state schema: {}",
+                state.schema
+            ));
+        }
+        Ok(Some(state))
+    }
+
+    fn save(&self, output_dir: &Path) -> Result<(), String> {
+        write_json_atomic(&output_dir.join(FINALIZE_STATE_FILE), self)
+    }
+
+    /// Record of `step`.
+    pub fn record(&self, step: FinalizeStep) -> &FinalizeStepRecord {
+        &self.steps[step as usize]
+    }
+
+    fn record_mut(&mut self, step: FinalizeStep) -> &mut FinalizeStepRecord {
+        &mut self.steps[step as usize]
+    }
+
+    fn output(&self, step: FinalizeStep, key: &str) -> String {
+        self.record(step)
+            .outputs
+            .get(key)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
 
 /// Options for running a deterministic end-to-end migration finalize flow.
 #[derive(Debug, Clone)]
@@ -38,6 +259,10 @@ pub struct FinalizeMigrationOptions {
     pub allow_unfrozen: bool,
     /// Permit overwriting existing artifacts.
     pub force: bool,
+    /// Derive artifact timestamps from their inputs instead of the wall clock.
+    pub deterministic: bool,
+    /// Stop after this step completes; later steps stay pending.
+    pub until: Option<FinalizeStep>,
 }
 
 /// Summary produced by finalize migration workflow.
@@ -59,6 +284,62 @@ pub struct FinalizeMigrationSummary {
     pub apply_state_path: String,
     /// Migration proposal artifact path.
     pub proposal_path: String,
+    /// Steps skipped because an earlier run completed them.
+    pub resumed: Vec<FinalizeStep>,
+    /// Steps executed by this run.
+    pub executed: Vec<FinalizeStep>,
+}
+
+/// What a finalize run would do with one step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StepPlan {
+    /// Already completed with unchanged outputs; would be skipped.
+    Skip,
+    /// Would run.
+    Run,
+    /// Beyond `until`; would stay pending.
+    Stop,
+    /// A precondition fails; the run would stop here.
+    Blocked(String),
+}
+
+struct FinalizePaths {
+    out_dir: PathBuf,
+    snapshot: PathBuf,
+    claims: PathBuf,
+    proposal: PathBuf,
+    apply_state: PathBuf,
+    registry_backup: PathBuf,
+}
+
+impl FinalizePaths {
+    fn new(opts: &FinalizeMigrationOptions) -> Self {
+        let out_dir = PathBuf::from(&opts.output_dir);
+        Self {
+            snapshot: out_dir.join("migration_snapshot.json"),
+            claims: out_dir.join("migration_claims.json"),
+            proposal: out_dir.join("migration_anchor.json"),
+            apply_state: opts
+                .apply_state_path
+                .as_ref()
+                .map(PathBuf::from)
+                .unwrap_or_else(|| out_dir.join("migration_apply_state.json")),
+            registry_backup: out_dir.join("registry_before_apply.json"),
+            out_dir,
+        }
+    }
+
+    /// Artifacts a fresh run must not overwrite without `--force`.
+    fn artifacts(&self) -> [&Path; 3] {
+        [&self.snapshot, &self.claims, &self.proposal]
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
 }
 
 fn ensure_writable(path: &std::path::Path, force: bool) -> Result<(), String> {
@@ -71,93 +352,381 @@ fn ensure_writable(path: &std::path::Path, force: bool) -> Result<(), String> {
     Ok(())
 }
 
+fn conversion_ratio(opts: &FinalizeMigrationOptions) -> u64 {
+    if opts.conversion_ratio == 0 {
+        1
+    } else {
+        opts.conversion_ratio
+    }
+}
+
+/// Digest of every option that shapes the artifacts, so a resume with
+/// different inputs is refused.
+fn options_digest(opts: &FinalizeMigrationOptions) -> String {
+    let inputs = serde_json::json!({
+        "registry_path": opts.registry_path,
+        "snapshot_height": opts.snapshot_height,
+        "log_dir": opts.log_dir,
+        "token_contract": opts.token_contract,
+        "conversion_ratio": conversion_ratio(opts),
+        "treasury_mint": opts.treasury_mint,
+        "amount_source": opts.amount_source,
+        "include_slashed": opts.include_slashed,
+        "claim_id_salt": opts.claim_id_salt,
+        "node_id": opts.node_id,
+        "quorum": opts.quorum,
+        "apply_state_path": opts.apply_state_path,
+        "deterministic": opts.deterministic,
+    });
+    let mut hasher = Blake2b256::new();
+    hasher.update(b"mfenx-migration-finalize-options-v1");
+    hasher.update(inputs.to_string().as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+fn file_digest(path: &Path) -> Result<String, String> {
+    let bytes =
+        std::fs::read(path).map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+    Ok(hex::encode(Blake2b256::digest(bytes)))
+}
+
+fn outputs_intact(record: &FinalizeStepRecord) -> Result<(), String> {
+    for (path, digest) in &record.files {
+        if file_digest(Path::new(path))? != *digest {
+            return Err(format!(
+                "{path} changed since step {} completed; rerun with --force",
+                record.step
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Checks the inputs `step` needs beyond the outputs of earlier steps.
+fn precondition(step: FinalizeStep, opts: &FinalizeMigrationOptions) -> Result<(), String> {
+    match step {
+        FinalizeStep::Snapshot if !Path::new(&opts.registry_path).exists() => {
+            Err(format!("registry {} does not exist", opts.registry_path))
+        }
+        FinalizeStep::ApplyClaims
+            if !opts.allow_unfrozen && !crate::net::migration_mode_frozen() =>
+        {
+            Err(
+                "migration freeze is not active (set PH_MIGRATION_MODE=freeze or use --allow-unfrozen)"
+                    .to_string(),
+            )
+        }
+        FinalizeStep::Proposal if !Path::new(&opts.log_dir).is_dir() => {
+            Err(format!("log dir {} does not exist", opts.log_dir))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Loads the progress file for `opts`, starting a new one when none exists or
+/// `--force` is set. Refuses to resume a run started with different options.
+fn open_state(
+    opts: &FinalizeMigrationOptions,
+    paths: &FinalizePaths,
+) -> Result<FinalizeState, String> {
+    let digest = options_digest(opts);
+    match FinalizeState::load(&paths.out_dir)? {
+        Some(state) if !opts.force && state.options_digest != digest => Err(format!(
+            "{} was started with different options; rerun with --force to restart",
+            paths.out_dir.join(FINALIZE_STATE_FILE).display()
+        )),
+        Some(state) if !opts.force => Ok(state),
+        _ => {
+            if !opts.force {
+                for path in paths.artifacts() {
+                    ensure_writable(path, false)?;
+                }
+            }
+            Ok(FinalizeState::new(digest))
+        }
+    }
+}
+
+/// Reports what [`run_finalize_migration`] would do with each step, without
+/// writing anything.
+pub fn plan_finalize_migration(
+    opts: &FinalizeMigrationOptions,
+) -> Result<Vec<(FinalizeStep, StepPlan)>, String> {
+    crate::net::refresh_migration_mode_from_env();
+    let paths = FinalizePaths::new(opts);
+    let state = open_state(opts, &paths)?;
+    let mut plan = Vec::new();
+    let mut blocked = false;
+    let mut rerun = false;
+    for step in FinalizeStep::ALL {
+        let record = state.record(step);
+        let entry = if blocked || opts.until.is_some_and(|until| step > until) {
+            StepPlan::Stop
+        } else if !rerun && record.status == StepStatus::Done {
+            match outputs_intact(record) {
+                Ok(()) => StepPlan::Skip,
+                Err(reason) => {
+                    blocked = true;
+                    StepPlan::Blocked(reason)
+                }
+            }
+        } else if let Err(reason) = precondition(step, opts) {
+            blocked = true;
+            StepPlan::Blocked(reason)
+        } else {
+            rerun = true;
+            StepPlan::Run
+        };
+        plan.push((step, entry));
+    }
+    Ok(plan)
+}
+
 /// Run full migration finalize pipeline:
-/// freeze-check, snapshot, claims, apply-claims, and proposal anchor artifact.
+/// snapshot, claims, apply-claims, and proposal anchor artifact.
+///
+/// Completed steps recorded in the output directory's progress file are
+/// skipped when their outputs are unchanged, so rerunning after a failure
+/// resumes at the failed step. Before claims are first applied, the registry
+/// is copied to `registry_before_apply.json` for manual rollback.
 pub fn run_finalize_migration(
     opts: &FinalizeMigrationOptions,
 ) -> Result<FinalizeMigrationSummary, String> {
     crate::net::refresh_migration_mode_from_env();
-    if !opts.allow_unfrozen && !crate::net::migration_mode_frozen() {
-        return Err(
-            "migration freeze is not active (set PH_MIGRATION_MODE=freeze or use --allow-unfrozen)"
-                .to_string(),
-        );
-    }
-
-    let out_dir = std::path::Path::new(&opts.output_dir);
-    std::fs::create_dir_all(out_dir)
-        .map_err(|err| format!("failed to create output dir {}: {err}", out_dir.display()))?;
+    let paths = FinalizePaths::new(opts);
+    std::fs::create_dir_all(&paths.out_dir).map_err(|err| {
+        format!(
+            "failed to create output dir {}: {err}",
+            paths.out_dir.display()
+        )
+    })?;
+    let mut state = open_state(opts, &paths)?;
+    state.save(&paths.out_dir)?;
 
-    let snapshot_path = out_dir.join("migration_snapshot.json");
-    let claims_path = out_dir.join("migration_claims.json");
-    let proposal_path = out_dir.join("migration_anchor.json");
-    let apply_state_path = opts.apply_state_path.clone().unwrap_or_else(|| {
-        out_dir
-            .join("migration_apply_state.json")
-            .display()
-            .to_string()
-    });
+    let mut resumed = Vec::new();
+    let mut executed = Vec::new();
+    for step in FinalizeStep::ALL {
+        if opts.until.is_some_and(|until| step > until) {
+            break;
+        }
+        let record = state.record(step);
+        if executed.is_empty() && record.status == StepStatus::Done {
+            outputs_intact(record)?;
+            resumed.push(step);
+            continue;
+        }
+        precondition(step, opts).map_err(|err| format!("step {step} cannot start: {err}"))?;
+        let record = state.record_mut(step);
+        record.status = StepStatus::Running;
+        record.updated_at_ms = now_millis();
+        record.error = None;
+        state.save(&paths.out_dir)?;
 
-    ensure_writable(&snapshot_path, opts.force)?;
-    ensure_writable(&claims_path, opts.force)?;
-    ensure_writable(&proposal_path, opts.force)?;
-
-    let snapshot_root = run_snapshot(
-        &opts.registry_path,
-        opts.snapshot_height,
-        snapshot_path.to_str().unwrap_or("migration_snapshot.json"),
-    )?;
-
-    let claims_root = run_build_claims(
-        snapshot_path.to_str().unwrap_or("migration_snapshot.json"),
-        claims_path.to_str().unwrap_or("migration_claims.json"),
-        &BuildClaimsOptions {
-            amount_source: opts.amount_source.clone(),
-            include_slashed: opts.include_slashed,
-            conversion_ratio: if opts.conversion_ratio == 0 {
-                1
-            } else {
-                opts.conversion_ratio
-            },
-            claim_id_salt: opts.claim_id_salt.clone(),
-            token_contract: Some(opts.token_contract.clone()),
-            snapshot_height_override: Some(opts.snapshot_height),
-            claim_mode: "native".to_string(),
-        },
-    )?;
-
-    let apply_summary = run_apply_claims(
-        &opts.registry_path,
-        claims_path.to_str().unwrap_or("migration_claims.json"),
-        &ApplyClaimsOptions {
-            state_path: Some(apply_state_path.clone()),
-            dry_run: false,
-        },
-    )?;
-
-    run_propose_migration(&ProposeMigrationOptions {
-        snapshot_height: opts.snapshot_height,
-        token_contract: opts.token_contract.clone(),
-        conversion_ratio: if opts.conversion_ratio == 0 {
-            1
-        } else {
-            opts.conversion_ratio
-        },
-        treasury_mint: opts.treasury_mint,
-        log_dir: opts.log_dir.clone(),
-        node_id: opts.node_id.clone(),
-        quorum: opts.quorum,
-        output: Some(proposal_path.display().to_string()),
-    })?;
+        let result = run_step(step, opts, &paths);
+        let record = state.record_mut(step);
+        record.updated_at_ms = now_millis();
+        match result {
+            Ok((outputs, files)) => {
+                record.status = StepStatus::Done;
+                record.outputs = outputs;
+                record.files = files
+                    .iter()
+                    .map(|path| Ok((path.display().to_string(), file_digest(path)?)))
+                    .collect::<Result<_, String>>()?;
+                state.save(&paths.out_dir)?;
+                executed.push(step);
+            }
+            Err(err) => {
+                record.status = StepStatus::Failed;
+                record.error = Some(err.clone());
+                state.save(&paths.out_dir)?;
+                return Err(format!(
+                    "step {step} failed: {err} (rerun to resume; rollback: {})",
+                    step.rollback()
+                ));
+            }
+        }
+    }
 
+    let parse = |step, key| state.output(step, key).parse().unwrap_or(0);
     Ok(FinalizeMigrationSummary {
-        snapshot_root,
-        claims_root,
-        applied_claims: apply_summary.applied,
-        skipped_claims: apply_summary.skipped,
-        snapshot_path: snapshot_path.display().to_string(),
-        claims_path: claims_path.display().to_string(),
-        apply_state_path,
-        proposal_path: proposal_path.display().to_string(),
+        snapshot_root: state.output(FinalizeStep::Snapshot, "snapshot_root"),
+        claims_root: state.output(FinalizeStep::Claims, "claims_root"),
+        applied_claims: parse(FinalizeStep::ApplyClaims, "applied"),
+        skipped_claims: parse(FinalizeStep::ApplyClaims, "skipped"),
+        snapshot_path: paths.snapshot.display().to_string(),
+        claims_path: paths.claims.display().to_string(),
+        apply_state_path: paths.apply_state.display().to_string(),
+        proposal_path: paths.proposal.display().to_string(),
+        resumed,
+        executed,
     })
 }
+
+type StepOutputs = (BTreeMap<String, String>, Vec<PathBuf>);
+
+fn run_step(
+    step: FinalizeStep,
+    opts: &FinalizeMigrationOptions,
+    paths: &FinalizePaths,
+) -> Result<StepOutputs, String> {
+    let clock = artifact_clock(opts.deterministic);
+    let path_str = |path: &Path| path.display().to_string();
+    match step {
+        FinalizeStep::Snapshot => {
+            let root = run_snapshot_with_clock(
+                &opts.registry_path,
+                opts.snapshot_height,
+                &path_str(&paths.snapshot),
+                clock,
+            )?;
+            Ok((
+                BTreeMap::from([("snapshot_root".to_string(), root)]),
+                vec![paths.snapshot.clone()],
+            ))
+        }
+        FinalizeStep::Claims => {
+            let root = run_build_claims_with_clock(
+                &path_str(&paths.snapshot),
+                &path_str(&paths.claims),
+                &BuildClaimsOptions {
+                    amount_source: opts.amount_source.clone(),
+                    include_slashed: opts.include_slashed,
+                    conversion_ratio: conversion_ratio(opts),
+                    claim_id_salt: opts.claim_id_salt.clone(),
+                    token_contract: Some(opts.token_contract.clone()),
+                    snapshot_height_override: Some(opts.snapshot_height),
+                    claim_mode: "native".to_string(),
+                    address_index: None,
+                    expires_at_epoch: None,
+                },
+                clock,
+            )?;
+            Ok((
+                BTreeMap::from([("claims_root".to_string(), root)]),
+                vec![paths.claims.clone()],
+            ))
+        }
+        FinalizeStep::ApplyClaims => {
+            if !paths.registry_backup.exists() {
+                StakeRegistry::load(Path::new(&opts.registry_path))?
+                    .save(&paths.registry_backup)
+                    .map_err(|err| format!("failed to back up registry: {err}"))?;
+            }
+            let summary = run_apply_claims(
+                &opts.registry_path,
+                &path_str(&paths.claims),
+                &ApplyClaimsOptions {
+                    state_path: Some(path_str(&paths.apply_state)),
+                    dry_run: false,
+                },
+            )?;
+            Ok((
+                BTreeMap::from([
+                    ("applied".to_string(), summary.applied.to_string()),
+                    ("skipped".to_string(), summary.skipped.to_string()),
+                    ("held".to_string(), summary.held.to_string()),
+                    (
+                        "registry_backup".to_string(),
+                        path_str(&paths.registry_backup),
+                    ),
+                ]),
+                vec![paths.registry_backup.clone()],
+            ))
+        }
+        FinalizeStep::Proposal => {
+            run_propose_migration_with_clock(
+                &ProposeMigrationOptions {
+                    snapshot_height: opts.snapshot_height,
+                    token_contract: opts.token_contract.clone(),
+                    conversion_ratio: conversion_ratio(opts),
+                    treasury_mint: opts.treasury_mint,
+                    log_dir: opts.log_dir.clone(),
+                    node_id: opts.node_id.clone(),
+                    quorum: opts.quorum,
+                    output: Some(path_str(&paths.proposal)),
+                },
+                clock,
+            )?;
+            Ok((BTreeMap::new(), vec![paths.proposal.clone()]))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_finalize_migration, FinalizeMigrationOptions, FinalizeState, FinalizeStep};
+    use serde_json::json;
+    use std::fs;
+
+    #[test]
+    fn finalize_resumes_after_partial_run_and_detects_changed_outputs() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("power_house_finalize_{nanos}"));
+        let log_dir = dir.join("logs");
+        fs::create_dir_all(&log_dir).unwrap();
+        let registry = dir.join("stake_registry.json");
+        fs::write(
+            &registry,
+            serde_json::to_vec(&json!({
+                "accounts": {"aKey": {"balance": 9, "stake": 3, "slashed": false}}
+            }))
+            .unwrap(),
+        )
+        .unwrap();
+        let mut opts = FinalizeMigrationOptions {
+            registry_path: registry.display().to_string(),
+            snapshot_height: 7,
+            log_dir: log_dir.display().to_string(),
+            output_dir: dir.join("out").display().to_string(),
+            token_contract: "native://julian".to_string(),
+            conversion_ratio: 1,
+            treasury_mint: 0,
+            amount_source: "total".to_string(),
+            include_slashed: false,
+            claim_id_salt: "salt".to_string(),
+            node_id: "finalize-test".to_string(),
+            quorum: 1,
+            apply_state_path: None,
+            allow_unfrozen: true,
+            force: false,
+            deterministic: true,
+            until: Some(FinalizeStep::Claims),
+        };
+
+        let first = run_finalize_migration(&opts).unwrap();
+        assert_eq!(
+            first.executed,
+            [FinalizeStep::Snapshot, FinalizeStep::Claims]
+        );
+        opts.until = None;
+        let second = run_finalize_migration(&opts).unwrap();
+        assert_eq!(
+            second.resumed,
+            [FinalizeStep::Snapshot, FinalizeStep::Claims]
+        );
+        assert_eq!(
+            second.executed,
+            [FinalizeStep::ApplyClaims, FinalizeStep::Proposal]
+        );
+        assert_eq!(
+            (second.claims_root, second.applied_claims),
+            (first.claims_root, 1)
+        );
+        let out = dir.join("out");
+        assert!(out.join("registry_before_apply.json").exists());
+
+        fs::write(&second.claims_path, b"{}").unwrap();
+        let err = run_finalize_migration(&opts).unwrap_err();
+        assert!(err.contains("changed since step claims completed"), "{err}");
+        opts.snapshot_height = 8;
+        let err = run_finalize_migration(&opts).unwrap_err();
+        assert!(err.contains("different options"), "{err}");
+        let state = FinalizeState::load(&out).unwrap().unwrap();
+        assert_eq!(state.record(FinalizeStep::Proposal).status.as_str(), "done");
+        let _ = fs::remove_dir_all(dir);
+    }
+}
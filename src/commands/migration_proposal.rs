@@ -2,13 +2,12 @@
 
 use crate::net::{AnchorJson, MigrationAnchor, MigrationProposal};
 use crate::{
-    compute_fold_digest, julian_genesis_anchor, parse_log_file, read_fold_digest_hint, EntryAnchor,
-    LedgerAnchor,
+    compute_fold_digest, iter_ledger_logs, julian_genesis_anchor, read_fold_digest_hint, Clock,
+    EntryAnchor, LedgerAnchor, SystemClock,
 };
 use serde::Serialize;
 use std::fs;
-use std::path::{Path, PathBuf};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::path::Path;
 
 /// Parameters for generating a migration proposal artifact.
 #[derive(Debug, Clone)]
@@ -37,21 +36,8 @@ struct MigrationProposalArtifact {
     anchor_json: AnchorJson,
 }
 
-fn now_millis() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as u64
-}
-
-fn is_ledger_file(path: &Path) -> bool {
-    match path.file_name().and_then(|n| n.to_str()) {
-        Some(name) => name.starts_with("ledger_") && name.ends_with(".txt"),
-        None => false,
-    }
-}
-
-fn load_anchor_from_logs(path: &Path) -> Result<LedgerAnchor, String> {
+pub(crate) fn load_anchor_from_logs(path: &Path) -> Result<LedgerAnchor, String> {
+    let _span = crate::profile::span("load_anchor_from_logs");
     let mut cutoff: Option<String> = None;
     let mut anchor_from_checkpoint = false;
     let anchor = match crate::net::load_latest_checkpoint(path) {
@@ -79,32 +65,23 @@ fn load_anchor_from_logs(path: &Path) -> Result<LedgerAnchor, String> {
         .crate_version
         .get_or_insert_with(|| env!("CARGO_PKG_VERSION").to_string());
 
-    let mut files: Vec<PathBuf> = fs::read_dir(path)
-        .map_err(|err| format!("failed to read directory {}: {err}", path.display()))?
-        .filter_map(|entry| entry.ok().map(|e| e.path()))
-        .filter(|p| p.is_file() && is_ledger_file(p))
-        .collect();
-    files.sort();
-
-    for file in files {
+    for record in iter_ledger_logs(path)? {
+        let record = record?;
+        let name = record.name.as_str();
         if let Some(ref cutoff_name) = cutoff {
-            if let Some(name) = file.file_name().and_then(|n| n.to_str()) {
-                if name <= cutoff_name.as_str() {
-                    continue;
-                }
+            if name <= cutoff_name.as_str() {
+                continue;
             }
         }
 
-        let parsed = parse_log_file(&file)?;
+        let parsed = record.parse()?;
         if let Some(mode) = parsed.metadata.challenge_mode {
             match &mut metadata.challenge_mode {
                 None => metadata.challenge_mode = Some(mode),
                 Some(existing) if existing != &mode => {
                     return Err(format!(
                         "{} challenge_mode {} conflicts with existing {}",
-                        file.display(),
-                        mode,
-                        existing
+                        name, mode, existing
                     ));
                 }
                 _ => {}
@@ -115,7 +92,7 @@ fn load_anchor_from_logs(path: &Path) -> Result<LedgerAnchor, String> {
                 if existing != &digest && anchor_from_checkpoint {
                     return Err(format!(
                         "{} fold_digest conflicts with existing value",
-                        file.display()
+                        name
                     ));
                 }
             }
@@ -127,6 +104,7 @@ fn load_anchor_from_logs(path: &Path) -> Result<LedgerAnchor, String> {
             statement: parsed.statement,
             merkle_root: crate::merkle_root(&entry_hashes),
             hashes: entry_hashes,
+            submission: parsed.metadata.submission,
         });
     }
 
@@ -154,6 +132,16 @@ fn load_anchor_from_logs(path: &Path) -> Result<LedgerAnchor, String> {
 ///
 /// Returns the encoded JSON artifact payload.
 pub fn run_propose_migration(opts: &ProposeMigrationOptions) -> Result<String, String> {
+    run_propose_migration_with_clock(opts, &SystemClock)
+}
+
+/// [`run_propose_migration`] with an explicit timestamp source.
+///
+/// The clock sees the proposal hash and the anchored fold digest.
+pub fn run_propose_migration_with_clock(
+    opts: &ProposeMigrationOptions,
+    clock: &dyn Clock,
+) -> Result<String, String> {
     let proposal = MigrationProposal {
         snapshot_height: opts.snapshot_height,
         token_contract: opts.token_contract.clone(),
@@ -176,8 +164,10 @@ pub fn run_propose_migration(opts: &ProposeMigrationOptions) -> Result<String, S
         statement: migration_anchor.statement.clone(),
         merkle_root: crate::merkle_root(&[proposal_digest]),
         hashes: vec![proposal_digest],
+        submission: None,
     });
-    ledger.metadata.fold_digest = Some(compute_fold_digest(&ledger));
+    let fold_digest = compute_fold_digest(&ledger);
+    ledger.metadata.fold_digest = Some(fold_digest);
     ledger
         .metadata
         .crate_version
@@ -187,7 +177,7 @@ pub fn run_propose_migration(opts: &ProposeMigrationOptions) -> Result<String, S
         opts.node_id.clone(),
         opts.quorum,
         &ledger,
-        now_millis(),
+        clock.timestamp_ms(&[&proposal_digest, &fold_digest]),
         Vec::new(),
         None,
     )
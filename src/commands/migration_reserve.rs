@@ -0,0 +1,452 @@
+#![cfg(feature = "net")]
+
+use crate::commands::migration_apply_claims::{
+    load_apply_state, load_native_claims, resolve_state_path,
+};
+use crate::commands::migration_burn_executor::{
+    read_lines, resolve_receipts_path, run_reconcile_burns, BurnReceipt, ReconcileBurnOptions,
+};
+use crate::commands::migration_proposal::load_anchor_from_logs;
+use crate::economics::NATIVE_ASSET;
+use crate::net::{
+    encode_public_key_base64, encode_signature_base64, sign_payload, verify_signature_base64,
+    AnchorJson, StakeRegistry, SupplyCause,
+};
+use crate::{compute_fold_digest, Clock, EntryAnchor, SystemClock};
+use blake2::digest::{consts::U32, Digest};
+use ed25519_dalek::SigningKey;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+type Blake2b256 = blake2::Blake2b<U32>;
+
+/// Schema of a signed proof-of-reserve report.
+pub const RESERVE_REPORT_SCHEMA: &str = "mfenx.powerhouse.reserve-report.v1";
+
+/// Ledger statement under which the report digest is anchored.
+pub const RESERVE_REPORT_STATEMENT: &str = "migration.reserve_report";
+
+/// Inputs for a proof-of-reserve report.
+#[derive(Debug, Clone)]
+pub struct ReserveReportOptions {
+    /// Stake registry whose supply journal is reconciled.
+    pub registry_path: String,
+    /// Native migration claims artifact defining the allocation.
+    pub claims_path: String,
+    /// Optional apply-state path; defaults beside the registry.
+    pub state_path: Option<String>,
+    /// Optional burn outbox; burns are only reconciled when given.
+    pub outbox_path: Option<String>,
+    /// Receipt path and trusted keys used for burn reconciliation.
+    pub burns: ReconcileBurnOptions,
+    /// Log directory whose latest anchor the report digest is appended to.
+    pub log_dir: String,
+    /// Node ID embedded in the anchor JSON.
+    pub node_id: String,
+    /// Quorum threshold embedded in the anchor JSON.
+    pub quorum: usize,
+    /// Identity signing the report.
+    pub signing: SigningKey,
+    /// Optional output path for the encoded artifact.
+    pub output: Option<String>,
+}
+
+/// Signed reconciliation of the migration allocation against registry supply.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReserveReport {
+    /// Schema identifier (`mfenx.powerhouse.reserve-report.v1`).
+    pub schema: String,
+    /// Millisecond timestamp of the report.
+    pub generated_at_ms: u64,
+    /// Merkle root of the claims artifact.
+    pub claims_root: String,
+    /// Registry epoch at report time.
+    pub registry_epoch: u64,
+    /// Number of claims in the artifact.
+    pub claim_count: usize,
+    /// Sum of every claim's `mint_amount`.
+    pub allocation: String,
+    /// Claims recorded as applied.
+    pub applied_claims: usize,
+    /// Amount of the applied claims.
+    pub applied_amount: String,
+    /// Claims recorded as swept after expiry.
+    pub swept_claims: usize,
+    /// Amount of the swept claims.
+    pub swept_amount: String,
+    /// Amount of claims neither applied nor swept.
+    pub outstanding_amount: String,
+    /// Native supply tracked by the registry.
+    pub total_supply: u64,
+    /// Native units minted per supply cause.
+    pub minted_by_cause: BTreeMap<SupplyCause, u64>,
+    /// Native units burned per supply cause.
+    pub burned_by_cause: BTreeMap<SupplyCause, u64>,
+    /// Burn intents in the outbox, when one was reconciled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub burn_intents: Option<usize>,
+    /// Intents acknowledged by exactly one valid receipt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub burns_matched: Option<usize>,
+    /// Every inconsistency found; empty for a clean report.
+    pub discrepancies: Vec<String>,
+    /// Base64 public key of the signing identity.
+    pub signer_b64: String,
+    /// Base64 ed25519 signature over the report with this field empty.
+    pub signature_b64: String,
+}
+
+impl ReserveReport {
+    fn signing_payload(&self) -> Vec<u8> {
+        let unsigned = Self {
+            signature_b64: String::new(),
+            ..self.clone()
+        };
+        serde_json::to_vec(&unsigned).expect("reserve report encodes")
+    }
+
+    /// Blake2b-256 digest of the signed report, as anchored in the ledger.
+    pub fn digest(&self) -> [u8; 32] {
+        let bytes = serde_json::to_vec(self).expect("reserve report encodes");
+        Blake2b256::digest(bytes).into()
+    }
+
+    /// Checks the schema and the signer's signature.
+    pub fn verify(&self) -> Result<(), String> {
+        if self.schema != RESERVE_REPORT_SCHEMA {
+            return Err(format!("unexpected reserve report schema: {}", self.schema));
+        }
+        verify_signature_base64(
+            &self.signer_b64,
+            &self.signing_payload(),
+            &self.signature_b64,
+        )
+        .map_err(|err| format!("reserve report signature invalid: {err}"))
+    }
+}
+
+/// Report plus the anchor carrying its digest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReserveReportArtifact {
+    /// The signed report.
+    pub report: ReserveReport,
+    /// Hex digest of `report`.
+    pub report_digest: String,
+    /// Latest ledger anchor with the digest appended under
+    /// [`RESERVE_REPORT_STATEMENT`].
+    pub anchor_json: AnchorJson,
+}
+
+/// Builds, signs and anchors a proof-of-reserve report.
+pub fn run_reserve_report(opts: &ReserveReportOptions) -> Result<ReserveReportArtifact, String> {
+    run_reserve_report_with_clock(opts, &SystemClock)
+}
+
+/// [`run_reserve_report`] with an explicit timestamp source.
+///
+/// The clock sees the claims root and the registry supply.
+pub fn run_reserve_report_with_clock(
+    opts: &ReserveReportOptions,
+    clock: &dyn Clock,
+) -> Result<ReserveReportArtifact, String> {
+    let registry_path = Path::new(&opts.registry_path);
+    let registry = StakeRegistry::load(registry_path)?;
+    let claims = load_native_claims(Path::new(&opts.claims_path))?;
+    let state = load_apply_state(&resolve_state_path(
+        registry_path,
+        opts.state_path.as_deref(),
+    ))?;
+    let mut discrepancies = Vec::new();
+
+    let mut amounts = HashMap::new();
+    let mut allocation: u128 = 0;
+    for claim in &claims.claims {
+        let amount = claim.checked_mint_amount()?;
+        if amounts.insert(claim.claim_id.clone(), amount).is_some() {
+            discrepancies.push(format!("duplicate claim id {}", claim.claim_id));
+        }
+        allocation = allocation.saturating_add(amount);
+    }
+
+    let mut seen = HashSet::new();
+    let mut tally = |ids: &[String], label: &str, discrepancies: &mut Vec<String>| {
+        let (mut count, mut amount) = (0usize, 0u128);
+        for id in ids {
+            match amounts.get(id) {
+                None => discrepancies.push(format!("{label} claim {id} is not in the artifact")),
+                Some(_) if !seen.insert(id.clone()) => {
+                    discrepancies.push(format!("claim {id} is recorded more than once"))
+                }
+                Some(value) => {
+                    count += 1;
+                    amount = amount.saturating_add(*value);
+                }
+            }
+        }
+        (count, amount)
+    };
+    let (applied_claims, applied_amount) =
+        tally(&state.applied_claim_ids, "applied", &mut discrepancies);
+    let (swept_claims, swept_amount) = tally(&state.swept_claim_ids, "swept", &mut discrepancies);
+
+    let supply = registry.supply();
+    let minted_by_cause = supply.minted_by_cause(NATIVE_ASSET);
+    let burned_by_cause = supply.burned_by_cause(NATIVE_ASSET);
+    let minted = |cause| u128::from(minted_by_cause.get(&cause).copied().unwrap_or_default());
+    for (cause, label, expected) in [
+        (
+            SupplyCause::MigrationClaim,
+            "migration_claim",
+            applied_amount,
+        ),
+        (SupplyCause::MigrationSweep, "migration_sweep", swept_amount),
+    ] {
+        if minted(cause) != expected {
+            discrepancies.push(format!(
+                "registry minted {} as {label} but the apply state accounts for {expected}",
+                minted(cause)
+            ));
+        }
+    }
+
+    let (mut burn_intents, mut burns_matched) = (None, None);
+    if let Some(outbox) = &opts.outbox_path {
+        let burns = run_reconcile_burns(outbox, &opts.burns)?;
+        burn_intents = Some(burns.intents);
+        burns_matched = Some(burns.matched);
+        let issues = [
+            ("burn intent has no receipt", &burns.unexecuted),
+            (
+                "burn intent executed more than once",
+                &burns.double_executed,
+            ),
+            ("burn intent signature invalid", &burns.invalid_intents),
+            ("burn receipt for unknown intent", &burns.orphan_receipts),
+        ];
+        for (label, ids) in issues {
+            discrepancies.extend(ids.iter().map(|id| format!("{label}: {id}")));
+        }
+        discrepancies.extend(
+            burns
+                .invalid_receipts
+                .iter()
+                .map(|line| format!("burn receipt invalid: line {line}")),
+        );
+
+        let receipts_path =
+            resolve_receipts_path(Path::new(outbox), opts.burns.receipts_path.as_deref());
+        for raw in read_lines(&receipts_path, "receipts")?.lines() {
+            let Ok(receipt) = serde_json::from_str::<BurnReceipt>(raw.trim()) else {
+                continue;
+            };
+            if receipt.verify().is_err() || receipt.outcome != "slashed" {
+                continue;
+            }
+            let slashed = registry
+                .account(&receipt.pubkey_b64)
+                .is_some_and(|acct| acct.slashed);
+            if !slashed {
+                discrepancies.push(format!(
+                    "burn receipt {} reports {} slashed but the registry does not",
+                    receipt.intent_id, receipt.pubkey_b64
+                ));
+            }
+        }
+    }
+
+    let claims_root = claims.merkle_root;
+    let mut report = ReserveReport {
+        schema: RESERVE_REPORT_SCHEMA.to_string(),
+        generated_at_ms: clock
+            .timestamp_ms(&[claims_root.as_bytes(), &supply.total_supply.to_be_bytes()]),
+        claims_root,
+        registry_epoch: registry.epoch(),
+        claim_count: amounts.len(),
+        allocation: allocation.to_string(),
+        applied_claims,
+        applied_amount: applied_amount.to_string(),
+        swept_claims,
+        swept_amount: swept_amount.to_string(),
+        outstanding_amount: allocation
+            .saturating_sub(applied_amount)
+            .saturating_sub(swept_amount)
+            .to_string(),
+        total_supply: supply.total_supply,
+        minted_by_cause,
+        burned_by_cause,
+        burn_intents,
+        burns_matched,
+        discrepancies,
+        signer_b64: encode_public_key_base64(&opts.signing.verifying_key()),
+        signature_b64: String::new(),
+    };
+    report.signature_b64 =
+        encode_signature_base64(&sign_payload(&opts.signing, &report.signing_payload()));
+
+    let digest = report.digest();
+    let mut ledger = load_anchor_from_logs(Path::new(&opts.log_dir))?;
+    ledger.entries.push(EntryAnchor {
+        statement: RESERVE_REPORT_STATEMENT.to_string(),
+        merkle_root: crate::merkle_root(&[digest]),
+        hashes: vec![digest],
+        submission: None,
+    });
+    let fold_digest = compute_fold_digest(&ledger);
+    ledger.metadata.fold_digest = Some(fold_digest);
+    ledger
+        .metadata
+        .crate_version
+        .get_or_insert_with(|| env!("CARGO_PKG_VERSION").to_string());
+    let anchor_json = AnchorJson::from_ledger(
+        opts.node_id.clone(),
+        opts.quorum,
+        &ledger,
+        report.generated_at_ms,
+        Vec::new(),
+        None,
+    )
+    .map_err(|err| format!("anchor conversion failed: {err}"))?;
+
+    let artifact = ReserveReportArtifact {
+        report,
+        report_digest: hex::encode(digest),
+        anchor_json,
+    };
+    if let Some(path) = &opts.output {
+        let path = Path::new(path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|err| format!("failed to create {}: {err}", parent.display()))?;
+        }
+        let bytes = serde_json::to_vec_pretty(&artifact)
+            .map_err(|err| format!("failed to encode reserve report: {err}"))?;
+        std::fs::write(path, bytes)
+            .map_err(|err| format!("failed to write {}: {err}", path.display()))?;
+    }
+    Ok(artifact)
+}
+
+/// Checks a report artifact: the signature, the digest, and that the anchor
+/// carries the digest under [`RESERVE_REPORT_STATEMENT`].
+pub fn verify_reserve_report(path: &str) -> Result<ReserveReportArtifact, String> {
+    let bytes = std::fs::read(path).map_err(|err| format!("failed to read {path}: {err}"))?;
+    let artifact: ReserveReportArtifact = serde_json::from_slice(&bytes)
+        .map_err(|err| format!("invalid reserve report {path}: {err}"))?;
+    artifact.report.verify()?;
+    let digest = artifact.report.digest();
+    if artifact.report_digest != hex::encode(digest) {
+        return Err("report_digest does not match the report".to_string());
+    }
+    let ledger = artifact
+        .anchor_json
+        .clone()
+        .into_ledger()
+        .map_err(|err| format!("invalid anchor: {err}"))?;
+    let anchored = ledger
+        .entries
+        .iter()
+        .any(|entry| entry.statement == RESERVE_REPORT_STATEMENT && entry.hashes.contains(&digest));
+    if !anchored {
+        return Err("anchor does not carry the report digest".to_string());
+    }
+    Ok(artifact)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_reserve_report, verify_reserve_report, ReserveReportOptions};
+    use crate::commands::migration_apply_claims::{run_apply_claims, ApplyClaimsOptions};
+    use crate::net::{load_or_derive_keypair, Ed25519KeySource};
+    use serde_json::json;
+    use std::fs;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        let mut p = std::env::temp_dir();
+        let ts = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        p.push(format!("{name}_{ts}"));
+        p
+    }
+
+    #[test]
+    fn reserve_report_lists_discrepancies_and_anchors_digest() {
+        let dir = temp_path("reserve_report");
+        let logs = dir.join("logs");
+        fs::create_dir_all(&logs).unwrap();
+        let registry = dir.join("registry.json");
+        let claims = dir.join("claims.json");
+        let state = dir.join("apply_state.json");
+        let output = dir.join("reserve_report.json");
+        fs::write(&registry, br#"{"accounts": {}}"#).unwrap();
+        fs::write(
+            &claims,
+            serde_json::to_vec(&json!({
+                "claim_mode": "native",
+                "merkle_root": "0xabc",
+                "claims": [
+                    {"pubkey_b64": "aKey", "account": "aKey", "claim_id": "c1", "mint_amount": "10"},
+                    {"pubkey_b64": "bKey", "account": "bKey", "claim_id": "c2", "mint_amount": "20"}
+                ]
+            }))
+            .unwrap(),
+        )
+        .unwrap();
+        run_apply_claims(
+            registry.to_str().unwrap(),
+            claims.to_str().unwrap(),
+            &ApplyClaimsOptions {
+                state_path: Some(state.display().to_string()),
+                dry_run: false,
+            },
+        )
+        .unwrap();
+
+        let key = load_or_derive_keypair(&Ed25519KeySource::Seed("reserve".into())).unwrap();
+        let opts = ReserveReportOptions {
+            registry_path: registry.display().to_string(),
+            claims_path: claims.display().to_string(),
+            state_path: Some(state.display().to_string()),
+            outbox_path: None,
+            burns: Default::default(),
+            log_dir: logs.display().to_string(),
+            node_id: "reserve-test".into(),
+            quorum: 1,
+            signing: key.signing.clone(),
+            output: Some(output.display().to_string()),
+        };
+        let clean = run_reserve_report(&opts).unwrap();
+        assert!(clean.report.discrepancies.is_empty());
+        assert_eq!(clean.report.allocation, "30");
+        assert_eq!(clean.report.applied_amount, "30");
+        assert_eq!(clean.report.outstanding_amount, "0");
+        let verified = verify_reserve_report(output.to_str().unwrap()).unwrap();
+        assert_eq!(verified.report, clean.report);
+
+        fs::write(
+            &state,
+            serde_json::to_vec(&json!({
+                "schema": "mfenx.powerhouse.migration-apply-state.v1",
+                "updated_at_ms": 0,
+                "applied_claim_ids": ["c1", "c9"]
+            }))
+            .unwrap(),
+        )
+        .unwrap();
+        let dirty = run_reserve_report(&opts).unwrap().report;
+        assert_eq!(dirty.outstanding_amount, "20");
+        assert_eq!(dirty.discrepancies.len(), 2);
+        assert!(dirty.discrepancies[0].contains("c9"));
+        assert!(dirty.discrepancies[1].contains("migration_claim"));
+
+        let mut tampered: serde_json::Value =
+            serde_json::from_slice(&fs::read(&output).unwrap()).unwrap();
+        tampered["report"]["discrepancies"] = json!([]);
+        fs::write(&output, serde_json::to_vec(&tampered).unwrap()).unwrap();
+        assert!(verify_reserve_report(output.to_str().unwrap()).is_err());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -8,7 +8,7 @@ use std::path::Path;
 /// Options for validating native migration claims/state consistency.
 #[derive(Debug, Clone)]
 pub struct VerifyStateOptions {
-    /// Require all claims in the artifact to appear in apply-state.
+    /// Require all claims in the artifact to appear in apply-state as applied or swept.
     pub require_complete: bool,
     /// Require registry balances to be at least minted totals per account.
     pub enforce_balance_floor: bool,
@@ -21,6 +21,8 @@ pub struct VerifyStateSummary {
     pub claim_count: usize,
     /// Applied claims discovered in state file.
     pub applied_count: usize,
+    /// Expired claims recorded as swept into the governance account.
+    pub swept_count: usize,
     /// Claim IDs present in artifact but missing from state.
     pub missing_count: usize,
     /// Claim IDs present in state but not in artifact.
@@ -46,6 +48,8 @@ struct ClaimEntry {
 #[derive(Debug, Deserialize)]
 struct ApplyState {
     applied_claim_ids: Vec<String>,
+    #[serde(default)]
+    swept_claim_ids: Vec<String>,
 }
 
 /// Verify migration claims/state consistency and optional registry balance floors.
@@ -116,8 +120,21 @@ pub fn run_verify_state(
         }
     }
 
+    let mut swept_count = 0usize;
+    for claim_id in state.swept_claim_ids {
+        if !by_id.contains_key(&claim_id) {
+            unknown_count += 1;
+        } else if !seen_state.insert(claim_id.clone()) {
+            return Err(format!(
+                "claim {claim_id} is recorded as both applied and swept"
+            ));
+        } else {
+            swept_count += 1;
+        }
+    }
+
     let claim_count = by_id.len();
-    let missing_count = claim_count.saturating_sub(applied_count);
+    let missing_count = claim_count.saturating_sub(applied_count + swept_count);
 
     if opts.require_complete && missing_count > 0 {
         return Err(format!(
@@ -149,6 +166,7 @@ pub fn run_verify_state(
     Ok(VerifyStateSummary {
         claim_count,
         applied_count,
+        swept_count,
         missing_count,
         unknown_count,
         applied_total_mint: applied_total_mint.to_string(),
@@ -8,6 +8,8 @@ pub mod migration_claims;
 pub mod migration_finalize;
 /// Governance migration proposal artifact builder.
 pub mod migration_proposal;
+/// Signed proof-of-reserve reports reconciling migration claims with registry supply.
+pub mod migration_reserve;
 /// Verification helpers for migration apply-state and registry consistency.
 pub mod migration_verify_state;
 /// Deterministic stake snapshot helpers used by migration tooling.
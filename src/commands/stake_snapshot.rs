@@ -1,14 +1,13 @@
 #![cfg(feature = "net")]
 
-use crate::net::{AnchorJson, StakeRegistry};
+use crate::net::{open_registry_store, AnchorJson, StakeRegistry};
 use crate::{
-    compute_fold_digest, julian_genesis_anchor, merkle_root, AnchorMetadata, EntryAnchor,
-    LedgerAnchor,
+    compute_fold_digest, julian_genesis_anchor, merkle_root, AnchorMetadata, Clock, EntryAnchor,
+    LedgerAnchor, SystemClock,
 };
 use blake2::digest::{consts::U32, Digest};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::path::{Path, PathBuf};
 
 /// One deterministic stake record included in a migration snapshot.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,15 +41,65 @@ pub struct StakeSnapshotArtifact {
     pub migration_anchor: AnchorJson,
 }
 
-type Blake2b256 = blake2::Blake2b<U32>;
+/// Schema identifier of a paged stake snapshot manifest.
+pub const SCHEMA_STAKE_SNAPSHOT_PAGES: &str = "mfenx.powerhouse.stake_snapshot_pages.v1";
+
+/// Default number of accounts written per snapshot page.
+pub const DEFAULT_SNAPSHOT_PAGE_SIZE: usize = 10_000;
+
+/// One page of a paged snapshot, stored as `page_<index>.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StakeSnapshotPage {
+    /// Snapshot height selected by governance.
+    pub snapshot_height: u64,
+    /// Zero-based page position.
+    pub index: u64,
+    /// Entries in public-key order, continuing the previous page.
+    pub entries: Vec<StakeSnapshotEntry>,
+    /// BLAKE2b-256 digest over the page's leaf hashes.
+    pub digest: String,
+}
+
+/// Manifest line describing one snapshot page.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StakeSnapshotPageRef {
+    /// Zero-based page position.
+    pub index: u64,
+    /// Number of entries on the page.
+    pub entries: usize,
+    /// First public key on the page.
+    pub first_key: String,
+    /// Last public key on the page.
+    pub last_key: String,
+    /// Page digest, as stored in the page file.
+    pub digest: String,
+    /// Running digest chaining this page onto every page before it.
+    pub chain: String,
+}
 
-fn now_millis() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as u64
+/// Manifest of a paged stake snapshot, stored as `manifest.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StakeSnapshotManifest {
+    /// Schema identifier (`mfenx.powerhouse.stake_snapshot_pages.v1`).
+    pub schema: String,
+    /// Snapshot height selected by governance.
+    pub snapshot_height: u64,
+    /// Source registry path.
+    pub registry_path: String,
+    /// Millisecond timestamp used while producing the snapshot.
+    pub generated_at_ms: u64,
+    /// Maximum entries per page.
+    pub page_size: usize,
+    /// Total entries across all pages.
+    pub accounts: u64,
+    /// Pages in order.
+    pub pages: Vec<StakeSnapshotPageRef>,
+    /// Chain digest after the last page.
+    pub snapshot_root: String,
 }
 
+type Blake2b256 = blake2::Blake2b<U32>;
+
 fn leaf_digest(height: u64, pk_b64: &str, balance: u64, stake: u64, slashed: bool) -> [u8; 32] {
     let mut hasher = Blake2b256::new();
     hasher.update(b"migration-snapshot-entry-v1");
@@ -63,11 +112,224 @@ fn leaf_digest(height: u64, pk_b64: &str, balance: u64, stake: u64, slashed: boo
     hasher.finalize().into()
 }
 
+fn page_digest(height: u64, index: u64, leaves: &[[u8; 32]]) -> [u8; 32] {
+    let mut hasher = Blake2b256::new();
+    hasher.update(b"migration-snapshot-page-v1");
+    hasher.update(height.to_be_bytes());
+    hasher.update(index.to_be_bytes());
+    for leaf in leaves {
+        hasher.update(leaf);
+    }
+    hasher.finalize().into()
+}
+
+fn chain_seed(height: u64) -> [u8; 32] {
+    let mut hasher = Blake2b256::new();
+    hasher.update(b"migration-snapshot-root-v1");
+    hasher.update(height.to_be_bytes());
+    hasher.finalize().into()
+}
+
+fn chain_digest(prev: &[u8; 32], page: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Blake2b256::new();
+    hasher.update(b"migration-snapshot-chain-v1");
+    hasher.update(prev);
+    hasher.update(page);
+    hasher.finalize().into()
+}
+
+fn page_path(dir: &Path, index: u64) -> PathBuf {
+    dir.join(format!("page_{index:06}.json"))
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    let bytes = serde_json::to_vec_pretty(value)
+        .map_err(|e| format!("failed to encode {}: {e}", path.display()))?;
+    std::fs::write(path, bytes).map_err(|e| format!("failed to write {}: {e}", path.display()))
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T, String> {
+    let bytes =
+        std::fs::read(path).map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+    serde_json::from_slice(&bytes).map_err(|e| format!("failed to decode {}: {e}", path.display()))
+}
+
+/// Streams the registry into `page_size`-entry page files under `output_dir`
+/// and writes `manifest.json` last.
+///
+/// Pages hold the same entries and leaf hashes as [`run_snapshot`], in the
+/// same order. Each page digest is chained onto the previous one, and the
+/// final chain value is the snapshot root. SQLite registries are read one page
+/// at a time, so memory is bounded by the page size rather than the registry.
+/// The root differs from the single-file Merkle root.
+pub fn run_paged_snapshot_with_clock(
+    registry_path: &str,
+    height: u64,
+    page_size: usize,
+    output_dir: &Path,
+    clock: &dyn Clock,
+) -> Result<StakeSnapshotManifest, String> {
+    if page_size == 0 {
+        return Err("page size must be positive".into());
+    }
+    std::fs::create_dir_all(output_dir)
+        .map_err(|e| format!("failed to create {}: {e}", output_dir.display()))?;
+    let mut pages = Vec::new();
+    let mut chain = chain_seed(height);
+    let mut accounts = 0u64;
+    open_registry_store(Path::new(registry_path))?.for_each_account_page(
+        page_size,
+        &mut |page| {
+            let index = pages.len() as u64;
+            let mut leaves = Vec::with_capacity(page.len());
+            let entries = page
+                .into_iter()
+                .map(|(pk, acct)| {
+                    let digest = leaf_digest(height, &pk, acct.balance, acct.stake, acct.slashed);
+                    leaves.push(digest);
+                    StakeSnapshotEntry {
+                        pubkey_b64: pk,
+                        balance: acct.balance,
+                        stake: acct.stake,
+                        slashed: acct.slashed,
+                        leaf_hash: hex::encode(digest),
+                    }
+                })
+                .collect::<Vec<_>>();
+            let digest = page_digest(height, index, &leaves);
+            chain = chain_digest(&chain, &digest);
+            accounts += entries.len() as u64;
+            pages.push(StakeSnapshotPageRef {
+                index,
+                entries: entries.len(),
+                first_key: entries[0].pubkey_b64.clone(),
+                last_key: entries[entries.len() - 1].pubkey_b64.clone(),
+                digest: hex::encode(digest),
+                chain: hex::encode(chain),
+            });
+            write_json(
+                &page_path(output_dir, index),
+                &StakeSnapshotPage {
+                    snapshot_height: height,
+                    index,
+                    entries,
+                    digest: hex::encode(digest),
+                },
+            )
+        },
+    )?;
+    let manifest = StakeSnapshotManifest {
+        schema: SCHEMA_STAKE_SNAPSHOT_PAGES.to_string(),
+        snapshot_height: height,
+        registry_path: registry_path.to_string(),
+        generated_at_ms: clock.timestamp_ms(&[&chain, &height.to_be_bytes()]),
+        page_size,
+        accounts,
+        pages,
+        snapshot_root: hex::encode(chain),
+    };
+    write_json(&output_dir.join("manifest.json"), &manifest)?;
+    Ok(manifest)
+}
+
+/// Re-derives every leaf, page digest and chain value of the paged snapshot
+/// in `dir`, one page at a time, and checks them against the manifest and its
+/// declared root. Keys must strictly increase across page boundaries.
+pub fn verify_paged_snapshot(dir: &Path) -> Result<StakeSnapshotManifest, String> {
+    let manifest: StakeSnapshotManifest = read_json(&dir.join("manifest.json"))?;
+    if manifest.schema != SCHEMA_STAKE_SNAPSHOT_PAGES {
+        return Err(format!("unexpected schema {}", manifest.schema));
+    }
+    let height = manifest.snapshot_height;
+    let mut chain = chain_seed(height);
+    let mut accounts = 0u64;
+    let mut last_key: Option<String> = None;
+    for (position, page_ref) in manifest.pages.iter().enumerate() {
+        let index = position as u64;
+        if page_ref.index != index {
+            return Err(format!("page {index} is listed as {}", page_ref.index));
+        }
+        let page: StakeSnapshotPage = read_json(&page_path(dir, index))?;
+        if page.snapshot_height != height || page.index != index {
+            return Err(format!("page {index} belongs to a different snapshot"));
+        }
+        if page.entries.is_empty() || page.entries.len() > manifest.page_size {
+            return Err(format!("page {index} has {} entries", page.entries.len()));
+        }
+        let mut leaves = Vec::with_capacity(page.entries.len());
+        for entry in &page.entries {
+            if last_key
+                .as_deref()
+                .is_some_and(|prev| entry.pubkey_b64.as_str() <= prev)
+            {
+                return Err(format!(
+                    "page {index}: {} is out of order",
+                    entry.pubkey_b64
+                ));
+            }
+            let digest = leaf_digest(
+                height,
+                &entry.pubkey_b64,
+                entry.balance,
+                entry.stake,
+                entry.slashed,
+            );
+            if entry.leaf_hash != hex::encode(digest) {
+                return Err(format!(
+                    "page {index}: leaf hash mismatch for {}",
+                    entry.pubkey_b64
+                ));
+            }
+            leaves.push(digest);
+            last_key = Some(entry.pubkey_b64.clone());
+        }
+        let page_hash = page_digest(height, index, &leaves);
+        let digest = hex::encode(page_hash);
+        if page.digest != digest || page_ref.digest != digest {
+            return Err(format!("page {index}: digest mismatch"));
+        }
+        if page_ref.entries != page.entries.len()
+            || page_ref.first_key != page.entries[0].pubkey_b64
+            || page_ref.last_key != page.entries[page.entries.len() - 1].pubkey_b64
+        {
+            return Err(format!("page {index}: manifest summary does not match"));
+        }
+        chain = chain_digest(&chain, &page_hash);
+        if page_ref.chain != hex::encode(chain) {
+            return Err(format!("page {index}: chain digest mismatch"));
+        }
+        accounts += page.entries.len() as u64;
+    }
+    if accounts != manifest.accounts {
+        return Err(format!(
+            "manifest declares {} accounts, pages hold {accounts}",
+            manifest.accounts
+        ));
+    }
+    if manifest.snapshot_root != hex::encode(chain) {
+        return Err("page chain does not reach the declared snapshot root".into());
+    }
+    Ok(manifest)
+}
+
 /// Build a deterministic stake snapshot artifact and return its Merkle root.
 ///
 /// The artifact is anchored using the same `AnchorJson::from_ledger` flow used by
 /// `julian net anchor`, and persisted to `output`.
 pub fn run_snapshot(registry_path: &str, height: u64, output: &str) -> Result<String, String> {
+    run_snapshot_with_clock(registry_path, height, output, &SystemClock)
+}
+
+/// [`run_snapshot`] with an explicit timestamp source.
+///
+/// The clock sees the Merkle root and height, so an [`crate::InputClock`]
+/// makes the artifact a pure function of registry contents and arguments.
+pub fn run_snapshot_with_clock(
+    registry_path: &str,
+    height: u64,
+    output: &str,
+    clock: &dyn Clock,
+) -> Result<String, String> {
     let registry = StakeRegistry::load(Path::new(registry_path))?;
 
     let mut ordered = registry
@@ -98,6 +360,7 @@ pub fn run_snapshot(registry_path: &str, height: u64, output: &str) -> Result<St
         statement,
         merkle_root: merkle,
         hashes: leaves,
+        submission: None,
     };
 
     let mut entries_for_anchor = julian_genesis_anchor().entries;
@@ -108,6 +371,7 @@ pub fn run_snapshot(registry_path: &str, height: u64, output: &str) -> Result<St
             challenge_mode: Some("migration".to_string()),
             fold_digest: None,
             crate_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            registry_root: None,
         },
     };
     ledger.metadata.fold_digest = Some(compute_fold_digest(&ledger));
@@ -121,14 +385,15 @@ pub fn run_snapshot(registry_path: &str, height: u64, output: &str) -> Result<St
         .and_then(|v| v.parse::<usize>().ok())
         .unwrap_or(1);
 
+    let generated_at_ms = clock.timestamp_ms(&[&merkle, &height.to_be_bytes()]);
     let migration_anchor =
-        AnchorJson::from_ledger(node_id, quorum, &ledger, now_millis(), Vec::new(), None)
+        AnchorJson::from_ledger(node_id, quorum, &ledger, generated_at_ms, Vec::new(), None)
             .map_err(|e| format!("failed to anchor snapshot: {e}"))?;
 
     let artifact = StakeSnapshotArtifact {
         snapshot_height: height,
         registry_path: registry_path.to_string(),
-        generated_at_ms: now_millis(),
+        generated_at_ms,
         merkle_root: hex::encode(merkle),
         entries,
         migration_anchor,
@@ -149,7 +414,11 @@ pub fn run_snapshot(registry_path: &str, height: u64, output: &str) -> Result<St
 
 #[cfg(test)]
 mod tests {
-    use super::run_snapshot;
+    use super::{
+        run_paged_snapshot_with_clock, run_snapshot, run_snapshot_with_clock,
+        verify_paged_snapshot, StakeSnapshotArtifact, StakeSnapshotPage,
+    };
+    use crate::{FixedClock, InputClock};
     use serde_json::json;
     use std::fs;
 
@@ -196,4 +465,80 @@ mod tests {
         let _ = fs::remove_file(out_a);
         let _ = fs::remove_file(out_b);
     }
+
+    #[test]
+    fn deterministic_snapshot_is_byte_identical() {
+        let reg = temp_path("reg_det.json");
+        let out_a = temp_path("snap_det_a.json");
+        let out_b = temp_path("snap_det_b.json");
+        let payload = json!({
+            "accounts": {"aKey": {"balance": 9, "stake": 3, "slashed": false}}
+        });
+        fs::write(&reg, serde_json::to_vec(&payload).unwrap()).unwrap();
+        let reg_path = reg.to_str().unwrap();
+
+        run_snapshot_with_clock(reg_path, 42, out_a.to_str().unwrap(), &InputClock).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        run_snapshot_with_clock(reg_path, 42, out_b.to_str().unwrap(), &InputClock).unwrap();
+        assert_eq!(fs::read(&out_a).unwrap(), fs::read(&out_b).unwrap());
+
+        run_snapshot_with_clock(reg_path, 42, out_b.to_str().unwrap(), &FixedClock(1_234)).unwrap();
+        let artifact: StakeSnapshotArtifact =
+            serde_json::from_slice(&fs::read(&out_b).unwrap()).unwrap();
+        assert_eq!(artifact.generated_at_ms, 1_234);
+        assert_eq!(artifact.migration_anchor.timestamp_ms, 1_234);
+
+        let _ = fs::remove_file(reg);
+        let _ = fs::remove_file(out_a);
+        let _ = fs::remove_file(out_b);
+    }
+
+    #[test]
+    fn paged_snapshot_matches_single_file_and_detects_tampering() {
+        let reg = temp_path("reg_paged.json");
+        let single = temp_path("snap_single.json");
+        let dir = temp_path("snap_pages");
+        let payload = json!({
+            "accounts": {
+                "dKey": {"balance": 1, "stake": 0, "slashed": false},
+                "bKey": {"balance": 2, "stake": 4, "slashed": false},
+                "aKey": {"balance": 9, "stake": 3, "slashed": true},
+                "cKey": {"balance": 0, "stake": 8, "slashed": false},
+                "eKey": {"balance": 6, "stake": 1, "slashed": false}
+            }
+        });
+        fs::write(&reg, serde_json::to_vec(&payload).unwrap()).unwrap();
+        let reg_path = reg.to_str().unwrap();
+
+        let manifest = run_paged_snapshot_with_clock(reg_path, 42, 2, &dir, &InputClock).unwrap();
+        assert_eq!(manifest.pages.len(), 3);
+        assert_eq!(manifest.accounts, 5);
+        assert_eq!(verify_paged_snapshot(&dir).unwrap(), manifest);
+
+        run_snapshot(reg_path, 42, single.to_str().unwrap()).unwrap();
+        let artifact: StakeSnapshotArtifact =
+            serde_json::from_slice(&fs::read(&single).unwrap()).unwrap();
+        let mut paged_leaves = Vec::new();
+        for index in 0..3 {
+            let page: StakeSnapshotPage = serde_json::from_slice(
+                &fs::read(dir.join(format!("page_{index:06}.json"))).unwrap(),
+            )
+            .unwrap();
+            paged_leaves.extend(page.entries.into_iter().map(|entry| entry.leaf_hash));
+        }
+        let single_leaves: Vec<_> = artifact.entries.into_iter().map(|e| e.leaf_hash).collect();
+        assert_eq!(paged_leaves, single_leaves);
+
+        let page_file = dir.join("page_000001.json");
+        let mut page: StakeSnapshotPage =
+            serde_json::from_slice(&fs::read(&page_file).unwrap()).unwrap();
+        page.entries[0].stake += 1;
+        fs::write(&page_file, serde_json::to_vec(&page).unwrap()).unwrap();
+        let err = verify_paged_snapshot(&dir).unwrap_err();
+        assert!(err.contains("leaf hash mismatch"), "{err}");
+
+        let _ = fs::remove_file(reg);
+        let _ = fs::remove_file(single);
+        let _ = fs::remove_dir_all(dir);
+    }
 }
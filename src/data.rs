@@ -5,7 +5,36 @@
 //! generalized sum-check proofs.  The records are deterministic and use a
 //! domain-separated BLAKE2b-256 digest to ensure tamper resistance while
 //! remaining human-auditable.
+//!
+//! A compact binary encoding of the same record is also provided for storage
+//! that does not need to be read by eye.  It is length-prefixed throughout, so
+//! whitespace edits cannot silently change its meaning:
+//!
+//! ```text
+//! magic "PHTR" | version u8 | statement (u32 len + UTF-8)
+//!   | transcript (u32 count + u64 BE...) | round_sums (u32 count + u64 BE...)
+//!   | final u64 BE | digest [u8; 32]
+//! ```
+//!
+//! Both encodings share [`compute_digest`], so a transcript hashes to the same
+//! value regardless of how it was stored.
+//!
+//! Generalized sum-check transcripts can also be stored compressed.  The
+//! challenges follow from the Fiat–Shamir seed (modulus, variable count,
+//! claimed sum, and one coefficient of each round polynomial), so a
+//! [`CompressedRecord`] keeps only those inputs and the final evaluation:
+//!
+//! ```text
+//! sumcheck_seed:<modulus> <claimed_sum>
+//! round_coeffs:<a_1> ... <a_n>
+//! final:<value>
+//! hash:<digest of the expanded transcript>
+//! ```
+//!
+//! [`parse_record`] recomputes the challenges and round sums before the digest
+//! is checked, so readers see the same transcript and digest either way.
 
+use crate::{field::is_prime_u64, sumcheck::GENERAL_SUMCHECK_DOMAIN, Field, Transcript};
 use blake2::digest::{consts::U32, Digest};
 
 type Blake2b256 = blake2::Blake2b<U32>;
@@ -13,6 +42,11 @@ type Blake2b256 = blake2::Blake2b<U32>;
 /// Domain tag applied to every transcript digest.
 const DIGEST_DOMAIN: &[u8] = b"MFENX_TRANSCRIPT";
 
+/// Magic prefix identifying a binary transcript record.
+const BINARY_MAGIC: &[u8; 4] = b"PHTR";
+/// Current binary record version.
+const BINARY_VERSION: u8 = 1;
+
 /// Fixed-width transcript digest.
 pub type TranscriptDigest = [u8; 32];
 
@@ -113,6 +147,133 @@ where
     write_line(&format!("hash:{}", digest_to_hex(&digest)))
 }
 
+/// Sum-check transcript reduced to the inputs its challenges derive from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompressedRecord {
+    /// Prime modulus the transcript was sampled in.
+    pub modulus: u64,
+    /// Claimed sum over the Boolean hypercube (the first round sum).
+    pub claimed_sum: u64,
+    /// Linear coefficient `a_i` of each round polynomial `g_i(z) = a_i·z + b_i`.
+    pub coefficients: Vec<u64>,
+    /// Final evaluation.
+    pub final_value: u64,
+}
+
+impl CompressedRecord {
+    /// Compresses a generalized sum-check transcript sampled modulo `modulus`.
+    ///
+    /// Returns `None` when the transcript was not produced by the generalized
+    /// sum-check protocol in that field, so it cannot be recomputed.
+    pub fn from_trace(
+        modulus: u64,
+        transcript: &[u64],
+        round_sums: &[u64],
+        final_value: u64,
+    ) -> Option<Self> {
+        if !(modulus >= 3 && is_prime_u64(modulus))
+            || transcript.is_empty()
+            || transcript.len() != round_sums.len()
+        {
+            return None;
+        }
+        let field = Field::new(modulus);
+        let half = field.inv(2);
+        let next_sums = round_sums[1..].iter().chain(std::iter::once(&final_value));
+        let mut coefficients = Vec::with_capacity(transcript.len());
+        for ((&challenge, &sum), &next) in transcript.iter().zip(round_sums).zip(next_sums) {
+            // next = a·r + b with a + 2b = sum, so next = a·(r - 1/2) + sum/2.
+            let slope = field.sub(challenge % modulus, half);
+            if slope == 0 {
+                return None;
+            }
+            let a = field.div(
+                field.sub(next % modulus, field.mul(sum % modulus, half)),
+                slope,
+            );
+            coefficients.push(a);
+        }
+        let record = Self {
+            modulus,
+            claimed_sum: round_sums[0],
+            coefficients,
+            final_value,
+        };
+        match record.expand() {
+            Ok((expanded, sums)) if expanded == transcript && sums == round_sums => Some(record),
+            _ => None,
+        }
+    }
+
+    /// Recomputes the challenges and round sums, checking that the last
+    /// round lands on the final evaluation.
+    pub fn expand(&self) -> Result<(Vec<u64>, Vec<u64>), String> {
+        if !(self.modulus >= 3 && is_prime_u64(self.modulus)) {
+            return Err(format!("modulus {} is not an odd prime", self.modulus));
+        }
+        let p = self.modulus;
+        if let Some(value) = std::iter::once(&self.claimed_sum)
+            .chain(&self.coefficients)
+            .find(|value| **value >= p)
+        {
+            return Err(format!("value {value} does not fit modulus {p}"));
+        }
+        let field = Field::new(p);
+        let half = field.inv(2);
+        let mut transcript = Transcript::new(GENERAL_SUMCHECK_DOMAIN);
+        transcript.append(p);
+        transcript.append(self.coefficients.len() as u64);
+        transcript.append(self.claimed_sum);
+        let mut challenges = Vec::with_capacity(self.coefficients.len());
+        let mut round_sums = Vec::with_capacity(self.coefficients.len());
+        let mut running = self.claimed_sum;
+        for &a in &self.coefficients {
+            round_sums.push(running);
+            let b = field.mul(field.sub(running, a), half);
+            transcript.append(a);
+            transcript.append(b);
+            let r = transcript.challenge(&field);
+            challenges.push(r);
+            running = field.add(field.mul(a, r), b);
+        }
+        if running != self.final_value {
+            return Err("final value does not match the recomputed rounds".to_string());
+        }
+        Ok((challenges, round_sums))
+    }
+}
+
+/// Writes a transcript record, compressed when its challenges can be
+/// recomputed from a sum-check seed in `modulus` and in full otherwise.
+///
+/// The stored hash is the digest of the full transcript in both cases.
+pub fn write_compact_record<W>(
+    mut write_line: W,
+    modulus: u64,
+    transcript: &[u64],
+    round_sums: &[u64],
+    final_value: u64,
+) -> std::io::Result<()>
+where
+    W: FnMut(&str) -> std::io::Result<()>,
+{
+    let Some(record) = CompressedRecord::from_trace(modulus, transcript, round_sums, final_value)
+    else {
+        return write_record(write_line, transcript, round_sums, final_value);
+    };
+    let digest = compute_digest(transcript, round_sums, final_value);
+    write_line(&format!(
+        "sumcheck_seed:{} {}",
+        record.modulus, record.claimed_sum
+    ))?;
+    write_line(&format!(
+        "round_coeffs:{}",
+        encode_u64_slice(&record.coefficients)
+    ))?;
+    write_line(&format!("final:{}", final_value))?;
+    write_line(&format!("hash:{}", digest_to_hex(&digest)))
+}
+
 fn parse_vec_u64(input: &str, prefix: &str) -> Result<Vec<u64>, String> {
     let tail = input
         .strip_prefix(prefix)
@@ -139,6 +300,9 @@ fn parse_u64(input: &str, prefix: &str) -> Result<u64, String> {
 }
 
 /// Parses a transcript record and returns its components and stored hash.
+///
+/// Compressed records are expanded, so the returned transcript and round sums
+/// are the recomputed ones.
 pub fn parse_record<'a, I>(lines: I) -> Result<(Vec<u64>, Vec<u64>, u64, TranscriptDigest), String>
 where
     I: IntoIterator<Item = &'a str>,
@@ -154,9 +318,25 @@ where
         .next()
         .ok_or_else(|| "missing final line".to_string())?;
     let hash_line = iter.next().ok_or_else(|| "missing hash line".to_string())?;
-    let transcript = parse_vec_u64(transcript_line, "transcript:")?;
-    let round_sums = parse_vec_u64(round_sums_line, "round_sums:")?;
     let final_value = parse_u64(final_line, "final:")?;
+    let (transcript, round_sums) = if transcript_line.starts_with("sumcheck_seed:") {
+        let seed = parse_vec_u64(transcript_line, "sumcheck_seed:")?;
+        let [modulus, claimed_sum] = seed[..] else {
+            return Err("sumcheck_seed: needs a modulus and a claimed sum".to_string());
+        };
+        CompressedRecord {
+            modulus,
+            claimed_sum,
+            coefficients: parse_vec_u64(round_sums_line, "round_coeffs:")?,
+            final_value,
+        }
+        .expand()?
+    } else {
+        (
+            parse_vec_u64(transcript_line, "transcript:")?,
+            parse_vec_u64(round_sums_line, "round_sums:")?,
+        )
+    };
     let stored_hash = digest_from_hex(
         hash_line
             .strip_prefix("hash:")
@@ -180,9 +360,140 @@ where
     }
 }
 
+/// Transcript record decoded from the binary encoding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinaryRecord {
+    /// Statement the transcript belongs to.
+    pub statement: String,
+    /// Fiat–Shamir challenges.
+    pub transcript: Vec<u64>,
+    /// Per-round sums.
+    pub round_sums: Vec<u64>,
+    /// Final evaluation.
+    pub final_value: u64,
+    /// Digest stored alongside the record.
+    pub digest: TranscriptDigest,
+}
+
+/// Returns true when `bytes` start with the binary record magic.
+pub fn is_binary_record(bytes: &[u8]) -> bool {
+    bytes.starts_with(BINARY_MAGIC)
+}
+
+/// Writes a binary transcript record, including its digest, to `writer`.
+pub fn write_binary_record<W: std::io::Write>(
+    mut writer: W,
+    statement: &str,
+    transcript: &[u64],
+    round_sums: &[u64],
+    final_value: u64,
+) -> std::io::Result<()> {
+    let too_long = |what: &str| {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("{what} too long"))
+    };
+    let digest = compute_digest(transcript, round_sums, final_value);
+    let mut out = Vec::new();
+    out.extend_from_slice(BINARY_MAGIC);
+    out.push(BINARY_VERSION);
+    let statement_len = u32::try_from(statement.len()).map_err(|_| too_long("statement"))?;
+    out.extend_from_slice(&statement_len.to_be_bytes());
+    out.extend_from_slice(statement.as_bytes());
+    for values in [transcript, round_sums] {
+        let count = u32::try_from(values.len()).map_err(|_| too_long("transcript"))?;
+        out.extend_from_slice(&count.to_be_bytes());
+        for value in values {
+            out.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+    out.extend_from_slice(&final_value.to_be_bytes());
+    out.extend_from_slice(&digest);
+    writer.write_all(&out)
+}
+
+struct ByteCursor<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> ByteCursor<'a> {
+    fn take(&mut self, len: usize, what: &str) -> Result<&'a [u8], String> {
+        if self.bytes.len() < len {
+            return Err(format!("truncated binary record: missing {what}"));
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn u32(&mut self, what: &str) -> Result<u32, String> {
+        let bytes = self.take(4, what)?;
+        Ok(u32::from_be_bytes(bytes.try_into().expect("4 bytes")))
+    }
+
+    fn u64(&mut self, what: &str) -> Result<u64, String> {
+        let bytes = self.take(8, what)?;
+        Ok(u64::from_be_bytes(bytes.try_into().expect("8 bytes")))
+    }
+
+    fn vec_u64(&mut self, what: &str) -> Result<Vec<u64>, String> {
+        let count = self.u32(what)? as usize;
+        if self.bytes.len() / 8 < count {
+            return Err(format!("truncated binary record: missing {what}"));
+        }
+        (0..count).map(|_| self.u64(what)).collect()
+    }
+}
+
+/// Decodes a binary transcript record without checking its digest.
+pub fn read_binary_record(bytes: &[u8]) -> Result<BinaryRecord, String> {
+    if !is_binary_record(bytes) {
+        return Err("missing binary record magic".to_string());
+    }
+    let mut cursor = ByteCursor {
+        bytes: &bytes[BINARY_MAGIC.len()..],
+    };
+    let version = cursor.take(1, "version")?[0];
+    if version != BINARY_VERSION {
+        return Err(format!("unsupported binary record version {version}"));
+    }
+    let statement_len = cursor.u32("statement length")? as usize;
+    let statement = std::str::from_utf8(cursor.take(statement_len, "statement")?)
+        .map_err(|_| "statement is not valid UTF-8".to_string())?
+        .to_string();
+    let transcript = cursor.vec_u64("transcript")?;
+    let round_sums = cursor.vec_u64("round_sums")?;
+    let final_value = cursor.u64("final")?;
+    let mut digest = [0u8; 32];
+    digest.copy_from_slice(cursor.take(32, "digest")?);
+    if !cursor.bytes.is_empty() {
+        return Err("trailing bytes after binary record".to_string());
+    }
+    Ok(BinaryRecord {
+        statement,
+        transcript,
+        round_sums,
+        final_value,
+        digest,
+    })
+}
+
+/// Decodes a binary transcript record and verifies its stored digest.
+pub fn verify_binary_record(bytes: &[u8]) -> Result<BinaryRecord, String> {
+    let record = read_binary_record(bytes)?;
+    let computed = compute_digest(&record.transcript, &record.round_sums, record.final_value);
+    if computed == record.digest {
+        Ok(record)
+    } else {
+        Err("hash mismatch".to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{compute_digest, digest_to_hex, parse_record, verify_record_lines, write_record};
+    use super::{
+        compute_digest, digest_to_hex, is_binary_record, parse_record, verify_binary_record,
+        verify_record_lines, write_binary_record, write_compact_record, write_record,
+    };
+    use crate::{Field, GeneralSumProof, MultilinearPolynomial};
 
     #[test]
     fn test_write_record_format() {
@@ -230,4 +541,73 @@ mod tests {
         ];
         assert!(verify_record_lines(lines.iter().map(|s| s.as_str())).is_err());
     }
+
+    #[test]
+    fn test_binary_roundtrip_and_tampering() {
+        let mut bytes = Vec::new();
+        write_binary_record(&mut bytes, "Binary statement", &[10, 20], &[5, 7], 9).unwrap();
+        assert!(is_binary_record(&bytes));
+        let record = verify_binary_record(&bytes).unwrap();
+        assert_eq!(record.statement, "Binary statement");
+        assert_eq!(record.transcript, vec![10, 20]);
+        assert_eq!(record.round_sums, vec![5, 7]);
+        assert_eq!(record.digest, compute_digest(&[10, 20], &[5, 7], 9));
+
+        let final_offset = bytes.len() - 32 - 8;
+        bytes[final_offset + 7] ^= 1;
+        assert_eq!(verify_binary_record(&bytes).unwrap_err(), "hash mismatch");
+        assert!(verify_binary_record(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_compact_record_recomputes_challenges() {
+        let field = Field::new(101);
+        let poly = MultilinearPolynomial::from_evaluations(3, vec![3, 1, 4, 1, 5, 9, 2, 6]);
+        let proof = GeneralSumProof::prove(&poly, &field);
+        let write = |modulus, transcript: &[u64]| {
+            let mut lines = Vec::new();
+            write_compact_record(
+                |line| {
+                    lines.push(line.to_string());
+                    Ok(())
+                },
+                modulus,
+                transcript,
+                &proof.round_sums,
+                proof.final_evaluation,
+            )
+            .unwrap();
+            lines
+        };
+
+        let lines = write(101, &proof.challenges);
+        assert_eq!(
+            lines[0],
+            format!("sumcheck_seed:101 {}", proof.claim.claimed_sum)
+        );
+        let coefficients: Vec<String> = proof
+            .claim
+            .rounds
+            .iter()
+            .map(|(a, _)| a.to_string())
+            .collect();
+        assert_eq!(lines[1], format!("round_coeffs:{}", coefficients.join(" ")));
+        let (transcript, round_sums, final_value, hash) =
+            parse_record(lines.iter().map(|s| s.as_str())).unwrap();
+        assert_eq!(transcript, proof.challenges);
+        assert_eq!(round_sums, proof.round_sums);
+        assert_eq!(
+            hash,
+            compute_digest(&proof.challenges, &proof.round_sums, final_value)
+        );
+
+        let mut tampered = lines.clone();
+        tampered[1] = tampered[1].replacen(' ', " 1", 1);
+        assert!(verify_record_lines(tampered.iter().map(|s| s.as_str())).is_err());
+
+        let mut foreign = proof.challenges.clone();
+        foreign[0] = (foreign[0] + 1) % 101;
+        assert!(write(101, &foreign)[0].starts_with("transcript:"));
+        assert!(write(103, &proof.challenges)[0].starts_with("transcript:"));
+    }
 }
@@ -3,6 +3,11 @@
 //! This module sketches fee and staking policies that can be enforced by the
 //! networking layer to price blob submission and reward availability attestations.
 
+use serde::{Deserialize, Serialize};
+
+/// Asset id of the native unit; every other id names a secondary asset.
+pub const NATIVE_ASSET: &str = "native";
+
 /// Fee policy applied to blob submissions.
 #[derive(Debug, Clone)]
 pub struct FeePolicy {
@@ -28,3 +33,106 @@ pub fn compute_fee(policy: &FeePolicy, size: usize) -> u64 {
     let variable = policy.fee_per_byte.saturating_mul(size as u64);
     std::cmp::max(variable, policy.min_fee)
 }
+
+/// Fee schedule applied to ledger proof submissions (see `ProofLedger::submit_paid`).
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct SubmissionFeeSchedule {
+    /// Flat fee debited for every submitted proof.
+    pub base_fee: u64,
+    /// Additional fee per byte of auxiliary proof data.
+    #[serde(default)]
+    pub fee_per_byte: u64,
+    /// Whether the fee is refunded when the proof fails verification.
+    #[serde(default)]
+    pub refund_on_rejection: bool,
+    /// Asset the fee is paid in; `None` means [`NATIVE_ASSET`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset: Option<String>,
+}
+
+impl SubmissionFeeSchedule {
+    /// Fee owed for a proof carrying `data_len` bytes of auxiliary data.
+    pub fn fee_for(&self, data_len: usize) -> u64 {
+        self.fee_per_byte
+            .saturating_mul(data_len as u64)
+            .saturating_add(self.base_fee)
+    }
+
+    /// Asset id the fee is charged in.
+    pub fn fee_asset(&self) -> &str {
+        self.asset.as_deref().unwrap_or(NATIVE_ASSET)
+    }
+}
+
+/// Destination of the part of a fee that is not paid out as a reward.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum FeeSinkPolicy {
+    /// Remove the fee from supply.
+    #[default]
+    Burn,
+    /// Credit the whole fee to `account`.
+    Sink {
+        /// Base64 public key of the fee-sink account.
+        account: String,
+    },
+    /// Burn `burn_bps` basis points of the fee and credit the rest to `account`.
+    Split {
+        /// Base64 public key of the fee-sink account.
+        account: String,
+        /// Share of each fee burned, in basis points.
+        burn_bps: u16,
+    },
+}
+
+impl FeeSinkPolicy {
+    /// Whether every fee is burned.
+    pub fn is_burn(&self) -> bool {
+        matches!(self, Self::Burn)
+    }
+
+    /// Account credited with the sunk part of each fee, if any.
+    pub fn sink_account(&self) -> Option<&str> {
+        match self {
+            Self::Burn => None,
+            Self::Sink { account } | Self::Split { account, .. } => Some(account),
+        }
+    }
+
+    /// Splits `fee` into `(burned, sunk)`; the burned part rounds down.
+    pub fn divide(&self, fee: u64) -> (u64, u64) {
+        let burned = match self {
+            Self::Burn => fee,
+            Self::Sink { .. } => 0,
+            Self::Split { burn_bps, .. } => {
+                (fee as u128 * (*burn_bps).min(10_000) as u128 / 10_000) as u64
+            }
+        };
+        (burned, fee - burned)
+    }
+
+    /// Rejects an empty sink account or a burn share above 10000 bps.
+    pub fn validate(&self) -> Result<(), String> {
+        if self
+            .sink_account()
+            .is_some_and(|account| account.is_empty())
+        {
+            return Err("fee sink account must not be empty".into());
+        }
+        if let Self::Split { burn_bps, .. } = self {
+            if *burn_bps > 10_000 {
+                return Err(format!("burn_bps {burn_bps} exceeds 10000"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Account backend that pays proof submission fees.
+pub trait FeeAccounts {
+    /// Debits `fee` units of `asset` from the account identified by `public_key`.
+    fn debit_fee(&mut self, public_key: &[u8], asset: &str, fee: u64) -> Result<(), String>;
+
+    /// Returns a previously debited `fee` to the account.
+    fn refund_fee(&mut self, public_key: &[u8], asset: &str, fee: u64);
+}
@@ -104,7 +104,7 @@ impl Field {
     }
 }
 
-fn is_prime_u64(value: u64) -> bool {
+pub(crate) fn is_prime_u64(value: u64) -> bool {
     if value < 2 {
         return false;
     }
@@ -0,0 +1,385 @@
+//! FRI low-degree testing over NTT-friendly prime fields.
+//!
+//! [`FriProof`] implements the Fast Reed–Solomon IOP of Proximity over the
+//! power-of-two subgroups exposed by the NTT helpers.  A prover commits to the
+//! Reed–Solomon codeword of a polynomial with BLAKE2b-256 Merkle trees, folds
+//! it in half with Fiat–Shamir challenges until a constant remains, and opens
+//! the folding relation at transcript-derived query positions.  Verification
+//! needs nothing but hashing and field arithmetic, so no trusted setup is
+//! involved.
+//!
+//! FRI requires an NTT-friendly prime: the codeword domain must be a
+//! multiplicative subgroup, so `p - 1` needs a large power-of-two factor
+//! (for example `998_244_353` or `2^64 - 2^32 + 1`).
+
+use blake2::digest::{consts::U32, Digest};
+
+use crate::merkle::{merkle_layers, proof_from_layers};
+use crate::ntt::{low_degree_extend, root_of_unity};
+use crate::{verify_merkle_proof, Field, MerkleProof, Transcript, TranscriptDigest};
+
+type Blake2b256 = blake2::Blake2b<U32>;
+
+/// Domain tag used for the FRI Fiat–Shamir transcript.
+const FRI_DOMAIN: &[u8] = b"power_house:v1:fri";
+/// Domain tag applied to every committed codeword value.
+const FRI_LEAF_DOMAIN: &[u8] = b"MFENX_FRI_LEAF";
+
+/// Tunable FRI parameters shared by prover and verifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FriConfig {
+    /// Base-two logarithm of the Reed–Solomon blowup factor.
+    pub blowup_log: u32,
+    /// Number of query positions opened by the prover.
+    pub num_queries: usize,
+}
+
+impl Default for FriConfig {
+    /// Blowup 4 with 32 queries, roughly 64 bits of proximity soundness.
+    fn default() -> Self {
+        Self {
+            blowup_log: 2,
+            num_queries: 32,
+        }
+    }
+}
+
+/// Authenticated values for one folding pair `f(x)`, `f(-x)`.
+#[derive(Debug, Clone)]
+pub struct FriLayerOpening {
+    /// Codeword value at the lower half of the domain (`x`).
+    pub lower: u64,
+    /// Codeword value at the upper half of the domain (`-x`).
+    pub upper: u64,
+    /// Merkle inclusion proof for `lower`.
+    pub lower_path: MerkleProof,
+    /// Merkle inclusion proof for `upper`.
+    pub upper_path: MerkleProof,
+}
+
+/// Openings for a single query, one pair per folded layer.
+#[derive(Debug, Clone)]
+pub struct FriQuery {
+    /// Query position in the initial codeword domain.
+    pub index: usize,
+    /// Folding-pair openings ordered from the first layer to the last.
+    pub layers: Vec<FriLayerOpening>,
+}
+
+/// Non-interactive FRI proof that a committed codeword has low degree.
+#[derive(Debug, Clone)]
+pub struct FriProof {
+    /// Number of coefficients of the committed polynomial (a power of two).
+    pub degree_bound: usize,
+    /// Base-two logarithm of the blowup factor used by the prover.
+    pub blowup_log: u32,
+    /// Merkle roots of each folded codeword; the first one is the commitment.
+    pub layer_roots: Vec<TranscriptDigest>,
+    /// Constant left after folding every layer.
+    pub final_value: u64,
+    /// Query openings.
+    pub queries: Vec<FriQuery>,
+}
+
+impl FriProof {
+    /// Commits to `coefficients` and proves that the codeword has degree
+    /// below `coefficients.len()` rounded up to a power of two.
+    ///
+    /// The `context` words are absorbed before any commitment, binding the
+    /// proof to the statement it accompanies.  Returns `None` when fewer than
+    /// two coefficients are supplied or the field has no subgroup large enough
+    /// for the blown-up domain.
+    pub fn prove(
+        field: &Field,
+        coefficients: &[u64],
+        config: &FriConfig,
+        context: &[u64],
+    ) -> Option<Self> {
+        if coefficients.len() < 2 {
+            return None;
+        }
+        let degree_bound = coefficients.len().next_power_of_two();
+        let log_degree = degree_bound.trailing_zeros();
+        let domain_log = log_degree + config.blowup_log;
+        let mut codeword = low_degree_extend(field, coefficients, 1usize << domain_log)?;
+        let mut generator = root_of_unity(field, domain_log)?;
+
+        let mut transcript = fri_transcript(field, degree_bound, config, context);
+        let mut layers = Vec::with_capacity(log_degree as usize);
+        let mut layer_roots = Vec::with_capacity(log_degree as usize);
+        for _ in 0..log_degree {
+            let leaves: Vec<TranscriptDigest> = codeword.iter().map(|v| leaf_digest(*v)).collect();
+            let tree = merkle_layers(&leaves);
+            let root = tree[tree.len() - 1][0];
+            transcript.append_message(b"layer-root", &root);
+            layer_roots.push(root);
+            let beta = transcript.challenge(field);
+            let folded = fold_codeword(field, &codeword, generator, beta);
+            layers.push((codeword, leaves, tree));
+            codeword = folded;
+            generator = field.mul(generator, generator);
+        }
+        let final_value = codeword[0];
+        debug_assert!(codeword.iter().all(|value| *value == final_value));
+        transcript.append(final_value);
+
+        let domain_size = 1usize << domain_log;
+        let queries = (0..config.num_queries)
+            .map(|_| {
+                let index = query_index(&mut transcript, field, domain_size);
+                let mut position = index;
+                let openings = layers
+                    .iter()
+                    .map(|(values, leaves, tree)| {
+                        let half = values.len() / 2;
+                        let lower_idx = position % half;
+                        let upper_idx = lower_idx + half;
+                        position = lower_idx;
+                        FriLayerOpening {
+                            lower: values[lower_idx],
+                            upper: values[upper_idx],
+                            lower_path: proof_from_layers(tree, leaves[lower_idx], lower_idx),
+                            upper_path: proof_from_layers(tree, leaves[upper_idx], upper_idx),
+                        }
+                    })
+                    .collect();
+                FriQuery {
+                    index,
+                    layers: openings,
+                }
+            })
+            .collect();
+
+        Some(Self {
+            degree_bound,
+            blowup_log: config.blowup_log,
+            layer_roots,
+            final_value,
+            queries,
+        })
+    }
+
+    /// Returns the Merkle root committing to the initial codeword.
+    pub fn commitment(&self) -> Option<TranscriptDigest> {
+        self.layer_roots.first().copied()
+    }
+
+    /// Verifies the proof under `config` and the same `context` the prover used.
+    pub fn verify(&self, field: &Field, config: &FriConfig, context: &[u64]) -> bool {
+        self.verify_openings(field, config, context).is_some()
+    }
+
+    /// Verifies the proof and returns the authenticated initial-codeword
+    /// openings as `(domain point, value)` pairs.
+    ///
+    /// Callers use these openings to check that the committed polynomial
+    /// agrees with public data at random points.
+    pub fn verify_openings(
+        &self,
+        field: &Field,
+        config: &FriConfig,
+        context: &[u64],
+    ) -> Option<Vec<(u64, u64)>> {
+        if self.blowup_log != config.blowup_log
+            || self.queries.len() != config.num_queries
+            || self.degree_bound < 2
+            || !self.degree_bound.is_power_of_two()
+        {
+            return None;
+        }
+        let log_degree = self.degree_bound.trailing_zeros();
+        if self.layer_roots.len() != log_degree as usize {
+            return None;
+        }
+        // `degree_bound` is prover-supplied: validate the domain against the
+        // field before shifting so an oversized bound cannot overflow.
+        let domain_log = log_degree.checked_add(config.blowup_log)?;
+        let generator = root_of_unity(field, domain_log)?;
+        let domain_size = 1usize.checked_shl(domain_log)?;
+
+        let mut transcript = fri_transcript(field, self.degree_bound, config, context);
+        let mut betas = Vec::with_capacity(self.layer_roots.len());
+        for root in &self.layer_roots {
+            transcript.append_message(b"layer-root", root);
+            betas.push(transcript.challenge(field));
+        }
+        if self.final_value >= field.modulus() {
+            return None;
+        }
+        transcript.append(self.final_value);
+
+        let mut openings = Vec::with_capacity(self.queries.len() * 2);
+        for query in &self.queries {
+            let index = query_index(&mut transcript, field, domain_size);
+            if query.index != index || query.layers.len() != self.layer_roots.len() {
+                return None;
+            }
+            let mut position = index;
+            let mut layer_size = domain_size;
+            let mut layer_generator = generator;
+            let mut expected: Option<u64> = None;
+            for (layer_idx, opening) in query.layers.iter().enumerate() {
+                let half = layer_size / 2;
+                let lower_idx = position % half;
+                let upper_idx = lower_idx + half;
+                let root = &self.layer_roots[layer_idx];
+                let depth = layer_size.trailing_zeros() as usize;
+                if !opening_matches(&opening.lower_path, root, opening.lower, lower_idx, depth)
+                    || !opening_matches(&opening.upper_path, root, opening.upper, upper_idx, depth)
+                {
+                    return None;
+                }
+                if let Some(value) = expected {
+                    let opened = if position == lower_idx {
+                        opening.lower
+                    } else {
+                        opening.upper
+                    };
+                    if opened != value {
+                        return None;
+                    }
+                }
+                let point = field.pow(layer_generator, lower_idx as u64);
+                if layer_idx == 0 {
+                    openings.push((point, opening.lower));
+                    openings.push((field.sub(0, point), opening.upper));
+                }
+                expected = Some(fold_pair(
+                    field,
+                    opening.lower,
+                    opening.upper,
+                    point,
+                    betas[layer_idx],
+                ));
+                position = lower_idx;
+                layer_size = half;
+                layer_generator = field.mul(layer_generator, layer_generator);
+            }
+            if expected != Some(self.final_value) {
+                return None;
+            }
+        }
+        Some(openings)
+    }
+}
+
+fn fri_transcript(
+    field: &Field,
+    degree_bound: usize,
+    config: &FriConfig,
+    context: &[u64],
+) -> Transcript {
+    let mut transcript = Transcript::new(FRI_DOMAIN);
+    transcript.append(field.modulus());
+    transcript.append(degree_bound as u64);
+    transcript.append(u64::from(config.blowup_log));
+    transcript.append(config.num_queries as u64);
+    transcript.append(context.len() as u64);
+    transcript.append_slice(context);
+    transcript
+}
+
+fn query_index(transcript: &mut Transcript, field: &Field, domain_size: usize) -> usize {
+    (transcript.challenge(field) % domain_size as u64) as usize
+}
+
+fn leaf_digest(value: u64) -> TranscriptDigest {
+    let mut hasher = Blake2b256::new();
+    hasher.update(FRI_LEAF_DOMAIN);
+    hasher.update(value.to_be_bytes());
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&hasher.finalize());
+    out
+}
+
+fn opening_matches(
+    path: &MerkleProof,
+    root: &TranscriptDigest,
+    value: u64,
+    index: usize,
+    depth: usize,
+) -> bool {
+    path.root == *root
+        && path.leaf == leaf_digest(value)
+        && path.index == index
+        && path.path.len() == depth
+        && path
+            .path
+            .iter()
+            .enumerate()
+            .all(|(level, node)| node.left == ((index >> level) & 1 == 1))
+        && verify_merkle_proof(path)
+}
+
+/// Folds `f(x)` and `f(-x)` into `f_even(x²) + β·f_odd(x²)`.
+fn fold_pair(field: &Field, lower: u64, upper: u64, point: u64, beta: u64) -> u64 {
+    let two_inv = field.inv(2);
+    let even = field.mul(field.add(lower, upper), two_inv);
+    let odd = field.mul(field.sub(lower, upper), field.inv(field.mul(2, point)));
+    field.add(even, field.mul(beta, odd))
+}
+
+fn fold_codeword(field: &Field, codeword: &[u64], generator: u64, beta: u64) -> Vec<u64> {
+    let half = codeword.len() / 2;
+    let mut point = 1u64;
+    let mut folded = Vec::with_capacity(half);
+    for idx in 0..half {
+        folded.push(fold_pair(
+            field,
+            codeword[idx],
+            codeword[idx + half],
+            point,
+            beta,
+        ));
+        point = field.mul(point, generator);
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NTT_PRIME: u64 = 998_244_353;
+
+    #[test]
+    fn fri_accepts_low_degree_codeword() {
+        let field = Field::new(NTT_PRIME);
+        let config = FriConfig::default();
+        let coefficients: Vec<u64> = (1..=16).collect();
+        let proof = FriProof::prove(&field, &coefficients, &config, &[42]).unwrap();
+        assert_eq!(proof.layer_roots.len(), 4);
+        assert!(proof.verify(&field, &config, &[42]));
+        assert!(!proof.verify(&field, &config, &[43]));
+    }
+
+    #[test]
+    fn fri_rejects_tampered_openings() {
+        let field = Field::new(NTT_PRIME);
+        let config = FriConfig::default();
+        let coefficients: Vec<u64> = (1..=8).collect();
+        let mut proof = FriProof::prove(&field, &coefficients, &config, &[]).unwrap();
+        proof.queries[0].layers[1].lower = field.add(proof.queries[0].layers[1].lower, 1);
+        assert!(!proof.verify(&field, &config, &[]));
+
+        let mut proof = FriProof::prove(&field, &coefficients, &config, &[]).unwrap();
+        proof.final_value = field.add(proof.final_value, 1);
+        assert!(!proof.verify(&field, &config, &[]));
+    }
+
+    #[test]
+    fn fri_rejects_oversized_degree_bound() {
+        let field = Field::new(NTT_PRIME);
+        let config = FriConfig::default();
+        let mut proof = FriProof::prove(&field, &[1, 2, 3, 4], &config, &[]).unwrap();
+        proof.degree_bound = 1 << 63;
+        proof.layer_roots = vec![proof.layer_roots[0]; 63];
+        assert!(proof.verify_openings(&field, &config, &[]).is_none());
+    }
+
+    #[test]
+    fn fri_requires_ntt_friendly_field() {
+        let field = Field::new(1_000_000_007);
+        assert!(FriProof::prove(&field, &[1, 2, 3, 4], &FriConfig::default(), &[]).is_none());
+    }
+}
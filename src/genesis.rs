@@ -0,0 +1,220 @@
+//! Network genesis configuration.
+//!
+//! A [`GenesisConfig`] names a network (the `network` field carried by every
+//! anchor, envelope and vote), its genesis statement, the EVM chain id served
+//! by the wallet RPC, the initial native-chain allocations and the initial
+//! validator set. Everything is hashed into the network's genesis digest, which
+//! becomes the transcript hash of the genesis ledger entry; two networks with
+//! different configs can therefore never reconcile each other's anchors.
+//!
+//! The default config describes the public MFENX Power-House network and keeps
+//! its historically pinned digest. A process switches networks once, at
+//! startup, with [`install_genesis`]; every later read goes through
+//! [`active_genesis`].
+
+use crate::{julian::JULIAN_GENESIS_DIGEST, TranscriptDigest};
+use blake2::digest::{consts::U32, Digest};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::Path, sync::OnceLock};
+
+/// Schema identifier of a genesis config file.
+pub const GENESIS_CONFIG_SCHEMA: &str = "mfenx.powerhouse.genesis.v1";
+/// Network identifier of the default (public) network.
+pub const DEFAULT_NETWORK_ID: &str = "MFENX-POWERHOUSE";
+/// Genesis statement of the default network.
+pub const DEFAULT_GENESIS_STATEMENT: &str = "JULIAN::GENESIS";
+/// EVM chain id of the default network.
+pub const DEFAULT_CHAIN_ID: u64 = 177155;
+
+const GENESIS_DIGEST_DOMAIN: &[u8] = b"MFENX_GENESIS_V1";
+const MAX_NETWORK_ID_LEN: usize = 64;
+
+type Blake2b256 = blake2::Blake2b<U32>;
+
+/// Deterministic description of a network's genesis.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenesisConfig {
+    /// Schema identifier (`mfenx.powerhouse.genesis.v1`).
+    pub schema: String,
+    /// Network identifier embedded in anchors, envelopes and votes.
+    pub network_id: String,
+    /// Statement of the genesis ledger entry.
+    pub genesis_statement: String,
+    /// EVM chain id exposed by the wallet RPC.
+    pub chain_id: u64,
+    /// Initial native-chain balances keyed by `0x` address or base64 public key.
+    #[serde(default)]
+    pub allocations: BTreeMap<String, u64>,
+    /// Initial validator set (base64 ed25519 public keys), kept sorted.
+    #[serde(default)]
+    pub validators: Vec<String>,
+}
+
+impl Default for GenesisConfig {
+    fn default() -> Self {
+        Self {
+            schema: GENESIS_CONFIG_SCHEMA.to_string(),
+            network_id: DEFAULT_NETWORK_ID.to_string(),
+            genesis_statement: DEFAULT_GENESIS_STATEMENT.to_string(),
+            chain_id: DEFAULT_CHAIN_ID,
+            allocations: BTreeMap::new(),
+            validators: Vec::new(),
+        }
+    }
+}
+
+impl GenesisConfig {
+    /// Reads, normalises and validates a genesis config file.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let bytes = fs::read(path)
+            .map_err(|err| format!("failed to read genesis config {}: {err}", path.display()))?;
+        let mut config: Self = serde_json::from_slice(&bytes)
+            .map_err(|err| format!("invalid genesis config {}: {err}", path.display()))?;
+        config.validators.sort();
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks identifiers, the chain id and the validator set.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.schema != GENESIS_CONFIG_SCHEMA {
+            return Err(format!("unsupported genesis schema {}", self.schema));
+        }
+        let id_ok = !self.network_id.is_empty()
+            && self.network_id.len() <= MAX_NETWORK_ID_LEN
+            && self
+                .network_id
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'));
+        if !id_ok {
+            return Err(format!("invalid network id '{}'", self.network_id));
+        }
+        if self.genesis_statement.trim().is_empty() {
+            return Err("genesis statement must not be empty".to_string());
+        }
+        if self.chain_id == 0 {
+            return Err("genesis chain id must be non-zero".to_string());
+        }
+        if self.validators.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err("genesis validators must be sorted and unique".to_string());
+        }
+        if self.validators.iter().any(|key| key.trim().is_empty()) {
+            return Err("genesis validators must not be empty".to_string());
+        }
+        Ok(())
+    }
+
+    /// Whether this is the default public network.
+    pub fn is_default_network(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Network-specific genesis digest.
+    ///
+    /// The default network keeps the pinned [`JULIAN_GENESIS_DIGEST`]; any other
+    /// config hashes its canonical JSON encoding under a domain separator.
+    pub fn digest(&self) -> TranscriptDigest {
+        if self.is_default_network() {
+            return JULIAN_GENESIS_DIGEST;
+        }
+        let encoded = serde_json::to_vec(self).expect("genesis config serialises");
+        let mut hasher = Blake2b256::new();
+        hasher.update(GENESIS_DIGEST_DOMAIN);
+        hasher.update(encoded);
+        hasher.finalize().into()
+    }
+}
+
+struct InstalledGenesis {
+    config: GenesisConfig,
+    digest: TranscriptDigest,
+}
+
+static ACTIVE_GENESIS: OnceLock<InstalledGenesis> = OnceLock::new();
+
+fn installed() -> &'static InstalledGenesis {
+    ACTIVE_GENESIS.get_or_init(|| {
+        let config = GenesisConfig::default();
+        InstalledGenesis {
+            digest: config.digest(),
+            config,
+        }
+    })
+}
+
+/// Makes `config` the process-wide genesis.
+///
+/// Must run before any anchor is built or decoded. Installing the same config
+/// twice is a no-op; switching to a different one after first use fails.
+pub fn install_genesis(config: GenesisConfig) -> Result<(), String> {
+    config.validate()?;
+    let digest = config.digest();
+    let active = ACTIVE_GENESIS.get_or_init(|| InstalledGenesis {
+        config: config.clone(),
+        digest,
+    });
+    if active.config != config {
+        return Err(format!(
+            "genesis for network {} is already active",
+            active.config.network_id
+        ));
+    }
+    Ok(())
+}
+
+/// Genesis config of the running process (the default network unless
+/// [`install_genesis`] ran first).
+pub fn active_genesis() -> &'static GenesisConfig {
+    &installed().config
+}
+
+/// Digest of [`active_genesis`].
+pub fn active_genesis_digest() -> TranscriptDigest {
+    installed().digest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_network_keeps_pinned_digest_and_testnets_diverge() {
+        let mainnet = GenesisConfig::default();
+        mainnet.validate().unwrap();
+        assert_eq!(mainnet.digest(), JULIAN_GENESIS_DIGEST);
+
+        let mut testnet = GenesisConfig {
+            network_id: "MFENX-TESTNET".to_string(),
+            chain_id: 177156,
+            ..GenesisConfig::default()
+        };
+        testnet.allocations.insert("0xabc".to_string(), 10);
+        let digest = testnet.digest();
+        assert_ne!(digest, JULIAN_GENESIS_DIGEST);
+        assert_eq!(digest, testnet.clone().digest());
+        let mut richer = testnet.clone();
+        richer.allocations.insert("0xabc".to_string(), 11);
+        assert_ne!(richer.digest(), digest);
+
+        let dir = std::env::temp_dir().join(format!(
+            "power_house_genesis_{}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("genesis.json");
+        testnet.validators = vec!["validator-b".to_string(), "validator-a".to_string()];
+        fs::write(&path, serde_json::to_vec(&testnet).unwrap()).unwrap();
+        let loaded = GenesisConfig::load(&path).unwrap();
+        assert_eq!(loaded.validators, vec!["validator-a", "validator-b"]);
+
+        testnet.network_id = "bad network|id".to_string();
+        fs::write(&path, serde_json::to_vec(&testnet).unwrap()).unwrap();
+        assert!(GenesisConfig::load(&path)
+            .unwrap_err()
+            .contains("invalid network id"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,443 @@
+//! Append-only transcript journal with framed, checksummed records.
+//!
+//! [`write_text_series`](crate::write_text_series) emits one small file per
+//! transcript and never syncs it.  The journal instead appends framed records
+//! to rolling segment files (`ledger_journal_000000.phj`, ...) with an explicit
+//! [`FsyncPolicy`].  Every segment starts with an 8-byte magic header and every
+//! frame is laid out as:
+//!
+//! ```text
+//! u32 BE payload length | u32 BE CRC-32 of payload | payload bytes
+//! ```
+//!
+//! The payload is the same newline-delimited ASCII record accepted by
+//! [`parse_log_file`](crate::parse_log_file), so the journal stays
+//! human-inspectable after stripping the frame headers.  A frame that runs
+//! past the end of its segment is treated as a torn write from a crash and
+//! ignored; a checksum mismatch is always reported as corruption.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Magic header written at the start of every journal segment.
+pub const JOURNAL_MAGIC: &[u8; 8] = b"PHJRNL01";
+/// File name prefix shared by every journal segment.
+pub const JOURNAL_SEGMENT_PREFIX: &str = "ledger_journal_";
+/// File extension used by journal segments.
+pub const JOURNAL_SEGMENT_EXTENSION: &str = "phj";
+
+const FRAME_HEADER_BYTES: usize = 8;
+const MAX_FRAME_BYTES: usize = 16 * 1024 * 1024;
+
+/// Durability policy applied after each appended record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FsyncPolicy {
+    /// `fsync` after every record.
+    #[default]
+    Always,
+    /// `fsync` at most once per interval (and on roll/close).
+    Interval(Duration),
+    /// Only flush userspace buffers; leave durability to the OS.
+    Never,
+}
+
+/// Configuration for [`TranscriptJournal`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JournalConfig {
+    /// Segment size after which the writer rolls to a new file.
+    pub max_segment_bytes: u64,
+    /// Durability policy for appended records.
+    pub fsync: FsyncPolicy,
+}
+
+impl Default for JournalConfig {
+    fn default() -> Self {
+        Self {
+            max_segment_bytes: 64 * 1024 * 1024,
+            fsync: FsyncPolicy::Always,
+        }
+    }
+}
+
+/// Location of a record inside the journal.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JournalPosition {
+    /// Segment index (the numeric suffix of the segment file).
+    pub segment: u64,
+    /// Zero-based record index within the segment.
+    pub record: u64,
+}
+
+impl JournalPosition {
+    /// Returns the stable record name `ledger_journal_NNNNNN.phj#RRRRRRRR`.
+    ///
+    /// Names sort after legacy `ledger_NNNN.txt` files and in append order,
+    /// so they can be used directly as checkpoint log cutoffs.
+    pub fn record_name(&self) -> String {
+        format!("{}#{:08}", segment_file_name(self.segment), self.record)
+    }
+}
+
+/// Buffered writer that appends framed transcript records to rolling segments.
+#[derive(Debug)]
+pub struct TranscriptJournal {
+    dir: PathBuf,
+    config: JournalConfig,
+    segment: u64,
+    records_in_segment: u64,
+    segment_bytes: u64,
+    writer: BufWriter<File>,
+    last_sync: Instant,
+}
+
+impl TranscriptJournal {
+    /// Opens a journal in `dir`, starting a fresh segment after any existing ones.
+    ///
+    /// Existing segments are never reopened for writing, so a torn tail left
+    /// by a crash stays isolated at the end of its own segment.
+    pub fn open(dir: impl AsRef<Path>, config: JournalConfig) -> io::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        let segment = list_segments(&dir)?
+            .last()
+            .map(|(index, _)| index + 1)
+            .unwrap_or(0);
+        let writer = create_segment(&dir, segment)?;
+        Ok(Self {
+            dir,
+            config,
+            segment,
+            records_in_segment: 0,
+            segment_bytes: JOURNAL_MAGIC.len() as u64,
+            writer,
+            last_sync: Instant::now(),
+        })
+    }
+
+    /// Returns the path of the segment currently being written.
+    pub fn current_segment_path(&self) -> PathBuf {
+        self.dir.join(segment_file_name(self.segment))
+    }
+
+    /// Appends one record made of newline-delimited `lines`.
+    ///
+    /// Returns the position of the record once it has been written and
+    /// synchronised according to the configured [`FsyncPolicy`].
+    pub fn append(&mut self, lines: &[String]) -> io::Result<JournalPosition> {
+        let mut payload = Vec::new();
+        for line in lines {
+            payload.extend_from_slice(line.as_bytes());
+            payload.push(b'\n');
+        }
+        if payload.len() > MAX_FRAME_BYTES {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "journal record exceeds maximum frame size",
+            ));
+        }
+        let frame_len = (FRAME_HEADER_BYTES + payload.len()) as u64;
+        if self.records_in_segment > 0
+            && self.segment_bytes + frame_len > self.config.max_segment_bytes
+        {
+            self.roll()?;
+        }
+        self.writer
+            .write_all(&(payload.len() as u32).to_be_bytes())?;
+        self.writer.write_all(&crc32(&payload).to_be_bytes())?;
+        self.writer.write_all(&payload)?;
+        let position = JournalPosition {
+            segment: self.segment,
+            record: self.records_in_segment,
+        };
+        self.records_in_segment += 1;
+        self.segment_bytes += frame_len;
+        self.writer.flush()?;
+        match self.config.fsync {
+            FsyncPolicy::Always => self.sync()?,
+            FsyncPolicy::Interval(interval) if self.last_sync.elapsed() >= interval => {
+                self.sync()?
+            }
+            FsyncPolicy::Interval(_) | FsyncPolicy::Never => {}
+        }
+        Ok(position)
+    }
+
+    /// Flushes buffered bytes and forces them to stable storage.
+    pub fn sync(&mut self) -> io::Result<()> {
+        self.writer.flush()?;
+        self.writer.get_ref().sync_data()?;
+        self.last_sync = Instant::now();
+        Ok(())
+    }
+
+    fn roll(&mut self) -> io::Result<()> {
+        if self.config.fsync != FsyncPolicy::Never {
+            self.sync()?;
+        }
+        self.segment += 1;
+        self.writer = create_segment(&self.dir, self.segment)?;
+        self.records_in_segment = 0;
+        self.segment_bytes = JOURNAL_MAGIC.len() as u64;
+        Ok(())
+    }
+}
+
+impl Drop for TranscriptJournal {
+    fn drop(&mut self) {
+        let _ = self.writer.flush();
+        if self.config.fsync != FsyncPolicy::Never {
+            let _ = self.writer.get_ref().sync_data();
+        }
+    }
+}
+
+/// A single record read back from the journal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JournalRecord {
+    /// Where the record lives.
+    pub position: JournalPosition,
+    /// Record payload as UTF-8 text.
+    pub text: String,
+}
+
+/// Iterator over every record in a journal directory, in append order.
+#[derive(Debug)]
+pub struct JournalReader {
+    segments: std::vec::IntoIter<(u64, PathBuf)>,
+    current: Option<(u64, PathBuf, Vec<u8>, usize, u64)>,
+}
+
+impl JournalReader {
+    /// Opens every journal segment in `dir`.  A missing directory yields an
+    /// empty reader.
+    pub fn open(dir: impl AsRef<Path>) -> io::Result<Self> {
+        let segments = match list_segments(dir.as_ref()) {
+            Ok(segments) => segments,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(err),
+        };
+        Ok(Self {
+            segments: segments.into_iter(),
+            current: None,
+        })
+    }
+}
+
+impl Iterator for JournalReader {
+    type Item = Result<JournalRecord, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.current.is_none() {
+                let (index, path) = self.segments.next()?;
+                let mut bytes = Vec::new();
+                if let Err(err) = File::open(&path).and_then(|mut f| f.read_to_end(&mut bytes)) {
+                    return Some(Err(format!("failed to read {}: {err}", path.display())));
+                }
+                if bytes.len() < JOURNAL_MAGIC.len() {
+                    // A segment created right before a crash may lack its header.
+                    continue;
+                }
+                if &bytes[..JOURNAL_MAGIC.len()] != JOURNAL_MAGIC {
+                    return Some(Err(format!(
+                        "{} is not a transcript journal segment",
+                        path.display()
+                    )));
+                }
+                self.current = Some((index, path, bytes, JOURNAL_MAGIC.len(), 0));
+            }
+            let (segment, path, bytes, offset, record) = self.current.as_mut()?;
+            let remaining = bytes.len() - *offset;
+            if remaining < FRAME_HEADER_BYTES {
+                self.current = None;
+                continue;
+            }
+            let header = &bytes[*offset..*offset + FRAME_HEADER_BYTES];
+            let len = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+            let stored_crc = u32::from_be_bytes([header[4], header[5], header[6], header[7]]);
+            if len > remaining - FRAME_HEADER_BYTES {
+                // Torn write at the end of the segment.
+                self.current = None;
+                continue;
+            }
+            let start = *offset + FRAME_HEADER_BYTES;
+            let payload = &bytes[start..start + len];
+            let position = JournalPosition {
+                segment: *segment,
+                record: *record,
+            };
+            *offset = start + len;
+            *record += 1;
+            if crc32(payload) != stored_crc {
+                let err = format!(
+                    "{} record {} checksum mismatch",
+                    path.display(),
+                    position.record
+                );
+                self.current = None;
+                return Some(Err(err));
+            }
+            return Some(match String::from_utf8(payload.to_vec()) {
+                Ok(text) => Ok(JournalRecord { position, text }),
+                Err(_) => Err(format!(
+                    "{} record {} is not valid UTF-8",
+                    path.display(),
+                    position.record
+                )),
+            });
+        }
+    }
+}
+
+/// Returns true when `path` names a journal segment file.
+pub fn is_journal_segment(path: &Path) -> bool {
+    parse_segment_index(path).is_some()
+}
+
+/// Returns the record name of the last complete record in `dir`, if any.
+pub fn latest_journal_record(dir: &Path) -> Option<String> {
+    JournalReader::open(dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .last()
+        .map(|record| record.position.record_name())
+}
+
+fn segment_file_name(index: u64) -> String {
+    format!("{JOURNAL_SEGMENT_PREFIX}{index:06}.{JOURNAL_SEGMENT_EXTENSION}")
+}
+
+fn parse_segment_index(path: &Path) -> Option<u64> {
+    let name = path.file_name()?.to_str()?;
+    name.strip_prefix(JOURNAL_SEGMENT_PREFIX)?
+        .strip_suffix(JOURNAL_SEGMENT_EXTENSION)?
+        .strip_suffix('.')?
+        .parse()
+        .ok()
+}
+
+fn list_segments(dir: &Path) -> io::Result<Vec<(u64, PathBuf)>> {
+    let mut segments: Vec<(u64, PathBuf)> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file())
+        .filter_map(|path| parse_segment_index(&path).map(|index| (index, path)))
+        .collect();
+    segments.sort();
+    Ok(segments)
+}
+
+fn create_segment(dir: &Path, index: u64) -> io::Result<BufWriter<File>> {
+    let file = OpenOptions::new()
+        .create_new(true)
+        .write(true)
+        .open(dir.join(segment_file_name(index)))?;
+    let mut writer = BufWriter::new(file);
+    writer.write_all(JOURNAL_MAGIC)?;
+    Ok(writer)
+}
+
+/// CRC-32 (IEEE 802.3, reflected, polynomial `0xEDB88320`).
+pub(crate) fn crc32(bytes: &[u8]) -> u32 {
+    const TABLE: [u32; 256] = {
+        let mut table = [0u32; 256];
+        let mut idx = 0;
+        while idx < 256 {
+            let mut crc = idx as u32;
+            let mut bit = 0;
+            while bit < 8 {
+                crc = if crc & 1 == 1 {
+                    (crc >> 1) ^ 0xEDB8_8320
+                } else {
+                    crc >> 1
+                };
+                bit += 1;
+            }
+            table[idx] = crc;
+            idx += 1;
+        }
+        table
+    };
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in bytes {
+        crc = TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        std::env::temp_dir().join(format!("power_house_{name}_{unique}"))
+    }
+
+    fn record(idx: usize) -> Vec<String> {
+        vec![format!("statement:record {idx}"), "final:0".to_string()]
+    }
+
+    #[test]
+    fn crc32_matches_reference_vector() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn journal_roundtrip_rolls_segments() {
+        let dir = temp_dir("journal_roll");
+        let config = JournalConfig {
+            max_segment_bytes: 64,
+            fsync: FsyncPolicy::Never,
+        };
+        {
+            let mut journal = TranscriptJournal::open(&dir, config.clone()).unwrap();
+            for idx in 0..5 {
+                journal.append(&record(idx)).unwrap();
+            }
+        }
+        let mut journal = TranscriptJournal::open(&dir, config).unwrap();
+        let position = journal.append(&record(5)).unwrap();
+        drop(journal);
+
+        let records: Vec<JournalRecord> = JournalReader::open(&dir)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(records.len(), 6);
+        assert!(records.last().unwrap().position.segment >= 2);
+        assert_eq!(records.last().unwrap().position, position);
+        assert_eq!(records[3].text, "statement:record 3\nfinal:0\n");
+        assert_eq!(latest_journal_record(&dir), Some(position.record_name()));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn reader_skips_torn_tail_and_rejects_corruption() {
+        let dir = temp_dir("journal_corrupt");
+        let path = {
+            let mut journal = TranscriptJournal::open(&dir, JournalConfig::default()).unwrap();
+            journal.append(&record(0)).unwrap();
+            journal.append(&record(1)).unwrap();
+            journal.current_segment_path()
+        };
+        let mut bytes = fs::read(&path).unwrap();
+        bytes.extend_from_slice(&[0, 0, 1, 0, 0, 0]);
+        fs::write(&path, &bytes).unwrap();
+        let records: Vec<_> = JournalReader::open(&dir).unwrap().collect();
+        assert_eq!(records.len(), 2);
+        assert!(records.iter().all(Result::is_ok));
+
+        let last = bytes.len() - 8;
+        bytes[last] ^= 0x01;
+        fs::write(&path, &bytes).unwrap();
+        let records: Vec<_> = JournalReader::open(&dir).unwrap().collect();
+        assert!(records.iter().any(Result::is_err));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -21,7 +21,9 @@
 //! * **Finality predicate**: `Final(anchors, quorum)` holds when at least
 //!   *quorum* anchors agree on every statement/hash pair.  In code this is
 //!   [`reconcile_anchors_with_quorum`].  Once the predicate returns `Ok(())`
-//!   the JULIAN ledger state is final.
+//!   the JULIAN ledger state is final.  Under a stake-weighted policy the
+//!   predicate counts bonded weight instead of identities; see
+//!   [`reconcile_anchors_with_weighted_quorum`].
 //!
 //! ## Multi-node reconciliation protocol
 //!
@@ -38,22 +40,232 @@
 //! deterministic anchor reconciliation.
 
 use crate::{
-    merkle_root, transcript_digest, write_text_series, write_transcript_record, ChainedSumProof,
-    Field, GeneralSumProof, MultilinearPolynomial, StreamingPolynomial, SumClaim, TranscriptDigest,
+    economics::{FeeAccounts, SubmissionFeeSchedule},
+    iter_ledger_logs, merkle_root,
+    statement_schema::{StatementSchema, StatementSchemaRegistry, StatementViolation},
+    transcript_digest,
+    verification_cache::{bound_proof_digest, proof_digest, CachedVerification, VerificationCache},
+    write_compact_transcript_record, write_text_series, write_transcript_record, ChainedSumProof,
+    Field, GeneralSumProof, IncrementalMerkleTree, JournalConfig, MultilinearPolynomial,
+    RangeProof, StreamingPolynomial, SumClaim, TranscriptDigest, TranscriptJournal,
 };
 use blake2::digest::{consts::U32, Digest};
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    io,
+    path::{Path, PathBuf},
+    sync::mpsc,
+    time::{Duration, Instant},
+};
 
 const ANCHOR_DOMAIN: &[u8] = b"MFENX_ANCHOR";
+const STATEMENT_DOMAIN: &[u8] = b"MFENX_STATEMENT";
+const SUBMISSION_DOMAIN: &[u8] = b"MFENX_SUBMISSION";
+
+/// Prefix marking the canonical serialization of a structured [`Statement`].
+pub const STATEMENT_SCHEMA_PREFIX: &str = "stmt:v1";
 
 /// Represents a statement to be proved.  In a full system this would
 /// encapsulate the input and the specification of the language `L`.
-#[derive(Debug, Clone)]
+///
+/// A statement is either *legacy* (a free-form description, kept for logs
+/// written before structured statements existed) or *structured* (an
+/// application id, a claim type and a sorted parameter map).  Either way the
+/// ledger anchors [`Statement::canonical`], so legacy statements hash exactly
+/// as they always have.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
 pub struct Statement {
+    /// Application namespace that issued the claim; empty for legacy statements.
+    pub application_id: String,
+    /// Kind of claim within the application (for example `sumcheck`).
+    pub claim_type: String,
+    /// Claim parameters, ordered by key for canonical serialization.
+    pub parameters: BTreeMap<String, String>,
     /// A human-readable description of the claim.
     pub description: String,
 }
 
+impl Statement {
+    /// Creates a legacy statement from a free-form description.
+    pub fn new(description: impl Into<String>) -> Self {
+        Self {
+            description: description.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Creates a structured statement for `application_id` and `claim_type`.
+    pub fn structured(application_id: impl Into<String>, claim_type: impl Into<String>) -> Self {
+        Self {
+            application_id: application_id.into(),
+            claim_type: claim_type.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Adds (or replaces) a claim parameter.
+    pub fn with_parameter(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.parameters.insert(key.into(), value.into());
+        self
+    }
+
+    /// Sets the human-readable description.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Returns `true` when the statement carries an application id and claim type.
+    pub fn is_structured(&self) -> bool {
+        !self.application_id.is_empty()
+    }
+
+    /// Application id of a structured statement.
+    pub fn application_id(&self) -> Option<&str> {
+        self.is_structured().then_some(self.application_id.as_str())
+    }
+
+    /// Canonical single-line serialization anchored in logs and digests.
+    ///
+    /// Legacy statements serialize to their description unchanged.  Structured
+    /// statements serialize as
+    /// `stmt:v1|app=<id>|claim=<type>|param.<key>=<value>...|desc=<text>` with
+    /// `%`, `|`, `=` and line breaks percent-encoded and parameters in key order.
+    pub fn canonical(&self) -> String {
+        if !self.is_structured() {
+            return self.description.clone();
+        }
+        let mut out = String::from(STATEMENT_SCHEMA_PREFIX);
+        out.push_str("|app=");
+        out.push_str(&escape_statement_field(&self.application_id));
+        out.push_str("|claim=");
+        out.push_str(&escape_statement_field(&self.claim_type));
+        for (key, value) in &self.parameters {
+            out.push_str("|param.");
+            out.push_str(&escape_statement_field(key));
+            out.push('=');
+            out.push_str(&escape_statement_field(value));
+        }
+        if !self.description.is_empty() {
+            out.push_str("|desc=");
+            out.push_str(&escape_statement_field(&self.description));
+        }
+        out
+    }
+
+    /// Parses a canonical statement string.
+    ///
+    /// Anything that is not a well-formed canonical structured statement (including
+    /// every statement logged before the schema existed) is returned as a legacy
+    /// statement, so `Statement::parse(s).canonical() == s` for every input.
+    pub fn parse(text: &str) -> Self {
+        parse_structured_statement(text)
+            .filter(|statement| statement.canonical() == text)
+            .unwrap_or_else(|| Self::new(text))
+    }
+}
+
+impl From<&str> for Statement {
+    fn from(description: &str) -> Self {
+        Self::new(description)
+    }
+}
+
+impl From<String> for Statement {
+    fn from(description: String) -> Self {
+        Self::new(description)
+    }
+}
+
+impl std::fmt::Display for Statement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.canonical())
+    }
+}
+
+fn escape_statement_field(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '%' | '|' | '=' | '\n' | '\r' => out.push_str(&format!("%{:02X}", ch as u32)),
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+fn unescape_statement_field(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if bytes[idx] == b'%' {
+            let hex = value.get(idx + 1..idx + 3)?;
+            out.push(u8::from_str_radix(hex, 16).ok()?);
+            idx += 3;
+        } else {
+            out.push(bytes[idx]);
+            idx += 1;
+        }
+    }
+    String::from_utf8(out).ok()
+}
+
+fn parse_structured_statement(text: &str) -> Option<Statement> {
+    let mut fields = text.split('|');
+    if fields.next()? != STATEMENT_SCHEMA_PREFIX {
+        return None;
+    }
+    let application_id = unescape_statement_field(fields.next()?.strip_prefix("app=")?)?;
+    let claim_type = unescape_statement_field(fields.next()?.strip_prefix("claim=")?)?;
+    let mut statement = Statement::structured(application_id, claim_type);
+    for field in fields {
+        if let Some(param) = field.strip_prefix("param.") {
+            let (key, value) = param.split_once('=')?;
+            statement = statement.with_parameter(
+                unescape_statement_field(key)?,
+                unescape_statement_field(value)?,
+            );
+        } else {
+            statement.description = unescape_statement_field(field.strip_prefix("desc=")?)?;
+        }
+    }
+    Some(statement)
+}
+
+/// Attribution attached to a ledger entry by whoever submitted it.
+///
+/// The ledger stores the bytes as given; signature checks live with the key
+/// handling code (see `net::verify_entry_submission` under the `net` feature).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EntrySubmission {
+    /// Submitter public key bytes.
+    pub public_key: Vec<u8>,
+    /// Signature over [`statement_digest`] of the entry's canonical statement.
+    pub signature: Vec<u8>,
+    /// Submission time in milliseconds since the Unix epoch.
+    pub submitted_ms: u64,
+}
+
+/// Submission fee charged for a ledger entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EntryFee {
+    /// Fee debited from the submitter's account.
+    pub paid: u64,
+    /// Whether the fee was returned because the proof was rejected.
+    pub refunded: bool,
+}
+
+/// Domain-separated digest of a canonical statement string, signed by submitters.
+pub fn statement_digest(statement: &str) -> TranscriptDigest {
+    let mut hasher = Blake2b256::new();
+    hasher.update(STATEMENT_DOMAIN);
+    mix_bytes(&mut hasher, statement.as_bytes());
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&hasher.finalize());
+    out
+}
+
 /// Different proof payloads that the ledger understands.
 #[derive(Debug, Clone)]
 pub enum ProofKind {
@@ -80,6 +292,11 @@ pub enum ProofKind {
         /// Chained sum-check proof object.
         proof: ChainedSumProof,
     },
+    /// A range check showing every value fits in the proof's bit width.
+    Range(RangeProof),
+    /// Transcripts reconstructed by [`ProofLedger::replay_from_logs`].  The
+    /// original proof object is not retained, so it cannot be resubmitted.
+    Replayed,
     /// The JULIAN protocol genesis anchor.
     Genesis,
 }
@@ -117,8 +334,185 @@ pub struct LedgerEntry {
     pub hashes: Vec<TranscriptDigest>,
     /// Merkle root over the transcript hashes.
     pub merkle_root: TranscriptDigest,
+    /// Optional submitter attribution.
+    pub submission: Option<EntrySubmission>,
+    /// Submission fee charged through [`ProofLedger::submit_paid`].
+    pub fee: Option<EntryFee>,
+    /// Limit that rejected the proof before or during verification, if any.
+    pub rejection: Option<VerificationRejection>,
 }
 
+/// Notification delivered to [`ProofLedger::subscribe`] receivers when an
+/// entry is recorded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LedgerEvent {
+    /// Position of the entry in [`ProofLedger::entries`].
+    pub index: usize,
+    /// Canonical statement of the entry.
+    pub statement: String,
+    /// Whether the proof was accepted.
+    pub accepted: bool,
+    /// Limit that rejected the proof, if any.
+    pub rejection: Option<VerificationRejection>,
+    /// Ticket returned by [`SubmissionQueue::enqueue`](crate::SubmissionQueue::enqueue)
+    /// for queued submissions.
+    pub ticket: Option<u64>,
+}
+
+/// Resource limits enforced on every proof submitted to a [`ProofLedger`].
+///
+/// The structural limits are checked before any verifier work, so an
+/// oversized polynomial or chain is turned away without being evaluated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerificationLimits {
+    /// Largest number of variables of any polynomial or sum-check claim.  Range
+    /// proofs count the variables of their constraint table.
+    pub max_vars: usize,
+    /// Largest number of links in a chained proof.
+    pub max_chain_links: usize,
+    /// Wall-clock budget for one verifier run.  It is checked between chain
+    /// links and when verification finishes.  Leave it `None` on targets without a monotonic clock, such
+    /// as `wasm32-unknown-unknown`.
+    pub time_budget: Option<Duration>,
+}
+
+impl VerificationLimits {
+    /// No limits; the behaviour of a new [`ProofLedger`].
+    pub const fn unlimited() -> Self {
+        Self {
+            max_vars: usize::MAX,
+            max_chain_links: usize::MAX,
+            time_budget: None,
+        }
+    }
+
+    /// Checks the structural limits against `kind` without verifying it.
+    pub fn check(&self, kind: &ProofKind) -> Result<(), VerificationRejection> {
+        let check_vars = |num_vars: usize| {
+            if num_vars > self.max_vars {
+                Err(VerificationRejection::TooManyVariables {
+                    num_vars,
+                    max: self.max_vars,
+                })
+            } else {
+                Ok(())
+            }
+        };
+        match kind {
+            ProofKind::Demo(_) | ProofKind::Replayed | ProofKind::Genesis => Ok(()),
+            ProofKind::General { polynomial, proof } => {
+                check_vars(polynomial.num_vars().max(proof.claim.num_vars))
+            }
+            ProofKind::StreamingGeneral { polynomial, proof } => {
+                check_vars(polynomial.num_vars().max(proof.claim.num_vars))
+            }
+            ProofKind::Chain { polynomials, proof } => {
+                let links = proof.links().len().max(polynomials.len());
+                if links > self.max_chain_links {
+                    return Err(VerificationRejection::ChainTooLong {
+                        links,
+                        max: self.max_chain_links,
+                    });
+                }
+                for polynomial in polynomials {
+                    check_vars(polynomial.num_vars())?;
+                }
+                for link in proof.links() {
+                    check_vars(link.proof.claim.num_vars)?;
+                }
+                Ok(())
+            }
+            ProofKind::Range(range) => {
+                let cells = range
+                    .decomposition
+                    .len()
+                    .max(range.values.len().saturating_mul(range.bits));
+                let table_vars = cells
+                    .checked_next_power_of_two()
+                    .map_or(usize::BITS, usize::trailing_zeros)
+                    as usize;
+                check_vars(table_vars.max(range.proof.claim.num_vars))
+            }
+        }
+    }
+}
+
+impl Default for VerificationLimits {
+    /// 24 variables (16M evaluations), 256 chain links, and a 10 second budget.
+    fn default() -> Self {
+        Self {
+            max_vars: 24,
+            max_chain_links: 256,
+            time_budget: Some(Duration::from_secs(10)),
+        }
+    }
+}
+
+/// Whether attributed submissions must carry proofs bound to their submitter.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProverBinding {
+    /// Proofs verify whoever submits them.
+    #[default]
+    Off,
+    /// Proofs of attributed submissions are verified with the submitter key
+    /// mixed into their transcript, as made by
+    /// [`GeneralSumProof::prove_bound`].  A proof lifted from another
+    /// submitter fails, and proof kinds that cannot be bound are rejected with
+    /// [`VerificationRejection::UnboundProofKind`].  Unattributed submissions
+    /// are unaffected.
+    Required,
+}
+
+/// Why a proof was rejected before or during verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificationRejection {
+    /// The statement breaks its application's registered schema.
+    InvalidStatement(StatementViolation),
+    /// A polynomial or claim has more variables than allowed.
+    TooManyVariables {
+        /// Variables in the proof.
+        num_vars: usize,
+        /// Configured maximum.
+        max: usize,
+    },
+    /// A chained proof has more links than allowed.
+    ChainTooLong {
+        /// Links in the proof.
+        links: usize,
+        /// Configured maximum.
+        max: usize,
+    },
+    /// Verification ran past its time budget.
+    BudgetExceeded {
+        /// Configured budget in milliseconds.
+        budget_ms: u64,
+    },
+    /// The ledger requires prover binding and the proof kind cannot carry it.
+    UnboundProofKind,
+}
+
+impl std::fmt::Display for VerificationRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidStatement(violation) => write!(f, "invalid statement: {violation}"),
+            Self::TooManyVariables { num_vars, max } => {
+                write!(f, "proof has {num_vars} variables, limit is {max}")
+            }
+            Self::ChainTooLong { links, max } => {
+                write!(f, "chain has {links} links, limit is {max}")
+            }
+            Self::BudgetExceeded { budget_ms } => {
+                write!(f, "verification exceeded its {budget_ms} ms budget")
+            }
+            Self::UnboundProofKind => {
+                write!(f, "proof kind cannot be bound to its submitter")
+            }
+        }
+    }
+}
+
+impl std::error::Error for VerificationRejection {}
+
 /// A simple proof ledger that stores entries.  In a real system, this
 /// would be replicated across validators and incorporate consensus and
 /// finality logic.
@@ -127,6 +521,15 @@ pub struct ProofLedger {
     entries: Vec<LedgerEntry>,
     log_dir: Option<PathBuf>,
     log_counter: usize,
+    journal: Option<TranscriptJournal>,
+    compress_transcripts: bool,
+    prover_binding: ProverBinding,
+    /// Every transcript hash of every entry, in ledger order.
+    hash_tree: IncrementalMerkleTree,
+    verification_cache: Option<VerificationCache>,
+    limits: VerificationLimits,
+    schemas: StatementSchemaRegistry,
+    subscribers: Vec<mpsc::Sender<LedgerEvent>>,
 }
 
 /// Anchor representing the hashed transcripts for a ledger entry.
@@ -138,6 +541,15 @@ pub struct EntryAnchor {
     pub hashes: Vec<TranscriptDigest>,
     /// Merkle root for the transcript hashes.
     pub merkle_root: TranscriptDigest,
+    /// Optional submitter attribution, hashed into [`anchor_digest`] when present.
+    pub submission: Option<EntrySubmission>,
+}
+
+impl EntryAnchor {
+    /// Parses the anchored canonical statement string.
+    pub fn parsed_statement(&self) -> Statement {
+        Statement::parse(&self.statement)
+    }
 }
 
 /// Additional metadata associated with a ledger anchor.
@@ -149,6 +561,8 @@ pub struct AnchorMetadata {
     pub fold_digest: Option<TranscriptDigest>,
     /// Crate version that produced this anchor.
     pub crate_version: Option<String>,
+    /// Sparse Merkle root of the producer's stake registry, when it runs one.
+    pub registry_root: Option<TranscriptDigest>,
 }
 
 /// Anchor aggregation for an entire ledger.
@@ -160,29 +574,56 @@ pub struct LedgerAnchor {
     pub metadata: AnchorMetadata,
 }
 
-/// Statement string used for the JULIAN genesis anchor.
-pub const JULIAN_GENESIS_STATEMENT: &str = "JULIAN::GENESIS";
+impl LedgerAnchor {
+    /// Merkle root over every transcript hash of every entry, in order.
+    pub fn ledger_root(&self) -> TranscriptDigest {
+        let mut tree = IncrementalMerkleTree::new();
+        for digest in self.entries.iter().flat_map(|entry| &entry.hashes) {
+            tree.append(digest);
+        }
+        tree.root()
+    }
+
+    /// Returns the entry anchors whose structured statement belongs to `application_id`.
+    pub fn entries_for_application<'a>(
+        &'a self,
+        application_id: &'a str,
+    ) -> impl Iterator<Item = &'a EntryAnchor> + 'a {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.parsed_statement().application_id() == Some(application_id))
+    }
+}
+
+/// Statement string used for the genesis anchor of the default network.
+pub const JULIAN_GENESIS_STATEMENT: &str = crate::genesis::DEFAULT_GENESIS_STATEMENT;
 
-/// Pinned digest associated with the JULIAN genesis transcript.
+/// Pinned digest associated with the default network's genesis transcript.
 pub const JULIAN_GENESIS_DIGEST: TranscriptDigest = [
     0xcd, 0xcc, 0x8f, 0x36, 0xbf, 0x3d, 0x51, 0x1f, 0x04, 0xdf, 0x86, 0xc6, 0x3b, 0xcf, 0x58, 0x0d,
     0xae, 0xe7, 0x3a, 0xa6, 0x7c, 0x0c, 0xf9, 0x14, 0x48, 0x3a, 0x05, 0xc2, 0xd2, 0x89, 0x58, 0x4a,
 ];
 
-/// Returns the digest associated with the JULIAN genesis transcript.
+/// Returns the genesis transcript digest of the active network.
 pub fn julian_genesis_hash() -> TranscriptDigest {
-    JULIAN_GENESIS_DIGEST
+    crate::genesis::active_genesis_digest()
+}
+
+/// Returns the genesis statement of the active network.
+pub fn julian_genesis_statement() -> &'static str {
+    &crate::genesis::active_genesis().genesis_statement
 }
 
-/// Returns the canonical JULIAN genesis anchor.
+/// Returns the canonical genesis anchor of the active network.
 pub fn julian_genesis_anchor() -> LedgerAnchor {
     let hashes = vec![julian_genesis_hash()];
     let merkle = merkle_root(&hashes);
     LedgerAnchor {
         entries: vec![EntryAnchor {
-            statement: JULIAN_GENESIS_STATEMENT.to_string(),
+            statement: julian_genesis_statement().to_string(),
             hashes: hashes.clone(),
             merkle_root: merkle,
+            submission: None,
         }],
         metadata: AnchorMetadata {
             crate_version: Some(env!("CARGO_PKG_VERSION").to_string()),
@@ -199,6 +640,14 @@ impl ProofLedger {
             entries: Vec::new(),
             log_dir: None,
             log_counter: 0,
+            journal: None,
+            compress_transcripts: false,
+            prover_binding: ProverBinding::Off,
+            hash_tree: IncrementalMerkleTree::new(),
+            verification_cache: None,
+            limits: VerificationLimits::unlimited(),
+            schemas: StatementSchemaRegistry::new(),
+            subscribers: Vec::new(),
         }
     }
 
@@ -206,96 +655,257 @@ impl ProofLedger {
     pub fn enable_logging<P: Into<PathBuf>>(&mut self, log_dir: P) {
         self.log_dir = Some(log_dir.into());
         self.log_counter = 0;
+        self.journal = None;
+    }
+
+    /// Streams accepted transcripts into a [`TranscriptJournal`] in `log_dir`
+    /// instead of writing one `ledger_NNNN.txt` file per record.
+    pub fn enable_journal<P: AsRef<Path>>(
+        &mut self,
+        log_dir: P,
+        config: JournalConfig,
+    ) -> io::Result<()> {
+        self.journal = Some(TranscriptJournal::open(log_dir.as_ref(), config)?);
+        self.log_dir = None;
+        Ok(())
+    }
+
+    /// Writes later sum-check transcripts as compressed records that keep
+    /// only the Fiat–Shamir seed inputs.
+    ///
+    /// Transcripts that cannot be recomputed from a seed are still written in
+    /// full.  Digests and anchors are unchanged, and both formats replay, but
+    /// releases without compressed-record support cannot read the new logs.
+    pub fn enable_transcript_compression(&mut self) {
+        self.compress_transcripts = true;
+    }
+
+    /// Reuses verification outcomes for proofs whose [`proof_digest`] was
+    /// already seen, keeping at most `capacity` of them in memory.
+    ///
+    /// Duplicate submissions still produce separate entries and transcript
+    /// records; only the verifier run is skipped.
+    pub fn enable_verification_cache(&mut self, capacity: usize) {
+        self.verification_cache = Some(VerificationCache::new(capacity));
+    }
+
+    /// Like [`Self::enable_verification_cache`], but loads earlier outcomes
+    /// from `path` and rewrites it after every verifier run.
+    pub fn enable_persistent_verification_cache<P: Into<PathBuf>>(
+        &mut self,
+        path: P,
+        capacity: usize,
+    ) -> io::Result<()> {
+        self.verification_cache = Some(VerificationCache::open(path, capacity)?);
+        Ok(())
+    }
+
+    /// Enforces `limits` on every later submission.
+    ///
+    /// A new ledger verifies proofs of any size; nodes that accept proofs from
+    /// untrusted submitters should install [`VerificationLimits::default`] or
+    /// tighter.  Proofs over a limit are recorded as rejected entries with the
+    /// reason in [`LedgerEntry::rejection`].
+    pub fn set_verification_limits(&mut self, limits: VerificationLimits) {
+        self.limits = limits;
+    }
+
+    /// Limits currently enforced on submissions.
+    pub fn verification_limits(&self) -> VerificationLimits {
+        self.limits
+    }
+
+    /// Sets whether later attributed submissions must be bound to their
+    /// submitter.
+    pub fn set_prover_binding(&mut self, binding: ProverBinding) {
+        self.prover_binding = binding;
+    }
+
+    /// Prover binding currently enforced on submissions.
+    pub fn prover_binding(&self) -> ProverBinding {
+        self.prover_binding
+    }
+
+    /// Key a submission's proof must be bound to under the current policy.
+    pub(crate) fn bound_prover<'a>(
+        &self,
+        submission: Option<&'a EntrySubmission>,
+    ) -> Option<&'a [u8]> {
+        match self.prover_binding {
+            ProverBinding::Off => None,
+            ProverBinding::Required => submission.map(|submission| &submission.public_key[..]),
+        }
+    }
+
+    /// Rejects later structured statements for `schema.application_id` that
+    /// break `schema`, replacing any schema registered for that application.
+    ///
+    /// The violation is kept in [`LedgerEntry::rejection`] and the proof is
+    /// not verified.
+    pub fn register_statement_schema(&mut self, schema: StatementSchema) {
+        self.schemas.register(schema);
+    }
+
+    /// Replaces every registered statement schema with `schemas`.
+    pub fn set_statement_schemas(&mut self, schemas: StatementSchemaRegistry) {
+        self.schemas = schemas;
+    }
+
+    /// Statement schemas enforced on submissions.
+    pub fn statement_schemas(&self) -> &StatementSchemaRegistry {
+        &self.schemas
+    }
+
+    /// Returns the verification cache, when one is enabled.
+    pub fn verification_cache(&self) -> Option<&VerificationCache> {
+        self.verification_cache.as_ref()
     }
 
     /// Submits a statement and proof to the ledger.  The verifier inspects
     /// demo proofs, generalized multilinear proofs, or chained proofs and logs
     /// the deterministic transcripts for future audit.
     pub fn submit(&mut self, statement: Statement, proof: Proof) {
-        if !matches!(proof.kind, ProofKind::Genesis) {
-            self.ensure_genesis();
+        self.submit_entry(statement, proof, None, None);
+    }
+
+    /// Submits a statement and proof attributed to `submission`.
+    ///
+    /// The attribution is written to transcript logs as `# submitter*` comment
+    /// lines, so it survives [`ProofLedger::replay_from_logs`].
+    pub fn submit_attributed(
+        &mut self,
+        statement: Statement,
+        proof: Proof,
+        submission: EntrySubmission,
+    ) {
+        self.submit_entry(statement, proof, Some(submission), None);
+    }
+
+    /// Submits an attributed proof after charging the submitter's fee account.
+    ///
+    /// The fee from `schedule` is debited from `submission.public_key` before
+    /// verification; if the debit fails nothing is recorded.  When the proof is
+    /// rejected and the schedule allows it, the fee is refunded.  The charge is
+    /// kept in [`LedgerEntry::fee`] and logged as a `# submission_fee` line.
+    /// Callers are expected to have checked the submission signature.
+    pub fn submit_paid<A: FeeAccounts + ?Sized>(
+        &mut self,
+        statement: Statement,
+        proof: Proof,
+        submission: EntrySubmission,
+        schedule: &SubmissionFeeSchedule,
+        accounts: &mut A,
+    ) -> Result<&LedgerEntry, String> {
+        let fee = schedule.fee_for(proof.data.len());
+        let asset = schedule.fee_asset();
+        accounts.debit_fee(&submission.public_key, asset, fee)?;
+        let payer = submission.public_key.clone();
+        self.submit_entry(statement, proof, Some(submission), Some(fee));
+        let entry = self.entries.last_mut().expect("entry submitted above");
+        if !entry.accepted && schedule.refund_on_rejection && fee > 0 {
+            accounts.refund_fee(&payer, asset, fee);
+            entry.fee = Some(EntryFee {
+                paid: fee,
+                refunded: true,
+            });
         }
+        Ok(entry)
+    }
 
-        let mut transcripts = Vec::new();
-        let mut round_sums = Vec::new();
-        let mut final_values = Vec::new();
-        let mut hashes = Vec::new();
-        let log_paths = Vec::new();
-        let log_error = None;
+    fn submit_entry(
+        &mut self,
+        statement: Statement,
+        proof: Proof,
+        submission: Option<EntrySubmission>,
+        fee: Option<u64>,
+    ) {
+        let prover = self.bound_prover(submission.as_ref());
+        let verdict = match self.precheck(&statement, &proof.kind, prover) {
+            Ok(verdict) => verdict,
+            Err(digest) => verify_detached(&proof.kind, prover, self.limits.time_budget, digest),
+        };
+        self.record_verdict(statement, proof, submission, fee, verdict, None);
+    }
 
-        let accepted = match &proof.kind {
-            ProofKind::Demo(claim) => claim.verify_demo(),
-            ProofKind::General { polynomial, proof } => {
-                let field = Field::new(proof.claim.p);
-                match proof.verify_with_trace(polynomial, &field) {
-                    Some(trace) => {
-                        transcripts.push(trace.challenges.clone());
-                        round_sums.push(trace.round_sums.clone());
-                        final_values.push(trace.final_evaluation);
-                        hashes.push(transcript_digest(
-                            &trace.challenges,
-                            &trace.round_sums,
-                            trace.final_evaluation,
-                        ));
-                        true
-                    }
-                    None => false,
-                }
-            }
-            ProofKind::StreamingGeneral { polynomial, proof } => {
-                let field = Field::new(proof.claim.p);
-                if polynomial.modulus() != proof.claim.p {
-                    false
-                } else {
-                    match proof.verify_streaming_with_trace(polynomial, &field) {
-                        Some(trace) => {
-                            transcripts.push(trace.challenges.clone());
-                            round_sums.push(trace.round_sums.clone());
-                            final_values.push(trace.final_evaluation);
-                            hashes.push(transcript_digest(
-                                &trace.challenges,
-                                &trace.round_sums,
-                                trace.final_evaluation,
-                            ));
-                            true
-                        }
-                        None => false,
-                    }
-                }
+    /// Settles a submission from the statement schemas, the limits, and the
+    /// verification cache alone.
+    ///
+    /// Returns the digest to cache a verifier result under when the proof
+    /// still needs a verifier run.
+    pub(crate) fn precheck(
+        &mut self,
+        statement: &Statement,
+        kind: &ProofKind,
+        prover: Option<&[u8]>,
+    ) -> Result<Verdict, Option<TranscriptDigest>> {
+        if let Err(violation) = self.schemas.validate(statement) {
+            return Ok(Verdict::rejected(VerificationRejection::InvalidStatement(
+                violation,
+            )));
+        }
+        if let Err(reason) = self.limits.check(kind) {
+            return Ok(Verdict::rejected(reason));
+        }
+        if prover.is_some()
+            && !matches!(
+                kind,
+                ProofKind::General { .. } | ProofKind::StreamingGeneral { .. }
+            )
+        {
+            return Ok(Verdict::rejected(VerificationRejection::UnboundProofKind));
+        }
+        let digest = self.verification_cache.as_ref().and_then(|_| match prover {
+            Some(prover) => bound_proof_digest(kind, prover),
+            None => proof_digest(kind),
+        });
+        if let (Some(cache), Some(key)) = (&mut self.verification_cache, &digest) {
+            if let Some(outcome) = cache.get(key) {
+                return Ok(Verdict {
+                    outcome,
+                    rejection: None,
+                    cache_digest: None,
+                });
             }
-            ProofKind::Chain {
-                polynomials,
-                proof: chain,
-            } => {
-                let modulus = chain
-                    .links()
-                    .first()
-                    .map(|link| link.proof.claim.p)
-                    .unwrap_or(0);
-                if modulus < 3 || modulus % 2 == 0 {
-                    false
-                } else {
-                    let field = Field::new(modulus);
-                    match chain.verify_with_traces(polynomials, &field) {
-                        Some(traces) => {
-                            for trace in traces {
-                                transcripts.push(trace.challenges.clone());
-                                round_sums.push(trace.round_sums.clone());
-                                final_values.push(trace.final_evaluation);
-                                hashes.push(transcript_digest(
-                                    &trace.challenges,
-                                    &trace.round_sums,
-                                    trace.final_evaluation,
-                                ));
-                            }
-                            true
-                        }
-                        None => false,
-                    }
-                }
+        }
+        Err(digest)
+    }
+
+    /// Records a proof whose verdict is already known and notifies subscribers.
+    pub(crate) fn record_verdict(
+        &mut self,
+        statement: Statement,
+        proof: Proof,
+        submission: Option<EntrySubmission>,
+        fee: Option<u64>,
+        verdict: Verdict,
+        ticket: Option<u64>,
+    ) {
+        if !matches!(proof.kind, ProofKind::Genesis) {
+            self.ensure_genesis();
+        }
+
+        let mut log_error = None;
+        if let (Some(cache), Some(digest)) = (&mut self.verification_cache, verdict.cache_digest) {
+            if let Err(err) = cache.insert(digest, verdict.outcome.clone()) {
+                log_error = Some(format!("verification cache: {err}"));
             }
-            ProofKind::Genesis => true,
-        };
+        }
+        let rejection = verdict.rejection;
+        let CachedVerification {
+            accepted,
+            transcripts,
+            round_sums,
+            final_values,
+        } = verdict.outcome;
+        let hashes: Vec<TranscriptDigest> = transcripts
+            .iter()
+            .zip(&round_sums)
+            .zip(&final_values)
+            .map(|((challenges, sums), final_value)| {
+                transcript_digest(challenges, sums, *final_value)
+            })
+            .collect();
+        let log_paths = Vec::new();
 
         let mut entry = if matches!(proof.kind, ProofKind::Genesis) {
             let hashes = vec![julian_genesis_hash()];
@@ -311,6 +921,9 @@ impl ProofLedger {
                 log_error: None,
                 hashes,
                 merkle_root: merkle,
+                submission: None,
+                fee: None,
+                rejection: None,
             }
         } else {
             let merkle = merkle_root(&hashes);
@@ -325,57 +938,141 @@ impl ProofLedger {
                 log_error,
                 hashes,
                 merkle_root: merkle,
+                submission,
+                fee: fee.map(|paid| EntryFee {
+                    paid,
+                    refunded: false,
+                }),
+                rejection,
             }
         };
 
-        if entry.accepted && !matches!(entry.proof.kind, ProofKind::Genesis) {
-            if let Some(dir) = &self.log_dir {
-                for idx in 0..entry.transcripts.len() {
-                    let mut lines = Vec::new();
-                    if let Err(err) = write_transcript_record(
-                        |line| {
-                            lines.push(line.to_string());
-                            Ok(())
-                        },
-                        &entry.transcripts[idx],
-                        &entry.round_sums[idx],
-                        entry.final_values[idx],
-                    ) {
-                        entry.log_error = Some(err.to_string());
-                        break;
-                    }
-                    lines.insert(0, format!("statement:{}", entry.statement.description));
-                    match write_text_series(dir, "ledger", self.log_counter, &lines) {
-                        Ok(path) => {
+        if entry.accepted
+            && !matches!(entry.proof.kind, ProofKind::Genesis)
+            && (self.log_dir.is_some() || self.journal.is_some())
+        {
+            let modulus = self
+                .compress_transcripts
+                .then(|| transcript_modulus(&entry.proof.kind))
+                .flatten();
+            for idx in 0..entry.transcripts.len() {
+                let mut lines = Vec::new();
+                let push = |line: &str| {
+                    lines.push(line.to_string());
+                    Ok(())
+                };
+                let (transcript, sums, final_value) = (
+                    &entry.transcripts[idx],
+                    &entry.round_sums[idx],
+                    entry.final_values[idx],
+                );
+                let written = match modulus {
+                    Some(modulus) => write_compact_transcript_record(
+                        push,
+                        modulus,
+                        transcript,
+                        sums,
+                        final_value,
+                    ),
+                    None => write_transcript_record(push, transcript, sums, final_value),
+                };
+                if let Err(err) = written {
+                    entry.log_error = Some(err.to_string());
+                    break;
+                }
+                lines.insert(0, format!("statement:{}", entry.statement.canonical()));
+                if let Some(submission) = &entry.submission {
+                    lines.splice(1..1, submission_comment_lines(submission));
+                }
+                if let Some(fee) = &entry.fee {
+                    lines.insert(1, format!("# submission_fee: {}", fee.paid));
+                }
+                lines.insert(1, format!("# entry_index: {}", self.entries.len()));
+                let written = match (&mut self.journal, &self.log_dir) {
+                    (Some(journal), _) => journal
+                        .append(&lines)
+                        .map(|_| journal.current_segment_path()),
+                    (None, Some(dir)) => write_text_series(dir, "ledger", self.log_counter, &lines),
+                    (None, None) => unreachable!("logging sink checked above"),
+                };
+                match written {
+                    Ok(path) => {
+                        if !entry.log_paths.contains(&path) {
                             entry.log_paths.push(path);
-                            self.log_counter += 1;
-                        }
-                        Err(err) => {
-                            entry.log_error = Some(err.to_string());
-                            break;
                         }
+                        self.log_counter += 1;
+                    }
+                    Err(err) => {
+                        entry.log_error = Some(err.to_string());
+                        break;
                     }
                 }
             }
         }
 
+        for digest in &entry.hashes {
+            self.hash_tree.append(digest);
+        }
+        if !self.subscribers.is_empty() {
+            let event = LedgerEvent {
+                index: self.entries.len(),
+                statement: entry.statement.canonical(),
+                accepted: entry.accepted,
+                rejection: entry.rejection.clone(),
+                ticket,
+            };
+            self.subscribers
+                .retain(|subscriber| subscriber.send(event.clone()).is_ok());
+        }
         self.entries.push(entry);
     }
 
+    /// Returns a receiver that gets a [`LedgerEvent`] for every entry recorded
+    /// from now on.  Dropping the receiver ends the subscription.
+    pub fn subscribe(&mut self) -> mpsc::Receiver<LedgerEvent> {
+        let (sender, receiver) = mpsc::channel();
+        self.subscribers.push(sender);
+        receiver
+    }
+
     /// Returns a read-only view of the current ledger entries.
     pub fn entries(&self) -> &[LedgerEntry] {
         &self.entries
     }
 
+    /// Returns the entries whose structured statement belongs to `application_id`.
+    pub fn entries_for_application<'a>(
+        &'a self,
+        application_id: &'a str,
+    ) -> impl Iterator<Item = &'a LedgerEntry> + 'a {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.statement.application_id() == Some(application_id))
+    }
+
+    /// Merkle root over every transcript hash in the ledger, in entry order.
+    ///
+    /// Maintained incrementally as entries are added and equal to
+    /// [`LedgerAnchor::ledger_root`] of [`Self::anchor`].
+    pub fn ledger_root(&self) -> TranscriptDigest {
+        self.hash_tree.root()
+    }
+
+    /// Frontier of the ledger-wide transcript hash tree.
+    pub fn hash_tree(&self) -> &IncrementalMerkleTree {
+        &self.hash_tree
+    }
+
     /// Returns the current ledger anchor containing transcript hashes per entry.
     pub fn anchor(&self) -> LedgerAnchor {
         let entries: Vec<EntryAnchor> = self
             .entries
             .iter()
             .map(|entry| EntryAnchor {
-                statement: entry.statement.description.clone(),
+                statement: entry.statement.canonical(),
                 hashes: entry.hashes.clone(),
                 merkle_root: entry.merkle_root,
+                submission: entry.submission.clone(),
             })
             .collect();
         let fold_digest = fold_digest_from_entries(&entries);
@@ -385,21 +1082,121 @@ impl ProofLedger {
                 challenge_mode: None,
                 fold_digest: Some(fold_digest),
                 crate_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+                registry_root: None,
             },
         }
     }
 
-    /// Ensures the JULIAN genesis anchor is present at the head of the ledger.
+    /// Rebuilds a ledger from the transcript logs in `log_dir`.
+    ///
+    /// Records are replayed in log order and grouped into entries by their
+    /// `# entry_index` line, so multi-transcript proofs fold back into one
+    /// entry while repeated submissions of a statement stay separate.  Logs
+    /// written before that line existed fall back to folding consecutive
+    /// records that share a statement and submitter.  A record that fails verification becomes a
+    /// rejected entry named after the record, with the failure kept in
+    /// [`LedgerEntry::log_error`].  With the `net` feature every checkpoint in
+    /// `log_dir/checkpoints` must agree with the replayed transcripts up to its
+    /// log cutoff.
+    pub fn replay_from_logs<P: AsRef<Path>>(log_dir: P) -> Result<Self, String> {
+        let log_dir = log_dir.as_ref();
+        let mut ledger = Self::new();
+        ledger.ensure_genesis();
+        let mut replayed: Vec<(String, String, TranscriptDigest)> = Vec::new();
+        let mut entry_tree = IncrementalMerkleTree::new();
+        let mut last_index: Option<u64> = None;
+        for record in iter_ledger_logs(log_dir)? {
+            let record = record?;
+            let log_paths: Vec<PathBuf> =
+                record.path().map(Path::to_path_buf).into_iter().collect();
+            let parsed = match record.parse() {
+                Ok(parsed) => parsed,
+                Err(err) => {
+                    ledger.entries.push(LedgerEntry {
+                        statement: Statement::new(record.name.clone()),
+                        proof: Proof {
+                            kind: ProofKind::Replayed,
+                            data: Vec::new(),
+                        },
+                        accepted: false,
+                        transcripts: Vec::new(),
+                        round_sums: Vec::new(),
+                        final_values: Vec::new(),
+                        log_paths,
+                        log_error: Some(err),
+                        hashes: Vec::new(),
+                        merkle_root: merkle_root(&[]),
+                        submission: None,
+                        fee: None,
+                        rejection: None,
+                    });
+                    continue;
+                }
+            };
+            replayed.push((record.name, parsed.statement.clone(), parsed.digest));
+            let extends_last = ledger.entries.last().is_some_and(|entry| {
+                entry.accepted
+                    && matches!(entry.proof.kind, ProofKind::Replayed)
+                    && match parsed.metadata.entry_index {
+                        Some(index) => last_index == Some(index),
+                        None => {
+                            last_index.is_none()
+                                && entry.statement.canonical() == parsed.statement
+                                && entry.submission == parsed.metadata.submission
+                        }
+                    }
+            });
+            if !extends_last {
+                entry_tree = IncrementalMerkleTree::new();
+                last_index = parsed.metadata.entry_index;
+                ledger.entries.push(LedgerEntry {
+                    statement: Statement::parse(&parsed.statement),
+                    proof: Proof {
+                        kind: ProofKind::Replayed,
+                        data: Vec::new(),
+                    },
+                    accepted: true,
+                    transcripts: Vec::new(),
+                    round_sums: Vec::new(),
+                    final_values: Vec::new(),
+                    log_paths: Vec::new(),
+                    log_error: None,
+                    hashes: Vec::new(),
+                    merkle_root: merkle_root(&[]),
+                    submission: parsed.metadata.submission.clone(),
+                    fee: parsed.metadata.submission_fee.map(|paid| EntryFee {
+                        paid,
+                        refunded: false,
+                    }),
+                    rejection: None,
+                });
+            }
+            let entry = ledger.entries.last_mut().expect("entry pushed above");
+            entry.transcripts.push(parsed.challenges);
+            entry.round_sums.push(parsed.round_sums);
+            entry.final_values.push(parsed.final_value);
+            entry.log_paths.extend(log_paths);
+            entry.hashes.push(parsed.digest);
+            entry_tree.append(&parsed.digest);
+            entry.merkle_root = entry_tree.root();
+            ledger.hash_tree.append(&parsed.digest);
+        }
+        #[cfg(feature = "net")]
+        validate_replay_checkpoints(log_dir, &replayed)?;
+        #[cfg(not(feature = "net"))]
+        let _ = replayed;
+        Ok(ledger)
+    }
+
+    /// Ensures the active network's genesis entry is present at the head of the ledger.
     pub fn ensure_genesis(&mut self) {
         let needs_genesis = self
             .entries
             .first()
-            .is_none_or(|entry| entry.statement.description != JULIAN_GENESIS_STATEMENT);
+            .is_none_or(|entry| entry.statement.canonical() != julian_genesis_statement());
         if needs_genesis {
             let genesis_entry = LedgerEntry {
-                statement: Statement {
-                    description: JULIAN_GENESIS_STATEMENT.to_string(),
-                },
+                statement: Statement::new(julian_genesis_statement()),
                 proof: Proof {
                     kind: ProofKind::Genesis,
                     data: Vec::new(),
@@ -412,36 +1209,284 @@ impl ProofLedger {
                 log_error: None,
                 hashes: vec![julian_genesis_hash()],
                 merkle_root: merkle_root(&[julian_genesis_hash()]),
+                submission: None,
+                fee: None,
+                rejection: None,
             };
             self.entries.insert(0, genesis_entry);
+            let hashes: Vec<TranscriptDigest> = self
+                .entries
+                .iter()
+                .flat_map(|entry| entry.hashes.iter().copied())
+                .collect();
+            self.hash_tree = IncrementalMerkleTree::from_leaves(&hashes);
         }
     }
 }
 
-impl Default for ProofLedger {
-    fn default() -> Self {
-        Self::new()
-    }
+/// Outcome of checking a proof, computed before its entry is recorded.
+#[derive(Debug)]
+pub(crate) struct Verdict {
+    outcome: CachedVerification,
+    rejection: Option<VerificationRejection>,
+    /// Key to store `outcome` under in the verification cache, when it came
+    /// from a verifier run.
+    cache_digest: Option<TranscriptDigest>,
 }
 
-fn mix_u64(hasher: &mut Blake2b256, value: u64) {
-    hasher.update(value.to_be_bytes());
+impl Verdict {
+    pub(crate) fn rejected(reason: VerificationRejection) -> Self {
+        Self {
+            outcome: CachedVerification::default(),
+            rejection: Some(reason),
+            cache_digest: None,
+        }
+    }
+
+    /// Verdict for a proof whose verifier run panicked.
+    pub(crate) fn failed() -> Self {
+        Self {
+            outcome: CachedVerification::default(),
+            rejection: None,
+            cache_digest: None,
+        }
+    }
 }
 
-fn mix_bytes(hasher: &mut Blake2b256, bytes: &[u8]) {
-    mix_u64(hasher, bytes.len() as u64);
-    hasher.update(bytes);
+/// Runs the verifier for `kind` under `time_budget` without touching a
+/// ledger, so it can run on any thread.
+pub(crate) fn verify_detached(
+    kind: &ProofKind,
+    prover: Option<&[u8]>,
+    time_budget: Option<Duration>,
+    cache_digest: Option<TranscriptDigest>,
+) -> Verdict {
+    match verify_proof_kind(kind, prover, &Deadline::start(time_budget)) {
+        Ok(outcome) => Verdict {
+            outcome,
+            rejection: None,
+            cache_digest,
+        },
+        Err(reason) => Verdict::rejected(reason),
+    }
 }
 
-fn mix_hash_list(hasher: &mut Blake2b256, hashes: &[TranscriptDigest]) {
-    mix_u64(hasher, hashes.len() as u64);
-    for digest in hashes {
-        hasher.update(digest);
+/// Field modulus the transcripts of `kind` were sampled in.
+fn transcript_modulus(kind: &ProofKind) -> Option<u64> {
+    match kind {
+        ProofKind::General { proof, .. } | ProofKind::StreamingGeneral { proof, .. } => {
+            Some(proof.claim.p)
+        }
+        ProofKind::Chain { proof, .. } => proof.links().first().map(|link| link.proof.claim.p),
+        ProofKind::Range(range) => Some(range.proof.claim.p),
+        ProofKind::Demo(_) | ProofKind::Replayed | ProofKind::Genesis => None,
     }
 }
 
-fn fold_digest_from_hashes(hashes: &[TranscriptDigest]) -> TranscriptDigest {
-    let mut hasher = Blake2b256::new();
+/// Runs the verifier for `kind` and collects its transcripts, giving up once
+/// `deadline` has passed.  The deadline is checked before every sum-check
+/// round, so one wide proof cannot overrun it.
+///
+/// General proofs are verified bound to `prover` when one is given.
+fn verify_proof_kind(
+    kind: &ProofKind,
+    prover: Option<&[u8]>,
+    deadline: &Deadline,
+) -> Result<CachedVerification, VerificationRejection> {
+    let mut transcripts = Vec::new();
+    let mut round_sums = Vec::new();
+    let mut final_values = Vec::new();
+
+    let accepted = match kind {
+        ProofKind::Demo(claim) => claim.verify_demo(),
+        ProofKind::General { polynomial, proof } => {
+            let field = Field::new(proof.claim.p);
+            match proof
+                .verify_with_trace_checked(polynomial, &field, prover, |_| deadline.check())?
+            {
+                Some(trace) => {
+                    transcripts.push(trace.challenges);
+                    round_sums.push(trace.round_sums);
+                    final_values.push(trace.final_evaluation);
+                    true
+                }
+                None => false,
+            }
+        }
+        ProofKind::StreamingGeneral { polynomial, proof } => {
+            let field = Field::new(proof.claim.p);
+            if polynomial.modulus() != proof.claim.p {
+                false
+            } else {
+                match proof.verify_streaming_with_trace_checked(
+                    polynomial,
+                    &field,
+                    prover,
+                    |_| deadline.check(),
+                )? {
+                    Some(trace) => {
+                        transcripts.push(trace.challenges);
+                        round_sums.push(trace.round_sums);
+                        final_values.push(trace.final_evaluation);
+                        true
+                    }
+                    None => false,
+                }
+            }
+        }
+        ProofKind::Chain {
+            polynomials,
+            proof: chain,
+        } => {
+            let modulus = chain
+                .links()
+                .first()
+                .map(|link| link.proof.claim.p)
+                .unwrap_or(0);
+            if modulus < 3 || modulus % 2 == 0 {
+                false
+            } else {
+                let field = Field::new(modulus);
+                match chain.verify_with_traces_checked(polynomials, &field, |_| deadline.check())? {
+                    Some(traces) => {
+                        for trace in traces {
+                            transcripts.push(trace.challenges);
+                            round_sums.push(trace.round_sums);
+                            final_values.push(trace.final_evaluation);
+                        }
+                        true
+                    }
+                    None => false,
+                }
+            }
+        }
+        ProofKind::Range(range) => {
+            let modulus = range.proof.claim.p;
+            if modulus < 3 || modulus.is_multiple_of(2) {
+                false
+            } else {
+                match range.verify_with_trace_checked(&Field::new(modulus), |_| deadline.check())? {
+                    Some(trace) => {
+                        transcripts.push(trace.challenges);
+                        round_sums.push(trace.round_sums);
+                        final_values.push(trace.final_evaluation);
+                        true
+                    }
+                    None => false,
+                }
+            }
+        }
+        ProofKind::Replayed => false,
+        ProofKind::Genesis => true,
+    };
+    deadline.check()?;
+    Ok(CachedVerification {
+        accepted,
+        transcripts,
+        round_sums,
+        final_values,
+    })
+}
+
+/// Cooperative wall-clock deadline for one verifier run.
+struct Deadline(Option<(Instant, Duration)>);
+
+impl Deadline {
+    fn start(budget: Option<Duration>) -> Self {
+        Self(budget.map(|budget| (Instant::now(), budget)))
+    }
+
+    fn check(&self) -> Result<(), VerificationRejection> {
+        match self.0 {
+            Some((started, budget)) if started.elapsed() > budget => {
+                Err(VerificationRejection::BudgetExceeded {
+                    budget_ms: budget.as_millis() as u64,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Checks that every checkpoint agrees with the replayed `(name, statement,
+/// digest)` sequence up to its log cutoff.
+#[cfg(feature = "net")]
+fn validate_replay_checkpoints(
+    log_dir: &Path,
+    replayed: &[(String, String, TranscriptDigest)],
+) -> Result<(), String> {
+    let checkpoints =
+        crate::net::load_checkpoints(log_dir).map_err(|err| format!("checkpoint error: {err}"))?;
+    for checkpoint in checkpoints {
+        let epoch = checkpoint.epoch;
+        let (anchor, cutoff) = checkpoint
+            .into_ledger()
+            .map_err(|err| format!("checkpoint {epoch} error: {err}"))?;
+        let Some(cutoff) = cutoff else {
+            continue;
+        };
+        let expected: Vec<(&str, &TranscriptDigest)> = anchor
+            .entries
+            .iter()
+            .filter(|entry| entry.statement != julian_genesis_statement())
+            .flat_map(|entry| {
+                entry
+                    .hashes
+                    .iter()
+                    .map(|hash| (entry.statement.as_str(), hash))
+            })
+            .collect();
+        let actual: Vec<(&str, &TranscriptDigest)> = replayed
+            .iter()
+            .take_while(|(name, _, _)| name.as_str() <= cutoff.as_str())
+            .map(|(_, statement, digest)| (statement.as_str(), digest))
+            .collect();
+        if let Some(position) =
+            (0..expected.len().max(actual.len())).find(|idx| expected.get(*idx) != actual.get(*idx))
+        {
+            return Err(format!(
+                "checkpoint {epoch} disagrees with replayed logs at transcript {position} (cutoff {cutoff})"
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn submission_comment_lines(submission: &EntrySubmission) -> Vec<String> {
+    vec![
+        format!("# submitter: {}", hex::encode(&submission.public_key)),
+        format!(
+            "# submitter_signature: {}",
+            hex::encode(&submission.signature)
+        ),
+        format!("# submitted_ms: {}", submission.submitted_ms),
+    ]
+}
+
+impl Default for ProofLedger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn mix_u64(hasher: &mut Blake2b256, value: u64) {
+    hasher.update(value.to_be_bytes());
+}
+
+fn mix_bytes(hasher: &mut Blake2b256, bytes: &[u8]) {
+    mix_u64(hasher, bytes.len() as u64);
+    hasher.update(bytes);
+}
+
+fn mix_hash_list(hasher: &mut Blake2b256, hashes: &[TranscriptDigest]) {
+    mix_u64(hasher, hashes.len() as u64);
+    for digest in hashes {
+        hasher.update(digest);
+    }
+}
+
+fn fold_digest_from_hashes(hashes: &[TranscriptDigest]) -> TranscriptDigest {
+    let mut hasher = Blake2b256::new();
     hasher.update(ANCHOR_DOMAIN);
     for digest in hashes {
         hasher.update(digest);
@@ -470,6 +1515,9 @@ pub fn compute_fold_digest(anchor: &LedgerAnchor) -> TranscriptDigest {
 }
 
 /// Computes a digest for the anchor contents (entries + statements + hashes).
+///
+/// Entries carrying an [`EntrySubmission`] also mix in the submitter key,
+/// signature and timestamp; unattributed entries hash exactly as before.
 pub fn anchor_digest(anchor: &LedgerAnchor) -> [u8; 32] {
     let mut hasher = Blake2b256::new();
     hasher.update(ANCHOR_DOMAIN);
@@ -477,6 +1525,12 @@ pub fn anchor_digest(anchor: &LedgerAnchor) -> [u8; 32] {
     for entry in &anchor.entries {
         mix_bytes(&mut hasher, entry.statement.as_bytes());
         mix_hash_list(&mut hasher, &entry.hashes);
+        if let Some(submission) = &entry.submission {
+            hasher.update(SUBMISSION_DOMAIN);
+            mix_bytes(&mut hasher, &submission.public_key);
+            mix_bytes(&mut hasher, &submission.signature);
+            mix_u64(&mut hasher, submission.submitted_ms);
+        }
     }
     let mut out = [0u8; 32];
     out.copy_from_slice(&hasher.finalize());
@@ -492,10 +1546,28 @@ pub struct AnchorVote<'a> {
 }
 
 /// Ensures that a collection of ledger anchors agree on every transcript hash.
+///
+/// Anchors that carry a registry root must also agree on it; anchors from
+/// nodes without a stake registry do not vote on economic state.
 pub fn reconcile_anchors(anchors: &[LedgerAnchor]) -> Result<(), String> {
+    let _span = crate::profile::span("reconcile_anchors");
     if anchors.is_empty() {
         return Ok(());
     }
+    let mut registry_roots = anchors
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, anchor)| anchor.metadata.registry_root.map(|root| (idx, root)));
+    if let Some((_, reference_root)) = registry_roots.next() {
+        if let Some((idx, root)) = registry_roots.find(|(_, root)| *root != reference_root) {
+            return Err(format!(
+                "anchor {} registry root {} mismatch reference {}",
+                idx,
+                hex::encode(root),
+                hex::encode(reference_root)
+            ));
+        }
+    }
     let reference = &anchors[0];
     for (idx, anchor) in anchors.iter().enumerate().skip(1) {
         if anchor.entries.len() != reference.entries.len() {
@@ -527,6 +1599,7 @@ pub fn reconcile_anchors_with_quorum(
     votes: &[AnchorVote<'_>],
     quorum: usize,
 ) -> Result<(), String> {
+    let _span = crate::profile::span("reconcile_anchors_with_quorum");
     if votes.is_empty() {
         return Ok(());
     }
@@ -564,6 +1637,112 @@ pub fn reconcile_anchors_with_quorum(
     Err("no anchor reached required quorum".to_string())
 }
 
+/// Stake-weighted anchor vote supplied to [`reconcile_anchors_with_weighted_quorum`].
+pub struct WeightedAnchorVote<'a> {
+    /// Ledger anchor produced by the peer.
+    pub anchor: &'a LedgerAnchor,
+    /// Public key bytes identifying the peer.
+    pub public_key: &'a [u8],
+    /// Voting weight (typically bonded stake) attributed to the peer.
+    pub weight: u64,
+}
+
+/// Converts a stake fraction (for example `2/3`) into an absolute quorum weight.
+///
+/// Rounds up, so the result is `ceil(total_weight * numerator / denominator)` and never
+/// less than one. A zero denominator yields an unreachable threshold.
+pub fn weighted_quorum_threshold(total_weight: u64, numerator: u64, denominator: u64) -> u64 {
+    if denominator == 0 {
+        return u64::MAX;
+    }
+    let scaled = (total_weight as u128) * (numerator as u128);
+    let threshold = scaled.div_ceil(denominator as u128);
+    threshold.clamp(1, u64::MAX as u128) as u64
+}
+
+type WeightedGroup = HashMap<Vec<u8>, (u64, LedgerAnchor)>;
+
+/// Ensures that identities holding at least `threshold_weight` agree on every transcript hash.
+///
+/// Votes are grouped by anchor digest and each group's weight is the sum of its distinct
+/// identities' weights. Tie-break rules:
+///
+/// * An identity repeated within one group is counted once, with the weight of its first vote.
+/// * An identity that votes for more than one digest is equivocating and its weight is
+///   excluded from every group.
+/// * The heaviest group wins; equal weights are broken by the number of distinct identities.
+/// * If two groups still tie and both meet the threshold, reconciliation fails rather than
+///   picking one arbitrarily.
+pub fn reconcile_anchors_with_weighted_quorum(
+    votes: &[WeightedAnchorVote<'_>],
+    threshold_weight: u64,
+) -> Result<(), String> {
+    let _span = crate::profile::span("reconcile_anchors_with_weighted_quorum");
+    if votes.is_empty() {
+        return Ok(());
+    }
+    if threshold_weight == 0 {
+        return Err("invalid quorum weight".to_string());
+    }
+    let mut digests_by_key: HashMap<&[u8], [u8; 32]> = HashMap::new();
+    let mut equivocators: HashSet<&[u8]> = HashSet::new();
+    let mut groups: HashMap<[u8; 32], WeightedGroup> = HashMap::new();
+    for vote in votes {
+        if vote.public_key.is_empty() {
+            return Err("vote missing public key bytes".to_string());
+        }
+        let digest = anchor_digest(vote.anchor);
+        match digests_by_key.get(vote.public_key) {
+            Some(existing) if *existing != digest => {
+                equivocators.insert(vote.public_key);
+            }
+            Some(_) => {}
+            None => {
+                digests_by_key.insert(vote.public_key, digest);
+            }
+        }
+        groups
+            .entry(digest)
+            .or_default()
+            .entry(vote.public_key.to_vec())
+            .or_insert_with(|| (vote.weight, vote.anchor.clone()));
+    }
+    let mut ranked: Vec<(u64, usize, Vec<LedgerAnchor>)> = groups
+        .into_values()
+        .map(|identity_map| {
+            let honest: Vec<(u64, LedgerAnchor)> = identity_map
+                .into_iter()
+                .filter(|(key, _)| !equivocators.contains(key.as_slice()))
+                .map(|(_, vote)| vote)
+                .collect();
+            let weight = honest
+                .iter()
+                .fold(0u64, |acc, (weight, _)| acc.saturating_add(*weight));
+            let count = honest.len();
+            let anchors = honest.into_iter().map(|(_, anchor)| anchor).collect();
+            (weight, count, anchors)
+        })
+        .collect();
+    ranked.sort_by_key(|(weight, count, _)| std::cmp::Reverse((*weight, *count)));
+    let mut ranked = ranked.into_iter();
+    let Some((weight, count, anchors)) = ranked.next() else {
+        return Err("no anchor reached required quorum weight".to_string());
+    };
+    if weight < threshold_weight || count == 0 {
+        return Err(format!(
+            "no anchor reached required quorum weight ({weight} < {threshold_weight})"
+        ));
+    }
+    if let Some((runner_weight, runner_count, _)) = ranked.next() {
+        if runner_weight == weight && runner_count == count {
+            return Err(format!(
+                "anchor quorum tie: two anchors hold weight {weight} from {count} identities"
+            ));
+        }
+    }
+    reconcile_anchors(&anchors)
+}
+
 type Blake2b256 = blake2::Blake2b<U32>;
 
 #[cfg(test)]
@@ -587,9 +1766,7 @@ mod tests {
         let field = Field::new(101);
         let poly = sample_poly(&field);
         let proof = GeneralSumProof::prove(&poly, &field);
-        let statement = Statement {
-            description: "Sum-check for 2-var polynomial".to_string(),
-        };
+        let statement = Statement::new("Sum-check for 2-var polynomial");
         let mut ledger = ProofLedger::new();
         let submission = Proof {
             kind: ProofKind::General {
@@ -612,6 +1789,28 @@ mod tests {
         assert_eq!(proof_entry.hashes.len(), 1);
     }
 
+    #[test]
+    fn test_ledger_accepts_range_proof() {
+        let field = Field::new(1_000_000_007);
+        let proof = RangeProof::prove(&[3, 14, 15, 9], 4, &field).unwrap();
+        let mut forged = proof.clone();
+        forged.values[2] = 16;
+        let mut ledger = ProofLedger::new();
+        for (label, range) in [("Range check", proof), ("Forged range", forged)] {
+            ledger.submit(
+                Statement::new(label),
+                Proof {
+                    kind: ProofKind::Range(range),
+                    data: Vec::new(),
+                },
+            );
+        }
+        let entries = ledger.entries();
+        assert!(entries[1].accepted);
+        assert_eq!(entries[1].hashes.len(), 1);
+        assert!(!entries[2].accepted);
+    }
+
     #[test]
     fn test_ledger_ensures_genesis() {
         let mut ledger = ProofLedger::new();
@@ -631,9 +1830,7 @@ mod tests {
         let streaming =
             StreamingPolynomial::new(poly.num_vars(), field.modulus(), move |idx| evals[idx]);
         let proof = GeneralSumProof::prove_streaming_poly(&streaming, &field);
-        let statement = Statement {
-            description: "Streaming sum-check".to_string(),
-        };
+        let statement = Statement::new("Streaming sum-check");
         let mut ledger = ProofLedger::new();
         ledger.submit(
             statement,
@@ -669,9 +1866,7 @@ mod tests {
             link.parent_final = Some(field.add(link.parent_final.unwrap(), 1));
         }
         let mut ledger = ProofLedger::new();
-        let statement = Statement {
-            description: "Tampered chained proof".to_string(),
-        };
+        let statement = Statement::new("Tampered chained proof");
         let submission = Proof {
             kind: ProofKind::Chain {
                 polynomials: polynomials.clone(),
@@ -690,6 +1885,104 @@ mod tests {
         assert!(entry.hashes.is_empty());
     }
 
+    #[test]
+    fn test_prover_binding_rejects_stolen_proofs() {
+        let field = Field::new(109);
+        let poly = sample_poly(&field);
+        let submitter = |key: u8| EntrySubmission {
+            public_key: vec![key; 32],
+            signature: Vec::new(),
+            submitted_ms: 0,
+        };
+        let general = |proof: GeneralSumProof| Proof {
+            kind: ProofKind::General {
+                polynomial: poly.clone(),
+                proof,
+            },
+            data: Vec::new(),
+        };
+        let bound = GeneralSumProof::prove_bound(&poly, &field, &[1; 32]);
+        assert!(bound
+            .verify_bound_with_trace(&poly, &field, &[1; 32])
+            .is_some());
+        assert!(!bound.verify(&poly, &field));
+
+        let mut ledger = ProofLedger::new();
+        ledger.enable_verification_cache(8);
+        ledger.set_prover_binding(ProverBinding::Required);
+        let statement = Statement::new("Bound proof");
+        ledger.submit_attributed(statement.clone(), general(bound.clone()), submitter(1));
+        ledger.submit_attributed(statement.clone(), general(bound.clone()), submitter(2));
+        ledger.submit_attributed(
+            statement.clone(),
+            general(GeneralSumProof::prove(&poly, &field)),
+            submitter(1),
+        );
+        ledger.submit(
+            statement.clone(),
+            general(GeneralSumProof::prove(&poly, &field)),
+        );
+        let (chain, _) = ChainedSumProof::prove_with_stats(std::slice::from_ref(&poly), &field);
+        ledger.submit_attributed(
+            statement,
+            Proof {
+                kind: ProofKind::Chain {
+                    polynomials: vec![poly.clone()],
+                    proof: chain,
+                },
+                data: Vec::new(),
+            },
+            submitter(1),
+        );
+        let accepted: Vec<bool> = ledger.entries()[1..]
+            .iter()
+            .map(|entry| entry.accepted)
+            .collect();
+        assert_eq!(accepted, [true, false, false, true, false]);
+        assert_eq!(
+            ledger.entries()[5].rejection,
+            Some(VerificationRejection::UnboundProofKind)
+        );
+    }
+
+    #[test]
+    fn test_compressed_logs_replay_to_the_same_anchor() {
+        let field = Field::new(1_000_000_007);
+        let evaluations = (0..64u64).map(|i| field.mul(i, 7_919)).collect();
+        let poly = MultilinearPolynomial::from_evaluations(6, evaluations);
+        let proof = GeneralSumProof::prove(&poly, &field);
+        let mut sizes = Vec::new();
+        for compress in [false, true] {
+            let base = std::env::temp_dir().join(format!("power_house_compressed_{compress}"));
+            let _ = std::fs::remove_dir_all(&base);
+            let mut ledger = ProofLedger::new();
+            ledger.enable_logging(&base);
+            if compress {
+                ledger.enable_transcript_compression();
+            }
+            ledger.submit(
+                Statement::new("Compressed proof"),
+                Proof {
+                    kind: ProofKind::General {
+                        polynomial: poly.clone(),
+                        proof: proof.clone(),
+                    },
+                    data: Vec::new(),
+                },
+            );
+            let log = &ledger.entries()[1].log_paths[0];
+            sizes.push(std::fs::metadata(log).unwrap().len());
+            let replayed = ProofLedger::replay_from_logs(&base).unwrap();
+            assert_eq!(replayed.anchor(), ledger.anchor());
+            assert_eq!(
+                replayed.entries()[1].transcripts,
+                vec![proof.challenges.clone()]
+            );
+            std::fs::remove_dir_all(&base).unwrap();
+        }
+        assert!(sizes[1] < sizes[0], "sizes {sizes:?}");
+    }
+
     #[test]
     fn test_ledger_writes_logs() {
         let field = Field::new(109);
@@ -701,9 +1994,7 @@ mod tests {
             std::fs::remove_dir_all(&base).unwrap();
         }
         ledger.enable_logging(&base);
-        let statement = Statement {
-            description: "Logged proof".into(),
-        };
+        let statement = Statement::new("Logged proof");
         ledger.submit(
             statement,
             Proof {
@@ -730,6 +2021,506 @@ mod tests {
         std::fs::remove_dir_all(&base).unwrap();
     }
 
+    #[test]
+    fn structured_statement_canonical_roundtrip() {
+        let statement = Statement::structured("dex|v2", "swap-settle")
+            .with_parameter("pool", "eth=usdc")
+            .with_parameter("epoch", "42")
+            .with_description("Settlement 100%");
+        let canonical = statement.canonical();
+        assert_eq!(
+            canonical,
+            "stmt:v1|app=dex%7Cv2|claim=swap-settle|param.epoch=42|param.pool=eth%3Dusdc|desc=Settlement 100%25"
+        );
+        assert_eq!(Statement::parse(&canonical), statement);
+        assert_eq!(statement.application_id(), Some("dex|v2"));
+
+        for legacy in [
+            "Logged proof",
+            JULIAN_GENESIS_STATEMENT,
+            "stmt:v1|app=|claim=x",
+            "stmt:v1|app=a|claim=b|desc=x|param.k=v",
+            "stmt:v1|app=a%zz|claim=b",
+        ] {
+            let parsed = Statement::parse(legacy);
+            assert!(!parsed.is_structured(), "{legacy}");
+            assert_eq!(parsed.canonical(), legacy);
+        }
+    }
+
+    #[test]
+    fn structured_statements_survive_replay_and_filter_by_application() {
+        let field = Field::new(109);
+        let poly = sample_poly(&field);
+        let proof = GeneralSumProof::prove(&poly, &field);
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let base = std::env::temp_dir().join(format!("power_house_structured_{nanos}"));
+        let mut ledger = ProofLedger::new();
+        ledger.enable_logging(&base);
+        let submit = |ledger: &mut ProofLedger, statement: Statement| {
+            ledger.submit(
+                statement,
+                Proof {
+                    kind: ProofKind::General {
+                        polynomial: poly.clone(),
+                        proof: proof.clone(),
+                    },
+                    data: Vec::new(),
+                },
+            )
+        };
+        submit(
+            &mut ledger,
+            Statement::structured("oracle", "price").with_parameter("pair", "BTC/USD"),
+        );
+        submit(&mut ledger, Statement::new("Legacy proof"));
+        submit(
+            &mut ledger,
+            Statement::structured("rollup", "batch").with_parameter("height", "7"),
+        );
+
+        assert_eq!(ledger.entries_for_application("oracle").count(), 1);
+        let replayed = ProofLedger::replay_from_logs(&base).unwrap();
+        assert_eq!(replayed.anchor(), ledger.anchor());
+        let rollup: Vec<&LedgerEntry> = replayed.entries_for_application("rollup").collect();
+        assert_eq!(rollup.len(), 1);
+        assert_eq!(rollup[0].statement.claim_type, "batch");
+        assert_eq!(rollup[0].statement.parameters["height"], "7");
+
+        let anchor = ledger.anchor();
+        let oracle: Vec<&EntryAnchor> = anchor.entries_for_application("oracle").collect();
+        assert_eq!(oracle.len(), 1);
+        assert_eq!(
+            oracle[0].parsed_statement().parameters["pair"],
+            "BTC/USD".to_string()
+        );
+        assert_eq!(anchor.entries_for_application("legacy").count(), 0);
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn test_ledger_writes_journal() {
+        let field = Field::new(109);
+        let poly = sample_poly(&field);
+        let proof = GeneralSumProof::prove(&poly, &field);
+        let mut ledger = ProofLedger::new();
+        let base = std::env::temp_dir().join("power_house_ledger_journal");
+        if base.exists() {
+            std::fs::remove_dir_all(&base).unwrap();
+        }
+        ledger
+            .enable_journal(&base, crate::JournalConfig::default())
+            .unwrap();
+        let statement = Statement::new("Journaled proof");
+        ledger.submit(
+            statement,
+            Proof {
+                kind: ProofKind::General {
+                    polynomial: poly,
+                    proof,
+                },
+                data: Vec::new(),
+            },
+        );
+        let entry = &ledger.entries()[1];
+        assert!(entry.accepted);
+        assert!(entry.log_error.is_none());
+        assert_eq!(entry.log_paths.len(), 1);
+        let records: Vec<_> = crate::iter_ledger_logs(&base)
+            .unwrap()
+            .map(|record| record.unwrap().parse().unwrap())
+            .collect();
+        assert_eq!(records.len(), entry.hashes.len());
+        assert_eq!(records[0].statement, "Journaled proof");
+        assert_eq!(records[0].digest, entry.hashes[0]);
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    fn replay_fixture(name: &str) -> (ProofLedger, std::path::PathBuf) {
+        let field = Field::new(101);
+        let poly_a = sample_poly(&field);
+        let proof_a = GeneralSumProof::prove(&poly_a, &field);
+        let poly_b = {
+            let points = 1usize << 3;
+            let c = field.mul(proof_a.final_evaluation, field.inv(points as u64));
+            MultilinearPolynomial::from_evaluations(3, vec![c; points])
+        };
+        let polynomials = vec![poly_a.clone(), poly_b];
+        let chain = ChainedSumProof::prove(&polynomials, &field);
+        let base = std::env::temp_dir().join(name);
+        if base.exists() {
+            std::fs::remove_dir_all(&base).unwrap();
+        }
+        let mut ledger = ProofLedger::new();
+        ledger.enable_logging(&base);
+        ledger.submit(
+            Statement::new("Replay general"),
+            Proof {
+                kind: ProofKind::General {
+                    polynomial: poly_a,
+                    proof: proof_a,
+                },
+                data: Vec::new(),
+            },
+        );
+        ledger.submit(
+            Statement::new("Replay chain"),
+            Proof {
+                kind: ProofKind::Chain {
+                    polynomials,
+                    proof: chain,
+                },
+                data: Vec::new(),
+            },
+        );
+        (ledger, base)
+    }
+
+    #[test]
+    fn test_replay_from_logs_rebuilds_ledger() {
+        let (ledger, base) = replay_fixture("power_house_ledger_replay");
+        let replayed = ProofLedger::replay_from_logs(&base).unwrap();
+        assert_eq!(replayed.anchor(), ledger.anchor());
+        assert_eq!(replayed.ledger_root(), ledger.ledger_root());
+        assert_eq!(ledger.ledger_root(), ledger.anchor().ledger_root());
+        let chain = &replayed.entries()[2];
+        assert!(chain.accepted);
+        assert_eq!(chain.merkle_root, merkle_root(&chain.hashes));
+        assert_eq!(chain.transcripts, ledger.entries()[2].transcripts);
+        assert_eq!(chain.log_paths, ledger.entries()[2].log_paths);
+
+        let tampered = &ledger.entries()[1].log_paths[0];
+        let contents = std::fs::read_to_string(tampered).unwrap();
+        std::fs::write(tampered, contents.replace("final:", "final:1")).unwrap();
+        let replayed = ProofLedger::replay_from_logs(&base).unwrap();
+        let rejected = &replayed.entries()[1];
+        assert!(!rejected.accepted);
+        assert!(rejected.hashes.is_empty());
+        assert!(rejected
+            .log_error
+            .as_deref()
+            .unwrap()
+            .contains("hash mismatch"));
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn test_replay_from_logs_keeps_repeated_submissions_apart() {
+        let field = Field::new(101);
+        let poly = sample_poly(&field);
+        let proof = GeneralSumProof::prove(&poly, &field);
+        let base = std::env::temp_dir().join("power_house_ledger_replay_repeated");
+        if base.exists() {
+            std::fs::remove_dir_all(&base).unwrap();
+        }
+        let mut ledger = ProofLedger::new();
+        ledger.enable_logging(&base);
+        for _ in 0..2 {
+            ledger.submit(
+                Statement::new("Repeated proof"),
+                Proof {
+                    kind: ProofKind::General {
+                        polynomial: poly.clone(),
+                        proof: proof.clone(),
+                    },
+                    data: Vec::new(),
+                },
+            );
+        }
+        let replayed = ProofLedger::replay_from_logs(&base).unwrap();
+        assert_eq!(replayed.entries().len(), 3);
+        assert_eq!(replayed.anchor(), ledger.anchor());
+        assert_eq!(
+            anchor_digest(&replayed.anchor()),
+            anchor_digest(&ledger.anchor())
+        );
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[cfg(feature = "net")]
+    #[test]
+    fn test_replay_from_logs_checks_checkpoints() {
+        use crate::net::{write_checkpoint, AnchorCheckpoint, AnchorJson};
+
+        let (ledger, base) = replay_fixture("power_house_ledger_replay_checkpoint");
+        let anchor =
+            AnchorJson::from_ledger("node", 1, &ledger.anchor(), 0, Vec::new(), None).unwrap();
+        let checkpoint =
+            AnchorCheckpoint::new(1, anchor, Vec::new(), Some("ledger_0002.txt".into()));
+        write_checkpoint(&base.join("checkpoints"), &checkpoint).unwrap();
+        assert!(ProofLedger::replay_from_logs(&base).is_ok());
+
+        let first = base.join("ledger_0000.txt");
+        let second = base.join("ledger_0001.txt");
+        let first_contents = std::fs::read(&first).unwrap();
+        std::fs::copy(&second, &first).unwrap();
+        std::fs::write(&second, first_contents).unwrap();
+        let err = ProofLedger::replay_from_logs(&base).unwrap_err();
+        assert!(err.contains("checkpoint 1 disagrees"), "{err}");
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[derive(Default)]
+    struct TestFeeAccounts(HashMap<Vec<u8>, u64>);
+
+    impl FeeAccounts for TestFeeAccounts {
+        fn debit_fee(&mut self, public_key: &[u8], _asset: &str, fee: u64) -> Result<(), String> {
+            let balance = self.0.entry(public_key.to_vec()).or_default();
+            if *balance < fee {
+                return Err("insufficient balance".into());
+            }
+            *balance -= fee;
+            Ok(())
+        }
+
+        fn refund_fee(&mut self, public_key: &[u8], _asset: &str, fee: u64) {
+            *self.0.entry(public_key.to_vec()).or_default() += fee;
+        }
+    }
+
+    #[test]
+    fn paid_submissions_debit_and_refund_fees() {
+        let field = Field::new(109);
+        let poly = sample_poly(&field);
+        let proof = GeneralSumProof::prove(&poly, &field);
+        let mut tampered = proof.clone();
+        tampered.claim.claimed_sum = field.add(tampered.claim.claimed_sum, 1);
+        let submission = EntrySubmission {
+            public_key: vec![7; 32],
+            signature: vec![1; 64],
+            submitted_ms: 10,
+        };
+        let schedule = SubmissionFeeSchedule {
+            base_fee: 5,
+            fee_per_byte: 1,
+            refund_on_rejection: true,
+            asset: None,
+        };
+        let mut accounts = TestFeeAccounts::default();
+        accounts.0.insert(submission.public_key.clone(), 20);
+        let make_proof = |proof: &GeneralSumProof| Proof {
+            kind: ProofKind::General {
+                polynomial: poly.clone(),
+                proof: proof.clone(),
+            },
+            data: vec![0; 3],
+        };
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let base = std::env::temp_dir().join(format!("power_house_paid_{nanos}"));
+        let mut ledger = ProofLedger::new();
+        ledger.enable_logging(&base);
+
+        let entry = ledger
+            .submit_paid(
+                Statement::new("Paid proof"),
+                make_proof(&proof),
+                submission.clone(),
+                &schedule,
+                &mut accounts,
+            )
+            .unwrap();
+        assert!(entry.accepted);
+        assert_eq!(
+            entry.fee,
+            Some(EntryFee {
+                paid: 8,
+                refunded: false
+            })
+        );
+        assert_eq!(accounts.0[&submission.public_key], 12);
+
+        let entry = ledger
+            .submit_paid(
+                Statement::new("Rejected proof"),
+                make_proof(&tampered),
+                submission.clone(),
+                &schedule,
+                &mut accounts,
+            )
+            .unwrap();
+        assert!(!entry.accepted);
+        assert!(entry.fee.unwrap().refunded);
+        assert_eq!(accounts.0[&submission.public_key], 12);
+
+        let broke = EntrySubmission {
+            public_key: vec![9; 32],
+            ..submission.clone()
+        };
+        let err = ledger
+            .submit_paid(
+                Statement::new("Unpaid proof"),
+                make_proof(&proof),
+                broke,
+                &schedule,
+                &mut accounts,
+            )
+            .unwrap_err();
+        assert!(err.contains("insufficient"), "{err}");
+        assert_eq!(ledger.entries().len(), 3);
+
+        let replayed = ProofLedger::replay_from_logs(&base).unwrap();
+        assert_eq!(replayed.entries()[1].fee.map(|fee| fee.paid), Some(8));
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[cfg(feature = "net")]
+    #[test]
+    fn stake_registry_pays_fees_under_governed_schedule() {
+        use crate::net::{
+            encode_public_key_base64, encode_signature_base64, governance::SignedApproval,
+            load_or_derive_keypair, sign_entry_submission, sign_payload, Ed25519KeySource,
+            FeeScheduleConfig, GovernanceUpdate, StakeRegistry, SupplyCause, UpdateKind,
+        };
+
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let base = std::env::temp_dir().join(format!("power_house_fee_schedule_{nanos}"));
+        std::fs::create_dir_all(&base).unwrap();
+        let governor = load_or_derive_keypair(&Ed25519KeySource::Seed("governor".into())).unwrap();
+        let submitter =
+            load_or_derive_keypair(&Ed25519KeySource::Seed("fee-payer".into())).unwrap();
+        let config_path = base.join("fee_schedule.json");
+        std::fs::write(
+            &config_path,
+            serde_json::json!({
+                "threshold": 1,
+                "signers": [encode_public_key_base64(&governor.verifying)],
+                "schedule": { "base_fee": 2 }
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let mut config = FeeScheduleConfig::load(&config_path).unwrap();
+        assert_eq!(config.schedule().base_fee, 2);
+
+        let metadata = serde_json::json!({ "base_fee": 6, "refund_on_rejection": true });
+        let untagged = format!(r#"{{"new_members":[],"metadata":{metadata}}}"#).into_bytes();
+        let canonical =
+            format!(r#"{{"kind":"fee_schedule","new_members":[],"metadata":{metadata}}}"#)
+                .into_bytes();
+        let mut update = GovernanceUpdate {
+            kind: UpdateKind::FeeSchedule,
+            new_members: Vec::new(),
+            metadata: Some(metadata),
+            signatures: vec![SignedApproval {
+                signer: encode_public_key_base64(&submitter.verifying),
+                signature: encode_signature_base64(&sign_payload(&submitter.signing, &canonical)),
+            }],
+        };
+        assert!(config.apply_update(&update).is_err());
+        update.signatures[0] = SignedApproval {
+            signer: encode_public_key_base64(&governor.verifying),
+            signature: encode_signature_base64(&sign_payload(&governor.signing, &untagged)),
+        };
+        assert!(config.apply_update(&update).is_err());
+        let membership = GovernanceUpdate {
+            kind: UpdateKind::Membership,
+            ..update.clone()
+        };
+        assert!(matches!(
+            config.apply_update(&membership),
+            Err(crate::net::PolicyUpdateError::WrongKind { .. })
+        ));
+        update.signatures[0] = SignedApproval {
+            signer: encode_public_key_base64(&governor.verifying),
+            signature: encode_signature_base64(&sign_payload(&governor.signing, &canonical)),
+        };
+        config.apply_update(&update).unwrap();
+        let reloaded = FeeScheduleConfig::load(&config_path).unwrap();
+        assert_eq!(reloaded.schedule().base_fee, 6);
+        assert!(reloaded.schedule().refund_on_rejection);
+
+        let field = Field::new(109);
+        let poly = sample_poly(&field);
+        let proof = GeneralSumProof::prove(&poly, &field);
+        let payer = encode_public_key_base64(&submitter.verifying);
+        let mut registry = StakeRegistry::default();
+        registry.mint(&payer, 10, SupplyCause::Funding).unwrap();
+        let statement = Statement::new("Registry paid");
+        let submission = sign_entry_submission(&submitter.signing, &statement.canonical(), 1);
+        let mut ledger = ProofLedger::new();
+        let entry = ledger
+            .submit_paid(
+                statement,
+                Proof {
+                    kind: ProofKind::General {
+                        polynomial: poly,
+                        proof,
+                    },
+                    data: Vec::new(),
+                },
+                submission,
+                reloaded.schedule(),
+                &mut registry,
+            )
+            .unwrap();
+        assert_eq!(entry.fee.map(|fee| fee.paid), Some(6));
+        assert_eq!(registry.account(&payer).unwrap().balance, 4);
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[cfg(feature = "net")]
+    #[test]
+    fn attributed_entries_survive_replay_and_bind_anchor_digest() {
+        use crate::net::{
+            load_or_derive_keypair, sign_entry_submission, verify_entry_submission, AnchorJson,
+            Ed25519KeySource,
+        };
+
+        let field = Field::new(109);
+        let poly = sample_poly(&field);
+        let proof = GeneralSumProof::prove(&poly, &field);
+        let key = load_or_derive_keypair(&Ed25519KeySource::Seed("submitter".into())).unwrap();
+        let statement = Statement::structured("oracle", "price").with_parameter("pair", "ETH/USD");
+        let submission = sign_entry_submission(&key.signing, &statement.canonical(), 1_700);
+        verify_entry_submission(&statement.canonical(), &submission).unwrap();
+        assert!(verify_entry_submission("other statement", &submission).is_err());
+
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let base = std::env::temp_dir().join(format!("power_house_attributed_{nanos}"));
+        let mut ledger = ProofLedger::new();
+        ledger.enable_logging(&base);
+        let make_proof = || Proof {
+            kind: ProofKind::General {
+                polynomial: poly.clone(),
+                proof: proof.clone(),
+            },
+            data: Vec::new(),
+        };
+        ledger.submit_attributed(statement.clone(), make_proof(), submission.clone());
+        ledger.submit(Statement::new("Unattributed"), make_proof());
+
+        let anchor = ledger.anchor();
+        assert_eq!(anchor.entries[1].submission.as_ref(), Some(&submission));
+        assert!(anchor.entries[2].submission.is_none());
+        let mut stripped = anchor.clone();
+        stripped.entries[1].submission = None;
+        assert_ne!(anchor_digest(&anchor), anchor_digest(&stripped));
+
+        let replayed = ProofLedger::replay_from_logs(&base).unwrap();
+        assert_eq!(replayed.anchor(), anchor);
+        assert_eq!(anchor_digest(&replayed.anchor()), anchor_digest(&anchor));
+
+        let json = AnchorJson::from_ledger("node", 1, &anchor, 0, Vec::new(), None).unwrap();
+        let decoded = AnchorJson::from_json_str(&json.to_json_string().unwrap())
+            .unwrap()
+            .into_ledger()
+            .unwrap();
+        assert_eq!(decoded.entries, anchor.entries);
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
     #[test]
     fn test_anchor_reconciliation_ok() {
         let field = Field::new(101);
@@ -737,9 +2528,7 @@ mod tests {
         let proof = GeneralSumProof::prove(&poly, &field);
         let mut ledger_a = ProofLedger::new();
         let mut ledger_b = ProofLedger::new();
-        let statement = Statement {
-            description: "Shared proof".into(),
-        };
+        let statement = Statement::new("Shared proof");
         let submission = Proof {
             kind: ProofKind::General {
                 polynomial: poly.clone(),
@@ -761,9 +2550,7 @@ mod tests {
         let proof = GeneralSumProof::prove(&poly, &field);
         let mut ledger_a = ProofLedger::new();
         let mut ledger_b = ProofLedger::new();
-        let statement = Statement {
-            description: "Divergent proof".into(),
-        };
+        let statement = Statement::new("Divergent proof");
         let submission = Proof {
             kind: ProofKind::General {
                 polynomial: poly.clone(),
@@ -792,9 +2579,7 @@ mod tests {
         let mut ledger_a = ProofLedger::new();
         let mut ledger_b = ProofLedger::new();
         let mut ledger_c = ProofLedger::new();
-        let statement = Statement {
-            description: "Quorum proof".into(),
-        };
+        let statement = Statement::new("Quorum proof");
         let submission = Proof {
             kind: ProofKind::General {
                 polynomial: poly.clone(),
@@ -830,9 +2615,7 @@ mod tests {
         let proof = GeneralSumProof::prove(&poly, &field);
         let mut ledger_a = ProofLedger::new();
         let mut ledger_b = ProofLedger::new();
-        let statement = Statement {
-            description: "Divergent quorum".into(),
-        };
+        let statement = Statement::new("Divergent quorum");
         let submission = Proof {
             kind: ProofKind::General {
                 polynomial: poly.clone(),
@@ -862,6 +2645,33 @@ mod tests {
         assert!(reconcile_anchors_with_quorum(&votes, 2).is_err());
     }
 
+    #[test]
+    fn test_reconcile_rejects_divergent_registry_roots() {
+        let ledger = ProofLedger::new();
+        let mut anchor_a = ledger.anchor();
+        let mut anchor_b = ledger.anchor();
+        let unattested = ledger.anchor();
+        anchor_a.metadata.registry_root = Some([1u8; 32]);
+        anchor_b.metadata.registry_root = Some([1u8; 32]);
+        let agreeing = [anchor_a.clone(), unattested, anchor_b.clone()];
+        assert!(reconcile_anchors(&agreeing).is_ok());
+
+        anchor_b.metadata.registry_root = Some([2u8; 32]);
+        assert_eq!(anchor_digest(&anchor_a), anchor_digest(&anchor_b));
+        let votes = [
+            AnchorVote {
+                anchor: &anchor_a,
+                public_key: b"A",
+            },
+            AnchorVote {
+                anchor: &anchor_b,
+                public_key: b"B",
+            },
+        ];
+        let err = reconcile_anchors_with_quorum(&votes, 2).unwrap_err();
+        assert!(err.contains("registry root"), "{err}");
+    }
+
     #[test]
     fn test_reconcile_rejects_duplicate_keys() {
         let field = Field::new(101);
@@ -869,9 +2679,7 @@ mod tests {
         let proof = GeneralSumProof::prove(&poly, &field);
         let mut ledger_a = ProofLedger::new();
         let mut ledger_b = ProofLedger::new();
-        let statement = Statement {
-            description: "Duplicate key check".into(),
-        };
+        let statement = Statement::new("Duplicate key check");
         let submission = Proof {
             kind: ProofKind::General {
                 polynomial: poly.clone(),
@@ -894,4 +2702,200 @@ mod tests {
         ];
         assert!(reconcile_anchors_with_quorum(&votes, 2).is_err());
     }
+
+    fn weighted_fixture() -> (LedgerAnchor, LedgerAnchor) {
+        let field = Field::new(101);
+        let poly = sample_poly(&field);
+        let proof = GeneralSumProof::prove(&poly, &field);
+        let mut honest = ProofLedger::new();
+        let mut divergent = ProofLedger::new();
+        let statement = Statement::new("Weighted quorum");
+        let submission = Proof {
+            kind: ProofKind::General {
+                polynomial: poly,
+                proof,
+            },
+            data: Vec::new(),
+        };
+        honest.submit(statement.clone(), submission.clone());
+        divergent.submit(statement, submission);
+        if let Some(hash) = divergent.entries[0].hashes.get_mut(0) {
+            hash[0] ^= 0x2A;
+        }
+        (honest.anchor(), divergent.anchor())
+    }
+
+    #[test]
+    fn test_weighted_quorum_threshold_rounds_up() {
+        assert_eq!(weighted_quorum_threshold(90, 2, 3), 60);
+        assert_eq!(weighted_quorum_threshold(100, 2, 3), 67);
+        assert_eq!(weighted_quorum_threshold(0, 2, 3), 1);
+        assert_eq!(weighted_quorum_threshold(10, 1, 0), u64::MAX);
+    }
+
+    #[test]
+    fn test_reconcile_with_weighted_quorum() {
+        let (honest, divergent) = weighted_fixture();
+        let votes = [
+            WeightedAnchorVote {
+                anchor: &honest,
+                public_key: b"A",
+                weight: 70,
+            },
+            WeightedAnchorVote {
+                anchor: &divergent,
+                public_key: b"B",
+                weight: 20,
+            },
+            WeightedAnchorVote {
+                anchor: &divergent,
+                public_key: b"C",
+                weight: 10,
+            },
+        ];
+        let threshold = weighted_quorum_threshold(100, 2, 3);
+        assert!(reconcile_anchors_with_weighted_quorum(&votes, threshold).is_ok());
+        // Two identities outnumber one, but not by stake.
+        assert!(reconcile_anchors_with_weighted_quorum(&votes[1..], threshold).is_err());
+        assert!(reconcile_anchors_with_weighted_quorum(&votes, 0).is_err());
+    }
+
+    #[test]
+    fn test_weighted_quorum_tie_breaks() {
+        let (honest, divergent) = weighted_fixture();
+        // Equal weight: the group with more identities wins.
+        let votes = [
+            WeightedAnchorVote {
+                anchor: &honest,
+                public_key: b"A",
+                weight: 25,
+            },
+            WeightedAnchorVote {
+                anchor: &honest,
+                public_key: b"B",
+                weight: 25,
+            },
+            WeightedAnchorVote {
+                anchor: &divergent,
+                public_key: b"C",
+                weight: 50,
+            },
+        ];
+        assert!(reconcile_anchors_with_weighted_quorum(&votes, 50).is_ok());
+        // Equal weight and identity count: refuse to pick a side.
+        let votes = [
+            WeightedAnchorVote {
+                anchor: &honest,
+                public_key: b"A",
+                weight: 50,
+            },
+            WeightedAnchorVote {
+                anchor: &divergent,
+                public_key: b"C",
+                weight: 50,
+            },
+        ];
+        let err = reconcile_anchors_with_weighted_quorum(&votes, 50).unwrap_err();
+        assert!(err.contains("tie"));
+    }
+
+    #[test]
+    fn test_weighted_quorum_ignores_equivocators() {
+        let (honest, divergent) = weighted_fixture();
+        let votes = [
+            WeightedAnchorVote {
+                anchor: &honest,
+                public_key: b"A",
+                weight: 40,
+            },
+            WeightedAnchorVote {
+                anchor: &honest,
+                public_key: b"E",
+                weight: 60,
+            },
+            WeightedAnchorVote {
+                anchor: &divergent,
+                public_key: b"E",
+                weight: 60,
+            },
+            WeightedAnchorVote {
+                anchor: &honest,
+                public_key: b"A",
+                weight: 500,
+            },
+        ];
+        assert!(reconcile_anchors_with_weighted_quorum(&votes, 40).is_ok());
+        assert!(reconcile_anchors_with_weighted_quorum(&votes, 41).is_err());
+    }
+
+    #[test]
+    fn verification_limits_reject_oversized_proofs_with_reason() {
+        let field = Field::new(101);
+        let poly = sample_poly(&field);
+        let general = || Proof {
+            kind: ProofKind::General {
+                polynomial: poly.clone(),
+                proof: GeneralSumProof::prove(&poly, &field),
+            },
+            data: Vec::new(),
+        };
+        let mut ledger = ProofLedger::new();
+        ledger.enable_verification_cache(8);
+        ledger.set_verification_limits(VerificationLimits {
+            max_vars: 1,
+            ..VerificationLimits::unlimited()
+        });
+        ledger.submit(Statement::new("wide"), general());
+        let entry = ledger.entries().last().unwrap();
+        assert!(!entry.accepted && entry.hashes.is_empty());
+        assert_eq!(
+            entry.rejection,
+            Some(VerificationRejection::TooManyVariables {
+                num_vars: 2,
+                max: 1
+            })
+        );
+
+        let final_evaluation = GeneralSumProof::prove(&poly, &field).final_evaluation;
+        let constant = field.mul(final_evaluation, field.inv(4));
+        let polynomials = vec![
+            poly.clone(),
+            MultilinearPolynomial::from_evaluations(2, vec![constant; 4]),
+        ];
+        let chain = ChainedSumProof::prove(&polynomials, &field);
+        ledger.set_verification_limits(VerificationLimits {
+            max_chain_links: 1,
+            ..VerificationLimits::unlimited()
+        });
+        ledger.submit(
+            Statement::new("long"),
+            Proof {
+                kind: ProofKind::Chain {
+                    polynomials,
+                    proof: chain,
+                },
+                data: Vec::new(),
+            },
+        );
+        assert_eq!(
+            ledger.entries().last().unwrap().rejection,
+            Some(VerificationRejection::ChainTooLong { links: 2, max: 1 })
+        );
+
+        ledger.set_verification_limits(VerificationLimits {
+            time_budget: Some(Duration::ZERO),
+            ..VerificationLimits::unlimited()
+        });
+        ledger.submit(Statement::new("slow"), general());
+        assert_eq!(
+            ledger.entries().last().unwrap().rejection,
+            Some(VerificationRejection::BudgetExceeded { budget_ms: 0 })
+        );
+        assert!(ledger.verification_cache().unwrap().is_empty());
+
+        ledger.set_verification_limits(VerificationLimits::default());
+        ledger.submit(Statement::new("fits"), general());
+        let entry = ledger.entries().last().unwrap();
+        assert!(entry.accepted && entry.rejection.is_none());
+    }
 }
@@ -0,0 +1,491 @@
+//! Versioned key-value store anchored in a [`ProofLedger`].
+//!
+//! Every `put` becomes a structured ledger statement in the `power_house.kv`
+//! application carrying the key, a digest of the value, and a version one
+//! above the key's previous version.  The statement is backed by a small
+//! sum-check proof over a table derived from those three fields, so the
+//! entry's transcript hash — and with it the ledger root — commits to them.
+//!
+//! A [`KvReceipt`] is a Merkle path from that transcript hash to the ledger
+//! root.  Anyone can recompute the leaf from the receipt's key, digest, and
+//! version, and a holder of an anchor can additionally confirm that no later
+//! version of the key was anchored, which proves the value is the latest.
+//!
+//! Values themselves stay off the ledger; [`KvStore::open`] keeps them beside
+//! the logs under `kv_values/` so `get` can return them.
+
+use crate::{
+    build_merkle_proof, transcript_digest, transcript_digest_from_hex, transcript_digest_to_hex,
+    verify_merkle_proof, Field, GeneralSumProof, JournalConfig, LedgerAnchor, MerkleProof,
+    MerkleProofNode, MultilinearPolynomial, ParameterRule, ParameterType, Proof, ProofKind,
+    ProofLedger, Statement, StatementSchema, TranscriptDigest,
+};
+use blake2::digest::{consts::U32, Digest};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+type Blake2b256 = blake2::Blake2b<U32>;
+
+/// Application id of key-value statements.
+pub const KV_APPLICATION_ID: &str = "power_house.kv";
+
+/// Claim type of a key-value write.
+pub const KV_CLAIM_PUT: &str = "put";
+
+/// Schema identifier of a [`KvReceipt`].
+pub const SCHEMA_KV_RECEIPT: &str = "mfenx.powerhouse.kv_receipt.v1";
+
+/// Longest accepted key, in bytes.
+pub const KV_MAX_KEY_LENGTH: usize = 256;
+
+const KV_VALUE_DOMAIN: &[u8] = b"MFENX_KV_VALUE";
+const KV_BINDING_DOMAIN: &[u8] = b"MFENX_KV_BINDING";
+/// Goldilocks prime; every table word is a `u32`, so it is always reduced.
+const KV_MODULUS: u64 = 0xFFFF_FFFF_0000_0001;
+const VALUES_DIR: &str = "kv_values";
+
+fn mix_u64(hasher: &mut Blake2b256, value: u64) {
+    hasher.update(value.to_be_bytes());
+}
+
+fn mix_bytes(hasher: &mut Blake2b256, bytes: &[u8]) {
+    mix_u64(hasher, bytes.len() as u64);
+    hasher.update(bytes);
+}
+
+/// Domain-separated digest of a stored value.
+pub fn kv_value_digest(value: &[u8]) -> TranscriptDigest {
+    let mut hasher = Blake2b256::new();
+    hasher.update(KV_VALUE_DOMAIN);
+    mix_bytes(&mut hasher, value);
+    hasher.finalize().into()
+}
+
+/// Ledger statement recording version `version` of `key`.
+pub fn kv_statement(key: &str, value_digest: &TranscriptDigest, version: u64) -> Statement {
+    Statement::structured(KV_APPLICATION_ID, KV_CLAIM_PUT)
+        .with_parameter("key", key)
+        .with_parameter("value_digest", transcript_digest_to_hex(value_digest))
+        .with_parameter("version", version.to_string())
+}
+
+/// Schema every key-value statement satisfies.
+pub fn kv_statement_schema() -> StatementSchema {
+    let rule = |kind| ParameterRule {
+        kind,
+        required: true,
+    };
+    StatementSchema {
+        application_id: KV_APPLICATION_ID.to_string(),
+        claim_types: vec![KV_CLAIM_PUT.to_string()],
+        parameters: BTreeMap::from([
+            (
+                "key".to_string(),
+                rule(ParameterType::String {
+                    max_length: Some(KV_MAX_KEY_LENGTH),
+                }),
+            ),
+            (
+                "value_digest".to_string(),
+                rule(ParameterType::Hex { bytes: Some(32) }),
+            ),
+            (
+                "version".to_string(),
+                rule(ParameterType::Uint {
+                    min: Some(1),
+                    max: None,
+                }),
+            ),
+        ]),
+        additional_parameters: false,
+    }
+}
+
+/// Eight-word table whose sum-check transcript commits to the write.
+fn binding_polynomial(
+    key: &str,
+    value_digest: &TranscriptDigest,
+    version: u64,
+) -> MultilinearPolynomial {
+    let mut hasher = Blake2b256::new();
+    hasher.update(KV_BINDING_DOMAIN);
+    mix_bytes(&mut hasher, key.as_bytes());
+    hasher.update(value_digest);
+    mix_u64(&mut hasher, version);
+    let binding: TranscriptDigest = hasher.finalize().into();
+    let words = binding
+        .chunks_exact(4)
+        .map(|word| u64::from(u32::from_be_bytes(word.try_into().expect("4-byte chunk"))))
+        .collect();
+    MultilinearPolynomial::from_evaluations(3, words)
+}
+
+/// Proof submitted alongside [`kv_statement`].
+pub fn kv_proof(key: &str, value_digest: &TranscriptDigest, version: u64) -> Proof {
+    let polynomial = binding_polynomial(key, value_digest, version);
+    let proof = GeneralSumProof::prove(&polynomial, &Field::new(KV_MODULUS));
+    Proof {
+        kind: ProofKind::General { polynomial, proof },
+        data: Vec::new(),
+    }
+}
+
+/// Transcript hash the ledger records for a write, recomputed from its fields.
+pub fn kv_transcript_digest(
+    key: &str,
+    value_digest: &TranscriptDigest,
+    version: u64,
+) -> TranscriptDigest {
+    let polynomial = binding_polynomial(key, value_digest, version);
+    let field = Field::new(KV_MODULUS);
+    let trace = GeneralSumProof::prove(&polynomial, &field)
+        .verify_with_trace(&polynomial, &field)
+        .expect("honest binding proof verifies");
+    transcript_digest(&trace.challenges, &trace.round_sums, trace.final_evaluation)
+}
+
+/// One anchored write.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KvRecord {
+    /// Key written.
+    pub key: String,
+    /// Version of the key, starting at 1.
+    pub version: u64,
+    /// [`kv_value_digest`] of the value.
+    pub value_digest: TranscriptDigest,
+    /// Index of the ledger entry holding the write.
+    pub entry_index: usize,
+}
+
+impl KvRecord {
+    /// Parses a key-value write from `statement`.
+    pub fn from_statement(statement: &Statement, entry_index: usize) -> Option<Self> {
+        if statement.application_id() != Some(KV_APPLICATION_ID)
+            || statement.claim_type != KV_CLAIM_PUT
+        {
+            return None;
+        }
+        let key = statement.parameters.get("key")?.clone();
+        let version = statement.parameters.get("version")?.parse().ok()?;
+        let value_digest =
+            transcript_digest_from_hex(statement.parameters.get("value_digest")?).ok()?;
+        Some(Self {
+            key,
+            version,
+            value_digest,
+            entry_index,
+        })
+    }
+}
+
+/// Sibling on a [`KvReceipt`] Merkle path.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KvPathNode {
+    /// Hex sibling digest.
+    pub sibling: String,
+    /// Whether the sibling is the left operand.
+    pub left: bool,
+}
+
+/// Merkle proof that a key's value was anchored, and was its latest version
+/// as of the ledger it was issued from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KvReceipt {
+    /// Schema identifier (`mfenx.powerhouse.kv_receipt.v1`).
+    pub schema: String,
+    /// Key proven.
+    pub key: String,
+    /// Version proven.
+    pub version: u64,
+    /// Hex [`kv_value_digest`] of the value.
+    pub value_digest: String,
+    /// Ledger entry holding the write.
+    pub entry_index: usize,
+    /// Position of the write's transcript hash among all ledger hashes.
+    pub leaf_index: usize,
+    /// Path from the transcript hash to [`Self::ledger_root`].
+    pub path: Vec<KvPathNode>,
+    /// Hex ledger root the path ends in.
+    pub ledger_root: String,
+    /// Number of ledger entries when the receipt was issued.
+    pub ledger_entries: usize,
+}
+
+impl KvReceipt {
+    fn merkle_proof(&self) -> Result<MerkleProof, String> {
+        let value_digest = transcript_digest_from_hex(&self.value_digest)?;
+        let path = self
+            .path
+            .iter()
+            .map(|node| {
+                Ok(MerkleProofNode {
+                    sibling: transcript_digest_from_hex(&node.sibling)?,
+                    left: node.left,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(MerkleProof {
+            root: transcript_digest_from_hex(&self.ledger_root)?,
+            leaf: kv_transcript_digest(&self.key, &value_digest, self.version),
+            index: self.leaf_index,
+            path,
+        })
+    }
+
+    /// Checks the schema and that the write's recomputed transcript hash
+    /// reaches [`Self::ledger_root`].
+    pub fn verify(&self) -> Result<(), String> {
+        if self.schema != SCHEMA_KV_RECEIPT {
+            return Err(format!("unexpected schema {}", self.schema));
+        }
+        if verify_merkle_proof(&self.merkle_proof()?) {
+            Ok(())
+        } else {
+            Err("merkle path does not reach the ledger root".to_string())
+        }
+    }
+
+    /// Checks the receipt against `anchor`: the write must be anchored at
+    /// [`Self::entry_index`] and no later entry may write the same key.
+    ///
+    /// The anchor may extend the ledger the receipt was issued from, which
+    /// shows the value is still the latest.
+    pub fn verify_against(&self, anchor: &LedgerAnchor) -> Result<(), String> {
+        self.verify()?;
+        if anchor.entries.len() < self.ledger_entries {
+            return Err(format!(
+                "anchor has {} entries, receipt was issued at {}",
+                anchor.entries.len(),
+                self.ledger_entries
+            ));
+        }
+        if anchor.entries.len() == self.ledger_entries
+            && transcript_digest_to_hex(&anchor.ledger_root()) != self.ledger_root
+        {
+            return Err("anchor ledger root differs from the receipt".to_string());
+        }
+        let value_digest = transcript_digest_from_hex(&self.value_digest)?;
+        let entry = &anchor.entries[self.entry_index];
+        let leaf = kv_transcript_digest(&self.key, &value_digest, self.version);
+        if entry.parsed_statement() != kv_statement(&self.key, &value_digest, self.version)
+            || !entry.hashes.contains(&leaf)
+        {
+            return Err(format!(
+                "anchor entry {} is not this write",
+                self.entry_index
+            ));
+        }
+        for (index, later) in anchor.entries.iter().enumerate().skip(self.entry_index + 1) {
+            if let Some(record) = KvRecord::from_statement(&later.parsed_statement(), index) {
+                if record.key == self.key {
+                    return Err(format!(
+                        "superseded by version {} at entry {index}",
+                        record.version
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `true` when `value` is the value the receipt commits to.
+    pub fn matches_value(&self, value: &[u8]) -> bool {
+        transcript_digest_to_hex(&kv_value_digest(value)) == self.value_digest
+    }
+}
+
+/// Key-value view over a ledger, appending writes as new entries.
+#[derive(Debug)]
+pub struct KvStore {
+    ledger: ProofLedger,
+    latest: BTreeMap<String, KvRecord>,
+    values_dir: Option<PathBuf>,
+}
+
+impl KvStore {
+    /// Indexes the accepted key-value writes already in `ledger`.
+    pub fn from_ledger(mut ledger: ProofLedger) -> Self {
+        ledger.register_statement_schema(kv_statement_schema());
+        let mut latest: BTreeMap<String, KvRecord> = BTreeMap::new();
+        for (index, entry) in ledger.entries().iter().enumerate() {
+            if !entry.accepted {
+                continue;
+            }
+            if let Some(record) = KvRecord::from_statement(&entry.statement, index) {
+                if latest
+                    .get(&record.key)
+                    .is_none_or(|current| record.version > current.version)
+                {
+                    latest.insert(record.key.clone(), record);
+                }
+            }
+        }
+        Self {
+            ledger,
+            latest,
+            values_dir: None,
+        }
+    }
+
+    /// Replays the logs in `log_dir` without enabling writes to disk.
+    pub fn load(log_dir: &Path) -> Result<Self, String> {
+        let mut store = Self::from_ledger(ProofLedger::replay_from_logs(log_dir)?);
+        store.values_dir = Some(log_dir.join(VALUES_DIR));
+        Ok(store)
+    }
+
+    /// Replays the logs in `log_dir` and appends further writes to its
+    /// transcript journal.
+    pub fn open(log_dir: &Path) -> Result<Self, String> {
+        fs::create_dir_all(log_dir)
+            .map_err(|err| format!("create {}: {err}", log_dir.display()))?;
+        let mut store = Self::load(log_dir)?;
+        store
+            .ledger
+            .enable_journal(log_dir, JournalConfig::default())
+            .map_err(|err| format!("open journal in {}: {err}", log_dir.display()))?;
+        Ok(store)
+    }
+
+    /// Writes `value` under `key` as the next version and returns its receipt.
+    pub fn put(&mut self, key: &str, value: &[u8]) -> Result<KvReceipt, String> {
+        if key.is_empty() || key.len() > KV_MAX_KEY_LENGTH {
+            return Err(format!(
+                "key must be 1 to {KV_MAX_KEY_LENGTH} bytes, got {}",
+                key.len()
+            ));
+        }
+        let value_digest = kv_value_digest(value);
+        let version = self.latest.get(key).map_or(1, |record| record.version + 1);
+        if let Some(dir) = &self.values_dir {
+            fs::create_dir_all(dir).map_err(|err| format!("create {}: {err}", dir.display()))?;
+            let path = dir.join(transcript_digest_to_hex(&value_digest));
+            fs::write(&path, value).map_err(|err| format!("write {}: {err}", path.display()))?;
+        }
+        self.ledger.submit(
+            kv_statement(key, &value_digest, version),
+            kv_proof(key, &value_digest, version),
+        );
+        let entry = self.ledger.entries().last().expect("entry just recorded");
+        if !entry.accepted {
+            let reason = entry
+                .rejection
+                .as_ref()
+                .map_or_else(|| "verification failed".to_string(), ToString::to_string);
+            return Err(format!("ledger rejected the write: {reason}"));
+        }
+        if let Some(err) = &entry.log_error {
+            return Err(format!("write was not logged: {err}"));
+        }
+        let record = KvRecord {
+            key: key.to_string(),
+            version,
+            value_digest,
+            entry_index: self.ledger.entries().len() - 1,
+        };
+        self.latest.insert(key.to_string(), record);
+        Ok(self.prove(key).expect("key was just written"))
+    }
+
+    /// Latest write of `key`.
+    pub fn get(&self, key: &str) -> Option<&KvRecord> {
+        self.latest.get(key)
+    }
+
+    /// Stored value of `record`, when the store keeps values on disk.
+    pub fn value(&self, record: &KvRecord) -> Option<Vec<u8>> {
+        let path = self
+            .values_dir
+            .as_ref()?
+            .join(transcript_digest_to_hex(&record.value_digest));
+        fs::read(path)
+            .ok()
+            .filter(|value| kv_value_digest(value) == record.value_digest)
+    }
+
+    /// Receipt for the latest write of `key` against the current ledger root.
+    pub fn prove(&self, key: &str) -> Option<KvReceipt> {
+        let record = self.latest.get(key)?;
+        let entries = self.ledger.entries();
+        let leaf_index = entries[..record.entry_index]
+            .iter()
+            .map(|entry| entry.hashes.len())
+            .sum();
+        let hashes: Vec<TranscriptDigest> = entries
+            .iter()
+            .flat_map(|entry| entry.hashes.iter().copied())
+            .collect();
+        let proof = build_merkle_proof(&hashes, leaf_index)?;
+        Some(KvReceipt {
+            schema: SCHEMA_KV_RECEIPT.to_string(),
+            key: record.key.clone(),
+            version: record.version,
+            value_digest: transcript_digest_to_hex(&record.value_digest),
+            entry_index: record.entry_index,
+            leaf_index,
+            path: proof
+                .path
+                .iter()
+                .map(|node| KvPathNode {
+                    sibling: transcript_digest_to_hex(&node.sibling),
+                    left: node.left,
+                })
+                .collect(),
+            ledger_root: transcript_digest_to_hex(&proof.root),
+            ledger_entries: entries.len(),
+        })
+    }
+
+    /// Keys with at least one write, in order.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.latest.keys().map(String::as_str)
+    }
+
+    /// Underlying ledger.
+    pub fn ledger(&self) -> &ProofLedger {
+        &self.ledger
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn receipts_prove_latest_value_across_reopen() {
+        let dir = std::env::temp_dir().join(format!("power_house_kv_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut store = KvStore::open(&dir).unwrap();
+        let first = store.put("config/rate", b"10").unwrap();
+        store.put("owner", b"alice").unwrap();
+        first.verify().unwrap();
+        first.verify_against(&store.ledger().anchor()).unwrap();
+        store.put("config/rate", b"12").unwrap();
+        drop(store);
+
+        let store = KvStore::load(&dir).unwrap();
+        let record = store.get("config/rate").unwrap();
+        assert_eq!(record.version, 2);
+        assert_eq!(store.value(record).unwrap(), b"12");
+        let receipt = store.prove("config/rate").unwrap();
+        assert_eq!(
+            transcript_digest_to_hex(&store.ledger().ledger_root()),
+            receipt.ledger_root
+        );
+        receipt.verify_against(&store.ledger().anchor()).unwrap();
+        assert!(receipt.matches_value(b"12") && !receipt.matches_value(b"10"));
+        assert_eq!(
+            first.verify_against(&store.ledger().anchor()),
+            Err("superseded by version 2 at entry 3".to_string())
+        );
+
+        let mut forged = receipt;
+        forged.value_digest = transcript_digest_to_hex(&kv_value_digest(b"99"));
+        assert!(forged.verify().is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -3,7 +3,7 @@
 //! Deterministic verification, portable proof provenance, and optional quorum
 //! networking.
 //!
-//! Power House combines seven interoperable layers:
+//! Power House combines eight interoperable layers:
 //!
 //! - [`identity`] provides immutable computational identities over `.pha` and
 //!   Rootprint.
@@ -15,12 +15,27 @@
 //! - [`provenance`] defines Power House Archive (`.pha`) and Rootprint v1.
 //! - [`sumcheck`] implements dense, streaming, constant, and seeded-affine
 //!   sum-check workflows.
+//! - [`aggregate`] folds an epoch's accepted sum-check proofs into a single
+//!   epoch proof that checkpoints can carry.
+//! - [`linalg`] compiles inner-product and matrix-multiplication claims into
+//!   multilinear sum-check statements.
+//! - [`range`] checks that public values fit in a bit width.
+//! - [`agreement`] lets two nodes show their committed tables are equal
+//!   without exchanging them.
+//! - [`fri`] adds a FRI low-degree test over NTT-friendly fields.
 //! - [`sfcs`] defines opt-in draft computational-fractal primitives when the
 //!   `sfcs` feature is enabled.
+//! - [`sparse_merkle`] commits to keyed state with inclusion and non-inclusion
+//!   proofs.
 //! - [`sparse_sumcheck`] implements stable seeded and commitment-bound sparse
 //!   certificate formats.
 //! - [`julian`] records proof transcripts, anchors them, and reconciles quorum
 //!   state.
+//! - [`kv`] layers a versioned key-value store over ledger statements with
+//!   Merkle receipts of the latest value.
+//! - [`clock`] supplies injectable timestamps so artifacts can be reproduced
+//!   byte for byte.
+//! - [`profile`] times hot paths as nested spans for logs and flamegraphs.
 //! - [`net`] adds signed libp2p transport, data availability, governance, and
 //!   quorum-finalized native RPC when the `net` feature is enabled.
 //!
@@ -89,7 +104,11 @@
 //! # Feature flags
 //!
 //! - `default`: proof, provenance, transcript, sparse-certificate, and memory
-//!   APIs.
+//!   APIs, plus `parallel` and `rollup`.
+//! - `verify-only`: with `--no-default-features`, field, sum-check, Merkle,
+//!   transcript, and anchor verification without heavy dependencies.
+//! - `parallel`: multithreaded dense sum-check proving.
+//! - `rollup`: Groth16 rollup settlement over the arkworks stack.
 //! - `memory`: portable proof-memory capsules without network access.
 //! - `memory-net`: memory workflows that may compose with network features.
 //! - `net`: networking, migration commands, data availability, governance,
@@ -102,27 +121,44 @@
 //! verification guide, and operational runbooks. See the
 //! [documentation index](https://github.com/JROChub/power_house/blob/main/docs/README.md).
 
+pub mod aggregate;
+pub mod agreement;
+pub mod clock;
 pub mod consensus;
 mod data;
 pub mod economics;
 mod field;
+pub mod fri;
+pub mod genesis;
 pub mod identity;
 mod io;
+mod journal;
 pub mod julian;
+pub mod kv;
+pub mod linalg;
 mod log_parser;
 pub mod memory;
 mod merkle;
 mod multilinear;
+mod ntt;
 pub mod observatory;
 mod prng;
+pub mod profile;
 pub mod provenance;
+pub mod range;
+#[cfg(feature = "rollup")]
 pub mod rollup;
 #[cfg(feature = "sfcs")]
 pub mod sfcs;
+pub mod sparse_merkle;
 pub mod sparse_sumcheck;
+pub mod statement_schema;
 mod streaming;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod submission_queue;
 pub mod sumcheck;
 mod transcript;
+pub mod verification_cache;
 
 /// CLI command helpers for migration and deterministic artifacts.
 #[cfg(feature = "net")]
@@ -130,22 +166,50 @@ pub mod commands;
 #[cfg(feature = "net")]
 pub mod net;
 
+pub use aggregate::{AggregateError, EpochAggregator, EpochProof};
+pub use agreement::{
+    check_agreement, AgreementChallenge, AgreementError, AgreementRecord, AgreementResponse,
+    TableCommitment,
+};
+pub use clock::{artifact_clock, Clock, FixedClock, InputClock, SystemClock};
 pub use consensus::consensus;
 pub use data::{
     compute_digest as transcript_digest, digest_from_hex as transcript_digest_from_hex,
-    digest_to_hex as transcript_digest_to_hex, parse_record as parse_transcript_record,
-    verify_record_lines as verify_transcript_lines, write_record as write_transcript_record,
-    TranscriptDigest,
+    digest_to_hex as transcript_digest_to_hex, is_binary_record as is_binary_transcript_record,
+    parse_record as parse_transcript_record, read_binary_record as read_binary_transcript_record,
+    verify_binary_record as verify_binary_transcript_record,
+    verify_record_lines as verify_transcript_lines,
+    write_binary_record as write_binary_transcript_record,
+    write_compact_record as write_compact_transcript_record,
+    write_record as write_transcript_record, BinaryRecord as BinaryTranscriptRecord,
+    CompressedRecord as CompressedTranscriptRecord, TranscriptDigest,
 };
 pub use field::Field;
+pub use fri::{FriConfig, FriProof};
+pub use genesis::{active_genesis, install_genesis, GenesisConfig};
 pub use identity::{Identity, IdentityError, IdentityState};
 pub use io::write_text_series;
+pub use journal::{
+    is_journal_segment, latest_journal_record, FsyncPolicy, JournalConfig, JournalPosition,
+    JournalReader, JournalRecord, TranscriptJournal,
+};
 pub use julian::{
-    compute_fold_digest, julian_genesis_anchor, julian_genesis_hash, reconcile_anchors,
-    reconcile_anchors_with_quorum, AnchorMetadata, AnchorVote, EntryAnchor, LedgerAnchor, Proof,
-    ProofKind, ProofLedger, Statement, JULIAN_GENESIS_STATEMENT,
+    compute_fold_digest, julian_genesis_anchor, julian_genesis_hash, julian_genesis_statement,
+    reconcile_anchors, reconcile_anchors_with_quorum, reconcile_anchors_with_weighted_quorum,
+    statement_digest, weighted_quorum_threshold, AnchorMetadata, AnchorVote, EntryAnchor, EntryFee,
+    EntrySubmission, LedgerAnchor, LedgerEvent, Proof, ProofKind, ProofLedger, ProverBinding,
+    Statement, VerificationLimits, VerificationRejection, WeightedAnchorVote,
+    JULIAN_GENESIS_STATEMENT, STATEMENT_SCHEMA_PREFIX,
+};
+pub use kv::{
+    kv_proof, kv_statement, kv_statement_schema, kv_transcript_digest, kv_value_digest, KvPathNode,
+    KvReceipt, KvRecord, KvStore, KV_APPLICATION_ID, SCHEMA_KV_RECEIPT,
+};
+pub use linalg::{InnerProductClaim, LinalgError, MatMulClaim};
+pub use log_parser::{
+    is_ledger_log_file, iter_ledger_logs, parse_log_bytes, parse_log_file, read_fold_digest_hint,
+    LedgerLogIter, LedgerLogRecord, LogFault, LogFaultKind, LogRecordMetadata, ParsedLogFile,
 };
-pub use log_parser::{parse_log_file, read_fold_digest_hint, LogRecordMetadata, ParsedLogFile};
 pub use memory::{
     ChallengeSuite, ChallengeVector, MemoryCapsule, MemoryCapsuleBuilder, MemoryCapsuleReport,
     MemoryChallengeReport, MemoryError, MemoryReplayReport, MemoryVerificationPolicy,
@@ -153,11 +217,13 @@ pub use memory::{
 };
 pub use merkle::{
     build_proof as build_merkle_proof, merkle_root, verify_proof as verify_merkle_proof,
-    MerkleProof, MerkleProofNode,
+    IncrementalMerkleTree, MerkleProof, MerkleProofNode,
 };
 pub use multilinear::MultilinearPolynomial;
+pub use ntt::{forward_ntt, inverse_ntt, root_of_unity, two_adicity};
 pub use observatory::{ObservatoryError, ObservatorySidecar};
-pub use prng::SimplePrng;
+pub use prng::{derive_many_mod_p, derive_many_mod_p_with, ChaChaPrng, PrngBackend, SimplePrng};
+pub use range::{RangeProof, RangeProofError};
 #[cfg(feature = "sfcs")]
 pub use sfcs::compiler::{
     compile_llvm_ir_source, compile_public_rust_source, compile_wasm_stack_source,
@@ -196,13 +262,25 @@ pub use sfcs::{
     SfcsRegionKind, SfcsRewriteKind, SfcsRewriteOperation, SfcsStructureRegion, SfcsSynthesisPlan,
     SfcsTraceStep, SovereignFastPath,
 };
+pub use sparse_merkle::{SparseMerkleError, SparseMerkleProof, SparseMerkleTree};
 pub use sparse_sumcheck::{
     CommittedSparsePolynomial, CommittedSparseProof, SeededSparseProof, SeededSparseSpec,
     SparseMonomial, SparseProofError, SparseVerificationReport,
 };
+pub use statement_schema::{
+    ParameterRule, ParameterType, StatementSchema, StatementSchemaRegistry, StatementViolation,
+};
 pub use streaming::StreamingPolynomial;
-pub use sumcheck::{ChainedSumProof, GeneralSumClaim, GeneralSumProof, ProofStats, SumClaim};
+#[cfg(not(target_arch = "wasm32"))]
+pub use submission_queue::SubmissionQueue;
+pub use sumcheck::{
+    ChainedSumProof, GeneralSumClaim, GeneralSumProof, MemoryBudgetError, ProofStats,
+    ProverScratch, SumClaim,
+};
 pub use transcript::Transcript;
+pub use verification_cache::{
+    proof_digest, CachedVerification, VerificationCache, SCHEMA_VERIFICATION_CACHE,
+};
 
 /// Verify that a `.pha` artifact contains a deterministic SFCS graph embedding.
 ///
@@ -0,0 +1,422 @@
+//! Inner-product and matrix-multiplication claims.
+//!
+//! Verifiable-compute users mostly want to prove `⟨a,b⟩ = c` or `A·B = C`
+//! without deriving polynomial encodings by hand.  This module compiles both
+//! claims into the multilinear form [`GeneralSumProof`] understands:
+//!
+//! - For `⟨a,b⟩ = c` the vectors are zero-padded to a power of two and the
+//!   product table `h(i) = a_i·b_i` is summed over the hypercube.
+//! - For `A·B = C` (Thaler's matrix-multiplication protocol) a Fiat–Shamir
+//!   point `(r, s)` bound to all three matrices selects one entry of the
+//!   multilinear extension `C̃`.  Since `C̃(r, s) = Σ_j Ã(r, j)·B̃(j, s)`, the
+//!   claim reduces to the sum of the table `h(j) = Ã(r, j)·B̃(j, s)`, and a
+//!   wrong `C` survives only if `C̃` agrees with the true product at `(r, s)`.
+//!
+//! Each claim also yields a structured [`Statement`] that commits to the
+//! operands by digest, and a ledger-ready [`Proof`].
+
+use crate::{
+    julian::{Proof, ProofKind, Statement},
+    Field, GeneralSumProof, MultilinearPolynomial, Transcript,
+};
+use blake2::digest::{consts::U32, Digest};
+use std::fmt;
+
+type Blake2b256 = blake2::Blake2b<U32>;
+
+/// Application id of statements produced by this module.
+pub const LINALG_APPLICATION_ID: &str = "power_house.linalg";
+
+const OPERAND_DOMAIN: &[u8] = b"power_house:v1:linalg-operand";
+const MATMUL_DOMAIN: &[u8] = b"power_house:v1:matmul";
+
+/// Errors returned while building, proving or verifying linear-algebra claims.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinalgError {
+    /// Operand shapes are empty or inconsistent.
+    Dimension(&'static str),
+    /// The claimed result does not match the operands.
+    ClaimMismatch,
+    /// The proof does not verify against the claim.
+    InvalidProof,
+}
+
+impl fmt::Display for LinalgError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Dimension(reason) => write!(formatter, "dimension mismatch: {reason}"),
+            Self::ClaimMismatch => write!(formatter, "claimed result does not match operands"),
+            Self::InvalidProof => write!(formatter, "proof does not verify"),
+        }
+    }
+}
+
+impl std::error::Error for LinalgError {}
+
+/// Claim `⟨a, b⟩ = c` over a prime field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InnerProductClaim {
+    /// Left operand.
+    pub a: Vec<u64>,
+    /// Right operand.
+    pub b: Vec<u64>,
+    /// Claimed inner product.
+    pub c: u64,
+}
+
+impl InnerProductClaim {
+    /// Builds a claim from operands and a claimed result.
+    pub fn new(a: Vec<u64>, b: Vec<u64>, c: u64) -> Result<Self, LinalgError> {
+        if a.is_empty() {
+            return Err(LinalgError::Dimension("empty vectors"));
+        }
+        if a.len() != b.len() {
+            return Err(LinalgError::Dimension("vector lengths differ"));
+        }
+        Ok(Self { a, b, c })
+    }
+
+    /// Builds the honest claim by computing `⟨a, b⟩`.
+    pub fn compute(a: Vec<u64>, b: Vec<u64>, field: &Field) -> Result<Self, LinalgError> {
+        let c = a
+            .iter()
+            .zip(&b)
+            .fold(0, |acc, (&x, &y)| field.add(acc, field.mul(x, y)));
+        Self::new(a, b, c)
+    }
+
+    /// Multilinear product table `h(i) = a_i·b_i` whose hypercube sum is `c`.
+    pub fn polynomial(&self, field: &Field) -> MultilinearPolynomial {
+        let size = padded_len(self.a.len());
+        let mut table = vec![0u64; size];
+        for (slot, (&x, &y)) in table.iter_mut().zip(self.a.iter().zip(&self.b)) {
+            *slot = field.mul(x % field.modulus(), y % field.modulus());
+        }
+        MultilinearPolynomial::from_evaluations(log2(size), table)
+    }
+
+    /// Structured statement committing to the operands and result.
+    pub fn statement(&self, field: &Field) -> Statement {
+        Statement::structured(LINALG_APPLICATION_ID, "inner_product")
+            .with_parameter("modulus", field.modulus().to_string())
+            .with_parameter("len", self.a.len().to_string())
+            .with_parameter("a", operand_digest(field, &self.a))
+            .with_parameter("b", operand_digest(field, &self.b))
+            .with_parameter("c", (self.c % field.modulus()).to_string())
+    }
+
+    /// Proves the claim; fails if `c` is not the inner product.
+    pub fn prove(&self, field: &Field) -> Result<GeneralSumProof, LinalgError> {
+        let proof = GeneralSumProof::prove(&self.polynomial(field), field);
+        if proof.claim.claimed_sum != self.c % field.modulus() {
+            return Err(LinalgError::ClaimMismatch);
+        }
+        Ok(proof)
+    }
+
+    /// Verifies `proof` against the operands and claimed result.
+    pub fn verify(&self, proof: &GeneralSumProof, field: &Field) -> Result<(), LinalgError> {
+        if proof.claim.claimed_sum != self.c % field.modulus() {
+            return Err(LinalgError::ClaimMismatch);
+        }
+        if !proof.verify(&self.polynomial(field), field) {
+            return Err(LinalgError::InvalidProof);
+        }
+        Ok(())
+    }
+
+    /// Statement and proof ready for [`crate::ProofLedger::submit`].
+    pub fn ledger_entry(&self, field: &Field) -> Result<(Statement, Proof), LinalgError> {
+        let proof = self.prove(field)?;
+        Ok((
+            self.statement(field),
+            general_proof(self.polynomial(field), proof),
+        ))
+    }
+}
+
+/// Claim `A·B = C` for row-major matrices over a prime field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatMulClaim {
+    /// Rows of `A` and `C`.
+    pub rows: usize,
+    /// Columns of `A`, rows of `B`.
+    pub inner: usize,
+    /// Columns of `B` and `C`.
+    pub cols: usize,
+    /// `rows × inner` left operand, row-major.
+    pub a: Vec<u64>,
+    /// `inner × cols` right operand, row-major.
+    pub b: Vec<u64>,
+    /// Claimed `rows × cols` product, row-major.
+    pub c: Vec<u64>,
+}
+
+impl MatMulClaim {
+    /// Builds a claim from operands and a claimed product.
+    pub fn new(
+        rows: usize,
+        inner: usize,
+        cols: usize,
+        a: Vec<u64>,
+        b: Vec<u64>,
+        c: Vec<u64>,
+    ) -> Result<Self, LinalgError> {
+        if rows == 0 || inner == 0 || cols == 0 {
+            return Err(LinalgError::Dimension("empty matrix"));
+        }
+        if a.len() != rows * inner {
+            return Err(LinalgError::Dimension("A is not rows × inner"));
+        }
+        if b.len() != inner * cols {
+            return Err(LinalgError::Dimension("B is not inner × cols"));
+        }
+        if c.len() != rows * cols {
+            return Err(LinalgError::Dimension("C is not rows × cols"));
+        }
+        Ok(Self {
+            rows,
+            inner,
+            cols,
+            a,
+            b,
+            c,
+        })
+    }
+
+    /// Builds the honest claim by computing `A·B`.
+    pub fn compute(
+        rows: usize,
+        inner: usize,
+        cols: usize,
+        a: Vec<u64>,
+        b: Vec<u64>,
+        field: &Field,
+    ) -> Result<Self, LinalgError> {
+        if a.len() != rows * inner || b.len() != inner * cols {
+            return Err(LinalgError::Dimension("operand shapes"));
+        }
+        let mut c = vec![0u64; rows * cols];
+        for i in 0..rows {
+            for k in 0..cols {
+                c[i * cols + k] = (0..inner).fold(0, |acc, j| {
+                    field.add(acc, field.mul(a[i * inner + j], b[j * cols + k]))
+                });
+            }
+        }
+        Self::new(rows, inner, cols, a, b, c)
+    }
+
+    /// Fiat–Shamir row and column point bound to `A`, `B` and `C`.
+    fn challenge_point(&self, field: &Field) -> (Vec<u64>, Vec<u64>) {
+        let mut transcript = Transcript::new(MATMUL_DOMAIN);
+        transcript.append(field.modulus());
+        transcript.append(self.rows as u64);
+        transcript.append(self.inner as u64);
+        transcript.append(self.cols as u64);
+        for matrix in [&self.a, &self.b, &self.c] {
+            transcript.append_slice(&reduce(field, matrix));
+        }
+        let row_point = (0..log2(padded_len(self.rows)))
+            .map(|_| transcript.challenge(field))
+            .collect();
+        let col_point = (0..log2(padded_len(self.cols)))
+            .map(|_| transcript.challenge(field))
+            .collect();
+        (row_point, col_point)
+    }
+
+    /// `C̃(r, s)`, the value the sum-check must reach.
+    pub fn claimed_sum(&self, field: &Field) -> u64 {
+        let (row_point, col_point) = self.challenge_point(field);
+        // Column-major so the row variables come first.
+        let rows_p = padded_len(self.rows);
+        let mut table = vec![0u64; rows_p * padded_len(self.cols)];
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                table[i + rows_p * k] = self.c[i * self.cols + k] % field.modulus();
+            }
+        }
+        let by_col = fold_low(field, table, &row_point);
+        fold_low(field, by_col, &col_point)[0]
+    }
+
+    /// Multilinear table `h(j) = Ã(r, j)·B̃(j, s)` whose hypercube sum is `C̃(r, s)`.
+    pub fn polynomial(&self, field: &Field) -> MultilinearPolynomial {
+        let (row_point, col_point) = self.challenge_point(field);
+        let rows_p = padded_len(self.rows);
+        let cols_p = padded_len(self.cols);
+        let inner_p = padded_len(self.inner);
+        let mut a_table = vec![0u64; rows_p * inner_p];
+        for i in 0..self.rows {
+            for j in 0..self.inner {
+                a_table[i + rows_p * j] = self.a[i * self.inner + j] % field.modulus();
+            }
+        }
+        let mut b_table = vec![0u64; cols_p * inner_p];
+        for j in 0..self.inner {
+            for k in 0..self.cols {
+                b_table[k + cols_p * j] = self.b[j * self.cols + k] % field.modulus();
+            }
+        }
+        let a_row = fold_low(field, a_table, &row_point);
+        let b_col = fold_low(field, b_table, &col_point);
+        let table = a_row
+            .iter()
+            .zip(&b_col)
+            .map(|(&x, &y)| field.mul(x, y))
+            .collect();
+        MultilinearPolynomial::from_evaluations(log2(inner_p), table)
+    }
+
+    /// Structured statement committing to the operands and product.
+    pub fn statement(&self, field: &Field) -> Statement {
+        Statement::structured(LINALG_APPLICATION_ID, "matmul")
+            .with_parameter("modulus", field.modulus().to_string())
+            .with_parameter(
+                "shape",
+                format!("{}x{}x{}", self.rows, self.inner, self.cols),
+            )
+            .with_parameter("a", operand_digest(field, &self.a))
+            .with_parameter("b", operand_digest(field, &self.b))
+            .with_parameter("c", operand_digest(field, &self.c))
+    }
+
+    /// Proves the claim; fails if `C` is not the product.
+    pub fn prove(&self, field: &Field) -> Result<GeneralSumProof, LinalgError> {
+        let proof = GeneralSumProof::prove(&self.polynomial(field), field);
+        if proof.claim.claimed_sum != self.claimed_sum(field) {
+            return Err(LinalgError::ClaimMismatch);
+        }
+        Ok(proof)
+    }
+
+    /// Verifies `proof` against the operands and claimed product.
+    pub fn verify(&self, proof: &GeneralSumProof, field: &Field) -> Result<(), LinalgError> {
+        if proof.claim.claimed_sum != self.claimed_sum(field) {
+            return Err(LinalgError::ClaimMismatch);
+        }
+        if !proof.verify(&self.polynomial(field), field) {
+            return Err(LinalgError::InvalidProof);
+        }
+        Ok(())
+    }
+
+    /// Statement and proof ready for [`crate::ProofLedger::submit`].
+    pub fn ledger_entry(&self, field: &Field) -> Result<(Statement, Proof), LinalgError> {
+        let proof = self.prove(field)?;
+        Ok((
+            self.statement(field),
+            general_proof(self.polynomial(field), proof),
+        ))
+    }
+}
+
+fn general_proof(polynomial: MultilinearPolynomial, proof: GeneralSumProof) -> Proof {
+    Proof {
+        kind: ProofKind::General { polynomial, proof },
+        data: Vec::new(),
+    }
+}
+
+/// Power-of-two length of at least two, so every table has a variable.
+fn padded_len(len: usize) -> usize {
+    len.max(2).next_power_of_two()
+}
+
+fn log2(size: usize) -> usize {
+    size.trailing_zeros() as usize
+}
+
+fn reduce(field: &Field, values: &[u64]) -> Vec<u64> {
+    values.iter().map(|&v| v % field.modulus()).collect()
+}
+
+/// Fixes the lowest `point.len()` variables of an evaluation table.
+fn fold_low(field: &Field, mut layer: Vec<u64>, point: &[u64]) -> Vec<u64> {
+    for &r in point {
+        layer = layer
+            .chunks_exact(2)
+            .map(|pair| field.add(field.mul(field.sub(pair[1], pair[0]), r), pair[0]))
+            .collect();
+    }
+    layer
+}
+
+/// Hex digest of an operand, reduced into the field.
+fn operand_digest(field: &Field, values: &[u64]) -> String {
+    let mut hasher = Blake2b256::new();
+    hasher.update(OPERAND_DOMAIN);
+    hasher.update((values.len() as u64).to_be_bytes());
+    for value in reduce(field, values) {
+        hasher.update(value.to_be_bytes());
+    }
+    hex::encode(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProofLedger;
+
+    #[test]
+    fn inner_product_proves_and_rejects_wrong_result() {
+        let field = Field::new(1_000_000_007);
+        let claim =
+            InnerProductClaim::compute(vec![1, 2, 3, 4, 5], vec![6, 7, 8, 9, 10], &field).unwrap();
+        assert_eq!(claim.c, 130);
+        let proof = claim.prove(&field).unwrap();
+        claim.verify(&proof, &field).unwrap();
+
+        let wrong = InnerProductClaim::new(claim.a.clone(), claim.b.clone(), 131).unwrap();
+        assert!(matches!(
+            wrong.prove(&field),
+            Err(LinalgError::ClaimMismatch)
+        ));
+        assert_eq!(
+            wrong.verify(&proof, &field),
+            Err(LinalgError::ClaimMismatch)
+        );
+
+        let mut tampered = claim.clone();
+        tampered.a.swap(0, 1);
+        assert_eq!(
+            tampered.verify(&proof, &field),
+            Err(LinalgError::InvalidProof)
+        );
+
+        assert!(InnerProductClaim::new(vec![1], vec![1, 2], 0).is_err());
+    }
+
+    #[test]
+    fn matmul_proves_non_square_products_and_submits_to_ledger() {
+        let field = Field::new(1_000_000_007);
+        let claim = MatMulClaim::compute(
+            3,
+            2,
+            5,
+            vec![1, 2, 3, 4, 5, 6],
+            vec![7, 8, 9, 10, 11, 12, 13, 14, 15, 16],
+            &field,
+        )
+        .unwrap();
+        assert_eq!(&claim.c[..5], &[31, 34, 37, 40, 43]);
+        let proof = claim.prove(&field).unwrap();
+        claim.verify(&proof, &field).unwrap();
+
+        let mut wrong = claim.clone();
+        wrong.c[7] += 1;
+        assert!(matches!(
+            wrong.prove(&field),
+            Err(LinalgError::ClaimMismatch)
+        ));
+        assert!(wrong.verify(&proof, &field).is_err());
+
+        let (statement, ledger_proof) = claim.ledger_entry(&field).unwrap();
+        assert_eq!(statement.application_id(), Some(LINALG_APPLICATION_ID));
+        assert_eq!(statement.parameters["shape"], "3x2x5");
+        let mut ledger = ProofLedger::new();
+        ledger.submit(statement, ledger_proof);
+        assert!(ledger.entries().last().unwrap().accepted);
+    }
+}
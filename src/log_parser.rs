@@ -1,7 +1,12 @@
+use crate::journal::JournalReader;
 use crate::{
-    parse_transcript_record, transcript_digest, verify_transcript_lines, TranscriptDigest,
+    is_binary_transcript_record, parse_transcript_record, read_binary_transcript_record,
+    transcript_digest, EntrySubmission, TranscriptDigest,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
 };
-use std::{fs, path::Path};
 
 /// Metadata captured from optional comment lines in a ledger log file.
 #[derive(Debug, Clone, Default)]
@@ -10,6 +15,14 @@ pub struct LogRecordMetadata {
     pub challenge_mode: Option<String>,
     /// Optional fold digest provided alongside the transcript.
     pub fold_digest: Option<TranscriptDigest>,
+    /// Submitter attribution from `# submitter`, `# submitter_signature` and
+    /// `# submitted_ms` lines (all three must be present).
+    pub submission: Option<EntrySubmission>,
+    /// Fee paid for the submission, from a `# submission_fee` line.
+    pub submission_fee: Option<u64>,
+    /// Ledger position of the entry the record belongs to, from an
+    /// `# entry_index` line.  Older logs omit it.
+    pub entry_index: Option<u64>,
 }
 
 /// Parsed contents of a ledger log file.
@@ -21,17 +34,138 @@ pub struct ParsedLogFile {
     pub digest: TranscriptDigest,
     /// Metadata surfaced from comment lines.
     pub metadata: LogRecordMetadata,
+    /// Fiat–Shamir challenges recorded in the transcript.
+    pub challenges: Vec<u64>,
+    /// Per-round sums recorded in the transcript.
+    pub round_sums: Vec<u64>,
+    /// Final evaluation recorded in the transcript.
+    pub final_value: u64,
+}
+
+impl ParsedLogFile {
+    fn check_modulus(self, source: &str, modulus: Option<u64>) -> Result<Self, LogFault> {
+        if let Some(p) = modulus {
+            let mut values = self
+                .challenges
+                .iter()
+                .chain(&self.round_sums)
+                .chain(std::iter::once(&self.final_value));
+            if let Some(value) = values.find(|value| **value >= p) {
+                return Err(LogFault::new(
+                    LogFaultKind::StaleModulus,
+                    format!("{source} value {value} does not fit modulus {p}"),
+                ));
+            }
+        }
+        Ok(self)
+    }
+}
+
+/// Category of a transcript log integrity failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LogFaultKind {
+    /// The stored digest (or journal checksum) does not match the contents.
+    BadDigest,
+    /// The record is empty or lacks its `statement:` line.
+    MissingStatement,
+    /// The record could not be decoded.
+    ParseError,
+    /// The transcript holds values outside the expected field.
+    StaleModulus,
+}
+
+impl LogFaultKind {
+    /// Short kebab-case label used in reports.
+    pub fn label(&self) -> &'static str {
+        match self {
+            LogFaultKind::BadDigest => "bad-digest",
+            LogFaultKind::MissingStatement => "missing-statement",
+            LogFaultKind::ParseError => "parse-error",
+            LogFaultKind::StaleModulus => "stale-modulus",
+        }
+    }
+}
+
+/// Classified failure returned by [`LedgerLogRecord::check`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogFault {
+    /// Failure category.
+    pub kind: LogFaultKind,
+    /// Human-readable description, prefixed with the record source.
+    pub message: String,
+}
+
+impl LogFault {
+    fn new(kind: LogFaultKind, message: String) -> Self {
+        Self { kind, message }
+    }
 }
 
 /// Parses a ledger log file, tolerating optional comment lines that begin with `#`.
+///
+/// Binary transcript records are detected by their magic prefix and verified
+/// against the same digest as the ASCII format.
 pub fn parse_log_file(path: &Path) -> Result<ParsedLogFile, String> {
-    let contents = fs::read_to_string(path)
-        .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
-    parse_log_contents(path, &contents)
+    check_log_file(path).map_err(|fault| fault.message)
+}
+
+/// Parses an in-memory ledger log record (ASCII or binary), labelling
+/// errors with `source`.
+pub fn parse_log_bytes(source: &str, bytes: &[u8]) -> Result<ParsedLogFile, String> {
+    check_log_bytes(source, bytes).map_err(|fault| fault.message)
+}
+
+fn check_log_file(path: &Path) -> Result<ParsedLogFile, LogFault> {
+    let source = path.display().to_string();
+    let bytes = fs::read(path).map_err(|err| {
+        LogFault::new(
+            LogFaultKind::ParseError,
+            format!("failed to read {source}: {err}"),
+        )
+    })?;
+    check_log_bytes(&source, &bytes)
+}
+
+fn check_log_bytes(source: &str, bytes: &[u8]) -> Result<ParsedLogFile, LogFault> {
+    if is_binary_transcript_record(bytes) {
+        return check_binary_log(source, bytes);
+    }
+    let contents = std::str::from_utf8(bytes).map_err(|_| {
+        LogFault::new(
+            LogFaultKind::ParseError,
+            format!("{source} is not valid UTF-8"),
+        )
+    })?;
+    check_log_contents(source, contents)
+}
+
+fn check_binary_log(source: &str, bytes: &[u8]) -> Result<ParsedLogFile, LogFault> {
+    let record = read_binary_transcript_record(bytes).map_err(|err| {
+        LogFault::new(
+            LogFaultKind::ParseError,
+            format!("{source} parse error: {err}"),
+        )
+    })?;
+    let computed = transcript_digest(&record.transcript, &record.round_sums, record.final_value);
+    if computed != record.digest {
+        return Err(hash_mismatch(source, &record.digest, &computed));
+    }
+    Ok(ParsedLogFile {
+        statement: record.statement,
+        digest: computed,
+        metadata: LogRecordMetadata::default(),
+        challenges: record.transcript,
+        round_sums: record.round_sums,
+        final_value: record.final_value,
+    })
 }
 
-fn parse_log_contents(path: &Path, contents: &str) -> Result<ParsedLogFile, String> {
+fn check_log_contents(source: &str, contents: &str) -> Result<ParsedLogFile, LogFault> {
+    let parse_error = |err: String| LogFault::new(LogFaultKind::ParseError, err);
     let mut metadata = LogRecordMetadata::default();
+    let mut submitter: Option<Vec<u8>> = None;
+    let mut submitter_signature: Option<Vec<u8>> = None;
+    let mut submitted_ms: Option<u64> = None;
     let mut lines: Vec<String> = Vec::new();
     for raw in contents.lines() {
         let line = raw.trim();
@@ -45,42 +179,89 @@ fn parse_log_contents(path: &Path, contents: &str) -> Result<ParsedLogFile, Stri
                 if key.eq_ignore_ascii_case("challenge_mode") && !value.is_empty() {
                     metadata.challenge_mode = Some(value.to_string());
                 } else if key.eq_ignore_ascii_case("fold_digest") && !value.is_empty() {
-                    metadata.fold_digest = Some(parse_fold_digest(value)?);
+                    metadata.fold_digest = Some(parse_fold_digest(value).map_err(parse_error)?);
+                } else if key.eq_ignore_ascii_case("submitter") {
+                    submitter = Some(hex::decode(value).map_err(|err| {
+                        parse_error(format!("{source} invalid submitter: {err}"))
+                    })?);
+                } else if key.eq_ignore_ascii_case("submitter_signature") {
+                    submitter_signature = Some(hex::decode(value).map_err(|err| {
+                        parse_error(format!("{source} invalid submitter signature: {err}"))
+                    })?);
+                } else if key.eq_ignore_ascii_case("submission_fee") {
+                    metadata.submission_fee = Some(value.parse().map_err(|err| {
+                        parse_error(format!("{source} invalid submission_fee: {err}"))
+                    })?);
+                } else if key.eq_ignore_ascii_case("entry_index") {
+                    metadata.entry_index = Some(value.parse().map_err(|err| {
+                        parse_error(format!("{source} invalid entry_index: {err}"))
+                    })?);
+                } else if key.eq_ignore_ascii_case("submitted_ms") {
+                    submitted_ms = Some(value.parse().map_err(|err| {
+                        parse_error(format!("{source} invalid submitted_ms: {err}"))
+                    })?);
                 }
             }
             continue;
         }
         lines.push(line.to_string());
     }
+    metadata.submission = match (submitter, submitter_signature, submitted_ms) {
+        (Some(public_key), Some(signature), Some(submitted_ms)) => Some(EntrySubmission {
+            public_key,
+            signature,
+            submitted_ms,
+        }),
+        (None, None, None) => None,
+        _ => {
+            return Err(parse_error(format!(
+                "{source} has incomplete submitter metadata"
+            )))
+        }
+    };
     if lines.is_empty() {
-        return Err(format!("{} is empty", path.display()));
+        return Err(LogFault::new(
+            LogFaultKind::MissingStatement,
+            format!("{} is empty", source),
+        ));
     }
     let statement_line = lines.remove(0);
     if !statement_line.starts_with("statement:") {
-        return Err(format!("{} missing statement prefix", path.display()));
+        return Err(LogFault::new(
+            LogFaultKind::MissingStatement,
+            format!("{} missing statement prefix", source),
+        ));
     }
     let statement = statement_line[10..].to_string();
-    verify_transcript_lines(lines.iter().map(|s| s.as_str()))
-        .map_err(|err| format!("{} verification failed: {err}", path.display()))?;
     let (challenges, round_sums, final_value, stored_hash) =
         parse_transcript_record(lines.iter().map(|s| s.as_str()))
-            .map_err(|err| format!("{} parse error: {err}", path.display()))?;
+            .map_err(|err| parse_error(format!("{} parse error: {err}", source)))?;
     let computed = transcript_digest(&challenges, &round_sums, final_value);
     if computed != stored_hash {
-        return Err(format!(
-            "{} hash mismatch: stored={}, computed={}",
-            path.display(),
-            crate::transcript_digest_to_hex(&stored_hash),
-            crate::transcript_digest_to_hex(&computed)
-        ));
+        return Err(hash_mismatch(source, &stored_hash, &computed));
     }
     Ok(ParsedLogFile {
         statement,
         digest: computed,
         metadata,
+        challenges,
+        round_sums,
+        final_value,
     })
 }
 
+fn hash_mismatch(source: &str, stored: &TranscriptDigest, computed: &TranscriptDigest) -> LogFault {
+    LogFault::new(
+        LogFaultKind::BadDigest,
+        format!(
+            "{} hash mismatch: stored={}, computed={}",
+            source,
+            crate::transcript_digest_to_hex(stored),
+            crate::transcript_digest_to_hex(computed)
+        ),
+    )
+}
+
 fn parse_fold_digest(value: &str) -> Result<TranscriptDigest, String> {
     let cleaned = value.trim();
     if cleaned.is_empty() {
@@ -89,6 +270,118 @@ fn parse_fold_digest(value: &str) -> Result<TranscriptDigest, String> {
     crate::transcript_digest_from_hex(cleaned)
 }
 
+/// Returns true for one-file-per-transcript logs (`ledger_*.txt` or the
+/// binary `ledger_*.bin`).
+pub fn is_ledger_log_file(path: &Path) -> bool {
+    match path.file_name().and_then(|n| n.to_str()) {
+        Some(name) => {
+            name.starts_with("ledger_") && (name.ends_with(".txt") || name.ends_with(".bin"))
+        }
+        None => false,
+    }
+}
+
+/// A transcript record located by [`LedgerLogIter`], parsed on demand.
+#[derive(Debug, Clone)]
+pub struct LedgerLogRecord {
+    /// Record name: a file name or `ledger_journal_NNNNNN.phj#RRRRRRRR`.
+    pub name: String,
+    source: LedgerLogSource,
+}
+
+#[derive(Debug, Clone)]
+enum LedgerLogSource {
+    File(PathBuf),
+    Journal(String),
+}
+
+impl LedgerLogRecord {
+    /// Parses and verifies the record.
+    pub fn parse(&self) -> Result<ParsedLogFile, String> {
+        self.check(None).map_err(|fault| fault.message)
+    }
+
+    /// Parses and verifies the record, classifying any failure.
+    ///
+    /// When `modulus` is given, transcripts holding values that do not fit
+    /// the field are reported as [`LogFaultKind::StaleModulus`].
+    pub fn check(&self, modulus: Option<u64>) -> Result<ParsedLogFile, LogFault> {
+        let parsed = match &self.source {
+            LedgerLogSource::File(path) => check_log_file(path)?,
+            LedgerLogSource::Journal(text) => check_log_contents(&self.name, text)?,
+        };
+        parsed.check_modulus(&self.name, modulus)
+    }
+
+    /// Returns the raw record bytes exactly as stored.
+    pub fn bytes(&self) -> Result<Vec<u8>, String> {
+        match &self.source {
+            LedgerLogSource::File(path) => {
+                fs::read(path).map_err(|err| format!("failed to read {}: {err}", path.display()))
+            }
+            LedgerLogSource::Journal(text) => Ok(text.as_bytes().to_vec()),
+        }
+    }
+
+    /// Returns the on-disk file holding this record, if it is not a journal record.
+    pub fn path(&self) -> Option<&Path> {
+        match &self.source {
+            LedgerLogSource::File(path) => Some(path),
+            LedgerLogSource::Journal(_) => None,
+        }
+    }
+}
+
+/// Iterator over every transcript record in a log directory.
+///
+/// Per-transcript `ledger_*` files are yielded first in file-name order, followed
+/// by the records of the framed transcript journal in append order.  Record
+/// names sort in yield order, so they can be compared against a checkpoint
+/// log cutoff before paying for [`LedgerLogRecord::parse`].
+#[derive(Debug)]
+pub struct LedgerLogIter {
+    files: std::vec::IntoIter<PathBuf>,
+    journal: JournalReader,
+}
+
+impl Iterator for LedgerLogIter {
+    type Item = Result<LedgerLogRecord, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(file) = self.files.next() {
+            let name = file
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or_default()
+                .to_string();
+            return Some(Ok(LedgerLogRecord {
+                name,
+                source: LedgerLogSource::File(file),
+            }));
+        }
+        Some(self.journal.next()?.map(|record| LedgerLogRecord {
+            name: record.position.record_name(),
+            source: LedgerLogSource::Journal(record.text),
+        }))
+    }
+}
+
+/// Opens a [`LedgerLogIter`] over `dir`.
+pub fn iter_ledger_logs(dir: &Path) -> Result<LedgerLogIter, String> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|err| format!("failed to read directory {}: {err}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.is_file() && is_ledger_log_file(p))
+        .collect();
+    files.sort();
+    let journal = JournalReader::open(dir)
+        .map_err(|err| format!("failed to open journal in {}: {err}", dir.display()))?;
+    Ok(LedgerLogIter {
+        files: files.into_iter(),
+        journal,
+    })
+}
+
 /// Attempts to load a fold digest hint from `fold_digest.txt` inside `dir`.
 pub fn read_fold_digest_hint(dir: &Path) -> Result<Option<TranscriptDigest>, String> {
     let path = dir.join("fold_digest.txt");
@@ -1,6 +1,10 @@
 //! Simple Merkle accumulator utilities for anchor transcript digests.
 //!
 //! The tree uses domain-separated BLAKE2b-256 hashing to combine leaves.
+//! An unpaired node is carried to the next level unchanged, so a tree over
+//! `n` leaves is a sequence of perfect subtrees, one per set bit of `n`.
+//! [`IncrementalMerkleTree`] keeps only those subtree roots (the frontier)
+//! and appends in `O(log n)`.
 
 use blake2::digest::{consts::U32, Digest};
 use blake2::Blake2b;
@@ -60,6 +64,112 @@ pub fn merkle_root(leaves: &[TranscriptDigest]) -> TranscriptDigest {
     level[0]
 }
 
+/// Append-only Merkle tree that stores its frontier instead of its leaves.
+///
+/// [`IncrementalMerkleTree::root`] always equals [`merkle_root`] over the
+/// leaves appended so far.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IncrementalMerkleTree {
+    len: u64,
+    /// Roots of the perfect subtrees, largest (leftmost) first.
+    frontier: Vec<TranscriptDigest>,
+}
+
+impl IncrementalMerkleTree {
+    /// Creates an empty tree.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a tree by appending every leaf in order.
+    pub fn from_leaves(leaves: &[TranscriptDigest]) -> Self {
+        let mut tree = Self::new();
+        for leaf in leaves {
+            tree.append(leaf);
+        }
+        tree
+    }
+
+    /// Appends a leaf digest in `O(log n)`.
+    pub fn append(&mut self, leaf: &TranscriptDigest) {
+        let mut node = hash_leaf(leaf);
+        let mut carry = self.len;
+        while carry & 1 == 1 {
+            let left = self
+                .frontier
+                .pop()
+                .expect("frontier holds one root per set bit of len");
+            node = hash_pair(&left, &node);
+            carry >>= 1;
+        }
+        self.frontier.push(node);
+        self.len += 1;
+    }
+
+    /// Number of leaves appended so far.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns `true` when no leaf has been appended.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Current root, computed from the frontier in `O(log n)`.
+    pub fn root(&self) -> TranscriptDigest {
+        let mut peaks = self.frontier.iter().rev();
+        let Some(&last) = peaks.next() else {
+            return hash_empty();
+        };
+        peaks.fold(last, |acc, peak| hash_pair(peak, &acc))
+    }
+
+    /// Roots of the perfect subtrees, largest first.
+    pub fn frontier(&self) -> &[TranscriptDigest] {
+        &self.frontier
+    }
+
+    /// Serialises the leaf count and frontier to a JSON string.
+    pub fn to_json_string(&self) -> String {
+        let frontier: Vec<_> = self.frontier.iter().map(digest_to_hex).collect();
+        json!({
+            "len": self.len,
+            "frontier": frontier
+        })
+        .to_string()
+    }
+
+    /// Parses a frontier previously emitted by [`Self::to_json_string`].
+    pub fn from_json_str(input: &str) -> Result<Self, String> {
+        let value: serde_json::Value =
+            serde_json::from_str(input).map_err(|err| format!("invalid frontier JSON: {err}"))?;
+        let len = value
+            .get("len")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| "missing len".to_string())?;
+        let frontier = value
+            .get("frontier")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| "missing frontier".to_string())?
+            .iter()
+            .map(|node| {
+                node.as_str()
+                    .ok_or_else(|| "invalid frontier node".to_string())
+                    .and_then(digest_from_hex)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if frontier.len() != len.count_ones() as usize {
+            return Err(format!(
+                "frontier has {} nodes, expected {} for {len} leaves",
+                frontier.len(),
+                len.count_ones()
+            ));
+        }
+        Ok(Self { len, frontier })
+    }
+}
+
 /// Describes a sibling hash encountered while walking a Merkle tree.
 #[derive(Debug, Clone)]
 pub struct MerkleProofNode {
@@ -87,10 +197,40 @@ pub fn build_proof(leaves: &[TranscriptDigest], index: usize) -> Option<MerklePr
     if leaves.is_empty() || index >= leaves.len() {
         return None;
     }
-    let mut layer: Vec<TranscriptDigest> = leaves.iter().map(hash_leaf).collect();
+    let layers = merkle_layers(leaves);
+    Some(proof_from_layers(&layers, leaves[index], index))
+}
+
+/// Computes every level of the tree, from hashed leaves up to the root.
+///
+/// Callers that need many inclusion proofs against the same leaves can build
+/// the layers once and extract paths with [`proof_from_layers`].
+pub(crate) fn merkle_layers(leaves: &[TranscriptDigest]) -> Vec<Vec<TranscriptDigest>> {
+    let mut layers = vec![leaves.iter().map(hash_leaf).collect::<Vec<_>>()];
+    while layers.last().is_some_and(|layer| layer.len() > 1) {
+        let layer = layers.last().expect("non-empty layers");
+        let mut next = Vec::with_capacity(layer.len().div_ceil(2));
+        for chunk in layer.chunks(2) {
+            if chunk.len() == 1 {
+                next.push(chunk[0]);
+            } else {
+                next.push(hash_pair(&chunk[0], &chunk[1]));
+            }
+        }
+        layers.push(next);
+    }
+    layers
+}
+
+/// Extracts the inclusion proof for `index` from precomputed [`merkle_layers`].
+pub(crate) fn proof_from_layers(
+    layers: &[Vec<TranscriptDigest>],
+    leaf: TranscriptDigest,
+    index: usize,
+) -> MerkleProof {
     let mut idx = index;
     let mut path = Vec::new();
-    while layer.len() > 1 {
+    for layer in &layers[..layers.len() - 1] {
         if idx.is_multiple_of(2) {
             if idx + 1 < layer.len() {
                 path.push(MerkleProofNode {
@@ -104,23 +244,14 @@ pub fn build_proof(leaves: &[TranscriptDigest], index: usize) -> Option<MerklePr
                 left: true,
             });
         }
-        let mut next = Vec::with_capacity(layer.len().div_ceil(2));
-        for chunk in layer.chunks(2) {
-            if chunk.len() == 1 {
-                next.push(chunk[0]);
-            } else {
-                next.push(hash_pair(&chunk[0], &chunk[1]));
-            }
-        }
-        layer = next;
         idx /= 2;
     }
-    Some(MerkleProof {
-        root: layer[0],
-        leaf: leaves[index],
+    MerkleProof {
+        root: layers[layers.len() - 1][0],
+        leaf,
         index,
         path,
-    })
+    }
 }
 
 /// Checks whether the proof recomputes the advertised Merkle root.
@@ -230,4 +361,26 @@ mod tests {
         let parsed = MerkleProof::from_json_str(&json).unwrap();
         assert!(verify_proof(&parsed));
     }
+
+    #[test]
+    fn incremental_tree_matches_batch_root() {
+        let leaves: Vec<_> = (0..40).map(leaf).collect();
+        let mut tree = IncrementalMerkleTree::new();
+        assert_eq!(tree.root(), merkle_root(&[]));
+        for (count, digest) in leaves.iter().enumerate() {
+            tree.append(digest);
+            assert_eq!(tree.root(), merkle_root(&leaves[..=count]));
+            assert_eq!(tree.frontier().len(), (count + 1).count_ones() as usize);
+        }
+
+        let restored = IncrementalMerkleTree::from_json_str(&tree.to_json_string()).unwrap();
+        assert_eq!(restored, tree);
+        let mut resumed = restored;
+        resumed.append(&leaf(40));
+        assert_eq!(
+            resumed.root(),
+            merkle_root(&(0..41).map(leaf).collect::<Vec<_>>())
+        );
+        assert!(IncrementalMerkleTree::from_json_str(r#"{"len":3,"frontier":[]}"#).is_err());
+    }
 }
@@ -0,0 +1,267 @@
+#![cfg(feature = "net")]
+
+//! Canonical mapping from registry public keys to derived EVM addresses.
+//!
+//! Every ed25519 registry key owns the `0x` address
+//! `blake2b256(ADDRESS_DERIVATION || pubkey)[12..]`. The native chain credits
+//! that address at genesis, the wallet RPC resolves it back to the key, the
+//! blob service asks the token oracle about it, and ERC-20 migration claims pay
+//! it. [`AddressIndex`] materialises the mapping for a registry as a file that
+//! records the derivation version, so every consumer reads one artifact.
+//! [`AddressIndex::root`] commits to the mapping in a sparse Merkle tree keyed
+//! by address, so an address can be proven bound to (or absent from) a key.
+
+use crate::net::{
+    native_chain::normalize_evm_address, sign::decode_public_key_base64,
+    stake_registry::StakeRegistry, storage::write_json_atomic,
+};
+use crate::sparse_merkle::{smt_key, smt_value, SmtDigest, SparseMerkleProof, SparseMerkleTree};
+use blake2::digest::{consts::U32, Digest};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Schema identifier of an address index file.
+pub const SCHEMA_ADDRESS_INDEX: &str = "mfenx.powerhouse.address-index.v1";
+/// Domain separator (and version tag) of the pubkey → address derivation.
+pub const ADDRESS_DERIVATION: &str = "mfenx-migration-address-v1";
+/// File name of the index kept next to a stake registry.
+pub const ADDRESS_INDEX_FILE: &str = "address_index.json";
+
+type Blake2b256 = blake2::Blake2b<U32>;
+
+/// Derives the `0x` address owned by raw ed25519 public key bytes.
+pub fn derive_evm_address(public_key: &[u8]) -> String {
+    let mut hasher = Blake2b256::new();
+    hasher.update(ADDRESS_DERIVATION.as_bytes());
+    hasher.update(public_key);
+    let digest: [u8; 32] = hasher.finalize().into();
+    format!("0x{}", hex::encode(&digest[12..]))
+}
+
+/// Address for a registry key: `0x` keys normalise to themselves, base64
+/// ed25519 keys are derived, anything else has no address.
+pub fn registry_key_address(key: &str) -> Option<String> {
+    if let Some(address) = normalize_evm_address(key) {
+        return Some(address);
+    }
+    let public = decode_public_key_base64(key).ok()?;
+    Some(derive_evm_address(public.as_bytes()))
+}
+
+#[derive(Serialize, Deserialize)]
+struct AddressIndexFile {
+    schema: String,
+    derivation: String,
+    entries: BTreeMap<String, String>,
+}
+
+/// Public key → address mapping for every addressable registry account.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AddressIndex {
+    entries: BTreeMap<String, String>,
+    by_address: HashMap<String, String>,
+}
+
+impl AddressIndex {
+    /// Path of the index maintained next to `registry_path`.
+    pub fn path_for(registry_path: &Path) -> PathBuf {
+        registry_path.with_file_name(ADDRESS_INDEX_FILE)
+    }
+
+    /// Indexes every account of `registry`.
+    pub fn build(registry: &StakeRegistry) -> Self {
+        let mut index = Self::default();
+        index.sync(registry);
+        index
+    }
+
+    /// Adds accounts created since the index was built; returns how many.
+    pub fn sync(&mut self, registry: &StakeRegistry) -> usize {
+        let mut added = 0;
+        for key in registry.accounts().keys() {
+            if self.entries.contains_key(key) {
+                continue;
+            }
+            if let Some(address) = registry_key_address(key) {
+                self.by_address.insert(address.clone(), key.clone());
+                self.entries.insert(key.clone(), address);
+                added += 1;
+            }
+        }
+        added
+    }
+
+    /// Loads an index, rejecting other schemas, other derivations and entries
+    /// that do not re-derive; missing file -> `None`.
+    pub fn load(path: &Path) -> Result<Option<Self>, String> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let bytes = fs::read(path).map_err(|e| e.to_string())?;
+        let file: AddressIndexFile = serde_json::from_slice(&bytes).map_err(|e| e.to_string())?;
+        if file.schema != SCHEMA_ADDRESS_INDEX {
+            return Err(format!("unsupported address index schema {}", file.schema));
+        }
+        if file.derivation != ADDRESS_DERIVATION {
+            return Err(format!(
+                "address index uses derivation {} (expected {ADDRESS_DERIVATION})",
+                file.derivation
+            ));
+        }
+        let mut index = Self::default();
+        for (key, address) in file.entries {
+            if registry_key_address(&key).as_deref() != Some(address.as_str()) {
+                return Err(format!("address index entry for {key} does not re-derive"));
+            }
+            index.by_address.insert(address.clone(), key.clone());
+            index.entries.insert(key, address);
+        }
+        Ok(Some(index))
+    }
+
+    /// Persists the index atomically.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        write_json_atomic(
+            path,
+            &AddressIndexFile {
+                schema: SCHEMA_ADDRESS_INDEX.to_string(),
+                derivation: ADDRESS_DERIVATION.to_string(),
+                entries: self.entries.clone(),
+            },
+        )
+    }
+
+    /// Loads the index next to `registry_path`, adds any new registry accounts
+    /// and rewrites the file only when something changed.
+    pub fn refresh(registry_path: &Path, registry: &StakeRegistry) -> Result<Self, String> {
+        let path = Self::path_for(registry_path);
+        let existing = Self::load(&path)?;
+        let fresh = existing.is_none();
+        let mut index = existing.unwrap_or_default();
+        if index.sync(registry) > 0 || fresh {
+            index.save(&path)?;
+        }
+        Ok(index)
+    }
+
+    /// Derived address of a registry key.
+    pub fn address_of(&self, key: &str) -> Option<&str> {
+        self.entries.get(key).map(String::as_str)
+    }
+
+    /// Registry key owning `address` (any hex case).
+    pub fn key_of(&self, address: &str) -> Option<&str> {
+        let address = normalize_evm_address(address)?;
+        self.by_address.get(&address).map(String::as_str)
+    }
+
+    /// Number of indexed keys.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no key is indexed.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Root of the sparse Merkle tree mapping each address to its key.
+    pub fn root(&self) -> SmtDigest {
+        self.tree().root()
+    }
+
+    /// Proves which key owns `address`, or that no indexed key does.
+    pub fn prove(&self, address: &str) -> Option<SparseMerkleProof> {
+        let address = normalize_evm_address(address)?;
+        Some(self.tree().prove(&smt_key(address.as_bytes())))
+    }
+
+    fn tree(&self) -> SparseMerkleTree {
+        let mut tree = SparseMerkleTree::new();
+        for (address, key) in &self.by_address {
+            tree.insert(smt_key(address.as_bytes()), smt_value(key.as_bytes()));
+        }
+        tree
+    }
+
+    /// Indexed `(key, address)` pairs in key order.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries
+            .iter()
+            .map(|(key, address)| (key.as_str(), address.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::{
+        encode_public_key_base64, load_or_derive_keypair, stake_registry::SupplyCause,
+        Ed25519KeySource,
+    };
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn index_refreshes_incrementally_and_rejects_tampering() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("power_house_address_index_{nanos}"));
+        let registry_path = dir.join("stake_registry.json");
+        let key = load_or_derive_keypair(&Ed25519KeySource::Seed("address-index".into())).unwrap();
+        let pk = encode_public_key_base64(&key.verifying);
+        let mut registry = StakeRegistry::default();
+        registry.mint(&pk, 5, SupplyCause::Funding).unwrap();
+        registry
+            .mint(
+                "0xABCDEFabcdefABCDEFabcdefABCDEFabcdefABCD",
+                1,
+                SupplyCause::Funding,
+            )
+            .unwrap();
+        registry.mint("not-a-key", 1, SupplyCause::Funding).unwrap();
+
+        let index = AddressIndex::refresh(&registry_path, &registry).unwrap();
+        assert_eq!(index.len(), 2);
+        let address = index.address_of(&pk).unwrap().to_string();
+        assert_eq!(address, derive_evm_address(key.verifying.as_bytes()));
+        assert_eq!(
+            index.key_of(&address.to_uppercase().replace("0X", "0x")),
+            Some(pk.as_str())
+        );
+        assert!(index.address_of("not-a-key").is_none());
+        let bound = index.prove(&address).unwrap();
+        assert_eq!(bound.value, Some(hex::encode(smt_value(pk.as_bytes()))));
+        assert_eq!(bound.verify(&index.root()), Ok(()));
+        let unbound = index
+            .prove("0x0000000000000000000000000000000000000001")
+            .unwrap();
+        assert_eq!(unbound.value, None);
+        assert_eq!(unbound.verify(&index.root()), Ok(()));
+
+        let other = encode_public_key_base64(
+            &load_or_derive_keypair(&Ed25519KeySource::Seed("address-index-2".into()))
+                .unwrap()
+                .verifying,
+        );
+        registry.mint(&other, 2, SupplyCause::Funding).unwrap();
+        let refreshed = AddressIndex::refresh(&registry_path, &registry).unwrap();
+        assert_eq!(refreshed.len(), 3);
+        let index_path = AddressIndex::path_for(&registry_path);
+        assert_eq!(AddressIndex::load(&index_path).unwrap(), Some(refreshed));
+
+        let tampered = fs::read_to_string(&index_path)
+            .unwrap()
+            .replace(&address, "0x0000000000000000000000000000000000000001");
+        fs::write(&index_path, tampered).unwrap();
+        assert!(AddressIndex::load(&index_path)
+            .unwrap_err()
+            .contains("does not re-derive"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
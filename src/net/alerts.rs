@@ -0,0 +1,813 @@
+#![cfg(feature = "net")]
+
+//! Operator alerts for conditions that otherwise fail silently.
+//!
+//! An [`AlertMonitor`] watches three conditions the swarm reports to it:
+//! checkpoint writes failing repeatedly, the node running without peers, and
+//! safe mode.  Each condition fires once when it crosses its threshold from
+//! the `--alerts` file and resolves once it clears; both transitions are sent
+//! to every configured [`AlertSink`] and reflected in the
+//! `powerhouse_alert_active` gauge through the shared [`AlertState`].
+
+use crate::net::webhooks::{sign_webhook_body, SIGNATURE_HEADER};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader},
+    net::TcpStream,
+    sync::mpsc,
+    time,
+};
+use tokio_rustls::{client::TlsStream, rustls, TlsConnector};
+
+/// Schema identifier of an [`Alert`].
+pub const SCHEMA_ALERT: &str = "mfenx.powerhouse.alert.v1";
+
+const SINK_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Errors produced while loading alert configuration.
+#[derive(Debug, thiserror::Error)]
+pub enum AlertError {
+    /// The configuration file could not be read.
+    #[error("alert I/O error: {0}")]
+    Io(String),
+    /// The configuration is malformed.
+    #[error("invalid alert config: {0}")]
+    Invalid(String),
+}
+
+/// Conditions the monitor raises alerts for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertKind {
+    /// Consecutive checkpoint writes failed.
+    CheckpointWriteFailing,
+    /// The node has had no connected peers for the grace period.
+    NoPeers,
+    /// Fork detection halted local anchor broadcast.
+    SafeMode,
+}
+
+impl AlertKind {
+    /// Every kind, in metrics order.
+    pub const ALL: [Self; 3] = [Self::CheckpointWriteFailing, Self::NoPeers, Self::SafeMode];
+
+    /// Label used in alert bodies and metrics.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::CheckpointWriteFailing => "checkpoint_write_failing",
+            Self::NoPeers => "no_peers",
+            Self::SafeMode => "safe_mode",
+        }
+    }
+}
+
+/// Destination for alert transitions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AlertSink {
+    /// `QSYS|mod=ALERT` lines on stderr only.
+    Log,
+    /// POST the [`Alert`] as JSON, signed like webhook events when a secret
+    /// is set.
+    Webhook {
+        /// Receiver URL.
+        url: String,
+        /// Shared secret for the `X-Powerhouse-Signature` header.
+        #[serde(default)]
+        secret: Option<String>,
+    },
+    /// Plain-text mail through an SMTP relay.
+    ///
+    /// Credentials are only sent once the connection is encrypted, unless
+    /// `insecure` is set.
+    Smtp {
+        /// Relay address, `host:port`.
+        server: String,
+        /// How the connection is encrypted.
+        #[serde(default)]
+        tls: SmtpTls,
+        /// Allows `AUTH PLAIN` over a cleartext connection.
+        #[serde(default)]
+        insecure: bool,
+        /// Envelope and header sender.
+        from: String,
+        /// Recipients.
+        to: Vec<String>,
+        /// `AUTH PLAIN` user name.
+        #[serde(default)]
+        username: Option<String>,
+        /// `AUTH PLAIN` password.
+        #[serde(default)]
+        password: Option<String>,
+    },
+    /// `m.text` message posted to a Matrix room.
+    Matrix {
+        /// Homeserver base URL, e.g. `https://matrix.example.org`.
+        homeserver: String,
+        /// Room id, e.g. `!ops:example.org`.
+        room_id: String,
+        /// Access token of the posting account.
+        access_token: String,
+    },
+}
+
+/// Transport security of an SMTP sink.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpTls {
+    /// Cleartext; suitable for an unauthenticated local relay.
+    #[default]
+    None,
+    /// Upgrade with `STARTTLS` after the greeting (submission port 587).
+    StartTls,
+    /// TLS from the first byte (port 465).
+    Implicit,
+}
+
+/// Contents of the `--alerts` file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlertConfig {
+    /// Where transitions are delivered.
+    pub sinks: Vec<AlertSink>,
+    /// Consecutive failed checkpoint writes before alerting.
+    #[serde(default = "default_checkpoint_failures")]
+    pub checkpoint_failures: u32,
+    /// Seconds without peers before alerting.
+    #[serde(default = "default_no_peers_secs")]
+    pub no_peers_secs: u64,
+}
+
+fn default_checkpoint_failures() -> u32 {
+    3
+}
+
+fn default_no_peers_secs() -> u64 {
+    120
+}
+
+impl AlertConfig {
+    /// Reads and validates a configuration file.
+    pub fn load(path: &Path) -> Result<Self, AlertError> {
+        let bytes = fs::read(path)
+            .map_err(|err| AlertError::Io(format!("read {}: {err}", path.display())))?;
+        let config: Self = serde_json::from_slice(&bytes)
+            .map_err(|err| AlertError::Invalid(format!("{}: {err}", path.display())))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks thresholds and sink settings.
+    pub fn validate(&self) -> Result<(), AlertError> {
+        if self.checkpoint_failures == 0 {
+            return Err(AlertError::Invalid(
+                "checkpoint_failures must be at least 1".to_string(),
+            ));
+        }
+        for sink in &self.sinks {
+            match sink {
+                AlertSink::Log => {}
+                AlertSink::Webhook { url, .. } => {
+                    reqwest::Url::parse(url)
+                        .map_err(|err| AlertError::Invalid(format!("webhook {url}: {err}")))?;
+                }
+                AlertSink::Smtp {
+                    server,
+                    tls,
+                    insecure,
+                    to,
+                    username,
+                    password,
+                    ..
+                } => {
+                    if to.is_empty() {
+                        return Err(AlertError::Invalid("smtp sink has no recipients".into()));
+                    }
+                    if username.is_some() != password.is_some() {
+                        return Err(AlertError::Invalid(
+                            "smtp username and password must be set together".into(),
+                        ));
+                    }
+                    if username.is_some() && *tls == SmtpTls::None && !insecure {
+                        return Err(AlertError::Invalid(format!(
+                            "smtp {server}: credentials need tls \"starttls\" or \"implicit\" \
+                             (set insecure to send them in cleartext)"
+                        )));
+                    }
+                }
+                AlertSink::Matrix { homeserver, .. } => {
+                    reqwest::Url::parse(homeserver).map_err(|err| {
+                        AlertError::Invalid(format!("matrix homeserver {homeserver}: {err}"))
+                    })?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One alert transition.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Alert {
+    /// Schema identifier (`mfenx.powerhouse.alert.v1`).
+    pub schema: String,
+    /// Condition.
+    pub alert: AlertKind,
+    /// Whether the condition started (`true`) or cleared (`false`).
+    pub firing: bool,
+    /// Node raising the alert.
+    pub node_id: String,
+    /// Millisecond timestamp of the transition.
+    pub at_ms: u64,
+    /// Human-readable detail.
+    pub message: String,
+}
+
+impl Alert {
+    /// One-line summary used as mail subject and chat message.
+    pub fn summary(&self) -> String {
+        format!(
+            "[{}] {} on {}: {}",
+            if self.firing { "FIRING" } else { "RESOLVED" },
+            self.alert.label(),
+            self.node_id,
+            self.message
+        )
+    }
+}
+
+/// Active flags per [`AlertKind`], shared with the metrics endpoint.
+#[derive(Debug, Clone, Default)]
+pub struct AlertState(Arc<[AtomicBool; AlertKind::ALL.len()]>);
+
+impl AlertState {
+    /// Whether `kind` is firing.
+    pub fn is_active(&self, kind: AlertKind) -> bool {
+        self.0[kind as usize].load(Ordering::Relaxed)
+    }
+
+    fn set(&self, kind: AlertKind, active: bool) -> bool {
+        self.0[kind as usize].swap(active, Ordering::Relaxed) != active
+    }
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    checkpoint_failures: u32,
+    peerless_since_ms: Option<u64>,
+}
+
+/// Evaluates thresholds and hands transitions to the sinks.
+#[derive(Debug)]
+pub struct AlertMonitor {
+    config: AlertConfig,
+    node_id: String,
+    state: AlertState,
+    counters: Mutex<Counters>,
+    sender: mpsc::UnboundedSender<Alert>,
+}
+
+impl AlertMonitor {
+    /// Spawns sink delivery on the current tokio runtime; `state` is updated
+    /// on every transition.
+    pub fn start(
+        config: AlertConfig,
+        node_id: impl Into<String>,
+        state: AlertState,
+    ) -> Result<Self, AlertError> {
+        config.validate()?;
+        let client = reqwest::Client::builder()
+            .timeout(SINK_TIMEOUT)
+            .build()
+            .map_err(|err| AlertError::Invalid(err.to_string()))?;
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(run_sinks(config.sinks.clone(), client, receiver));
+        Ok(Self {
+            config,
+            node_id: node_id.into(),
+            state,
+            counters: Mutex::new(Counters::default()),
+            sender,
+        })
+    }
+
+    /// Records a successful checkpoint write.
+    pub fn checkpoint_written(&self, now_ms: u64) {
+        self.counters().checkpoint_failures = 0;
+        self.transition(
+            AlertKind::CheckpointWriteFailing,
+            false,
+            now_ms,
+            "checkpoint written".to_string(),
+        );
+    }
+
+    /// Records a failed checkpoint write.
+    pub fn checkpoint_failed(&self, error: &str, now_ms: u64) {
+        let failures = {
+            let mut counters = self.counters();
+            counters.checkpoint_failures = counters.checkpoint_failures.saturating_add(1);
+            counters.checkpoint_failures
+        };
+        if failures >= self.config.checkpoint_failures {
+            self.transition(
+                AlertKind::CheckpointWriteFailing,
+                true,
+                now_ms,
+                format!("{failures} consecutive checkpoint writes failed: {error}"),
+            );
+        }
+    }
+
+    /// Records the current connected peer count.
+    pub fn peers(&self, connected: usize, now_ms: u64) {
+        if connected > 0 {
+            self.counters().peerless_since_ms = None;
+            self.transition(
+                AlertKind::NoPeers,
+                false,
+                now_ms,
+                format!("{connected} peers connected"),
+            );
+            return;
+        }
+        let since = *self.counters().peerless_since_ms.get_or_insert(now_ms);
+        let peerless_ms = now_ms.saturating_sub(since);
+        if peerless_ms >= self.config.no_peers_secs.saturating_mul(1000) {
+            self.transition(
+                AlertKind::NoPeers,
+                true,
+                now_ms,
+                format!("no connected peers for {}s", peerless_ms / 1000),
+            );
+        }
+    }
+
+    /// Records whether safe mode is engaged.
+    pub fn safe_mode(&self, engaged: bool, now_ms: u64) {
+        let message = if engaged {
+            "fork detected; anchor broadcast halted until `julian net resolve-fork`"
+        } else {
+            "safe mode cleared"
+        };
+        self.transition(AlertKind::SafeMode, engaged, now_ms, message.to_string());
+    }
+
+    fn counters(&self) -> std::sync::MutexGuard<'_, Counters> {
+        self.counters.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn transition(&self, kind: AlertKind, firing: bool, now_ms: u64, message: String) {
+        if !self.state.set(kind, firing) {
+            return;
+        }
+        let alert = Alert {
+            schema: SCHEMA_ALERT.to_string(),
+            alert: kind,
+            firing,
+            node_id: self.node_id.clone(),
+            at_ms: now_ms,
+            message,
+        };
+        println!(
+            "QSYS|mod=ALERT|evt={}|alert={}|detail={}",
+            if firing { "FIRING" } else { "RESOLVED" },
+            kind.label(),
+            alert.message
+        );
+        let _ = self.sender.send(alert);
+    }
+}
+
+async fn run_sinks(
+    sinks: Vec<AlertSink>,
+    client: reqwest::Client,
+    mut receiver: mpsc::UnboundedReceiver<Alert>,
+) {
+    let sinks = Arc::new(sinks);
+    while let Some(alert) = receiver.recv().await {
+        for index in 0..sinks.len() {
+            let sinks = sinks.clone();
+            let client = client.clone();
+            let alert = alert.clone();
+            tokio::spawn(async move {
+                let sink = &sinks[index];
+                if let Err(err) = deliver_alert(&client, sink, &alert).await {
+                    eprintln!(
+                        "QSYS|mod=ALERT|evt=SINK_FAILED|alert={}|error={err}",
+                        alert.alert.label()
+                    );
+                }
+            });
+        }
+    }
+}
+
+/// Sends `alert` to one sink.
+pub async fn deliver_alert(
+    client: &reqwest::Client,
+    sink: &AlertSink,
+    alert: &Alert,
+) -> Result<(), String> {
+    match sink {
+        AlertSink::Log => {
+            eprintln!("QSYS|mod=ALERT|evt=NOTIFY|{}", alert.summary());
+            Ok(())
+        }
+        AlertSink::Webhook { url, secret } => {
+            let body = serde_json::to_vec(alert).map_err(|err| err.to_string())?;
+            let mut request = client
+                .post(url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json");
+            if let Some(secret) = secret {
+                request = request.header(
+                    SIGNATURE_HEADER,
+                    sign_webhook_body(secret.as_bytes(), &body),
+                );
+            }
+            check_status(request.body(body).send().await)
+        }
+        AlertSink::Smtp {
+            server,
+            tls,
+            insecure,
+            from,
+            to,
+            username,
+            password,
+        } => {
+            let credentials = username.as_deref().zip(password.as_deref());
+            time::timeout(
+                SINK_TIMEOUT,
+                send_smtp(server, *tls, *insecure, from, to, credentials, alert),
+            )
+            .await
+            .map_err(|_| "SMTP relay timed out".to_string())?
+        }
+        AlertSink::Matrix {
+            homeserver,
+            room_id,
+            access_token,
+        } => {
+            let mut url = reqwest::Url::parse(homeserver).map_err(|err| err.to_string())?;
+            let txn_id = format!("{}-{}-{}", alert.alert.label(), alert.at_ms, alert.firing);
+            url.path_segments_mut()
+                .map_err(|_| "matrix homeserver cannot be a base URL".to_string())?
+                .pop_if_empty()
+                .extend([
+                    "_matrix",
+                    "client",
+                    "v3",
+                    "rooms",
+                    room_id.as_str(),
+                    "send",
+                    "m.room.message",
+                    txn_id.as_str(),
+                ]);
+            let request = client
+                .put(url)
+                .bearer_auth(access_token)
+                .json(&serde_json::json!({"msgtype": "m.text", "body": alert.summary()}));
+            check_status(request.send().await)
+        }
+    }
+}
+
+fn check_status(response: reqwest::Result<reqwest::Response>) -> Result<(), String> {
+    let response = response.map_err(|err| err.to_string())?;
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("HTTP {}", response.status()))
+    }
+}
+
+async fn send_smtp(
+    server: &str,
+    tls: SmtpTls,
+    insecure: bool,
+    from: &str,
+    to: &[String],
+    credentials: Option<(&str, &str)>,
+    alert: &Alert,
+) -> Result<(), String> {
+    if credentials.is_some() && tls == SmtpTls::None && !insecure {
+        return Err("refusing to send SMTP credentials over a cleartext connection".to_string());
+    }
+    let stream = TcpStream::connect(server)
+        .await
+        .map_err(|err| format!("connect {server}: {err}"))?;
+    match tls {
+        SmtpTls::None => {
+            let mut stream = BufReader::new(stream);
+            smtp_reply(&mut stream, 220).await?;
+            deliver_smtp(stream, from, to, credentials, alert).await
+        }
+        SmtpTls::Implicit => {
+            let mut stream = BufReader::new(tls_connect(server, stream).await?);
+            smtp_reply(&mut stream, 220).await?;
+            deliver_smtp(stream, from, to, credentials, alert).await
+        }
+        SmtpTls::StartTls => {
+            let mut stream = BufReader::new(stream);
+            smtp_reply(&mut stream, 220).await?;
+            let extensions = smtp_command(&mut stream, "EHLO powerhouse", 250).await?;
+            if !extensions
+                .iter()
+                .any(|line| line.eq_ignore_ascii_case("STARTTLS"))
+            {
+                return Err(format!("SMTP relay {server} does not offer STARTTLS"));
+            }
+            smtp_command(&mut stream, "STARTTLS", 220).await?;
+            let stream = BufReader::new(tls_connect(server, stream.into_inner()).await?);
+            deliver_smtp(stream, from, to, credentials, alert).await
+        }
+    }
+}
+
+/// Runs the TLS handshake with `server`, checked against the webpki roots.
+async fn tls_connect(server: &str, stream: TcpStream) -> Result<TlsStream<TcpStream>, String> {
+    let host = server
+        .rsplit_once(':')
+        .map_or(server, |(host, _)| host)
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+    let name = rustls::ServerName::try_from(host)
+        .map_err(|err| format!("SMTP relay name {host}: {err}"))?;
+    let mut roots = rustls::RootCertStore::empty();
+    roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|anchor| {
+        rustls::OwnedTrustAnchor::from_subject_spki_name_constraints(
+            anchor.subject,
+            anchor.spki,
+            anchor.name_constraints,
+        )
+    }));
+    let config = rustls::ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    TlsConnector::from(Arc::new(config))
+        .connect(name, stream)
+        .await
+        .map_err(|err| format!("TLS with {server}: {err}"))
+}
+
+/// Sends the envelope and message once the session is (re)greeted.
+async fn deliver_smtp<S>(
+    mut stream: BufReader<S>,
+    from: &str,
+    to: &[String],
+    credentials: Option<(&str, &str)>,
+    alert: &Alert,
+) -> Result<(), String>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    smtp_command(&mut stream, "EHLO powerhouse", 250).await?;
+    if let Some((username, password)) = credentials {
+        let token = BASE64.encode(format!("\0{username}\0{password}"));
+        smtp_command(&mut stream, &format!("AUTH PLAIN {token}"), 235).await?;
+    }
+    smtp_command(&mut stream, &format!("MAIL FROM:<{from}>"), 250).await?;
+    for recipient in to {
+        smtp_command(&mut stream, &format!("RCPT TO:<{recipient}>"), 250).await?;
+    }
+    smtp_command(&mut stream, "DATA", 354).await?;
+    let body = serde_json::to_string_pretty(alert).map_err(|err| err.to_string())?;
+    let mut message = format!(
+        "From: <{from}>\r\nTo: {}\r\nSubject: {}\r\nContent-Type: text/plain; charset=utf-8\r\n\r\n",
+        to.iter()
+            .map(|recipient| format!("<{recipient}>"))
+            .collect::<Vec<_>>()
+            .join(", "),
+        alert.summary()
+    );
+    for line in body.lines() {
+        // RFC 5321 dot-stuffing.
+        if line.starts_with('.') {
+            message.push('.');
+        }
+        message.push_str(line);
+        message.push_str("\r\n");
+    }
+    message.push('.');
+    smtp_command(&mut stream, &message, 250).await?;
+    smtp_command(&mut stream, "QUIT", 221).await?;
+    Ok(())
+}
+
+/// Sends `command` and returns the text of the reply lines.
+async fn smtp_command<S>(
+    stream: &mut S,
+    command: &str,
+    expected: u16,
+) -> Result<Vec<String>, String>
+where
+    S: AsyncBufRead + AsyncWrite + Unpin,
+{
+    stream
+        .write_all(format!("{command}\r\n").as_bytes())
+        .await
+        .map_err(|err| err.to_string())?;
+    stream.flush().await.map_err(|err| err.to_string())?;
+    smtp_reply(stream, expected).await
+}
+
+async fn smtp_reply<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    expected: u16,
+) -> Result<Vec<String>, String> {
+    let mut lines = Vec::new();
+    loop {
+        let mut line = String::new();
+        if reader
+            .read_line(&mut line)
+            .await
+            .map_err(|err| err.to_string())?
+            == 0
+        {
+            return Err("SMTP relay closed the connection".to_string());
+        }
+        let code = line.get(..3).and_then(|code| code.parse::<u16>().ok());
+        lines.push(line.get(4..).unwrap_or("").trim_end().to_string());
+        if line.as_bytes().get(3) == Some(&b'-') {
+            continue;
+        }
+        return match code {
+            Some(code) if code == expected => Ok(lines),
+            _ => Err(format!("SMTP relay replied {}", line.trim_end())),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn thresholds_fire_once_and_mail_goes_through_the_relay() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let relay = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (reader, mut writer) = stream.into_split();
+            let mut reader = BufReader::new(reader);
+            writer.write_all(b"220 relay\r\n").await.unwrap();
+            let mut transcript = String::new();
+            let mut in_data = false;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).await.unwrap();
+                transcript.push_str(&line);
+                let reply: &[u8] = if in_data {
+                    if line != ".\r\n" {
+                        continue;
+                    }
+                    in_data = false;
+                    b"250 queued\r\n"
+                } else if line.starts_with("EHLO") {
+                    b"250-relay\r\n250 AUTH PLAIN\r\n"
+                } else if line.starts_with("AUTH") {
+                    b"235 ok\r\n"
+                } else if line.starts_with("DATA") {
+                    in_data = true;
+                    b"354 go\r\n"
+                } else if line.starts_with("QUIT") {
+                    writer.write_all(b"221 bye\r\n").await.unwrap();
+                    return transcript;
+                } else {
+                    b"250 ok\r\n"
+                };
+                writer.write_all(reply).await.unwrap();
+            }
+        });
+
+        let config: AlertConfig = serde_json::from_value(serde_json::json!({
+            "sinks": [
+                {"type": "log"},
+                {"type": "smtp", "server": addr.to_string(), "from": "node@example.org",
+                 "to": ["ops@example.org"], "username": "node", "password": "pw",
+                 "insecure": true}
+            ],
+            "checkpoint_failures": 2,
+            "no_peers_secs": 60
+        }))
+        .unwrap();
+        let state = AlertState::default();
+        let monitor = AlertMonitor::start(config, "boot1", state.clone()).unwrap();
+
+        monitor.peers(0, 1_000);
+        monitor.peers(0, 60_000);
+        assert!(!state.is_active(AlertKind::NoPeers));
+        monitor.peers(3, 61_000);
+        monitor.peers(0, 62_000);
+        monitor.peers(0, 122_000);
+        assert!(state.is_active(AlertKind::NoPeers));
+        let transcript = relay.await.unwrap();
+        assert!(transcript.contains("AUTH PLAIN AG5vZGUAcHc=\r\n"));
+        assert!(transcript.contains("RCPT TO:<ops@example.org>\r\n"));
+        assert!(transcript
+            .contains("Subject: [FIRING] no_peers on boot1: no connected peers for 60s\r\n"));
+        monitor.peers(1, 123_000);
+        assert!(!state.is_active(AlertKind::NoPeers));
+
+        monitor.checkpoint_failed("disk full", 1);
+        assert!(!state.is_active(AlertKind::CheckpointWriteFailing));
+        monitor.checkpoint_failed("disk full", 2);
+        assert!(state.is_active(AlertKind::CheckpointWriteFailing));
+        monitor.checkpoint_written(3);
+        assert!(!state.is_active(AlertKind::CheckpointWriteFailing));
+    }
+
+    #[tokio::test]
+    async fn smtp_credentials_are_never_sent_in_cleartext() {
+        let sink = |extra: serde_json::Value| {
+            let mut sink = serde_json::json!({
+                "type": "smtp", "server": "127.0.0.1:25", "from": "node@example.org",
+                "to": ["ops@example.org"], "username": "node", "password": "pw"
+            });
+            sink.as_object_mut()
+                .unwrap()
+                .extend(extra.as_object().unwrap().clone());
+            serde_json::from_value::<AlertConfig>(serde_json::json!({"sinks": [sink]})).unwrap()
+        };
+        assert!(sink(serde_json::json!({})).validate().is_err());
+        sink(serde_json::json!({"tls": "starttls"}))
+            .validate()
+            .unwrap();
+        sink(serde_json::json!({"tls": "implicit"}))
+            .validate()
+            .unwrap();
+        sink(serde_json::json!({"insecure": true}))
+            .validate()
+            .unwrap();
+
+        let alert = Alert {
+            schema: SCHEMA_ALERT.to_string(),
+            alert: AlertKind::NoPeers,
+            firing: true,
+            node_id: "boot1".to_string(),
+            at_ms: 1,
+            message: "no connected peers".to_string(),
+        };
+        let to = vec!["ops@example.org".to_string()];
+        let credentials = Some(("node", "pw"));
+        let err = send_smtp(
+            "127.0.0.1:1",
+            SmtpTls::None,
+            false,
+            "n@x",
+            &to,
+            credentials,
+            &alert,
+        )
+        .await
+        .unwrap_err();
+        assert!(err.contains("cleartext"), "{err}");
+
+        // A relay that does not offer STARTTLS never sees the credentials.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let relay = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut stream = BufReader::new(stream);
+            stream.write_all(b"220 relay\r\n").await.unwrap();
+            let mut transcript = String::new();
+            loop {
+                let mut line = String::new();
+                if stream.read_line(&mut line).await.unwrap() == 0 {
+                    return transcript;
+                }
+                transcript.push_str(&line);
+                stream
+                    .write_all(b"250-relay\r\n250 AUTH PLAIN\r\n")
+                    .await
+                    .unwrap();
+            }
+        });
+        let err = send_smtp(
+            &addr.to_string(),
+            SmtpTls::StartTls,
+            false,
+            "n@x",
+            &to,
+            credentials,
+            &alert,
+        )
+        .await
+        .unwrap_err();
+        assert!(err.contains("does not offer STARTTLS"), "{err}");
+        let transcript = relay.await.unwrap();
+        assert_eq!(transcript, "EHLO powerhouse\r\n");
+    }
+}
@@ -0,0 +1,273 @@
+#![cfg(feature = "net")]
+
+//! Lookup of past anchors by checkpoint epoch or anchor digest.
+//!
+//! An [`AnchorHistory`] indexes the checkpoints under a log directory and the
+//! entries of any number of [`AnchorArchive`]s, so "what was the anchor at
+//! epoch 17" or "which epoch produced digest `0x…`" can be answered offline.
+//! Archives are verified before they are indexed.  Sources that agree on an
+//! epoch have their checkpoint signatures merged; sources that disagree are
+//! refused with [`AnchorHistoryError::Conflict`].
+
+use crate::net::archive::{verify_archive, AnchorArchive};
+use crate::net::checkpoint::{load_checkpoints, AnchorCheckpoint, CheckpointSignature};
+use crate::net::schema::AnchorJson;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
+
+/// Errors produced while indexing anchor history.
+#[derive(Debug, thiserror::Error)]
+pub enum AnchorHistoryError {
+    /// A checkpoint or archive could not be read.
+    #[error("anchor history I/O error: {0}")]
+    Io(String),
+    /// A checkpoint or archive is malformed or fails verification.
+    #[error("invalid anchor history source: {0}")]
+    Invalid(String),
+    /// Two sources record different anchors for one epoch.
+    #[error("epoch {epoch} is {existing} in one source and {incoming} in another")]
+    Conflict {
+        /// Epoch both sources claim.
+        epoch: u64,
+        /// Hex digest already indexed.
+        existing: String,
+        /// Hex digest of the rejected source.
+        incoming: String,
+    },
+}
+
+/// Anchor recorded for one epoch, with every signature seen for it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoricalAnchor {
+    /// Checkpoint epoch.
+    pub epoch: u64,
+    /// Hex anchor digest.
+    pub anchor_digest: String,
+    /// Anchor as checkpointed.
+    pub anchor: AnchorJson,
+    /// Checkpoint signatures, one per signing key.
+    pub signatures: Vec<CheckpointSignature>,
+    /// Where the anchor was found, e.g. `checkpoint` or `archive:<node_id>`.
+    pub sources: Vec<String>,
+}
+
+/// Past anchors indexed by epoch and by digest.
+#[derive(Debug, Clone, Default)]
+pub struct AnchorHistory {
+    by_epoch: BTreeMap<u64, HistoricalAnchor>,
+    by_digest: HashMap<[u8; 32], u64>,
+}
+
+impl AnchorHistory {
+    /// Creates an empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Indexes every checkpoint under `log_dir/checkpoints`.
+    ///
+    /// Returns the number of checkpoints indexed.
+    pub fn index_checkpoints(&mut self, log_dir: &Path) -> Result<usize, AnchorHistoryError> {
+        let checkpoints =
+            load_checkpoints(log_dir).map_err(|err| AnchorHistoryError::Io(err.to_string()))?;
+        let count = checkpoints.len();
+        for checkpoint in checkpoints {
+            self.insert_checkpoint(checkpoint, "checkpoint".to_string())?;
+        }
+        Ok(count)
+    }
+
+    /// Verifies `archive` and indexes its entries.
+    ///
+    /// The live head of an archive has no checkpoint and is indexed without
+    /// signatures.  Returns the number of entries indexed.
+    pub fn index_archive(&mut self, archive: &AnchorArchive) -> Result<usize, AnchorHistoryError> {
+        verify_archive(archive).map_err(|err| AnchorHistoryError::Invalid(err.to_string()))?;
+        let source = format!("archive:{}", archive.node_id);
+        for entry in &archive.entries {
+            match &entry.checkpoint {
+                Some(checkpoint) => self.insert_checkpoint(checkpoint.clone(), source.clone())?,
+                None => self.insert(
+                    entry.epoch,
+                    entry.anchor.clone(),
+                    Vec::new(),
+                    source.clone(),
+                )?,
+            }
+        }
+        Ok(archive.entries.len())
+    }
+
+    /// Anchor checkpointed at `epoch`.
+    pub fn at_epoch(&self, epoch: u64) -> Option<&HistoricalAnchor> {
+        self.by_epoch.get(&epoch)
+    }
+
+    /// Anchor whose digest is `digest`.
+    pub fn by_digest(&self, digest: &[u8; 32]) -> Option<&HistoricalAnchor> {
+        self.by_digest
+            .get(digest)
+            .and_then(|epoch| self.by_epoch.get(epoch))
+    }
+
+    /// Indexed anchors in epoch order.
+    pub fn iter(&self) -> impl Iterator<Item = &HistoricalAnchor> {
+        self.by_epoch.values()
+    }
+
+    /// Number of indexed epochs.
+    pub fn len(&self) -> usize {
+        self.by_epoch.len()
+    }
+
+    /// Returns `true` when nothing is indexed.
+    pub fn is_empty(&self) -> bool {
+        self.by_epoch.is_empty()
+    }
+
+    fn insert_checkpoint(
+        &mut self,
+        checkpoint: AnchorCheckpoint,
+        source: String,
+    ) -> Result<(), AnchorHistoryError> {
+        self.insert(
+            checkpoint.epoch,
+            checkpoint.anchor,
+            checkpoint.signatures,
+            source,
+        )
+    }
+
+    fn insert(
+        &mut self,
+        epoch: u64,
+        anchor: AnchorJson,
+        signatures: Vec<CheckpointSignature>,
+        source: String,
+    ) -> Result<(), AnchorHistoryError> {
+        let ledger = anchor
+            .clone()
+            .into_ledger()
+            .map_err(|err| AnchorHistoryError::Invalid(format!("epoch {epoch}: {err}")))?;
+        let digest = crate::julian::anchor_digest(&ledger);
+        let anchor_digest = hex::encode(digest);
+        if let Some(existing) = self.by_epoch.get_mut(&epoch) {
+            if existing.anchor_digest != anchor_digest {
+                return Err(AnchorHistoryError::Conflict {
+                    epoch,
+                    existing: existing.anchor_digest.clone(),
+                    incoming: anchor_digest,
+                });
+            }
+            for signature in signatures {
+                if !existing
+                    .signatures
+                    .iter()
+                    .any(|known| known.public_key == signature.public_key)
+                {
+                    existing.signatures.push(signature);
+                }
+            }
+            if !existing.sources.contains(&source) {
+                existing.sources.push(source);
+            }
+            return Ok(());
+        }
+        // A digest checkpointed in several epochs resolves to the earliest.
+        let slot = self.by_digest.entry(digest).or_insert(epoch);
+        *slot = (*slot).min(epoch);
+        self.by_epoch.insert(
+            epoch,
+            HistoricalAnchor {
+                epoch,
+                anchor_digest,
+                anchor,
+                signatures,
+                sources: vec![source],
+            },
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::archive::export_archive;
+    use crate::net::checkpoint::write_checkpoint;
+    use crate::net::sign::{
+        encode_public_key_base64, encode_signature_base64, load_or_derive_keypair, sign_payload,
+        Ed25519KeySource,
+    };
+    use crate::{julian_genesis_anchor, merkle_root, statement_digest, EntryAnchor};
+
+    fn checkpoint(epoch: u64, statements: &[&str], signer: &str) -> AnchorCheckpoint {
+        let mut ledger = julian_genesis_anchor();
+        for statement in statements {
+            let hashes = vec![statement_digest(statement)];
+            ledger.entries.push(EntryAnchor {
+                statement: statement.to_string(),
+                merkle_root: merkle_root(&hashes),
+                hashes,
+                submission: None,
+            });
+        }
+        let anchor = AnchorJson::from_ledger("node-a", 1, &ledger, 0, Vec::new(), None).unwrap();
+        let key = load_or_derive_keypair(&Ed25519KeySource::Seed(signer.to_string())).unwrap();
+        let payload = serde_json::to_vec(&anchor).unwrap();
+        let signature = CheckpointSignature {
+            node_id: signer.to_string(),
+            public_key: encode_public_key_base64(&key.verifying),
+            signature: encode_signature_base64(&sign_payload(&key.signing, &payload)),
+        };
+        AnchorCheckpoint::new(epoch, anchor, vec![signature], None)
+    }
+
+    #[test]
+    fn lookups_merge_signatures_and_refuse_conflicts() {
+        let dir = std::env::temp_dir().join(format!("anchor_history_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let checkpoints = dir.join("checkpoints");
+        write_checkpoint(&checkpoints, &checkpoint(3, &["a"], "node-a")).unwrap();
+        write_checkpoint(&checkpoints, &checkpoint(17, &["a", "b"], "node-a")).unwrap();
+
+        let mut history = AnchorHistory::new();
+        assert_eq!(history.index_checkpoints(&dir).unwrap(), 2);
+        let digest = history.at_epoch(3).unwrap().anchor_digest.clone();
+        let digest_bytes: [u8; 32] = hex::decode(&digest).unwrap().try_into().unwrap();
+        assert_eq!(history.by_digest(&digest_bytes).unwrap().epoch, 3);
+        assert!(history.at_epoch(4).is_none());
+
+        // A peer's archive co-signs epoch 17 and adds its live head.
+        let peer_dir = dir.join("peer");
+        write_checkpoint(
+            &peer_dir.join("checkpoints"),
+            &checkpoint(17, &["a", "b"], "node-b"),
+        )
+        .unwrap();
+        let key = load_or_derive_keypair(&Ed25519KeySource::Seed("node-b".to_string())).unwrap();
+        let head = checkpoint(0, &["a", "b", "c"], "node-b").anchor;
+        let archive = export_archive(&peer_dir, "node-b", &key, Some(head), 0).unwrap();
+        assert_eq!(history.index_archive(&archive).unwrap(), 2);
+        let epoch = history.at_epoch(17).unwrap();
+        assert_eq!(epoch.signatures.len(), 2);
+        assert_eq!(epoch.sources, vec!["checkpoint", "archive:node-b"]);
+        assert!(history.at_epoch(18).unwrap().signatures.is_empty());
+        assert_eq!(history.len(), 3);
+
+        let forked = dir.join("forked");
+        write_checkpoint(
+            &forked.join("checkpoints"),
+            &checkpoint(3, &["x"], "node-c"),
+        )
+        .unwrap();
+        assert!(matches!(
+            history.index_checkpoints(&forked),
+            Err(AnchorHistoryError::Conflict { epoch: 3, .. })
+        ));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
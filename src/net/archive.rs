@@ -0,0 +1,405 @@
+#![cfg(feature = "net")]
+
+//! Portable, signed export of a node's anchor history.
+//!
+//! An archive lists one entry per checkpoint epoch, optionally followed by the
+//! node's live head anchor.  Every entry carries the anchor digest and a link
+//! hash that chains it to the previous entry; the exporting node signs each
+//! link with its ed25519 identity.  [`verify_archive`] re-derives every digest
+//! and link, checks all signatures (including the checkpoint signatures), and
+//! confirms that each anchor extends the one before it, so an auditor can
+//! validate the whole history offline.
+
+use crate::julian::anchor_digest;
+use crate::net::checkpoint::{load_checkpoints, AnchorCheckpoint};
+use crate::net::schema::AnchorJson;
+use crate::net::sign::{
+    encode_public_key_base64, encode_signature_base64, sign_payload, verify_signature_base64,
+    KeyMaterial,
+};
+use crate::LedgerAnchor;
+use blake2::digest::{consts::U32, Digest};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+/// Schema identifier of an anchor archive bundle.
+pub const SCHEMA_ANCHOR_ARCHIVE: &str = "mfenx.powerhouse.archive.v1";
+
+const ARCHIVE_LINK_DOMAIN: &[u8] = b"MFENX_ARCHIVE_LINK";
+
+type Blake2b256 = blake2::Blake2b<U32>;
+
+/// Errors produced while exporting or verifying an archive.
+#[derive(Debug, thiserror::Error)]
+pub enum ArchiveError {
+    /// Local filesystem or encoding failure.
+    #[error("archive I/O error: {0}")]
+    Io(String),
+    /// The archive or one of its anchors is malformed.
+    #[error("invalid archive: {0}")]
+    Invalid(String),
+    /// A signature in the archive does not verify.
+    #[error("archive signature check failed: {0}")]
+    Signature(String),
+    /// The chained structure is broken (bad link, reordering, rewritten history).
+    #[error("archive chain broken at epoch {epoch}: {reason}")]
+    Chain {
+        /// Epoch of the offending entry.
+        epoch: u64,
+        /// Human-readable failure description.
+        reason: String,
+    },
+}
+
+/// Signed bundle containing a node's anchor history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnchorArchive {
+    /// Schema identifier (`mfenx.powerhouse.archive.v1`).
+    pub schema: String,
+    /// Node identifier that produced the archive.
+    pub node_id: String,
+    /// Base64 ed25519 public key that signed every entry.
+    pub public_key: String,
+    /// Millisecond timestamp of the export.
+    pub created_ms: u64,
+    /// Entries ordered by strictly increasing epoch.
+    pub entries: Vec<ArchiveEntry>,
+}
+
+/// One epoch of anchor history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveEntry {
+    /// Checkpoint epoch, or one past the last checkpoint for the live head.
+    pub epoch: u64,
+    /// Anchor recorded for the epoch.
+    pub anchor: AnchorJson,
+    /// Checkpoint the anchor came from; `None` for the live head.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checkpoint: Option<AnchorCheckpoint>,
+    /// Hex anchor digest.
+    pub anchor_digest: String,
+    /// Hex link hash of the previous entry (all zeros for the first entry).
+    pub prev_link: String,
+    /// Hex link hash binding epoch, anchor digest and `prev_link`.
+    pub link: String,
+    /// Base64 signature by the archive key over the link bytes.
+    pub signature: String,
+}
+
+/// Result of a successful [`verify_archive`] run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveSummary {
+    /// Number of entries verified.
+    pub entries: usize,
+    /// Number of checkpoint signatures verified.
+    pub checkpoint_signatures: usize,
+    /// Hex link hash of the final entry.
+    pub head_link: String,
+}
+
+impl AnchorArchive {
+    /// Reads an archive from a JSON file.
+    pub fn read(path: &Path) -> Result<Self, ArchiveError> {
+        let bytes = fs::read(path).map_err(|err| ArchiveError::Io(err.to_string()))?;
+        serde_json::from_slice(&bytes).map_err(|err| ArchiveError::Invalid(err.to_string()))
+    }
+
+    /// Writes the archive as pretty-printed JSON.
+    pub fn write(&self, path: &Path) -> Result<(), ArchiveError> {
+        let bytes =
+            serde_json::to_vec_pretty(self).map_err(|err| ArchiveError::Io(err.to_string()))?;
+        fs::write(path, bytes).map_err(|err| ArchiveError::Io(err.to_string()))
+    }
+}
+
+/// Computes the link hash chaining an entry to its predecessor.
+pub fn archive_link(epoch: u64, anchor_digest: &[u8; 32], prev_link: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Blake2b256::new();
+    hasher.update(ARCHIVE_LINK_DOMAIN);
+    hasher.update(epoch.to_be_bytes());
+    hasher.update(anchor_digest);
+    hasher.update(prev_link);
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&hasher.finalize());
+    out
+}
+
+/// Builds a signed archive from the checkpoints under `log_dir` plus an optional live head.
+///
+/// The head is appended only when it differs from the latest checkpointed anchor.
+pub fn export_archive(
+    log_dir: &Path,
+    node_id: &str,
+    key: &KeyMaterial,
+    head: Option<AnchorJson>,
+    created_ms: u64,
+) -> Result<AnchorArchive, ArchiveError> {
+    let checkpoints = load_checkpoints(log_dir).map_err(|err| ArchiveError::Io(err.to_string()))?;
+    let mut epochs: Vec<(u64, AnchorJson, Option<AnchorCheckpoint>)> = checkpoints
+        .into_iter()
+        .map(|checkpoint| {
+            (
+                checkpoint.epoch,
+                checkpoint.anchor.clone(),
+                Some(checkpoint),
+            )
+        })
+        .collect();
+    if let Some(head) = head {
+        let head_digest = anchor_json_digest(&head)?;
+        let last = epochs.last();
+        let unchanged = match last {
+            Some((_, anchor, _)) => anchor_json_digest(anchor)? == head_digest,
+            None => false,
+        };
+        if !unchanged {
+            let epoch = last.map(|(epoch, _, _)| epoch + 1).unwrap_or(0);
+            epochs.push((epoch, head, None));
+        }
+    }
+    if epochs.is_empty() {
+        return Err(ArchiveError::Invalid(
+            "no checkpoints or head anchor to archive".to_string(),
+        ));
+    }
+    let mut prev_link = [0u8; 32];
+    let mut entries = Vec::with_capacity(epochs.len());
+    for (epoch, anchor, checkpoint) in epochs {
+        let digest = anchor_json_digest(&anchor)?;
+        let link = archive_link(epoch, &digest, &prev_link);
+        let signature = sign_payload(&key.signing, &link);
+        entries.push(ArchiveEntry {
+            epoch,
+            anchor,
+            checkpoint,
+            anchor_digest: hex::encode(digest),
+            prev_link: hex::encode(prev_link),
+            link: hex::encode(link),
+            signature: encode_signature_base64(&signature),
+        });
+        prev_link = link;
+    }
+    Ok(AnchorArchive {
+        schema: SCHEMA_ANCHOR_ARCHIVE.to_string(),
+        node_id: node_id.to_string(),
+        public_key: encode_public_key_base64(&key.verifying),
+        created_ms,
+        entries,
+    })
+}
+
+/// Validates an archive offline: digests, links, signatures and append-only history.
+pub fn verify_archive(archive: &AnchorArchive) -> Result<ArchiveSummary, ArchiveError> {
+    if archive.schema != SCHEMA_ANCHOR_ARCHIVE {
+        return Err(ArchiveError::Invalid(format!(
+            "unexpected schema {}",
+            archive.schema
+        )));
+    }
+    if archive.entries.is_empty() {
+        return Err(ArchiveError::Invalid("archive has no entries".to_string()));
+    }
+    let mut prev_link = [0u8; 32];
+    let mut prev: Option<(u64, LedgerAnchor)> = None;
+    let mut checkpoint_signatures = 0usize;
+    for entry in &archive.entries {
+        let epoch = entry.epoch;
+        let chain_err = |reason: String| ArchiveError::Chain { epoch, reason };
+        if let Some((prev_epoch, _)) = &prev {
+            if epoch <= *prev_epoch {
+                return Err(chain_err(format!(
+                    "epoch does not increase (previous {prev_epoch})"
+                )));
+            }
+        }
+        let ledger = entry
+            .anchor
+            .clone()
+            .into_ledger()
+            .map_err(|err| chain_err(format!("invalid anchor: {err}")))?;
+        let digest = anchor_digest(&ledger);
+        if entry.anchor_digest != hex::encode(digest) {
+            return Err(chain_err("anchor digest mismatch".to_string()));
+        }
+        if entry.prev_link != hex::encode(prev_link) {
+            return Err(chain_err("previous link mismatch".to_string()));
+        }
+        let link = archive_link(epoch, &digest, &prev_link);
+        if entry.link != hex::encode(link) {
+            return Err(chain_err("link hash mismatch".to_string()));
+        }
+        verify_signature_base64(&archive.public_key, &link, &entry.signature)
+            .map_err(|err| ArchiveError::Signature(format!("epoch {epoch}: {err}")))?;
+        if let Some(checkpoint) = &entry.checkpoint {
+            checkpoint_signatures += verify_checkpoint(entry, checkpoint)?;
+        }
+        if let Some((_, prev_ledger)) = &prev {
+            if !extends(prev_ledger, &ledger) {
+                return Err(chain_err(
+                    "anchor does not extend the previous epoch".to_string(),
+                ));
+            }
+        }
+        prev_link = link;
+        prev = Some((epoch, ledger));
+    }
+    Ok(ArchiveSummary {
+        entries: archive.entries.len(),
+        checkpoint_signatures,
+        head_link: hex::encode(prev_link),
+    })
+}
+
+fn verify_checkpoint(
+    entry: &ArchiveEntry,
+    checkpoint: &AnchorCheckpoint,
+) -> Result<usize, ArchiveError> {
+    let epoch = entry.epoch;
+    if checkpoint.epoch != epoch {
+        return Err(ArchiveError::Chain {
+            epoch,
+            reason: format!("checkpoint epoch {} differs", checkpoint.epoch),
+        });
+    }
+    if checkpoint.anchor != entry.anchor {
+        return Err(ArchiveError::Chain {
+            epoch,
+            reason: "checkpoint anchor differs from archived anchor".to_string(),
+        });
+    }
+    let payload = serde_json::to_vec(&checkpoint.anchor)
+        .map_err(|err| ArchiveError::Invalid(err.to_string()))?;
+    for signature in &checkpoint.signatures {
+        verify_signature_base64(&signature.public_key, &payload, &signature.signature).map_err(
+            |err| {
+                ArchiveError::Signature(format!(
+                    "epoch {epoch} checkpoint signer {}: {err}",
+                    signature.node_id
+                ))
+            },
+        )?;
+    }
+    Ok(checkpoint.signatures.len())
+}
+
+/// Returns true when `next` keeps every entry of `prev`, allowing the last
+/// entry to gain further transcript hashes.
+fn extends(prev: &LedgerAnchor, next: &LedgerAnchor) -> bool {
+    if next.entries.len() < prev.entries.len() {
+        return false;
+    }
+    let Some((last, head)) = prev.entries.split_last() else {
+        return true;
+    };
+    head.iter()
+        .zip(&next.entries)
+        .all(|(left, right)| left.statement == right.statement && left.hashes == right.hashes)
+        && {
+            let candidate = &next.entries[head.len()];
+            candidate.statement == last.statement && candidate.hashes.starts_with(&last.hashes)
+        }
+}
+
+fn anchor_json_digest(anchor: &AnchorJson) -> Result<[u8; 32], ArchiveError> {
+    let ledger = anchor
+        .clone()
+        .into_ledger()
+        .map_err(|err| ArchiveError::Invalid(err.to_string()))?;
+    Ok(anchor_digest(&ledger))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::checkpoint::{write_checkpoint, CheckpointSignature};
+    use crate::net::sign::{load_or_derive_keypair, Ed25519KeySource};
+    use crate::{julian_genesis_anchor, EntryAnchor};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn anchor_with(hashes: &[[u8; 32]]) -> AnchorJson {
+        let mut ledger = julian_genesis_anchor();
+        if !hashes.is_empty() {
+            ledger.entries.push(EntryAnchor {
+                statement: "archived statement".to_string(),
+                hashes: hashes.to_vec(),
+                merkle_root: crate::merkle_root(hashes),
+                submission: None,
+            });
+        }
+        AnchorJson::from_ledger("node-a", 1, &ledger, 0, Vec::new(), None).unwrap()
+    }
+
+    fn signed_checkpoint(key: &KeyMaterial, epoch: u64, anchor: AnchorJson) -> AnchorCheckpoint {
+        let payload = serde_json::to_vec(&anchor).unwrap();
+        AnchorCheckpoint::new(
+            epoch,
+            anchor,
+            vec![CheckpointSignature {
+                node_id: "node-a".to_string(),
+                public_key: encode_public_key_base64(&key.verifying),
+                signature: encode_signature_base64(&sign_payload(&key.signing, &payload)),
+            }],
+            None,
+        )
+    }
+
+    #[test]
+    fn archive_export_verifies_and_detects_tampering() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let log_dir = std::env::temp_dir().join(format!("power_house_archive_{nanos}"));
+        let key = load_or_derive_keypair(&Ed25519KeySource::Seed("ed25519://archive-test".into()))
+            .unwrap();
+        let checkpoints = log_dir.join("checkpoints");
+        write_checkpoint(
+            &checkpoints,
+            &signed_checkpoint(&key, 2, anchor_with(&[[1u8; 32]])),
+        )
+        .unwrap();
+        write_checkpoint(
+            &checkpoints,
+            &signed_checkpoint(&key, 4, anchor_with(&[[1u8; 32], [2u8; 32]])),
+        )
+        .unwrap();
+        let head = anchor_with(&[[1u8; 32], [2u8; 32], [3u8; 32]]);
+
+        let archive = export_archive(&log_dir, "node-a", &key, Some(head), 99).unwrap();
+        assert_eq!(archive.entries.len(), 3);
+        assert_eq!(archive.entries[2].epoch, 5);
+        assert!(archive.entries[2].checkpoint.is_none());
+        let path = log_dir.join("archive.json");
+        archive.write(&path).unwrap();
+        let summary = verify_archive(&AnchorArchive::read(&path).unwrap()).unwrap();
+        assert_eq!(summary.entries, 3);
+        assert_eq!(summary.checkpoint_signatures, 2);
+        assert_eq!(summary.head_link, archive.entries[2].link);
+
+        let mut dropped = archive.clone();
+        dropped.entries.remove(1);
+        assert!(matches!(
+            verify_archive(&dropped),
+            Err(ArchiveError::Chain { epoch: 5, .. })
+        ));
+
+        let mut rewritten = archive.clone();
+        rewritten.entries[1].anchor = anchor_with(&[[9u8; 32]]);
+        assert!(matches!(
+            verify_archive(&rewritten),
+            Err(ArchiveError::Chain { epoch: 4, .. })
+        ));
+
+        let mut forged = archive.clone();
+        forged.public_key = encode_public_key_base64(
+            &load_or_derive_keypair(&Ed25519KeySource::Seed("ed25519://other".into()))
+                .unwrap()
+                .verifying,
+        );
+        assert!(matches!(
+            verify_archive(&forged),
+            Err(ArchiveError::Signature(_))
+        ));
+        let _ = fs::remove_dir_all(&log_dir);
+    }
+}
@@ -0,0 +1,328 @@
+#![cfg(feature = "net")]
+
+//! Signed finality attestations for external auditors.
+//!
+//! Every time a node finalizes an anchor it appends an [`AnchorAttestation`]
+//! to `attestations.jsonl` in its log directory: "node X attested digest D at
+//! time T", signed with the node's ed25519 identity.  [`export_attestations`]
+//! collects the records since a cutoff into an [`AttestationBundle`], and
+//! [`verify_attestation_bundle`] checks every signature using nothing but the
+//! public keys carried in (or supplied alongside) the bundle.
+
+use crate::net::sign::{
+    encode_public_key_base64, encode_signature_base64, sign_payload, verify_signature_base64,
+    KeyMaterial,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeSet,
+    fs,
+    io::{BufRead, BufReader, Write},
+    path::Path,
+};
+
+/// Schema identifier of a single finality attestation.
+pub const SCHEMA_ANCHOR_ATTESTATION: &str = "mfenx.powerhouse.anchor_attestation.v1";
+/// Schema identifier of an exported attestation bundle.
+pub const SCHEMA_ATTESTATION_BUNDLE: &str = "mfenx.powerhouse.attestation_bundle.v1";
+/// File name of the attestation log inside a node's log directory.
+pub const ATTESTATIONS_FILE: &str = "attestations.jsonl";
+
+const ATTESTATION_DOMAIN: &[u8] = b"MFENX_ANCHOR_ATTESTATION";
+
+/// Errors produced while recording, exporting or verifying attestations.
+#[derive(Debug, thiserror::Error)]
+pub enum AuditError {
+    /// Local filesystem or encoding failure.
+    #[error("attestation I/O error: {0}")]
+    Io(String),
+    /// The bundle or one of its records is malformed.
+    #[error("invalid attestation: {0}")]
+    Invalid(String),
+    /// A record's signature does not verify.
+    #[error("attestation {index} signature check failed: {reason}")]
+    Signature {
+        /// Position of the offending record in the bundle.
+        index: usize,
+        /// Human-readable failure description.
+        reason: String,
+    },
+    /// A record was signed by a key the auditor did not trust.
+    #[error("attestation {index} signed by untrusted key {public_key}")]
+    Untrusted {
+        /// Position of the offending record in the bundle.
+        index: usize,
+        /// Base64 key that signed the record.
+        public_key: String,
+    },
+}
+
+/// Signed statement that a node finalized an anchor digest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnchorAttestation {
+    /// Schema identifier (`mfenx.powerhouse.anchor_attestation.v1`).
+    pub schema: String,
+    /// Network the anchor was finalized on.
+    pub network: String,
+    /// Node identifier of the attesting node.
+    pub node_id: String,
+    /// Base64 ed25519 public key of the attesting node.
+    pub public_key: String,
+    /// Hex digest of the finalized anchor.
+    pub anchor_digest: String,
+    /// Number of ledger entries in the finalized anchor.
+    pub entries: u64,
+    /// Millisecond timestamp of the finality event.
+    pub finalized_ms: u64,
+    /// Base64 signature over [`AnchorAttestation::signing_bytes`].
+    pub signature: String,
+}
+
+impl AnchorAttestation {
+    /// Builds and signs an attestation for `anchor_digest`.
+    pub fn sign(
+        network: &str,
+        node_id: &str,
+        key: &KeyMaterial,
+        anchor_digest: &[u8; 32],
+        entries: u64,
+        finalized_ms: u64,
+    ) -> Self {
+        let mut attestation = Self {
+            schema: SCHEMA_ANCHOR_ATTESTATION.to_string(),
+            network: network.to_string(),
+            node_id: node_id.to_string(),
+            public_key: encode_public_key_base64(&key.verifying),
+            anchor_digest: hex::encode(anchor_digest),
+            entries,
+            finalized_ms,
+            signature: String::new(),
+        };
+        let signature = sign_payload(&key.signing, &attestation.signing_bytes());
+        attestation.signature = encode_signature_base64(&signature);
+        attestation
+    }
+
+    /// Domain-separated, length-prefixed bytes covered by the signature.
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        let mut out = ATTESTATION_DOMAIN.to_vec();
+        for field in [
+            &self.schema,
+            &self.network,
+            &self.node_id,
+            &self.public_key,
+            &self.anchor_digest,
+        ] {
+            out.extend_from_slice(&(field.len() as u64).to_be_bytes());
+            out.extend_from_slice(field.as_bytes());
+        }
+        out.extend_from_slice(&self.entries.to_be_bytes());
+        out.extend_from_slice(&self.finalized_ms.to_be_bytes());
+        out
+    }
+
+    /// Checks the schema, digest encoding and signature.
+    pub fn verify(&self) -> Result<(), String> {
+        if self.schema != SCHEMA_ANCHOR_ATTESTATION {
+            return Err(format!("unexpected schema {}", self.schema));
+        }
+        let digest = hex::decode(&self.anchor_digest).map_err(|err| err.to_string())?;
+        if digest.len() != 32 {
+            return Err("anchor digest must be 32 bytes".to_string());
+        }
+        verify_signature_base64(&self.public_key, &self.signing_bytes(), &self.signature)
+            .map_err(|err| err.to_string())
+    }
+}
+
+/// Appends an attestation to the log in `log_dir`.
+pub fn append_attestation(
+    log_dir: &Path,
+    attestation: &AnchorAttestation,
+) -> Result<(), AuditError> {
+    fs::create_dir_all(log_dir).map_err(|err| AuditError::Io(err.to_string()))?;
+    let mut line =
+        serde_json::to_vec(attestation).map_err(|err| AuditError::Io(err.to_string()))?;
+    line.push(b'\n');
+    fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_dir.join(ATTESTATIONS_FILE))
+        .and_then(|mut file| file.write_all(&line))
+        .map_err(|err| AuditError::Io(err.to_string()))
+}
+
+/// Reads the attestations in `log_dir` finalized at or after `since_ms`.
+pub fn load_attestations(
+    log_dir: &Path,
+    since_ms: u64,
+) -> Result<Vec<AnchorAttestation>, AuditError> {
+    let file = match fs::File::open(log_dir.join(ATTESTATIONS_FILE)) {
+        Ok(file) => file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(AuditError::Io(err.to_string())),
+    };
+    let mut out = Vec::new();
+    for (number, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|err| AuditError::Io(err.to_string()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let attestation: AnchorAttestation = serde_json::from_str(&line).map_err(|err| {
+            AuditError::Invalid(format!("{ATTESTATIONS_FILE} line {}: {err}", number + 1))
+        })?;
+        if attestation.finalized_ms >= since_ms {
+            out.push(attestation);
+        }
+    }
+    Ok(out)
+}
+
+/// Attestations exported for third-party verification.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttestationBundle {
+    /// Schema identifier (`mfenx.powerhouse.attestation_bundle.v1`).
+    pub schema: String,
+    /// Millisecond timestamp of the export.
+    pub created_ms: u64,
+    /// Lower bound (inclusive) on `finalized_ms` used for the export.
+    pub since_ms: u64,
+    /// Attestations ordered as they were logged.
+    pub attestations: Vec<AnchorAttestation>,
+}
+
+/// Result of a successful [`verify_attestation_bundle`] run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttestationBundleSummary {
+    /// Number of attestations verified.
+    pub attestations: usize,
+    /// Distinct anchor digests attested.
+    pub digests: usize,
+    /// Distinct `(node_id, public_key)` signers.
+    pub signers: BTreeSet<(String, String)>,
+}
+
+impl AttestationBundle {
+    /// Reads a bundle from a JSON file.
+    pub fn read(path: &Path) -> Result<Self, AuditError> {
+        let bytes = fs::read(path).map_err(|err| AuditError::Io(err.to_string()))?;
+        serde_json::from_slice(&bytes).map_err(|err| AuditError::Invalid(err.to_string()))
+    }
+
+    /// Writes the bundle as pretty-printed JSON.
+    pub fn write(&self, path: &Path) -> Result<(), AuditError> {
+        let bytes =
+            serde_json::to_vec_pretty(self).map_err(|err| AuditError::Io(err.to_string()))?;
+        fs::write(path, bytes).map_err(|err| AuditError::Io(err.to_string()))
+    }
+}
+
+/// Bundles the attestations in `log_dir` finalized at or after `since_ms`.
+pub fn export_attestations(
+    log_dir: &Path,
+    since_ms: u64,
+    created_ms: u64,
+) -> Result<AttestationBundle, AuditError> {
+    Ok(AttestationBundle {
+        schema: SCHEMA_ATTESTATION_BUNDLE.to_string(),
+        created_ms,
+        since_ms,
+        attestations: load_attestations(log_dir, since_ms)?,
+    })
+}
+
+/// Verifies every attestation in `bundle`.
+///
+/// When `trusted_keys` is non-empty each record must also be signed by one of
+/// those base64 public keys; otherwise the keys embedded in the records are
+/// reported in the summary for the auditor to compare.
+pub fn verify_attestation_bundle(
+    bundle: &AttestationBundle,
+    trusted_keys: &[String],
+) -> Result<AttestationBundleSummary, AuditError> {
+    if bundle.schema != SCHEMA_ATTESTATION_BUNDLE {
+        return Err(AuditError::Invalid(format!(
+            "unexpected schema {}",
+            bundle.schema
+        )));
+    }
+    let mut digests = BTreeSet::new();
+    let mut signers = BTreeSet::new();
+    for (index, attestation) in bundle.attestations.iter().enumerate() {
+        if attestation.finalized_ms < bundle.since_ms {
+            return Err(AuditError::Invalid(format!(
+                "attestation {index} predates the bundle cutoff"
+            )));
+        }
+        if !trusted_keys.is_empty() && !trusted_keys.contains(&attestation.public_key) {
+            return Err(AuditError::Untrusted {
+                index,
+                public_key: attestation.public_key.clone(),
+            });
+        }
+        attestation
+            .verify()
+            .map_err(|reason| AuditError::Signature { index, reason })?;
+        digests.insert(attestation.anchor_digest.clone());
+        signers.insert((attestation.node_id.clone(), attestation.public_key.clone()));
+    }
+    Ok(AttestationBundleSummary {
+        attestations: bundle.attestations.len(),
+        digests: digests.len(),
+        signers,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::sign::{load_or_derive_keypair, Ed25519KeySource};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn exported_bundle_verifies_with_public_keys_only() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let log_dir = std::env::temp_dir().join(format!("power_house_audit_{nanos}"));
+        let key =
+            load_or_derive_keypair(&Ed25519KeySource::Seed("ed25519://audit-test".into())).unwrap();
+        for (digest, ms) in [([1u8; 32], 100), ([2u8; 32], 200), ([3u8; 32], 300)] {
+            let attestation = AnchorAttestation::sign("mainnet", "node-a", &key, &digest, 2, ms);
+            append_attestation(&log_dir, &attestation).unwrap();
+        }
+
+        let bundle = export_attestations(&log_dir, 200, 999).unwrap();
+        assert_eq!(bundle.attestations.len(), 2);
+        let path = log_dir.join("bundle.json");
+        bundle.write(&path).unwrap();
+        let bundle = AttestationBundle::read(&path).unwrap();
+        let summary = verify_attestation_bundle(&bundle, &[]).unwrap();
+        assert_eq!(summary.attestations, 2);
+        assert_eq!(summary.digests, 2);
+        let public_key = encode_public_key_base64(&key.verifying);
+        assert!(summary
+            .signers
+            .contains(&("node-a".to_string(), public_key.clone())));
+        verify_attestation_bundle(&bundle, &[public_key]).unwrap();
+        assert!(matches!(
+            verify_attestation_bundle(&bundle, &["other".to_string()]),
+            Err(AuditError::Untrusted { index: 0, .. })
+        ));
+
+        let mut tampered = bundle.clone();
+        tampered.attestations[1].finalized_ms += 1;
+        assert!(matches!(
+            verify_attestation_bundle(&tampered, &[]),
+            Err(AuditError::Signature { index: 1, .. })
+        ));
+        let mut backdated = bundle;
+        backdated.since_ms = 250;
+        assert!(matches!(
+            verify_attestation_bundle(&backdated, &[]),
+            Err(AuditError::Invalid(_))
+        ));
+        let _ = fs::remove_dir_all(&log_dir);
+    }
+}
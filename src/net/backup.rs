@@ -0,0 +1,673 @@
+#![cfg(feature = "net")]
+
+//! Signed, digest-checked backups of a node's critical state.
+//!
+//! `julian backup create` packs checkpoints, the stake registry, the native
+//! chain (RPC) state, the migration apply-state and operator config files into
+//! a gzip-compressed ustar archive.  The first entry is a [`BackupManifest`]
+//! listing every file with its size and BLAKE2b-256 digest, signed by the node
+//! key.  Private keys are never packed: files that look like key material are
+//! refused.
+//!
+//! `julian backup restore` verifies the signature, every digest, and that the
+//! archive holds exactly the manifest's files before anything is written, then
+//! stages all files next to their destinations and renames them into place, so
+//! a damaged backup restores nothing.
+
+use crate::net::schema::network_id;
+use crate::net::sign::{
+    decode_public_key_base64, encode_public_key_base64, encode_signature_base64, sign_payload,
+    verify_signature_base64, KeyMaterial,
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use blake2::digest::{consts::U32, Digest};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{Read, Write},
+    path::{Component, Path, PathBuf},
+};
+
+type Blake2b256 = blake2::Blake2b<U32>;
+
+/// Schema identifier of a [`BackupManifest`].
+pub const SCHEMA_BACKUP_MANIFEST: &str = "mfenx.powerhouse.backup_manifest.v1";
+/// Archive entry holding the manifest.
+pub const BACKUP_MANIFEST_ENTRY: &str = "MANIFEST.json";
+
+const BACKUP_MANIFEST_DOMAIN: &[u8] = b"MFENX_BACKUP_MANIFEST";
+const BLOCK: usize = 512;
+const STATE_FILES: [&str; 4] = [
+    "stake_registry.json",
+    "stake_registry.sqlite",
+    "native_chain_state.json",
+    "native_chain_state.sqlite",
+];
+
+/// Errors produced while creating, verifying, or restoring backups.
+#[derive(Debug, thiserror::Error)]
+pub enum BackupError {
+    /// A file could not be read or written.
+    #[error("backup I/O error: {0}")]
+    Io(String),
+    /// The archive or manifest is malformed, tampered with, or incomplete.
+    #[error("invalid backup: {0}")]
+    Invalid(String),
+    /// The operation was refused before anything was written.
+    #[error("backup refused: {0}")]
+    Refused(String),
+}
+
+fn io_error(path: &Path, err: std::io::Error) -> BackupError {
+    BackupError::Io(format!("{}: {err}", path.display()))
+}
+
+/// What a backed-up file is, which also decides where it is restored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupRole {
+    /// Stake registry (`stake_registry.*`).
+    Registry,
+    /// Native chain state served by the wallet RPC (`native_chain_state.*`).
+    RpcState,
+    /// Checkpoint under `<log-dir>/checkpoints`.
+    Checkpoint,
+    /// Migration apply-state file.
+    ApplyState,
+    /// Operator configuration file.
+    Config,
+}
+
+impl BackupRole {
+    fn directory(self) -> &'static str {
+        match self {
+            Self::Registry | Self::RpcState => "state",
+            Self::Checkpoint => "log/checkpoints",
+            Self::ApplyState | Self::Config => "config",
+        }
+    }
+}
+
+/// One file recorded in a [`BackupManifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupFile {
+    /// Path inside the archive, `<role directory>/<file name>`.
+    pub path: String,
+    /// What the file is.
+    pub role: BackupRole,
+    /// Size in bytes.
+    pub size: u64,
+    /// Hex BLAKE2b-256 digest of the contents.
+    pub digest: String,
+}
+
+/// Signed inventory stored as the first archive entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    /// Schema identifier (`mfenx.powerhouse.backup_manifest.v1`).
+    pub schema: String,
+    /// Network the node belongs to.
+    pub network: String,
+    /// Millisecond timestamp of the backup.
+    pub created_ms: u64,
+    /// Every file in the archive, in archive order.
+    pub files: Vec<BackupFile>,
+    /// Signing node key (base64).
+    pub public_key: String,
+    /// Base64 signature over every other field.
+    pub signature: String,
+}
+
+impl BackupManifest {
+    fn signing_bytes(&self) -> Vec<u8> {
+        #[derive(Serialize)]
+        struct Unsigned<'a> {
+            schema: &'a str,
+            network: &'a str,
+            created_ms: u64,
+            files: &'a [BackupFile],
+            public_key: &'a str,
+        }
+        let mut bytes = BACKUP_MANIFEST_DOMAIN.to_vec();
+        bytes.extend(
+            serde_json::to_vec(&Unsigned {
+                schema: &self.schema,
+                network: &self.network,
+                created_ms: self.created_ms,
+                files: &self.files,
+                public_key: &self.public_key,
+            })
+            .expect("backup manifest fields serialize"),
+        );
+        bytes
+    }
+
+    /// Checks the schema, the signature, and `trusted_key` if given.
+    pub fn verify(&self, trusted_key: Option<&str>) -> Result<(), BackupError> {
+        if self.schema != SCHEMA_BACKUP_MANIFEST {
+            return Err(BackupError::Invalid(format!(
+                "unexpected schema {}",
+                self.schema
+            )));
+        }
+        if let Some(trusted) = trusted_key {
+            if trusted != self.public_key {
+                return Err(BackupError::Invalid(format!(
+                    "manifest signed by {}, expected {trusted}",
+                    self.public_key
+                )));
+            }
+        }
+        decode_public_key_base64(&self.public_key)
+            .map_err(|err| BackupError::Invalid(format!("public key: {err}")))?;
+        verify_signature_base64(&self.public_key, &self.signing_bytes(), &self.signature)
+            .map_err(|err| BackupError::Invalid(format!("manifest signature: {err}")))
+    }
+}
+
+/// State locations to back up.
+#[derive(Debug, Clone, Default)]
+pub struct BackupSources {
+    /// Node log directory; its `checkpoints/` are included.
+    pub log_dir: PathBuf,
+    /// Directory holding the registry and native chain state (the node's
+    /// `--blob-dir`); defaults to `log_dir`.
+    pub state_dir: Option<PathBuf>,
+    /// Migration apply-state file.
+    pub apply_state: Option<PathBuf>,
+    /// Operator configuration files (policies, genesis, webhook and alert
+    /// configs, ...).
+    pub config: Vec<PathBuf>,
+}
+
+impl BackupSources {
+    fn collect(&self) -> Result<Vec<(BackupRole, PathBuf)>, BackupError> {
+        let mut files = Vec::new();
+        let checkpoints = self.log_dir.join("checkpoints");
+        if checkpoints.is_dir() {
+            let mut entries = fs::read_dir(&checkpoints)
+                .map_err(|err| io_error(&checkpoints, err))?
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.is_file())
+                .collect::<Vec<_>>();
+            entries.sort();
+            files.extend(
+                entries
+                    .into_iter()
+                    .map(|path| (BackupRole::Checkpoint, path)),
+            );
+        }
+        let state_dir = self.state_dir.as_ref().unwrap_or(&self.log_dir);
+        for name in STATE_FILES {
+            let path = state_dir.join(name);
+            if path.is_file() {
+                let role = if name.starts_with("stake_registry") {
+                    BackupRole::Registry
+                } else {
+                    BackupRole::RpcState
+                };
+                files.push((role, path));
+            }
+        }
+        if let Some(path) = &self.apply_state {
+            files.push((BackupRole::ApplyState, path.clone()));
+        }
+        files.extend(
+            self.config
+                .iter()
+                .map(|path| (BackupRole::Config, path.clone())),
+        );
+        if files.is_empty() {
+            return Err(BackupError::Refused(format!(
+                "no state found under {}",
+                self.log_dir.display()
+            )));
+        }
+        Ok(files)
+    }
+}
+
+/// Whether `bytes` look like a node private key in any format `julian`
+/// reads: raw 32 bytes, hex, an encrypted identity, a mnemonic key file, or
+/// PEM.
+pub fn looks_like_private_key(bytes: &[u8]) -> bool {
+    if bytes.len() == 32 {
+        return true;
+    }
+    let Ok(text) = std::str::from_utf8(bytes) else {
+        return false;
+    };
+    let trimmed = text.trim();
+    trimmed.starts_with("# julian key file")
+        || trimmed.contains("PRIVATE KEY")
+        || (trimmed.len() == 64 && hex::decode(trimmed).is_ok())
+        || BASE64
+            .decode(trimmed)
+            .map(|decoded| decoded.len() == 32)
+            .unwrap_or(false)
+}
+
+fn digest_hex(bytes: &[u8]) -> String {
+    hex::encode(Blake2b256::digest(bytes))
+}
+
+/// Writes a signed backup of `sources` to `out`.
+pub fn create_backup(
+    sources: &BackupSources,
+    out: &Path,
+    key: &KeyMaterial,
+    created_ms: u64,
+) -> Result<BackupManifest, BackupError> {
+    let mut entries: Vec<(BackupFile, Vec<u8>)> = Vec::new();
+    for (role, path) in sources.collect()? {
+        let bytes = fs::read(&path).map_err(|err| io_error(&path, err))?;
+        if looks_like_private_key(&bytes) {
+            return Err(BackupError::Refused(format!(
+                "{} looks like private key material",
+                path.display()
+            )));
+        }
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| {
+                BackupError::Refused(format!("unusable file name {}", path.display()))
+            })?;
+        let archive_path = format!("{}/{name}", role.directory());
+        if entries.iter().any(|(file, _)| file.path == archive_path) {
+            return Err(BackupError::Refused(format!(
+                "two files would be stored as {archive_path}"
+            )));
+        }
+        entries.push((
+            BackupFile {
+                path: archive_path,
+                role,
+                size: bytes.len() as u64,
+                digest: digest_hex(&bytes),
+            },
+            bytes,
+        ));
+    }
+    let mut manifest = BackupManifest {
+        schema: SCHEMA_BACKUP_MANIFEST.to_string(),
+        network: network_id().to_string(),
+        created_ms,
+        files: entries.iter().map(|(file, _)| file.clone()).collect(),
+        public_key: encode_public_key_base64(&key.verifying),
+        signature: String::new(),
+    };
+    manifest.signature =
+        encode_signature_base64(&sign_payload(&key.signing, &manifest.signing_bytes()));
+
+    let manifest_bytes = serde_json::to_vec_pretty(&manifest)
+        .map_err(|err| BackupError::Invalid(err.to_string()))?;
+    let mtime = created_ms / 1000;
+    let mut tar = Vec::new();
+    append_tar_entry(&mut tar, BACKUP_MANIFEST_ENTRY, &manifest_bytes, mtime)?;
+    for (file, bytes) in &entries {
+        append_tar_entry(&mut tar, &file.path, bytes, mtime)?;
+    }
+    tar.extend([0u8; 2 * BLOCK]);
+
+    let tmp = out.with_extension("partial");
+    let file = fs::File::create(&tmp).map_err(|err| io_error(&tmp, err))?;
+    let mut encoder = GzEncoder::new(file, Compression::default());
+    encoder
+        .write_all(&tar)
+        .and_then(|_| encoder.finish().map(drop))
+        .map_err(|err| io_error(&tmp, err))?;
+    fs::rename(&tmp, out).map_err(|err| io_error(out, err))?;
+    Ok(manifest)
+}
+
+fn read_archive(path: &Path) -> Result<Vec<(String, Vec<u8>)>, BackupError> {
+    let file = fs::File::open(path).map_err(|err| io_error(path, err))?;
+    let mut tar = Vec::new();
+    GzDecoder::new(file)
+        .read_to_end(&mut tar)
+        .map_err(|err| BackupError::Invalid(format!("{}: {err}", path.display())))?;
+    parse_tar(&tar)
+}
+
+/// Verifies `path` completely and returns its manifest.
+///
+/// The manifest must be correctly signed (by `trusted_key` if given), and the
+/// archive must hold exactly the listed files with matching sizes and digests.
+pub fn verify_backup(
+    path: &Path,
+    trusted_key: Option<&str>,
+) -> Result<BackupManifest, BackupError> {
+    verify_contents(path, trusted_key).map(|(manifest, _)| manifest)
+}
+
+fn verify_contents(
+    path: &Path,
+    trusted_key: Option<&str>,
+) -> Result<(BackupManifest, BTreeMap<String, Vec<u8>>), BackupError> {
+    let mut entries = read_archive(path)?.into_iter();
+    let (name, manifest_bytes) = entries
+        .next()
+        .ok_or_else(|| BackupError::Invalid("archive is empty".to_string()))?;
+    if name != BACKUP_MANIFEST_ENTRY {
+        return Err(BackupError::Invalid(format!(
+            "first entry is {name}, expected {BACKUP_MANIFEST_ENTRY}"
+        )));
+    }
+    let manifest: BackupManifest = serde_json::from_slice(&manifest_bytes)
+        .map_err(|err| BackupError::Invalid(format!("manifest: {err}")))?;
+    manifest.verify(trusted_key)?;
+
+    let mut contents = BTreeMap::new();
+    for (name, bytes) in entries {
+        if contents.insert(name.clone(), bytes).is_some() {
+            return Err(BackupError::Invalid(format!("duplicate entry {name}")));
+        }
+    }
+    for file in &manifest.files {
+        if !file
+            .path
+            .starts_with(&format!("{}/", file.role.directory()))
+            || Path::new(&file.path)
+                .components()
+                .any(|component| !matches!(component, Component::Normal(_)))
+            || file.path.matches('/').count() != file.role.directory().matches('/').count() + 1
+        {
+            return Err(BackupError::Invalid(format!(
+                "manifest path {} does not match its role",
+                file.path
+            )));
+        }
+        let bytes = contents
+            .get(&file.path)
+            .ok_or_else(|| BackupError::Invalid(format!("{} is missing", file.path)))?;
+        if bytes.len() as u64 != file.size || digest_hex(bytes) != file.digest {
+            return Err(BackupError::Invalid(format!(
+                "{} does not match its manifest digest",
+                file.path
+            )));
+        }
+    }
+    if contents.len() != manifest.files.len() {
+        let extra = contents
+            .keys()
+            .find(|name| !manifest.files.iter().any(|file| &file.path == *name))
+            .cloned()
+            .unwrap_or_default();
+        return Err(BackupError::Invalid(format!(
+            "entry {extra} is not in the manifest"
+        )));
+    }
+    Ok((manifest, contents))
+}
+
+/// Where restored files go.
+#[derive(Debug, Clone, Default)]
+pub struct RestoreTargets {
+    /// Receives `checkpoints/`.
+    pub log_dir: PathBuf,
+    /// Receives the registry and native chain state; defaults to `log_dir`.
+    pub state_dir: Option<PathBuf>,
+    /// Receives the apply-state and config files; required when the backup
+    /// contains any.
+    pub config_dir: Option<PathBuf>,
+}
+
+impl RestoreTargets {
+    fn destination(&self, file: &BackupFile) -> Result<PathBuf, BackupError> {
+        let name = file.path.rsplit('/').next().unwrap_or(&file.path);
+        let dir = match file.role {
+            BackupRole::Checkpoint => self.log_dir.join("checkpoints"),
+            BackupRole::Registry | BackupRole::RpcState => self
+                .state_dir
+                .clone()
+                .unwrap_or_else(|| self.log_dir.clone()),
+            BackupRole::ApplyState | BackupRole::Config => {
+                self.config_dir.clone().ok_or_else(|| {
+                    BackupError::Refused(format!(
+                        "{} needs a config directory to restore into",
+                        file.path
+                    ))
+                })?
+            }
+        };
+        Ok(dir.join(name))
+    }
+}
+
+/// Restores every file of a verified backup, or nothing.
+///
+/// Existing destination files are only replaced when `force` is set.
+pub fn restore_backup(
+    path: &Path,
+    targets: &RestoreTargets,
+    trusted_key: Option<&str>,
+    force: bool,
+) -> Result<BackupManifest, BackupError> {
+    let (manifest, contents) = verify_contents(path, trusted_key)?;
+    let mut plan = Vec::new();
+    for file in &manifest.files {
+        let destination = targets.destination(file)?;
+        if destination.exists() && !force {
+            return Err(BackupError::Refused(format!(
+                "{} exists; pass --force to replace it",
+                destination.display()
+            )));
+        }
+        plan.push((destination, &contents[&file.path]));
+    }
+
+    let mut staged = Vec::new();
+    let staging = plan.iter().try_for_each(|(destination, bytes)| {
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent).map_err(|err| io_error(parent, err))?;
+        }
+        let tmp = destination.with_extension("restore.tmp");
+        fs::write(&tmp, bytes).map_err(|err| io_error(&tmp, err))?;
+        staged.push((tmp, destination.clone()));
+        Ok(())
+    });
+    if let Err(err) = staging {
+        for (tmp, _) in &staged {
+            let _ = fs::remove_file(tmp);
+        }
+        return Err(err);
+    }
+    for (tmp, destination) in &staged {
+        fs::rename(tmp, destination).map_err(|err| io_error(destination, err))?;
+    }
+    Ok(manifest)
+}
+
+fn append_tar_entry(
+    tar: &mut Vec<u8>,
+    name: &str,
+    bytes: &[u8],
+    mtime: u64,
+) -> Result<(), BackupError> {
+    if name.len() >= 100 {
+        return Err(BackupError::Refused(format!(
+            "archive path {name} is too long"
+        )));
+    }
+    let mut header = [0u8; BLOCK];
+    header[..name.len()].copy_from_slice(name.as_bytes());
+    header[100..108].copy_from_slice(b"0000644\0");
+    header[108..116].copy_from_slice(b"0000000\0");
+    header[116..124].copy_from_slice(b"0000000\0");
+    header[124..136].copy_from_slice(format!("{:011o}\0", bytes.len()).as_bytes());
+    header[136..148].copy_from_slice(format!("{:011o}\0", mtime).as_bytes());
+    header[148..156].copy_from_slice(b"        ");
+    header[156] = b'0';
+    header[257..263].copy_from_slice(b"ustar\0");
+    header[263..265].copy_from_slice(b"00");
+    let checksum: u32 = header.iter().map(|&byte| u32::from(byte)).sum();
+    header[148..156].copy_from_slice(format!("{checksum:06o}\0 ").as_bytes());
+    tar.extend_from_slice(&header);
+    tar.extend_from_slice(bytes);
+    tar.resize(tar.len().next_multiple_of(BLOCK), 0);
+    Ok(())
+}
+
+fn parse_octal(field: &[u8]) -> Result<u64, BackupError> {
+    let text = std::str::from_utf8(field)
+        .map_err(|_| BackupError::Invalid("non-ASCII tar header".to_string()))?
+        .trim_matches(|c: char| c == '\0' || c == ' ');
+    if text.is_empty() {
+        return Ok(0);
+    }
+    u64::from_str_radix(text, 8)
+        .map_err(|_| BackupError::Invalid(format!("bad tar number {text:?}")))
+}
+
+fn parse_tar(tar: &[u8]) -> Result<Vec<(String, Vec<u8>)>, BackupError> {
+    let mut entries = Vec::new();
+    let mut offset = 0;
+    while offset + BLOCK <= tar.len() {
+        let header = &tar[offset..offset + BLOCK];
+        if header.iter().all(|&byte| byte == 0) {
+            return Ok(entries);
+        }
+        let stored = parse_octal(&header[148..156])?;
+        let computed: u64 = header
+            .iter()
+            .enumerate()
+            .map(|(index, &byte)| {
+                if (148..156).contains(&index) {
+                    u64::from(b' ')
+                } else {
+                    u64::from(byte)
+                }
+            })
+            .sum();
+        if stored != computed {
+            return Err(BackupError::Invalid(
+                "tar header checksum mismatch".to_string(),
+            ));
+        }
+        if !matches!(header[156], b'0' | 0) {
+            return Err(BackupError::Invalid(
+                "archive contains a non-regular entry".to_string(),
+            ));
+        }
+        let name_end = header[..100]
+            .iter()
+            .position(|&byte| byte == 0)
+            .unwrap_or(100);
+        let name = String::from_utf8(header[..name_end].to_vec())
+            .map_err(|_| BackupError::Invalid("non-UTF-8 entry name".to_string()))?;
+        let size = parse_octal(&header[124..136])? as usize;
+        let start = offset + BLOCK;
+        let end = start
+            .checked_add(size)
+            .filter(|&end| end <= tar.len())
+            .ok_or_else(|| BackupError::Invalid(format!("entry {name} is truncated")))?;
+        entries.push((name, tar[start..end].to_vec()));
+        offset = end.next_multiple_of(BLOCK);
+    }
+    Err(BackupError::Invalid(
+        "archive ends without a terminator".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::sign::{load_or_derive_keypair, Ed25519KeySource};
+
+    #[test]
+    fn restores_are_verified_and_all_or_nothing() {
+        let root = std::env::temp_dir().join(format!("backup_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let log_dir = root.join("node");
+        fs::create_dir_all(log_dir.join("checkpoints")).unwrap();
+        fs::write(
+            log_dir.join("checkpoints/checkpoint_1.json"),
+            b"{\"epoch\":1}",
+        )
+        .unwrap();
+        fs::write(log_dir.join("stake_registry.json"), b"{\"accounts\":{}}").unwrap();
+        fs::write(log_dir.join("native_chain_state.json"), b"{\"blocks\":[]}").unwrap();
+        fs::write(root.join("alerts.json"), b"{\"sinks\":[]}").unwrap();
+        let key = load_or_derive_keypair(&Ed25519KeySource::Seed("backup".to_string())).unwrap();
+        fs::write(root.join("node.key"), hex::encode(key.signing.to_bytes())).unwrap();
+
+        let mut sources = BackupSources {
+            log_dir: log_dir.clone(),
+            config: vec![root.join("alerts.json"), root.join("node.key")],
+            ..BackupSources::default()
+        };
+        let out = root.join("backup.tar.gz");
+        assert!(matches!(
+            create_backup(&sources, &out, &key, 5_000),
+            Err(BackupError::Refused(_))
+        ));
+        sources.config.pop();
+        let manifest = create_backup(&sources, &out, &key, 5_000).unwrap();
+        let roles: Vec<_> = manifest.files.iter().map(|file| file.role).collect();
+        assert_eq!(
+            roles,
+            [
+                BackupRole::Checkpoint,
+                BackupRole::Registry,
+                BackupRole::RpcState,
+                BackupRole::Config
+            ]
+        );
+        let public_key = encode_public_key_base64(&key.verifying);
+        assert_eq!(verify_backup(&out, Some(&public_key)).unwrap(), manifest);
+        assert!(verify_backup(&out, Some("other")).is_err());
+
+        let restored = root.join("restored");
+        let mut targets = RestoreTargets {
+            log_dir: restored.clone(),
+            ..RestoreTargets::default()
+        };
+        assert!(matches!(
+            restore_backup(&out, &targets, None, false),
+            Err(BackupError::Refused(_))
+        ));
+        assert!(!restored.exists());
+
+        let mut tar = Vec::new();
+        GzDecoder::new(fs::File::open(&out).unwrap())
+            .read_to_end(&mut tar)
+            .unwrap();
+        let mut entries = parse_tar(&tar).unwrap();
+        entries[2].1[0] ^= 1;
+        let mut tampered = Vec::new();
+        for (name, bytes) in &entries {
+            append_tar_entry(&mut tampered, name, bytes, 5).unwrap();
+        }
+        tampered.extend([0u8; 2 * BLOCK]);
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&tampered).unwrap();
+        let tampered_path = root.join("tampered.tar.gz");
+        fs::write(&tampered_path, encoder.finish().unwrap()).unwrap();
+        targets.config_dir = Some(root.join("config"));
+        assert!(matches!(
+            restore_backup(&tampered_path, &targets, None, false),
+            Err(BackupError::Invalid(_))
+        ));
+        assert!(!restored.exists());
+
+        restore_backup(&out, &targets, Some(&public_key), false).unwrap();
+        assert_eq!(
+            fs::read(restored.join("checkpoints/checkpoint_1.json")).unwrap(),
+            b"{\"epoch\":1}"
+        );
+        assert_eq!(
+            fs::read(root.join("config/alerts.json")).unwrap(),
+            b"{\"sinks\":[]}"
+        );
+        assert!(matches!(
+            restore_backup(&out, &targets, None, false),
+            Err(BackupError::Refused(_))
+        ));
+        restore_backup(&out, &targets, None, true).unwrap();
+        fs::remove_dir_all(root).unwrap();
+    }
+}
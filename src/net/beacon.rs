@@ -0,0 +1,231 @@
+#![cfg(feature = "net")]
+
+//! Per-epoch random beacon derived from finalized checkpoints.
+//!
+//! The beacon value of epoch `e` hashes the checkpoint's fold digest together
+//! with the Ed25519 signatures of the first `threshold` verified signers in
+//! public-key order.  [`derive_beacon`] enforces the assumptions the value's
+//! unpredictability and unbiasability rest on:
+//!
+//! 1. **Finality.** Only a checkpoint carrying at least `threshold` distinct
+//!    verified signatures over its anchor yields a value; callers should use
+//!    the quorum as threshold.
+//! 2. **Fixed input.** The fold digest must match the anchor's entries, so
+//!    every signer committed to the same input before any signature existed.
+//! 3. **Unique signatures.** Ed25519 signatures are deterministic (RFC 8032):
+//!    a signer cannot grind its contribution, only publish or withhold it.
+//! 4. **Canonical signer set.** Contributions are taken from the lowest public
+//!    keys, never chosen by the aggregator, and one key counts once.
+//!
+//! A value is unpredictable as long as one contributing signer is honest.
+//! Withholding remains possible: a signer among the first `threshold` keys
+//! can refuse to sign and let the next key take its place, so a coalition of
+//! `k` such signers chooses among at most `2^k` outcomes and forfeits the
+//! epoch reward each time.  Applications must commit to how they use epoch
+//! `e` before checkpoint `e` is final.
+
+use crate::julian::compute_fold_digest;
+use crate::net::checkpoint::{load_checkpoints, AnchorCheckpoint};
+use crate::net::sign::verify_signature_base64;
+use crate::LedgerAnchor;
+use blake2::digest::{consts::U32, Digest};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+type Blake2b256 = blake2::Blake2b<U32>;
+
+/// Schema identifier of a beacon value (`mfenx.powerhouse.beacon.v1`).
+pub const SCHEMA_BEACON: &str = "mfenx.powerhouse.beacon.v1";
+
+const BEACON_DOMAIN: &[u8] = b"power_house:v1:random-beacon";
+
+/// Errors produced while deriving a beacon value.
+#[derive(Debug, thiserror::Error)]
+pub enum BeaconError {
+    /// Local filesystem or encoding failure.
+    #[error("beacon I/O error: {0}")]
+    Io(String),
+    /// No checkpoint has been recorded for the epoch.
+    #[error("no checkpoint for epoch {0}")]
+    MissingCheckpoint(u64),
+    /// The threshold is zero.
+    #[error("beacon threshold must be at least 1")]
+    InvalidThreshold,
+    /// The checkpoint anchor is malformed or its fold digest is inconsistent.
+    #[error("invalid beacon checkpoint: {0}")]
+    Invalid(String),
+    /// Too few distinct signers verified over the checkpoint anchor.
+    #[error("checkpoint has {have} verified signers, threshold is {need}")]
+    InsufficientSignatures {
+        /// Distinct verified signers.
+        have: usize,
+        /// Required threshold.
+        need: usize,
+    },
+}
+
+/// Randomness published for one checkpoint epoch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BeaconValue {
+    /// Schema identifier (`mfenx.powerhouse.beacon.v1`).
+    pub schema: String,
+    /// Checkpoint epoch.
+    pub epoch: u64,
+    /// Hex fold digest of the finalized anchor.
+    pub fold_digest: String,
+    /// Signer threshold the value was derived under.
+    pub threshold: usize,
+    /// Base64 public keys of the contributing signers, in order.
+    pub signers: Vec<String>,
+    /// Hex-encoded 32-byte beacon output.
+    pub randomness: String,
+}
+
+/// Derives the beacon value of `checkpoint` from `threshold` signers.
+pub fn derive_beacon(
+    checkpoint: &AnchorCheckpoint,
+    threshold: usize,
+) -> Result<BeaconValue, BeaconError> {
+    if threshold == 0 {
+        return Err(BeaconError::InvalidThreshold);
+    }
+    let ledger = checkpoint
+        .anchor
+        .clone()
+        .into_ledger()
+        .map_err(|err| BeaconError::Invalid(err.to_string()))?;
+    let fold_digest = ledger
+        .metadata
+        .fold_digest
+        .ok_or_else(|| BeaconError::Invalid("anchor has no fold digest".to_string()))?;
+    let recomputed = compute_fold_digest(&LedgerAnchor {
+        entries: ledger.entries.clone(),
+        metadata: Default::default(),
+    });
+    if fold_digest != recomputed {
+        return Err(BeaconError::Invalid(
+            "fold digest does not match anchor entries".to_string(),
+        ));
+    }
+
+    let payload =
+        serde_json::to_vec(&checkpoint.anchor).map_err(|err| BeaconError::Io(err.to_string()))?;
+    let mut verified: BTreeMap<&str, &str> = BTreeMap::new();
+    for signature in &checkpoint.signatures {
+        if verify_signature_base64(&signature.public_key, &payload, &signature.signature).is_ok() {
+            verified
+                .entry(&signature.public_key)
+                .or_insert(&signature.signature);
+        }
+    }
+    if verified.len() < threshold {
+        return Err(BeaconError::InsufficientSignatures {
+            have: verified.len(),
+            need: threshold,
+        });
+    }
+
+    let mut hasher = Blake2b256::new();
+    hasher.update(BEACON_DOMAIN);
+    hasher.update(checkpoint.epoch.to_be_bytes());
+    hasher.update(fold_digest);
+    hasher.update((threshold as u64).to_be_bytes());
+    let mut signers = Vec::with_capacity(threshold);
+    for (public_key, signature) in verified.into_iter().take(threshold) {
+        for part in [public_key, signature] {
+            hasher.update((part.len() as u64).to_be_bytes());
+            hasher.update(part.as_bytes());
+        }
+        signers.push(public_key.to_string());
+    }
+    Ok(BeaconValue {
+        schema: SCHEMA_BEACON.to_string(),
+        epoch: checkpoint.epoch,
+        fold_digest: hex::encode(fold_digest),
+        threshold,
+        signers,
+        randomness: hex::encode(hasher.finalize()),
+    })
+}
+
+/// Derives the beacon for `epoch` from the checkpoints under `log_dir`.
+pub fn load_beacon(
+    log_dir: &Path,
+    epoch: u64,
+    threshold: usize,
+) -> Result<BeaconValue, BeaconError> {
+    let checkpoint = load_checkpoints(log_dir)
+        .map_err(|err| BeaconError::Io(err.to_string()))?
+        .into_iter()
+        .find(|checkpoint| checkpoint.epoch == epoch)
+        .ok_or(BeaconError::MissingCheckpoint(epoch))?;
+    derive_beacon(&checkpoint, threshold)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::julian_genesis_anchor;
+    use crate::net::checkpoint::CheckpointSignature;
+    use crate::net::schema::AnchorJson;
+    use crate::net::sign::{
+        encode_public_key_base64, encode_signature_base64, load_or_derive_keypair, sign_payload,
+        Ed25519KeySource,
+    };
+
+    fn signature(seed: &str, payload: &[u8]) -> CheckpointSignature {
+        let key = load_or_derive_keypair(&Ed25519KeySource::Seed(seed.to_string())).unwrap();
+        CheckpointSignature {
+            node_id: seed.to_string(),
+            public_key: encode_public_key_base64(&key.verifying),
+            signature: encode_signature_base64(&sign_payload(&key.signing, payload)),
+        }
+    }
+
+    #[test]
+    fn beacon_requires_threshold_and_ignores_signature_order() {
+        let anchor =
+            AnchorJson::from_ledger("node-a", 4, &julian_genesis_anchor(), 0, Vec::new(), None)
+                .unwrap();
+        let payload = serde_json::to_vec(&anchor).unwrap();
+        let signatures: Vec<_> = ["beacon-a", "beacon-b", "beacon-c"]
+            .iter()
+            .map(|seed| signature(seed, &payload))
+            .collect();
+        let mut with_forgery = signatures.clone();
+        with_forgery.push(signature("beacon-d", b"other anchor"));
+        let checkpoint = AnchorCheckpoint::new(4, anchor.clone(), with_forgery, None);
+        let value = derive_beacon(&checkpoint, 2).unwrap();
+        assert_eq!(value.signers.len(), 2);
+        assert!(value.signers.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(matches!(
+            derive_beacon(&checkpoint, 4),
+            Err(BeaconError::InsufficientSignatures { have: 3, need: 4 })
+        ));
+
+        let mut reversed = signatures.clone();
+        reversed.reverse();
+        reversed.push(signatures[0].clone());
+        let reordered = AnchorCheckpoint::new(4, anchor.clone(), reversed, None);
+        assert_eq!(derive_beacon(&reordered, 2).unwrap(), value);
+
+        let later = AnchorCheckpoint::new(5, anchor.clone(), signatures.clone(), None);
+        assert_ne!(
+            derive_beacon(&later, 2).unwrap().randomness,
+            value.randomness
+        );
+
+        let mut tampered = anchor;
+        tampered.fold_digest = Some(hex::encode([7u8; 32]));
+        let payload = serde_json::to_vec(&tampered).unwrap();
+        let resigned = ["beacon-a", "beacon-b"]
+            .iter()
+            .map(|seed| signature(seed, &payload))
+            .collect();
+        assert!(matches!(
+            derive_beacon(&AnchorCheckpoint::new(4, tampered, resigned, None), 1),
+            Err(BeaconError::Invalid(_))
+        ));
+    }
+}
@@ -1,6 +1,6 @@
 #![cfg(feature = "net")]
 
-use crate::net::schema::{NETWORK_ID, SCHEMA_ENVELOPE};
+use crate::net::schema::{network_id, SCHEMA_ENVELOPE};
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
@@ -52,7 +52,7 @@ impl BlobJson {
         let hash = blake2b_hex(data);
         Self {
             schema: SCHEMA_BLOB.to_string(),
-            network: NETWORK_ID.to_string(),
+            network: network_id().to_string(),
             namespace: namespace.into(),
             hash,
             size: data.len() as u64,
@@ -81,9 +81,9 @@ impl BlobJson {
                 found: self.schema.clone(),
             });
         }
-        if self.network != NETWORK_ID {
+        if self.network != network_id() {
             return Err(BlobCodecError::InvalidNetwork {
-                expected: NETWORK_ID,
+                expected: network_id(),
                 found: self.network.clone(),
             });
         }
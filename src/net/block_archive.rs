@@ -0,0 +1,199 @@
+#![cfg(feature = "net")]
+
+//! Gzip-compressed archive of pruned native-chain blocks.
+//!
+//! Validators that prune old history (see [`NativeChainState::prune_into`])
+//! move finalized blocks into segment files named
+//! `native-blocks-<first>-<last>.jsonl.gz`, one JSON block per line. Segments
+//! cover consecutive height ranges, so the wallet RPC can still answer
+//! transaction lookups for heights that left the live state.
+//!
+//! [`NativeChainState::prune_into`]: crate::net::NativeChainState::prune_into
+
+use crate::net::native_chain::FinalizedNativeBlock;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::Serialize;
+use std::{
+    fs,
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+};
+
+/// Directory name, beside the native-chain state, holding archive segments.
+pub const BLOCK_ARCHIVE_DIR: &str = "native_block_archive";
+
+const SEGMENT_PREFIX: &str = "native-blocks-";
+const SEGMENT_SUFFIX: &str = ".jsonl.gz";
+
+/// One archive file and the block heights it covers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ArchiveSegment {
+    /// Lowest block number in the segment.
+    pub first: u64,
+    /// Highest block number in the segment.
+    pub last: u64,
+    /// Location of the compressed segment.
+    pub path: PathBuf,
+}
+
+/// Contents of one segment as reported by `julian net block-archive`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SegmentSummary {
+    /// Segment location and height range.
+    #[serde(flatten)]
+    pub segment: ArchiveSegment,
+    /// Number of blocks read from the segment.
+    pub blocks: usize,
+    /// Number of transactions across those blocks.
+    pub transactions: usize,
+    /// Compressed size on disk.
+    pub bytes: u64,
+}
+
+/// Writes `blocks` (consecutive heights) as a new segment in `dir`.
+pub fn write_segment(
+    dir: &Path,
+    blocks: &[FinalizedNativeBlock],
+) -> Result<ArchiveSegment, String> {
+    let (Some(head), Some(tail)) = (blocks.first(), blocks.last()) else {
+        return Err("cannot archive an empty block range".to_string());
+    };
+    if blocks
+        .windows(2)
+        .any(|pair| pair[1].proposal.number != pair[0].proposal.number + 1)
+    {
+        return Err("archived blocks must have consecutive heights".to_string());
+    }
+    let segment = ArchiveSegment {
+        first: head.proposal.number,
+        last: tail.proposal.number,
+        path: dir.join(format!(
+            "{SEGMENT_PREFIX}{:012}-{:012}{SEGMENT_SUFFIX}",
+            head.proposal.number, tail.proposal.number
+        )),
+    };
+    fs::create_dir_all(dir).map_err(|err| format!("create {}: {err}", dir.display()))?;
+    let tmp = segment.path.with_extension("tmp");
+    let file = fs::File::create(&tmp).map_err(|err| format!("create {}: {err}", tmp.display()))?;
+    let mut encoder = GzEncoder::new(file, Compression::default());
+    for block in blocks {
+        serde_json::to_writer(&mut encoder, block).map_err(|err| err.to_string())?;
+        encoder
+            .write_all(b"\n")
+            .map_err(|err| format!("write {}: {err}", tmp.display()))?;
+    }
+    encoder
+        .finish()
+        .and_then(|file| file.sync_all())
+        .map_err(|err| format!("write {}: {err}", tmp.display()))?;
+    fs::rename(&tmp, &segment.path)
+        .map_err(|err| format!("rename {}: {err}", segment.path.display()))?;
+    Ok(segment)
+}
+
+/// Lists the segments in `dir` ordered by height; a missing directory is empty.
+pub fn list_segments(dir: &Path) -> Result<Vec<ArchiveSegment>, String> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(format!("read {}: {err}", dir.display())),
+    };
+    let mut segments = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|err| format!("read {}: {err}", dir.display()))?
+            .path();
+        let Some(range) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix(SEGMENT_PREFIX))
+            .and_then(|name| name.strip_suffix(SEGMENT_SUFFIX))
+        else {
+            continue;
+        };
+        let Some((first, last)) = range
+            .split_once('-')
+            .and_then(|(first, last)| Some((first.parse().ok()?, last.parse().ok()?)))
+        else {
+            continue;
+        };
+        segments.push(ArchiveSegment { first, last, path });
+    }
+    segments.sort_by_key(|segment| (segment.first, segment.last));
+    Ok(segments)
+}
+
+/// Reads every block of a segment.
+pub fn read_segment(path: &Path) -> Result<Vec<FinalizedNativeBlock>, String> {
+    let file = fs::File::open(path).map_err(|err| format!("open {}: {err}", path.display()))?;
+    let mut blocks = Vec::new();
+    for line in BufReader::new(GzDecoder::new(file)).lines() {
+        let line = line.map_err(|err| format!("read {}: {err}", path.display()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        blocks.push(
+            serde_json::from_str(&line)
+                .map_err(|err| format!("invalid block in {}: {err}", path.display()))?,
+        );
+    }
+    Ok(blocks)
+}
+
+/// Summarizes a segment for inspection.
+pub fn inspect_segment(segment: &ArchiveSegment) -> Result<SegmentSummary, String> {
+    let blocks = read_segment(&segment.path)?;
+    let bytes = fs::metadata(&segment.path)
+        .map_err(|err| format!("stat {}: {err}", segment.path.display()))?
+        .len();
+    Ok(SegmentSummary {
+        segment: segment.clone(),
+        blocks: blocks.len(),
+        transactions: blocks
+            .iter()
+            .map(|block| block.proposal.transactions.len())
+            .sum(),
+        bytes,
+    })
+}
+
+/// Archived block at height `number`, if a segment covers it.
+pub fn find_archived_block(
+    dir: &Path,
+    number: u64,
+) -> Result<Option<FinalizedNativeBlock>, String> {
+    for segment in list_segments(dir)?
+        .into_iter()
+        .filter(|segment| (segment.first..=segment.last).contains(&number))
+    {
+        if let Some(block) = read_segment(&segment.path)?
+            .into_iter()
+            .find(|block| block.proposal.number == number)
+        {
+            return Ok(Some(block));
+        }
+    }
+    Ok(None)
+}
+
+/// Archived block containing transaction `hash`, with the transaction index.
+///
+/// Scans segments newest first; lookups cost one segment decode each.
+pub fn find_archived_transaction(
+    dir: &Path,
+    hash: &str,
+) -> Result<Option<(FinalizedNativeBlock, usize)>, String> {
+    for segment in list_segments(dir)?.iter().rev() {
+        for block in read_segment(&segment.path)? {
+            if let Some(index) = block
+                .proposal
+                .transactions
+                .iter()
+                .position(|tx| tx.hash.eq_ignore_ascii_case(hash))
+            {
+                return Ok(Some((block, index)));
+            }
+        }
+    }
+    Ok(None)
+}
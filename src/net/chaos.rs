@@ -0,0 +1,215 @@
+#![cfg(feature = "net")]
+
+//! Fault injection for outgoing gossip.
+//!
+//! Operators rehearsing degraded networks can make a (debug build) node delay,
+//! drop or duplicate the gossip it publishes. Every injected fault is counted
+//! under `chaos_injected_total{kind=...}`, separately from organic publish
+//! failures and gossip rejects, so finality latency can be measured against a
+//! known amount of disturbance.
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{
+    collections::VecDeque,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Outgoing-gossip perturbation settings.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ChaosConfig {
+    /// Delay added to every published message, in milliseconds.
+    pub latency_ms: u64,
+    /// Probability in `[0, 1]` that a message is silently not published.
+    pub drop_rate: f64,
+    /// Probability in `[0, 1]` that a message is published twice.
+    pub duplicate_rate: f64,
+}
+
+impl ChaosConfig {
+    /// Checks that both rates are probabilities.
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, rate) in [
+            ("drop rate", self.drop_rate),
+            ("duplicate rate", self.duplicate_rate),
+        ] {
+            if !(0.0..=1.0).contains(&rate) {
+                return Err(format!("{name} must be between 0 and 1, got {rate}"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether any perturbation is configured.
+    pub fn is_active(&self) -> bool {
+        self.latency_ms > 0 || self.drop_rate > 0.0 || self.duplicate_rate > 0.0
+    }
+}
+
+/// Kind of injected fault, used as the metrics label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ChaosKind {
+    Drop,
+    Delay,
+    Duplicate,
+}
+
+impl ChaosKind {
+    pub(crate) const ALL: [Self; 3] = [Self::Drop, Self::Delay, Self::Duplicate];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Drop => "drop",
+            Self::Delay => "delay",
+            Self::Duplicate => "duplicate",
+        }
+    }
+}
+
+/// Fate of one outgoing message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ChaosVerdict {
+    /// Do not publish.
+    Drop,
+    /// Publish once `due` has passed, twice if `duplicate`.
+    Publish { due: Instant, duplicate: bool },
+}
+
+/// A message held back by injected latency.
+pub(crate) struct DelayedMessage<T> {
+    pub(crate) due: Instant,
+    pub(crate) topic: T,
+    pub(crate) data: Vec<u8>,
+    pub(crate) duplicate: bool,
+}
+
+/// Applies a [`ChaosConfig`] to outgoing messages and holds delayed ones.
+pub(crate) struct ChaosInjector<T> {
+    config: ChaosConfig,
+    rng: Mutex<StdRng>,
+    delayed: Mutex<VecDeque<DelayedMessage<T>>>,
+}
+
+impl<T> ChaosInjector<T> {
+    pub(crate) fn new(config: ChaosConfig) -> Self {
+        Self::with_rng(config, StdRng::from_entropy())
+    }
+
+    fn with_rng(config: ChaosConfig, rng: StdRng) -> Self {
+        Self {
+            config,
+            rng: Mutex::new(rng),
+            delayed: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub(crate) fn config(&self) -> ChaosConfig {
+        self.config
+    }
+
+    /// Decides what happens to a message published at `now`.
+    pub(crate) fn verdict(&self, now: Instant) -> ChaosVerdict {
+        let mut rng = self.rng.lock().expect("chaos rng lock");
+        if self.config.drop_rate > 0.0 && rng.gen_bool(self.config.drop_rate) {
+            return ChaosVerdict::Drop;
+        }
+        let duplicate =
+            self.config.duplicate_rate > 0.0 && rng.gen_bool(self.config.duplicate_rate);
+        ChaosVerdict::Publish {
+            due: now + Duration::from_millis(self.config.latency_ms),
+            duplicate,
+        }
+    }
+
+    /// Holds a message until its due time.
+    pub(crate) fn delay(&self, message: DelayedMessage<T>) {
+        self.delayed
+            .lock()
+            .expect("chaos queue lock")
+            .push_back(message);
+    }
+
+    /// Removes and returns the held messages that are due at `now`.
+    ///
+    /// Every message carries the same latency, so the queue is already in due
+    /// order.
+    pub(crate) fn take_due(&self, now: Instant) -> Vec<DelayedMessage<T>> {
+        let mut delayed = self.delayed.lock().expect("chaos queue lock");
+        let ready = delayed
+            .iter()
+            .take_while(|message| message.due <= now)
+            .count();
+        delayed.drain(..ready).collect()
+    }
+}
+
+/// Distinct payload carrying the same JSON document.
+///
+/// Gossip message ids are content hashes, so an injected duplicate needs
+/// different bytes to leave this node; trailing whitespace keeps it decodable
+/// and lets receivers' payload caches see it as a duplicate.
+pub(crate) fn duplicate_payload(data: &[u8]) -> Vec<u8> {
+    let mut copy = data.to_vec();
+    copy.push(b'\n');
+    copy
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verdicts_follow_rates_and_delayed_messages_release_in_order() {
+        assert!(ChaosConfig {
+            drop_rate: 1.5,
+            ..ChaosConfig::default()
+        }
+        .validate()
+        .is_err());
+        assert!(!ChaosConfig::default().is_active());
+
+        let now = Instant::now();
+        let dropping = ChaosInjector::<()>::with_rng(
+            ChaosConfig {
+                drop_rate: 1.0,
+                ..ChaosConfig::default()
+            },
+            StdRng::seed_from_u64(7),
+        );
+        assert_eq!(dropping.verdict(now), ChaosVerdict::Drop);
+
+        let slow = ChaosInjector::with_rng(
+            ChaosConfig {
+                latency_ms: 50,
+                duplicate_rate: 1.0,
+                ..ChaosConfig::default()
+            },
+            StdRng::seed_from_u64(7),
+        );
+        let ChaosVerdict::Publish { due, duplicate } = slow.verdict(now) else {
+            panic!("latency-only config must not drop");
+        };
+        assert_eq!(due, now + Duration::from_millis(50));
+        assert!(duplicate);
+        for (offset, topic) in [(0, "a"), (10, "b")] {
+            slow.delay(DelayedMessage {
+                due: due + Duration::from_millis(offset),
+                topic,
+                data: b"{}".to_vec(),
+                duplicate,
+            });
+        }
+        assert!(slow.take_due(now).is_empty());
+        let first = slow.take_due(due);
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].topic, "a");
+        assert_eq!(slow.take_due(due + Duration::from_secs(1))[0].topic, "b");
+
+        let copy = duplicate_payload(b"{\"a\":1}");
+        assert_ne!(copy, b"{\"a\":1}");
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&copy).unwrap(),
+            serde_json::json!({"a": 1})
+        );
+    }
+}
@@ -0,0 +1,201 @@
+#![cfg(feature = "net")]
+
+//! Signed receipts proving that an offline check was run.
+//!
+//! `julian node reconcile`, `net verify-envelope`, and `migration
+//! verify-state` accept `--sign-receipt <file>`, which records the command,
+//! SHA-256 digests of its inputs, the verdict, and the time in a
+//! [`CheckReceipt`] signed by the operator's node key. `julian receipt verify`
+//! checks the signature later and can re-digest the inputs to show that the
+//! receipt covers the same files.
+
+use crate::net::sign::{
+    encode_public_key_base64, encode_signature_base64, sign_payload, verify_signature_base64,
+    KeyMaterial,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeMap, fs, path::Path};
+
+/// Schema identifier of a check receipt.
+pub const SCHEMA_CHECK_RECEIPT: &str = "mfenx.powerhouse.check_receipt.v1";
+
+const CHECK_RECEIPT_DOMAIN: &[u8] = b"MFENX_CHECK_RECEIPT";
+
+/// Signed record of one check and its verdict.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckReceipt {
+    /// Schema identifier (`mfenx.powerhouse.check_receipt.v1`).
+    pub schema: String,
+    /// Command that ran, e.g. `node reconcile`.
+    pub command: String,
+    /// Named inputs: `sha256:<hex>` digests for files and directories, or
+    /// literal parameter values.
+    pub inputs: BTreeMap<String, String>,
+    /// Whether the check passed.
+    pub passed: bool,
+    /// Summary on success, or the failure reason.
+    pub result: String,
+    /// Millisecond timestamp when the check finished.
+    pub issued_ms: u64,
+    /// Node identifier of the signer, when one was given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_id: Option<String>,
+    /// Base64 ed25519 key of the signer.
+    pub public_key: String,
+    /// Base64 signature over [`CheckReceipt::signing_bytes`].
+    pub signature: String,
+}
+
+impl CheckReceipt {
+    /// Signs a receipt for `command` with verdict `outcome`.
+    pub fn sign(
+        command: &str,
+        inputs: BTreeMap<String, String>,
+        outcome: &Result<String, String>,
+        issued_ms: u64,
+        node_id: Option<String>,
+        key: &KeyMaterial,
+    ) -> Self {
+        let (passed, result) = match outcome {
+            Ok(summary) => (true, summary.clone()),
+            Err(reason) => (false, reason.clone()),
+        };
+        let mut receipt = Self {
+            schema: SCHEMA_CHECK_RECEIPT.to_string(),
+            command: command.to_string(),
+            inputs,
+            passed,
+            result,
+            issued_ms,
+            node_id,
+            public_key: encode_public_key_base64(&key.verifying),
+            signature: String::new(),
+        };
+        let signature = sign_payload(&key.signing, &receipt.signing_bytes());
+        receipt.signature = encode_signature_base64(&signature);
+        receipt
+    }
+
+    /// Domain-separated, length-prefixed bytes covered by the signature.
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        let mut out = CHECK_RECEIPT_DOMAIN.to_vec();
+        let mut push = |field: &str| {
+            out.extend_from_slice(&(field.len() as u64).to_be_bytes());
+            out.extend_from_slice(field.as_bytes());
+        };
+        push(&self.schema);
+        push(&self.command);
+        push(&self.inputs.len().to_string());
+        for (name, value) in &self.inputs {
+            push(name);
+            push(value);
+        }
+        push(&self.result);
+        push(self.node_id.as_deref().unwrap_or_default());
+        push(&self.public_key);
+        out.push(u8::from(self.passed));
+        out.extend_from_slice(&self.issued_ms.to_be_bytes());
+        out
+    }
+
+    /// Checks the schema and signature, and the signer against `trusted_keys`
+    /// when that list is non-empty.
+    pub fn verify(&self, trusted_keys: &[String]) -> Result<(), String> {
+        if self.schema != SCHEMA_CHECK_RECEIPT {
+            return Err(format!("unexpected schema {}", self.schema));
+        }
+        if !trusted_keys.is_empty() && !trusted_keys.contains(&self.public_key) {
+            return Err(format!("signer {} is not trusted", self.public_key));
+        }
+        verify_signature_base64(&self.public_key, &self.signing_bytes(), &self.signature)
+            .map_err(|err| err.to_string())
+    }
+}
+
+/// `sha256:<hex>` digest of a file, or of a directory tree.
+///
+/// Directory digests cover every regular file's relative path and contents in
+/// sorted order, so they change when any file is added, removed, or edited.
+pub fn digest_input_path(path: &Path) -> Result<String, String> {
+    let mut hasher = Sha256::new();
+    if path.is_dir() {
+        let mut files = Vec::new();
+        collect_files(path, path, &mut files)?;
+        files.sort();
+        for relative in files {
+            let contents = fs::read(path.join(&relative))
+                .map_err(|err| format!("read {}: {err}", path.join(&relative).display()))?;
+            hasher.update((relative.len() as u64).to_be_bytes());
+            hasher.update(relative.as_bytes());
+            hasher.update((contents.len() as u64).to_be_bytes());
+            hasher.update(&contents);
+        }
+    } else {
+        hasher.update(fs::read(path).map_err(|err| format!("read {}: {err}", path.display()))?);
+    }
+    Ok(format!("sha256:{}", hex::encode(hasher.finalize())))
+}
+
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<String>) -> Result<(), String> {
+    for entry in fs::read_dir(dir).map_err(|err| format!("read {}: {err}", dir.display()))? {
+        let path = entry
+            .map_err(|err| format!("read {}: {err}", dir.display()))?
+            .path();
+        if path.is_dir() {
+            collect_files(root, &path, files)?;
+        } else if path.is_file() {
+            let relative = path.strip_prefix(root).expect("path is under root");
+            files.push(relative.to_string_lossy().replace('\\', "/"));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::{load_or_derive_keypair, Ed25519KeySource};
+
+    #[test]
+    fn receipt_signature_covers_inputs_and_verdict() {
+        let dir = std::env::temp_dir().join(format!("check_receipt_{}", std::process::id()));
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        fs::write(dir.join("a.log"), "one").unwrap();
+        let before = digest_input_path(&dir).unwrap();
+        fs::write(dir.join("nested/b.log"), "two").unwrap();
+        let after = digest_input_path(&dir).unwrap();
+        assert_ne!(before, after);
+        assert_eq!(
+            digest_input_path(&dir.join("a.log")).unwrap(),
+            format!("sha256:{}", hex::encode(Sha256::digest(b"one")))
+        );
+
+        let key = load_or_derive_keypair(&Ed25519KeySource::Seed("auditor".to_string())).unwrap();
+        let inputs = BTreeMap::from([
+            ("log_dir".to_string(), after),
+            ("quorum".to_string(), "2".to_string()),
+        ]);
+        let receipt = CheckReceipt::sign(
+            "node reconcile",
+            inputs,
+            &Ok("finality reached".to_string()),
+            7,
+            Some("node-a".to_string()),
+            &key,
+        );
+        receipt.verify(&[]).unwrap();
+        receipt
+            .verify(std::slice::from_ref(&receipt.public_key))
+            .unwrap();
+        assert!(receipt.verify(&["someone-else".to_string()]).is_err());
+
+        let mut forged = receipt.clone();
+        forged.passed = false;
+        assert!(forged.verify(&[]).is_err());
+        let mut forged = receipt;
+        forged.inputs.insert("quorum".to_string(), "1".to_string());
+        assert!(forged.verify(&[]).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
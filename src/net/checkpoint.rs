@@ -1,12 +1,18 @@
 #![cfg(feature = "net")]
 
-use crate::net::schema::AnchorJson;
-use crate::{merkle_root, LedgerAnchor};
+use crate::julian::anchor_digest;
+use crate::net::native_chain::NativeChainState;
+use crate::net::rollup_store::RollupCommitmentStore;
+use crate::net::schema::{network_id, network_topic, AnchorJson};
+use crate::net::stake_registry::EpochFees;
+use crate::{is_ledger_log_file, latest_journal_record, merkle_root, LedgerAnchor};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 
 const CHECKPOINT_SCHEMA: &str = "mfenx.powerhouse.checkpoint.v1";
+/// Prefix of the Kademlia provider key advertising a checkpoint digest.
+pub const CHECKPOINT_PROVIDER_PREFIX: &str = "mfenx/powerhouse/checkpoint/v1/";
 
 /// Serialized snapshot describing a quorum-approved anchor state.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +27,18 @@ pub struct AnchorCheckpoint {
     pub signatures: Vec<CheckpointSignature>,
     /// Optional highest ledger log filename included in the snapshot.
     pub log_cutoff: Option<String>,
+    /// Optional hex sparse Merkle root of the stake registry at this epoch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry_root: Option<String>,
+    /// Optional hex digest of the wallet RPC state at this epoch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpc_state_root: Option<String>,
+    /// Optional hex Merkle root of the rollup commitment store at this epoch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rollup_root: Option<String>,
+    /// Optional native fees burned and sunk since the previous checkpoint.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fees: Option<EpochFees>,
 }
 
 /// Signature material contributed by a validator in the checkpoint set.
@@ -48,21 +66,135 @@ impl AnchorCheckpoint {
             anchor,
             signatures,
             log_cutoff,
+            registry_root: None,
+            rpc_state_root: None,
+            rollup_root: None,
+            fees: None,
         }
     }
 
+    /// Records the stake registry state root (see
+    /// [`crate::net::StakeRegistry::state_root`]).
+    ///
+    /// The embedded anchor must carry the same root, if it carries one.
+    pub fn with_registry_root(mut self, root: [u8; 32]) -> Self {
+        self.registry_root = Some(hex::encode(root));
+        self
+    }
+
+    /// Records the wallet RPC state digest (see
+    /// [`NativeChainState::rpc_state_digest`]).
+    pub fn with_rpc_state_root(mut self, root: [u8; 32]) -> Self {
+        self.rpc_state_root = Some(hex::encode(root));
+        self
+    }
+
+    /// Records the rollup commitment store root (see
+    /// [`RollupCommitmentStore::root`]).
+    pub fn with_rollup_root(mut self, root: [u8; 32]) -> Self {
+        self.rollup_root = Some(hex::encode(root));
+        self
+    }
+
+    /// Records the fees the stake registry journalled during the epoch (see
+    /// [`crate::net::SupplyLedger::fees_in_epoch`]).
+    pub fn with_fees(mut self, fees: EpochFees) -> Self {
+        self.fees = Some(fees);
+        self
+    }
+
+    /// Confirms that `store` is the rollup commitment store committed by this
+    /// checkpoint.
+    pub fn verify_rollup_store(
+        &self,
+        store: &RollupCommitmentStore,
+    ) -> Result<(), CheckpointError> {
+        let Some(root) = &self.rollup_root else {
+            return Err(CheckpointError::RollupStore(format!(
+                "epoch {} commits no rollup store",
+                self.epoch
+            )));
+        };
+        let computed = store.root().map(hex::encode).unwrap_or_default();
+        if *root != computed {
+            return Err(CheckpointError::RollupStore(format!(
+                "store root {computed} differs from committed {root}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Confirms that `state` is the RPC state committed by this checkpoint.
+    pub fn verify_rpc_state(&self, state: &NativeChainState) -> Result<(), CheckpointError> {
+        let Some(root) = &self.rpc_state_root else {
+            return Err(CheckpointError::RpcState(format!(
+                "epoch {} commits no RPC state",
+                self.epoch
+            )));
+        };
+        let computed = hex::encode(state.rpc_state_digest());
+        if *root != computed {
+            return Err(CheckpointError::RpcState(format!(
+                "state digest {computed} differs from committed {root}"
+            )));
+        }
+        Ok(())
+    }
+
     /// Converts the checkpoint back into a ledger anchor plus optional log cutoff marker.
     pub fn into_ledger(self) -> Result<(LedgerAnchor, Option<String>), CheckpointError> {
         if self.schema != CHECKPOINT_SCHEMA {
             return Err(CheckpointError::InvalidSchema(self.schema));
         }
+        if self.anchor.network != network_id() {
+            return Err(CheckpointError::WrongNetwork(self.anchor.network));
+        }
         let ledger = self
             .anchor
             .clone()
             .into_ledger()
             .map_err(|err| CheckpointError::InvalidAnchor(err.to_string()))?;
+        if let (Some(root), Some(anchored)) = (&self.registry_root, &ledger.metadata.registry_root)
+        {
+            if *root != hex::encode(anchored) {
+                return Err(CheckpointError::InvalidAnchor(format!(
+                    "registry root {root} differs from anchored {}",
+                    hex::encode(anchored)
+                )));
+            }
+        }
         Ok((ledger, self.log_cutoff))
     }
+
+    /// Returns the anchor digest identifying this checkpoint on the DHT.
+    pub fn digest(&self) -> Result<[u8; 32], CheckpointError> {
+        let ledger = self
+            .anchor
+            .clone()
+            .into_ledger()
+            .map_err(|err| CheckpointError::InvalidAnchor(err.to_string()))?;
+        Ok(anchor_digest(&ledger))
+    }
+}
+
+/// Provider key prefix of the active network.
+fn network_provider_prefix() -> String {
+    format!(
+        "{}/",
+        network_topic(CHECKPOINT_PROVIDER_PREFIX.trim_end_matches('/'))
+    )
+}
+
+/// Kademlia provider key under which holders of a checkpoint advertise themselves.
+pub fn checkpoint_provider_key(digest: &[u8; 32]) -> Vec<u8> {
+    format!("{}{}", network_provider_prefix(), hex::encode(digest)).into_bytes()
+}
+
+/// Recovers the checkpoint digest from a provider key produced by [`checkpoint_provider_key`].
+pub fn parse_checkpoint_provider_key(key: &[u8]) -> Option<[u8; 32]> {
+    let text = std::str::from_utf8(key).ok()?;
+    let hex_digest = text.strip_prefix(network_provider_prefix().as_str())?;
+    hex::decode(hex_digest).ok()?.try_into().ok()
 }
 
 /// Errors that may occur while handling checkpoints.
@@ -74,6 +206,12 @@ pub enum CheckpointError {
     InvalidSchema(String),
     /// The embedded anchor failed validation.
     InvalidAnchor(String),
+    /// The checkpoint belongs to another network.
+    WrongNetwork(String),
+    /// A wallet RPC state does not match the committed digest.
+    RpcState(String),
+    /// A rollup commitment store does not match the committed root.
+    RollupStore(String),
 }
 
 impl std::fmt::Display for CheckpointError {
@@ -82,6 +220,9 @@ impl std::fmt::Display for CheckpointError {
             Self::Io(err) => write!(f, "checkpoint I/O error: {err}"),
             Self::InvalidSchema(schema) => write!(f, "invalid checkpoint schema: {schema}"),
             Self::InvalidAnchor(err) => write!(f, "invalid checkpoint anchor: {err}"),
+            Self::WrongNetwork(network) => write!(f, "checkpoint from network {network}"),
+            Self::RpcState(err) => write!(f, "RPC state mismatch: {err}"),
+            Self::RollupStore(err) => write!(f, "rollup store mismatch: {err}"),
         }
     }
 }
@@ -105,61 +246,67 @@ pub fn write_checkpoint(
 
 /// Returns the checkpoint with the highest epoch if one exists.
 pub fn load_latest_checkpoint(dir: &Path) -> Result<Option<AnchorCheckpoint>, CheckpointError> {
-    let path = dir.join("checkpoints");
-    let entries = match fs::read_dir(&path) {
+    match checkpoint_paths(dir).pop() {
+        Some((_, path)) => read_checkpoint(&path).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Returns every checkpoint stored under `dir/checkpoints`, ordered by epoch.
+pub fn load_checkpoints(dir: &Path) -> Result<Vec<AnchorCheckpoint>, CheckpointError> {
+    checkpoint_paths(dir)
+        .iter()
+        .map(|(_, path)| read_checkpoint(path))
+        .collect()
+}
+
+fn checkpoint_paths(dir: &Path) -> Vec<(u64, PathBuf)> {
+    let entries = match fs::read_dir(dir.join("checkpoints")) {
         Ok(entries) => entries,
-        Err(_) => return Ok(None),
+        Err(_) => return Vec::new(),
     };
-    let mut best: Option<(u64, PathBuf)> = None;
-    for entry in entries.flatten() {
-        let path = entry.path();
-        if !path.is_file() {
-            continue;
-        }
-        if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
-            if let Some(epoch_str) = stem.strip_prefix("checkpoint_") {
-                if let Ok(epoch) = epoch_str.parse::<u64>() {
-                    if best
-                        .as_ref()
-                        .map(|(best_epoch, _)| epoch > *best_epoch)
-                        .unwrap_or(true)
-                    {
-                        best = Some((epoch, path.clone()));
-                    }
-                }
-            }
-        }
-    }
-    if let Some((_, path)) = best {
-        let contents =
-            fs::read_to_string(&path).map_err(|err| CheckpointError::Io(err.to_string()))?;
-        let checkpoint: AnchorCheckpoint =
-            serde_json::from_str(&contents).map_err(|err| CheckpointError::Io(err.to_string()))?;
-        Ok(Some(checkpoint))
-    } else {
-        Ok(None)
-    }
+    let mut paths: Vec<(u64, PathBuf)> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter_map(|path| {
+            let epoch = path
+                .file_stem()
+                .and_then(|s| s.to_str())?
+                .strip_prefix("checkpoint_")?
+                .parse::<u64>()
+                .ok()?;
+            Some((epoch, path))
+        })
+        .collect();
+    paths.sort();
+    paths
+}
+
+fn read_checkpoint(path: &Path) -> Result<AnchorCheckpoint, CheckpointError> {
+    let contents = fs::read_to_string(path).map_err(|err| CheckpointError::Io(err.to_string()))?;
+    serde_json::from_str(&contents).map_err(|err| CheckpointError::Io(err.to_string()))
 }
 
-/// Determines the lexicographically greatest `ledger_*.txt` file in `log_dir`.
+/// Determines the lexicographically greatest `ledger_*` file or journal
+/// record name in `log_dir`.
 pub fn latest_log_cutoff(log_dir: &Path) -> Option<String> {
     let mut best: Option<String> = None;
     if let Ok(entries) = fs::read_dir(log_dir) {
         for entry in entries.flatten() {
             let path = entry.path();
-            if !path.is_file() {
+            if !path.is_file() || !is_ledger_log_file(&path) {
                 continue;
             }
             if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                if name.starts_with("ledger_")
-                    && best.as_ref().map(|b| name > b.as_str()).unwrap_or(true)
-                {
+                if best.as_ref().map(|b| name > b.as_str()).unwrap_or(true) {
                     best = Some(name.to_string());
                 }
             }
         }
     }
-    best
+    // Journal record names sort after every legacy file name.
+    latest_journal_record(log_dir).or(best)
 }
 
 /// Computes a tree hash across all transcript digests in `anchor`.
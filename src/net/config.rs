@@ -0,0 +1,1025 @@
+#![cfg(feature = "net")]
+
+//! Typed builders for [`NetConfig`] and [`EvmRpcConfig`], and the config file
+//! read by `julian net start --config` and `julian net rpc-replica --config`.
+//!
+//! The builders take the few values a node cannot run without, default the
+//! rest, and check the combination in `build()`: quorums of at least one,
+//! listeners with a concrete port that do not collide, and intervals between
+//! [`MIN_INTERVAL`] and [`MAX_INTERVAL`].
+//!
+//! Config files are written in a subset of TOML: a `[net]` table for the node
+//! and an `[rpc]` table for replicas, each holding `key = value` lines.  A
+//! value is a quoted string, an integer, a boolean, or a one-line array of
+//! those; `#` starts a comment.  Unknown keys are rejected so that a typo does
+//! not silently fall back to a default.
+//!
+//! ```toml
+//! [net]
+//! node_id = "validator-1"
+//! listen = "/ip4/0.0.0.0/tcp/7001"
+//! log_dir = "/var/lib/powerhouse/logs"
+//! quorum = 2
+//! broadcast_interval_ms = 5_000
+//! rpc_tiers = ["expensive=4:8"]
+//! ```
+
+use crate::net::fork::SafeMode;
+use crate::net::governance::{MembershipPolicy, StaticPolicy};
+use crate::net::native_chain::{NativeChainCommand, SharedNativeChainState};
+use crate::net::parameters::SharedParameters;
+use crate::net::replica::ReplicaMode;
+use crate::net::rpc::{
+    ConnectionLimiter, CorsPolicy, EvmRpcConfig, TxInputHandlers, DEFAULT_GAS_MARGIN_PERCENT,
+};
+use crate::net::rpc_tiers::{RpcTier, RpcTierLimits, RpcTiers};
+use crate::net::sign::KeyMaterial;
+use crate::net::swarm::NetConfig;
+use libp2p::{multiaddr::Protocol, Multiaddr};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::{
+    fs,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+use tokio::sync::mpsc;
+
+/// Shortest broadcast, BFT round, block, or request interval accepted.
+pub const MIN_INTERVAL: Duration = Duration::from_millis(100);
+/// Longest broadcast, BFT round, block, or request interval accepted.
+pub const MAX_INTERVAL: Duration = Duration::from_secs(3_600);
+/// Largest `eth_estimateGas` safety margin accepted, in percent.
+pub const MAX_GAS_MARGIN_PERCENT: u64 = 100;
+
+const DEFAULT_BROADCAST_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Errors produced while reading a config file or validating a builder.
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    /// The config file could not be read.
+    #[error("config I/O error: {0}")]
+    Io(String),
+    /// The file is not in the supported TOML subset.
+    #[error("config line {line}: {message}")]
+    Syntax {
+        /// One-based line number.
+        line: usize,
+        /// What was wrong with the line.
+        message: String,
+    },
+    /// A key is unknown, has the wrong type, or fails validation.
+    #[error("invalid config: {0}")]
+    Invalid(String),
+}
+
+fn invalid(message: impl Into<String>) -> ConfigError {
+    ConfigError::Invalid(message.into())
+}
+
+/// Contents of a config file.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigFile {
+    /// `[net]`: settings of `julian net start`.
+    #[serde(default)]
+    pub net: NetSettings,
+    /// `[rpc]`: settings of `julian net rpc-replica`.
+    #[serde(default)]
+    pub rpc: RpcSettings,
+}
+
+impl ConfigFile {
+    /// Parses config file text.
+    pub fn from_toml(text: &str) -> Result<Self, ConfigError> {
+        serde_json::from_value(Value::Object(parse_toml(text)?))
+            .map_err(|err| invalid(err.to_string()))
+    }
+
+    /// Reads and parses the config file at `path`.
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let text = fs::read_to_string(path)
+            .map_err(|err| ConfigError::Io(format!("{}: {err}", path.display())))?;
+        Self::from_toml(&text)
+    }
+}
+
+/// `[net]` table; keys mirror the `julian net start` flags.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetSettings {
+    /// Node identifier (`--node-id`).
+    pub node_id: Option<String>,
+    /// Libp2p listen multiaddr (`--listen`).
+    pub listen: Option<String>,
+    /// Bootstrap multiaddrs (`--bootstrap`).
+    #[serde(default)]
+    pub bootstraps: Vec<String>,
+    /// Transcript log directory (`--log-dir`).
+    pub log_dir: Option<PathBuf>,
+    /// Key spec (`--key`): `ed25519://<seed>` or a key file.  Loaded by the
+    /// caller, since identities may need a passphrase.
+    pub key: Option<String>,
+    /// Anchor finality quorum (`--quorum`).
+    pub quorum: Option<usize>,
+    /// Blob attestation quorum (`--attestation-quorum`).
+    pub attestation_quorum: Option<usize>,
+    /// Anchor broadcast interval in milliseconds (`--broadcast-interval`).
+    pub broadcast_interval_ms: Option<u64>,
+    /// Checkpoint interval in broadcasts (`--checkpoint-interval`).
+    pub checkpoint_interval: Option<u64>,
+    /// Anchor gossip topic (`--anchor-topic`).
+    pub anchor_topic: Option<String>,
+    /// Additional bridge topics (`--gossip-bridge-topics`).
+    #[serde(default)]
+    pub bridge_topics: Vec<String>,
+    /// BFT finality rounds (`--bft`).
+    pub bft: Option<bool>,
+    /// BFT round duration in milliseconds (`--bft-round-ms`).
+    pub bft_round_ms: Option<u64>,
+    /// Prometheus listener (`--metrics`).
+    pub metrics: Option<SocketAddr>,
+    /// Blob data directory (`--blob-dir`).
+    pub blob_dir: Option<PathBuf>,
+    /// Blob HTTP listener (`--blob-listen`).
+    pub blob_listen: Option<SocketAddr>,
+    /// Wallet JSON-RPC listener (`--evm-rpc-listen`).
+    pub evm_rpc_listen: Option<SocketAddr>,
+    /// Native chain ID (`--evm-chain-id`).
+    pub evm_chain_id: Option<u64>,
+    /// Idle seconds before an empty block is sealed (`--native-max-block-interval`).
+    pub native_max_block_interval_secs: Option<u64>,
+    /// Finalized blocks kept in live state (`--native-prune-keep`).
+    pub native_prune_keep: Option<u64>,
+    /// Epochs before never-finalized entries are archived (`--entry-gc-epochs`).
+    pub entry_gc_epochs: Option<u64>,
+    /// Seconds a native transaction may stay pending (`--native-pending-ttl`).
+    pub native_pending_ttl_secs: Option<u64>,
+    /// Pending native transactions held at once (`--native-max-pending`).
+    pub native_max_pending: Option<usize>,
+    /// Concurrent metrics and RPC connections (`--max-http-connections`).
+    pub max_http_connections: Option<usize>,
+    /// Anchors accepted per identity per minute (`--max-anchors-per-minute`).
+    pub max_anchors_per_minute: Option<u32>,
+    /// `<tier>=<concurrency>:<queue>` overrides (`--rpc-tier`).
+    #[serde(default)]
+    pub rpc_tiers: Vec<String>,
+    /// `eth_estimateGas` safety margin in percent (`--evm-gas-margin`).
+    pub evm_gas_margin_percent: Option<u64>,
+}
+
+/// `[rpc]` table; keys mirror the `julian net rpc-replica` flags.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RpcSettings {
+    /// JSON-RPC listener (`--rpc-listen`).
+    pub listen: Option<SocketAddr>,
+    /// Native chain state followed by the replica (`--state`).
+    pub state: Option<PathBuf>,
+    /// Primary RPC named in write refusals (`--primary-rpc`).
+    pub primary_rpc: Option<String>,
+    /// Stake registry behind the `asset_*` methods (`--registry`).
+    pub registry: Option<PathBuf>,
+    /// Log directory behind `beacon_get` (`--log-dir`).
+    pub log_dir: Option<PathBuf>,
+    /// State poll interval in milliseconds (`--poll-ms`).
+    pub poll_ms: Option<u64>,
+    /// Request read and acceptance timeout in milliseconds.
+    pub request_timeout_ms: Option<u64>,
+    /// Minimum signer threshold for beacon values.
+    pub beacon_threshold: Option<usize>,
+    /// `<tier>=<concurrency>:<queue>` overrides (`--rpc-tier`).
+    #[serde(default)]
+    pub tiers: Vec<String>,
+    /// `eth_estimateGas` safety margin in percent (`--gas-margin`).
+    pub gas_margin_percent: Option<u64>,
+}
+
+fn check_quorum(name: &str, value: usize) -> Result<(), ConfigError> {
+    if value == 0 {
+        return Err(invalid(format!("{name} must be at least 1")));
+    }
+    Ok(())
+}
+
+fn check_interval(name: &str, value: Duration) -> Result<(), ConfigError> {
+    if !(MIN_INTERVAL..=MAX_INTERVAL).contains(&value) {
+        return Err(invalid(format!(
+            "{name} of {value:?} is outside {MIN_INTERVAL:?}..={MAX_INTERVAL:?}"
+        )));
+    }
+    Ok(())
+}
+
+fn check_gas_margin(percent: u64) -> Result<(), ConfigError> {
+    if percent > MAX_GAS_MARGIN_PERCENT {
+        return Err(invalid(format!(
+            "gas margin of {percent}% exceeds {MAX_GAS_MARGIN_PERCENT}%"
+        )));
+    }
+    Ok(())
+}
+
+fn check_listener(name: &str, addr: SocketAddr) -> Result<(), ConfigError> {
+    if addr.port() == 0 {
+        return Err(invalid(format!("{name} {addr} needs a fixed port")));
+    }
+    Ok(())
+}
+
+/// Requires an IP or DNS host followed by a TCP or UDP port.
+fn check_multiaddr(name: &str, addr: &Multiaddr) -> Result<(), ConfigError> {
+    let mut protocols = addr.iter();
+    let host = matches!(
+        protocols.next(),
+        Some(
+            Protocol::Ip4(_)
+                | Protocol::Ip6(_)
+                | Protocol::Dns(_)
+                | Protocol::Dns4(_)
+                | Protocol::Dns6(_)
+        )
+    );
+    let port = matches!(protocols.next(), Some(Protocol::Tcp(_) | Protocol::Udp(_)));
+    if !host || !port {
+        return Err(invalid(format!(
+            "{name} {addr} must start with an ip4, ip6, or dns host and a tcp or udp port"
+        )));
+    }
+    Ok(())
+}
+
+fn parse_multiaddr(name: &str, raw: &str) -> Result<Multiaddr, ConfigError> {
+    let addr = raw
+        .parse()
+        .map_err(|err| invalid(format!("{name} {raw:?}: {err}")))?;
+    check_multiaddr(name, &addr)?;
+    Ok(addr)
+}
+
+fn check_tier_limits(limits: &RpcTierLimits) -> Result<(), ConfigError> {
+    for tier in RpcTier::ALL {
+        if limits.get(tier).concurrency == 0 {
+            return Err(invalid(format!(
+                "{} tier concurrency must be at least 1",
+                tier.label()
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn apply_tier_specs(limits: &mut RpcTierLimits, specs: &[String]) -> Result<(), ConfigError> {
+    for spec in specs {
+        limits.apply_spec(spec).map_err(invalid)?;
+    }
+    Ok(())
+}
+
+/// Validating builder for [`NetConfig`]; see [`NetConfig::builder`].
+pub struct NetConfigBuilder {
+    node_id: String,
+    listen_addr: Multiaddr,
+    log_dir: PathBuf,
+    key_material: KeyMaterial,
+    bootstraps: Vec<Multiaddr>,
+    quorum: usize,
+    attestation_quorum: Option<usize>,
+    broadcast_interval: Duration,
+    checkpoint_interval: Option<u64>,
+    anchor_topic: Option<String>,
+    bridge_topics: Option<Vec<String>>,
+    bft_enabled: bool,
+    bft_round: Option<Duration>,
+    metrics_addr: Option<SocketAddr>,
+    membership_policy: Option<Arc<dyn MembershipPolicy>>,
+    blob_dir: Option<PathBuf>,
+    blob_listen: Option<SocketAddr>,
+    evm_rpc_listen: Option<SocketAddr>,
+    evm_chain_id: Option<u64>,
+    native_max_block_interval: Option<Duration>,
+    native_prune_keep: Option<u64>,
+    entry_gc_epochs: Option<u64>,
+    native_pending_ttl: Option<Duration>,
+    native_max_pending: Option<usize>,
+    max_http_connections: Option<usize>,
+    max_anchors_per_minute: Option<u32>,
+    rpc_tier_limits: RpcTierLimits,
+    evm_gas_margin_percent: u64,
+}
+
+impl NetConfigBuilder {
+    /// Builder with the required identity, listener, and log directory; every
+    /// other setting starts at the `julian net start` default.
+    pub fn new(
+        node_id: impl Into<String>,
+        listen_addr: Multiaddr,
+        log_dir: impl Into<PathBuf>,
+        key_material: KeyMaterial,
+    ) -> Self {
+        Self {
+            node_id: node_id.into(),
+            listen_addr,
+            log_dir: log_dir.into(),
+            key_material,
+            bootstraps: Vec::new(),
+            quorum: 1,
+            attestation_quorum: None,
+            broadcast_interval: DEFAULT_BROADCAST_INTERVAL,
+            checkpoint_interval: None,
+            anchor_topic: None,
+            bridge_topics: None,
+            bft_enabled: false,
+            bft_round: None,
+            metrics_addr: None,
+            membership_policy: None,
+            blob_dir: None,
+            blob_listen: None,
+            evm_rpc_listen: None,
+            evm_chain_id: None,
+            native_max_block_interval: None,
+            native_prune_keep: None,
+            entry_gc_epochs: None,
+            native_pending_ttl: None,
+            native_max_pending: None,
+            max_http_connections: None,
+            max_anchors_per_minute: None,
+            rpc_tier_limits: RpcTierLimits::default(),
+            evm_gas_margin_percent: DEFAULT_GAS_MARGIN_PERCENT,
+        }
+    }
+
+    /// Builder from a `[net]` table, which must name the node, its listener,
+    /// and its log directory.
+    pub fn from_settings(
+        settings: &NetSettings,
+        key_material: KeyMaterial,
+    ) -> Result<Self, ConfigError> {
+        let required = |key: &str| invalid(format!("[net] {key} is required"));
+        let node_id = settings
+            .node_id
+            .clone()
+            .ok_or_else(|| required("node_id"))?;
+        let listen = settings
+            .listen
+            .as_deref()
+            .ok_or_else(|| required("listen"))?;
+        let log_dir = settings
+            .log_dir
+            .clone()
+            .ok_or_else(|| required("log_dir"))?;
+        let mut builder = Self::new(
+            node_id,
+            parse_multiaddr("listen", listen)?,
+            log_dir,
+            key_material,
+        );
+        for raw in &settings.bootstraps {
+            builder.bootstraps.push(parse_multiaddr("bootstrap", raw)?);
+        }
+        if let Some(quorum) = settings.quorum {
+            builder.quorum = quorum;
+        }
+        builder.attestation_quorum = settings.attestation_quorum;
+        if let Some(ms) = settings.broadcast_interval_ms {
+            builder.broadcast_interval = Duration::from_millis(ms);
+        }
+        builder.checkpoint_interval = settings.checkpoint_interval;
+        builder.anchor_topic = settings.anchor_topic.clone();
+        if !settings.bridge_topics.is_empty() {
+            builder.bridge_topics = Some(settings.bridge_topics.clone());
+        }
+        builder.bft_enabled = settings.bft.unwrap_or(false);
+        builder.bft_round = settings.bft_round_ms.map(Duration::from_millis);
+        builder.metrics_addr = settings.metrics;
+        builder.blob_dir = settings.blob_dir.clone();
+        builder.blob_listen = settings.blob_listen;
+        builder.evm_rpc_listen = settings.evm_rpc_listen;
+        builder.evm_chain_id = settings.evm_chain_id;
+        builder.native_max_block_interval = settings
+            .native_max_block_interval_secs
+            .map(Duration::from_secs);
+        builder.native_prune_keep = settings.native_prune_keep;
+        builder.entry_gc_epochs = settings.entry_gc_epochs;
+        builder.native_pending_ttl = settings.native_pending_ttl_secs.map(Duration::from_secs);
+        builder.native_max_pending = settings.native_max_pending;
+        builder.max_http_connections = settings.max_http_connections;
+        builder.max_anchors_per_minute = settings.max_anchors_per_minute;
+        apply_tier_specs(&mut builder.rpc_tier_limits, &settings.rpc_tiers)?;
+        if let Some(percent) = settings.evm_gas_margin_percent {
+            builder.evm_gas_margin_percent = percent;
+        }
+        Ok(builder)
+    }
+
+    /// Builder from the `[net]` table of config file text.
+    pub fn from_toml(text: &str, key_material: KeyMaterial) -> Result<Self, ConfigError> {
+        Self::from_settings(&ConfigFile::from_toml(text)?.net, key_material)
+    }
+
+    /// Bootstrap peers dialed on startup.
+    pub fn bootstraps(mut self, bootstraps: Vec<Multiaddr>) -> Self {
+        self.bootstraps = bootstraps;
+        self
+    }
+
+    /// Anchor finality quorum (default 1).
+    pub fn quorum(mut self, quorum: usize) -> Self {
+        self.quorum = quorum;
+        self
+    }
+
+    /// Blob attestation quorum (defaults to the anchor quorum).
+    pub fn attestation_quorum(mut self, quorum: usize) -> Self {
+        self.attestation_quorum = Some(quorum);
+        self
+    }
+
+    /// Anchor recomputation and broadcast interval (default 5 s).
+    pub fn broadcast_interval(mut self, interval: Duration) -> Self {
+        self.broadcast_interval = interval;
+        self
+    }
+
+    /// Checkpoint every `broadcasts` anchor broadcasts.
+    pub fn checkpoint_interval(mut self, broadcasts: u64) -> Self {
+        self.checkpoint_interval = Some(broadcasts);
+        self
+    }
+
+    /// Anchor gossip topic (defaults to the network's anchor topic).
+    pub fn anchor_topic(mut self, topic: impl Into<String>) -> Self {
+        self.anchor_topic = Some(topic.into());
+        self
+    }
+
+    /// Additional anchor topics bridged across shards.
+    pub fn bridge_topics(mut self, topics: Vec<String>) -> Self {
+        self.bridge_topics = Some(topics);
+        self
+    }
+
+    /// Enables BFT votes; `round` defaults to the broadcast interval.
+    pub fn bft(mut self, round: Option<Duration>) -> Self {
+        self.bft_enabled = true;
+        self.bft_round = round;
+        self
+    }
+
+    /// Prometheus listener.
+    pub fn metrics_addr(mut self, addr: SocketAddr) -> Self {
+        self.metrics_addr = Some(addr);
+        self
+    }
+
+    /// Membership policy (default: allow everyone).
+    pub fn membership_policy(mut self, policy: Arc<dyn MembershipPolicy>) -> Self {
+        self.membership_policy = Some(policy);
+        self
+    }
+
+    /// Blob data directory, which also holds the stake registry.
+    pub fn blob_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.blob_dir = Some(dir.into());
+        self
+    }
+
+    /// Blob HTTP listener.
+    pub fn blob_listen(mut self, addr: SocketAddr) -> Self {
+        self.blob_listen = Some(addr);
+        self
+    }
+
+    /// Wallet JSON-RPC listener; enables native-chain finality.
+    pub fn evm_rpc_listen(mut self, addr: SocketAddr) -> Self {
+        self.evm_rpc_listen = Some(addr);
+        self
+    }
+
+    /// Native chain ID; enables native-chain finality.
+    pub fn evm_chain_id(mut self, chain_id: u64) -> Self {
+        self.evm_chain_id = Some(chain_id);
+        self
+    }
+
+    /// Longest an idle native chain waits before sealing an empty block.
+    pub fn native_max_block_interval(mut self, interval: Duration) -> Self {
+        self.native_max_block_interval = Some(interval);
+        self
+    }
+
+    /// Finalized blocks kept in live state before archiving.
+    pub fn native_prune_keep(mut self, blocks: u64) -> Self {
+        self.native_prune_keep = Some(blocks);
+        self
+    }
+
+    /// Checkpoint epochs a never-finalized ledger entry is kept before it is
+    /// archived.
+    pub fn entry_gc_epochs(mut self, epochs: u64) -> Self {
+        self.entry_gc_epochs = Some(epochs);
+        self
+    }
+
+    /// How long a native transaction may stay pending before it is expired.
+    pub fn native_pending_ttl(mut self, ttl: Duration) -> Self {
+        self.native_pending_ttl = Some(ttl);
+        self
+    }
+
+    /// Pending native transactions held before the cheapest is displaced.
+    pub fn native_max_pending(mut self, max: usize) -> Self {
+        self.native_max_pending = Some(max);
+        self
+    }
+
+    /// Connection slots shared by the metrics and RPC listeners.
+    pub fn max_http_connections(mut self, max: usize) -> Self {
+        self.max_http_connections = Some(max);
+        self
+    }
+
+    /// Anchors accepted per identity per minute (0 disables the limit).
+    pub fn max_anchors_per_minute(mut self, max: u32) -> Self {
+        self.max_anchors_per_minute = Some(max);
+        self
+    }
+
+    /// Per-tier limits of the wallet JSON-RPC.
+    pub fn rpc_tier_limits(mut self, limits: RpcTierLimits) -> Self {
+        self.rpc_tier_limits = limits;
+        self
+    }
+
+    /// Safety margin added to calldata-bearing `eth_estimateGas` results.
+    pub fn evm_gas_margin_percent(mut self, percent: u64) -> Self {
+        self.evm_gas_margin_percent = percent;
+        self
+    }
+
+    /// Validates the settings and assembles the node configuration.
+    pub fn build(self) -> Result<NetConfig, ConfigError> {
+        if self.node_id.trim().is_empty() {
+            return Err(invalid("node_id must not be empty"));
+        }
+        check_quorum("quorum", self.quorum)?;
+        if let Some(quorum) = self.attestation_quorum {
+            check_quorum("attestation_quorum", quorum)?;
+        }
+        check_multiaddr("listen", &self.listen_addr)?;
+        for addr in &self.bootstraps {
+            check_multiaddr("bootstrap", addr)?;
+        }
+        check_interval("broadcast_interval", self.broadcast_interval)?;
+        if let Some(round) = self.bft_round {
+            check_interval("bft_round", round)?;
+        }
+        if let Some(interval) = self.native_max_block_interval {
+            check_interval("native_max_block_interval", interval)?;
+        }
+        if let Some(ttl) = self.native_pending_ttl {
+            if ttl.as_secs() == 0 {
+                return Err(invalid("native_pending_ttl must be at least one second"));
+            }
+        }
+        if self.native_max_pending == Some(0) {
+            return Err(invalid("native_max_pending must be at least 1"));
+        }
+        if self.checkpoint_interval == Some(0) {
+            return Err(invalid("checkpoint_interval must be at least 1"));
+        }
+        if self.max_http_connections == Some(0) {
+            return Err(invalid("max_http_connections must be at least 1"));
+        }
+        check_tier_limits(&self.rpc_tier_limits)?;
+        check_gas_margin(self.evm_gas_margin_percent)?;
+        let listeners = [
+            ("metrics", self.metrics_addr),
+            ("blob_listen", self.blob_listen),
+            ("evm_rpc_listen", self.evm_rpc_listen),
+        ];
+        for (index, (name, addr)) in listeners.iter().enumerate() {
+            let Some(addr) = addr else { continue };
+            check_listener(name, *addr)?;
+            if let Some((other, _)) = listeners[..index]
+                .iter()
+                .find(|(_, other)| *other == Some(*addr))
+            {
+                return Err(invalid(format!("{name} and {other} both use {addr}")));
+            }
+        }
+
+        let mut config = NetConfig::new(
+            self.node_id,
+            self.listen_addr,
+            self.bootstraps,
+            self.log_dir,
+            self.quorum,
+            self.broadcast_interval,
+            self.key_material,
+            self.anchor_topic,
+            self.bridge_topics,
+            self.bft_enabled,
+            self.bft_round.map(|round| round.as_millis() as u64),
+            self.metrics_addr,
+            self.membership_policy
+                .unwrap_or_else(|| Arc::new(StaticPolicy::allow_all())),
+            self.checkpoint_interval,
+            self.blob_dir,
+            self.blob_listen,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            self.attestation_quorum,
+            None,
+            None,
+            self.evm_rpc_listen,
+            self.evm_chain_id,
+        );
+        if let Some(interval) = self.native_max_block_interval {
+            config.native_max_block_interval = interval;
+        }
+        config.native_prune_keep = self.native_prune_keep;
+        config.entry_gc_epochs = self.entry_gc_epochs;
+        if let Some(ttl) = self.native_pending_ttl {
+            config.native_pending_ttl = ttl;
+        }
+        if let Some(max) = self.native_max_pending {
+            config.native_max_pending = max;
+        }
+        if let Some(max) = self.max_http_connections {
+            config.http_connection_limit = ConnectionLimiter::new(max);
+        }
+        if let Some(max) = self.max_anchors_per_minute {
+            config.max_anchors_per_minute = max;
+        }
+        config.evm_rpc_tiers = RpcTiers::new(self.rpc_tier_limits);
+        config.evm_rpc_gas_margin_percent = self.evm_gas_margin_percent;
+        Ok(config)
+    }
+}
+
+/// Validating builder for [`EvmRpcConfig`]; see [`EvmRpcConfig::builder`].
+pub struct EvmRpcConfigBuilder {
+    config: EvmRpcConfig,
+    tier_limits: RpcTierLimits,
+}
+
+impl EvmRpcConfigBuilder {
+    /// Builder serving `state` on `listen` and submitting writes to `command_sender`.
+    pub fn new(
+        listen: SocketAddr,
+        chain_id: u64,
+        state: SharedNativeChainState,
+        command_sender: mpsc::Sender<NativeChainCommand>,
+    ) -> Self {
+        Self {
+            config: EvmRpcConfig::new(listen, chain_id, state, command_sender),
+            tier_limits: RpcTierLimits::default(),
+        }
+    }
+
+    /// Applies the registry, beacon, timeout, tier, and gas margin keys of an
+    /// `[rpc]` table.
+    pub fn settings(mut self, settings: &RpcSettings) -> Result<Self, ConfigError> {
+        if let Some(path) = &settings.registry {
+            self.config.asset_registry = Some(path.clone());
+        }
+        if let Some(dir) = &settings.log_dir {
+            self.config.beacon_log_dir = Some(dir.clone());
+        }
+        if let Some(ms) = settings.request_timeout_ms {
+            self.config.request_timeout = Duration::from_millis(ms);
+        }
+        if let Some(threshold) = settings.beacon_threshold {
+            self.config.beacon_threshold = threshold;
+        }
+        apply_tier_specs(&mut self.tier_limits, &settings.tiers)?;
+        if let Some(percent) = settings.gas_margin_percent {
+            self.config.gas_margin_percent = percent;
+        }
+        Ok(self)
+    }
+
+    /// Request read and transaction acceptance timeout (default 10 s).
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.config.request_timeout = timeout;
+        self
+    }
+
+    /// Connection slots, possibly shared with other listeners.
+    pub fn connection_limit(mut self, limit: ConnectionLimiter) -> Self {
+        self.config.connection_limit = limit;
+        self
+    }
+
+    /// Stake registry backing the `asset_*` methods.
+    pub fn asset_registry(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config.asset_registry = Some(path.into());
+        self
+    }
+
+    /// Log directory whose checkpoints back `beacon_get`.
+    pub fn beacon_log_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.config.beacon_log_dir = Some(dir.into());
+        self
+    }
+
+    /// Minimum signer threshold for beacon values (default 1).
+    pub fn beacon_threshold(mut self, threshold: usize) -> Self {
+        self.config.beacon_threshold = threshold;
+        self
+    }
+
+    /// Archive of pruned blocks.
+    pub fn block_archive(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.config.block_archive = Some(dir.into());
+        self
+    }
+
+    /// Migration apply-state file behind `isClaimed`.
+    pub fn claim_state(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config.claim_state = Some(path.into());
+        self
+    }
+
+    /// Cross-origin policy.
+    pub fn cors(mut self, cors: CorsPolicy) -> Self {
+        self.config.cors = cors;
+        self
+    }
+
+    /// Application calldata hooks.
+    pub fn tx_input_handlers(mut self, handlers: TxInputHandlers) -> Self {
+        self.config.tx_input_handlers = handlers;
+        self
+    }
+
+    /// Governance-managed runtime parameters.
+    pub fn parameters(mut self, parameters: SharedParameters) -> Self {
+        self.config.parameters = parameters;
+        self
+    }
+
+    /// Fork safe mode shared with the node.
+    pub fn safe_mode(mut self, safe_mode: SafeMode) -> Self {
+        self.config.safe_mode = safe_mode;
+        self
+    }
+
+    /// Per-tier concurrency and queue limits.
+    pub fn tier_limits(mut self, limits: RpcTierLimits) -> Self {
+        self.tier_limits = limits;
+        self
+    }
+
+    /// Safety margin added to calldata-bearing `eth_estimateGas` results.
+    pub fn gas_margin_percent(mut self, percent: u64) -> Self {
+        self.config.gas_margin_percent = percent;
+        self
+    }
+
+    /// Serves as a read-only replica.
+    pub fn replica(mut self, replica: ReplicaMode) -> Self {
+        self.config.replica = Some(replica);
+        self
+    }
+
+    /// Validates the settings and assembles the RPC configuration.
+    pub fn build(mut self) -> Result<EvmRpcConfig, ConfigError> {
+        check_listener("listen", self.config.listen)?;
+        if self.config.chain_id == 0 {
+            return Err(invalid("chain_id must not be 0"));
+        }
+        check_interval("request_timeout", self.config.request_timeout)?;
+        check_quorum("beacon_threshold", self.config.beacon_threshold)?;
+        check_tier_limits(&self.tier_limits)?;
+        check_gas_margin(self.config.gas_margin_percent)?;
+        self.config.tiers = RpcTiers::new(self.tier_limits);
+        Ok(self.config)
+    }
+}
+
+/// Parses the supported TOML subset into nested JSON objects.
+fn parse_toml(text: &str) -> Result<Map<String, Value>, ConfigError> {
+    let mut root = Map::new();
+    let mut table: Option<String> = None;
+    for (index, raw) in text.lines().enumerate() {
+        let syntax = |message: String| ConfigError::Syntax {
+            line: index + 1,
+            message,
+        };
+        let line = strip_comment(raw).trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(header) = line.strip_prefix('[') {
+            let name = header
+                .strip_suffix(']')
+                .map(str::trim)
+                .filter(|name| is_bare_key(name))
+                .ok_or_else(|| syntax(format!("expected a [table] header, got {line}")))?;
+            if root
+                .insert(name.to_string(), Value::Object(Map::new()))
+                .is_some()
+            {
+                return Err(syntax(format!("table [{name}] defined twice")));
+            }
+            table = Some(name.to_string());
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| syntax(format!("expected key = value, got {line}")))?;
+        let key = key.trim();
+        if !is_bare_key(key) {
+            return Err(syntax(format!("invalid key {key:?}")));
+        }
+        let value = parse_value(value).map_err(syntax)?;
+        let target = match &table {
+            Some(name) => root
+                .get_mut(name)
+                .and_then(Value::as_object_mut)
+                .expect("table headers insert objects"),
+            None => &mut root,
+        };
+        if target.insert(key.to_string(), value).is_some() {
+            return Err(syntax(format!("duplicate key {key}")));
+        }
+    }
+    Ok(root)
+}
+
+fn is_bare_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Drops a trailing `#` comment that is not inside a string.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+    for (index, c) in line.char_indices() {
+        match quote {
+            Some('"') if escaped => escaped = false,
+            Some('"') if c == '\\' => escaped = true,
+            Some(open) if c == open => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '#' => return &line[..index],
+            None => {}
+        }
+    }
+    line
+}
+
+fn parse_value(input: &str) -> Result<Value, String> {
+    let (value, rest) = value_prefix(input)?;
+    if !rest.trim().is_empty() {
+        return Err(format!("unexpected {:?} after value", rest.trim()));
+    }
+    Ok(value)
+}
+
+/// Parses one value from the start of `input` and returns the remainder.
+fn value_prefix(input: &str) -> Result<(Value, &str), String> {
+    let input = input.trim_start();
+    if let Some(rest) = input.strip_prefix('"') {
+        let mut out = String::new();
+        let mut chars = rest.char_indices();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '"' => return Ok((Value::String(out), &rest[index + 1..])),
+                '\\' => match chars.next().map(|(_, escaped)| escaped) {
+                    Some('"') => out.push('"'),
+                    Some('\\') => out.push('\\'),
+                    Some('n') => out.push('\n'),
+                    Some('t') => out.push('\t'),
+                    Some(other) => return Err(format!("unsupported escape \\{other}")),
+                    None => break,
+                },
+                c => out.push(c),
+            }
+        }
+        return Err("unterminated string".to_string());
+    }
+    if let Some(rest) = input.strip_prefix('\'') {
+        let end = rest.find('\'').ok_or("unterminated string")?;
+        return Ok((Value::String(rest[..end].to_string()), &rest[end + 1..]));
+    }
+    if let Some(mut rest) = input.strip_prefix('[') {
+        let mut items = Vec::new();
+        loop {
+            rest = rest.trim_start();
+            if let Some(after) = rest.strip_prefix(']') {
+                return Ok((Value::Array(items), after));
+            }
+            let (item, after) = value_prefix(rest)?;
+            items.push(item);
+            rest = after.trim_start();
+            if let Some(after) = rest.strip_prefix(',') {
+                rest = after;
+            } else if !rest.starts_with(']') {
+                return Err("expected , or ] in array".to_string());
+            }
+        }
+    }
+    let end = input
+        .find(|c: char| c == ',' || c == ']' || c.is_whitespace())
+        .unwrap_or(input.len());
+    let (token, rest) = input.split_at(end);
+    let value = match token {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        "" => return Err("missing value".to_string()),
+        _ => token
+            .replace('_', "")
+            .parse::<i64>()
+            .map(Value::from)
+            .map_err(|_| format!("unsupported value {token}; strings must be quoted"))?,
+    };
+    Ok((value, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::sign::{load_or_derive_keypair, Ed25519KeySource};
+
+    #[test]
+    fn config_file_builds_validated_net_config() {
+        let key = || load_or_derive_keypair(&Ed25519KeySource::Seed("config".into())).unwrap();
+        let text = r#"
+            # validator settings
+            [net]
+            node_id = "validator-1"   # trailing comment
+            listen = "/ip4/0.0.0.0/tcp/7001"
+            bootstraps = ['/dns4/boot.example/tcp/7001', ]
+            log_dir = "/var/lib/powerhouse/logs"
+            quorum = 2
+            broadcast_interval_ms = 1_500
+            bft = true
+            anchor_topic = "anchors#shard-a"
+            evm_rpc_listen = "127.0.0.1:8545"
+            rpc_tiers = ["expensive=2:4"]
+
+            [rpc]
+            listen = "127.0.0.1:9545"
+            tiers = []
+        "#;
+        let file = ConfigFile::from_toml(text).unwrap();
+        assert_eq!(file.rpc.listen, Some("127.0.0.1:9545".parse().unwrap()));
+        let config = NetConfigBuilder::from_settings(&file.net, key())
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(config.node_id, "validator-1");
+        assert_eq!(config.quorum, 2);
+        assert_eq!(config.attestation_quorum, 2);
+        assert_eq!(config.broadcast_interval, Duration::from_millis(1_500));
+        assert_eq!(config.bft_round_ms, 1_500);
+        assert_eq!(config.anchor_topic.to_string(), "anchors#shard-a");
+        assert_eq!(config.bootstraps.len(), 1);
+        assert!(config.native_chain_enabled);
+        assert!(config
+            .evm_rpc_tiers
+            .render_metrics()
+            .contains("powerhouse_rpc_tier_capacity{tier=\"expensive\"} 2"));
+
+        let rejected = |text: &str| {
+            NetConfigBuilder::from_toml(text, key())
+                .and_then(NetConfigBuilder::build)
+                .err()
+                .unwrap()
+                .to_string()
+        };
+        let base = "[net]\nnode_id = \"n\"\nlog_dir = \"logs\"\n";
+        let with = |extra: &str| format!("{base}listen = \"/ip4/0.0.0.0/tcp/7001\"\n{extra}");
+        assert!(rejected(&with("quorum = 0")).contains("quorum must be at least 1"));
+        assert!(rejected(&with("quorom = 2")).contains("unknown field `quorom`"));
+        assert!(rejected(&with("broadcast_interval_ms = 10")).contains("outside"));
+        assert!(rejected(&with(
+            "metrics = \"0.0.0.0:9100\"\nblob_listen = \"0.0.0.0:9100\""
+        ))
+        .contains("blob_listen and metrics both use"));
+        assert!(rejected(&with("evm_rpc_listen = \"127.0.0.1:0\"")).contains("fixed port"));
+        assert!(rejected(&format!("{base}listen = \"/tcp/7001\"")).contains("tcp or udp port"));
+        assert_eq!(
+            rejected(&with("quorum = two")),
+            "config line 5: unsupported value two; strings must be quoted"
+        );
+        assert!(
+            NetConfigBuilder::new("n", "/ip4/127.0.0.1/tcp/0".parse().unwrap(), "logs", key())
+                .bft(Some(Duration::from_secs(7_200)))
+                .build()
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,455 @@
+#![cfg(feature = "net")]
+
+//! Content-addressed export of transcripts and anchor archives.
+//!
+//! Auditors fetch audit data from untrusted mirrors, so every byte of an
+//! export is addressed by its hash.  [`export_content_bundle`] splits a signed
+//! [`AnchorArchive`] and every transcript record of a log directory into
+//! fixed-size chunks, stores each chunk under `blocks/<cid>` and writes a
+//! [`ContentManifest`] linking every archived anchor digest and transcript
+//! digest to the CIDs that carry it.  [`verify_content_bundle`] recomputes
+//! every CID, reassembles the objects, verifies the archive and checks that
+//! each transcript is anchored.
+//!
+//! CIDs are CIDv1 over the `raw` codec with a sha2-256 multihash, rendered in
+//! base32 multibase (`bafkrei...`), so each chunk is a valid IPFS raw block
+//! (`ipfs block put --cid-codec raw --mhtype sha2-256`).  Object CIDs use the
+//! same encoding over the whole object; they equal the IPFS file CID only for
+//! objects that fit in one chunk.
+
+use crate::net::archive::{verify_archive, AnchorArchive, ArchiveSummary};
+use crate::{
+    iter_ledger_logs, parse_log_bytes, transcript_digest_from_hex, transcript_digest_to_hex,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{collections::HashSet, fs, path::Path};
+
+/// Schema identifier of a content manifest.
+pub const SCHEMA_CONTENT_MANIFEST: &str = "mfenx.powerhouse.content-manifest.v1";
+/// File name of the manifest inside a bundle directory.
+pub const CONTENT_MANIFEST_FILE: &str = "manifest.json";
+/// Directory inside a bundle holding one file per block, named by CID.
+pub const CONTENT_BLOCKS_DIR: &str = "blocks";
+/// Default chunk size, matching the IPFS fixed-size chunker.
+pub const DEFAULT_CHUNK_SIZE: usize = 256 * 1024;
+
+const ARCHIVE_OBJECT_NAME: &str = "archive.json";
+const CID_V1: u8 = 0x01;
+const CODEC_RAW: u8 = 0x55;
+const MULTIHASH_SHA2_256: u8 = 0x12;
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// Errors produced while exporting or verifying a content bundle.
+#[derive(Debug, thiserror::Error)]
+pub enum ContentError {
+    /// Local filesystem or encoding failure.
+    #[error("content bundle I/O error: {0}")]
+    Io(String),
+    /// The manifest is malformed or inconsistent.
+    #[error("invalid content manifest: {0}")]
+    Invalid(String),
+    /// A block or object does not hash to its CID.
+    #[error("content mismatch for {name}: {reason}")]
+    Mismatch {
+        /// Object name.
+        name: String,
+        /// Human-readable failure description.
+        reason: String,
+    },
+    /// The embedded archive fails verification.
+    #[error("archive in content bundle failed verification: {0}")]
+    Archive(String),
+}
+
+/// Chunked object listed in a manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentObject {
+    /// Object name: `archive.json` or a transcript record name.
+    pub name: String,
+    /// Object length in bytes.
+    pub size: u64,
+    /// CID over the whole object.
+    pub cid: String,
+    /// CIDs of the object's chunks, in order.
+    pub chunks: Vec<String>,
+}
+
+/// Archived anchor linked to the archive object carrying it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnchorLink {
+    /// Archive entry epoch.
+    pub epoch: u64,
+    /// Hex anchor digest.
+    pub anchor_digest: String,
+    /// CID of the archive object containing the anchor.
+    pub archive_cid: String,
+}
+
+/// Transcript record linked to its digest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptLink {
+    /// Hex transcript digest recorded in the anchors.
+    pub transcript_digest: String,
+    /// The record's chunks.
+    pub object: ContentObject,
+}
+
+/// Index of a content-addressed bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentManifest {
+    /// Schema identifier (`mfenx.powerhouse.content-manifest.v1`).
+    pub schema: String,
+    /// Node that signed the archive.
+    pub node_id: String,
+    /// Chunk size used for every object.
+    pub chunk_size: usize,
+    /// The signed anchor archive.
+    pub archive: ContentObject,
+    /// Every archived anchor digest with the CID carrying it.
+    pub anchors: Vec<AnchorLink>,
+    /// Every transcript record of the log directory.
+    pub transcripts: Vec<TranscriptLink>,
+}
+
+/// Result of a successful [`verify_content_bundle`] run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentSummary {
+    /// Number of blocks whose CID was recomputed.
+    pub blocks: usize,
+    /// Number of archived anchors.
+    pub anchors: usize,
+    /// Number of transcript records checked against the anchors.
+    pub transcripts: usize,
+    /// Verification summary of the embedded archive.
+    pub archive: ArchiveSummary,
+}
+
+/// CIDv1 (`raw` codec, sha2-256) of `bytes` in base32 multibase.
+pub fn raw_cid(bytes: &[u8]) -> String {
+    let mut cid = vec![CID_V1, CODEC_RAW, MULTIHASH_SHA2_256, 32];
+    cid.extend_from_slice(&Sha256::digest(bytes));
+    format!("b{}", base32_encode(&cid))
+}
+
+/// Returns the sha2-256 digest inside a CID produced by [`raw_cid`].
+pub fn parse_raw_cid(cid: &str) -> Result<[u8; 32], ContentError> {
+    let invalid = || ContentError::Invalid(format!("unsupported CID {cid}"));
+    let body = cid.strip_prefix('b').ok_or_else(invalid)?;
+    let bytes = base32_decode(body).ok_or_else(invalid)?;
+    match bytes.as_slice() {
+        [CID_V1, CODEC_RAW, MULTIHASH_SHA2_256, 32, digest @ ..] if digest.len() == 32 => {
+            let mut out = [0u8; 32];
+            out.copy_from_slice(digest);
+            Ok(out)
+        }
+        _ => Err(invalid()),
+    }
+}
+
+/// Writes `archive` and every transcript record of `log_dir` into `out_dir`
+/// as CID-named blocks plus a manifest.
+pub fn export_content_bundle(
+    log_dir: &Path,
+    archive: &AnchorArchive,
+    out_dir: &Path,
+    chunk_size: usize,
+) -> Result<ContentManifest, ContentError> {
+    if chunk_size == 0 {
+        return Err(ContentError::Invalid("chunk size must be positive".into()));
+    }
+    let blocks_dir = out_dir.join(CONTENT_BLOCKS_DIR);
+    fs::create_dir_all(&blocks_dir).map_err(|err| ContentError::Io(err.to_string()))?;
+
+    let archive_bytes =
+        serde_json::to_vec_pretty(archive).map_err(|err| ContentError::Io(err.to_string()))?;
+    let archive_object =
+        store_object(&blocks_dir, ARCHIVE_OBJECT_NAME, &archive_bytes, chunk_size)?;
+    let anchors = archive
+        .entries
+        .iter()
+        .map(|entry| AnchorLink {
+            epoch: entry.epoch,
+            anchor_digest: entry.anchor_digest.clone(),
+            archive_cid: archive_object.cid.clone(),
+        })
+        .collect();
+
+    let mut transcripts = Vec::new();
+    for record in iter_ledger_logs(log_dir).map_err(ContentError::Io)? {
+        let record = record.map_err(ContentError::Io)?;
+        let parsed = record.parse().map_err(ContentError::Invalid)?;
+        let bytes = record.bytes().map_err(ContentError::Io)?;
+        transcripts.push(TranscriptLink {
+            transcript_digest: transcript_digest_to_hex(&parsed.digest),
+            object: store_object(&blocks_dir, &record.name, &bytes, chunk_size)?,
+        });
+    }
+
+    let manifest = ContentManifest {
+        schema: SCHEMA_CONTENT_MANIFEST.to_string(),
+        node_id: archive.node_id.clone(),
+        chunk_size,
+        archive: archive_object,
+        anchors,
+        transcripts,
+    };
+    let manifest_bytes =
+        serde_json::to_vec_pretty(&manifest).map_err(|err| ContentError::Io(err.to_string()))?;
+    fs::write(out_dir.join(CONTENT_MANIFEST_FILE), manifest_bytes)
+        .map_err(|err| ContentError::Io(err.to_string()))?;
+    Ok(manifest)
+}
+
+/// Verifies a downloaded bundle directory against its manifest.
+pub fn verify_content_bundle(bundle_dir: &Path) -> Result<ContentSummary, ContentError> {
+    let bytes = fs::read(bundle_dir.join(CONTENT_MANIFEST_FILE))
+        .map_err(|err| ContentError::Io(err.to_string()))?;
+    let manifest: ContentManifest =
+        serde_json::from_slice(&bytes).map_err(|err| ContentError::Invalid(err.to_string()))?;
+    if manifest.schema != SCHEMA_CONTENT_MANIFEST {
+        return Err(ContentError::Invalid(format!(
+            "unsupported schema {}",
+            manifest.schema
+        )));
+    }
+    let blocks_dir = bundle_dir.join(CONTENT_BLOCKS_DIR);
+    let mut blocks = 0usize;
+
+    let archive_bytes = load_object(&blocks_dir, &manifest.archive, &mut blocks)?;
+    let archive: AnchorArchive = serde_json::from_slice(&archive_bytes)
+        .map_err(|err| ContentError::Archive(err.to_string()))?;
+    let archive_summary =
+        verify_archive(&archive).map_err(|err| ContentError::Archive(err.to_string()))?;
+    if archive.node_id != manifest.node_id {
+        return Err(ContentError::Invalid(format!(
+            "manifest node {} differs from archive node {}",
+            manifest.node_id, archive.node_id
+        )));
+    }
+    let archived: Vec<(u64, &str)> = archive
+        .entries
+        .iter()
+        .map(|entry| (entry.epoch, entry.anchor_digest.as_str()))
+        .collect();
+    let listed: Vec<(u64, &str)> = manifest
+        .anchors
+        .iter()
+        .map(|link| (link.epoch, link.anchor_digest.as_str()))
+        .collect();
+    if archived != listed {
+        return Err(ContentError::Invalid(
+            "anchor links do not match the archive entries".into(),
+        ));
+    }
+    if let Some(link) = manifest
+        .anchors
+        .iter()
+        .find(|link| link.archive_cid != manifest.archive.cid)
+    {
+        return Err(ContentError::Invalid(format!(
+            "anchor at epoch {} points to {} instead of the archive",
+            link.epoch, link.archive_cid
+        )));
+    }
+
+    let mut anchored = HashSet::new();
+    for entry in &archive.entries {
+        for anchor_entry in &entry.anchor.entries {
+            for hash in &anchor_entry.hashes {
+                let digest = transcript_digest_from_hex(hash).map_err(ContentError::Archive)?;
+                anchored.insert(digest);
+            }
+        }
+    }
+    for link in &manifest.transcripts {
+        let bytes = load_object(&blocks_dir, &link.object, &mut blocks)?;
+        let parsed = parse_log_bytes(&link.object.name, &bytes).map_err(|reason| {
+            ContentError::Mismatch {
+                name: link.object.name.clone(),
+                reason,
+            }
+        })?;
+        if transcript_digest_to_hex(&parsed.digest) != link.transcript_digest {
+            return Err(ContentError::Mismatch {
+                name: link.object.name.clone(),
+                reason: "transcript digest differs from the manifest".into(),
+            });
+        }
+        if !anchored.contains(&parsed.digest) {
+            return Err(ContentError::Mismatch {
+                name: link.object.name.clone(),
+                reason: format!("transcript {} is not anchored", link.transcript_digest),
+            });
+        }
+    }
+
+    Ok(ContentSummary {
+        blocks,
+        anchors: manifest.anchors.len(),
+        transcripts: manifest.transcripts.len(),
+        archive: archive_summary,
+    })
+}
+
+fn store_object(
+    blocks_dir: &Path,
+    name: &str,
+    bytes: &[u8],
+    chunk_size: usize,
+) -> Result<ContentObject, ContentError> {
+    let mut chunks = Vec::new();
+    for chunk in bytes.chunks(chunk_size) {
+        let cid = raw_cid(chunk);
+        let path = blocks_dir.join(&cid);
+        if !path.exists() {
+            fs::write(&path, chunk).map_err(|err| ContentError::Io(err.to_string()))?;
+        }
+        chunks.push(cid);
+    }
+    Ok(ContentObject {
+        name: name.to_string(),
+        size: bytes.len() as u64,
+        cid: raw_cid(bytes),
+        chunks,
+    })
+}
+
+fn load_object(
+    blocks_dir: &Path,
+    object: &ContentObject,
+    blocks: &mut usize,
+) -> Result<Vec<u8>, ContentError> {
+    let mismatch = |reason: String| ContentError::Mismatch {
+        name: object.name.clone(),
+        reason,
+    };
+    let mut bytes = Vec::with_capacity(object.size as usize);
+    for cid in &object.chunks {
+        parse_raw_cid(cid)?;
+        let chunk = fs::read(blocks_dir.join(cid))
+            .map_err(|err| mismatch(format!("block {cid} unreadable: {err}")))?;
+        if raw_cid(&chunk) != *cid {
+            return Err(mismatch(format!("block {cid} does not match its CID")));
+        }
+        bytes.extend_from_slice(&chunk);
+        *blocks += 1;
+    }
+    if bytes.len() as u64 != object.size {
+        return Err(mismatch(format!(
+            "reassembled {} bytes, manifest lists {}",
+            bytes.len(),
+            object.size
+        )));
+    }
+    if raw_cid(&bytes) != object.cid {
+        return Err(mismatch("object does not match its CID".into()));
+    }
+    Ok(bytes)
+}
+
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity((bytes.len() * 8).div_ceil(5));
+    let mut buffer = 0u32;
+    let mut bits = 0u32;
+    for &byte in bytes {
+        buffer = (buffer << 8) | u32::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32_ALPHABET[((buffer >> bits) & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+    out
+}
+
+fn base32_decode(text: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(text.len() * 5 / 8);
+    let mut buffer = 0u32;
+    let mut bits = 0u32;
+    for ch in text.bytes() {
+        let value = BASE32_ALPHABET.iter().position(|&c| c == ch)? as u32;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::archive::export_archive;
+    use crate::net::schema::AnchorJson;
+    use crate::net::sign::{load_or_derive_keypair, Ed25519KeySource};
+    use crate::{julian_genesis_anchor, merkle_root, transcript_digest, EntryAnchor};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn raw_cids_match_ipfs_encoding() {
+        assert_eq!(
+            raw_cid(b""),
+            "bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku"
+        );
+        let cid = raw_cid(b"hello world");
+        assert_eq!(
+            parse_raw_cid(&cid).unwrap(),
+            <[u8; 32]>::from(Sha256::digest(b"hello world"))
+        );
+        assert!(parse_raw_cid("../../etc/passwd").is_err());
+    }
+
+    #[test]
+    fn bundle_round_trips_and_detects_tampering() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("power_house_content_{nanos}"));
+        let log_dir = root.join("logs");
+        fs::create_dir_all(&log_dir).unwrap();
+        let digest = transcript_digest(&[1, 2], &[3, 4], 5);
+        let record = format!(
+            "statement:Content\ntranscript:1 2\nround_sums:3 4\nfinal:5\nhash:{}\n",
+            transcript_digest_to_hex(&digest)
+        );
+        fs::write(log_dir.join("ledger_0000.txt"), &record).unwrap();
+
+        let mut head = julian_genesis_anchor();
+        head.entries.push(EntryAnchor {
+            statement: "Content".to_string(),
+            hashes: vec![digest],
+            merkle_root: merkle_root(&[digest]),
+            submission: None,
+        });
+        let head = AnchorJson::from_ledger("node-a", 1, &head, 0, Vec::new(), None).unwrap();
+        let key = load_or_derive_keypair(&Ed25519KeySource::Seed("content-export".into())).unwrap();
+        let archive = export_archive(&log_dir, "node-a", &key, Some(head), 7).unwrap();
+
+        let bundle = root.join("bundle");
+        let manifest = export_content_bundle(&log_dir, &archive, &bundle, 64).unwrap();
+        assert!(manifest.archive.chunks.len() > 1);
+        assert_eq!(manifest.transcripts.len(), 1);
+        let summary = verify_content_bundle(&bundle).unwrap();
+        assert_eq!(summary.anchors, 1);
+        assert_eq!(summary.transcripts, 1);
+
+        let block = bundle
+            .join(CONTENT_BLOCKS_DIR)
+            .join(&manifest.transcripts[0].object.chunks[0]);
+        let mut bytes = fs::read(&block).unwrap();
+        bytes[0] ^= 1;
+        fs::write(&block, bytes).unwrap();
+        assert!(matches!(
+            verify_content_bundle(&bundle),
+            Err(ContentError::Mismatch { .. })
+        ));
+        let _ = fs::remove_dir_all(&root);
+    }
+}
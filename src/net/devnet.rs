@@ -0,0 +1,451 @@
+#![cfg(feature = "net")]
+
+//! Workspace layout for a local test network, launched by `julian net dev`.
+//!
+//! [`generate_devnet`] writes everything a handful of nodes on one machine
+//! need into a single workspace directory:
+//!
+//! ```text
+//! <workspace>/
+//!   devnet.json          manifest (schema `mfenx.powerhouse.devnet.v1`)
+//!   genesis.json         network id, chain id, validators and allocations
+//!   allowlist.json       membership allowlist shared by every node
+//!   node-1/
+//!     node.key           hex ed25519 secret (0600)
+//!     node.toml          `[net]` table for `julian net start --config`
+//!     logs/  blob/       log and blob directories
+//!     node.out           stdout and stderr of the running node
+//! ```
+//!
+//! Keys are derived from a seed string, so the same [`DevnetSpec`] always
+//! produces the same peer ids, validator set and genesis digest.  Node 1 is the
+//! seed node: every other node bootstraps from its multiaddr.
+//!
+//! Regenerating wipes the workspace first, but only when it already holds a
+//! devnet manifest or is empty; any other directory is refused so that a
+//! mistyped `--workspace` cannot delete unrelated files.
+
+use crate::genesis::{GenesisConfig, GENESIS_CONFIG_SCHEMA};
+use crate::net::address_index::derive_evm_address;
+use crate::net::sign::{encode_public_key_base64, load_or_derive_keypair, Ed25519KeySource};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+};
+
+/// Schema identifier of a devnet manifest.
+pub const DEVNET_SCHEMA: &str = "mfenx.powerhouse.devnet.v1";
+/// File name of the manifest at the root of a devnet workspace.
+pub const DEVNET_MANIFEST: &str = "devnet.json";
+/// Network id written into the devnet genesis.
+pub const DEVNET_NETWORK_ID: &str = "MFENX-DEVNET";
+/// Largest number of nodes a devnet may contain.
+pub const MAX_DEVNET_NODES: usize = 16;
+
+/// Errors produced while generating or loading a devnet workspace.
+#[derive(Debug, thiserror::Error)]
+pub enum DevnetError {
+    /// Filesystem failure inside the workspace.
+    #[error("devnet I/O error: {0}")]
+    Io(String),
+    /// The spec or manifest is malformed.
+    #[error("invalid devnet: {0}")]
+    Invalid(String),
+    /// The workspace holds files that do not belong to a devnet.
+    #[error("refusing to use workspace: {0}")]
+    Refused(String),
+}
+
+/// Shape of a devnet: how many nodes, which ports and which chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DevnetSpec {
+    /// Number of nodes (1 through [`MAX_DEVNET_NODES`]).
+    pub nodes: usize,
+    /// TCP port of node 1's libp2p listener; node `i` uses `base + i - 1`.
+    pub p2p_base_port: u16,
+    /// Port of node 1's EVM RPC listener; node `i` uses `base + i - 1`.
+    pub rpc_base_port: u16,
+    /// EVM chain id written into the genesis.
+    pub chain_id: u64,
+    /// Seed from which node keys are derived (`<seed>-<i>`).
+    pub seed: String,
+    /// Anchor broadcast interval of every node.
+    pub broadcast_interval_ms: u64,
+    /// Genesis balance allocated to each validator.
+    pub allocation: u64,
+}
+
+impl Default for DevnetSpec {
+    fn default() -> Self {
+        Self {
+            nodes: 4,
+            p2p_base_port: 17_101,
+            rpc_base_port: 18_645,
+            chain_id: 31_337,
+            seed: "devnet".to_string(),
+            broadcast_interval_ms: 500,
+            allocation: 1_000_000,
+        }
+    }
+}
+
+impl DevnetSpec {
+    /// Majority quorum of the spec's validator set.
+    pub fn quorum(&self) -> usize {
+        self.nodes / 2 + 1
+    }
+
+    /// Checks the node count, port ranges, chain id and seed.
+    pub fn validate(&self) -> Result<(), DevnetError> {
+        if self.nodes == 0 || self.nodes > MAX_DEVNET_NODES {
+            return Err(DevnetError::Invalid(format!(
+                "node count must be between 1 and {MAX_DEVNET_NODES}"
+            )));
+        }
+        let span = self.nodes as u32 - 1;
+        for (label, base) in [("p2p", self.p2p_base_port), ("rpc", self.rpc_base_port)] {
+            if base == 0 || u32::from(base) + span > u32::from(u16::MAX) {
+                return Err(DevnetError::Invalid(format!(
+                    "{label} ports {base}..+{span} are out of range"
+                )));
+            }
+        }
+        let p2p = u32::from(self.p2p_base_port)..=u32::from(self.p2p_base_port) + span;
+        let rpc = u32::from(self.rpc_base_port)..=u32::from(self.rpc_base_port) + span;
+        if p2p.start() <= rpc.end() && rpc.start() <= p2p.end() {
+            return Err(DevnetError::Invalid(
+                "p2p and rpc port ranges overlap".to_string(),
+            ));
+        }
+        if self.chain_id == 0 {
+            return Err(DevnetError::Invalid(
+                "chain id must be non-zero".to_string(),
+            ));
+        }
+        if self.seed.trim().is_empty() {
+            return Err(DevnetError::Invalid("seed must not be empty".to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// One generated node and the endpoints it will serve.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DevnetNode {
+    /// Node id (`node-<i>`).
+    pub node_id: String,
+    /// Directory holding the node's key, config, logs and output.
+    pub dir: PathBuf,
+    /// Hex secret key file.
+    pub key_path: PathBuf,
+    /// `node.toml` passed to `julian net start --config`.
+    pub config_path: PathBuf,
+    /// File receiving the node's stdout and stderr.
+    pub output_path: PathBuf,
+    /// Base64 ed25519 public key.
+    pub public_key: String,
+    /// `0x` address owning the node's genesis allocation.
+    pub address: String,
+    /// Libp2p peer id.
+    pub peer_id: String,
+    /// Dialable multiaddr including the `/p2p/<peer_id>` suffix.
+    pub multiaddr: String,
+    /// EVM RPC listener.
+    pub rpc: SocketAddr,
+}
+
+impl DevnetNode {
+    /// TCP address of the node's libp2p listener.
+    pub fn p2p_addr(&self) -> Option<SocketAddr> {
+        let mut parts = self.multiaddr.split('/').skip(1);
+        let (Some("ip4"), Some(ip), Some("tcp"), Some(port)) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return None;
+        };
+        format!("{ip}:{port}").parse().ok()
+    }
+
+    /// HTTP URL of the node's EVM RPC.
+    pub fn rpc_url(&self) -> String {
+        format!("http://{}", self.rpc)
+    }
+}
+
+/// Manifest describing a generated devnet workspace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Devnet {
+    /// Schema identifier (`mfenx.powerhouse.devnet.v1`).
+    pub schema: String,
+    /// Workspace root.
+    pub workspace: PathBuf,
+    /// Network id of the genesis.
+    pub network_id: String,
+    /// EVM chain id of the genesis.
+    pub chain_id: u64,
+    /// Anchor and native-chain quorum of every node.
+    pub quorum: usize,
+    /// Shared genesis config.
+    pub genesis_path: PathBuf,
+    /// Shared membership allowlist.
+    pub allowlist_path: PathBuf,
+    /// Nodes in launch order; node 1 is the seed node.
+    pub nodes: Vec<DevnetNode>,
+}
+
+impl Devnet {
+    /// Loads the manifest of a previously generated workspace.
+    pub fn load(workspace: &Path) -> Result<Self, DevnetError> {
+        let path = workspace.join(DEVNET_MANIFEST);
+        let bytes =
+            fs::read(&path).map_err(|err| DevnetError::Io(format!("{}: {err}", path.display())))?;
+        let devnet: Self = serde_json::from_slice(&bytes)
+            .map_err(|err| DevnetError::Invalid(format!("{}: {err}", path.display())))?;
+        if devnet.schema != DEVNET_SCHEMA {
+            return Err(DevnetError::Invalid(format!(
+                "unsupported devnet schema {}",
+                devnet.schema
+            )));
+        }
+        Ok(devnet)
+    }
+}
+
+/// Generates a devnet workspace for `spec`, replacing any previous devnet in
+/// `workspace`, and returns its manifest.
+pub fn generate_devnet(workspace: &Path, spec: &DevnetSpec) -> Result<Devnet, DevnetError> {
+    spec.validate()?;
+    clean_devnet(workspace)?;
+    create_dir(workspace)?;
+    let workspace = fs::canonicalize(workspace)
+        .map_err(|err| DevnetError::Io(format!("{}: {err}", workspace.display())))?;
+
+    let mut nodes = Vec::with_capacity(spec.nodes);
+    let mut secrets = Vec::with_capacity(spec.nodes);
+    for index in 1..=spec.nodes {
+        let node_id = format!("node-{index}");
+        let material =
+            load_or_derive_keypair(&Ed25519KeySource::Seed(format!("{}-{index}", spec.seed)))
+                .map_err(|err| DevnetError::Invalid(err.to_string()))?;
+        let peer_id = material.libp2p.public().to_peer_id().to_string();
+        let offset = (index - 1) as u16;
+        let dir = workspace.join(&node_id);
+        nodes.push(DevnetNode {
+            key_path: dir.join("node.key"),
+            config_path: dir.join("node.toml"),
+            output_path: dir.join("node.out"),
+            dir,
+            node_id,
+            public_key: encode_public_key_base64(&material.verifying),
+            address: derive_evm_address(material.verifying.as_bytes()),
+            multiaddr: format!(
+                "/ip4/127.0.0.1/tcp/{}/p2p/{peer_id}",
+                spec.p2p_base_port + offset
+            ),
+            peer_id,
+            rpc: SocketAddr::from(([127, 0, 0, 1], spec.rpc_base_port + offset)),
+        });
+        secrets.push(material.signing.to_bytes());
+    }
+
+    let mut validators: Vec<String> = nodes.iter().map(|node| node.public_key.clone()).collect();
+    validators.sort();
+    let genesis = GenesisConfig {
+        schema: GENESIS_CONFIG_SCHEMA.to_string(),
+        network_id: DEVNET_NETWORK_ID.to_string(),
+        genesis_statement: format!("JULIAN::GENESIS::{}", spec.seed),
+        chain_id: spec.chain_id,
+        allocations: validators
+            .iter()
+            .map(|key| (key.clone(), spec.allocation))
+            .collect::<BTreeMap<_, _>>(),
+        validators: validators.clone(),
+    };
+    genesis.validate().map_err(DevnetError::Invalid)?;
+    let genesis_path = workspace.join("genesis.json");
+    write_json(&genesis_path, &genesis)?;
+    let allowlist_path = workspace.join("allowlist.json");
+    write_json(
+        &allowlist_path,
+        &serde_json::json!({ "allowed": validators }),
+    )?;
+
+    let seed_node = nodes[0].multiaddr.clone();
+    for (index, (node, secret)) in nodes.iter().zip(&secrets).enumerate() {
+        create_dir(&node.dir.join("logs"))?;
+        create_dir(&node.dir.join("blob"))?;
+        write_secret(
+            &node.key_path,
+            &format!(
+                "# devnet key for {} (seed {}-{})\n{}\n",
+                node.node_id,
+                spec.seed,
+                index + 1,
+                hex::encode(secret)
+            ),
+        )?;
+        let bootstraps = if index == 0 {
+            Vec::new()
+        } else {
+            vec![seed_node.clone()]
+        };
+        write_file(&node.config_path, &node_toml(node, spec, &bootstraps))?;
+    }
+
+    let devnet = Devnet {
+        schema: DEVNET_SCHEMA.to_string(),
+        workspace: workspace.clone(),
+        network_id: genesis.network_id,
+        chain_id: genesis.chain_id,
+        quorum: spec.quorum(),
+        genesis_path,
+        allowlist_path,
+        nodes,
+    };
+    write_json(&workspace.join(DEVNET_MANIFEST), &devnet)?;
+    Ok(devnet)
+}
+
+/// Removes a devnet workspace.  A missing or empty directory is left as is;
+/// a non-empty directory without a devnet manifest is refused.
+pub fn clean_devnet(workspace: &Path) -> Result<(), DevnetError> {
+    let mut entries = match fs::read_dir(workspace) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(DevnetError::Io(format!("{}: {err}", workspace.display()))),
+    };
+    if entries.next().is_none() {
+        return Ok(());
+    }
+    Devnet::load(workspace).map_err(|_| {
+        DevnetError::Refused(format!(
+            "{} is not empty and has no {DEVNET_MANIFEST}",
+            workspace.display()
+        ))
+    })?;
+    fs::remove_dir_all(workspace)
+        .map_err(|err| DevnetError::Io(format!("{}: {err}", workspace.display())))
+}
+
+fn node_toml(node: &DevnetNode, spec: &DevnetSpec, bootstraps: &[String]) -> String {
+    let listen = node
+        .multiaddr
+        .split("/p2p/")
+        .next()
+        .unwrap_or(&node.multiaddr);
+    let bootstraps = bootstraps
+        .iter()
+        .map(|addr| toml_string(addr))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "# Generated by `julian net dev`; regenerating the devnet overwrites it.\n\
+         [net]\n\
+         node_id = {node_id}\n\
+         listen = {listen}\n\
+         bootstraps = [{bootstraps}]\n\
+         log_dir = {log_dir}\n\
+         blob_dir = {blob_dir}\n\
+         key = {key}\n\
+         quorum = {quorum}\n\
+         broadcast_interval_ms = {broadcast}\n\
+         evm_rpc_listen = {rpc}\n\
+         evm_chain_id = {chain_id}\n",
+        node_id = toml_string(&node.node_id),
+        listen = toml_string(listen),
+        log_dir = toml_string(&node.dir.join("logs").display().to_string()),
+        blob_dir = toml_string(&node.dir.join("blob").display().to_string()),
+        key = toml_string(&node.key_path.display().to_string()),
+        quorum = spec.quorum(),
+        broadcast = spec.broadcast_interval_ms,
+        rpc = toml_string(&node.rpc.to_string()),
+        chain_id = spec.chain_id,
+    )
+}
+
+fn toml_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn create_dir(path: &Path) -> Result<(), DevnetError> {
+    fs::create_dir_all(path).map_err(|err| DevnetError::Io(format!("{}: {err}", path.display())))
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), DevnetError> {
+    fs::write(path, contents).map_err(|err| DevnetError::Io(format!("{}: {err}", path.display())))
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), DevnetError> {
+    let mut text =
+        serde_json::to_string_pretty(value).map_err(|err| DevnetError::Invalid(err.to_string()))?;
+    text.push('\n');
+    write_file(path, &text)
+}
+
+fn write_secret(path: &Path, contents: &str) -> Result<(), DevnetError> {
+    write_file(path, contents)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))
+            .map_err(|err| DevnetError::Io(format!("{}: {err}", path.display())))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::config::{ConfigFile, NetConfigBuilder};
+    use crate::net::sign::Ed25519KeySource;
+
+    #[test]
+    fn generated_workspace_loads_and_refuses_foreign_dirs() {
+        let root = std::env::temp_dir().join(format!("devnet_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let workspace = root.join("net");
+        let spec = DevnetSpec {
+            nodes: 3,
+            ..DevnetSpec::default()
+        };
+
+        let devnet = generate_devnet(&workspace, &spec).expect("generate");
+        assert_eq!(devnet.quorum, 2);
+        assert_eq!(Devnet::load(&workspace).expect("manifest"), devnet);
+        let genesis = GenesisConfig::load(&devnet.genesis_path).expect("genesis");
+        assert_eq!(genesis.validators.len(), 3);
+        assert_eq!(genesis.chain_id, spec.chain_id);
+
+        for (index, node) in devnet.nodes.iter().enumerate() {
+            let file = ConfigFile::load(&node.config_path).expect("node.toml");
+            assert_eq!(file.net.bootstraps.len(), usize::from(index > 0));
+            let key = load_or_derive_keypair(&Ed25519KeySource::File(node.key_path.clone()))
+                .expect("key file");
+            assert_eq!(encode_public_key_base64(&key.verifying), node.public_key);
+            NetConfigBuilder::from_settings(&file.net, key)
+                .expect("settings")
+                .build()
+                .expect("config");
+            assert_eq!(
+                node.p2p_addr().map(|addr| addr.port()),
+                Some(spec.p2p_base_port + index as u16)
+            );
+        }
+
+        // Regenerating is deterministic and replaces the old workspace.
+        assert_eq!(generate_devnet(&workspace, &spec).expect("regen"), devnet);
+
+        let foreign = root.join("foreign");
+        fs::create_dir_all(&foreign).unwrap();
+        fs::write(foreign.join("keep.txt"), b"keep").unwrap();
+        assert!(matches!(
+            generate_devnet(&foreign, &spec),
+            Err(DevnetError::Refused(_))
+        ));
+        assert!(foreign.join("keep.txt").exists());
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,266 @@
+#![cfg(feature = "net")]
+
+//! DID-style identity documents binding a node's names together.
+//!
+//! `node_id` in anchor envelopes is free-form, so nothing stops a peer from
+//! signing envelopes under someone else's name.  An [`IdentityDocument`] binds
+//! `node_id` to the ed25519 verifying key that signs envelopes and to the
+//! libp2p peer id that authors gossip.  The ed25519 signature over the
+//! document proves control of the key; the swarm only registers a document
+//! published (as a signed gossip message) by the peer id it names, which proves
+//! control of the peer id.  Once registered, [`IdentityRegistry::check_envelope`]
+//! rejects envelopes whose `node_id` disagrees with the binding of their key,
+//! or that claim a `node_id` bound to another key.
+
+use crate::net::sign::{
+    encode_public_key_base64, encode_signature_base64, sign_payload, verify_signature_base64,
+    KeyMaterial,
+};
+use libp2p::PeerId;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::RwLock};
+
+/// Schema identifier of an identity document.
+pub const SCHEMA_IDENTITY_DOCUMENT: &str = "mfenx.powerhouse.identity.v1";
+/// Gossip topic (before network scoping) carrying identity documents.
+pub const IDENTITY_TOPIC: &str = "mfenx/powerhouse/identity/v1";
+
+const DID_PREFIX: &str = "did:mfenx:";
+const IDENTITY_DOMAIN: &[u8] = b"MFENX_IDENTITY_DOCUMENT";
+
+/// Errors produced while verifying or registering identity documents.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum IdentityError {
+    /// The document is malformed or internally inconsistent.
+    #[error("invalid identity document: {0}")]
+    Invalid(String),
+    /// The document signature does not verify.
+    #[error("identity document signature check failed: {0}")]
+    Signature(String),
+    /// The document or envelope contradicts a registered binding.
+    #[error("identity conflict: {0}")]
+    Conflict(String),
+}
+
+/// Signed binding of `node_id` ↔ ed25519 key ↔ libp2p peer id.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdentityDocument {
+    /// Schema identifier (`mfenx.powerhouse.identity.v1`).
+    pub schema: String,
+    /// Decentralised identifier, `did:mfenx:<peer_id>`.
+    pub id: String,
+    /// Network the binding applies to.
+    pub network: String,
+    /// Node identifier used in envelopes and logs.
+    pub node_id: String,
+    /// Base64 ed25519 verifying key that signs the node's envelopes.
+    pub public_key: String,
+    /// Libp2p peer id the node authors gossip as.
+    pub peer_id: String,
+    /// Millisecond timestamp of issue; a newer document replaces an older one.
+    pub issued_ms: u64,
+    /// Base64 ed25519 signature over [`IdentityDocument::signing_bytes`].
+    pub signature: String,
+}
+
+impl IdentityDocument {
+    /// Issues a document for `key`, binding it to the libp2p peer id of the same key material.
+    pub fn issue(network: &str, node_id: &str, key: &KeyMaterial, issued_ms: u64) -> Self {
+        let peer_id = key.libp2p.public().to_peer_id().to_string();
+        let mut document = Self {
+            schema: SCHEMA_IDENTITY_DOCUMENT.to_string(),
+            id: format!("{DID_PREFIX}{peer_id}"),
+            network: network.to_string(),
+            node_id: node_id.to_string(),
+            public_key: encode_public_key_base64(&key.verifying),
+            peer_id,
+            issued_ms,
+            signature: String::new(),
+        };
+        let signature = sign_payload(&key.signing, &document.signing_bytes());
+        document.signature = encode_signature_base64(&signature);
+        document
+    }
+
+    /// Domain-separated, length-prefixed bytes covered by the signature.
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        let mut out = IDENTITY_DOMAIN.to_vec();
+        for field in [
+            &self.schema,
+            &self.id,
+            &self.network,
+            &self.node_id,
+            &self.public_key,
+            &self.peer_id,
+        ] {
+            out.extend_from_slice(&(field.len() as u64).to_be_bytes());
+            out.extend_from_slice(field.as_bytes());
+        }
+        out.extend_from_slice(&self.issued_ms.to_be_bytes());
+        out
+    }
+
+    /// Checks the schema, DID, peer id encoding and signature.
+    pub fn verify(&self) -> Result<PeerId, IdentityError> {
+        if self.schema != SCHEMA_IDENTITY_DOCUMENT {
+            return Err(IdentityError::Invalid(format!(
+                "unexpected schema {}",
+                self.schema
+            )));
+        }
+        if self.node_id.trim().is_empty() {
+            return Err(IdentityError::Invalid("empty node_id".to_string()));
+        }
+        let peer_id: PeerId = self
+            .peer_id
+            .parse()
+            .map_err(|err| IdentityError::Invalid(format!("peer_id: {err}")))?;
+        if self.id != format!("{DID_PREFIX}{}", self.peer_id) {
+            return Err(IdentityError::Invalid(format!(
+                "id {} does not name peer {}",
+                self.id, self.peer_id
+            )));
+        }
+        verify_signature_base64(&self.public_key, &self.signing_bytes(), &self.signature)
+            .map_err(|err| IdentityError::Signature(err.to_string()))?;
+        Ok(peer_id)
+    }
+}
+
+#[derive(Default)]
+struct Bindings {
+    by_key: HashMap<String, IdentityDocument>,
+    key_by_node: HashMap<String, String>,
+}
+
+/// Registered identity bindings, shared between gossip validation and the event loop.
+#[derive(Default)]
+pub struct IdentityRegistry {
+    bindings: RwLock<Bindings>,
+}
+
+impl IdentityRegistry {
+    /// Registers a verified document received from `source`.
+    ///
+    /// Returns `Ok(true)` when the binding is new or replaced an older document
+    /// for the same key, `Ok(false)` for a stale or repeated document.
+    pub fn register(
+        &self,
+        document: &IdentityDocument,
+        source: &PeerId,
+    ) -> Result<bool, IdentityError> {
+        let peer_id = document.verify()?;
+        if peer_id != *source {
+            return Err(IdentityError::Conflict(format!(
+                "document for peer {peer_id} published by {source}"
+            )));
+        }
+        let mut bindings = self.bindings.write().expect("identity registry lock");
+        if let Some(owner) = bindings.key_by_node.get(&document.node_id) {
+            if *owner != document.public_key {
+                return Err(IdentityError::Conflict(format!(
+                    "node_id {} is bound to another key",
+                    document.node_id
+                )));
+            }
+        }
+        if let Some(current) = bindings.by_key.get(&document.public_key) {
+            if current.issued_ms >= document.issued_ms {
+                return Ok(false);
+            }
+            let previous = current.node_id.clone();
+            bindings.key_by_node.remove(&previous);
+        }
+        bindings
+            .key_by_node
+            .insert(document.node_id.clone(), document.public_key.clone());
+        bindings
+            .by_key
+            .insert(document.public_key.clone(), document.clone());
+        Ok(true)
+    }
+
+    /// Checks an envelope's claimed `node_id` against the registered bindings.
+    ///
+    /// Keys without a registered document are accepted so nodes that predate
+    /// identity documents keep participating.
+    pub fn check_envelope(&self, node_id: &str, public_key: &str) -> Result<(), IdentityError> {
+        let bindings = self.bindings.read().expect("identity registry lock");
+        if let Some(document) = bindings.by_key.get(public_key) {
+            if document.node_id != node_id {
+                return Err(IdentityError::Conflict(format!(
+                    "key is bound to node_id {}, envelope claims {node_id}",
+                    document.node_id
+                )));
+            }
+        }
+        if let Some(owner) = bindings.key_by_node.get(node_id) {
+            if owner != public_key {
+                return Err(IdentityError::Conflict(format!(
+                    "node_id {node_id} is bound to another key"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Registered document for a base64 verifying key.
+    pub fn document_for_key(&self, public_key: &str) -> Option<IdentityDocument> {
+        self.bindings
+            .read()
+            .expect("identity registry lock")
+            .by_key
+            .get(public_key)
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::sign::{load_or_derive_keypair, Ed25519KeySource};
+
+    fn key(seed: &str) -> KeyMaterial {
+        load_or_derive_keypair(&Ed25519KeySource::Seed(format!("ed25519://{seed}"))).unwrap()
+    }
+
+    #[test]
+    fn registry_binds_node_id_key_and_peer() {
+        let alice = key("did-alice");
+        let mallory = key("did-mallory");
+        let alice_peer = alice.libp2p.public().to_peer_id();
+        let mallory_peer = mallory.libp2p.public().to_peer_id();
+        let registry = IdentityRegistry::default();
+
+        let document = IdentityDocument::issue("mainnet", "alice", &alice, 10);
+        assert_eq!(document.verify().unwrap(), alice_peer);
+        assert!(matches!(
+            registry.register(&document, &mallory_peer),
+            Err(IdentityError::Conflict(_))
+        ));
+        assert_eq!(registry.register(&document, &alice_peer), Ok(true));
+        assert_eq!(registry.register(&document, &alice_peer), Ok(false));
+
+        let alice_key = encode_public_key_base64(&alice.verifying);
+        let mallory_key = encode_public_key_base64(&mallory.verifying);
+        registry.check_envelope("alice", &alice_key).unwrap();
+        assert!(registry.check_envelope("bob", &alice_key).is_err());
+        assert!(registry.check_envelope("alice", &mallory_key).is_err());
+        registry.check_envelope("mallory", &mallory_key).unwrap();
+
+        let squatter = IdentityDocument::issue("mainnet", "alice", &mallory, 20);
+        assert!(matches!(
+            registry.register(&squatter, &mallory_peer),
+            Err(IdentityError::Conflict(_))
+        ));
+
+        let renamed = IdentityDocument::issue("mainnet", "alice-2", &alice, 30);
+        assert_eq!(registry.register(&renamed, &alice_peer), Ok(true));
+        registry.check_envelope("alice-2", &alice_key).unwrap();
+        registry.check_envelope("alice", &mallory_key).unwrap();
+
+        let mut forged = renamed;
+        forged.node_id = "root".to_string();
+        assert!(matches!(forged.verify(), Err(IdentityError::Signature(_))));
+    }
+}
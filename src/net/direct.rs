@@ -0,0 +1,445 @@
+#![cfg(feature = "net")]
+
+//! Direct peer-to-peer delivery of proof submissions and transcript records.
+//!
+//! Some applications want to hand a payload to one chosen peer rather than
+//! gossiping it to the whole mesh.  The swarm speaks a request-response
+//! protocol ([`DIRECT_PROTOCOL`]) over the same Noise-secured, yamux-framed
+//! connections it uses for gossip, so payloads are encrypted between the two
+//! endpoints and never relayed.  Each request carries one [`DirectMessage`];
+//! the receiver checks it and answers with a [`DirectReceipt`] signed by its
+//! node key, which the sender verifies against the peer id it dialed.
+//!
+//! Messages to send are queued in `direct_outbox.jsonl` in the node's log
+//! directory ([`enqueue_direct`]); accepted inbound messages land in
+//! `direct_inbox.jsonl` and verified receipts in `direct_receipts.jsonl`.
+
+use crate::net::shipping::ShippedRecord;
+use crate::net::sign::{
+    decode_public_key_base64, encode_public_key_base64, encode_signature_base64, sign_payload,
+    verify_entry_submission, verify_signature_base64, KeyMaterial,
+};
+use crate::EntrySubmission;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use blake2::digest::{consts::U32, Digest};
+use libp2p::{identity, PeerId};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+
+/// Request-response protocol name of the direct channel.
+pub const DIRECT_PROTOCOL: &str = "/mfenx/powerhouse/direct/1.0.0";
+/// Schema identifier of a direct message.
+pub const SCHEMA_DIRECT_MESSAGE: &str = "mfenx.powerhouse.direct.v1";
+/// Schema identifier of a direct-delivery receipt.
+pub const SCHEMA_DIRECT_RECEIPT: &str = "mfenx.powerhouse.direct_receipt.v1";
+/// Queue of messages waiting to be sent, inside the log directory.
+pub const DIRECT_OUTBOX_FILE: &str = "direct_outbox.jsonl";
+/// Accepted inbound messages, inside the log directory.
+pub const DIRECT_INBOX_FILE: &str = "direct_inbox.jsonl";
+/// Verified receipts for sent messages, inside the log directory.
+pub const DIRECT_RECEIPTS_FILE: &str = "direct_receipts.jsonl";
+
+const RECEIPT_DOMAIN: &[u8] = b"MFENX_DIRECT_RECEIPT";
+
+type Blake2b256 = blake2::Blake2b<U32>;
+
+/// Statement and proof attributed to a submitter, as sent to a single peer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofSubmission {
+    /// Canonical statement the proof is for.
+    pub statement: String,
+    /// Base64 proof bytes.
+    pub proof: String,
+    /// Base64 ed25519 key of the submitter.
+    pub public_key: String,
+    /// Base64 signature over [`crate::statement_digest`] of `statement`.
+    pub signature: String,
+    /// Submission time in milliseconds since the Unix epoch.
+    pub submitted_ms: u64,
+}
+
+impl ProofSubmission {
+    /// Attributes `proof` for `statement` to `key`.
+    pub fn sign(statement: &str, proof: &[u8], key: &KeyMaterial, submitted_ms: u64) -> Self {
+        let signature = sign_payload(&key.signing, &crate::statement_digest(statement));
+        Self {
+            statement: statement.to_string(),
+            proof: BASE64.encode(proof),
+            public_key: encode_public_key_base64(&key.verifying),
+            signature: encode_signature_base64(&signature),
+            submitted_ms,
+        }
+    }
+
+    /// Checks the proof encoding and the submitter's signature.
+    pub fn verify(&self) -> Result<(), String> {
+        BASE64
+            .decode(&self.proof)
+            .map_err(|err| format!("proof: {err}"))?;
+        let public_key = decode_public_key_base64(&self.public_key).map_err(|e| e.to_string())?;
+        let signature = BASE64
+            .decode(&self.signature)
+            .map_err(|err| format!("signature: {err}"))?;
+        let submission = EntrySubmission {
+            public_key: public_key.to_bytes().to_vec(),
+            signature,
+            submitted_ms: self.submitted_ms,
+        };
+        verify_entry_submission(&self.statement, &submission).map_err(|err| err.to_string())
+    }
+}
+
+/// Payload carried by a direct message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "body", rename_all = "snake_case")]
+pub enum DirectPayload {
+    /// A statement and proof for the receiver to verify or include.
+    ProofSubmission(ProofSubmission),
+    /// A signed transcript record.
+    TranscriptRecord(ShippedRecord),
+}
+
+impl DirectPayload {
+    /// Verifies the payload's own signature.
+    pub fn verify(&self) -> Result<(), String> {
+        match self {
+            Self::ProofSubmission(submission) => submission.verify(),
+            Self::TranscriptRecord(record) => {
+                record.verify().map(|_| ()).map_err(|e| e.to_string())
+            }
+        }
+    }
+
+    /// Metrics and log label.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::ProofSubmission(_) => "proof_submission",
+            Self::TranscriptRecord(_) => "transcript_record",
+        }
+    }
+}
+
+/// One request on the direct channel.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DirectMessage {
+    /// Schema identifier (`mfenx.powerhouse.direct.v1`).
+    pub schema: String,
+    /// Network the sender belongs to.
+    pub network: String,
+    /// Node identifier of the sender.
+    pub sender_node_id: String,
+    /// Millisecond timestamp at which the sender dispatched the message.
+    pub sent_ms: u64,
+    /// Delivered payload.
+    pub payload: DirectPayload,
+}
+
+impl DirectMessage {
+    /// Wraps `payload` for sending.
+    pub fn new(network: &str, sender_node_id: &str, sent_ms: u64, payload: DirectPayload) -> Self {
+        Self {
+            schema: SCHEMA_DIRECT_MESSAGE.to_string(),
+            network: network.to_string(),
+            sender_node_id: sender_node_id.to_string(),
+            sent_ms,
+            payload,
+        }
+    }
+
+    /// Digest of the JSON encoding, quoted by receipts.
+    pub fn digest(&self) -> [u8; 32] {
+        let encoded = serde_json::to_vec(self).expect("direct message serialises");
+        let mut hasher = Blake2b256::new();
+        hasher.update(SCHEMA_DIRECT_MESSAGE.as_bytes());
+        hasher.update(encoded);
+        hasher.finalize().into()
+    }
+
+    /// Checks schema, network and payload signature.
+    pub fn validate(&self, network: &str) -> Result<(), String> {
+        if self.schema != SCHEMA_DIRECT_MESSAGE {
+            return Err(format!("unexpected schema {}", self.schema));
+        }
+        if self.network != network {
+            return Err(format!("message from network {}", self.network));
+        }
+        self.payload.verify()
+    }
+}
+
+/// Receiver-signed acknowledgement of a direct message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DirectReceipt {
+    /// Schema identifier (`mfenx.powerhouse.direct_receipt.v1`).
+    pub schema: String,
+    /// Hex [`DirectMessage::digest`] of the acknowledged message.
+    pub message_digest: String,
+    /// Node identifier of the receiver.
+    pub receiver_node_id: String,
+    /// Base64 ed25519 key of the receiver; its libp2p peer id must match the dialed peer.
+    pub receiver_public_key: String,
+    /// Whether the receiver accepted the payload.
+    pub accepted: bool,
+    /// Rejection reason when `accepted` is false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Millisecond timestamp of receipt.
+    pub received_ms: u64,
+    /// Base64 signature over [`DirectReceipt::signing_bytes`].
+    pub signature: String,
+}
+
+impl DirectReceipt {
+    /// Signs a receipt for `message_digest`.
+    pub fn sign(
+        message_digest: &[u8; 32],
+        receiver_node_id: &str,
+        key: &KeyMaterial,
+        outcome: Result<(), String>,
+        received_ms: u64,
+    ) -> Self {
+        let mut receipt = Self {
+            schema: SCHEMA_DIRECT_RECEIPT.to_string(),
+            message_digest: hex::encode(message_digest),
+            receiver_node_id: receiver_node_id.to_string(),
+            receiver_public_key: encode_public_key_base64(&key.verifying),
+            accepted: outcome.is_ok(),
+            reason: outcome.err(),
+            received_ms,
+            signature: String::new(),
+        };
+        let signature = sign_payload(&key.signing, &receipt.signing_bytes());
+        receipt.signature = encode_signature_base64(&signature);
+        receipt
+    }
+
+    /// Domain-separated, length-prefixed bytes covered by the signature.
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        let mut out = RECEIPT_DOMAIN.to_vec();
+        let reason = self.reason.as_deref().unwrap_or_default();
+        for field in [
+            self.schema.as_str(),
+            &self.message_digest,
+            &self.receiver_node_id,
+            &self.receiver_public_key,
+            reason,
+        ] {
+            out.extend_from_slice(&(field.len() as u64).to_be_bytes());
+            out.extend_from_slice(field.as_bytes());
+        }
+        out.push(u8::from(self.accepted));
+        out.extend_from_slice(&self.received_ms.to_be_bytes());
+        out
+    }
+
+    /// Checks that the receipt acknowledges `message_digest` and was signed by `peer`'s key.
+    pub fn verify(&self, peer: &PeerId, message_digest: &[u8; 32]) -> Result<(), String> {
+        if self.schema != SCHEMA_DIRECT_RECEIPT {
+            return Err(format!("unexpected schema {}", self.schema));
+        }
+        if self.message_digest != hex::encode(message_digest) {
+            return Err("receipt quotes a different message".to_string());
+        }
+        let verifying =
+            decode_public_key_base64(&self.receiver_public_key).map_err(|e| e.to_string())?;
+        let signer = identity::ed25519::PublicKey::try_from_bytes(&verifying.to_bytes())
+            .map(|key| identity::PublicKey::from(key).to_peer_id())
+            .map_err(|err| err.to_string())?;
+        if signer != *peer {
+            return Err(format!("receipt signed by {signer}, not {peer}"));
+        }
+        verify_signature_base64(
+            &self.receiver_public_key,
+            &self.signing_bytes(),
+            &self.signature,
+        )
+        .map_err(|err| err.to_string())
+    }
+}
+
+/// Queued outbound message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DirectOutboxEntry {
+    /// Libp2p peer id of the recipient.
+    pub peer_id: String,
+    /// Payload to deliver.
+    pub payload: DirectPayload,
+}
+
+/// Inbound message as recorded in the inbox.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirectInboxEntry {
+    /// Libp2p peer id of the sender.
+    pub peer_id: String,
+    /// Message received.
+    pub message: DirectMessage,
+}
+
+/// Sent message digest paired with its verified receipt.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirectReceiptEntry {
+    /// Libp2p peer id of the recipient.
+    pub peer_id: String,
+    /// Receipt returned by the recipient.
+    pub receipt: DirectReceipt,
+}
+
+/// Queues `payload` for delivery to `peer_id` by the node running on `log_dir`.
+pub fn enqueue_direct(log_dir: &Path, peer_id: &PeerId, payload: DirectPayload) -> io::Result<()> {
+    append_jsonl(
+        &log_dir.join(DIRECT_OUTBOX_FILE),
+        &DirectOutboxEntry {
+            peer_id: peer_id.to_string(),
+            payload,
+        },
+    )
+}
+
+/// Removes and returns every queued outbound message.
+///
+/// The outbox is renamed aside before it is read, so messages that
+/// [`enqueue_direct`] appends meanwhile (typically from another process)
+/// start a fresh outbox instead of being truncated away.  A file left aside
+/// by an interrupted drain is returned first; the live outbox then waits for
+/// the next call.
+pub(crate) fn drain_outbox(log_dir: &Path) -> io::Result<Vec<DirectOutboxEntry>> {
+    let draining = log_dir.join(format!("{DIRECT_OUTBOX_FILE}.draining"));
+    if !draining.exists() {
+        match fs::rename(log_dir.join(DIRECT_OUTBOX_FILE), &draining) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        }
+    }
+    let contents = fs::read_to_string(&draining)?;
+    fs::remove_file(&draining)?;
+    Ok(contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(entry) => Some(entry),
+            Err(err) => {
+                eprintln!("skipping malformed {DIRECT_OUTBOX_FILE} line: {err}");
+                None
+            }
+        })
+        .collect())
+}
+
+/// Puts a drained message back into the outbox after a failed send.
+pub(crate) fn requeue_outbox(log_dir: &Path, entry: &DirectOutboxEntry) -> io::Result<()> {
+    append_jsonl(&log_dir.join(DIRECT_OUTBOX_FILE), entry)
+}
+
+/// Appends one JSON line to `path`.
+pub(crate) fn append_jsonl<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut line = serde_json::to_vec(value).map_err(io::Error::other)?;
+    line.push(b'\n');
+    fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(&line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::sign::{load_or_derive_keypair, Ed25519KeySource};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn key(seed: &str) -> KeyMaterial {
+        load_or_derive_keypair(&Ed25519KeySource::Seed(format!("ed25519://{seed}"))).unwrap()
+    }
+
+    #[test]
+    fn receipts_bind_message_digest_and_receiver_peer() {
+        let sender = key("direct-sender");
+        let receiver = key("direct-receiver");
+        let receiver_peer = receiver.libp2p.public().to_peer_id();
+        let submission = ProofSubmission::sign("statement", b"proof", &sender, 5);
+        let message = DirectMessage::new(
+            "mainnet",
+            "sender",
+            6,
+            DirectPayload::ProofSubmission(submission.clone()),
+        );
+        message.validate("mainnet").unwrap();
+        assert!(message.validate("testnet").is_err());
+        let mut forged = message.clone();
+        if let DirectPayload::ProofSubmission(inner) = &mut forged.payload {
+            inner.statement = "other statement".to_string();
+        }
+        assert!(forged.validate("mainnet").is_err());
+
+        let digest = message.digest();
+        let receipt = DirectReceipt::sign(&digest, "receiver", &receiver, Ok(()), 7);
+        receipt.verify(&receiver_peer, &digest).unwrap();
+        assert!(receipt.verify(&receiver_peer, &forged.digest()).is_err());
+        assert!(receipt
+            .verify(&sender.libp2p.public().to_peer_id(), &digest)
+            .is_err());
+        let mut flipped = DirectReceipt::sign(&digest, "receiver", &receiver, Err("no".into()), 7);
+        assert!(!flipped.accepted);
+        flipped.accepted = true;
+        assert!(flipped.verify(&receiver_peer, &digest).is_err());
+
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let log_dir = std::env::temp_dir().join(format!("power_house_direct_{nanos}"));
+        enqueue_direct(
+            &log_dir,
+            &receiver_peer,
+            DirectPayload::ProofSubmission(submission),
+        )
+        .unwrap();
+        let queued = drain_outbox(&log_dir).unwrap();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].peer_id, receiver_peer.to_string());
+        assert!(drain_outbox(&log_dir).unwrap().is_empty());
+        let _ = fs::remove_dir_all(&log_dir);
+    }
+
+    #[test]
+    fn drained_outbox_keeps_late_and_failed_messages() {
+        let receiver = key("direct-receiver");
+        let peer = receiver.libp2p.public().to_peer_id();
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let log_dir = std::env::temp_dir().join(format!("power_house_direct_drain_{nanos}"));
+        let sender = key("direct-sender");
+        let note = |statement: &str| {
+            DirectPayload::ProofSubmission(ProofSubmission::sign(statement, b"proof", &sender, 1))
+        };
+
+        enqueue_direct(&log_dir, &peer, note("leftover")).unwrap();
+        fs::rename(
+            log_dir.join(DIRECT_OUTBOX_FILE),
+            log_dir.join(format!("{DIRECT_OUTBOX_FILE}.draining")),
+        )
+        .unwrap();
+        enqueue_direct(&log_dir, &peer, note("late")).unwrap();
+        let first = drain_outbox(&log_dir).unwrap();
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].payload, note("leftover"));
+        let second = drain_outbox(&log_dir).unwrap();
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].payload, note("late"));
+
+        requeue_outbox(&log_dir, &second[0]).unwrap();
+        assert_eq!(drain_outbox(&log_dir).unwrap(), second);
+        assert!(drain_outbox(&log_dir).unwrap().is_empty());
+        let _ = fs::remove_dir_all(&log_dir);
+    }
+}
@@ -0,0 +1,402 @@
+#![cfg(feature = "net")]
+
+//! Expiry of ledger entries that never reach network finality.
+//!
+//! Whenever quorum finalizes an anchor, the swarm marks the transcript digests
+//! of its entries as finalized in `entry_finality.json` ([`EntryFinalityIndex`]).
+//! [`collect_expired_entries`] walks the ledger log files. Any entry that is
+//! still not finalized `expiry_epochs` checkpoint epochs after a GC pass first
+//! saw it has its file moved into `entry_gc/`. The move is listed in
+//! `entry_gc/manifest.json` ([`EntryGcManifest`]), and
+//! [`load_anchor_from_logs`](crate::net::load_anchor_from_logs) leaves archived
+//! digests out of the anchor even when an older checkpoint still carries them.
+//!
+//! [`import_archived_entries`] reverses the move. A restored file sorted at or
+//! below the latest checkpoint cutoff is listed as restored, so the anchor
+//! loader reads it again instead of skipping it. Journal records cannot be
+//! moved one at a time and are never collected.
+
+use crate::iter_ledger_logs;
+use crate::net::checkpoint::load_latest_checkpoint;
+use crate::{LedgerAnchor, TranscriptDigest};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{btree_map::Entry, BTreeMap, HashSet},
+    fs,
+    path::Path,
+};
+
+/// File in the log directory holding the [`EntryFinalityIndex`].
+pub const ENTRY_FINALITY_FILE: &str = "entry_finality.json";
+/// Directory in the log directory holding collected ledger log files.
+pub const ENTRY_GC_DIR: &str = "entry_gc";
+/// Schema identifier of the GC manifest.
+pub const SCHEMA_ENTRY_GC: &str = "mfenx.powerhouse.entry_gc.v1";
+
+const ENTRY_GC_MANIFEST: &str = "manifest.json";
+
+/// Errors produced while tracking, collecting or restoring entries.
+#[derive(Debug, thiserror::Error)]
+pub enum EntryGcError {
+    /// Local filesystem or encoding failure.
+    #[error("entry gc I/O error: {0}")]
+    Io(String),
+    /// A ledger record, index or manifest is malformed or inconsistent.
+    #[error("entry gc failed: {0}")]
+    Invalid(String),
+}
+
+/// Finality status of the transcript digests this node has accepted.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryFinalityIndex {
+    /// Hex digests finalized by quorum, with the first finality time (ms).
+    #[serde(default)]
+    pub finalized: BTreeMap<String, u64>,
+    /// Hex digests not yet finalized, with the checkpoint epoch a GC pass
+    /// first saw them in.
+    #[serde(default)]
+    pub pending: BTreeMap<String, u64>,
+}
+
+impl EntryFinalityIndex {
+    /// Reads the index from `log_dir`; a missing file is an empty index.
+    pub fn load(log_dir: &Path) -> Result<Self, EntryGcError> {
+        read_json(&log_dir.join(ENTRY_FINALITY_FILE))
+    }
+
+    /// Writes the index to `log_dir`.
+    pub fn save(&self, log_dir: &Path) -> Result<(), EntryGcError> {
+        write_json(&log_dir.join(ENTRY_FINALITY_FILE), self)
+    }
+
+    /// Marks every digest in `anchor` finalized; returns how many were new.
+    pub fn record_finalized(&mut self, anchor: &LedgerAnchor, finalized_ms: u64) -> usize {
+        let mut added = 0;
+        for hash in anchor.entries.iter().flat_map(|entry| &entry.hashes) {
+            let digest = hex::encode(hash);
+            self.pending.remove(&digest);
+            if let Entry::Vacant(slot) = self.finalized.entry(digest) {
+                slot.insert(finalized_ms);
+                added += 1;
+            }
+        }
+        added
+    }
+}
+
+/// Loads the index in `log_dir`, marks `anchor`'s entries finalized and saves
+/// it when anything changed.
+pub fn record_finalized_entries(
+    log_dir: &Path,
+    anchor: &LedgerAnchor,
+    finalized_ms: u64,
+) -> Result<usize, EntryGcError> {
+    let mut index = EntryFinalityIndex::load(log_dir)?;
+    let added = index.record_finalized(anchor, finalized_ms);
+    if added > 0 {
+        index.save(log_dir)?;
+    }
+    Ok(added)
+}
+
+/// Ledger log file moved out of the live log directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchivedEntry {
+    /// Ledger log file name.
+    pub name: String,
+    /// Hex transcript digest of the entry.
+    pub digest: String,
+    /// Statement the entry proves.
+    pub statement: String,
+    /// Checkpoint epoch a GC pass first saw the entry in.
+    pub first_seen_epoch: u64,
+    /// Checkpoint epoch the entry was collected in.
+    pub archived_epoch: u64,
+}
+
+/// Entries collected into `entry_gc/` and restored files the anchor loader
+/// must read despite the checkpoint cutoff.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryGcManifest {
+    /// Schema identifier (`mfenx.powerhouse.entry_gc.v1`).
+    pub schema: String,
+    /// Entries currently held in `entry_gc/`.
+    #[serde(default)]
+    pub archived: Vec<ArchivedEntry>,
+    /// Restored file names at or below the checkpoint cutoff they came back under.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub restored: Vec<String>,
+}
+
+impl Default for EntryGcManifest {
+    fn default() -> Self {
+        Self {
+            schema: SCHEMA_ENTRY_GC.to_string(),
+            archived: Vec::new(),
+            restored: Vec::new(),
+        }
+    }
+}
+
+impl EntryGcManifest {
+    /// Reads the manifest under `log_dir`; a missing file is an empty manifest.
+    pub fn load(log_dir: &Path) -> Result<Self, EntryGcError> {
+        let manifest: Self = read_json(&log_dir.join(ENTRY_GC_DIR).join(ENTRY_GC_MANIFEST))?;
+        if manifest.schema != SCHEMA_ENTRY_GC {
+            return Err(EntryGcError::Invalid(format!(
+                "unexpected manifest schema {}",
+                manifest.schema
+            )));
+        }
+        Ok(manifest)
+    }
+
+    /// Writes the manifest under `log_dir`.
+    pub fn save(&self, log_dir: &Path) -> Result<(), EntryGcError> {
+        let dir = log_dir.join(ENTRY_GC_DIR);
+        fs::create_dir_all(&dir).map_err(|err| EntryGcError::Io(err.to_string()))?;
+        write_json(&dir.join(ENTRY_GC_MANIFEST), self)
+    }
+
+    /// Transcript digests of the archived entries.
+    pub fn archived_digests(&self) -> HashSet<TranscriptDigest> {
+        self.archived
+            .iter()
+            .filter_map(|entry| hex::decode(&entry.digest).ok()?.try_into().ok())
+            .collect()
+    }
+
+    /// Whether `name` was restored at or below a checkpoint cutoff.
+    pub fn is_restored(&self, name: &str) -> bool {
+        self.restored.iter().any(|restored| restored == name)
+    }
+}
+
+/// Outcome of a [`collect_expired_entries`] pass.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EntryGcReport {
+    /// Checkpoint epoch the pass ran at.
+    pub epoch: u64,
+    /// Ledger files whose entry is finalized.
+    pub finalized: usize,
+    /// Ledger files still waiting for finality within the expiry window.
+    pub pending: usize,
+    /// Entries collected (or, on a dry run, that would be).
+    pub archived: Vec<ArchivedEntry>,
+}
+
+/// Moves ledger log files whose entry is not finalized `expiry_epochs` after
+/// it was first seen into `entry_gc/`.
+///
+/// With `dry_run` nothing is moved and neither the index nor the manifest is
+/// written.
+pub fn collect_expired_entries(
+    log_dir: &Path,
+    expiry_epochs: u64,
+    epoch: u64,
+    dry_run: bool,
+) -> Result<EntryGcReport, EntryGcError> {
+    let mut index = EntryFinalityIndex::load(log_dir)?;
+    let mut manifest = EntryGcManifest::load(log_dir)?;
+    let mut report = EntryGcReport {
+        epoch,
+        finalized: 0,
+        pending: 0,
+        archived: Vec::new(),
+    };
+    for record in iter_ledger_logs(log_dir).map_err(EntryGcError::Io)? {
+        let record = record.map_err(EntryGcError::Io)?;
+        if record.name.contains('#') {
+            continue;
+        }
+        let parsed = record.parse().map_err(EntryGcError::Invalid)?;
+        let digest = hex::encode(parsed.digest);
+        if index.finalized.contains_key(&digest) {
+            report.finalized += 1;
+            continue;
+        }
+        let first_seen = *index.pending.entry(digest.clone()).or_insert(epoch);
+        if epoch.saturating_sub(first_seen) < expiry_epochs {
+            report.pending += 1;
+            continue;
+        }
+        report.archived.push(ArchivedEntry {
+            name: record.name,
+            digest,
+            statement: parsed.statement,
+            first_seen_epoch: first_seen,
+            archived_epoch: epoch,
+        });
+    }
+    if dry_run {
+        return Ok(report);
+    }
+    let gc_dir = log_dir.join(ENTRY_GC_DIR);
+    fs::create_dir_all(&gc_dir).map_err(|err| EntryGcError::Io(err.to_string()))?;
+    for entry in &report.archived {
+        fs::rename(log_dir.join(&entry.name), gc_dir.join(&entry.name))
+            .map_err(|err| EntryGcError::Io(format!("move {}: {err}", entry.name)))?;
+        index.pending.remove(&entry.digest);
+        manifest.restored.retain(|name| name != &entry.name);
+        manifest.archived.push(entry.clone());
+    }
+    if !report.archived.is_empty() {
+        manifest.save(log_dir)?;
+    }
+    index.save(log_dir)?;
+    Ok(report)
+}
+
+/// Moves archived entries back into the live log directory.
+///
+/// Restores every archived entry when `digests` is empty, otherwise only the
+/// listed hex digests. A restored entry starts a fresh expiry window.
+pub fn import_archived_entries(
+    log_dir: &Path,
+    digests: &[String],
+) -> Result<Vec<ArchivedEntry>, EntryGcError> {
+    let mut manifest = EntryGcManifest::load(log_dir)?;
+    if let Some(missing) = digests.iter().find(|digest| {
+        !manifest
+            .archived
+            .iter()
+            .any(|entry| &entry.digest == *digest)
+    }) {
+        return Err(EntryGcError::Invalid(format!(
+            "digest {missing} is not archived"
+        )));
+    }
+    let cutoff = load_latest_checkpoint(log_dir)
+        .map_err(|err| EntryGcError::Io(err.to_string()))?
+        .and_then(|checkpoint| checkpoint.log_cutoff);
+    let mut index = EntryFinalityIndex::load(log_dir)?;
+    let (restore, keep): (Vec<_>, Vec<_>) = manifest
+        .archived
+        .drain(..)
+        .partition(|entry| digests.is_empty() || digests.contains(&entry.digest));
+    manifest.archived = keep;
+    for entry in &restore {
+        let target = log_dir.join(&entry.name);
+        if target.exists() {
+            return Err(EntryGcError::Invalid(format!(
+                "{} already exists in the log directory",
+                entry.name
+            )));
+        }
+        fs::rename(log_dir.join(ENTRY_GC_DIR).join(&entry.name), &target)
+            .map_err(|err| EntryGcError::Io(format!("restore {}: {err}", entry.name)))?;
+        if cutoff
+            .as_deref()
+            .is_some_and(|cutoff| entry.name.as_str() <= cutoff)
+        {
+            manifest.restored.push(entry.name.clone());
+        }
+        index.pending.remove(&entry.digest);
+    }
+    manifest.save(log_dir)?;
+    index.save(log_dir)?;
+    Ok(restore)
+}
+
+fn read_json<T: Default + for<'de> Deserialize<'de>>(path: &Path) -> Result<T, EntryGcError> {
+    match fs::read(path) {
+        Ok(bytes) => serde_json::from_slice(&bytes)
+            .map_err(|err| EntryGcError::Invalid(format!("{}: {err}", path.display()))),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(T::default()),
+        Err(err) => Err(EntryGcError::Io(format!("{}: {err}", path.display()))),
+    }
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), EntryGcError> {
+    let bytes =
+        serde_json::to_vec_pretty(value).map_err(|err| EntryGcError::Io(err.to_string()))?;
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, bytes).map_err(|err| EntryGcError::Io(err.to_string()))?;
+    fs::rename(&tmp, path).map_err(|err| EntryGcError::Io(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::checkpoint::{write_checkpoint, AnchorCheckpoint};
+    use crate::net::schema::AnchorJson;
+    use crate::net::swarm::load_anchor_from_logs;
+    use crate::{transcript_digest, transcript_digest_to_hex};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn write_record(dir: &Path, name: &str, statement: &str, final_value: u64) -> String {
+        let hash = transcript_digest(&[1, 2], &[3, 4], final_value);
+        let record = [
+            format!("statement:{statement}"),
+            "transcript:1 2".to_string(),
+            "round_sums:3 4".to_string(),
+            format!("final:{final_value}"),
+            format!("hash:{}", transcript_digest_to_hex(&hash)),
+        ];
+        fs::write(dir.join(name), record.join("\n")).unwrap();
+        hex::encode(hash)
+    }
+
+    fn statements(dir: &Path) -> Vec<String> {
+        load_anchor_from_logs(dir)
+            .unwrap()
+            .entries
+            .into_iter()
+            .skip(1)
+            .map(|entry| entry.statement)
+            .collect()
+    }
+
+    #[test]
+    fn never_finalized_entries_are_archived_and_imported_back() {
+        let dir = std::env::temp_dir().join(format!(
+            "power_house_entry_gc_{}",
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        write_record(&dir, "ledger_0000.txt", "Agreed", 5);
+        let orphan = write_record(&dir, "ledger_0001.txt", "Orphan", 6);
+
+        let mut finalized = load_anchor_from_logs(&dir).unwrap();
+        finalized.entries.truncate(2);
+        assert_eq!(record_finalized_entries(&dir, &finalized, 1).unwrap(), 2);
+        let anchor = load_anchor_from_logs(&dir).unwrap();
+        let anchor = AnchorJson::from_ledger("node-a", 1, &anchor, 1, Vec::new(), None).unwrap();
+        let checkpoint =
+            AnchorCheckpoint::new(5, anchor, Vec::new(), Some("ledger_0001.txt".into()));
+        write_checkpoint(&dir.join("checkpoints"), &checkpoint).unwrap();
+
+        let first = collect_expired_entries(&dir, 2, 5, false).unwrap();
+        assert_eq!((first.finalized, first.pending), (1, 1));
+        assert!(first.archived.is_empty());
+        assert_eq!(
+            collect_expired_entries(&dir, 2, 7, true)
+                .unwrap()
+                .archived
+                .len(),
+            1
+        );
+        assert!(dir.join("ledger_0001.txt").exists());
+
+        let second = collect_expired_entries(&dir, 2, 7, false).unwrap();
+        assert_eq!(second.archived[0].digest, orphan);
+        assert_eq!(second.archived[0].first_seen_epoch, 5);
+        assert!(dir.join(ENTRY_GC_DIR).join("ledger_0001.txt").exists());
+        assert_eq!(statements(&dir), vec!["Agreed"]);
+
+        assert!(import_archived_entries(&dir, &["00".repeat(32)]).is_err());
+        let restored = import_archived_entries(&dir, &[]).unwrap();
+        assert_eq!(restored.len(), 1);
+        assert!(EntryGcManifest::load(&dir)
+            .unwrap()
+            .is_restored("ledger_0001.txt"));
+        assert_eq!(statements(&dir), vec!["Agreed", "Orphan"]);
+        let third = collect_expired_entries(&dir, 2, 7, false).unwrap();
+        assert_eq!((third.pending, third.archived.len()), (1, 0));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,504 @@
+#![cfg(feature = "net")]
+
+//! Publication of finalized fold digests to an EVM chain.
+//!
+//! Operators who want JULIAN anchors pinned to Ethereum run a pinner next to
+//! their node.  On every poll it reads the latest checkpoint from the log
+//! directory and, if its fold digest is not pinned yet, calls
+//! `pinAnchor(uint256 epoch, bytes32 foldDigest)` on the configured contract
+//! with a raw EIP-1559 transaction signed by a secp256k1 key.  Submissions
+//! are recorded in [`PIN_LOG_FILE`] inside the log directory; later polls
+//! fetch their receipts and mark them confirmed once they are buried under
+//! the configured number of blocks, or reverted so the digest is pinned again.
+
+use crate::net::checkpoint::{load_latest_checkpoint, AnchorCheckpoint};
+use crate::net::native_chain::normalize_evm_address;
+use crate::net::storage::write_json_atomic;
+use crate::{compute_fold_digest, transcript_digest_to_hex};
+use k256::ecdsa::SigningKey;
+use rlp::RlpStream;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha3::{Digest, Keccak256};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::time;
+
+/// Schema identifier of the pinning log.
+pub const SCHEMA_PIN_LOG: &str = "mfenx.powerhouse.evm-pin-log.v1";
+/// File inside the log directory that records every pin transaction.
+pub const PIN_LOG_FILE: &str = "evm_pin_log.json";
+/// Solidity signature of the pinning call.
+pub const PIN_ANCHOR_SIGNATURE: &str = "pinAnchor(uint256,bytes32)";
+
+/// Errors produced while pinning anchors.
+#[derive(Debug, thiserror::Error)]
+pub enum PinError {
+    /// Local filesystem failure.
+    #[error("evm pin I/O error: {0}")]
+    Io(String),
+    /// The latest checkpoint could not be read.
+    #[error("evm pin checkpoint error: {0}")]
+    Checkpoint(String),
+    /// The signing key or contract address is invalid.
+    #[error("evm pin configuration error: {0}")]
+    Config(String),
+    /// The JSON-RPC endpoint failed or answered unexpectedly.
+    #[error("evm rpc error: {0}")]
+    Rpc(String),
+}
+
+/// Settings for the anchor pinner.
+#[derive(Debug, Clone)]
+pub struct EvmPinConfig {
+    /// JSON-RPC endpoint of the target chain.
+    pub rpc_url: String,
+    /// `0x` address of the pinning contract.
+    pub contract: String,
+    /// EIP-155 chain id of the target chain.
+    pub chain_id: u64,
+    /// Node log directory holding `checkpoints/` and the pinning log.
+    pub log_dir: PathBuf,
+    /// Delay between polls.
+    pub poll_interval: Duration,
+    /// Blocks (including the inclusion block) before a pin counts as confirmed.
+    pub confirmations: u64,
+    /// Gas limit of each pin transaction.
+    pub gas_limit: u64,
+    /// EIP-1559 fee cap in wei.
+    pub max_fee_per_gas: u128,
+    /// EIP-1559 priority fee in wei.
+    pub max_priority_fee_per_gas: u128,
+    /// Timeout applied to each RPC request.
+    pub request_timeout: Duration,
+}
+
+impl EvmPinConfig {
+    /// Creates a pinner configuration with default polling, fee and
+    /// confirmation settings.
+    pub fn new(rpc_url: String, contract: String, chain_id: u64, log_dir: PathBuf) -> Self {
+        Self {
+            rpc_url,
+            contract,
+            chain_id,
+            log_dir,
+            poll_interval: Duration::from_secs(60),
+            confirmations: 12,
+            gas_limit: 100_000,
+            max_fee_per_gas: 30_000_000_000,
+            max_priority_fee_per_gas: 1_000_000_000,
+            request_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// State of a pin transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PinStatus {
+    /// Broadcast, not yet buried under enough blocks.
+    Submitted,
+    /// Included successfully with the required confirmations.
+    Confirmed,
+    /// Included but reverted; the digest will be pinned again.
+    Reverted,
+}
+
+/// One pin transaction in the pinning log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PinRecord {
+    /// Checkpoint epoch whose fold digest was pinned.
+    pub epoch: u64,
+    /// Hex fold digest passed to the contract.
+    pub fold_digest: String,
+    /// Chain id the transaction was signed for.
+    pub chain_id: u64,
+    /// Contract address called.
+    pub contract: String,
+    /// Sender nonce of the transaction.
+    pub nonce: u64,
+    /// `0x` hash of the signed transaction on the target chain.
+    pub tx_hash: String,
+    /// Millisecond timestamp of submission.
+    pub submitted_ms: u64,
+    /// Current state of the transaction.
+    pub status: PinStatus,
+    /// Block that included the transaction, once known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PinLogFile {
+    schema: String,
+    records: Vec<PinRecord>,
+}
+
+/// ABI calldata for `pinAnchor(epoch, fold_digest)`.
+pub fn pin_calldata(epoch: u64, fold_digest: &[u8; 32]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(4 + 64);
+    data.extend_from_slice(&keccak256(PIN_ANCHOR_SIGNATURE.as_bytes())[..4]);
+    data.extend_from_slice(&[0u8; 24]);
+    data.extend_from_slice(&epoch.to_be_bytes());
+    data.extend_from_slice(fold_digest);
+    data
+}
+
+/// `0x` address controlled by a secp256k1 signing key.
+pub fn evm_signer_address(key: &SigningKey) -> String {
+    let public = key.verifying_key().to_encoded_point(false);
+    let hash = keccak256(&public.as_bytes()[1..]);
+    format!("0x{}", hex::encode(&hash[12..]))
+}
+
+/// Parses a hex secp256k1 secret key (with or without `0x`).
+pub fn parse_evm_signing_key(input: &str) -> Result<SigningKey, PinError> {
+    let trimmed = input.trim();
+    let bytes = hex::decode(trimmed.strip_prefix("0x").unwrap_or(trimmed))
+        .map_err(|err| PinError::Config(format!("invalid signing key hex: {err}")))?;
+    SigningKey::from_slice(&bytes)
+        .map_err(|err| PinError::Config(format!("invalid signing key: {err}")))
+}
+
+/// Signs an EIP-1559 transaction calling `pinAnchor`, returning the raw
+/// transaction and its `0x` hash.
+pub fn sign_pin_transaction(
+    cfg: &EvmPinConfig,
+    key: &SigningKey,
+    nonce: u64,
+    epoch: u64,
+    fold_digest: &[u8; 32],
+) -> Result<(Vec<u8>, String), PinError> {
+    let contract = contract_bytes(&cfg.contract)?;
+    let calldata = pin_calldata(epoch, fold_digest);
+    let fields = |stream: &mut RlpStream| {
+        stream.append(&cfg.chain_id);
+        stream.append(&nonce);
+        stream.append(&cfg.max_priority_fee_per_gas);
+        stream.append(&cfg.max_fee_per_gas);
+        stream.append(&cfg.gas_limit);
+        stream.append(&contract.as_slice());
+        stream.append(&0u8);
+        stream.append(&calldata.as_slice());
+        stream.begin_list(0);
+    };
+    let mut unsigned = RlpStream::new_list(9);
+    fields(&mut unsigned);
+    let mut payload = vec![0x02];
+    payload.extend_from_slice(&unsigned.out());
+    let (signature, recovery) = key
+        .sign_prehash_recoverable(&keccak256(&payload))
+        .map_err(|err| PinError::Config(format!("signing failed: {err}")))?;
+
+    let mut signed = RlpStream::new_list(12);
+    fields(&mut signed);
+    signed.append(&u8::from(recovery));
+    signed.append(&strip_leading_zeros(&signature.r().to_bytes()));
+    signed.append(&strip_leading_zeros(&signature.s().to_bytes()));
+    let mut raw = vec![0x02];
+    raw.extend_from_slice(&signed.out());
+    let hash = format!("0x{}", hex::encode(keccak256(&raw)));
+    Ok((raw, hash))
+}
+
+/// Reads the pinning log of `log_dir`; a missing file is an empty log.
+pub fn load_pin_log(log_dir: &Path) -> Result<Vec<PinRecord>, PinError> {
+    let path = log_dir.join(PIN_LOG_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let bytes = fs::read(&path).map_err(|err| PinError::Io(err.to_string()))?;
+    let file: PinLogFile =
+        serde_json::from_slice(&bytes).map_err(|err| PinError::Io(err.to_string()))?;
+    if file.schema != SCHEMA_PIN_LOG {
+        return Err(PinError::Io(format!(
+            "unsupported pin log schema {}",
+            file.schema
+        )));
+    }
+    Ok(file.records)
+}
+
+fn save_pin_log(log_dir: &Path, records: &[PinRecord]) -> Result<(), PinError> {
+    write_json_atomic(
+        &log_dir.join(PIN_LOG_FILE),
+        &PinLogFile {
+            schema: SCHEMA_PIN_LOG.to_string(),
+            records: records.to_vec(),
+        },
+    )
+    .map_err(PinError::Io)
+}
+
+/// Pins the latest checkpoint's fold digest unless a live pin already covers
+/// it; returns the new record, if any.
+pub async fn pin_latest_checkpoint(
+    cfg: &EvmPinConfig,
+    key: &SigningKey,
+    client: &reqwest::Client,
+) -> Result<Option<PinRecord>, PinError> {
+    let Some(checkpoint) = load_latest_checkpoint(&cfg.log_dir)
+        .map_err(|err| PinError::Checkpoint(err.to_string()))?
+    else {
+        return Ok(None);
+    };
+    let fold_digest = checkpoint_fold_digest(&checkpoint)?;
+    let fold_hex = transcript_digest_to_hex(&fold_digest);
+    let mut records = load_pin_log(&cfg.log_dir)?;
+    if records.iter().any(|record| {
+        record.fold_digest == fold_hex
+            && record.chain_id == cfg.chain_id
+            && record.status != PinStatus::Reverted
+    }) {
+        return Ok(None);
+    }
+    let sender = evm_signer_address(key);
+    let nonce = parse_quantity(
+        &rpc_call(
+            cfg,
+            client,
+            "eth_getTransactionCount",
+            json!([sender, "pending"]),
+        )
+        .await?,
+    )?;
+    let (raw, tx_hash) = sign_pin_transaction(cfg, key, nonce, checkpoint.epoch, &fold_digest)?;
+    let accepted = rpc_call(
+        cfg,
+        client,
+        "eth_sendRawTransaction",
+        json!([format!("0x{}", hex::encode(&raw))]),
+    )
+    .await?;
+    if accepted.as_str() != Some(tx_hash.as_str()) {
+        return Err(PinError::Rpc(format!(
+            "node returned hash {accepted} for transaction {tx_hash}"
+        )));
+    }
+    let record = PinRecord {
+        epoch: checkpoint.epoch,
+        fold_digest: fold_hex,
+        chain_id: cfg.chain_id,
+        contract: normalize_evm_address(&cfg.contract).unwrap_or_else(|| cfg.contract.clone()),
+        nonce,
+        tx_hash,
+        submitted_ms: now_millis(),
+        status: PinStatus::Submitted,
+        block_number: None,
+    };
+    records.push(record.clone());
+    save_pin_log(&cfg.log_dir, &records)?;
+    Ok(Some(record))
+}
+
+/// Fetches receipts of submitted pins and settles those with enough
+/// confirmations; returns how many changed state.
+pub async fn confirm_pins(cfg: &EvmPinConfig, client: &reqwest::Client) -> Result<usize, PinError> {
+    let mut records = load_pin_log(&cfg.log_dir)?;
+    if records
+        .iter()
+        .all(|record| record.status != PinStatus::Submitted)
+    {
+        return Ok(0);
+    }
+    let head = parse_quantity(&rpc_call(cfg, client, "eth_blockNumber", json!([])).await?)?;
+    let mut settled = 0usize;
+    for record in records
+        .iter_mut()
+        .filter(|record| record.status == PinStatus::Submitted && record.chain_id == cfg.chain_id)
+    {
+        let receipt = rpc_call(
+            cfg,
+            client,
+            "eth_getTransactionReceipt",
+            json!([record.tx_hash]),
+        )
+        .await?;
+        if receipt.is_null() {
+            continue;
+        }
+        let block = parse_quantity(&receipt["blockNumber"])?;
+        record.block_number = Some(block);
+        let to = receipt["to"].as_str().and_then(normalize_evm_address);
+        if to.as_deref() != Some(record.contract.as_str()) {
+            return Err(PinError::Rpc(format!(
+                "receipt for {} names recipient {}",
+                record.tx_hash, receipt["to"]
+            )));
+        }
+        if receipt["status"].as_str() != Some("0x1") {
+            record.status = PinStatus::Reverted;
+            settled += 1;
+        } else if head.saturating_add(1) >= block.saturating_add(cfg.confirmations) {
+            record.status = PinStatus::Confirmed;
+            settled += 1;
+        }
+    }
+    save_pin_log(&cfg.log_dir, &records)?;
+    Ok(settled)
+}
+
+/// Runs the pinner until the task is cancelled.
+pub async fn run_evm_pinner(cfg: EvmPinConfig, key: SigningKey) -> Result<(), PinError> {
+    let client = reqwest::Client::builder()
+        .timeout(cfg.request_timeout)
+        .build()
+        .map_err(|err| PinError::Rpc(err.to_string()))?;
+    println!(
+        "QSYS|mod=EVMPIN|evt=START|chain_id={}|contract={}|sender={}",
+        cfg.chain_id,
+        cfg.contract,
+        evm_signer_address(&key)
+    );
+    loop {
+        match confirm_pins(&cfg, &client).await {
+            Ok(0) => {}
+            Ok(count) => println!("QSYS|mod=EVMPIN|evt=SETTLED|pins={count}"),
+            Err(err) => eprintln!("evm pin confirmation error: {err}"),
+        }
+        match pin_latest_checkpoint(&cfg, &key, &client).await {
+            Ok(Some(record)) => println!(
+                "QSYS|mod=EVMPIN|evt=SUBMITTED|epoch={}|fold_digest={}|tx={}",
+                record.epoch, record.fold_digest, record.tx_hash
+            ),
+            Ok(None) => {}
+            Err(err) => eprintln!("evm pin error: {err}"),
+        }
+        time::sleep(cfg.poll_interval).await;
+    }
+}
+
+fn checkpoint_fold_digest(checkpoint: &AnchorCheckpoint) -> Result<[u8; 32], PinError> {
+    let (anchor, _) = checkpoint
+        .clone()
+        .into_ledger()
+        .map_err(|err| PinError::Checkpoint(err.to_string()))?;
+    Ok(anchor
+        .metadata
+        .fold_digest
+        .unwrap_or_else(|| compute_fold_digest(&anchor)))
+}
+
+async fn rpc_call(
+    cfg: &EvmPinConfig,
+    client: &reqwest::Client,
+    method: &str,
+    params: Value,
+) -> Result<Value, PinError> {
+    let payload = json!({
+        "jsonrpc": "2.0",
+        "id": 1u64,
+        "method": method,
+        "params": params,
+    });
+    let response = client
+        .post(&cfg.rpc_url)
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|err| PinError::Rpc(format!("{method} request failed: {err}")))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(PinError::Rpc(format!("{method} status {status}: {body}")));
+    }
+    let mut value: Value = response
+        .json()
+        .await
+        .map_err(|err| PinError::Rpc(format!("{method} decode failed: {err}")))?;
+    if let Some(error) = value.get("error") {
+        return Err(PinError::Rpc(format!("{method} failed: {error}")));
+    }
+    Ok(value["result"].take())
+}
+
+fn parse_quantity(value: &Value) -> Result<u64, PinError> {
+    let text = value
+        .as_str()
+        .ok_or_else(|| PinError::Rpc(format!("expected hex quantity, got {value}")))?;
+    u64::from_str_radix(text.strip_prefix("0x").unwrap_or(text), 16)
+        .map_err(|err| PinError::Rpc(format!("invalid hex quantity {text}: {err}")))
+}
+
+fn contract_bytes(contract: &str) -> Result<[u8; 20], PinError> {
+    let normalized = normalize_evm_address(contract)
+        .ok_or_else(|| PinError::Config(format!("invalid contract address {contract}")))?;
+    let mut out = [0u8; 20];
+    hex::decode_to_slice(&normalized[2..], &mut out)
+        .map_err(|err| PinError::Config(err.to_string()))?;
+    Ok(out)
+}
+
+fn strip_leading_zeros(bytes: &[u8]) -> &[u8] {
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+    use rlp::Rlp;
+
+    #[test]
+    fn pin_transaction_recovers_signer_and_carries_calldata() {
+        let key = parse_evm_signing_key(&format!("0x{}", "11".repeat(32))).unwrap();
+        let cfg = EvmPinConfig::new(
+            "http://127.0.0.1:8545".into(),
+            "0x00000000000000000000000000000000000000AA".into(),
+            11155111,
+            PathBuf::from("."),
+        );
+        let digest = [7u8; 32];
+        let (raw, hash) = sign_pin_transaction(&cfg, &key, 3, 42, &digest).unwrap();
+        assert_eq!(hash, format!("0x{}", hex::encode(keccak256(&raw))));
+        assert_eq!(raw[0], 0x02);
+
+        let tx = Rlp::new(&raw[1..]);
+        assert_eq!(tx.item_count().unwrap(), 12);
+        assert_eq!(tx.val_at::<u64>(0).unwrap(), 11155111);
+        assert_eq!(tx.val_at::<u64>(1).unwrap(), 3);
+        let mut contract = [0u8; 20];
+        contract[19] = 0xaa;
+        assert_eq!(tx.at(5).unwrap().data().unwrap(), &contract);
+        let calldata = tx.at(7).unwrap().data().unwrap().to_vec();
+        assert_eq!(calldata, pin_calldata(42, &digest));
+        assert!(calldata[4..28].iter().all(|byte| *byte == 0));
+        assert_eq!(calldata[28..36], 42u64.to_be_bytes());
+        assert_eq!(&calldata[36..], &digest);
+
+        let mut unsigned = RlpStream::new_list(9);
+        for idx in 0..9 {
+            unsigned.append_raw(tx.at(idx).unwrap().as_raw(), 1);
+        }
+        let mut payload = vec![0x02];
+        payload.extend_from_slice(&unsigned.out());
+        let mut r = [0u8; 32];
+        let mut s = [0u8; 32];
+        let r_bytes = tx.at(10).unwrap().data().unwrap();
+        let s_bytes = tx.at(11).unwrap().data().unwrap();
+        r[32 - r_bytes.len()..].copy_from_slice(r_bytes);
+        s[32 - s_bytes.len()..].copy_from_slice(s_bytes);
+        let signature = Signature::from_scalars(r, s).unwrap();
+        let recovery = RecoveryId::from_byte(tx.val_at::<u8>(9).unwrap()).unwrap();
+        let recovered =
+            VerifyingKey::recover_from_prehash(&keccak256(&payload), &signature, recovery).unwrap();
+        assert_eq!(&recovered, key.verifying_key());
+    }
+}
@@ -0,0 +1,145 @@
+#![cfg(feature = "net")]
+
+//! Anchor finality timing.
+//!
+//! The swarm records when it first sees each anchor digest and observes the
+//! elapsed time once that digest reaches quorum. Divergence episodes run from
+//! the first conflicting anchor until the next finalization. Both are exported
+//! as Prometheus histograms so operators can answer how long the network takes
+//! to finalize a new entry and how long it stays split when it does not.
+
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+/// Upper bounds (seconds) of the finality and divergence histogram buckets.
+const BUCKETS_SECS: [f64; 11] = [
+    0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0,
+];
+
+/// Cumulative Prometheus histogram over durations.
+#[derive(Default)]
+pub(crate) struct DurationHistogram {
+    buckets: [AtomicU64; BUCKETS_SECS.len()],
+    count: AtomicU64,
+    sum_micros: AtomicU64,
+}
+
+impl DurationHistogram {
+    pub(crate) fn observe(&self, elapsed: Duration) {
+        let secs = elapsed.as_secs_f64();
+        for (bound, bucket) in BUCKETS_SECS.iter().zip(&self.buckets) {
+            if secs <= *bound {
+                bucket.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_micros.fetch_add(
+            u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX),
+            Ordering::Relaxed,
+        );
+    }
+
+    /// Appends the histogram in text exposition format under `name`.
+    pub(crate) fn render(&self, name: &str, out: &mut String) {
+        let count = self.count.load(Ordering::Relaxed);
+        out.push_str(&format!("# TYPE {name} histogram\n"));
+        for (bound, bucket) in BUCKETS_SECS.iter().zip(&self.buckets) {
+            out.push_str(&format!(
+                "{name}_bucket{{le=\"{bound}\"}} {}\n",
+                bucket.load(Ordering::Relaxed)
+            ));
+        }
+        out.push_str(&format!("{name}_bucket{{le=\"+Inf\"}} {count}\n"));
+        out.push_str(&format!(
+            "{name}_sum {}\n{name}_count {count}\n",
+            self.sum_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0
+        ));
+    }
+}
+
+/// First-seen times of pending anchor digests and the open divergence episode.
+#[derive(Default)]
+pub(crate) struct FinalityTracker {
+    first_seen: HashMap<[u8; 32], Instant>,
+    diverged_since: Option<Instant>,
+}
+
+impl FinalityTracker {
+    /// Records the first sighting of `digest`; later sightings keep the original time.
+    pub(crate) fn observe(&mut self, digest: [u8; 32], now: Instant) {
+        self.first_seen.entry(digest).or_insert(now);
+    }
+
+    /// Forgets `digest` and returns how long ago it was first seen.
+    pub(crate) fn finalize(&mut self, digest: &[u8; 32], now: Instant) -> Option<Duration> {
+        self.first_seen
+            .remove(digest)
+            .map(|seen| now.saturating_duration_since(seen))
+    }
+
+    /// Drops digests first seen longer than `ttl` ago.
+    pub(crate) fn expire(&mut self, now: Instant, ttl: Duration) {
+        self.first_seen
+            .retain(|_, seen| now.saturating_duration_since(*seen) < ttl);
+    }
+
+    /// Opens a divergence episode; returns `true` if none was open.
+    pub(crate) fn diverge(&mut self, now: Instant) -> bool {
+        if self.diverged_since.is_some() {
+            return false;
+        }
+        self.diverged_since = Some(now);
+        true
+    }
+
+    /// Closes the open divergence episode and returns its duration.
+    pub(crate) fn converge(&mut self, now: Instant) -> Option<Duration> {
+        self.diverged_since
+            .take()
+            .map(|since| now.saturating_duration_since(since))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracker_measures_first_seen_latency_and_divergence_episodes() {
+        let start = Instant::now();
+        let mut tracker = FinalityTracker::default();
+        tracker.observe([1; 32], start);
+        tracker.observe([1; 32], start + Duration::from_millis(400));
+        assert_eq!(
+            tracker.finalize(&[1; 32], start + Duration::from_secs(2)),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(tracker.finalize(&[1; 32], start), None);
+
+        tracker.observe([2; 32], start);
+        tracker.expire(start + Duration::from_secs(10), Duration::from_secs(5));
+        assert_eq!(tracker.finalize(&[2; 32], start), None);
+
+        assert!(tracker.diverge(start));
+        assert!(!tracker.diverge(start + Duration::from_secs(1)));
+        assert_eq!(
+            tracker.converge(start + Duration::from_secs(4)),
+            Some(Duration::from_secs(4))
+        );
+        assert_eq!(tracker.converge(start), None);
+
+        let histogram = DurationHistogram::default();
+        histogram.observe(Duration::from_millis(300));
+        histogram.observe(Duration::from_secs(20));
+        let mut rendered = String::new();
+        histogram.render("anchor_finality_latency_seconds", &mut rendered);
+        assert!(rendered.contains("anchor_finality_latency_seconds_bucket{le=\"0.25\"} 0\n"));
+        assert!(rendered.contains("anchor_finality_latency_seconds_bucket{le=\"0.5\"} 1\n"));
+        assert!(rendered.contains("anchor_finality_latency_seconds_bucket{le=\"30\"} 2\n"));
+        assert!(rendered.contains("anchor_finality_latency_seconds_count 2\n"));
+        assert!(rendered.contains("anchor_finality_latency_seconds_sum 20.3\n"));
+    }
+}
@@ -0,0 +1,408 @@
+#![cfg(feature = "net")]
+
+//! Detection of conflicting finalized checkpoints and the safe mode it triggers.
+//!
+//! Every node signs the anchor it checkpoints, so an anchor digest counts as
+//! finalized for an epoch once checkpoints from at least `quorum` distinct
+//! keys (never fewer than two) carry it.  A [`CheckpointTally`] collects the
+//! local and gossiped checkpoints; two finalized digests for one epoch whose
+//! ledgers do not extend one another mean the network forked.  The tally then
+//! returns the [`ForkEvidence`], and [`SafeMode::engage`] persists it as
+//! `safe_mode.json` in the log directory.  While that file exists the node
+//! stops broadcasting anchors and checkpoints and refuses RPC writes, across
+//! restarts, until an operator picks a branch with [`resolve_fork`]
+//! (`julian net resolve-fork`).
+
+use crate::net::checkpoint::{write_checkpoint, AnchorCheckpoint};
+use crate::net::sign::verify_signature_base64;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// Schema identifier of fork evidence.
+pub const SCHEMA_FORK_EVIDENCE: &str = "mfenx.powerhouse.fork_evidence.v1";
+
+/// Schema identifier of a fork resolution record.
+pub const SCHEMA_FORK_RESOLUTION: &str = "mfenx.powerhouse.fork_resolution.v1";
+
+/// File in the log directory whose presence keeps the node in safe mode.
+pub const SAFE_MODE_FILE: &str = "safe_mode.json";
+
+const FORK_RESOLUTIONS_FILE: &str = "fork_resolutions.jsonl";
+
+/// Epochs a [`CheckpointTally`] keeps before dropping the oldest.
+const TALLY_EPOCHS: usize = 64;
+
+/// Errors produced while detecting or resolving forks.
+#[derive(Debug, thiserror::Error)]
+pub enum ForkError {
+    /// Local filesystem or encoding failure.
+    #[error("fork state I/O error: {0}")]
+    Io(String),
+    /// A checkpoint or evidence file is malformed.
+    #[error("invalid fork evidence: {0}")]
+    Invalid(String),
+    /// [`resolve_fork`] was called while the node is not in safe mode.
+    #[error("node is not in safe mode")]
+    NotInSafeMode,
+    /// The chosen digest matches neither branch.
+    #[error("{0} is not one of the forked branches")]
+    UnknownBranch(String),
+}
+
+/// One side of a fork.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForkBranch {
+    /// Hex anchor digest of the branch.
+    pub anchor_digest: String,
+    /// Base64 keys that validly checkpointed the anchor.
+    pub signers: Vec<String>,
+    /// Checkpoints carrying the anchor, in the order observed.
+    pub checkpoints: Vec<AnchorCheckpoint>,
+}
+
+/// Two finalized anchors that disagree on one epoch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForkEvidence {
+    /// Schema identifier (`mfenx.powerhouse.fork_evidence.v1`).
+    pub schema: String,
+    /// Epoch both checkpoints claim.
+    pub epoch: u64,
+    /// Millisecond timestamp of detection.
+    pub detected_ms: u64,
+    /// The branch finalized first, then the conflicting one.
+    pub branches: Vec<ForkBranch>,
+}
+
+impl ForkEvidence {
+    /// Branch whose digest is `digest` (hex, optionally `0x`-prefixed).
+    pub fn branch(&self, digest: &str) -> Option<&ForkBranch> {
+        let digest = digest.strip_prefix("0x").unwrap_or(digest).to_lowercase();
+        self.branches
+            .iter()
+            .find(|branch| branch.anchor_digest == digest)
+    }
+}
+
+/// Operator decision recorded by [`resolve_fork`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForkResolution {
+    /// Schema identifier (`mfenx.powerhouse.fork_resolution.v1`).
+    pub schema: String,
+    /// Epoch that forked.
+    pub epoch: u64,
+    /// Hex digest of the branch kept.
+    pub chosen: String,
+    /// Hex digests of the branches set aside.
+    pub abandoned: Vec<String>,
+    /// Millisecond timestamp of the decision.
+    pub resolved_ms: u64,
+}
+
+/// Checkpoints observed per epoch, grouped by anchor digest.
+#[derive(Debug, Clone, Default)]
+pub struct CheckpointTally {
+    epochs: BTreeMap<u64, Vec<ForkBranch>>,
+}
+
+impl CheckpointTally {
+    /// Creates an empty tally.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts the valid signatures of `checkpoint` toward its anchor digest.
+    ///
+    /// Returns evidence when this makes a second digest of the epoch
+    /// finalized under `quorum` and neither ledger extends the other; a
+    /// checkpoint that is merely behind is not a fork.
+    pub fn observe(
+        &mut self,
+        checkpoint: &AnchorCheckpoint,
+        quorum: usize,
+        detected_ms: u64,
+    ) -> Result<Option<ForkEvidence>, ForkError> {
+        let digest = checkpoint
+            .digest()
+            .map(hex::encode)
+            .map_err(|err| ForkError::Invalid(err.to_string()))?;
+        let branches = self.epochs.entry(checkpoint.epoch).or_default();
+        let index = match branches
+            .iter()
+            .position(|branch| branch.anchor_digest == digest)
+        {
+            Some(index) => index,
+            None => {
+                branches.push(ForkBranch {
+                    anchor_digest: digest,
+                    signers: Vec::new(),
+                    checkpoints: Vec::new(),
+                });
+                branches.len() - 1
+            }
+        };
+        let branch = &mut branches[index];
+        let fresh: Vec<String> = valid_signers(checkpoint)
+            .into_iter()
+            .filter(|key| !branch.signers.contains(key))
+            .collect();
+        if fresh.is_empty() {
+            return Ok(None);
+        }
+        branch.signers.extend(fresh);
+        branch.checkpoints.push(checkpoint.clone());
+        while self.epochs.len() > TALLY_EPOCHS {
+            self.epochs.pop_first();
+        }
+
+        let quorum = quorum.max(2);
+        let Some(branches) = self.epochs.get(&checkpoint.epoch) else {
+            return Ok(None);
+        };
+        let branch = &branches[index];
+        if branch.signers.len() < quorum {
+            return Ok(None);
+        }
+        for other in branches {
+            if other.anchor_digest == branch.anchor_digest || other.signers.len() < quorum {
+                continue;
+            }
+            if diverged(&other.checkpoints[0], &branch.checkpoints[0])? {
+                return Ok(Some(ForkEvidence {
+                    schema: SCHEMA_FORK_EVIDENCE.to_string(),
+                    epoch: checkpoint.epoch,
+                    detected_ms,
+                    branches: vec![other.clone(), branch.clone()],
+                }));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Keys with a valid signature over the checkpoint anchor.
+fn valid_signers(checkpoint: &AnchorCheckpoint) -> BTreeSet<String> {
+    let Ok(payload) = serde_json::to_vec(&checkpoint.anchor) else {
+        return BTreeSet::new();
+    };
+    checkpoint
+        .signatures
+        .iter()
+        .filter(|signature| {
+            verify_signature_base64(&signature.public_key, &payload, &signature.signature).is_ok()
+        })
+        .map(|signature| signature.public_key.clone())
+        .collect()
+}
+
+/// Whether neither checkpointed ledger is a prefix of the other.
+fn diverged(left: &AnchorCheckpoint, right: &AnchorCheckpoint) -> Result<bool, ForkError> {
+    let ledger = |checkpoint: &AnchorCheckpoint| {
+        checkpoint
+            .anchor
+            .clone()
+            .into_ledger()
+            .map_err(|err| ForkError::Invalid(err.to_string()))
+    };
+    let (left, right) = (ledger(left)?, ledger(right)?);
+    let shared = left.entries.len().min(right.entries.len());
+    Ok(left.entries[..shared] != right.entries[..shared])
+}
+
+/// Shared, persisted safe-mode flag.
+#[derive(Debug, Clone, Default)]
+pub struct SafeMode(Arc<AtomicBool>);
+
+impl SafeMode {
+    /// Reads the flag from `log_dir`: engaged while [`SAFE_MODE_FILE`] exists.
+    pub fn load(log_dir: &Path) -> Self {
+        Self(Arc::new(AtomicBool::new(
+            log_dir.join(SAFE_MODE_FILE).is_file(),
+        )))
+    }
+
+    /// Whether broadcasting and writes are halted.
+    pub fn is_engaged(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Engages safe mode and persists `evidence` to `log_dir`.  Evidence
+    /// already on disk is kept, so the first fork observed is the one the
+    /// operator resolves.
+    pub fn engage(&self, log_dir: &Path, evidence: &ForkEvidence) -> Result<(), ForkError> {
+        self.0.store(true, Ordering::Relaxed);
+        let path = log_dir.join(SAFE_MODE_FILE);
+        if !path.exists() {
+            let bytes = serde_json::to_vec_pretty(evidence)
+                .map_err(|err| ForkError::Io(err.to_string()))?;
+            let tmp = log_dir.join(format!("{SAFE_MODE_FILE}.tmp"));
+            fs::write(&tmp, bytes).map_err(|err| ForkError::Io(err.to_string()))?;
+            fs::rename(&tmp, &path).map_err(|err| ForkError::Io(err.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+/// Evidence that put `log_dir` into safe mode, if it is engaged.
+pub fn read_fork_evidence(log_dir: &Path) -> Result<Option<ForkEvidence>, ForkError> {
+    let path = log_dir.join(SAFE_MODE_FILE);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let bytes = fs::read(&path).map_err(|err| ForkError::Io(err.to_string()))?;
+    serde_json::from_slice(&bytes)
+        .map(Some)
+        .map_err(|err| ForkError::Invalid(format!("{}: {err}", path.display())))
+}
+
+/// Keeps the branch whose digest is `chosen` and leaves safe mode.
+///
+/// The chosen checkpoint replaces the local one for the epoch; the other
+/// branches are kept under `checkpoints/forks/` and the decision is appended
+/// to `fork_resolutions.jsonl`.  A running node picks the change up on restart.
+pub fn resolve_fork(
+    log_dir: &Path,
+    chosen: &str,
+    resolved_ms: u64,
+) -> Result<ForkResolution, ForkError> {
+    let evidence = read_fork_evidence(log_dir)?.ok_or(ForkError::NotInSafeMode)?;
+    let branch = evidence
+        .branch(chosen)
+        .ok_or_else(|| ForkError::UnknownBranch(chosen.to_string()))?;
+    let checkpoints = log_dir.join("checkpoints");
+    write_checkpoint(&checkpoints, &branch.checkpoints[0])
+        .map_err(|err| ForkError::Io(err.to_string()))?;
+    let forks = checkpoints.join("forks");
+    fs::create_dir_all(&forks).map_err(|err| ForkError::Io(err.to_string()))?;
+    let mut abandoned = Vec::new();
+    for other in &evidence.branches {
+        if other.anchor_digest == branch.anchor_digest {
+            continue;
+        }
+        let path: PathBuf = forks.join(format!(
+            "checkpoint_{}_{}.json",
+            evidence.epoch, other.anchor_digest
+        ));
+        let bytes = serde_json::to_vec_pretty(&other.checkpoints[0])
+            .map_err(|err| ForkError::Io(err.to_string()))?;
+        fs::write(&path, bytes).map_err(|err| ForkError::Io(err.to_string()))?;
+        abandoned.push(other.anchor_digest.clone());
+    }
+    let resolution = ForkResolution {
+        schema: SCHEMA_FORK_RESOLUTION.to_string(),
+        epoch: evidence.epoch,
+        chosen: branch.anchor_digest.clone(),
+        abandoned,
+        resolved_ms,
+    };
+    let line = serde_json::to_string(&resolution).map_err(|err| ForkError::Io(err.to_string()))?;
+    fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_dir.join(FORK_RESOLUTIONS_FILE))
+        .and_then(|mut file| writeln!(file, "{line}"))
+        .map_err(|err| ForkError::Io(err.to_string()))?;
+    fs::remove_file(log_dir.join(SAFE_MODE_FILE)).map_err(|err| ForkError::Io(err.to_string()))?;
+    Ok(resolution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::checkpoint::{load_checkpoints, CheckpointSignature};
+    use crate::net::schema::AnchorJson;
+    use crate::net::sign::{
+        encode_public_key_base64, encode_signature_base64, load_or_derive_keypair, sign_payload,
+        Ed25519KeySource,
+    };
+    use crate::{julian_genesis_anchor, merkle_root, statement_digest, EntryAnchor};
+
+    fn checkpoint(statements: &[&str], signer: &str) -> AnchorCheckpoint {
+        let mut ledger = julian_genesis_anchor();
+        for statement in statements {
+            let hashes = vec![statement_digest(statement)];
+            ledger.entries.push(EntryAnchor {
+                statement: statement.to_string(),
+                merkle_root: merkle_root(&hashes),
+                hashes,
+                submission: None,
+            });
+        }
+        let anchor = AnchorJson::from_ledger(signer, 1, &ledger, 0, Vec::new(), None).unwrap();
+        let key = load_or_derive_keypair(&Ed25519KeySource::Seed(signer.to_string())).unwrap();
+        let payload = serde_json::to_vec(&anchor).unwrap();
+        let signature = CheckpointSignature {
+            node_id: signer.to_string(),
+            public_key: encode_public_key_base64(&key.verifying),
+            signature: encode_signature_base64(&sign_payload(&key.signing, &payload)),
+        };
+        AnchorCheckpoint::new(5, anchor, vec![signature], None)
+    }
+
+    #[test]
+    fn finalized_conflict_engages_safe_mode_until_resolved() {
+        let dir = std::env::temp_dir().join(format!("fork_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        // A lagging node checkpointing a prefix is not a fork.
+        let mut tally = CheckpointTally::new();
+        for (statements, signer) in [
+            (&["left"][..], "a"),
+            (&["left"][..], "b"),
+            (&[][..], "c"),
+            (&[][..], "d"),
+        ] {
+            assert!(tally
+                .observe(&checkpoint(statements, signer), 2, 0)
+                .unwrap()
+                .is_none());
+        }
+        // A repeated signer does not count twice toward the quorum.
+        for signer in ["c", "c"] {
+            assert!(tally
+                .observe(&checkpoint(&["right"], signer), 2, 0)
+                .unwrap()
+                .is_none());
+        }
+        let evidence = tally
+            .observe(&checkpoint(&["right"], "d"), 2, 7)
+            .unwrap()
+            .unwrap();
+        assert_eq!(evidence.epoch, 5);
+        assert_eq!(evidence.branches[0].signers.len(), 2);
+        let chosen = evidence.branches[1].anchor_digest.clone();
+
+        let safe_mode = SafeMode::load(&dir);
+        assert!(!safe_mode.is_engaged());
+        safe_mode.engage(&dir, &evidence).unwrap();
+        assert!(SafeMode::load(&dir).is_engaged());
+        assert!(matches!(
+            resolve_fork(&dir, "00", 8),
+            Err(ForkError::UnknownBranch(_))
+        ));
+
+        let resolution = resolve_fork(&dir, &format!("0x{chosen}"), 8).unwrap();
+        assert_eq!(resolution.chosen, chosen);
+        assert_eq!(
+            resolution.abandoned,
+            vec![evidence.branches[0].anchor_digest.clone()]
+        );
+        assert!(!SafeMode::load(&dir).is_engaged());
+        let kept = load_checkpoints(&dir).unwrap();
+        assert_eq!(hex::encode(kept[0].digest().unwrap()), chosen);
+        assert!(matches!(
+            resolve_fork(&dir, &chosen, 9),
+            Err(ForkError::NotInSafeMode)
+        ));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
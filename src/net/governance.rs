@@ -1,6 +1,9 @@
 #![cfg(feature = "net")]
 
-use crate::net::sign::encode_public_key_base64;
+use crate::{
+    economics::SubmissionFeeSchedule,
+    net::sign::{encode_public_key_base64, encode_signature_base64, sign_payload, KeyMaterial},
+};
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use blake2::digest::{consts::U32, Digest};
 use ed25519_dalek::{Signature, VerifyingKey, PUBLIC_KEY_LENGTH, SIGNATURE_LENGTH};
@@ -38,9 +41,49 @@ pub trait MembershipPolicy: Send + Sync {
     }
 }
 
+/// Action a [`GovernanceUpdate`] authorises.
+///
+/// The kind is part of the signed payload, so approvals for one kind of
+/// update cannot be replayed as another.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateKind {
+    /// Membership rotation.
+    #[default]
+    Membership,
+    /// Replacement submission fee schedule.
+    FeeSchedule,
+    /// One-off credit past the stake registry's guard limits.
+    GuardOverride,
+    /// Compliance hold placed on or lifted from a stake account.
+    Freeze,
+}
+
+impl UpdateKind {
+    /// Name used in the signed payload and in error messages.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Membership => "membership",
+            Self::FeeSchedule => "fee_schedule",
+            Self::GuardOverride => "guard_override",
+            Self::Freeze => "freeze",
+        }
+    }
+}
+
+impl std::fmt::Display for UpdateKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
 /// Raw governance update payload used to evolve membership.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GovernanceUpdate {
+    /// Action the update authorises; absent in updates written before kinds
+    /// existed, which are membership rotations.
+    #[serde(default)]
+    pub kind: UpdateKind,
     /// Replacement membership set expressed as base64 ed25519 public keys.
     pub new_members: Vec<String>,
     /// Optional metadata describing the rotation.
@@ -49,6 +92,30 @@ pub struct GovernanceUpdate {
     pub signatures: Vec<SignedApproval>,
 }
 
+impl GovernanceUpdate {
+    /// Fails unless the update authorises `kind`.
+    pub fn expect_kind(&self, kind: UpdateKind) -> Result<(), PolicyUpdateError> {
+        if self.kind == kind {
+            Ok(())
+        } else {
+            Err(PolicyUpdateError::WrongKind {
+                expected: kind,
+                actual: self.kind,
+            })
+        }
+    }
+
+    /// Adds `key`'s approval, replacing an earlier one by the same signer.
+    pub fn approve(&mut self, key: &KeyMaterial) -> Result<(), PolicyUpdateError> {
+        let canonical = canonical_update_payload(self)?;
+        let signer = encode_public_key_base64(&key.verifying);
+        let signature = encode_signature_base64(&sign_payload(&key.signing, &canonical));
+        self.signatures.retain(|approval| approval.signer != signer);
+        self.signatures.push(SignedApproval { signer, signature });
+        Ok(())
+    }
+}
+
 /// Signature authorising a governance update.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SignedApproval {
@@ -160,6 +227,17 @@ pub enum PolicyUpdateError {
     #[error("signature verification failed")]
     /// Update signatures failed to verify.
     BadSignature,
+    #[error("expected a {expected} update, got {actual}")]
+    /// Update authorises a different action than the one being applied.
+    WrongKind {
+        /// Kind the caller applies.
+        expected: UpdateKind,
+        /// Kind carried by the update.
+        actual: UpdateKind,
+    },
+    #[error("update leaves the policy with no members")]
+    /// Membership update would replace the member set with nothing.
+    EmptyMembership,
 }
 
 // ---------------------------------------------------------------------
@@ -276,6 +354,28 @@ impl MultisigPolicy {
         })
     }
 
+    /// Number of signer approvals an update needs.
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Governing signer keys (base64), sorted.
+    pub fn signers(&self) -> Vec<String> {
+        let mut signers: Vec<String> = self.signers.iter().map(encode_public_key_base64).collect();
+        signers.sort();
+        signers
+    }
+
+    /// Replaces the members without signer approval, for a rotation already
+    /// passed by a governance vote.
+    pub(crate) fn set_members(&mut self, new_members: &[String]) -> Result<(), PolicyUpdateError> {
+        self.members = new_members
+            .iter()
+            .map(|b64| decode_public_key(b64))
+            .collect::<Result<_, _>>()?;
+        self.persist()
+    }
+
     fn persist(&self) -> Result<(), PolicyUpdateError> {
         let state = MultisigState {
             threshold: self.threshold,
@@ -293,49 +393,20 @@ impl MembershipPolicy for MultisigPolicy {
     }
 
     fn verify_update(&self, update: &GovernanceUpdate) -> Result<(), PolicyUpdateError> {
-        if update.signatures.is_empty() {
-            return Err(PolicyUpdateError::Threshold {
-                required: self.threshold,
-                actual: 0,
-            });
-        }
         let canonical = canonical_update_payload(update)?;
-        let mut approvals = 0usize;
-        let mut seen: HashSet<[u8; PUBLIC_KEY_LENGTH]> = HashSet::new();
-        for approval in &update.signatures {
-            let signer = decode_public_key(&approval.signer)?;
-            if !self.signers.contains(&signer) {
-                return Err(PolicyUpdateError::Unauthorized);
-            }
-            if !seen.insert(signer.to_bytes()) {
-                continue;
-            }
-            let signature_bytes = BASE64
-                .decode(&approval.signature)
-                .map_err(|err| PolicyUpdateError::Decode(err.to_string()))?;
-            if signature_bytes.len() != SIGNATURE_LENGTH {
-                return Err(PolicyUpdateError::Decode("invalid signature length".into()));
-            }
-            let sig_array: [u8; SIGNATURE_LENGTH] = signature_bytes
-                .as_slice()
-                .try_into()
-                .expect("signature length checked");
-            let signature = Signature::from_bytes(&sig_array);
-            signer
-                .verify_strict(&canonical, &signature)
-                .map_err(|_| PolicyUpdateError::BadSignature)?;
-            approvals += 1;
-        }
-        if approvals < self.threshold {
-            return Err(PolicyUpdateError::Threshold {
-                required: self.threshold,
-                actual: approvals,
-            });
-        }
-        Ok(())
+        verify_approvals(
+            &self.signers,
+            self.threshold,
+            &update.signatures,
+            &canonical,
+        )
     }
 
     fn apply_update(&mut self, update: &GovernanceUpdate) -> Result<(), PolicyUpdateError> {
+        update.expect_kind(UpdateKind::Membership)?;
+        if update.new_members.is_empty() {
+            return Err(PolicyUpdateError::EmptyMembership);
+        }
         self.verify_update(update)?;
         let mut members = Vec::new();
         for base64 in &update.new_members {
@@ -506,39 +577,12 @@ impl StakePolicy {
         }
         let metadata = self.parse_metadata(update)?;
         let canonical = canonical_stake_payload(&metadata)?;
-
-        let mut approvals = 0usize;
-        let mut seen: HashSet<[u8; PUBLIC_KEY_LENGTH]> = HashSet::new();
-        for approval in &update.signatures {
-            let signer = decode_public_key(&approval.signer)?;
-            if !self.signers.contains(&signer) {
-                return Err(PolicyUpdateError::Unauthorized);
-            }
-            if !seen.insert(signer.to_bytes()) {
-                continue;
-            }
-            let signature_bytes = BASE64
-                .decode(&approval.signature)
-                .map_err(|err| PolicyUpdateError::Decode(err.to_string()))?;
-            if signature_bytes.len() != SIGNATURE_LENGTH {
-                return Err(PolicyUpdateError::Decode("invalid signature length".into()));
-            }
-            let sig_array: [u8; SIGNATURE_LENGTH] = signature_bytes
-                .as_slice()
-                .try_into()
-                .expect("signature length checked");
-            let signature = Signature::from_bytes(&sig_array);
-            signer
-                .verify_strict(&canonical, &signature)
-                .map_err(|_| PolicyUpdateError::BadSignature)?;
-            approvals += 1;
-        }
-        if approvals < self.threshold {
-            return Err(PolicyUpdateError::Threshold {
-                required: self.threshold,
-                actual: approvals,
-            });
-        }
+        verify_approvals(
+            &self.signers,
+            self.threshold,
+            &update.signatures,
+            &canonical,
+        )?;
         for deposit in &metadata.deposits {
             if deposit.bond < self.bond_threshold {
                 return Err(PolicyUpdateError::Decode(format!(
@@ -617,10 +661,156 @@ impl MembershipPolicy for StakePolicy {
     }
 }
 
+// ---------------------------------------------------------------------
+// Submission fee schedule
+// ---------------------------------------------------------------------
+
+#[derive(Debug, Serialize, Deserialize)]
+struct FeeScheduleState {
+    threshold: usize,
+    signers: Vec<String>,
+    schedule: SubmissionFeeSchedule,
+}
+
+/// Proof submission fee schedule that only a K-of-N signer set may change.
+///
+/// Updates have kind [`UpdateKind::FeeSchedule`] and carry the replacement
+/// [`SubmissionFeeSchedule`] as their `metadata`.
+#[derive(Debug)]
+pub struct FeeScheduleConfig {
+    state_path: PathBuf,
+    threshold: usize,
+    signers: HashSet<VerifyingKey>,
+    schedule: SubmissionFeeSchedule,
+}
+
+impl FeeScheduleConfig {
+    /// Restores the fee schedule from its JSON state file.
+    pub fn load(path: &Path) -> Result<Self, PolicyUpdateError> {
+        let contents =
+            fs::read_to_string(path).map_err(|err| PolicyUpdateError::Io(err.to_string()))?;
+        let state: FeeScheduleState = serde_json::from_str(&contents)
+            .map_err(|err| PolicyUpdateError::Decode(err.to_string()))?;
+        let signers = state
+            .signers
+            .iter()
+            .map(|b64| decode_public_key(b64))
+            .collect::<Result<HashSet<_>, _>>()?;
+        Ok(Self {
+            state_path: path.to_path_buf(),
+            threshold: state.threshold,
+            signers,
+            schedule: state.schedule,
+        })
+    }
+
+    /// Currently active fee schedule.
+    pub fn schedule(&self) -> &SubmissionFeeSchedule {
+        &self.schedule
+    }
+
+    /// Checks the update signatures and decodes the proposed schedule.
+    pub fn verify_update(
+        &self,
+        update: &GovernanceUpdate,
+    ) -> Result<SubmissionFeeSchedule, PolicyUpdateError> {
+        update.expect_kind(UpdateKind::FeeSchedule)?;
+        let metadata = update
+            .metadata
+            .as_ref()
+            .ok_or_else(|| PolicyUpdateError::Decode("fee update requires metadata".into()))?;
+        let schedule: SubmissionFeeSchedule = serde_json::from_value(metadata.clone())
+            .map_err(|err| PolicyUpdateError::Decode(err.to_string()))?;
+        let canonical = canonical_update_payload(update)?;
+        verify_approvals(
+            &self.signers,
+            self.threshold,
+            &update.signatures,
+            &canonical,
+        )?;
+        Ok(schedule)
+    }
+
+    /// Verifies and applies an update, persisting the new schedule.
+    pub fn apply_update(&mut self, update: &GovernanceUpdate) -> Result<(), PolicyUpdateError> {
+        self.schedule = self.verify_update(update)?;
+        self.persist()
+    }
+
+    /// Installs `schedule` without signer approval, for a change already
+    /// passed by a governance vote.
+    pub(crate) fn set_schedule(
+        &mut self,
+        schedule: SubmissionFeeSchedule,
+    ) -> Result<(), PolicyUpdateError> {
+        self.schedule = schedule;
+        self.persist()
+    }
+
+    fn persist(&self) -> Result<(), PolicyUpdateError> {
+        let state = FeeScheduleState {
+            threshold: self.threshold,
+            signers: self.signers.iter().map(encode_public_key_base64).collect(),
+            schedule: self.schedule.clone(),
+        };
+        let pretty = serde_json::to_string_pretty(&state)
+            .map_err(|err| PolicyUpdateError::Decode(err.to_string()))?;
+        fs::write(&self.state_path, pretty).map_err(|err| PolicyUpdateError::Io(err.to_string()))
+    }
+}
+
 // ---------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------
 
+/// Counts distinct authorised approvals over `canonical`, failing below `threshold`.
+fn verify_approvals(
+    signers: &HashSet<VerifyingKey>,
+    threshold: usize,
+    approvals: &[SignedApproval],
+    canonical: &[u8],
+) -> Result<(), PolicyUpdateError> {
+    if approvals.is_empty() {
+        return Err(PolicyUpdateError::Threshold {
+            required: threshold,
+            actual: 0,
+        });
+    }
+    let mut count = 0usize;
+    let mut seen: HashSet<[u8; PUBLIC_KEY_LENGTH]> = HashSet::new();
+    for approval in approvals {
+        let signer = decode_public_key(&approval.signer)?;
+        if !signers.contains(&signer) {
+            return Err(PolicyUpdateError::Unauthorized);
+        }
+        if !seen.insert(signer.to_bytes()) {
+            continue;
+        }
+        let signature_bytes = BASE64
+            .decode(&approval.signature)
+            .map_err(|err| PolicyUpdateError::Decode(err.to_string()))?;
+        if signature_bytes.len() != SIGNATURE_LENGTH {
+            return Err(PolicyUpdateError::Decode("invalid signature length".into()));
+        }
+        let sig_array: [u8; SIGNATURE_LENGTH] = signature_bytes
+            .as_slice()
+            .try_into()
+            .expect("signature length checked");
+        let signature = Signature::from_bytes(&sig_array);
+        signer
+            .verify_strict(canonical, &signature)
+            .map_err(|_| PolicyUpdateError::BadSignature)?;
+        count += 1;
+    }
+    if count < threshold {
+        return Err(PolicyUpdateError::Threshold {
+            required: threshold,
+            actual: count,
+        });
+    }
+    Ok(())
+}
+
 fn decode_public_key(input: &str) -> Result<VerifyingKey, PolicyUpdateError> {
     let decoded = BASE64
         .decode(input)
@@ -634,13 +824,21 @@ fn decode_public_key(input: &str) -> Result<VerifyingKey, PolicyUpdateError> {
         .map_err(|err| PolicyUpdateError::Decode(err.to_string()))
 }
 
+/// Bytes signed by update approvals.
+///
+/// Membership rotations keep the untagged `{new_members, metadata}` payload so
+/// existing approvals stay valid; every other kind leads with its `kind` tag,
+/// so its bytes never match a membership payload or another kind's.
 fn canonical_update_payload(update: &GovernanceUpdate) -> Result<Vec<u8>, PolicyUpdateError> {
     #[derive(Serialize)]
     struct Canonical<'a> {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        kind: Option<&'static str>,
         new_members: &'a [String],
         metadata: &'a Option<serde_json::Value>,
     }
     serde_json::to_vec(&Canonical {
+        kind: (update.kind != UpdateKind::Membership).then(|| update.kind.label()),
         new_members: &update.new_members,
         metadata: &update.metadata,
     })
@@ -0,0 +1,415 @@
+#![cfg(feature = "grpc")]
+
+//! Typed gRPC node API (`proto/powerhouse_node.proto`).
+//!
+//! Integrators in other languages generate clients from the proto file rather
+//! than scraping CLI output.  The server reads the same log directory the node
+//! writes, so every RPC is backed by an existing library function: anchors by
+//! the swarm's log replay, checkpoints by [`load_latest_checkpoint`], accounts
+//! by [`StakeRegistry`] state proofs, finality events by the attestation log
+//! ([`load_attestations`]) and submissions by the direct-delivery inbox.
+
+use crate::julian::anchor_digest;
+use crate::net::audit::{load_attestations, AnchorAttestation};
+use crate::net::checkpoint::{load_latest_checkpoint, AnchorCheckpoint};
+use crate::net::direct::{
+    append_jsonl, DirectInboxEntry, DirectMessage, DirectPayload, ProofSubmission,
+    DIRECT_INBOX_FILE,
+};
+use crate::net::schema::{network_id, AnchorJson};
+use crate::net::stake_registry::StakeRegistry;
+use crate::net::swarm::load_anchor_from_logs;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use std::{
+    net::SocketAddr,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status};
+
+/// Code generated from `proto/powerhouse_node.proto`.
+#[allow(missing_docs, clippy::all)]
+pub mod proto {
+    tonic::include_proto!("mfenx.powerhouse.v1");
+}
+
+use proto::node_server::{Node, NodeServer};
+
+/// Sender id recorded for submissions received over gRPC.
+pub const GRPC_SENDER: &str = "grpc";
+
+const DEFAULT_EVENT_POLL: Duration = Duration::from_secs(1);
+const EVENT_BUFFER: usize = 64;
+
+/// Errors produced by the gRPC server.
+#[derive(Debug, thiserror::Error)]
+pub enum GrpcError {
+    /// The listener or HTTP/2 transport failed.
+    #[error("gRPC transport error: {0}")]
+    Transport(String),
+}
+
+/// Settings for [`run_grpc_server`].
+#[derive(Debug, Clone)]
+pub struct GrpcConfig {
+    /// HTTP/2 listen socket.
+    pub listen: SocketAddr,
+    /// Log directory of the node being served.
+    pub log_dir: PathBuf,
+    /// Node identifier reported in anchors.
+    pub node_id: String,
+    /// Quorum reported in anchors.
+    pub quorum: usize,
+    /// Stake registry backing `GetAccount`; the RPC fails without one.
+    pub registry_path: Option<PathBuf>,
+    /// How often `StreamFinalityEvents` re-reads the attestation log.
+    pub event_poll: Duration,
+}
+
+impl GrpcConfig {
+    /// Creates a configuration with quorum 1, no registry and a one second poll.
+    pub fn new(listen: SocketAddr, log_dir: PathBuf, node_id: impl Into<String>) -> Self {
+        Self {
+            listen,
+            log_dir,
+            node_id: node_id.into(),
+            quorum: 1,
+            registry_path: None,
+            event_poll: DEFAULT_EVENT_POLL,
+        }
+    }
+}
+
+/// [`proto::node_server::Node`] implementation over a node's log directory.
+#[derive(Debug, Clone)]
+pub struct NodeApi {
+    cfg: GrpcConfig,
+}
+
+impl NodeApi {
+    /// Serves the node described by `cfg`.
+    pub fn new(cfg: GrpcConfig) -> Self {
+        Self { cfg }
+    }
+}
+
+/// Serves the node API until the listener fails.
+pub async fn run_grpc_server(cfg: GrpcConfig) -> Result<(), GrpcError> {
+    let listen = cfg.listen;
+    println!(
+        "QSYS|mod=GRPC|evt=LISTEN|addr={listen}|log_dir={}",
+        cfg.log_dir.display()
+    );
+    tonic::transport::Server::builder()
+        .add_service(NodeServer::new(NodeApi::new(cfg)))
+        .serve(listen)
+        .await
+        .map_err(|err| GrpcError::Transport(err.to_string()))
+}
+
+#[tonic::async_trait]
+impl Node for NodeApi {
+    type StreamFinalityEventsStream = ReceiverStream<Result<proto::FinalityEvent, Status>>;
+
+    async fn submit_proof(
+        &self,
+        request: Request<proto::SubmitProofRequest>,
+    ) -> Result<Response<proto::SubmitProofResponse>, Status> {
+        let peer = request
+            .remote_addr()
+            .map(|addr| format!("{GRPC_SENDER}:{addr}"))
+            .unwrap_or_else(|| GRPC_SENDER.to_string());
+        let request = request.into_inner();
+        let submission = ProofSubmission {
+            statement: request.statement,
+            proof: BASE64.encode(&request.proof),
+            public_key: request.public_key,
+            signature: request.signature,
+            submitted_ms: request.submitted_ms,
+        };
+        let message = DirectMessage::new(
+            network_id(),
+            GRPC_SENDER,
+            now_millis(),
+            DirectPayload::ProofSubmission(submission),
+        );
+        message
+            .validate(network_id())
+            .map_err(Status::invalid_argument)?;
+        let digest = message.digest();
+        let entry = DirectInboxEntry {
+            peer_id: peer,
+            message,
+        };
+        append_jsonl(&self.cfg.log_dir.join(DIRECT_INBOX_FILE), &entry)
+            .map_err(|err| Status::internal(err.to_string()))?;
+        Ok(Response::new(proto::SubmitProofResponse {
+            message_digest: hex::encode(digest),
+        }))
+    }
+
+    async fn get_anchor(
+        &self,
+        _request: Request<proto::GetAnchorRequest>,
+    ) -> Result<Response<proto::Anchor>, Status> {
+        let ledger = load_anchor_from_logs(&self.cfg.log_dir)
+            .map_err(|err| Status::internal(err.to_string()))?;
+        let anchor = AnchorJson::from_ledger(
+            self.cfg.node_id.clone(),
+            self.cfg.quorum,
+            &ledger,
+            now_millis(),
+            Vec::new(),
+            None,
+        )
+        .map_err(|err| Status::internal(err.to_string()))?;
+        anchor_message(&anchor)
+            .map(Response::new)
+            .map_err(Status::internal)
+    }
+
+    async fn get_checkpoint(
+        &self,
+        _request: Request<proto::GetCheckpointRequest>,
+    ) -> Result<Response<proto::Checkpoint>, Status> {
+        let checkpoint = load_latest_checkpoint(&self.cfg.log_dir)
+            .map_err(|err| Status::internal(err.to_string()))?
+            .ok_or_else(|| Status::not_found("no checkpoint recorded"))?;
+        checkpoint_message(&checkpoint)
+            .map(Response::new)
+            .map_err(Status::internal)
+    }
+
+    async fn get_account(
+        &self,
+        request: Request<proto::GetAccountRequest>,
+    ) -> Result<Response<proto::Account>, Status> {
+        let public_key = request.into_inner().public_key;
+        let path = self
+            .cfg
+            .registry_path
+            .as_deref()
+            .ok_or_else(|| Status::failed_precondition("server has no stake registry"))?;
+        let registry = StakeRegistry::load(path).map_err(Status::internal)?;
+        let proof = serde_json::to_string(&registry.account_proof(&public_key))
+            .map_err(|err| Status::internal(err.to_string()))?;
+        let account = registry.account(&public_key);
+        Ok(Response::new(proto::Account {
+            exists: account.is_some(),
+            balance: account.map_or(0, |account| account.balance),
+            stake: account.map_or(0, |account| account.stake),
+            slashed: account.is_some_and(|account| account.slashed),
+            transfer_nonce: account.map_or(0, |account| account.transfer_nonce),
+            registry_root: hex::encode(registry.state_root()),
+            proof_json: proof,
+            public_key,
+        }))
+    }
+
+    async fn stream_finality_events(
+        &self,
+        request: Request<proto::StreamFinalityEventsRequest>,
+    ) -> Result<Response<Self::StreamFinalityEventsStream>, Status> {
+        let since_ms = request.into_inner().since_ms;
+        let log_dir = self.cfg.log_dir.clone();
+        let poll = self.cfg.event_poll;
+        // Fail fast on an unreadable log instead of opening an empty stream.
+        load_attestations(&log_dir, since_ms).map_err(|err| Status::internal(err.to_string()))?;
+        let (tx, rx) = mpsc::channel(EVENT_BUFFER);
+        tokio::spawn(async move {
+            let mut sent = 0usize;
+            loop {
+                match load_attestations(&log_dir, since_ms) {
+                    Ok(attestations) => {
+                        for attestation in attestations.iter().skip(sent) {
+                            if tx
+                                .send(finality_event(attestation).map_err(Status::internal))
+                                .await
+                                .is_err()
+                            {
+                                return;
+                            }
+                        }
+                        sent = sent.max(attestations.len());
+                    }
+                    Err(err) => {
+                        let _ = tx.send(Err(Status::internal(err.to_string()))).await;
+                        return;
+                    }
+                }
+                tokio::select! {
+                    _ = tx.closed() => return,
+                    _ = tokio::time::sleep(poll) => {}
+                }
+            }
+        });
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+}
+
+fn anchor_message(anchor: &AnchorJson) -> Result<proto::Anchor, String> {
+    let json = serde_json::to_string(anchor).map_err(|err| err.to_string())?;
+    let ledger = anchor
+        .clone()
+        .into_ledger()
+        .map_err(|err| err.to_string())?;
+    Ok(proto::Anchor {
+        node_id: anchor.node_id.clone(),
+        network: anchor.network.clone(),
+        entries: anchor
+            .entries
+            .iter()
+            .map(|entry| proto::AnchorEntry {
+                statement: entry.statement.clone(),
+                hashes: entry.hashes.clone(),
+                merkle_root: entry.merkle_root.clone().unwrap_or_default(),
+            })
+            .collect(),
+        quorum: anchor.quorum as u64,
+        timestamp_ms: anchor.timestamp_ms,
+        anchor_digest: hex::encode(anchor_digest(&ledger)),
+        registry_root: anchor.registry_root.clone().unwrap_or_default(),
+        json,
+    })
+}
+
+fn checkpoint_message(checkpoint: &AnchorCheckpoint) -> Result<proto::Checkpoint, String> {
+    let json = serde_json::to_string(checkpoint).map_err(|err| err.to_string())?;
+    Ok(proto::Checkpoint {
+        epoch: checkpoint.epoch,
+        anchor: Some(anchor_message(&checkpoint.anchor)?),
+        signatures: checkpoint
+            .signatures
+            .iter()
+            .map(|signature| proto::CheckpointSignature {
+                node_id: signature.node_id.clone(),
+                public_key: signature.public_key.clone(),
+                signature: signature.signature.clone(),
+            })
+            .collect(),
+        log_cutoff: checkpoint.log_cutoff.clone().unwrap_or_default(),
+        registry_root: checkpoint.registry_root.clone().unwrap_or_default(),
+        json,
+    })
+}
+
+fn finality_event(attestation: &AnchorAttestation) -> Result<proto::FinalityEvent, String> {
+    let json = serde_json::to_string(attestation).map_err(|err| err.to_string())?;
+    Ok(proto::FinalityEvent {
+        node_id: attestation.node_id.clone(),
+        public_key: attestation.public_key.clone(),
+        anchor_digest: attestation.anchor_digest.clone(),
+        entries: attestation.entries,
+        finalized_ms: attestation.finalized_ms,
+        signature: attestation.signature.clone(),
+        json,
+    })
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::audit::append_attestation;
+    use crate::net::sign::{load_or_derive_keypair, Ed25519KeySource};
+    use crate::net::stake_registry::verify_account_proof;
+    use crate::net::SupplyCause;
+    use crate::SparseMerkleProof;
+    use tokio_stream::StreamExt;
+
+    #[tokio::test]
+    async fn node_api_serves_log_directory_state() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let log_dir = std::env::temp_dir().join(format!("power_house_grpc_{nanos}"));
+        std::fs::create_dir_all(&log_dir).unwrap();
+        let mut registry = StakeRegistry::default();
+        registry.mint("alice", 10, SupplyCause::Funding).unwrap();
+        let registry_path = log_dir.join("stake_registry.json");
+        registry.save(&registry_path).unwrap();
+        let mut cfg = GrpcConfig::new("127.0.0.1:0".parse().unwrap(), log_dir.clone(), "node-a");
+        cfg.registry_path = Some(registry_path);
+        cfg.event_poll = Duration::from_millis(10);
+        let api = NodeApi::new(cfg);
+
+        let anchor = api
+            .get_anchor(Request::new(proto::GetAnchorRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(anchor.node_id, "node-a");
+        assert_eq!(anchor.entries.len(), 1);
+        let ledger = crate::julian_genesis_anchor();
+        assert_eq!(anchor.anchor_digest, hex::encode(anchor_digest(&ledger)));
+        let status = api
+            .get_checkpoint(Request::new(proto::GetCheckpointRequest {}))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+
+        let account = api
+            .get_account(Request::new(proto::GetAccountRequest {
+                public_key: "alice".to_string(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(account.exists);
+        assert_eq!(account.balance, 10);
+        let proof: SparseMerkleProof = serde_json::from_str(&account.proof_json).unwrap();
+        assert_eq!(
+            verify_account_proof(
+                &registry.state_root(),
+                "alice",
+                registry.account("alice"),
+                &proof
+            ),
+            Ok(())
+        );
+
+        let key = load_or_derive_keypair(&Ed25519KeySource::Seed("grpc-test".into())).unwrap();
+        let signed = ProofSubmission::sign("statement", b"proof", &key, 5);
+        let mut request = proto::SubmitProofRequest {
+            statement: signed.statement.clone(),
+            proof: b"proof".to_vec(),
+            public_key: signed.public_key.clone(),
+            signature: signed.signature.clone(),
+            submitted_ms: 5,
+        };
+        api.submit_proof(Request::new(request.clone()))
+            .await
+            .unwrap();
+        let inbox = std::fs::read_to_string(log_dir.join(DIRECT_INBOX_FILE)).unwrap();
+        assert_eq!(inbox.lines().count(), 1);
+        request.statement = "other statement".to_string();
+        let status = api.submit_proof(Request::new(request)).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+        let mut events = api
+            .stream_finality_events(Request::new(proto::StreamFinalityEventsRequest {
+                since_ms: 100,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        for finalized_ms in [50, 150] {
+            let attestation =
+                AnchorAttestation::sign(network_id(), "node-a", &key, &[7u8; 32], 1, finalized_ms);
+            append_attestation(&log_dir, &attestation).unwrap();
+        }
+        let event = events.next().await.unwrap().unwrap();
+        assert_eq!(event.finalized_ms, 150);
+        assert_eq!(event.anchor_digest, hex::encode([7u8; 32]));
+        let _ = std::fs::remove_dir_all(&log_dir);
+    }
+}
@@ -0,0 +1,356 @@
+#![cfg(feature = "net")]
+
+//! Bounded pool of native transactions waiting for a block.
+//!
+//! Every pending transaction carries the second it was admitted.  Entries
+//! older than the pool's TTL are expired, and once the pool is full a new
+//! transaction only gets in by displacing the cheapest one, ranked by priority
+//! fee and then max fee.  Only the last pending nonce of a sender is ever
+//! displaced, so eviction never opens a nonce gap.  Dropping a transaction
+//! drops the sender's later nonces with it, since they can no longer execute.
+//!
+//! The pool is a cheap shared handle: the native chain runtime mutates it, and
+//! the wallet RPC and the metrics endpoint read from clones.
+
+use crate::net::native_chain::NativeTransaction;
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+
+/// How long a transaction may wait before it is expired.
+pub const DEFAULT_PENDING_TTL: Duration = Duration::from_secs(600);
+/// Transactions held before the cheapest is displaced.
+pub const DEFAULT_MAX_PENDING: usize = 4096;
+
+/// Why a transaction left the pool without being included in a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvictionReason {
+    /// Waited longer than the TTL.
+    Expired = 0,
+    /// Displaced by a better-paying transaction while the pool was full.
+    PoolFull = 1,
+    /// Its nonce was consumed by a finalized block.
+    Stale = 2,
+}
+
+impl EvictionReason {
+    /// Every reason, in metric order.
+    pub const ALL: [Self; 3] = [Self::Expired, Self::PoolFull, Self::Stale];
+
+    /// Metric label and log name.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Expired => "ttl",
+            Self::PoolFull => "pool_full",
+            Self::Stale => "stale",
+        }
+    }
+}
+
+/// A pending transaction and the unix second it was admitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingEntry {
+    /// The waiting transaction.
+    pub tx: NativeTransaction,
+    /// Unix second of admission; the TTL counts from here.
+    pub received_at: u64,
+}
+
+struct PoolInner {
+    entries: BTreeMap<String, PendingEntry>,
+    ttl_secs: u64,
+    max_size: usize,
+    evictions: [u64; EvictionReason::ALL.len()],
+}
+
+/// Shared handle to the pending transaction pool.
+#[derive(Clone)]
+pub struct PendingPool {
+    inner: Arc<Mutex<PoolInner>>,
+}
+
+impl Default for PendingPool {
+    fn default() -> Self {
+        Self::new(DEFAULT_PENDING_TTL, DEFAULT_MAX_PENDING)
+    }
+}
+
+impl PendingPool {
+    /// Empty pool expiring entries after `ttl` and holding at most `max_size`.
+    pub fn new(ttl: Duration, max_size: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(PoolInner {
+                entries: BTreeMap::new(),
+                ttl_secs: ttl.as_secs().max(1),
+                max_size: max_size.max(1),
+                evictions: [0; EvictionReason::ALL.len()],
+            })),
+        }
+    }
+
+    /// Replaces the TTL and size bound; a smaller bound applies on the next admission.
+    pub fn set_limits(&self, ttl: Duration, max_size: usize) {
+        let mut inner = self.lock();
+        inner.ttl_secs = ttl.as_secs().max(1);
+        inner.max_size = max_size.max(1);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, PoolInner> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Transactions waiting.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Whether nothing is waiting.
+    pub fn is_empty(&self) -> bool {
+        self.lock().entries.is_empty()
+    }
+
+    /// Whether a transaction with `hash` is waiting.
+    pub fn contains(&self, hash: &str) -> bool {
+        self.lock().entries.contains_key(hash)
+    }
+
+    /// Pending nonces of `sender`.
+    pub fn nonces_of(&self, sender: &str) -> Vec<u64> {
+        self.lock()
+            .entries
+            .values()
+            .filter(|entry| entry.tx.from == sender)
+            .map(|entry| entry.tx.nonce)
+            .collect()
+    }
+
+    /// Every waiting transaction, in hash order.
+    pub fn transactions(&self) -> Vec<NativeTransaction> {
+        self.lock()
+            .entries
+            .values()
+            .map(|entry| entry.tx.clone())
+            .collect()
+    }
+
+    /// Waiting transactions sent from or to `address`, ordered by sender and nonce.
+    pub fn for_address(&self, address: &str) -> Vec<PendingEntry> {
+        let mut entries = self
+            .lock()
+            .entries
+            .values()
+            .filter(|entry| entry.tx.from == address || entry.tx.to == address)
+            .cloned()
+            .collect::<Vec<_>>();
+        entries.sort_by(|a, b| (&a.tx.from, a.tx.nonce).cmp(&(&b.tx.from, b.tx.nonce)));
+        entries
+    }
+
+    /// Unix second at which an entry admitted at `received_at` expires.
+    pub fn expires_at(&self, received_at: u64) -> u64 {
+        received_at.saturating_add(self.lock().ttl_secs)
+    }
+
+    /// Admits `tx`, displacing the cheapest displaceable entry when full.
+    ///
+    /// Returns the hashes displaced to make room.  The caller has already
+    /// checked that the nonce follows the sender's last pending one.
+    pub fn admit(&self, tx: NativeTransaction, now: u64) -> Result<Vec<String>, String> {
+        let mut inner = self.lock();
+        let mut evicted = Vec::new();
+        if inner.entries.len() >= inner.max_size {
+            let incoming = fee_rank(&tx);
+            let Some(victim) = cheapest_tail(&inner.entries, &tx.from) else {
+                return Err("transaction pool is full".to_string());
+            };
+            if fee_rank(&inner.entries[&victim].tx) >= incoming {
+                return Err(format!(
+                    "transaction pool is full; fee must exceed {}",
+                    inner.entries[&victim].tx.max_priority_fee_per_gas
+                ));
+            }
+            evicted = inner.evict(&victim, EvictionReason::PoolFull);
+        }
+        inner.entries.insert(
+            tx.hash.clone(),
+            PendingEntry {
+                tx,
+                received_at: now,
+            },
+        );
+        Ok(evicted)
+    }
+
+    /// Removes transactions included in a block.
+    pub fn remove_included<'a>(&self, hashes: impl IntoIterator<Item = &'a str>) {
+        let mut inner = self.lock();
+        for hash in hashes {
+            inner.entries.remove(hash);
+        }
+    }
+
+    /// Expires every entry older than the TTL, with the sender's later nonces.
+    pub fn expire(&self, now: u64) -> Vec<String> {
+        let mut inner = self.lock();
+        let ttl = inner.ttl_secs;
+        let stale = inner
+            .entries
+            .values()
+            .filter(|entry| entry.received_at.saturating_add(ttl) <= now)
+            .map(|entry| entry.tx.hash.clone())
+            .collect::<Vec<_>>();
+        let mut evicted = Vec::new();
+        for hash in stale {
+            evicted.extend(inner.evict(&hash, EvictionReason::Expired));
+        }
+        evicted
+    }
+
+    /// Drops entries whose nonce is below `next_nonce(sender)`.
+    pub fn drop_stale(&self, next_nonce: impl Fn(&str) -> u64) -> Vec<String> {
+        let mut inner = self.lock();
+        let stale = inner
+            .entries
+            .values()
+            .filter(|entry| entry.tx.nonce < next_nonce(&entry.tx.from))
+            .map(|entry| entry.tx.hash.clone())
+            .collect::<Vec<_>>();
+        for hash in &stale {
+            inner.entries.remove(hash);
+            inner.evictions[EvictionReason::Stale as usize] += 1;
+        }
+        stale
+    }
+
+    /// Transactions evicted so far for `reason`.
+    pub fn evictions_total(&self, reason: EvictionReason) -> u64 {
+        self.lock().evictions[reason as usize]
+    }
+
+    /// Prometheus exposition of the pool size and evictions by reason.
+    pub fn render_metrics(&self) -> String {
+        let inner = self.lock();
+        let mut out = String::new();
+        let _ = writeln!(out, "# TYPE powerhouse_mempool_pending gauge");
+        let _ = writeln!(out, "powerhouse_mempool_pending {}", inner.entries.len());
+        let _ = writeln!(out, "# TYPE powerhouse_mempool_capacity gauge");
+        let _ = writeln!(out, "powerhouse_mempool_capacity {}", inner.max_size);
+        let _ = writeln!(out, "# TYPE powerhouse_mempool_evictions_total counter");
+        for reason in EvictionReason::ALL {
+            let _ = writeln!(
+                out,
+                "powerhouse_mempool_evictions_total{{reason=\"{}\"}} {}",
+                reason.label(),
+                inner.evictions[reason as usize]
+            );
+        }
+        out
+    }
+}
+
+impl PoolInner {
+    /// Removes `hash` and every later nonce of the same sender.
+    fn evict(&mut self, hash: &str, reason: EvictionReason) -> Vec<String> {
+        let Some(entry) = self.entries.get(hash) else {
+            return Vec::new();
+        };
+        let (sender, nonce) = (entry.tx.from.clone(), entry.tx.nonce);
+        let doomed = self
+            .entries
+            .values()
+            .filter(|entry| entry.tx.from == sender && entry.tx.nonce >= nonce)
+            .map(|entry| entry.tx.hash.clone())
+            .collect::<Vec<_>>();
+        for hash in &doomed {
+            self.entries.remove(hash);
+        }
+        self.evictions[reason as usize] += doomed.len() as u64;
+        doomed
+    }
+}
+
+/// Last pending nonce of each sender other than `skip`, cheapest first.
+fn cheapest_tail(entries: &BTreeMap<String, PendingEntry>, skip: &str) -> Option<String> {
+    let mut tails: BTreeMap<&str, &PendingEntry> = BTreeMap::new();
+    for entry in entries.values() {
+        if entry.tx.from == skip {
+            continue;
+        }
+        let tail = tails.entry(&entry.tx.from).or_insert(entry);
+        if entry.tx.nonce > tail.tx.nonce {
+            *tail = entry;
+        }
+    }
+    tails
+        .into_values()
+        .min_by_key(|entry| (fee_rank(&entry.tx), entry.received_at))
+        .map(|entry| entry.tx.hash.clone())
+}
+
+fn fee_rank(tx: &NativeTransaction) -> (u128, u128) {
+    (
+        parse_quantity(&tx.max_priority_fee_per_gas),
+        parse_quantity(&tx.max_fee_per_gas),
+    )
+}
+
+fn parse_quantity(value: &str) -> u128 {
+    let digits = value.trim_start_matches("0x");
+    u128::from_str_radix(digits, 16).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(hash: &str, from: &str, nonce: u64, tip: u128) -> NativeTransaction {
+        NativeTransaction {
+            hash: hash.to_string(),
+            raw: String::new(),
+            from: from.to_string(),
+            to: "0xdead".to_string(),
+            nonce,
+            value_wei: "0x0".to_string(),
+            value_units: 0,
+            gas_limit: 21_000,
+            max_fee_per_gas: format!("{:#x}", tip),
+            max_priority_fee_per_gas: format!("{:#x}", tip),
+            input: "0x".to_string(),
+            y_parity: 0,
+            r: String::new(),
+            s: String::new(),
+        }
+    }
+
+    #[test]
+    fn evicts_cheapest_tail_and_expires_with_dependents() {
+        let pool = PendingPool::new(Duration::from_secs(60), 3);
+        pool.admit(tx("a0", "0xa", 0, 5), 100).unwrap();
+        pool.admit(tx("a1", "0xa", 1, 1), 100).unwrap();
+        pool.admit(tx("b0", "0xb", 0, 2), 110).unwrap();
+
+        // a1 is the cheapest tail; a0 is cheaper than b0 but is not a tail.
+        assert!(pool.admit(tx("c0", "0xc", 0, 1), 120).is_err());
+        assert_eq!(pool.admit(tx("c0", "0xc", 0, 3), 120).unwrap(), ["a1"]);
+        assert_eq!(pool.nonces_of("0xa"), [0]);
+        assert_eq!(pool.evictions_total(EvictionReason::PoolFull), 1);
+
+        pool.admit(tx("b1", "0xb", 1, 2), 130).unwrap_err();
+        pool.set_limits(Duration::from_secs(60), 8);
+        pool.admit(tx("b1", "0xb", 1, 9), 130).unwrap();
+        let mut expired = pool.expire(170);
+        expired.sort();
+        assert_eq!(expired, ["a0", "b0", "b1"]);
+        assert_eq!(pool.evictions_total(EvictionReason::Expired), 3);
+
+        assert_eq!(pool.drop_stale(|_| 1), ["c0"]);
+        assert!(pool.is_empty());
+        let metrics = pool.render_metrics();
+        assert!(metrics.contains("powerhouse_mempool_evictions_total{reason=\"stale\"} 1"));
+    }
+}
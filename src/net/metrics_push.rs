@@ -0,0 +1,174 @@
+#![cfg(feature = "net")]
+
+//! Push-mode delivery of the Prometheus exposition.
+//!
+//! Nodes behind an air gap cannot be scraped, so the swarm can instead POST
+//! the same text it serves on `/metrics` to a collector on a fixed interval
+//! (a Prometheus Pushgateway `/metrics/job/<job>` URL, or any endpoint that
+//! imports the text format, such as VictoriaMetrics
+//! `/api/v1/import/prometheus`).  Failed pushes back off exponentially up to
+//! [`MetricsPushConfig::max_backoff`] and are counted in the exposition
+//! itself, so the next successful push reports how many were lost.
+
+use std::time::Duration;
+use tokio::time;
+
+/// Content type of the Prometheus text exposition.
+pub const EXPOSITION_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+const DEFAULT_PUSH_INTERVAL: Duration = Duration::from_secs(15);
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(300);
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Settings for the metrics pusher.
+#[derive(Debug, Clone)]
+pub struct MetricsPushConfig {
+    /// Collector URL receiving `POST` requests with the exposition body.
+    pub endpoint: String,
+    /// Value of the `Authorization` header, e.g. `Bearer <token>`.
+    pub authorization: Option<String>,
+    /// Delay between pushes while the collector accepts them.
+    pub interval: Duration,
+    /// Upper bound for the delay after consecutive failures.
+    pub max_backoff: Duration,
+    /// Timeout applied to each HTTP request.
+    pub request_timeout: Duration,
+}
+
+impl MetricsPushConfig {
+    /// Creates a push configuration with a 15 s interval and 5 min backoff cap.
+    pub fn new(endpoint: String) -> Self {
+        Self {
+            endpoint,
+            authorization: None,
+            interval: DEFAULT_PUSH_INTERVAL,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+        }
+    }
+
+    /// Delay before the next push after `failures` consecutive failures.
+    ///
+    /// The interval doubles with every failure and is capped at
+    /// [`Self::max_backoff`] (or the interval, if that is larger).
+    pub fn next_delay(&self, failures: u32) -> Duration {
+        let factor = 1u32 << failures.min(16);
+        self.interval
+            .saturating_mul(factor)
+            .min(self.max_backoff.max(self.interval))
+    }
+}
+
+/// POSTs one rendered exposition to the collector.
+pub async fn push_metrics(
+    cfg: &MetricsPushConfig,
+    client: &reqwest::Client,
+    body: String,
+) -> Result<(), String> {
+    let mut request = client
+        .post(&cfg.endpoint)
+        .header(reqwest::header::CONTENT_TYPE, EXPOSITION_CONTENT_TYPE)
+        .body(body);
+    if let Some(authorization) = &cfg.authorization {
+        request = request.header(reqwest::header::AUTHORIZATION, authorization);
+    }
+    let response = request.send().await.map_err(|err| err.to_string())?;
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(format!("HTTP {status}: {body}"))
+}
+
+/// Pushes `render()` every interval until the task is cancelled.
+///
+/// `on_result` observes every attempt, letting the caller count failures in
+/// the metrics it renders.
+pub async fn run_metrics_pusher<R, F>(
+    cfg: MetricsPushConfig,
+    render: R,
+    on_result: F,
+) -> Result<(), String>
+where
+    R: Fn() -> String,
+    F: Fn(bool),
+{
+    let client = reqwest::Client::builder()
+        .timeout(cfg.request_timeout)
+        .build()
+        .map_err(|err| err.to_string())?;
+    println!(
+        "QSYS|mod=METRICS|evt=PUSH_START|endpoint={}|interval_ms={}",
+        cfg.endpoint,
+        cfg.interval.as_millis()
+    );
+    let mut failures = 0u32;
+    loop {
+        match push_metrics(&cfg, &client, render()).await {
+            Ok(()) => {
+                if failures > 0 {
+                    println!("QSYS|mod=METRICS|evt=PUSH_RECOVERED|failures={failures}");
+                }
+                failures = 0;
+                on_result(true);
+            }
+            Err(err) => {
+                failures = failures.saturating_add(1);
+                on_result(false);
+                eprintln!("QSYS|mod=METRICS|evt=PUSH_FAILED|failures={failures}|error={err}");
+            }
+        }
+        time::sleep(cfg.next_delay(failures)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn pushes_exposition_and_backs_off_on_failure() {
+        let mut cfg = MetricsPushConfig::new(String::new());
+        cfg.interval = Duration::from_secs(10);
+        cfg.max_backoff = Duration::from_secs(60);
+        assert_eq!(cfg.next_delay(0), Duration::from_secs(10));
+        assert_eq!(cfg.next_delay(2), Duration::from_secs(40));
+        assert_eq!(cfg.next_delay(30), Duration::from_secs(60));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let mut requests = Vec::new();
+            for status in ["500 Internal Server Error", "200 OK"] {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buf = vec![0u8; 4096];
+                let mut read = 0;
+                while !String::from_utf8_lossy(&buf[..read]).contains("up 1") {
+                    read += stream.read(&mut buf[read..]).await.unwrap();
+                }
+                requests.push(String::from_utf8_lossy(&buf[..read]).to_string());
+                let response =
+                    format!("HTTP/1.1 {status}\r\nConnection: close\r\nContent-Length: 0\r\n\r\n");
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+            requests
+        });
+
+        let mut cfg = MetricsPushConfig::new(format!("http://{addr}/metrics/job/node"));
+        cfg.authorization = Some("Bearer secret".to_string());
+        let client = reqwest::Client::new();
+        let first = push_metrics(&cfg, &client, "up 1\n".to_string()).await;
+        assert!(first.unwrap_err().starts_with("HTTP 500"));
+        push_metrics(&cfg, &client, "up 1\n".to_string())
+            .await
+            .unwrap();
+        let requests = server.await.unwrap();
+        assert!(requests[1].starts_with("POST /metrics/job/node "));
+        let lower = requests[1].to_ascii_lowercase();
+        assert!(lower.contains("authorization: bearer secret"));
+        assert!(lower.contains("content-type: text/plain; version=0.0.4"));
+    }
+}
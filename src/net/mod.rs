@@ -5,64 +5,279 @@
 //! anchors and envelopes, signing helpers, and the libp2p swarm orchestration
 //! that powers the public testnet mode.
 
+/// Canonical registry public key to EVM address index.
+pub mod address_index;
+/// Threshold alerts for operator-critical conditions and their sinks.
+pub mod alerts;
+/// Past anchors indexed by checkpoint epoch and digest.
+pub mod anchor_history;
+/// Signed anchor history archives for offline audit.
+pub mod archive;
 /// Availability attestations and quorum helpers.
 pub mod attestation;
+/// Signed finality attestations exported for external auditors.
+pub mod audit;
 /// Erasure coding helpers and commitments.
 pub mod availability;
+/// Signed, digest-verified backups of node state and all-or-nothing restore.
+pub mod backup;
+/// Per-epoch random beacon derived from finalized checkpoints.
+pub mod beacon;
 /// Data-availability blob schema and envelope types.
 pub mod blob;
+/// Gzip-compressed archive of pruned native-chain blocks.
+pub mod block_archive;
+/// Fault injection for outgoing gossip in stress rehearsals.
+pub mod chaos;
+/// Signed receipts recording the inputs and verdict of offline checks.
+pub mod check_receipt;
 /// Anchor checkpoint helpers for fast sync.
 pub mod checkpoint;
+/// Validating builders for node and RPC configs and the TOML config file.
+pub mod config;
+/// Content-addressed (CIDv1) export of transcripts and anchor archives.
+pub mod content;
+/// Generated workspaces for local multi-node test networks.
+pub mod devnet;
+/// DID-style identity documents binding node ids, keys and peer ids.
+pub mod did;
+/// Direct peer-to-peer delivery of proofs and transcript records.
+pub mod direct;
+/// Per-entry finality tracking and expiry of never-finalized entries.
+pub mod entry_gc;
+/// Publication of finalized fold digests to an EVM chain.
+pub mod evm_pin;
+/// Anchor finality latency and divergence timing.
+pub mod finality;
+/// Fork detection over finalized checkpoints and the safe mode it triggers.
+pub mod fork;
 /// Governance policy implementations for membership rotation.
 pub mod governance;
+/// Typed gRPC node API (requires the `grpc` feature).
+pub mod grpc;
+/// Bounded pool of pending native transactions with TTL and fee eviction.
+pub mod mempool;
+/// Push-mode delivery of the Prometheus exposition.
+pub mod metrics_push;
 /// Migration mode helpers and feature switches.
 pub mod migration;
 /// Quorum-finalized native transfer chain used by the wallet RPC.
 pub mod native_chain;
+/// Real and simulated clocks driving the node runtime.
+pub mod node_clock;
+/// Signed validator join requests and the admissions that apply them.
+pub mod onboarding;
+/// Governance-managed runtime parameters reloaded without a restart.
+pub mod parameters;
+/// Non-interactive identity passphrase sources and OS keychain access.
+pub mod passphrase;
+/// Persistent peer address book for bootstrap caching.
+pub mod peer_store;
 /// Identity admission policy helpers.
 pub mod policy;
+/// Validated allowlist and multisig key edits with a change history.
+pub mod policy_edit;
+/// Pseudo-contract view calls served through `eth_call`.
+pub mod precompile;
+/// Governance proposals, member votes, and execution of passed changes.
+pub mod proposal;
+/// Per-identity anchor rate limiting.
+pub mod rate_limit;
+/// Read-only wallet RPC replicas following a primary's native chain state.
+pub mod replica;
+/// Per-epoch reward distribution to checkpoint signers.
+pub mod rewards;
+/// Persistent registry of settled and faulted rollup commitments.
+pub mod rollup_store;
 /// MetaMask-compatible EVM JSON-RPC facade for native token balances.
 pub mod rpc;
+/// Priority tiers and load shedding for the wallet JSON-RPC.
+pub mod rpc_tiers;
 /// Machine-readable schema types shared across the network CLI and swarm.
 pub mod schema;
+/// Signed transcript log shipping client and aggregator server.
+pub mod shipping;
 /// Deterministic key derivation and ed25519 signing helpers.
 pub mod sign;
 /// Durable stake/balance store for fee enforcement and slashing.
 pub mod stake_registry;
+/// JSON and SQLite persistence backends for registry and native-chain state.
+pub mod storage;
 /// Libp2p orchestration layer and networking runtime.
 pub mod swarm;
 /// Signed validator registration and identity validation.
 pub mod validator_registry;
+/// Signed webhook notifications for ledger, finality, and fork events.
+pub mod webhooks;
 
+pub use address_index::{
+    derive_evm_address, registry_key_address, AddressIndex, ADDRESS_DERIVATION, ADDRESS_INDEX_FILE,
+    SCHEMA_ADDRESS_INDEX,
+};
+pub use alerts::{
+    deliver_alert, Alert, AlertConfig, AlertError, AlertKind, AlertMonitor, AlertSink, AlertState,
+    SCHEMA_ALERT,
+};
+pub use anchor_history::{AnchorHistory, AnchorHistoryError, HistoricalAnchor};
+pub use archive::{
+    archive_link, export_archive, verify_archive, AnchorArchive, ArchiveEntry, ArchiveError,
+    ArchiveSummary, SCHEMA_ANCHOR_ARCHIVE,
+};
 pub use attestation::{aggregate_attestations, Attestation, AttestationQuorum};
-pub use availability::{encode_shares, share_proof, verify_sample, ShareCommitment};
+pub use audit::{
+    append_attestation, export_attestations, load_attestations, verify_attestation_bundle,
+    AnchorAttestation, AttestationBundle, AttestationBundleSummary, AuditError, ATTESTATIONS_FILE,
+    SCHEMA_ANCHOR_ATTESTATION, SCHEMA_ATTESTATION_BUNDLE,
+};
+pub use availability::{
+    encode_shares, pedersen_share_proof, share_proof, verify_sample, ShareCommitment,
+};
+pub use backup::{
+    create_backup, looks_like_private_key, restore_backup, verify_backup, BackupError, BackupFile,
+    BackupManifest, BackupRole, BackupSources, RestoreTargets, BACKUP_MANIFEST_ENTRY,
+    SCHEMA_BACKUP_MANIFEST,
+};
+pub use beacon::{derive_beacon, load_beacon, BeaconError, BeaconValue, SCHEMA_BEACON};
 pub use blob::{BlobCodecError, BlobEnvelope, BlobJson, SCHEMA_BLOB, TOPIC_BLOBS};
+pub use block_archive::{
+    find_archived_block, find_archived_transaction, inspect_segment, list_segments, read_segment,
+    write_segment, ArchiveSegment, SegmentSummary, BLOCK_ARCHIVE_DIR,
+};
+pub use chaos::ChaosConfig;
+pub use check_receipt::{digest_input_path, CheckReceipt, SCHEMA_CHECK_RECEIPT};
 pub use checkpoint::{
-    anchor_hasher, latest_log_cutoff, load_latest_checkpoint, write_checkpoint, AnchorCheckpoint,
-    CheckpointError, CheckpointSignature,
+    anchor_hasher, checkpoint_provider_key, latest_log_cutoff, load_checkpoints,
+    load_latest_checkpoint, parse_checkpoint_provider_key, write_checkpoint, AnchorCheckpoint,
+    CheckpointError, CheckpointSignature, CHECKPOINT_PROVIDER_PREFIX,
+};
+pub use config::{
+    ConfigError, ConfigFile, EvmRpcConfigBuilder, NetConfigBuilder, NetSettings, RpcSettings,
+    MAX_GAS_MARGIN_PERCENT, MAX_INTERVAL, MIN_INTERVAL,
+};
+pub use content::{
+    export_content_bundle, parse_raw_cid, raw_cid, verify_content_bundle, AnchorLink, ContentError,
+    ContentManifest, ContentObject, ContentSummary, TranscriptLink, CONTENT_BLOCKS_DIR,
+    CONTENT_MANIFEST_FILE, DEFAULT_CHUNK_SIZE, SCHEMA_CONTENT_MANIFEST,
+};
+pub use devnet::{
+    clean_devnet, generate_devnet, Devnet, DevnetError, DevnetNode, DevnetSpec, DEVNET_MANIFEST,
+    DEVNET_NETWORK_ID, DEVNET_SCHEMA, MAX_DEVNET_NODES,
+};
+pub use did::{
+    IdentityDocument, IdentityError, IdentityRegistry, IDENTITY_TOPIC, SCHEMA_IDENTITY_DOCUMENT,
+};
+pub use direct::{
+    enqueue_direct, DirectInboxEntry, DirectMessage, DirectOutboxEntry, DirectPayload,
+    DirectReceipt, DirectReceiptEntry, ProofSubmission, DIRECT_INBOX_FILE, DIRECT_OUTBOX_FILE,
+    DIRECT_PROTOCOL, DIRECT_RECEIPTS_FILE, SCHEMA_DIRECT_MESSAGE, SCHEMA_DIRECT_RECEIPT,
+};
+pub use entry_gc::{
+    collect_expired_entries, import_archived_entries, record_finalized_entries, ArchivedEntry,
+    EntryFinalityIndex, EntryGcError, EntryGcManifest, EntryGcReport, ENTRY_FINALITY_FILE,
+    ENTRY_GC_DIR, SCHEMA_ENTRY_GC,
+};
+pub use evm_pin::{
+    confirm_pins, evm_signer_address, load_pin_log, parse_evm_signing_key, pin_calldata,
+    pin_latest_checkpoint, run_evm_pinner, sign_pin_transaction, EvmPinConfig, PinError, PinRecord,
+    PinStatus, PIN_ANCHOR_SIGNATURE, PIN_LOG_FILE, SCHEMA_PIN_LOG,
+};
+pub use fork::{
+    read_fork_evidence, resolve_fork, CheckpointTally, ForkBranch, ForkError, ForkEvidence,
+    ForkResolution, SafeMode, SAFE_MODE_FILE, SCHEMA_FORK_EVIDENCE, SCHEMA_FORK_RESOLUTION,
 };
 pub use governance::{
-    GovernanceUpdate, MembershipPolicy, MigrationAnchor, MigrationProposal, MultisigPolicy,
-    PolicyUpdateError, StakePolicy, StaticPolicy,
+    FeeScheduleConfig, GovernanceUpdate, MembershipPolicy, MigrationAnchor, MigrationProposal,
+    MultisigPolicy, PolicyUpdateError, StakePolicy, StaticPolicy, UpdateKind,
+};
+#[cfg(feature = "grpc")]
+pub use grpc::{run_grpc_server, GrpcConfig, GrpcError, NodeApi, GRPC_SENDER};
+pub use mempool::{
+    EvictionReason, PendingEntry, PendingPool, DEFAULT_MAX_PENDING, DEFAULT_PENDING_TTL,
+};
+pub use metrics_push::{
+    push_metrics, run_metrics_pusher, MetricsPushConfig, EXPOSITION_CONTENT_TYPE,
 };
 pub use migration::{migration_mode_frozen, refresh_migration_mode_from_env};
 pub use native_chain::{
-    NativeChainCommand, NativeChainMessage, NativeChainMessagePayload, NativeChainRuntime,
-    NativeChainState, SharedNativeChainState, NATIVE_CHAIN_TOPIC,
+    NativeBalanceChange, NativeChainCommand, NativeChainMessage, NativeChainMessagePayload,
+    NativeChainRuntime, NativeChainState, NativeChangeKind, SharedNativeChainState,
+    DEFAULT_MAX_BLOCK_INTERVAL, NATIVE_CHAIN_TOPIC, PRUNE_BATCH_BLOCKS,
 };
+pub use node_clock::{NodeClock, SharedNodeClock, SimulatedClock, Ticker, WallClock};
+pub use onboarding::{AllowlistFile, JoinError, JoinRequest, SCHEMA_JOIN_REQUEST};
+pub use parameters::{
+    parameter_change_statement, ParameterError, ParameterStore, RuntimeParameters,
+    SharedParameters, MAX_ENVELOPE_BYTES, PARAM_BROADCAST_INTERVAL_MS, PARAM_FEE_SCHEDULE,
+    PARAM_MAX_ENVELOPE_BYTES, PARAM_QUORUM,
+};
+#[cfg(feature = "keychain")]
+pub use passphrase::keychain_store;
+pub use passphrase::{keychain_account, read_passphrase, PassphraseSource, KEYCHAIN_SERVICE};
+pub use peer_store::{PeerRecord, PeerStore, PEER_STORE_FILE, PEER_STORE_MAX_AGE_SECS};
 pub use policy::{IdentityPolicy, PolicyError};
-pub use rpc::{run_evm_rpc_server, EvmRpcConfig};
-pub use schema::{AnchorEnvelope, AnchorJson, AnchorVoteJson, SCHEMA_VOTE};
+pub use policy_edit::{
+    apply_key_edit, load_policy_history, policy_history_path, KeyEdit, KeyEditAction, PolicyChange,
+    PolicyEditError, PolicyKind, PolicySnapshot, SCHEMA_POLICY_CHANGE,
+};
+pub use precompile::{
+    asset_token_address, PrecompileCall, MIGRATION_CLAIMS_ADDRESS, NATIVE_TOKEN_ADDRESS,
+};
+pub use proposal::{
+    enqueue_governance, record_proposal, Ballot, ExecutionHook, GovernanceBook, GovernanceError,
+    GovernanceMessage, Proposal, ProposalAction, ProposalRecord, ProposalStatus, ProposalVote,
+    Tally, GOVERNANCE_APPLICATION_ID, GOVERNANCE_DIR, GOVERNANCE_TOPIC, SCHEMA_PROPOSAL,
+    SCHEMA_PROPOSAL_VOTE,
+};
+pub use rate_limit::{DEFAULT_MAX_ANCHORS_PER_MINUTE, SUSTAINED_VIOLATION_WINDOWS};
+pub use replica::{follow_primary_state, ReplicaMode, ReplicaStatus, DEFAULT_REPLICA_POLL};
+pub use rewards::{
+    compute_epoch_rewards, distribute_epoch_rewards, reward_distribution_path, RewardConfig,
+    RewardDistribution, RewardError, RewardShare, RewardWeighting, SCHEMA_REWARD_DISTRIBUTION,
+};
+pub use rollup_store::{
+    append_rollup_record, RollupCommitmentStore, RollupOutcome, RollupStoreError, StoredCommitment,
+    ROLLUP_STORE_FILE, SCHEMA_ROLLUP_STORE,
+};
+pub use rpc::{
+    run_evm_rpc_server, ConnectionLimiter, CorsPolicy, EvmRpcConfig, TxInput, TxInputHandler,
+    TxInputHandlers, DEFAULT_GAS_MARGIN_PERCENT, DEFAULT_MAX_CONNECTIONS,
+};
+pub use rpc_tiers::{RpcTier, RpcTierLimits, RpcTiers, TierLimit};
+pub use schema::{
+    network_id, network_topic, AnchorEnvelope, AnchorJson, AnchorVoteJson, EntrySubmissionJson,
+    SCHEMA_VOTE,
+};
+pub use shipping::{
+    run_log_aggregator, run_log_shipper, ship_pending_logs, store_shipped_record,
+    LogAggregatorConfig, LogShipperConfig, ShippedRecord, ShippingError, StoreOutcome,
+    LOG_SHIP_CURSOR_FILE, SCHEMA_SHIPPED_RECORD,
+};
 pub use sign::{
-    decode_public_key_base64, decode_signature_base64, encode_public_key_base64,
-    encode_signature_base64, encrypt_identity_base64, load_encrypted_identity,
-    load_or_derive_keypair, sign_payload, verify_signature, verify_signature_base64,
-    Ed25519KeySource, KeyError, KeyMaterial,
+    decode_public_key_base64, decode_signature_base64, derive_mnemonic_key,
+    encode_public_key_base64, encode_signature_base64, encrypt_identity_base64, generate_mnemonic,
+    load_encrypted_identity, load_or_derive_keypair, mnemonic_from_entropy, mnemonic_key_file,
+    mnemonic_to_entropy, mnemonic_to_seed, sign_entry_submission, sign_payload,
+    verify_entry_submission, verify_signature, verify_signature_base64, Ed25519KeySource, KeyError,
+    KeyMaterial, DEFAULT_MNEMONIC_PATH, MNEMONIC_KEY_SCHEME,
 };
-pub use stake_registry::StakeRegistry;
-pub use swarm::{run_network, NamespaceRule, NetConfig, NetworkError};
+pub use stake_registry::{
+    account_state_key, account_state_value, validate_asset_id, verify_account_proof, AccountFreeze,
+    AssetTransfer, EpochFees, FreezeOrder, GuardLimits, GuardOverride, GuardWindow, PendingUnbond,
+    RegistryTransaction, RegistryView, StakeRegistry, SupplyCause, SupplyEntry, SupplyLedger,
+    SupplyOp, SupplySnapshot,
+};
+pub use storage::{
+    migrate_chain_state, migrate_registry, open_chain_state_store, open_registry_store,
+    ChainStateStore, JsonStore, RegistryStore, StorageBackend,
+};
+pub use swarm::{load_anchor_from_logs, run_network, NamespaceRule, NetConfig, NetworkError};
 pub use validator_registry::{
     ObserverRegistration, ObserverRegistry, ValidatorRegistration, ValidatorRegistry,
     ValidatorRegistryError, OBSERVER_REGISTRATION_SCHEMA, OBSERVER_REGISTRY_SCHEMA,
     VALIDATOR_REGISTRATION_SCHEMA, VALIDATOR_REGISTRY_SCHEMA,
 };
+pub use webhooks::{
+    deliver_webhook, sign_webhook_body, verify_webhook_signature, WebhookConfig, WebhookDispatcher,
+    WebhookEndpoint, WebhookError, WebhookEvent, WebhookEventKind, EVENT_HEADER,
+    SCHEMA_WEBHOOK_EVENT, SIGNATURE_HEADER,
+};
@@ -3,11 +3,17 @@
 
 //! Quorum-finalized native transfers exposed through the wallet RPC adapter.
 
+use crate::genesis::active_genesis;
 use crate::net::{
-    decode_public_key_base64, encode_public_key_base64, encode_signature_base64,
+    address_index::registry_key_address,
+    block_archive::{write_segment, ArchiveSegment},
+    encode_public_key_base64, encode_signature_base64,
+    mempool::PendingPool,
+    node_clock::SharedNodeClock,
+    schema::{network_id, NETWORK_ID},
+    storage::open_chain_state_store,
     verify_signature_base64, StakeRegistry,
 };
-use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use blake2::digest::{consts::U32, Digest as BlakeDigest};
 use ed25519_dalek::{Signer, SigningKey};
 use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
@@ -16,11 +22,9 @@ use serde::{Deserialize, Serialize};
 use sha3::Keccak256;
 use std::{
     collections::{BTreeMap, BTreeSet},
-    fs,
-    io::Write,
     path::{Path, PathBuf},
     sync::Arc,
-    time::{SystemTime, UNIX_EPOCH},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use tokio::sync::{oneshot, RwLock};
 
@@ -34,7 +38,10 @@ const STATE_SCHEMA: &str = "mfenx.powerhouse.native-chain-state.v1";
 const MESSAGE_SCHEMA: &str = "mfenx.powerhouse.native-chain-message.v1";
 const MAX_BLOCK_TRANSACTIONS: usize = 256;
 const MAX_FUTURE_SECONDS: u64 = 30;
-const EMPTY_BLOCK_MIN_SECONDS: u64 = 12;
+/// Longest a validator waits before sealing an empty heartbeat block.
+pub const DEFAULT_MAX_BLOCK_INTERVAL: Duration = Duration::from_secs(12);
+/// Fewest blocks moved into one archive segment when pruning.
+pub const PRUNE_BATCH_BLOCKS: u64 = 256;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct NativeTransaction {
@@ -60,6 +67,28 @@ pub struct NativeAccount {
     pub nonce: u64,
 }
 
+/// Direction of a [`NativeBalanceChange`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NativeChangeKind {
+    /// Units leaving the sender.
+    Debit,
+    /// Units arriving at the recipient.
+    Credit,
+}
+
+/// One account mutation made by a finalized transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NativeBalanceChange {
+    pub kind: NativeChangeKind,
+    pub address: String,
+    pub amount: u64,
+    pub balance_before: u64,
+    pub balance_after: u64,
+    pub nonce_before: u64,
+    pub nonce_after: u64,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct NativeBlockProposal {
     pub chain_id: u64,
@@ -117,6 +146,10 @@ pub struct NativeChainState {
     pub blocks: Vec<FinalizedNativeBlock>,
     #[serde(default)]
     pub votes_cast: BTreeMap<u64, String>,
+    /// Accounts after the oldest retained block once older blocks were pruned;
+    /// that block's state root commits to them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pruned_accounts: Option<BTreeMap<String, NativeAccount>>,
 }
 
 pub type SharedNativeChainState = Arc<RwLock<NativeChainState>>;
@@ -136,6 +169,8 @@ pub enum NativeChainMessagePayload {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NativeChainMessage {
     pub schema: String,
+    #[serde(default = "default_message_network")]
+    pub network: String,
     pub payload: NativeChainMessagePayload,
 }
 
@@ -143,6 +178,7 @@ impl NativeChainMessage {
     pub fn new(payload: NativeChainMessagePayload) -> Self {
         Self {
             schema: MESSAGE_SCHEMA.to_string(),
+            network: network_id().to_string(),
             payload,
         }
     }
@@ -151,10 +187,20 @@ impl NativeChainMessage {
         if self.schema != MESSAGE_SCHEMA {
             return Err(format!("unsupported native-chain schema: {}", self.schema));
         }
+        if self.network != network_id() {
+            return Err(format!(
+                "native-chain message from network {}",
+                self.network
+            ));
+        }
         Ok(())
     }
 }
 
+fn default_message_network() -> String {
+    NETWORK_ID.to_string()
+}
+
 #[derive(Debug)]
 pub struct NativeChainCommand {
     pub transaction: NativeTransaction,
@@ -167,11 +213,14 @@ pub struct NativeChainRuntime {
     validators: Vec<String>,
     quorum: usize,
     local_validator: String,
-    pending: BTreeMap<String, NativeTransaction>,
+    pool: PendingPool,
     proposals: BTreeMap<String, NativeBlockProposal>,
     votes: BTreeMap<String, BTreeMap<String, NativeBlockVote>>,
     orphan_votes: BTreeMap<String, BTreeMap<String, NativeBlockVote>>,
     voted_heights: BTreeMap<u64, String>,
+    max_block_interval_secs: u64,
+    pruning: Option<(PathBuf, u64)>,
+    clock: SharedNodeClock,
 }
 
 impl NativeChainRuntime {
@@ -181,6 +230,7 @@ impl NativeChainRuntime {
         mut validators: Vec<String>,
         quorum: usize,
         signing: &SigningKey,
+        clock: SharedNodeClock,
     ) -> Result<Self, String> {
         validators.sort();
         validators.dedup();
@@ -201,7 +251,7 @@ impl NativeChainRuntime {
         }
         let voted_heights = {
             let current = state.read().await;
-            current.validate()?;
+            current.validate(clock.now().as_secs())?;
             if current.validators != validators || current.quorum != quorum {
                 return Err(
                     "native chain validator configuration does not match persisted genesis"
@@ -216,16 +266,76 @@ impl NativeChainRuntime {
             validators,
             quorum,
             local_validator: encode_public_key_base64(&signing.verifying_key()),
-            pending: BTreeMap::new(),
+            pool: PendingPool::default(),
             proposals: BTreeMap::new(),
             votes: BTreeMap::new(),
             orphan_votes: BTreeMap::new(),
             voted_heights,
+            max_block_interval_secs: DEFAULT_MAX_BLOCK_INTERVAL.as_secs(),
+            pruning: None,
+            clock,
         })
     }
 
+    /// Archives finalized blocks more than `keep_blocks` below the tip into
+    /// `archive_dir`, in batches of at least [`PRUNE_BATCH_BLOCKS`].
+    pub fn set_pruning(&mut self, archive_dir: PathBuf, keep_blocks: u64) {
+        self.pruning = Some((archive_dir, keep_blocks));
+    }
+
+    /// Sets how long an idle chain waits before sealing an empty block.
+    ///
+    /// Block timestamps have second resolution, so sub-second parts are dropped.
+    pub fn set_max_block_interval(&mut self, interval: Duration) {
+        self.max_block_interval_secs = interval.as_secs();
+    }
+
+    /// Sets how long a transaction may stay pending and how many may wait.
+    pub fn set_pending_limits(&mut self, ttl: Duration, max_size: usize) {
+        self.pool.set_limits(ttl, max_size);
+    }
+
+    /// Shared handle to the pending pool, for the RPC and metrics readers.
+    pub fn pending_pool(&self) -> PendingPool {
+        self.pool.clone()
+    }
+
+    /// Expires transactions pending longer than the TTL; returns their hashes.
+    pub fn expire_pending(&mut self) -> Vec<String> {
+        self.pool.expire(self.now_secs())
+    }
+
+    fn now_secs(&self) -> u64 {
+        self.clock.now().as_secs()
+    }
+
+    /// Whether the next block should be sealed now.
+    ///
+    /// A block is due as soon as transactions are pending, or once the max
+    /// interval has elapsed since the last finalized block, unless a proposal
+    /// for the next height is already in flight.
+    pub async fn seal_due(&self) -> bool {
+        let state = self.state.read().await;
+        let number = state.latest_number().saturating_add(1);
+        if self
+            .proposals
+            .values()
+            .any(|proposal| proposal.number == number)
+        {
+            return false;
+        }
+        !self.pool.is_empty() || self.idle_interval_elapsed(&state)
+    }
+
+    fn idle_interval_elapsed(&self, state: &NativeChainState) -> bool {
+        state
+            .latest_timestamp()
+            .saturating_add(self.max_block_interval_secs)
+            <= self.now_secs()
+    }
+
     pub async fn accept_transaction(&mut self, tx: NativeTransaction) -> Result<bool, String> {
-        if self.pending.contains_key(&tx.hash) {
+        if self.pool.contains(&tx.hash) {
             return Ok(false);
         }
         let state = self.state.read().await;
@@ -235,10 +345,9 @@ impl NativeChainRuntime {
         validate_transaction(&state, &tx)?;
         let mut expected = state.account(&tx.from).nonce;
         let pending_nonces = self
-            .pending
-            .values()
-            .filter(|item| item.from == tx.from)
-            .map(|item| item.nonce)
+            .pool
+            .nonces_of(&tx.from)
+            .into_iter()
             .collect::<BTreeSet<_>>();
         while pending_nonces.contains(&expected) {
             expected = expected.saturating_add(1);
@@ -250,7 +359,13 @@ impl NativeChainRuntime {
             ));
         }
         drop(state);
-        self.pending.insert(tx.hash.clone(), tx);
+        let evicted = self.pool.admit(tx, self.now_secs())?;
+        if !evicted.is_empty() {
+            println!(
+                "QSYS|mod=NATIVE_CHAIN|evt=TX_EVICTED|reason=pool_full|count={}",
+                evicted.len()
+            );
+        }
         Ok(true)
     }
 
@@ -259,14 +374,10 @@ impl NativeChainRuntime {
         signing: &SigningKey,
     ) -> Result<Option<NativeBlockProposal>, String> {
         let state = self.state.read().await;
-        let now = now_secs();
-        let empty_heartbeat = self.pending.is_empty();
-        if empty_heartbeat
-            && state
-                .latest_timestamp()
-                .saturating_add(EMPTY_BLOCK_MIN_SECONDS)
-                > now
-        {
+        let now = self.now_secs();
+        let pending = self.pool.transactions();
+        let empty_heartbeat = pending.is_empty();
+        if empty_heartbeat && !self.idle_interval_elapsed(&state) {
             return Ok(None);
         }
         let number = state.latest_number().saturating_add(1);
@@ -283,7 +394,7 @@ impl NativeChainRuntime {
 
         let mut transactions = Vec::new();
         let mut working = state.accounts.clone();
-        for tx in self.pending.values() {
+        for tx in &pending {
             if transactions.len() >= MAX_BLOCK_TRANSACTIONS {
                 break;
             }
@@ -347,7 +458,7 @@ impl NativeChainRuntime {
         NativeChainMessage::new(NativeChainMessagePayload::Tip(NativeChainTip {
             height: state.latest_number(),
             hash: state.latest_hash().to_string(),
-            observed_at: now_secs(),
+            observed_at: self.now_secs(),
         }))
     }
 
@@ -428,7 +539,7 @@ impl NativeChainRuntime {
                 }
                 return Err("conflicting stale proposal".to_string());
             }
-            validate_proposal(&state, &proposal, &self.validators)?;
+            validate_proposal(&state, &proposal, &self.validators, self.now_secs())?;
         }
         if let Some(previous) = self.voted_heights.get(&proposal.number) {
             if previous != &proposal.hash {
@@ -537,12 +648,19 @@ impl NativeChainRuntime {
                 .blocks
                 .iter()
                 .any(|existing| existing.proposal.hash == block.proposal.hash)
+                || state.block_by_number(block.proposal.number).is_none()
             {
                 return Ok(());
             }
             return Err("conflicting finalized block below current height".to_string());
         }
-        validate_finalized(&state, &block, &self.validators, self.quorum)?;
+        validate_finalized(
+            &state,
+            &block,
+            &self.validators,
+            self.quorum,
+            self.now_secs(),
+        )?;
         let mut next_accounts = state.accounts.clone();
         for tx in &block.proposal.transactions {
             apply_transaction_to_accounts(state.chain_id, &mut next_accounts, tx)?;
@@ -552,12 +670,33 @@ impl NativeChainRuntime {
         state
             .votes_cast
             .retain(|number, _| *number > block.proposal.number);
+        if let Some((archive_dir, keep)) = &self.pruning {
+            if let Some(segment) = state.prune_into(archive_dir, *keep, PRUNE_BATCH_BLOCKS)? {
+                println!(
+                    "QSYS|mod=NATIVE_CHAIN|evt=PRUNED|first={}|last={}|path={}",
+                    segment.first,
+                    segment.last,
+                    segment.path.display()
+                );
+            }
+        }
         save_state_atomic(&self.state_path, &state)?;
+        self.pool.remove_included(
+            block
+                .proposal
+                .transactions
+                .iter()
+                .map(|tx| tx.hash.as_str()),
+        );
+        let stale = self.pool.drop_stale(|sender| state.account(sender).nonce);
+        if !stale.is_empty() {
+            println!(
+                "QSYS|mod=NATIVE_CHAIN|evt=TX_EVICTED|reason=stale|count={}",
+                stale.len()
+            );
+        }
         drop(state);
 
-        for tx in &block.proposal.transactions {
-            self.pending.remove(&tx.hash);
-        }
         let height = block.proposal.number;
         self.proposals
             .retain(|_, proposal| proposal.number > height);
@@ -571,12 +710,16 @@ impl NativeChainRuntime {
 }
 
 impl NativeChainState {
+    /// Loads and replays the state at `path`, or writes a new genesis state.
+    ///
+    /// Stored blocks are checked against `now_secs`, the caller's clock.
     pub fn load_or_initialize(
         path: &Path,
         chain_id: u64,
         registry_path: Option<&Path>,
         mut validators: Vec<String>,
         quorum: usize,
+        now_secs: u64,
     ) -> Result<Self, String> {
         validators.sort();
         validators.dedup();
@@ -587,10 +730,8 @@ impl NativeChainState {
         {
             return Err("native chain requires a strict-majority validator quorum".to_string());
         }
-        if path.exists() {
-            let bytes = fs::read(path).map_err(|err| err.to_string())?;
-            let state: Self = serde_json::from_slice(&bytes).map_err(|err| err.to_string())?;
-            state.validate()?;
+        if let Some(state) = open_chain_state_store(path)?.load_chain_state()? {
+            state.validate(now_secs)?;
             if state.chain_id != chain_id {
                 return Err(format!(
                     "native chain ID mismatch: state={} configured={chain_id}",
@@ -610,7 +751,7 @@ impl NativeChainState {
         if let Some(registry_path) = registry_path {
             let registry = StakeRegistry::load(registry_path)?;
             for (key, account) in registry.accounts() {
-                if let Some(address) = registry_key_to_evm_address(key) {
+                if let Some(address) = registry_key_address(key) {
                     let entry = accounts
                         .entry(address)
                         .or_insert_with(NativeAccount::default);
@@ -618,6 +759,14 @@ impl NativeChainState {
                 }
             }
         }
+        for (key, amount) in &active_genesis().allocations {
+            let address = registry_key_address(key)
+                .ok_or_else(|| format!("genesis allocation key {key} has no EVM address"))?;
+            let entry = accounts
+                .entry(address)
+                .or_insert_with(NativeAccount::default);
+            entry.balance = entry.balance.saturating_add(*amount);
+        }
         let genesis = genesis_block(chain_id, &accounts, &validators, quorum);
         let state = Self {
             schema: STATE_SCHEMA.to_string(),
@@ -628,12 +777,43 @@ impl NativeChainState {
             accounts,
             blocks: vec![genesis],
             votes_cast: BTreeMap::new(),
+            pruned_accounts: None,
         };
         save_state_atomic(path, &state)?;
         Ok(state)
     }
 
-    pub fn validate(&self) -> Result<(), String> {
+    /// Reads and validates a state file written by a node, checking block
+    /// timestamps against `now_secs`.
+    pub fn load(path: &Path, now_secs: u64) -> Result<Self, String> {
+        let state = open_chain_state_store(path)?
+            .load_chain_state()?
+            .ok_or_else(|| format!("{} holds no native chain state", path.display()))?;
+        state.validate(now_secs)?;
+        Ok(state)
+    }
+
+    /// Digest of the state the wallet RPC serves: chain ID, tip, and account
+    /// balances and nonces.
+    ///
+    /// The tip hash chains every finalized block, so receipts are covered
+    /// even after pruning.  Local vote bookkeeping and the pruning horizon
+    /// are left out, so nodes at the same tip produce the same digest.
+    pub fn rpc_state_digest(&self) -> [u8; 32] {
+        let mut hasher = Blake2b256::new();
+        hasher.update(b"mfenx-native-rpc-state-v1");
+        hasher.update(self.chain_id.to_be_bytes());
+        hasher.update(self.latest_number().to_be_bytes());
+        hasher.update(self.latest_hash().as_bytes());
+        hasher.update(accounts_root(&self.accounts).as_bytes());
+        hasher.finalize().into()
+    }
+
+    /// Replays every retained block from genesis and checks the result.
+    ///
+    /// `now_secs` bounds block timestamps; pass the node clock's time so a
+    /// simulated clock replays the history it produced.
+    pub fn validate(&self, now_secs: u64) -> Result<(), String> {
         if self.schema != STATE_SCHEMA {
             return Err(format!(
                 "unsupported native chain state schema: {}",
@@ -672,9 +852,26 @@ impl NativeChainState {
             accounts: self.genesis_accounts.clone(),
             blocks: vec![self.blocks[0].clone()],
             votes_cast: BTreeMap::new(),
+            pruned_accounts: None,
         };
-        for block in self.blocks.iter().skip(1) {
-            validate_finalized(&replay, block, &self.validators, self.quorum)?;
+        let mut replayed = 1;
+        if let Some(base) = &self.pruned_accounts {
+            // The parent of the oldest retained block is archived, so only its
+            // certificate and the state root over the base accounts are checked.
+            let first = self
+                .blocks
+                .get(1)
+                .ok_or_else(|| "pruned native chain state has no retained block".to_string())?;
+            validate_certificate(first, &self.validators, self.quorum)?;
+            if accounts_root(base) != first.proposal.state_root {
+                return Err("pruned base accounts do not match the retained state root".to_string());
+            }
+            replay.accounts = base.clone();
+            replay.blocks.push(first.clone());
+            replayed = 2;
+        }
+        for block in self.blocks.iter().skip(replayed) {
+            validate_finalized(&replay, block, &self.validators, self.quorum, now_secs)?;
             for tx in &block.proposal.transactions {
                 apply_transaction_to_accounts(self.chain_id, &mut replay.accounts, tx)?;
             }
@@ -693,18 +890,80 @@ impl NativeChainState {
     }
 
     pub fn account_at(&self, address: &str, number: u64) -> Result<NativeAccount, String> {
+        let normalized =
+            normalize_evm_address(address).ok_or_else(|| "invalid address format".to_string())?;
+        Ok(self
+            .accounts_at(number)?
+            .get(&normalized)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    /// Sum of all native balances after block `number`, in native units.
+    pub fn total_supply_at(&self, number: u64) -> Result<u128, String> {
+        Ok(self
+            .accounts_at(number)?
+            .values()
+            .map(|account| u128::from(account.balance))
+            .sum())
+    }
+
+    fn accounts_at(&self, number: u64) -> Result<BTreeMap<String, NativeAccount>, String> {
         if number > self.latest_number() {
             return Err(format!("block {number} has not been finalized"));
         }
-        let normalized =
-            normalize_evm_address(address).ok_or_else(|| "invalid address format".to_string())?;
-        let mut accounts = self.genesis_accounts.clone();
-        for block in self.blocks.iter().skip(1).take(number as usize) {
+        if number == 0 {
+            return Ok(self.genesis_accounts.clone());
+        }
+        let (mut accounts, replayed) = match &self.pruned_accounts {
+            Some(_) if number < self.first_retained() => {
+                return Err(format!(
+                    "block {number} has been pruned to the block archive"
+                ));
+            }
+            Some(base) => (base.clone(), 2),
+            None => (self.genesis_accounts.clone(), 1),
+        };
+        for block in self
+            .blocks
+            .iter()
+            .skip(replayed)
+            .take_while(|block| block.proposal.number <= number)
+        {
             for tx in &block.proposal.transactions {
                 apply_transaction_to_accounts(self.chain_id, &mut accounts, tx)?;
             }
         }
-        Ok(accounts.get(&normalized).cloned().unwrap_or_default())
+        Ok(accounts)
+    }
+
+    /// Oldest non-genesis block still held in full (1 until history is pruned).
+    pub fn first_retained(&self) -> u64 {
+        self.blocks.get(1).map_or(1, |block| block.proposal.number)
+    }
+
+    /// Moves blocks more than `keep` below the tip into a segment in `archive_dir`.
+    ///
+    /// Nothing happens until at least `min_batch` blocks are prunable. The
+    /// genesis block and the oldest retained block's post-state stay in the
+    /// live state, so balances at retained heights remain answerable.
+    pub fn prune_into(
+        &mut self,
+        archive_dir: &Path,
+        keep: u64,
+        min_batch: u64,
+    ) -> Result<Option<ArchiveSegment>, String> {
+        let first = self.first_retained();
+        let new_first = self.latest_number().saturating_sub(keep);
+        if new_first <= first || new_first - first < min_batch.max(1) {
+            return Ok(None);
+        }
+        let base = self.accounts_at(new_first)?;
+        let cut = (new_first - first) as usize;
+        let segment = write_segment(archive_dir, &self.blocks[1..=cut])?;
+        self.blocks.drain(1..=cut);
+        self.pruned_accounts = Some(base);
+        Ok(Some(segment))
     }
 
     pub fn latest_block(&self) -> &FinalizedNativeBlock {
@@ -724,7 +983,12 @@ impl NativeChainState {
     }
 
     pub fn block_by_number(&self, number: u64) -> Option<&FinalizedNativeBlock> {
-        self.blocks.get(number as usize)
+        if number == 0 {
+            return self.blocks.first();
+        }
+        let offset = number.checked_sub(self.first_retained())?;
+        self.blocks
+            .get(usize::try_from(offset).ok()?.checked_add(1)?)
     }
 
     pub fn block_by_hash(&self, hash: &str) -> Option<&FinalizedNativeBlock> {
@@ -747,6 +1011,72 @@ impl NativeChainState {
                 .map(|(index, tx)| (block, index, tx))
         })
     }
+    /// Checks that `from` could send `value_units` to `to` on the finalized
+    /// state, without changing it.
+    pub fn dry_run_transfer(&self, from: &str, to: &str, value_units: u64) -> Result<(), String> {
+        let sender = self.account(from);
+        if sender.balance < value_units {
+            return Err(format!(
+                "insufficient native balance: {from} holds {} units, transfer needs {value_units}",
+                sender.balance
+            ));
+        }
+        if normalize_evm_address(from) != normalize_evm_address(to)
+            && self.account(to).balance.checked_add(value_units).is_none()
+        {
+            return Err(format!("recipient {to} balance would overflow"));
+        }
+        Ok(())
+    }
+
+    /// Account mutations of transaction `index` in `block`, reconstructed by
+    /// replaying finalized blocks up to it.  Fails for blocks whose parent
+    /// state was pruned.
+    pub fn trace_transaction(
+        &self,
+        block: &FinalizedNativeBlock,
+        index: usize,
+    ) -> Result<Vec<NativeBalanceChange>, String> {
+        let number = block.proposal.number;
+        let tx = block
+            .proposal
+            .transactions
+            .get(index)
+            .ok_or_else(|| format!("block {number} has no transaction {index}"))?;
+        let mut accounts = self.accounts_at(number.saturating_sub(1))?;
+        for earlier in &block.proposal.transactions[..index] {
+            apply_transaction_to_accounts(self.chain_id, &mut accounts, earlier)?;
+        }
+        let from = normalize_evm_address(&tx.from).ok_or_else(|| "invalid sender".to_string())?;
+        let to = normalize_evm_address(&tx.to).ok_or_else(|| "invalid recipient".to_string())?;
+        let sender = accounts.get(&from).cloned().unwrap_or_default();
+        let recipient_before = accounts.get(&to).map_or(0, |account| account.balance);
+        apply_transaction_to_accounts(self.chain_id, &mut accounts, tx)?;
+        let debit = NativeBalanceChange {
+            kind: NativeChangeKind::Debit,
+            address: from.clone(),
+            amount: tx.value_units,
+            balance_before: sender.balance,
+            balance_after: sender.balance - tx.value_units,
+            nonce_before: sender.nonce,
+            nonce_after: sender.nonce.saturating_add(1),
+        };
+        let recipient = accounts.get(&to).cloned().unwrap_or_default();
+        let credit = NativeBalanceChange {
+            kind: NativeChangeKind::Credit,
+            address: to.clone(),
+            amount: tx.value_units,
+            balance_before: if to == from {
+                debit.balance_after
+            } else {
+                recipient_before
+            },
+            balance_after: recipient.balance,
+            nonce_before: recipient.nonce,
+            nonce_after: recipient.nonce,
+        };
+        Ok(vec![debit, credit])
+    }
 }
 
 pub fn decode_eip1559_transaction(
@@ -779,10 +1109,9 @@ pub fn decode_eip1559_transaction(
     }
     let value_wei = rlp_u128(&rlp, 6)?;
     let value_units = wei_to_native_units(value_wei)?;
+    // Calldata carries no chain-state effect; the RPC only admits it for
+    // addresses with a registered input handler.
     let input_bytes = rlp_data(&rlp, 7)?;
-    if !input_bytes.is_empty() {
-        return Err("contract calldata is not supported".to_string());
-    }
     let access_list_item = rlp.at(8).map_err(|err| err.to_string())?;
     if !access_list_item.is_list() {
         return Err("EIP-1559 access list must be an RLP list".to_string());
@@ -836,7 +1165,7 @@ pub fn decode_eip1559_transaction(
         gas_limit,
         max_fee_per_gas: to_quantity_u128(max_fee_per_gas),
         max_priority_fee_per_gas: to_quantity_u128(max_priority_fee_per_gas),
-        input: "0x".to_string(),
+        input: format!("0x{}", hex::encode(input_bytes)),
         y_parity: y_parity as u8,
         r: format!("0x{}", hex::encode(r32)),
         s: format!("0x{}", hex::encode(s32)),
@@ -922,6 +1251,7 @@ fn validate_proposal(
     state: &NativeChainState,
     proposal: &NativeBlockProposal,
     validators: &[String],
+    now_secs: u64,
 ) -> Result<(), String> {
     if proposal.chain_id != state.chain_id
         || proposal.number != state.latest_number().saturating_add(1)
@@ -933,7 +1263,7 @@ fn validate_proposal(
         return Err("proposal transaction count is invalid".to_string());
     }
     if proposal.timestamp <= state.latest_timestamp()
-        || proposal.timestamp > now_secs().saturating_add(MAX_FUTURE_SECONDS)
+        || proposal.timestamp > now_secs.saturating_add(MAX_FUTURE_SECONDS)
     {
         return Err("proposal timestamp is outside the accepted range".to_string());
     }
@@ -1003,8 +1333,40 @@ fn validate_finalized(
     block: &FinalizedNativeBlock,
     validators: &[String],
     quorum: usize,
+    now_secs: u64,
+) -> Result<(), String> {
+    validate_proposal(state, &block.proposal, validators, now_secs)?;
+    validate_quorum_votes(block, validators, quorum)
+}
+
+/// Checks a block's hash, proposer signature and quorum certificate without
+/// its parent, for the oldest block retained after pruning.
+fn validate_certificate(
+    block: &FinalizedNativeBlock,
+    validators: &[String],
+    quorum: usize,
+) -> Result<(), String> {
+    let proposal = &block.proposal;
+    if expected_leader(validators, proposal.number) != proposal.proposer {
+        return Err("proposal was not signed by the expected leader".to_string());
+    }
+    if block_hash(proposal) != proposal.hash {
+        return Err("proposal hash mismatch".to_string());
+    }
+    verify_signature_base64(
+        &proposal.proposer,
+        block_signing_payload(&proposal.hash).as_bytes(),
+        &proposal.signature,
+    )
+    .map_err(|err| format!("invalid proposer signature: {err}"))?;
+    validate_quorum_votes(block, validators, quorum)
+}
+
+fn validate_quorum_votes(
+    block: &FinalizedNativeBlock,
+    validators: &[String],
+    quorum: usize,
 ) -> Result<(), String> {
-    validate_proposal(state, &block.proposal, validators)?;
     let mut signers = BTreeSet::new();
     for vote in &block.votes {
         validate_vote(vote, &block.proposal, validators)?;
@@ -1093,47 +1455,11 @@ fn vote_signing_payload(hash: &str, number: u64) -> String {
 }
 
 fn save_state_atomic(path: &Path, state: &NativeChainState) -> Result<(), String> {
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).map_err(|err| err.to_string())?;
-    }
-    let bytes = serde_json::to_vec_pretty(state).map_err(|err| err.to_string())?;
-    let temp = path.with_extension(format!("tmp-{}-{}", std::process::id(), now_nanos()));
-    let result = (|| -> Result<(), String> {
-        let mut file = fs::OpenOptions::new()
-            .write(true)
-            .create_new(true)
-            .open(&temp)
-            .map_err(|err| err.to_string())?;
-        file.write_all(&bytes).map_err(|err| err.to_string())?;
-        file.sync_all().map_err(|err| err.to_string())?;
-        fs::rename(&temp, path).map_err(|err| err.to_string())?;
-        if let Some(parent) = path.parent() {
-            fs::File::open(parent)
-                .and_then(|directory| directory.sync_all())
-                .map_err(|err| err.to_string())?;
-        }
-        Ok(())
-    })();
-    if result.is_err() {
-        let _ = fs::remove_file(temp);
-    }
-    result
-}
-
-fn registry_key_to_evm_address(key: &str) -> Option<String> {
-    if let Some(address) = normalize_evm_address(key) {
-        return Some(address);
-    }
-    let public = BASE64.decode(key).ok()?;
-    let _ = decode_public_key_base64(key).ok()?;
-    let mut hasher = Blake2b256::new();
-    hasher.update(b"mfenx-migration-address-v1");
-    hasher.update(public);
-    let digest: [u8; 32] = hasher.finalize().into();
-    Some(format!("0x{}", hex::encode(&digest[12..])))
+    open_chain_state_store(path)?.save_chain_state(state)
 }
 
-fn wei_to_native_units(value: u128) -> Result<u64, String> {
+/// Converts a wei amount to whole native units, refusing fractional tokens.
+pub fn wei_to_native_units(value: u128) -> Result<u64, String> {
     if !value.is_multiple_of(NATIVE_DECIMAL_FACTOR) {
         return Err("value must be aligned to whole native tokens".to_string());
     }
@@ -1183,13 +1509,6 @@ fn keccak256(data: &[u8]) -> [u8; 32] {
     hasher.finalize().into()
 }
 
-fn now_secs() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs()
-}
-
 fn now_nanos() -> u128 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -1204,6 +1523,18 @@ pub(crate) fn signed_test_transfer(
     nonce: u64,
     to: [u8; 20],
     units: u64,
+) -> NativeTransaction {
+    signed_test_call(secret, chain_id, nonce, to, units, &[])
+}
+
+#[cfg(test)]
+pub(crate) fn signed_test_call(
+    secret: [u8; 32],
+    chain_id: u64,
+    nonce: u64,
+    to: [u8; 20],
+    units: u64,
+    input: &[u8],
 ) -> NativeTransaction {
     use k256::ecdsa::SigningKey as SecpSigningKey;
 
@@ -1217,7 +1548,7 @@ pub(crate) fn signed_test_transfer(
     unsigned.append(&NATIVE_GAS_LIMIT);
     unsigned.append(&to.as_slice());
     unsigned.append(&value);
-    unsigned.append(&&[][..]);
+    unsigned.append(&input);
     unsigned.begin_list(0);
     let encoded = unsigned.out();
     let mut payload = vec![0x02];
@@ -1233,7 +1564,7 @@ pub(crate) fn signed_test_transfer(
     signed.append(&NATIVE_GAS_LIMIT);
     signed.append(&to.as_slice());
     signed.append(&value);
-    signed.append(&&[][..]);
+    signed.append(&input);
     signed.begin_list(0);
     signed.append(&u8::from(recovery));
     signed.append(&signature.r().to_bytes().as_slice());
@@ -1246,12 +1577,57 @@ pub(crate) fn signed_test_transfer(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::net::{load_or_derive_keypair, Ed25519KeySource};
+    use crate::net::block_archive::{find_archived_transaction, list_segments, BLOCK_ARCHIVE_DIR};
+    use crate::net::{
+        load_or_derive_keypair, Ed25519KeySource, NodeClock, SimulatedClock, WallClock,
+    };
+    use std::fs;
 
     fn validator(seed: &str) -> crate::net::KeyMaterial {
         load_or_derive_keypair(&Ed25519KeySource::Seed(seed.to_string())).unwrap()
     }
 
+    #[test]
+    fn checkpoints_commit_the_rpc_state_digest() {
+        use crate::net::{AnchorCheckpoint, AnchorJson, CheckpointError};
+
+        let validators = vec![encode_public_key_base64(&validator("a").verifying)];
+        let path = std::env::temp_dir().join(format!("rpc_state_{}.json", now_nanos()));
+        let state = NativeChainState::load_or_initialize(&path, 7, None, validators, 1, 0).unwrap();
+        let anchor = AnchorJson::from_ledger(
+            "node-a",
+            1,
+            &crate::julian_genesis_anchor(),
+            0,
+            Vec::new(),
+            None,
+        )
+        .unwrap();
+        let checkpoint = AnchorCheckpoint::new(3, anchor, Vec::new(), None);
+        assert!(matches!(
+            checkpoint.verify_rpc_state(&state),
+            Err(CheckpointError::RpcState(_))
+        ));
+        let checkpoint = checkpoint.with_rpc_state_root(state.rpc_state_digest());
+        let loaded = NativeChainState::load(&path, 0).unwrap();
+        checkpoint.verify_rpc_state(&loaded).unwrap();
+
+        let mut local = loaded.clone();
+        local.votes_cast.insert(1, "0xabc".to_string());
+        checkpoint.verify_rpc_state(&local).unwrap();
+        let mut tampered = loaded;
+        tampered
+            .accounts
+            .entry("0x0000000000000000000000000000000000000001".to_string())
+            .or_default()
+            .nonce = 4;
+        assert!(matches!(
+            checkpoint.verify_rpc_state(&tampered),
+            Err(CheckpointError::RpcState(_))
+        ));
+        fs::remove_file(path).unwrap();
+    }
+
     #[tokio::test]
     async fn replicas_apply_identical_quorum_finalized_block() {
         let chain_id = 177155;
@@ -1278,6 +1654,7 @@ mod tests {
             accounts: accounts.clone(),
             blocks: vec![genesis_block(chain_id, &accounts, &validator_ids, 2)],
             votes_cast: BTreeMap::new(),
+            pruned_accounts: None,
         };
         let root = std::env::temp_dir().join(format!("native_chain_test_{}", now_nanos()));
         fs::create_dir_all(&root).unwrap();
@@ -1291,6 +1668,7 @@ mod tests {
                     validator_ids.clone(),
                     2,
                     &key.signing,
+                    WallClock::shared(),
                 )
                 .await
                 .unwrap(),
@@ -1387,16 +1765,19 @@ mod tests {
             accounts: accounts.clone(),
             blocks: vec![genesis_block(chain_id, &accounts, &validator_ids, 1)],
             votes_cast: BTreeMap::new(),
+            pruned_accounts: None,
         };
         let root = std::env::temp_dir().join(format!("native_chain_heartbeat_{}", now_nanos()));
         fs::create_dir_all(&root).unwrap();
         let state = Arc::new(RwLock::new(base));
+        let clock = SimulatedClock::new(Duration::from_secs(1_700_000_000));
         let mut runtime = NativeChainRuntime::new(
             state.clone(),
             root.join("state.json"),
             validator_ids,
             1,
             &validator.signing,
+            clock.shared(),
         )
         .await
         .unwrap();
@@ -1406,6 +1787,7 @@ mod tests {
             .await
             .unwrap()
             .expect("genesis-idle chain should produce a heartbeat block");
+        assert_eq!(proposal.timestamp, 1_700_000_000);
         assert!(proposal.transactions.is_empty());
         let messages = runtime
             .handle_message(
@@ -1425,9 +1807,187 @@ mod tests {
         assert_eq!(finalized.latest_number(), 1);
         assert_eq!(finalized.accounts, accounts);
         assert_eq!(finalized.latest_block().proposal.transactions.len(), 0);
-        finalized.validate().unwrap();
+        finalized.validate(clock.now().as_secs()).unwrap();
         drop(finalized);
+        assert!(!runtime.seal_due().await);
         assert!(runtime.propose(&validator.signing).await.unwrap().is_none());
+        clock.advance(DEFAULT_MAX_BLOCK_INTERVAL - Duration::from_secs(1));
+        assert!(!runtime.seal_due().await);
+        clock.advance(Duration::from_secs(1));
+        assert!(runtime.seal_due().await);
+        let heartbeat = runtime.propose(&validator.signing).await.unwrap().unwrap();
+        assert_eq!(
+            heartbeat.timestamp,
+            1_700_000_000 + DEFAULT_MAX_BLOCK_INTERVAL.as_secs()
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn pruned_blocks_move_to_archive_and_state_still_validates() {
+        let chain_id = 177155;
+        let validator = validator("pruner");
+        let validator_ids = vec![encode_public_key_base64(&validator.verifying)];
+        let transfers = (0..3)
+            .map(|nonce| signed_test_transfer([7u8; 32], chain_id, nonce, [9u8; 20], 1))
+            .collect::<Vec<_>>();
+        let tx = transfers[0].clone();
+        let mut accounts = BTreeMap::new();
+        accounts.insert(
+            tx.from.clone(),
+            NativeAccount {
+                balance: 5,
+                nonce: 0,
+            },
+        );
+        let base = NativeChainState {
+            schema: STATE_SCHEMA.to_string(),
+            chain_id,
+            validators: validator_ids.clone(),
+            quorum: 1,
+            genesis_accounts: accounts.clone(),
+            accounts: accounts.clone(),
+            blocks: vec![genesis_block(chain_id, &accounts, &validator_ids, 1)],
+            votes_cast: BTreeMap::new(),
+            pruned_accounts: None,
+        };
+        let root = std::env::temp_dir().join(format!("native_chain_prune_{}", now_nanos()));
+        fs::create_dir_all(&root).unwrap();
+        let state = Arc::new(RwLock::new(base));
+        let mut runtime = NativeChainRuntime::new(
+            state.clone(),
+            root.join("state.json"),
+            validator_ids,
+            1,
+            &validator.signing,
+            WallClock::shared(),
+        )
+        .await
+        .unwrap();
+        for transfer in transfers {
+            runtime.accept_transaction(transfer).await.unwrap();
+            let proposal = runtime.propose(&validator.signing).await.unwrap().unwrap();
+            let messages = runtime
+                .handle_message(
+                    NativeChainMessage::new(NativeChainMessagePayload::Proposal(proposal)),
+                    &validator.signing,
+                )
+                .await
+                .unwrap();
+            for message in messages {
+                runtime
+                    .handle_message(message, &validator.signing)
+                    .await
+                    .unwrap();
+            }
+        }
+
+        let archive = root.join(BLOCK_ARCHIVE_DIR);
+        let mut pruned = state.write().await;
+        assert_eq!(pruned.latest_number(), 3);
+        assert!(pruned.prune_into(&archive, 1, 4).unwrap().is_none());
+        let segment = pruned.prune_into(&archive, 1, 1).unwrap().unwrap();
+        assert_eq!((segment.first, segment.last), (1, 1));
+        assert_eq!(pruned.first_retained(), 2);
+        pruned.validate(WallClock.now().as_secs()).unwrap();
+        assert!(pruned.block_by_number(1).is_none());
+        assert_eq!(pruned.block_by_number(3).unwrap().proposal.number, 3);
+        assert!(pruned.transaction(&tx.hash).is_none());
+        assert!(pruned.accounts_at(1).is_err());
+        assert_eq!(pruned.accounts_at(2).unwrap()[&tx.to].balance, 2);
+        assert_eq!(pruned.accounts_at(3).unwrap(), pruned.accounts);
+
+        assert_eq!(list_segments(&archive).unwrap(), vec![segment]);
+        let (block, index) = find_archived_transaction(&archive, &tx.hash)
+            .unwrap()
+            .unwrap();
+        assert_eq!((block.proposal.number, index), (1, 0));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn traces_replay_earlier_transactions_in_the_block() {
+        let chain_id = 177155;
+        let validator = validator("tracer");
+        let validator_ids = vec![encode_public_key_base64(&validator.verifying)];
+        let first = signed_test_transfer([7u8; 32], chain_id, 0, [9u8; 20], 2);
+        let second = signed_test_transfer([8u8; 32], chain_id, 0, [9u8; 20], 2);
+        let mut accounts = BTreeMap::new();
+        for (sender, balance) in [(&first.from, 5), (&second.from, 3)] {
+            accounts.insert(sender.clone(), NativeAccount { balance, nonce: 0 });
+        }
+        let base = NativeChainState {
+            schema: STATE_SCHEMA.to_string(),
+            chain_id,
+            validators: validator_ids.clone(),
+            quorum: 1,
+            genesis_accounts: accounts.clone(),
+            accounts: accounts.clone(),
+            blocks: vec![genesis_block(chain_id, &accounts, &validator_ids, 1)],
+            votes_cast: BTreeMap::new(),
+            pruned_accounts: None,
+        };
+        let root = std::env::temp_dir().join(format!("native_chain_trace_{}", now_nanos()));
+        fs::create_dir_all(&root).unwrap();
+        let state = Arc::new(RwLock::new(base));
+        let mut runtime = NativeChainRuntime::new(
+            state.clone(),
+            root.join("state.json"),
+            validator_ids,
+            1,
+            &validator.signing,
+            WallClock::shared(),
+        )
+        .await
+        .unwrap();
+        runtime.accept_transaction(first).await.unwrap();
+        runtime.accept_transaction(second).await.unwrap();
+        let proposal = runtime.propose(&validator.signing).await.unwrap().unwrap();
+        let messages = runtime
+            .handle_message(
+                NativeChainMessage::new(NativeChainMessagePayload::Proposal(proposal)),
+                &validator.signing,
+            )
+            .await
+            .unwrap();
+        for message in messages {
+            runtime
+                .handle_message(message, &validator.signing)
+                .await
+                .unwrap();
+        }
+
+        let state = state.read().await;
+        let block = state.latest_block();
+        assert_eq!(block.proposal.transactions.len(), 2);
+        // The recipient already holds the first transfer of the block.
+        let traced = &block.proposal.transactions[1];
+        let funded = accounts[&traced.from].balance;
+        let changes = state.trace_transaction(block, 1).unwrap();
+        assert_eq!(
+            changes,
+            vec![
+                NativeBalanceChange {
+                    kind: NativeChangeKind::Debit,
+                    address: traced.from.clone(),
+                    amount: 2,
+                    balance_before: funded,
+                    balance_after: funded - 2,
+                    nonce_before: 0,
+                    nonce_after: 1,
+                },
+                NativeBalanceChange {
+                    kind: NativeChangeKind::Credit,
+                    address: traced.to.clone(),
+                    amount: 2,
+                    balance_before: 2,
+                    balance_after: 4,
+                    nonce_before: 0,
+                    nonce_after: 0,
+                },
+            ]
+        );
+        assert!(state.trace_transaction(block, 2).is_err());
         fs::remove_dir_all(root).unwrap();
     }
 }
@@ -0,0 +1,184 @@
+#![cfg(feature = "net")]
+
+//! Real and simulated time for the node runtime.
+//!
+//! The swarm tickers, native block production, and the anchor pacing that
+//! numbers checkpoint epochs (and therefore unbonding) read time through a
+//! [`NodeClock`].  Nodes run on [`WallClock`]; tests hand the runtime a
+//! [`SimulatedClock`] and call [`SimulatedClock::advance`] to fast-forward
+//! block intervals and epochs deterministically instead of sleeping.
+
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::watch;
+
+/// Future returned by [`NodeClock::sleep_until`].
+pub type Sleep = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// Time source shared by a node's runtime tasks.
+pub trait NodeClock: Send + Sync + fmt::Debug {
+    /// Time elapsed since the Unix epoch.
+    fn now(&self) -> Duration;
+
+    /// Resolves once [`now`](Self::now) reaches `deadline`.
+    fn sleep_until(&self, deadline: Duration) -> Sleep;
+
+    /// Time elapsed since `earlier`, zero if the clock is behind it.
+    fn since(&self, earlier: Duration) -> Duration {
+        self.now().saturating_sub(earlier)
+    }
+
+    /// [`now`](Self::now) in whole milliseconds.
+    fn now_ms(&self) -> u64 {
+        self.now().as_millis() as u64
+    }
+}
+
+/// Clock handle threaded through [`NetConfig`](crate::net::swarm::NetConfig)
+/// and [`NativeChainRuntime`](crate::net::NativeChainRuntime).
+pub type SharedNodeClock = Arc<dyn NodeClock>;
+
+/// The system wall clock, sleeping on the tokio timer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WallClock;
+
+impl WallClock {
+    /// A shared handle to the wall clock.
+    pub fn shared() -> SharedNodeClock {
+        Arc::new(Self)
+    }
+}
+
+impl NodeClock for WallClock {
+    fn now(&self) -> Duration {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+    }
+
+    fn sleep_until(&self, deadline: Duration) -> Sleep {
+        Box::pin(tokio::time::sleep(deadline.saturating_sub(self.now())))
+    }
+}
+
+/// Manually advanced clock for tests and simulations.
+///
+/// Clones share one timeline, so a test keeps a clone and advances the clock
+/// the runtime under test reads.
+#[derive(Debug, Clone)]
+pub struct SimulatedClock {
+    now: Arc<watch::Sender<Duration>>,
+}
+
+impl SimulatedClock {
+    /// Starts the clock at `start` past the Unix epoch.
+    pub fn new(start: Duration) -> Self {
+        Self {
+            now: Arc::new(watch::Sender::new(start)),
+        }
+    }
+
+    /// A shared handle reading this clock's timeline.
+    pub fn shared(&self) -> SharedNodeClock {
+        Arc::new(self.clone())
+    }
+
+    /// Moves the clock forward by `by`, waking sleepers whose deadline passed.
+    pub fn advance(&self, by: Duration) {
+        self.now.send_modify(|now| *now = now.saturating_add(by));
+    }
+}
+
+impl NodeClock for SimulatedClock {
+    fn now(&self) -> Duration {
+        *self.now.borrow()
+    }
+
+    fn sleep_until(&self, deadline: Duration) -> Sleep {
+        let mut now = self.now.subscribe();
+        Box::pin(async move {
+            if now.wait_for(|now| *now >= deadline).await.is_err() {
+                std::future::pending::<()>().await;
+            }
+        })
+    }
+}
+
+/// Fixed-period ticker driven by a [`NodeClock`].
+///
+/// The first tick completes immediately.  A tick observed late pushes the
+/// following ones back instead of firing a burst to catch up.
+#[derive(Debug)]
+pub struct Ticker {
+    clock: SharedNodeClock,
+    period: Duration,
+    next: Duration,
+}
+
+impl Ticker {
+    /// Ticks every `period` (at least one millisecond) on `clock`.
+    pub fn new(clock: SharedNodeClock, period: Duration) -> Self {
+        let next = clock.now();
+        Self {
+            clock,
+            period: period.max(Duration::from_millis(1)),
+            next,
+        }
+    }
+
+    /// The tick period.
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// Delays the next tick until a full period from now.
+    pub fn reset(&mut self) {
+        self.next = self.clock.now().saturating_add(self.period);
+    }
+
+    /// Waits for the next tick and returns the clock reading it fired at.
+    ///
+    /// Cancel safe: dropping the future leaves the schedule unchanged.
+    pub async fn tick(&mut self) -> Duration {
+        self.clock.sleep_until(self.next).await;
+        let now = self.clock.now();
+        self.next = now.saturating_add(self.period);
+        now
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::FutureExt;
+
+    #[tokio::test]
+    async fn simulated_ticks_follow_advances_without_sleeping() {
+        let clock = SimulatedClock::new(Duration::from_secs(1_000));
+        let mut ticker = Ticker::new(clock.shared(), Duration::from_secs(5));
+        assert_eq!(ticker.tick().await, Duration::from_secs(1_000));
+        assert!(ticker.tick().now_or_never().is_none());
+
+        clock.advance(Duration::from_secs(4));
+        assert!(ticker.tick().now_or_never().is_none());
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(ticker.tick().await, Duration::from_secs(1_005));
+
+        let pending = tokio::spawn({
+            let clock = clock.shared();
+            async move { clock.sleep_until(Duration::from_secs(1_060)).await }
+        });
+        clock.advance(Duration::from_secs(60));
+        pending.await.unwrap();
+        assert_eq!(ticker.tick().await, Duration::from_secs(1_065));
+        assert_eq!(
+            clock.shared().since(Duration::from_secs(1_000)).as_secs(),
+            65
+        );
+    }
+}
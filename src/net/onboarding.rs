@@ -0,0 +1,287 @@
+#![cfg(feature = "net")]
+
+//! Validator onboarding: signed join requests and the membership updates they
+//! produce once governance admits them.
+//!
+//! An operator who wants to join runs `julian net join-request`, which signs a
+//! [`JoinRequest`] with the node key it will run under.  Any member wraps the
+//! artifact in an `admit_member` [`Proposal`](crate::net::Proposal); when the
+//! proposal passes, each node adds the applicant's key to the multisig policy
+//! or [`AllowlistFile`] it was started with.  Onboarding therefore needs two
+//! artifacts, the request and the proposal, instead of hand-edited allowlists
+//! on every peer.
+
+use crate::net::proposal::{ExecutionHook, Proposal, ProposalAction};
+use crate::net::schema::network_id;
+use crate::net::sign::{
+    decode_public_key_base64, encode_public_key_base64, encode_signature_base64, sign_payload,
+    verify_signature_base64, KeyMaterial,
+};
+use blake2::digest::{consts::U32, Digest};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+type Blake2b256 = blake2::Blake2b<U32>;
+
+/// Schema identifier of a [`JoinRequest`].
+pub const SCHEMA_JOIN_REQUEST: &str = "mfenx.powerhouse.join_request.v1";
+
+const JOIN_REQUEST_DOMAIN: &[u8] = b"MFENX_JOIN_REQUEST";
+
+/// Errors produced while handling join requests.
+#[derive(Debug, thiserror::Error)]
+pub enum JoinError {
+    /// A request or allowlist could not be read or written.
+    #[error("onboarding I/O error: {0}")]
+    Io(String),
+    /// A request is malformed or wrongly signed.
+    #[error("invalid join request: {0}")]
+    Invalid(String),
+}
+
+/// Membership application signed by the key the applicant will run under.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JoinRequest {
+    /// Schema identifier (`mfenx.powerhouse.join_request.v1`).
+    pub schema: String,
+    /// Network the applicant wants to join.
+    pub network: String,
+    /// Node identifier the applicant will announce.
+    pub node_id: String,
+    /// Applicant public key (base64).
+    pub public_key: String,
+    /// Multiaddrs peers can dial the applicant on.
+    #[serde(default)]
+    pub addresses: Vec<String>,
+    /// Millisecond timestamp of the request.
+    pub created_ms: u64,
+    /// Base64 signature over every other field.
+    pub signature: String,
+}
+
+impl JoinRequest {
+    /// Builds and signs a request for `node_id` reachable at `addresses`.
+    pub fn new(
+        node_id: impl Into<String>,
+        addresses: Vec<String>,
+        created_ms: u64,
+        key: &KeyMaterial,
+    ) -> Self {
+        let mut request = Self {
+            schema: SCHEMA_JOIN_REQUEST.to_string(),
+            network: network_id().to_string(),
+            node_id: node_id.into(),
+            public_key: encode_public_key_base64(&key.verifying),
+            addresses,
+            created_ms,
+            signature: String::new(),
+        };
+        request.signature =
+            encode_signature_base64(&sign_payload(&key.signing, &request.signing_bytes()));
+        request
+    }
+
+    fn signing_bytes(&self) -> Vec<u8> {
+        #[derive(Serialize)]
+        struct Unsigned<'a> {
+            schema: &'a str,
+            network: &'a str,
+            node_id: &'a str,
+            public_key: &'a str,
+            addresses: &'a [String],
+            created_ms: u64,
+        }
+        serde_json::to_vec(&Unsigned {
+            schema: &self.schema,
+            network: &self.network,
+            node_id: &self.node_id,
+            public_key: &self.public_key,
+            addresses: &self.addresses,
+            created_ms: self.created_ms,
+        })
+        .expect("join request fields serialize")
+    }
+
+    /// Domain-separated digest identifying the request.
+    pub fn digest(&self) -> [u8; 32] {
+        let mut hasher = Blake2b256::new();
+        hasher.update(JOIN_REQUEST_DOMAIN);
+        hasher.update(self.signing_bytes());
+        hasher.finalize().into()
+    }
+
+    /// Checks the schema, network, node id, addresses, and signature.
+    pub fn verify(&self) -> Result<(), JoinError> {
+        if self.schema != SCHEMA_JOIN_REQUEST {
+            return Err(JoinError::Invalid(format!(
+                "unexpected schema {}",
+                self.schema
+            )));
+        }
+        if self.network != network_id() {
+            return Err(JoinError::Invalid(format!(
+                "request targets network {}",
+                self.network
+            )));
+        }
+        if self.node_id.trim().is_empty() {
+            return Err(JoinError::Invalid("node id is empty".to_string()));
+        }
+        for address in &self.addresses {
+            address
+                .parse::<libp2p::Multiaddr>()
+                .map_err(|err| JoinError::Invalid(format!("address {address}: {err}")))?;
+        }
+        decode_public_key_base64(&self.public_key)
+            .map_err(|err| JoinError::Invalid(format!("public key: {err}")))?;
+        verify_signature_base64(&self.public_key, &self.signing_bytes(), &self.signature)
+            .map_err(|err| JoinError::Invalid(format!("request signature: {err}")))
+    }
+
+    /// Reads and verifies a request artifact.
+    pub fn load(path: &Path) -> Result<Self, JoinError> {
+        let bytes = fs::read(path)
+            .map_err(|err| JoinError::Io(format!("read {}: {err}", path.display())))?;
+        let request: Self = serde_json::from_slice(&bytes)
+            .map_err(|err| JoinError::Invalid(format!("{}: {err}", path.display())))?;
+        request.verify()?;
+        Ok(request)
+    }
+}
+
+/// Static allowlist file (`{"allowed": [...]}`) that admissions extend.
+#[derive(Debug, Clone)]
+pub struct AllowlistFile {
+    path: PathBuf,
+    allowed: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AllowlistContents {
+    allowed: Vec<String>,
+}
+
+impl AllowlistFile {
+    /// Opens the allowlist at `path`; a missing file is an empty list.
+    pub fn open(path: &Path) -> Result<Self, JoinError> {
+        let allowed = match fs::read(path) {
+            Ok(bytes) => {
+                serde_json::from_slice::<AllowlistContents>(&bytes)
+                    .map_err(|err| JoinError::Invalid(format!("{}: {err}", path.display())))?
+                    .allowed
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(JoinError::Io(err.to_string())),
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            allowed,
+        })
+    }
+
+    /// Allowed keys in file order.
+    pub fn allowed(&self) -> &[String] {
+        &self.allowed
+    }
+
+    /// Adds `public_key`, returning whether it was new.
+    pub fn admit(&mut self, public_key: &str) -> Result<bool, JoinError> {
+        if self.allowed.iter().any(|known| known == public_key) {
+            return Ok(false);
+        }
+        self.allowed.push(public_key.to_string());
+        self.persist()?;
+        Ok(true)
+    }
+
+    /// Replaces the list with `members`.
+    pub fn replace(&mut self, members: &[String]) -> Result<(), JoinError> {
+        self.allowed = members.to_vec();
+        self.persist()
+    }
+
+    fn persist(&self) -> Result<(), JoinError> {
+        let bytes = serde_json::to_vec_pretty(&AllowlistContents {
+            allowed: self.allowed.clone(),
+        })
+        .map_err(|err| JoinError::Io(err.to_string()))?;
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(&tmp, bytes).map_err(|err| JoinError::Io(err.to_string()))?;
+        fs::rename(&tmp, &self.path).map_err(|err| JoinError::Io(err.to_string()))
+    }
+}
+
+impl ExecutionHook for AllowlistFile {
+    fn execute(&mut self, proposal: &Proposal) -> Result<bool, String> {
+        match &proposal.action {
+            ProposalAction::AdmitMember { request } => {
+                self.admit(&request.public_key)
+                    .map_err(|err| err.to_string())?;
+            }
+            ProposalAction::MembershipRotation { new_members } => {
+                self.replace(new_members).map_err(|err| err.to_string())?;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::governance::{MembershipPolicy, StaticPolicy};
+    use crate::net::sign::{load_or_derive_keypair, Ed25519KeySource};
+
+    #[test]
+    fn admitted_requests_extend_the_allowlist() {
+        let dir = std::env::temp_dir().join(format!("onboarding_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let applicant =
+            load_or_derive_keypair(&Ed25519KeySource::Seed("applicant".to_string())).unwrap();
+        let member = load_or_derive_keypair(&Ed25519KeySource::Seed("member".to_string())).unwrap();
+        let request = JoinRequest::new(
+            "boot3",
+            vec!["/ip4/10.0.0.3/tcp/7003".to_string()],
+            5,
+            &applicant,
+        );
+        let path = dir.join("join.json");
+        fs::write(&path, serde_json::to_vec(&request).unwrap()).unwrap();
+        assert_eq!(JoinRequest::load(&path).unwrap(), request);
+        let mut forged = request.clone();
+        forged.node_id = "boot4".to_string();
+        assert!(matches!(forged.verify(), Err(JoinError::Invalid(_))));
+
+        let allowlist_path = dir.join("allowlist.json");
+        let member_key = encode_public_key_base64(&member.verifying);
+        fs::write(
+            &allowlist_path,
+            format!(r#"{{"allowed":["{member_key}"]}}"#),
+        )
+        .unwrap();
+        let mut allowlist = AllowlistFile::open(&allowlist_path).unwrap();
+        let admission = Proposal::new(
+            ProposalAction::AdmitMember {
+                request: request.clone(),
+            },
+            "",
+            10,
+            20,
+            &member,
+        );
+        admission.verify().unwrap();
+        assert!(allowlist.execute(&admission).unwrap());
+        assert!(!allowlist.admit(&request.public_key).unwrap());
+        let policy = StaticPolicy::from_allowlist(&allowlist_path).unwrap();
+        assert_eq!(
+            policy.current_members(),
+            vec![member.verifying, applicant.verifying]
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,402 @@
+#![cfg(feature = "net")]
+
+//! Runtime parameters that passed governance proposals change without a
+//! restart.
+//!
+//! A node starts from the quorum and broadcast interval it was launched with,
+//! the default gossip envelope limit, and an empty submission fee schedule.
+//! Overrides are kept in `log_dir/governance/parameters.json` by a
+//! [`ParameterStore`].  When a `parameter_change` or `fee_schedule` proposal
+//! is executed the store validates the value, records a
+//! `power_house.governance` `parameter_change` statement on the ledger, saves
+//! the override, and publishes it through the [`SharedParameters`] handle that
+//! the swarm and the JSON-RPC server read on every use.  Names the node does
+//! not interpret are stored verbatim for operators and applications.
+
+use crate::economics::SubmissionFeeSchedule;
+use crate::net::governance::FeeScheduleConfig;
+use crate::net::proposal::{
+    binding_proof, record_statement, write_json, ExecutionHook, Proposal, ProposalAction,
+    GOVERNANCE_APPLICATION_ID, GOVERNANCE_DIR,
+};
+use crate::Statement;
+use blake2::digest::{consts::U32, Digest};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, PoisonError, RwLock},
+    time::Duration,
+};
+
+type Blake2b256 = blake2::Blake2b<U32>;
+
+/// Parameter holding the number of matching anchors that finalize an epoch.
+pub const PARAM_QUORUM: &str = "quorum";
+
+/// Parameter holding the anchor broadcast interval in milliseconds.
+pub const PARAM_BROADCAST_INTERVAL_MS: &str = "broadcast_interval_ms";
+
+/// Parameter holding the largest accepted gossip envelope payload.
+pub const PARAM_MAX_ENVELOPE_BYTES: &str = "max_envelope_bytes";
+
+/// Parameter holding the JSON [`SubmissionFeeSchedule`].
+pub const PARAM_FEE_SCHEDULE: &str = "fee_schedule";
+
+/// Upper bound of [`PARAM_MAX_ENVELOPE_BYTES`], the gossipsub frame size.
+pub const MAX_ENVELOPE_BYTES: usize = 64 * 1024;
+
+const MIN_ENVELOPE_BYTES: usize = 1024;
+const MIN_BROADCAST_INTERVAL_MS: u64 = 100;
+const DEFAULT_BROADCAST_INTERVAL_MS: u64 = 5_000;
+const PARAMETERS_FILE: &str = "parameters.json";
+const PARAMETER_CHANGE_DOMAIN: &[u8] = b"MFENX_GOVERNANCE_PARAMETER_CHANGE";
+
+/// Errors produced by the parameter store.
+#[derive(Debug, thiserror::Error)]
+pub enum ParameterError {
+    /// The store could not be read or written.
+    #[error("parameter store I/O error: {0}")]
+    Io(String),
+    /// A value is not acceptable for its parameter.
+    #[error("invalid value for `{name}`: {reason}")]
+    Invalid {
+        /// Parameter name.
+        name: String,
+        /// What was wrong with the value.
+        reason: String,
+    },
+}
+
+/// Values the swarm and RPC server read while running.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuntimeParameters {
+    /// Matching anchors needed to finalize an epoch.
+    pub quorum: usize,
+    /// Milliseconds between anchor broadcasts.
+    pub broadcast_interval_ms: u64,
+    /// Largest accepted gossip envelope payload.
+    pub max_envelope_bytes: usize,
+    /// Fee schedule quoted to proof submitters.
+    pub fee_schedule: SubmissionFeeSchedule,
+}
+
+impl Default for RuntimeParameters {
+    fn default() -> Self {
+        Self::new(1, Duration::from_millis(DEFAULT_BROADCAST_INTERVAL_MS))
+    }
+}
+
+impl RuntimeParameters {
+    /// Parameters a node launched with `quorum` and `broadcast_interval`
+    /// starts from.
+    pub fn new(quorum: usize, broadcast_interval: Duration) -> Self {
+        Self {
+            quorum,
+            broadcast_interval_ms: broadcast_interval.as_millis() as u64,
+            max_envelope_bytes: MAX_ENVELOPE_BYTES,
+            fee_schedule: SubmissionFeeSchedule::default(),
+        }
+    }
+
+    /// Anchor broadcast interval.
+    pub fn broadcast_interval(&self) -> Duration {
+        Duration::from_millis(self.broadcast_interval_ms)
+    }
+
+    /// Sets `name` from its string form.  Returns `false`, leaving the
+    /// parameters untouched, when `name` is not a runtime parameter.
+    pub fn set(&mut self, name: &str, value: &str) -> Result<bool, ParameterError> {
+        let invalid = |reason: String| ParameterError::Invalid {
+            name: name.to_string(),
+            reason,
+        };
+        let number = || {
+            value
+                .parse::<u64>()
+                .map_err(|_| invalid("not a decimal integer".to_string()))
+        };
+        match name {
+            PARAM_QUORUM => {
+                let quorum = number()?;
+                if quorum == 0 {
+                    return Err(invalid("must be at least 1".to_string()));
+                }
+                self.quorum = quorum as usize;
+            }
+            PARAM_BROADCAST_INTERVAL_MS => {
+                let interval = number()?;
+                if interval < MIN_BROADCAST_INTERVAL_MS {
+                    return Err(invalid(format!(
+                        "must be at least {MIN_BROADCAST_INTERVAL_MS} ms"
+                    )));
+                }
+                self.broadcast_interval_ms = interval;
+            }
+            PARAM_MAX_ENVELOPE_BYTES => {
+                let bytes = number()?;
+                if !(MIN_ENVELOPE_BYTES as u64..=MAX_ENVELOPE_BYTES as u64).contains(&bytes) {
+                    return Err(invalid(format!(
+                        "outside [{MIN_ENVELOPE_BYTES}, {MAX_ENVELOPE_BYTES}]"
+                    )));
+                }
+                self.max_envelope_bytes = bytes as usize;
+            }
+            PARAM_FEE_SCHEDULE => {
+                self.fee_schedule = serde_json::from_str(value)
+                    .map_err(|err| invalid(format!("not a fee schedule: {err}")))?;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+/// Runtime parameters shared between the swarm, the RPC server, and the
+/// governance executor.
+#[derive(Debug, Clone, Default)]
+pub struct SharedParameters(Arc<RwLock<RuntimeParameters>>);
+
+impl SharedParameters {
+    /// Shares `parameters`.
+    pub fn new(parameters: RuntimeParameters) -> Self {
+        Self(Arc::new(RwLock::new(parameters)))
+    }
+
+    /// Copy of the current parameters.
+    pub fn snapshot(&self) -> RuntimeParameters {
+        self.0
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Current anchor quorum.
+    pub fn quorum(&self) -> usize {
+        self.0.read().unwrap_or_else(PoisonError::into_inner).quorum
+    }
+
+    /// Current anchor broadcast interval.
+    pub fn broadcast_interval(&self) -> Duration {
+        self.0
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .broadcast_interval()
+    }
+
+    /// Current gossip envelope limit.
+    pub fn max_envelope_bytes(&self) -> usize {
+        self.0
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .max_envelope_bytes
+    }
+
+    /// Replaces every parameter.
+    pub fn replace(&self, parameters: RuntimeParameters) {
+        *self.0.write().unwrap_or_else(PoisonError::into_inner) = parameters;
+    }
+
+    /// Sets one parameter; see [`RuntimeParameters::set`].
+    pub fn set(&self, name: &str, value: &str) -> Result<bool, ParameterError> {
+        self.0
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .set(name, value)
+    }
+}
+
+/// Ledger statement recording that `proposal_id` set `name` to `value`.
+pub fn parameter_change_statement(proposal_id: &str, name: &str, value: &str) -> Statement {
+    Statement::structured(GOVERNANCE_APPLICATION_ID, "parameter_change")
+        .with_parameter("proposal", proposal_id)
+        .with_parameter("name", name)
+        .with_parameter("value", value)
+}
+
+fn parameter_change_digest(proposal_id: &str, name: &str, value: &str) -> [u8; 32] {
+    let mut hasher = Blake2b256::new();
+    hasher.update(PARAMETER_CHANGE_DOMAIN);
+    for field in [proposal_id, name, value] {
+        hasher.update((field.len() as u64).to_be_bytes());
+        hasher.update(field.as_bytes());
+    }
+    hasher.finalize().into()
+}
+
+/// Network parameters set by passed proposals, kept as a JSON map.
+#[derive(Debug)]
+pub struct ParameterStore {
+    log_dir: PathBuf,
+    path: PathBuf,
+    values: BTreeMap<String, String>,
+    runtime: Option<SharedParameters>,
+    fee_schedule_config: Option<FeeScheduleConfig>,
+}
+
+impl ParameterStore {
+    /// Opens the store at `log_dir/governance/parameters.json`.
+    pub fn open(log_dir: &Path) -> Result<Self, ParameterError> {
+        let path = log_dir.join(GOVERNANCE_DIR).join(PARAMETERS_FILE);
+        let values = match fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map_err(|err| ParameterError::Io(format!("{}: {err}", path.display())))?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(ParameterError::Io(err.to_string())),
+        };
+        Ok(Self {
+            log_dir: log_dir.to_path_buf(),
+            path,
+            values,
+            runtime: None,
+            fee_schedule_config: None,
+        })
+    }
+
+    /// Publishes executed changes to `runtime`.
+    pub fn with_runtime(mut self, runtime: SharedParameters) -> Self {
+        self.runtime = Some(runtime);
+        self
+    }
+
+    /// Mirrors executed fee schedule changes into `config`.
+    pub fn with_fee_schedule_config(mut self, config: FeeScheduleConfig) -> Self {
+        self.fee_schedule_config = Some(config);
+        self
+    }
+
+    /// Current value of `name`.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    /// Every parameter in name order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Applies the stored overrides on top of `base`.
+    pub fn apply(&self, mut base: RuntimeParameters) -> Result<RuntimeParameters, ParameterError> {
+        for (name, value) in &self.values {
+            base.set(name, value)?;
+        }
+        Ok(base)
+    }
+
+    fn change(&mut self, proposal: &Proposal, name: &str, value: &str) -> Result<(), String> {
+        let mut validated = RuntimeParameters::default();
+        validated.set(name, value).map_err(|err| err.to_string())?;
+        let proposal_id = proposal.id_hex();
+        record_statement(
+            &self.log_dir,
+            parameter_change_statement(&proposal_id, name, value),
+            binding_proof(&parameter_change_digest(&proposal_id, name, value)),
+        )
+        .map_err(|err| err.to_string())?;
+        self.values.insert(name.to_string(), value.to_string());
+        write_json(&self.path, &self.values).map_err(|err| err.to_string())?;
+        if name == PARAM_FEE_SCHEDULE {
+            if let Some(config) = self.fee_schedule_config.as_mut() {
+                config
+                    .set_schedule(validated.fee_schedule)
+                    .map_err(|err| err.to_string())?;
+            }
+        }
+        if let Some(runtime) = &self.runtime {
+            runtime.set(name, value).map_err(|err| err.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+impl ExecutionHook for ParameterStore {
+    fn execute(&mut self, proposal: &Proposal) -> Result<bool, String> {
+        match &proposal.action {
+            ProposalAction::ParameterChange { name, value } => {
+                self.change(proposal, name, value)?
+            }
+            ProposalAction::FeeSchedule { schedule } => {
+                let value = serde_json::to_string(schedule).map_err(|err| err.to_string())?;
+                self.change(proposal, PARAM_FEE_SCHEDULE, &value)?
+            }
+            ProposalAction::MembershipRotation { .. } | ProposalAction::AdmitMember { .. } => {
+                return Ok(false)
+            }
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::sign::{load_or_derive_keypair, Ed25519KeySource};
+    use crate::ProofLedger;
+
+    #[test]
+    fn executed_changes_reach_the_runtime_and_the_ledger() {
+        let dir = std::env::temp_dir().join(format!("parameters_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let key = load_or_derive_keypair(&Ed25519KeySource::Seed("m1".to_string())).unwrap();
+        let runtime = SharedParameters::new(RuntimeParameters::new(2, Duration::from_secs(5)));
+        let mut store = ParameterStore::open(&dir)
+            .unwrap()
+            .with_runtime(runtime.clone());
+        let propose = |name: &str, value: &str| {
+            Proposal::new(
+                ProposalAction::ParameterChange {
+                    name: name.to_string(),
+                    value: value.to_string(),
+                },
+                "",
+                0,
+                1,
+                &key,
+            )
+        };
+
+        assert!(store.execute(&propose(PARAM_QUORUM, "3")).unwrap());
+        assert!(store
+            .execute(&propose(PARAM_BROADCAST_INTERVAL_MS, "50"))
+            .unwrap_err()
+            .contains("at least 100 ms"));
+        assert!(store.execute(&propose("motd", "hello")).unwrap());
+        let schedule = SubmissionFeeSchedule {
+            base_fee: 7,
+            ..SubmissionFeeSchedule::default()
+        };
+        let fees = Proposal::new(
+            ProposalAction::FeeSchedule {
+                schedule: schedule.clone(),
+            },
+            "",
+            0,
+            1,
+            &key,
+        );
+        assert!(store.execute(&fees).unwrap());
+        assert_eq!(runtime.quorum(), 3);
+        assert_eq!(runtime.snapshot().fee_schedule, schedule);
+        assert_eq!(runtime.broadcast_interval(), Duration::from_secs(5));
+
+        let reopened = ParameterStore::open(&dir).unwrap();
+        assert_eq!(reopened.get("motd"), Some("hello"));
+        let restarted = reopened
+            .apply(RuntimeParameters::new(1, Duration::from_secs(1)))
+            .unwrap();
+        assert_eq!((restarted.quorum, restarted.fee_schedule.base_fee), (3, 7));
+
+        let ledger = ProofLedger::replay_from_logs(&dir).unwrap();
+        let changes: Vec<_> = ledger
+            .entries()
+            .iter()
+            .filter(|entry| entry.statement.claim_type == "parameter_change")
+            .map(|entry| entry.statement.parameters["name"].clone())
+            .collect();
+        assert_eq!(changes, ["quorum", "motd", "fee_schedule"]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
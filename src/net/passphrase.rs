@@ -0,0 +1,237 @@
+#![cfg(feature = "net")]
+
+//! Non-interactive sources for the identity passphrase.
+//!
+//! `julian net start --identity` prompts on the terminal by default, which
+//! blocks unattended restarts. Operators can instead pass the passphrase
+//! through an inherited file descriptor, an environment variable, a file, or
+//! (with the `keychain` feature) the OS secret store. The node logs which
+//! source it used, never the passphrase itself.
+
+use crate::net::sign::KeyError;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Keychain service name under which identity passphrases are stored.
+pub const KEYCHAIN_SERVICE: &str = "power_house";
+
+/// Where the identity passphrase comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PassphraseSource {
+    /// Interactive terminal prompt.
+    Prompt,
+    /// Contents of an inherited file descriptor, e.g. `3<secret` in a shell.
+    Fd(u32),
+    /// Value of the named environment variable.
+    Env(String),
+    /// Contents of a file readable only by the node user.
+    File(PathBuf),
+    /// OS keychain entry for the given account (requires the `keychain` feature).
+    Keychain(String),
+}
+
+impl PassphraseSource {
+    /// Short description for logs; never includes secret material.
+    pub fn label(&self) -> String {
+        match self {
+            Self::Prompt => "prompt".to_string(),
+            Self::Fd(fd) => format!("fd:{fd}"),
+            Self::Env(var) => format!("env:{var}"),
+            Self::File(path) => format!("file:{}", path.display()),
+            Self::Keychain(account) => format!("keychain:{KEYCHAIN_SERVICE}/{account}"),
+        }
+    }
+}
+
+/// Keychain account naming the identity stored at `identity`.
+pub fn keychain_account(identity: &Path) -> String {
+    fs::canonicalize(identity)
+        .unwrap_or_else(|_| identity.to_path_buf())
+        .display()
+        .to_string()
+}
+
+/// Reads a passphrase from a non-interactive source.
+///
+/// [`PassphraseSource::Prompt`] is left to the caller, which owns the terminal.
+/// One trailing newline is stripped from descriptor and file contents.
+pub fn read_passphrase(source: &PassphraseSource) -> Result<String, KeyError> {
+    let raw = match source {
+        PassphraseSource::Prompt => {
+            return Err(KeyError::Io(
+                "interactive prompt is handled by the caller".to_string(),
+            ))
+        }
+        PassphraseSource::Fd(fd) => fs::read_to_string(format!("/dev/fd/{fd}"))
+            .map_err(|err| KeyError::Io(format!("read passphrase fd {fd}: {err}")))?,
+        PassphraseSource::Env(var) => {
+            return std::env::var(var)
+                .map_err(|err| KeyError::Io(format!("read passphrase env {var}: {err}")))
+        }
+        PassphraseSource::File(path) => fs::read_to_string(path)
+            .map_err(|err| KeyError::Io(format!("read {}: {err}", path.display())))?,
+        PassphraseSource::Keychain(account) => keychain_lookup(account)?,
+    };
+    Ok(strip_newline(raw))
+}
+
+fn strip_newline(mut value: String) -> String {
+    if value.ends_with('\n') {
+        value.pop();
+        if value.ends_with('\r') {
+            value.pop();
+        }
+    }
+    value
+}
+
+#[cfg(not(feature = "keychain"))]
+fn keychain_lookup(_account: &str) -> Result<String, KeyError> {
+    Err(KeyError::Io(
+        "julian was built without the `keychain` feature".to_string(),
+    ))
+}
+
+#[cfg(feature = "keychain")]
+fn keychain_lookup(account: &str) -> Result<String, KeyError> {
+    let output = keychain::lookup_command(account)
+        .output()
+        .map_err(|err| KeyError::Io(format!("run keychain tool: {err}")))?;
+    if !output.status.success() {
+        return Err(KeyError::Io(format!(
+            "no keychain entry for {KEYCHAIN_SERVICE}/{account}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    String::from_utf8(output.stdout)
+        .map_err(|_| KeyError::Decode("keychain passphrase is not UTF-8".to_string()))
+}
+
+/// Stores `passphrase` in the OS keychain under `account`.
+///
+/// Uses `secret-tool` (libsecret) on Linux and `security -i` on macOS; both
+/// receive the passphrase on stdin rather than the command line.
+#[cfg(feature = "keychain")]
+pub fn keychain_store(account: &str, passphrase: &str) -> Result<(), KeyError> {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let input = keychain::store_input(account, passphrase)?;
+    let mut child = keychain::store_command(account)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| KeyError::Io(format!("run keychain tool: {err}")))?;
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(input.as_bytes())
+        .map_err(|err| KeyError::Io(format!("write keychain tool input: {err}")))?;
+    let output = child
+        .wait_with_output()
+        .map_err(|err| KeyError::Io(format!("run keychain tool: {err}")))?;
+    if !output.status.success() {
+        return Err(KeyError::Io(format!(
+            "keychain store failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(all(feature = "keychain", target_os = "macos"))]
+mod keychain {
+    use super::{KeyError, KEYCHAIN_SERVICE};
+    use std::process::Command;
+
+    pub(super) fn lookup_command(account: &str) -> Command {
+        let mut command = Command::new("security");
+        command.args([
+            "find-generic-password",
+            "-s",
+            KEYCHAIN_SERVICE,
+            "-a",
+            account,
+            "-w",
+        ]);
+        command
+    }
+
+    pub(super) fn store_command(_account: &str) -> Command {
+        // Interactive mode reads the command, and so the password, from stdin.
+        let mut command = Command::new("security");
+        command.arg("-i");
+        command
+    }
+
+    pub(super) fn store_input(account: &str, passphrase: &str) -> Result<String, KeyError> {
+        if [account, passphrase]
+            .iter()
+            .any(|value| value.contains(['"', '\\', '\n']))
+        {
+            return Err(KeyError::Decode(
+                "keychain values cannot contain quotes, backslashes, or newlines".to_string(),
+            ));
+        }
+        Ok(format!(
+            "add-generic-password -U -s {KEYCHAIN_SERVICE} -a \"{account}\" -w \"{passphrase}\"\n"
+        ))
+    }
+}
+
+#[cfg(all(feature = "keychain", not(target_os = "macos")))]
+mod keychain {
+    use super::{KeyError, KEYCHAIN_SERVICE};
+    use std::process::Command;
+
+    pub(super) fn lookup_command(account: &str) -> Command {
+        let mut command = Command::new("secret-tool");
+        command.args(["lookup", "service", KEYCHAIN_SERVICE, "account", account]);
+        command
+    }
+
+    pub(super) fn store_command(account: &str) -> Command {
+        let mut command = Command::new("secret-tool");
+        command.args([
+            "store",
+            "--label",
+            "power_house identity passphrase",
+            "service",
+            KEYCHAIN_SERVICE,
+            "account",
+            account,
+        ]);
+        command
+    }
+
+    pub(super) fn store_input(_account: &str, passphrase: &str) -> Result<String, KeyError> {
+        Ok(passphrase.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_passphrase_from_file_and_env_sources() {
+        let path = std::env::temp_dir().join(format!("passphrase_{}.txt", std::process::id()));
+        fs::write(&path, "correct horse\r\n").unwrap();
+        let source = PassphraseSource::File(path.clone());
+        assert_eq!(read_passphrase(&source).unwrap(), "correct horse");
+        assert_eq!(source.label(), format!("file:{}", path.display()));
+        fs::remove_file(&path).unwrap();
+        assert!(read_passphrase(&source).is_err());
+
+        assert!(read_passphrase(&PassphraseSource::Env(
+            "POWER_HOUSE_TEST_UNSET_PASSPHRASE".to_string()
+        ))
+        .is_err());
+        assert!(read_passphrase(&PassphraseSource::Prompt).is_err());
+        assert_eq!(PassphraseSource::Fd(3).label(), "fd:3");
+    }
+}
@@ -0,0 +1,231 @@
+#![cfg(feature = "net")]
+
+//! Persistent peer address book used to warm up the swarm after a restart.
+
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// File name of the peer store inside the node log directory.
+pub const PEER_STORE_FILE: &str = "peers.json";
+/// Peers not seen within this window are skipped when dialing and pruned on save.
+pub const PEER_STORE_MAX_AGE_SECS: u64 = 7 * 24 * 60 * 60;
+
+const MAX_ADDRS_PER_PEER: usize = 8;
+const MAX_PEERS: usize = 256;
+const MIN_SCORE: i64 = -20;
+const MAX_SCORE: i64 = 20;
+
+/// Known addresses and health for a single peer.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct PeerRecord {
+    /// Dialable multiaddrs, most recently confirmed first.
+    pub addrs: Vec<String>,
+    /// Unix timestamp (seconds) of the last successful contact.
+    pub last_seen: u64,
+    /// Health score: successful connections raise it, dial failures lower it.
+    pub score: i64,
+}
+
+/// Peer address book keyed by libp2p peer id.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PeerStore {
+    peers: BTreeMap<String, PeerRecord>,
+}
+
+impl PeerStore {
+    /// Path of the peer store for a node log directory.
+    pub fn path_for(log_dir: &Path) -> PathBuf {
+        log_dir.join(PEER_STORE_FILE)
+    }
+
+    /// Load from JSON; missing file -> empty store.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let bytes = fs::read(path).map_err(|e| e.to_string())?;
+        serde_json::from_slice(&bytes).map_err(|e| e.to_string())
+    }
+
+    /// Persist to JSON atomically.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let data = serde_json::to_vec_pretty(self).map_err(|e| e.to_string())?;
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let temp_path = path.with_extension(format!("tmp-{}-{nonce}", std::process::id()));
+        let write_result = (|| -> Result<(), String> {
+            let mut file = fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&temp_path)
+                .map_err(|e| e.to_string())?;
+            file.write_all(&data).map_err(|e| e.to_string())?;
+            file.sync_all().map_err(|e| e.to_string())?;
+            fs::rename(&temp_path, path).map_err(|e| e.to_string())
+        })();
+        if write_result.is_err() {
+            let _ = fs::remove_file(&temp_path);
+        }
+        write_result
+    }
+
+    /// Return the record for a peer, if known.
+    pub fn peer(&self, peer_id: &str) -> Option<&PeerRecord> {
+        self.peers.get(peer_id)
+    }
+
+    /// Number of peers tracked.
+    pub fn len(&self) -> usize {
+        self.peers.len()
+    }
+
+    /// Whether the store is empty.
+    pub fn is_empty(&self) -> bool {
+        self.peers.is_empty()
+    }
+
+    /// Record a successful connection to `peer_id` reachable at `addr`.
+    pub fn record_connected(&mut self, peer_id: &str, addr: &str, now: u64) {
+        let record = self.peers.entry(peer_id.to_string()).or_default();
+        record.last_seen = now;
+        record.score = (record.score + 1).min(MAX_SCORE);
+        promote_addr(record, addr);
+    }
+
+    /// Record additional listen addresses advertised by a connected peer.
+    pub fn record_addrs(&mut self, peer_id: &str, addrs: &[String], now: u64) {
+        let record = self.peers.entry(peer_id.to_string()).or_default();
+        record.last_seen = now;
+        for addr in addrs.iter().rev() {
+            promote_addr(record, addr);
+        }
+    }
+
+    /// Record a failed dial attempt.
+    pub fn record_failure(&mut self, peer_id: &str) {
+        if let Some(record) = self.peers.get_mut(peer_id) {
+            record.score = (record.score - 1).max(MIN_SCORE);
+        }
+    }
+
+    /// Drop peers not seen within `max_age_secs`, then keep the healthiest `MAX_PEERS`.
+    pub fn prune(&mut self, now: u64, max_age_secs: u64) {
+        self.peers
+            .retain(|_, record| now.saturating_sub(record.last_seen) <= max_age_secs);
+        if self.peers.len() > MAX_PEERS {
+            let keep: Vec<String> = self
+                .ranked(now, u64::MAX)
+                .into_iter()
+                .take(MAX_PEERS)
+                .map(|(peer_id, _)| peer_id.to_string())
+                .collect();
+            self.peers.retain(|peer_id, _| keep.contains(peer_id));
+        }
+    }
+
+    /// Addresses of recently healthy peers in dial order.
+    ///
+    /// Peers with a negative score or a `last_seen` older than `max_age_secs` are
+    /// skipped. The rest are ordered by score, then recency, then peer id.
+    pub fn preferred_addrs(&self, now: u64, max_age_secs: u64) -> Vec<String> {
+        self.ranked(now, max_age_secs)
+            .into_iter()
+            .filter(|(_, record)| record.score >= 0)
+            .flat_map(|(peer_id, record)| {
+                record
+                    .addrs
+                    .iter()
+                    .map(move |addr| with_peer_suffix(addr, peer_id))
+            })
+            .collect()
+    }
+
+    fn ranked(&self, now: u64, max_age_secs: u64) -> Vec<(&str, &PeerRecord)> {
+        let mut ranked: Vec<(&str, &PeerRecord)> = self
+            .peers
+            .iter()
+            .filter(|(_, record)| now.saturating_sub(record.last_seen) <= max_age_secs)
+            .map(|(peer_id, record)| (peer_id.as_str(), record))
+            .collect();
+        ranked.sort_by(|(left_id, left), (right_id, right)| {
+            right
+                .score
+                .cmp(&left.score)
+                .then(right.last_seen.cmp(&left.last_seen))
+                .then(left_id.cmp(right_id))
+        });
+        ranked
+    }
+}
+
+fn promote_addr(record: &mut PeerRecord, addr: &str) {
+    if addr.is_empty() {
+        return;
+    }
+    record.addrs.retain(|existing| existing != addr);
+    record.addrs.insert(0, addr.to_string());
+    record.addrs.truncate(MAX_ADDRS_PER_PEER);
+}
+
+fn with_peer_suffix(addr: &str, peer_id: &str) -> String {
+    if addr.contains("/p2p/") {
+        addr.to_string()
+    } else {
+        format!("{}/p2p/{peer_id}", addr.trim_end_matches('/'))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn peer_store_roundtrip_and_ordering() {
+        let mut store = PeerStore::default();
+        store.record_connected("peer-a", "/ip4/10.0.0.1/tcp/7000", 100);
+        store.record_connected("peer-b", "/ip4/10.0.0.2/tcp/7000", 200);
+        store.record_connected("peer-b", "/ip4/10.0.0.2/tcp/7000", 210);
+        store.record_connected("peer-c", "/ip4/10.0.0.3/tcp/7000/p2p/peer-c", 220);
+        store.record_failure("peer-c");
+        store.record_failure("peer-c");
+        store.record_connected("peer-old", "/ip4/10.0.0.9/tcp/7000", 1);
+
+        let preferred = store.preferred_addrs(300, 250);
+        assert_eq!(
+            preferred,
+            vec![
+                "/ip4/10.0.0.2/tcp/7000/p2p/peer-b".to_string(),
+                "/ip4/10.0.0.1/tcp/7000/p2p/peer-a".to_string(),
+            ]
+        );
+
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("power_house_peer_store_{nanos}"));
+        let path = PeerStore::path_for(&dir);
+        store.prune(300, 250);
+        store.save(&path).unwrap();
+        let loaded = PeerStore::load(&path).unwrap();
+        assert_eq!(loaded.len(), 3);
+        assert!(loaded.peer("peer-old").is_none());
+        assert_eq!(loaded.peer("peer-b").unwrap().score, 2);
+        assert_eq!(loaded.peer("peer-c").unwrap().score, -1);
+        assert!(PeerStore::load(&dir.join("missing.json"))
+            .unwrap()
+            .is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,492 @@
+#![cfg(feature = "net")]
+
+//! Validated membership policy edits with a change history.
+//!
+//! `julian policy add-key|remove-key` edit a static allowlist
+//! (`{"allowed": [...]}`) or a multisig policy state in place.  Keys must
+//! decode to ed25519 public keys, and an edit that would leave no members is
+//! refused because an empty list admits every peer.  A multisig edit takes
+//! effect only with a [`GovernanceUpdate`] that names the resulting members,
+//! is bound to the current [`policy_digest`](PolicySnapshot::digest), and
+//! carries approvals from enough signers.  Each applied edit is appended to
+//! `<policy>.history.jsonl`; operators compare the digest across nodes to
+//! confirm every peer runs the same policy.
+
+use crate::net::governance::{GovernanceUpdate, MembershipPolicy, MultisigPolicy, UpdateKind};
+use crate::net::onboarding::AllowlistFile;
+use crate::net::sign::{decode_public_key_base64, encode_public_key_base64};
+use blake2::digest::{consts::U32, Digest};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{
+    collections::HashSet,
+    fs::{self, OpenOptions},
+    io::{self, Write as _},
+    path::{Path, PathBuf},
+};
+
+type Blake2b256 = blake2::Blake2b<U32>;
+
+/// Schema identifier of a [`PolicyChange`] history record.
+pub const SCHEMA_POLICY_CHANGE: &str = "mfenx.powerhouse.policy_change.v1";
+
+const POLICY_DIGEST_DOMAIN: &[u8] = b"MFENX_POLICY_DIGEST";
+
+/// Errors produced while inspecting or editing a policy file.
+#[derive(Debug, thiserror::Error)]
+pub enum PolicyEditError {
+    /// The policy or its history could not be read or written.
+    #[error("policy I/O error: {0}")]
+    Io(String),
+    /// A key, policy file, or approval is malformed or does not match the edit.
+    #[error("invalid policy edit: {0}")]
+    Invalid(String),
+    /// The edit was refused and the policy left unchanged.
+    #[error("policy edit refused: {0}")]
+    Refused(String),
+}
+
+/// Format of a policy file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyKind {
+    /// Static allowlist edited directly by the operator.
+    Allowlist,
+    /// Multisig policy whose edits need signer approvals.
+    Multisig,
+}
+
+impl PolicyKind {
+    /// Name used in output and digests.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Allowlist => "allowlist",
+            Self::Multisig => "multisig",
+        }
+    }
+}
+
+/// Direction of a key edit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyEditAction {
+    /// Admit a key.
+    AddKey,
+    /// Remove a key.
+    RemoveKey,
+}
+
+impl KeyEditAction {
+    /// Name used in approvals and history records.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::AddKey => "add_key",
+            Self::RemoveKey => "remove_key",
+        }
+    }
+}
+
+/// Membership state read from a policy file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicySnapshot {
+    /// File format.
+    pub kind: PolicyKind,
+    /// Member keys (base64) in file order.
+    pub members: Vec<String>,
+    /// Approvals a multisig edit needs; `None` for allowlists.
+    pub threshold: Option<usize>,
+    /// Multisig signer keys (base64), sorted; empty for allowlists.
+    pub signers: Vec<String>,
+}
+
+impl PolicySnapshot {
+    /// Reads the allowlist or multisig state at `path`.
+    pub fn load(path: &Path) -> Result<Self, PolicyEditError> {
+        let bytes = fs::read(path)
+            .map_err(|err| PolicyEditError::Io(format!("{}: {err}", path.display())))?;
+        let value: Value = serde_json::from_slice(&bytes)
+            .map_err(|err| PolicyEditError::Invalid(format!("{}: {err}", path.display())))?;
+        if value.get("allowed").is_some() {
+            let allowlist = AllowlistFile::open(path)
+                .map_err(|err| PolicyEditError::Invalid(err.to_string()))?;
+            for key in allowlist.allowed() {
+                canonical_key(key)?;
+            }
+            Ok(Self {
+                kind: PolicyKind::Allowlist,
+                members: allowlist.allowed().to_vec(),
+                threshold: None,
+                signers: Vec::new(),
+            })
+        } else if value.get("signers").is_some() {
+            let policy = MultisigPolicy::load(path)
+                .map_err(|err| PolicyEditError::Invalid(format!("{}: {err}", path.display())))?;
+            Ok(Self {
+                kind: PolicyKind::Multisig,
+                members: policy
+                    .current_members()
+                    .iter()
+                    .map(encode_public_key_base64)
+                    .collect(),
+                threshold: Some(policy.threshold()),
+                signers: policy.signers(),
+            })
+        } else {
+            Err(PolicyEditError::Invalid(format!(
+                "{} is neither an allowlist nor a multisig policy",
+                path.display()
+            )))
+        }
+    }
+
+    /// BLAKE2b-256 over the kind, threshold, and sorted members and signers;
+    /// equal on every node running the same policy regardless of key order.
+    pub fn digest(&self) -> [u8; 32] {
+        let mut members = self.members.clone();
+        members.sort();
+        let mut hasher = Blake2b256::new();
+        hasher.update(POLICY_DIGEST_DOMAIN);
+        hasher.update(self.kind.label().as_bytes());
+        hasher.update((self.threshold.unwrap_or(0) as u64).to_le_bytes());
+        for list in [&members, &self.signers] {
+            hasher.update((list.len() as u64).to_le_bytes());
+            for key in list {
+                hasher.update(key.as_bytes());
+                hasher.update(b"\n");
+            }
+        }
+        hasher.finalize().into()
+    }
+
+    /// Hex of [`Self::digest`].
+    pub fn digest_hex(&self) -> String {
+        hex::encode(self.digest())
+    }
+
+    /// Members after applying `edit`.
+    pub fn edited_members(&self, edit: &KeyEdit) -> Result<Vec<String>, PolicyEditError> {
+        let present = self.members.contains(&edit.key);
+        let mut members = self.members.clone();
+        match edit.action {
+            KeyEditAction::AddKey if present => {
+                return Err(PolicyEditError::Refused(format!(
+                    "{} is already a member",
+                    edit.key
+                )));
+            }
+            KeyEditAction::AddKey => members.push(edit.key.clone()),
+            KeyEditAction::RemoveKey if !present => {
+                return Err(PolicyEditError::Refused(format!(
+                    "{} is not a member",
+                    edit.key
+                )));
+            }
+            KeyEditAction::RemoveKey => members.retain(|member| *member != edit.key),
+        }
+        if members.is_empty() {
+            return Err(PolicyEditError::Refused(
+                "removing the last member would admit every peer".to_string(),
+            ));
+        }
+        Ok(members)
+    }
+
+    /// Distinct signers of this policy that approved `update`.
+    pub fn approvals(&self, update: &GovernanceUpdate) -> usize {
+        update
+            .signatures
+            .iter()
+            .filter(|approval| self.signers.contains(&approval.signer))
+            .map(|approval| approval.signer.as_str())
+            .collect::<HashSet<_>>()
+            .len()
+    }
+}
+
+/// A key to add or remove.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyEdit {
+    /// Add or remove.
+    pub action: KeyEditAction,
+    /// Canonical base64 ed25519 public key.
+    pub key: String,
+}
+
+fn canonical_key(key: &str) -> Result<String, PolicyEditError> {
+    decode_public_key_base64(key.trim())
+        .map(|key| encode_public_key_base64(&key))
+        .map_err(|err| PolicyEditError::Invalid(format!("{key}: not an ed25519 public key: {err}")))
+}
+
+impl KeyEdit {
+    /// Validates `key` as a base64 ed25519 public key.
+    pub fn new(action: KeyEditAction, key: &str) -> Result<Self, PolicyEditError> {
+        Ok(Self {
+            action,
+            key: canonical_key(key)?,
+        })
+    }
+
+    fn metadata(&self, policy: &PolicySnapshot, note: Option<&str>) -> Value {
+        json!({
+            "action": self.action.label(),
+            "key": self.key,
+            "policy_digest": policy.digest_hex(),
+            "note": note,
+        })
+    }
+
+    /// Unsigned multisig update performing this edit on `policy`; signers add
+    /// their approvals with [`GovernanceUpdate::approve`].
+    pub fn update(
+        &self,
+        policy: &PolicySnapshot,
+        note: Option<&str>,
+    ) -> Result<GovernanceUpdate, PolicyEditError> {
+        Ok(GovernanceUpdate {
+            kind: UpdateKind::Membership,
+            new_members: policy.edited_members(self)?,
+            metadata: Some(self.metadata(policy, note)),
+            signatures: Vec::new(),
+        })
+    }
+
+    /// Checks that `update` performs this edit on the current `policy`.
+    pub fn check_update(
+        &self,
+        policy: &PolicySnapshot,
+        update: &GovernanceUpdate,
+    ) -> Result<(), PolicyEditError> {
+        let note = update
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get("note"))
+            .and_then(Value::as_str);
+        if update.new_members != policy.edited_members(self)?
+            || update.metadata.as_ref() != Some(&self.metadata(policy, note))
+        {
+            return Err(PolicyEditError::Invalid(
+                "the update was prepared for a different edit or policy state".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// History record of an applied edit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyChange {
+    /// Schema identifier (`mfenx.powerhouse.policy_change.v1`).
+    pub schema: String,
+    /// Millisecond timestamp of the edit.
+    pub changed_ms: u64,
+    /// Add or remove.
+    pub action: KeyEditAction,
+    /// Edited key (base64).
+    pub key: String,
+    /// Format of the edited policy.
+    pub policy: PolicyKind,
+    /// Policy digest before the edit.
+    pub digest_before: String,
+    /// Policy digest after the edit.
+    pub digest_after: String,
+    /// Signers whose approvals authorised a multisig edit.
+    #[serde(default)]
+    pub approvers: Vec<String>,
+    /// Operator note.
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// History file kept beside the policy at `path`.
+pub fn policy_history_path(path: &Path) -> PathBuf {
+    path.with_extension("history.jsonl")
+}
+
+/// Reads the edit history of the policy at `path`, oldest first.
+pub fn load_policy_history(path: &Path) -> Result<Vec<PolicyChange>, PolicyEditError> {
+    let history = policy_history_path(path);
+    let text = match fs::read_to_string(&history) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => {
+            return Err(PolicyEditError::Io(format!("{}: {err}", history.display())));
+        }
+    };
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line)
+                .map_err(|err| PolicyEditError::Invalid(format!("{}: {err}", history.display())))
+        })
+        .collect()
+}
+
+/// Applies `edit` to the policy at `path` and records it in the history.
+///
+/// Multisig policies require `approval`, an update from [`KeyEdit::update`]
+/// approved by at least the policy threshold of signers; allowlists ignore it.
+pub fn apply_key_edit(
+    path: &Path,
+    edit: &KeyEdit,
+    approval: Option<&GovernanceUpdate>,
+    changed_ms: u64,
+) -> Result<PolicyChange, PolicyEditError> {
+    let before = PolicySnapshot::load(path)?;
+    let members = before.edited_members(edit)?;
+    let mut approvers = Vec::new();
+    let mut note = None;
+    match before.kind {
+        PolicyKind::Allowlist => {
+            AllowlistFile::open(path)
+                .and_then(|mut allowlist| allowlist.replace(&members))
+                .map_err(|err| PolicyEditError::Io(err.to_string()))?;
+        }
+        PolicyKind::Multisig => {
+            let update = approval.ok_or_else(|| {
+                PolicyEditError::Refused(format!(
+                    "multisig policies need a governance update approved by {} signers",
+                    before.threshold.unwrap_or(0)
+                ))
+            })?;
+            edit.check_update(&before, update)?;
+            let mut policy = MultisigPolicy::load(path)
+                .map_err(|err| PolicyEditError::Invalid(err.to_string()))?;
+            policy
+                .apply_update(update)
+                .map_err(|err| PolicyEditError::Refused(err.to_string()))?;
+            approvers = update
+                .signatures
+                .iter()
+                .map(|approval| approval.signer.clone())
+                .collect();
+            note = update
+                .metadata
+                .as_ref()
+                .and_then(|metadata| metadata.get("note"))
+                .and_then(Value::as_str)
+                .map(str::to_string);
+        }
+    }
+    let after = PolicySnapshot::load(path)?;
+    let change = PolicyChange {
+        schema: SCHEMA_POLICY_CHANGE.to_string(),
+        changed_ms,
+        action: edit.action,
+        key: edit.key.clone(),
+        policy: before.kind,
+        digest_before: before.digest_hex(),
+        digest_after: after.digest_hex(),
+        approvers,
+        note,
+    };
+    let history = policy_history_path(path);
+    let mut line = serde_json::to_string(&change).expect("policy change serializes");
+    line.push('\n');
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&history)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .map_err(|err| PolicyEditError::Io(format!("{}: {err}", history.display())))?;
+    Ok(change)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::governance::PolicyUpdateError;
+    use crate::net::sign::{load_or_derive_keypair, Ed25519KeySource, KeyMaterial};
+
+    fn key(seed: &str) -> KeyMaterial {
+        load_or_derive_keypair(&Ed25519KeySource::Seed(seed.to_string())).unwrap()
+    }
+
+    fn b64(seed: &str) -> String {
+        encode_public_key_base64(&key(seed).verifying)
+    }
+
+    #[test]
+    fn edits_are_validated_approved_and_recorded() {
+        let dir = std::env::temp_dir().join(format!("policy_edit_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let allowlist = dir.join("allowlist.json");
+        fs::write(&allowlist, json!({ "allowed": [b64("a")] }).to_string()).unwrap();
+        assert!(matches!(
+            KeyEdit::new(KeyEditAction::AddKey, "not-a-key"),
+            Err(PolicyEditError::Invalid(_))
+        ));
+        let add_b = KeyEdit::new(KeyEditAction::AddKey, &b64("b")).unwrap();
+        let change = apply_key_edit(&allowlist, &add_b, None, 7).unwrap();
+        let snapshot = PolicySnapshot::load(&allowlist).unwrap();
+        assert_eq!(snapshot.members, vec![b64("a"), b64("b")]);
+        assert_eq!(change.digest_after, snapshot.digest_hex());
+        assert!(matches!(
+            apply_key_edit(&allowlist, &add_b, None, 8),
+            Err(PolicyEditError::Refused(_))
+        ));
+        for seed in ["a", "b"] {
+            let remove = KeyEdit::new(KeyEditAction::RemoveKey, &b64(seed)).unwrap();
+            let result = apply_key_edit(&allowlist, &remove, None, 9);
+            assert_eq!(result.is_ok(), seed == "a");
+        }
+        assert_eq!(load_policy_history(&allowlist).unwrap().len(), 2);
+
+        let multisig = dir.join("multisig.json");
+        fs::write(
+            &multisig,
+            json!({ "threshold": 2, "signers": [b64("s1"), b64("s2")], "members": [b64("a")] })
+                .to_string(),
+        )
+        .unwrap();
+        let before = PolicySnapshot::load(&multisig).unwrap();
+        assert!(matches!(
+            apply_key_edit(&multisig, &add_b, None, 10),
+            Err(PolicyEditError::Refused(_))
+        ));
+        let mut update = add_b.update(&before, Some("boot2 joins")).unwrap();
+        update.approve(&key("s1")).unwrap();
+        update.approve(&key("s1")).unwrap();
+        assert_eq!(before.approvals(&update), 1);
+        assert!(matches!(
+            apply_key_edit(&multisig, &add_b, Some(&update), 11),
+            Err(PolicyEditError::Refused(_))
+        ));
+        let add_c = KeyEdit::new(KeyEditAction::AddKey, &b64("c")).unwrap();
+        assert!(matches!(
+            apply_key_edit(&multisig, &add_c, Some(&update), 12),
+            Err(PolicyEditError::Invalid(_))
+        ));
+        update.approve(&key("s2")).unwrap();
+        let change = apply_key_edit(&multisig, &add_b, Some(&update), 13).unwrap();
+        assert_eq!(change.approvers.len(), 2);
+        assert_eq!(change.note.as_deref(), Some("boot2 joins"));
+        let after = PolicySnapshot::load(&multisig).unwrap();
+        assert_eq!(after.members, vec![b64("a"), b64("b")]);
+        assert_ne!(after.digest(), before.digest());
+        assert!(matches!(
+            add_b.check_update(&after, &update),
+            Err(PolicyEditError::Refused(_))
+        ));
+        let mut wipe = GovernanceUpdate {
+            kind: UpdateKind::Membership,
+            new_members: Vec::new(),
+            metadata: None,
+            signatures: Vec::new(),
+        };
+        wipe.approve(&key("s1")).unwrap();
+        wipe.approve(&key("s2")).unwrap();
+        let mut policy = MultisigPolicy::load(&multisig).unwrap();
+        assert!(matches!(
+            policy.apply_update(&wipe),
+            Err(PolicyUpdateError::EmptyMembership)
+        ));
+        assert_eq!(
+            PolicySnapshot::load(&multisig).unwrap().members,
+            after.members
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,164 @@
+#![cfg(feature = "net")]
+
+//! Read-only `eth_call` surface for dapps on the native chain.
+//!
+//! The native chain has no EVM. Instead a handful of pseudo-contract
+//! addresses answer well-known view selectors with ABI-encoded results:
+//!
+//! - [`NATIVE_TOKEN_ADDRESS`] behaves like an ERC-20 over native balances
+//!   (`balanceOf`, `totalSupply`, `decimals`, 18 decimals like `eth_getBalance`);
+//! - [`asset_token_address`] gives every registry asset the same ERC-20 view
+//!   surface over stake-registry balances (0 decimals, raw units);
+//! - [`MIGRATION_CLAIMS_ADDRESS`] answers `isClaimed(string)` from the
+//!   migration apply state.
+//!
+//! This module only decodes calldata and encodes results; the RPC server
+//! resolves the values against finalized state.
+
+use sha3::{Digest, Keccak256};
+
+/// Pseudo-contract exposing native balances through ERC-20 view selectors.
+pub const NATIVE_TOKEN_ADDRESS: &str = "0x0000000000000000000000000000000000000800";
+/// Pseudo-contract answering migration claim status queries.
+pub const MIGRATION_CLAIMS_ADDRESS: &str = "0x0000000000000000000000000000000000000801";
+/// Runtime code returned by `eth_getCode` for pseudo-contracts (a lone `INVALID`).
+pub const PSEUDO_CONTRACT_CODE: &str = "0xfe";
+
+const ASSET_ADDRESS_DOMAIN: &[u8] = b"mfenx.powerhouse.asset-token.v1:";
+
+/// View call decoded from `eth_call` calldata.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PrecompileCall {
+    /// `balanceOf(address)`, with the holder as a lowercase `0x` address.
+    BalanceOf(String),
+    /// `totalSupply()`.
+    TotalSupply,
+    /// `decimals()`.
+    Decimals,
+    /// `isClaimed(string)`, with the migration claim id.
+    IsClaimed(String),
+}
+
+/// Address of the pseudo-contract serving registry asset `asset`.
+///
+/// The low 20 bytes of `keccak256(domain || asset)`, so every node derives
+/// the same address without a deployment registry.
+pub fn asset_token_address(asset: &str) -> String {
+    let mut preimage = ASSET_ADDRESS_DOMAIN.to_vec();
+    preimage.extend_from_slice(asset.as_bytes());
+    format!("0x{}", hex::encode(&keccak256(&preimage)[12..]))
+}
+
+/// Four-byte function selector of a canonical Solidity signature.
+pub fn selector(signature: &str) -> [u8; 4] {
+    let hash = keccak256(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Decodes calldata into one of the supported view calls.
+pub fn decode_call(data: &[u8]) -> Result<PrecompileCall, String> {
+    if data.len() < 4 {
+        return Err("calldata is shorter than a function selector".to_string());
+    }
+    let (head, args) = data.split_at(4);
+    if head == selector("balanceOf(address)") {
+        let word = abi_word(args, 0)?;
+        if word[..12].iter().any(|byte| *byte != 0) {
+            return Err("balanceOf address is not left-padded".to_string());
+        }
+        Ok(PrecompileCall::BalanceOf(format!(
+            "0x{}",
+            hex::encode(&word[12..])
+        )))
+    } else if head == selector("totalSupply()") {
+        Ok(PrecompileCall::TotalSupply)
+    } else if head == selector("decimals()") {
+        Ok(PrecompileCall::Decimals)
+    } else if head == selector("isClaimed(string)") {
+        Ok(PrecompileCall::IsClaimed(abi_string(args, 0)?))
+    } else {
+        Err(format!("unsupported selector 0x{}", hex::encode(head)))
+    }
+}
+
+/// ABI encoding of a `uint256` return value.
+pub fn encode_uint(value: u128) -> String {
+    let mut word = [0u8; 32];
+    word[16..].copy_from_slice(&value.to_be_bytes());
+    format!("0x{}", hex::encode(word))
+}
+
+/// ABI encoding of a `bool` return value.
+pub fn encode_bool(value: bool) -> String {
+    encode_uint(u128::from(value))
+}
+
+fn abi_word(args: &[u8], index: usize) -> Result<&[u8], String> {
+    let start = index * 32;
+    args.get(start..start + 32)
+        .ok_or_else(|| format!("calldata is missing argument {index}"))
+}
+
+fn abi_usize(word: &[u8]) -> Result<usize, String> {
+    if word[..24].iter().any(|byte| *byte != 0) {
+        return Err("ABI offset or length is out of range".to_string());
+    }
+    let mut raw = [0u8; 8];
+    raw.copy_from_slice(&word[24..]);
+    usize::try_from(u64::from_be_bytes(raw))
+        .map_err(|_| "ABI offset or length is out of range".to_string())
+}
+
+fn abi_string(args: &[u8], index: usize) -> Result<String, String> {
+    let offset = abi_usize(abi_word(args, index)?)?;
+    let tail = args
+        .get(offset..)
+        .ok_or_else(|| "string offset is past the calldata".to_string())?;
+    let len = abi_usize(abi_word(tail, 0)?)?;
+    let bytes = tail
+        .get(32..)
+        .and_then(|rest| rest.get(..len))
+        .ok_or_else(|| "string length is past the calldata".to_string())?;
+    String::from_utf8(bytes.to_vec()).map_err(|_| "string argument is not UTF-8".to_string())
+}
+
+fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_well_known_selectors() {
+        assert_eq!(hex::encode(selector("balanceOf(address)")), "70a08231");
+        assert_eq!(hex::encode(selector("totalSupply()")), "18160ddd");
+        assert_eq!(hex::encode(selector("decimals()")), "313ce567");
+
+        let holder = "00000000000000000000000011223344556677889900aabbccddeeff00112233";
+        let call = hex::decode(format!("70a08231{holder}")).unwrap();
+        assert_eq!(
+            decode_call(&call).unwrap(),
+            PrecompileCall::BalanceOf("0x11223344556677889900aabbccddeeff00112233".to_string())
+        );
+
+        let mut call = selector("isClaimed(string)").to_vec();
+        call.extend_from_slice(&hex::decode(encode_uint(32).trim_start_matches("0x")).unwrap());
+        call.extend_from_slice(&hex::decode(encode_uint(2).trim_start_matches("0x")).unwrap());
+        call.extend_from_slice(b"c1");
+        call.extend_from_slice(&[0u8; 30]);
+        assert_eq!(
+            decode_call(&call).unwrap(),
+            PrecompileCall::IsClaimed("c1".to_string())
+        );
+        assert!(decode_call(&call[..40]).is_err());
+        assert!(decode_call(&[0xde, 0xad, 0xbe, 0xef]).is_err());
+
+        assert!(encode_bool(true).ends_with("01"));
+        assert_eq!(asset_token_address("USDX").len(), 42);
+        assert_ne!(asset_token_address("USDX"), asset_token_address("GOLD"));
+    }
+}
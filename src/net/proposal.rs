@@ -0,0 +1,858 @@
+#![cfg(feature = "net")]
+
+//! Governance proposals: propose, vote, tally, execute.
+//!
+//! A [`Proposal`] is a signed request to change a network parameter, rotate
+//! the membership, admit a validator that filed a
+//! [`JoinRequest`](crate::net::JoinRequest), or replace the submission fee
+//! schedule.  The proposer's
+//! node records it on its ledger as a `power_house.governance` statement and
+//! gossips it; members answer with signed [`ProposalVote`]s.  A
+//! [`GovernanceBook`] keeps both under `log_dir/governance/` and tallies the
+//! votes against the node's [`MembershipPolicy`]: each current member weighs
+//! its bonded stake (or 1 when the policy tracks none), and a proposal passes
+//! once more than two thirds of that weight voted yes.  Passed proposals are
+//! handed to [`ExecutionHook`]s until one applies them.
+//!
+//! Policies that list no members (the permissionless allow-all policy) have no
+//! electorate, so their proposals can only expire.
+
+use crate::economics::SubmissionFeeSchedule;
+use crate::net::direct::append_jsonl;
+use crate::net::governance::{FeeScheduleConfig, MembershipPolicy, MultisigPolicy};
+use crate::net::onboarding::JoinRequest;
+use crate::net::schema::network_id;
+use crate::net::sign::{
+    decode_public_key_base64, encode_public_key_base64, encode_signature_base64, sign_payload,
+    verify_signature_base64, KeyMaterial,
+};
+use crate::{
+    Field, GeneralSumProof, JournalConfig, MultilinearPolynomial, Proof, ProofKind, ProofLedger,
+    Statement,
+};
+use blake2::digest::{consts::U32, Digest};
+use ed25519_dalek::VerifyingKey;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+type Blake2b256 = blake2::Blake2b<U32>;
+
+/// Schema identifier of a [`Proposal`].
+pub const SCHEMA_PROPOSAL: &str = "mfenx.powerhouse.governance_proposal.v1";
+
+/// Schema identifier of a [`ProposalVote`].
+pub const SCHEMA_PROPOSAL_VOTE: &str = "mfenx.powerhouse.governance_vote.v1";
+
+/// Application id of on-ledger proposal statements.
+pub const GOVERNANCE_APPLICATION_ID: &str = "power_house.governance";
+
+/// Gossip topic carrying [`GovernanceMessage`]s.
+pub const GOVERNANCE_TOPIC: &str = "mfenx/powerhouse/governance/v1";
+
+/// Directory under the log directory holding governance state.
+pub const GOVERNANCE_DIR: &str = "governance";
+
+const PROPOSALS_FILE: &str = "proposals.json";
+const OUTBOX_FILE: &str = "outbox.jsonl";
+const PROPOSAL_DOMAIN: &[u8] = b"MFENX_GOVERNANCE_PROPOSAL";
+/// Goldilocks prime; every table word is a `u32`, so it is always reduced.
+const PROPOSAL_MODULUS: u64 = 0xFFFF_FFFF_0000_0001;
+
+/// Errors produced by the proposal lifecycle.
+#[derive(Debug, thiserror::Error)]
+pub enum GovernanceError {
+    /// Local filesystem or encoding failure.
+    #[error("governance I/O error: {0}")]
+    Io(String),
+    /// A proposal or vote is malformed or wrongly signed.
+    #[error("invalid governance message: {0}")]
+    Invalid(String),
+    /// A vote names a proposal this node has not seen.
+    #[error("unknown proposal {0}")]
+    UnknownProposal(String),
+    /// A vote arrived after its proposal was decided.
+    #[error("proposal {0} is no longer open")]
+    Closed(String),
+}
+
+/// Change a proposal asks for.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProposalAction {
+    /// Sets a named network parameter.
+    ParameterChange {
+        /// Parameter name.
+        name: String,
+        /// New value.
+        value: String,
+    },
+    /// Replaces the membership set.
+    MembershipRotation {
+        /// Base64 ed25519 public keys of the new members.
+        new_members: Vec<String>,
+    },
+    /// Replaces the proof submission fee schedule.
+    FeeSchedule {
+        /// Schedule to install.
+        schedule: SubmissionFeeSchedule,
+    },
+    /// Adds the applicant of a signed join request to the membership.
+    AdmitMember {
+        /// The applicant's request.
+        request: JoinRequest,
+    },
+}
+
+impl ProposalAction {
+    /// Short label, e.g. `parameter_change`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::ParameterChange { .. } => "parameter_change",
+            Self::MembershipRotation { .. } => "membership_rotation",
+            Self::FeeSchedule { .. } => "fee_schedule",
+            Self::AdmitMember { .. } => "admit_member",
+        }
+    }
+}
+
+/// Signed governance proposal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Proposal {
+    /// Schema identifier (`mfenx.powerhouse.governance_proposal.v1`).
+    pub schema: String,
+    /// Network the proposal applies to.
+    pub network: String,
+    /// Requested change.
+    pub action: ProposalAction,
+    /// Free-form rationale.
+    #[serde(default)]
+    pub description: String,
+    /// Proposer public key (base64).
+    pub proposer: String,
+    /// Millisecond timestamp of creation.
+    pub created_ms: u64,
+    /// Millisecond timestamp after which the proposal expires unless decided.
+    pub voting_ends_ms: u64,
+    /// Base64 signature over every other field.
+    pub signature: String,
+}
+
+impl Proposal {
+    /// Builds and signs a proposal open for votes until `voting_ends_ms`.
+    pub fn new(
+        action: ProposalAction,
+        description: impl Into<String>,
+        created_ms: u64,
+        voting_ends_ms: u64,
+        key: &KeyMaterial,
+    ) -> Self {
+        let mut proposal = Self {
+            schema: SCHEMA_PROPOSAL.to_string(),
+            network: network_id().to_string(),
+            action,
+            description: description.into(),
+            proposer: encode_public_key_base64(&key.verifying),
+            created_ms,
+            voting_ends_ms,
+            signature: String::new(),
+        };
+        proposal.signature =
+            encode_signature_base64(&sign_payload(&key.signing, &proposal.signing_bytes()));
+        proposal
+    }
+
+    fn signing_bytes(&self) -> Vec<u8> {
+        #[derive(Serialize)]
+        struct Unsigned<'a> {
+            schema: &'a str,
+            network: &'a str,
+            action: &'a ProposalAction,
+            description: &'a str,
+            proposer: &'a str,
+            created_ms: u64,
+            voting_ends_ms: u64,
+        }
+        serde_json::to_vec(&Unsigned {
+            schema: &self.schema,
+            network: &self.network,
+            action: &self.action,
+            description: &self.description,
+            proposer: &self.proposer,
+            created_ms: self.created_ms,
+            voting_ends_ms: self.voting_ends_ms,
+        })
+        .expect("proposal fields serialize")
+    }
+
+    /// Domain-separated digest identifying the proposal.
+    pub fn id(&self) -> [u8; 32] {
+        let mut hasher = Blake2b256::new();
+        hasher.update(PROPOSAL_DOMAIN);
+        hasher.update(self.signing_bytes());
+        hasher.finalize().into()
+    }
+
+    /// Hex form of [`Proposal::id`].
+    pub fn id_hex(&self) -> String {
+        hex::encode(self.id())
+    }
+
+    /// Checks the schema, network, voting window, action, and signature.
+    pub fn verify(&self) -> Result<(), GovernanceError> {
+        if self.schema != SCHEMA_PROPOSAL {
+            return Err(GovernanceError::Invalid(format!(
+                "unexpected schema {}",
+                self.schema
+            )));
+        }
+        if self.network != network_id() {
+            return Err(GovernanceError::Invalid(format!(
+                "proposal targets network {}",
+                self.network
+            )));
+        }
+        if self.voting_ends_ms <= self.created_ms {
+            return Err(GovernanceError::Invalid(
+                "voting must end after creation".to_string(),
+            ));
+        }
+        match &self.action {
+            ProposalAction::ParameterChange { name, .. } if name.is_empty() => {
+                return Err(GovernanceError::Invalid(
+                    "parameter name is empty".to_string(),
+                ));
+            }
+            ProposalAction::MembershipRotation { new_members } => {
+                if new_members.is_empty() {
+                    return Err(GovernanceError::Invalid(
+                        "membership rotation names no members".to_string(),
+                    ));
+                }
+                for member in new_members {
+                    decode_public_key_base64(member).map_err(|err| {
+                        GovernanceError::Invalid(format!("member {member}: {err}"))
+                    })?;
+                }
+            }
+            ProposalAction::AdmitMember { request } => request
+                .verify()
+                .map_err(|err| GovernanceError::Invalid(err.to_string()))?,
+            _ => {}
+        }
+        verify_signature_base64(&self.proposer, &self.signing_bytes(), &self.signature)
+            .map_err(|err| GovernanceError::Invalid(format!("proposal signature: {err}")))
+    }
+
+    /// Ledger statement recording the proposal.
+    pub fn statement(&self) -> Statement {
+        Statement::structured(GOVERNANCE_APPLICATION_ID, "proposal")
+            .with_parameter("id", self.id_hex())
+            .with_parameter("action", self.action.kind())
+            .with_parameter("voting_ends_ms", self.voting_ends_ms.to_string())
+    }
+
+    /// Proof submitted alongside [`Proposal::statement`]; its transcript
+    /// commits to the proposal id.
+    pub fn ledger_proof(&self) -> Proof {
+        binding_proof(&self.id())
+    }
+}
+
+/// Proof whose sum-check transcript commits to `digest`.
+pub(crate) fn binding_proof(digest: &[u8; 32]) -> Proof {
+    let words = digest
+        .chunks_exact(4)
+        .map(|word| u64::from(u32::from_be_bytes(word.try_into().expect("4-byte chunk"))))
+        .collect();
+    let polynomial = MultilinearPolynomial::from_evaluations(3, words);
+    let proof = GeneralSumProof::prove(&polynomial, &Field::new(PROPOSAL_MODULUS));
+    Proof {
+        kind: ProofKind::General { polynomial, proof },
+        data: Vec::new(),
+    }
+}
+
+/// Choice expressed by a vote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Ballot {
+    /// In favour.
+    Yes,
+    /// Against.
+    No,
+}
+
+/// Member's signed vote on a proposal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposalVote {
+    /// Schema identifier (`mfenx.powerhouse.governance_vote.v1`).
+    pub schema: String,
+    /// Network the vote applies to.
+    pub network: String,
+    /// Hex id of the proposal.
+    pub proposal_id: String,
+    /// Voter public key (base64).
+    pub voter: String,
+    /// Choice.
+    pub ballot: Ballot,
+    /// Base64 signature over every other field.
+    pub signature: String,
+}
+
+impl ProposalVote {
+    /// Builds and signs a vote on the proposal `proposal_id`.
+    pub fn new(proposal_id: &str, ballot: Ballot, key: &KeyMaterial) -> Self {
+        let mut vote = Self {
+            schema: SCHEMA_PROPOSAL_VOTE.to_string(),
+            network: network_id().to_string(),
+            proposal_id: proposal_id.to_lowercase(),
+            voter: encode_public_key_base64(&key.verifying),
+            ballot,
+            signature: String::new(),
+        };
+        vote.signature =
+            encode_signature_base64(&sign_payload(&key.signing, &vote.signing_bytes()));
+        vote
+    }
+
+    fn signing_bytes(&self) -> Vec<u8> {
+        #[derive(Serialize)]
+        struct Unsigned<'a> {
+            schema: &'a str,
+            network: &'a str,
+            proposal_id: &'a str,
+            voter: &'a str,
+            ballot: Ballot,
+        }
+        serde_json::to_vec(&Unsigned {
+            schema: &self.schema,
+            network: &self.network,
+            proposal_id: &self.proposal_id,
+            voter: &self.voter,
+            ballot: self.ballot,
+        })
+        .expect("vote fields serialize")
+    }
+
+    /// Checks the schema, network, and signature.
+    pub fn verify(&self) -> Result<(), GovernanceError> {
+        if self.schema != SCHEMA_PROPOSAL_VOTE {
+            return Err(GovernanceError::Invalid(format!(
+                "unexpected schema {}",
+                self.schema
+            )));
+        }
+        if self.network != network_id() {
+            return Err(GovernanceError::Invalid(format!(
+                "vote targets network {}",
+                self.network
+            )));
+        }
+        verify_signature_base64(&self.voter, &self.signing_bytes(), &self.signature)
+            .map_err(|err| GovernanceError::Invalid(format!("vote signature: {err}")))
+    }
+}
+
+/// Proposal or vote exchanged over [`GOVERNANCE_TOPIC`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GovernanceMessage {
+    /// A new proposal.
+    Proposal(Proposal),
+    /// A vote on a known proposal.
+    Vote(ProposalVote),
+}
+
+impl GovernanceMessage {
+    /// Public key (base64) that signed the message.
+    pub fn signer(&self) -> &str {
+        match self {
+            Self::Proposal(proposal) => &proposal.proposer,
+            Self::Vote(vote) => &vote.voter,
+        }
+    }
+
+    /// Verifies the signed message.
+    pub fn verify(&self) -> Result<(), GovernanceError> {
+        match self {
+            Self::Proposal(proposal) => proposal.verify(),
+            Self::Vote(vote) => vote.verify(),
+        }
+    }
+}
+
+/// Where a proposal stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalStatus {
+    /// Collecting votes.
+    Open,
+    /// Passed; waiting for a hook to apply it.
+    Passed,
+    /// Enough weight voted no that it can no longer pass.
+    Rejected,
+    /// Voting ended undecided.
+    Expired,
+    /// Passed and applied.
+    Executed,
+}
+
+impl ProposalStatus {
+    /// Lowercase label, e.g. `passed`.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Passed => "passed",
+            Self::Rejected => "rejected",
+            Self::Expired => "expired",
+            Self::Executed => "executed",
+        }
+    }
+}
+
+/// Vote weight counted for a proposal.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tally {
+    /// Weight of current members voting yes.
+    pub yes: u64,
+    /// Weight of current members voting no.
+    pub no: u64,
+    /// Total weight of current members.
+    pub eligible: u64,
+}
+
+impl Tally {
+    /// Counts the votes of `policy`'s current members, each weighted by its
+    /// stake or 1.  Votes from non-members count for nothing.
+    pub fn count(policy: &dyn MembershipPolicy, votes: &[ProposalVote]) -> Self {
+        let members = policy.current_members();
+        let weight = |key: &VerifyingKey| policy.stake_for(key).unwrap_or(1);
+        let mut tally = Self {
+            eligible: members.iter().map(weight).sum(),
+            ..Self::default()
+        };
+        for vote in votes {
+            let Ok(key) = decode_public_key_base64(&vote.voter) else {
+                continue;
+            };
+            if !members.contains(&key) {
+                continue;
+            }
+            match vote.ballot {
+                Ballot::Yes => tally.yes += weight(&key),
+                Ballot::No => tally.no += weight(&key),
+            }
+        }
+        tally
+    }
+
+    /// Status the tally implies at `now_ms` for voting ending at
+    /// `voting_ends_ms`.
+    pub fn status(&self, now_ms: u64, voting_ends_ms: u64) -> ProposalStatus {
+        let (yes, no, eligible) = (self.yes as u128, self.no as u128, self.eligible as u128);
+        if eligible > 0 && yes * 3 > eligible * 2 {
+            ProposalStatus::Passed
+        } else if eligible > 0 && no * 3 >= eligible {
+            ProposalStatus::Rejected
+        } else if now_ms >= voting_ends_ms {
+            ProposalStatus::Expired
+        } else {
+            ProposalStatus::Open
+        }
+    }
+}
+
+/// Applies passed proposals.
+pub trait ExecutionHook {
+    /// Applies `proposal`.  Returns `Ok(false)` when the hook does not handle
+    /// its action.
+    fn execute(&mut self, proposal: &Proposal) -> Result<bool, String>;
+}
+
+impl ExecutionHook for FeeScheduleConfig {
+    fn execute(&mut self, proposal: &Proposal) -> Result<bool, String> {
+        let ProposalAction::FeeSchedule { schedule } = &proposal.action else {
+            return Ok(false);
+        };
+        self.set_schedule(schedule.clone())
+            .map(|()| true)
+            .map_err(|err| err.to_string())
+    }
+}
+
+impl ExecutionHook for MultisigPolicy {
+    fn execute(&mut self, proposal: &Proposal) -> Result<bool, String> {
+        let new_members = match &proposal.action {
+            ProposalAction::MembershipRotation { new_members } => new_members.clone(),
+            ProposalAction::AdmitMember { request } => {
+                let mut members: Vec<String> = self
+                    .current_members()
+                    .iter()
+                    .map(encode_public_key_base64)
+                    .collect();
+                if members.contains(&request.public_key) {
+                    return Ok(true);
+                }
+                members.push(request.public_key.clone());
+                members
+            }
+            _ => return Ok(false),
+        };
+        self.set_members(&new_members)
+            .map(|()| true)
+            .map_err(|err| err.to_string())
+    }
+}
+
+/// Proposal with the votes received for it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposalRecord {
+    /// The proposal.
+    pub proposal: Proposal,
+    /// One vote per voter, first received wins.
+    pub votes: Vec<ProposalVote>,
+    /// Status after the last evaluation.
+    pub status: ProposalStatus,
+    /// Tally at the last evaluation.
+    #[serde(default)]
+    pub tally: Tally,
+    /// Last hook failure, while the proposal waits to be executed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution_error: Option<String>,
+}
+
+/// Proposals and votes known to a node, persisted under
+/// `log_dir/governance/proposals.json`.
+#[derive(Debug, Clone)]
+pub struct GovernanceBook {
+    path: PathBuf,
+    records: BTreeMap<String, ProposalRecord>,
+}
+
+impl GovernanceBook {
+    /// Loads the book of `log_dir`, empty when none was written yet.
+    pub fn open(log_dir: &Path) -> Result<Self, GovernanceError> {
+        let path = log_dir.join(GOVERNANCE_DIR).join(PROPOSALS_FILE);
+        let records = match fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map_err(|err| GovernanceError::Invalid(format!("{}: {err}", path.display())))?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(GovernanceError::Io(err.to_string())),
+        };
+        Ok(Self { path, records })
+    }
+
+    /// Verifies and stores `proposal`.  Returns `false` when it was known.
+    pub fn insert_proposal(&mut self, proposal: Proposal) -> Result<bool, GovernanceError> {
+        proposal.verify()?;
+        let id = proposal.id_hex();
+        if self.records.contains_key(&id) {
+            return Ok(false);
+        }
+        self.records.insert(
+            id,
+            ProposalRecord {
+                proposal,
+                votes: Vec::new(),
+                status: ProposalStatus::Open,
+                tally: Tally::default(),
+                execution_error: None,
+            },
+        );
+        self.persist()?;
+        Ok(true)
+    }
+
+    /// Verifies and stores `vote`.  Returns `false` when the voter already
+    /// voted on the proposal.
+    pub fn insert_vote(&mut self, vote: ProposalVote) -> Result<bool, GovernanceError> {
+        vote.verify()?;
+        let record = self
+            .records
+            .get_mut(&vote.proposal_id)
+            .ok_or_else(|| GovernanceError::UnknownProposal(vote.proposal_id.clone()))?;
+        if record.status != ProposalStatus::Open {
+            return Err(GovernanceError::Closed(vote.proposal_id.clone()));
+        }
+        if record.votes.iter().any(|known| known.voter == vote.voter) {
+            return Ok(false);
+        }
+        record.votes.push(vote);
+        self.persist()?;
+        Ok(true)
+    }
+
+    /// Stores a gossiped proposal or vote.
+    pub fn insert(&mut self, message: GovernanceMessage) -> Result<bool, GovernanceError> {
+        match message {
+            GovernanceMessage::Proposal(proposal) => self.insert_proposal(proposal),
+            GovernanceMessage::Vote(vote) => self.insert_vote(vote),
+        }
+    }
+
+    /// Re-tallies open proposals against `policy` and runs `hooks` on passed
+    /// ones.  Returns the proposals whose status changed, with the new status.
+    pub fn evaluate(
+        &mut self,
+        policy: &dyn MembershipPolicy,
+        now_ms: u64,
+        hooks: &mut [&mut dyn ExecutionHook],
+    ) -> Result<Vec<(String, ProposalStatus)>, GovernanceError> {
+        let mut changed = Vec::new();
+        let mut dirty = false;
+        for (id, record) in &mut self.records {
+            let previous = record.status;
+            if record.status == ProposalStatus::Open {
+                let tally = Tally::count(policy, &record.votes);
+                dirty |= tally != record.tally;
+                record.tally = tally;
+                record.status = tally.status(now_ms, record.proposal.voting_ends_ms);
+            }
+            if record.status == ProposalStatus::Passed {
+                let mut error = None;
+                for hook in hooks.iter_mut() {
+                    match hook.execute(&record.proposal) {
+                        Ok(true) => {
+                            record.status = ProposalStatus::Executed;
+                            error = None;
+                            break;
+                        }
+                        Ok(false) => {}
+                        Err(err) => error = Some(err),
+                    }
+                }
+                dirty |= error != record.execution_error;
+                record.execution_error = error;
+            }
+            if record.status != previous {
+                changed.push((id.clone(), record.status));
+            }
+        }
+        if dirty || !changed.is_empty() {
+            self.persist()?;
+        }
+        Ok(changed)
+    }
+
+    /// Record of the proposal with hex id `id`.
+    pub fn get(&self, id: &str) -> Option<&ProposalRecord> {
+        self.records
+            .get(&id.strip_prefix("0x").unwrap_or(id).to_lowercase())
+    }
+
+    /// Every record in id order.
+    pub fn iter(&self) -> impl Iterator<Item = &ProposalRecord> {
+        self.records.values()
+    }
+
+    fn persist(&self) -> Result<(), GovernanceError> {
+        write_json(&self.path, &self.records).map_err(|err| GovernanceError::Io(err.to_string()))
+    }
+}
+
+pub(crate) fn write_json<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let bytes = serde_json::to_vec_pretty(value).map_err(io::Error::other)?;
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, bytes)?;
+    fs::rename(&tmp, path)
+}
+
+/// Appends `proposal`'s statement to the ledger journal in `log_dir` and
+/// returns its entry index.
+pub fn record_proposal(log_dir: &Path, proposal: &Proposal) -> Result<usize, GovernanceError> {
+    record_statement(log_dir, proposal.statement(), proposal.ledger_proof())
+}
+
+/// Appends a governance statement to the ledger journal in `log_dir`.
+pub(crate) fn record_statement(
+    log_dir: &Path,
+    statement: Statement,
+    proof: Proof,
+) -> Result<usize, GovernanceError> {
+    fs::create_dir_all(log_dir).map_err(|err| GovernanceError::Io(err.to_string()))?;
+    let mut ledger = ProofLedger::replay_from_logs(log_dir).map_err(GovernanceError::Io)?;
+    ledger
+        .enable_journal(log_dir, JournalConfig::default())
+        .map_err(|err| GovernanceError::Io(err.to_string()))?;
+    let claim = statement.claim_type.clone();
+    ledger.submit(statement, proof);
+    let entry = ledger.entries().last().expect("entry just recorded");
+    if !entry.accepted {
+        return Err(GovernanceError::Invalid(format!(
+            "ledger rejected the {claim} statement"
+        )));
+    }
+    if let Some(err) = &entry.log_error {
+        return Err(GovernanceError::Io(format!(
+            "{claim} statement was not logged: {err}"
+        )));
+    }
+    Ok(ledger.entries().len() - 1)
+}
+
+/// Queues `message` for the node running on `log_dir` to gossip.
+pub fn enqueue_governance(log_dir: &Path, message: &GovernanceMessage) -> io::Result<()> {
+    append_jsonl(&log_dir.join(GOVERNANCE_DIR).join(OUTBOX_FILE), message)
+}
+
+/// Removes and returns every queued governance message.
+pub(crate) fn drain_governance_outbox(log_dir: &Path) -> io::Result<Vec<GovernanceMessage>> {
+    let path = log_dir.join(GOVERNANCE_DIR).join(OUTBOX_FILE);
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    if contents.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    fs::write(&path, "")?;
+    Ok(contents
+        .lines()
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(message) => Some(message),
+            Err(err) => {
+                eprintln!("skipping malformed governance outbox line: {err}");
+                None
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::governance::StaticPolicy;
+    use crate::net::parameters::ParameterStore;
+    use crate::net::sign::{load_or_derive_keypair, Ed25519KeySource};
+
+    fn key(seed: &str) -> KeyMaterial {
+        load_or_derive_keypair(&Ed25519KeySource::Seed(seed.to_string())).unwrap()
+    }
+
+    #[test]
+    fn supermajority_passes_and_hooks_execute() {
+        let dir = std::env::temp_dir().join(format!("governance_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let members: Vec<KeyMaterial> = ["m1", "m2", "m3"].into_iter().map(key).collect();
+        let policy = StaticPolicy::from_base64_strings(
+            &members
+                .iter()
+                .map(|member| encode_public_key_base64(&member.verifying))
+                .collect::<Vec<_>>(),
+        )
+        .unwrap();
+
+        let mut book = GovernanceBook::open(&dir).unwrap();
+        let change = Proposal::new(
+            ProposalAction::ParameterChange {
+                name: "broadcast_interval_ms".to_string(),
+                value: "2000".to_string(),
+            },
+            "slow down anchors",
+            10,
+            1_000,
+            &members[0],
+        );
+        let id = change.id_hex();
+        assert!(book.insert_proposal(change.clone()).unwrap());
+        assert!(!book.insert_proposal(change.clone()).unwrap());
+        let mut forged = change.clone();
+        forged.description = "tampered".to_string();
+        assert!(book.insert_proposal(forged).is_err());
+
+        // Outsiders and repeat voters do not move the tally; two of three
+        // members is not more than two thirds.
+        book.insert_vote(ProposalVote::new(&id, Ballot::Yes, &key("outsider")))
+            .unwrap();
+        for member in &members[..2] {
+            assert!(book
+                .insert_vote(ProposalVote::new(&id, Ballot::Yes, member))
+                .unwrap());
+        }
+        assert!(!book
+            .insert_vote(ProposalVote::new(&id, Ballot::No, &members[0]))
+            .unwrap());
+        let mut parameters = ParameterStore::open(&dir).unwrap();
+        assert!(book
+            .evaluate(&policy, 20, &mut [&mut parameters])
+            .unwrap()
+            .is_empty());
+        assert_eq!(book.get(&id).unwrap().tally.yes, 2);
+
+        book.insert_vote(ProposalVote::new(&id, Ballot::Yes, &members[2]))
+            .unwrap();
+        assert_eq!(
+            book.evaluate(&policy, 30, &mut [&mut parameters]).unwrap(),
+            vec![(id.clone(), ProposalStatus::Executed)]
+        );
+        assert_eq!(
+            ParameterStore::open(&dir)
+                .unwrap()
+                .get("broadcast_interval_ms"),
+            Some("2000")
+        );
+        assert!(matches!(
+            book.insert_vote(ProposalVote::new(&id, Ballot::No, &members[1])),
+            Err(GovernanceError::Closed(_))
+        ));
+
+        // One no vote in three blocks the supermajority; silence expires.
+        let rotation = Proposal::new(
+            ProposalAction::MembershipRotation {
+                new_members: vec![encode_public_key_base64(&members[0].verifying)],
+            },
+            "",
+            10,
+            1_000,
+            &members[1],
+        );
+        let quiet = Proposal::new(
+            ProposalAction::FeeSchedule {
+                schedule: SubmissionFeeSchedule::default(),
+            },
+            "",
+            10,
+            40,
+            &members[2],
+        );
+        book.insert(GovernanceMessage::Proposal(rotation.clone()))
+            .unwrap();
+        book.insert(GovernanceMessage::Proposal(quiet.clone()))
+            .unwrap();
+        book.insert(GovernanceMessage::Vote(ProposalVote::new(
+            &rotation.id_hex(),
+            Ballot::No,
+            &members[2],
+        )))
+        .unwrap();
+        let mut changed = book.evaluate(&policy, 50, &mut []).unwrap();
+        changed.sort_by(|left, right| left.0.cmp(&right.0));
+        let mut expected = vec![
+            (rotation.id_hex(), ProposalStatus::Rejected),
+            (quiet.id_hex(), ProposalStatus::Expired),
+        ];
+        expected.sort_by(|left, right| left.0.cmp(&right.0));
+        assert_eq!(changed, expected);
+
+        let reopened = GovernanceBook::open(&dir).unwrap();
+        assert_eq!(reopened.iter().count(), 3);
+        assert_eq!(
+            reopened.get(&format!("0x{id}")).unwrap().status,
+            ProposalStatus::Executed
+        );
+
+        record_proposal(&dir, &change).unwrap();
+        let ledger = ProofLedger::replay_from_logs(&dir).unwrap();
+        let entry = ledger.entries().last().unwrap();
+        assert_eq!(entry.statement, change.statement());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,139 @@
+#![cfg(feature = "net")]
+
+//! Per-identity anchor rate limiting.
+//!
+//! A single signing key could otherwise flood the anchors topic.  The swarm
+//! counts validated anchor envelopes per public key in fixed one-minute
+//! windows.  Envelopes beyond the configured budget are dropped without
+//! penalty; once a key has overrun its budget in
+//! [`SUSTAINED_VIOLATION_WINDOWS`] consecutive windows, further excess is
+//! treated as misbehaviour and counted against the forwarding peer.
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// Default anchors accepted per identity per minute.
+pub const DEFAULT_MAX_ANCHORS_PER_MINUTE: u32 = 60;
+/// Consecutive over-budget windows after which excess is penalised.
+pub const SUSTAINED_VIOLATION_WINDOWS: u32 = 3;
+
+const WINDOW: Duration = Duration::from_secs(60);
+/// Identities idle for this long are forgotten.
+const IDLE_EXPIRY: Duration = Duration::from_secs(10 * 60);
+
+/// Outcome of counting one anchor against its identity's budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RateVerdict {
+    /// Within budget.
+    Allowed,
+    /// Over budget; drop without penalty.
+    Excess,
+    /// Over budget for several windows in a row; drop and penalise.
+    Sustained,
+}
+
+struct IdentityWindow {
+    started: Instant,
+    count: u32,
+    /// Consecutive completed windows that went over budget.
+    violating_windows: u32,
+}
+
+/// Fixed-window anchor counters keyed by identity.
+pub(crate) struct AnchorRateLimiter {
+    max_per_minute: u32,
+    windows: HashMap<String, IdentityWindow>,
+}
+
+impl AnchorRateLimiter {
+    /// `max_per_minute == 0` disables the limit.
+    pub(crate) fn new(max_per_minute: u32) -> Self {
+        Self {
+            max_per_minute,
+            windows: HashMap::new(),
+        }
+    }
+
+    /// Counts an anchor from `identity` received at `now`.
+    pub(crate) fn check(&mut self, identity: &str, now: Instant) -> RateVerdict {
+        if self.max_per_minute == 0 {
+            return RateVerdict::Allowed;
+        }
+        if self.windows.len() > 1024 {
+            self.windows
+                .retain(|_, window| now.saturating_duration_since(window.started) < IDLE_EXPIRY);
+        }
+        let max = self.max_per_minute;
+        let window = self
+            .windows
+            .entry(identity.to_string())
+            .or_insert(IdentityWindow {
+                started: now,
+                count: 0,
+                violating_windows: 0,
+            });
+        let elapsed = now.saturating_duration_since(window.started);
+        if elapsed >= WINDOW {
+            let consecutive = elapsed < WINDOW * 2 && window.count > max;
+            window.violating_windows = if consecutive {
+                window.violating_windows + 1
+            } else {
+                0
+            };
+            window.started = now;
+            window.count = 0;
+        }
+        window.count = window.count.saturating_add(1);
+        if window.count <= max {
+            RateVerdict::Allowed
+        } else if window.violating_windows + 1 >= SUSTAINED_VIOLATION_WINDOWS {
+            RateVerdict::Sustained
+        } else {
+            RateVerdict::Excess
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn excess_is_dropped_and_sustained_excess_escalates() {
+        let start = Instant::now();
+        let mut limiter = AnchorRateLimiter::new(2);
+        let burst = |limiter: &mut AnchorRateLimiter, at: Instant| {
+            (0..3)
+                .map(|_| limiter.check("flooder", at))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            burst(&mut limiter, start),
+            [
+                RateVerdict::Allowed,
+                RateVerdict::Allowed,
+                RateVerdict::Excess
+            ]
+        );
+        assert_eq!(limiter.check("quiet", start), RateVerdict::Allowed);
+        assert_eq!(burst(&mut limiter, start + WINDOW)[2], RateVerdict::Excess);
+        assert_eq!(
+            burst(&mut limiter, start + WINDOW * 2)[2],
+            RateVerdict::Sustained
+        );
+        // A window within budget resets the streak.
+        assert_eq!(
+            limiter.check("flooder", start + WINDOW * 3),
+            RateVerdict::Allowed
+        );
+        assert_eq!(
+            burst(&mut limiter, start + WINDOW * 4)[2],
+            RateVerdict::Excess
+        );
+
+        let mut unlimited = AnchorRateLimiter::new(0);
+        assert!((0..100).all(|_| unlimited.check("any", start) == RateVerdict::Allowed));
+    }
+}
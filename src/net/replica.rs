@@ -0,0 +1,392 @@
+#![cfg(feature = "net")]
+
+//! Read-only wallet RPC replicas.
+//!
+//! A replica serves every read method of the wallet JSON-RPC from a copy of a
+//! primary node's native chain state, so balance queries scale out without
+//! exposing the write path.  [`follow_primary_state`] tails the primary's
+//! `native_chain_state` store (a shared volume or a synced copy), reloading it
+//! whenever its fingerprint changes.  Write methods are refused with a hint
+//! naming the primary, and [`ReplicaStatus`] exports how far behind the
+//! replica is.
+
+use crate::net::native_chain::{NativeChainState, SharedNativeChainState};
+use crate::net::node_clock::{SharedNodeClock, Ticker};
+use crate::net::storage::StoreFingerprint;
+use std::{
+    fmt::Write as _,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// Default interval between checks of the primary's state.
+pub const DEFAULT_REPLICA_POLL: Duration = Duration::from_secs(1);
+
+/// Replica settings of an [`EvmRpcConfig`](crate::net::EvmRpcConfig).
+#[derive(Debug, Clone)]
+pub struct ReplicaMode {
+    /// Primary RPC endpoint named to wallets whose writes are refused.
+    pub primary_rpc: Option<String>,
+    /// Sync progress behind `/metrics` and `/healthz`.
+    pub status: ReplicaStatus,
+}
+
+impl ReplicaMode {
+    /// Error message returned for write methods.
+    pub fn redirect_hint(&self) -> String {
+        match &self.primary_rpc {
+            Some(primary) => format!("read-only replica; send transactions to {primary}"),
+            None => "read-only replica; send transactions to the primary node".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct ReplicaCounters {
+    last_sync_ms: AtomicU64,
+    tip_number: AtomicU64,
+    tip_timestamp_secs: AtomicU64,
+    reloads: AtomicU64,
+    reload_errors: AtomicU64,
+}
+
+/// Shared sync progress of a replica.
+#[derive(Debug, Clone)]
+pub struct ReplicaStatus {
+    clock: SharedNodeClock,
+    counters: Arc<ReplicaCounters>,
+}
+
+impl ReplicaStatus {
+    /// A replica that has not synced yet, timed by `clock`.
+    pub fn new(clock: SharedNodeClock) -> Self {
+        Self {
+            clock,
+            counters: Arc::default(),
+        }
+    }
+
+    fn record_tip(&self, state: &NativeChainState) {
+        self.counters
+            .tip_number
+            .store(state.latest_number(), Ordering::Relaxed);
+        let timestamp = state
+            .blocks
+            .last()
+            .map_or(0, |block| block.proposal.timestamp);
+        self.counters
+            .tip_timestamp_secs
+            .store(timestamp, Ordering::Relaxed);
+    }
+
+    fn record_sync(&self) {
+        self.counters
+            .last_sync_ms
+            .store(self.clock.now_ms(), Ordering::Relaxed);
+    }
+
+    /// Finalized height currently served.
+    pub fn tip_number(&self) -> u64 {
+        self.counters.tip_number.load(Ordering::Relaxed)
+    }
+
+    /// Seconds since the primary's state was last read successfully, or
+    /// `None` before the first sync.
+    pub fn sync_age_secs(&self) -> Option<u64> {
+        match self.counters.last_sync_ms.load(Ordering::Relaxed) {
+            0 => None,
+            last => Some(self.clock.now_ms().saturating_sub(last) / 1000),
+        }
+    }
+
+    /// Seconds since the served tip block was produced.
+    pub fn tip_age_secs(&self) -> u64 {
+        let timestamp = self.counters.tip_timestamp_secs.load(Ordering::Relaxed);
+        (self.clock.now_ms() / 1000).saturating_sub(timestamp)
+    }
+
+    /// Prometheus exposition of the replica's staleness.
+    pub fn render_metrics(&self) -> String {
+        let mut out = String::new();
+        let mut gauge = |name: &str, kind: &str, help: &str, value: u64| {
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} {kind}");
+            let _ = writeln!(out, "{name} {value}");
+        };
+        gauge(
+            "powerhouse_replica_finalized_block",
+            "gauge",
+            "Finalized native block height served by this replica.",
+            self.tip_number(),
+        );
+        gauge(
+            "powerhouse_replica_sync_age_seconds",
+            "gauge",
+            "Seconds since the primary's state was last read successfully.",
+            self.sync_age_secs().unwrap_or(u64::MAX),
+        );
+        gauge(
+            "powerhouse_replica_tip_age_seconds",
+            "gauge",
+            "Seconds since the served tip block was produced.",
+            self.tip_age_secs(),
+        );
+        gauge(
+            "powerhouse_replica_reloads_total",
+            "counter",
+            "State reloads after the primary's store changed.",
+            self.counters.reloads.load(Ordering::Relaxed),
+        );
+        gauge(
+            "powerhouse_replica_reload_errors_total",
+            "counter",
+            "Failed reads of the primary's state.",
+            self.counters.reload_errors.load(Ordering::Relaxed),
+        );
+        out
+    }
+}
+
+/// Keeps `state` in step with the store at `path` until the task is dropped.
+///
+/// The store is reloaded when its size or modification time changes.  A
+/// store that fails validation, belongs to another chain, or would move the
+/// tip backwards is logged and skipped until it changes again; meanwhile the
+/// replica keeps serving its last good state and its sync age grows.
+pub async fn follow_primary_state(
+    path: PathBuf,
+    state: SharedNativeChainState,
+    status: ReplicaStatus,
+    poll: Duration,
+) {
+    let mut ticker = Ticker::new(status.clock.clone(), poll);
+    let mut seen: Option<StoreFingerprint> = None;
+    let mut healthy = false;
+    status.record_tip(&*state.read().await);
+    loop {
+        ticker.tick().await;
+        let fingerprint = StoreFingerprint::of(&path);
+        if seen.as_ref() == Some(&fingerprint) {
+            if healthy {
+                status.record_sync();
+            }
+            continue;
+        }
+        seen = Some(fingerprint);
+        let load_path = path.clone();
+        let now_secs = status.clock.now().as_secs();
+        let loaded =
+            tokio::task::spawn_blocking(move || NativeChainState::load(&load_path, now_secs))
+                .await
+                .unwrap_or_else(|err| Err(err.to_string()));
+        let mut current = state.write().await;
+        let result = loaded.and_then(|loaded| {
+            if loaded.chain_id != current.chain_id {
+                Err(format!(
+                    "chain id {} does not match served chain {}",
+                    loaded.chain_id, current.chain_id
+                ))
+            } else if loaded.latest_number() < current.latest_number() {
+                Err(format!(
+                    "primary tip {} is behind served tip {}",
+                    loaded.latest_number(),
+                    current.latest_number()
+                ))
+            } else {
+                Ok(loaded)
+            }
+        });
+        match result {
+            Ok(loaded) => {
+                if loaded.latest_hash() != current.latest_hash() {
+                    println!(
+                        "QSYS|mod=REPLICA|evt=RELOAD|height={}|hash={}",
+                        loaded.latest_number(),
+                        loaded.latest_hash()
+                    );
+                    status.counters.reloads.fetch_add(1, Ordering::Relaxed);
+                }
+                *current = loaded;
+                status.record_tip(&current);
+                status.record_sync();
+                healthy = true;
+            }
+            Err(err) => {
+                healthy = false;
+                status
+                    .counters
+                    .reload_errors
+                    .fetch_add(1, Ordering::Relaxed);
+                eprintln!(
+                    "QSYS|mod=REPLICA|evt=RELOAD_FAILED|path={}|error={err}",
+                    path.display()
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::{
+        encode_public_key_base64, load_or_derive_keypair,
+        native_chain::{
+            signed_test_transfer, NativeChainMessage, NativeChainMessagePayload, NativeChainRuntime,
+        },
+        run_evm_rpc_server, Ed25519KeySource, EvmRpcConfig, NodeClock, SimulatedClock,
+        StakeRegistry, SupplyCause,
+    };
+    use serde_json::{json, Value};
+    use std::{fs, net::TcpListener as StdTcpListener};
+    use tokio::sync::{mpsc, RwLock};
+
+    #[tokio::test]
+    async fn replica_follows_primary_and_refuses_writes() {
+        let root = std::env::temp_dir().join(format!("powerhouse_replica_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        let chain_id = 177155;
+        let transaction = signed_test_transfer([7u8; 32], chain_id, 0, [9u8; 20], 2);
+        let registry_path = root.join("stake_registry.json");
+        let mut registry = StakeRegistry::default();
+        registry
+            .mint(&transaction.from, 5, SupplyCause::Funding)
+            .unwrap();
+        registry.save(&registry_path).unwrap();
+        let validator =
+            load_or_derive_keypair(&Ed25519KeySource::Seed("replica".to_string())).unwrap();
+        let validators = vec![encode_public_key_base64(&validator.verifying)];
+        let state_path = root.join("native_chain_state.json");
+        let primary = NativeChainState::load_or_initialize(
+            &state_path,
+            chain_id,
+            Some(&registry_path),
+            validators.clone(),
+            1,
+            0,
+        )
+        .unwrap();
+
+        let clock = SimulatedClock::new(Duration::from_secs(1_700_000_000));
+        let replica = Arc::new(RwLock::new(
+            NativeChainState::load(&state_path, clock.now().as_secs()).unwrap(),
+        ));
+        let status = ReplicaStatus::new(clock.shared());
+        tokio::spawn(follow_primary_state(
+            state_path.clone(),
+            replica.clone(),
+            status.clone(),
+            Duration::from_secs(1),
+        ));
+
+        let mut runtime = NativeChainRuntime::new(
+            Arc::new(RwLock::new(primary)),
+            state_path.clone(),
+            validators,
+            1,
+            &validator.signing,
+            clock.shared(),
+        )
+        .await
+        .unwrap();
+        runtime
+            .accept_transaction(transaction.clone())
+            .await
+            .unwrap();
+        let proposal = runtime.propose(&validator.signing).await.unwrap().unwrap();
+        for vote in runtime
+            .handle_message(
+                NativeChainMessage::new(NativeChainMessagePayload::Proposal(proposal)),
+                &validator.signing,
+            )
+            .await
+            .unwrap()
+        {
+            runtime
+                .handle_message(vote, &validator.signing)
+                .await
+                .unwrap();
+        }
+
+        for _ in 0..200 {
+            if replica.read().await.latest_number() == 1 {
+                break;
+            }
+            clock.advance(Duration::from_secs(1));
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(replica.read().await.latest_number(), 1);
+        assert_eq!(status.sync_age_secs(), Some(0));
+
+        fs::write(&state_path, b"{\"truncated\":").unwrap();
+        for _ in 0..200 {
+            if status.render_metrics().contains("reload_errors_total 1") {
+                break;
+            }
+            clock.advance(Duration::from_secs(1));
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let metrics = status.render_metrics();
+        assert!(metrics.contains("powerhouse_replica_reload_errors_total 1"));
+        assert!(metrics.contains("powerhouse_replica_finalized_block 1"));
+        clock.advance(Duration::from_secs(30));
+        assert!(status.sync_age_secs().unwrap() >= 30);
+
+        let reserved = StdTcpListener::bind("127.0.0.1:0").unwrap();
+        let listen = reserved.local_addr().unwrap();
+        drop(reserved);
+        let (sender, _commands) = mpsc::channel(1);
+        let mut rpc = EvmRpcConfig::new(listen, chain_id, replica, sender);
+        rpc.replica = Some(ReplicaMode {
+            primary_rpc: Some("https://rpc.example".to_string()),
+            status,
+        });
+        tokio::spawn(run_evm_rpc_server(rpc));
+        let client = reqwest::Client::new();
+        let url = format!("http://{listen}");
+        for _ in 0..50 {
+            if client.get(format!("{url}/healthz")).send().await.is_ok() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        let call = |method: &str, params: Value| {
+            client
+                .post(&url)
+                .json(&json!({"jsonrpc":"2.0","id":1,"method":method,"params":params}))
+                .send()
+        };
+        let balance: Value = call("eth_getBalance", json!([transaction.from, "latest"]))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert!(balance["result"].is_string());
+        let refused: Value = call("eth_sendRawTransaction", json!([transaction.raw]))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert!(refused["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("https://rpc.example"));
+        let metrics = client
+            .get(format!("{url}/metrics"))
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert!(metrics.contains("powerhouse_replica_sync_age_seconds"));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,369 @@
+#![cfg(feature = "net")]
+
+//! Per-epoch reward distribution to validators that signed a checkpoint.
+//!
+//! At each checkpoint epoch a fixed reward pool is split across the distinct
+//! identities whose signatures over the checkpoint anchor verify.  Shares are
+//! minted into the [`StakeRegistry`] as [`SupplyCause::EpochReward`], and the node signs a
+//! [`RewardDistribution`] artifact so other operators can audit the payout.
+//! Artifacts are written once per epoch; an existing artifact means the epoch
+//! has already been paid and is never credited twice.
+
+use crate::economics::NATIVE_ASSET;
+use crate::net::checkpoint::AnchorCheckpoint;
+use crate::net::sign::{
+    encode_public_key_base64, encode_signature_base64, sign_payload, verify_signature_base64,
+    KeyMaterial,
+};
+use crate::net::stake_registry::{validate_asset_id, StakeRegistry, SupplyCause};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Schema identifier of a signed reward distribution artifact.
+pub const SCHEMA_REWARD_DISTRIBUTION: &str = "mfenx.powerhouse.rewards.v1";
+
+/// Errors produced while computing, paying or verifying epoch rewards.
+#[derive(Debug, thiserror::Error)]
+pub enum RewardError {
+    /// Local filesystem or encoding failure.
+    #[error("reward I/O error: {0}")]
+    Io(String),
+    /// The checkpoint or artifact is malformed.
+    #[error("invalid reward input: {0}")]
+    Invalid(String),
+    /// A distribution signature does not verify.
+    #[error("reward signature check failed: {0}")]
+    Signature(String),
+    /// Rewards for this epoch were already distributed.
+    #[error("rewards for epoch {0} already distributed")]
+    AlreadyDistributed(u64),
+}
+
+/// How the epoch pool is split across eligible signers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RewardWeighting {
+    /// Every eligible signer receives the same share.
+    #[default]
+    Equal,
+    /// Shares are proportional to bonded stake in the registry.
+    Stake,
+}
+
+/// Reward pool configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RewardConfig {
+    /// Amount distributed at every checkpoint epoch.
+    pub pool_per_epoch: u64,
+    /// Split rule applied to the pool.
+    #[serde(default)]
+    pub weighting: RewardWeighting,
+    /// Asset the pool is paid in; `None` means [`NATIVE_ASSET`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset: Option<String>,
+}
+
+/// Amount credited to one signer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RewardShare {
+    /// Node identifier recorded in the checkpoint signature.
+    pub node_id: String,
+    /// Base64 ed25519 public key credited in the stake registry.
+    pub public_key: String,
+    /// Credited amount.
+    pub amount: u64,
+}
+
+/// Signed record of one epoch's payout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RewardDistribution {
+    /// Schema identifier (`mfenx.powerhouse.rewards.v1`).
+    pub schema: String,
+    /// Checkpoint epoch being rewarded.
+    pub epoch: u64,
+    /// Hex anchor digest of the rewarded checkpoint.
+    pub checkpoint_digest: String,
+    /// Configured pool for the epoch.
+    pub pool: u64,
+    /// Split rule used.
+    pub weighting: RewardWeighting,
+    /// Asset paid; `None` means [`NATIVE_ASSET`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset: Option<String>,
+    /// Shares in public-key order.
+    pub shares: Vec<RewardShare>,
+    /// Base64 public key of the distributing node.
+    pub distributor: String,
+    /// Base64 signature over [`RewardDistribution::signing_bytes`].
+    pub signature: String,
+}
+
+impl RewardDistribution {
+    /// Canonical bytes covered by the distributor signature.
+    pub fn signing_bytes(&self) -> Result<Vec<u8>, RewardError> {
+        let mut unsigned = self.clone();
+        unsigned.signature = String::new();
+        serde_json::to_vec(&unsigned).map_err(|err| RewardError::Invalid(err.to_string()))
+    }
+
+    /// Checks the schema, the share total and the distributor signature.
+    pub fn verify(&self) -> Result<(), RewardError> {
+        if self.schema != SCHEMA_REWARD_DISTRIBUTION {
+            return Err(RewardError::Invalid(format!(
+                "unexpected schema {}",
+                self.schema
+            )));
+        }
+        let total = self
+            .shares
+            .iter()
+            .try_fold(0u64, |acc, share| acc.checked_add(share.amount))
+            .ok_or_else(|| RewardError::Invalid("share total overflows".to_string()))?;
+        if total > self.pool {
+            return Err(RewardError::Invalid(format!(
+                "shares total {total} exceeds pool {}",
+                self.pool
+            )));
+        }
+        verify_signature_base64(&self.distributor, &self.signing_bytes()?, &self.signature)
+            .map_err(|err| RewardError::Signature(err.to_string()))
+    }
+
+    /// Reads a distribution artifact from JSON.
+    pub fn read(path: &Path) -> Result<Self, RewardError> {
+        let bytes = fs::read(path).map_err(|err| RewardError::Io(err.to_string()))?;
+        serde_json::from_slice(&bytes).map_err(|err| RewardError::Invalid(err.to_string()))
+    }
+}
+
+/// Path of the artifact for `epoch` under a node log directory.
+pub fn reward_distribution_path(log_dir: &Path, epoch: u64) -> PathBuf {
+    log_dir
+        .join("rewards")
+        .join(format!("rewards_{epoch}.json"))
+}
+
+/// Splits the epoch pool across the checkpoint's verified signers.
+///
+/// Signatures that do not verify over the checkpoint anchor are ignored and a
+/// key that signed more than once is counted once.  Integer remainders go to
+/// the signers with the largest fractional share, ties broken by public key,
+/// so every node derives the same split.
+pub fn compute_epoch_rewards(
+    checkpoint: &AnchorCheckpoint,
+    config: &RewardConfig,
+    registry: &StakeRegistry,
+) -> Result<Vec<RewardShare>, RewardError> {
+    let payload = serde_json::to_vec(&checkpoint.anchor)
+        .map_err(|err| RewardError::Invalid(err.to_string()))?;
+    let mut signers: BTreeMap<String, String> = BTreeMap::new();
+    for signature in &checkpoint.signatures {
+        if verify_signature_base64(&signature.public_key, &payload, &signature.signature).is_ok() {
+            signers
+                .entry(signature.public_key.clone())
+                .or_insert_with(|| signature.node_id.clone());
+        }
+    }
+    let weights: Vec<(String, String, u64)> = signers
+        .into_iter()
+        .map(|(public_key, node_id)| {
+            let weight = match config.weighting {
+                RewardWeighting::Equal => 1,
+                RewardWeighting::Stake => registry.stake_for(&public_key).unwrap_or(0),
+            };
+            (public_key, node_id, weight)
+        })
+        .filter(|(_, _, weight)| *weight > 0)
+        .collect();
+    let total_weight: u128 = weights.iter().map(|(_, _, weight)| *weight as u128).sum();
+    if total_weight == 0 || config.pool_per_epoch == 0 {
+        return Ok(Vec::new());
+    }
+    let pool = config.pool_per_epoch as u128;
+    let mut shares: Vec<(RewardShare, u128)> = weights
+        .into_iter()
+        .map(|(public_key, node_id, weight)| {
+            let scaled = pool * weight as u128;
+            let share = RewardShare {
+                node_id,
+                public_key,
+                amount: (scaled / total_weight) as u64,
+            };
+            (share, scaled % total_weight)
+        })
+        .collect();
+    let paid: u128 = shares.iter().map(|(share, _)| share.amount as u128).sum();
+    let mut remainder = (pool - paid) as usize;
+    let mut order: Vec<usize> = (0..shares.len()).collect();
+    order.sort_by(|left, right| {
+        shares[*right]
+            .1
+            .cmp(&shares[*left].1)
+            .then_with(|| shares[*left].0.public_key.cmp(&shares[*right].0.public_key))
+    });
+    for idx in order {
+        if remainder == 0 {
+            break;
+        }
+        shares[idx].0.amount += 1;
+        remainder -= 1;
+    }
+    Ok(shares
+        .into_iter()
+        .map(|(share, _)| share)
+        .filter(|share| share.amount > 0)
+        .collect())
+}
+
+/// Credits the epoch's rewards to `registry` and returns the signed artifact.
+///
+/// The artifact is written to [`reward_distribution_path`]; if it already
+/// exists the epoch is rejected with [`RewardError::AlreadyDistributed`] and
+/// the registry is left untouched.  Persisting the registry is up to the caller.
+pub fn distribute_epoch_rewards(
+    log_dir: &Path,
+    checkpoint: &AnchorCheckpoint,
+    config: &RewardConfig,
+    registry: &mut StakeRegistry,
+    key: &KeyMaterial,
+) -> Result<RewardDistribution, RewardError> {
+    let path = reward_distribution_path(log_dir, checkpoint.epoch);
+    if path.exists() {
+        return Err(RewardError::AlreadyDistributed(checkpoint.epoch));
+    }
+    let digest = checkpoint
+        .digest()
+        .map_err(|err| RewardError::Invalid(err.to_string()))?;
+    let shares = compute_epoch_rewards(checkpoint, config, registry)?;
+    let mut distribution = RewardDistribution {
+        schema: SCHEMA_REWARD_DISTRIBUTION.to_string(),
+        epoch: checkpoint.epoch,
+        checkpoint_digest: hex::encode(digest),
+        pool: config.pool_per_epoch,
+        weighting: config.weighting,
+        asset: config.asset.clone(),
+        shares,
+        distributor: encode_public_key_base64(&key.verifying),
+        signature: String::new(),
+    };
+    let signature = sign_payload(&key.signing, &distribution.signing_bytes()?);
+    distribution.signature = encode_signature_base64(&signature);
+
+    let asset = config.asset.as_deref().unwrap_or(NATIVE_ASSET);
+    validate_asset_id(asset).map_err(RewardError::Invalid)?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| RewardError::Io(err.to_string()))?;
+    }
+    let bytes =
+        serde_json::to_vec_pretty(&distribution).map_err(|err| RewardError::Io(err.to_string()))?;
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, bytes).map_err(|err| RewardError::Io(err.to_string()))?;
+    fs::rename(&tmp_path, &path).map_err(|err| RewardError::Io(err.to_string()))?;
+
+    let mut tx = registry.transaction();
+    for share in &distribution.shares {
+        tx.mint_asset(
+            &share.public_key,
+            asset,
+            share.amount,
+            SupplyCause::EpochReward,
+        );
+    }
+    tx.commit().map_err(RewardError::Invalid)?;
+    Ok(distribution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::julian_genesis_anchor;
+    use crate::net::checkpoint::CheckpointSignature;
+    use crate::net::schema::AnchorJson;
+    use crate::net::sign::{load_or_derive_keypair, Ed25519KeySource};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn key(seed: &str) -> KeyMaterial {
+        load_or_derive_keypair(&Ed25519KeySource::Seed(seed.to_string())).unwrap()
+    }
+
+    fn signed(node_id: &str, key: &KeyMaterial, payload: &[u8]) -> CheckpointSignature {
+        CheckpointSignature {
+            node_id: node_id.to_string(),
+            public_key: encode_public_key_base64(&key.verifying),
+            signature: encode_signature_base64(&sign_payload(&key.signing, payload)),
+        }
+    }
+
+    #[test]
+    fn epoch_rewards_split_across_verified_signers_once() {
+        let anchor =
+            AnchorJson::from_ledger("node-a", 2, &julian_genesis_anchor(), 0, Vec::new(), None)
+                .unwrap();
+        let payload = serde_json::to_vec(&anchor).unwrap();
+        let (a, b, c) = (key("reward-a"), key("reward-b"), key("reward-c"));
+        let mut forged = signed("node-c", &c, b"other payload");
+        forged.public_key = encode_public_key_base64(&c.verifying);
+        let checkpoint = AnchorCheckpoint::new(
+            3,
+            anchor,
+            vec![
+                signed("node-a", &a, &payload),
+                signed("node-b", &b, &payload),
+                signed("node-a", &a, &payload),
+                forged,
+            ],
+            None,
+        );
+
+        let mut registry = StakeRegistry::default();
+        let a_pk = encode_public_key_base64(&a.verifying);
+        let b_pk = encode_public_key_base64(&b.verifying);
+        let equal = RewardConfig {
+            pool_per_epoch: 101,
+            weighting: RewardWeighting::Equal,
+            asset: None,
+        };
+        let shares = compute_epoch_rewards(&checkpoint, &equal, &registry).unwrap();
+        assert_eq!(shares.len(), 2);
+        assert_eq!(shares.iter().map(|share| share.amount).sum::<u64>(), 101);
+
+        registry.mint(&a_pk, 30, SupplyCause::Funding).unwrap();
+        registry.bond(&a_pk, 30).unwrap();
+        registry.mint(&b_pk, 10, SupplyCause::Funding).unwrap();
+        registry.bond(&b_pk, 10).unwrap();
+        let by_stake = RewardConfig {
+            pool_per_epoch: 100,
+            weighting: RewardWeighting::Stake,
+            asset: None,
+        };
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("power_house_rewards_{nanos}"));
+        let distributor = key("reward-distributor");
+        let distribution =
+            distribute_epoch_rewards(&dir, &checkpoint, &by_stake, &mut registry, &distributor)
+                .unwrap();
+        distribution.verify().unwrap();
+        assert_eq!(registry.account(&a_pk).unwrap().balance, 75);
+        assert_eq!(registry.account(&b_pk).unwrap().balance, 25);
+
+        let stored = RewardDistribution::read(&reward_distribution_path(&dir, 3)).unwrap();
+        assert_eq!(stored, distribution);
+        let mut tampered = stored.clone();
+        tampered.shares[0].amount += 1;
+        assert!(tampered.verify().is_err());
+        assert!(matches!(
+            distribute_epoch_rewards(&dir, &checkpoint, &by_stake, &mut registry, &distributor),
+            Err(RewardError::AlreadyDistributed(3))
+        ));
+        assert_eq!(registry.account(&a_pk).unwrap().balance, 75);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
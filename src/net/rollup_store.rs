@@ -0,0 +1,349 @@
+#![cfg(feature = "net")]
+
+//! Persistent registry of settled and faulted rollup commitments.
+//!
+//! A [`RollupCommitment`](crate::rollup::RollupCommitment) used to live
+//! only as long as its settlement call.  The store keeps every one, keyed by
+//! namespace and a per-namespace sequence number, together with the outcome:
+//! the settlement receipt or the fault that rejected it.  Records are never rewritten, so a `(namespace,
+//! sequence)` pair names the same commitment forever.
+//!
+//! The store commits to its records with a Merkle root over one leaf per
+//! record, in namespace then sequence order.  Checkpoints carry that root as
+//! `rollup_root`, and [`RollupCommitmentStore::prove`] returns the inclusion
+//! proof a downstream verifier checks against it.
+
+use crate::merkle::{build_proof, merkle_root, MerkleProof};
+use crate::rollup::{RollupCommitment, RollupFaultEvidence, RollupPathHash, SettlementReceipt};
+use blake2::digest::{consts::U32, Digest};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::Write as _,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+type Blake2b256 = blake2::Blake2b<U32>;
+
+/// Schema identifier of the store file.
+pub const SCHEMA_ROLLUP_STORE: &str = "mfenx.powerhouse.rollup_store.v1";
+/// File name of the store inside a blob or registry directory.
+pub const ROLLUP_STORE_FILE: &str = "rollup_commitments.json";
+
+const ROLLUP_LEAF_DOMAIN: &[u8] = b"MFENX_ROLLUP_COMMITMENT";
+
+/// Errors produced while reading or writing the commitment store.
+#[derive(Debug, thiserror::Error)]
+pub enum RollupStoreError {
+    /// The store could not be read or written.
+    #[error("rollup store I/O error: {0}")]
+    Io(String),
+    /// The store file is malformed.
+    #[error("invalid rollup store: {0}")]
+    Invalid(String),
+}
+
+/// How a stored commitment's settlement ended.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum RollupOutcome {
+    /// The fee was settled.
+    Settled {
+        /// Fee payer public key.
+        payer: String,
+        /// Fee debited from the payer.
+        fee: u64,
+    },
+    /// Verification or settlement failed with fault evidence.
+    Faulted {
+        /// Reason carried by the fault evidence.
+        reason: String,
+        /// Optional evidence payload.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        payload: Option<String>,
+    },
+}
+
+impl From<&SettlementReceipt> for RollupOutcome {
+    fn from(receipt: &SettlementReceipt) -> Self {
+        Self::Settled {
+            payer: receipt.payer.clone(),
+            fee: receipt.fee,
+        }
+    }
+}
+
+impl From<&RollupFaultEvidence> for RollupOutcome {
+    fn from(fault: &RollupFaultEvidence) -> Self {
+        Self::Faulted {
+            reason: fault.reason.clone(),
+            payload: fault.payload.clone(),
+        }
+    }
+}
+
+/// One commitment and its outcome.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredCommitment {
+    /// Namespace of the DA blob.
+    pub namespace: String,
+    /// Position within the namespace, starting at 0.
+    pub sequence: u64,
+    /// Hex-encoded share root of the DA blob.
+    pub share_root: String,
+    /// Optional ZK path root of the DA blob.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pedersen_root: Option<String>,
+    /// Hash of the Merkle path under `pedersen_root`; omitted when Pedersen.
+    #[serde(default, skip_serializing_if = "RollupPathHash::is_pedersen")]
+    pub path_hash: RollupPathHash,
+    /// Optional L1 settlement identifier.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settlement_slot: Option<String>,
+    /// Unix millis at which the outcome was recorded.
+    pub recorded_at_ms: u64,
+    /// Settlement receipt or fault.
+    pub outcome: RollupOutcome,
+}
+
+impl StoredCommitment {
+    /// Merkle leaf committing to every field of the record.
+    pub fn leaf(&self) -> [u8; 32] {
+        let mut hasher = Blake2b256::new();
+        hasher.update(ROLLUP_LEAF_DOMAIN);
+        hasher.update(serde_json::to_vec(self).expect("stored commitment serializes"));
+        hasher.finalize().into()
+    }
+}
+
+/// Commitments keyed by namespace, each namespace in sequence order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollupCommitmentStore {
+    schema: String,
+    namespaces: BTreeMap<String, Vec<StoredCommitment>>,
+}
+
+impl Default for RollupCommitmentStore {
+    fn default() -> Self {
+        Self {
+            schema: SCHEMA_ROLLUP_STORE.to_string(),
+            namespaces: BTreeMap::new(),
+        }
+    }
+}
+
+impl RollupCommitmentStore {
+    /// Path of the store inside `dir`.
+    pub fn path_for(dir: &Path) -> PathBuf {
+        dir.join(ROLLUP_STORE_FILE)
+    }
+
+    /// Loads the store; a missing file is an empty store.
+    pub fn load(path: &Path) -> Result<Self, RollupStoreError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let bytes = fs::read(path).map_err(|err| RollupStoreError::Io(err.to_string()))?;
+        let store: Self = serde_json::from_slice(&bytes)
+            .map_err(|err| RollupStoreError::Invalid(err.to_string()))?;
+        if store.schema != SCHEMA_ROLLUP_STORE {
+            return Err(RollupStoreError::Invalid(format!(
+                "unexpected schema {}",
+                store.schema
+            )));
+        }
+        for (namespace, records) in &store.namespaces {
+            for (index, record) in records.iter().enumerate() {
+                if record.namespace != *namespace || record.sequence != index as u64 {
+                    return Err(RollupStoreError::Invalid(format!(
+                        "record {}/{} is out of place",
+                        record.namespace, record.sequence
+                    )));
+                }
+            }
+        }
+        Ok(store)
+    }
+
+    /// Persists the store atomically.
+    pub fn save(&self, path: &Path) -> Result<(), RollupStoreError> {
+        let io = |err: std::io::Error| RollupStoreError::Io(err.to_string());
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(io)?;
+        }
+        let data = serde_json::to_vec_pretty(self)
+            .map_err(|err| RollupStoreError::Invalid(err.to_string()))?;
+        let temp_path = path.with_extension(format!("tmp-{}", std::process::id()));
+        let write_result = (|| {
+            let mut file = fs::File::create(&temp_path)?;
+            file.write_all(&data)?;
+            file.sync_all()?;
+            fs::rename(&temp_path, path)
+        })();
+        if write_result.is_err() {
+            let _ = fs::remove_file(&temp_path);
+        }
+        write_result.map_err(io)
+    }
+
+    /// Appends `commitment` with `outcome` and returns the stored record.
+    pub fn record(
+        &mut self,
+        commitment: &RollupCommitment,
+        outcome: RollupOutcome,
+        recorded_at_ms: u64,
+    ) -> &StoredCommitment {
+        let records = self
+            .namespaces
+            .entry(commitment.namespace.clone())
+            .or_default();
+        records.push(StoredCommitment {
+            namespace: commitment.namespace.clone(),
+            sequence: records.len() as u64,
+            share_root: commitment.share_root.clone(),
+            pedersen_root: commitment.pedersen_root.clone(),
+            path_hash: commitment.path_hash,
+            settlement_slot: commitment.settlement_slot.clone(),
+            recorded_at_ms,
+            outcome,
+        });
+        records.last().expect("record just pushed")
+    }
+
+    /// Namespaces with at least one record.
+    pub fn namespaces(&self) -> impl Iterator<Item = &str> {
+        self.namespaces.keys().map(String::as_str)
+    }
+
+    /// Records of `namespace` in sequence order.
+    pub fn namespace(&self, namespace: &str) -> &[StoredCommitment] {
+        self.namespaces
+            .get(namespace)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// The record at `sequence` in `namespace`.
+    pub fn get(&self, namespace: &str, sequence: u64) -> Option<&StoredCommitment> {
+        self.namespace(namespace)
+            .get(usize::try_from(sequence).ok()?)
+    }
+
+    /// Records across every namespace.
+    pub fn len(&self) -> usize {
+        self.namespaces.values().map(Vec::len).sum()
+    }
+
+    /// Whether nothing has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.namespaces.is_empty()
+    }
+
+    fn leaves(&self) -> Vec<[u8; 32]> {
+        self.namespaces
+            .values()
+            .flatten()
+            .map(StoredCommitment::leaf)
+            .collect()
+    }
+
+    /// Merkle root over every record, or `None` for an empty store.
+    pub fn root(&self) -> Option<[u8; 32]> {
+        (!self.is_empty()).then(|| merkle_root(&self.leaves()))
+    }
+
+    /// Inclusion proof of the record at `sequence` in `namespace` against [`Self::root`].
+    pub fn prove(&self, namespace: &str, sequence: u64) -> Option<MerkleProof> {
+        self.get(namespace, sequence)?;
+        let index = self
+            .namespaces
+            .iter()
+            .take_while(|(name, _)| name.as_str() < namespace)
+            .map(|(_, records)| records.len())
+            .sum::<usize>()
+            + sequence as usize;
+        build_proof(&self.leaves(), index)
+    }
+}
+
+/// Loads the store at `path`, records one outcome, and saves it.
+pub fn append_rollup_record(
+    path: &Path,
+    commitment: &RollupCommitment,
+    outcome: RollupOutcome,
+) -> Result<StoredCommitment, RollupStoreError> {
+    let mut store = RollupCommitmentStore::load(path)?;
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64;
+    let stored = store.record(commitment, outcome, now_ms).clone();
+    store.save(path)?;
+    Ok(stored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::merkle::verify_proof;
+
+    fn commitment(namespace: &str, share_root: &str) -> RollupCommitment {
+        RollupCommitment {
+            namespace: namespace.to_string(),
+            share_root: share_root.to_string(),
+            pedersen_root: None,
+            settlement_slot: None,
+            path_hash: RollupPathHash::Pedersen,
+        }
+    }
+
+    #[test]
+    fn records_persist_by_namespace_and_prove_against_the_root() {
+        let dir = std::env::temp_dir().join(format!(
+            "powerhouse_rollup_store_{}_{}",
+            std::process::id(),
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        let path = RollupCommitmentStore::path_for(&dir);
+        assert!(RollupCommitmentStore::load(&path).unwrap().root().is_none());
+
+        let settled = RollupOutcome::Settled {
+            payer: "payer".to_string(),
+            fee: 10,
+        };
+        append_rollup_record(&path, &commitment("beta", "b0"), settled.clone()).unwrap();
+        append_rollup_record(&path, &commitment("alpha", "a0"), settled).unwrap();
+        let fault = RollupFaultEvidence {
+            namespace: "alpha".to_string(),
+            commitment: "a1".to_string(),
+            reason: "zk proof invalid".to_string(),
+            payload: None,
+        };
+        let mut store = RollupCommitmentStore::load(&path).unwrap();
+        let stored = store.record(&commitment("alpha", "a1"), (&fault).into(), 7);
+        assert_eq!((stored.namespace.as_str(), stored.sequence), ("alpha", 1));
+        store.save(&path).unwrap();
+
+        let store = RollupCommitmentStore::load(&path).unwrap();
+        assert_eq!(store.len(), 3);
+        assert_eq!(store.namespaces().collect::<Vec<_>>(), ["alpha", "beta"]);
+        assert!(matches!(
+            store.get("alpha", 1).unwrap().outcome,
+            RollupOutcome::Faulted { .. }
+        ));
+        let root = store.root().unwrap();
+        for (namespace, sequence) in [("alpha", 0), ("alpha", 1), ("beta", 0)] {
+            let proof = store.prove(namespace, sequence).unwrap();
+            assert_eq!(proof.root, root);
+            assert_eq!(proof.leaf, store.get(namespace, sequence).unwrap().leaf());
+            assert!(verify_proof(&proof));
+        }
+        assert!(store.prove("beta", 1).is_none());
+        let _ = fs::remove_dir_all(dir);
+    }
+}
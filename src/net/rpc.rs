@@ -1,20 +1,56 @@
 #![cfg(feature = "net")]
 
 //! MetaMask-compatible JSON-RPC backed exclusively by finalized native-chain state.
+//!
+//! Secondary assets held in the stake registry are served through the
+//! ERC-20-style `asset_balanceOf`, `asset_totalSupply`, `asset_nonceOf` and
+//! `asset_transfer` methods; native units stay on the `eth_*` surface.
+//! `beacon_get` serves the random beacon of a finalized checkpoint epoch.
+//! `ph_traceTransaction` replays finalized blocks to list the account
+//! mutations of one stored transaction.
+//! `eth_call` answers view selectors on the pseudo-contracts described in
+//! [`crate::net::precompile`]. Transactions carrying calldata are accepted
+//! only for addresses with a registered [`TxInputHandler`].
 
+use crate::commands::migration_apply_claims::{claim_applied, resolve_state_path};
+use crate::economics::NATIVE_ASSET;
+use crate::net::address_index::AddressIndex;
+use crate::net::beacon::{load_beacon, BeaconError};
+use crate::net::block_archive::find_archived_transaction;
+use crate::net::config::EvmRpcConfigBuilder;
+use crate::net::fork::SafeMode;
+use crate::net::mempool::PendingPool;
 use crate::net::native_chain::{
     decode_eip1559_transaction, decode_hex_prefixed, normalize_evm_address, to_quantity_u128,
-    to_quantity_u64, FinalizedNativeBlock, NativeChainCommand, NativeTransaction,
-    SharedNativeChainState, NATIVE_DECIMAL_FACTOR, NATIVE_GAS_LIMIT, NATIVE_GAS_PRICE,
+    to_quantity_u64, wei_to_native_units, FinalizedNativeBlock, NativeChainCommand,
+    NativeChangeKind, NativeTransaction, SharedNativeChainState, NATIVE_DECIMAL_FACTOR,
+    NATIVE_GAS_LIMIT, NATIVE_GAS_PRICE,
 };
+use crate::net::parameters::SharedParameters;
+use crate::net::precompile::{
+    asset_token_address, decode_call, encode_bool, encode_uint, PrecompileCall,
+    MIGRATION_CLAIMS_ADDRESS, NATIVE_TOKEN_ADDRESS, PSEUDO_CONTRACT_CODE,
+};
+use crate::net::replica::ReplicaMode;
+use crate::net::rpc_tiers::{RpcTier, RpcTiers};
+use crate::net::stake_registry::{validate_asset_id, AssetTransfer, RegistryView, StakeRegistry};
+use crate::net::storage::StoreFingerprint;
 use blake2::digest::{consts::U32, Digest as BlakeDigest};
 use serde::Deserialize;
 use serde_json::{json, Value};
-use std::{collections::HashMap, io, net::SocketAddr, str, time::Duration};
+use std::{
+    collections::HashMap,
+    io,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    str,
+    sync::{Arc, PoisonError, RwLock as StdRwLock},
+    time::Duration,
+};
 use tokio::{
     io::{AsyncReadExt, AsyncWriteExt},
     net::{TcpListener, TcpStream},
-    sync::{mpsc, oneshot, Semaphore},
+    sync::{mpsc, oneshot, Mutex, OwnedSemaphorePermit, Semaphore},
     time,
 };
 
@@ -24,16 +60,143 @@ const MAX_HEADER_BYTES: usize = 32 * 1024;
 const MAX_BODY_BYTES: usize = 1024 * 1024;
 const MAX_BATCH_REQUESTS: usize = 100;
 const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
-const DEFAULT_MAX_CONNECTIONS: usize = 256;
+const RPC_LIMIT_EXCEEDED: i64 = -32005;
+/// Gas charged per zero calldata byte (EIP-2028).
+const CALLDATA_ZERO_BYTE_GAS: u64 = 4;
+/// Gas charged per non-zero calldata byte (EIP-2028).
+const CALLDATA_NONZERO_BYTE_GAS: u64 = 16;
+/// Percentage added to calldata-bearing gas estimates unless configured.
+pub const DEFAULT_GAS_MARGIN_PERCENT: u64 = 20;
+/// Default number of concurrently serviced HTTP connections per [`ConnectionLimiter`].
+pub const DEFAULT_MAX_CONNECTIONS: usize = 256;
+const DEFAULT_SATURATION_WAIT: Duration = Duration::from_millis(250);
 const EMPTY_UNCLES_HASH: &str =
     "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347";
 
+/// Bounded pool of connection slots for the node's HTTP listeners.
+///
+/// Clones share the same pool, so one limiter handed to both the RPC and the
+/// metrics server caps their combined concurrency. When every slot is taken the
+/// accept loop waits briefly for one to free up, then answers
+/// `503 Service Unavailable` instead of spawning another task.
+#[derive(Clone, Debug)]
+pub struct ConnectionLimiter {
+    permits: Arc<Semaphore>,
+    max_connections: usize,
+    saturation_wait: Duration,
+}
+
+impl ConnectionLimiter {
+    /// Creates a limiter admitting at most `max_connections` (minimum one) at a time.
+    pub fn new(max_connections: usize) -> Self {
+        let max_connections = max_connections.max(1);
+        Self {
+            permits: Arc::new(Semaphore::new(max_connections)),
+            max_connections,
+            saturation_wait: DEFAULT_SATURATION_WAIT,
+        }
+    }
+
+    /// Overrides how long a saturated accept loop waits for a free slot.
+    pub fn with_saturation_wait(mut self, wait: Duration) -> Self {
+        self.saturation_wait = wait;
+        self
+    }
+
+    /// Configured connection ceiling.
+    pub fn max_connections(&self) -> usize {
+        self.max_connections
+    }
+
+    /// Slots currently free.
+    pub fn available(&self) -> usize {
+        self.permits.available_permits()
+    }
+
+    /// Reserves a slot for `stream`, or replies 503 and returns `None` when saturated.
+    pub(crate) async fn admit(&self, stream: &mut TcpStream) -> Option<OwnedSemaphorePermit> {
+        match time::timeout(self.saturation_wait, self.permits.clone().acquire_owned()).await {
+            Ok(Ok(permit)) => Some(permit),
+            _ => {
+                let _ =
+                    time::timeout(self.saturation_wait, write_service_unavailable(stream)).await;
+                None
+            }
+        }
+    }
+}
+
+impl Default for ConnectionLimiter {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CONNECTIONS)
+    }
+}
+
+/// Cross-origin policy applied to every JSON-RPC response.
+///
+/// The default keeps the historical behaviour: any origin, `content-type`
+/// as the only allowed request header, and `OPTIONS` preflights answered.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CorsPolicy {
+    /// Origins allowed to read responses; `*` allows any and an empty list
+    /// sends no CORS headers at all.
+    pub allowed_origins: Vec<String>,
+    /// Request headers browsers may send on cross-origin calls.
+    pub allowed_headers: Vec<String>,
+    /// Whether `OPTIONS` preflights are answered; otherwise they get `405`.
+    pub serve_preflight: bool,
+}
+
+impl Default for CorsPolicy {
+    fn default() -> Self {
+        Self {
+            allowed_origins: vec!["*".to_string()],
+            allowed_headers: vec!["content-type".to_string()],
+            serve_preflight: true,
+        }
+    }
+}
+
+impl CorsPolicy {
+    /// `Access-Control-Allow-Origin` value for a request sent from `origin`.
+    ///
+    /// Listed origins are echoed back; unlisted or missing origins get none.
+    pub fn allowed_origin(&self, origin: Option<&str>) -> Option<String> {
+        if self.allowed_origins.iter().any(|allowed| allowed == "*") {
+            return Some("*".to_string());
+        }
+        let origin = origin?;
+        self.allowed_origins
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(origin))
+            .then(|| origin.to_string())
+    }
+
+    /// CORS header lines (each ending in CRLF) for a request from `origin`.
+    pub(crate) fn response_headers(&self, origin: Option<&str>) -> String {
+        let mut headers = String::new();
+        let wildcard = self.allowed_origins.iter().any(|allowed| allowed == "*");
+        if !wildcard && !self.allowed_origins.is_empty() {
+            headers.push_str("Vary: Origin\r\n");
+        }
+        if let Some(allowed) = self.allowed_origin(origin) {
+            headers.push_str(&format!(
+                "Access-Control-Allow-Origin: {allowed}\r\n\
+                 Access-Control-Allow-Methods: POST, OPTIONS, GET\r\n\
+                 Access-Control-Allow-Headers: {}\r\n",
+                self.allowed_headers.join(", ")
+            ));
+        }
+        headers
+    }
+}
+
 #[derive(Debug)]
-struct HttpRequest {
-    method: String,
-    path: String,
-    headers: HashMap<String, String>,
-    body: Vec<u8>,
+pub(crate) struct HttpRequest {
+    pub(crate) method: String,
+    pub(crate) path: String,
+    pub(crate) headers: HashMap<String, String>,
+    pub(crate) body: Vec<u8>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -80,6 +243,20 @@ impl RpcError {
         }
     }
 
+    fn reverted(message: impl Into<String>) -> Self {
+        Self {
+            code: 3,
+            message: format!("execution reverted: {}", message.into()),
+        }
+    }
+
+    fn limit_exceeded(tier: RpcTier) -> Self {
+        Self {
+            code: RPC_LIMIT_EXCEEDED,
+            message: format!("{} requests are saturated, retry later", tier.label()),
+        }
+    }
+
     fn method_not_found(method: &str) -> Self {
         Self {
             code: -32601,
@@ -88,6 +265,86 @@ impl RpcError {
     }
 }
 
+/// Calldata of a transaction sent to an address with a registered handler.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TxInput {
+    /// Transaction hash as returned by `eth_sendRawTransaction`.
+    pub hash: String,
+    /// Recovered sender address.
+    pub from: String,
+    /// Target address the handler is registered for.
+    pub to: String,
+    /// Native units transferred to `to` alongside the calldata.
+    pub value_units: u64,
+    /// Raw calldata bytes.
+    pub input: Vec<u8>,
+}
+
+/// Application hook receiving transaction calldata for one target address.
+///
+/// Handlers run on the serving node outside consensus, like the `asset_*`
+/// methods: `validate` may reject the transaction before submission, and
+/// `apply` runs once the native chain has accepted it into the mempool. The
+/// value transfer itself still finalizes through consensus.
+pub trait TxInputHandler: Send + Sync {
+    /// Checks the calldata without side effects; an error rejects the transaction.
+    fn validate(&self, input: &TxInput) -> Result<(), String>;
+
+    /// Applies the application effect of accepted calldata.
+    fn apply(&self, input: &TxInput) -> Result<(), String>;
+
+    /// Gas `apply` is expected to use beyond the intrinsic transfer and
+    /// calldata costs; `eth_estimateGas` adds it to its estimate.
+    fn gas_cost(&self, _input: &TxInput) -> u64 {
+        0
+    }
+}
+
+/// Registered [`TxInputHandler`]s keyed by lowercase target address.
+#[derive(Clone, Default)]
+pub struct TxInputHandlers {
+    handlers: HashMap<String, Arc<dyn TxInputHandler>>,
+}
+
+impl TxInputHandlers {
+    /// Registers `handler` for calldata sent to `address`, replacing any previous one.
+    pub fn register(
+        &mut self,
+        address: &str,
+        handler: Arc<dyn TxInputHandler>,
+    ) -> Result<(), String> {
+        let address = normalize_evm_address(address)
+            .ok_or_else(|| format!("invalid handler address {address}"))?;
+        self.handlers.insert(address, handler);
+        Ok(())
+    }
+
+    /// Handler registered for `address`, if any.
+    pub fn get(&self, address: &str) -> Option<Arc<dyn TxInputHandler>> {
+        normalize_evm_address(address).and_then(|address| self.handlers.get(&address).cloned())
+    }
+
+    /// Number of registered handlers.
+    pub fn len(&self) -> usize {
+        self.handlers.len()
+    }
+
+    /// Whether no handler is registered.
+    pub fn is_empty(&self) -> bool {
+        self.handlers.is_empty()
+    }
+}
+
+impl std::fmt::Debug for TxInputHandlers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut addresses = self.handlers.keys().collect::<Vec<_>>();
+        addresses.sort();
+        f.debug_struct("TxInputHandlers")
+            .field("addresses", &addresses)
+            .finish()
+    }
+}
+
 #[derive(Clone)]
 /// Runtime settings and shared finalized state for the wallet JSON-RPC server.
 pub struct EvmRpcConfig {
@@ -101,11 +358,83 @@ pub struct EvmRpcConfig {
     pub command_sender: mpsc::Sender<NativeChainCommand>,
     /// Maximum time allowed for request reads and transaction acceptance.
     pub request_timeout: Duration,
-    /// Maximum number of concurrently serviced HTTP connections.
-    pub connection_limit: std::sync::Arc<Semaphore>,
+    /// Connection slots shared with other HTTP listeners on the node.
+    pub connection_limit: ConnectionLimiter,
+    /// Stake registry backing the `asset_*` methods; `None` disables them.
+    pub asset_registry: Option<PathBuf>,
+    /// Log directory whose checkpoints back `beacon_get`; `None` disables it.
+    pub beacon_log_dir: Option<PathBuf>,
+    /// Minimum signer threshold for beacon values.
+    pub beacon_threshold: usize,
+    /// Archive of pruned blocks searched when a transaction left the live state.
+    pub block_archive: Option<PathBuf>,
+    /// Fork safe mode; while engaged, write methods are refused.
+    pub safe_mode: SafeMode,
+    /// Migration apply-state file behind `isClaimed`; defaults to the one
+    /// beside `asset_registry`.
+    pub claim_state: Option<PathBuf>,
+    /// Cross-origin policy for browser wallets and dapps.
+    pub cors: CorsPolicy,
+    /// Application hooks admitting calldata for their target addresses.
+    pub tx_input_handlers: TxInputHandlers,
+    /// Governance-managed runtime parameters served by `governance_parameters`.
+    pub parameters: SharedParameters,
+    /// Per-tier admission control; shed requests get HTTP 429.
+    pub tiers: RpcTiers,
+    /// Percentage added to `eth_estimateGas` results for calldata-bearing
+    /// transactions; plain transfers always estimate exactly 21000.
+    pub gas_margin_percent: u64,
+    /// Serve as a read-only replica: refuse writes and export staleness on
+    /// `GET /metrics`.
+    pub replica: Option<ReplicaMode>,
+    /// Pending pool listed by `ph_pendingTransactions`; `None` where the node
+    /// keeps no pool of its own.
+    pub pending: Option<PendingPool>,
+    asset_lock: Arc<Mutex<()>>,
+    registry_cache: Arc<RegistryCache>,
+}
+
+/// Indexed registry snapshot shared by the `asset_*` handlers.
+///
+/// Reloaded only when the store fingerprint changes, i.e. after a save in this
+/// process or a write by another process.
+#[derive(Default)]
+struct RegistryCache {
+    current: StdRwLock<Option<(StoreFingerprint, Arc<RegistryView>)>>,
+}
+
+impl RegistryCache {
+    fn view(&self, path: &Path) -> Result<Arc<RegistryView>, String> {
+        let fingerprint = StoreFingerprint::of(path);
+        if let Some((cached, view)) = &*self.current.read().unwrap_or_else(PoisonError::into_inner)
+        {
+            if *cached == fingerprint {
+                return Ok(Arc::clone(view));
+            }
+        }
+        let registry = StakeRegistry::load(path)?;
+        let view = match AddressIndex::load(&AddressIndex::path_for(path))? {
+            Some(index) => RegistryView::with_index(registry, index),
+            None => RegistryView::new(registry),
+        };
+        let view = Arc::new(view);
+        *self.current.write().unwrap_or_else(PoisonError::into_inner) =
+            Some((fingerprint, Arc::clone(&view)));
+        Ok(view)
+    }
 }
 
 impl EvmRpcConfig {
+    /// Starts a validating builder with the same required values as [`EvmRpcConfig::new`].
+    pub fn builder(
+        listen: SocketAddr,
+        chain_id: u64,
+        state: SharedNativeChainState,
+        command_sender: mpsc::Sender<NativeChainCommand>,
+    ) -> EvmRpcConfigBuilder {
+        EvmRpcConfigBuilder::new(listen, chain_id, state, command_sender)
+    }
+
     /// Creates an RPC configuration backed by the supplied consensus state and command queue.
     pub fn new(
         listen: SocketAddr,
@@ -119,7 +448,22 @@ impl EvmRpcConfig {
             state,
             command_sender,
             request_timeout: DEFAULT_REQUEST_TIMEOUT,
-            connection_limit: std::sync::Arc::new(Semaphore::new(DEFAULT_MAX_CONNECTIONS)),
+            connection_limit: ConnectionLimiter::default(),
+            asset_registry: None,
+            beacon_log_dir: None,
+            beacon_threshold: 1,
+            block_archive: None,
+            safe_mode: SafeMode::default(),
+            claim_state: None,
+            cors: CorsPolicy::default(),
+            tx_input_handlers: TxInputHandlers::default(),
+            parameters: SharedParameters::default(),
+            tiers: RpcTiers::default(),
+            gas_margin_percent: DEFAULT_GAS_MARGIN_PERCENT,
+            replica: None,
+            pending: None,
+            asset_lock: Arc::new(Mutex::new(())),
+            registry_cache: Arc::new(RegistryCache::default()),
         }
     }
 }
@@ -128,17 +472,21 @@ impl EvmRpcConfig {
 pub async fn run_evm_rpc_server(cfg: EvmRpcConfig) -> io::Result<()> {
     let listener = TcpListener::bind(cfg.listen).await?;
     println!(
-        "QSYS|mod=EVMRPC|evt=LISTEN|addr={}|chain_id={}|state=finalized",
-        cfg.listen, cfg.chain_id
+        "QSYS|mod=EVMRPC|evt=LISTEN|addr={}|chain_id={}|state=finalized|mode={}",
+        cfg.listen,
+        cfg.chain_id,
+        if cfg.replica.is_some() {
+            "replica"
+        } else {
+            "primary"
+        }
     );
     loop {
         let (mut stream, _) = listener.accept().await?;
-        let permit = cfg
-            .connection_limit
-            .clone()
-            .acquire_owned()
-            .await
-            .map_err(|_| io::Error::other("RPC connection limiter closed"))?;
+        let Some(permit) = cfg.connection_limit.admit(&mut stream).await else {
+            eprintln!("evm rpc saturated: rejected connection with 503");
+            continue;
+        };
         let cfg = cfg.clone();
         tokio::spawn(async move {
             let _permit = permit;
@@ -164,31 +512,58 @@ async fn handle_connection(stream: &mut TcpStream, cfg: &EvmRpcConfig) -> io::Re
                 stream,
                 "400 Bad Request",
                 &json_rpc_error(Value::Null, -32700, format!("parse error: {err}")),
+                &cfg.cors.response_headers(None),
             )
             .await;
         }
     };
+    let cors = cfg
+        .cors
+        .response_headers(request.headers.get("origin").map(String::as_str));
 
     if request.method.eq_ignore_ascii_case("OPTIONS") {
-        stream.write_all(&preflight_response()).await?;
+        if !cfg.cors.serve_preflight {
+            return write_json(
+                stream,
+                "405 Method Not Allowed",
+                &json_rpc_error(Value::Null, -32600, "preflight requests are disabled"),
+                &cors,
+            )
+            .await;
+        }
+        stream.write_all(&preflight_response(&cors)).await?;
         return stream.shutdown().await;
     }
     if request.method.eq_ignore_ascii_case("GET") && request.path == "/healthz" {
         let state = cfg.state.read().await;
-        let body = json!({
+        let mut body = json!({
             "status": "ok",
             "service": "power-house-finalized-rpc",
             "chain_id": cfg.chain_id,
             "finalized_block": state.latest_number(),
             "finalized_hash": state.latest_hash(),
         });
-        return write_json(stream, "200 OK", &body).await;
+        if let Some(replica) = &cfg.replica {
+            body["replica"] = json!({
+                "primary_rpc": replica.primary_rpc,
+                "sync_age_secs": replica.status.sync_age_secs(),
+                "tip_age_secs": replica.status.tip_age_secs(),
+            });
+        }
+        return write_json(stream, "200 OK", &body, &cors).await;
+    }
+    if let Some(replica) = &cfg.replica {
+        if request.method.eq_ignore_ascii_case("GET") && request.path == "/metrics" {
+            let body = replica.status.render_metrics() + &cfg.tiers.render_metrics();
+            return write_metrics(stream, &body).await;
+        }
     }
     if !request.method.eq_ignore_ascii_case("POST") {
         return write_json(
             stream,
             "405 Method Not Allowed",
             &json_rpc_error(Value::Null, -32600, "JSON-RPC requires POST"),
+            &cors,
         )
         .await;
     }
@@ -197,6 +572,7 @@ async fn handle_connection(stream: &mut TcpStream, cfg: &EvmRpcConfig) -> io::Re
             stream,
             "404 Not Found",
             &json_rpc_error(Value::Null, -32600, "unknown RPC path"),
+            &cors,
         )
         .await;
     }
@@ -209,6 +585,7 @@ async fn handle_connection(stream: &mut TcpStream, cfg: &EvmRpcConfig) -> io::Re
                 stream,
                 "415 Unsupported Media Type",
                 &json_rpc_error(Value::Null, -32600, "content-type must be application/json"),
+                &cors,
             )
             .await;
         }
@@ -221,6 +598,7 @@ async fn handle_connection(stream: &mut TcpStream, cfg: &EvmRpcConfig) -> io::Re
                 stream,
                 "400 Bad Request",
                 &json_rpc_error(Value::Null, -32700, format!("parse error: {err}")),
+                &cors,
             )
             .await;
         }
@@ -248,9 +626,18 @@ async fn handle_connection(stream: &mut TcpStream, cfg: &EvmRpcConfig) -> io::Re
     } else {
         process_request(document, cfg).await
     };
+    let shed = |response: &Value| response["error"]["code"] == json!(RPC_LIMIT_EXCEEDED);
     match response {
-        Some(response) => write_json(stream, "200 OK", &response).await,
-        None => write_no_content(stream).await,
+        Some(response)
+            if response
+                .as_array()
+                .map_or_else(|| shed(&response), |items| items.iter().all(shed)) =>
+        {
+            let headers = format!("{cors}Retry-After: 1\r\n");
+            write_json(stream, "429 Too Many Requests", &response, &headers).await
+        }
+        Some(response) => write_json(stream, "200 OK", &response, &cors).await,
+        None => write_no_content(stream, &cors).await,
     }
 }
 
@@ -270,7 +657,14 @@ async fn process_request(document: Value, cfg: &EvmRpcConfig) -> Option<Value> {
     let result = if request.jsonrpc.as_deref() != Some("2.0") {
         Err(RpcError::invalid_request("jsonrpc must equal 2.0"))
     } else {
-        handle_rpc_method(&request, cfg).await
+        let tier = RpcTier::of(&request.method, &request.params);
+        match cfg.tiers.admit(tier, cfg.request_timeout).await {
+            Some(_permit) => {
+                let _span = crate::profile::detached_span(format!("rpc.{}", request.method));
+                handle_rpc_method(&request, cfg).await
+            }
+            None => Err(RpcError::limit_exceeded(tier)),
+        }
     };
     if notification {
         return None;
@@ -285,6 +679,18 @@ async fn handle_rpc_method(
     request: &JsonRpcRequest,
     cfg: &EvmRpcConfig,
 ) -> Result<Value, RpcError> {
+    let write = matches!(
+        request.method.as_str(),
+        "eth_sendRawTransaction" | "asset_transfer"
+    );
+    if let Some(replica) = cfg.replica.as_ref().filter(|_| write) {
+        return Err(RpcError::unsupported(replica.redirect_hint()));
+    }
+    if cfg.safe_mode.is_engaged() && write {
+        return Err(RpcError::unsupported(
+            "node is in safe mode after a checkpoint fork; writes are disabled",
+        ));
+    }
     match request.method.as_str() {
         "web3_clientVersion" => Ok(Value::String(format!(
             "power-house/{}/finalized-native-rpc",
@@ -327,10 +733,19 @@ async fn handle_rpc_method(
                     .nonce,
             )))
         }
-        "eth_estimateGas" => validate_native_call(&request.params)
-            .map(|_| Value::String(to_quantity_u64(NATIVE_GAS_LIMIT))),
-        "eth_getCode" => Ok(Value::String("0x".to_string())),
-        "eth_call" | "eth_getStorageAt" => Err(RpcError::unsupported(
+        "eth_estimateGas" => estimate_gas(request, cfg).await,
+        "eth_getCode" => {
+            let address = required_string(&request.params, 0, "address")?;
+            let address = normalize_evm_address(&address)
+                .ok_or_else(|| RpcError::invalid_params("invalid address format"))?;
+            let code = match pseudo_contract(&address, cfg)? {
+                Some(_) => PSEUDO_CONTRACT_CODE,
+                None => "0x",
+            };
+            Ok(Value::String(code.to_string()))
+        }
+        "eth_call" => eth_call(request, cfg).await,
+        "eth_getStorageAt" => Err(RpcError::unsupported(
             "contract execution is not available on the native transfer chain",
         )),
         "eth_accounts" => Ok(Value::Array(Vec::new())),
@@ -343,13 +758,295 @@ async fn handle_rpc_method(
         "eth_getBlockTransactionCountByHash" => block_transaction_count_by_hash(request, cfg).await,
         "eth_getTransactionByHash" => get_transaction_by_hash(request, cfg).await,
         "eth_getTransactionReceipt" => get_transaction_receipt(request, cfg).await,
+        "ph_traceTransaction" => trace_transaction(request, cfg).await,
+        "ph_pendingTransactions" => pending_transactions(request, cfg),
         "eth_sendRawTransaction" => send_raw_transaction(request, cfg).await,
         "eth_getLogs" => Ok(Value::Array(Vec::new())),
-        "rpc_modules" => Ok(json!({"eth":"1.0","net":"1.0","web3":"1.0"})),
+        "asset_balanceOf" => {
+            let asset = secondary_asset(&request.params, 0)?;
+            let account = required_string(&request.params, 1, "account")?;
+            let view = asset_registry_view(cfg)?;
+            Ok(Value::String(to_quantity_u64(
+                view.balance_of(&account, &asset),
+            )))
+        }
+        "asset_totalSupply" => {
+            let asset = secondary_asset(&request.params, 0)?;
+            let view = asset_registry_view(cfg)?;
+            Ok(Value::String(to_quantity_u64(
+                view.registry().supply().supply_of(&asset),
+            )))
+        }
+        "asset_nonceOf" => {
+            let account = required_string(&request.params, 0, "account")?;
+            let view = asset_registry_view(cfg)?;
+            Ok(Value::String(to_quantity_u64(
+                view.transfer_nonce(&account),
+            )))
+        }
+        "asset_transfer" => asset_transfer(request, cfg).await,
+        "beacon_get" => beacon_get(request, cfg),
+        "governance_parameters" => serde_json::to_value(cfg.parameters.snapshot())
+            .map_err(|err| RpcError::internal(err.to_string())),
+        "rpc_modules" => Ok(json!({
+            "asset":"1.0","beacon":"1.0","eth":"1.0","governance":"1.0","net":"1.0","ph":"1.0",
+            "web3":"1.0"
+        })),
         other => Err(RpcError::method_not_found(other)),
     }
 }
 
+async fn asset_transfer(request: &JsonRpcRequest, cfg: &EvmRpcConfig) -> Result<Value, RpcError> {
+    let transfer: AssetTransfer = request
+        .params
+        .as_array()
+        .and_then(|items| items.first())
+        .cloned()
+        .ok_or_else(|| RpcError::invalid_params("asset_transfer expects a transfer object"))
+        .and_then(|value| {
+            serde_json::from_value(value)
+                .map_err(|err| RpcError::invalid_params(format!("invalid transfer: {err}")))
+        })?;
+    validate_asset_id(&transfer.asset).map_err(RpcError::invalid_params)?;
+    let _guard = cfg.asset_lock.lock().await;
+    let path = asset_registry_path(cfg)?;
+    let mut registry = StakeRegistry::load(path).map_err(RpcError::internal)?;
+    registry
+        .apply_signed_transfer(&transfer)
+        .map_err(RpcError::invalid_params)?;
+    registry.save(path).map_err(RpcError::internal)?;
+    println!(
+        "QSYS|mod=EVMRPC|evt=ASSET_TRANSFER|asset={}|amount={}|nonce={}",
+        transfer.asset, transfer.amount, transfer.nonce
+    );
+    Ok(Value::Bool(true))
+}
+
+/// Dry-runs the transfer against the finalized state and, for calldata sent to
+/// a registered [`TxInputHandler`], validates it and adds the calldata and
+/// handler costs plus the configured safety margin.
+async fn estimate_gas(request: &JsonRpcRequest, cfg: &EvmRpcConfig) -> Result<Value, RpcError> {
+    let handler = call_target(&request.params).and_then(|to| cfg.tx_input_handlers.get(&to));
+    if handler.is_none() {
+        validate_native_call(&request.params)?;
+    }
+    let call = request
+        .params
+        .as_array()
+        .and_then(|items| items.first())
+        .and_then(Value::as_object)
+        .ok_or_else(|| RpcError::invalid_params("eth_estimateGas expects a transaction object"))?;
+    let address = |field: &str| {
+        call.get(field)
+            .and_then(Value::as_str)
+            .map(|value| {
+                normalize_evm_address(value)
+                    .ok_or_else(|| RpcError::invalid_params(format!("invalid `{field}` address")))
+            })
+            .transpose()
+    };
+    let to = address("to")?
+        .ok_or_else(|| RpcError::unsupported("contract creation is not supported"))?;
+    let from = address("from")?;
+    let value_units = match call.get("value").and_then(Value::as_str) {
+        Some(quantity) => {
+            let wei = quantity
+                .strip_prefix("0x")
+                .and_then(|hex| u128::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| RpcError::invalid_params("`value` must be a hex quantity"))?;
+            wei_to_native_units(wei).map_err(RpcError::invalid_params)?
+        }
+        None => 0,
+    };
+    if let Some(from) = &from {
+        cfg.state
+            .read()
+            .await
+            .dry_run_transfer(from, &to, value_units)
+            .map_err(RpcError::reverted)?;
+    }
+    let data = call
+        .get("data")
+        .or_else(|| call.get("input"))
+        .and_then(Value::as_str)
+        .filter(|data| *data != "0x0")
+        .map_or_else(|| Ok(Vec::new()), decode_hex_prefixed)
+        .map_err(RpcError::invalid_params)?;
+    let Some(handler) = handler.filter(|_| !data.is_empty()) else {
+        return Ok(Value::String(to_quantity_u64(NATIVE_GAS_LIMIT)));
+    };
+    let input = TxInput {
+        hash: String::new(),
+        from: from.unwrap_or_else(zero_address),
+        to,
+        value_units,
+        input: data,
+    };
+    handler.validate(&input).map_err(RpcError::reverted)?;
+    let gas = NATIVE_GAS_LIMIT
+        .saturating_add(calldata_gas(&input.input))
+        .saturating_add(handler.gas_cost(&input));
+    let gas = gas.saturating_mul(100 + cfg.gas_margin_percent) / 100;
+    Ok(Value::String(to_quantity_u64(gas)))
+}
+
+fn calldata_gas(data: &[u8]) -> u64 {
+    data.iter()
+        .map(|byte| {
+            if *byte == 0 {
+                CALLDATA_ZERO_BYTE_GAS
+            } else {
+                CALLDATA_NONZERO_BYTE_GAS
+            }
+        })
+        .sum()
+}
+
+/// Pseudo-contract targeted by an `eth_call`.
+enum PseudoContract {
+    NativeToken,
+    Asset(String),
+    MigrationClaims,
+}
+
+fn pseudo_contract(address: &str, cfg: &EvmRpcConfig) -> Result<Option<PseudoContract>, RpcError> {
+    if address == NATIVE_TOKEN_ADDRESS {
+        return Ok(Some(PseudoContract::NativeToken));
+    }
+    if address == MIGRATION_CLAIMS_ADDRESS {
+        return Ok(Some(PseudoContract::MigrationClaims));
+    }
+    if cfg.asset_registry.is_none() {
+        return Ok(None);
+    }
+    let view = asset_registry_view(cfg)?;
+    Ok(view
+        .registry()
+        .supply()
+        .asset_ids()
+        .into_iter()
+        .filter(|asset| asset != NATIVE_ASSET)
+        .find(|asset| asset_token_address(asset) == address)
+        .map(PseudoContract::Asset))
+}
+
+async fn eth_call(request: &JsonRpcRequest, cfg: &EvmRpcConfig) -> Result<Value, RpcError> {
+    let call = request
+        .params
+        .as_array()
+        .and_then(|items| items.first())
+        .and_then(Value::as_object)
+        .ok_or_else(|| RpcError::invalid_params("eth_call expects a transaction object"))?;
+    let to = call
+        .get("to")
+        .and_then(Value::as_str)
+        .and_then(normalize_evm_address)
+        .ok_or_else(|| RpcError::invalid_params("eth_call requires a `to` address"))?;
+    let data = call
+        .get("data")
+        .or_else(|| call.get("input"))
+        .and_then(Value::as_str)
+        .map_or_else(|| Ok(Vec::new()), decode_hex_prefixed)
+        .map_err(RpcError::invalid_params)?;
+    let Some(target) = pseudo_contract(&to, cfg)? else {
+        if data.is_empty() {
+            return Ok(Value::String("0x".to_string()));
+        }
+        return Err(RpcError::unsupported(
+            "contract execution is not available on the native transfer chain",
+        ));
+    };
+    let call = decode_call(&data).map_err(RpcError::reverted)?;
+    let encoded = match (target, call) {
+        (PseudoContract::NativeToken, PrecompileCall::BalanceOf(holder)) => {
+            let state = cfg.state.read().await;
+            let number = requested_block(&request.params, 1, state.latest_number())?;
+            let units = state
+                .account_at(&holder, number)
+                .map_err(RpcError::invalid_params)?
+                .balance;
+            encode_uint(u128::from(units).saturating_mul(NATIVE_DECIMAL_FACTOR))
+        }
+        (PseudoContract::NativeToken, PrecompileCall::TotalSupply) => {
+            let state = cfg.state.read().await;
+            let number = requested_block(&request.params, 1, state.latest_number())?;
+            let units = state
+                .total_supply_at(number)
+                .map_err(RpcError::invalid_params)?;
+            encode_uint(units.saturating_mul(NATIVE_DECIMAL_FACTOR))
+        }
+        (PseudoContract::NativeToken, PrecompileCall::Decimals) => encode_uint(18),
+        (PseudoContract::Asset(asset), PrecompileCall::BalanceOf(holder)) => encode_uint(
+            u128::from(asset_registry_view(cfg)?.balance_of(&holder, &asset)),
+        ),
+        (PseudoContract::Asset(asset), PrecompileCall::TotalSupply) => encode_uint(u128::from(
+            asset_registry_view(cfg)?
+                .registry()
+                .supply()
+                .supply_of(&asset),
+        )),
+        (PseudoContract::Asset(_), PrecompileCall::Decimals) => encode_uint(0),
+        (PseudoContract::MigrationClaims, PrecompileCall::IsClaimed(claim_id)) => {
+            let state_path = match (&cfg.claim_state, &cfg.asset_registry) {
+                (Some(path), _) => path.clone(),
+                (None, Some(registry)) => resolve_state_path(registry, None),
+                (None, None) => {
+                    return Err(RpcError::unsupported(
+                        "migration claim state is not configured",
+                    ))
+                }
+            };
+            encode_bool(claim_applied(&state_path, &claim_id).map_err(RpcError::internal)?)
+        }
+        _ => {
+            return Err(RpcError::reverted(
+                "selector is not served by this contract",
+            ))
+        }
+    };
+    Ok(Value::String(encoded))
+}
+
+fn beacon_get(request: &JsonRpcRequest, cfg: &EvmRpcConfig) -> Result<Value, RpcError> {
+    let log_dir = cfg
+        .beacon_log_dir
+        .as_deref()
+        .ok_or_else(|| RpcError::unsupported("random beacon is not configured"))?;
+    let epoch = optional_u64(&request.params, 0)
+        .ok_or_else(|| RpcError::invalid_params("missing epoch parameter"))?;
+    // Callers may demand more signers than the node minimum, never fewer.
+    let threshold = optional_u64(&request.params, 1)
+        .map_or(cfg.beacon_threshold, |requested| requested as usize)
+        .max(cfg.beacon_threshold);
+    let value = load_beacon(log_dir, epoch, threshold).map_err(|err| match err {
+        BeaconError::Io(_) => RpcError::internal(err.to_string()),
+        _ => RpcError::invalid_params(err.to_string()),
+    })?;
+    serde_json::to_value(value).map_err(|err| RpcError::internal(err.to_string()))
+}
+
+fn asset_registry_path(cfg: &EvmRpcConfig) -> Result<&Path, RpcError> {
+    cfg.asset_registry
+        .as_deref()
+        .ok_or_else(|| RpcError::unsupported("asset registry is not configured"))
+}
+
+fn asset_registry_view(cfg: &EvmRpcConfig) -> Result<Arc<RegistryView>, RpcError> {
+    let path = asset_registry_path(cfg)?;
+    cfg.registry_cache.view(path).map_err(RpcError::internal)
+}
+
+fn secondary_asset(params: &Value, index: usize) -> Result<String, RpcError> {
+    let asset = required_string(params, index, "asset")?;
+    validate_asset_id(&asset).map_err(RpcError::invalid_params)?;
+    if asset == NATIVE_ASSET {
+        return Err(RpcError::invalid_params(
+            "native balances are served by eth_getBalance",
+        ));
+    }
+    Ok(asset)
+}
+
 async fn handle_fee_history(
     request: &JsonRpcRequest,
     cfg: &EvmRpcConfig,
@@ -423,22 +1120,37 @@ async fn get_transaction_by_hash(
     cfg: &EvmRpcConfig,
 ) -> Result<Value, RpcError> {
     let hash = required_string(&request.params, 0, "transaction hash")?;
-    let state = cfg.state.read().await;
-    Ok(state
-        .transaction(&hash)
-        .map(|(block, index, tx)| transaction_to_rpc(tx, block, index))
+    Ok(find_transaction(cfg, &hash)
+        .await?
+        .map(|(block, index)| {
+            transaction_to_rpc(&block.proposal.transactions[index], &block, index)
+        })
         .unwrap_or(Value::Null))
 }
 
+/// Looks a transaction up in the live state, then in the pruned-block archive.
+async fn find_transaction(
+    cfg: &EvmRpcConfig,
+    hash: &str,
+) -> Result<Option<(FinalizedNativeBlock, usize)>, RpcError> {
+    if let Some((block, index, _)) = cfg.state.read().await.transaction(hash) {
+        return Ok(Some((block.clone(), index)));
+    }
+    match &cfg.block_archive {
+        Some(dir) => find_archived_transaction(dir, hash).map_err(RpcError::internal),
+        None => Ok(None),
+    }
+}
+
 async fn get_transaction_receipt(
     request: &JsonRpcRequest,
     cfg: &EvmRpcConfig,
 ) -> Result<Value, RpcError> {
     let hash = required_string(&request.params, 0, "transaction hash")?;
-    let state = cfg.state.read().await;
-    Ok(state
-        .transaction(&hash)
-        .map(|(block, index, tx)| {
+    Ok(find_transaction(cfg, &hash)
+        .await?
+        .map(|(block, index)| {
+            let tx = &block.proposal.transactions[index];
             json!({
                 "transactionHash": tx.hash,
                 "transactionIndex": to_quantity_u64(index as u64),
@@ -461,6 +1173,102 @@ async fn get_transaction_receipt(
         .unwrap_or(Value::Null))
 }
 
+/// Account mutations of a stored transaction, with the registry key mapped to
+/// each address when an asset registry is configured.  The native chain
+/// charges no gas, so the fee is always zero and is reported against the zero
+/// address it would be burned to.
+async fn trace_transaction(
+    request: &JsonRpcRequest,
+    cfg: &EvmRpcConfig,
+) -> Result<Value, RpcError> {
+    let hash = required_string(&request.params, 0, "transaction hash")?;
+    let Some((block, index)) = find_transaction(cfg, &hash).await? else {
+        return Ok(Value::Null);
+    };
+    let changes = cfg
+        .state
+        .read()
+        .await
+        .trace_transaction(&block, index)
+        .map_err(RpcError::invalid_params)?;
+    let view = match cfg.asset_registry {
+        Some(_) => Some(asset_registry_view(cfg)?),
+        None => None,
+    };
+    let tx = &block.proposal.transactions[index];
+    let changes = changes
+        .iter()
+        .map(|change| {
+            json!({
+                "kind": match change.kind {
+                    NativeChangeKind::Debit => "debit",
+                    NativeChangeKind::Credit => "credit",
+                },
+                "address": change.address,
+                "registryKey": view
+                    .as_ref()
+                    .and_then(|view| view.addresses().key_of(&change.address)),
+                "amount": to_quantity_u64(change.amount),
+                "balanceBefore": to_quantity_u64(change.balance_before),
+                "balanceAfter": to_quantity_u64(change.balance_after),
+                "nonceBefore": to_quantity_u64(change.nonce_before),
+                "nonceAfter": to_quantity_u64(change.nonce_after),
+            })
+        })
+        .collect::<Vec<_>>();
+    let input_bytes = decode_hex_prefixed(&tx.input).map_or(0, |input| input.len());
+    Ok(json!({
+        "transactionHash": tx.hash,
+        "blockHash": block.proposal.hash,
+        "blockNumber": to_quantity_u64(block.proposal.number),
+        "transactionIndex": to_quantity_u64(index as u64),
+        "from": tx.from,
+        "to": tx.to,
+        "value": to_quantity_u64(tx.value_units),
+        "changes": changes,
+        "fee": {
+            "gasUsed": to_quantity_u64(NATIVE_GAS_LIMIT),
+            "gasPrice": to_quantity_u64(NATIVE_GAS_PRICE),
+            "amount": to_quantity_u64(NATIVE_GAS_LIMIT.saturating_mul(NATIVE_GAS_PRICE)),
+            "sink": zero_address(),
+        },
+        "input": {
+            "bytes": to_quantity_u64(input_bytes as u64),
+            "handler": input_bytes > 0 && cfg.tx_input_handlers.get(&tx.to).is_some(),
+        },
+    }))
+}
+
+fn pending_transactions(request: &JsonRpcRequest, cfg: &EvmRpcConfig) -> Result<Value, RpcError> {
+    let address = required_string(&request.params, 0, "address")?;
+    let address = normalize_evm_address(&address)
+        .ok_or_else(|| RpcError::invalid_params("invalid address format"))?;
+    let pool = cfg
+        .pending
+        .as_ref()
+        .ok_or_else(|| RpcError::unsupported("this node keeps no pending transaction pool"))?;
+    let entries = pool
+        .for_address(&address)
+        .into_iter()
+        .map(|entry| {
+            let tx = &entry.tx;
+            json!({
+                "hash": tx.hash,
+                "from": tx.from,
+                "to": tx.to,
+                "nonce": to_quantity_u64(tx.nonce),
+                "value": tx.value_wei,
+                "gas": to_quantity_u64(tx.gas_limit),
+                "maxFeePerGas": tx.max_fee_per_gas,
+                "maxPriorityFeePerGas": tx.max_priority_fee_per_gas,
+                "receivedAt": to_quantity_u64(entry.received_at),
+                "expiresAt": to_quantity_u64(pool.expires_at(entry.received_at)),
+            })
+        })
+        .collect();
+    Ok(Value::Array(entries))
+}
+
 async fn send_raw_transaction(
     request: &JsonRpcRequest,
     cfg: &EvmRpcConfig,
@@ -470,6 +1278,23 @@ async fn send_raw_transaction(
     let transaction =
         decode_eip1559_transaction(&raw, cfg.chain_id).map_err(RpcError::invalid_params)?;
     let hash = transaction.hash.clone();
+    let handled = if transaction.input == "0x" {
+        None
+    } else {
+        let handler = cfg
+            .tx_input_handlers
+            .get(&transaction.to)
+            .ok_or_else(|| RpcError::unsupported("contract calldata is not supported"))?;
+        let input = TxInput {
+            hash: hash.clone(),
+            from: transaction.from.clone(),
+            to: transaction.to.clone(),
+            value_units: transaction.value_units,
+            input: decode_hex_prefixed(&transaction.input).map_err(RpcError::invalid_params)?,
+        };
+        handler.validate(&input).map_err(RpcError::invalid_params)?;
+        Some((handler, input))
+    };
     let (sender, receiver) = oneshot::channel();
     cfg.command_sender
         .send(NativeChainCommand {
@@ -483,6 +1308,21 @@ async fn send_raw_transaction(
         .map_err(|_| RpcError::internal("native chain acceptance timed out"))?
         .map_err(|_| RpcError::internal("native chain acceptance channel closed"))?;
     result.map_err(RpcError::internal)?;
+    if let Some((handler, input)) = handled {
+        // The transfer is already pending, so a failed hook is reported in
+        // the log rather than to the wallet, which must not resubmit.
+        match handler.apply(&input) {
+            Ok(()) => println!(
+                "QSYS|mod=EVMRPC|evt=TX_INPUT|hash={hash}|to={}|bytes={}",
+                input.to,
+                input.input.len()
+            ),
+            Err(err) => eprintln!(
+                "QSYS|mod=EVMRPC|evt=TX_INPUT_FAILED|hash={hash}|to={}|error={err}",
+                input.to
+            ),
+        }
+    }
     Ok(Value::String(hash))
 }
 
@@ -563,6 +1403,15 @@ fn transaction_to_rpc(tx: &NativeTransaction, block: &FinalizedNativeBlock, inde
     })
 }
 
+fn call_target(params: &Value) -> Option<String> {
+    params
+        .as_array()
+        .and_then(|items| items.first())
+        .and_then(|call| call.get("to"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
 fn validate_native_call(params: &Value) -> Result<(), RpcError> {
     let request = params
         .as_array()
@@ -658,15 +1507,20 @@ fn json_rpc_error(id: Value, code: i64, message: impl Into<String>) -> Value {
     })
 }
 
-async fn write_json(stream: &mut TcpStream, status: &str, body: &Value) -> io::Result<()> {
+/// Writes a JSON response with `cors`, the header lines rendered by
+/// [`CorsPolicy::response_headers`] for the server's configured policy.
+pub(crate) async fn write_json(
+    stream: &mut TcpStream,
+    status: &str,
+    body: &Value,
+    cors: &str,
+) -> io::Result<()> {
     let encoded = serde_json::to_vec(body)
         .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
     let response = format!(
         "HTTP/1.1 {status}\r\n\
          Content-Type: application/json\r\n\
-         Access-Control-Allow-Origin: *\r\n\
-         Access-Control-Allow-Methods: POST, OPTIONS, GET\r\n\
-         Access-Control-Allow-Headers: content-type\r\n\
+         {cors}\
          Cache-Control: no-store\r\n\
          X-Content-Type-Options: nosniff\r\n\
          Content-Length: {}\r\n\
@@ -678,31 +1532,53 @@ async fn write_json(stream: &mut TcpStream, status: &str, body: &Value) -> io::R
     stream.shutdown().await
 }
 
-async fn write_no_content(stream: &mut TcpStream) -> io::Result<()> {
-    stream
-        .write_all(
-            b"HTTP/1.1 204 No Content\r\n\
-              Access-Control-Allow-Origin: *\r\n\
-              Cache-Control: no-store\r\n\
-              Content-Length: 0\r\n\
-              Connection: close\r\n\r\n",
-        )
-        .await?;
+async fn write_metrics(stream: &mut TcpStream, body: &str) -> io::Result<()> {
+    let response = format!(
+        "HTTP/1.1 200 OK\r\n\
+         Content-Type: text/plain; version=0.0.4\r\n\
+         Cache-Control: no-store\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\r\n",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.write_all(body.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+async fn write_service_unavailable(stream: &mut TcpStream) -> io::Result<()> {
+    let body = br#"{"error":"server saturated, retry later"}"#;
+    let response = format!(
+        "HTTP/1.1 503 Service Unavailable\r\n\
+         Content-Type: application/json\r\n\
+         Retry-After: 1\r\n\
+         Cache-Control: no-store\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\r\n",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.write_all(body).await?;
+    stream.shutdown().await
+}
+
+async fn write_no_content(stream: &mut TcpStream, cors: &str) -> io::Result<()> {
+    stream.write_all(&preflight_response(cors)).await?;
     stream.shutdown().await
 }
 
-fn preflight_response() -> Vec<u8> {
-    b"HTTP/1.1 204 No Content\r\n\
-      Access-Control-Allow-Origin: *\r\n\
-      Access-Control-Allow-Methods: POST, OPTIONS, GET\r\n\
-      Access-Control-Allow-Headers: content-type\r\n\
-      Cache-Control: no-store\r\n\
-      Content-Length: 0\r\n\
-      Connection: close\r\n\r\n"
-        .to_vec()
+fn preflight_response(cors: &str) -> Vec<u8> {
+    format!(
+        "HTTP/1.1 204 No Content\r\n\
+         {cors}\
+         Cache-Control: no-store\r\n\
+         Content-Length: 0\r\n\
+         Connection: close\r\n\r\n"
+    )
+    .into_bytes()
 }
 
-async fn read_http_request(
+pub(crate) async fn read_http_request(
     stream: &mut TcpStream,
     max_header_bytes: usize,
     max_body_bytes: usize,
@@ -783,16 +1659,66 @@ async fn read_http_request(
 mod tests {
     use super::*;
     use crate::net::{
-        encode_public_key_base64, load_or_derive_keypair,
+        encode_public_key_base64, encode_signature_base64, load_or_derive_keypair,
         native_chain::{
-            normalize_evm_address, signed_test_transfer, NativeChainMessage,
-            NativeChainMessagePayload, NativeChainRuntime, NativeChainState,
+            normalize_evm_address, signed_test_call, signed_test_transfer, NativeAccount,
+            NativeChainMessage, NativeChainMessagePayload, NativeChainRuntime, NativeChainState,
         },
-        Ed25519KeySource, StakeRegistry,
+        sign_payload, Ed25519KeySource, StakeRegistry, SupplyCause, WallClock,
     };
-    use std::{fs, net::TcpListener as StdTcpListener, sync::Arc};
+    use std::{fs, net::TcpListener as StdTcpListener};
     use tokio::sync::RwLock;
 
+    #[tokio::test]
+    async fn saturated_limiter_answers_503() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let limiter = ConnectionLimiter::new(1).with_saturation_wait(Duration::from_millis(20));
+        let shared = limiter.clone();
+        let server = tokio::spawn(async move {
+            let (mut first, _) = listener.accept().await.unwrap();
+            let held = shared.admit(&mut first).await;
+            assert!(held.is_some());
+            let (mut second, _) = listener.accept().await.unwrap();
+            assert!(shared.admit(&mut second).await.is_none());
+            drop(held);
+            let (mut third, _) = listener.accept().await.unwrap();
+            assert!(shared.admit(&mut third).await.is_some());
+        });
+        let _first = TcpStream::connect(addr).await.unwrap();
+        let mut second = TcpStream::connect(addr).await.unwrap();
+        let mut response = String::new();
+        second.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 503 Service Unavailable"));
+        assert!(response.contains("Retry-After: 1"));
+        let _third = TcpStream::connect(addr).await.unwrap();
+        server.await.unwrap();
+        assert_eq!(limiter.available(), 1);
+        assert_eq!(limiter.max_connections(), 1);
+    }
+
+    #[test]
+    fn cors_policy_echoes_only_listed_origins() {
+        let open = CorsPolicy::default().response_headers(Some("https://evil.example"));
+        assert!(open.contains("Access-Control-Allow-Origin: *\r\n"));
+        assert!(!open.contains("Vary"));
+
+        let policy = CorsPolicy {
+            allowed_origins: vec!["https://wallet.example".to_string()],
+            allowed_headers: vec!["content-type".to_string(), "authorization".to_string()],
+            serve_preflight: false,
+        };
+        let listed = policy.response_headers(Some("https://wallet.example"));
+        assert!(listed.contains("Access-Control-Allow-Origin: https://wallet.example\r\n"));
+        assert!(listed.contains("Access-Control-Allow-Headers: content-type, authorization\r\n"));
+        assert!(listed.contains("Vary: Origin\r\n"));
+        let other = policy.response_headers(Some("https://evil.example"));
+        assert_eq!(other, "Vary: Origin\r\n");
+        assert_eq!(policy.allowed_origin(None), None);
+        let preflight = String::from_utf8(preflight_response(&other)).unwrap();
+        assert!(!preflight.contains("Access-Control-Allow-Origin"));
+    }
+
     #[test]
     fn block_tags_are_strict() {
         assert_eq!(parse_block_tag("latest", 7).unwrap(), 7);
@@ -800,6 +1726,179 @@ mod tests {
         assert!(parse_block_tag("42", 7).is_err());
     }
 
+    #[tokio::test]
+    async fn calldata_reaches_registered_input_handler() {
+        #[derive(Default)]
+        struct Recorder(std::sync::Mutex<Vec<TxInput>>);
+        impl TxInputHandler for Recorder {
+            fn validate(&self, input: &TxInput) -> Result<(), String> {
+                if input.input.starts_with(b"stmt:") {
+                    Ok(())
+                } else {
+                    Err("expected a statement".to_string())
+                }
+            }
+            fn apply(&self, input: &TxInput) -> Result<(), String> {
+                self.0.lock().unwrap().push(input.clone());
+                Ok(())
+            }
+        }
+
+        let root = std::env::temp_dir().join(format!(
+            "powerhouse_rpc_tx_input_{}_{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        fs::create_dir_all(&root).unwrap();
+        let state = NativeChainState::load_or_initialize(
+            &root.join("native_chain_state.json"),
+            177155,
+            None,
+            vec!["validator".to_string()],
+            1,
+            0,
+        )
+        .unwrap();
+        let (sender, mut receiver) = mpsc::channel::<NativeChainCommand>(4);
+        tokio::spawn(async move {
+            while let Some(command) = receiver.recv().await {
+                let _ = command.response.send(Ok(command.transaction.hash.clone()));
+            }
+        });
+        let mut cfg = EvmRpcConfig::new(
+            "127.0.0.1:0".parse().unwrap(),
+            177155,
+            Arc::new(RwLock::new(state)),
+            sender,
+        );
+        let target = [0x42u8; 20];
+        let recorder = Arc::new(Recorder::default());
+        cfg.tx_input_handlers
+            .register(&format!("0x{}", hex::encode(target)), recorder.clone())
+            .unwrap();
+        let send = |tx: NativeTransaction| json!({"jsonrpc":"2.0","id":1,"method":"eth_sendRawTransaction","params":[tx.raw]});
+
+        let accepted = signed_test_call([7u8; 32], 177155, 0, target, 0, b"stmt:hello");
+        let response = process_request(send(accepted.clone()), &cfg).await.unwrap();
+        assert_eq!(response["result"], accepted.hash);
+        let applied = recorder.0.lock().unwrap().clone();
+        assert_eq!(applied.len(), 1);
+        assert_eq!(applied[0].input, b"stmt:hello");
+        assert_eq!(applied[0].from, accepted.from);
+
+        let invalid = signed_test_call([7u8; 32], 177155, 1, target, 0, b"other");
+        let response = process_request(send(invalid), &cfg).await.unwrap();
+        assert_eq!(response["error"]["code"], -32602);
+        let unrouted = signed_test_call([7u8; 32], 177155, 1, [0x43u8; 20], 0, b"stmt:x");
+        let response = process_request(send(unrouted), &cfg).await.unwrap();
+        assert_eq!(response["error"]["code"], -32004);
+        assert_eq!(recorder.0.lock().unwrap().len(), 1);
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[tokio::test]
+    async fn estimate_gas_dry_runs_transfers_and_prices_handler_calldata() {
+        struct Statements;
+        impl TxInputHandler for Statements {
+            fn validate(&self, input: &TxInput) -> Result<(), String> {
+                if input.input.starts_with(b"stmt:") {
+                    Ok(())
+                } else {
+                    Err("expected a statement".to_string())
+                }
+            }
+            fn apply(&self, _input: &TxInput) -> Result<(), String> {
+                Ok(())
+            }
+            fn gas_cost(&self, _input: &TxInput) -> u64 {
+                5_000
+            }
+        }
+
+        let root = std::env::temp_dir().join(format!(
+            "powerhouse_rpc_estimate_{}_{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        fs::create_dir_all(&root).unwrap();
+        let mut state = NativeChainState::load_or_initialize(
+            &root.join("native_chain_state.json"),
+            177155,
+            None,
+            vec!["validator".to_string()],
+            1,
+            0,
+        )
+        .unwrap();
+        let sender = "0x4a62316623ad457f02cdc5d997ded67a383ec569";
+        state.accounts.insert(
+            sender.to_string(),
+            NativeAccount {
+                balance: 5,
+                nonce: 0,
+            },
+        );
+        let (command_sender, _commands) = mpsc::channel(1);
+        let mut cfg = EvmRpcConfig::new(
+            "127.0.0.1:0".parse().unwrap(),
+            177155,
+            Arc::new(RwLock::new(state)),
+            command_sender,
+        );
+        let target = format!("0x{}", "42".repeat(20));
+        cfg.tx_input_handlers
+            .register(&target, Arc::new(Statements))
+            .unwrap();
+        let estimate = |to: &str, value_units: u128, data: &[u8]| {
+            json!({"jsonrpc":"2.0","id":1,"method":"eth_estimateGas","params":[{
+                "from": sender,
+                "to": to,
+                "value": to_quantity_u128(value_units * NATIVE_DECIMAL_FACTOR),
+                "data": format!("0x{}", hex::encode(data)),
+            }]})
+        };
+        let recipient = format!("0x{}", "09".repeat(20));
+
+        let plain = process_request(estimate(&recipient, 5, b""), &cfg)
+            .await
+            .unwrap();
+        assert_eq!(plain["result"], "0x5208");
+        let overdraft = process_request(estimate(&recipient, 6, b""), &cfg)
+            .await
+            .unwrap();
+        assert_eq!(overdraft["error"]["code"], 3);
+        assert!(overdraft["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("insufficient native balance"));
+
+        // (21000 intrinsic + 10 non-zero bytes * 16 + 5000 handler) * 120%.
+        let call = process_request(estimate(&target, 1, b"stmt:hello"), &cfg)
+            .await
+            .unwrap();
+        assert_eq!(call["result"], to_quantity_u64(31_392));
+        cfg.gas_margin_percent = 0;
+        let call = process_request(estimate(&target, 1, b"stmt:hello"), &cfg)
+            .await
+            .unwrap();
+        assert_eq!(call["result"], to_quantity_u64(26_160));
+        let rejected = process_request(estimate(&target, 1, b"other"), &cfg)
+            .await
+            .unwrap();
+        assert_eq!(rejected["error"]["code"], 3);
+        let unrouted = process_request(estimate(&recipient, 1, b"stmt:x"), &cfg)
+            .await
+            .unwrap();
+        assert_eq!(unrouted["error"]["code"], -32004);
+        fs::remove_dir_all(root).ok();
+    }
+
     #[test]
     fn native_call_rejects_calldata() {
         let params = json!([{"to":"0x0000000000000000000000000000000000000001","data":"0x12"}]);
@@ -814,6 +1913,196 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn eth_call_serves_pseudo_contract_views() {
+        let root = std::env::temp_dir().join(format!(
+            "powerhouse_rpc_eth_call_{}_{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        fs::create_dir_all(&root).unwrap();
+        let holder =
+            load_or_derive_keypair(&Ed25519KeySource::Seed("rpc-call-holder".into())).unwrap();
+        let holder_pk = encode_public_key_base64(&holder.verifying);
+        let registry_path = root.join("stake_registry.json");
+        let mut registry = StakeRegistry::default();
+        registry
+            .mint_asset(&holder_pk, "usdx", 42, SupplyCause::Funding)
+            .unwrap();
+        registry.save(&registry_path).unwrap();
+        fs::write(
+            resolve_state_path(&registry_path, None),
+            json!({"schema":"mfenx.powerhouse.migration-apply-state.v1","updated_at_ms":0,"applied_claim_ids":["c1"]})
+                .to_string(),
+        )
+        .unwrap();
+        let state = NativeChainState::load_or_initialize(
+            &root.join("native_chain_state.json"),
+            177155,
+            None,
+            vec![holder_pk.clone()],
+            1,
+            0,
+        )
+        .unwrap();
+        let (sender, _receiver) = mpsc::channel(1);
+        let mut cfg = EvmRpcConfig::new(
+            "127.0.0.1:0".parse().unwrap(),
+            177155,
+            Arc::new(RwLock::new(state)),
+            sender,
+        );
+        cfg.asset_registry = Some(registry_path);
+        let eth_call = |to: &str, data: String| json!({"jsonrpc":"2.0","id":1,"method":"eth_call","params":[{"to":to,"data":data},"latest"]});
+        let usdx = asset_token_address("usdx");
+        let holder_address = crate::net::registry_key_address(&holder_pk).unwrap();
+        let balance_call = format!("0x70a08231{:0>64}", holder_address.trim_start_matches("0x"));
+        let balance = process_request(eth_call(&usdx, balance_call), &cfg)
+            .await
+            .unwrap();
+        assert_eq!(balance["result"], encode_uint(42));
+        let decimals = process_request(eth_call(NATIVE_TOKEN_ADDRESS, "0x313ce567".into()), &cfg)
+            .await
+            .unwrap();
+        assert_eq!(decimals["result"], encode_uint(18));
+
+        let is_claimed = |claim_id: &str| {
+            format!(
+                "0x{}{}{}{:0<64}",
+                hex::encode(crate::net::precompile::selector("isClaimed(string)")),
+                &encode_uint(32)[2..],
+                &encode_uint(claim_id.len() as u128)[2..],
+                hex::encode(claim_id)
+            )
+        };
+        let claimed = process_request(eth_call(MIGRATION_CLAIMS_ADDRESS, is_claimed("c1")), &cfg)
+            .await
+            .unwrap();
+        assert_eq!(claimed["result"], encode_bool(true));
+        let unclaimed = process_request(eth_call(MIGRATION_CLAIMS_ADDRESS, is_claimed("c2")), &cfg)
+            .await
+            .unwrap();
+        assert_eq!(unclaimed["result"], encode_bool(false));
+        let reverted = process_request(eth_call(&usdx, "0xdeadbeef".into()), &cfg)
+            .await
+            .unwrap();
+        assert_eq!(reverted["error"]["code"], 3);
+
+        let code = process_request(
+            json!({"jsonrpc":"2.0","id":1,"method":"eth_getCode","params":[usdx,"latest"]}),
+            &cfg,
+        )
+        .await
+        .unwrap();
+        assert_eq!(code["result"], PSEUDO_CONTRACT_CODE);
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[tokio::test]
+    async fn asset_methods_read_and_transfer_registry_balances() {
+        let root = std::env::temp_dir().join(format!(
+            "powerhouse_rpc_assets_{}_{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        fs::create_dir_all(&root).unwrap();
+        let holder =
+            load_or_derive_keypair(&Ed25519KeySource::Seed("rpc-asset-holder".to_string()))
+                .unwrap();
+        let holder_pk = encode_public_key_base64(&holder.verifying);
+        let registry_path = root.join("stake_registry.json");
+        let mut registry = StakeRegistry::default();
+        registry
+            .mint_asset(&holder_pk, "usdx", 50, SupplyCause::Funding)
+            .unwrap();
+        registry.save(&registry_path).unwrap();
+
+        let state = NativeChainState::load_or_initialize(
+            &root.join("native_chain_state.json"),
+            177155,
+            None,
+            vec![holder_pk.clone()],
+            1,
+            0,
+        )
+        .unwrap();
+        let (sender, _receiver) = mpsc::channel(1);
+        let mut cfg = EvmRpcConfig::new(
+            "127.0.0.1:0".parse().unwrap(),
+            177155,
+            Arc::new(RwLock::new(state)),
+            sender,
+        );
+        let call = |method: &str, params: Value| json!({"jsonrpc":"2.0","id":1,"method":method,"params":params});
+        let disabled = process_request(call("asset_totalSupply", json!(["usdx"])), &cfg)
+            .await
+            .unwrap();
+        assert_eq!(disabled["error"]["code"], -32004);
+        cfg.asset_registry = Some(registry_path.clone());
+
+        let mut transfer = AssetTransfer {
+            asset: "usdx".into(),
+            from: holder_pk.clone(),
+            to: "recipient".into(),
+            amount: 20,
+            nonce: 0,
+            signature: String::new(),
+        };
+        transfer.signature =
+            encode_signature_base64(&sign_payload(&holder.signing, &transfer.signing_bytes()));
+        let sent = process_request(call("asset_transfer", json!([transfer])), &cfg)
+            .await
+            .unwrap();
+        assert_eq!(sent["result"], true);
+        let replayed = process_request(call("asset_transfer", json!([transfer])), &cfg)
+            .await
+            .unwrap();
+        assert_eq!(replayed["error"]["code"], -32602);
+
+        let balance = process_request(call("asset_balanceOf", json!(["usdx", "recipient"])), &cfg)
+            .await
+            .unwrap();
+        assert_eq!(balance["result"], "0x14");
+        let nonce = process_request(call("asset_nonceOf", json!([holder_pk])), &cfg)
+            .await
+            .unwrap();
+        assert_eq!(nonce["result"], "0x1");
+        let native = process_request(
+            call("asset_balanceOf", json!([NATIVE_ASSET, holder_pk])),
+            &cfg,
+        )
+        .await
+        .unwrap();
+        assert_eq!(native["error"]["code"], -32602);
+
+        let address = crate::net::address_index::registry_key_address(&holder_pk).unwrap();
+        let by_address = process_request(call("asset_balanceOf", json!(["usdx", address])), &cfg)
+            .await
+            .unwrap();
+        assert_eq!(by_address["result"], "0x1e");
+        let cached = cfg.registry_cache.view(&registry_path).unwrap();
+        assert!(Arc::ptr_eq(
+            &cached,
+            &cfg.registry_cache.view(&registry_path).unwrap()
+        ));
+        let mut external = StakeRegistry::load(&registry_path).unwrap();
+        external
+            .mint_asset("recipient", "usdx", 5, SupplyCause::Funding)
+            .unwrap();
+        external.save(&registry_path).unwrap();
+        let refreshed = process_request(call("asset_totalSupply", json!(["usdx"])), &cfg)
+            .await
+            .unwrap();
+        assert_eq!(refreshed["result"], "0x37");
+        let _ = fs::remove_dir_all(root);
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
     async fn raw_transaction_reaches_finalized_receipt_over_http() {
         let root = std::env::temp_dir().join(format!(
@@ -829,7 +2118,9 @@ mod tests {
         let transaction = signed_test_transfer([7u8; 32], chain_id, 0, [9u8; 20], 2);
         let registry_path = root.join("stake_registry.json");
         let mut registry = StakeRegistry::default();
-        registry.fund_balance(&transaction.from, 5);
+        registry
+            .mint(&transaction.from, 5, SupplyCause::Funding)
+            .unwrap();
         registry.save(&registry_path).unwrap();
 
         let validator =
@@ -842,6 +2133,7 @@ mod tests {
             Some(&registry_path),
             validators.clone(),
             1,
+            0,
         )
         .unwrap();
         let shared = Arc::new(RwLock::new(state));
@@ -851,6 +2143,7 @@ mod tests {
             validators,
             1,
             &validator.signing,
+            WallClock::shared(),
         )
         .await
         .unwrap();
@@ -0,0 +1,332 @@
+#![cfg(feature = "net")]
+
+//! Priority tiers and load shedding for the wallet JSON-RPC.
+//!
+//! Every method belongs to an [`RpcTier`]: writes, cheap reads such as balance
+//! and nonce lookups, and expensive reads that walk blocks, run pseudo-contract
+//! calls, or read checkpoints.  Each tier has its own concurrency limit and
+//! bounded wait queue, so a flood of one kind of call cannot starve the others.
+//! A request that finds its queue full, or that waits longer than the request
+//! timeout, is shed and answered with HTTP 429.  Lower tiers are shed first: a
+//! request that cannot start at once is shed while any higher-priority tier
+//! has requests waiting.
+
+use serde_json::Value;
+use std::{
+    fmt::Write as _,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::{
+    sync::{OwnedSemaphorePermit, Semaphore},
+    time,
+};
+
+/// Method class, in priority order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RpcTier {
+    /// `eth_sendRawTransaction` and `asset_transfer`.
+    Write = 0,
+    /// Lookups answered from in-memory state: balances, nonces, receipts.
+    Read = 1,
+    /// Full blocks, fee history, logs, `eth_call`, `eth_estimateGas`, and
+    /// `beacon_get`.
+    Expensive = 2,
+}
+
+impl RpcTier {
+    /// Every tier, highest priority first.
+    pub const ALL: [Self; 3] = [Self::Write, Self::Read, Self::Expensive];
+
+    /// Metric label and command-line name.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Write => "write",
+            Self::Read => "read",
+            Self::Expensive => "expensive",
+        }
+    }
+
+    /// Tier of a JSON-RPC call.
+    pub fn of(method: &str, params: &Value) -> Self {
+        match method {
+            "eth_sendRawTransaction" | "asset_transfer" => Self::Write,
+            "eth_call"
+            | "eth_estimateGas"
+            | "eth_feeHistory"
+            | "eth_getLogs"
+            | "beacon_get"
+            | "ph_traceTransaction" => Self::Expensive,
+            "eth_getBlockByNumber" | "eth_getBlockByHash"
+                if params.get(1).and_then(Value::as_bool) == Some(true) =>
+            {
+                Self::Expensive
+            }
+            _ => Self::Read,
+        }
+    }
+}
+
+/// Concurrency and queue bounds of one tier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TierLimit {
+    /// Requests served at once.
+    pub concurrency: usize,
+    /// Requests allowed to wait for a slot; later ones are shed.
+    pub queue_depth: usize,
+}
+
+/// Limits of every tier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RpcTierLimits {
+    /// Transaction submissions.
+    pub write: TierLimit,
+    /// Cheap reads.
+    pub read: TierLimit,
+    /// Expensive reads.
+    pub expensive: TierLimit,
+}
+
+impl Default for RpcTierLimits {
+    fn default() -> Self {
+        Self {
+            write: TierLimit {
+                concurrency: 32,
+                queue_depth: 256,
+            },
+            read: TierLimit {
+                concurrency: 128,
+                queue_depth: 512,
+            },
+            expensive: TierLimit {
+                concurrency: 8,
+                queue_depth: 16,
+            },
+        }
+    }
+}
+
+impl RpcTierLimits {
+    /// Limit of `tier`.
+    pub fn get(&self, tier: RpcTier) -> TierLimit {
+        match tier {
+            RpcTier::Write => self.write,
+            RpcTier::Read => self.read,
+            RpcTier::Expensive => self.expensive,
+        }
+    }
+
+    /// Applies a `<tier>=<concurrency>:<queue_depth>` override.
+    pub fn apply_spec(&mut self, spec: &str) -> Result<(), String> {
+        let err = || format!("expected <write|read|expensive>=<concurrency>:<queue>, got {spec}");
+        let (name, limit) = spec.split_once('=').ok_or_else(err)?;
+        let (concurrency, queue_depth) = limit.split_once(':').ok_or_else(err)?;
+        let limit = TierLimit {
+            concurrency: concurrency.parse().map_err(|_| err())?,
+            queue_depth: queue_depth.parse().map_err(|_| err())?,
+        };
+        if limit.concurrency == 0 {
+            return Err(format!("{name} concurrency must be at least 1"));
+        }
+        let slot = match name {
+            "write" => &mut self.write,
+            "read" => &mut self.read,
+            "expensive" => &mut self.expensive,
+            _ => return Err(err()),
+        };
+        *slot = limit;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct TierState {
+    limit: TierLimit,
+    permits: Arc<Semaphore>,
+    queued: AtomicUsize,
+    shed: AtomicU64,
+}
+
+/// Decrements a tier's queue length when a waiter leaves, however it leaves.
+struct QueueSlot<'a>(&'a AtomicUsize);
+
+impl Drop for QueueSlot<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Shared admission control for the RPC tiers.
+///
+/// Clones share the same slots and counters.
+#[derive(Debug, Clone)]
+pub struct RpcTiers {
+    tiers: Arc<[TierState; 3]>,
+}
+
+impl Default for RpcTiers {
+    fn default() -> Self {
+        Self::new(RpcTierLimits::default())
+    }
+}
+
+impl RpcTiers {
+    /// Admission control enforcing `limits`.
+    pub fn new(limits: RpcTierLimits) -> Self {
+        let state = |tier| {
+            let limit = limits.get(tier);
+            TierState {
+                limit,
+                permits: Arc::new(Semaphore::new(limit.concurrency)),
+                queued: AtomicUsize::new(0),
+                shed: AtomicU64::new(0),
+            }
+        };
+        Self {
+            tiers: Arc::new(RpcTier::ALL.map(state)),
+        }
+    }
+
+    fn state(&self, tier: RpcTier) -> &TierState {
+        &self.tiers[tier as usize]
+    }
+
+    /// Waits up to `max_wait` for a slot in `tier`; `None` means the request
+    /// was shed.
+    pub async fn admit(&self, tier: RpcTier, max_wait: Duration) -> Option<OwnedSemaphorePermit> {
+        let state = self.state(tier);
+        if let Ok(permit) = state.permits.clone().try_acquire_owned() {
+            return Some(permit);
+        }
+        let higher_waiting = RpcTier::ALL[..tier as usize]
+            .iter()
+            .any(|&higher| self.queued(higher) > 0);
+        if higher_waiting || state.queued.fetch_add(1, Ordering::SeqCst) >= state.limit.queue_depth
+        {
+            if !higher_waiting {
+                state.queued.fetch_sub(1, Ordering::SeqCst);
+            }
+            state.shed.fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+        let _slot = QueueSlot(&state.queued);
+        match time::timeout(max_wait, state.permits.clone().acquire_owned()).await {
+            Ok(Ok(permit)) => Some(permit),
+            _ => {
+                state.shed.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Requests of `tier` being served.
+    pub fn in_flight(&self, tier: RpcTier) -> usize {
+        let state = self.state(tier);
+        state
+            .limit
+            .concurrency
+            .saturating_sub(state.permits.available_permits())
+    }
+
+    /// Requests of `tier` waiting for a slot.
+    pub fn queued(&self, tier: RpcTier) -> usize {
+        self.state(tier).queued.load(Ordering::SeqCst)
+    }
+
+    /// Requests of `tier` shed so far.
+    pub fn shed_total(&self, tier: RpcTier) -> u64 {
+        self.state(tier).shed.load(Ordering::Relaxed)
+    }
+
+    /// Prometheus exposition of per-tier limits, load, and shedding.
+    pub fn render_metrics(&self) -> String {
+        let mut out = String::new();
+        let mut family = |name: &str, kind: &str, value: &dyn Fn(RpcTier) -> String| {
+            let _ = writeln!(out, "# TYPE {name} {kind}");
+            for tier in RpcTier::ALL {
+                let _ = writeln!(out, "{name}{{tier=\"{}\"}} {}", tier.label(), value(tier));
+            }
+        };
+        family("powerhouse_rpc_tier_capacity", "gauge", &|tier| {
+            self.state(tier).limit.concurrency.to_string()
+        });
+        family("powerhouse_rpc_tier_queue_depth", "gauge", &|tier| {
+            self.state(tier).limit.queue_depth.to_string()
+        });
+        family("powerhouse_rpc_tier_in_flight", "gauge", &|tier| {
+            self.in_flight(tier).to_string()
+        });
+        family("powerhouse_rpc_tier_queued", "gauge", &|tier| {
+            self.queued(tier).to_string()
+        });
+        family("powerhouse_rpc_tier_saturation", "gauge", &|tier| {
+            let saturation =
+                self.in_flight(tier) as f64 / self.state(tier).limit.concurrency.max(1) as f64;
+            format!("{saturation:.3}")
+        });
+        family("powerhouse_rpc_tier_shed_total", "counter", &|tier| {
+            self.shed_total(tier).to_string()
+        });
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn full_tiers_shed_lowest_priority_first() {
+        let mut limits = RpcTierLimits::default();
+        limits.apply_spec("write=1:1").unwrap();
+        limits.apply_spec("expensive=1:4").unwrap();
+        assert!(limits.apply_spec("bulk=1:1").is_err());
+        let tiers = RpcTiers::new(limits);
+        assert_eq!(
+            RpcTier::of("eth_getBlockByNumber", &json!(["latest", true])),
+            RpcTier::Expensive
+        );
+        assert_eq!(
+            RpcTier::of("eth_getBlockByNumber", &json!(["latest", false])),
+            RpcTier::Read
+        );
+        assert_eq!(
+            RpcTier::of("eth_estimateGas", &json!([{}])),
+            RpcTier::Expensive
+        );
+        assert_eq!(RpcTier::of("eth_getBalance", &json!([])), RpcTier::Read);
+
+        let wait = Duration::from_secs(5);
+        let write = tiers.admit(RpcTier::Write, wait).await.unwrap();
+        let expensive = tiers.admit(RpcTier::Expensive, wait).await.unwrap();
+        let queued_write = tokio::spawn({
+            let tiers = tiers.clone();
+            async move { tiers.admit(RpcTier::Write, wait).await.is_some() }
+        });
+        while tiers.queued(RpcTier::Write) == 0 {
+            tokio::task::yield_now().await;
+        }
+        assert!(tiers.admit(RpcTier::Write, wait).await.is_none());
+        assert!(tiers.admit(RpcTier::Expensive, wait).await.is_none());
+        assert!(tiers.admit(RpcTier::Read, wait).await.is_some());
+        assert!(tiers
+            .render_metrics()
+            .contains("powerhouse_rpc_tier_saturation{tier=\"expensive\"} 1.000"));
+
+        drop(write);
+        assert!(queued_write.await.unwrap());
+        assert!(tiers
+            .admit(RpcTier::Expensive, Duration::from_millis(10))
+            .await
+            .is_none());
+        drop(expensive);
+        assert!(tiers.admit(RpcTier::Expensive, wait).await.is_some());
+        assert_eq!(tiers.shed_total(RpcTier::Write), 1);
+        assert_eq!(tiers.shed_total(RpcTier::Expensive), 2);
+        assert_eq!(tiers.queued(RpcTier::Write), 0);
+    }
+}
@@ -1,9 +1,11 @@
 #![cfg(feature = "net")]
 
 use crate::{
-    compute_fold_digest, data::digest_from_hex, data::digest_to_hex,
-    julian::JULIAN_GENESIS_STATEMENT, AnchorMetadata, EntryAnchor, LedgerAnchor,
+    compute_fold_digest, data::digest_from_hex, data::digest_to_hex, genesis::active_genesis,
+    julian::julian_genesis_statement, julian_genesis_hash, AnchorMetadata, EntryAnchor,
+    EntrySubmission, LedgerAnchor,
 };
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use serde::{Deserialize, Serialize};
 use std::{env, error::Error, fmt};
 
@@ -13,10 +15,37 @@ pub const SCHEMA_ANCHOR: &str = "mfenx.powerhouse.anchor.v1";
 pub const SCHEMA_ENVELOPE: &str = "mfenx.powerhouse.envelope.v1";
 /// Schema identifier used for anchor vote messages.
 pub const SCHEMA_VOTE: &str = "mfenx.powerhouse.vote.v1";
-/// Current envelope schema major version.
-pub const ENVELOPE_SCHEMA_VERSION: u32 = 1;
-/// Network identifier used across all JULIAN Protocol deployments for MFENX Power-House.
-pub const NETWORK_ID: &str = "MFENX-POWERHOUSE";
+/// Current envelope schema major version (2 adds the envelope `network` field).
+pub const ENVELOPE_SCHEMA_VERSION: u32 = 2;
+/// Oldest envelope schema version still accepted (and emitted to legacy peers).
+pub const MIN_ENVELOPE_SCHEMA_VERSION: u32 = 1;
+/// Newest envelope schema version this node parses.
+pub const MAX_ENVELOPE_SCHEMA_VERSION: u32 = ENVELOPE_SCHEMA_VERSION;
+const ENVELOPE_VERSION_TOKEN: &str = "envelope/";
+/// Network identifier of the default MFENX Power-House network.
+pub const NETWORK_ID: &str = crate::genesis::DEFAULT_NETWORK_ID;
+
+/// Network identifier of the active genesis; anchors, envelopes and votes from
+/// any other network are rejected.
+pub fn network_id() -> &'static str {
+    &active_genesis().network_id
+}
+
+/// Scopes a gossip topic or DHT key prefix to the active network.
+///
+/// The default network keeps its historical names; any other network appends
+/// `/net/<network_id>`, so peers of different networks never share a topic.
+pub fn network_topic(base: &str) -> String {
+    scoped_topic(base, network_id())
+}
+
+fn scoped_topic(base: &str, network: &str) -> String {
+    if network == crate::genesis::DEFAULT_NETWORK_ID {
+        base.to_string()
+    } else {
+        format!("{base}/net/{network}")
+    }
+}
 
 /// Machine-readable representation of a single anchor entry.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -28,6 +57,44 @@ pub struct AnchorEntryJson {
     /// Optional Merkle root over the hashes (hex encoded).
     #[serde(default)]
     pub merkle_root: Option<String>,
+    /// Optional submitter attribution.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub submission: Option<EntrySubmissionJson>,
+}
+
+/// Machine-readable submitter attribution for an anchor entry.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EntrySubmissionJson {
+    /// Base64-encoded submitter public key.
+    pub public_key: String,
+    /// Base64-encoded signature over the statement digest.
+    pub signature: String,
+    /// Submission time in milliseconds since the Unix epoch.
+    pub submitted_ms: u64,
+}
+
+impl EntrySubmissionJson {
+    /// Encodes a ledger submission record.
+    pub fn from_submission(submission: &EntrySubmission) -> Self {
+        Self {
+            public_key: BASE64.encode(&submission.public_key),
+            signature: BASE64.encode(&submission.signature),
+            submitted_ms: submission.submitted_ms,
+        }
+    }
+
+    /// Decodes into a ledger submission record.
+    pub fn into_submission(self) -> Result<EntrySubmission, String> {
+        Ok(EntrySubmission {
+            public_key: BASE64
+                .decode(self.public_key.as_bytes())
+                .map_err(|err| format!("invalid submitter key: {err}"))?,
+            signature: BASE64
+                .decode(self.signature.as_bytes())
+                .map_err(|err| format!("invalid submitter signature: {err}"))?,
+            submitted_ms: self.submitted_ms,
+        })
+    }
 }
 
 /// Machine-readable representation of a JULIAN ledger anchor.
@@ -62,6 +129,9 @@ pub struct AnchorJson {
     /// Optional evidence root (hex) for slashing records.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub evidence_root: Option<String>,
+    /// Sparse Merkle root (hex) of the emitting node's stake registry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry_root: Option<String>,
 }
 
 /// Data-availability commitment describing blob binding.
@@ -97,9 +167,14 @@ pub struct DaCommitmentJson {
 pub struct AnchorEnvelope {
     /// Schema identifier (`mfenx.powerhouse.envelope.v1`).
     pub schema: String,
-    /// Envelope schema version (major).
+    /// Envelope schema version (major); envelopes without it are version 1.
     #[serde(default = "default_envelope_version")]
     pub schema_version: u32,
+    /// Network identifier, checked before the payload is decoded. Required
+    /// from version 2; [`AnchorEnvelope::upgrade`] assigns version 1 envelopes
+    /// to the default network.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub network: String,
     /// Base64-encoded ed25519 public key for signature verification.
     pub public_key: String,
     /// Sender node identifier.
@@ -144,8 +219,19 @@ pub enum AnchorCodecError {
         /// Encountered network identifier.
         found: String,
     },
+    /// The envelope schema version is outside the supported range.
+    UnsupportedVersion {
+        /// Encountered schema version.
+        found: u32,
+        /// Oldest supported version.
+        min: u32,
+        /// Newest supported version.
+        max: u32,
+    },
     /// The ledger anchor was missing the JULIAN genesis entry.
     MissingGenesis,
+    /// The genesis entry carries another network's genesis digest.
+    GenesisMismatch,
     /// A transcript hash was malformed.
     InvalidDigest {
         /// Index of the entry containing the malformed hash.
@@ -153,6 +239,13 @@ pub enum AnchorCodecError {
         /// Reason for the failure.
         reason: String,
     },
+    /// Submitter attribution on an entry was malformed.
+    InvalidSubmission {
+        /// Index of the entry carrying the attribution.
+        entry: usize,
+        /// Reason for the failure.
+        reason: String,
+    },
 }
 
 impl fmt::Display for AnchorCodecError {
@@ -164,13 +257,28 @@ impl fmt::Display for AnchorCodecError {
             Self::InvalidNetwork { expected, found } => {
                 write!(f, "invalid network: expected {expected}, found {found}")
             }
+            Self::UnsupportedVersion { found, min, max } => {
+                write!(
+                    f,
+                    "unsupported envelope schema version {found} (supported {min}..={max})"
+                )
+            }
             Self::MissingGenesis => write!(f, "ledger anchor missing JULIAN genesis entry"),
+            Self::GenesisMismatch => {
+                write!(f, "ledger anchor genesis digest belongs to another network")
+            }
             Self::InvalidDigest { entry, reason } => {
                 write!(
                     f,
                     "ledger anchor entry {entry} has invalid digest: {reason}"
                 )
             }
+            Self::InvalidSubmission { entry, reason } => {
+                write!(
+                    f,
+                    "ledger anchor entry {entry} has invalid submitter: {reason}"
+                )
+            }
         }
     }
 }
@@ -189,7 +297,7 @@ impl AnchorJson {
     ) -> Result<Self, AnchorCodecError> {
         if anchor.entries.is_empty()
             || anchor.entries.first().map(|e| e.statement.as_str())
-                != Some(JULIAN_GENESIS_STATEMENT)
+                != Some(julian_genesis_statement())
         {
             return Err(AnchorCodecError::MissingGenesis);
         }
@@ -200,6 +308,10 @@ impl AnchorJson {
                 statement: entry.statement.clone(),
                 hashes: entry.hashes.iter().map(digest_to_hex).collect(),
                 merkle_root: Some(digest_to_hex(&entry.merkle_root)),
+                submission: entry
+                    .submission
+                    .as_ref()
+                    .map(EntrySubmissionJson::from_submission),
             })
             .collect();
         let fold_digest = anchor
@@ -208,9 +320,9 @@ impl AnchorJson {
             .unwrap_or_else(|| compute_fold_digest(anchor));
         Ok(Self {
             schema: SCHEMA_ANCHOR.to_string(),
-            network: NETWORK_ID.to_string(),
+            network: network_id().to_string(),
             node_id: node_id.into(),
-            genesis: JULIAN_GENESIS_STATEMENT.to_string(),
+            genesis: julian_genesis_statement().to_string(),
             entries,
             quorum,
             timestamp_ms,
@@ -219,6 +331,7 @@ impl AnchorJson {
             crate_version: anchor.metadata.crate_version.clone(),
             da_commitments,
             evidence_root,
+            registry_root: anchor.metadata.registry_root.as_ref().map(digest_to_hex),
         })
     }
 
@@ -230,13 +343,13 @@ impl AnchorJson {
                 found: self.schema,
             });
         }
-        if self.network != NETWORK_ID {
+        if self.network != network_id() {
             return Err(AnchorCodecError::InvalidNetwork {
-                expected: NETWORK_ID,
+                expected: network_id(),
                 found: self.network,
             });
         }
-        if self.entries.first().map(|e| e.statement.as_str()) != Some(JULIAN_GENESIS_STATEMENT) {
+        if self.entries.first().map(|e| e.statement.as_str()) != Some(julian_genesis_statement()) {
             return Err(AnchorCodecError::MissingGenesis);
         }
         let mut entries = Vec::with_capacity(self.entries.len());
@@ -253,12 +366,21 @@ impl AnchorJson {
             } else {
                 crate::merkle_root(&hashes)
             };
+            let submission = entry
+                .submission
+                .map(EntrySubmissionJson::into_submission)
+                .transpose()
+                .map_err(|reason| AnchorCodecError::InvalidSubmission { entry: idx, reason })?;
             entries.push(EntryAnchor {
                 statement: entry.statement,
                 hashes,
                 merkle_root,
+                submission,
             });
         }
+        if entries[0].hashes != [julian_genesis_hash()] {
+            return Err(AnchorCodecError::GenesisMismatch);
+        }
         let mut metadata = AnchorMetadata {
             challenge_mode: self.challenge_mode,
             crate_version: self
@@ -272,6 +394,12 @@ impl AnchorJson {
                     .map_err(|reason| AnchorCodecError::InvalidDigest { entry: 0, reason })?,
             );
         }
+        if let Some(root_hex) = self.registry_root {
+            metadata.registry_root = Some(
+                digest_from_hex(&root_hex)
+                    .map_err(|reason| AnchorCodecError::InvalidDigest { entry: 0, reason })?,
+            );
+        }
         if metadata.fold_digest.is_none() {
             let temp = LedgerAnchor {
                 entries: entries.clone(),
@@ -294,7 +422,29 @@ impl AnchorJson {
 }
 
 impl AnchorEnvelope {
-    /// Ensures the envelope schema field matches the expected identifier.
+    /// Brings a supported older envelope up to the current schema version.
+    ///
+    /// Version 1 envelopes predate the `network` field and are assigned to the
+    /// default network; the signed payload is untouched.
+    pub fn upgrade(mut self) -> Result<Self, AnchorCodecError> {
+        check_envelope_version(self.schema_version)?;
+        if self.schema_version == 1 && self.network.is_empty() {
+            self.network = NETWORK_ID.to_string();
+        }
+        self.schema_version = ENVELOPE_SCHEMA_VERSION;
+        Ok(self)
+    }
+
+    /// Re-encodes the envelope for peers that only parse up to `version`.
+    pub fn downgrade(mut self, version: u32) -> Self {
+        if version < 2 {
+            self.network.clear();
+        }
+        self.schema_version = version.clamp(MIN_ENVELOPE_SCHEMA_VERSION, ENVELOPE_SCHEMA_VERSION);
+        self
+    }
+
+    /// Ensures the envelope schema, version and network match this node.
     pub fn validate(&self) -> Result<(), AnchorCodecError> {
         if self.schema != SCHEMA_ENVELOPE {
             return Err(AnchorCodecError::InvalidSchema {
@@ -302,10 +452,11 @@ impl AnchorEnvelope {
                 found: self.schema.clone(),
             });
         }
-        if self.schema_version > ENVELOPE_SCHEMA_VERSION {
-            return Err(AnchorCodecError::InvalidSchema {
-                expected: "schema_version <= current",
-                found: format!("{}", self.schema_version),
+        check_envelope_version(self.schema_version)?;
+        if self.network != network_id() {
+            return Err(AnchorCodecError::InvalidNetwork {
+                expected: network_id(),
+                found: self.network.clone(),
             });
         }
         Ok(())
@@ -321,9 +472,9 @@ impl AnchorVoteJson {
                 found: self.schema.clone(),
             });
         }
-        if self.network != NETWORK_ID {
+        if self.network != network_id() {
             return Err(AnchorCodecError::InvalidNetwork {
-                expected: NETWORK_ID,
+                expected: network_id(),
                 found: self.network.clone(),
             });
         }
@@ -339,5 +490,204 @@ impl AnchorVoteJson {
 }
 
 fn default_envelope_version() -> u32 {
-    ENVELOPE_SCHEMA_VERSION
+    MIN_ENVELOPE_SCHEMA_VERSION
+}
+
+fn check_envelope_version(version: u32) -> Result<(), AnchorCodecError> {
+    if (MIN_ENVELOPE_SCHEMA_VERSION..=MAX_ENVELOPE_SCHEMA_VERSION).contains(&version) {
+        Ok(())
+    } else {
+        Err(AnchorCodecError::UnsupportedVersion {
+            found: version,
+            min: MIN_ENVELOPE_SCHEMA_VERSION,
+            max: MAX_ENVELOPE_SCHEMA_VERSION,
+        })
+    }
+}
+
+/// Identify agent-version token advertising the supported envelope versions
+/// (`envelope/<min>-<max>`).
+pub fn envelope_version_token() -> String {
+    format!("{ENVELOPE_VERSION_TOKEN}{MIN_ENVELOPE_SCHEMA_VERSION}-{MAX_ENVELOPE_SCHEMA_VERSION}")
+}
+
+/// Supported envelope version range advertised in a peer's agent version.
+/// Peers that advertise nothing predate negotiation and only parse version 1.
+pub fn parse_envelope_versions(agent_version: &str) -> (u32, u32) {
+    agent_version
+        .split_whitespace()
+        .find_map(|token| {
+            let (min, max) = token
+                .strip_prefix(ENVELOPE_VERSION_TOKEN)?
+                .split_once('-')?;
+            let (min, max) = (min.parse().ok()?, max.parse().ok()?);
+            (min <= max).then_some((min, max))
+        })
+        .unwrap_or((1, 1))
+}
+
+/// Envelope version to publish given the ranges of connected peers: the
+/// newest version every peer parses, never below this node's minimum.
+pub fn negotiate_envelope_version(peers: impl IntoIterator<Item = (u32, u32)>) -> u32 {
+    peers
+        .into_iter()
+        .fold(ENVELOPE_SCHEMA_VERSION, |version, (_, max)| {
+            version.min(max)
+        })
+        .max(MIN_ENVELOPE_SCHEMA_VERSION)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        julian_genesis_anchor,
+        net::{blob::BlobJson, checkpoint::AnchorCheckpoint, CheckpointError},
+    };
+
+    #[test]
+    fn cross_network_artifacts_are_rejected() {
+        assert_eq!(
+            scoped_topic("mfenx/powerhouse/votes/v1", NETWORK_ID),
+            "mfenx/powerhouse/votes/v1"
+        );
+        assert_eq!(
+            scoped_topic("mfenx/powerhouse/votes/v1", "MFENX-TESTNET"),
+            "mfenx/powerhouse/votes/v1/net/MFENX-TESTNET"
+        );
+
+        let anchor =
+            AnchorJson::from_ledger("node-a", 1, &julian_genesis_anchor(), 0, Vec::new(), None)
+                .unwrap();
+        assert!(anchor.clone().into_ledger().is_ok());
+        let foreign = AnchorJson {
+            network: "MFENX-TESTNET".to_string(),
+            ..anchor.clone()
+        };
+        assert!(matches!(
+            foreign.clone().into_ledger(),
+            Err(AnchorCodecError::InvalidNetwork { .. })
+        ));
+        let mut forged = anchor.clone();
+        forged.entries[0].hashes = vec![digest_to_hex(&[7u8; 32])];
+        forged.entries[0].merkle_root = None;
+        assert!(matches!(
+            forged.into_ledger(),
+            Err(AnchorCodecError::GenesisMismatch)
+        ));
+
+        let legacy = serde_json::from_value::<AnchorEnvelope>(serde_json::json!({
+            "schema": SCHEMA_ENVELOPE,
+            "public_key": "",
+            "node_id": "node-a",
+            "payload": "",
+            "signature": "",
+        }))
+        .unwrap()
+        .upgrade()
+        .unwrap();
+        assert_eq!(legacy.network, NETWORK_ID);
+        legacy.validate().unwrap();
+        let envelope = AnchorEnvelope {
+            network: "MFENX-TESTNET".to_string(),
+            ..legacy
+        };
+        assert!(matches!(
+            envelope.validate(),
+            Err(AnchorCodecError::InvalidNetwork { .. })
+        ));
+
+        let vote = AnchorVoteJson {
+            schema: SCHEMA_VOTE.to_string(),
+            network: "MFENX-TESTNET".to_string(),
+            round: 1,
+            anchor_hash: "ab".repeat(32),
+            public_key: String::new(),
+            signature: String::new(),
+        };
+        assert!(matches!(
+            vote.validate(),
+            Err(AnchorCodecError::InvalidNetwork { .. })
+        ));
+
+        let checkpoint = AnchorCheckpoint::new(1, foreign, Vec::new(), None);
+        assert!(matches!(
+            checkpoint.into_ledger(),
+            Err(CheckpointError::WrongNetwork(network)) if network == "MFENX-TESTNET"
+        ));
+
+        let mut blob = BlobJson::from_bytes("default", b"payload");
+        blob.validate().unwrap();
+        blob.network = "MFENX-TESTNET".to_string();
+        assert!(blob.validate().is_err());
+    }
+
+    #[test]
+    fn registry_root_round_trips_and_binds_checkpoints() {
+        let mut ledger = julian_genesis_anchor();
+        ledger.metadata.registry_root = Some([9u8; 32]);
+        let anchor = AnchorJson::from_ledger("node-a", 1, &ledger, 0, Vec::new(), None).unwrap();
+        assert_eq!(anchor.registry_root, Some(digest_to_hex(&[9u8; 32])));
+        let decoded = anchor.clone().into_ledger().unwrap();
+        assert_eq!(decoded.metadata.registry_root, Some([9u8; 32]));
+
+        let checkpoint = AnchorCheckpoint::new(1, anchor, Vec::new(), None);
+        assert!(checkpoint
+            .clone()
+            .with_registry_root([9u8; 32])
+            .into_ledger()
+            .is_ok());
+        assert!(matches!(
+            checkpoint.with_registry_root([8u8; 32]).into_ledger(),
+            Err(CheckpointError::InvalidAnchor(_))
+        ));
+    }
+
+    #[test]
+    fn envelope_versions_negotiate_and_round_trip() {
+        let current = AnchorEnvelope {
+            schema: SCHEMA_ENVELOPE.to_string(),
+            schema_version: ENVELOPE_SCHEMA_VERSION,
+            network: NETWORK_ID.to_string(),
+            public_key: String::new(),
+            node_id: "node-a".to_string(),
+            payload: String::new(),
+            signature: String::new(),
+        };
+        current.validate().unwrap();
+
+        let legacy = current.clone().downgrade(1);
+        let encoded = serde_json::to_value(&legacy).unwrap();
+        assert_eq!(encoded["schema_version"], 1);
+        assert!(encoded.get("network").is_none());
+        assert!(legacy.validate().is_err());
+        assert_eq!(legacy.upgrade().unwrap(), current);
+
+        for version in [0, ENVELOPE_SCHEMA_VERSION + 1] {
+            let unsupported = AnchorEnvelope {
+                schema_version: version,
+                ..current.clone()
+            };
+            assert!(matches!(
+                unsupported.clone().upgrade(),
+                Err(AnchorCodecError::UnsupportedVersion { found, .. }) if found == version
+            ));
+            assert!(unsupported.validate().is_err());
+        }
+
+        let agent = format!("power_house/0.1.0 {}", envelope_version_token());
+        assert_eq!(
+            parse_envelope_versions(&agent),
+            (MIN_ENVELOPE_SCHEMA_VERSION, MAX_ENVELOPE_SCHEMA_VERSION)
+        );
+        assert_eq!(parse_envelope_versions("power_house/0.1.0"), (1, 1));
+        assert_eq!(parse_envelope_versions("envelope/3-1"), (1, 1));
+        assert_eq!(negotiate_envelope_version([]), ENVELOPE_SCHEMA_VERSION);
+        assert_eq!(negotiate_envelope_version([(1, 5), (1, 2)]), 2);
+        assert_eq!(negotiate_envelope_version([(1, 2), (1, 1)]), 1);
+        assert_eq!(
+            negotiate_envelope_version([(0, 0)]),
+            MIN_ENVELOPE_SCHEMA_VERSION
+        );
+    }
 }
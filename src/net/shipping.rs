@@ -0,0 +1,604 @@
+#![cfg(feature = "net")]
+
+//! Remote transcript log shipping and a minimal aggregation server.
+//!
+//! A node running the shipper walks its log directory (legacy files and the
+//! transcript journal), signs every record it has not shipped yet with its
+//! ed25519 identity and POSTs it to an aggregator.  Progress is tracked in a
+//! cursor file next to the logs, so restarts resume where they left off.
+//!
+//! The aggregator verifies the signature and the transcript digest, pins the
+//! first public key it sees for each node identity, and stores accepted
+//! records under `<store_dir>/<node_id>/` using `ledger_*` names.  Each
+//! per-node folder is therefore itself a log directory that `julian node
+//! anchor` can replay.
+
+use crate::net::rpc::{read_http_request, write_json, CorsPolicy};
+use crate::net::sign::{
+    encode_public_key_base64, encode_signature_base64, sign_payload, verify_signature_base64,
+    KeyMaterial,
+};
+use crate::{iter_ledger_logs, parse_log_bytes};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{
+    fs, io,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tokio::{net::TcpListener, time};
+
+/// Schema identifier carried by every shipped record.
+pub const SCHEMA_SHIPPED_RECORD: &str = "mfenx.powerhouse.logship.v1";
+/// File inside the log directory that remembers the last shipped record.
+pub const LOG_SHIP_CURSOR_FILE: &str = "log_ship_cursor.txt";
+/// File inside each node folder that pins the node's public key.
+const NODE_KEY_FILE: &str = "node_key.txt";
+
+const MAX_HEADER_BYTES: usize = 16 * 1024;
+const MAX_BODY_BYTES: usize = 4 * 1024 * 1024;
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Errors produced while shipping or aggregating transcript records.
+#[derive(Debug, thiserror::Error)]
+pub enum ShippingError {
+    /// Local filesystem failure.
+    #[error("log shipping I/O error: {0}")]
+    Io(String),
+    /// Record is malformed or fails transcript verification.
+    #[error("invalid shipped record: {0}")]
+    Invalid(String),
+    /// Signature or key pinning check failed.
+    #[error("shipped record not authorised: {0}")]
+    Unauthorised(String),
+    /// A different record was already stored under the same name.
+    #[error("shipped record conflicts with stored copy: {0}")]
+    Conflict(String),
+    /// The aggregator permanently rejected a record.
+    #[error("aggregator rejected {name}: HTTP {status}: {body}")]
+    Rejected {
+        /// Record name.
+        name: String,
+        /// HTTP status code.
+        status: u16,
+        /// Response body.
+        body: String,
+    },
+    /// The aggregator could not be reached after every retry.
+    #[error("aggregator unreachable: {0}")]
+    Transport(String),
+}
+
+/// Signed transcript record as sent over the wire.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ShippedRecord {
+    /// Schema identifier (`mfenx.powerhouse.logship.v1`).
+    pub schema: String,
+    /// Identity of the shipping node.
+    pub node_id: String,
+    /// Record name as produced by [`iter_ledger_logs`].
+    pub name: String,
+    /// Base64-encoded record bytes (ASCII or binary transcript record).
+    pub payload: String,
+    /// Base64 ed25519 public key of the node.
+    pub public_key: String,
+    /// Base64 signature over [`ShippedRecord::signing_bytes`].
+    pub signature: String,
+}
+
+impl ShippedRecord {
+    /// Builds and signs a record for `bytes`.
+    pub fn sign(node_id: &str, name: &str, bytes: &[u8], key: &KeyMaterial) -> Self {
+        let mut record = Self {
+            schema: SCHEMA_SHIPPED_RECORD.to_string(),
+            node_id: node_id.to_string(),
+            name: name.to_string(),
+            payload: BASE64.encode(bytes),
+            public_key: encode_public_key_base64(&key.verifying),
+            signature: String::new(),
+        };
+        let signature = sign_payload(&key.signing, &record.signing_bytes());
+        record.signature = encode_signature_base64(&signature);
+        record
+    }
+
+    /// Canonical bytes covered by the signature.
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        format!(
+            "{}|{}|{}|{}",
+            self.schema, self.node_id, self.name, self.payload
+        )
+        .into_bytes()
+    }
+
+    /// Checks identifiers, signature and transcript digest, returning the
+    /// decoded record bytes.
+    pub fn verify(&self) -> Result<Vec<u8>, ShippingError> {
+        if self.schema != SCHEMA_SHIPPED_RECORD {
+            return Err(ShippingError::Invalid(format!(
+                "unsupported schema {}",
+                self.schema
+            )));
+        }
+        if !is_safe_node_id(&self.node_id) {
+            return Err(ShippingError::Invalid(format!(
+                "invalid node id {:?}",
+                self.node_id
+            )));
+        }
+        if !is_safe_record_name(&self.name) {
+            return Err(ShippingError::Invalid(format!(
+                "invalid record name {:?}",
+                self.name
+            )));
+        }
+        verify_signature_base64(&self.public_key, &self.signing_bytes(), &self.signature)
+            .map_err(|err| ShippingError::Unauthorised(err.to_string()))?;
+        let bytes = BASE64
+            .decode(&self.payload)
+            .map_err(|err| ShippingError::Invalid(format!("payload: {err}")))?;
+        parse_log_bytes(&self.name, &bytes).map_err(ShippingError::Invalid)?;
+        Ok(bytes)
+    }
+}
+
+/// Settings for the log-shipping client.
+#[derive(Debug, Clone)]
+pub struct LogShipperConfig {
+    /// Aggregator URL that accepts `POST` requests with a [`ShippedRecord`].
+    pub endpoint: String,
+    /// Node identity attached to each record.
+    pub node_id: String,
+    /// Log directory to ship from.
+    pub log_dir: PathBuf,
+    /// Delay between scans for new records.
+    pub poll_interval: Duration,
+    /// Retries per record before giving up on the current scan.
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled after each failure.
+    pub initial_backoff: Duration,
+    /// Upper bound for the retry delay.
+    pub max_backoff: Duration,
+    /// Timeout applied to each HTTP request.
+    pub request_timeout: Duration,
+}
+
+impl LogShipperConfig {
+    /// Creates a shipper configuration with default polling and retry settings.
+    pub fn new(endpoint: String, node_id: String, log_dir: PathBuf) -> Self {
+        Self {
+            endpoint,
+            node_id,
+            log_dir,
+            poll_interval: Duration::from_secs(5),
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+        }
+    }
+}
+
+/// Ships every record newer than the stored cursor, returning how many were sent.
+pub async fn ship_pending_logs(
+    cfg: &LogShipperConfig,
+    key: &KeyMaterial,
+    client: &reqwest::Client,
+) -> Result<usize, ShippingError> {
+    let cursor_path = cfg.log_dir.join(LOG_SHIP_CURSOR_FILE);
+    let cursor = fs::read_to_string(&cursor_path)
+        .ok()
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty());
+    let mut shipped = 0usize;
+    for record in iter_ledger_logs(&cfg.log_dir).map_err(ShippingError::Io)? {
+        let record = record.map_err(ShippingError::Invalid)?;
+        if cursor
+            .as_deref()
+            .is_some_and(|last| record.name.as_str() <= last)
+        {
+            continue;
+        }
+        let bytes = record.bytes().map_err(ShippingError::Io)?;
+        let shipped_record = ShippedRecord::sign(&cfg.node_id, &record.name, &bytes, key);
+        post_with_retry(cfg, client, &shipped_record).await?;
+        fs::write(&cursor_path, format!("{}\n", record.name))
+            .map_err(|err| ShippingError::Io(err.to_string()))?;
+        shipped += 1;
+    }
+    Ok(shipped)
+}
+
+/// Runs the shipper until the task is cancelled, rescanning every poll interval.
+pub async fn run_log_shipper(cfg: LogShipperConfig, key: KeyMaterial) -> Result<(), ShippingError> {
+    let client = reqwest::Client::builder()
+        .timeout(cfg.request_timeout)
+        .build()
+        .map_err(|err| ShippingError::Transport(err.to_string()))?;
+    println!(
+        "QSYS|mod=LOGSHIP|evt=START|endpoint={}|node_id={}",
+        cfg.endpoint, cfg.node_id
+    );
+    loop {
+        match ship_pending_logs(&cfg, &key, &client).await {
+            Ok(0) => {}
+            Ok(count) => println!("QSYS|mod=LOGSHIP|evt=SHIPPED|records={count}"),
+            Err(err) => eprintln!("log shipping error: {err}"),
+        }
+        time::sleep(cfg.poll_interval).await;
+    }
+}
+
+async fn post_with_retry(
+    cfg: &LogShipperConfig,
+    client: &reqwest::Client,
+    record: &ShippedRecord,
+) -> Result<(), ShippingError> {
+    let mut backoff = cfg.initial_backoff;
+    let mut attempt = 0u32;
+    loop {
+        let last_error = match client.post(&cfg.endpoint).json(record).send().await {
+            Ok(response) if response.status().is_success() => return Ok(()),
+            Ok(response) => {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                let retryable = status.is_server_error()
+                    || status == reqwest::StatusCode::REQUEST_TIMEOUT
+                    || status == reqwest::StatusCode::TOO_MANY_REQUESTS;
+                if !retryable {
+                    return Err(ShippingError::Rejected {
+                        name: record.name.clone(),
+                        status: status.as_u16(),
+                        body,
+                    });
+                }
+                format!("HTTP {status}: {body}")
+            }
+            Err(err) => err.to_string(),
+        };
+        if attempt >= cfg.max_retries {
+            return Err(ShippingError::Transport(format!(
+                "{} after {} attempt(s): {last_error}",
+                record.name,
+                attempt + 1
+            )));
+        }
+        attempt += 1;
+        time::sleep(backoff).await;
+        backoff = (backoff * 2).min(cfg.max_backoff);
+    }
+}
+
+/// Settings for the built-in aggregator server.
+#[derive(Debug, Clone)]
+pub struct LogAggregatorConfig {
+    /// HTTP listen socket.
+    pub listen: SocketAddr,
+    /// Root directory holding one sub-folder per node identity.
+    pub store_dir: PathBuf,
+    /// Maximum time allowed to read a request.
+    pub request_timeout: Duration,
+    /// Cross-origin policy for responses.
+    pub cors: CorsPolicy,
+}
+
+impl LogAggregatorConfig {
+    /// Creates an aggregator configuration with the default request timeout.
+    ///
+    /// Shippers are nodes rather than browsers, so no origin is allowed
+    /// until `cors` is set.
+    pub fn new(listen: SocketAddr, store_dir: PathBuf) -> Self {
+        Self {
+            listen,
+            store_dir,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            cors: CorsPolicy {
+                allowed_origins: Vec::new(),
+                ..CorsPolicy::default()
+            },
+        }
+    }
+}
+
+/// Result of storing a shipped record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StoreOutcome {
+    /// The record was written to the given path.
+    Stored(PathBuf),
+    /// An identical record was already present.
+    Duplicate(PathBuf),
+}
+
+/// Verifies `record` and stores it under `store_dir/<node_id>/`.
+pub fn store_shipped_record(
+    store_dir: &Path,
+    record: &ShippedRecord,
+) -> Result<StoreOutcome, ShippingError> {
+    let bytes = record.verify()?;
+    let node_dir = store_dir.join(&record.node_id);
+    fs::create_dir_all(&node_dir).map_err(|err| ShippingError::Io(err.to_string()))?;
+    let key_path = node_dir.join(NODE_KEY_FILE);
+    match fs::read_to_string(&key_path) {
+        Ok(pinned) if pinned.trim() != record.public_key => {
+            return Err(ShippingError::Unauthorised(format!(
+                "node {} is bound to a different public key",
+                record.node_id
+            )));
+        }
+        Ok(_) => {}
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            fs::write(&key_path, format!("{}\n", record.public_key))
+                .map_err(|err| ShippingError::Io(err.to_string()))?;
+        }
+        Err(err) => return Err(ShippingError::Io(err.to_string())),
+    }
+    let path = node_dir.join(stored_file_name(&record.name));
+    match fs::read(&path) {
+        Ok(existing) if existing == bytes => return Ok(StoreOutcome::Duplicate(path)),
+        Ok(_) => return Err(ShippingError::Conflict(record.name.clone())),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(ShippingError::Io(err.to_string())),
+    }
+    let tmp = path.with_extension("partial");
+    fs::write(&tmp, &bytes).map_err(|err| ShippingError::Io(err.to_string()))?;
+    fs::rename(&tmp, &path).map_err(|err| ShippingError::Io(err.to_string()))?;
+    Ok(StoreOutcome::Stored(path))
+}
+
+/// Serves the aggregator until the task is cancelled or the listener fails.
+pub async fn run_log_aggregator(cfg: LogAggregatorConfig) -> io::Result<()> {
+    fs::create_dir_all(&cfg.store_dir)?;
+    let listener = TcpListener::bind(cfg.listen).await?;
+    println!(
+        "QSYS|mod=LOGSHIP|evt=AGGREGATOR_LISTEN|addr={}|store={}",
+        cfg.listen,
+        cfg.store_dir.display()
+    );
+    loop {
+        let (mut stream, _) = listener.accept().await?;
+        let cfg = cfg.clone();
+        tokio::spawn(async move {
+            if let Err(err) = handle_aggregator_connection(&mut stream, &cfg).await {
+                eprintln!("log aggregator connection error: {err}");
+            }
+        });
+    }
+}
+
+async fn handle_aggregator_connection(
+    stream: &mut tokio::net::TcpStream,
+    cfg: &LogAggregatorConfig,
+) -> io::Result<()> {
+    let request = match read_http_request(
+        stream,
+        MAX_HEADER_BYTES,
+        MAX_BODY_BYTES,
+        cfg.request_timeout,
+    )
+    .await
+    {
+        Ok(request) => request,
+        Err(err) => {
+            return write_json(
+                stream,
+                "400 Bad Request",
+                &json!({"error": err.to_string()}),
+                &cfg.cors.response_headers(None),
+            )
+            .await;
+        }
+    };
+    let cors = cfg
+        .cors
+        .response_headers(request.headers.get("origin").map(String::as_str));
+    if request.method.eq_ignore_ascii_case("GET") && request.path == "/healthz" {
+        return write_json(stream, "200 OK", &json!({"status": "ok"}), &cors).await;
+    }
+    if !request.method.eq_ignore_ascii_case("POST") {
+        return write_json(
+            stream,
+            "405 Method Not Allowed",
+            &json!({"error": "records must be POSTed"}),
+            &cors,
+        )
+        .await;
+    }
+    let record: ShippedRecord = match serde_json::from_slice(&request.body) {
+        Ok(record) => record,
+        Err(err) => {
+            return write_json(
+                stream,
+                "400 Bad Request",
+                &json!({"error": err.to_string()}),
+                &cors,
+            )
+            .await;
+        }
+    };
+    let store_dir = cfg.store_dir.clone();
+    let outcome = tokio::task::spawn_blocking(move || store_shipped_record(&store_dir, &record))
+        .await
+        .map_err(io::Error::other)?;
+    let (status, body) = match outcome {
+        Ok(StoreOutcome::Stored(path)) => (
+            "200 OK",
+            json!({"status": "stored", "path": path.display().to_string()}),
+        ),
+        Ok(StoreOutcome::Duplicate(path)) => (
+            "200 OK",
+            json!({"status": "duplicate", "path": path.display().to_string()}),
+        ),
+        Err(err @ ShippingError::Invalid(_)) => {
+            ("400 Bad Request", json!({"error": err.to_string()}))
+        }
+        Err(err @ ShippingError::Unauthorised(_)) => {
+            ("401 Unauthorized", json!({"error": err.to_string()}))
+        }
+        Err(err @ ShippingError::Conflict(_)) => {
+            ("409 Conflict", json!({"error": err.to_string()}))
+        }
+        Err(err) => (
+            "500 Internal Server Error",
+            json!({"error": err.to_string()}),
+        ),
+    };
+    write_json(stream, status, &body, &cors).await
+}
+
+fn is_safe_node_id(node_id: &str) -> bool {
+    !node_id.is_empty()
+        && node_id.len() <= 128
+        && !node_id.starts_with('.')
+        && node_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+fn is_safe_record_name(name: &str) -> bool {
+    name.starts_with("ledger_")
+        && name.len() <= 128
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '#'))
+        && !name.contains("..")
+}
+
+/// Maps a record name to a `ledger_*` file name that keeps the shipped order.
+fn stored_file_name(name: &str) -> String {
+    match name.split_once('#') {
+        Some((segment, record)) => {
+            format!("{}_{record}.txt", segment.trim_end_matches(".phj"))
+        }
+        None => name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::{load_or_derive_keypair, Ed25519KeySource};
+    use crate::{transcript_digest, transcript_digest_to_hex};
+    use std::time::SystemTime;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        std::env::temp_dir().join(format!("{name}_{nanos}"))
+    }
+
+    fn log_bytes(statement: &str) -> Vec<u8> {
+        let hash = transcript_digest(&[1, 2], &[3, 4], 5);
+        format!(
+            "statement:{statement}\ntranscript:1 2\nround_sums:3 4\nfinal:5\nhash:{}\n",
+            transcript_digest_to_hex(&hash)
+        )
+        .into_bytes()
+    }
+
+    #[test]
+    fn aggregator_stores_verified_records_per_node() {
+        let store = temp_path("powerhouse_logship_store");
+        let key = load_or_derive_keypair(&Ed25519KeySource::Seed("ship-a".into())).unwrap();
+        let other = load_or_derive_keypair(&Ed25519KeySource::Seed("ship-b".into())).unwrap();
+
+        let record = ShippedRecord::sign(
+            "node-a",
+            "ledger_journal_000000.phj#00000000",
+            &log_bytes("Shipped"),
+            &key,
+        );
+        let stored = match store_shipped_record(&store, &record).unwrap() {
+            StoreOutcome::Stored(path) => path,
+            other => panic!("unexpected outcome {other:?}"),
+        };
+        assert!(stored.ends_with("node-a/ledger_journal_000000_00000000.txt"));
+        assert!(matches!(
+            store_shipped_record(&store, &record).unwrap(),
+            StoreOutcome::Duplicate(_)
+        ));
+
+        let mut tampered = record.clone();
+        tampered.payload = BASE64.encode(log_bytes("Forged"));
+        assert!(matches!(
+            store_shipped_record(&store, &tampered),
+            Err(ShippingError::Unauthorised(_))
+        ));
+
+        let impostor = ShippedRecord::sign("node-a", "ledger_0001.txt", &log_bytes("X"), &other);
+        assert!(matches!(
+            store_shipped_record(&store, &impostor),
+            Err(ShippingError::Unauthorised(_))
+        ));
+
+        let escape = ShippedRecord::sign("../node-a", "ledger_0001.txt", &log_bytes("X"), &key);
+        assert!(matches!(
+            store_shipped_record(&store, &escape),
+            Err(ShippingError::Invalid(_))
+        ));
+
+        let parsed = crate::parse_log_file(&stored).unwrap();
+        assert_eq!(parsed.statement, "Shipped");
+        fs::remove_dir_all(&store).unwrap();
+    }
+
+    #[tokio::test]
+    async fn shipper_retries_until_aggregator_is_up() {
+        let log_dir = temp_path("powerhouse_logship_logs");
+        let store = temp_path("powerhouse_logship_remote");
+        fs::create_dir_all(&log_dir).unwrap();
+        fs::write(log_dir.join("ledger_0000.txt"), log_bytes("First")).unwrap();
+        fs::write(log_dir.join("ledger_0001.txt"), log_bytes("Second")).unwrap();
+
+        let reserved = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let listen = reserved.local_addr().unwrap();
+        drop(reserved);
+
+        let key = load_or_derive_keypair(&Ed25519KeySource::Seed("ship-c".into())).unwrap();
+        let mut cfg = LogShipperConfig::new(
+            format!("http://{listen}/"),
+            "node-c".into(),
+            log_dir.clone(),
+        );
+        cfg.initial_backoff = Duration::from_millis(50);
+        cfg.max_retries = 40;
+        let client = reqwest::Client::new();
+
+        let mut aggregator = LogAggregatorConfig::new(listen, store.clone());
+        assert!(aggregator.cors.allowed_origins.is_empty());
+        aggregator.cors.allowed_origins = vec!["https://ops.example".into()];
+        let server = tokio::spawn(async move {
+            time::sleep(Duration::from_millis(150)).await;
+            run_log_aggregator(aggregator).await
+        });
+
+        assert_eq!(ship_pending_logs(&cfg, &key, &client).await.unwrap(), 2);
+        assert_eq!(ship_pending_logs(&cfg, &key, &client).await.unwrap(), 0);
+        let cursor = fs::read_to_string(log_dir.join(LOG_SHIP_CURSOR_FILE)).unwrap();
+        assert_eq!(cursor.trim(), "ledger_0001.txt");
+        assert!(store.join("node-c").join("ledger_0001.txt").exists());
+        for (origin, allowed) in [
+            ("https://ops.example", Some("https://ops.example")),
+            ("https://evil.example", None),
+        ] {
+            let response = client
+                .get(format!("http://{listen}/healthz"))
+                .header("Origin", origin)
+                .send()
+                .await
+                .unwrap();
+            let header = response
+                .headers()
+                .get("access-control-allow-origin")
+                .map(|value| value.to_str().unwrap().to_string());
+            assert_eq!(header.as_deref(), allowed);
+        }
+
+        server.abort();
+        fs::remove_dir_all(&log_dir).unwrap();
+        fs::remove_dir_all(&store).unwrap();
+    }
+}
@@ -1,16 +1,28 @@
 #![cfg(feature = "net")]
 
+use crate::EntrySubmission;
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey, SECRET_KEY_LENGTH};
+use hmac::{Hmac, Mac};
 use libp2p::identity;
-use rand_core::OsRng;
-use sha2::{Digest, Sha512};
+use once_cell::sync::Lazy;
+use rand_core::{OsRng, RngCore};
+use sha2::{Digest, Sha256, Sha512};
 use std::{
     error::Error,
     fmt, fs,
     path::{Path, PathBuf},
 };
 
+/// SLIP-0010 path used by `julian key from-mnemonic` unless `--path` is given.
+pub const DEFAULT_MNEMONIC_PATH: &str = "m/44'/177155'/0'/0'/0'";
+
+/// Derivation scheme recorded in the header of mnemonic-derived key files.
+pub const MNEMONIC_KEY_SCHEME: &str = "bip39-english/pbkdf2-hmac-sha512-2048/slip10-ed25519";
+
+static BIP39_ENGLISH: Lazy<Vec<&'static str>> =
+    Lazy::new(|| include_str!("bip39_english.txt").lines().collect());
+
 /// Describes how an ed25519 key should be obtained.
 #[derive(Debug, Clone)]
 pub enum Ed25519KeySource {
@@ -142,7 +154,12 @@ fn load_key_from_file(path: &Path) -> Result<[u8; SECRET_KEY_LENGTH], KeyError>
         return Ok(secret);
     }
     if let Ok(text) = std::str::from_utf8(&contents) {
-        let trimmed = text.trim();
+        // `#` lines are header notes, e.g. the derivation of mnemonic keys.
+        let body = text
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .collect::<String>();
+        let trimmed = body.trim();
         if trimmed.len() == SECRET_KEY_LENGTH * 2 && trimmed.chars().all(|c| c.is_ascii_hexdigit())
         {
             let mut secret = [0u8; SECRET_KEY_LENGTH];
@@ -213,6 +230,161 @@ fn bytes_to_secret(bytes: impl AsRef<[u8]>) -> Result<[u8; SECRET_KEY_LENGTH], K
     Ok(secret)
 }
 
+/// Generates a BIP-39 English mnemonic of `words` words from OS randomness.
+pub fn generate_mnemonic(words: usize) -> Result<String, KeyError> {
+    if !matches!(words, 12 | 15 | 18 | 21 | 24) {
+        return Err(KeyError::Decode(format!(
+            "mnemonic must have 12, 15, 18, 21, or 24 words, not {words}"
+        )));
+    }
+    let mut entropy = vec![0u8; words * 4 / 3];
+    OsRng.fill_bytes(&mut entropy);
+    mnemonic_from_entropy(&entropy)
+}
+
+/// Encodes 16 to 32 bytes of entropy as a BIP-39 English mnemonic.
+pub fn mnemonic_from_entropy(entropy: &[u8]) -> Result<String, KeyError> {
+    if !matches!(entropy.len(), 16 | 20 | 24 | 28 | 32) {
+        return Err(KeyError::InvalidLength(entropy.len()));
+    }
+    let checksum = Sha256::digest(entropy);
+    let bits = entropy.len() * 8 + entropy.len() / 4;
+    let bit = |index: usize| {
+        let byte = if index < entropy.len() * 8 {
+            entropy[index / 8]
+        } else {
+            checksum[(index - entropy.len() * 8) / 8]
+        };
+        usize::from(byte >> (7 - index % 8) & 1)
+    };
+    let words = (0..bits / 11)
+        .map(|word| {
+            let index = (0..11).fold(0, |acc, offset| acc << 1 | bit(word * 11 + offset));
+            BIP39_ENGLISH[index]
+        })
+        .collect::<Vec<_>>();
+    Ok(words.join(" "))
+}
+
+/// Checks the words and checksum of a mnemonic and returns its entropy.
+pub fn mnemonic_to_entropy(mnemonic: &str) -> Result<Vec<u8>, KeyError> {
+    let words = mnemonic.split_whitespace().collect::<Vec<_>>();
+    if !matches!(words.len(), 12 | 15 | 18 | 21 | 24) {
+        return Err(KeyError::Decode(format!(
+            "mnemonic must have 12, 15, 18, 21, or 24 words, not {}",
+            words.len()
+        )));
+    }
+    let mut bits = Vec::with_capacity(words.len() * 11);
+    for word in &words {
+        let index = BIP39_ENGLISH
+            .binary_search(&word.to_ascii_lowercase().as_str())
+            .map_err(|_| KeyError::Decode(format!("`{word}` is not a BIP-39 English word")))?;
+        bits.extend((0..11).rev().map(|shift| (index >> shift) & 1 == 1));
+    }
+    let entropy_bits = bits.len() * 32 / 33;
+    let entropy = bits[..entropy_bits]
+        .chunks(8)
+        .map(|byte| byte.iter().fold(0u8, |acc, bit| acc << 1 | u8::from(*bit)))
+        .collect::<Vec<_>>();
+    if mnemonic_from_entropy(&entropy)? != words.join(" ").to_ascii_lowercase() {
+        return Err(KeyError::Decode("mnemonic checksum mismatch".to_string()));
+    }
+    Ok(entropy)
+}
+
+/// Stretches a mnemonic and optional passphrase into the 64-byte BIP-39 seed.
+///
+/// Passphrases must be ASCII: they are used without NFKD normalization.
+pub fn mnemonic_to_seed(mnemonic: &str, passphrase: &str) -> Result<[u8; 64], KeyError> {
+    mnemonic_to_entropy(mnemonic)?;
+    if !passphrase.is_ascii() {
+        return Err(KeyError::Decode(
+            "mnemonic passphrase must be ASCII".to_string(),
+        ));
+    }
+    let normalized = mnemonic
+        .split_whitespace()
+        .map(|word| word.to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let salt = format!("mnemonic{passphrase}");
+    let mut block = hmac_sha512(
+        normalized.as_bytes(),
+        &[salt.as_bytes(), &1u32.to_be_bytes()],
+    );
+    let mut seed = block;
+    for _ in 1..2048 {
+        block = hmac_sha512(normalized.as_bytes(), &[&block]);
+        for (out, byte) in seed.iter_mut().zip(block) {
+            *out ^= byte;
+        }
+    }
+    Ok(seed)
+}
+
+/// Derives key material from a mnemonic along a hardened SLIP-0010 path.
+///
+/// Derivation is fully determined by the words, the passphrase, and `path`,
+/// so the same identity can be recreated on any host.
+pub fn derive_mnemonic_key(
+    mnemonic: &str,
+    passphrase: &str,
+    path: &str,
+) -> Result<KeyMaterial, KeyError> {
+    let seed = mnemonic_to_seed(mnemonic, passphrase)?;
+    key_material_from_secret(slip10_ed25519_secret(&seed, path)?)
+}
+
+/// Renders a key file for `signing` with a header describing its derivation.
+///
+/// The header lines start with `#` and are skipped when the file is loaded as
+/// a key specification; the body is the hex-encoded secret.
+pub fn mnemonic_key_file(signing: &SigningKey, path: &str, passphrase: bool) -> String {
+    format!(
+        "# julian key file\n# derivation: {MNEMONIC_KEY_SCHEME}\n# path: {path}\n# passphrase: {}\n{}\n",
+        if passphrase { "required" } else { "none" },
+        hex::encode(signing.to_bytes())
+    )
+}
+
+fn slip10_ed25519_secret(seed: &[u8], path: &str) -> Result<[u8; SECRET_KEY_LENGTH], KeyError> {
+    let mut segments = path.split('/');
+    if segments.next() != Some("m") {
+        return Err(KeyError::Decode(format!(
+            "derivation path `{path}` must start with m"
+        )));
+    }
+    let mut node = hmac_sha512(b"ed25519 seed", &[seed]);
+    for segment in segments {
+        let index = segment
+            .strip_suffix('\'')
+            .or_else(|| segment.strip_suffix('h'))
+            .and_then(|index| index.parse::<u32>().ok())
+            .filter(|index| *index < 1 << 31)
+            .ok_or_else(|| {
+                KeyError::Decode(format!(
+                    "derivation path segment `{segment}` must be hardened, e.g. 44'"
+                ))
+            })?;
+        node = hmac_sha512(
+            &node[32..],
+            &[&[0], &node[..32], &(index | 1 << 31).to_be_bytes()],
+        );
+    }
+    let mut secret = [0u8; SECRET_KEY_LENGTH];
+    secret.copy_from_slice(&node[..SECRET_KEY_LENGTH]);
+    Ok(secret)
+}
+
+fn hmac_sha512(key: &[u8], parts: &[&[u8]]) -> [u8; 64] {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts any key length");
+    for part in parts {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().into()
+}
+
 /// Signs a payload with the provided signing key.
 pub fn sign_payload(signing: &SigningKey, payload: &[u8]) -> Signature {
     signing.sign(payload)
@@ -229,6 +401,37 @@ pub fn verify_signature(
         .map_err(|err| KeyError::Decode(err.to_string()))
 }
 
+/// Attributes a statement to `signing`, signing its [`crate::statement_digest`].
+pub fn sign_entry_submission(
+    signing: &SigningKey,
+    statement: &str,
+    submitted_ms: u64,
+) -> EntrySubmission {
+    let signature = sign_payload(signing, &crate::statement_digest(statement));
+    EntrySubmission {
+        public_key: signing.verifying_key().to_bytes().to_vec(),
+        signature: signature.to_bytes().to_vec(),
+        submitted_ms,
+    }
+}
+
+/// Checks that `submission` carries a valid ed25519 signature over `statement`.
+pub fn verify_entry_submission(
+    statement: &str,
+    submission: &EntrySubmission,
+) -> Result<(), KeyError> {
+    let key_bytes: [u8; 32] = submission
+        .public_key
+        .as_slice()
+        .try_into()
+        .map_err(|_| KeyError::InvalidLength(submission.public_key.len()))?;
+    let verifying =
+        VerifyingKey::from_bytes(&key_bytes).map_err(|err| KeyError::Decode(err.to_string()))?;
+    let signature = Signature::from_slice(&submission.signature)
+        .map_err(|err| KeyError::Decode(err.to_string()))?;
+    verify_signature(&verifying, &crate::statement_digest(statement), &signature)
+}
+
 /// Helper that derives a verifying key from a base64 string and checks the signature.
 pub fn verify_signature_base64(
     public_key_b64: &str,
@@ -239,3 +442,43 @@ pub fn verify_signature_base64(
     let signature = decode_signature_base64(signature_b64)?;
     verify_signature(&verifying, payload, &signature)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mnemonic_derivation_matches_reference_vectors() {
+        let phrase = mnemonic_from_entropy(&[0u8; 16]).unwrap();
+        assert_eq!(phrase, format!("{}about", "abandon ".repeat(11)));
+        assert_eq!(mnemonic_to_entropy(&phrase).unwrap(), vec![0u8; 16]);
+        assert!(mnemonic_to_entropy(&format!("{}abandon", "abandon ".repeat(11))).is_err());
+        assert_eq!(
+            hex::encode(mnemonic_to_seed(&phrase, "TREZOR").unwrap()),
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        );
+
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        assert_eq!(
+            hex::encode(slip10_ed25519_secret(&seed, "m").unwrap()),
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"
+        );
+        assert_eq!(
+            hex::encode(slip10_ed25519_secret(&seed, "m/0'").unwrap()),
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"
+        );
+        assert!(slip10_ed25519_secret(&seed, "m/0").is_err());
+
+        let generated = generate_mnemonic(24).unwrap();
+        let material = derive_mnemonic_key(&generated, "", DEFAULT_MNEMONIC_PATH).unwrap();
+        let path = std::env::temp_dir().join(format!("mnemonic_key_{}.key", std::process::id()));
+        fs::write(
+            &path,
+            mnemonic_key_file(&material.signing, DEFAULT_MNEMONIC_PATH, false),
+        )
+        .unwrap();
+        let loaded = load_or_derive_keypair(&Ed25519KeySource::File(path.clone())).unwrap();
+        assert_eq!(loaded.verifying, material.verifying);
+        fs::remove_file(path).unwrap();
+    }
+}
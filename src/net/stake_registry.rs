@@ -1,16 +1,76 @@
 #![cfg(feature = "net")]
 
 //! Durable stake/balance store for fee enforcement and slashing.
+//!
+//! Stake moves through a bond -> unbond -> withdraw lifecycle. Unbonding stake
+//! stops counting toward quorum weight immediately but stays slashable until
+//! the unbonding period (measured in checkpoint epochs) has elapsed.
+//!
+//! Every change to the amount held by the registry is a [`SupplyOp`] mint,
+//! burn or transfer tagged with a [`SupplyCause`] and journalled in the
+//! [`SupplyLedger`]. `load` and `save` refuse registries whose holdings do not
+//! add up to the tracked total supply. Each checkpoint epoch records a verified
+//! [`SupplySnapshot`], so the journal is only replayed from the last snapshot.
+//!
+//! Stake is always denominated in
+//! [`NATIVE_ASSET`](crate::economics::NATIVE_ASSET). Accounts may also hold
+//! secondary assets, each with its own supply figure and journal lines; holders
+//! move them with ed25519-signed [`AssetTransfer`]s.
+//!
+//! Multi-step settlements (fee debit plus operator and attester credits) are
+//! staged in a [`RegistryTransaction`] so they apply all-or-nothing, and
+//! [`StakeRegistry::transact`] persists them with a single write.
+//!
+//! Optional [`GuardLimits`] cap how fast native units can be credited: per
+//! mint or transfer, per account per UTC day, and minted per UTC day. A
+//! governance-approved [`GuardOverride`] lets one larger credit through.
+//!
+//! A governance-approved [`FreezeOrder`] puts an account under a compliance
+//! hold ([`AccountFreeze`]); frozen accounts cannot be debited, bond, unbond
+//! or withdraw.
+//!
+//! Fees retained by the protocol go through [`StakeRegistry::collect_fee`],
+//! which burns them, credits a fee-sink account, or splits them according to
+//! the registry's [`FeeSinkPolicy`](crate::economics::FeeSinkPolicy).
+//! [`SupplyLedger::fees_in_epoch`] totals them per checkpoint epoch.
+//!
+//! [`StakeRegistry::state_root`] commits to every account in a sparse Merkle
+//! tree keyed by public key; checkpoints embed it so light clients can check
+//! an [`StakeRegistry::account_proof`] with [`verify_account_proof`].
 
+use crate::economics::{FeeAccounts, FeeSinkPolicy, NATIVE_ASSET};
+use crate::net::address_index::AddressIndex;
+use crate::net::governance::{GovernanceUpdate, MembershipPolicy, UpdateKind};
+use crate::net::node_clock::{SharedNodeClock, WallClock};
+use crate::net::sign::verify_signature_base64;
+use crate::net::storage::{note_registry_write, open_registry_store, StoreRevision};
+use crate::sparse_merkle::{smt_key, smt_value, SmtDigest, SparseMerkleProof, SparseMerkleTree};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use blake2::digest::{consts::U32, Digest};
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
-    fs,
-    io::Write,
+    collections::{BTreeMap, HashMap},
     path::Path,
-    time::{SystemTime, UNIX_EPOCH},
 };
 
+/// Unbonding period applied to registries that do not configure one.
+pub const DEFAULT_UNBONDING_EPOCHS: u64 = 7;
+/// Domain prefix of the payload signed for an [`AssetTransfer`].
+pub const ASSET_TRANSFER_DOMAIN: &str = "mfenx.powerhouse.asset-transfer.v1";
+
+const MAX_ASSET_ID_LEN: usize = 32;
+
+const GUARD_DAY_MS: u64 = 86_400_000;
+
+/// Stake leaving the bonded set, withdrawable once `release_epoch` is reached.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PendingUnbond {
+    /// Amount of stake being unbonded.
+    pub amount: u64,
+    /// First checkpoint epoch at which the amount may be withdrawn.
+    pub release_epoch: u64,
+}
+
 /// Account record storing stake and balance.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct StakeAccount {
@@ -20,53 +80,586 @@ pub struct StakeAccount {
     pub stake: u64,
     /// Whether the account is slashed.
     pub slashed: bool,
+    /// Stake waiting out the unbonding period.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unbonding: Vec<PendingUnbond>,
+    /// Balances of secondary assets keyed by asset id.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub assets: BTreeMap<String, u64>,
+    /// Nonce expected on the next signed asset transfer from this account.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub transfer_nonce: u64,
+    /// Compliance hold blocking debits and stake moves, if the account is frozen.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frozen: Option<AccountFreeze>,
+    /// Nonce of the last freeze order applied to this account.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub freeze_nonce: u64,
 }
 
-/// Registry keyed by base64 public key.
+/// Why and on whose authority an account is frozen.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AccountFreeze {
+    /// Reason given in the freeze order.
+    pub reason: String,
+    /// Base64 keys of the governance signers that approved the order.
+    pub authority: Vec<String>,
+    /// Registry epoch at which the freeze was applied.
+    pub since_epoch: u64,
+}
+
+/// Governance order freezing or releasing an account, carried as the
+/// `metadata` of a [`GovernanceUpdate`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FreezeOrder {
+    /// Account the order applies to.
+    pub account: String,
+    /// `true` to freeze, `false` to release.
+    pub freeze: bool,
+    /// Reason recorded with the freeze.
+    pub reason: String,
+    /// Must exceed the account's `freeze_nonce`, so older orders cannot be replayed.
+    pub nonce: u64,
+}
+
+impl FreezeOrder {
+    /// Wraps the order in an unsigned governance update for signers to approve.
+    pub fn to_update(&self) -> GovernanceUpdate {
+        GovernanceUpdate {
+            kind: UpdateKind::Freeze,
+            new_members: Vec::new(),
+            metadata: Some(serde_json::to_value(self).expect("freeze order encodes")),
+            signatures: Vec::new(),
+        }
+    }
+}
+
+fn is_zero(value: &u64) -> bool {
+    *value == 0
+}
+
+impl StakeAccount {
+    /// Spendable balance of `asset`.
+    pub fn balance_of(&self, asset: &str) -> u64 {
+        if asset == NATIVE_ASSET {
+            self.balance
+        } else {
+            self.assets.get(asset).copied().unwrap_or_default()
+        }
+    }
+
+    fn balance_mut(&mut self, asset: &str) -> &mut u64 {
+        if asset == NATIVE_ASSET {
+            &mut self.balance
+        } else {
+            self.assets.entry(asset.to_string()).or_default()
+        }
+    }
+
+    fn holdings_of(&self, asset: &str) -> u128 {
+        if asset == NATIVE_ASSET {
+            self.balance as u128 + self.stake as u128 + self.unbonding_total() as u128
+        } else {
+            self.balance_of(asset) as u128
+        }
+    }
+
+    /// Total stake still waiting out the unbonding period.
+    pub fn unbonding_total(&self) -> u64 {
+        self.unbonding
+            .iter()
+            .fold(0u64, |acc, pending| acc.saturating_add(pending.amount))
+    }
+}
+
+/// Reason attached to every supply mutation.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum SupplyCause {
+    /// Holdings found in a registry written before supply tracking existed.
+    OpeningBalance,
+    /// External funds credited by an operator.
+    Funding,
+    /// Native-mode migration claim.
+    MigrationClaim,
+    /// Expired migration claims swept into a governance-controlled account.
+    MigrationSweep,
+    /// Per-epoch checkpoint signer reward.
+    EpochReward,
+    /// Reward for blob attestation or rollup settlement.
+    Reward,
+    /// Fee paid for a blob, proof or rollup settlement.
+    Fee,
+    /// Refund of a previously burned fee.
+    FeeRefund,
+    /// Part of a fee credited to the fee-sink account.
+    FeeSink,
+    /// Stake forfeited through slashing.
+    Slash,
+    /// Holder-signed transfer between accounts.
+    Transfer,
+}
+
+/// Kind of supply mutation.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SupplyOp {
+    /// New units credited to `to`.
+    Mint,
+    /// Units removed from `from`.
+    Burn,
+    /// Units moved from `from` to `to`.
+    Transfer,
+}
+
+/// Journal line recorded for every supply mutation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SupplyEntry {
+    /// Mutation kind.
+    pub op: SupplyOp,
+    /// Debited account (burns and transfers).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    /// Credited account (mints and transfers).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    /// Amount moved.
+    pub amount: u64,
+    /// Secondary asset moved; `None` means [`NATIVE_ASSET`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset: Option<String>,
+    /// Why the mutation happened.
+    pub cause: SupplyCause,
+    /// Registry checkpoint epoch at the time of the mutation.
+    pub epoch: u64,
+}
+
+impl SupplyEntry {
+    /// Asset id this entry moved.
+    pub fn asset_id(&self) -> &str {
+        self.asset.as_deref().unwrap_or(NATIVE_ASSET)
+    }
+}
+
+/// Total supply and the mint/burn/transfer journal that produced it.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SupplyLedger {
+    /// Native units currently held across balances, bonded and unbonding stake.
+    pub total_supply: u64,
+    /// Supply of each secondary asset keyed by asset id.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub asset_supply: BTreeMap<String, u64>,
+    /// Every supply mutation in application order.
+    pub entries: Vec<SupplyEntry>,
+    /// Supply last verified against the journal; replays start from here.
+    #[serde(default, skip_serializing_if = "SupplySnapshot::is_empty")]
+    pub snapshot: SupplySnapshot,
+}
+
+/// Supply figures verified against the first `entries` journal lines.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct SupplySnapshot {
+    /// Number of journal entries the snapshot covers.
+    pub entries: usize,
+    /// Native supply after those entries.
+    pub total_supply: u64,
+    /// Secondary asset supply after those entries.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub asset_supply: BTreeMap<String, u64>,
+}
+
+impl SupplySnapshot {
+    fn is_empty(&self) -> bool {
+        self.entries == 0
+    }
+
+    /// Supply of `asset` at the snapshot.
+    pub fn supply_of(&self, asset: &str) -> u64 {
+        if asset == NATIVE_ASSET {
+            self.total_supply
+        } else {
+            self.asset_supply.get(asset).copied().unwrap_or_default()
+        }
+    }
+}
+
+impl SupplyLedger {
+    /// Tracked supply of `asset`.
+    pub fn supply_of(&self, asset: &str) -> u64 {
+        if asset == NATIVE_ASSET {
+            self.total_supply
+        } else {
+            self.asset_supply.get(asset).copied().unwrap_or_default()
+        }
+    }
+
+    /// Native asset followed by every secondary asset with a tracked supply.
+    pub fn asset_ids(&self) -> Vec<String> {
+        std::iter::once(NATIVE_ASSET.to_string())
+            .chain(self.asset_supply.keys().cloned())
+            .collect()
+    }
+
+    /// Total of `asset` minted per cause.
+    pub fn minted_by_cause(&self, asset: &str) -> BTreeMap<SupplyCause, u64> {
+        self.totals_by_cause(SupplyOp::Mint, asset)
+    }
+
+    /// Total of `asset` burned per cause.
+    pub fn burned_by_cause(&self, asset: &str) -> BTreeMap<SupplyCause, u64> {
+        self.totals_by_cause(SupplyOp::Burn, asset)
+    }
+
+    fn supply_mut(&mut self, asset: &str) -> &mut u64 {
+        if asset == NATIVE_ASSET {
+            &mut self.total_supply
+        } else {
+            self.asset_supply.entry(asset.to_string()).or_default()
+        }
+    }
+
+    /// Native fees burned, sunk and refunded in registry epoch `epoch`.
+    pub fn fees_in_epoch(&self, epoch: u64) -> EpochFees {
+        let mut fees = EpochFees {
+            epoch,
+            ..EpochFees::default()
+        };
+        for entry in self
+            .entries
+            .iter()
+            .filter(|entry| entry.epoch == epoch && entry.asset.is_none())
+        {
+            let total = match (entry.op, entry.cause) {
+                (SupplyOp::Burn, SupplyCause::Fee) => &mut fees.burned,
+                (SupplyOp::Transfer, SupplyCause::FeeSink) => &mut fees.sunk,
+                (_, SupplyCause::FeeRefund) => &mut fees.refunded,
+                _ => continue,
+            };
+            *total = total.saturating_add(entry.amount);
+        }
+        fees
+    }
+
+    fn totals_by_cause(&self, op: SupplyOp, asset: &str) -> BTreeMap<SupplyCause, u64> {
+        let mut totals = BTreeMap::new();
+        for entry in self
+            .entries
+            .iter()
+            .filter(|entry| entry.op == op && entry.asset_id() == asset)
+        {
+            let total: &mut u64 = totals.entry(entry.cause).or_default();
+            *total = total.saturating_add(entry.amount);
+        }
+        totals
+    }
+}
+
+/// Native fee totals journalled during one registry epoch.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct EpochFees {
+    /// Registry epoch the fees were journalled in.
+    pub epoch: u64,
+    /// Fees removed from supply.
+    pub burned: u64,
+    /// Fees credited to the fee-sink account.
+    pub sunk: u64,
+    /// Fees returned to payers.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub refunded: u64,
+}
+
+/// Registry keyed by base64 public key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StakeRegistry {
     accounts: HashMap<String, StakeAccount>,
+    /// Supply accounting for every balance and stake mutation.
+    #[serde(default)]
+    supply: SupplyLedger,
+    /// Latest checkpoint epoch observed by the registry.
+    #[serde(default)]
+    epoch: u64,
+    /// Number of checkpoint epochs unbonding stake stays locked.
+    #[serde(default = "default_unbonding_epochs")]
+    unbonding_epochs: u64,
+    /// Rate-of-change limits on native credits.
+    #[serde(default, skip_serializing_if = "GuardLimits::is_unset")]
+    guards: GuardLimits,
+    /// Counters the limits are checked against.
+    #[serde(default, skip_serializing_if = "GuardWindow::is_empty")]
+    guard_window: GuardWindow,
+    /// Where retained fees go.
+    #[serde(default, skip_serializing_if = "FeeSinkPolicy::is_burn")]
+    fee_sink: FeeSinkPolicy,
+    #[serde(skip)]
+    grant: Option<OverrideGrant>,
+    /// Time source that dates the guard window.
+    #[serde(skip, default = "WallClock::shared")]
+    clock: SharedNodeClock,
+    #[serde(skip)]
+    #[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+    revision: StoreRevision,
+}
+
+impl Default for StakeRegistry {
+    fn default() -> Self {
+        Self::from_parts(
+            HashMap::new(),
+            SupplyLedger::default(),
+            0,
+            DEFAULT_UNBONDING_EPOCHS,
+        )
+    }
+}
+
+/// Sparse Merkle key of the account owned by `pk`.
+pub fn account_state_key(pk: &str) -> SmtDigest {
+    smt_key(pk.as_bytes())
+}
+
+/// Sparse Merkle leaf value committing to `account`.
+pub fn account_state_value(account: &StakeAccount) -> SmtDigest {
+    smt_value(&serde_json::to_vec(account).expect("stake account serialises"))
+}
+
+/// Checks that `proof` shows `pk` holding `account` (or no account) under a
+/// registry state root.
+pub fn verify_account_proof(
+    root: &SmtDigest,
+    pk: &str,
+    account: Option<&StakeAccount>,
+    proof: &SparseMerkleProof,
+) -> Result<(), String> {
+    if proof.key != hex::encode(account_state_key(pk)) {
+        return Err(format!("proof is not for account {pk}"));
+    }
+    let expected = account.map(|acct| hex::encode(account_state_value(acct)));
+    if proof.value != expected {
+        return Err(format!("proof does not commit to the given state of {pk}"));
+    }
+    proof.verify(root).map_err(|err| err.to_string())
+}
+
+/// Rate-of-change limits on native credits; `None` leaves a limit off.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct GuardLimits {
+    /// Largest amount a single mint or transfer may credit to one account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_single_credit: Option<u64>,
+    /// Largest amount credited to one account per UTC day.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_account_daily_delta: Option<u64>,
+    /// Largest amount minted across all accounts per UTC day.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_daily_mint: Option<u64>,
+}
+
+impl GuardLimits {
+    /// True when every limit is off.
+    pub fn is_unset(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Credits counted against [`GuardLimits`] for the current UTC day, plus the
+/// overrides already spent.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct GuardWindow {
+    /// Days since the Unix epoch the counters belong to.
+    pub day: u64,
+    /// Native units minted during `day`.
+    pub minted: u64,
+    /// Native units credited to each account during `day`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub credited: BTreeMap<String, u64>,
+    /// Ids of overrides already granted; each is accepted once.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub used_overrides: Vec<String>,
+}
+
+impl GuardWindow {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Governance-approved permission to credit `account` up to `amount` past the
+/// [`GuardLimits`], carried as the `metadata` of a [`GovernanceUpdate`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GuardOverride {
+    /// Account the override applies to.
+    pub account: String,
+    /// Total native units the override lets through.
+    pub amount: u64,
+    /// Last registry epoch at which the override may be granted.
+    pub expires_at_epoch: u64,
+    /// Distinguishes otherwise identical overrides.
+    pub nonce: u64,
+}
+
+impl GuardOverride {
+    /// Wraps the override in an unsigned governance update for signers to approve.
+    pub fn to_update(&self) -> GovernanceUpdate {
+        GovernanceUpdate {
+            kind: UpdateKind::GuardOverride,
+            new_members: Vec::new(),
+            metadata: Some(serde_json::to_value(self).expect("guard override encodes")),
+            signatures: Vec::new(),
+        }
+    }
+
+    /// Checks the update's kind and approvals against `policy` and decodes
+    /// the override.
+    pub fn from_update(
+        update: &GovernanceUpdate,
+        policy: &dyn MembershipPolicy,
+    ) -> Result<Self, String> {
+        update
+            .expect_kind(UpdateKind::GuardOverride)
+            .map_err(|err| err.to_string())?;
+        let metadata = update
+            .metadata
+            .clone()
+            .ok_or_else(|| "guard override update carries no metadata".to_string())?;
+        let decoded: Self = serde_json::from_value(metadata)
+            .map_err(|err| format!("invalid guard override: {err}"))?;
+        policy
+            .verify_update(update)
+            .map_err(|err| format!("guard override not approved: {err}"))?;
+        Ok(decoded)
+    }
+
+    /// Hex digest identifying the override.
+    pub fn id(&self) -> String {
+        let bytes = serde_json::to_vec(self).expect("guard override encodes");
+        hex::encode(blake2::Blake2b::<U32>::digest(bytes))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct OverrideGrant {
+    account: String,
+    remaining: u64,
+}
+
+fn default_unbonding_epochs() -> u64 {
+    DEFAULT_UNBONDING_EPOCHS
 }
 
 impl StakeRegistry {
-    /// Load from JSON; missing file -> empty registry.
+    pub(crate) fn from_parts(
+        accounts: HashMap<String, StakeAccount>,
+        supply: SupplyLedger,
+        epoch: u64,
+        unbonding_epochs: u64,
+    ) -> Self {
+        Self {
+            accounts,
+            supply,
+            epoch,
+            unbonding_epochs,
+            guards: GuardLimits::default(),
+            guard_window: GuardWindow::default(),
+            fee_sink: FeeSinkPolicy::Burn,
+            grant: None,
+            clock: WallClock::shared(),
+            revision: StoreRevision::default(),
+        }
+    }
+
+    /// Replaces the wall clock that dates the [`GuardWindow`], so the daily
+    /// limits follow the node's clock rather than the host's.
+    pub fn set_clock(&mut self, clock: SharedNodeClock) {
+        self.clock = clock;
+    }
+
+    /// UTC day the guard counters are currently kept for.
+    fn guard_day(&self) -> u64 {
+        self.clock.now_ms() / GUARD_DAY_MS
+    }
+
+    #[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+    pub(crate) fn with_guard_state(mut self, guards: GuardLimits, window: GuardWindow) -> Self {
+        self.guards = guards;
+        self.guard_window = window;
+        self
+    }
+
+    #[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+    pub(crate) fn with_fee_sink(mut self, fee_sink: FeeSinkPolicy) -> Self {
+        self.fee_sink = fee_sink;
+        self
+    }
+
+    #[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+    pub(crate) fn revision(&self) -> &StoreRevision {
+        &self.revision
+    }
+
+    /// Load from JSON or SQLite (chosen by extension); missing store -> empty registry.
+    ///
+    /// Registries written before supply tracking get their current holdings
+    /// recorded as an opening-balance mint. Fails if the books do not balance.
     pub fn load(path: &Path) -> Result<Self, String> {
-        if !path.exists() {
+        let Some(mut registry) = open_registry_store(path)?.load_registry()? else {
             return Ok(Self::default());
+        };
+        if registry.supply.entries.is_empty() && registry.supply.total_supply == 0 {
+            let holdings = u64::try_from(registry.holdings())
+                .map_err(|_| "registry holdings exceed u64 supply".to_string())?;
+            if holdings > 0 {
+                registry.supply.total_supply = holdings;
+                registry.record(
+                    SupplyOp::Mint,
+                    None,
+                    None,
+                    NATIVE_ASSET,
+                    holdings,
+                    SupplyCause::OpeningBalance,
+                );
+            }
         }
-        let bytes = fs::read(path).map_err(|e| e.to_string())?;
-        serde_json::from_slice(&bytes).map_err(|e| e.to_string())
+        registry.check_invariants()?;
+        Ok(registry)
     }
 
-    /// Persist to JSON.
+    /// Persist through the store for `path`; refuses to write a registry whose
+    /// books do not balance.
+    ///
+    /// SQLite stores also refuse the write when another writer committed after
+    /// this registry was loaded.
     pub fn save(&self, path: &Path) -> Result<(), String> {
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        self.check_invariants()?;
+        open_registry_store(path)?.save_registry(self)?;
+        note_registry_write();
+        Ok(())
+    }
+
+    /// Start staging mutations that [`RegistryTransaction::commit`] applies
+    /// all-or-nothing.
+    pub fn transaction(&mut self) -> RegistryTransaction<'_> {
+        RegistryTransaction {
+            registry: self,
+            ops: Vec::new(),
         }
-        let data = serde_json::to_vec_pretty(self).map_err(|e| e.to_string())?;
-        let nonce = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_nanos();
-        let temp_path = path.with_extension(format!("tmp-{}-{nonce}", std::process::id()));
-        let write_result = (|| -> Result<(), String> {
-            let mut file = fs::OpenOptions::new()
-                .write(true)
-                .create_new(true)
-                .open(&temp_path)
-                .map_err(|e| e.to_string())?;
-            file.write_all(&data).map_err(|e| e.to_string())?;
-            file.sync_all().map_err(|e| e.to_string())?;
-            fs::rename(&temp_path, path).map_err(|e| e.to_string())
-        })();
-        if write_result.is_err() {
-            let _ = fs::remove_file(&temp_path);
-        }
-        write_result
-    }
-
-    /// Ensure an account exists and return mutable ref.
-    pub fn ensure_account(&mut self, pk: &str) -> &mut StakeAccount {
+    }
+
+    /// Load the registry at `path`, commit the mutations staged by `stage`, and
+    /// persist the result with a single write.
+    ///
+    /// Nothing is written if `stage` returns an error or any mutation fails.
+    pub fn transact<T>(
+        path: &Path,
+        stage: impl FnOnce(&mut RegistryTransaction<'_>) -> Result<T, String>,
+    ) -> Result<T, String> {
+        let mut registry = Self::load(path)?;
+        let mut tx = registry.transaction();
+        let value = stage(&mut tx)?;
+        tx.commit()?;
+        registry.save(path)?;
+        Ok(value)
+    }
+
+    fn ensure_account(&mut self, pk: &str) -> &mut StakeAccount {
         self.accounts.entry(pk.to_string()).or_default()
     }
 
@@ -80,52 +673,529 @@ impl StakeRegistry {
         &self.accounts
     }
 
-    /// Return stake weight if not slashed.
-    pub fn stake_for(&self, pk: &str) -> Option<u64> {
+    /// Sparse Merkle tree mapping every account key to its state.
+    pub fn state_tree(&self) -> SparseMerkleTree {
+        let mut tree = SparseMerkleTree::new();
+        for (pk, acct) in &self.accounts {
+            tree.insert(account_state_key(pk), account_state_value(acct));
+        }
+        tree
+    }
+
+    /// Root of [`Self::state_tree`].
+    pub fn state_root(&self) -> SmtDigest {
+        self.state_tree().root()
+    }
+
+    /// Proves the state of `pk`'s account, or that it does not exist.
+    pub fn account_proof(&self, pk: &str) -> SparseMerkleProof {
+        self.state_tree().prove(&account_state_key(pk))
+    }
+
+    /// Supply accounting journal.
+    pub fn supply(&self) -> &SupplyLedger {
+        &self.supply
+    }
+
+    /// Sum of native balances, bonded and unbonding stake across all accounts.
+    pub fn holdings(&self) -> u128 {
+        self.holdings_of(NATIVE_ASSET)
+    }
+
+    /// Sum of `asset` held across all accounts.
+    pub fn holdings_of(&self, asset: &str) -> u128 {
+        self.accounts
+            .values()
+            .map(|acct| acct.holdings_of(asset))
+            .sum()
+    }
+
+    /// Spendable balance of `asset` held by `pk`.
+    pub fn balance_of(&self, pk: &str, asset: &str) -> u64 {
         self.accounts
             .get(pk)
-            .filter(|acct| !acct.slashed)
-            .map(|acct| acct.stake)
+            .map(|acct| acct.balance_of(asset))
+            .unwrap_or_default()
     }
 
-    /// Credit a reward or fee refund to balance.
-    pub fn credit_reward(&mut self, pk: &str, amount: u64) {
-        let acct = self.ensure_account(pk);
-        acct.balance = acct.balance.saturating_add(amount);
+    /// Verify, for every asset, that holdings equal the tracked supply and that
+    /// the journal replays from the last [`SupplySnapshot`] to the same figure.
+    pub fn check_invariants(&self) -> Result<(), String> {
+        let snapshot = &self.supply.snapshot;
+        let Some(tail) = self.supply.entries.get(snapshot.entries..) else {
+            return Err(format!(
+                "supply snapshot covers {} entries but the journal has {}",
+                snapshot.entries,
+                self.supply.entries.len()
+            ));
+        };
+        let mut assets = self.supply.asset_ids();
+        for acct in self.accounts.values() {
+            assets.extend(acct.assets.keys().cloned());
+        }
+        assets.sort();
+        assets.dedup();
+        for asset in &assets {
+            let holdings = self.holdings_of(asset);
+            let supply = self.supply.supply_of(asset) as u128;
+            if holdings != supply {
+                return Err(format!(
+                    "supply invariant violated for {asset}: holdings={holdings} total_supply={supply}"
+                ));
+            }
+            let mut replayed = snapshot.supply_of(asset) as i128;
+            for entry in tail.iter().filter(|entry| entry.asset_id() == asset) {
+                match entry.op {
+                    SupplyOp::Mint => replayed += entry.amount as i128,
+                    SupplyOp::Burn => replayed -= entry.amount as i128,
+                    SupplyOp::Transfer => {}
+                }
+            }
+            if replayed != supply as i128 {
+                return Err(format!(
+                    "supply invariant violated for {asset}: journal={replayed} total_supply={supply}"
+                ));
+            }
+        }
+        Ok(())
     }
 
-    /// Debit fee from balance.
-    pub fn debit_fee(&mut self, pk: &str, fee: u64) -> Result<(), String> {
-        let acct = self.ensure_account(pk);
-        if acct.balance < fee {
-            return Err("insufficient balance".into());
+    /// Create `amount` new native units in `pk`'s balance.
+    pub fn mint(&mut self, pk: &str, amount: u64, cause: SupplyCause) -> Result<(), String> {
+        self.mint_asset(pk, NATIVE_ASSET, amount, cause)
+    }
+
+    /// Destroy `amount` native units from `pk`'s balance.
+    pub fn burn(&mut self, pk: &str, amount: u64, cause: SupplyCause) -> Result<(), String> {
+        self.burn_asset(pk, NATIVE_ASSET, amount, cause)
+    }
+
+    /// Move `amount` native units from `from`'s balance to `to`'s balance.
+    pub fn transfer(
+        &mut self,
+        from: &str,
+        to: &str,
+        amount: u64,
+        cause: SupplyCause,
+    ) -> Result<(), String> {
+        self.transfer_asset(from, to, NATIVE_ASSET, amount, cause)
+    }
+
+    /// Create `amount` new units of `asset` in `pk`'s balance.
+    pub fn mint_asset(
+        &mut self,
+        pk: &str,
+        asset: &str,
+        amount: u64,
+        cause: SupplyCause,
+    ) -> Result<(), String> {
+        validate_asset_id(asset)?;
+        let supply = self
+            .supply
+            .supply_of(asset)
+            .checked_add(amount)
+            .ok_or_else(|| "mint would overflow total supply".to_string())?;
+        let granted = self.check_credit(pk, asset, amount, true)?;
+        let balance = self.ensure_account(pk).balance_mut(asset);
+        *balance = balance
+            .checked_add(amount)
+            .ok_or_else(|| "mint would overflow account balance".to_string())?;
+        *self.supply.supply_mut(asset) = supply;
+        self.note_credit(pk, asset, amount, true, granted);
+        self.record(SupplyOp::Mint, None, Some(pk), asset, amount, cause);
+        Ok(())
+    }
+
+    /// Destroy `amount` units of `asset` from `pk`'s balance.
+    pub fn burn_asset(
+        &mut self,
+        pk: &str,
+        asset: &str,
+        amount: u64,
+        cause: SupplyCause,
+    ) -> Result<(), String> {
+        self.debit(pk, asset, amount)?;
+        *self.supply.supply_mut(asset) -= amount;
+        self.record(SupplyOp::Burn, Some(pk), None, asset, amount, cause);
+        Ok(())
+    }
+
+    /// Move `amount` units of `asset` from `from`'s balance to `to`'s balance.
+    pub fn transfer_asset(
+        &mut self,
+        from: &str,
+        to: &str,
+        asset: &str,
+        amount: u64,
+        cause: SupplyCause,
+    ) -> Result<(), String> {
+        let granted = self.check_credit(to, asset, amount, false)?;
+        self.debit(from, asset, amount)?;
+        let balance = self.ensure_account(to).balance_mut(asset);
+        *balance = balance.saturating_add(amount);
+        self.note_credit(to, asset, amount, false, granted);
+        self.record(
+            SupplyOp::Transfer,
+            Some(from),
+            Some(to),
+            asset,
+            amount,
+            cause,
+        );
+        Ok(())
+    }
+
+    /// Nonce the next signed transfer from `pk` must carry.
+    pub fn transfer_nonce(&self, pk: &str) -> u64 {
+        self.accounts
+            .get(pk)
+            .map(|acct| acct.transfer_nonce)
+            .unwrap_or_default()
+    }
+
+    /// Verify and apply a holder-signed transfer of a secondary asset.
+    pub fn apply_signed_transfer(&mut self, transfer: &AssetTransfer) -> Result<(), String> {
+        if transfer.asset == NATIVE_ASSET {
+            return Err("native units move on the native chain, not by asset transfer".into());
         }
-        acct.balance -= fee;
+        transfer.verify()?;
+        let expected = self.transfer_nonce(&transfer.from);
+        if transfer.nonce != expected {
+            return Err(format!(
+                "stale transfer nonce {} (expected {expected})",
+                transfer.nonce
+            ));
+        }
+        self.transfer_asset(
+            &transfer.from,
+            &transfer.to,
+            &transfer.asset,
+            transfer.amount,
+            SupplyCause::Transfer,
+        )?;
+        self.ensure_account(&transfer.from).transfer_nonce = expected + 1;
         Ok(())
     }
 
-    /// Debit fee from payer and credit operator reward.
-    pub fn transfer_fee(&mut self, payer: &str, operator: &str, fee: u64) -> Result<(), String> {
-        self.debit_fee(payer, fee)?;
-        self.credit_reward(operator, fee);
+    /// Rate-of-change limits on native credits.
+    pub fn guard_limits(&self) -> GuardLimits {
+        self.guards
+    }
+
+    /// Credits counted against the limits so far.
+    pub fn guard_window(&self) -> &GuardWindow {
+        &self.guard_window
+    }
+
+    /// Replace the rate-of-change limits; counters already recorded are kept.
+    pub fn set_guard_limits(&mut self, limits: GuardLimits) {
+        self.guards = limits;
+    }
+
+    /// Policy deciding where retained fees go.
+    pub fn fee_sink(&self) -> &FeeSinkPolicy {
+        &self.fee_sink
+    }
+
+    /// Replace the fee policy; fees already collected stay where they went.
+    pub fn set_fee_sink(&mut self, policy: FeeSinkPolicy) -> Result<(), String> {
+        policy.validate()?;
+        self.fee_sink = policy;
         Ok(())
     }
 
-    /// Slash stake to zero and mark slashed.
-    pub fn slash(&mut self, pk: &str) {
+    /// Debit `fee` units of `asset` from `payer` and route them by the fee
+    /// policy: the burned part as a [`SupplyCause::Fee`] burn, the rest as a
+    /// [`SupplyCause::FeeSink`] transfer to the sink account. All or nothing.
+    pub fn collect_fee(&mut self, payer: &str, asset: &str, fee: u64) -> Result<(), String> {
+        let mut tx = self.transaction();
+        tx.collect_fee(payer, asset, fee);
+        tx.commit()
+    }
+
+    /// Verify a guard override's approvals against `policy` and grant it.
+    ///
+    /// Credits to the override's account totalling its amount then pass the
+    /// limits until the registry is dropped; they do not count toward the
+    /// daily totals.
+    pub fn apply_override_update(
+        &mut self,
+        update: &GovernanceUpdate,
+        policy: &dyn MembershipPolicy,
+    ) -> Result<GuardOverride, String> {
+        let grant = GuardOverride::from_update(update, policy)?;
+        self.grant_override(&grant)?;
+        Ok(grant)
+    }
+
+    /// Grants an override already approved by governance.
+    ///
+    /// Each override is accepted once and only up to its expiry epoch; the
+    /// spent id is persisted with the registry.
+    pub(crate) fn grant_override(&mut self, grant: &GuardOverride) -> Result<(), String> {
+        if self.epoch > grant.expires_at_epoch {
+            return Err(format!(
+                "guard override expired at epoch {} (registry epoch {})",
+                grant.expires_at_epoch, self.epoch
+            ));
+        }
+        let id = grant.id();
+        if self.guard_window.used_overrides.contains(&id) {
+            return Err(format!("guard override {id} was already used"));
+        }
+        self.guard_window.used_overrides.push(id);
+        self.grant = Some(OverrideGrant {
+            account: grant.account.clone(),
+            remaining: grant.amount,
+        });
+        Ok(())
+    }
+
+    /// Checks a native credit against the limits; returns whether the active
+    /// override covers it instead.
+    fn check_credit(&self, pk: &str, asset: &str, amount: u64, mint: bool) -> Result<bool, String> {
+        if asset != NATIVE_ASSET || self.guards.is_unset() {
+            return Ok(false);
+        }
+        if self
+            .grant
+            .as_ref()
+            .is_some_and(|grant| grant.account == pk && grant.remaining >= amount)
+        {
+            return Ok(true);
+        }
+        let window = &self.guard_window;
+        let today = window.day == self.guard_day();
+        let credited = if today {
+            window.credited.get(pk).copied().unwrap_or_default()
+        } else {
+            0
+        };
+        let minted = if today { window.minted } else { 0 };
+        let exceeded = |limit: Option<u64>, used: u64| {
+            limit.filter(|limit| used.saturating_add(amount) > *limit)
+        };
+        if let Some(limit) = exceeded(self.guards.max_single_credit, 0) {
+            return Err(format!(
+                "credit of {amount} to {pk} exceeds max_single_credit {limit}; a governance override is required"
+            ));
+        }
+        if let Some(limit) = exceeded(self.guards.max_account_daily_delta, credited) {
+            return Err(format!(
+                "credit of {amount} to {pk} exceeds max_account_daily_delta {limit} ({credited} already credited today)"
+            ));
+        }
+        if let Some(limit) = exceeded(self.guards.max_daily_mint.filter(|_| mint), minted) {
+            return Err(format!(
+                "mint of {amount} exceeds max_daily_mint {limit} ({minted} already minted today)"
+            ));
+        }
+        Ok(false)
+    }
+
+    fn note_credit(&mut self, pk: &str, asset: &str, amount: u64, mint: bool, granted: bool) {
+        if asset != NATIVE_ASSET || self.guards.is_unset() {
+            return;
+        }
+        if granted {
+            if let Some(grant) = self.grant.as_mut() {
+                grant.remaining -= amount;
+            }
+            return;
+        }
+        let day = self.guard_day();
+        let window = &mut self.guard_window;
+        if window.day != day {
+            window.day = day;
+            window.minted = 0;
+            window.credited.clear();
+        }
+        let credited = window.credited.entry(pk.to_string()).or_default();
+        *credited = credited.saturating_add(amount);
+        if mint {
+            window.minted = window.minted.saturating_add(amount);
+        }
+    }
+
+    /// Verify a freeze order's approvals against `policy` and apply it.
+    ///
+    /// The approving signers are recorded as the freeze authority.
+    pub fn apply_freeze_update(
+        &mut self,
+        update: &GovernanceUpdate,
+        policy: &dyn MembershipPolicy,
+    ) -> Result<FreezeOrder, String> {
+        update
+            .expect_kind(UpdateKind::Freeze)
+            .map_err(|err| err.to_string())?;
+        let metadata = update
+            .metadata
+            .clone()
+            .ok_or_else(|| "freeze order update carries no metadata".to_string())?;
+        let order: FreezeOrder = serde_json::from_value(metadata)
+            .map_err(|err| format!("invalid freeze order: {err}"))?;
+        policy
+            .verify_update(update)
+            .map_err(|err| format!("freeze order not approved: {err}"))?;
+        let epoch = self.epoch;
+        let acct = self.ensure_account(&order.account);
+        if order.nonce <= acct.freeze_nonce {
+            return Err(format!(
+                "stale freeze order nonce {} (last applied {})",
+                order.nonce, acct.freeze_nonce
+            ));
+        }
+        acct.freeze_nonce = order.nonce;
+        acct.frozen = order.freeze.then(|| {
+            let mut authority = update
+                .signatures
+                .iter()
+                .map(|approval| approval.signer.clone())
+                .collect::<Vec<_>>();
+            authority.sort();
+            authority.dedup();
+            AccountFreeze {
+                reason: order.reason.clone(),
+                authority,
+                since_epoch: epoch,
+            }
+        });
+        Ok(order)
+    }
+
+    /// Accounts under a compliance hold, sorted by public key.
+    pub fn frozen_accounts(&self) -> Vec<(&str, &AccountFreeze)> {
+        let mut frozen = self
+            .accounts
+            .iter()
+            .filter_map(|(pk, acct)| acct.frozen.as_ref().map(|hold| (pk.as_str(), hold)))
+            .collect::<Vec<_>>();
+        frozen.sort_by_key(|(pk, _)| *pk);
+        frozen
+    }
+
+    /// Fails if `pk` is under a compliance hold.
+    fn check_not_frozen(&self, pk: &str) -> Result<(), String> {
+        match self.accounts.get(pk).and_then(|acct| acct.frozen.as_ref()) {
+            Some(hold) => Err(format!("account is frozen: {}", hold.reason)),
+            None => Ok(()),
+        }
+    }
+
+    fn debit(&mut self, pk: &str, asset: &str, amount: u64) -> Result<(), String> {
+        self.check_not_frozen(pk)?;
+        let balance = self
+            .accounts
+            .get_mut(pk)
+            .map(|acct| acct.balance_mut(asset))
+            .filter(|balance| **balance >= amount)
+            .ok_or_else(|| "insufficient balance".to_string())?;
+        *balance -= amount;
+        Ok(())
+    }
+
+    fn record(
+        &mut self,
+        op: SupplyOp,
+        from: Option<&str>,
+        to: Option<&str>,
+        asset: &str,
+        amount: u64,
+        cause: SupplyCause,
+    ) {
+        self.supply.entries.push(SupplyEntry {
+            op,
+            from: from.map(str::to_string),
+            to: to.map(str::to_string),
+            amount,
+            asset: (asset != NATIVE_ASSET).then(|| asset.to_string()),
+            cause,
+            epoch: self.epoch,
+        });
+    }
+
+    /// Latest checkpoint epoch observed by the registry.
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Advance to checkpoint `epoch`; older epochs are ignored.
+    ///
+    /// A new epoch also checkpoints the supply snapshot when the books balance;
+    /// otherwise the previous snapshot stays and the next check reports the gap.
+    pub fn advance_epoch(&mut self, epoch: u64) {
+        if epoch > self.epoch {
+            let _ = self.checkpoint_supply();
+        }
+        self.epoch = self.epoch.max(epoch);
+    }
+
+    /// Verify the books and record the current supply as the snapshot later
+    /// checks replay from.
+    pub fn checkpoint_supply(&mut self) -> Result<(), String> {
+        self.check_invariants()?;
+        self.supply.snapshot = SupplySnapshot {
+            entries: self.supply.entries.len(),
+            total_supply: self.supply.total_supply,
+            asset_supply: self.supply.asset_supply.clone(),
+        };
+        Ok(())
+    }
+
+    /// Number of checkpoint epochs unbonding stake stays locked.
+    pub fn unbonding_epochs(&self) -> u64 {
+        self.unbonding_epochs
+    }
+
+    /// Set the unbonding period in checkpoint epochs.
+    ///
+    /// Unbonds already in flight keep the release epoch they were created with.
+    pub fn set_unbonding_epochs(&mut self, epochs: u64) {
+        self.unbonding_epochs = epochs;
+    }
+
+    /// Return bonded stake weight if not slashed; unbonding stake is excluded.
+    pub fn stake_for(&self, pk: &str) -> Option<u64> {
+        self.accounts
+            .get(pk)
+            .filter(|acct| !acct.slashed)
+            .map(|acct| acct.stake)
+    }
+
+    /// Burn bonded and unbonding stake, mark the account slashed, and return
+    /// the amount burned.
+    ///
+    /// Balance is not touched.
+    pub fn slash(&mut self, pk: &str) -> u64 {
         let acct = self.ensure_account(pk);
+        let burned = acct.stake.saturating_add(acct.unbonding_total());
         acct.stake = 0;
+        acct.unbonding.clear();
         acct.slashed = true;
-    }
-    /// Credit external funds to balance.
-    pub fn fund_balance(&mut self, pk: &str, amount: u64) {
-        let acct = self.ensure_account(pk);
-        acct.balance = acct.balance.saturating_add(amount);
+        if burned > 0 {
+            self.supply.total_supply -= burned;
+            self.record(
+                SupplyOp::Burn,
+                Some(pk),
+                None,
+                NATIVE_ASSET,
+                burned,
+                SupplyCause::Slash,
+            );
+        }
+        burned
     }
 
     /// Move balance into bonded stake.
-    pub fn bond_from_balance(&mut self, pk: &str, amount: u64) -> Result<(), String> {
+    ///
+    /// Bonding, unbonding and withdrawal move units inside one account and
+    /// leave total supply unchanged.
+    pub fn bond(&mut self, pk: &str, amount: u64) -> Result<(), String> {
+        self.check_not_frozen(pk)?;
         let acct = self.ensure_account(pk);
+        if acct.slashed {
+            return Err("account is slashed".into());
+        }
         if acct.balance < amount {
             return Err("insufficient balance to bond".into());
         }
@@ -134,21 +1204,442 @@ impl StakeRegistry {
         Ok(())
     }
 
-    /// Unbond stake back to balance.
-    pub fn unbond(&mut self, pk: &str, amount: u64) -> Result<(), String> {
-        let acct = self.ensure_account(pk);
-        if acct.stake < amount {
+    /// Start unbonding stake; returns the epoch at which it becomes withdrawable.
+    pub fn begin_unbond(&mut self, pk: &str, amount: u64) -> Result<u64, String> {
+        self.check_not_frozen(pk)?;
+        let release_epoch = self.epoch.saturating_add(self.unbonding_epochs);
+        let acct = self
+            .accounts
+            .get_mut(pk)
+            .ok_or_else(|| "unknown account".to_string())?;
+        if acct.slashed {
+            return Err("account is slashed".into());
+        }
+        if amount == 0 || acct.stake < amount {
             return Err("insufficient stake to unbond".into());
         }
         acct.stake -= amount;
-        acct.balance = acct.balance.saturating_add(amount);
+        match acct.unbonding.last_mut() {
+            Some(last) if last.release_epoch == release_epoch => {
+                last.amount = last.amount.saturating_add(amount);
+            }
+            _ => acct.unbonding.push(PendingUnbond {
+                amount,
+                release_epoch,
+            }),
+        }
+        Ok(release_epoch)
+    }
+
+    /// Move every matured unbond back to balance; returns the amount withdrawn.
+    pub fn withdraw(&mut self, pk: &str) -> Result<u64, String> {
+        self.check_not_frozen(pk)?;
+        let epoch = self.epoch;
+        let acct = self
+            .accounts
+            .get_mut(pk)
+            .ok_or_else(|| "unknown account".to_string())?;
+        if acct.slashed {
+            return Err("account is slashed".into());
+        }
+        let mut withdrawn = 0u64;
+        acct.unbonding.retain(|pending| {
+            if pending.release_epoch <= epoch {
+                withdrawn = withdrawn.saturating_add(pending.amount);
+                false
+            } else {
+                true
+            }
+        });
+        if withdrawn == 0 {
+            return Err(match acct.unbonding.first() {
+                Some(next) => format!(
+                    "no matured unbonds (next release at epoch {}, current {epoch})",
+                    next.release_epoch
+                ),
+                None => "no pending unbonds".into(),
+            });
+        }
+        acct.balance = acct.balance.saturating_add(withdrawn);
+        Ok(withdrawn)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum StagedOp {
+    Mint {
+        pk: String,
+        asset: String,
+        amount: u64,
+        cause: SupplyCause,
+    },
+    Burn {
+        pk: String,
+        asset: String,
+        amount: u64,
+        cause: SupplyCause,
+    },
+    Transfer {
+        from: String,
+        to: String,
+        asset: String,
+        amount: u64,
+        cause: SupplyCause,
+    },
+}
+
+impl StagedOp {
+    fn accounts(&self) -> [&str; 2] {
+        match self {
+            Self::Mint { pk, .. } | Self::Burn { pk, .. } => [pk, pk],
+            Self::Transfer { from, to, .. } => [from, to],
+        }
+    }
+
+    fn apply(&self, registry: &mut StakeRegistry) -> Result<(), String> {
+        match self {
+            Self::Mint {
+                pk,
+                asset,
+                amount,
+                cause,
+            } => registry.mint_asset(pk, asset, *amount, *cause),
+            Self::Burn {
+                pk,
+                asset,
+                amount,
+                cause,
+            } => registry.burn_asset(pk, asset, *amount, *cause),
+            Self::Transfer {
+                from,
+                to,
+                asset,
+                amount,
+                cause,
+            } => registry.transfer_asset(from, to, asset, *amount, *cause),
+        }
+    }
+}
+
+/// Mutations staged against a [`StakeRegistry`] and committed atomically.
+///
+/// Steps are validated against balances in staging order when committed, so
+/// a credit staged earlier can fund a debit staged later.
+pub struct RegistryTransaction<'a> {
+    registry: &'a mut StakeRegistry,
+    ops: Vec<StagedOp>,
+}
+
+impl RegistryTransaction<'_> {
+    /// Stage a native mint.
+    pub fn mint(&mut self, pk: &str, amount: u64, cause: SupplyCause) -> &mut Self {
+        self.mint_asset(pk, NATIVE_ASSET, amount, cause)
+    }
+
+    /// Stage a mint of `asset`.
+    pub fn mint_asset(
+        &mut self,
+        pk: &str,
+        asset: &str,
+        amount: u64,
+        cause: SupplyCause,
+    ) -> &mut Self {
+        self.ops.push(StagedOp::Mint {
+            pk: pk.to_string(),
+            asset: asset.to_string(),
+            amount,
+            cause,
+        });
+        self
+    }
+
+    /// Stage a native burn.
+    pub fn burn(&mut self, pk: &str, amount: u64, cause: SupplyCause) -> &mut Self {
+        self.burn_asset(pk, NATIVE_ASSET, amount, cause)
+    }
+
+    /// Stage a burn of `asset`.
+    pub fn burn_asset(
+        &mut self,
+        pk: &str,
+        asset: &str,
+        amount: u64,
+        cause: SupplyCause,
+    ) -> &mut Self {
+        self.ops.push(StagedOp::Burn {
+            pk: pk.to_string(),
+            asset: asset.to_string(),
+            amount,
+            cause,
+        });
+        self
+    }
+
+    /// Stage a native transfer.
+    pub fn transfer(&mut self, from: &str, to: &str, amount: u64, cause: SupplyCause) -> &mut Self {
+        self.transfer_asset(from, to, NATIVE_ASSET, amount, cause)
+    }
+
+    /// Stage a transfer of `asset`.
+    pub fn transfer_asset(
+        &mut self,
+        from: &str,
+        to: &str,
+        asset: &str,
+        amount: u64,
+        cause: SupplyCause,
+    ) -> &mut Self {
+        self.ops.push(StagedOp::Transfer {
+            from: from.to_string(),
+            to: to.to_string(),
+            asset: asset.to_string(),
+            amount,
+            cause,
+        });
+        self
+    }
+
+    /// Stage a fee debit routed by the registry's [`FeeSinkPolicy`].
+    pub fn collect_fee(&mut self, payer: &str, asset: &str, fee: u64) -> &mut Self {
+        let (burned, sunk) = self.registry.fee_sink.divide(fee);
+        let sink = self.registry.fee_sink.sink_account().map(str::to_string);
+        if let Some(sink) = sink.filter(|_| sunk > 0) {
+            self.transfer_asset(payer, &sink, asset, sunk, SupplyCause::FeeSink);
+        }
+        if burned > 0 || sunk == 0 {
+            self.burn_asset(payer, asset, burned, SupplyCause::Fee);
+        }
+        self
+    }
+
+    /// Read-only view of the registry as it was before staging.
+    pub fn registry(&self) -> &StakeRegistry {
+        self.registry
+    }
+
+    /// Number of staged mutations.
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    /// Whether nothing has been staged.
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// Apply every staged mutation in order.
+    ///
+    /// If any step fails, the registry is restored to its state before the
+    /// commit and the error names the failing step.
+    pub fn commit(self) -> Result<(), String> {
+        let registry = self.registry;
+        let mut touched: HashMap<String, Option<StakeAccount>> = HashMap::new();
+        for pk in self.ops.iter().flat_map(StagedOp::accounts) {
+            touched
+                .entry(pk.to_string())
+                .or_insert_with(|| registry.accounts.get(pk).cloned());
+        }
+        let total_supply = registry.supply.total_supply;
+        let asset_supply = registry.supply.asset_supply.clone();
+        let journal_len = registry.supply.entries.len();
+        let guard_window = registry.guard_window.clone();
+        let grant = registry.grant.clone();
+
+        for (step, op) in self.ops.iter().enumerate() {
+            if let Err(err) = op.apply(registry) {
+                for (pk, account) in touched {
+                    match account {
+                        Some(account) => registry.accounts.insert(pk, account),
+                        None => registry.accounts.remove(&pk),
+                    };
+                }
+                registry.supply.total_supply = total_supply;
+                registry.supply.asset_supply = asset_supply;
+                registry.supply.entries.truncate(journal_len);
+                registry.guard_window = guard_window;
+                registry.grant = grant;
+                return Err(format!("registry transaction step {step} failed: {err}"));
+            }
+        }
         Ok(())
     }
 }
 
+/// Holder-signed transfer of a secondary asset between registry accounts.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AssetTransfer {
+    /// Secondary asset id.
+    pub asset: String,
+    /// Sender public key (base64); must sign the transfer.
+    pub from: String,
+    /// Recipient public key (base64).
+    pub to: String,
+    /// Units moved.
+    pub amount: u64,
+    /// Sender's transfer nonce, see [`StakeRegistry::transfer_nonce`].
+    pub nonce: u64,
+    /// Base64 ed25519 signature over [`AssetTransfer::signing_bytes`].
+    pub signature: String,
+}
+
+impl AssetTransfer {
+    /// Payload the sender signs.
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        format!(
+            "{ASSET_TRANSFER_DOMAIN}|{}|{}|{}|{}|{}",
+            self.asset, self.from, self.to, self.amount, self.nonce
+        )
+        .into_bytes()
+    }
+
+    /// Checks the sender's signature.
+    pub fn verify(&self) -> Result<(), String> {
+        verify_signature_base64(&self.from, &self.signing_bytes(), &self.signature)
+            .map_err(|err| format!("invalid transfer signature: {err}"))
+    }
+}
+
+/// Rejects empty, overlong or non `[A-Za-z0-9._-]` asset ids.
+pub fn validate_asset_id(asset: &str) -> Result<(), String> {
+    let valid = !asset.is_empty()
+        && asset.len() <= MAX_ASSET_ID_LEN
+        && asset
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b'-'));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("invalid asset id '{asset}'"))
+    }
+}
+
+/// Read-only registry snapshot indexed by public key and derived EVM address.
+///
+/// Addresses come from the canonical [`AddressIndex`], so a base64 key
+/// resolves to the same `0x` address the native chain credits at genesis.
+#[derive(Debug, Clone)]
+pub struct RegistryView {
+    registry: StakeRegistry,
+    addresses: AddressIndex,
+}
+
+impl RegistryView {
+    /// Indexes every account of `registry`.
+    pub fn new(registry: StakeRegistry) -> Self {
+        let addresses = AddressIndex::build(&registry);
+        Self::with_index(registry, addresses)
+    }
+
+    /// Reuses a loaded index, adding accounts it does not cover yet.
+    pub fn with_index(registry: StakeRegistry, mut addresses: AddressIndex) -> Self {
+        addresses.sync(&registry);
+        Self {
+            registry,
+            addresses,
+        }
+    }
+
+    /// Address index covering every account of the snapshot.
+    pub fn addresses(&self) -> &AddressIndex {
+        &self.addresses
+    }
+
+    /// Underlying registry snapshot.
+    pub fn registry(&self) -> &StakeRegistry {
+        &self.registry
+    }
+
+    /// Registry key for a base64 public key or a derived `0x` address.
+    pub fn resolve(&self, key_or_address: &str) -> Option<&str> {
+        if let Some((pk, _)) = self.registry.accounts.get_key_value(key_or_address) {
+            return Some(pk);
+        }
+        self.addresses.key_of(key_or_address)
+    }
+
+    /// Account for a public key or derived address.
+    pub fn account(&self, key_or_address: &str) -> Option<&StakeAccount> {
+        self.resolve(key_or_address)
+            .and_then(|pk| self.registry.account(pk))
+    }
+
+    /// Balance of `asset` for a public key or derived address.
+    pub fn balance_of(&self, key_or_address: &str, asset: &str) -> u64 {
+        self.account(key_or_address)
+            .map(|acct| acct.balance_of(asset))
+            .unwrap_or(0)
+    }
+
+    /// Next asset-transfer nonce for a public key or derived address.
+    pub fn transfer_nonce(&self, key_or_address: &str) -> u64 {
+        self.account(key_or_address)
+            .map(|acct| acct.transfer_nonce)
+            .unwrap_or(0)
+    }
+}
+
+impl FeeAccounts for StakeRegistry {
+    fn debit_fee(&mut self, public_key: &[u8], asset: &str, fee: u64) -> Result<(), String> {
+        let pk = BASE64.encode(public_key);
+        if self.account(&pk).is_some_and(|acct| acct.slashed) {
+            return Err("account is slashed".into());
+        }
+        self.collect_fee(&pk, asset, fee)
+    }
+
+    fn refund_fee(&mut self, public_key: &[u8], asset: &str, fee: u64) {
+        let pk = BASE64.encode(public_key);
+        let (burned, sunk) = self.fee_sink.divide(fee);
+        let sink = self.fee_sink.sink_account().map(str::to_string);
+        let mut tx = self.transaction();
+        if let Some(sink) = sink.filter(|_| sunk > 0) {
+            tx.transfer_asset(&sink, &pk, asset, sunk, SupplyCause::FeeRefund);
+        }
+        if burned > 0 || sunk == 0 {
+            tx.mint_asset(&pk, asset, burned, SupplyCause::FeeRefund);
+        }
+        if let Err(err) = tx.commit() {
+            eprintln!("fee refund not credited: {err}");
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::net::SimulatedClock;
+    use std::{
+        fs,
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    };
+
+    #[test]
+    fn account_proofs_verify_against_state_root() {
+        let mut registry = StakeRegistry::default();
+        registry.mint("alice", 10, SupplyCause::Funding).unwrap();
+        registry.mint("bob", 4, SupplyCause::Funding).unwrap();
+        registry.bond("bob", 3).unwrap();
+        let root = registry.state_root();
+
+        let bob = registry.account("bob").unwrap().clone();
+        let proof = registry.account_proof("bob");
+        assert_eq!(
+            verify_account_proof(&root, "bob", Some(&bob), &proof),
+            Ok(())
+        );
+        let mut inflated = bob.clone();
+        inflated.balance += 1;
+        assert!(verify_account_proof(&root, "bob", Some(&inflated), &proof).is_err());
+        assert!(verify_account_proof(&root, "alice", Some(&bob), &proof).is_err());
+        let absent = registry.account_proof("carol");
+        assert_eq!(verify_account_proof(&root, "carol", None, &absent), Ok(()));
+
+        registry
+            .transfer("alice", "bob", 1, SupplyCause::Transfer)
+            .unwrap();
+        assert_ne!(registry.state_root(), root);
+        assert!(verify_account_proof(&registry.state_root(), "bob", Some(&bob), &proof).is_err());
+    }
 
     #[test]
     fn save_replaces_registry_without_leaving_temp_files() {
@@ -162,9 +1653,9 @@ mod tests {
         ));
         let path = base.join("stake_registry.json");
         let mut registry = StakeRegistry::default();
-        registry.fund_balance("operator", 10);
+        registry.mint("operator", 10, SupplyCause::Funding).unwrap();
         registry.save(&path).unwrap();
-        registry.fund_balance("operator", 5);
+        registry.mint("operator", 5, SupplyCause::Funding).unwrap();
         registry.save(&path).unwrap();
 
         let loaded = StakeRegistry::load(&path).unwrap();
@@ -173,4 +1664,498 @@ mod tests {
         assert_eq!(entries, 1);
         fs::remove_dir_all(base).unwrap();
     }
+
+    #[test]
+    fn unbonding_waits_for_period_and_stays_slashable() {
+        let mut registry = StakeRegistry::default();
+        registry.set_unbonding_epochs(3);
+        registry.advance_epoch(10);
+        registry.mint("alice", 100, SupplyCause::Funding).unwrap();
+        registry.bond("alice", 80).unwrap();
+        assert_eq!(registry.stake_for("alice"), Some(80));
+
+        assert_eq!(registry.begin_unbond("alice", 30).unwrap(), 13);
+        assert_eq!(registry.stake_for("alice"), Some(50));
+        let acct = registry.account("alice").unwrap();
+        assert_eq!((acct.balance, acct.unbonding_total()), (20, 30));
+        assert!(registry.begin_unbond("alice", 60).is_err());
+
+        registry.advance_epoch(12);
+        assert!(registry.withdraw("alice").is_err());
+        registry.advance_epoch(11);
+        assert_eq!(registry.epoch(), 12);
+        registry.advance_epoch(13);
+        assert_eq!(registry.withdraw("alice").unwrap(), 30);
+        assert_eq!(registry.account("alice").unwrap().balance, 50);
+
+        registry.begin_unbond("alice", 20).unwrap();
+        registry.slash("alice");
+        let acct = registry.account("alice").unwrap();
+        assert_eq!((acct.stake, acct.unbonding_total()), (0, 0));
+        assert_eq!(acct.balance, 50);
+        assert_eq!(registry.stake_for("alice"), None);
+        assert!(registry.withdraw("alice").is_err());
+        assert!(registry.bond("alice", 10).is_err());
+        registry.check_invariants().unwrap();
+
+        let legacy: StakeRegistry =
+            serde_json::from_str(r#"{"accounts":{"bob":{"balance":1,"stake":2,"slashed":false}}}"#)
+                .unwrap();
+        assert_eq!(legacy.unbonding_epochs(), DEFAULT_UNBONDING_EPOCHS);
+        assert_eq!(legacy.epoch(), 0);
+    }
+
+    #[test]
+    fn supply_mutations_balance_the_books() {
+        let mut registry = StakeRegistry::default();
+        registry.mint("alice", 100, SupplyCause::Funding).unwrap();
+        registry
+            .mint("bob", 10, SupplyCause::MigrationClaim)
+            .unwrap();
+        registry
+            .transfer("alice", "bob", 40, SupplyCause::Fee)
+            .unwrap();
+        assert!(registry
+            .transfer("bob", "carol", 51, SupplyCause::Fee)
+            .is_err());
+        registry.burn("bob", 20, SupplyCause::Fee).unwrap();
+        registry.bond("alice", 50).unwrap();
+        registry.begin_unbond("alice", 10).unwrap();
+        assert_eq!(registry.slash("alice"), 50);
+        assert_eq!(registry.supply().total_supply, 40);
+        assert_eq!(
+            registry.supply().burned_by_cause(NATIVE_ASSET),
+            BTreeMap::from([(SupplyCause::Fee, 20), (SupplyCause::Slash, 50)])
+        );
+        registry.check_invariants().unwrap();
+
+        registry.accounts.get_mut("bob").unwrap().balance += 1;
+        let err = registry.check_invariants().unwrap_err();
+        assert!(err.contains("supply invariant violated"), "{err}");
+        let path = std::env::temp_dir().join(format!(
+            "power_house_supply_{}.json",
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        assert!(registry.save(&path).is_err());
+        assert!(!path.exists());
+
+        fs::write(
+            &path,
+            r#"{"accounts":{"bob":{"balance":1,"stake":2,"slashed":false}}}"#,
+        )
+        .unwrap();
+        let legacy = StakeRegistry::load(&path).unwrap();
+        assert_eq!(legacy.supply().total_supply, 3);
+        assert_eq!(
+            legacy.supply().minted_by_cause(NATIVE_ASSET),
+            BTreeMap::from([(SupplyCause::OpeningBalance, 3)])
+        );
+        fs::write(
+            &path,
+            r#"{"accounts":{"bob":{"balance":1,"stake":2,"slashed":false}},
+                "supply":{"total_supply":2,"entries":[]}}"#,
+        )
+        .unwrap();
+        assert!(StakeRegistry::load(&path).is_err());
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn invariant_checks_replay_from_the_last_supply_snapshot() {
+        let mut registry = StakeRegistry::default();
+        registry.mint("alice", 100, SupplyCause::Funding).unwrap();
+        registry.advance_epoch(1);
+        assert_eq!(registry.supply().snapshot.entries, 1);
+        assert_eq!(registry.supply().snapshot.total_supply, 100);
+
+        registry.burn("alice", 30, SupplyCause::Fee).unwrap();
+        registry.check_invariants().unwrap();
+        // Entries before the snapshot are no longer replayed.
+        registry.supply.entries[0].amount = 7;
+        registry.check_invariants().unwrap();
+        registry.supply.entries[1].amount = 31;
+        let err = registry.check_invariants().unwrap_err();
+        assert!(err.contains("journal=69"), "{err}");
+
+        // Unbalanced books keep the previous snapshot.
+        registry.advance_epoch(2);
+        assert_eq!(registry.supply().snapshot.entries, 1);
+        registry.supply.entries[1].amount = 30;
+        registry.advance_epoch(3);
+        assert_eq!(registry.supply().snapshot.entries, 2);
+        assert_eq!(registry.supply().snapshot.total_supply, 70);
+
+        registry.supply.snapshot.entries = 5;
+        let err = registry.check_invariants().unwrap_err();
+        assert!(err.contains("covers 5 entries"), "{err}");
+    }
+
+    #[test]
+    fn transactions_commit_all_or_nothing_with_one_write() {
+        let path = std::env::temp_dir().join(format!(
+            "power_house_registry_tx_{}.json",
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        let mut registry = StakeRegistry::default();
+        registry.mint("payer", 10, SupplyCause::Funding).unwrap();
+        registry.save(&path).unwrap();
+
+        let err = StakeRegistry::transact(&path, |tx| {
+            tx.transfer("payer", "operator", 6, SupplyCause::Fee)
+                .transfer("payer", "attester-a", 3, SupplyCause::Fee)
+                .transfer("payer", "attester-b", 3, SupplyCause::Fee);
+            Ok(())
+        })
+        .unwrap_err();
+        assert!(err.contains("step 2"), "{err}");
+        let unchanged = StakeRegistry::load(&path).unwrap();
+        assert_eq!(unchanged.balance_of("payer", NATIVE_ASSET), 10);
+        assert!(unchanged.account("operator").is_none());
+        assert_eq!(unchanged.supply().entries.len(), 1);
+
+        let mut in_memory = unchanged.clone();
+        let mut tx = in_memory.transaction();
+        tx.mint("operator", 5, SupplyCause::Reward)
+            .burn("operator", 6, SupplyCause::Fee);
+        assert_eq!(tx.len(), 2);
+        assert!(tx.commit().is_err());
+        assert!(in_memory.account("operator").is_none());
+        assert_eq!(in_memory.supply().total_supply, 10);
+
+        StakeRegistry::transact(&path, |tx| {
+            tx.transfer("payer", "operator", 6, SupplyCause::Fee)
+                .transfer("operator", "attester-a", 2, SupplyCause::Reward)
+                .burn("payer", 4, SupplyCause::Fee);
+            Ok(())
+        })
+        .unwrap();
+        let settled = StakeRegistry::load(&path).unwrap();
+        assert_eq!(settled.balance_of("payer", NATIVE_ASSET), 0);
+        assert_eq!(settled.balance_of("operator", NATIVE_ASSET), 4);
+        assert_eq!(settled.balance_of("attester-a", NATIVE_ASSET), 2);
+        assert_eq!(settled.supply().total_supply, 6);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn secondary_assets_move_by_signed_transfer() {
+        use crate::net::sign::{
+            encode_public_key_base64, encode_signature_base64, load_or_derive_keypair,
+            sign_payload, Ed25519KeySource,
+        };
+
+        let alice = load_or_derive_keypair(&Ed25519KeySource::Seed("asset-alice".into())).unwrap();
+        let alice_pk = encode_public_key_base64(&alice.verifying);
+        let mut registry = StakeRegistry::default();
+        registry
+            .mint_asset(&alice_pk, "usdx", 100, SupplyCause::Funding)
+            .unwrap();
+        registry.mint(&alice_pk, 7, SupplyCause::Funding).unwrap();
+        assert!(registry
+            .mint_asset(&alice_pk, "bad id", 1, SupplyCause::Funding)
+            .is_err());
+
+        let sign = |mut transfer: AssetTransfer| {
+            let signature = sign_payload(&alice.signing, &transfer.signing_bytes());
+            transfer.signature = encode_signature_base64(&signature);
+            transfer
+        };
+        let transfer = sign(AssetTransfer {
+            asset: "usdx".into(),
+            from: alice_pk.clone(),
+            to: "bob".into(),
+            amount: 40,
+            nonce: 0,
+            signature: String::new(),
+        });
+        registry.apply_signed_transfer(&transfer).unwrap();
+        assert!(registry.apply_signed_transfer(&transfer).is_err());
+        let mut forged = transfer.clone();
+        forged.nonce = 1;
+        forged.amount = 60;
+        assert!(registry.apply_signed_transfer(&forged).is_err());
+        let native = sign(AssetTransfer {
+            asset: NATIVE_ASSET.into(),
+            nonce: 1,
+            ..transfer.clone()
+        });
+        assert!(registry.apply_signed_transfer(&native).is_err());
+
+        assert_eq!(registry.balance_of(&alice_pk, "usdx"), 60);
+        assert_eq!(registry.balance_of("bob", "usdx"), 40);
+        assert_eq!(registry.balance_of(&alice_pk, NATIVE_ASSET), 7);
+        assert_eq!(registry.transfer_nonce(&alice_pk), 1);
+        registry
+            .burn_asset("bob", "usdx", 15, SupplyCause::Fee)
+            .unwrap();
+        assert_eq!(registry.supply().supply_of("usdx"), 85);
+        assert_eq!(registry.supply().supply_of(NATIVE_ASSET), 7);
+        registry.check_invariants().unwrap();
+
+        registry
+            .accounts
+            .get_mut("bob")
+            .unwrap()
+            .assets
+            .insert("usdx".into(), 26);
+        let err = registry.check_invariants().unwrap_err();
+        assert!(err.contains("usdx"), "{err}");
+    }
+
+    #[test]
+    fn guards_cap_credits_until_governance_overrides() {
+        use crate::net::governance::MultisigPolicy;
+        use crate::net::sign::{
+            encode_public_key_base64, load_or_derive_keypair, Ed25519KeySource,
+        };
+
+        let base = std::env::temp_dir().join(format!(
+            "power_house_registry_guards_{}_{}",
+            std::process::id(),
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        fs::create_dir_all(&base).unwrap();
+        let clock = SimulatedClock::new(Duration::from_secs(1_700_000_000));
+        let mut registry = StakeRegistry::default();
+        registry.set_clock(clock.shared());
+        registry.set_guard_limits(GuardLimits {
+            max_single_credit: Some(100),
+            max_account_daily_delta: Some(150),
+            max_daily_mint: Some(250),
+        });
+        let err = registry
+            .mint("alice", 101, SupplyCause::Funding)
+            .unwrap_err();
+        assert!(err.contains("max_single_credit"));
+        registry.mint("alice", 100, SupplyCause::Funding).unwrap();
+        let err = registry
+            .mint("alice", 60, SupplyCause::Funding)
+            .unwrap_err();
+        assert!(err.contains("max_account_daily_delta"));
+        registry.mint("bob", 100, SupplyCause::Funding).unwrap();
+        let err = registry
+            .mint("carol", 60, SupplyCause::Funding)
+            .unwrap_err();
+        assert!(err.contains("max_daily_mint"));
+        registry
+            .transfer("alice", "carol", 50, SupplyCause::Transfer)
+            .unwrap();
+        assert!(registry
+            .transfer("bob", "carol", 101, SupplyCause::Transfer)
+            .is_err());
+        assert_eq!(registry.account("bob").unwrap().balance, 100);
+        registry
+            .mint_asset("carol", "usdx", 1_000, SupplyCause::Funding)
+            .unwrap();
+
+        let signer = load_or_derive_keypair(&Ed25519KeySource::Seed("guard-gov".into())).unwrap();
+        let policy_path = base.join("multisig.json");
+        fs::write(
+            &policy_path,
+            serde_json::to_vec(&serde_json::json!({
+                "threshold": 1,
+                "signers": [encode_public_key_base64(&signer.verifying)],
+                "members": []
+            }))
+            .unwrap(),
+        )
+        .unwrap();
+        let policy = MultisigPolicy::load(&policy_path).unwrap();
+        let grant = GuardOverride {
+            account: "dave".into(),
+            amount: 1_000,
+            expires_at_epoch: 0,
+            nonce: 1,
+        };
+        let mut update = grant.to_update();
+        assert!(registry.apply_override_update(&update, &policy).is_err());
+        let mut untagged = GovernanceUpdate {
+            kind: UpdateKind::Membership,
+            ..update.clone()
+        };
+        untagged.approve(&signer).unwrap();
+        assert!(registry
+            .apply_override_update(&untagged, &policy)
+            .unwrap_err()
+            .contains("expected a guard_override update"));
+        update.approve(&signer).unwrap();
+        let approved = registry.apply_override_update(&update, &policy).unwrap();
+        assert_eq!(approved, grant);
+        registry.mint("dave", 1_000, SupplyCause::Funding).unwrap();
+        assert!(registry.mint("dave", 101, SupplyCause::Funding).is_err());
+
+        let path = base.join("stake_registry.json");
+        registry.save(&path).unwrap();
+        let mut loaded = StakeRegistry::load(&path).unwrap();
+        loaded.set_clock(clock.shared());
+        assert_eq!(loaded.guard_limits(), registry.guard_limits());
+        assert!(loaded
+            .apply_override_update(&update, &policy)
+            .unwrap_err()
+            .contains("already used"));
+        loaded.mint("erin", 50, SupplyCause::Funding).unwrap();
+        assert!(loaded.mint("alice", 1, SupplyCause::Funding).is_err());
+        clock.advance(Duration::from_millis(GUARD_DAY_MS));
+        loaded.mint("alice", 100, SupplyCause::Funding).unwrap();
+        assert_eq!(
+            loaded.guard_window().day,
+            1_700_000_000_000 / GUARD_DAY_MS + 1
+        );
+        fs::remove_dir_all(base).unwrap();
+    }
+
+    #[test]
+    fn frozen_accounts_cannot_be_debited_until_released() {
+        use crate::net::governance::MultisigPolicy;
+        use crate::net::sign::{
+            encode_public_key_base64, load_or_derive_keypair, Ed25519KeySource,
+        };
+
+        let policy_path = std::env::temp_dir().join(format!(
+            "power_house_freeze_policy_{}_{}.json",
+            std::process::id(),
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        let signer = load_or_derive_keypair(&Ed25519KeySource::Seed("freeze-gov".into())).unwrap();
+        let signer_b64 = encode_public_key_base64(&signer.verifying);
+        fs::write(
+            &policy_path,
+            serde_json::to_vec(&serde_json::json!({
+                "threshold": 1,
+                "signers": [signer_b64.clone()],
+                "members": []
+            }))
+            .unwrap(),
+        )
+        .unwrap();
+        let policy = MultisigPolicy::load(&policy_path).unwrap();
+
+        let mut registry = StakeRegistry::default();
+        registry.mint("alice", 10, SupplyCause::Funding).unwrap();
+        registry.bond("alice", 4).unwrap();
+        registry.begin_unbond("alice", 2).unwrap();
+        registry.advance_epoch(4);
+        let order = |freeze: bool, nonce: u64| {
+            let mut update = FreezeOrder {
+                account: "alice".into(),
+                freeze,
+                reason: "investigation 17".into(),
+                nonce,
+            }
+            .to_update();
+            update.approve(&signer).unwrap();
+            update
+        };
+        let freeze = order(true, 1);
+        let unsigned = GovernanceUpdate {
+            signatures: Vec::new(),
+            ..freeze.clone()
+        };
+        assert!(registry.apply_freeze_update(&unsigned, &policy).is_err());
+        let mut untagged = GovernanceUpdate {
+            kind: UpdateKind::Membership,
+            signatures: Vec::new(),
+            ..freeze.clone()
+        };
+        untagged.approve(&signer).unwrap();
+        assert!(registry
+            .apply_freeze_update(&untagged, &policy)
+            .unwrap_err()
+            .contains("expected a freeze update"));
+        registry.apply_freeze_update(&freeze, &policy).unwrap();
+        let hold = registry.account("alice").unwrap().frozen.clone().unwrap();
+        assert_eq!(hold.authority, vec![signer_b64]);
+        assert_eq!(hold.since_epoch, 4);
+        assert_eq!(registry.frozen_accounts().len(), 1);
+
+        let err = registry
+            .transfer("alice", "bob", 1, SupplyCause::Transfer)
+            .unwrap_err();
+        assert!(err.contains("frozen"));
+        assert!(registry.burn("alice", 1, SupplyCause::Fee).is_err());
+        assert!(registry
+            .debit_fee(&BASE64.decode("YWxpY2U=").unwrap(), NATIVE_ASSET, 1)
+            .is_err());
+        assert!(registry.bond("alice", 1).unwrap_err().contains("frozen"));
+        assert!(registry
+            .begin_unbond("alice", 1)
+            .unwrap_err()
+            .contains("frozen"));
+        assert!(registry.withdraw("alice").unwrap_err().contains("frozen"));
+        registry.mint("alice", 5, SupplyCause::Funding).unwrap();
+
+        registry
+            .apply_freeze_update(&order(false, 2), &policy)
+            .unwrap();
+        assert!(registry
+            .apply_freeze_update(&freeze, &policy)
+            .unwrap_err()
+            .contains("stale"));
+        registry.advance_epoch(DEFAULT_UNBONDING_EPOCHS);
+        assert_eq!(registry.withdraw("alice").unwrap(), 2);
+        registry
+            .transfer("alice", "bob", 9, SupplyCause::Transfer)
+            .unwrap();
+        assert!(registry.frozen_accounts().is_empty());
+        fs::remove_file(policy_path).unwrap();
+    }
+
+    #[test]
+    fn fees_follow_the_sink_policy_and_are_reported_per_epoch() {
+        let payer = BASE64.encode(b"payer");
+        let mut registry = StakeRegistry::default();
+        registry.mint(&payer, 100, SupplyCause::Funding).unwrap();
+        registry.debit_fee(b"payer", NATIVE_ASSET, 10).unwrap();
+        assert!(registry
+            .set_fee_sink(FeeSinkPolicy::Split {
+                account: "sink".into(),
+                burn_bps: 10_001,
+            })
+            .is_err());
+        registry
+            .set_fee_sink(FeeSinkPolicy::Split {
+                account: "sink".into(),
+                burn_bps: 2_500,
+            })
+            .unwrap();
+        registry.advance_epoch(3);
+        registry.collect_fee(&payer, NATIVE_ASSET, 10).unwrap();
+        registry.debit_fee(b"payer", NATIVE_ASSET, 8).unwrap();
+        registry.refund_fee(b"payer", NATIVE_ASSET, 8);
+        assert!(registry.collect_fee(&payer, NATIVE_ASSET, 1_000).is_err());
+
+        assert_eq!(registry.balance_of("sink", NATIVE_ASSET), 8);
+        assert_eq!(registry.balance_of(&payer, NATIVE_ASSET), 80);
+        assert_eq!(registry.supply().total_supply, 88);
+        assert_eq!(
+            registry.supply().fees_in_epoch(0),
+            EpochFees {
+                epoch: 0,
+                burned: 10,
+                sunk: 0,
+                refunded: 0
+            }
+        );
+        assert_eq!(
+            registry.supply().fees_in_epoch(3),
+            EpochFees {
+                epoch: 3,
+                burned: 4,
+                sunk: 14,
+                refunded: 8
+            }
+        );
+    }
 }
@@ -0,0 +1,822 @@
+#![cfg(feature = "net")]
+
+//! Storage backends for the stake registry and the native-chain state served by
+//! the wallet RPC.
+//!
+//! The JSON backend keeps one document per store and replaces it with a
+//! temp-file rename on every save. With the `sqlite` feature, paths ending in
+//! `.sqlite`, `.sqlite3` or `.db` are opened as a WAL-mode SQLite database
+//! instead: saves run in one `BEGIN IMMEDIATE` transaction, journals and blocks
+//! are appended rather than rewritten, and a registry save is rejected when
+//! another writer committed since the snapshot was loaded.
+
+use crate::net::{
+    native_chain::NativeChainState,
+    stake_registry::{StakeAccount, StakeRegistry},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Backend selected for a registry or native-chain state path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StorageBackend {
+    /// Pretty-printed JSON document replaced atomically on save.
+    #[default]
+    Json,
+    /// SQLite database in WAL mode (requires the `sqlite` feature).
+    Sqlite,
+}
+
+impl StorageBackend {
+    /// Parses `json` or `sqlite`.
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "json" => Ok(Self::Json),
+            "sqlite" if cfg!(feature = "sqlite") => Ok(Self::Sqlite),
+            "sqlite" => Err("sqlite storage requires the `sqlite` feature".to_string()),
+            other => Err(format!("unknown storage backend '{other}'")),
+        }
+    }
+
+    /// Backend implied by the extension of `path`.
+    pub fn for_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("sqlite" | "sqlite3" | "db") => Self::Sqlite,
+            _ => Self::Json,
+        }
+    }
+
+    /// File extension used for stores created with this backend.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Sqlite => "sqlite",
+        }
+    }
+}
+
+/// Persistence interface for the [`StakeRegistry`].
+///
+/// Implementations only move bytes; supply migration and invariant checks stay
+/// in [`StakeRegistry::load`] and [`StakeRegistry::save`].
+pub trait RegistryStore {
+    /// Returns the stored registry, or `None` when nothing was saved yet.
+    fn load_registry(&self) -> Result<Option<StakeRegistry>, String>;
+    /// Durably replaces the stored registry.
+    fn save_registry(&self, registry: &StakeRegistry) -> Result<(), String>;
+
+    /// Feeds every stored account to `visit` in public-key order, at most
+    /// `page_size` per call.
+    ///
+    /// The default loads the whole registry; SQLite reads one page per query
+    /// so memory stays bounded by the page size.
+    fn for_each_account_page(
+        &self,
+        page_size: usize,
+        visit: &mut dyn FnMut(Vec<(String, StakeAccount)>) -> Result<(), String>,
+    ) -> Result<(), String> {
+        let Some(registry) = self.load_registry()? else {
+            return Ok(());
+        };
+        let mut accounts = registry
+            .accounts()
+            .iter()
+            .map(|(key, account)| (key.clone(), account.clone()))
+            .collect::<Vec<_>>();
+        accounts.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut accounts = accounts.into_iter().peekable();
+        while accounts.peek().is_some() {
+            visit(accounts.by_ref().take(page_size.max(1)).collect())?;
+        }
+        Ok(())
+    }
+}
+
+/// Persistence interface for the [`NativeChainState`] behind the wallet RPC.
+pub trait ChainStateStore {
+    /// Returns the stored state, or `None` when nothing was saved yet.
+    fn load_chain_state(&self) -> Result<Option<NativeChainState>, String>;
+    /// Durably replaces the stored state.
+    fn save_chain_state(&self, state: &NativeChainState) -> Result<(), String>;
+}
+
+/// Opens the registry store for `path`, choosing the backend by extension.
+pub fn open_registry_store(path: &Path) -> Result<Box<dyn RegistryStore>, String> {
+    match StorageBackend::for_path(path) {
+        StorageBackend::Json => Ok(Box::new(JsonStore::new(path))),
+        #[cfg(feature = "sqlite")]
+        StorageBackend::Sqlite => Ok(Box::new(sqlite::SqliteStore::open(path)?)),
+        #[cfg(not(feature = "sqlite"))]
+        StorageBackend::Sqlite => Err(sqlite_disabled(path)),
+    }
+}
+
+/// Opens the native-chain state store for `path`, choosing the backend by extension.
+pub fn open_chain_state_store(path: &Path) -> Result<Box<dyn ChainStateStore>, String> {
+    match StorageBackend::for_path(path) {
+        StorageBackend::Json => Ok(Box::new(JsonStore::new(path))),
+        #[cfg(feature = "sqlite")]
+        StorageBackend::Sqlite => Ok(Box::new(sqlite::SqliteStore::open(path)?)),
+        #[cfg(not(feature = "sqlite"))]
+        StorageBackend::Sqlite => Err(sqlite_disabled(path)),
+    }
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn sqlite_disabled(path: &Path) -> String {
+    format!(
+        "{} needs SQLite storage; rebuild with the `sqlite` feature",
+        path.display()
+    )
+}
+
+/// Copies a JSON registry into an empty store at `target`; returns the account count.
+pub fn migrate_registry(source: &Path, target: &Path) -> Result<usize, String> {
+    if StorageBackend::for_path(source) != StorageBackend::Json {
+        return Err(format!("{} is not a JSON registry", source.display()));
+    }
+    if !source.exists() {
+        return Err(format!("{} does not exist", source.display()));
+    }
+    let registry = StakeRegistry::load(source)?;
+    let store = open_registry_store(target)?;
+    if store.load_registry()?.is_some() {
+        return Err(format!("{} already holds a registry", target.display()));
+    }
+    registry.check_invariants()?;
+    store.save_registry(&registry)?;
+    Ok(registry.accounts().len())
+}
+
+/// Copies JSON native-chain state into an empty store at `target`; returns the block count.
+///
+/// The state is replayed first, with block timestamps checked against `now_secs`.
+pub fn migrate_chain_state(source: &Path, target: &Path, now_secs: u64) -> Result<usize, String> {
+    if StorageBackend::for_path(source) != StorageBackend::Json {
+        return Err(format!("{} is not a JSON chain state", source.display()));
+    }
+    let state = JsonStore::new(source)
+        .load_chain_state()?
+        .ok_or_else(|| format!("{} does not exist", source.display()))?;
+    state.validate(now_secs)?;
+    let store = open_chain_state_store(target)?;
+    if store.load_chain_state()?.is_some() {
+        return Err(format!("{} already holds chain state", target.display()));
+    }
+    store.save_chain_state(&state)?;
+    Ok(state.blocks.len())
+}
+
+static REGISTRY_WRITES: AtomicU64 = AtomicU64::new(0);
+
+/// Records that this process saved a registry, invalidating cached views.
+pub(crate) fn note_registry_write() {
+    REGISTRY_WRITES.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Cheap change detector for a registry store.
+///
+/// Combines this process's registry write counter with the size and
+/// modification time of the store file and, for SQLite, its write-ahead log, so
+/// writes from other processes invalidate cached views as well.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct StoreFingerprint {
+    path: PathBuf,
+    writes: u64,
+    main: Option<(u64, SystemTime)>,
+    wal: Option<(u64, SystemTime)>,
+}
+
+impl StoreFingerprint {
+    /// Fingerprint of the store at `path`; take it before loading the snapshot.
+    pub(crate) fn of(path: &Path) -> Self {
+        let stat = |path: &Path| {
+            fs::metadata(path)
+                .ok()
+                .map(|meta| (meta.len(), meta.modified().unwrap_or(UNIX_EPOCH)))
+        };
+        let mut wal = path.as_os_str().to_owned();
+        wal.push("-wal");
+        Self {
+            path: path.to_path_buf(),
+            writes: REGISTRY_WRITES.load(Ordering::SeqCst),
+            main: stat(path),
+            wal: stat(Path::new(&wal)),
+        }
+    }
+}
+
+/// Store revision a registry snapshot was loaded at.
+///
+/// SQLite saves compare it with the database to detect concurrent writers;
+/// the JSON backend ignores it.
+#[derive(Debug, Default)]
+pub(crate) struct StoreRevision(AtomicU64);
+
+impl StoreRevision {
+    pub(crate) fn get(&self) -> u64 {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    #[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+    pub(crate) fn set(&self, revision: u64) {
+        self.0.store(revision, Ordering::SeqCst);
+    }
+}
+
+impl Clone for StoreRevision {
+    fn clone(&self) -> Self {
+        Self(AtomicU64::new(self.get()))
+    }
+}
+
+/// Single-document JSON store.
+#[derive(Debug, Clone)]
+pub struct JsonStore {
+    path: PathBuf,
+}
+
+impl JsonStore {
+    /// Store backed by the JSON file at `path`.
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+        }
+    }
+
+    fn read<T: DeserializeOwned>(&self) -> Result<Option<T>, String> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+        let bytes = fs::read(&self.path).map_err(|e| e.to_string())?;
+        serde_json::from_slice(&bytes)
+            .map(Some)
+            .map_err(|e| e.to_string())
+    }
+}
+
+impl RegistryStore for JsonStore {
+    fn load_registry(&self) -> Result<Option<StakeRegistry>, String> {
+        self.read()
+    }
+
+    fn save_registry(&self, registry: &StakeRegistry) -> Result<(), String> {
+        write_json_atomic(&self.path, registry)
+    }
+}
+
+impl ChainStateStore for JsonStore {
+    fn load_chain_state(&self) -> Result<Option<NativeChainState>, String> {
+        self.read()
+    }
+
+    fn save_chain_state(&self, state: &NativeChainState) -> Result<(), String> {
+        write_json_atomic(&self.path, state)
+    }
+}
+
+/// Writes `value` as pretty JSON through a synced temp file, rename, and
+/// directory sync.
+pub(crate) fn write_json_atomic<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let data = serde_json::to_vec_pretty(value).map_err(|e| e.to_string())?;
+    let nonce = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let temp_path = path.with_extension(format!("tmp-{}-{nonce}", std::process::id()));
+    let write_result = (|| -> Result<(), String> {
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&temp_path)
+            .map_err(|e| e.to_string())?;
+        file.write_all(&data).map_err(|e| e.to_string())?;
+        file.sync_all().map_err(|e| e.to_string())?;
+        fs::rename(&temp_path, path).map_err(|e| e.to_string())?;
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::File::open(parent)
+                .and_then(|directory| directory.sync_all())
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    })();
+    if write_result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    write_result
+}
+
+#[cfg(feature = "sqlite")]
+mod sqlite {
+    use super::{ChainStateStore, RegistryStore};
+    use crate::net::{
+        native_chain::NativeChainState,
+        stake_registry::{StakeAccount, StakeRegistry, SupplyEntry, SupplyLedger},
+    };
+    use rusqlite::{params, Connection, OptionalExtension, Transaction, TransactionBehavior};
+    use serde::{de::DeserializeOwned, Serialize};
+    use std::{
+        collections::{BTreeMap, HashMap},
+        fs,
+        path::{Path, PathBuf},
+        time::Duration,
+    };
+
+    const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+    const SCHEMA: &str = "
+        CREATE TABLE IF NOT EXISTS registry_meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);
+        CREATE TABLE IF NOT EXISTS registry_accounts (public_key TEXT PRIMARY KEY, account TEXT NOT NULL);
+        CREATE TABLE IF NOT EXISTS registry_journal (seq INTEGER PRIMARY KEY, entry TEXT NOT NULL);
+        CREATE TABLE IF NOT EXISTS chain_meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);
+        CREATE TABLE IF NOT EXISTS chain_accounts (address TEXT PRIMARY KEY, account TEXT NOT NULL);
+        CREATE TABLE IF NOT EXISTS chain_genesis_accounts (address TEXT PRIMARY KEY, account TEXT NOT NULL);
+        CREATE TABLE IF NOT EXISTS chain_blocks (number INTEGER PRIMARY KEY, block TEXT NOT NULL);
+        CREATE TABLE IF NOT EXISTS chain_votes (number INTEGER PRIMARY KEY, hash TEXT NOT NULL);
+    ";
+
+    fn sql(err: rusqlite::Error) -> String {
+        format!("sqlite: {err}")
+    }
+
+    fn encode<T: Serialize>(value: &T) -> Result<String, String> {
+        serde_json::to_string(value).map_err(|e| e.to_string())
+    }
+
+    fn decode<T: DeserializeOwned>(text: &str) -> Result<T, String> {
+        serde_json::from_str(text).map_err(|e| e.to_string())
+    }
+
+    fn to_i64(value: u64) -> Result<i64, String> {
+        i64::try_from(value).map_err(|_| format!("{value} exceeds the sqlite integer range"))
+    }
+
+    /// WAL-mode SQLite database holding registry and/or native-chain tables.
+    pub(super) struct SqliteStore {
+        path: PathBuf,
+    }
+
+    impl SqliteStore {
+        pub(super) fn open(path: &Path) -> Result<Self, String> {
+            let store = Self {
+                path: path.to_path_buf(),
+            };
+            store.connect()?;
+            Ok(store)
+        }
+
+        fn connect(&self) -> Result<Connection, String> {
+            if let Some(parent) = self.path.parent() {
+                fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            let conn = Connection::open(&self.path).map_err(sql)?;
+            conn.busy_timeout(BUSY_TIMEOUT).map_err(sql)?;
+            conn.pragma_update(None, "journal_mode", "WAL")
+                .map_err(sql)?;
+            conn.pragma_update(None, "synchronous", "FULL")
+                .map_err(sql)?;
+            conn.execute_batch(SCHEMA).map_err(sql)?;
+            Ok(conn)
+        }
+    }
+
+    fn meta_get<T: DeserializeOwned>(
+        conn: &Connection,
+        table: &str,
+        key: &str,
+    ) -> Result<Option<T>, String> {
+        let value: Option<String> = conn
+            .query_row(
+                &format!("SELECT value FROM {table} WHERE key = ?1"),
+                params![key],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(sql)?;
+        value.as_deref().map(decode).transpose()
+    }
+
+    fn meta_put<T: Serialize>(
+        tx: &Transaction<'_>,
+        table: &str,
+        key: &str,
+        value: &T,
+    ) -> Result<(), String> {
+        tx.execute(
+            &format!("INSERT OR REPLACE INTO {table} (key, value) VALUES (?1, ?2)"),
+            params![key, encode(value)?],
+        )
+        .map_err(sql)?;
+        Ok(())
+    }
+
+    fn rows<T: DeserializeOwned>(conn: &Connection, query: &str) -> Result<Vec<T>, String> {
+        let mut stmt = conn.prepare(query).map_err(sql)?;
+        let texts = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .map_err(sql)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(sql)?;
+        texts.iter().map(|text| decode(text)).collect()
+    }
+
+    fn keyed_rows<T: DeserializeOwned>(
+        conn: &Connection,
+        query: &str,
+    ) -> Result<BTreeMap<String, T>, String> {
+        let mut stmt = conn.prepare(query).map_err(sql)?;
+        let pairs = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(sql)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(sql)?;
+        pairs
+            .into_iter()
+            .map(|(key, text)| Ok((key, decode(&text)?)))
+            .collect()
+    }
+
+    fn replace_keyed<'a, T: Serialize + 'a>(
+        tx: &Transaction<'_>,
+        table: &str,
+        key_column: &str,
+        rows: impl IntoIterator<Item = (&'a String, &'a T)>,
+    ) -> Result<(), String> {
+        tx.execute(&format!("DELETE FROM {table}"), [])
+            .map_err(sql)?;
+        let mut stmt = tx
+            .prepare(&format!(
+                "INSERT INTO {table} ({key_column}, account) VALUES (?1, ?2)"
+            ))
+            .map_err(sql)?;
+        for (key, value) in rows {
+            stmt.execute(params![key, encode(value)?]).map_err(sql)?;
+        }
+        Ok(())
+    }
+
+    impl RegistryStore for SqliteStore {
+        fn load_registry(&self) -> Result<Option<StakeRegistry>, String> {
+            let conn = self.connect()?;
+            let Some(revision) = meta_get::<u64>(&conn, "registry_meta", "revision")? else {
+                return Ok(None);
+            };
+            let accounts: HashMap<String, StakeAccount> =
+                keyed_rows(&conn, "SELECT public_key, account FROM registry_accounts")?
+                    .into_iter()
+                    .collect();
+            let entries: Vec<SupplyEntry> =
+                rows(&conn, "SELECT entry FROM registry_journal ORDER BY seq")?;
+            let supply = SupplyLedger {
+                total_supply: meta_get(&conn, "registry_meta", "total_supply")?.unwrap_or(0),
+                asset_supply: meta_get(&conn, "registry_meta", "asset_supply")?.unwrap_or_default(),
+                entries,
+                snapshot: meta_get(&conn, "registry_meta", "supply_snapshot")?.unwrap_or_default(),
+            };
+            let registry = StakeRegistry::from_parts(
+                accounts,
+                supply,
+                meta_get(&conn, "registry_meta", "epoch")?.unwrap_or(0),
+                meta_get(&conn, "registry_meta", "unbonding_epochs")?
+                    .unwrap_or_else(|| StakeRegistry::default().unbonding_epochs()),
+            )
+            .with_guard_state(
+                meta_get(&conn, "registry_meta", "guards")?.unwrap_or_default(),
+                meta_get(&conn, "registry_meta", "guard_window")?.unwrap_or_default(),
+            )
+            .with_fee_sink(meta_get(&conn, "registry_meta", "fee_sink")?.unwrap_or_default());
+            registry.revision().set(revision);
+            Ok(Some(registry))
+        }
+
+        fn for_each_account_page(
+            &self,
+            page_size: usize,
+            visit: &mut dyn FnMut(Vec<(String, StakeAccount)>) -> Result<(), String>,
+        ) -> Result<(), String> {
+            let conn = self.connect()?;
+            if meta_get::<u64>(&conn, "registry_meta", "revision")?.is_none() {
+                return Ok(());
+            }
+            let mut stmt = conn
+                .prepare(
+                    "SELECT public_key, account FROM registry_accounts \
+                     WHERE ?1 IS NULL OR public_key > ?1 ORDER BY public_key LIMIT ?2",
+                )
+                .map_err(sql)?;
+            let limit = to_i64(page_size.max(1) as u64)?;
+            let mut after: Option<String> = None;
+            loop {
+                let page = stmt
+                    .query_map(params![after, limit], |row| {
+                        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                    })
+                    .map_err(sql)?
+                    .map(|pair| {
+                        let (key, text) = pair.map_err(sql)?;
+                        Ok((key, decode::<StakeAccount>(&text)?))
+                    })
+                    .collect::<Result<Vec<_>, String>>()?;
+                let Some((last, _)) = page.last() else {
+                    return Ok(());
+                };
+                after = Some(last.clone());
+                visit(page)?;
+            }
+        }
+
+        fn save_registry(&self, registry: &StakeRegistry) -> Result<(), String> {
+            let mut conn = self.connect()?;
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .map_err(sql)?;
+            let stored: u64 = meta_get(&tx, "registry_meta", "revision")?.unwrap_or(0);
+            let loaded = registry.revision().get();
+            if stored != loaded {
+                return Err(format!(
+                    "registry changed since it was loaded (stored revision {stored}, loaded {loaded})"
+                ));
+            }
+            let persisted: i64 = tx
+                .query_row("SELECT COUNT(*) FROM registry_journal", [], |row| {
+                    row.get(0)
+                })
+                .map_err(sql)?;
+            let persisted = usize::try_from(persisted).unwrap_or(0);
+            let entries = &registry.supply().entries;
+            if persisted > entries.len() {
+                return Err(format!(
+                    "registry journal shrank from {persisted} to {} entries",
+                    entries.len()
+                ));
+            }
+            {
+                let mut stmt = tx
+                    .prepare("INSERT INTO registry_journal (seq, entry) VALUES (?1, ?2)")
+                    .map_err(sql)?;
+                for (seq, entry) in entries.iter().enumerate().skip(persisted) {
+                    stmt.execute(params![to_i64(seq as u64)?, encode(entry)?])
+                        .map_err(sql)?;
+                }
+            }
+            replace_keyed(
+                &tx,
+                "registry_accounts",
+                "public_key",
+                registry.accounts().iter(),
+            )?;
+            let supply = registry.supply();
+            meta_put(&tx, "registry_meta", "total_supply", &supply.total_supply)?;
+            meta_put(&tx, "registry_meta", "asset_supply", &supply.asset_supply)?;
+            meta_put(&tx, "registry_meta", "supply_snapshot", &supply.snapshot)?;
+            meta_put(&tx, "registry_meta", "epoch", &registry.epoch())?;
+            meta_put(
+                &tx,
+                "registry_meta",
+                "unbonding_epochs",
+                &registry.unbonding_epochs(),
+            )?;
+            meta_put(&tx, "registry_meta", "guards", &registry.guard_limits())?;
+            meta_put(
+                &tx,
+                "registry_meta",
+                "guard_window",
+                registry.guard_window(),
+            )?;
+            meta_put(&tx, "registry_meta", "fee_sink", registry.fee_sink())?;
+            meta_put(&tx, "registry_meta", "revision", &(stored + 1))?;
+            tx.commit().map_err(sql)?;
+            registry.revision().set(stored + 1);
+            Ok(())
+        }
+    }
+
+    impl ChainStateStore for SqliteStore {
+        fn load_chain_state(&self) -> Result<Option<NativeChainState>, String> {
+            let conn = self.connect()?;
+            let Some(schema) = meta_get::<String>(&conn, "chain_meta", "schema")? else {
+                return Ok(None);
+            };
+            let mut votes_cast = BTreeMap::new();
+            {
+                let mut stmt = conn
+                    .prepare("SELECT number, hash FROM chain_votes")
+                    .map_err(sql)?;
+                let pairs = stmt
+                    .query_map([], |row| {
+                        Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+                    })
+                    .map_err(sql)?;
+                for pair in pairs {
+                    let (number, hash) = pair.map_err(sql)?;
+                    votes_cast.insert(number as u64, hash);
+                }
+            }
+            Ok(Some(NativeChainState {
+                schema,
+                chain_id: meta_get(&conn, "chain_meta", "chain_id")?.unwrap_or(0),
+                validators: meta_get(&conn, "chain_meta", "validators")?.unwrap_or_default(),
+                quorum: meta_get(&conn, "chain_meta", "quorum")?.unwrap_or(0),
+                genesis_accounts: keyed_rows(
+                    &conn,
+                    "SELECT address, account FROM chain_genesis_accounts",
+                )?,
+                accounts: keyed_rows(&conn, "SELECT address, account FROM chain_accounts")?,
+                blocks: rows(&conn, "SELECT block FROM chain_blocks ORDER BY number")?,
+                votes_cast,
+                pruned_accounts: meta_get(&conn, "chain_meta", "pruned_accounts")?,
+            }))
+        }
+
+        fn save_chain_state(&self, state: &NativeChainState) -> Result<(), String> {
+            let mut conn = self.connect()?;
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .map_err(sql)?;
+            let stored: Option<i64> = tx
+                .query_row("SELECT MAX(number) FROM chain_blocks", [], |row| row.get(0))
+                .map_err(sql)?;
+            let next = stored.map(|number| number as u64 + 1).unwrap_or(0);
+            let latest = state
+                .blocks
+                .last()
+                .map(|block| block.proposal.number)
+                .unwrap_or(0);
+            if stored.is_some() && next > latest + 1 {
+                return Err(format!(
+                    "stored chain height {} is ahead of state height {latest}",
+                    next - 1
+                ));
+            }
+            {
+                let mut stmt = tx
+                    .prepare("INSERT INTO chain_blocks (number, block) VALUES (?1, ?2)")
+                    .map_err(sql)?;
+                for block in state
+                    .blocks
+                    .iter()
+                    .filter(|block| block.proposal.number >= next)
+                {
+                    stmt.execute(params![to_i64(block.proposal.number)?, encode(block)?])
+                        .map_err(sql)?;
+                }
+            }
+            tx.execute(
+                "DELETE FROM chain_blocks WHERE number > 0 AND number < ?1",
+                params![to_i64(state.first_retained())?],
+            )
+            .map_err(sql)?;
+            replace_keyed(&tx, "chain_accounts", "address", state.accounts.iter())?;
+            replace_keyed(
+                &tx,
+                "chain_genesis_accounts",
+                "address",
+                state.genesis_accounts.iter(),
+            )?;
+            tx.execute("DELETE FROM chain_votes", []).map_err(sql)?;
+            for (number, hash) in &state.votes_cast {
+                tx.execute(
+                    "INSERT INTO chain_votes (number, hash) VALUES (?1, ?2)",
+                    params![to_i64(*number)?, hash],
+                )
+                .map_err(sql)?;
+            }
+            meta_put(&tx, "chain_meta", "schema", &state.schema)?;
+            meta_put(&tx, "chain_meta", "chain_id", &state.chain_id)?;
+            meta_put(&tx, "chain_meta", "validators", &state.validators)?;
+            meta_put(&tx, "chain_meta", "quorum", &state.quorum)?;
+            match &state.pruned_accounts {
+                Some(accounts) => meta_put(&tx, "chain_meta", "pruned_accounts", accounts)?,
+                None => {
+                    tx.execute("DELETE FROM chain_meta WHERE key = 'pruned_accounts'", [])
+                        .map_err(sql)?;
+                }
+            }
+            tx.commit().map_err(sql)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+    use crate::net::{
+        encode_public_key_base64, load_or_derive_keypair, stake_registry::SupplyCause,
+        Ed25519KeySource,
+    };
+
+    fn temp_dir(label: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("power_house_storage_{label}_{nanos}"));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn sqlite_registry_migrates_from_json_and_rejects_stale_writers() {
+        let dir = temp_dir("registry");
+        let json_path = dir.join("stake_registry.json");
+        let sqlite_path = dir.join("stake_registry.sqlite");
+        let mut registry = StakeRegistry::default();
+        registry.mint("alice", 40, SupplyCause::Funding).unwrap();
+        registry
+            .mint_asset("alice", "usdx", 9, SupplyCause::Funding)
+            .unwrap();
+        registry.bond("alice", 15).unwrap();
+        registry.advance_epoch(3);
+        registry.save(&json_path).unwrap();
+
+        assert_eq!(migrate_registry(&json_path, &sqlite_path).unwrap(), 1);
+        assert!(migrate_registry(&json_path, &sqlite_path).is_err());
+        let loaded = StakeRegistry::load(&sqlite_path).unwrap();
+        assert_eq!(loaded.balance_of("alice", "native"), 25);
+        assert_eq!(loaded.balance_of("alice", "usdx"), 9);
+        assert_eq!(loaded.stake_for("alice"), Some(15));
+        assert_eq!(loaded.epoch(), 3);
+        assert_eq!(loaded.supply().entries, registry.supply().entries);
+        assert_eq!(loaded.supply().snapshot, registry.supply().snapshot);
+        assert_eq!(
+            loaded.supply().snapshot.entries,
+            registry.supply().entries.len()
+        );
+
+        let mut first = StakeRegistry::load(&sqlite_path).unwrap();
+        let mut second = StakeRegistry::load(&sqlite_path).unwrap();
+        first.mint("bob", 5, SupplyCause::Funding).unwrap();
+        first.save(&sqlite_path).unwrap();
+        first.mint("bob", 1, SupplyCause::Funding).unwrap();
+        first.save(&sqlite_path).unwrap();
+        second.mint("carol", 7, SupplyCause::Funding).unwrap();
+        let err = second.save(&sqlite_path).unwrap_err();
+        assert!(err.contains("changed since it was loaded"), "{err}");
+
+        StakeRegistry::transact(&sqlite_path, |tx| {
+            tx.transfer("bob", "carol", 4, SupplyCause::Fee);
+            Ok(())
+        })
+        .unwrap();
+        let settled = StakeRegistry::load(&sqlite_path).unwrap();
+        assert_eq!(settled.balance_of("bob", "native"), 2);
+        assert_eq!(settled.balance_of("carol", "native"), 4);
+        assert_eq!(settled.supply().total_supply, 46);
+        let mut paged = Vec::new();
+        open_registry_store(&sqlite_path)
+            .unwrap()
+            .for_each_account_page(2, &mut |page| {
+                assert!(page.len() <= 2);
+                paged.extend(page.into_iter().map(|(key, _)| key));
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(paged, ["alice", "bob", "carol"]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn sqlite_chain_state_appends_blocks_and_matches_json() {
+        let dir = temp_dir("chain");
+        let json_path = dir.join("native_chain_state.json");
+        let sqlite_path = dir.join("native_chain_state.sqlite");
+        let key =
+            load_or_derive_keypair(&Ed25519KeySource::Seed("storage-validator".into())).unwrap();
+        let validators = vec![encode_public_key_base64(&key.verifying)];
+        let state =
+            NativeChainState::load_or_initialize(&json_path, 7, None, validators.clone(), 1, 0)
+                .unwrap();
+
+        assert_eq!(migrate_chain_state(&json_path, &sqlite_path, 0).unwrap(), 1);
+        let reopened =
+            NativeChainState::load_or_initialize(&sqlite_path, 7, None, validators, 1, 0).unwrap();
+        assert_eq!(reopened.blocks, state.blocks);
+        assert_eq!(reopened.genesis_accounts, state.genesis_accounts);
+
+        let store = open_chain_state_store(&sqlite_path).unwrap();
+        let mut extended = reopened.clone();
+        let mut next = extended.blocks[0].clone();
+        next.proposal.number = 1;
+        extended.blocks.push(next);
+        extended.votes_cast.insert(1, "hash".to_string());
+        store.save_chain_state(&extended).unwrap();
+        let loaded = store.load_chain_state().unwrap().unwrap();
+        assert_eq!(loaded.blocks.len(), 2);
+        assert_eq!(loaded.votes_cast.get(&1).map(String::as_str), Some("hash"));
+        assert!(store
+            .save_chain_state(&reopened)
+            .unwrap_err()
+            .contains("ahead of state height"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
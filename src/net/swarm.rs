@@ -1,54 +1,103 @@
 #![cfg(feature = "net")]
 
+use crate::commands::migration_burn_executor::{sign_burn_intent, BURN_INTENT_SCHEMA};
+use crate::economics::NATIVE_ASSET;
 use crate::julian::anchor_digest;
 use crate::net::sign::{
     decode_public_key_base64, encode_public_key_base64, encode_signature_base64, sign_payload,
     verify_signature_base64, KeyError, KeyMaterial,
 };
 use crate::net::{
+    address_index::{derive_evm_address, AddressIndex},
+    alerts::{AlertConfig, AlertKind, AlertMonitor, AlertState},
     attestation::{aggregate_attestations, Attestation},
+    audit::{append_attestation, AnchorAttestation},
     availability::{self, encode_shares, AvailabilityEvidence},
     blob::BlobJson,
+    block_archive::BLOCK_ARCHIVE_DIR,
+    chaos::{
+        duplicate_payload, ChaosConfig, ChaosInjector, ChaosKind, ChaosVerdict, DelayedMessage,
+    },
     checkpoint::{
-        latest_log_cutoff, load_latest_checkpoint, write_checkpoint, AnchorCheckpoint,
-        CheckpointSignature,
+        checkpoint_provider_key, latest_log_cutoff, load_latest_checkpoint,
+        parse_checkpoint_provider_key, write_checkpoint, AnchorCheckpoint, CheckpointSignature,
+    },
+    config::NetConfigBuilder,
+    did::{IdentityDocument, IdentityRegistry, IDENTITY_TOPIC},
+    direct::{
+        append_jsonl, drain_outbox, requeue_outbox, DirectInboxEntry, DirectMessage,
+        DirectOutboxEntry, DirectReceipt, DirectReceiptEntry, DIRECT_INBOX_FILE, DIRECT_PROTOCOL,
+        DIRECT_RECEIPTS_FILE,
     },
-    governance::MembershipPolicy,
+    entry_gc::{collect_expired_entries, record_finalized_entries, EntryGcManifest},
+    finality::{DurationHistogram, FinalityTracker},
+    fork::{read_fork_evidence, CheckpointTally, SafeMode},
+    governance::{FeeScheduleConfig, MembershipPolicy, MultisigPolicy},
+    mempool::{PendingPool, DEFAULT_MAX_PENDING, DEFAULT_PENDING_TTL},
+    metrics_push::{run_metrics_pusher, MetricsPushConfig},
     native_chain::{
         NativeChainCommand, NativeChainMessage, NativeChainMessagePayload, NativeChainRuntime,
-        NativeChainState, NATIVE_CHAIN_TOPIC,
+        NativeChainState, SharedNativeChainState, DEFAULT_MAX_BLOCK_INTERVAL, NATIVE_CHAIN_TOPIC,
+    },
+    node_clock::{SharedNodeClock, Ticker, WallClock},
+    onboarding::AllowlistFile,
+    parameters::{ParameterStore, RuntimeParameters, SharedParameters, MAX_ENVELOPE_BYTES},
+    peer_store::{PeerStore, PEER_STORE_MAX_AGE_SECS},
+    proposal::{
+        drain_governance_outbox, ExecutionHook, GovernanceBook, GovernanceMessage, ProposalAction,
+        ProposalStatus, GOVERNANCE_TOPIC,
+    },
+    rate_limit::{AnchorRateLimiter, RateVerdict, DEFAULT_MAX_ANCHORS_PER_MINUTE},
+    rewards::{distribute_epoch_rewards, RewardConfig, RewardError},
+    rollup_store::{append_rollup_record, RollupCommitmentStore, RollupOutcome},
+    rpc::{
+        run_evm_rpc_server, ConnectionLimiter, CorsPolicy, EvmRpcConfig, TxInputHandlers,
+        DEFAULT_GAS_MARGIN_PERCENT,
     },
-    rpc::{run_evm_rpc_server, EvmRpcConfig},
+    rpc_tiers::RpcTiers,
     schema::{
-        AnchorCodecError, AnchorEnvelope, AnchorJson, AnchorVoteJson, DaCommitmentJson,
-        ENVELOPE_SCHEMA_VERSION, NETWORK_ID, SCHEMA_ENVELOPE, SCHEMA_VOTE,
+        envelope_version_token, negotiate_envelope_version, network_id, network_topic,
+        parse_envelope_versions, AnchorCodecError, AnchorEnvelope, AnchorJson, AnchorVoteJson,
+        DaCommitmentJson, ENVELOPE_SCHEMA_VERSION, MIN_ENVELOPE_SCHEMA_VERSION, SCHEMA_ENVELOPE,
+        SCHEMA_VOTE,
     },
-    stake_registry::StakeRegistry,
+    shipping::{run_log_shipper, LogShipperConfig},
+    stake_registry::{EpochFees, StakeRegistry, SupplyCause},
+    storage::StorageBackend,
+    webhooks::{WebhookConfig, WebhookDispatcher, WebhookEventKind},
 };
 use crate::{
-    build_merkle_proof, compute_fold_digest, julian_genesis_anchor, merkle_root, parse_log_file,
+    build_merkle_proof, compute_fold_digest,
+    genesis::active_genesis,
+    iter_ledger_logs, julian_genesis_anchor, merkle_root,
+    prng::ChaChaPrng,
     read_fold_digest_hint,
     rollup::{
-        settle_rollup_with_rewards, RollupCommitment, RollupFaultEvidence, RollupSettlementMode,
-        ZkRollupProof,
+        settle_rollup_with_rewards, CircuitVerifyingKey, RollupCommitment, RollupFaultEvidence,
+        RollupPathHash, RollupSettlementMode, ZkRollupProof, ROLLUP_BATCH_VK_FILE,
+        ROLLUP_TRANSFER_VK_FILE,
     },
-    AnchorVote, EntryAnchor, LedgerAnchor,
+    weighted_quorum_threshold, AnchorVote, EntryAnchor, LedgerAnchor, WeightedAnchorVote,
 };
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
-use blake2::digest::{consts::U32, Digest as BlakeDigest};
-use ed25519_dalek::{Signer, SigningKey};
+use blake2::digest::Digest as BlakeDigest;
+use ed25519_dalek::{Signer, SigningKey, VerifyingKey};
 use futures::StreamExt;
 use hex;
 use libp2p::{
-    gossipsub::{self, IdentTopic, MessageAuthenticity, PublishError, ValidationMode},
+    gossipsub::{
+        self, IdentTopic, MessageAcceptance, MessageAuthenticity, PublishError, ValidationMode,
+    },
     identify, identity,
     kad::{self, store::MemoryStore},
     multiaddr::Protocol,
     noise,
+    request_response::{self, OutboundRequestId, ProtocolSupport},
     swarm::{NetworkBehaviour, Swarm, SwarmEvent},
-    tcp, yamux, Multiaddr, PeerId, SwarmBuilder,
+    tcp, yamux, Multiaddr, PeerId, StreamProtocol, SwarmBuilder,
 };
 use once_cell::sync::Lazy;
+use rand_core::{OsRng, RngCore};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json;
@@ -62,7 +111,7 @@ use std::{
     str,
     sync::{
         atomic::{AtomicBool, AtomicU64, Ordering},
-        Arc,
+        Arc, OnceLock,
     },
     time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
@@ -73,11 +122,27 @@ use tokio::{select, signal, time};
 
 const DEFAULT_ANCHOR_TOPIC: &str = "mfenx/powerhouse/anchors/v1";
 static TOPIC_EVIDENCE: Lazy<IdentTopic> =
-    Lazy::new(|| IdentTopic::new("mfenx/powerhouse/evidence/v1"));
-static TOPIC_VOTES: Lazy<IdentTopic> = Lazy::new(|| IdentTopic::new("mfenx/powerhouse/votes/v1"));
-static TOPIC_NATIVE_CHAIN: Lazy<IdentTopic> = Lazy::new(|| IdentTopic::new(NATIVE_CHAIN_TOPIC));
+    Lazy::new(|| IdentTopic::new(network_topic("mfenx/powerhouse/evidence/v1")));
+static TOPIC_VOTES: Lazy<IdentTopic> =
+    Lazy::new(|| IdentTopic::new(network_topic("mfenx/powerhouse/votes/v1")));
+static TOPIC_IDENTITY: Lazy<IdentTopic> =
+    Lazy::new(|| IdentTopic::new(network_topic(IDENTITY_TOPIC)));
+static TOPIC_NATIVE_CHAIN: Lazy<IdentTopic> =
+    Lazy::new(|| IdentTopic::new(network_topic(NATIVE_CHAIN_TOPIC)));
+static TOPIC_GOVERNANCE: Lazy<IdentTopic> =
+    Lazy::new(|| IdentTopic::new(network_topic(GOVERNANCE_TOPIC)));
+static TOPIC_CHECKPOINTS: Lazy<IdentTopic> =
+    Lazy::new(|| IdentTopic::new(network_topic("mfenx/powerhouse/checkpoints/v1")));
 static NO_GOSSIP_PEERS_LOGGED: AtomicBool = AtomicBool::new(false);
-const MAX_ENVELOPE_BYTES: usize = 64 * 1024;
+static SAFE_MODE_HALT_LOGGED: AtomicBool = AtomicBool::new(false);
+/// Checkpoints carry a whole anchor, so they get more room than envelopes.
+const MAX_CHECKPOINT_BYTES: usize = 4 * MAX_ENVELOPE_BYTES;
+const CHAOS_FLUSH_INTERVAL: Duration = Duration::from_millis(10);
+/// Longer than the gossipsub duplicate cache so an unchanged document propagates again.
+const IDENTITY_REPUBLISH_INTERVAL: Duration = Duration::from_secs(120);
+/// How often the native block producer checks whether a block is due.
+const NATIVE_PRODUCER_TICK: Duration = Duration::from_secs(1);
+const DIRECT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
 const MAX_NATIVE_MESSAGE_BYTES: usize = 512 * 1024;
 const MAX_ANCHOR_ENTRIES: usize = 10_000;
 const SEEN_CACHE_LIMIT: usize = 2048;
@@ -86,8 +151,14 @@ const MAX_HEADER_BYTES: usize = 32 * 1024;
 const DEFAULT_MAX_REQUEST_BYTES: usize = 64 * 1024 * 1024;
 const DEFAULT_REQUEST_TIMEOUT_MS: u64 = 10_000;
 const DEFAULT_BLOB_MAX_CONCURRENCY: usize = 128;
+/// Fraction of bonded stake required for anchor finality under a stake policy.
+const STAKE_QUORUM_NUMERATOR: u64 = 2;
+const STAKE_QUORUM_DENOMINATOR: u64 = 3;
 
 type AnchorVotes = HashMap<[u8; 32], (Instant, HashMap<Vec<u8>, LedgerAnchor>)>;
+/// Direct messages awaiting a receipt: recipient, message digest and the
+/// outbox entry to requeue if the send fails.
+type DirectPending = HashMap<OutboundRequestId, (PeerId, [u8; 32], DirectOutboxEntry)>;
 
 struct BftState {
     round: u64,
@@ -145,6 +216,31 @@ fn policy_permits(policy: &dyn MembershipPolicy, key: &[u8]) -> bool {
     members.iter().any(|vk| vk.to_bytes().as_slice() == key)
 }
 
+/// Returns the anchor finality weight when the membership policy is stake-weighted.
+fn stake_quorum_threshold(policy: &dyn MembershipPolicy) -> Option<u64> {
+    if policy.name() != "stake" {
+        return None;
+    }
+    let total = policy
+        .current_members()
+        .iter()
+        .filter_map(|vk| policy.stake_for(vk))
+        .fold(0u64, u64::saturating_add);
+    Some(weighted_quorum_threshold(
+        total,
+        STAKE_QUORUM_NUMERATOR,
+        STAKE_QUORUM_DENOMINATOR,
+    ))
+}
+
+fn anchor_vote_weight(policy: &dyn MembershipPolicy, key: &[u8]) -> u64 {
+    <[u8; 32]>::try_from(key)
+        .ok()
+        .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+        .and_then(|vk| policy.stake_for(&vk))
+        .unwrap_or(0)
+}
+
 /// Configuration and runtime context for the JULIAN network node.
 pub struct NetConfig {
     /// Human-readable node identifier used in logs and envelopes.
@@ -173,6 +269,8 @@ pub struct NetConfig {
     pub membership_policy: Arc<dyn MembershipPolicy>,
     /// Optional checkpoint interval (in broadcasts).
     pub checkpoint_interval: Option<u64>,
+    /// Checkpoint digests whose DHT providers should be located and dialed on startup.
+    pub checkpoint_queries: Vec<[u8; 32]>,
     /// Directory used to store blobs and share commitments.
     pub blob_dir: Option<PathBuf>,
     /// TCP socket for the blob ingest server.
@@ -193,6 +291,10 @@ pub struct NetConfig {
     pub attestation_quorum: usize,
     /// Path to the stake registry used for fees and slashing.
     pub stake_registry_path: Option<PathBuf>,
+    /// Reward pool paid to checkpoint signers at every checkpoint epoch.
+    pub reward_config: Option<RewardConfig>,
+    /// Backend holding the stake registry and native-chain state.
+    pub storage_backend: StorageBackend,
     /// Optional public token contract used during migration dual-mode.
     pub token_mode_contract: Option<String>,
     /// Optional JSON-RPC endpoint used for token migration oracle checks.
@@ -201,13 +303,73 @@ pub struct NetConfig {
     pub evm_rpc_listen: Option<SocketAddr>,
     /// EVM chain ID exposed by the RPC facade.
     pub evm_chain_id: u64,
+    /// Cross-origin policy applied by the EVM JSON-RPC listener.
+    pub evm_rpc_cors: CorsPolicy,
+    /// Application hooks admitting transaction calldata on the EVM JSON-RPC.
+    pub evm_rpc_tx_handlers: TxInputHandlers,
+    /// Per-tier concurrency and queue limits of the EVM JSON-RPC.
+    pub evm_rpc_tiers: RpcTiers,
+    /// Safety margin in percent added to calldata-bearing gas estimates.
+    pub evm_rpc_gas_margin_percent: u64,
     /// Whether this node participates in native-chain transaction finality.
     pub native_chain_enabled: bool,
+    /// Longest an idle native chain waits before sealing an empty block.
+    pub native_max_block_interval: Duration,
+    /// Finalized native blocks kept in live state; older ones move to the
+    /// block archive. `None` keeps the full history.
+    pub native_prune_keep: Option<u64>,
+    /// Checkpoint epochs a never-finalized ledger entry is kept before it is
+    /// moved to the entry GC archive. `None` keeps every entry.
+    pub entry_gc_epochs: Option<u64>,
+    /// How long a native transaction may stay pending before it is expired.
+    pub native_pending_ttl: Duration,
+    /// Pending native transactions held before the cheapest is displaced.
+    pub native_max_pending: usize,
+    /// Time source for tickers, block production, and anchor pacing.
+    pub clock: SharedNodeClock,
+    /// Webhook receivers notified of ledger, finality, and fork events.
+    pub webhooks: Option<WebhookConfig>,
+    /// Alert sinks and thresholds for operator-critical conditions.
+    pub alerts: Option<AlertConfig>,
+    /// Optional remote log shipping settings; records are signed with `key_material`.
+    pub log_shipping: Option<LogShipperConfig>,
+    /// Optional push-mode delivery of the metrics exposition.
+    pub metrics_push: Option<MetricsPushConfig>,
+    /// Connection slots shared by the metrics and EVM JSON-RPC listeners.
+    pub http_connection_limit: ConnectionLimiter,
+    /// Anchor envelopes accepted per signing identity per minute (0 disables the limit).
+    pub max_anchors_per_minute: u32,
+    /// Fee schedule state replaced by passed `fee_schedule` proposals.
+    pub governance_fee_schedule: Option<PathBuf>,
+    /// Multisig policy state whose members passed `membership_rotation`
+    /// proposals replace and `admit_member` proposals extend.
+    pub governance_multisig: Option<PathBuf>,
+    /// Static allowlist updated like `governance_multisig` when no multisig
+    /// state is configured.
+    pub governance_allowlist: Option<PathBuf>,
     metrics: Arc<Metrics>,
     metrics_addr: Option<SocketAddr>,
+    chaos: Option<Arc<ChaosInjector<IdentTopic>>>,
+    identities: Arc<IdentityRegistry>,
+    safe_mode: SafeMode,
+    checkpoint_tally: Arc<Mutex<CheckpointTally>>,
+    parameters: SharedParameters,
+    native_state: Option<SharedNativeChainState>,
+    webhook_dispatcher: Option<WebhookDispatcher>,
+    alert_monitor: Option<AlertMonitor>,
 }
 
 impl NetConfig {
+    /// Starts a validating builder; prefer it over the positional [`NetConfig::new`].
+    pub fn builder(
+        node_id: impl Into<String>,
+        listen_addr: Multiaddr,
+        log_dir: impl Into<PathBuf>,
+        key_material: KeyMaterial,
+    ) -> NetConfigBuilder {
+        NetConfigBuilder::new(node_id, listen_addr, log_dir, key_material)
+    }
+
     /// Constructs a networking configuration for the JULIAN net CLI.
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -240,8 +402,9 @@ impl NetConfig {
         evm_chain_id: Option<u64>,
     ) -> Self {
         let attestation_quorum = attestation_quorum.unwrap_or(quorum);
+        let safe_mode = SafeMode::default();
         let anchor_topic =
-            IdentTopic::new(anchor_topic.unwrap_or_else(|| DEFAULT_ANCHOR_TOPIC.to_string()));
+            IdentTopic::new(anchor_topic.unwrap_or_else(|| network_topic(DEFAULT_ANCHOR_TOPIC)));
         let mut bridge_topics_vec = Vec::new();
         let mut seen_topics: HashSet<String> = HashSet::new();
         let anchor_str = anchor_topic.to_string();
@@ -278,6 +441,7 @@ impl NetConfig {
             bft_round_ms,
             membership_policy,
             checkpoint_interval,
+            checkpoint_queries: Vec::new(),
             blob_dir,
             blob_listen,
             max_blob_bytes,
@@ -288,15 +452,68 @@ impl NetConfig {
             blob_request_timeout,
             attestation_quorum,
             stake_registry_path,
+            reward_config: None,
+            storage_backend: StorageBackend::Json,
             token_mode_contract,
             token_oracle_rpc,
             evm_rpc_listen,
-            evm_chain_id: evm_chain_id.unwrap_or(177155),
+            evm_chain_id: evm_chain_id.unwrap_or(active_genesis().chain_id),
+            evm_rpc_cors: CorsPolicy::default(),
+            evm_rpc_tx_handlers: TxInputHandlers::default(),
+            evm_rpc_tiers: RpcTiers::default(),
+            evm_rpc_gas_margin_percent: DEFAULT_GAS_MARGIN_PERCENT,
             native_chain_enabled,
-            metrics: Arc::new(Metrics::default()),
+            native_max_block_interval: DEFAULT_MAX_BLOCK_INTERVAL,
+            native_prune_keep: None,
+            entry_gc_epochs: None,
+            native_pending_ttl: DEFAULT_PENDING_TTL,
+            native_max_pending: DEFAULT_MAX_PENDING,
+            clock: WallClock::shared(),
+            webhooks: None,
+            alerts: None,
+            log_shipping: None,
+            metrics_push: None,
+            max_anchors_per_minute: DEFAULT_MAX_ANCHORS_PER_MINUTE,
+            governance_fee_schedule: None,
+            governance_multisig: None,
+            governance_allowlist: None,
+            http_connection_limit: ConnectionLimiter::default(),
+            metrics: Arc::new(Metrics {
+                safe_mode: safe_mode.clone(),
+                ..Metrics::default()
+            }),
             metrics_addr,
+            chaos: None,
+            identities: Arc::new(IdentityRegistry::default()),
+            safe_mode,
+            checkpoint_tally: Arc::new(Mutex::new(CheckpointTally::new())),
+            parameters: SharedParameters::default(),
+            native_state: None,
+            webhook_dispatcher: None,
+            alert_monitor: None,
+        }
+    }
+
+    /// Switches registry and native-chain state storage to `backend`.
+    ///
+    /// The default stake registry path is renamed to the backend's extension;
+    /// existing JSON files are left in place for `julian storage migrate`.
+    pub fn set_storage_backend(&mut self, backend: StorageBackend) {
+        self.storage_backend = backend;
+        if let Some(path) = self.stake_registry_path.as_mut() {
+            path.set_extension(backend.extension());
         }
     }
+
+    /// Perturbs every gossip message this node publishes (stress rehearsals
+    /// only; the CLI accepts the flags in debug builds).
+    pub fn set_chaos(&mut self, config: ChaosConfig) -> Result<(), String> {
+        config.validate()?;
+        self.chaos = config
+            .is_active()
+            .then(|| Arc::new(ChaosInjector::new(config)));
+        Ok(())
+    }
 }
 
 #[derive(Clone)]
@@ -694,7 +911,7 @@ async fn handle_blob_connection(stream: &mut TcpStream, cfg: BlobServiceConfig)
             "200 OK".to_string(),
             format!(
                 "{{\"status\":\"ok\",\"network\":\"{}\",\"version\":\"{}\"}}",
-                NETWORK_ID,
+                network_id(),
                 env!("CARGO_PKG_VERSION")
             ),
             "application/json".to_string(),
@@ -754,8 +971,16 @@ async fn handle_blob_connection(stream: &mut TcpStream, cfg: BlobServiceConfig)
                 .to_string(),
             )
         }
+        "GET" if req.path.starts_with("/rollup/") => {
+            let json = handle_rollup_query(&req, &cfg).unwrap_or_else(|e| e);
+            if json.starts_with("{") {
+                ("200 OK".to_string(), json, "application/json".to_string())
+            } else {
+                ("404 Not Found".to_string(), json, "text/plain".to_string())
+            }
+        }
         "POST" if req.path.starts_with("/rollup_settle") => {
-            match handle_rollup_settle(&req, &cfg) {
+            match handle_rollup_settle(&req, &cfg).await {
                 Ok(json) => ("200 OK".to_string(), json, "application/json".to_string()),
                 Err(err) => (
                     "400 Bad Request".to_string(),
@@ -906,15 +1131,10 @@ fn token_mode_requires_oracle(cfg: &BlobServiceConfig) -> bool {
 }
 
 fn pubkey_b64_to_migration_address(pk_b64: &str) -> Result<String, String> {
-    type Blake2b256 = blake2::Blake2b<U32>;
     let decoded = BASE64
         .decode(pk_b64.as_bytes())
         .map_err(|e| format!("publisher key decode failed: {e}"))?;
-    let mut hasher = Blake2b256::new();
-    hasher.update(b"mfenx-migration-address-v1");
-    hasher.update(decoded);
-    let digest: [u8; 32] = hasher.finalize().into();
-    Ok(format!("0x{}", hex::encode(&digest[12..])))
+    Ok(derive_evm_address(&decoded))
 }
 
 fn parse_hex_u128(input: &str) -> Result<u128, String> {
@@ -966,6 +1186,7 @@ async fn token_oracle_balance_sufficient(
 }
 
 fn queue_token_burn_intent(
+    signing: &SigningKey,
     registry_path: &Option<PathBuf>,
     token_mode_contract: &Option<String>,
     token_oracle_rpc: &Option<String>,
@@ -986,8 +1207,8 @@ fn queue_token_burn_intent(
             return;
         }
     };
-    let payload = serde_json::json!({
-        "schema": "mfenx.powerhouse.token-burn-intent.v1",
+    let mut payload = serde_json::json!({
+        "schema": BURN_INTENT_SCHEMA,
         "token_contract": contract,
         "token_oracle": token_oracle_rpc,
         "account": account,
@@ -995,6 +1216,10 @@ fn queue_token_burn_intent(
         "reason": reason,
         "ts": now_millis(),
     });
+    if let Err(err) = sign_burn_intent(&mut payload, signing) {
+        eprintln!("failed to sign token burn intent: {err}");
+        return;
+    }
     if let Some(parent) = outbox.parent() {
         let _ = fs::create_dir_all(parent);
     }
@@ -1058,7 +1283,8 @@ async fn prepare_fee_settlement(
     let mut reg =
         StakeRegistry::load(path).map_err(|err| format!("failed to load stake registry: {err}"))?;
     let mut settled_via_registry = true;
-    if let Err(debit_err) = reg.debit_fee(&payer, amount) {
+    if reg.balance_of(&payer, NATIVE_ASSET) < amount {
+        let debit_err = "insufficient balance";
         if token_mode_enabled(cfg) {
             if token_mode_requires_oracle(cfg) {
                 let covered = token_oracle_balance_sufficient(cfg, &payer, amount)
@@ -1083,15 +1309,13 @@ async fn prepare_fee_settlement(
         let op_bps = ns_rule.operator_reward_bps.unwrap_or(5000) as u64;
         let operator_cut = amount.saturating_mul(op_bps).saturating_div(10_000);
         let attestor_pool = amount.saturating_sub(operator_cut);
-        reg.credit_reward(&cfg.verifying_b64, operator_cut);
         let total_weight: u64 = meta
             .attestations
             .iter()
             .filter_map(|att| reg.stake_for(&att.pk))
             .sum();
-        if total_weight == 0 {
-            reg.credit_reward(&cfg.verifying_b64, attestor_pool);
-        } else {
+        let mut attestor_shares = Vec::new();
+        if total_weight > 0 {
             for att in &meta.attestations {
                 if let Some(weight) = reg.stake_for(&att.pk) {
                     let share = attestor_pool
@@ -1099,11 +1323,26 @@ async fn prepare_fee_settlement(
                         .checked_div(total_weight)
                         .unwrap_or_default();
                     if share > 0 {
-                        reg.credit_reward(&att.pk, share);
+                        attestor_shares.push((att.pk.clone(), share));
                     }
                 }
             }
         }
+        // Attestors are paid straight from the payer; the operator receives
+        // its cut plus any rounding remainder or unclaimed attestor pool.
+        let attestor_total: u64 = attestor_shares.iter().map(|(_, share)| share).sum();
+        let mut tx = reg.transaction();
+        tx.transfer(
+            &payer,
+            &cfg.verifying_b64,
+            amount - attestor_total,
+            SupplyCause::Fee,
+        );
+        for (pk, share) in &attestor_shares {
+            tx.transfer(&payer, pk, *share, SupplyCause::Fee);
+        }
+        tx.commit()
+            .map_err(|err| format!("fee settlement failed: {err}"))?;
     }
     Ok(Some((path.clone(), reg)))
 }
@@ -1299,7 +1538,10 @@ async fn handle_submit_blob(req: &HttpRequest, cfg: &BlobServiceConfig) -> Resul
     Ok(response.to_string())
 }
 
-fn handle_rollup_settle(req: &HttpRequest, cfg: &BlobServiceConfig) -> Result<String, String> {
+async fn handle_rollup_settle(
+    req: &HttpRequest,
+    cfg: &BlobServiceConfig,
+) -> Result<String, String> {
     #[derive(Deserialize)]
     struct RollupSettleRequest {
         namespace: String,
@@ -1320,6 +1562,8 @@ fn handle_rollup_settle(req: &HttpRequest, cfg: &BlobServiceConfig) -> Result<St
         public_inputs_b64: Option<String>,
         #[serde(default)]
         merkle_path_b64: Option<String>,
+        #[serde(default)]
+        path_hash: RollupPathHash,
     }
     let req_body: RollupSettleRequest =
         serde_json::from_slice(&req.body).map_err(|e| format!("decode error: {e}"))?;
@@ -1336,6 +1580,7 @@ fn handle_rollup_settle(req: &HttpRequest, cfg: &BlobServiceConfig) -> Result<St
         share_root: req_body.share_root.clone(),
         pedersen_root: Some(pedersen_root),
         settlement_slot: None,
+        path_hash: req_body.path_hash,
     };
     let operator_pk = req_body
         .operator_pk
@@ -1371,12 +1616,21 @@ fn handle_rollup_settle(req: &HttpRequest, cfg: &BlobServiceConfig) -> Result<St
         }
     };
 
-    let mode_enum = if mode == "zk" {
-        RollupSettlementMode::Zk(zk_proof)
-    } else {
+    let mode_enum = if mode != "zk" {
         RollupSettlementMode::Optimistic(Vec::new())
+    } else if zk_proof.is_batch() {
+        let key = CircuitVerifyingKey::load(&cfg.base_dir.join(ROLLUP_BATCH_VK_FILE))
+            .map_err(|e| format!("batch proofs need a verifying key: {e}"))?;
+        RollupSettlementMode::ZkBatch(zk_proof, Box::new(key))
+    } else {
+        let key = CircuitVerifyingKey::load(&cfg.base_dir.join(ROLLUP_TRANSFER_VK_FILE))
+            .map_err(|e| format!("single-transfer proofs need a verifying key: {e}"))?;
+        RollupSettlementMode::Zk(zk_proof, Box::new(key))
     };
 
+    // Serializes registry debits and commitment store appends.
+    let _registry_guard = cfg.stake_registry_lock.lock().await;
+    let store_path = RollupCommitmentStore::path_for(&cfg.base_dir);
     match settle_rollup_with_rewards(
         registry_path,
         commitment.clone(),
@@ -1386,21 +1640,75 @@ fn handle_rollup_settle(req: &HttpRequest, cfg: &BlobServiceConfig) -> Result<St
         req_body.fee,
         mode_enum,
     ) {
-        Ok(receipt) => Ok(serde_json::to_string(&serde_json::json!({
-            "status": "ok",
-            "payer": receipt.payer,
-            "fee": receipt.fee,
-            "commitment": receipt.commitment.share_root
-        }))
-        .unwrap_or_else(|_| "{}".to_string())),
+        Ok(receipt) => {
+            let outcome = RollupOutcome::from(&receipt);
+            let sequence = match append_rollup_record(&store_path, &commitment, outcome) {
+                Ok(stored) => Some(stored.sequence),
+                Err(err) => {
+                    eprintln!("rollup commitment store error: {err}");
+                    None
+                }
+            };
+            Ok(serde_json::to_string(&serde_json::json!({
+                "status": "ok",
+                "payer": receipt.payer,
+                "fee": receipt.fee,
+                "commitment": receipt.commitment.share_root,
+                "namespace": commitment.namespace,
+                "sequence": sequence,
+            }))
+            .unwrap_or_else(|_| "{}".to_string()))
+        }
         Err(fault) => {
             let outbox = cfg.base_dir.join("evidence_outbox.jsonl");
             append_rollup_fault_evidence(&outbox, &fault);
+            let outcome = RollupOutcome::from(&fault);
+            if let Err(err) = append_rollup_record(&store_path, &commitment, outcome) {
+                eprintln!("rollup commitment store error: {err}");
+            }
             Err(format!("rollup fault: {}", fault.reason))
         }
     }
 }
 
+/// `GET /rollup/<namespace>` lists a namespace's stored commitments;
+/// `GET /rollup/<namespace>/<sequence>` returns one with its inclusion proof.
+fn handle_rollup_query(req: &HttpRequest, cfg: &BlobServiceConfig) -> Result<String, String> {
+    let parts: Vec<&str> = req.path.trim_end_matches('/').split('/').collect();
+    let namespace = parts
+        .get(2)
+        .and_then(|part| sanitize_token(part))
+        .ok_or_else(|| "Not Found".to_string())?;
+    let store = RollupCommitmentStore::load(&RollupCommitmentStore::path_for(&cfg.base_dir))
+        .map_err(|err| err.to_string())?;
+    let root = store.root().map(hex::encode);
+    let body = match parts.get(3) {
+        None => serde_json::json!({
+            "namespace": namespace,
+            "root": root,
+            "commitments": store.namespace(&namespace),
+        }),
+        Some(sequence) => {
+            let sequence: u64 = sequence.parse().map_err(|_| "Not Found".to_string())?;
+            let record = store
+                .get(&namespace, sequence)
+                .ok_or_else(|| "Not Found".to_string())?;
+            let proof = store
+                .prove(&namespace, sequence)
+                .ok_or_else(|| "Not Found".to_string())?;
+            let proof: serde_json::Value =
+                serde_json::from_str(&proof.to_json_string()).map_err(|err| err.to_string())?;
+            serde_json::json!({
+                "commitment": record,
+                "leaf": hex::encode(record.leaf()),
+                "root": root,
+                "proof": proof,
+            })
+        }
+    };
+    Ok(body.to_string())
+}
+
 fn handle_commitment(req: &HttpRequest, cfg: &BlobServiceConfig) -> Result<String, String> {
     let parts: Vec<&str> = req.path.split('/').collect();
     if parts.len() < 4 {
@@ -1432,21 +1740,42 @@ fn handle_commitment(req: &HttpRequest, cfg: &BlobServiceConfig) -> Result<Strin
     Ok(body.to_string())
 }
 
-fn parse_count(query: Option<&str>) -> usize {
-    query
-        .and_then(|q| {
-            q.split('&').find_map(|kv| {
-                let mut parts = kv.split('=');
-                if parts.next() == Some("count") {
-                    parts.next().and_then(|v| v.parse().ok())
-                } else {
-                    None
-                }
-            })
+fn parse_query_u64(query: Option<&str>, key: &str) -> Option<u64> {
+    query.and_then(|q| {
+        q.split('&').find_map(|kv| {
+            let mut parts = kv.split('=');
+            if parts.next() == Some(key) {
+                parts.next().and_then(|v| v.parse().ok())
+            } else {
+                None
+            }
         })
+    })
+}
+
+fn parse_count(query: Option<&str>) -> usize {
+    parse_query_u64(query, "count")
+        .and_then(|c| usize::try_from(c).ok())
         .unwrap_or(1)
 }
 
+/// Picks `count` distinct share indices out of `total` with [`ChaChaPrng`].
+///
+/// A partial Fisher–Yates shuffle keeps every subset equally likely. The
+/// same `seed` always yields the same indices, so a light client can replay
+/// a sample it requested with `?seed=`.
+fn sample_share_indices(total: usize, count: usize, seed: u64) -> Vec<usize> {
+    let mut rng = ChaChaPrng::new(seed);
+    let mut indices: Vec<usize> = (0..total).collect();
+    let count = count.min(total);
+    for i in 0..count {
+        let j = i + rng.gen_mod((total - i) as u64) as usize;
+        indices.swap(i, j);
+    }
+    indices.truncate(count);
+    indices
+}
+
 fn pick_slash_target(meta: &BlobMeta) -> Option<String> {
     meta.publisher_pk
         .clone()
@@ -1479,6 +1808,7 @@ fn handle_sample(
         Err(_err) => {
             if let Some(pk) = meta.publisher_pk.as_ref() {
                 record_slash_with_registry(
+                    &cfg.signing,
                     &cfg.membership_policy,
                     &cfg.stake_registry_path,
                     &cfg.token_mode_contract,
@@ -1523,9 +1853,8 @@ fn handle_sample(
             return Err((400, "pedersen_root mismatch".into()));
         }
     }
-    let mut indices: Vec<usize> = (0..shares.len()).collect();
-    let count = parse_count(query_part);
-    indices.truncate(count.min(indices.len()));
+    let seed = parse_query_u64(query_part, "seed").unwrap_or_else(|| OsRng.next_u64());
+    let indices = sample_share_indices(shares.len(), parse_count(query_part), seed);
     let mut sampled = Vec::new();
     for idx in indices {
         let proof = build_merkle_proof(&share_hashes, idx).ok_or((400, "bad index".into()))?;
@@ -1552,6 +1881,7 @@ fn handle_sample(
         "pedersen_root": meta.pedersen_root,
         "data_shards": meta.data_shards,
         "parity_shards": meta.parity_shards,
+        "seed": seed,
         "shares": sampled,
         "attestations": attestations,
     });
@@ -1580,6 +1910,7 @@ fn handle_prove_storage(
         Err(_err) => {
             if let Some(pk) = pick_slash_target(&meta) {
                 record_slash_with_registry(
+                    &cfg.signing,
                     &cfg.membership_policy,
                     &cfg.stake_registry_path,
                     &cfg.token_mode_contract,
@@ -1656,10 +1987,23 @@ struct Metrics {
     invalid_envelopes_total: AtomicU64,
     lrucache_evictions_total: AtomicU64,
     finality_events_total: AtomicU64,
+    metrics_pushes_total: AtomicU64,
+    metrics_push_failures_total: AtomicU64,
     gossipsub_rejects_total: AtomicU64,
+    gossipsub_rejects_by_reason: [AtomicU64; RejectReason::ALL.len()],
     native_transactions_accepted_total: AtomicU64,
     native_blocks_finalized_total: AtomicU64,
     native_sync_blocks_applied_total: AtomicU64,
+    chaos_injected_by_kind: [AtomicU64; ChaosKind::ALL.len()],
+    anchor_finality_latency: DurationHistogram,
+    anchor_divergence_duration: DurationHistogram,
+    /// Unix millis at which the open divergence episode began, 0 when converged.
+    anchor_divergence_since_ms: AtomicU64,
+    forks_detected_total: AtomicU64,
+    safe_mode: SafeMode,
+    alerts: AlertState,
+    rpc_tiers: OnceLock<RpcTiers>,
+    mempool: OnceLock<PendingPool>,
 }
 
 #[derive(Clone)]
@@ -1704,8 +2048,36 @@ impl Metrics {
         self.finality_events_total.fetch_add(1, Ordering::Relaxed);
     }
 
-    fn inc_gossipsub_rejects(&self) {
+    fn record_metrics_push(&self, delivered: bool) {
+        if delivered {
+            self.metrics_pushes_total.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.metrics_push_failures_total
+                .fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn observe_finality_latency(&self, latency: Duration) {
+        self.anchor_finality_latency.observe(latency);
+    }
+
+    fn divergence_started(&self) {
+        self.anchor_divergence_since_ms
+            .store(now_millis().max(1), Ordering::Relaxed);
+    }
+
+    fn divergence_ended(&self, duration: Duration) {
+        self.anchor_divergence_since_ms.store(0, Ordering::Relaxed);
+        self.anchor_divergence_duration.observe(duration);
+    }
+
+    fn inc_gossipsub_rejects(&self, reason: RejectReason) {
         self.gossipsub_rejects_total.fetch_add(1, Ordering::Relaxed);
+        self.gossipsub_rejects_by_reason[reason as usize].fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn inc_chaos_injected(&self, kind: ChaosKind) {
+        self.chaos_injected_by_kind[kind as usize].fetch_add(1, Ordering::Relaxed);
     }
 
     fn inc_native_transactions_accepted(&self) {
@@ -1718,13 +2090,25 @@ impl Metrics {
             .fetch_add(1, Ordering::Relaxed);
     }
 
+    fn inc_forks_detected(&self) {
+        self.forks_detected_total.fetch_add(1, Ordering::Relaxed);
+    }
+
     fn inc_native_sync_blocks_applied(&self) {
         self.native_sync_blocks_applied_total
             .fetch_add(1, Ordering::Relaxed);
     }
 
     fn render(&self, identity: &MetricsIdentity) -> String {
-        format!(
+        let mut by_reason = String::from("# TYPE gossipsub_rejects_by_reason_total counter\n");
+        for reason in RejectReason::ALL {
+            by_reason.push_str(&format!(
+                "gossipsub_rejects_by_reason_total{{reason=\"{}\"}} {}\n",
+                reason.label(),
+                self.gossipsub_rejects_by_reason[reason as usize].load(Ordering::Relaxed)
+            ));
+        }
+        let mut rendered = format!(
             "# TYPE powerhouse_node_identity gauge\n\
 powerhouse_node_identity{{node_id=\"{}\",peer_id=\"{}\",public_key_b64=\"{}\",chain_id=\"{}\"}} 1\n\
 # TYPE powerhouse_connected_peers gauge\npowerhouse_connected_peers {}\n\
@@ -1733,10 +2117,14 @@ powerhouse_node_identity{{node_id=\"{}\",peer_id=\"{}\",public_key_b64=\"{}\",ch
 # TYPE invalid_envelopes_total counter\ninvalid_envelopes_total {}\n\
 # TYPE lrucache_evictions_total counter\nlrucache_evictions_total {}\n\
 # TYPE finality_events_total counter\nfinality_events_total {}\n\
+# TYPE metrics_pushes_total counter\nmetrics_pushes_total {}\n\
+# TYPE metrics_push_failures_total counter\nmetrics_push_failures_total {}\n\
 # TYPE gossipsub_rejects_total counter\ngossipsub_rejects_total {}\n\
 # TYPE native_transactions_accepted_total counter\nnative_transactions_accepted_total {}\n\
 # TYPE native_blocks_finalized_total counter\nnative_blocks_finalized_total {}\n\
-# TYPE native_sync_blocks_applied_total counter\nnative_sync_blocks_applied_total {}\n",
+# TYPE native_sync_blocks_applied_total counter\nnative_sync_blocks_applied_total {}\n\
+# TYPE powerhouse_forks_detected_total counter\npowerhouse_forks_detected_total {}\n\
+# TYPE powerhouse_safe_mode gauge\npowerhouse_safe_mode {}\n",
             prometheus_label(&identity.node_id),
             prometheus_label(&identity.peer_id),
             prometheus_label(&identity.public_key_b64),
@@ -1747,14 +2135,276 @@ powerhouse_node_identity{{node_id=\"{}\",peer_id=\"{}\",public_key_b64=\"{}\",ch
             self.invalid_envelopes_total.load(Ordering::Relaxed),
             self.lrucache_evictions_total.load(Ordering::Relaxed),
             self.finality_events_total.load(Ordering::Relaxed),
+            self.metrics_pushes_total.load(Ordering::Relaxed),
+            self.metrics_push_failures_total.load(Ordering::Relaxed),
             self.gossipsub_rejects_total.load(Ordering::Relaxed),
             self.native_transactions_accepted_total
                 .load(Ordering::Relaxed),
             self.native_blocks_finalized_total.load(Ordering::Relaxed),
             self.native_sync_blocks_applied_total
                 .load(Ordering::Relaxed),
-        )
+            self.forks_detected_total.load(Ordering::Relaxed),
+            u8::from(self.safe_mode.is_engaged()),
+        );
+        rendered.push_str(&by_reason);
+        rendered.push_str("# TYPE powerhouse_alert_active gauge\n");
+        for kind in AlertKind::ALL {
+            rendered.push_str(&format!(
+                "powerhouse_alert_active{{alert=\"{}\"}} {}\n",
+                kind.label(),
+                u8::from(self.alerts.is_active(kind))
+            ));
+        }
+        if let Some(tiers) = self.rpc_tiers.get() {
+            rendered.push_str(&tiers.render_metrics());
+        }
+        if let Some(pool) = self.mempool.get() {
+            rendered.push_str(&pool.render_metrics());
+        }
+        rendered.push_str("# TYPE chaos_injected_total counter\n");
+        for kind in ChaosKind::ALL {
+            rendered.push_str(&format!(
+                "chaos_injected_total{{kind=\"{}\"}} {}\n",
+                kind.label(),
+                self.chaos_injected_by_kind[kind as usize].load(Ordering::Relaxed)
+            ));
+        }
+        self.anchor_finality_latency
+            .render("anchor_finality_latency_seconds", &mut rendered);
+        self.anchor_divergence_duration
+            .render("anchor_divergence_duration_seconds", &mut rendered);
+        let diverged_since = self.anchor_divergence_since_ms.load(Ordering::Relaxed);
+        let divergence_age = if diverged_since == 0 {
+            0.0
+        } else {
+            now_millis().saturating_sub(diverged_since) as f64 / 1000.0
+        };
+        rendered.push_str(&format!(
+            "# TYPE anchor_divergence_active gauge\nanchor_divergence_active {}\n\
+# TYPE anchor_divergence_current_seconds gauge\nanchor_divergence_current_seconds {}\n",
+            u8::from(diverged_since != 0),
+            divergence_age
+        ));
+        rendered
+    }
+}
+
+/// Reason a gossip message was rejected (or a local publish failed), for per-reason metrics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RejectReason {
+    Oversize,
+    Malformed,
+    Signature,
+    Policy,
+    Network,
+    Duplicate,
+    DataAvailability,
+    LocalPublish,
+    /// Envelope schema version outside the supported range.
+    Version,
+    /// Identity document or envelope `node_id` contradicts a registered binding.
+    Identity,
+    /// Identity exceeded its anchors-per-minute budget.
+    RateLimit,
+    /// Identity kept exceeding its budget over several windows.
+    RateLimitSustained,
+}
+
+impl RejectReason {
+    const ALL: [Self; 12] = [
+        Self::Oversize,
+        Self::Malformed,
+        Self::Signature,
+        Self::Policy,
+        Self::Network,
+        Self::Duplicate,
+        Self::DataAvailability,
+        Self::LocalPublish,
+        Self::Version,
+        Self::Identity,
+        Self::RateLimit,
+        Self::RateLimitSustained,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Oversize => "oversize",
+            Self::Malformed => "malformed",
+            Self::Signature => "signature",
+            Self::Policy => "policy",
+            Self::Network => "network",
+            Self::Duplicate => "duplicate",
+            Self::DataAvailability => "data_availability",
+            Self::LocalPublish => "local_publish",
+            Self::Version => "version",
+            Self::Identity => "identity",
+            Self::RateLimit => "rate_limit",
+            Self::RateLimitSustained => "rate_limit_sustained",
+        }
+    }
+}
+
+/// Anchor envelope that passed pre-propagation checks.
+struct ValidatedAnchor {
+    envelope: AnchorEnvelope,
+    anchor_json: AnchorJson,
+    remote_verifying: VerifyingKey,
+}
+
+/// Outcome of validating a gossip message before it is forwarded.
+enum ValidatedMessage {
+    Anchor(Box<ValidatedAnchor>),
+    Native(Box<NativeChainMessage>),
+    Evidence,
+    Vote,
+    Identity(Box<IdentityDocument>),
+    Checkpoint(Box<AnchorCheckpoint>),
+    Governance(Box<GovernanceMessage>),
+    /// Topic this node does not handle; dropped without penalising the sender.
+    Ignored,
+}
+
+/// Checks size, encoding, signature and membership policy of an inbound gossip
+/// message. Only messages that pass are forwarded to other peers.
+fn validate_gossip_message(
+    cfg: &NetConfig,
+    topic: &gossipsub::TopicHash,
+    data: &[u8],
+) -> Result<ValidatedMessage, RejectReason> {
+    if *topic == TOPIC_NATIVE_CHAIN.hash() {
+        if data.len() > MAX_NATIVE_MESSAGE_BYTES {
+            return Err(RejectReason::Oversize);
+        }
+        let message: NativeChainMessage =
+            serde_json::from_slice(data).map_err(|_| RejectReason::Malformed)?;
+        if message.network != network_id() {
+            return Err(RejectReason::Network);
+        }
+        return Ok(ValidatedMessage::Native(Box::new(message)));
+    }
+    if *topic == TOPIC_CHECKPOINTS.hash() {
+        if data.len() > MAX_CHECKPOINT_BYTES {
+            return Err(RejectReason::Oversize);
+        }
+        let checkpoint: AnchorCheckpoint =
+            serde_json::from_slice(data).map_err(|_| RejectReason::Malformed)?;
+        if checkpoint.anchor.network != network_id() {
+            return Err(RejectReason::Network);
+        }
+        // Publishers gossip the checkpoint they signed themselves.
+        let [signature] = checkpoint.signatures.as_slice() else {
+            return Err(RejectReason::Malformed);
+        };
+        let payload =
+            serde_json::to_vec(&checkpoint.anchor).map_err(|_| RejectReason::Malformed)?;
+        verify_signature_base64(&signature.public_key, &payload, &signature.signature)
+            .map_err(|_| RejectReason::Signature)?;
+        cfg.identities
+            .check_envelope(&signature.node_id, &signature.public_key)
+            .map_err(|_| RejectReason::Identity)?;
+        let vk =
+            decode_public_key_base64(&signature.public_key).map_err(|_| RejectReason::Malformed)?;
+        if !policy_permits(cfg.membership_policy.as_ref(), &vk.to_bytes()) {
+            return Err(RejectReason::Policy);
+        }
+        return Ok(ValidatedMessage::Checkpoint(Box::new(checkpoint)));
+    }
+    if data.len() > cfg.parameters.max_envelope_bytes() {
+        return Err(RejectReason::Oversize);
+    }
+    if *topic == TOPIC_EVIDENCE.hash() {
+        if let Ok(env) = serde_json::from_slice::<EvidenceEnvelope>(data) {
+            let payload = serde_json::to_vec(&env.evidence).map_err(|_| RejectReason::Malformed)?;
+            verify_signature_base64(&env.public_key, &payload, &env.signature)
+                .map_err(|_| RejectReason::Signature)?;
+            let vk =
+                decode_public_key_base64(&env.public_key).map_err(|_| RejectReason::Malformed)?;
+            if !policy_permits(cfg.membership_policy.as_ref(), &vk.to_bytes()) {
+                return Err(RejectReason::Policy);
+            }
+        } else if serde_json::from_slice::<AvailabilityEvidence>(data).is_err()
+            && serde_json::from_slice::<RollupFaultEvidence>(data).is_err()
+        {
+            return Err(RejectReason::Malformed);
+        }
+        return Ok(ValidatedMessage::Evidence);
+    }
+    if *topic == TOPIC_VOTES.hash() {
+        let vote: AnchorVoteJson =
+            serde_json::from_slice(data).map_err(|_| RejectReason::Malformed)?;
+        if vote.network != network_id() {
+            return Err(RejectReason::Network);
+        }
+        vote.validate().map_err(|_| RejectReason::Malformed)?;
+        let payload = vote_payload_bytes(vote.round, &vote.anchor_hash);
+        verify_signature_base64(&vote.public_key, &payload, &vote.signature)
+            .map_err(|_| RejectReason::Signature)?;
+        let vk = decode_public_key_base64(&vote.public_key).map_err(|_| RejectReason::Malformed)?;
+        if !policy_permits(cfg.membership_policy.as_ref(), &vk.to_bytes()) {
+            return Err(RejectReason::Policy);
+        }
+        return Ok(ValidatedMessage::Vote);
+    }
+    if *topic == TOPIC_GOVERNANCE.hash() {
+        let message: GovernanceMessage =
+            serde_json::from_slice(data).map_err(|_| RejectReason::Malformed)?;
+        message.verify().map_err(|_| RejectReason::Signature)?;
+        let vk = decode_public_key_base64(message.signer()).map_err(|_| RejectReason::Malformed)?;
+        if !policy_permits(cfg.membership_policy.as_ref(), &vk.to_bytes()) {
+            return Err(RejectReason::Policy);
+        }
+        return Ok(ValidatedMessage::Governance(Box::new(message)));
     }
+    if *topic == TOPIC_IDENTITY.hash() {
+        let document: IdentityDocument =
+            serde_json::from_slice(data).map_err(|_| RejectReason::Malformed)?;
+        if document.network != network_id() {
+            return Err(RejectReason::Network);
+        }
+        document.verify().map_err(|_| RejectReason::Signature)?;
+        return Ok(ValidatedMessage::Identity(Box::new(document)));
+    }
+    if !is_anchor_topic(cfg, topic) {
+        return Ok(ValidatedMessage::Ignored);
+    }
+    let envelope = serde_json::from_slice::<AnchorEnvelope>(data)
+        .map_err(|_| RejectReason::Malformed)?
+        .upgrade()
+        .map_err(|_| RejectReason::Version)?;
+    if envelope.network != network_id() {
+        return Err(RejectReason::Network);
+    }
+    envelope.validate().map_err(|_| RejectReason::Malformed)?;
+    let payload = BASE64
+        .decode(envelope.payload.as_bytes())
+        .map_err(|_| RejectReason::Malformed)?;
+    if payload.len() > cfg.parameters.max_envelope_bytes() {
+        return Err(RejectReason::Oversize);
+    }
+    verify_signature_base64(&envelope.public_key, &payload, &envelope.signature)
+        .map_err(|_| RejectReason::Signature)?;
+    cfg.identities
+        .check_envelope(&envelope.node_id, &envelope.public_key)
+        .map_err(|_| RejectReason::Identity)?;
+    let remote_verifying =
+        decode_public_key_base64(&envelope.public_key).map_err(|_| RejectReason::Malformed)?;
+    if !policy_permits(cfg.membership_policy.as_ref(), &remote_verifying.to_bytes()) {
+        return Err(RejectReason::Policy);
+    }
+    let payload_str = std::str::from_utf8(&payload).map_err(|_| RejectReason::Malformed)?;
+    let anchor_json =
+        AnchorJson::from_json_str(payload_str).map_err(|_| RejectReason::Malformed)?;
+    if anchor_json.network != network_id() {
+        return Err(RejectReason::Network);
+    }
+    if anchor_json.entries.len() > MAX_ANCHOR_ENTRIES {
+        return Err(RejectReason::Oversize);
+    }
+    Ok(ValidatedMessage::Anchor(Box::new(ValidatedAnchor {
+        envelope,
+        anchor_json,
+        remote_verifying,
+    })))
 }
 
 /// Errors surfaced by the networking runtime.
@@ -1812,6 +2462,7 @@ struct JrocBehaviour {
     pub gossipsub: gossipsub::Behaviour,
     pub identify: identify::Behaviour,
     pub kademlia: kad::Behaviour<MemoryStore>,
+    pub direct: request_response::json::Behaviour<DirectMessage, DirectReceipt>,
 }
 
 /// Starts the JULIAN Protocol libp2p node and continues until Ctrl+C.
@@ -1819,8 +2470,10 @@ struct JrocBehaviour {
 /// The swarm listens on `cfg.listen_addr`, optionally dials bootstrap peers,
 /// recomputes anchors at `cfg.broadcast_interval`, and emits gossip messages
 /// for every new anchor. Incoming envelopes are verified and reconciled with
-/// the local ledger according to `cfg.quorum`.
-pub async fn run_network(cfg: NetConfig) -> Result<(), NetworkError> {
+/// the local ledger according to `cfg.quorum`.  Both values, and the gossip
+/// envelope limit, are overridden by `governance/parameters.json` and follow
+/// executed governance proposals while the node runs.
+pub async fn run_network(mut cfg: NetConfig) -> Result<(), NetworkError> {
     crate::net::refresh_migration_mode_from_env();
     let local_key_bytes = cfg.key_material.verifying.to_bytes();
     if !policy_permits(cfg.membership_policy.as_ref(), &local_key_bytes) {
@@ -1834,6 +2487,25 @@ pub async fn run_network(cfg: NetConfig) -> Result<(), NetworkError> {
             cfg.log_dir.display()
         ))
     })?;
+    let parameters = ParameterStore::open(&cfg.log_dir)
+        .and_then(|store| store.apply(RuntimeParameters::new(cfg.quorum, cfg.broadcast_interval)))
+        .map_err(|err| NetworkError::Policy(err.to_string()))?;
+    println!(
+        "QSYS|mod=PARAMS|evt=LOADED|quorum={}|broadcast_interval_ms={}|max_envelope_bytes={}",
+        parameters.quorum, parameters.broadcast_interval_ms, parameters.max_envelope_bytes
+    );
+    cfg.parameters = SharedParameters::new(parameters);
+    match read_fork_evidence(&cfg.log_dir) {
+        Ok(Some(evidence)) => {
+            let _ = cfg.safe_mode.engage(&cfg.log_dir, &evidence);
+            println!(
+                "QSYS|mod=FORK|evt=SAFE_MODE|epoch={}|action=resume_halted",
+                evidence.epoch
+            );
+        }
+        Ok(None) => {}
+        Err(err) => eprintln!("fork evidence unreadable: {err}"),
+    }
     if let Some(blob_dir) = cfg.blob_dir.as_ref() {
         fs::create_dir_all(blob_dir).map_err(|err| {
             NetworkError::Io(format!(
@@ -1842,11 +2514,48 @@ pub async fn run_network(cfg: NetConfig) -> Result<(), NetworkError> {
             ))
         })?;
     }
+    if let Some(shipping) = cfg.log_shipping.clone() {
+        let key = cfg.key_material.clone();
+        tokio::spawn(async move {
+            if let Err(err) = run_log_shipper(shipping, key).await {
+                eprintln!("log shipper error: {err}");
+            }
+        });
+    }
     let mut swarm = build_swarm(&cfg)?;
     Swarm::listen_on(&mut swarm, cfg.listen_addr.clone())
         .map_err(|err| NetworkError::Libp2p(format!("{err:?}")))?;
-    let mut bootstrap_peers = 0usize;
+    let peer_store_path = PeerStore::path_for(&cfg.log_dir);
+    let mut peer_store = PeerStore::load(&peer_store_path).unwrap_or_else(|err| {
+        eprintln!(
+            "failed to load peer store {}: {err}",
+            peer_store_path.display()
+        );
+        PeerStore::default()
+    });
+    let genesis = active_genesis();
+    println!(
+        "QSYS|mod=GENESIS|evt=ACTIVE|network={}|chain_id={}|digest={}",
+        genesis.network_id,
+        genesis.chain_id,
+        hex::encode(crate::genesis::active_genesis_digest())
+    );
+    let cached: Vec<Multiaddr> = peer_store
+        .preferred_addrs(now_millis() / 1000, PEER_STORE_MAX_AGE_SECS)
+        .iter()
+        .filter_map(|addr| addr.parse().ok())
+        .collect();
+    if !cached.is_empty() {
+        println!("QSYS|mod=NET|evt=PEER_CACHE|peers={}", cached.len());
+    }
+    let mut dial_order = cached;
     for addr in &cfg.bootstraps {
+        if !dial_order.contains(addr) {
+            dial_order.push(addr.clone());
+        }
+    }
+    let mut bootstrap_peers = 0usize;
+    for addr in &dial_order {
         if let Some(peer_id) = extract_peer_id(addr) {
             swarm
                 .behaviour_mut()
@@ -1865,21 +2574,71 @@ pub async fn run_network(cfg: NetConfig) -> Result<(), NetworkError> {
             Err(err) => eprintln!("kademlia bootstrap failed: {err:?}"),
         }
     }
+    match load_latest_checkpoint(&cfg.log_dir) {
+        Ok(Some(checkpoint)) => match checkpoint.digest() {
+            Ok(digest) => {
+                provide_checkpoint(&mut swarm, &digest);
+                find_checkpoint_providers(&mut swarm, &digest);
+            }
+            Err(err) => eprintln!("latest checkpoint not advertised: {err}"),
+        },
+        Ok(None) => {}
+        Err(err) => eprintln!("latest checkpoint not advertised: {err}"),
+    }
+    for digest in &cfg.checkpoint_queries {
+        find_checkpoint_providers(&mut swarm, digest);
+    }
 
-    let mut ticker = time::interval(cfg.broadcast_interval);
-    ticker.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+    let mut ticker = Ticker::new(cfg.clock.clone(), cfg.parameters.broadcast_interval());
+    let mut chaos_ticker = Ticker::new(cfg.clock.clone(), CHAOS_FLUSH_INTERVAL);
+    let mut native_producer_ticker = Ticker::new(cfg.clock.clone(), NATIVE_PRODUCER_TICK);
+    if let Some(chaos) = cfg.chaos.as_ref() {
+        let config = chaos.config();
+        println!(
+            "QSYS|mod=CHAOS|evt=ENABLED|latency_ms={}|drop_rate={}|duplicate_rate={}",
+            config.latency_ms, config.drop_rate, config.duplicate_rate
+        );
+    }
 
     let metrics = cfg.metrics.clone();
+    let identity = MetricsIdentity {
+        node_id: cfg.node_id.clone(),
+        peer_id: cfg.key_material.libp2p.public().to_peer_id().to_string(),
+        public_key_b64: encode_public_key_base64(&cfg.key_material.verifying),
+        chain_id: cfg.evm_chain_id,
+    };
+    if let Some(push) = cfg.metrics_push.clone() {
+        let render_metrics = metrics.clone();
+        let record_metrics = metrics.clone();
+        let identity = identity.clone();
+        tokio::spawn(async move {
+            let pusher = run_metrics_pusher(
+                push,
+                move || render_metrics.render(&identity),
+                move |delivered| record_metrics.record_metrics_push(delivered),
+            );
+            if let Err(err) = pusher.await {
+                eprintln!("metrics pusher error: {err}");
+            }
+        });
+    }
+    if let Some(webhooks) = cfg.webhooks.clone() {
+        cfg.webhook_dispatcher = Some(
+            WebhookDispatcher::start(webhooks, cfg.node_id.clone())
+                .map_err(|err| NetworkError::Io(err.to_string()))?,
+        );
+    }
+    if let Some(alerts) = cfg.alerts.clone() {
+        cfg.alert_monitor = Some(
+            AlertMonitor::start(alerts, cfg.node_id.clone(), metrics.alerts.clone())
+                .map_err(|err| NetworkError::Io(err.to_string()))?,
+        );
+    }
     if let Some(addr) = cfg.metrics_addr {
         let metrics_clone = metrics.clone();
-        let identity = MetricsIdentity {
-            node_id: cfg.node_id.clone(),
-            peer_id: cfg.key_material.libp2p.public().to_peer_id().to_string(),
-            public_key_b64: encode_public_key_base64(&cfg.key_material.verifying),
-            chain_id: cfg.evm_chain_id,
-        };
+        let limiter = cfg.http_connection_limit.clone();
         tokio::spawn(async move {
-            if let Err(err) = run_metrics_server(addr, metrics_clone, identity).await {
+            if let Err(err) = run_metrics_server(addr, metrics_clone, identity, limiter).await {
                 eprintln!("metrics server error: {err}");
             }
         });
@@ -1897,6 +2656,17 @@ pub async fn run_network(cfg: NetConfig) -> Result<(), NetworkError> {
             .into_iter()
             .map(|key| encode_public_key_base64(&key))
             .collect::<Vec<_>>();
+        let genesis_validators = &active_genesis().validators;
+        if !genesis_validators.is_empty() {
+            validators.sort();
+            if validators.is_empty() {
+                validators = genesis_validators.clone();
+            } else if &validators != genesis_validators {
+                return Err(NetworkError::Policy(
+                    "membership policy differs from the genesis validator set".to_string(),
+                ));
+            }
+        }
         if validators.is_empty() {
             if cfg.quorum != 1 {
                 return Err(NetworkError::Policy(
@@ -1907,34 +2677,59 @@ pub async fn run_network(cfg: NetConfig) -> Result<(), NetworkError> {
             validators.push(local_validator);
         }
         let state_base = cfg.blob_dir.as_ref().unwrap_or(&cfg.log_dir);
-        let state_path = state_base.join("native_chain_state.json");
+        let state_path = state_base.join(format!(
+            "native_chain_state.{}",
+            cfg.storage_backend.extension()
+        ));
         let state = NativeChainState::load_or_initialize(
             &state_path,
             cfg.evm_chain_id,
             cfg.stake_registry_path.as_deref(),
             validators.clone(),
             cfg.quorum,
+            cfg.clock.now().as_secs(),
         )
         .map_err(NetworkError::Codec)?;
         let shared_state = Arc::new(RwLock::new(state));
-        native_runtime = Some(
-            NativeChainRuntime::new(
-                shared_state.clone(),
-                state_path,
-                validators,
-                cfg.quorum,
-                &cfg.key_material.signing,
-            )
-            .await
-            .map_err(NetworkError::Policy)?,
-        );
+        cfg.native_state = Some(shared_state.clone());
+        let mut runtime = NativeChainRuntime::new(
+            shared_state.clone(),
+            state_path,
+            validators,
+            cfg.quorum,
+            &cfg.key_material.signing,
+            cfg.clock.clone(),
+        )
+        .await
+        .map_err(NetworkError::Policy)?;
+        runtime.set_max_block_interval(cfg.native_max_block_interval);
+        runtime.set_pending_limits(cfg.native_pending_ttl, cfg.native_max_pending);
+        let pending_pool = runtime.pending_pool();
+        let _ = cfg.metrics.mempool.set(pending_pool.clone());
+        let block_archive = state_base.join(BLOCK_ARCHIVE_DIR);
+        if let Some(keep) = cfg.native_prune_keep {
+            runtime.set_pruning(block_archive.clone(), keep);
+        }
+        native_runtime = Some(runtime);
         if let Some(addr) = cfg.evm_rpc_listen {
-            let rpc_cfg = EvmRpcConfig::new(
+            let mut rpc_cfg = EvmRpcConfig::new(
                 addr,
                 cfg.evm_chain_id,
                 shared_state,
                 native_command_sender.clone(),
             );
+            rpc_cfg.connection_limit = cfg.http_connection_limit.clone();
+            rpc_cfg.asset_registry = cfg.stake_registry_path.clone();
+            rpc_cfg.beacon_log_dir = Some(cfg.log_dir.clone());
+            rpc_cfg.cors = cfg.evm_rpc_cors.clone();
+            rpc_cfg.tx_input_handlers = cfg.evm_rpc_tx_handlers.clone();
+            rpc_cfg.block_archive = Some(block_archive);
+            rpc_cfg.safe_mode = cfg.safe_mode.clone();
+            rpc_cfg.parameters = cfg.parameters.clone();
+            rpc_cfg.tiers = cfg.evm_rpc_tiers.clone();
+            rpc_cfg.gas_margin_percent = cfg.evm_rpc_gas_margin_percent;
+            rpc_cfg.pending = Some(pending_pool);
+            let _ = cfg.metrics.rpc_tiers.set(cfg.evm_rpc_tiers.clone());
             tokio::spawn(async move {
                 if let Err(err) = run_evm_rpc_server(rpc_cfg).await {
                     eprintln!("evm rpc server error: {err}");
@@ -1972,14 +2767,26 @@ pub async fn run_network(cfg: NetConfig) -> Result<(), NetworkError> {
 
     let mut seen_payloads = PayloadCache::new(metrics.clone());
     let mut invalid_counters: HashMap<libp2p::PeerId, usize> = HashMap::new();
+    let mut envelope_peers: HashMap<libp2p::PeerId, (u32, u32)> = HashMap::new();
     let mut last_payload = Vec::new();
-    let mut last_publish: Option<Instant> = None;
+    let mut last_publish: Option<Duration> = None;
     let mut broadcast_counter: u64 = 0;
     let mut bft_state = BftState::new(cfg.bft_round_ms);
     let mut anchor_votes = AnchorVotes::new();
-    let mut last_native_tip: Option<Instant> = None;
+    let mut finality = FinalityTracker::default();
+    let mut anchor_rates = AnchorRateLimiter::new(cfg.max_anchors_per_minute);
+    let mut direct_pending = DirectPending::new();
+    let mut governance =
+        GovernanceBook::open(&cfg.log_dir).map_err(|err| NetworkError::Io(err.to_string()))?;
+    let mut last_native_tip: Option<Duration> = None;
 
     let local_peer = cfg.key_material.libp2p.public().to_peer_id();
+    let identity_document =
+        IdentityDocument::issue(network_id(), &cfg.node_id, &cfg.key_material, now_millis());
+    cfg.identities
+        .register(&identity_document, &local_peer)
+        .map_err(|err| NetworkError::Key(err.to_string()))?;
+    let mut last_identity_publish: Option<Duration> = None;
 
     println!(
         "QSYS|mod=NET|evt=LISTEN|node={} peer={} addr={} topic={}",
@@ -1992,6 +2799,13 @@ pub async fn run_network(cfg: NetConfig) -> Result<(), NetworkError> {
     loop {
         select! {
             _ = ticker.tick() => {
+                if let Some(alerts) = &cfg.alert_monitor {
+                    let now_ms = cfg.clock.now_ms();
+                    alerts.peers(swarm.connected_peers().count(), now_ms);
+                    alerts.safe_mode(cfg.safe_mode.is_engaged(), now_ms);
+                }
+                let envelope_version =
+                    negotiate_envelope_version(envelope_peers.values().copied());
                 if cfg.bft_enabled {
                     if let Err(err) = bft_tick(
                         &mut swarm,
@@ -2000,11 +2814,12 @@ pub async fn run_network(cfg: NetConfig) -> Result<(), NetworkError> {
                         &mut last_payload,
                         &mut last_publish,
                         &mut broadcast_counter,
+                        envelope_version,
                         &metrics,
                     )
                     .await
                     {
-                        metrics.inc_gossipsub_rejects();
+                        metrics.inc_gossipsub_rejects(RejectReason::LocalPublish);
                         eprintln!("bft tick error: {err}");
                     }
                 } else if let Err(err) = broadcast_local_anchor(
@@ -2013,52 +2828,46 @@ pub async fn run_network(cfg: NetConfig) -> Result<(), NetworkError> {
                     &mut last_payload,
                     &mut last_publish,
                     &mut broadcast_counter,
+                    envelope_version,
                     &metrics,
                 )
                 .await
                 {
-                    metrics.inc_gossipsub_rejects();
+                    metrics.inc_gossipsub_rejects(RejectReason::LocalPublish);
                     eprintln!("broadcast error: {err}");
                 }
                 if let Err(err) = broadcast_evidence(&mut swarm, &cfg) {
                     eprintln!("evidence broadcast error: {err}");
                 }
-                if let Some(runtime) = native_runtime.as_mut() {
-                    match runtime.propose(&cfg.key_material.signing).await {
-                        Ok(Some(proposal)) => {
-                            println!(
-                                "QSYS|mod=NATIVE_CHAIN|evt=PROPOSE|height={}|hash={}|txs={}",
-                                proposal.number,
-                                proposal.hash,
-                                proposal.transactions.len()
-                            );
-                            let message = NativeChainMessage::new(
-                                NativeChainMessagePayload::Proposal(proposal)
-                            );
-                            if let Err(err) = publish_native_message(
-                                &mut swarm,
-                                runtime,
-                                &cfg.key_material.signing,
-                                message,
-                                true,
-                                true,
-                                &metrics,
-                            ).await {
-                                eprintln!("native chain proposal error: {err}");
-                            }
-                        }
-                        Ok(None) => {}
-                        Err(err) => eprintln!("native chain proposal error: {err}"),
+                send_direct_outbox(&mut swarm, &cfg, &mut direct_pending);
+                run_governance(&mut swarm, &cfg, &mut governance);
+                let interval = cfg.parameters.broadcast_interval();
+                if ticker.period() != interval {
+                    ticker = Ticker::new(cfg.clock.clone(), interval);
+                    ticker.reset();
+                }
+                if last_identity_publish
+                    .map(|published| cfg.clock.since(published) >= IDENTITY_REPUBLISH_INTERVAL)
+                    .unwrap_or(true)
+                {
+                    match publish_identity_document(&mut swarm, &cfg, &identity_document) {
+                        Ok(true) => last_identity_publish = Some(cfg.clock.now()),
+                        Ok(false) => {}
+                        Err(err) => eprintln!("identity publish error: {err}"),
                     }
+                }
+                if let Some(runtime) = native_runtime.as_mut() {
+                    // Re-broadcasts an in-flight proposal for the next height.
+                    propose_native_block(&mut swarm, runtime, &cfg, &metrics).await;
                     if last_native_tip
-                        .map(|published| published.elapsed() >= Duration::from_secs(5))
+                        .map(|published| cfg.clock.since(published) >= Duration::from_secs(5))
                         .unwrap_or(true)
                     {
                         let message = runtime.tip_message().await;
                         if let Err(err) = publish_native_message(
                             &mut swarm,
                             runtime,
-                            &cfg.key_material.signing,
+                            &cfg,
                             message,
                             false,
                             true,
@@ -2066,11 +2875,27 @@ pub async fn run_network(cfg: NetConfig) -> Result<(), NetworkError> {
                         ).await {
                             eprintln!("native chain tip error: {err}");
                         } else {
-                            last_native_tip = Some(Instant::now());
+                            last_native_tip = Some(cfg.clock.now());
                         }
                     }
                 }
             }
+            _ = native_producer_ticker.tick(), if native_runtime.is_some() => {
+                let runtime = native_runtime.as_mut().expect("guarded native runtime");
+                let expired = runtime.expire_pending();
+                if !expired.is_empty() {
+                    println!(
+                        "QSYS|mod=NATIVE_CHAIN|evt=TX_EVICTED|reason=ttl|count={}",
+                        expired.len()
+                    );
+                }
+                if runtime.seal_due().await {
+                    propose_native_block(&mut swarm, runtime, &cfg, &metrics).await;
+                }
+            }
+            _ = chaos_ticker.tick(), if cfg.chaos.is_some() => {
+                flush_delayed_gossip(&mut swarm, &cfg);
+            }
             event = swarm.select_next_some() => {
                 if let Err(err) = handle_event(
                     event,
@@ -2080,8 +2905,14 @@ pub async fn run_network(cfg: NetConfig) -> Result<(), NetworkError> {
                     &mut invalid_counters,
                     &mut bft_state,
                     &mut anchor_votes,
+                    &mut finality,
+                    &mut anchor_rates,
+                    &mut direct_pending,
+                    &mut governance,
                     &metrics,
                     &mut native_runtime,
+                    &mut peer_store,
+                    &mut envelope_peers,
                 ).await {
                     eprintln!("network error: {err}");
                 }
@@ -2101,7 +2932,7 @@ pub async fn run_network(cfg: NetConfig) -> Result<(), NetworkError> {
                                 if let Err(err) = publish_native_message(
                                     &mut swarm,
                                     runtime,
-                                    &cfg.key_material.signing,
+                                    &cfg,
                                     message,
                                     false,
                                     true,
@@ -2157,6 +2988,7 @@ fn build_behaviour(
 
     let gossipsub_config = gossipsub::ConfigBuilder::default()
         .validation_mode(ValidationMode::Strict)
+        .validate_messages()
         .message_id_fn(|message: &gossipsub::Message| {
             let mut hasher = Sha256::new();
             hasher.update(&message.data);
@@ -2176,6 +3008,15 @@ fn build_behaviour(
     gossipsub
         .subscribe(&TOPIC_VOTES)
         .map_err(|err| NetworkError::Libp2p(format!("{err:?}")))?;
+    gossipsub
+        .subscribe(&TOPIC_IDENTITY)
+        .map_err(|err| NetworkError::Libp2p(format!("{err:?}")))?;
+    gossipsub
+        .subscribe(&TOPIC_CHECKPOINTS)
+        .map_err(|err| NetworkError::Libp2p(format!("{err:?}")))?;
+    gossipsub
+        .subscribe(&TOPIC_GOVERNANCE)
+        .map_err(|err| NetworkError::Libp2p(format!("{err:?}")))?;
     if native_chain_enabled {
         gossipsub
             .subscribe(&TOPIC_NATIVE_CHAIN)
@@ -2183,70 +3024,330 @@ fn build_behaviour(
     }
 
     let identify_config = identify::Config::new("mfenx-powerhouse/1.0.0".into(), key.public())
+        .with_agent_version(format!(
+            "power_house/{} {}",
+            env!("CARGO_PKG_VERSION"),
+            envelope_version_token()
+        ))
         .with_push_listen_addr_updates(true);
     let identify = identify::Behaviour::new(identify_config);
 
     let store = MemoryStore::new(peer_id);
-    let kademlia = kad::Behaviour::with_config(peer_id, store, kad::Config::default());
+    let mut kademlia = kad::Behaviour::with_config(peer_id, store, kad::Config::default());
+    // Validators must answer provider lookups even before an external address is confirmed.
+    kademlia.set_mode(Some(kad::Mode::Server));
+
+    let direct = request_response::json::Behaviour::new(
+        [(StreamProtocol::new(DIRECT_PROTOCOL), ProtocolSupport::Full)],
+        request_response::Config::default().with_request_timeout(DIRECT_REQUEST_TIMEOUT),
+    );
 
     Ok(JrocBehaviour {
         gossipsub,
         identify,
         kademlia,
+        direct,
     })
 }
 
-fn evidence_outbox(cfg: &NetConfig) -> Option<PathBuf> {
-    cfg.blob_dir
-        .as_ref()
-        .map(|d| d.join("evidence_outbox.jsonl"))
-}
+/// Gossips queued governance messages, then re-tallies undecided proposals
+/// and executes the ones that passed.
+fn run_governance(swarm: &mut Swarm<JrocBehaviour>, cfg: &NetConfig, book: &mut GovernanceBook) {
+    let queued = drain_governance_outbox(&cfg.log_dir).unwrap_or_else(|err| {
+        eprintln!("governance outbox error: {err}");
+        Vec::new()
+    });
+    for message in queued {
+        if !record_governance_message(book, message.clone(), "local") {
+            continue;
+        }
+        let encoded = match serde_json::to_vec(&message) {
+            Ok(encoded) => encoded,
+            Err(err) => {
+                eprintln!("governance encode failed: {err}");
+                continue;
+            }
+        };
+        match publish_gossip(swarm, cfg, TOPIC_GOVERNANCE.clone(), encoded) {
+            Ok(()) | Err(PublishError::Duplicate) => {}
+            Err(PublishError::NoPeersSubscribedToTopic) => {
+                println!("QSYS|mod=GOVERNANCE|evt=STANDBY|reason=awaiting_peers");
+            }
+            Err(err) => eprintln!("governance gossip failed: {err}"),
+        }
+    }
 
-fn broadcast_evidence(
-    swarm: &mut Swarm<JrocBehaviour>,
-    cfg: &NetConfig,
-) -> Result<(), NetworkError> {
-    let Some(path) = evidence_outbox(cfg) else {
-        return Ok(());
-    };
-    let contents = match fs::read_to_string(&path) {
-        Ok(c) => c,
+    if !book
+        .iter()
+        .any(|record| matches!(record.status, ProposalStatus::Open | ProposalStatus::Passed))
+    {
+        return;
+    }
+    let mut parameters = match ParameterStore::open(&cfg.log_dir) {
+        Ok(parameters) => parameters.with_runtime(cfg.parameters.clone()),
         Err(err) => {
-            if err.kind() == io::ErrorKind::NotFound {
-                return Ok(());
-            }
-            return Err(NetworkError::Io(err.to_string()));
+            eprintln!("governance parameters unreadable: {err}");
+            return;
         }
     };
-    if contents.trim().is_empty() {
-        return Ok(());
+    if let Some(path) = cfg.governance_fee_schedule.as_ref() {
+        match FeeScheduleConfig::load(path) {
+            Ok(config) => parameters = parameters.with_fee_schedule_config(config),
+            Err(err) => eprintln!("governance fee schedule unreadable: {err}"),
+        }
     }
-    let lines: Vec<&str> = contents.lines().filter(|l| !l.trim().is_empty()).collect();
-    if lines.is_empty() {
-        return Ok(());
+    let before = cfg.parameters.snapshot();
+    let mut multisig = cfg.governance_multisig.as_ref().and_then(|path| {
+        MultisigPolicy::load(path)
+            .map_err(|err| eprintln!("governance multisig policy unreadable: {err}"))
+            .ok()
+    });
+    let mut allowlist = cfg.governance_allowlist.as_ref().and_then(|path| {
+        AllowlistFile::open(path)
+            .map_err(|err| eprintln!("governance allowlist unreadable: {err}"))
+            .ok()
+    });
+    let mut hooks: Vec<&mut dyn ExecutionHook> = vec![&mut parameters];
+    if let Some(multisig) = multisig.as_mut() {
+        hooks.push(multisig);
     }
-    fs::write(&path, "").map_err(|e| NetworkError::Io(e.to_string()))?;
-    for line in lines {
+    if let Some(allowlist) = allowlist.as_mut() {
+        hooks.push(allowlist);
+    }
+    match book.evaluate(
+        cfg.membership_policy.as_ref(),
+        cfg.clock.now_ms(),
+        &mut hooks,
+    ) {
+        Ok(changed) => {
+            for (id, status) in changed {
+                println!(
+                    "QSYS|mod=GOVERNANCE|evt=STATUS|id={id}|status={}",
+                    status.label()
+                );
+                if status != ProposalStatus::Executed {
+                    continue;
+                }
+                if let Some(ProposalAction::AdmitMember { request }) =
+                    book.get(&id).map(|record| &record.proposal.action)
+                {
+                    println!(
+                        "QSYS|mod=GOVERNANCE|evt=ADMITTED|node={}|key={}|restart_required=true",
+                        request.node_id, request.public_key
+                    );
+                }
+            }
+        }
+        Err(err) => eprintln!("governance evaluation failed: {err}"),
+    }
+    let after = cfg.parameters.snapshot();
+    if after != before {
+        println!(
+            "QSYS|mod=PARAMS|evt=RELOAD|quorum={}|broadcast_interval_ms={}|max_envelope_bytes={}|base_fee={}",
+            after.quorum,
+            after.broadcast_interval_ms,
+            after.max_envelope_bytes,
+            after.fee_schedule.base_fee
+        );
+    }
+}
+
+/// Stores a proposal or vote, returning whether it was new.
+fn record_governance_message(
+    book: &mut GovernanceBook,
+    message: GovernanceMessage,
+    source: &str,
+) -> bool {
+    let (kind, id) = match &message {
+        GovernanceMessage::Proposal(proposal) => ("proposal", proposal.id_hex()),
+        GovernanceMessage::Vote(vote) => ("vote", vote.proposal_id.clone()),
+    };
+    match book.insert(message) {
+        Ok(true) => {
+            println!("QSYS|mod=GOVERNANCE|evt=RECEIVED|kind={kind}|id={id}|source={source}");
+            true
+        }
+        Ok(false) => false,
+        Err(err) => {
+            eprintln!("governance {kind} {id} refused: {err}");
+            false
+        }
+    }
+}
+
+/// Sends every message queued in the direct outbox.
+fn send_direct_outbox(
+    swarm: &mut Swarm<JrocBehaviour>,
+    cfg: &NetConfig,
+    pending: &mut DirectPending,
+) {
+    let queued = match drain_outbox(&cfg.log_dir) {
+        Ok(queued) => queued,
+        Err(err) => {
+            eprintln!("direct outbox error: {err}");
+            return;
+        }
+    };
+    for entry in queued {
+        let Ok(peer) = entry.peer_id.parse::<PeerId>() else {
+            eprintln!("direct outbox: invalid peer id {}", entry.peer_id);
+            continue;
+        };
+        let message = DirectMessage::new(
+            network_id(),
+            &cfg.node_id,
+            now_millis(),
+            entry.payload.clone(),
+        );
+        let digest = message.digest();
+        let kind = message.payload.kind();
+        let request_id = swarm.behaviour_mut().direct.send_request(&peer, message);
+        pending.insert(request_id, (peer, digest, entry));
+        println!(
+            "QSYS|mod=DIRECT|evt=SENT|peer={peer}|kind={kind}|digest={}",
+            hex::encode(digest)
+        );
+    }
+}
+
+fn handle_direct_event(
+    swarm: &mut Swarm<JrocBehaviour>,
+    cfg: &NetConfig,
+    pending: &mut DirectPending,
+    event: request_response::Event<DirectMessage, DirectReceipt>,
+) {
+    match event {
+        request_response::Event::Message {
+            peer,
+            message:
+                request_response::Message::Request {
+                    request, channel, ..
+                },
+            ..
+        } => {
+            let digest = request.digest();
+            let kind = request.payload.kind();
+            let outcome = request.validate(network_id());
+            let accepted = outcome.is_ok();
+            if accepted {
+                let entry = DirectInboxEntry {
+                    peer_id: peer.to_string(),
+                    message: request,
+                };
+                if let Err(err) = append_jsonl(&cfg.log_dir.join(DIRECT_INBOX_FILE), &entry) {
+                    eprintln!("direct inbox write failed: {err}");
+                }
+            }
+            let receipt = DirectReceipt::sign(
+                &digest,
+                &cfg.node_id,
+                &cfg.key_material,
+                outcome,
+                now_millis(),
+            );
+            println!(
+                "QSYS|mod=DIRECT|evt=RECEIVED|peer={peer}|kind={kind}|accepted={accepted}|digest={}",
+                hex::encode(digest)
+            );
+            if swarm
+                .behaviour_mut()
+                .direct
+                .send_response(channel, receipt)
+                .is_err()
+            {
+                eprintln!("direct receipt to {peer} not sent: stream closed");
+            }
+        }
+        request_response::Event::Message {
+            peer,
+            message:
+                request_response::Message::Response {
+                    request_id,
+                    response,
+                },
+            ..
+        } => {
+            let Some((recipient, digest, _)) = pending.remove(&request_id) else {
+                return;
+            };
+            if let Err(err) = response.verify(&recipient, &digest) {
+                println!("QSYS|mod=DIRECT|evt=RECEIPT_INVALID|peer={peer}|reason={err}");
+                return;
+            }
+            println!(
+                "QSYS|mod=DIRECT|evt=RECEIPT|peer={peer}|accepted={}|digest={}",
+                response.accepted, response.message_digest
+            );
+            let entry = DirectReceiptEntry {
+                peer_id: peer.to_string(),
+                receipt: response,
+            };
+            if let Err(err) = append_jsonl(&cfg.log_dir.join(DIRECT_RECEIPTS_FILE), &entry) {
+                eprintln!("direct receipt write failed: {err}");
+            }
+        }
+        request_response::Event::OutboundFailure {
+            peer,
+            request_id,
+            error,
+            ..
+        } => {
+            println!("QSYS|mod=DIRECT|evt=SEND_FAILED|peer={peer}|reason={error}");
+            if let Some((_, _, entry)) = pending.remove(&request_id) {
+                if let Err(err) = requeue_outbox(&cfg.log_dir, &entry) {
+                    eprintln!("direct outbox requeue failed: {err}");
+                }
+            }
+        }
+        request_response::Event::InboundFailure { peer, error, .. } => {
+            println!("QSYS|mod=DIRECT|evt=RECEIVE_FAILED|peer={peer}|reason={error}");
+        }
+        request_response::Event::ResponseSent { .. } => {}
+    }
+}
+
+fn evidence_outbox(cfg: &NetConfig) -> Option<PathBuf> {
+    cfg.blob_dir
+        .as_ref()
+        .map(|d| d.join("evidence_outbox.jsonl"))
+}
+
+fn broadcast_evidence(
+    swarm: &mut Swarm<JrocBehaviour>,
+    cfg: &NetConfig,
+) -> Result<(), NetworkError> {
+    let Some(path) = evidence_outbox(cfg) else {
+        return Ok(());
+    };
+    let contents = match fs::read_to_string(&path) {
+        Ok(c) => c,
+        Err(err) => {
+            if err.kind() == io::ErrorKind::NotFound {
+                return Ok(());
+            }
+            return Err(NetworkError::Io(err.to_string()));
+        }
+    };
+    if contents.trim().is_empty() {
+        return Ok(());
+    }
+    let lines: Vec<&str> = contents.lines().filter(|l| !l.trim().is_empty()).collect();
+    if lines.is_empty() {
+        return Ok(());
+    }
+    fs::write(&path, "").map_err(|e| NetworkError::Io(e.to_string()))?;
+    for line in lines {
         if let Ok(record) = serde_json::from_str::<EvidenceRecord>(line) {
             let env = sign_evidence(&cfg.key_material.signing, &record);
             if let Ok(msg) = serde_json::to_vec(&env) {
-                let _ = swarm
-                    .behaviour_mut()
-                    .gossipsub
-                    .publish(TOPIC_EVIDENCE.clone(), msg);
+                let _ = publish_gossip(swarm, cfg, TOPIC_EVIDENCE.clone(), msg);
             }
         } else if let Ok(av) = serde_json::from_str::<AvailabilityEvidence>(line) {
             let msg = serde_json::to_vec(&av).map_err(|e| NetworkError::Codec(e.to_string()))?;
-            let _ = swarm
-                .behaviour_mut()
-                .gossipsub
-                .publish(TOPIC_EVIDENCE.clone(), msg);
+            let _ = publish_gossip(swarm, cfg, TOPIC_EVIDENCE.clone(), msg);
         } else if let Ok(rf) = serde_json::from_str::<RollupFaultEvidence>(line) {
             let msg = serde_json::to_vec(&rf).map_err(|e| NetworkError::Codec(e.to_string()))?;
-            let _ = swarm
-                .behaviour_mut()
-                .gossipsub
-                .publish(TOPIC_EVIDENCE.clone(), msg);
+            let _ = publish_gossip(swarm, cfg, TOPIC_EVIDENCE.clone(), msg);
         }
     }
     Ok(())
@@ -2281,6 +3382,7 @@ fn handle_evidence_message(cfg: &NetConfig, data: &[u8]) -> Result<(), NetworkEr
             );
         }
         record_slash_with_registry(
+            &cfg.key_material.signing,
             &cfg.membership_policy,
             &cfg.stake_registry_path,
             &cfg.token_mode_contract,
@@ -2308,6 +3410,7 @@ fn handle_evidence_message(cfg: &NetConfig, data: &[u8]) -> Result<(), NetworkEr
             da_receipt: None,
         }) {
             record_slash_with_registry(
+                &cfg.key_material.signing,
                 &cfg.membership_policy,
                 &cfg.stake_registry_path,
                 &cfg.token_mode_contract,
@@ -2344,6 +3447,7 @@ fn handle_evidence_message(cfg: &NetConfig, data: &[u8]) -> Result<(), NetworkEr
             da_receipt: None,
         }) {
             record_slash_with_registry(
+                &cfg.key_material.signing,
                 &cfg.membership_policy,
                 &cfg.stake_registry_path,
                 &cfg.token_mode_contract,
@@ -2382,12 +3486,20 @@ fn handle_vote_message(
     Ok(())
 }
 
+/// Local anchor as broadcast and voted on: the ledger from the logs plus the
+/// current stake registry root.
+fn load_local_anchor(cfg: &NetConfig) -> Result<LedgerAnchor, NetworkError> {
+    let mut anchor = load_anchor_from_logs(&cfg.log_dir)?;
+    anchor.metadata.registry_root = stake_registry_root(cfg);
+    Ok(anchor)
+}
+
 fn build_anchor_payload(cfg: &NetConfig) -> Result<(AnchorJson, Vec<u8>, usize), NetworkError> {
-    let ledger = load_anchor_from_logs(&cfg.log_dir)?;
-    let timestamp_ms = now_millis();
+    let ledger = load_local_anchor(cfg)?;
+    let timestamp_ms = cfg.clock.now_ms();
     let anchor_json = AnchorJson::from_ledger(
         cfg.node_id.clone(),
-        cfg.quorum,
+        cfg.parameters.quorum(),
         &ledger,
         timestamp_ms,
         latest_da_commitments(&cfg.blob_dir),
@@ -2406,15 +3518,22 @@ async fn publish_anchor_payload(
     payload: Vec<u8>,
     entries_len: usize,
     last_payload: &mut Vec<u8>,
-    last_publish: &mut Option<Instant>,
+    last_publish: &mut Option<Duration>,
     broadcast_counter: &mut u64,
+    envelope_version: u32,
     metrics: &Arc<Metrics>,
 ) -> Result<(), NetworkError> {
+    if cfg.safe_mode.is_engaged() {
+        if !SAFE_MODE_HALT_LOGGED.swap(true, Ordering::Relaxed) {
+            println!("QSYS|mod=FORK|evt=SAFE_MODE|action=halt_broadcast");
+        }
+        return Ok(());
+    }
     if *last_payload == payload {
         return Ok(());
     }
     if let Some(prev) = last_publish {
-        if prev.elapsed() < cfg.broadcast_interval {
+        if cfg.clock.since(*prev) < cfg.parameters.broadcast_interval() {
             return Ok(());
         }
     }
@@ -2423,19 +3542,17 @@ async fn publish_anchor_payload(
     let envelope = AnchorEnvelope {
         schema: SCHEMA_ENVELOPE.to_string(),
         schema_version: ENVELOPE_SCHEMA_VERSION,
+        network: network_id().to_string(),
         public_key: encode_public_key_base64(&cfg.key_material.verifying),
         node_id: cfg.node_id.clone(),
         payload: BASE64.encode(&payload),
         signature: signature_b64.clone(),
-    };
+    }
+    .downgrade(envelope_version);
     let message =
         serde_json::to_vec(&envelope).map_err(|err| NetworkError::Codec(err.to_string()))?;
     let message_clone = message.clone();
-    match swarm
-        .behaviour_mut()
-        .gossipsub
-        .publish(cfg.anchor_topic.clone(), message)
-    {
+    match publish_gossip(swarm, cfg, cfg.anchor_topic.clone(), message) {
         Ok(_) => {
             NO_GOSSIP_PEERS_LOGGED.store(false, Ordering::Relaxed);
         }
@@ -2449,7 +3566,7 @@ async fn publish_anchor_payload(
             return Ok(());
         }
         Err(err) => {
-            metrics.inc_gossipsub_rejects();
+            metrics.inc_gossipsub_rejects(RejectReason::LocalPublish);
             return Err(NetworkError::Libp2p(err.to_string()));
         }
     }
@@ -2461,13 +3578,13 @@ async fn publish_anchor_payload(
         metrics,
     );
     *last_payload = payload;
-    *last_publish = Some(Instant::now());
+    *last_publish = Some(cfg.clock.now());
     println!("QSYS|mod=ANCHOR|evt=BROADCAST|entries={}", entries_len);
     if let Some(interval) = cfg.checkpoint_interval {
         if interval > 0 {
             *broadcast_counter = broadcast_counter.saturating_add(1);
             if (*broadcast_counter).is_multiple_of(interval) {
-                let checkpoint = AnchorCheckpoint::new(
+                let mut checkpoint = AnchorCheckpoint::new(
                     *broadcast_counter,
                     anchor_json.clone(),
                     vec![CheckpointSignature {
@@ -2477,13 +3594,58 @@ async fn publish_anchor_payload(
                     }],
                     latest_log_cutoff(&cfg.log_dir),
                 );
+                checkpoint.registry_root = anchor_json.registry_root.clone();
+                if let Some(blob_dir) = &cfg.blob_dir {
+                    match RollupCommitmentStore::load(&RollupCommitmentStore::path_for(blob_dir)) {
+                        Ok(store) => {
+                            if let Some(root) = store.root() {
+                                checkpoint = checkpoint.with_rollup_root(root);
+                            }
+                        }
+                        Err(err) => eprintln!("rollup commitment store error: {err}"),
+                    }
+                }
+                if let Some(state) = &cfg.native_state {
+                    checkpoint =
+                        checkpoint.with_rpc_state_root(state.read().await.rpc_state_digest());
+                }
+                if let Some(fees) = stake_registry_epoch_fees(cfg) {
+                    checkpoint = checkpoint.with_fees(fees);
+                }
                 if let Err(err) = write_checkpoint(&cfg.log_dir.join("checkpoints"), &checkpoint) {
                     eprintln!("checkpoint write failed: {err}");
+                    if let Some(alerts) = &cfg.alert_monitor {
+                        alerts.checkpoint_failed(&err.to_string(), cfg.clock.now_ms());
+                    }
                 } else {
+                    if let Some(alerts) = &cfg.alert_monitor {
+                        alerts.checkpoint_written(cfg.clock.now_ms());
+                    }
                     println!(
                         "QSYS|mod=CHECKPOINT|evt=RECORDED|epoch={} entries={}",
                         checkpoint.epoch, entries_len
                     );
+                    if let Some(webhooks) = &cfg.webhook_dispatcher {
+                        webhooks.emit(
+                            WebhookEventKind::CheckpointWritten,
+                            cfg.clock.now_ms(),
+                            serde_json::json!({
+                                "epoch": checkpoint.epoch,
+                                "entries": entries_len,
+                                "digest": checkpoint.digest().ok().map(hex::encode),
+                            }),
+                        );
+                    }
+                    if let Ok(digest) = checkpoint.digest() {
+                        provide_checkpoint(swarm, &digest);
+                    }
+                    publish_checkpoint(swarm, cfg, &checkpoint);
+                    observe_checkpoint(cfg, &checkpoint, metrics).await;
+                    advance_stake_registry_epoch(cfg, checkpoint.epoch);
+                    if let Some(expiry_epochs) = cfg.entry_gc_epochs {
+                        collect_expired_log_entries(cfg, expiry_epochs, checkpoint.epoch);
+                    }
+                    distribute_checkpoint_rewards(cfg, &checkpoint);
                 }
             }
         }
@@ -2491,12 +3653,182 @@ async fn publish_anchor_payload(
     Ok(())
 }
 
+fn publish_checkpoint(
+    swarm: &mut Swarm<JrocBehaviour>,
+    cfg: &NetConfig,
+    checkpoint: &AnchorCheckpoint,
+) {
+    let message = match serde_json::to_vec(checkpoint) {
+        Ok(message) => message,
+        Err(err) => {
+            eprintln!("checkpoint encode failed: {err}");
+            return;
+        }
+    };
+    match publish_gossip(swarm, cfg, TOPIC_CHECKPOINTS.clone(), message) {
+        Ok(()) | Err(PublishError::NoPeersSubscribedToTopic | PublishError::Duplicate) => {}
+        Err(err) => eprintln!("checkpoint gossip failed: {err}"),
+    }
+}
+
+/// Tallies a local or gossiped checkpoint and enters safe mode on a fork.
+async fn observe_checkpoint(cfg: &NetConfig, checkpoint: &AnchorCheckpoint, metrics: &Metrics) {
+    if cfg.safe_mode.is_engaged() {
+        return;
+    }
+    let observed = cfg.checkpoint_tally.lock().await.observe(
+        checkpoint,
+        cfg.parameters.quorum(),
+        now_millis(),
+    );
+    let evidence = match observed {
+        Ok(Some(evidence)) => evidence,
+        Ok(None) => return,
+        Err(err) => {
+            eprintln!("checkpoint tally failed: {err}");
+            return;
+        }
+    };
+    metrics.inc_forks_detected();
+    if let Err(err) = cfg.safe_mode.engage(&cfg.log_dir, &evidence) {
+        eprintln!("safe mode persist failed: {err}");
+    }
+    let digests: Vec<&str> = evidence
+        .branches
+        .iter()
+        .map(|branch| branch.anchor_digest.as_str())
+        .collect();
+    println!(
+        "QSYS|mod=FORK|evt=DETECTED|epoch={}|branches={}|action=safe_mode",
+        evidence.epoch,
+        digests.join(",")
+    );
+    if let Some(webhooks) = &cfg.webhook_dispatcher {
+        webhooks.emit(
+            WebhookEventKind::ForkDetected,
+            cfg.clock.now_ms(),
+            serde_json::json!({
+                "epoch": evidence.epoch,
+                "branches": digests,
+            }),
+        );
+    }
+}
+
+fn stake_registry_root(cfg: &NetConfig) -> Option<[u8; 32]> {
+    let registry_path = cfg.stake_registry_path.as_ref()?;
+    if !registry_path.exists() {
+        return None;
+    }
+    match StakeRegistry::load(registry_path) {
+        Ok(registry) => Some(registry.state_root()),
+        Err(err) => {
+            eprintln!("stake registry load failed: {err}");
+            None
+        }
+    }
+}
+
+fn collect_expired_log_entries(cfg: &NetConfig, expiry_epochs: u64, epoch: u64) {
+    match collect_expired_entries(&cfg.log_dir, expiry_epochs, epoch, false) {
+        Ok(report) if !report.archived.is_empty() => println!(
+            "QSYS|mod=ENTRY_GC|evt=ARCHIVED|epoch={epoch}|entries={}|pending={}",
+            report.archived.len(),
+            report.pending
+        ),
+        Ok(_) => {}
+        Err(err) => eprintln!("entry gc failed at epoch {epoch}: {err}"),
+    }
+}
+
+/// Fees journalled since the registry last advanced, i.e. since the previous
+/// checkpoint.
+fn stake_registry_epoch_fees(cfg: &NetConfig) -> Option<EpochFees> {
+    let registry_path = cfg.stake_registry_path.as_ref()?;
+    if !registry_path.exists() {
+        return None;
+    }
+    match StakeRegistry::load(registry_path) {
+        Ok(registry) => Some(registry.supply().fees_in_epoch(registry.epoch())),
+        Err(err) => {
+            eprintln!("checkpoint fee report skipped: {err}");
+            None
+        }
+    }
+}
+
+fn advance_stake_registry_epoch(cfg: &NetConfig, epoch: u64) {
+    let Some(registry_path) = &cfg.stake_registry_path else {
+        return;
+    };
+    if !registry_path.exists() {
+        return;
+    }
+    match StakeRegistry::load(registry_path) {
+        Ok(mut registry) => {
+            if registry.epoch() >= epoch {
+                return;
+            }
+            registry.advance_epoch(epoch);
+            if let Err(err) = registry.save(registry_path) {
+                eprintln!("failed to persist stake registry epoch {epoch}: {err}");
+            }
+            if let Err(err) = AddressIndex::refresh(registry_path, &registry) {
+                eprintln!("failed to refresh address index at epoch {epoch}: {err}");
+            }
+        }
+        Err(err) => eprintln!("stake registry epoch not advanced: {err}"),
+    }
+}
+
+fn distribute_checkpoint_rewards(cfg: &NetConfig, checkpoint: &AnchorCheckpoint) {
+    let (Some(config), Some(registry_path)) = (&cfg.reward_config, &cfg.stake_registry_path) else {
+        return;
+    };
+    let mut registry = match StakeRegistry::load(registry_path) {
+        Ok(registry) => registry,
+        Err(err) => {
+            eprintln!("reward distribution skipped: stake registry load failed: {err}");
+            return;
+        }
+    };
+    registry.set_clock(cfg.clock.clone());
+    match distribute_epoch_rewards(
+        &cfg.log_dir,
+        checkpoint,
+        config,
+        &mut registry,
+        &cfg.key_material,
+    ) {
+        Ok(distribution) => {
+            if let Err(err) = registry.save(registry_path) {
+                eprintln!(
+                    "reward distribution for epoch {} not persisted: {err}",
+                    checkpoint.epoch
+                );
+                return;
+            }
+            let paid: u64 = distribution.shares.iter().map(|share| share.amount).sum();
+            println!(
+                "QSYS|mod=REWARDS|evt=DISTRIBUTED|epoch={}|recipients={}|paid={}|pool={}",
+                distribution.epoch,
+                distribution.shares.len(),
+                paid,
+                distribution.pool
+            );
+        }
+        Err(RewardError::AlreadyDistributed(_)) => {}
+        Err(err) => eprintln!("reward distribution failed: {err}"),
+    }
+}
+
 async fn broadcast_local_anchor(
     swarm: &mut Swarm<JrocBehaviour>,
     cfg: &NetConfig,
     last_payload: &mut Vec<u8>,
-    last_publish: &mut Option<Instant>,
+    last_publish: &mut Option<Duration>,
     broadcast_counter: &mut u64,
+    envelope_version: u32,
     metrics: &Arc<Metrics>,
 ) -> Result<(), NetworkError> {
     if !policy_permits(
@@ -2508,6 +3840,9 @@ async fn broadcast_local_anchor(
         ));
     }
     let (anchor_json, payload, entries_len) = build_anchor_payload(cfg)?;
+    if let Some(webhooks) = &cfg.webhook_dispatcher {
+        webhooks.observe_entries(anchor_json.timestamp_ms, &anchor_json.entries);
+    }
     publish_anchor_payload(
         swarm,
         cfg,
@@ -2517,6 +3852,7 @@ async fn broadcast_local_anchor(
         last_payload,
         last_publish,
         broadcast_counter,
+        envelope_version,
         metrics,
     )
     .await
@@ -2534,35 +3870,33 @@ async fn broadcast_anchor_vote(
     let signature_b64 = encode_signature_base64(&signature);
     let vote = AnchorVoteJson {
         schema: SCHEMA_VOTE.to_string(),
-        network: NETWORK_ID.to_string(),
+        network: network_id().to_string(),
         round,
         anchor_hash: anchor_hash.to_string(),
         public_key: encode_public_key_base64(&cfg.key_material.verifying),
         signature: signature_b64,
     };
     let message = serde_json::to_vec(&vote).map_err(|err| NetworkError::Codec(err.to_string()))?;
-    match swarm
-        .behaviour_mut()
-        .gossipsub
-        .publish(TOPIC_VOTES.clone(), message)
-    {
+    match publish_gossip(swarm, cfg, TOPIC_VOTES.clone(), message) {
         Ok(_) => Ok(()),
         Err(PublishError::NoPeersSubscribedToTopic) => Ok(()),
         Err(PublishError::Duplicate) => Ok(()),
         Err(err) => {
-            metrics.inc_gossipsub_rejects();
+            metrics.inc_gossipsub_rejects(RejectReason::LocalPublish);
             Err(NetworkError::Libp2p(err.to_string()))
         }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn bft_tick(
     swarm: &mut Swarm<JrocBehaviour>,
     cfg: &NetConfig,
     bft_state: &mut BftState,
     last_payload: &mut Vec<u8>,
-    last_publish: &mut Option<Instant>,
+    last_publish: &mut Option<Duration>,
     broadcast_counter: &mut u64,
+    envelope_version: u32,
     metrics: &Arc<Metrics>,
 ) -> Result<(), NetworkError> {
     if !policy_permits(
@@ -2585,7 +3919,8 @@ async fn bft_tick(
     let local_key = cfg.key_material.verifying.to_bytes();
     let votes = bft_state.record_vote(&anchor_hash, &local_key);
 
-    if votes >= cfg.quorum {
+    let quorum = cfg.parameters.quorum();
+    if votes >= quorum {
         publish_anchor_payload(
             swarm,
             cfg,
@@ -2595,6 +3930,7 @@ async fn bft_tick(
             last_payload,
             last_publish,
             broadcast_counter,
+            envelope_version,
             metrics,
         )
         .await?;
@@ -2602,7 +3938,7 @@ async fn bft_tick(
     } else {
         println!(
             "QSYS|mod=BFT|evt=WAITING|round={} votes={}/{}",
-            round, votes, cfg.quorum
+            round, votes, quorum
         );
     }
     Ok(())
@@ -2617,8 +3953,14 @@ async fn handle_event(
     invalid_counters: &mut HashMap<libp2p::PeerId, usize>,
     bft_state: &mut BftState,
     anchor_votes: &mut AnchorVotes,
+    finality: &mut FinalityTracker,
+    anchor_rates: &mut AnchorRateLimiter,
+    direct_pending: &mut DirectPending,
+    governance: &mut GovernanceBook,
     metrics: &Arc<Metrics>,
     native_runtime: &mut Option<NativeChainRuntime>,
+    peer_store: &mut PeerStore,
+    envelope_peers: &mut HashMap<libp2p::PeerId, (u32, u32)>,
 ) -> Result<(), NetworkError> {
     #[allow(clippy::collapsible_match, clippy::single_match)]
     match event {
@@ -2628,12 +3970,62 @@ async fn handle_event(
         SwarmEvent::ConnectionEstablished {
             peer_id,
             num_established,
+            endpoint,
             ..
         } => {
             if num_established.get() == 1 {
                 metrics.peer_connected();
                 println!("QSYS|mod=NET|evt=PEER_UP|peer={peer_id}");
             }
+            if endpoint.is_dialer() {
+                peer_store.record_connected(
+                    &peer_id.to_string(),
+                    &endpoint.get_remote_address().to_string(),
+                    now_millis() / 1000,
+                );
+                persist_peer_store(cfg, peer_store);
+            }
+        }
+        SwarmEvent::OutgoingConnectionError {
+            peer_id: Some(peer_id),
+            ..
+        } => {
+            peer_store.record_failure(&peer_id.to_string());
+            persist_peer_store(cfg, peer_store);
+        }
+        SwarmEvent::Behaviour(JrocBehaviourEvent::Identify(identify::Event::Received {
+            peer_id,
+            info,
+            ..
+        })) => {
+            for addr in &info.listen_addrs {
+                swarm
+                    .behaviour_mut()
+                    .kademlia
+                    .add_address(&peer_id, addr.clone());
+            }
+            let addrs: Vec<String> = info
+                .listen_addrs
+                .iter()
+                .filter(|addr| is_shareable_addr(addr))
+                .map(|addr| addr.to_string())
+                .collect();
+            if !addrs.is_empty() {
+                peer_store.record_addrs(&peer_id.to_string(), &addrs, now_millis() / 1000);
+                persist_peer_store(cfg, peer_store);
+            }
+            let (min, max) = parse_envelope_versions(&info.agent_version);
+            if envelope_peers.insert(peer_id, (min, max)).is_none() {
+                println!("QSYS|mod=NET|evt=ENVELOPE_VERSIONS|peer={peer_id}|min={min}|max={max}");
+            }
+            if max < MIN_ENVELOPE_SCHEMA_VERSION || min > ENVELOPE_SCHEMA_VERSION {
+                println!(
+                    "QSYS|mod=NET|evt=ENVELOPE_INCOMPATIBLE|peer={peer_id}|min={min}|max={max}"
+                );
+            }
+        }
+        SwarmEvent::Behaviour(JrocBehaviourEvent::Direct(event)) => {
+            handle_direct_event(swarm, cfg, direct_pending, event);
         }
         SwarmEvent::ConnectionClosed {
             peer_id,
@@ -2641,106 +4033,148 @@ async fn handle_event(
             ..
         } => {
             if num_established == 0 {
+                envelope_peers.remove(&peer_id);
                 metrics.peer_disconnected();
                 println!("QSYS|mod=NET|evt=PEER_DOWN|peer={peer_id}");
             }
         }
+        SwarmEvent::Behaviour(JrocBehaviourEvent::Kademlia(
+            kad::Event::OutboundQueryProgressed {
+                result:
+                    kad::QueryResult::GetProviders(Ok(kad::GetProvidersOk::FoundProviders {
+                        key,
+                        providers,
+                    })),
+                ..
+            },
+        )) => {
+            let Some(digest) = parse_checkpoint_provider_key(key.as_ref()) else {
+                return Ok(());
+            };
+            let local_peer = *swarm.local_peer_id();
+            for provider in providers {
+                if provider == local_peer {
+                    continue;
+                }
+                println!(
+                    "QSYS|mod=DHT|evt=CHECKPOINT_PROVIDER|digest={}|peer={provider}",
+                    hex::encode(digest)
+                );
+                swarm.behaviour_mut().gossipsub.add_explicit_peer(&provider);
+                if !swarm.is_connected(&provider) {
+                    if let Err(err) = swarm.dial(provider) {
+                        eprintln!("dial checkpoint provider {provider} failed: {err}");
+                    }
+                }
+            }
+        }
         SwarmEvent::Behaviour(JrocBehaviourEvent::Gossipsub(event)) => match event {
             gossipsub::Event::Message {
                 propagation_source,
+                message_id,
                 message,
-                ..
             } => {
-                if message.topic == TOPIC_NATIVE_CHAIN.hash() {
-                    if message.data.len() > MAX_NATIVE_MESSAGE_BYTES {
-                        metrics.inc_gossipsub_rejects();
-                        record_invalid(invalid_counters, propagation_source, metrics);
+                if is_anchor_topic(cfg, &message.topic) {
+                    metrics.inc_anchors_received();
+                }
+                let verdict = validate_gossip_message(cfg, &message.topic, &message.data).and_then(
+                    |validated| match validated {
+                        ValidatedMessage::Identity(document) => {
+                            register_identity(cfg, &document, message.source.as_ref())
+                                .map(|()| ValidatedMessage::Identity(document))
+                        }
+                        ValidatedMessage::Anchor(anchor) => {
+                            match anchor_rates.check(&anchor.envelope.public_key, Instant::now()) {
+                                RateVerdict::Allowed => Ok(ValidatedMessage::Anchor(anchor)),
+                                RateVerdict::Excess => Err(RejectReason::RateLimit),
+                                RateVerdict::Sustained => Err(RejectReason::RateLimitSustained),
+                            }
+                        }
+                        other => Ok(other),
+                    },
+                );
+                let acceptance = match &verdict {
+                    Ok(ValidatedMessage::Ignored) => MessageAcceptance::Ignore,
+                    Ok(_) => MessageAcceptance::Accept,
+                    // Peers on a newer release, or relaying a briefly chatty identity, are not
+                    // misbehaving; drop without penalty.
+                    Err(RejectReason::Version | RejectReason::RateLimit) => {
+                        MessageAcceptance::Ignore
+                    }
+                    Err(_) => MessageAcceptance::Reject,
+                };
+                swarm
+                    .behaviour_mut()
+                    .gossipsub
+                    .report_message_validation_result(&message_id, &propagation_source, acceptance);
+                let validated = match verdict {
+                    Ok(validated) => validated,
+                    Err(reason) => {
+                        metrics.inc_gossipsub_rejects(reason);
+                        if !matches!(reason, RejectReason::Version | RejectReason::RateLimit) {
+                            record_invalid(invalid_counters, propagation_source, metrics);
+                        }
+                        println!(
+                            "QSYS|mod=GOSSIP|evt=REJECT|peer={propagation_source}|reason={}",
+                            reason.label()
+                        );
                         return Ok(());
                     }
-                    let Some(runtime) = native_runtime.as_mut() else {
+                };
+                let validated_anchor = match validated {
+                    ValidatedMessage::Anchor(anchor) => *anchor,
+                    ValidatedMessage::Native(native_message) => {
+                        let Some(runtime) = native_runtime.as_mut() else {
+                            return Ok(());
+                        };
+                        publish_native_message(
+                            swarm,
+                            runtime,
+                            cfg,
+                            *native_message,
+                            true,
+                            false,
+                            metrics,
+                        )
+                        .await?;
                         return Ok(());
-                    };
-                    let native_message: NativeChainMessage = serde_json::from_slice(&message.data)
-                        .map_err(|err| NetworkError::Codec(err.to_string()))?;
-                    publish_native_message(
-                        swarm,
-                        runtime,
-                        &cfg.key_material.signing,
-                        native_message,
-                        true,
-                        false,
-                        metrics,
-                    )
-                    .await?;
-                    return Ok(());
-                }
-                if message.topic == TOPIC_EVIDENCE.hash() {
-                    handle_evidence_message(cfg, &message.data)?;
-                    return Ok(());
-                }
-                if message.topic == TOPIC_VOTES.hash() {
-                    if cfg.bft_enabled {
-                        handle_vote_message(cfg, bft_state, &message.data)?;
                     }
-                    return Ok(());
-                }
-                if !is_anchor_topic(cfg, &message.topic) {
-                    return Ok(());
-                }
-                metrics.inc_anchors_received();
-                if message.data.len() > MAX_ENVELOPE_BYTES {
-                    metrics.inc_gossipsub_rejects();
-                    record_invalid(invalid_counters, propagation_source, metrics);
-                    return Ok(());
-                }
+                    ValidatedMessage::Evidence => {
+                        handle_evidence_message(cfg, &message.data)?;
+                        return Ok(());
+                    }
+                    ValidatedMessage::Vote => {
+                        if cfg.bft_enabled {
+                            handle_vote_message(cfg, bft_state, &message.data)?;
+                        }
+                        return Ok(());
+                    }
+                    ValidatedMessage::Checkpoint(checkpoint) => {
+                        observe_checkpoint(cfg, &checkpoint, metrics).await;
+                        return Ok(());
+                    }
+                    ValidatedMessage::Governance(governance_message) => {
+                        record_governance_message(governance, *governance_message, "gossip");
+                        return Ok(());
+                    }
+                    ValidatedMessage::Identity(_) | ValidatedMessage::Ignored => return Ok(()),
+                };
                 let digest = sha256_digest(&message.data);
                 if !seen_payloads.insert(digest) {
-                    metrics.inc_gossipsub_rejects();
+                    metrics.inc_gossipsub_rejects(RejectReason::Duplicate);
                     return Ok(());
                 }
-                let envelope: AnchorEnvelope = serde_json::from_slice(&message.data)
-                    .map_err(|err| NetworkError::Codec(err.to_string()))?;
-                envelope.validate()?;
-                let payload = BASE64
-                    .decode(envelope.payload.as_bytes())
-                    .map_err(|err| NetworkError::Codec(err.to_string()))?;
-                if payload.len() > MAX_ENVELOPE_BYTES {
-                    metrics.inc_gossipsub_rejects();
-                    record_invalid(invalid_counters, propagation_source, metrics);
-                    return Ok(());
-                }
-                verify_signature_base64(&envelope.public_key, &payload, &envelope.signature)?;
-                let remote_verifying = decode_public_key_base64(&envelope.public_key)
-                    .map_err(|err| NetworkError::Codec(err.to_string()))?;
+                let ValidatedAnchor {
+                    envelope,
+                    anchor_json,
+                    remote_verifying,
+                } = validated_anchor;
                 let remote_key_bytes = remote_verifying.to_bytes();
-                if !policy_permits(cfg.membership_policy.as_ref(), &remote_key_bytes) {
-                    metrics.inc_gossipsub_rejects();
-                    record_invalid(invalid_counters, propagation_source, metrics);
-                    println!(
-                        "rejecting peer {}: identity not permitted by policy",
-                        envelope.node_id
-                    );
-                    return Ok(());
-                }
-                let payload_str = std::str::from_utf8(&payload)
-                    .map_err(|err| NetworkError::Codec(err.to_string()))?;
-                let anchor_json = AnchorJson::from_json_str(payload_str)
-                    .map_err(|err| NetworkError::Codec(err.to_string()))?;
-                if anchor_json.network != NETWORK_ID {
-                    metrics.inc_gossipsub_rejects();
-                    record_invalid(invalid_counters, propagation_source, metrics);
-                    return Ok(());
-                }
-                if anchor_json.entries.len() > MAX_ANCHOR_ENTRIES {
-                    metrics.inc_gossipsub_rejects();
-                    record_invalid(invalid_counters, propagation_source, metrics);
-                    return Ok(());
-                }
                 // DA gating: require commitments only after non-genesis entries exist,
                 // then verify share roots + attestation QC; require persisted QC.
                 if anchor_json.da_commitments.is_empty() {
                     if anchor_json.entries.len() > 1 {
-                        metrics.inc_gossipsub_rejects();
+                        metrics.inc_gossipsub_rejects(RejectReason::DataAvailability);
                         println!(
                             "rejecting peer {}: missing DA commitments in anchor (entries={})",
                             envelope.node_id,
@@ -2754,7 +4188,7 @@ async fn handle_event(
                             .ok()
                             .flatten();
                         let Some(meta) = meta else {
-                            metrics.inc_gossipsub_rejects();
+                            metrics.inc_gossipsub_rejects(RejectReason::DataAvailability);
                             println!(
                                 "rejecting peer {}: missing blob {} in {}",
                                 envelope.node_id, da.blob_hash, da.namespace
@@ -2762,7 +4196,7 @@ async fn handle_event(
                             return Ok(());
                         };
                         if meta.share_root != da.share_root {
-                            metrics.inc_gossipsub_rejects();
+                            metrics.inc_gossipsub_rejects(RejectReason::DataAvailability);
                             println!(
                                 "rejecting peer {}: share_root mismatch for {}",
                                 envelope.node_id, da.blob_hash
@@ -2772,7 +4206,7 @@ async fn handle_event(
                         if let (Some(meta_p), Some(da_p)) = (&meta.pedersen_root, &da.pedersen_root)
                         {
                             if meta_p != da_p {
-                                metrics.inc_gossipsub_rejects();
+                                metrics.inc_gossipsub_rejects(RejectReason::DataAvailability);
                                 println!(
                                     "rejecting peer {}: pedersen_root mismatch for {}",
                                     envelope.node_id, da.blob_hash
@@ -2781,7 +4215,7 @@ async fn handle_event(
                             }
                         }
                         if da.pedersen_root.is_none() {
-                            metrics.inc_gossipsub_rejects();
+                            metrics.inc_gossipsub_rejects(RejectReason::DataAvailability);
                             println!(
                                 "rejecting peer {}: pedersen_root missing for {}",
                                 envelope.node_id, da.blob_hash
@@ -2799,7 +4233,7 @@ async fn handle_event(
                             })
                             .map_err(|e| NetworkError::Codec(e.to_string()))?;
                         if !qc.quorum_reached {
-                            metrics.inc_gossipsub_rejects();
+                            metrics.inc_gossipsub_rejects(RejectReason::DataAvailability);
                             println!(
                                 "rejecting peer {}: DA quorum not met for {}",
                                 envelope.node_id, da.blob_hash
@@ -2819,11 +4253,14 @@ async fn handle_event(
                             }
                             // Reward attesters (best-effort).
                             if let Some(path) = &cfg.stake_registry_path {
-                                if let Ok(mut reg) = StakeRegistry::load(path) {
+                                let rewarded = StakeRegistry::transact(path, |tx| {
                                     for signer in &qc.signers {
-                                        reg.credit_reward(signer, 1);
+                                        tx.mint(signer, 1, SupplyCause::Reward);
                                     }
-                                    let _ = reg.save(path);
+                                    Ok(())
+                                });
+                                if let Err(err) = rewarded {
+                                    eprintln!("attester rewards not credited: {err}");
                                 }
                             }
                         }
@@ -2833,7 +4270,7 @@ async fn handle_event(
                                 .join(&da.namespace)
                                 .join(format!("{}.qc", da.blob_hash));
                             if !qc_path.exists() {
-                                metrics.inc_gossipsub_rejects();
+                                metrics.inc_gossipsub_rejects(RejectReason::DataAvailability);
                                 println!(
                                     "rejecting peer {}: missing QC for {}",
                                     envelope.node_id, da.blob_hash
@@ -2844,7 +4281,7 @@ async fn handle_event(
                     }
                 }
                 let remote_anchor = anchor_json.clone().into_ledger()?;
-                let local_anchor = load_anchor_from_logs(&cfg.log_dir)?;
+                let local_anchor = load_local_anchor(cfg)?;
                 let local_key_bytes = cfg.key_material.verifying.to_bytes();
 
                 let remote_digest = anchor_digest(&remote_anchor);
@@ -2854,6 +4291,13 @@ async fn handle_event(
                         "anchor divergence with peer {}: digest mismatch",
                         envelope.node_id
                     );
+                    record_divergence(
+                        finality,
+                        metrics,
+                        &envelope.node_id,
+                        &remote_digest,
+                        &local_digest,
+                    );
                     if let Err(slash_err) = cfg.membership_policy.record_slash(&remote_verifying) {
                         eprintln!(
                             "failed to record slash for {}: {}",
@@ -2869,12 +4313,14 @@ async fn handle_event(
                 }
                 bridge_anchor_message(swarm, cfg, &message.topic, &message.data, metrics);
 
+                let now = Instant::now();
                 if anchor_votes.len() > 64 {
                     let ttl = Duration::from_secs(300);
                     anchor_votes.retain(|_, (ts, _)| ts.elapsed() < ttl);
+                    finality.expire(now, ttl);
                 }
+                finality.observe(remote_digest, now);
 
-                let now = Instant::now();
                 let entry = anchor_votes
                     .entry(remote_digest)
                     .or_insert_with(|| (now, HashMap::new()));
@@ -2888,28 +4334,104 @@ async fn handle_event(
                     .entry(remote_key_bytes.to_vec())
                     .or_insert_with(|| remote_anchor.clone());
 
-                if entry.1.len() >= cfg.quorum {
-                    let votes: Vec<AnchorVote<'_>> = entry
-                        .1
-                        .iter()
-                        .map(|(key, anchor)| AnchorVote {
-                            anchor,
-                            public_key: key,
-                        })
-                        .collect();
-                    match crate::reconcile_anchors_with_quorum(&votes, cfg.quorum) {
+                let policy = cfg.membership_policy.as_ref();
+                let stake_threshold = stake_quorum_threshold(policy);
+                let ready = match stake_threshold {
+                    Some(threshold) => {
+                        entry.1.keys().fold(0u64, |acc, key| {
+                            acc.saturating_add(anchor_vote_weight(policy, key))
+                        }) >= threshold
+                    }
+                    None => entry.1.len() >= cfg.parameters.quorum(),
+                };
+                if ready {
+                    let outcome = match stake_threshold {
+                        Some(threshold) => {
+                            let votes: Vec<WeightedAnchorVote<'_>> = entry
+                                .1
+                                .iter()
+                                .map(|(key, anchor)| WeightedAnchorVote {
+                                    anchor,
+                                    public_key: key,
+                                    weight: anchor_vote_weight(policy, key),
+                                })
+                                .collect();
+                            crate::reconcile_anchors_with_weighted_quorum(&votes, threshold)
+                        }
+                        None => {
+                            let votes: Vec<AnchorVote<'_>> = entry
+                                .1
+                                .iter()
+                                .map(|(key, anchor)| AnchorVote {
+                                    anchor,
+                                    public_key: key,
+                                })
+                                .collect();
+                            crate::reconcile_anchors_with_quorum(&votes, cfg.parameters.quorum())
+                        }
+                    };
+                    match outcome {
                         Ok(()) => {
                             metrics.inc_anchors_verified();
                             metrics.inc_finality_events();
+                            let now = Instant::now();
+                            let latency = finality.finalize(&remote_digest, now);
+                            if let Some(latency) = latency {
+                                metrics.observe_finality_latency(latency);
+                            }
                             println!(
-                                "QSYS|mod=QUORUM|evt=FINALIZED|peer={} entries={}",
+                                "QSYS|mod=QUORUM|evt=FINALIZED|peer={}|digest={}|entries={}|latency_ms={}",
                                 envelope.node_id,
-                                remote_anchor.entries.len()
+                                hex::encode(remote_digest),
+                                remote_anchor.entries.len(),
+                                latency.map_or(0, |latency| latency.as_millis())
                             );
+                            if let Some(webhooks) = &cfg.webhook_dispatcher {
+                                webhooks.emit(
+                                    WebhookEventKind::FinalityReached,
+                                    cfg.clock.now_ms(),
+                                    serde_json::json!({
+                                        "peer": envelope.node_id,
+                                        "digest": hex::encode(remote_digest),
+                                        "entries": remote_anchor.entries.len(),
+                                    }),
+                                );
+                            }
+                            let attestation = AnchorAttestation::sign(
+                                network_id(),
+                                &cfg.node_id,
+                                &cfg.key_material,
+                                &remote_digest,
+                                remote_anchor.entries.len() as u64,
+                                now_millis(),
+                            );
+                            if let Err(err) = append_attestation(&cfg.log_dir, &attestation) {
+                                eprintln!("failed to record finality attestation: {err}");
+                            }
+                            if let Err(err) =
+                                record_finalized_entries(&cfg.log_dir, &remote_anchor, now_millis())
+                            {
+                                eprintln!("failed to record entry finality: {err}");
+                            }
+                            if let Some(duration) = finality.converge(now) {
+                                metrics.divergence_ended(duration);
+                                println!(
+                                    "QSYS|mod=QUORUM|evt=CONVERGED|digest={}|duration_ms={}",
+                                    hex::encode(remote_digest),
+                                    duration.as_millis()
+                                );
+                            }
                             anchor_votes.remove(&remote_digest);
                         }
                         Err(err) => {
                             println!("anchor divergence with peer {}: {}", envelope.node_id, err);
+                            record_divergence(
+                                finality,
+                                metrics,
+                                &envelope.node_id,
+                                &remote_digest,
+                                &local_digest,
+                            );
                             if let Err(slash_err) =
                                 cfg.membership_policy.record_slash(&remote_verifying)
                             {
@@ -2934,10 +4456,56 @@ async fn handle_event(
     Ok(())
 }
 
+/// Opens a divergence episode on the first conflicting anchor after finality.
+fn record_divergence(
+    finality: &mut FinalityTracker,
+    metrics: &Metrics,
+    peer: &str,
+    remote_digest: &[u8; 32],
+    local_digest: &[u8; 32],
+) {
+    if finality.diverge(Instant::now()) {
+        metrics.divergence_started();
+        println!(
+            "QSYS|mod=QUORUM|evt=DIVERGED|peer={}|digest={}|local_digest={}",
+            peer,
+            hex::encode(remote_digest),
+            hex::encode(local_digest)
+        );
+    }
+}
+
+/// Proposes the next native block when this node leads its height.
+async fn propose_native_block(
+    swarm: &mut Swarm<JrocBehaviour>,
+    runtime: &mut NativeChainRuntime,
+    cfg: &NetConfig,
+    metrics: &Arc<Metrics>,
+) {
+    match runtime.propose(&cfg.key_material.signing).await {
+        Ok(Some(proposal)) => {
+            println!(
+                "QSYS|mod=NATIVE_CHAIN|evt=PROPOSE|height={}|hash={}|txs={}",
+                proposal.number,
+                proposal.hash,
+                proposal.transactions.len()
+            );
+            let message = NativeChainMessage::new(NativeChainMessagePayload::Proposal(proposal));
+            if let Err(err) =
+                publish_native_message(swarm, runtime, cfg, message, true, true, metrics).await
+            {
+                eprintln!("native chain proposal error: {err}");
+            }
+        }
+        Ok(None) => {}
+        Err(err) => eprintln!("native chain proposal error: {err}"),
+    }
+}
+
 async fn publish_native_message(
     swarm: &mut Swarm<JrocBehaviour>,
     runtime: &mut NativeChainRuntime,
-    signing: &SigningKey,
+    cfg: &NetConfig,
     message: NativeChainMessage,
     process_local: bool,
     publish_initial: bool,
@@ -2950,7 +4518,7 @@ async fn publish_native_message(
             let sync_response =
                 matches!(&message.payload, NativeChainMessagePayload::SyncResponse(_));
             let generated = runtime
-                .handle_message(message.clone(), signing)
+                .handle_message(message.clone(), &cfg.key_material.signing)
                 .await
                 .map_err(NetworkError::Codec)?;
             for next in generated {
@@ -2976,11 +4544,7 @@ async fn publish_native_message(
             }
             let bytes =
                 serde_json::to_vec(&message).map_err(|err| NetworkError::Codec(err.to_string()))?;
-            match swarm
-                .behaviour_mut()
-                .gossipsub
-                .publish(TOPIC_NATIVE_CHAIN.clone(), bytes)
-            {
+            match publish_gossip(swarm, cfg, TOPIC_NATIVE_CHAIN.clone(), bytes) {
                 Ok(_)
                 | Err(PublishError::NoPeersSubscribedToTopic)
                 | Err(PublishError::Duplicate) => {}
@@ -2991,6 +4555,121 @@ async fn publish_native_message(
     Ok(())
 }
 
+/// Publishes through the chaos injector when one is configured.
+fn publish_gossip(
+    swarm: &mut Swarm<JrocBehaviour>,
+    cfg: &NetConfig,
+    topic: IdentTopic,
+    data: Vec<u8>,
+) -> Result<(), PublishError> {
+    let Some(chaos) = cfg.chaos.as_ref() else {
+        return swarm
+            .behaviour_mut()
+            .gossipsub
+            .publish(topic, data)
+            .map(|_| ());
+    };
+    let now = Instant::now();
+    match chaos.verdict(now) {
+        ChaosVerdict::Drop => {
+            cfg.metrics.inc_chaos_injected(ChaosKind::Drop);
+            Ok(())
+        }
+        ChaosVerdict::Publish { due, duplicate } if due > now => {
+            cfg.metrics.inc_chaos_injected(ChaosKind::Delay);
+            chaos.delay(DelayedMessage {
+                due,
+                topic,
+                data,
+                duplicate,
+            });
+            Ok(())
+        }
+        ChaosVerdict::Publish { duplicate, .. } => {
+            publish_with_duplicate(swarm, cfg, topic, data, duplicate)
+        }
+    }
+}
+
+fn publish_with_duplicate(
+    swarm: &mut Swarm<JrocBehaviour>,
+    cfg: &NetConfig,
+    topic: IdentTopic,
+    data: Vec<u8>,
+    duplicate: bool,
+) -> Result<(), PublishError> {
+    if duplicate {
+        cfg.metrics.inc_chaos_injected(ChaosKind::Duplicate);
+        let _ = swarm
+            .behaviour_mut()
+            .gossipsub
+            .publish(topic.clone(), duplicate_payload(&data));
+    }
+    swarm
+        .behaviour_mut()
+        .gossipsub
+        .publish(topic, data)
+        .map(|_| ())
+}
+
+/// Publishes messages whose injected latency has elapsed.
+fn flush_delayed_gossip(swarm: &mut Swarm<JrocBehaviour>, cfg: &NetConfig) {
+    let Some(chaos) = cfg.chaos.as_ref() else {
+        return;
+    };
+    for message in chaos.take_due(Instant::now()) {
+        match publish_with_duplicate(swarm, cfg, message.topic, message.data, message.duplicate) {
+            Ok(()) | Err(PublishError::NoPeersSubscribedToTopic) | Err(PublishError::Duplicate) => {
+            }
+            Err(err) => {
+                cfg.metrics
+                    .inc_gossipsub_rejects(RejectReason::LocalPublish);
+                eprintln!("delayed publish error: {err}");
+            }
+        }
+    }
+}
+
+/// Binds a gossiped identity document to the peer that authored the message.
+fn register_identity(
+    cfg: &NetConfig,
+    document: &IdentityDocument,
+    source: Option<&PeerId>,
+) -> Result<(), RejectReason> {
+    let Some(source) = source else {
+        return Err(RejectReason::Identity);
+    };
+    match cfg.identities.register(document, source) {
+        Ok(true) => {
+            println!(
+                "QSYS|mod=IDENTITY|evt=BOUND|node={}|peer={}|public_key={}",
+                document.node_id, document.peer_id, document.public_key
+            );
+            Ok(())
+        }
+        Ok(false) => Ok(()),
+        Err(err) => {
+            println!("QSYS|mod=IDENTITY|evt=CONFLICT|peer={source}|reason={err}");
+            Err(RejectReason::Identity)
+        }
+    }
+}
+
+/// Publishes this node's identity document; `Ok(false)` when no peer has joined the topic yet.
+fn publish_identity_document(
+    swarm: &mut Swarm<JrocBehaviour>,
+    cfg: &NetConfig,
+    document: &IdentityDocument,
+) -> Result<bool, NetworkError> {
+    let message =
+        serde_json::to_vec(document).map_err(|err| NetworkError::Codec(err.to_string()))?;
+    match publish_gossip(swarm, cfg, TOPIC_IDENTITY.clone(), message) {
+        Ok(()) | Err(PublishError::Duplicate) => Ok(true),
+        Err(PublishError::NoPeersSubscribedToTopic) => Ok(false),
+        Err(err) => Err(NetworkError::Libp2p(err.to_string())),
+    }
+}
+
 fn is_anchor_topic(cfg: &NetConfig, topic: &gossipsub::TopicHash) -> bool {
     cfg.bridge_topics
         .iter()
@@ -3011,16 +4690,12 @@ fn bridge_anchor_message(
         if topic.hash() == *origin {
             continue;
         }
-        match swarm
-            .behaviour_mut()
-            .gossipsub
-            .publish(topic.clone(), message.to_vec())
-        {
+        match publish_gossip(swarm, cfg, topic.clone(), message.to_vec()) {
             Ok(_) => {}
             Err(PublishError::NoPeersSubscribedToTopic) => {}
             Err(PublishError::Duplicate) => {}
             Err(err) => {
-                metrics.inc_gossipsub_rejects();
+                metrics.inc_gossipsub_rejects(RejectReason::LocalPublish);
                 eprintln!("bridge publish error: {err}");
             }
         }
@@ -3035,6 +4710,38 @@ fn sha256_digest(data: &[u8]) -> [u8; 32] {
     out
 }
 
+fn provide_checkpoint(swarm: &mut Swarm<JrocBehaviour>, digest: &[u8; 32]) {
+    let key = kad::RecordKey::new(&checkpoint_provider_key(digest));
+    match swarm.behaviour_mut().kademlia.start_providing(key) {
+        Ok(_) => println!("QSYS|mod=DHT|evt=PROVIDE|digest={}", hex::encode(digest)),
+        Err(err) => eprintln!("checkpoint provider record failed: {err:?}"),
+    }
+}
+
+fn find_checkpoint_providers(swarm: &mut Swarm<JrocBehaviour>, digest: &[u8; 32]) {
+    let key = kad::RecordKey::new(&checkpoint_provider_key(digest));
+    swarm.behaviour_mut().kademlia.get_providers(key);
+    println!(
+        "QSYS|mod=DHT|evt=FIND_PROVIDERS|digest={}",
+        hex::encode(digest)
+    );
+}
+
+fn persist_peer_store(cfg: &NetConfig, peer_store: &mut PeerStore) {
+    peer_store.prune(now_millis() / 1000, PEER_STORE_MAX_AGE_SECS);
+    if let Err(err) = peer_store.save(&PeerStore::path_for(&cfg.log_dir)) {
+        eprintln!("failed to persist peer store: {err}");
+    }
+}
+
+fn is_shareable_addr(addr: &Multiaddr) -> bool {
+    !addr.iter().any(|proto| match proto {
+        Protocol::Ip4(ip) => ip.is_loopback() || ip.is_unspecified(),
+        Protocol::Ip6(ip) => ip.is_loopback() || ip.is_unspecified(),
+        _ => false,
+    })
+}
+
 fn record_invalid(
     map: &mut HashMap<libp2p::PeerId, usize>,
     peer: libp2p::PeerId,
@@ -3060,13 +4767,19 @@ async fn run_metrics_server(
     addr: SocketAddr,
     metrics: Arc<Metrics>,
     identity: MetricsIdentity,
+    limiter: ConnectionLimiter,
 ) -> io::Result<()> {
     let listener = TcpListener::bind(addr).await?;
     loop {
         let (mut stream, _) = listener.accept().await?;
+        let Some(permit) = limiter.admit(&mut stream).await else {
+            eprintln!("metrics server saturated: rejected connection with 503");
+            continue;
+        };
         let metrics = metrics.clone();
         let identity = identity.clone();
         tokio::spawn(async move {
+            let _permit = permit;
             if let Err(err) = respond_with_metrics(&mut stream, metrics, identity).await {
                 eprintln!("metrics connection error: {err}");
             }
@@ -3408,6 +5121,7 @@ fn append_rollup_fault_evidence(path: &Path, ev: &RollupFaultEvidence) {
 }
 
 fn record_slash_with_registry(
+    signing: &SigningKey,
     policy: &Arc<dyn MembershipPolicy>,
     registry_path: &Option<PathBuf>,
     token_mode_contract: &Option<String>,
@@ -3434,6 +5148,7 @@ fn record_slash_with_registry(
         }
     }
     queue_token_burn_intent(
+        signing,
         registry_path,
         token_mode_contract,
         token_oracle_rpc,
@@ -3441,7 +5156,9 @@ fn record_slash_with_registry(
         reason,
     );
 }
-fn load_anchor_from_logs(path: &Path) -> Result<LedgerAnchor, NetworkError> {
+/// Rebuilds the local anchor from the latest checkpoint plus the logs after its cutoff.
+pub fn load_anchor_from_logs(path: &Path) -> Result<LedgerAnchor, NetworkError> {
+    let _span = crate::profile::span("load_anchor_from_logs");
     let mut cutoff: Option<String> = None;
     let mut anchor_from_checkpoint = false;
     let anchor = match load_latest_checkpoint(path) {
@@ -3457,7 +5174,12 @@ fn load_anchor_from_logs(path: &Path) -> Result<LedgerAnchor, NetworkError> {
         Ok(None) => julian_genesis_anchor(),
         Err(err) => return Err(NetworkError::Anchor(err.to_string())),
     };
+    let gc = EntryGcManifest::load(path).map_err(|err| NetworkError::Anchor(err.to_string()))?;
+    let archived = gc.archived_digests();
     let mut entries = anchor.entries;
+    let sealed = entries.len();
+    entries.retain(|entry| !entry.hashes.iter().any(|hash| archived.contains(hash)));
+    let mut regathered = entries.len() != sealed;
     let mut metadata = anchor.metadata;
     if !anchor_from_checkpoint {
         metadata.challenge_mode = None;
@@ -3466,30 +5188,35 @@ fn load_anchor_from_logs(path: &Path) -> Result<LedgerAnchor, NetworkError> {
     metadata
         .crate_version
         .get_or_insert_with(|| env!("CARGO_PKG_VERSION").to_string());
-    let mut files: Vec<PathBuf> = std::fs::read_dir(path)
-        .map_err(|err| NetworkError::Io(format!("failed to read {}: {err}", path.display())))?
-        .filter_map(|entry| entry.ok().map(|e| e.path()))
-        .filter(|p| p.is_file() && is_ledger_file(p))
-        .collect();
-    files.sort();
-    for file in files {
-        if let Some(ref cutoff_name) = cutoff {
-            if let Some(name) = file.file_name().and_then(|n| n.to_str()) {
-                if name <= cutoff_name.as_str() {
-                    continue;
-                }
+    for record in iter_ledger_logs(path).map_err(NetworkError::Anchor)? {
+        let record = record.map_err(NetworkError::Anchor)?;
+        let name = record.name.as_str();
+        let below_cutoff = cutoff
+            .as_deref()
+            .is_some_and(|cutoff_name| name <= cutoff_name);
+        if below_cutoff && !gc.is_restored(name) {
+            continue;
+        }
+        let parsed = record.parse().map_err(NetworkError::Anchor)?;
+        if archived.contains(&parsed.digest) {
+            continue;
+        }
+        if below_cutoff {
+            if entries
+                .iter()
+                .any(|entry| entry.hashes.contains(&parsed.digest))
+            {
+                continue;
             }
+            regathered = true;
         }
-        let parsed = parse_log_file(&file).map_err(NetworkError::Anchor)?;
         if let Some(mode) = parsed.metadata.challenge_mode {
             match &mut metadata.challenge_mode {
                 None => metadata.challenge_mode = Some(mode),
                 Some(existing) if existing != &mode => {
                     return Err(NetworkError::Anchor(format!(
                         "{} challenge_mode {} conflicts with existing {}",
-                        file.display(),
-                        mode,
-                        existing
+                        name, mode, existing
                     )));
                 }
                 _ => {}
@@ -3500,7 +5227,7 @@ fn load_anchor_from_logs(path: &Path) -> Result<LedgerAnchor, NetworkError> {
                 if existing != &digest && anchor_from_checkpoint {
                     return Err(NetworkError::Anchor(format!(
                         "{} fold_digest conflicts with existing value",
-                        file.display()
+                        name
                     )));
                 }
             }
@@ -3511,6 +5238,7 @@ fn load_anchor_from_logs(path: &Path) -> Result<LedgerAnchor, NetworkError> {
             statement: parsed.statement,
             merkle_root: merkle_root(&entry_hashes),
             hashes: entry_hashes,
+            submission: parsed.metadata.submission,
         });
     }
     if entries.is_empty() {
@@ -3527,19 +5255,12 @@ fn load_anchor_from_logs(path: &Path) -> Result<LedgerAnchor, NetworkError> {
         metadata.fold_digest = Some(digest);
     }
     let mut anchor = LedgerAnchor { entries, metadata };
-    if anchor.metadata.fold_digest.is_none() {
+    if regathered || anchor.metadata.fold_digest.is_none() {
         anchor.metadata.fold_digest = Some(compute_fold_digest(&anchor));
     }
     Ok(anchor)
 }
 
-fn is_ledger_file(path: &Path) -> bool {
-    match path.file_name().and_then(|n| n.to_str()) {
-        Some(name) => name.starts_with("ledger_") && name.ends_with(".txt"),
-        None => false,
-    }
-}
-
 fn now_millis() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -3557,7 +5278,7 @@ fn anchor_payload_hash(payload: &[u8]) -> String {
 }
 
 fn vote_payload_bytes(round: u64, anchor_hash: &str) -> Vec<u8> {
-    format!("{NETWORK_ID}:{round}:{anchor_hash}").into_bytes()
+    format!("{}:{round}:{anchor_hash}", network_id()).into_bytes()
 }
 #[cfg(test)]
 mod tests {
@@ -3577,6 +5298,295 @@ mod tests {
         base
     }
 
+    #[test]
+    fn share_samples_are_distinct_and_replayable_by_seed() {
+        let picked = sample_share_indices(12, 5, 42);
+        assert_eq!(picked.len(), 5);
+        let mut unique = picked.clone();
+        unique.sort_unstable();
+        unique.dedup();
+        assert_eq!(unique.len(), 5);
+        assert!(picked.iter().all(|&i| i < 12));
+        assert_eq!(picked, sample_share_indices(12, 5, 42));
+        assert_ne!(picked, sample_share_indices(12, 5, 43));
+        assert_eq!(sample_share_indices(3, 10, 7).len(), 3);
+        assert_eq!(parse_query_u64(Some("count=2&seed=9"), "seed"), Some(9));
+    }
+
+    #[test]
+    fn stake_policy_drives_weighted_anchor_quorum() {
+        use crate::net::governance::{StakePolicy, StaticPolicy};
+        let heavy = SigningKey::from_bytes(&[7u8; 32]).verifying_key();
+        let light = SigningKey::from_bytes(&[9u8; 32]).verifying_key();
+        let path = temp_path("power_house_stake_quorum").with_extension("json");
+        let state = serde_json::json!({
+            "threshold": 1,
+            "bond_threshold": 1,
+            "signers": [],
+            "entries": [
+                {"public_key": BASE64.encode(heavy.to_bytes()), "bond": 70, "slashed": false},
+                {"public_key": BASE64.encode(light.to_bytes()), "bond": 30, "slashed": false},
+            ],
+        });
+        fs::write(&path, serde_json::to_vec(&state).unwrap()).unwrap();
+        let policy = StakePolicy::load(&path, None, None).unwrap();
+        assert_eq!(stake_quorum_threshold(&policy), Some(67));
+        assert_eq!(anchor_vote_weight(&policy, &heavy.to_bytes()), 70);
+        assert_eq!(anchor_vote_weight(&policy, b"short"), 0);
+        let static_policy = StaticPolicy::allow_all();
+        assert_eq!(stake_quorum_threshold(&static_policy), None);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn checkpoint_provider_keys_roundtrip() {
+        let anchor = julian_genesis_anchor();
+        let checkpoint = AnchorCheckpoint::new(
+            4,
+            AnchorJson::from_ledger("node-a", 1, &anchor, 0, Vec::new(), None).unwrap(),
+            Vec::new(),
+            None,
+        );
+        let digest = checkpoint.digest().unwrap();
+        assert_eq!(digest, anchor_digest(&anchor));
+        let key = checkpoint_provider_key(&digest);
+        assert!(key.starts_with(b"mfenx/powerhouse/checkpoint/v1/"));
+        assert_eq!(parse_checkpoint_provider_key(&key), Some(digest));
+        assert_eq!(parse_checkpoint_provider_key(b"/ipfs/other"), None);
+        assert_eq!(
+            parse_checkpoint_provider_key(b"mfenx/powerhouse/checkpoint/v1/abcd"),
+            None
+        );
+    }
+
+    fn validation_config(policy: Arc<dyn MembershipPolicy>) -> NetConfig {
+        let key_material = crate::net::sign::load_or_derive_keypair(
+            &crate::net::sign::Ed25519KeySource::Seed("ed25519://validation-test".into()),
+        )
+        .unwrap();
+        NetConfig::new(
+            "validator-1".into(),
+            "/ip4/127.0.0.1/tcp/0".parse().unwrap(),
+            Vec::new(),
+            temp_path("power_house_gossip_validation"),
+            1,
+            Duration::from_secs(1),
+            key_material,
+            None,
+            None,
+            false,
+            None,
+            None,
+            policy,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    fn signed_anchor_envelope(cfg: &NetConfig, network: &str) -> Vec<u8> {
+        let mut anchor_json = AnchorJson::from_ledger(
+            "validator-1",
+            1,
+            &julian_genesis_anchor(),
+            0,
+            Vec::new(),
+            None,
+        )
+        .unwrap();
+        anchor_json.network = network.to_string();
+        let payload = serde_json::to_vec(&anchor_json).unwrap();
+        let signature = sign_payload(&cfg.key_material.signing, &payload);
+        serde_json::to_vec(&AnchorEnvelope {
+            schema: SCHEMA_ENVELOPE.to_string(),
+            schema_version: ENVELOPE_SCHEMA_VERSION,
+            network: network.to_string(),
+            public_key: encode_public_key_base64(&cfg.key_material.verifying),
+            node_id: cfg.node_id.clone(),
+            payload: BASE64.encode(&payload),
+            signature: encode_signature_base64(&signature),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn identity_bindings_reject_spoofed_envelope_node_ids() {
+        use crate::net::governance::StaticPolicy;
+        let cfg = validation_config(Arc::new(StaticPolicy::allow_all()));
+        let topic = cfg.anchor_topic.hash();
+        let envelope = signed_anchor_envelope(&cfg, network_id());
+        assert!(matches!(
+            validate_gossip_message(&cfg, &topic, &envelope),
+            Ok(ValidatedMessage::Anchor(_))
+        ));
+
+        let document = IdentityDocument::issue(network_id(), "renamed", &cfg.key_material, 1);
+        let encoded = serde_json::to_vec(&document).unwrap();
+        let Ok(ValidatedMessage::Identity(received)) =
+            validate_gossip_message(&cfg, &TOPIC_IDENTITY.hash(), &encoded)
+        else {
+            panic!("signed identity document must validate");
+        };
+        assert_eq!(
+            register_identity(&cfg, &received, Some(&PeerId::random())),
+            Err(RejectReason::Identity)
+        );
+        let author = cfg.key_material.libp2p.public().to_peer_id();
+        assert_eq!(register_identity(&cfg, &received, Some(&author)), Ok(()));
+        assert_eq!(
+            validate_gossip_message(&cfg, &topic, &envelope).err(),
+            Some(RejectReason::Identity)
+        );
+
+        let mut forged = document.clone();
+        forged.node_id = "other".into();
+        assert_eq!(
+            validate_gossip_message(
+                &cfg,
+                &TOPIC_IDENTITY.hash(),
+                &serde_json::to_vec(&forged).unwrap()
+            )
+            .err(),
+            Some(RejectReason::Signature)
+        );
+        let foreign = IdentityDocument::issue("other-net", "renamed", &cfg.key_material, 2);
+        assert_eq!(
+            validate_gossip_message(
+                &cfg,
+                &TOPIC_IDENTITY.hash(),
+                &serde_json::to_vec(&foreign).unwrap()
+            )
+            .err(),
+            Some(RejectReason::Network)
+        );
+    }
+
+    #[test]
+    fn gossip_validation_classifies_anchor_envelopes() {
+        use crate::net::governance::StaticPolicy;
+        let cfg = validation_config(Arc::new(StaticPolicy::allow_all()));
+        let topic = cfg.anchor_topic.hash();
+        let valid = signed_anchor_envelope(&cfg, network_id());
+        assert!(matches!(
+            validate_gossip_message(&cfg, &topic, &valid),
+            Ok(ValidatedMessage::Anchor(_))
+        ));
+
+        let mut tampered: AnchorEnvelope = serde_json::from_slice(&valid).unwrap();
+        tampered.node_id = "validator-2".into();
+        tampered.payload = BASE64.encode(b"{}");
+        let tampered = serde_json::to_vec(&tampered).unwrap();
+        let reject = |data: &[u8]| validate_gossip_message(&cfg, &topic, data).err();
+        assert_eq!(reject(&tampered), Some(RejectReason::Signature));
+        assert_eq!(reject(b"not json"), Some(RejectReason::Malformed));
+        assert_eq!(
+            reject(&vec![b' '; MAX_ENVELOPE_BYTES + 1]),
+            Some(RejectReason::Oversize)
+        );
+        assert_eq!(
+            reject(&signed_anchor_envelope(&cfg, "other-net")),
+            Some(RejectReason::Network)
+        );
+        let mut future: AnchorEnvelope = serde_json::from_slice(&valid).unwrap();
+        future.schema_version = ENVELOPE_SCHEMA_VERSION + 1;
+        assert_eq!(
+            reject(&serde_json::to_vec(&future).unwrap()),
+            Some(RejectReason::Version)
+        );
+        let legacy: AnchorEnvelope = serde_json::from_slice(&valid).unwrap();
+        assert!(matches!(
+            validate_gossip_message(
+                &cfg,
+                &topic,
+                &serde_json::to_vec(&legacy.downgrade(1)).unwrap()
+            ),
+            Ok(ValidatedMessage::Anchor(_))
+        ));
+        let mut relabelled: AnchorEnvelope = serde_json::from_slice(&valid).unwrap();
+        relabelled.network = "other-net".into();
+        assert_eq!(
+            reject(&serde_json::to_vec(&relabelled).unwrap()),
+            Some(RejectReason::Network)
+        );
+        let vote = AnchorVoteJson {
+            schema: SCHEMA_VOTE.to_string(),
+            network: "other-net".into(),
+            round: 1,
+            anchor_hash: "ab".repeat(32),
+            public_key: encode_public_key_base64(&cfg.key_material.verifying),
+            signature: String::new(),
+        };
+        assert_eq!(
+            validate_gossip_message(
+                &cfg,
+                &TOPIC_VOTES.hash(),
+                &serde_json::to_vec(&vote).unwrap()
+            )
+            .err(),
+            Some(RejectReason::Network)
+        );
+        let mut native = NativeChainMessage::new(NativeChainMessagePayload::Tip(
+            crate::net::native_chain::NativeChainTip {
+                height: 0,
+                hash: String::new(),
+                observed_at: 0,
+            },
+        ));
+        native.network = "other-net".into();
+        assert_eq!(
+            validate_gossip_message(
+                &cfg,
+                &TOPIC_NATIVE_CHAIN.hash(),
+                &serde_json::to_vec(&native).unwrap()
+            )
+            .err(),
+            Some(RejectReason::Network)
+        );
+        assert!(native.validate_schema().is_err());
+        assert!(matches!(
+            validate_gossip_message(&cfg, &IdentTopic::new("unrelated").hash(), b"x"),
+            Ok(ValidatedMessage::Ignored)
+        ));
+
+        let outsider = SigningKey::from_bytes(&[3u8; 32]).verifying_key();
+        let allowlist =
+            StaticPolicy::from_base64_strings(&[encode_public_key_base64(&outsider)]).unwrap();
+        let restricted = validation_config(Arc::new(allowlist));
+        assert_eq!(
+            validate_gossip_message(&restricted, &topic, &valid).err(),
+            Some(RejectReason::Policy)
+        );
+    }
+
+    #[test]
+    fn reject_reasons_render_per_reason_counters() {
+        let metrics = Metrics::default();
+        metrics.inc_gossipsub_rejects(RejectReason::Signature);
+        metrics.inc_gossipsub_rejects(RejectReason::Signature);
+        metrics.inc_gossipsub_rejects(RejectReason::Policy);
+        let rendered = metrics.render(&MetricsIdentity {
+            node_id: "validator-1".to_string(),
+            peer_id: "peer".to_string(),
+            public_key_b64: "pk".to_string(),
+            chain_id: 1,
+        });
+        assert!(rendered.contains("gossipsub_rejects_total 3\n"));
+        assert!(rendered.contains("gossipsub_rejects_by_reason_total{reason=\"signature\"} 2\n"));
+        assert!(rendered.contains("gossipsub_rejects_by_reason_total{reason=\"policy\"} 1\n"));
+        assert!(rendered.contains("gossipsub_rejects_by_reason_total{reason=\"oversize\"} 0\n"));
+    }
+
     #[test]
     fn payload_cache_rejects_duplicates() {
         let metrics = Arc::new(Metrics::default());
@@ -3656,6 +5666,56 @@ mod tests {
         fs::remove_dir_all(&dir).unwrap();
     }
 
+    #[test]
+    fn journal_records_extend_legacy_logs() {
+        let dir = temp_path("mfenx_powerhouse_logs_journal");
+        fs::create_dir_all(&dir).unwrap();
+        let record = |statement: &str, final_value: u64| {
+            let hash = transcript_digest(&[1, 2], &[3, 4], final_value);
+            vec![
+                format!("statement:{statement}"),
+                "transcript:1 2".to_string(),
+                "round_sums:3 4".to_string(),
+                format!("final:{final_value}"),
+                format!("hash:{}", crate::transcript_digest_to_hex(&hash)),
+            ]
+        };
+        fs::write(dir.join("ledger_0000.txt"), record("Legacy", 5).join("\n")).unwrap();
+        {
+            let mut journal =
+                crate::TranscriptJournal::open(&dir, crate::JournalConfig::default()).unwrap();
+            journal.append(&record("Journaled", 6)).unwrap();
+        }
+
+        let anchor = load_anchor_from_logs(&dir).unwrap();
+        let statements: Vec<_> = anchor
+            .entries
+            .iter()
+            .map(|entry| entry.statement.as_str())
+            .collect();
+        assert_eq!(statements[1..], ["Legacy", "Journaled"]);
+        let cutoff = latest_log_cutoff(&dir).unwrap();
+        assert!(cutoff.starts_with("ledger_journal_000000.phj#"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn binary_log_files_are_detected() {
+        let dir = temp_path("mfenx_powerhouse_logs_binary");
+        fs::create_dir_all(&dir).unwrap();
+        let mut bytes = Vec::new();
+        crate::write_binary_transcript_record(&mut bytes, "Binary", &[1, 2], &[3, 4], 5).unwrap();
+        fs::write(dir.join("ledger_0000.bin"), &bytes).unwrap();
+
+        let anchor = load_anchor_from_logs(&dir).unwrap();
+        let entry = anchor.entries.last().unwrap();
+        assert_eq!(entry.statement, "Binary");
+        assert_eq!(entry.hashes, vec![transcript_digest(&[1, 2], &[3, 4], 5)]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn tampered_log_is_rejected() {
         let dir = temp_path("mfenx_powerhouse_logs_tampered");
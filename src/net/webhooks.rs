@@ -0,0 +1,482 @@
+#![cfg(feature = "net")]
+
+//! Outbound webhook notifications for ledger and finality events.
+//!
+//! Integrators register URLs in a JSON file passed to `julian net start
+//! --webhooks <file>`.  The swarm hands each event to a [`WebhookDispatcher`],
+//! which POSTs a [`WebhookEvent`] to every endpoint subscribed to its kind.
+//! Each body is signed with the endpoint's shared secret as
+//! `X-Powerhouse-Signature: sha256=<hex HMAC-SHA256 of the body>`, and failed
+//! deliveries are retried with exponential backoff up to
+//! [`WebhookConfig::max_attempts`].  Delivery never blocks the swarm; events
+//! that exhaust their retries are logged and dropped.
+
+use crate::net::schema::AnchorEntryJson;
+use blake2::digest::{consts::U32, Digest};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::{
+    collections::HashSet,
+    fs,
+    path::Path,
+    sync::{Arc, Mutex, PoisonError},
+    time::Duration,
+};
+use tokio::{sync::mpsc, time};
+
+type Blake2b256 = blake2::Blake2b<U32>;
+
+/// Schema identifier of a [`WebhookEvent`].
+pub const SCHEMA_WEBHOOK_EVENT: &str = "mfenx.powerhouse.webhook_event.v1";
+/// Header carrying `sha256=<hex>` over the request body.
+pub const SIGNATURE_HEADER: &str = "X-Powerhouse-Signature";
+/// Header carrying the event kind, e.g. `finality_reached`.
+pub const EVENT_HEADER: &str = "X-Powerhouse-Event";
+
+const WEBHOOK_EVENT_DOMAIN: &[u8] = b"MFENX_WEBHOOK_EVENT";
+
+/// Errors produced while loading webhook configuration.
+#[derive(Debug, thiserror::Error)]
+pub enum WebhookError {
+    /// The configuration file could not be read.
+    #[error("webhook I/O error: {0}")]
+    Io(String),
+    /// The configuration is malformed.
+    #[error("invalid webhook config: {0}")]
+    Invalid(String),
+}
+
+/// Node events that can be delivered to webhooks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEventKind {
+    /// A transcript hash appeared in the local ledger.
+    EntryAccepted,
+    /// A peer anchor reached quorum agreement with the local ledger.
+    FinalityReached,
+    /// The node wrote a checkpoint.
+    CheckpointWritten,
+    /// Conflicting finalized checkpoints put the node in safe mode.
+    ForkDetected,
+}
+
+impl WebhookEventKind {
+    /// Wire name used in the body and [`EVENT_HEADER`].
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::EntryAccepted => "entry_accepted",
+            Self::FinalityReached => "finality_reached",
+            Self::CheckpointWritten => "checkpoint_written",
+            Self::ForkDetected => "fork_detected",
+        }
+    }
+}
+
+/// One registered receiver.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebhookEndpoint {
+    /// URL receiving `POST` requests.
+    pub url: String,
+    /// Shared secret keying the body signature.
+    pub secret: String,
+    /// Kinds delivered to this endpoint; empty subscribes to all of them.
+    #[serde(default)]
+    pub events: Vec<WebhookEventKind>,
+}
+
+impl WebhookEndpoint {
+    /// Whether this endpoint receives `kind`.
+    pub fn subscribes_to(&self, kind: WebhookEventKind) -> bool {
+        self.events.is_empty() || self.events.contains(&kind)
+    }
+}
+
+/// Contents of the `--webhooks` file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    /// Registered receivers.
+    pub endpoints: Vec<WebhookEndpoint>,
+    /// Delivery attempts per event and endpoint, including the first.
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    /// Delay before the first retry; later retries double it.
+    #[serde(default = "default_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+    /// Upper bound for the retry delay.
+    #[serde(default = "default_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+    /// Timeout applied to each HTTP request.
+    #[serde(default = "default_request_timeout_ms")]
+    pub request_timeout_ms: u64,
+}
+
+fn default_max_attempts() -> u32 {
+    5
+}
+
+fn default_initial_backoff_ms() -> u64 {
+    1_000
+}
+
+fn default_max_backoff_ms() -> u64 {
+    60_000
+}
+
+fn default_request_timeout_ms() -> u64 {
+    10_000
+}
+
+impl WebhookConfig {
+    /// Reads and validates a configuration file.
+    pub fn load(path: &Path) -> Result<Self, WebhookError> {
+        let bytes = fs::read(path)
+            .map_err(|err| WebhookError::Io(format!("read {}: {err}", path.display())))?;
+        let config: Self = serde_json::from_slice(&bytes)
+            .map_err(|err| WebhookError::Invalid(format!("{}: {err}", path.display())))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks that every endpoint has an HTTP(S) URL and a secret.
+    pub fn validate(&self) -> Result<(), WebhookError> {
+        if self.max_attempts == 0 {
+            return Err(WebhookError::Invalid(
+                "max_attempts must be at least 1".to_string(),
+            ));
+        }
+        for endpoint in &self.endpoints {
+            let url = reqwest::Url::parse(&endpoint.url)
+                .map_err(|err| WebhookError::Invalid(format!("url {}: {err}", endpoint.url)))?;
+            if !matches!(url.scheme(), "http" | "https") {
+                return Err(WebhookError::Invalid(format!(
+                    "url {} must use http or https",
+                    endpoint.url
+                )));
+            }
+            if endpoint.secret.is_empty() {
+                return Err(WebhookError::Invalid(format!(
+                    "endpoint {} has no secret",
+                    endpoint.url
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Delay before retry number `retry` (1 for the first retry).
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 1u64 << retry.saturating_sub(1).min(16);
+        Duration::from_millis(
+            self.initial_backoff_ms
+                .saturating_mul(factor)
+                .min(self.max_backoff_ms.max(self.initial_backoff_ms)),
+        )
+    }
+}
+
+/// Body POSTed to webhook endpoints.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookEvent {
+    /// Schema identifier (`mfenx.powerhouse.webhook_event.v1`).
+    pub schema: String,
+    /// Hex digest of the event, stable across retries for deduplication.
+    pub id: String,
+    /// Event kind.
+    pub event: WebhookEventKind,
+    /// Node that observed the event.
+    pub node_id: String,
+    /// Millisecond timestamp of the observation.
+    pub emitted_ms: u64,
+    /// Kind-specific fields.
+    pub data: serde_json::Value,
+}
+
+impl WebhookEvent {
+    /// Builds an event and derives its id.
+    pub fn new(
+        event: WebhookEventKind,
+        node_id: impl Into<String>,
+        emitted_ms: u64,
+        data: serde_json::Value,
+    ) -> Self {
+        let node_id = node_id.into();
+        let mut hasher = Blake2b256::new();
+        hasher.update(WEBHOOK_EVENT_DOMAIN);
+        hasher.update(event.as_str().as_bytes());
+        hasher.update([0]);
+        hasher.update(node_id.as_bytes());
+        hasher.update([0]);
+        hasher.update(emitted_ms.to_be_bytes());
+        hasher.update(data.to_string().as_bytes());
+        Self {
+            schema: SCHEMA_WEBHOOK_EVENT.to_string(),
+            id: hex::encode(hasher.finalize()),
+            event,
+            node_id,
+            emitted_ms,
+            data,
+        }
+    }
+}
+
+/// Value of [`SIGNATURE_HEADER`] for `body` under `secret`.
+pub fn sign_webhook_body(secret: &[u8], body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Checks a received [`SIGNATURE_HEADER`] value in constant time.
+pub fn verify_webhook_signature(secret: &[u8], body: &[u8], header: &str) -> bool {
+    let Some(signature) = header
+        .strip_prefix("sha256=")
+        .and_then(|hex_mac| hex::decode(hex_mac).ok())
+    else {
+        return false;
+    };
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(body);
+    mac.verify_slice(&signature).is_ok()
+}
+
+/// POSTs one signed body to `endpoint`.
+pub async fn deliver_webhook(
+    client: &reqwest::Client,
+    endpoint: &WebhookEndpoint,
+    kind: WebhookEventKind,
+    body: &[u8],
+) -> Result<(), String> {
+    let response = client
+        .post(&endpoint.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(EVENT_HEADER, kind.as_str())
+        .header(
+            SIGNATURE_HEADER,
+            sign_webhook_body(endpoint.secret.as_bytes(), body),
+        )
+        .body(body.to_vec())
+        .send()
+        .await
+        .map_err(|err| err.to_string())?;
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
+    }
+    let text = response.text().await.unwrap_or_default();
+    Err(format!("HTTP {status}: {text}"))
+}
+
+/// Queues events for background delivery.
+#[derive(Debug)]
+pub struct WebhookDispatcher {
+    node_id: String,
+    sender: mpsc::UnboundedSender<WebhookEvent>,
+    seen_entries: Mutex<Option<HashSet<String>>>,
+}
+
+impl WebhookDispatcher {
+    /// Spawns the delivery task on the current tokio runtime.
+    pub fn start(config: WebhookConfig, node_id: impl Into<String>) -> Result<Self, WebhookError> {
+        config.validate()?;
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.request_timeout_ms))
+            .build()
+            .map_err(|err| WebhookError::Invalid(err.to_string()))?;
+        let (sender, receiver) = mpsc::unbounded_channel();
+        println!(
+            "QSYS|mod=WEBHOOK|evt=START|endpoints={}",
+            config.endpoints.len()
+        );
+        tokio::spawn(run_dispatcher(Arc::new(config), client, receiver));
+        Ok(Self {
+            node_id: node_id.into(),
+            sender,
+            seen_entries: Mutex::new(None),
+        })
+    }
+
+    /// Queues a `kind` event carrying `data`.
+    pub fn emit(&self, kind: WebhookEventKind, emitted_ms: u64, data: serde_json::Value) {
+        let event = WebhookEvent::new(kind, self.node_id.clone(), emitted_ms, data);
+        if self.sender.send(event).is_err() {
+            eprintln!("QSYS|mod=WEBHOOK|evt=DROPPED|reason=dispatcher_stopped");
+        }
+    }
+
+    /// Emits `entry_accepted` for every transcript hash not in the previous
+    /// call's `entries`.
+    ///
+    /// The first call records the ledger as it stands without emitting, so a
+    /// restart does not replay the history.
+    pub fn observe_entries(&self, emitted_ms: u64, entries: &[AnchorEntryJson]) {
+        let mut seen = self
+            .seen_entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let Some(seen) = seen.as_mut() else {
+            *seen = Some(
+                entries
+                    .iter()
+                    .flat_map(|entry| entry.hashes.iter().cloned())
+                    .collect(),
+            );
+            return;
+        };
+        for entry in entries {
+            for hash in &entry.hashes {
+                if seen.insert(hash.clone()) {
+                    self.emit(
+                        WebhookEventKind::EntryAccepted,
+                        emitted_ms,
+                        serde_json::json!({
+                            "statement": entry.statement,
+                            "hash": hash,
+                        }),
+                    );
+                }
+            }
+        }
+    }
+}
+
+async fn run_dispatcher(
+    config: Arc<WebhookConfig>,
+    client: reqwest::Client,
+    mut receiver: mpsc::UnboundedReceiver<WebhookEvent>,
+) {
+    while let Some(event) = receiver.recv().await {
+        let body = match serde_json::to_vec(&event) {
+            Ok(body) => Arc::new(body),
+            Err(err) => {
+                eprintln!("QSYS|mod=WEBHOOK|evt=ENCODE_FAILED|error={err}");
+                continue;
+            }
+        };
+        for (index, endpoint) in config.endpoints.iter().enumerate() {
+            if !endpoint.subscribes_to(event.event) {
+                continue;
+            }
+            tokio::spawn(deliver_with_retry(
+                config.clone(),
+                index,
+                client.clone(),
+                event.event,
+                event.id.clone(),
+                body.clone(),
+            ));
+        }
+    }
+}
+
+async fn deliver_with_retry(
+    config: Arc<WebhookConfig>,
+    index: usize,
+    client: reqwest::Client,
+    kind: WebhookEventKind,
+    id: String,
+    body: Arc<Vec<u8>>,
+) {
+    let endpoint = &config.endpoints[index];
+    for attempt in 1..=config.max_attempts {
+        match deliver_webhook(&client, endpoint, kind, &body).await {
+            Ok(()) => return,
+            Err(err) if attempt < config.max_attempts => {
+                eprintln!(
+                    "QSYS|mod=WEBHOOK|evt=RETRY|url={}|id={id}|attempt={attempt}|error={err}",
+                    endpoint.url
+                );
+                time::sleep(config.backoff(attempt)).await;
+            }
+            Err(err) => eprintln!(
+                "QSYS|mod=WEBHOOK|evt=FAILED|url={}|id={id}|attempts={attempt}|error={err}",
+                endpoint.url
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn signed_events_are_retried_until_delivered() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let mut requests = Vec::new();
+            for status in ["503 Service Unavailable", "200 OK"] {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buf = vec![0u8; 8192];
+                let mut read = 0;
+                while !String::from_utf8_lossy(&buf[..read]).ends_with('}') {
+                    read += stream.read(&mut buf[read..]).await.unwrap();
+                }
+                requests.push(String::from_utf8_lossy(&buf[..read]).to_string());
+                let response =
+                    format!("HTTP/1.1 {status}\r\nConnection: close\r\nContent-Length: 0\r\n\r\n");
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+            requests
+        });
+
+        let config: WebhookConfig = serde_json::from_value(serde_json::json!({
+            "endpoints": [
+                {"url": format!("http://{addr}/hooks"), "secret": "s3cret",
+                 "events": ["entry_accepted"]},
+                {"url": "http://127.0.0.1:9/unused", "secret": "x",
+                 "events": ["fork_detected"]}
+            ],
+            "initial_backoff_ms": 10
+        }))
+        .unwrap();
+        assert_eq!(config.max_attempts, 5);
+        assert_eq!(config.backoff(3), Duration::from_millis(40));
+        let dispatcher = WebhookDispatcher::start(config, "node-a").unwrap();
+        let entry = |hashes: &[&str]| AnchorEntryJson {
+            statement: "stmt".to_string(),
+            hashes: hashes.iter().map(|hash| hash.to_string()).collect(),
+            merkle_root: None,
+            submission: None,
+        };
+        dispatcher.observe_entries(1, &[entry(&["aa"])]);
+        dispatcher.observe_entries(2, &[entry(&["aa", "bb"])]);
+        dispatcher.emit(WebhookEventKind::FinalityReached, 3, serde_json::json!({}));
+
+        let requests = server.await.unwrap();
+        assert_eq!(requests[0], requests[1]);
+        let (head, body) = requests[1].split_once("\r\n\r\n").unwrap();
+        let head = head.to_ascii_lowercase();
+        assert!(head.starts_with("post /hooks "));
+        assert!(head.contains("x-powerhouse-event: entry_accepted"));
+        let signature = head
+            .lines()
+            .find_map(|line| line.strip_prefix("x-powerhouse-signature: "))
+            .unwrap();
+        assert!(verify_webhook_signature(
+            b"s3cret",
+            body.as_bytes(),
+            signature
+        ));
+        assert!(!verify_webhook_signature(
+            b"other",
+            body.as_bytes(),
+            signature
+        ));
+        let event: WebhookEvent = serde_json::from_str(body).unwrap();
+        assert_eq!(event.event, WebhookEventKind::EntryAccepted);
+        assert_eq!(event.data["hash"], "bb");
+        assert_eq!(
+            event,
+            WebhookEvent::new(
+                WebhookEventKind::EntryAccepted,
+                "node-a",
+                2,
+                serde_json::json!({"statement": "stmt", "hash": "bb"}),
+            )
+        );
+    }
+}
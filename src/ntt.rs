@@ -0,0 +1,146 @@
+//! Number-theoretic transforms over power-of-two multiplicative subgroups.
+//!
+//! The transforms evaluate a coefficient vector on the subgroup generated by
+//! a primitive `2^k`-th root of unity and interpolate back again.  They only
+//! exist for primes whose multiplicative group has enough two-adicity, so
+//! every entry point returns `None` when the requested domain does not fit in
+//! the field (for example `p = 1_000_000_007`, where `p - 1` has a single
+//! factor of two).
+
+use crate::Field;
+
+/// Returns the largest `k` such that `2^k` divides `p - 1`.
+pub fn two_adicity(field: &Field) -> u32 {
+    (field.modulus() - 1).trailing_zeros()
+}
+
+/// Returns a primitive `2^log_size`-th root of unity, if the field has one.
+///
+/// The root is derived deterministically by scanning small candidates in
+/// increasing order, so prover and verifier always agree on the domain.
+pub fn root_of_unity(field: &Field, log_size: u32) -> Option<u64> {
+    if log_size > two_adicity(field) {
+        return None;
+    }
+    if log_size == 0 {
+        return Some(1);
+    }
+    let p = field.modulus();
+    let cofactor = (p - 1) >> log_size;
+    let half_order = 1u64 << (log_size - 1);
+    (2..p).find_map(|candidate| {
+        let root = field.pow(candidate, cofactor);
+        (field.pow(root, half_order) != 1).then_some(root)
+    })
+}
+
+/// Evaluates `coefficients` on the subgroup of size `coefficients.len()`.
+///
+/// Returns `None` when the length is not a power of two or the field lacks a
+/// root of unity of that order.  Output index `i` holds `f(ω^i)`.
+pub fn forward_ntt(field: &Field, coefficients: &[u64]) -> Option<Vec<u64>> {
+    let log_size = log2_exact(coefficients.len())?;
+    let root = root_of_unity(field, log_size)?;
+    let mut values: Vec<u64> = coefficients.iter().map(|c| c % field.modulus()).collect();
+    transform(field, &mut values, root);
+    Some(values)
+}
+
+/// Interpolates subgroup evaluations back into coefficient form.
+///
+/// This is the exact inverse of [`forward_ntt`] and shares its failure modes.
+pub fn inverse_ntt(field: &Field, evaluations: &[u64]) -> Option<Vec<u64>> {
+    let log_size = log2_exact(evaluations.len())?;
+    let root = root_of_unity(field, log_size)?;
+    let mut values: Vec<u64> = evaluations.iter().map(|v| v % field.modulus()).collect();
+    transform(field, &mut values, field.inv(root));
+    let scale = field.inv(values.len() as u64 % field.modulus());
+    for value in &mut values {
+        *value = field.mul(*value, scale);
+    }
+    Some(values)
+}
+
+/// Evaluates a low-degree polynomial on the subgroup of `domain_size` points,
+/// producing its Reed–Solomon codeword.
+pub(crate) fn low_degree_extend(
+    field: &Field,
+    coefficients: &[u64],
+    domain_size: usize,
+) -> Option<Vec<u64>> {
+    if coefficients.len() > domain_size {
+        return None;
+    }
+    let mut padded = coefficients.to_vec();
+    padded.resize(domain_size, 0);
+    forward_ntt(field, &padded)
+}
+
+fn log2_exact(len: usize) -> Option<u32> {
+    (len.is_power_of_two()).then(|| len.trailing_zeros())
+}
+
+fn transform(field: &Field, values: &mut [u64], root: u64) {
+    let n = values.len();
+    if n <= 1 {
+        return;
+    }
+    let shift = usize::BITS - n.trailing_zeros();
+    for idx in 0..n {
+        let rev = idx.reverse_bits() >> shift;
+        if idx < rev {
+            values.swap(idx, rev);
+        }
+    }
+    let mut len = 2;
+    while len <= n {
+        let step = field.pow(root, (n / len) as u64);
+        for start in (0..n).step_by(len) {
+            let mut twiddle = 1u64;
+            for offset in 0..len / 2 {
+                let even = values[start + offset];
+                let odd = field.mul(values[start + offset + len / 2], twiddle);
+                values[start + offset] = field.add(even, odd);
+                values[start + offset + len / 2] = field.sub(even, odd);
+                twiddle = field.mul(twiddle, step);
+            }
+        }
+        len <<= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NTT_PRIME: u64 = 998_244_353;
+
+    fn naive_eval(field: &Field, coefficients: &[u64], point: u64) -> u64 {
+        coefficients
+            .iter()
+            .rev()
+            .fold(0, |acc, &c| field.add(field.mul(acc, point), c))
+    }
+
+    #[test]
+    fn forward_matches_naive_evaluation() {
+        let field = Field::new(NTT_PRIME);
+        let coefficients = [3, 1, 4, 1, 5, 9, 2, 6];
+        let values = forward_ntt(&field, &coefficients).unwrap();
+        let root = root_of_unity(&field, 3).unwrap();
+        for (idx, value) in values.iter().enumerate() {
+            let point = field.pow(root, idx as u64);
+            assert_eq!(*value, naive_eval(&field, &coefficients, point));
+        }
+        assert_eq!(inverse_ntt(&field, &values).unwrap(), coefficients);
+    }
+
+    #[test]
+    fn rejects_fields_without_two_adicity() {
+        let field = Field::new(1_000_000_007);
+        assert_eq!(two_adicity(&field), 1);
+        assert!(root_of_unity(&field, 2).is_none());
+        assert!(forward_ntt(&field, &[1, 2, 3, 4]).is_none());
+        assert!(forward_ntt(&Field::new(NTT_PRIME), &[1, 2, 3]).is_none());
+    }
+}
@@ -11,13 +11,51 @@
 //! linear-congruential helper but upgrades the security story: every output
 //! chunk is derived from a keyed hash of the seed and an invocation counter,
 //! ensuring forward secrecy and resistance to trivial state reconstruction.
+//!
+//! [`ChaChaPrng`] offers the same seeding API over ChaCha20 for simulations
+//! and sampling where statistical quality matters, and [`PrngBackend`]
+//! selects between the two generators when deriving challenges.
 
 use blake2::digest::{consts::U32, Digest};
+use rand_chacha::rand_core::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
 
 type Blake2b256 = blake2::Blake2b<U32>;
 
 const PRNG_DOMAIN: &[u8] = b"MFENX_PRNG";
-const CHALLENGE_DOMAIN: &[u8] = b"MFENX_CHALLENGE";
+/// Bumped from `MFENX_CHALLENGE` when derivation moved to rejection
+/// sampling, so biased and unbiased challenges never coincide.
+const CHALLENGE_DOMAIN: &[u8] = b"MFENX_CHALLENGE_V2";
+const CHACHA_DOMAIN: &[u8] = b"MFENX_PRNG_CHACHA20";
+
+/// Generator used to expand a transcript seed into challenges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PrngBackend {
+    /// BLAKE2b-256 counter mode ([`SimplePrng`]).
+    #[default]
+    Blake2b,
+    /// ChaCha20 stream ([`ChaChaPrng`]).
+    ChaCha20,
+}
+
+impl PrngBackend {
+    /// Stable identifier recorded alongside derived challenges.
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::Blake2b => "blake2b-ctr",
+            Self::ChaCha20 => "chacha20",
+        }
+    }
+
+    /// Parses an identifier produced by [`Self::id`].
+    pub fn from_id(id: &str) -> Option<Self> {
+        match id {
+            "blake2b-ctr" => Some(Self::Blake2b),
+            "chacha20" => Some(Self::ChaCha20),
+            _ => None,
+        }
+    }
+}
 
 /// A deterministic stream generator derived from BLAKE2b-256.
 #[derive(Debug, Clone)]
@@ -70,14 +108,71 @@ impl SimplePrng {
         u64::from_be_bytes(chunk)
     }
 
-    /// Returns a pseudorandom number reduced modulo `modulus`.
+    /// Returns a uniformly distributed number in `[0, modulus)`.
+    ///
+    /// Draws from the biased tail of the 64-bit range are rejected, so every
+    /// residue is equally likely.
     ///
     /// # Panics
     ///
     /// Panics if `modulus` is zero.
     pub fn gen_mod(&mut self, modulus: u64) -> u64 {
-        assert!(modulus != 0, "modulus must be non-zero");
-        self.next_u64() % modulus
+        sample_mod(modulus, || self.next_u64())
+    }
+}
+
+/// A deterministic ChaCha20 stream generator.
+#[derive(Debug, Clone)]
+pub struct ChaChaPrng {
+    rng: ChaCha20Rng,
+}
+
+impl ChaChaPrng {
+    /// Creates a new PRNG seeded with `seed`.
+    pub fn new(seed: u64) -> Self {
+        let mut hasher = Blake2b256::new();
+        hasher.update(CHACHA_DOMAIN);
+        hasher.update(seed.to_be_bytes());
+        let mut base = [0u8; 32];
+        base.copy_from_slice(&hasher.finalize());
+        Self::from_seed_bytes(base)
+    }
+
+    /// Creates a PRNG from a raw 32-byte seed.
+    pub fn from_seed_bytes(seed: [u8; 32]) -> Self {
+        Self {
+            rng: ChaCha20Rng::from_seed(seed),
+        }
+    }
+
+    /// Advances the generator and returns the next 64-bit pseudorandom number.
+    pub fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    /// Returns a uniformly distributed number in `[0, modulus)`.
+    ///
+    /// Draws from the biased tail of the 64-bit range are rejected, so every
+    /// residue is equally likely.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is zero.
+    pub fn gen_mod(&mut self, modulus: u64) -> u64 {
+        sample_mod(modulus, || self.next_u64())
+    }
+}
+
+/// Rejection-samples `next` into `[0, modulus)`.
+fn sample_mod(modulus: u64, mut next: impl FnMut() -> u64) -> u64 {
+    assert!(modulus != 0, "modulus must be non-zero");
+    // Largest accepted draw: 2^64 - (2^64 mod modulus) - 1.
+    let zone = u64::MAX - (u64::MAX % modulus + 1) % modulus;
+    loop {
+        let draw = next();
+        if draw <= zone {
+            return draw % modulus;
+        }
     }
 }
 
@@ -86,22 +181,107 @@ impl SimplePrng {
 /// Given a prime modulus `p`, a domain tag (used to separate different
 /// derivation contexts) and a slice of `u64` words representing the
 /// transcript, this function returns `count` field elements in `[0,p)`.
+/// Equivalent to [`derive_many_mod_p_with`] with the default generator.
 pub fn derive_many_mod_p(p: u64, domain_tag: &[u8], transcript: &[u64], count: usize) -> Vec<u64> {
+    derive_many_mod_p_with(PrngBackend::default(), p, domain_tag, transcript, count)
+}
+
+/// Derives field elements from a transcript with the selected generator.
+///
+/// Given a generator, a prime modulus `p`, a domain tag (used to separate
+/// different derivation contexts) and a slice of `u64` words representing the
+/// transcript, this function returns `count` field elements in `[0,p)`.
+/// Every element is rejection-sampled, so no residue is favoured.  Backends
+/// other than [`PrngBackend::Blake2b`] also bind their id into the seed.
+///
+/// Crate code derives challenges through [`crate::Transcript`] rather than
+/// calling this directly.
+pub fn derive_many_mod_p_with(
+    backend: PrngBackend,
+    p: u64,
+    domain_tag: &[u8],
+    transcript: &[u64],
+    count: usize,
+) -> Vec<u64> {
     assert!(p != 0, "modulus must be non-zero");
+    let seed_bytes = challenge_seed(backend, domain_tag, transcript);
+    match backend {
+        PrngBackend::Blake2b => {
+            let mut prng = SimplePrng::from_seed_bytes(seed_bytes);
+            (0..count).map(|_| prng.gen_mod(p)).collect()
+        }
+        PrngBackend::ChaCha20 => {
+            let mut prng = ChaChaPrng::from_seed_bytes(seed_bytes);
+            (0..count).map(|_| prng.gen_mod(p)).collect()
+        }
+    }
+}
+
+/// Fills `out` with pseudorandom bytes derived from a transcript.
+pub(crate) fn derive_bytes_with(
+    backend: PrngBackend,
+    domain_tag: &[u8],
+    transcript: &[u64],
+    out: &mut [u8],
+) {
+    let seed_bytes = challenge_seed(backend, domain_tag, transcript);
+    let mut next: Box<dyn FnMut() -> u64> = match backend {
+        PrngBackend::Blake2b => {
+            let mut prng = SimplePrng::from_seed_bytes(seed_bytes);
+            Box::new(move || prng.next_u64())
+        }
+        PrngBackend::ChaCha20 => {
+            let mut prng = ChaChaPrng::from_seed_bytes(seed_bytes);
+            Box::new(move || prng.next_u64())
+        }
+    };
+    for chunk in out.chunks_mut(8) {
+        chunk.copy_from_slice(&next().to_be_bytes()[..chunk.len()]);
+    }
+}
+
+fn challenge_seed(backend: PrngBackend, domain_tag: &[u8], transcript: &[u64]) -> [u8; 32] {
     let mut seed_hasher = Blake2b256::new();
     seed_hasher.update(CHALLENGE_DOMAIN);
+    if backend != PrngBackend::Blake2b {
+        let id = backend.id().as_bytes();
+        seed_hasher.update((id.len() as u64).to_be_bytes());
+        seed_hasher.update(id);
+    }
     seed_hasher.update((domain_tag.len() as u64).to_be_bytes());
     seed_hasher.update(domain_tag);
     seed_hasher.update((transcript.len() as u64).to_be_bytes());
     for &word in transcript {
         seed_hasher.update(word.to_be_bytes());
     }
-    let mut seed_bytes = [0u8; 32];
-    seed_bytes.copy_from_slice(&seed_hasher.finalize());
-    let mut prng = SimplePrng::from_seed_bytes(seed_bytes);
-    let mut out = Vec::with_capacity(count);
-    for _ in 0..count {
-        out.push(prng.gen_mod(p));
+    seed_hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backends_are_domain_separated_and_in_range() {
+        let transcript = [7u64, 11, 13];
+        let p = (1u64 << 63) + 29;
+        let blake = derive_many_mod_p_with(PrngBackend::Blake2b, p, b"test", &transcript, 4);
+        let chacha = derive_many_mod_p_with(PrngBackend::ChaCha20, p, b"test", &transcript, 4);
+        assert_ne!(blake, chacha);
+        assert_eq!(blake, derive_many_mod_p(p, b"test", &transcript, 4));
+        assert!(blake.iter().all(|&value| value < p));
+        assert!(chacha.iter().all(|&value| value < p));
+        assert_eq!(
+            chacha,
+            derive_many_mod_p_with(PrngBackend::ChaCha20, p, b"test", &transcript, 4)
+        );
+
+        // Half of all draws fall in the rejected tail for this modulus, yet
+        // the sampler still terminates and stays uniform over [0, p).
+        let mut prng = ChaChaPrng::new(42);
+        assert!((0..1000).all(|_| prng.gen_mod(p) < p));
+        for backend in [PrngBackend::Blake2b, PrngBackend::ChaCha20] {
+            assert_eq!(PrngBackend::from_id(backend.id()), Some(backend));
+        }
     }
-    out
 }
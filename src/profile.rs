@@ -0,0 +1,238 @@
+//! Timing spans for hot paths, off unless enabled.
+//!
+//! [`span`] returns a guard that measures until it is dropped.  Spans opened
+//! while another is live on the same thread nest under it, so the collected
+//! paths (`load_anchor_from_logs;GeneralSumProof::verify`) are the folded
+//! stacks flamegraph tools read.  [`detached_span`] measures work that crosses
+//! `.await` points, where the thread can change, and always starts its own
+//! stack.
+//!
+//! Nothing is measured until [`enable`] is called.  Afterwards every span is
+//! accumulated for [`summary`], and each outermost span is also logged as a
+//! `QSYS|mod=PROFILE|evt=SPAN|span=<path>|elapsed_us=<n>` line.
+
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    collections::BTreeMap,
+    fmt::Write as _,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, PoisonError,
+    },
+    time::{Duration, Instant},
+};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static TOTALS: Mutex<BTreeMap<String, SpanStats>> = Mutex::new(BTreeMap::new());
+
+thread_local! {
+    static STACK: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Starts collecting spans for the rest of the process.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Whether spans are being collected.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Accumulated timings of one span path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanStats {
+    /// `;`-separated names from the outermost span to this one.
+    pub path: String,
+    /// Completed spans.
+    pub calls: u64,
+    /// Time spent inside the span, children included.
+    pub total: Duration,
+    /// Longest single span.
+    pub max: Duration,
+}
+
+/// Guard returned by [`span`] and [`detached_span`].
+#[must_use = "a span measures until the guard is dropped"]
+pub struct Span {
+    active: Option<ActiveSpan>,
+}
+
+struct ActiveSpan {
+    path: String,
+    start: Instant,
+    depth: Option<usize>,
+}
+
+/// Opens a span named `name` nested under the spans live on this thread.
+pub fn span(name: &'static str) -> Span {
+    if !is_enabled() {
+        return Span { active: None };
+    }
+    let (path, depth) = STACK.with(|stack| {
+        let mut stack = stack.borrow_mut();
+        stack.push(name);
+        (stack.join(";"), stack.len())
+    });
+    Span {
+        active: Some(ActiveSpan {
+            path,
+            start: Instant::now(),
+            depth: Some(depth),
+        }),
+    }
+}
+
+/// Opens an outermost span that does not nest, for async work.
+pub fn detached_span(name: impl Into<Cow<'static, str>>) -> Span {
+    if !is_enabled() {
+        return Span { active: None };
+    }
+    Span {
+        active: Some(ActiveSpan {
+            path: name.into().into_owned(),
+            start: Instant::now(),
+            depth: None,
+        }),
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let Some(active) = self.active.take() else {
+            return;
+        };
+        let elapsed = active.start.elapsed();
+        if let Some(depth) = active.depth {
+            STACK.with(|stack| stack.borrow_mut().truncate(depth - 1));
+        }
+        if active.depth.is_none_or(|depth| depth == 1) {
+            println!(
+                "QSYS|mod=PROFILE|evt=SPAN|span={}|elapsed_us={}",
+                active.path,
+                elapsed.as_micros()
+            );
+        }
+        let mut totals = TOTALS.lock().unwrap_or_else(PoisonError::into_inner);
+        let stats = totals
+            .entry(active.path.clone())
+            .or_insert_with(|| SpanStats {
+                path: active.path,
+                calls: 0,
+                total: Duration::ZERO,
+                max: Duration::ZERO,
+            });
+        stats.calls += 1;
+        stats.total += elapsed;
+        stats.max = stats.max.max(elapsed);
+    }
+}
+
+/// Timings collected so far, in path order.
+pub fn summary() -> Vec<SpanStats> {
+    TOTALS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .values()
+        .cloned()
+        .collect()
+}
+
+/// Renders `stats` as an aligned table of calls, total, mean, and max.
+pub fn render_table(stats: &[SpanStats]) -> String {
+    let width = stats
+        .iter()
+        .map(|stats| stats.path.len())
+        .max()
+        .unwrap_or(0)
+        .max("span".len());
+    let mut out = format!(
+        "{:<width$} {:>8} {:>12} {:>12} {:>12}\n",
+        "span", "calls", "total_ms", "mean_ms", "max_ms"
+    );
+    for stats in stats {
+        let millis = |duration: Duration| duration.as_secs_f64() * 1000.0;
+        let _ = writeln!(
+            out,
+            "{:<width$} {:>8} {:>12.3} {:>12.3} {:>12.3}",
+            stats.path,
+            stats.calls,
+            millis(stats.total),
+            millis(stats.total) / stats.calls.max(1) as f64,
+            millis(stats.max)
+        );
+    }
+    out
+}
+
+/// Renders `stats` as folded stacks weighted by self time in microseconds,
+/// the input format of `flamegraph.pl` and `inferno-flamegraph`.
+pub fn render_folded(stats: &[SpanStats]) -> String {
+    let mut out = String::new();
+    for stats_entry in stats {
+        let children: Duration = stats
+            .iter()
+            .filter(|child| {
+                child
+                    .path
+                    .strip_prefix(stats_entry.path.as_str())
+                    .and_then(|rest| rest.strip_prefix(';'))
+                    .is_some_and(|rest| !rest.contains(';'))
+            })
+            .map(|child| child.total)
+            .sum();
+        let own = stats_entry.total.saturating_sub(children).as_micros();
+        if own > 0 {
+            let _ = writeln!(out, "{} {own}", stats_entry.path);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_spans_fold_into_stacks() {
+        enable();
+        for _ in 0..2 {
+            let _outer = span("profile_test_outer");
+            std::thread::sleep(Duration::from_millis(2));
+            let _inner = span("profile_test_inner");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        drop(detached_span(format!("profile_test_{}", "rpc")));
+
+        let stats: Vec<_> = summary()
+            .into_iter()
+            .filter(|stats| stats.path.starts_with("profile_test_"))
+            .collect();
+        let paths: Vec<_> = stats.iter().map(|stats| stats.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "profile_test_outer",
+                "profile_test_outer;profile_test_inner",
+                "profile_test_rpc"
+            ]
+        );
+        assert_eq!(stats[0].calls, 2);
+        assert!(stats[0].total >= stats[1].total + Duration::from_millis(4));
+        assert!(stats[0].max <= stats[0].total);
+
+        let folded = render_folded(&stats[..2]);
+        let own: u128 = folded
+            .lines()
+            .next()
+            .unwrap()
+            .split(' ')
+            .nth(1)
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(own, (stats[0].total - stats[1].total).as_micros());
+        assert!(render_table(&stats).starts_with("span"));
+    }
+}
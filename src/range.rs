@@ -0,0 +1,236 @@
+//! Transparent range checks by bit decomposition.
+//!
+//! [`RangeProof`] shows that every value in a public list lies in
+//! `[0, 2^bits)`.  The prover decomposes each value `v_i` into bits
+//! `b_{i,k}` and the two constraints of a valid decomposition,
+//!
+//! ```text
+//! b_{i,k}·(1 - b_{i,k}) = 0            (booleanity)
+//! Σ_k 2^k·b_{i,k}       = v_i          (recomposition)
+//! ```
+//!
+//! are folded into one multilinear table with Fiat–Shamir weights `α` and
+//! `β` bound to the values and the decomposition:
+//!
+//! ```text
+//! w(i, k) = α^{cell+1}·b_{i,k}·(1 - b_{i,k}) + β^{i+1}·2^k·b_{i,k}
+//! ```
+//!
+//! Its hypercube sum equals `Σ_i β^{i+1}·v_i` exactly when both constraints
+//! hold (up to the usual `O(n·bits/p)` Schwartz–Zippel error), and a single
+//! [`GeneralSumProof`] attests to that sum.  The ledger accepts range proofs
+//! under [`ProofKind::Range`](crate::ProofKind::Range).
+//!
+//! The values and their decomposition travel in the clear and are not
+//! committed: the verifier rebuilds the whole constraint table from them, so
+//! a range proof hides nothing and is no cheaper to check than the
+//! decomposition itself.  The sum-check only gives the check a ledger
+//! transcript.
+
+use crate::sumcheck::GeneralSumTrace;
+use crate::{Field, GeneralSumProof, MultilinearPolynomial, Transcript};
+use std::convert::Infallible;
+use std::fmt;
+
+const RANGE_DOMAIN: &[u8] = b"power_house:v1:range-check";
+
+/// Errors returned while building a range proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RangeProofError {
+    /// No values were supplied.
+    Empty,
+    /// `bits` is zero or `2^bits` exceeds the field modulus.
+    InvalidBits(usize),
+    /// The value at this index does not fit in `bits` bits.
+    OutOfRange(usize),
+}
+
+impl fmt::Display for RangeProofError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(formatter, "no values to range-check"),
+            Self::InvalidBits(bits) => write!(formatter, "unsupported bit width {bits}"),
+            Self::OutOfRange(index) => write!(formatter, "value {index} is out of range"),
+        }
+    }
+}
+
+impl std::error::Error for RangeProofError {}
+
+/// Transparent check that every public value fits in `bits` bits.
+#[derive(Debug, Clone)]
+pub struct RangeProof {
+    /// Values being range-checked.
+    pub values: Vec<u64>,
+    /// Bit width of the range `[0, 2^bits)`.
+    pub bits: usize,
+    /// Public bit decomposition, `bits` entries per value, least significant
+    /// first.
+    pub decomposition: Vec<u64>,
+    /// Sum-check over the combined constraint table.
+    pub proof: GeneralSumProof,
+}
+
+impl RangeProof {
+    /// Proves that every value lies in `[0, 2^bits)`.
+    pub fn prove(values: &[u64], bits: usize, field: &Field) -> Result<Self, RangeProofError> {
+        if values.is_empty() {
+            return Err(RangeProofError::Empty);
+        }
+        if !supported_bits(bits, field) {
+            return Err(RangeProofError::InvalidBits(bits));
+        }
+        if let Some(index) = values.iter().position(|&v| v >> bits != 0) {
+            return Err(RangeProofError::OutOfRange(index));
+        }
+        let decomposition: Vec<u64> = values
+            .iter()
+            .flat_map(|&v| (0..bits).map(move |k| (v >> k) & 1))
+            .collect();
+        let table = constraint_table(values, bits, &decomposition, field)
+            .expect("well-formed decomposition");
+        Ok(Self {
+            values: values.to_vec(),
+            bits,
+            decomposition,
+            proof: GeneralSumProof::prove(&table, field),
+        })
+    }
+
+    /// Combined constraint table, or `None` if the proof is malformed.
+    pub fn polynomial(&self, field: &Field) -> Option<MultilinearPolynomial> {
+        constraint_table(&self.values, self.bits, &self.decomposition, field)
+    }
+
+    /// Sum the constraint table must reach: `Σ_i β^{i+1}·v_i`.
+    fn expected_sum(&self, field: &Field) -> u64 {
+        let (_, beta) = challenges(&self.values, self.bits, &self.decomposition, field);
+        let mut weight = beta;
+        let mut sum = 0;
+        for &value in &self.values {
+            sum = field.add(sum, field.mul(weight, value % field.modulus()));
+            weight = field.mul(weight, beta);
+        }
+        sum
+    }
+
+    /// Verifies the proof and returns the sum-check trace on success.
+    pub fn verify_with_trace(&self, field: &Field) -> Option<GeneralSumTrace> {
+        match self.verify_with_trace_checked(field, |_| Ok::<(), Infallible>(())) {
+            Ok(trace) => trace,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Like [`Self::verify_with_trace`], but calls `checkpoint` with the round
+    /// index before each sum-check round and stops with its error.
+    pub fn verify_with_trace_checked<E>(
+        &self,
+        field: &Field,
+        checkpoint: impl FnMut(usize) -> Result<(), E>,
+    ) -> Result<Option<GeneralSumTrace>, E> {
+        if self.proof.claim.p != field.modulus() {
+            return Ok(None);
+        }
+        if self.values.iter().any(|&v| v >= field.modulus()) {
+            return Ok(None);
+        }
+        let Some(table) = self.polynomial(field) else {
+            return Ok(None);
+        };
+        if self.proof.claim.claimed_sum != self.expected_sum(field) {
+            return Ok(None);
+        }
+        self.proof
+            .verify_with_trace_checked(&table, field, None, checkpoint)
+    }
+
+    /// Returns true when [`Self::verify_with_trace`] succeeds.
+    pub fn verify(&self, field: &Field) -> bool {
+        self.verify_with_trace(field).is_some()
+    }
+}
+
+/// Fiat–Shamir weights `(α, β)` bound to the values and decomposition.
+fn challenges(values: &[u64], bits: usize, decomposition: &[u64], field: &Field) -> (u64, u64) {
+    let mut transcript = Transcript::new(RANGE_DOMAIN);
+    transcript.append(field.modulus());
+    transcript.append(bits as u64);
+    transcript.append(values.len() as u64);
+    transcript.append_slice(values);
+    transcript.append_slice(decomposition);
+    (transcript.challenge(field), transcript.challenge(field))
+}
+
+fn constraint_table(
+    values: &[u64],
+    bits: usize,
+    decomposition: &[u64],
+    field: &Field,
+) -> Option<MultilinearPolynomial> {
+    if values.is_empty()
+        || !supported_bits(bits, field)
+        || decomposition.len() != values.len() * bits
+    {
+        return None;
+    }
+    let (alpha, beta) = challenges(values, bits, decomposition, field);
+    let width = bits.next_power_of_two();
+    let rows = values.len().next_power_of_two();
+    let mut table = vec![0u64; (width * rows).max(2)];
+    let mut cell_weight = alpha;
+    let mut row_weight = beta;
+    for (i, row) in decomposition.chunks_exact(bits).enumerate() {
+        for (k, &bit) in row.iter().enumerate() {
+            let bit = bit % field.modulus();
+            let booleanity = field.mul(bit, field.sub(1, bit));
+            let recomposition = field.mul(field.mul(row_weight, 1u64 << k), bit);
+            table[i * width + k] = field.add(field.mul(cell_weight, booleanity), recomposition);
+            cell_weight = field.mul(cell_weight, alpha);
+        }
+        row_weight = field.mul(row_weight, beta);
+    }
+    let num_vars = table.len().trailing_zeros() as usize;
+    Some(MultilinearPolynomial::from_evaluations(num_vars, table))
+}
+
+fn supported_bits(bits: usize, field: &Field) -> bool {
+    bits > 0 && bits < 64 && (1u64 << bits) <= field.modulus()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn range_proof_accepts_small_values_and_rejects_forgeries() {
+        let field = Field::new(1_000_000_007);
+        let proof = RangeProof::prove(&[0, 5, 255, 17, 128], 8, &field).unwrap();
+        assert!(proof.verify(&field));
+
+        assert_eq!(
+            RangeProof::prove(&[3, 256], 8, &field).unwrap_err(),
+            RangeProofError::OutOfRange(1)
+        );
+        assert_eq!(
+            RangeProof::prove(&[1], 40, &field).unwrap_err(),
+            RangeProofError::InvalidBits(40)
+        );
+
+        // 256 = 2·128 smuggled in as a non-boolean top "bit".
+        let mut forged = RangeProof::prove(&[128], 8, &field).unwrap();
+        forged.values = vec![256];
+        forged.decomposition[7] = 2;
+        let table = forged.polynomial(&field).unwrap();
+        forged.proof = GeneralSumProof::prove(&table, &field);
+        assert!(!forged.verify(&field));
+
+        let mut relabelled = proof.clone();
+        relabelled.values[1] = 6;
+        assert!(!relabelled.verify(&field));
+
+        let mut truncated = proof;
+        truncated.decomposition.pop();
+        assert!(!truncated.verify(&field));
+    }
+}
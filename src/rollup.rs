@@ -1,25 +1,33 @@
 //! Rollup integration with Groth16 verification.
-//! Circuit: next = prev + tx_root (Fr) plus Pedersen Merkle inclusion of tx_root into pedersen_root bytes (public).
+//! Circuit: next = prev + tx_root (Fr) plus Merkle inclusion of tx_root into the public path
+//! root, hashed with the commitment's [`RollupPathHash`] (Pedersen or Poseidon).
+//! Batch circuit: a fixed-size batch of transfers moves the Poseidon account-state root from
+//! old_root to new_root, committed by a public batch_root.
+//! Both circuits prove and verify under keys from a one-off setup ([`rollup_transfer_setup`],
+//! [`rollup_batch_setup`]).
 
 use ark_bn254::{Bn254, Fr};
 use ark_crypto_primitives::crh::pedersen::constraints::{
-    CRHGadget, CRHParametersVar, TwoToOneCRHGadget,
+    CRHGadget as PedersenGadget, CRHParametersVar as PedersenParamsVar,
 };
-use ark_crypto_primitives::crh::{pedersen, CRHScheme, CRHSchemeGadget, TwoToOneCRHSchemeGadget};
+use ark_crypto_primitives::crh::poseidon::constraints::{CRHGadget, CRHParametersVar};
+use ark_crypto_primitives::crh::{pedersen, poseidon, CRHScheme, CRHSchemeGadget};
+use ark_crypto_primitives::sponge::poseidon::{find_poseidon_ark_and_mds, PoseidonConfig};
 use ark_ed_on_bn254::{constraints::EdwardsVar as PedersenVar, EdwardsProjective as PedersenCurve};
-use ark_ff::PrimeField;
-use ark_groth16::{Groth16, Proof};
+use ark_ff::{BigInteger, Field, PrimeField};
+use ark_groth16::{prepare_verifying_key, Groth16, Proof, ProvingKey, VerifyingKey};
 use ark_r1cs_std::{
-    alloc::AllocVar, boolean::Boolean, eq::EqGadget, fields::fp::FpVar, uint8::UInt8, ToBitsGadget,
-    ToBytesGadget,
+    alloc::AllocVar, boolean::Boolean, eq::EqGadget, fields::fp::FpVar, fields::FieldVar,
+    select::CondSelectGadget, uint8::UInt8, ToBitsGadget,
 };
 use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
 use rand::rngs::StdRng;
-use rand::SeedableRng;
+use rand::{CryptoRng, RngCore, SeedableRng};
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
 use std::io::Cursor;
+use std::path::Path;
+use std::sync::OnceLock;
 
 #[derive(Clone)]
 struct PedersenWindow;
@@ -81,17 +89,204 @@ fn pedersen_root_from_path(leaf: &[u8], path: &[(bool, [u8; 32])]) -> Result<Vec
     Ok(current.to_vec())
 }
 
-/// Internal circuit enforcing the simple state transition and Pedersen Merkle inclusion.
+/// Poseidon over BN254 with width 3 (rate 2), alpha 5, and 8 full / 57 partial
+/// rounds; round constants and MDS come from the reference Grain LFSR.
+fn poseidon_config() -> &'static PoseidonConfig<Fr> {
+    static CONFIG: OnceLock<PoseidonConfig<Fr>> = OnceLock::new();
+    CONFIG.get_or_init(|| {
+        let (ark, mds) = find_poseidon_ark_and_mds::<Fr>(254, 2, 8, 57, 0);
+        PoseidonConfig::new(8, 57, 5, mds, ark, 2, 1)
+    })
+}
+
+fn poseidon_hash(input: &[Fr]) -> Fr {
+    poseidon::CRH::<Fr>::evaluate(poseidon_config(), input).expect("poseidon eval")
+}
+
+fn poseidon_leaf(leaf: Fr) -> Fr {
+    poseidon_hash(&[Fr::from(0u8), leaf])
+}
+
+fn poseidon_hash_pair(left: Fr, right: Fr) -> Fr {
+    poseidon_hash(&[Fr::from(1u8), left, right])
+}
+
+fn fr_to_bytes(value: Fr) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&value.into_bigint().to_bytes_le());
+    out
+}
+
+fn poseidon_root_from_path(leaf: &[u8], path: &[(bool, [u8; 32])]) -> Result<Vec<u8>, String> {
+    if leaf.len() != 32 {
+        return Err("leaf must be 32 bytes".into());
+    }
+    let mut current = poseidon_leaf(Fr::from_le_bytes_mod_order(leaf));
+    for (left, sib_arr) in path {
+        let sibling = Fr::from_le_bytes_mod_order(sib_arr);
+        current = if *left {
+            poseidon_hash_pair(sibling, current)
+        } else {
+            poseidon_hash_pair(current, sibling)
+        };
+    }
+    Ok(fr_to_bytes(current).to_vec())
+}
+
+/// Poseidon Merkle root over 32-byte leaves, shaped like the Pedersen share
+/// tree: an odd node is carried up unchanged.
+pub fn poseidon_merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    let mut level: Vec<Fr> = leaves
+        .iter()
+        .map(|leaf| poseidon_leaf(Fr::from_le_bytes_mod_order(leaf)))
+        .collect();
+    if level.is_empty() {
+        return fr_to_bytes(poseidon_leaf(Fr::from(0u8)));
+    }
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => poseidon_hash_pair(*left, *right),
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+    fr_to_bytes(level[0])
+}
+
+/// Poseidon Merkle path of `leaves[index]` up to [`poseidon_merkle_root`].
+pub fn poseidon_merkle_path(
+    leaves: &[[u8; 32]],
+    index: usize,
+) -> Result<Vec<MerkleSibling>, String> {
+    if index >= leaves.len() {
+        return Err("invalid index".into());
+    }
+    let mut level: Vec<Fr> = leaves
+        .iter()
+        .map(|leaf| poseidon_leaf(Fr::from_le_bytes_mod_order(leaf)))
+        .collect();
+    let mut i = index;
+    let mut path = Vec::new();
+    while level.len() > 1 {
+        let sibling = if i % 2 == 1 {
+            Some((true, level[i - 1]))
+        } else {
+            level.get(i + 1).map(|sib| (false, *sib))
+        };
+        if let Some((left, sib)) = sibling {
+            path.push(MerkleSibling {
+                left,
+                hash: hex::encode(fr_to_bytes(sib)),
+            });
+        }
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => poseidon_hash_pair(*left, *right),
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+        i /= 2;
+    }
+    Ok(path)
+}
+
+/// Merkle path witness of a [`RollupCircuit`], in the commitment's path hash.
+#[derive(Clone)]
+enum CircuitPath {
+    /// Leaf bytes of `tx_root` and the byte siblings up to the Pedersen root.
+    Pedersen {
+        leaf: [u8; 32],
+        path: Vec<(bool, [u8; 32])>,
+    },
+    /// Field siblings up to the Poseidon root.
+    Poseidon(Vec<(bool, Fr)>),
+}
+
+/// Internal circuit enforcing the state transition and Merkle inclusion of
+/// `tx_root` under the public share root.
 #[derive(Clone)]
 struct RollupCircuit {
     prev: Fr,
     next: Fr,
     tx_root: Fr,
     share_root: Fr,
-    share_root_bytes: [u8; 32],
-    tx_bytes: [u8; 32],
-    path: Vec<(bool, [u8; 32])>,
-    pedersen_params: pedersen::Parameters<PedersenCurve>,
+    path: CircuitPath,
+}
+
+impl CircuitPath {
+    fn len(&self) -> usize {
+        match self {
+            Self::Pedersen { path, .. } => path.len(),
+            Self::Poseidon(path) => path.len(),
+        }
+    }
+}
+
+impl RollupCircuit {
+    /// Circuit of the right shape for key generation; its values are unused.
+    fn blank(path_hash: RollupPathHash, depth: usize) -> Self {
+        let zero = Fr::from(0u8);
+        Self {
+            prev: zero,
+            next: zero,
+            tx_root: zero,
+            share_root: zero,
+            path: match path_hash {
+                RollupPathHash::Pedersen => CircuitPath::Pedersen {
+                    leaf: [0u8; 32],
+                    path: vec![(false, [0u8; 32]); depth],
+                },
+                RollupPathHash::Poseidon => CircuitPath::Poseidon(vec![(false, zero); depth]),
+            },
+        }
+    }
+}
+
+/// Bits of `bytes`, least significant first.
+fn byte_bits(bytes: &[UInt8<Fr>]) -> Result<Vec<Boolean<Fr>>, SynthesisError> {
+    let mut bits = Vec::with_capacity(bytes.len() * 8);
+    for byte in bytes {
+        bits.extend(byte.to_bits_le()?);
+    }
+    Ok(bits)
+}
+
+/// Little-endian `bits` reduced mod p, matching `Fr::from_le_bytes_mod_order`;
+/// unlike `Boolean::le_bits_to_fp_var` it accepts values of p or more.
+fn bits_to_fr_mod_order(bits: &[Boolean<Fr>]) -> FpVar<Fr> {
+    let mut power = Fr::from(1u8);
+    let mut value = FpVar::zero();
+    for bit in bits {
+        value += FpVar::from(bit.clone()) * power;
+        power.double_in_place();
+    }
+    value
+}
+
+/// In-circuit [`pedersen_hash_bytes`] over `domain || data`, returned as the
+/// 256 bits of the compressed point: `y` little-endian with the sign of `x`
+/// in the top bit.
+fn pedersen_hash_var(
+    params: &PedersenParamsVar<PedersenCurve, PedersenVar>,
+    domain: u8,
+    data: &[Boolean<Fr>],
+) -> Result<Vec<Boolean<Fr>>, SynthesisError> {
+    let mut input = vec![UInt8::constant(domain)];
+    input.extend(data.chunks(8).map(UInt8::from_bits_le));
+    let point =
+        PedersenGadget::<PedersenCurve, PedersenVar, PedersenWindow>::evaluate(params, &input)?;
+    let mut bits = point.y.to_bits_le()?;
+    bits.resize(255, Boolean::FALSE);
+    // x is "negative" when x > (p - 1) / 2, which is exactly when the
+    // canonical 2x wraps past p and comes out odd.
+    let x_negative = point.x.double()?.to_bits_le()?[0].clone();
+    bits.push(x_negative);
+    Ok(bits)
 }
 
 impl ConstraintSynthesizer<Fr> for RollupCircuit {
@@ -99,62 +294,78 @@ impl ConstraintSynthesizer<Fr> for RollupCircuit {
         let prev = FpVar::new_input(cs.clone(), || Ok(self.prev))?;
         let next = FpVar::new_input(cs.clone(), || Ok(self.next))?;
         let tx = FpVar::new_input(cs.clone(), || Ok(self.tx_root))?;
-        let _share = FpVar::new_input(cs.clone(), || Ok(self.share_root))?;
+        let share = FpVar::new_input(cs.clone(), || Ok(self.share_root))?;
 
         let sum = &prev + &tx;
         sum.enforce_equal(&next)?;
 
-        // Pedersen Merkle inclusion of tx_root into share_root_bytes.
-        let params_const = CRHParametersVar::<PedersenCurve, PedersenVar>::new_constant(
-            cs.clone(),
-            &self.pedersen_params,
-        )?;
-        let two_params_const = CRHParametersVar::<PedersenCurve, PedersenVar>::new_constant(
-            cs.clone(),
-            &self.pedersen_params,
-        )?;
-
-        let tx_bytes_var = UInt8::new_input_vec(cs.clone(), &self.tx_bytes)?;
-        let mut current = CRHGadget::<PedersenCurve, PedersenVar, PedersenWindow>::evaluate(
-            &params_const,
-            &tx_bytes_var,
-        )?;
-
-        for (left, sib) in self.path {
-            let sib_bytes = UInt8::new_witness_vec(cs.clone(), &sib)?;
-            let sib_hash = CRHGadget::<PedersenCurve, PedersenVar, PedersenWindow>::evaluate(
-                &params_const,
-                &sib_bytes,
-            )?;
-            let hashed = TwoToOneCRHGadget::<PedersenCurve, PedersenVar, PedersenWindow>::compress(
-                &two_params_const,
-                if left { &sib_hash } else { &current },
-                if left { &current } else { &sib_hash },
-            )?;
-            current = hashed;
+        let path = match self.path {
+            CircuitPath::Poseidon(path) => path,
+            CircuitPath::Pedersen { leaf, path } => {
+                let params = PedersenParamsVar::new_constant(cs.clone(), pedersen_params())?;
+                let leaf = UInt8::new_witness_vec(cs.clone(), &leaf)?;
+                let leaf_bits = byte_bits(&leaf)?;
+                bits_to_fr_mod_order(&leaf_bits).enforce_equal(&tx)?;
+                let mut current = pedersen_hash_var(&params, 0, &leaf_bits)?;
+                for (left, sibling) in path {
+                    let sibling = UInt8::new_witness_vec(cs.clone(), &sibling)?;
+                    let sibling = byte_bits(&sibling)?;
+                    let left = Boolean::new_witness(cs.clone(), || Ok(left))?;
+                    let mut pair = Vec::with_capacity(512);
+                    for (cur, sib) in current.iter().zip(&sibling) {
+                        pair.push(Boolean::conditionally_select(&left, sib, cur)?);
+                    }
+                    for (cur, sib) in current.iter().zip(&sibling) {
+                        pair.push(Boolean::conditionally_select(&left, cur, sib)?);
+                    }
+                    current = pedersen_hash_var(&params, 1, &pair)?;
+                }
+                bits_to_fr_mod_order(&current).enforce_equal(&share)?;
+                return Ok(());
+            }
+        };
+        let params = CRHParametersVar::new_constant(cs.clone(), poseidon_config())?;
+        let mut current =
+            CRHGadget::<Fr>::evaluate(&params, &[FpVar::Constant(Fr::from(0u8)), tx])?;
+        for (left, sibling) in path {
+            let sibling = FpVar::new_witness(cs.clone(), || Ok(sibling))?;
+            let left = Boolean::new_witness(cs.clone(), || Ok(left))?;
+            let lhs = FpVar::conditionally_select(&left, &sibling, &current)?;
+            let rhs = FpVar::conditionally_select(&left, &current, &sibling)?;
+            current =
+                CRHGadget::<Fr>::evaluate(&params, &[FpVar::Constant(Fr::from(1u8)), lhs, rhs])?;
         }
-
-        let share_bytes = UInt8::new_input_vec(cs, &self.share_root_bytes)?;
-        let share_bits: Vec<Boolean<Fr>> = share_bytes
-            .iter()
-            .map(|b| b.to_bits_le())
-            .collect::<Result<Vec<_>, _>>()?
-            .into_iter()
-            .flatten()
-            .collect();
-        let current_bytes = current
-            .to_bytes()?
-            .iter()
-            .map(|b| b.to_bits_le())
-            .collect::<Result<Vec<_>, _>>()?
-            .into_iter()
-            .flatten()
-            .collect::<Vec<_>>();
-        current_bytes.enforce_equal(&share_bits)?;
+        current.enforce_equal(&share)?;
         Ok(())
     }
 }
 
+/// Hash of the Merkle path from `tx_root` to a commitment's path root.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RollupPathHash {
+    /// Pedersen CRH over bytes, the DA blob's `pedersen_root`.
+    #[default]
+    Pedersen,
+    /// Poseidon over BN254 field elements, far cheaper in circuit.
+    Poseidon,
+}
+
+impl RollupPathHash {
+    /// Name used in JSON and error messages.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pedersen => "pedersen",
+            Self::Poseidon => "poseidon",
+        }
+    }
+
+    /// Whether this is the default Pedersen path hash.
+    pub fn is_pedersen(&self) -> bool {
+        *self == Self::Pedersen
+    }
+}
+
 /// Commitment linking a rollup batch to a DA blob.
 #[derive(Debug, Clone)]
 pub struct RollupCommitment {
@@ -162,10 +373,12 @@ pub struct RollupCommitment {
     pub namespace: String,
     /// Hex-encoded share root of the DA blob.
     pub share_root: String,
-    /// Optional Pedersen share root of the DA blob.
+    /// Optional ZK path root over the blob's share hashes, built with `path_hash`.
     pub pedersen_root: Option<String>,
     /// Optional L1 settlement identifier.
     pub settlement_slot: Option<String>,
+    /// Hash of the Merkle path under `pedersen_root`.
+    pub path_hash: RollupPathHash,
 }
 
 /// Merkle path element (hex-encoded sibling) for out-of-circuit verification.
@@ -182,12 +395,21 @@ pub struct MerkleSibling {
 pub struct ZkRollupProof {
     /// Serialized Groth16 proof bytes.
     pub proof: Vec<u8>,
-    /// Public inputs: prev||next||tx_root||share_root (4 x 32 bytes LE).
+    /// Public inputs: prev||next||tx_root||share_root (4 x 32 bytes LE), or
+    /// old_root||new_root||batch_root (3 x 32 bytes LE) for a batch proof.
     pub public_inputs: Vec<u8>,
     /// Merkle path siblings (JSON-serialized `Vec<MerkleSibling>`).
     pub merkle_path: Vec<u8>,
 }
 
+impl ZkRollupProof {
+    /// Whether this is a batch proof from [`prove_rollup_batch`], recognised by
+    /// its 96-byte public inputs.
+    pub fn is_batch(&self) -> bool {
+        !self.proof.is_empty() && self.public_inputs.len() == BATCH_PUBLIC_INPUTS_LEN
+    }
+}
+
 /// Fault evidence used for optimistic mode.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OptimisticFault {
@@ -229,14 +451,110 @@ pub fn build_rollup_fault(
     }
 }
 
-/// Verify Groth16 rollup proof with deterministic parameters and Merkle check.
+/// Public inputs `prev||next||tx_root||share_root` for a batch whose `tx_root`
+/// is a leaf under `pedersen_root`, with `next = prev + tx_root` in the field.
+pub fn rollup_public_inputs(
+    prev: &[u8; 32],
+    tx_root: &[u8; 32],
+    pedersen_root: &[u8; 32],
+) -> Vec<u8> {
+    let next = Fr::from_le_bytes_mod_order(prev) + Fr::from_le_bytes_mod_order(tx_root);
+    let mut inputs = Vec::with_capacity(128);
+    inputs.extend_from_slice(prev);
+    inputs.extend_from_slice(&next.into_bigint().to_bytes_le());
+    inputs.extend_from_slice(tx_root);
+    inputs.extend_from_slice(pedersen_root);
+    inputs
+}
+
+/// Produce a single-transfer Groth16 rollup proof under `key`.
+///
+/// `merkle_path` is the JSON-serialized `Vec<MerkleSibling>` from `tx_root`
+/// up to the commitment's path root. `key` must come from
+/// [`rollup_transfer_setup`] for the commitment's path hash and the path's
+/// length, and [`verify_zk_rollup`] accepts the proof under its verifying key.
+pub fn prove_zk_rollup<R: RngCore + CryptoRng>(
+    commitment: &RollupCommitment,
+    public_inputs: Vec<u8>,
+    merkle_path: Vec<u8>,
+    key: &CircuitProvingKey,
+    rng: &mut R,
+) -> Result<ZkRollupProof, String> {
+    if public_inputs.len() != 128 {
+        return Err("public inputs must be 128 bytes".into());
+    }
+    let (circuit_id, circuit) = transfer_circuit(commitment, &public_inputs, &merkle_path)?;
+    let key = key.for_circuit(&circuit_id)?;
+    let groth_proof = Groth16::<Bn254>::create_random_proof_with_reduction(circuit, key, rng)
+        .map_err(|e| format!("proof gen failed: {e}"))?;
+    let mut proof = Vec::new();
+    groth_proof
+        .serialize_compressed(&mut proof)
+        .map_err(|e| format!("proof encode failed: {e}"))?;
+    Ok(ZkRollupProof {
+        proof,
+        public_inputs,
+        merkle_path,
+    })
+}
+
+/// Verify a single-transfer proof from [`prove_zk_rollup`] against `key`,
+/// checking the Merkle path outside the circuit as well.
+///
+/// Batch proofs, recognised by their 96-byte public inputs, go through
+/// [`verify_rollup_batch`] instead.
 pub fn verify_zk_rollup(
     commitment: &RollupCommitment,
     proof: &ZkRollupProof,
+    key: &CircuitVerifyingKey,
 ) -> Result<(), String> {
+    if proof.is_batch() {
+        return Err("batch proof needs a batch verifying key".into());
+    }
     if proof.proof.is_empty() || proof.public_inputs.len() != 128 {
         return Err("zk proof missing".into());
     }
+    let (circuit_id, _) = transfer_circuit(commitment, &proof.public_inputs, &proof.merkle_path)?;
+    let inputs: Vec<Fr> = proof
+        .public_inputs
+        .chunks(32)
+        .map(Fr::from_le_bytes_mod_order)
+        .collect();
+    let groth_proof: Proof<Bn254> =
+        Proof::deserialize_with_mode(&mut Cursor::new(&proof.proof), Compress::Yes, Validate::Yes)
+            .map_err(|e| format!("proof decode failed: {e}"))?;
+    if key.verify(&circuit_id, &groth_proof, &inputs)? {
+        Ok(())
+    } else {
+        Err("zk proof invalid".into())
+    }
+}
+
+fn transfer_circuit_id(path_hash: RollupPathHash, depth: usize) -> String {
+    format!("rollup_transfer:{}:{depth}", path_hash.as_str())
+}
+
+/// Run the Groth16 setup for single-transfer proofs whose Merkle path has
+/// `depth` levels hashed with `path_hash`.
+pub fn rollup_transfer_setup<R: RngCore + CryptoRng>(
+    path_hash: RollupPathHash,
+    depth: usize,
+    rng: &mut R,
+) -> Result<CircuitProvingKey, String> {
+    CircuitProvingKey::setup(
+        transfer_circuit_id(path_hash, depth),
+        RollupCircuit::blank(path_hash, depth),
+        rng,
+    )
+}
+
+/// Checks the state transition and Merkle path, then builds the transfer
+/// circuit together with the id of the key it needs.
+fn transfer_circuit(
+    commitment: &RollupCommitment,
+    public_inputs: &[u8],
+    merkle_path: &[u8],
+) -> Result<(String, RollupCircuit), String> {
     let pedersen_root_hex = commitment
         .pedersen_root
         .clone()
@@ -246,13 +564,17 @@ pub fn verify_zk_rollup(
     if pedersen_root_bytes.len() != 32 {
         return Err("share_root must be 32 bytes".into());
     }
-    let prev = Fr::from_le_bytes_mod_order(&proof.public_inputs[0..32]);
-    let next = Fr::from_le_bytes_mod_order(&proof.public_inputs[32..64]);
-    let tx_root = Fr::from_le_bytes_mod_order(&proof.public_inputs[64..96]);
-    let share_root = Fr::from_le_bytes_mod_order(&proof.public_inputs[96..128]);
+    let prev = Fr::from_le_bytes_mod_order(&public_inputs[0..32]);
+    let next = Fr::from_le_bytes_mod_order(&public_inputs[32..64]);
+    let tx_root = Fr::from_le_bytes_mod_order(&public_inputs[64..96]);
+    let share_root = Fr::from_le_bytes_mod_order(&public_inputs[96..128]);
+    if prev + tx_root != next {
+        return Err("state transition invalid: next != prev + tx_root".into());
+    }
 
-    // Out-of-circuit Pedersen Merkle check: verify tx_root bytes against pedersen_root via path.
-    let path_json: Vec<MerkleSibling> = serde_json::from_slice(&proof.merkle_path)
+    // Verify tx_root bytes against the path root out of circuit as well; the circuit
+    // enforces the same path, but checking here first gives a precise error.
+    let path_json: Vec<MerkleSibling> = serde_json::from_slice(merkle_path)
         .map_err(|e| format!("merkle path decode failed: {e}"))?;
     let mut path = Vec::new();
     for sib in &path_json {
@@ -264,50 +586,711 @@ pub fn verify_zk_rollup(
         arr.copy_from_slice(&bytes);
         path.push((sib.left, arr));
     }
-    let current = pedersen_root_from_path(&proof.public_inputs[64..96], &path)?;
+    let current = match commitment.path_hash {
+        RollupPathHash::Pedersen => pedersen_root_from_path(&public_inputs[64..96], &path)?,
+        RollupPathHash::Poseidon => poseidon_root_from_path(&public_inputs[64..96], &path)?,
+    };
     if current != pedersen_root_bytes {
-        return Err("merkle path invalid (pedersen)".into());
+        return Err(format!(
+            "merkle path invalid ({})",
+            commitment.path_hash.as_str()
+        ));
     }
-    if proof.public_inputs[96..128] != pedersen_root_bytes {
+    if public_inputs[96..128] != pedersen_root_bytes {
         return Err("public share_root does not match pedersen_root".into());
     }
 
-    let mut hasher = Sha256::new();
-    hasher.update(commitment.namespace.as_bytes());
-    hasher.update(&proof.public_inputs);
-    hasher.update(&pedersen_root_bytes);
-    let seed: [u8; 32] = hasher.finalize().into();
-    let mut rng = StdRng::from_seed(seed);
-
-    let mut share_root_bytes_arr = [0u8; 32];
-    share_root_bytes_arr.copy_from_slice(&pedersen_root_bytes);
-    let mut tx_bytes_arr = [0u8; 32];
-    tx_bytes_arr.copy_from_slice(&proof.public_inputs[64..96]);
     let circuit = RollupCircuit {
         prev,
         next,
         tx_root,
         share_root,
-        share_root_bytes: share_root_bytes_arr,
-        tx_bytes: tx_bytes_arr,
-        path,
-        pedersen_params: pedersen_params(),
+        path: match commitment.path_hash {
+            RollupPathHash::Pedersen => {
+                let mut leaf = [0u8; 32];
+                leaf.copy_from_slice(&public_inputs[64..96]);
+                CircuitPath::Pedersen { leaf, path }
+            }
+            RollupPathHash::Poseidon => CircuitPath::Poseidon(
+                path.iter()
+                    .map(|(left, sib)| (*left, Fr::from_le_bytes_mod_order(sib)))
+                    .collect(),
+            ),
+        },
     };
-    let params = Groth16::<Bn254, ark_groth16::r1cs_to_qap::LibsnarkReduction>::generate_random_parameters_with_reduction(circuit.clone(), &mut rng)
-        .map_err(|e| format!("parameter gen failed: {e}"))?;
-    let groth_proof: Proof<Bn254> = Groth16::<Bn254, ark_groth16::r1cs_to_qap::LibsnarkReduction>::create_random_proof_with_reduction(circuit, &params, &mut rng)
-        .map_err(|e| format!("proof gen failed: {e}"))?;
+    Ok((
+        transfer_circuit_id(commitment.path_hash, circuit.path.len()),
+        circuit,
+    ))
+}
 
-    let mut cursor = Cursor::new(&proof.proof);
-    let provided: Proof<Bn254> =
-        Proof::deserialize_with_mode(&mut cursor, Compress::Yes, Validate::Yes)
-            .map_err(|e| format!("proof decode failed: {e}"))?;
-    if provided != groth_proof {
-        return Err("zk proof invalid".into());
+/// Schema tag written at the start of every Groth16 key file.
+pub const SCHEMA_GROTH16_KEY: &str = "mfenx.powerhouse.groth16_key.v1";
+
+fn write_key_file<K: CanonicalSerialize>(
+    path: &Path,
+    circuit: &str,
+    key: &K,
+) -> Result<(), String> {
+    let mut bytes = Vec::new();
+    (SCHEMA_GROTH16_KEY.to_string(), circuit.to_string())
+        .serialize_compressed(&mut bytes)
+        .and_then(|_| key.serialize_compressed(&mut bytes))
+        .map_err(|e| format!("key encode failed: {e}"))?;
+    std::fs::write(path, bytes).map_err(|e| format!("failed to write {}: {e}", path.display()))
+}
+
+fn read_key_file<K: CanonicalDeserialize>(path: &Path) -> Result<(String, K), String> {
+    let bytes =
+        std::fs::read(path).map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+    let mut cursor = Cursor::new(bytes.as_slice());
+    let (schema, circuit) = <(String, String)>::deserialize_compressed(&mut cursor)
+        .map_err(|e| format!("{}: key decode failed: {e}", path.display()))?;
+    if schema != SCHEMA_GROTH16_KEY {
+        return Err(format!("{}: unexpected schema {schema}", path.display()));
+    }
+    let key = K::deserialize_with_mode(&mut cursor, Compress::Yes, Validate::Yes)
+        .map_err(|e| format!("{}: key decode failed: {e}", path.display()))?;
+    Ok((circuit, key))
+}
+
+/// Groth16 proving key from a one-off setup, tagged with its circuit shape.
+///
+/// Whoever runs the setup learns the trapdoor and could forge proofs, so
+/// generate keys with a fresh OS RNG on a trusted machine and publish only
+/// the [`CircuitVerifyingKey`].
+#[derive(Clone)]
+pub struct CircuitProvingKey {
+    circuit: String,
+    key: ProvingKey<Bn254>,
+}
+
+impl CircuitProvingKey {
+    pub(crate) fn setup<C, R>(circuit: String, blank: C, rng: &mut R) -> Result<Self, String>
+    where
+        C: ConstraintSynthesizer<Fr>,
+        R: RngCore + CryptoRng,
+    {
+        let key = Groth16::<Bn254>::generate_random_parameters_with_reduction(blank, rng)
+            .map_err(|e| format!("parameter gen failed: {e}"))?;
+        Ok(Self { circuit, key })
+    }
+
+    /// Circuit shape this key was generated for, e.g. `rollup_batch:4x4`.
+    pub fn circuit(&self) -> &str {
+        &self.circuit
+    }
+
+    /// Verifying half of the key, safe to publish.
+    pub fn verifying_key(&self) -> CircuitVerifyingKey {
+        CircuitVerifyingKey {
+            circuit: self.circuit.clone(),
+            key: self.key.vk.clone(),
+        }
+    }
+
+    pub(crate) fn for_circuit(&self, circuit: &str) -> Result<&ProvingKey<Bn254>, String> {
+        if self.circuit != circuit {
+            return Err(format!(
+                "proving key is for {}, not {circuit}",
+                self.circuit
+            ));
+        }
+        Ok(&self.key)
+    }
+
+    /// Write the key to `path`.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        write_key_file(path, &self.circuit, &self.key)
+    }
+
+    /// Read a key written by [`CircuitProvingKey::save`].
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let (circuit, key) = read_key_file(path)?;
+        Ok(Self { circuit, key })
+    }
+}
+
+/// Groth16 verifying key matching a [`CircuitProvingKey`].
+#[derive(Debug, Clone)]
+pub struct CircuitVerifyingKey {
+    circuit: String,
+    key: VerifyingKey<Bn254>,
+}
+
+impl CircuitVerifyingKey {
+    /// Circuit shape this key was generated for.
+    pub fn circuit(&self) -> &str {
+        &self.circuit
+    }
+
+    /// Check `proof` over `inputs`, failing unless the key is for `circuit`.
+    pub(crate) fn verify(
+        &self,
+        circuit: &str,
+        proof: &Proof<Bn254>,
+        inputs: &[Fr],
+    ) -> Result<bool, String> {
+        if self.circuit != circuit {
+            return Err(format!(
+                "verifying key is for {}, not {circuit}",
+                self.circuit
+            ));
+        }
+        Groth16::<Bn254>::verify_proof(&prepare_verifying_key(&self.key), proof, inputs)
+            .map_err(|e| format!("zk verification failed: {e}"))
+    }
+
+    /// Write the key to `path`.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        write_key_file(path, &self.circuit, &self.key)
+    }
+
+    /// Read a key written by [`CircuitVerifyingKey::save`].
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let (circuit, key) = read_key_file(path)?;
+        Ok(Self { circuit, key })
+    }
+}
+
+/// Transfers per batch proof; shorter batches are padded with no-op transfers.
+pub const ROLLUP_BATCH_SIZE: usize = 4;
+/// Depth of the account-state tree, which holds `2^depth` account slots.
+pub const ROLLUP_STATE_DEPTH: usize = 4;
+
+const BATCH_PUBLIC_INPUTS_LEN: usize = 96;
+const ACCOUNT_LEAF_DOMAIN: u8 = 2;
+const TRANSFER_LEAF_DOMAIN: u8 = 3;
+
+/// One slot of the rollup account-state tree.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RollupAccount {
+    /// Hex-encoded 32-byte owner identifier; empty for an unassigned slot.
+    pub owner: String,
+    /// Balance in base units.
+    pub balance: u64,
+}
+
+impl RollupAccount {
+    fn owner_fr(&self) -> Result<Fr, String> {
+        if self.owner.is_empty() {
+            return Ok(Fr::from(0u8));
+        }
+        let bytes = hex::decode(&self.owner).map_err(|e| format!("bad owner hex: {e}"))?;
+        if bytes.len() != 32 {
+            return Err("owner must be 32 bytes".into());
+        }
+        Ok(Fr::from_le_bytes_mod_order(&bytes))
+    }
+}
+
+fn account_leaf(owner: Fr, balance: u64) -> Fr {
+    poseidon_hash(&[Fr::from(ACCOUNT_LEAF_DOMAIN), owner, Fr::from(balance)])
+}
+
+/// Transfer between two account slots inside a batch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RollupTransfer {
+    /// Sender slot.
+    pub from: u32,
+    /// Receiver slot.
+    pub to: u32,
+    /// Amount moved.
+    pub amount: u64,
+}
+
+impl RollupTransfer {
+    fn leaf(&self) -> Fr {
+        poseidon_hash(&[
+            Fr::from(TRANSFER_LEAF_DOMAIN),
+            Fr::from(self.from),
+            Fr::from(self.to),
+            Fr::from(self.amount),
+        ])
+    }
+}
+
+/// Account-state tree of a rollup: `2^depth` slots under a Poseidon root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RollupState {
+    depth: usize,
+    accounts: Vec<RollupAccount>,
+}
+
+impl RollupState {
+    /// State of depth [`ROLLUP_STATE_DEPTH`] with `accounts` in the first slots.
+    pub fn new(accounts: Vec<RollupAccount>) -> Result<Self, String> {
+        Self::with_depth(ROLLUP_STATE_DEPTH, accounts)
+    }
+
+    fn with_depth(depth: usize, mut accounts: Vec<RollupAccount>) -> Result<Self, String> {
+        let slots = 1usize << depth;
+        if accounts.len() > slots {
+            return Err(format!("state holds at most {slots} accounts"));
+        }
+        for account in &accounts {
+            account.owner_fr()?;
+        }
+        accounts.resize(slots, RollupAccount::default());
+        Ok(Self { depth, accounts })
+    }
+
+    /// Every slot, including empty ones.
+    pub fn accounts(&self) -> &[RollupAccount] {
+        &self.accounts
+    }
+
+    /// Poseidon root over the account leaves.
+    pub fn root(&self) -> [u8; 32] {
+        fr_to_bytes(self.levels().pop().expect("tree has a root")[0])
+    }
+
+    fn levels(&self) -> Vec<Vec<Fr>> {
+        let mut levels = vec![self
+            .accounts
+            .iter()
+            .map(|account| {
+                account_leaf(
+                    account.owner_fr().expect("validated owner"),
+                    account.balance,
+                )
+            })
+            .collect::<Vec<_>>()];
+        while levels.last().expect("leaf level").len() > 1 {
+            let next = levels
+                .last()
+                .expect("leaf level")
+                .chunks(2)
+                .map(|pair| poseidon_hash_pair(pair[0], pair[1]))
+                .collect();
+            levels.push(next);
+        }
+        levels
+    }
+
+    fn slot(&self, index: u32) -> Result<usize, String> {
+        let slot = index as usize;
+        if slot >= self.accounts.len() {
+            return Err(format!("account slot {index} out of range"));
+        }
+        Ok(slot)
+    }
+
+    fn step(&self, index: u32) -> Result<AccountStep, String> {
+        let slot = self.slot(index)?;
+        let levels = self.levels();
+        let siblings = (0..self.depth)
+            .map(|level| levels[level][(slot >> level) ^ 1])
+            .collect();
+        let account = &self.accounts[slot];
+        Ok(AccountStep {
+            owner: account.owner_fr()?,
+            balance: account.balance,
+            siblings,
+        })
+    }
+
+    fn debit(&mut self, transfer: &RollupTransfer) -> Result<(), String> {
+        let slot = self.slot(transfer.from)?;
+        let balance = &mut self.accounts[slot].balance;
+        *balance = balance
+            .checked_sub(transfer.amount)
+            .ok_or_else(|| format!("insufficient balance in slot {}", transfer.from))?;
+        Ok(())
+    }
+
+    fn credit(&mut self, transfer: &RollupTransfer) -> Result<(), String> {
+        let slot = self.slot(transfer.to)?;
+        let balance = &mut self.accounts[slot].balance;
+        *balance = balance
+            .checked_add(transfer.amount)
+            .ok_or_else(|| format!("balance overflow in slot {}", transfer.to))?;
+        Ok(())
+    }
+
+    /// Applies `transfer`, rejecting unknown slots, overdrafts and overflow.
+    pub fn apply(&mut self, transfer: &RollupTransfer) -> Result<(), String> {
+        self.slot(transfer.to)?;
+        self.debit(transfer)?;
+        if let Err(err) = self.credit(transfer) {
+            self.accounts[transfer.from as usize].balance += transfer.amount;
+            return Err(err);
+        }
+        Ok(())
+    }
+}
+
+fn padded_batch(
+    transfers: &[RollupTransfer],
+    batch_size: usize,
+) -> Result<Vec<RollupTransfer>, String> {
+    if transfers.len() > batch_size {
+        return Err(format!("batch holds at most {batch_size} transfers"));
+    }
+    let mut padded = transfers.to_vec();
+    padded.resize(batch_size, RollupTransfer::default());
+    Ok(padded)
+}
+
+fn batch_root_fr(transfers: &[RollupTransfer]) -> Fr {
+    let mut level: Vec<Fr> = transfers.iter().map(RollupTransfer::leaf).collect();
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => poseidon_hash_pair(*left, *right),
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+    level[0]
+}
+
+/// Poseidon root committing to a batch padded to [`ROLLUP_BATCH_SIZE`] transfers.
+pub fn rollup_batch_root(transfers: &[RollupTransfer]) -> Result<[u8; 32], String> {
+    Ok(fr_to_bytes(batch_root_fr(&padded_batch(
+        transfers,
+        ROLLUP_BATCH_SIZE,
+    )?)))
+}
+
+#[derive(Clone)]
+struct AccountStep {
+    owner: Fr,
+    balance: u64,
+    siblings: Vec<Fr>,
+}
+
+#[derive(Clone)]
+struct TransferStep {
+    transfer: RollupTransfer,
+    sender: AccountStep,
+    receiver: AccountStep,
+}
+
+/// Circuit for a fixed-size batch of transfers.
+///
+/// Public inputs are the old state root, the new state root, and the batch
+/// root. For each transfer the circuit checks both account paths against the
+/// running root, that the sender's new balance and the receiver's new balance
+/// fit in 64 bits, and folds the transfer into the batch root.
+#[derive(Clone)]
+struct RollupBatchCircuit {
+    old_root: Fr,
+    new_root: Fr,
+    batch_root: Fr,
+    steps: Vec<TransferStep>,
+}
+
+impl RollupBatchCircuit {
+    /// Circuit of the given shape with zero witnesses, for key generation.
+    fn blank(batch_size: usize, depth: usize) -> Self {
+        let account = AccountStep {
+            owner: Fr::from(0u8),
+            balance: 0,
+            siblings: vec![Fr::from(0u8); depth],
+        };
+        let step = TransferStep {
+            transfer: RollupTransfer::default(),
+            sender: account.clone(),
+            receiver: account,
+        };
+        Self {
+            old_root: Fr::from(0u8),
+            new_root: Fr::from(0u8),
+            batch_root: Fr::from(0u8),
+            steps: vec![step; batch_size],
+        }
+    }
+}
+
+fn enforce_bits(
+    cs: &ConstraintSystemRef<Fr>,
+    value: &FpVar<Fr>,
+    witness: u64,
+    bits: usize,
+) -> Result<Vec<Boolean<Fr>>, SynthesisError> {
+    let bits = (0..bits)
+        .map(|i| Boolean::new_witness(cs.clone(), || Ok((witness >> i) & 1 == 1)))
+        .collect::<Result<Vec<_>, _>>()?;
+    Boolean::le_bits_to_fp_var(&bits)?.enforce_equal(value)?;
+    Ok(bits)
+}
+
+/// Checks `step` against `root` at `index`, moves `amount` out of (`debit`) or
+/// into the account, and returns the updated root.
+#[allow(clippy::too_many_arguments)]
+fn update_account(
+    cs: &ConstraintSystemRef<Fr>,
+    params: &CRHParametersVar<Fr>,
+    root: &FpVar<Fr>,
+    index: &FpVar<Fr>,
+    index_value: u32,
+    step: &AccountStep,
+    amount: &FpVar<Fr>,
+    amount_value: u64,
+    debit: bool,
+) -> Result<FpVar<Fr>, SynthesisError> {
+    let bits = enforce_bits(cs, index, index_value.into(), step.siblings.len())?;
+    let owner = FpVar::new_witness(cs.clone(), || Ok(step.owner))?;
+    let balance = FpVar::new_witness(cs.clone(), || Ok(Fr::from(step.balance)))?;
+    let (new_balance, new_value) = if debit {
+        (&balance - amount, step.balance.wrapping_sub(amount_value))
+    } else {
+        (&balance + amount, step.balance.wrapping_add(amount_value))
+    };
+    enforce_bits(cs, &new_balance, new_value, 64)?;
+
+    let domain = FpVar::Constant(Fr::from(ACCOUNT_LEAF_DOMAIN));
+    let mut old_node =
+        CRHGadget::<Fr>::evaluate(params, &[domain.clone(), owner.clone(), balance])?;
+    let mut new_node = CRHGadget::<Fr>::evaluate(params, &[domain, owner, new_balance])?;
+    let pair_domain = FpVar::Constant(Fr::from(1u8));
+    for (bit, sibling) in bits.iter().zip(&step.siblings) {
+        let sibling = FpVar::new_witness(cs.clone(), || Ok(*sibling))?;
+        for node in [&mut old_node, &mut new_node] {
+            // A set index bit means the node is a right child.
+            let left = FpVar::conditionally_select(bit, &sibling, node)?;
+            let right = FpVar::conditionally_select(bit, node, &sibling)?;
+            *node = CRHGadget::<Fr>::evaluate(params, &[pair_domain.clone(), left, right])?;
+        }
+    }
+    old_node.enforce_equal(root)?;
+    Ok(new_node)
+}
+
+impl ConstraintSynthesizer<Fr> for RollupBatchCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        let old_root = FpVar::new_input(cs.clone(), || Ok(self.old_root))?;
+        let new_root = FpVar::new_input(cs.clone(), || Ok(self.new_root))?;
+        let batch_root = FpVar::new_input(cs.clone(), || Ok(self.batch_root))?;
+        let params = CRHParametersVar::new_constant(cs.clone(), poseidon_config())?;
+
+        let mut root = old_root;
+        let mut leaves = Vec::with_capacity(self.steps.len());
+        for step in &self.steps {
+            let transfer = step.transfer;
+            let from = FpVar::new_witness(cs.clone(), || Ok(Fr::from(transfer.from)))?;
+            let to = FpVar::new_witness(cs.clone(), || Ok(Fr::from(transfer.to)))?;
+            let amount = FpVar::new_witness(cs.clone(), || Ok(Fr::from(transfer.amount)))?;
+            enforce_bits(&cs, &amount, transfer.amount, 64)?;
+            leaves.push(CRHGadget::<Fr>::evaluate(
+                &params,
+                &[
+                    FpVar::Constant(Fr::from(TRANSFER_LEAF_DOMAIN)),
+                    from.clone(),
+                    to.clone(),
+                    amount.clone(),
+                ],
+            )?);
+            root = update_account(
+                &cs,
+                &params,
+                &root,
+                &from,
+                transfer.from,
+                &step.sender,
+                &amount,
+                transfer.amount,
+                true,
+            )?;
+            root = update_account(
+                &cs,
+                &params,
+                &root,
+                &to,
+                transfer.to,
+                &step.receiver,
+                &amount,
+                transfer.amount,
+                false,
+            )?;
+        }
+        root.enforce_equal(&new_root)?;
+
+        let pair_domain = FpVar::Constant(Fr::from(1u8));
+        while leaves.len() > 1 {
+            let mut next = Vec::with_capacity(leaves.len().div_ceil(2));
+            for pair in leaves.chunks(2) {
+                next.push(match pair {
+                    [left, right] => CRHGadget::<Fr>::evaluate(
+                        &params,
+                        &[pair_domain.clone(), left.clone(), right.clone()],
+                    )?,
+                    [single] => single.clone(),
+                    _ => unreachable!(),
+                });
+            }
+            leaves = next;
+        }
+        leaves[0].enforce_equal(&batch_root)?;
+        Ok(())
+    }
+}
+
+/// File name of the batch verifying key a node loads from its blob directory.
+pub const ROLLUP_BATCH_VK_FILE: &str = "rollup_batch.vk";
+/// File name of the single-transfer verifying key a node loads from its blob
+/// directory.
+pub const ROLLUP_TRANSFER_VK_FILE: &str = "rollup_transfer.vk";
+
+fn batch_circuit_id(batch_size: usize, depth: usize) -> String {
+    format!("rollup_batch:{batch_size}x{depth}")
+}
+
+/// Run the Groth16 setup for the batch circuit of [`ROLLUP_BATCH_SIZE`]
+/// transfers over a depth-[`ROLLUP_STATE_DEPTH`] state tree.
+pub fn rollup_batch_setup<R: RngCore + CryptoRng>(
+    rng: &mut R,
+) -> Result<CircuitProvingKey, String> {
+    batch_setup_with_shape(ROLLUP_BATCH_SIZE, ROLLUP_STATE_DEPTH, rng)
+}
+
+fn batch_setup_with_shape<R: RngCore + CryptoRng>(
+    batch_size: usize,
+    depth: usize,
+    rng: &mut R,
+) -> Result<CircuitProvingKey, String> {
+    CircuitProvingKey::setup(
+        batch_circuit_id(batch_size, depth),
+        RollupBatchCircuit::blank(batch_size, depth),
+        rng,
+    )
+}
+
+fn check_batch_commitment(commitment: &RollupCommitment, batch_root: &[u8]) -> Result<(), String> {
+    if commitment.path_hash != RollupPathHash::Poseidon {
+        return Err("batch proofs require a poseidon commitment".into());
+    }
+    if commitment.pedersen_root.as_deref() != Some(hex::encode(batch_root).as_str()) {
+        return Err("batch root does not match commitment".into());
     }
     Ok(())
 }
 
+/// Prove `transfers` against `state` and return the proof with the new state.
+///
+/// The commitment must be a Poseidon commitment whose path root is
+/// [`rollup_batch_root`] of the same transfers. The proof's public inputs are
+/// `old_root||new_root||batch_root` (3 x 32 bytes LE) and
+/// [`verify_rollup_batch`] accepts it under `key`'s verifying key. `rng`
+/// supplies the proof's blinding; use a fresh OS RNG.
+pub fn prove_rollup_batch<R: RngCore + CryptoRng>(
+    commitment: &RollupCommitment,
+    state: &RollupState,
+    transfers: &[RollupTransfer],
+    key: &CircuitProvingKey,
+    rng: &mut R,
+) -> Result<(ZkRollupProof, RollupState), String> {
+    if state.depth != ROLLUP_STATE_DEPTH {
+        return Err(format!("state depth must be {ROLLUP_STATE_DEPTH}"));
+    }
+    prove_batch_with_shape(commitment, state, transfers, ROLLUP_BATCH_SIZE, key, rng)
+}
+
+fn prove_batch_with_shape<R: RngCore + CryptoRng>(
+    commitment: &RollupCommitment,
+    state: &RollupState,
+    transfers: &[RollupTransfer],
+    batch_size: usize,
+    key: &CircuitProvingKey,
+    rng: &mut R,
+) -> Result<(ZkRollupProof, RollupState), String> {
+    let key = key.for_circuit(&batch_circuit_id(batch_size, state.depth))?;
+    let transfers = padded_batch(transfers, batch_size)?;
+    let batch_root = fr_to_bytes(batch_root_fr(&transfers));
+    check_batch_commitment(commitment, &batch_root)?;
+
+    let mut next = state.clone();
+    let mut steps = Vec::with_capacity(transfers.len());
+    for transfer in &transfers {
+        let sender = next.step(transfer.from)?;
+        next.slot(transfer.to)?;
+        next.debit(transfer)?;
+        let receiver = next.step(transfer.to)?;
+        next.credit(transfer)?;
+        steps.push(TransferStep {
+            transfer: *transfer,
+            sender,
+            receiver,
+        });
+    }
+    let mut public_inputs = Vec::with_capacity(BATCH_PUBLIC_INPUTS_LEN);
+    public_inputs.extend_from_slice(&state.root());
+    public_inputs.extend_from_slice(&next.root());
+    public_inputs.extend_from_slice(&batch_root);
+    let circuit = RollupBatchCircuit {
+        old_root: Fr::from_le_bytes_mod_order(&public_inputs[0..32]),
+        new_root: Fr::from_le_bytes_mod_order(&public_inputs[32..64]),
+        batch_root: Fr::from_le_bytes_mod_order(&batch_root),
+        steps,
+    };
+
+    let groth_proof = Groth16::<Bn254>::create_random_proof_with_reduction(circuit, key, rng)
+        .map_err(|e| format!("batch proof gen failed: {e}"))?;
+    let mut proof = Vec::new();
+    groth_proof
+        .serialize_compressed(&mut proof)
+        .map_err(|e| format!("proof encode failed: {e}"))?;
+    Ok((
+        ZkRollupProof {
+            proof,
+            public_inputs,
+            merkle_path: Vec::new(),
+        },
+        next,
+    ))
+}
+
+/// Verify a batch proof from [`prove_rollup_batch`] against `key`.
+pub fn verify_rollup_batch(
+    commitment: &RollupCommitment,
+    proof: &ZkRollupProof,
+    key: &CircuitVerifyingKey,
+) -> Result<(), String> {
+    verify_batch_with_shape(
+        commitment,
+        proof,
+        ROLLUP_BATCH_SIZE,
+        ROLLUP_STATE_DEPTH,
+        key,
+    )
+}
+
+fn verify_batch_with_shape(
+    commitment: &RollupCommitment,
+    proof: &ZkRollupProof,
+    batch_size: usize,
+    depth: usize,
+    key: &CircuitVerifyingKey,
+) -> Result<(), String> {
+    if proof.public_inputs.len() != BATCH_PUBLIC_INPUTS_LEN {
+        return Err("batch public inputs must be 96 bytes".into());
+    }
+    check_batch_commitment(commitment, &proof.public_inputs[64..96])?;
+    let inputs: Vec<Fr> = proof
+        .public_inputs
+        .chunks(32)
+        .map(Fr::from_le_bytes_mod_order)
+        .collect();
+    if inputs
+        .iter()
+        .zip(proof.public_inputs.chunks(32))
+        .any(|(input, bytes)| fr_to_bytes(*input) != bytes)
+    {
+        return Err("public inputs are not canonical field elements".into());
+    }
+    let groth_proof: Proof<Bn254> =
+        Proof::deserialize_with_mode(&mut Cursor::new(&proof.proof), Compress::Yes, Validate::Yes)
+            .map_err(|e| format!("proof decode failed: {e}"))?;
+    if key.verify(&batch_circuit_id(batch_size, depth), &groth_proof, &inputs)? {
+        Ok(())
+    } else {
+        Err("zk proof invalid".into())
+    }
+}
+
 /// Verify optimistic rollup faults (rejects if any).
 pub fn verify_optimistic_rollup(
     commitment: &RollupCommitment,
@@ -332,25 +1315,34 @@ pub fn settle_rollup_with_fault(
     fee: u64,
     mode: RollupSettlementMode,
 ) -> Result<SettlementReceipt, RollupFaultEvidence> {
-    match &mode {
-        RollupSettlementMode::Zk(proof) => {
-            if let Err(err) = verify_zk_rollup(&commitment, proof) {
-                return Err(build_rollup_fault(&commitment, &err, None));
-            }
-        }
-        RollupSettlementMode::Optimistic(faults) => {
-            if let Err(err) = verify_optimistic_rollup(&commitment, faults) {
-                return Err(build_rollup_fault(&commitment, &err, None));
-            }
-        }
-        RollupSettlementMode::Fault(ev) => return Err(ev.clone()),
-    }
+    verify_settlement_mode(&commitment, &mode)?;
     let cloned = commitment.clone();
     settle_rollup(registry_path, commitment, payer_pk, fee)
         .map_err(|e| build_rollup_fault(&cloned, &e, None))
 }
 
+#[cfg(feature = "net")]
+fn verify_settlement_mode(
+    commitment: &RollupCommitment,
+    mode: &RollupSettlementMode,
+) -> Result<(), RollupFaultEvidence> {
+    match mode {
+        RollupSettlementMode::Zk(proof, key) => verify_zk_rollup(commitment, proof, key)
+            .map_err(|err| build_rollup_fault(commitment, &err, None)),
+        RollupSettlementMode::ZkBatch(proof, key) => verify_rollup_batch(commitment, proof, key)
+            .map_err(|err| build_rollup_fault(commitment, &err, None)),
+        RollupSettlementMode::Optimistic(faults) => verify_optimistic_rollup(commitment, faults)
+            .map_err(|err| build_rollup_fault(commitment, &err, None)),
+        RollupSettlementMode::Fault(ev) => Err(ev.clone()),
+    }
+}
+
 /// Settle a rollup with fee rewards split between operator and attesters.
+///
+/// The payer's fee is split in one registry transaction: half (rounded up) to
+/// the operator, the rest evenly across attesters, and any remainder collected
+/// under the registry's fee policy.
+/// Either every transfer lands in a single registry write or none does.
 #[cfg(feature = "net")]
 pub fn settle_rollup_with_rewards(
     registry_path: &std::path::Path,
@@ -361,22 +1353,33 @@ pub fn settle_rollup_with_rewards(
     fee: u64,
     mode: RollupSettlementMode,
 ) -> Result<SettlementReceipt, RollupFaultEvidence> {
-    let receipt = settle_rollup_with_fault(registry_path, commitment, payer_pk, fee, mode)?;
-    let mut reg = crate::net::stake_registry::StakeRegistry::load(registry_path).map_err(|e| {
-        build_rollup_fault(&receipt.commitment, &format!("load registry: {e}"), None)
-    })?;
+    use crate::economics::NATIVE_ASSET;
+    use crate::net::stake_registry::{StakeRegistry, SupplyCause};
+
+    verify_settlement_mode(&commitment, &mode)?;
     let operator_share = fee.div_ceil(2);
-    reg.credit_reward(operator_pk, operator_share);
-    if !attesters.is_empty() {
-        let per = (fee.saturating_sub(operator_share)) / attesters.len() as u64;
+    let per = match attesters.len() {
+        0 => 0,
+        n => (fee - operator_share) / n as u64,
+    };
+    let dust = fee - operator_share - per * attesters.len() as u64;
+    StakeRegistry::transact(registry_path, |tx| {
+        tx.transfer(payer_pk, operator_pk, operator_share, SupplyCause::Fee);
         for a in attesters {
-            reg.credit_reward(a, per);
+            tx.transfer(payer_pk, a, per, SupplyCause::Reward);
         }
-    }
-    reg.save(registry_path).map_err(|e| {
-        build_rollup_fault(&receipt.commitment, &format!("persist registry: {e}"), None)
-    })?;
-    Ok(receipt)
+        if dust > 0 {
+            tx.collect_fee(payer_pk, NATIVE_ASSET, dust);
+        }
+        Ok(())
+    })
+    .map_err(|e| build_rollup_fault(&commitment, &e, None))?;
+    Ok(SettlementReceipt {
+        commitment,
+        payer: payer_pk.to_string(),
+        fee,
+        fault: None,
+    })
 }
 
 /// Receipt returned after settling a rollup fee.
@@ -393,7 +1396,7 @@ pub struct SettlementReceipt {
     pub fault: Option<RollupFaultEvidence>,
 }
 
-/// Settle a rollup fee by debiting the stake registry.
+/// Settle a rollup fee by debiting the stake registry under its fee policy.
 #[cfg(feature = "net")]
 pub fn settle_rollup(
     registry_path: &std::path::Path,
@@ -402,7 +1405,7 @@ pub fn settle_rollup(
     fee: u64,
 ) -> Result<SettlementReceipt, String> {
     let mut reg = crate::net::stake_registry::StakeRegistry::load(registry_path)?;
-    reg.debit_fee(payer_pk, fee)?;
+    reg.collect_fee(payer_pk, crate::economics::NATIVE_ASSET, fee)?;
     reg.save(registry_path)?;
     Ok(SettlementReceipt {
         commitment,
@@ -415,8 +1418,11 @@ pub fn settle_rollup(
 /// Rollup settlement verification mode.
 #[derive(Debug, Clone)]
 pub enum RollupSettlementMode {
-    /// Verify using a Groth16 proof.
-    Zk(ZkRollupProof),
+    /// Verify a single-transfer proof against a verifying key from
+    /// [`rollup_transfer_setup`].
+    Zk(ZkRollupProof, Box<CircuitVerifyingKey>),
+    /// Verify a batch proof against a verifying key from [`rollup_batch_setup`].
+    ZkBatch(ZkRollupProof, Box<CircuitVerifyingKey>),
     /// Optimistic mode with fault evidence list.
     Optimistic(Vec<OptimisticFault>),
     /// Invalid: attach rollup fault evidence.
@@ -433,9 +1439,219 @@ pub fn settle_rollup_verified(
     mode: RollupSettlementMode,
 ) -> Result<SettlementReceipt, String> {
     match &mode {
-        RollupSettlementMode::Zk(proof) => verify_zk_rollup(&commitment, proof)?,
+        RollupSettlementMode::Zk(proof, key) => verify_zk_rollup(&commitment, proof, key)?,
+        RollupSettlementMode::ZkBatch(proof, key) => verify_rollup_batch(&commitment, proof, key)?,
         RollupSettlementMode::Optimistic(faults) => verify_optimistic_rollup(&commitment, faults)?,
         RollupSettlementMode::Fault(ev) => return Err(format!("rollup fault: {}", ev.reason)),
     }
     settle_rollup(registry_path, commitment, payer_pk, fee)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proved_batch_verifies_and_tampering_is_rejected() {
+        let tx_root = [7u8; 32];
+        let sibling = [9u8; 32];
+        let root_bytes = pedersen_root_from_path(&tx_root, &[(true, sibling)]).unwrap();
+        let mut root = [0u8; 32];
+        root.copy_from_slice(&root_bytes);
+        let commitment = RollupCommitment {
+            namespace: "rollup".to_string(),
+            share_root: hex::encode([1u8; 32]),
+            pedersen_root: Some(hex::encode(root)),
+            settlement_slot: None,
+            path_hash: RollupPathHash::Pedersen,
+        };
+        let path = serde_json::to_vec(&[MerkleSibling {
+            left: true,
+            hash: hex::encode(sibling),
+        }])
+        .unwrap();
+        let key = rollup_transfer_setup(RollupPathHash::Pedersen, 1, &mut StdRng::seed_from_u64(1))
+            .unwrap();
+        assert_eq!(key.circuit(), "rollup_transfer:pedersen:1");
+        let vk = key.verifying_key();
+        let mut rng = StdRng::seed_from_u64(2);
+        let inputs = rollup_public_inputs(&[3u8; 32], &tx_root, &root);
+        let proof =
+            prove_zk_rollup(&commitment, inputs.clone(), path.clone(), &key, &mut rng).unwrap();
+        verify_zk_rollup(&commitment, &proof, &vk).unwrap();
+
+        let mut bad_next = inputs;
+        bad_next[32] ^= 1;
+        assert!(prove_zk_rollup(&commitment, bad_next, path, &key, &mut rng).is_err());
+        let mut forged = proof.clone();
+        forged.public_inputs[..32].copy_from_slice(&[4u8; 32]);
+        forged.public_inputs[32..64]
+            .copy_from_slice(&rollup_public_inputs(&[4u8; 32], &tx_root, &root)[32..64]);
+        assert_eq!(
+            verify_zk_rollup(&commitment, &forged, &vk).unwrap_err(),
+            "zk proof invalid"
+        );
+        let other =
+            rollup_transfer_setup(RollupPathHash::Pedersen, 1, &mut StdRng::seed_from_u64(3))
+                .unwrap();
+        assert_eq!(
+            verify_zk_rollup(&commitment, &proof, &other.verifying_key()).unwrap_err(),
+            "zk proof invalid"
+        );
+    }
+
+    #[test]
+    fn pedersen_path_is_enforced_in_circuit() {
+        use ark_relations::r1cs::ConstraintSystem;
+
+        let tx_root = [7u8; 32];
+        let sibling = [9u8; 32];
+        let root = pedersen_root_from_path(&tx_root, &[(false, sibling)]).unwrap();
+        let circuit = |share_root: &[u8]| {
+            let prev = Fr::from(3u8);
+            let tx = Fr::from_le_bytes_mod_order(&tx_root);
+            RollupCircuit {
+                prev,
+                next: prev + tx,
+                tx_root: tx,
+                share_root: Fr::from_le_bytes_mod_order(share_root),
+                path: CircuitPath::Pedersen {
+                    leaf: tx_root,
+                    path: vec![(false, sibling)],
+                },
+            }
+        };
+        let satisfied = |circuit: RollupCircuit| {
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            circuit.generate_constraints(cs.clone()).unwrap();
+            cs.is_satisfied().unwrap()
+        };
+        assert!(satisfied(circuit(&root)));
+        let mut forged = root.clone();
+        forged[0] ^= 1;
+        assert!(!satisfied(circuit(&forged)));
+    }
+
+    #[test]
+    fn poseidon_path_is_enforced_in_circuit() {
+        let leaves: Vec<[u8; 32]> = (1..=5u8).map(|i| [i; 32]).collect();
+        let root = poseidon_merkle_root(&leaves);
+        let commitment = RollupCommitment {
+            namespace: "rollup".to_string(),
+            share_root: hex::encode([1u8; 32]),
+            pedersen_root: Some(hex::encode(root)),
+            settlement_slot: None,
+            path_hash: RollupPathHash::Poseidon,
+        };
+        // Leaf 4 is carried up unpaired before joining the tree.
+        let path = poseidon_merkle_path(&leaves, 4).unwrap();
+        let key = rollup_transfer_setup(
+            RollupPathHash::Poseidon,
+            path.len(),
+            &mut StdRng::seed_from_u64(1),
+        )
+        .unwrap();
+        let mut rng = StdRng::seed_from_u64(2);
+        let inputs = rollup_public_inputs(&[2u8; 32], &leaves[4], &root);
+        let proof = prove_zk_rollup(
+            &commitment,
+            inputs,
+            serde_json::to_vec(&path).unwrap(),
+            &key,
+            &mut rng,
+        )
+        .unwrap();
+        verify_zk_rollup(&commitment, &proof, &key.verifying_key()).unwrap();
+
+        let path = serde_json::to_vec(&poseidon_merkle_path(&leaves, 1).unwrap()).unwrap();
+        let inputs = rollup_public_inputs(&[2u8; 32], &leaves[1], &root);
+        let mut pedersen = commitment.clone();
+        pedersen.path_hash = RollupPathHash::Pedersen;
+        assert_eq!(
+            prove_zk_rollup(&pedersen, inputs.clone(), path.clone(), &key, &mut rng).unwrap_err(),
+            "merkle path invalid (pedersen)"
+        );
+        let wrong_leaf = rollup_public_inputs(&[2u8; 32], &leaves[2], &root);
+        assert!(prove_zk_rollup(&commitment, wrong_leaf, path, &key, &mut rng).is_err());
+    }
+
+    #[test]
+    fn batch_moves_state_root_and_rejects_forged_roots() {
+        let owner = |byte: u8| hex::encode([byte; 32]);
+        let accounts = vec![
+            RollupAccount {
+                owner: owner(1),
+                balance: 100,
+            },
+            RollupAccount {
+                owner: owner(2),
+                balance: 0,
+            },
+        ];
+        let state = RollupState::with_depth(1, accounts).unwrap();
+        let transfers = [
+            RollupTransfer {
+                from: 0,
+                to: 1,
+                amount: 30,
+            },
+            RollupTransfer {
+                from: 1,
+                to: 0,
+                amount: 5,
+            },
+        ];
+        let batch_root = fr_to_bytes(batch_root_fr(&transfers));
+        let commitment = RollupCommitment {
+            namespace: "rollup".to_string(),
+            share_root: hex::encode([1u8; 32]),
+            pedersen_root: Some(hex::encode(batch_root)),
+            settlement_slot: None,
+            path_hash: RollupPathHash::Poseidon,
+        };
+        let key = batch_setup_with_shape(2, 1, &mut StdRng::seed_from_u64(7)).unwrap();
+        let vk_path =
+            std::env::temp_dir().join(format!("power_house_batch_{}.vk", std::process::id()));
+        key.verifying_key().save(&vk_path).unwrap();
+        let vk = CircuitVerifyingKey::load(&vk_path).unwrap();
+        let _ = std::fs::remove_file(&vk_path);
+        assert_eq!(vk.circuit(), "rollup_batch:2x1");
+
+        let mut rng = StdRng::seed_from_u64(9);
+        let (proof, next) =
+            prove_batch_with_shape(&commitment, &state, &transfers, 2, &key, &mut rng).unwrap();
+        let balances: Vec<u64> = next.accounts().iter().map(|a| a.balance).collect();
+        assert_eq!(balances, [75, 25]);
+        assert_eq!(proof.public_inputs[..32], state.root());
+        assert_eq!(proof.public_inputs[32..64], next.root());
+        verify_batch_with_shape(&commitment, &proof, 2, 1, &vk).unwrap();
+        assert_eq!(
+            verify_zk_rollup(&commitment, &proof, &vk).unwrap_err(),
+            "batch proof needs a batch verifying key"
+        );
+
+        let mut forged = proof.clone();
+        forged.public_inputs[32..64].copy_from_slice(&state.root());
+        assert_eq!(
+            verify_batch_with_shape(&commitment, &forged, 2, 1, &vk).unwrap_err(),
+            "zk proof invalid"
+        );
+        let other = batch_setup_with_shape(2, 1, &mut StdRng::seed_from_u64(8)).unwrap();
+        assert_eq!(
+            verify_batch_with_shape(&commitment, &proof, 2, 1, &other.verifying_key()).unwrap_err(),
+            "zk proof invalid"
+        );
+        assert!(verify_rollup_batch(&commitment, &proof, &vk)
+            .unwrap_err()
+            .contains("verifying key is for rollup_batch:2x1"));
+        let overdraft = [RollupTransfer {
+            from: 1,
+            to: 0,
+            amount: 1,
+        }];
+        assert!(state.clone().apply(&overdraft[0]).is_err());
+        assert!(
+            prove_batch_with_shape(&commitment, &state, &overdraft, 2, &key, &mut rng).is_err()
+        );
+    }
+}
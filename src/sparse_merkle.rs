@@ -0,0 +1,336 @@
+//! Sparse Merkle tree over 256-bit keys for keyed state commitments.
+//!
+//! Every possible key owns one leaf of a depth-256 binary tree; absent keys
+//! hold the empty leaf, so a single root commits to a whole key-value map and
+//! proofs show both inclusion ("key `k` maps to `v`") and non-inclusion ("key
+//! `k` is absent").  Bits of the key select the path from the root, most
+//! significant bit first.  Hashing is domain-separated BLAKE2b-256:
+//!
+//! ```text
+//! empty   = [0; 32]
+//! leaf    = H("power_house:v1:smt" || 0x00 || key || value)
+//! node    = H("power_house:v1:smt" || 0x01 || left || right)
+//! ```
+//!
+//! A subtree holding a single leaf is represented by that leaf's hash rather
+//! than a 256-deep chain of nodes, so the root costs `O(n log n)` hashes and
+//! proofs carry about `log2 n` siblings.  A proof ends where its key's path
+//! reaches an empty subtree or a lone leaf; for non-inclusion that leaf
+//! belongs to another key sharing the path prefix.
+
+use blake2::digest::{consts::U32, Digest};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+type Blake2b256 = blake2::Blake2b<U32>;
+
+/// Key, value and node digests of the tree.
+pub type SmtDigest = [u8; 32];
+
+/// Depth of the tree, in bits of key.
+pub const SMT_DEPTH: usize = 256;
+
+const SMT_DOMAIN: &[u8] = b"power_house:v1:smt";
+const SMT_KEY_DOMAIN: &[u8] = b"power_house:v1:smt-key";
+const SMT_VALUE_DOMAIN: &[u8] = b"power_house:v1:smt-value";
+
+/// Root of an empty subtree of any height.
+const EMPTY: SmtDigest = [0u8; 32];
+
+/// Errors produced while checking a sparse Merkle proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SparseMerkleError {
+    /// A field is not valid hex of the expected length.
+    Encoding(&'static str),
+    /// The proof's structure is inconsistent.
+    Malformed(&'static str),
+    /// The proof recomputes a different root.
+    RootMismatch,
+}
+
+impl fmt::Display for SparseMerkleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Encoding(field) => write!(f, "sparse merkle proof has invalid {field}"),
+            Self::Malformed(reason) => write!(f, "malformed sparse merkle proof: {reason}"),
+            Self::RootMismatch => write!(f, "sparse merkle proof does not match the root"),
+        }
+    }
+}
+
+impl std::error::Error for SparseMerkleError {}
+
+/// Hashes arbitrary key material into a tree key.
+pub fn smt_key(bytes: &[u8]) -> SmtDigest {
+    let mut hasher = Blake2b256::new();
+    hasher.update(SMT_KEY_DOMAIN);
+    hasher.update(bytes);
+    hasher.finalize().into()
+}
+
+/// Hashes an encoded value into a leaf value.
+pub fn smt_value(bytes: &[u8]) -> SmtDigest {
+    let mut hasher = Blake2b256::new();
+    hasher.update(SMT_VALUE_DOMAIN);
+    hasher.update(bytes);
+    hasher.finalize().into()
+}
+
+fn hash_leaf(key: &SmtDigest, value: &SmtDigest) -> SmtDigest {
+    let mut hasher = Blake2b256::new();
+    hasher.update(SMT_DOMAIN);
+    hasher.update([0u8]);
+    hasher.update(key);
+    hasher.update(value);
+    hasher.finalize().into()
+}
+
+fn hash_node(left: &SmtDigest, right: &SmtDigest) -> SmtDigest {
+    let mut hasher = Blake2b256::new();
+    hasher.update(SMT_DOMAIN);
+    hasher.update([1u8]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Non-empty leaf as `(key, value)`.
+type Leaf = (SmtDigest, SmtDigest);
+
+fn key_bit(key: &SmtDigest, depth: usize) -> bool {
+    key[depth / 8] & (0x80 >> (depth % 8)) != 0
+}
+
+/// Map from 256-bit keys to 256-bit values committed by a single root.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SparseMerkleTree {
+    leaves: BTreeMap<SmtDigest, SmtDigest>,
+}
+
+impl SparseMerkleTree {
+    /// Creates an empty tree.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets `key` to `value`, returning the previous value.
+    pub fn insert(&mut self, key: SmtDigest, value: SmtDigest) -> Option<SmtDigest> {
+        self.leaves.insert(key, value)
+    }
+
+    /// Clears `key`, returning its value.
+    pub fn remove(&mut self, key: &SmtDigest) -> Option<SmtDigest> {
+        self.leaves.remove(key)
+    }
+
+    /// Value stored under `key`.
+    pub fn get(&self, key: &SmtDigest) -> Option<&SmtDigest> {
+        self.leaves.get(key)
+    }
+
+    /// Number of non-empty leaves.
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// Returns `true` when every leaf is empty.
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Root committing to every key-value pair.
+    pub fn root(&self) -> SmtDigest {
+        subtree_root(&self.sorted_leaves(), 0)
+    }
+
+    /// Proves the value of `key`, or its absence.
+    pub fn prove(&self, key: &SmtDigest) -> SparseMerkleProof {
+        let leaves = self.sorted_leaves();
+        let mut slice = leaves.as_slice();
+        let mut siblings = Vec::new();
+        let mut depth = 0;
+        while slice.len() > 1 {
+            let (left, right) = split_at_bit(slice, depth);
+            let (path, other) = if key_bit(key, depth) {
+                (right, left)
+            } else {
+                (left, right)
+            };
+            siblings.push(hex::encode(subtree_root(other, depth + 1)));
+            slice = path;
+            depth += 1;
+        }
+        let (value, other_leaf) = match slice {
+            [(leaf_key, value)] if leaf_key == key => (Some(hex::encode(value)), None),
+            [(leaf_key, value)] => (
+                None,
+                Some(SparseMerkleLeaf {
+                    key: hex::encode(leaf_key),
+                    value: hex::encode(value),
+                }),
+            ),
+            _ => (None, None),
+        };
+        SparseMerkleProof {
+            key: hex::encode(key),
+            value,
+            other_leaf,
+            siblings,
+        }
+    }
+
+    fn sorted_leaves(&self) -> Vec<Leaf> {
+        self.leaves.iter().map(|(k, v)| (*k, *v)).collect()
+    }
+}
+
+/// Splits leaves sharing a `depth`-bit prefix by the bit at `depth`.
+fn split_at_bit(leaves: &[Leaf], depth: usize) -> (&[Leaf], &[Leaf]) {
+    leaves.split_at(leaves.partition_point(|(key, _)| !key_bit(key, depth)))
+}
+
+/// Root of the subtree at `depth` holding `leaves`, which share the path
+/// prefix and are sorted by key.
+fn subtree_root(leaves: &[Leaf], depth: usize) -> SmtDigest {
+    match leaves {
+        [] => EMPTY,
+        [(key, value)] => hash_leaf(key, value),
+        _ => {
+            let (left, right) = split_at_bit(leaves, depth);
+            hash_node(
+                &subtree_root(left, depth + 1),
+                &subtree_root(right, depth + 1),
+            )
+        }
+    }
+}
+
+/// Leaf of another key met by a non-inclusion proof.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SparseMerkleLeaf {
+    /// Hex key of the leaf.
+    pub key: String,
+    /// Hex value of the leaf.
+    pub value: String,
+}
+
+/// Inclusion or non-inclusion proof for one key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SparseMerkleProof {
+    /// Hex tree key.
+    pub key: String,
+    /// Hex value under the key; `None` proves the key is absent.
+    pub value: Option<String>,
+    /// Lone leaf of another key ending the path, for non-inclusion.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub other_leaf: Option<SparseMerkleLeaf>,
+    /// Hex siblings along the key's path, root first.
+    pub siblings: Vec<String>,
+}
+
+impl SparseMerkleProof {
+    /// Recomputes the root this proof commits to.
+    pub fn compute_root(&self) -> Result<SmtDigest, SparseMerkleError> {
+        let key = decode_digest(&self.key, "key")?;
+        let depth = self.siblings.len();
+        if depth > SMT_DEPTH {
+            return Err(SparseMerkleError::Malformed("path longer than the tree"));
+        }
+        let mut node = match (&self.value, &self.other_leaf) {
+            (Some(_), Some(_)) => {
+                return Err(SparseMerkleError::Malformed(
+                    "inclusion proof carries another leaf",
+                ))
+            }
+            (Some(value), None) => hash_leaf(&key, &decode_digest(value, "value")?),
+            (None, Some(leaf)) => {
+                let other = decode_digest(&leaf.key, "leaf key")?;
+                if other == key || (0..depth).any(|d| key_bit(&other, d) != key_bit(&key, d)) {
+                    return Err(SparseMerkleError::Malformed(
+                        "other leaf is not on the key's path",
+                    ));
+                }
+                hash_leaf(&other, &decode_digest(&leaf.value, "leaf value")?)
+            }
+            (None, None) => EMPTY,
+        };
+        for (d, sibling) in self.siblings.iter().enumerate().rev() {
+            let sibling = decode_digest(sibling, "sibling")?;
+            node = if key_bit(&key, d) {
+                hash_node(&sibling, &node)
+            } else {
+                hash_node(&node, &sibling)
+            };
+        }
+        Ok(node)
+    }
+
+    /// Checks the proof against `root`.
+    pub fn verify(&self, root: &SmtDigest) -> Result<(), SparseMerkleError> {
+        if self.compute_root()? == *root {
+            Ok(())
+        } else {
+            Err(SparseMerkleError::RootMismatch)
+        }
+    }
+}
+
+fn decode_digest(input: &str, field: &'static str) -> Result<SmtDigest, SparseMerkleError> {
+    let bytes = hex::decode(input).map_err(|_| SparseMerkleError::Encoding(field))?;
+    bytes
+        .try_into()
+        .map_err(|_| SparseMerkleError::Encoding(field))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proves_inclusion_and_non_inclusion() {
+        let mut tree = SparseMerkleTree::new();
+        assert_eq!(tree.root(), EMPTY);
+        let keys: Vec<_> = (0u8..20).map(|i| smt_key(&[i])).collect();
+        for (i, key) in keys.iter().enumerate() {
+            tree.insert(*key, smt_value(&[i as u8, 1]));
+        }
+        let root = tree.root();
+
+        for key in &keys {
+            let proof = tree.prove(key);
+            assert!(proof.value.is_some());
+            assert_eq!(proof.verify(&root), Ok(()));
+        }
+        let absent = tree.prove(&smt_key(b"absent"));
+        assert_eq!(absent.value, None);
+        assert_eq!(absent.verify(&root), Ok(()));
+        assert!(absent.siblings.len() < 16);
+        let json = serde_json::to_string(&absent).unwrap();
+        let parsed: SparseMerkleProof = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.verify(&root), Ok(()));
+
+        let mut forged = absent.clone();
+        forged.value = Some(hex::encode(smt_value(b"forged")));
+        forged.other_leaf = None;
+        assert_eq!(forged.verify(&root), Err(SparseMerkleError::RootMismatch));
+        let mut hidden = tree.prove(&keys[3]);
+        hidden.value = None;
+        assert_eq!(hidden.verify(&root), Err(SparseMerkleError::RootMismatch));
+        let mut deeper = tree.prove(&keys[4]);
+        deeper.siblings.push(hex::encode(EMPTY));
+        assert_eq!(deeper.verify(&root), Err(SparseMerkleError::RootMismatch));
+
+        let mut rebuilt = SparseMerkleTree::new();
+        for (i, key) in keys.iter().enumerate().rev() {
+            rebuilt.insert(*key, smt_value(&[i as u8, 1]));
+        }
+        assert_eq!(rebuilt.root(), root);
+        rebuilt.insert(keys[0], smt_value(b"changed"));
+        assert_ne!(rebuilt.root(), root);
+        rebuilt.insert(keys[0], smt_value(&[0, 1]));
+        rebuilt.remove(&keys[1]);
+        assert_eq!(rebuilt.prove(&keys[1]).verify(&rebuilt.root()), Ok(()));
+        assert_ne!(rebuilt.root(), root);
+    }
+}
@@ -0,0 +1,375 @@
+//! Per-application constraints on structured statement parameters.
+//!
+//! A [`StatementSchema`] names the claim types an application issues and the
+//! parameters each statement must carry, with a JSON-schema-like type per
+//! parameter.  Once a schema is registered with
+//! [`ProofLedger::register_statement_schema`](crate::ProofLedger::register_statement_schema),
+//! a structured statement for that application that breaks it is recorded as
+//! rejected with a [`StatementViolation`] instead of being verified.  Legacy
+//! statements and applications without a schema are not checked.
+//!
+//! Schemas are plain JSON:
+//!
+//! ```json
+//! {
+//!   "application_id": "power_house.attestation",
+//!   "claim_types": ["column_sum"],
+//!   "parameters": {
+//!     "dataset": { "type": "hex", "bytes": 32, "required": true },
+//!     "rows": { "type": "uint", "min": 1, "required": true },
+//!     "column": { "type": "string", "max_length": 64 }
+//!   }
+//! }
+//! ```
+
+use crate::Statement;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt, fs, path::Path};
+
+/// Type a parameter value must satisfy.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ParameterType {
+    /// Any string, optionally bounded in length (bytes).
+    String {
+        /// Longest accepted value.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_length: Option<usize>,
+    },
+    /// Decimal unsigned integer, optionally bounded.
+    Uint {
+        /// Smallest accepted value.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min: Option<u64>,
+        /// Largest accepted value.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max: Option<u64>,
+    },
+    /// Lowercase hex, optionally of an exact decoded length.
+    Hex {
+        /// Decoded length in bytes.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        bytes: Option<usize>,
+    },
+    /// One of a fixed set of values.
+    Enum {
+        /// Accepted values.
+        values: Vec<String>,
+    },
+}
+
+impl ParameterType {
+    fn check(&self, value: &str) -> Result<(), String> {
+        match self {
+            Self::String { max_length } => match max_length {
+                Some(max) if value.len() > *max => Err(format!("longer than {max} bytes")),
+                _ => Ok(()),
+            },
+            Self::Uint { min, max } => {
+                if value.is_empty() || !value.bytes().all(|byte| byte.is_ascii_digit()) {
+                    return Err("not a decimal integer".to_string());
+                }
+                let parsed: u64 = value
+                    .parse()
+                    .map_err(|_| "does not fit in 64 bits".to_string())?;
+                if min.is_some_and(|min| parsed < min) || max.is_some_and(|max| parsed > max) {
+                    return Err(format!(
+                        "outside [{}, {}]",
+                        min.unwrap_or(0),
+                        max.unwrap_or(u64::MAX)
+                    ));
+                }
+                Ok(())
+            }
+            Self::Hex { bytes } => {
+                if !value.len().is_multiple_of(2)
+                    || !value
+                        .bytes()
+                        .all(|byte| byte.is_ascii_digit() || (b'a'..=b'f').contains(&byte))
+                {
+                    return Err("not lowercase hex".to_string());
+                }
+                match bytes {
+                    Some(bytes) if value.len() != bytes * 2 => {
+                        Err(format!("expected {bytes} bytes"))
+                    }
+                    _ => Ok(()),
+                }
+            }
+            Self::Enum { values } => {
+                if values.iter().any(|allowed| allowed == value) {
+                    Ok(())
+                } else {
+                    Err(format!("not one of {}", values.join(", ")))
+                }
+            }
+        }
+    }
+}
+
+/// Constraint on one named parameter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParameterRule {
+    /// Accepted values.
+    #[serde(flatten)]
+    pub kind: ParameterType,
+    /// Whether every statement must carry the parameter.
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// Parameter constraints for the statements of one application.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatementSchema {
+    /// Application the schema applies to.
+    pub application_id: String,
+    /// Claim types the application issues; empty accepts any.
+    #[serde(default)]
+    pub claim_types: Vec<String>,
+    /// Rules keyed by parameter name.
+    #[serde(default)]
+    pub parameters: BTreeMap<String, ParameterRule>,
+    /// Whether parameters without a rule are accepted.
+    #[serde(default)]
+    pub additional_parameters: bool,
+}
+
+impl StatementSchema {
+    /// Checks `statement` against the schema.
+    pub fn validate(&self, statement: &Statement) -> Result<(), StatementViolation> {
+        if !self.claim_types.is_empty() && !self.claim_types.contains(&statement.claim_type) {
+            return Err(StatementViolation::UnknownClaimType {
+                application_id: self.application_id.clone(),
+                claim_type: statement.claim_type.clone(),
+            });
+        }
+        for (name, rule) in &self.parameters {
+            match statement.parameters.get(name) {
+                Some(value) => rule.kind.check(value).map_err(|reason| {
+                    StatementViolation::InvalidParameter {
+                        name: name.clone(),
+                        reason,
+                    }
+                })?,
+                None if rule.required => {
+                    return Err(StatementViolation::MissingParameter { name: name.clone() })
+                }
+                None => {}
+            }
+        }
+        if !self.additional_parameters {
+            if let Some(name) = statement
+                .parameters
+                .keys()
+                .find(|name| !self.parameters.contains_key(*name))
+            {
+                return Err(StatementViolation::UnexpectedParameter { name: name.clone() });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Why a statement does not satisfy its application's schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatementViolation {
+    /// The claim type is not listed by the schema.
+    UnknownClaimType {
+        /// Application of the statement.
+        application_id: String,
+        /// Offending claim type.
+        claim_type: String,
+    },
+    /// A required parameter is absent.
+    MissingParameter {
+        /// Parameter name.
+        name: String,
+    },
+    /// A parameter has no rule and the schema forbids extras.
+    UnexpectedParameter {
+        /// Parameter name.
+        name: String,
+    },
+    /// A parameter value breaks its rule.
+    InvalidParameter {
+        /// Parameter name.
+        name: String,
+        /// What was wrong with the value.
+        reason: String,
+    },
+}
+
+impl fmt::Display for StatementViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownClaimType {
+                application_id,
+                claim_type,
+            } => write!(f, "{application_id} does not issue `{claim_type}` claims"),
+            Self::MissingParameter { name } => write!(f, "missing parameter `{name}`"),
+            Self::UnexpectedParameter { name } => write!(f, "unexpected parameter `{name}`"),
+            Self::InvalidParameter { name, reason } => {
+                write!(f, "parameter `{name}` is invalid: {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for StatementViolation {}
+
+/// Statement schemas keyed by application id.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatementSchemaRegistry {
+    schemas: BTreeMap<String, StatementSchema>,
+}
+
+impl StatementSchemaRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a JSON array of schemas.
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        let schemas: Vec<StatementSchema> =
+            serde_json::from_str(text).map_err(|err| format!("invalid schema list: {err}"))?;
+        let mut registry = Self::new();
+        for schema in schemas {
+            let application_id = schema.application_id.clone();
+            if registry.register(schema).is_some() {
+                return Err(format!("duplicate schema for {application_id}"));
+            }
+        }
+        Ok(registry)
+    }
+
+    /// Reads a JSON array of schemas from `path`.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text =
+            fs::read_to_string(path).map_err(|err| format!("read {}: {err}", path.display()))?;
+        Self::from_json(&text)
+    }
+
+    /// Registers `schema`, returning the one it replaces.
+    pub fn register(&mut self, schema: StatementSchema) -> Option<StatementSchema> {
+        self.schemas.insert(schema.application_id.clone(), schema)
+    }
+
+    /// Schema registered for `application_id`.
+    pub fn get(&self, application_id: &str) -> Option<&StatementSchema> {
+        self.schemas.get(application_id)
+    }
+
+    /// Number of registered schemas.
+    pub fn len(&self) -> usize {
+        self.schemas.len()
+    }
+
+    /// Returns `true` when no schema is registered.
+    pub fn is_empty(&self) -> bool {
+        self.schemas.is_empty()
+    }
+
+    /// Checks `statement` against its application's schema, if one is
+    /// registered.
+    pub fn validate(&self, statement: &Statement) -> Result<(), StatementViolation> {
+        match statement
+            .application_id()
+            .and_then(|application_id| self.get(application_id))
+        {
+            Some(schema) => schema.validate(statement),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Field, GeneralSumProof, MultilinearPolynomial, Proof, ProofKind, ProofLedger,
+        VerificationRejection,
+    };
+
+    const SCHEMAS: &str = r#"[{
+        "application_id": "power_house.attestation",
+        "claim_types": ["column_sum"],
+        "parameters": {
+            "dataset": { "type": "hex", "bytes": 2, "required": true },
+            "rows": { "type": "uint", "min": 1, "required": true },
+            "mode": { "type": "enum", "values": ["exact", "mod_p"] }
+        }
+    }]"#;
+
+    #[test]
+    fn ledger_rejects_statements_that_break_their_schema() {
+        let registry = StatementSchemaRegistry::from_json(SCHEMAS).unwrap();
+        let valid = Statement::structured("power_house.attestation", "column_sum")
+            .with_parameter("dataset", "beef")
+            .with_parameter("rows", "4");
+        registry.validate(&valid).unwrap();
+        registry
+            .validate(&Statement::structured("other.app", "anything"))
+            .unwrap();
+        registry.validate(&Statement::new("legacy text")).unwrap();
+
+        let cases = [
+            (
+                Statement::structured("power_house.attestation", "row_count"),
+                StatementViolation::UnknownClaimType {
+                    application_id: "power_house.attestation".to_string(),
+                    claim_type: "row_count".to_string(),
+                },
+            ),
+            (
+                valid.clone().with_parameter("rows", "0"),
+                StatementViolation::InvalidParameter {
+                    name: "rows".to_string(),
+                    reason: format!("outside [1, {}]", u64::MAX),
+                },
+            ),
+            (
+                valid.clone().with_parameter("dataset", "BEEF"),
+                StatementViolation::InvalidParameter {
+                    name: "dataset".to_string(),
+                    reason: "not lowercase hex".to_string(),
+                },
+            ),
+            (
+                valid.clone().with_parameter("note", "hi"),
+                StatementViolation::UnexpectedParameter {
+                    name: "note".to_string(),
+                },
+            ),
+        ];
+        for (statement, violation) in &cases {
+            assert_eq!(registry.validate(statement).as_ref(), Err(violation));
+        }
+
+        let field = Field::new(101);
+        let polynomial = MultilinearPolynomial::from_evaluations(1, vec![2, 3]);
+        let proof = || Proof {
+            kind: ProofKind::General {
+                polynomial: polynomial.clone(),
+                proof: GeneralSumProof::prove(&polynomial, &field),
+            },
+            data: Vec::new(),
+        };
+        let mut ledger = ProofLedger::new();
+        ledger.set_statement_schemas(registry);
+        ledger.submit(valid.clone().with_parameter("mode", "approx"), proof());
+        let entry = ledger.entries().last().unwrap();
+        assert!(!entry.accepted);
+        assert_eq!(
+            entry.rejection,
+            Some(VerificationRejection::InvalidStatement(
+                StatementViolation::InvalidParameter {
+                    name: "mode".to_string(),
+                    reason: "not one of exact, mod_p".to_string(),
+                }
+            ))
+        );
+        ledger.submit(valid.with_parameter("mode", "exact"), proof());
+        assert!(ledger.entries().last().unwrap().accepted);
+    }
+}
@@ -0,0 +1,282 @@
+//! Off-thread verification for [`ProofLedger`] submissions.
+//!
+//! [`ProofLedger::submit`] verifies on the caller's thread, so one large proof
+//! stalls whatever loop is feeding the ledger.  A [`SubmissionQueue`] owns the
+//! ledger and a pool of worker threads: [`SubmissionQueue::enqueue`] returns a
+//! ticket immediately, workers verify in parallel, and finished verdicts are
+//! recorded strictly in ticket order, so the ledger ends up byte-identical to
+//! one fed by `submit` in the same order.  Results arrive through
+//! [`ProofLedger::subscribe`] events carrying the ticket.
+//!
+//! Statement schemas, limits, and the verification cache are consulted when a
+//! proof is enqueued; proofs they settle never reach a worker.
+
+use crate::julian::{verify_detached, Verdict};
+use crate::{EntrySubmission, LedgerEvent, Proof, ProofLedger, Statement, TranscriptDigest};
+use std::{
+    collections::BTreeMap,
+    panic::{self, AssertUnwindSafe},
+    sync::{mpsc, Arc, Mutex},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+struct Job {
+    ticket: u64,
+    proof: Proof,
+    prover: Option<Vec<u8>>,
+    time_budget: Option<Duration>,
+    cache_digest: Option<TranscriptDigest>,
+}
+
+struct Pending {
+    statement: Statement,
+    submission: Option<EntrySubmission>,
+    /// Proof and verdict, once known.
+    verified: Option<(Proof, Verdict)>,
+}
+
+/// Ledger front end that verifies submissions on a worker pool.
+pub struct SubmissionQueue {
+    ledger: ProofLedger,
+    jobs: Option<mpsc::Sender<Job>>,
+    results: mpsc::Receiver<(u64, Proof, Verdict)>,
+    workers: Vec<JoinHandle<()>>,
+    pending: BTreeMap<u64, Pending>,
+    next_ticket: u64,
+    next_record: u64,
+}
+
+impl SubmissionQueue {
+    /// Takes ownership of `ledger` and starts `workers` verifier threads (at
+    /// least one).
+    pub fn new(ledger: ProofLedger, workers: usize) -> Self {
+        let (jobs, job_receiver) = mpsc::channel::<Job>();
+        let (result_sender, results) = mpsc::channel();
+        let job_receiver = Arc::new(Mutex::new(job_receiver));
+        let workers = (0..workers.max(1))
+            .map(|_| {
+                let job_receiver = Arc::clone(&job_receiver);
+                let result_sender = result_sender.clone();
+                thread::spawn(move || loop {
+                    let job = match job_receiver.lock() {
+                        Ok(receiver) => receiver.recv(),
+                        Err(_) => return,
+                    };
+                    let Ok(job) = job else {
+                        return;
+                    };
+                    let verdict = panic::catch_unwind(AssertUnwindSafe(|| {
+                        verify_detached(
+                            &job.proof.kind,
+                            job.prover.as_deref(),
+                            job.time_budget,
+                            job.cache_digest,
+                        )
+                    }))
+                    .unwrap_or_else(|_| Verdict::failed());
+                    if result_sender
+                        .send((job.ticket, job.proof, verdict))
+                        .is_err()
+                    {
+                        return;
+                    }
+                })
+            })
+            .collect();
+        Self {
+            ledger,
+            jobs: Some(jobs),
+            results,
+            workers,
+            pending: BTreeMap::new(),
+            next_ticket: 0,
+            next_record: 0,
+        }
+    }
+
+    /// Queues a proof for verification and returns its ticket.
+    pub fn enqueue(&mut self, statement: Statement, proof: Proof) -> u64 {
+        self.enqueue_entry(statement, proof, None)
+    }
+
+    /// Queues a proof attributed to `submission`, like
+    /// [`ProofLedger::submit_attributed`].
+    pub fn enqueue_attributed(
+        &mut self,
+        statement: Statement,
+        proof: Proof,
+        submission: EntrySubmission,
+    ) -> u64 {
+        self.enqueue_entry(statement, proof, Some(submission))
+    }
+
+    fn enqueue_entry(
+        &mut self,
+        statement: Statement,
+        proof: Proof,
+        submission: Option<EntrySubmission>,
+    ) -> u64 {
+        let ticket = self.next_ticket;
+        self.next_ticket += 1;
+        let mut pending = Pending {
+            statement,
+            submission,
+            verified: None,
+        };
+        let prover = self.ledger.bound_prover(pending.submission.as_ref());
+        match self
+            .ledger
+            .precheck(&pending.statement, &proof.kind, prover)
+        {
+            Ok(verdict) => pending.verified = Some((proof, verdict)),
+            Err(cache_digest) => {
+                let job = Job {
+                    ticket,
+                    proof,
+                    prover: prover.map(<[u8]>::to_vec),
+                    time_budget: self.ledger.verification_limits().time_budget,
+                    cache_digest,
+                };
+                self.jobs
+                    .as_ref()
+                    .expect("job channel is open until drop")
+                    .send(job)
+                    .expect("verifier workers are running");
+            }
+        }
+        self.pending.insert(ticket, pending);
+        self.poll();
+        ticket
+    }
+
+    /// Records every verdict that is ready and next in ticket order without
+    /// blocking.  Returns the number of entries recorded.
+    pub fn poll(&mut self) -> usize {
+        while let Ok((ticket, proof, verdict)) = self.results.try_recv() {
+            self.accept_result(ticket, proof, verdict);
+        }
+        self.record_ready()
+    }
+
+    /// Blocks until every queued submission has been recorded.
+    pub fn flush(&mut self) {
+        self.record_ready();
+        while !self.pending.is_empty() {
+            let (ticket, proof, verdict) = self
+                .results
+                .recv()
+                .expect("verifier workers stopped with submissions pending");
+            self.accept_result(ticket, proof, verdict);
+            self.record_ready();
+        }
+    }
+
+    /// Number of submissions not yet recorded.
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Returns a receiver of [`LedgerEvent`]s for entries recorded from now on.
+    pub fn subscribe(&mut self) -> mpsc::Receiver<LedgerEvent> {
+        self.ledger.subscribe()
+    }
+
+    /// The ledger as of the last recorded submission.
+    pub fn ledger(&self) -> &ProofLedger {
+        &self.ledger
+    }
+
+    /// Waits for pending submissions and returns the ledger.
+    pub fn into_ledger(mut self) -> ProofLedger {
+        self.flush();
+        std::mem::take(&mut self.ledger)
+    }
+
+    fn accept_result(&mut self, ticket: u64, proof: Proof, verdict: Verdict) {
+        if let Some(pending) = self.pending.get_mut(&ticket) {
+            pending.verified = Some((proof, verdict));
+        }
+    }
+
+    fn record_ready(&mut self) -> usize {
+        let mut recorded = 0;
+        while self
+            .pending
+            .get(&self.next_record)
+            .is_some_and(|pending| pending.verified.is_some())
+        {
+            let pending = self
+                .pending
+                .remove(&self.next_record)
+                .expect("pending entry checked above");
+            let (proof, verdict) = pending.verified.expect("verdict checked above");
+            self.ledger.record_verdict(
+                pending.statement,
+                proof,
+                pending.submission,
+                None,
+                verdict,
+                Some(self.next_record),
+            );
+            self.next_record += 1;
+            recorded += 1;
+        }
+        recorded
+    }
+}
+
+impl Drop for SubmissionQueue {
+    fn drop(&mut self) {
+        self.jobs = None;
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Field, GeneralSumProof, MultilinearPolynomial, ProofKind};
+
+    fn proof(seed: u64, tamper: bool) -> Proof {
+        let field = Field::new(101);
+        let polynomial = MultilinearPolynomial::from_evaluations(3, (seed..seed + 8).collect());
+        let mut proof = GeneralSumProof::prove(&polynomial, &field);
+        if tamper {
+            proof.final_evaluation += 1;
+        }
+        Proof {
+            kind: ProofKind::General { polynomial, proof },
+            data: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn queued_submissions_match_synchronous_ledger() {
+        let mut expected = ProofLedger::new();
+        let mut queue = SubmissionQueue::new(ProofLedger::new(), 3);
+        let events = queue.subscribe();
+        let mut tickets = Vec::new();
+        for seed in 0..12 {
+            let statement = Statement::new(format!("proof {seed}"));
+            expected.submit(statement.clone(), proof(seed, seed % 5 == 0));
+            tickets.push(queue.enqueue(statement, proof(seed, seed % 5 == 0)));
+        }
+        assert_eq!(tickets, (0..12).collect::<Vec<_>>());
+        let ledger = queue.into_ledger();
+        assert_eq!(ledger.anchor(), expected.anchor());
+        let accepted: Vec<bool> = ledger.entries().iter().map(|e| e.accepted).collect();
+        let expected_accepted: Vec<bool> = expected.entries().iter().map(|e| e.accepted).collect();
+        assert_eq!(accepted, expected_accepted);
+
+        let events: Vec<LedgerEvent> = events.try_iter().collect();
+        assert_eq!(events.len(), 12);
+        for (ticket, event) in events.iter().enumerate() {
+            assert_eq!(event.ticket, Some(ticket as u64));
+            assert_eq!(event.index, ticket + 1);
+            assert_eq!(event.accepted, ticket % 5 != 0);
+        }
+    }
+}
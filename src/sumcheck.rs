@@ -24,10 +24,12 @@
 //! embedding into a proof ledger.  The soundness error decreases
 //! exponentially in the parameter `k`.
 
-use crate::{field::Field, prng::derive_many_mod_p};
+use crate::field::Field;
 use crate::{MultilinearPolynomial, StreamingPolynomial, Transcript};
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
 use rayon::prelude::*;
+use std::convert::Infallible;
+use std::fmt;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -101,10 +103,7 @@ impl SumClaim {
         let g1_a = field.sub(g1_1, g1_0);
         let g1_b = g1_0;
         // Derive r1 deterministically from the base transcript.
-        let base_transcript = [p, s, g1_a, g1_b, 0u64, 0u64, k as u64];
-        // Use a domain tag specific to the sum-check protocol.
-        let r1_values = derive_many_mod_p(p, b"power_house:v1:sumcheck:r1", &base_transcript, 1);
-        let r1 = r1_values[0];
+        let r1 = demo_r1(field, &[p, s, g1_a, g1_b, 0u64, 0u64, k as u64]);
         // Compute S1 = g1(r1) mod p.
         let _s1 = field.add(field.mul(g1_a, r1), g1_b);
         // Compute g2 coefficients by sampling g2 at 0 and 1.
@@ -141,18 +140,18 @@ impl SumClaim {
             return false;
         }
         // Derive r1 from base transcript.
-        let base_transcript = [
-            self.p,
-            self.claimed_sum,
-            self.g1_a,
-            self.g1_b,
-            0u64,
-            0u64,
-            self.k as u64,
-        ];
-        let r1_values =
-            derive_many_mod_p(self.p, b"power_house:v1:sumcheck:r1", &base_transcript, 1);
-        let r1 = r1_values[0];
+        let r1 = demo_r1(
+            &field,
+            &[
+                self.p,
+                self.claimed_sum,
+                self.g1_a,
+                self.g1_b,
+                0u64,
+                0u64,
+                self.k as u64,
+            ],
+        );
         // S1 = g1(r1)
         let s1 = field.add(field.mul(self.g1_a, r1), self.g1_b);
         // Check 2: g2(0) + g2(1) == S1
@@ -164,7 +163,8 @@ impl SumClaim {
         }
         // Final: derive r2_i challenges.
         // Transcript includes all public data.
-        let transcript = [
+        let mut transcript = Transcript::new(DEMO_R2_DOMAIN);
+        transcript.append_slice(&[
             self.p,
             self.claimed_sum,
             self.g1_a,
@@ -172,9 +172,9 @@ impl SumClaim {
             self.g2_a,
             self.g2_b,
             self.k as u64,
-        ];
-        let r2s = derive_many_mod_p(self.p, b"power_house:v1:sumcheck:r2", &transcript, self.k);
-        for &r2 in &r2s {
+        ]);
+        for _ in 0..self.k {
+            let r2 = transcript.challenge_scalar(b"r2", &field);
             // Compute g2(r2).
             let left = field.add(field.mul(self.g2_a, r2), self.g2_b);
             // Compute f(r1, r2).
@@ -187,9 +187,26 @@ impl SumClaim {
     }
 }
 
+// Tags carry the challenge-scheme version; v2 (v3 for the general transcript)
+// marks rejection-sampled derivation.
+const DEMO_R1_DOMAIN: &[u8] = b"power_house:v2:sumcheck:r1";
+const DEMO_R2_DOMAIN: &[u8] = b"power_house:v2:sumcheck:r2";
 /// Domain tag used for the generalized sum-check Fiat–Shamir transcript.
-pub(crate) const GENERAL_SUMCHECK_DOMAIN: &[u8] = b"power_house:v2:sumcheck";
-const SEEDED_AFFINE_DOMAIN: &[u8] = b"power_house:v1:seeded-affine";
+pub(crate) const GENERAL_SUMCHECK_DOMAIN: &[u8] = b"power_house:v3:sumcheck";
+const SEEDED_AFFINE_DOMAIN: &[u8] = b"power_house:v2:seeded-affine";
+/// Label binding a prover's public key into the general transcript.
+const PROVER_LABEL: &[u8] = b"prover";
+
+/// Starts the general transcript, bound to `prover` when one is given.
+fn general_transcript(p: u64, num_vars: usize, prover: Option<&[u8]>) -> Transcript {
+    let mut transcript = Transcript::new(GENERAL_SUMCHECK_DOMAIN);
+    if let Some(prover) = prover {
+        transcript.append_message(PROVER_LABEL, prover);
+    }
+    transcript.append(p);
+    transcript.append(num_vars as u64);
+    transcript
+}
 
 /// Generalized non-interactive sum-check claim for multilinear polynomials.
 #[derive(Debug, Clone)]
@@ -236,8 +253,65 @@ pub struct ProofStats {
     pub total_duration: Duration,
     /// Duration of each folding round.
     pub round_durations: Vec<Duration>,
+    /// High-water mark of prover working memory (folding layer plus
+    /// streaming weights), in bytes.
+    pub peak_memory_bytes: usize,
 }
 
+/// Reusable buffers for [`GeneralSumProof::prove_with_budget`].
+///
+/// The dense prover folds its layer in place, so one scratch buffer serves
+/// every round; keeping the scratch across proofs also avoids reallocating
+/// it per proof.
+#[derive(Debug, Default)]
+pub struct ProverScratch {
+    layer: Vec<u64>,
+    weights: Vec<u64>,
+}
+
+impl ProverScratch {
+    /// Creates an empty scratch arena.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a scratch arena with room for a `2^num_vars` folding layer.
+    pub fn with_num_vars(num_vars: usize) -> Self {
+        Self {
+            layer: Vec::with_capacity(1usize << num_vars),
+            weights: Vec::new(),
+        }
+    }
+
+    /// Bytes currently reserved by the arena.
+    pub fn capacity_bytes(&self) -> usize {
+        (self.layer.capacity() + self.weights.capacity()) * WORD_BYTES
+    }
+}
+
+/// The memory budget cannot accommodate even the streaming prover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryBudgetError {
+    /// Smallest working memory the prover needs, in bytes.
+    pub required: usize,
+    /// Configured budget, in bytes.
+    pub budget: usize,
+}
+
+impl fmt::Display for MemoryBudgetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "sum-check prover needs at least {} bytes of working memory, budget is {}",
+            self.required, self.budget
+        )
+    }
+}
+
+impl std::error::Error for MemoryBudgetError {}
+
+const WORD_BYTES: usize = std::mem::size_of::<u64>();
+
 /// A single link in a chained proof, referencing its parent evaluation.
 #[derive(Debug, Clone)]
 pub struct ChainLink {
@@ -309,7 +383,8 @@ impl GeneralSumClaim {
         poly: &MultilinearPolynomial,
         field: &Field,
     ) -> Option<GeneralSumTrace> {
-        verify_general_sum(self, poly, field)
+        let _span = crate::profile::span("GeneralSumProof::verify");
+        infallible(verify_general_sum(self, poly, field, None, uninterrupted))
     }
 
     /// Streaming variant of [`Self::verify_with_trace`].
@@ -318,7 +393,14 @@ impl GeneralSumClaim {
         poly: &StreamingPolynomial,
         field: &Field,
     ) -> Option<GeneralSumTrace> {
-        verify_general_sum_streaming(self, poly, field)
+        let _span = crate::profile::span("GeneralSumProof::verify_streaming");
+        infallible(verify_general_sum_streaming(
+            self,
+            poly,
+            field,
+            None,
+            uninterrupted,
+        ))
     }
 
     /// Verifies a closed-form proof for a constant multilinear polynomial.
@@ -386,14 +468,72 @@ impl GeneralSumProof {
 
     /// Produces a proof together with per-round timing information.
     pub fn prove_with_stats(poly: &MultilinearPolynomial, field: &Field) -> (Self, ProofStats) {
+        Self::prove_with_budget(poly, field, None, &mut ProverScratch::new())
+            .expect("an unbounded prover cannot exceed its budget")
+    }
+
+    /// Produces a proof whose working memory stays within `budget` bytes.
+    ///
+    /// With room for the whole `2^n` layer the prover folds it in place inside
+    /// `scratch`.  Otherwise the first rounds stream over the polynomial's
+    /// evaluations, weighting them by the equality polynomial at the
+    /// challenges drawn so far, until the folded layer and the weight table
+    /// fit together; folding then continues in place.  Each streamed round
+    /// costs a full pass over the evaluations.  The proof is identical to
+    /// [`Self::prove`] whichever path runs.
+    ///
+    /// Returns [`MemoryBudgetError`] when even the smallest split,
+    /// `2^j + 2^(n-j)` words, exceeds the budget.  The polynomial itself is
+    /// not counted.
+    pub fn prove_with_budget(
+        poly: &MultilinearPolynomial,
+        field: &Field,
+        budget: Option<usize>,
+        scratch: &mut ProverScratch,
+    ) -> Result<(Self, ProofStats), MemoryBudgetError> {
+        Self::prove_with_budget_for(poly, field, budget, scratch, None)
+    }
+
+    /// Produces a proof that verifies only for `prover`, whose public key is
+    /// mixed into the transcript before the first challenge.
+    ///
+    /// Check it with [`Self::verify_bound_with_trace`]; a copy replayed under
+    /// another key draws different challenges and fails.
+    pub fn prove_bound(poly: &MultilinearPolynomial, field: &Field, prover: &[u8]) -> Self {
+        Self::prove_with_budget_for(poly, field, None, &mut ProverScratch::new(), Some(prover))
+            .expect("an unbounded prover cannot exceed its budget")
+            .0
+    }
+
+    /// Streaming variant of [`Self::prove_bound`].
+    pub fn prove_streaming_bound(poly: &StreamingPolynomial, field: &Field, prover: &[u8]) -> Self {
+        assert_eq!(poly.modulus(), field.modulus(), "field mismatch");
+        prove_streaming_with_stats_inner(poly.num_vars(), field, poly.evaluator(), Some(prover)).0
+    }
+
+    fn prove_with_budget_for(
+        poly: &MultilinearPolynomial,
+        field: &Field,
+        budget: Option<usize>,
+        scratch: &mut ProverScratch,
+        prover: Option<&[u8]>,
+    ) -> Result<(Self, ProofStats), MemoryBudgetError> {
         let p = field.modulus();
         let num_vars = poly.num_vars();
-        let mut layer = poly.evaluations_mod_p(field);
+        let footprint = |j: usize| ((1usize << j) + (1usize << (num_vars - j))) * WORD_BYTES;
+        let streamed_rounds = match budget {
+            None => 0,
+            Some(budget) => (0..=num_vars)
+                .find(|&j| footprint(j) <= budget)
+                .ok_or_else(|| MemoryBudgetError {
+                    required: (0..=num_vars).map(footprint).min().unwrap_or(0),
+                    budget,
+                })?,
+        };
+        let evals = poly.evaluations();
         let claimed_sum = poly.sum_over_hypercube(field);
 
-        let mut transcript = Transcript::new(GENERAL_SUMCHECK_DOMAIN);
-        transcript.append(p);
-        transcript.append(num_vars as u64);
+        let mut transcript = general_transcript(p, num_vars, prover);
         transcript.append(claimed_sum);
 
         let total_start = Instant::now();
@@ -401,20 +541,70 @@ impl GeneralSumProof {
         let mut challenges = Vec::with_capacity(num_vars);
         let mut round_sums = Vec::with_capacity(num_vars);
         let mut round_durations = Vec::with_capacity(num_vars);
+        let mut peak_words = 0usize;
 
         let mut running_sum = claimed_sum;
 
-        for _ in 0..num_vars {
+        // Streamed rounds: `weights[x] = eq(r, x)` over the folded low bits.
+        let weights = &mut scratch.weights;
+        weights.clear();
+        weights.push(1);
+        for round in 0..streamed_rounds {
+            round_sums.push(running_sum);
+            let round_start = Instant::now();
+            peak_words = peak_words.max(weights.len());
+
+            let mask = weights.len() - 1;
+            let mut sums = [0u64; 2];
+            for (idx, &value) in evals.iter().enumerate() {
+                let bit = (idx >> round) & 1;
+                let term = field.mul(weights[idx & mask], value % p);
+                sums[bit] = field.add(sums[bit], term);
+            }
+            let a = field.sub(sums[1], sums[0]);
+            let b = sums[0];
+            rounds.push((a, b));
+
+            transcript.append(a);
+            transcript.append(b);
+            let r = transcript.challenge(field);
+            challenges.push(r);
+
+            let half = weights.len();
+            weights.extend_from_within(..);
+            for x in 0..half {
+                let high = field.mul(weights[x], r);
+                weights[x] = field.sub(weights[x], high);
+                weights[x + half] = high;
+            }
+            running_sum = field.add(field.mul(a, r), b);
+            round_durations.push(round_start.elapsed());
+        }
+
+        let layer = &mut scratch.layer;
+        layer.clear();
+        if streamed_rounds == 0 {
+            layer.extend(evals.iter().map(|&v| v % p));
+        } else {
+            let mask = weights.len() - 1;
+            layer.resize(evals.len() >> streamed_rounds, 0);
+            for (idx, &value) in evals.iter().enumerate() {
+                let slot = &mut layer[idx >> streamed_rounds];
+                *slot = field.add(*slot, field.mul(weights[idx & mask], value % p));
+            }
+        }
+        peak_words = peak_words.max(weights.len() + layer.len());
+        weights.clear();
+
+        for _ in streamed_rounds..num_vars {
             round_sums.push(running_sum);
             let round_start = Instant::now();
 
             let mut g0_sum = 0u64;
             let mut g1_sum = 0u64;
             for chunk in layer.chunks(2) {
-                let v0 = chunk[0];
-                let v1 = chunk[1];
-                g0_sum = field.add(g0_sum, v0);
-                g1_sum = field.add(g1_sum, v1);
+                g0_sum = field.add(g0_sum, chunk[0]);
+                g1_sum = field.add(g1_sum, chunk[1]);
             }
             let a = field.sub(g1_sum, g0_sum);
             let b = g0_sum;
@@ -425,17 +615,16 @@ impl GeneralSumProof {
             let r = transcript.challenge(field);
             challenges.push(r);
 
-            let mut next_layer = Vec::with_capacity(layer.len() / 2);
+            let half = layer.len() / 2;
             let mut next_sum = 0u64;
-            for chunk in layer.chunks(2) {
-                let v0 = chunk[0];
-                let v1 = chunk[1];
-                let diff = field.sub(v1, v0);
-                let eval = field.add(field.mul(diff, r), v0);
+            for i in 0..half {
+                let v0 = layer[2 * i];
+                let v1 = layer[2 * i + 1];
+                let eval = field.add(field.mul(field.sub(v1, v0), r), v0);
                 next_sum = field.add(next_sum, eval);
-                next_layer.push(eval);
+                layer[i] = eval;
             }
-            layer = next_layer;
+            layer.truncate(half);
             running_sum = next_sum;
             round_durations.push(round_start.elapsed());
         }
@@ -456,15 +645,16 @@ impl GeneralSumProof {
 
         let proof = GeneralSumProof {
             claim,
-            challenges: challenges.clone(),
-            round_sums: round_sums.clone(),
+            challenges,
+            round_sums,
             final_evaluation,
         };
         let stats = ProofStats {
             total_duration: total_start.elapsed(),
             round_durations,
+            peak_memory_bytes: peak_words * WORD_BYTES,
         };
-        (proof, stats)
+        Ok((proof, stats))
     }
 
     /// Streaming variant of [`Self::prove_with_stats`].
@@ -473,7 +663,7 @@ impl GeneralSumProof {
         field: &Field,
     ) -> (Self, ProofStats) {
         assert_eq!(poly.modulus(), field.modulus(), "field mismatch");
-        prove_streaming_with_stats_inner(poly.num_vars(), field, poly.evaluator())
+        prove_streaming_with_stats_inner(poly.num_vars(), field, poly.evaluator(), None)
     }
 
     /// Streaming variant of [`Self::prove_with_stats`] that accepts an evaluator closure.
@@ -486,7 +676,7 @@ impl GeneralSumProof {
         F: Fn(usize) -> u64 + Send + Sync + 'static,
     {
         let eval: Arc<dyn Fn(usize) -> u64 + Send + Sync> = Arc::new(evaluator);
-        prove_streaming_with_stats_inner(num_vars, field, eval)
+        prove_streaming_with_stats_inner(num_vars, field, eval, None)
     }
 
     /// Verifies the proof against the polynomial.
@@ -522,6 +712,73 @@ impl GeneralSumProof {
         field: &Field,
     ) -> Option<GeneralSumTrace> {
         let trace = self.claim.verify_streaming_with_trace(poly, field)?;
+        self.matches(trace)
+    }
+
+    /// Verifies a proof made by [`Self::prove_bound`] for `prover`.
+    pub fn verify_bound_with_trace(
+        &self,
+        poly: &MultilinearPolynomial,
+        field: &Field,
+        prover: &[u8],
+    ) -> Option<GeneralSumTrace> {
+        let _span = crate::profile::span("GeneralSumProof::verify");
+        self.matches(infallible(verify_general_sum(
+            &self.claim,
+            poly,
+            field,
+            Some(prover),
+            uninterrupted,
+        ))?)
+    }
+
+    /// Streaming variant of [`Self::verify_bound_with_trace`].
+    pub fn verify_streaming_bound_with_trace(
+        &self,
+        poly: &StreamingPolynomial,
+        field: &Field,
+        prover: &[u8],
+    ) -> Option<GeneralSumTrace> {
+        let _span = crate::profile::span("GeneralSumProof::verify_streaming");
+        self.matches(infallible(verify_general_sum_streaming(
+            &self.claim,
+            poly,
+            field,
+            Some(prover),
+            uninterrupted,
+        ))?)
+    }
+
+    /// Like [`Self::verify_bound_with_trace`] (or [`Self::verify_with_trace`]
+    /// when `prover` is `None`), but calls `checkpoint` with the round index
+    /// before each sum-check round and stops with its error, so callers can
+    /// abandon wide polynomials cooperatively.
+    pub fn verify_with_trace_checked<E>(
+        &self,
+        poly: &MultilinearPolynomial,
+        field: &Field,
+        prover: Option<&[u8]>,
+        checkpoint: impl FnMut(usize) -> Result<(), E>,
+    ) -> Result<Option<GeneralSumTrace>, E> {
+        let _span = crate::profile::span("GeneralSumProof::verify");
+        let trace = verify_general_sum(&self.claim, poly, field, prover, checkpoint)?;
+        Ok(trace.and_then(|trace| self.matches(trace)))
+    }
+
+    /// Streaming variant of [`Self::verify_with_trace_checked`].
+    pub fn verify_streaming_with_trace_checked<E>(
+        &self,
+        poly: &StreamingPolynomial,
+        field: &Field,
+        prover: Option<&[u8]>,
+        checkpoint: impl FnMut(usize) -> Result<(), E>,
+    ) -> Result<Option<GeneralSumTrace>, E> {
+        let _span = crate::profile::span("GeneralSumProof::verify_streaming");
+        let trace = verify_general_sum_streaming(&self.claim, poly, field, prover, checkpoint)?;
+        Ok(trace.and_then(|trace| self.matches(trace)))
+    }
+
+    fn matches(&self, trace: GeneralSumTrace) -> Option<GeneralSumTrace> {
         if trace.challenges != self.challenges
             || trace.round_sums != self.round_sums
             || trace.final_evaluation != self.final_evaluation
@@ -678,22 +935,20 @@ fn prove_seeded_affine_inner(num_vars: usize, field: &Field, seed: &[u8]) -> Gen
     }
 }
 
-fn seed_to_transcript_words(seed: &[u8]) -> Vec<u64> {
-    let mut words = Vec::with_capacity(1 + seed.len().div_ceil(8));
-    let seed_len = u64::try_from(seed.len()).expect("seed length must fit in transcript word");
-    words.push(seed_len);
-    for chunk in seed.chunks(8) {
-        let mut word = [0u8; 8];
-        word[..chunk.len()].copy_from_slice(chunk);
-        words.push(u64::from_be_bytes(word));
-    }
-    words
+/// Challenge `r1` of the demo protocol, bound to the first-round transcript.
+fn demo_r1(field: &Field, base: &[u64]) -> u64 {
+    let mut transcript = Transcript::new(DEMO_R1_DOMAIN);
+    transcript.append_slice(base);
+    transcript.challenge_scalar(b"r1", field)
 }
 
 fn derive_seeded_affine_parameters(num_vars: usize, field: &Field, seed: &[u8]) -> Vec<u64> {
-    let mut words = seed_to_transcript_words(seed);
-    words.push(u64::try_from(num_vars).expect("num_vars must fit in transcript word"));
-    derive_many_mod_p(field.modulus(), SEEDED_AFFINE_DOMAIN, &words, num_vars + 1)
+    let mut transcript = Transcript::new(SEEDED_AFFINE_DOMAIN);
+    transcript.append_message(b"seed", seed);
+    transcript.append(u64::try_from(num_vars).expect("num_vars must fit in transcript word"));
+    (0..=num_vars)
+        .map(|_| transcript.challenge_scalar(b"parameter", field))
+        .collect()
 }
 
 fn seeded_affine_claimed_sum(
@@ -734,26 +989,25 @@ fn prove_streaming_with_stats_inner(
     num_vars: usize,
     field: &Field,
     evaluator: Arc<dyn Fn(usize) -> u64 + Send + Sync>,
+    prover: Option<&[u8]>,
 ) -> (GeneralSumProof, ProofStats) {
     assert!(num_vars >= 1, "num_vars must be at least 1");
     let p = field.modulus();
     let size = 1usize << num_vars;
     let field = *field;
     let use_parallel = {
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
         {
             const PARALLEL_THRESHOLD: usize = 1 << 16;
             size >= PARALLEL_THRESHOLD && rayon::current_num_threads() > 1
         }
-        #[cfg(target_arch = "wasm32")]
+        #[cfg(any(not(feature = "parallel"), target_arch = "wasm32"))]
         {
             false
         }
     };
 
-    let mut transcript = Transcript::new(GENERAL_SUMCHECK_DOMAIN);
-    transcript.append(p);
-    transcript.append(num_vars as u64);
+    let mut transcript = general_transcript(p, num_vars, prover);
 
     let mut round_sums = Vec::with_capacity(num_vars);
     let mut rounds = Vec::with_capacity(num_vars);
@@ -763,7 +1017,7 @@ fn prove_streaming_with_stats_inner(
     let total_start = Instant::now();
 
     let (claimed_sum, g0_sum, g1_sum) = if use_parallel {
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
         {
             (0..size / 2)
                 .into_par_iter()
@@ -784,7 +1038,7 @@ fn prove_streaming_with_stats_inner(
                     },
                 )
         }
-        #[cfg(target_arch = "wasm32")]
+        #[cfg(any(not(feature = "parallel"), target_arch = "wasm32"))]
         {
             (0u64, 0u64, 0u64)
         }
@@ -814,7 +1068,7 @@ fn prove_streaming_with_stats_inner(
     challenges.push(r);
 
     let (mut layer, mut current_sum) = if use_parallel {
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
         {
             let layer: Vec<u64> = (0..size / 2)
                 .into_par_iter()
@@ -832,7 +1086,7 @@ fn prove_streaming_with_stats_inner(
                 .reduce(|| 0u64, |acc, v| field.add(acc, v));
             (layer, current_sum)
         }
-        #[cfg(target_arch = "wasm32")]
+        #[cfg(any(not(feature = "parallel"), target_arch = "wasm32"))]
         {
             (Vec::new(), 0u64)
         }
@@ -850,23 +1104,24 @@ fn prove_streaming_with_stats_inner(
         (layer, current_sum)
     };
     round_durations.push(round_start.elapsed());
+    let mut peak_words = layer.len();
 
     for _round in 1..num_vars {
         round_sums.push(current_sum);
         let round_start = Instant::now();
         let use_parallel_layer = {
-            #[cfg(not(target_arch = "wasm32"))]
+            #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
             {
                 const PARALLEL_LAYER_THRESHOLD: usize = 1 << 14;
                 use_parallel && layer.len() >= PARALLEL_LAYER_THRESHOLD
             }
-            #[cfg(target_arch = "wasm32")]
+            #[cfg(any(not(feature = "parallel"), target_arch = "wasm32"))]
             {
                 false
             }
         };
         let (g0_sum, g1_sum) = if use_parallel_layer {
-            #[cfg(not(target_arch = "wasm32"))]
+            #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
             {
                 layer
                     .par_chunks(2)
@@ -876,7 +1131,7 @@ fn prove_streaming_with_stats_inner(
                         |acc, (v0, v1)| (field.add(acc.0, v0), field.add(acc.1, v1)),
                     )
             }
-            #[cfg(target_arch = "wasm32")]
+            #[cfg(any(not(feature = "parallel"), target_arch = "wasm32"))]
             {
                 (0u64, 0u64)
             }
@@ -898,7 +1153,7 @@ fn prove_streaming_with_stats_inner(
         challenges.push(r);
 
         let (next_layer, next_sum) = if use_parallel_layer {
-            #[cfg(not(target_arch = "wasm32"))]
+            #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
             {
                 let next_layer: Vec<u64> = layer
                     .par_chunks(2)
@@ -915,23 +1170,29 @@ fn prove_streaming_with_stats_inner(
                     .reduce(|| 0u64, |acc, v| field.add(acc, v));
                 (next_layer, next_sum)
             }
-            #[cfg(target_arch = "wasm32")]
+            #[cfg(any(not(feature = "parallel"), target_arch = "wasm32"))]
             {
                 (Vec::new(), 0u64)
             }
         } else {
-            let mut next_layer = Vec::with_capacity(layer.len() / 2);
+            let mut folded = std::mem::take(&mut layer);
+            let half = folded.len() / 2;
             let mut next_sum = 0u64;
-            for chunk in layer.chunks(2) {
-                let v0 = chunk[0];
-                let v1 = chunk[1];
+            for i in 0..half {
+                let v0 = folded[2 * i];
+                let v1 = folded[2 * i + 1];
                 let diff = field.sub(v1, v0);
                 let val = field.add(field.mul(diff, r), v0);
                 next_sum = field.add(next_sum, val);
-                next_layer.push(val);
+                folded[i] = val;
             }
-            (next_layer, next_sum)
+            folded.truncate(half);
+            (folded, next_sum)
         };
+        if use_parallel_layer {
+            // The parallel fold allocates the next layer beside the current one.
+            peak_words = peak_words.max(layer.len() + next_layer.len());
+        }
         layer = next_layer;
         current_sum = next_sum;
         round_durations.push(round_start.elapsed());
@@ -952,6 +1213,7 @@ fn prove_streaming_with_stats_inner(
     let stats = ProofStats {
         total_duration: total_start.elapsed(),
         round_durations,
+        peak_memory_bytes: peak_words * WORD_BYTES,
     };
     (proof, stats)
 }
@@ -993,9 +1255,12 @@ impl ChainedSumProof {
         let mut stats = Vec::with_capacity(polynomials.len());
         let mut links = Vec::with_capacity(polynomials.len());
         let mut previous_final: Option<u64> = None;
+        let mut scratch = ProverScratch::new();
         for poly in polynomials {
             let parent_for_this = previous_final;
-            let (proof, proof_stats) = GeneralSumProof::prove_with_stats(poly, field);
+            let (proof, proof_stats) =
+                GeneralSumProof::prove_with_budget(poly, field, None, &mut scratch)
+                    .expect("an unbounded prover cannot exceed its budget");
             stats.push(proof_stats);
             if let Some(expected_sum) = parent_for_this {
                 if field.sub(proof.claim.claimed_sum, expected_sum) != 0 {
@@ -1040,25 +1305,43 @@ impl ChainedSumProof {
         polynomials: &[MultilinearPolynomial],
         field: &Field,
     ) -> Option<Vec<GeneralSumTrace>> {
+        infallible(self.verify_with_traces_checked(polynomials, field, uninterrupted))
+    }
+
+    /// Like [`Self::verify_with_traces`], but calls `checkpoint` with the link
+    /// index before each link and each of its sum-check rounds, and stops with
+    /// its error, so callers can abandon long chains cooperatively.
+    pub fn verify_with_traces_checked<E>(
+        &self,
+        polynomials: &[MultilinearPolynomial],
+        field: &Field,
+        mut checkpoint: impl FnMut(usize) -> Result<(), E>,
+    ) -> Result<Option<Vec<GeneralSumTrace>>, E> {
         if self.links.len() != polynomials.len() {
-            return None;
+            return Ok(None);
         }
         let mut traces = Vec::with_capacity(self.links.len());
         let mut previous_final: Option<u64> = None;
-        for (link, poly) in self.links.iter().zip(polynomials) {
+        for (index, (link, poly)) in self.links.iter().zip(polynomials).enumerate() {
+            checkpoint(index)?;
             if link.parent_final != previous_final {
-                return None;
+                return Ok(None);
             }
-            let trace = link.proof.verify_with_trace(poly, field)?;
+            let Some(trace) = link
+                .proof
+                .verify_with_trace_checked(poly, field, None, |_| checkpoint(index))?
+            else {
+                return Ok(None);
+            };
             if let Some(expected_sum) = previous_final {
                 if field.sub(link.proof.claim.claimed_sum, expected_sum) != 0 {
-                    return None;
+                    return Ok(None);
                 }
             }
             previous_final = Some(trace.final_evaluation);
             traces.push(trace);
         }
-        Some(traces)
+        Ok(Some(traces))
     }
 
     /// Verifies the chain of proofs.
@@ -1067,24 +1350,37 @@ impl ChainedSumProof {
     }
 }
 
-fn verify_general_sum(
+/// Round checkpoint for verifiers that cannot be interrupted.
+fn uninterrupted(_round: usize) -> Result<(), Infallible> {
+    Ok(())
+}
+
+/// Unwraps the result of a verifier run with [`uninterrupted`].
+fn infallible<T>(result: Result<T, Infallible>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(never) => match never {},
+    }
+}
+
+fn verify_general_sum<E>(
     claim: &GeneralSumClaim,
     poly: &MultilinearPolynomial,
     field: &Field,
-) -> Option<GeneralSumTrace> {
+    prover: Option<&[u8]>,
+    mut checkpoint: impl FnMut(usize) -> Result<(), E>,
+) -> Result<Option<GeneralSumTrace>, E> {
     if claim.p != field.modulus() {
-        return None;
+        return Ok(None);
     }
     if claim.num_vars != poly.num_vars() {
-        return None;
+        return Ok(None);
     }
     if claim.rounds.len() != claim.num_vars {
-        return None;
+        return Ok(None);
     }
 
-    let mut transcript = Transcript::new(GENERAL_SUMCHECK_DOMAIN);
-    transcript.append(claim.p);
-    transcript.append(claim.num_vars as u64);
+    let mut transcript = general_transcript(claim.p, claim.num_vars, prover);
     transcript.append(claim.claimed_sum);
 
     let mut layer = poly.evaluations_mod_p(field);
@@ -1092,11 +1388,12 @@ fn verify_general_sum(
     let mut challenges = Vec::with_capacity(claim.num_vars);
     let mut round_sums = Vec::with_capacity(claim.num_vars);
 
-    for &(a, b) in &claim.rounds {
+    for (round_idx, &(a, b)) in claim.rounds.iter().enumerate() {
+        checkpoint(round_idx)?;
         round_sums.push(running_claim);
         let sum_check = field.add(b, field.add(a, b));
         if sum_check != running_claim {
-            return None;
+            return Ok(None);
         }
 
         transcript.append(a);
@@ -1119,40 +1416,40 @@ fn verify_general_sum(
     }
 
     if layer.len() != 1 {
-        return None;
+        return Ok(None);
     }
 
     let final_evaluation = poly.evaluate(field, &challenges);
     if final_evaluation != running_claim {
-        return None;
+        return Ok(None);
     }
 
-    Some(GeneralSumTrace {
+    Ok(Some(GeneralSumTrace {
         challenges,
         round_sums,
         final_evaluation,
-    })
+    }))
 }
 
-fn verify_general_sum_streaming(
+fn verify_general_sum_streaming<E>(
     claim: &GeneralSumClaim,
     poly: &StreamingPolynomial,
     field: &Field,
-) -> Option<GeneralSumTrace> {
+    prover: Option<&[u8]>,
+    mut checkpoint: impl FnMut(usize) -> Result<(), E>,
+) -> Result<Option<GeneralSumTrace>, E> {
     if claim.p != field.modulus() || claim.p != poly.modulus() {
-        return None;
+        return Ok(None);
     }
     if claim.num_vars != poly.num_vars() || claim.rounds.len() != claim.num_vars {
-        return None;
+        return Ok(None);
     }
 
     let p = claim.p;
     let num_vars = claim.num_vars;
     let size = 1usize << num_vars;
     let eval = poly.evaluator();
-    let mut transcript = Transcript::new(GENERAL_SUMCHECK_DOMAIN);
-    transcript.append(p);
-    transcript.append(num_vars as u64);
+    let mut transcript = general_transcript(p, num_vars, prover);
     transcript.append(claim.claimed_sum);
 
     let mut round_sums = Vec::with_capacity(num_vars);
@@ -1169,7 +1466,7 @@ fn verify_general_sum_streaming(
         computed_sum = field.add(computed_sum, field.add(v0, v1));
     }
     if computed_sum != claim.claimed_sum {
-        return None;
+        return Ok(None);
     }
     round_sums.push(computed_sum);
 
@@ -1177,8 +1474,9 @@ fn verify_general_sum_streaming(
     let mut running_sum = computed_sum;
 
     for (round_idx, &(a, b)) in claim.rounds.iter().enumerate() {
+        checkpoint(round_idx)?;
         if b % p != g0_sum || field.sub(g1_sum, g0_sum) != a {
-            return None;
+            return Ok(None);
         }
         transcript.append(a);
         transcript.append(b);
@@ -1224,18 +1522,18 @@ fn verify_general_sum_streaming(
     }
 
     if layer.len() != 1 {
-        return None;
+        return Ok(None);
     }
     let final_evaluation = layer[0];
     if final_evaluation != running_sum {
-        return None;
+        return Ok(None);
     }
 
-    Some(GeneralSumTrace {
+    Ok(Some(GeneralSumTrace {
         challenges,
         round_sums,
         final_evaluation,
-    })
+    }))
 }
 
 fn verify_constant_sum(
@@ -1400,7 +1698,7 @@ mod tests {
             0u64,
             forged.k as u64,
         ];
-        let r1 = derive_many_mod_p(forged.p, b"power_house:v1:sumcheck:r1", &base_transcript, 1)[0];
+        let r1 = demo_r1(&field, &base_transcript);
         let s1 = field.add(field.mul(forged.g1_a, r1), forged.g1_b);
         // Solve for b: a*r + b = t => r irrelevant here; ensure g2(0)+g2(1) = s1
         // g2(0) = b, g2(1) = a + b => sum = a + 2b.  We know desired sum s1.
@@ -1463,6 +1761,35 @@ mod tests {
         assert_eq!(trace.final_evaluation, proof.final_evaluation);
     }
 
+    #[test]
+    fn test_checked_verification_stops_between_rounds() {
+        let field = Field::new(101);
+        let poly = sample_poly(&field);
+        let proof = GeneralSumProof::prove(&poly, &field);
+        let mut visited = Vec::new();
+        let trace = proof
+            .verify_with_trace_checked(&poly, &field, None, |round| {
+                visited.push(round);
+                Ok::<(), usize>(())
+            })
+            .unwrap();
+        assert!(trace.is_some());
+        assert_eq!(visited, vec![0, 1, 2]);
+
+        let evals = poly.evaluations().to_vec();
+        let streaming_poly =
+            StreamingPolynomial::new(poly.num_vars(), field.modulus(), move |idx| evals[idx]);
+        let stop_at = |round: usize| if round == 1 { Err(round) } else { Ok(()) };
+        assert_eq!(
+            proof.verify_with_trace_checked(&poly, &field, None, stop_at),
+            Err(1)
+        );
+        assert_eq!(
+            proof.verify_streaming_with_trace_checked(&streaming_poly, &field, None, stop_at),
+            Err(1)
+        );
+    }
+
     #[test]
     fn test_general_sumproof_stats() {
         let field = Field::new(101);
@@ -1471,6 +1798,34 @@ mod tests {
         assert_eq!(stats.round_durations.len(), poly.num_vars());
     }
 
+    #[test]
+    fn test_budgeted_prover_streams_and_matches_dense() {
+        let field = Field::new(1_000_003);
+        let evals = (0..256u64).map(|i| i * i * 7_919 + 13).collect();
+        let poly = MultilinearPolynomial::from_evaluations(8, evals);
+        let (dense, dense_stats) = GeneralSumProof::prove_with_stats(&poly, &field);
+        assert_eq!(dense_stats.peak_memory_bytes, (256 + 1) * 8);
+
+        let mut scratch = ProverScratch::with_num_vars(8);
+        for budget in [4096, 1024, 300, 256] {
+            let (proof, stats) =
+                GeneralSumProof::prove_with_budget(&poly, &field, Some(budget), &mut scratch)
+                    .unwrap();
+            assert!(stats.peak_memory_bytes <= budget);
+            assert_eq!(proof.claim.rounds, dense.claim.rounds);
+            assert_eq!(proof.round_sums, dense.round_sums);
+            assert_eq!(proof.final_evaluation, dense.final_evaluation);
+            assert!(proof.verify(&poly, &field));
+        }
+        assert_eq!(
+            GeneralSumProof::prove_with_budget(&poly, &field, Some(200), &mut scratch).unwrap_err(),
+            MemoryBudgetError {
+                required: 256,
+                budget: 200
+            }
+        );
+    }
+
     #[test]
     fn test_streaming_matches_standard() {
         let field = Field::new(101);
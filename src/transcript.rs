@@ -3,10 +3,29 @@
 //! The [`Transcript`] type provides a minimal interface for recording field
 //! elements and deriving deterministic challenges using a domain-separated
 //! BLAKE2b-256 expander.  Challenges are produced by hashing the accumulated
-//! transcript words together with a monotonic counter and reducing the result
-//! modulo the ambient field.
+//! transcript words together with a monotonic counter and rejection-sampling
+//! the output into the ambient field, so every residue is equally likely.
+//! [`Transcript::with_generator`] selects another [`PrngBackend`]; its id is
+//! recorded as the labelled `generator` message that opens every transcript,
+//! so challenges drawn under different generators never coincide.
+//!
+//! Besides raw words, the transcript accepts labelled byte messages in the
+//! style of merlin: [`Transcript::append_message`],
+//! [`Transcript::challenge_bytes`] and [`Transcript::challenge_scalar`] bind
+//! a label to every input and output, and [`Transcript::fork`] derives an
+//! independent transcript for a sub-protocol that runs in parallel.
+
+use crate::{
+    prng::{derive_bytes_with, derive_many_mod_p_with, PrngBackend},
+    Field,
+};
 
-use crate::{prng::derive_many_mod_p, Field};
+/// Marks a label in the word stream.
+const LABEL_MARKER: u64 = 0x4c41_4245_4c00_0001;
+/// Marks a byte challenge, separating it from field challenges.
+const BYTES_MARKER: u64 = 0x4259_5445_5300_0002;
+/// Marks the branch point of a forked transcript.
+const FORK_MARKER: u64 = 0x464f_524b_0000_0003;
 
 /// Stateful helper that derives challenges from a recorded transcript.
 #[derive(Debug, Clone)]
@@ -14,16 +33,33 @@ pub struct Transcript {
     domain_tag: &'static [u8],
     words: Vec<u64>,
     counter: u64,
+    generator: PrngBackend,
 }
 
 impl Transcript {
     /// Creates an empty transcript associated with the given domain tag.
     pub fn new(domain_tag: &'static [u8]) -> Self {
-        Self {
+        Self::with_generator(domain_tag, PrngBackend::default())
+    }
+
+    /// Creates a transcript deriving challenges with `generator`.
+    ///
+    /// The generator id is absorbed as a labelled message before anything
+    /// else, so it is part of every challenge the transcript produces.
+    pub fn with_generator(domain_tag: &'static [u8], generator: PrngBackend) -> Self {
+        let mut transcript = Self {
             domain_tag,
             words: Vec::new(),
             counter: 0,
-        }
+            generator,
+        };
+        transcript.append_message(b"generator", generator.id().as_bytes());
+        transcript
+    }
+
+    /// Generator used for challenge derivation.
+    pub fn generator(&self) -> PrngBackend {
+        self.generator
     }
 
     /// Appends a single `u64` word to the transcript.
@@ -36,6 +72,15 @@ impl Transcript {
         self.words.extend_from_slice(values);
     }
 
+    /// Appends a labelled byte message.
+    ///
+    /// Label and message are length-prefixed, so no two distinct
+    /// `(label, message)` sequences produce the same transcript.
+    pub fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        self.append_label(label);
+        self.words.extend(bytes_to_words(message));
+    }
+
     /// Returns an immutable view of the accumulated transcript words.
     pub fn snapshot(&self) -> &[u64] {
         &self.words
@@ -48,10 +93,118 @@ impl Transcript {
     /// and returns it to the caller.
     pub fn challenge(&mut self, field: &Field) -> u64 {
         self.words.push(self.counter);
-        let challenge = derive_many_mod_p(field.modulus(), self.domain_tag, &self.words, 1)[0];
+        let challenge = derive_many_mod_p_with(
+            self.generator,
+            field.modulus(),
+            self.domain_tag,
+            &self.words,
+            1,
+        )[0];
         self.words.pop();
         self.words.push(challenge);
         self.counter = self.counter.wrapping_add(1);
         challenge
     }
+
+    /// Derives a labelled field challenge.
+    pub fn challenge_scalar(&mut self, label: &'static [u8], field: &Field) -> u64 {
+        self.append_label(label);
+        self.challenge(field)
+    }
+
+    /// Fills `dest` with a labelled challenge of arbitrary length.
+    ///
+    /// The output is absorbed like a field challenge, so later challenges
+    /// depend on it.
+    pub fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]) {
+        self.append_label(label);
+        self.words.push(BYTES_MARKER);
+        self.words.push(dest.len() as u64);
+        self.words.push(self.counter);
+        derive_bytes_with(self.generator, self.domain_tag, &self.words, dest);
+        self.words.pop();
+        self.words.extend(bytes_to_words(dest));
+        self.counter = self.counter.wrapping_add(1);
+    }
+
+    /// Returns an independent transcript for a sub-protocol.
+    ///
+    /// The fork starts from the current state and absorbs `label`, so
+    /// challenges drawn from it differ from those of the parent and of forks
+    /// with other labels.  The parent is left unchanged.
+    pub fn fork(&self, label: &'static [u8]) -> Self {
+        let mut fork = self.clone();
+        fork.words.push(FORK_MARKER);
+        fork.append_label(label);
+        fork
+    }
+
+    fn append_label(&mut self, label: &'static [u8]) {
+        self.words.push(LABEL_MARKER);
+        self.words.extend(bytes_to_words(label));
+    }
+}
+
+/// Length-prefixed big-endian packing of `bytes` into transcript words.
+pub(crate) fn bytes_to_words(bytes: &[u8]) -> Vec<u64> {
+    let mut words = Vec::with_capacity(1 + bytes.len().div_ceil(8));
+    words.push(bytes.len() as u64);
+    for chunk in bytes.chunks(8) {
+        let mut word = [0u8; 8];
+        word[..chunk.len()].copy_from_slice(chunk);
+        words.push(u64::from_be_bytes(word));
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_messages_and_forks_separate_challenges() {
+        let field = Field::new(1_000_000_007);
+        let mut base = Transcript::new(b"power_house:test:transcript");
+        base.append_message(b"commitment", &[1, 2, 3]);
+
+        let mut relabelled = Transcript::new(b"power_house:test:transcript");
+        relabelled.append_message(b"commitmen", &[b't', 1, 2, 3]);
+        assert_ne!(
+            base.clone().challenge_scalar(b"alpha", &field),
+            relabelled.challenge_scalar(b"alpha", &field)
+        );
+
+        let mut left = base.fork(b"left");
+        let mut right = base.fork(b"right");
+        let mut again = base.fork(b"left");
+        let l = left.challenge_scalar(b"alpha", &field);
+        assert_ne!(l, right.challenge_scalar(b"alpha", &field));
+        assert_eq!(l, again.challenge_scalar(b"alpha", &field));
+        assert_ne!(l, base.clone().challenge_scalar(b"alpha", &field));
+
+        let mut first = [0u8; 40];
+        let mut second = [0u8; 40];
+        base.clone().challenge_bytes(b"seed", &mut first);
+        base.challenge_bytes(b"seed", &mut second);
+        assert_eq!(first, second);
+        let mut next = [0u8; 40];
+        base.challenge_bytes(b"seed", &mut next);
+        assert_ne!(first, next);
+    }
+
+    #[test]
+    fn generator_id_is_recorded_as_a_labelled_message() {
+        let field = Field::new(1_000_000_007);
+        let mut expected = Vec::new();
+        expected.push(LABEL_MARKER);
+        expected.extend(bytes_to_words(b"generator"));
+        expected.extend(bytes_to_words(PrngBackend::ChaCha20.id().as_bytes()));
+        let chacha =
+            Transcript::with_generator(b"power_house:test:transcript", PrngBackend::ChaCha20);
+        assert_eq!(chacha.snapshot(), expected.as_slice());
+
+        let mut blake = Transcript::new(b"power_house:test:transcript");
+        assert_ne!(blake.snapshot(), chacha.snapshot());
+        assert_ne!(blake.challenge(&field), chacha.clone().challenge(&field));
+    }
 }
@@ -0,0 +1,384 @@
+//! Verification results cached by proof digest.
+//!
+//! [`ProofLedger`](crate::ProofLedger) re-runs the full verifier for every
+//! submission, so an identical proof submitted twice costs twice.  With a
+//! [`VerificationCache`] enabled the ledger first computes [`proof_digest`],
+//! a domain-separated BLAKE2b-256 hash over every field the verifier reads,
+//! and reuses the recorded outcome when the digest was seen before.  Each
+//! submission still becomes its own ledger entry and still writes its own
+//! transcript records; only the verifier run is skipped.
+//!
+//! The cache keeps at most `capacity` results and evicts the least recently
+//! used one when full.  It can optionally be persisted as JSON so results
+//! survive restarts.  The persisted file is trusted local state, like the
+//! transcript logs themselves: anyone who can edit it can make the ledger
+//! accept a proof it would otherwise reject.
+
+use crate::{GeneralSumProof, MultilinearPolynomial, ProofKind, TranscriptDigest};
+use blake2::digest::{consts::U32, Digest};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Schema identifier of a persisted verification cache.
+pub const SCHEMA_VERIFICATION_CACHE: &str = "mfenx.powerhouse.verification_cache.v1";
+
+const PROOF_DOMAIN: &[u8] = b"MFENX_PROOF";
+
+type Blake2b256 = blake2::Blake2b<U32>;
+
+/// Outcome of one verifier run, as recorded in a ledger entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedVerification {
+    /// Whether the proof was accepted.
+    pub accepted: bool,
+    /// Fiat–Shamir challenges of each transcript.
+    pub transcripts: Vec<Vec<u64>>,
+    /// Round sums of each transcript.
+    pub round_sums: Vec<Vec<u64>>,
+    /// Final evaluation of each transcript.
+    pub final_values: Vec<u64>,
+}
+
+/// Canonical digest of a proof payload, or `None` when it cannot be cached.
+///
+/// The digest covers the proof kind, the crate version, and every polynomial
+/// evaluation and proof field the verifier consumes, so two payloads share a
+/// digest only when verification would see identical inputs.  Streaming
+/// proofs are not cached because their evaluator is a closure that cannot be
+/// hashed without evaluating it; replayed and genesis entries are never
+/// verified in the first place.
+pub fn proof_digest(kind: &ProofKind) -> Option<TranscriptDigest> {
+    let mut hasher = Blake2b256::new();
+    hasher.update(PROOF_DOMAIN);
+    mix_bytes(&mut hasher, env!("CARGO_PKG_VERSION").as_bytes());
+    match kind {
+        ProofKind::Demo(claim) => {
+            mix_bytes(&mut hasher, b"demo");
+            for value in [
+                claim.p,
+                claim.claimed_sum,
+                claim.g1_a,
+                claim.g1_b,
+                claim.g2_a,
+                claim.g2_b,
+                claim.k as u64,
+            ] {
+                mix_u64(&mut hasher, value);
+            }
+        }
+        ProofKind::General { polynomial, proof } => {
+            mix_bytes(&mut hasher, b"general");
+            mix_polynomial(&mut hasher, polynomial);
+            mix_general_proof(&mut hasher, proof);
+        }
+        ProofKind::Chain { polynomials, proof } => {
+            mix_bytes(&mut hasher, b"chain");
+            mix_u64(&mut hasher, polynomials.len() as u64);
+            for polynomial in polynomials {
+                mix_polynomial(&mut hasher, polynomial);
+            }
+            mix_u64(&mut hasher, proof.links().len() as u64);
+            for link in proof.links() {
+                match link.parent_final {
+                    Some(value) => {
+                        hasher.update([1]);
+                        mix_u64(&mut hasher, value);
+                    }
+                    None => hasher.update([0]),
+                }
+                mix_general_proof(&mut hasher, &link.proof);
+            }
+        }
+        ProofKind::Range(range) => {
+            mix_bytes(&mut hasher, b"range");
+            mix_u64_list(&mut hasher, &range.values);
+            mix_u64(&mut hasher, range.bits as u64);
+            mix_u64_list(&mut hasher, &range.decomposition);
+            mix_general_proof(&mut hasher, &range.proof);
+        }
+        ProofKind::StreamingGeneral { .. } | ProofKind::Replayed | ProofKind::Genesis => {
+            return None
+        }
+    }
+    Some(hasher.finalize().into())
+}
+
+fn mix_u64(hasher: &mut Blake2b256, value: u64) {
+    hasher.update(value.to_be_bytes());
+}
+
+/// [`proof_digest`] of a proof verified bound to the `prover` key.
+pub(crate) fn bound_proof_digest(kind: &ProofKind, prover: &[u8]) -> Option<TranscriptDigest> {
+    let digest = proof_digest(kind)?;
+    let mut hasher = Blake2b256::new();
+    hasher.update(PROOF_DOMAIN);
+    mix_bytes(&mut hasher, b"bound");
+    mix_bytes(&mut hasher, prover);
+    hasher.update(digest);
+    Some(hasher.finalize().into())
+}
+
+fn mix_bytes(hasher: &mut Blake2b256, bytes: &[u8]) {
+    mix_u64(hasher, bytes.len() as u64);
+    hasher.update(bytes);
+}
+
+fn mix_u64_list(hasher: &mut Blake2b256, values: &[u64]) {
+    mix_u64(hasher, values.len() as u64);
+    for value in values {
+        mix_u64(hasher, *value);
+    }
+}
+
+fn mix_polynomial(hasher: &mut Blake2b256, polynomial: &MultilinearPolynomial) {
+    mix_u64(hasher, polynomial.num_vars() as u64);
+    mix_u64_list(hasher, polynomial.evaluations());
+}
+
+fn mix_general_proof(hasher: &mut Blake2b256, proof: &GeneralSumProof) {
+    mix_u64(hasher, proof.claim.p);
+    mix_u64(hasher, proof.claim.num_vars as u64);
+    mix_u64(hasher, proof.claim.claimed_sum);
+    mix_u64(hasher, proof.claim.rounds.len() as u64);
+    for (a, b) in &proof.claim.rounds {
+        mix_u64(hasher, *a);
+        mix_u64(hasher, *b);
+    }
+    mix_u64_list(hasher, &proof.challenges);
+    mix_u64_list(hasher, &proof.round_sums);
+    mix_u64(hasher, proof.final_evaluation);
+}
+
+/// Bounded least-recently-used cache of verification outcomes.
+#[derive(Debug, Clone)]
+pub struct VerificationCache {
+    capacity: usize,
+    entries: HashMap<TranscriptDigest, (CachedVerification, u64)>,
+    clock: u64,
+    path: Option<PathBuf>,
+    hits: u64,
+    misses: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PersistedCache {
+    schema: String,
+    entries: Vec<PersistedEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PersistedEntry {
+    digest: String,
+    #[serde(flatten)]
+    result: CachedVerification,
+}
+
+impl VerificationCache {
+    /// Creates an in-memory cache holding at most `capacity` results.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            clock: 0,
+            path: None,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Opens a cache persisted at `path`, starting empty when the file does
+    /// not exist yet.
+    ///
+    /// Every insertion rewrites the file.  Entries beyond `capacity` are
+    /// dropped oldest first.
+    pub fn open<P: Into<PathBuf>>(path: P, capacity: usize) -> io::Result<Self> {
+        let path = path.into();
+        let mut cache = Self::new(capacity);
+        match fs::read(&path) {
+            Ok(bytes) => {
+                let persisted: PersistedCache = serde_json::from_slice(&bytes)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+                if persisted.schema != SCHEMA_VERIFICATION_CACHE {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("unexpected schema {}", persisted.schema),
+                    ));
+                }
+                for entry in persisted.entries {
+                    let digest = crate::transcript_digest_from_hex(&entry.digest)
+                        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+                    cache.store(digest, entry.result);
+                }
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+        cache.path = Some(path);
+        Ok(cache)
+    }
+
+    /// Maximum number of results kept.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of results currently cached.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` when nothing is cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Lookups answered from the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Lookups that required a verifier run.
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// File the cache is persisted to, if any.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Returns the cached outcome for `digest` and marks it recently used.
+    pub fn get(&mut self, digest: &TranscriptDigest) -> Option<CachedVerification> {
+        self.clock += 1;
+        match self.entries.get_mut(digest) {
+            Some((result, last_used)) => {
+                *last_used = self.clock;
+                self.hits += 1;
+                Some(result.clone())
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Records the outcome for `digest`, evicting the least recently used
+    /// result when the cache is full, and rewrites the persisted file.
+    pub fn insert(
+        &mut self,
+        digest: TranscriptDigest,
+        result: CachedVerification,
+    ) -> io::Result<()> {
+        self.store(digest, result);
+        self.save()
+    }
+
+    fn store(&mut self, digest: TranscriptDigest, result: CachedVerification) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.clock += 1;
+        if !self.entries.contains_key(&digest) && self.entries.len() >= self.capacity {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(digest, _)| *digest)
+                .expect("cache is non-empty");
+            self.entries.remove(&oldest);
+        }
+        self.entries.insert(digest, (result, self.clock));
+    }
+
+    /// Writes the cache to its file, least recently used first; a no-op for
+    /// in-memory caches.
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let mut ordered: Vec<_> = self.entries.iter().collect();
+        ordered.sort_by_key(|(_, (_, last_used))| *last_used);
+        let persisted = PersistedCache {
+            schema: SCHEMA_VERIFICATION_CACHE.to_string(),
+            entries: ordered
+                .into_iter()
+                .map(|(digest, (result, _))| PersistedEntry {
+                    digest: crate::transcript_digest_to_hex(digest),
+                    result: result.clone(),
+                })
+                .collect(),
+        };
+        let bytes = serde_json::to_vec(&persisted)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, bytes)?;
+        fs::rename(&tmp, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Field, Proof, ProofLedger, Statement};
+
+    fn general_proof(seed: u64) -> Proof {
+        let field = Field::new(101);
+        let polynomial = MultilinearPolynomial::from_evaluations(2, vec![seed, 3, 5, 7]);
+        let proof = GeneralSumProof::prove(&polynomial, &field);
+        Proof {
+            kind: ProofKind::General { polynomial, proof },
+            data: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn duplicate_proofs_reuse_cached_verification() {
+        let path = std::env::temp_dir().join(format!("verify_cache_{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut ledger = ProofLedger::new();
+        ledger
+            .enable_persistent_verification_cache(&path, 1)
+            .unwrap();
+        ledger.submit(Statement::new("first"), general_proof(1));
+        ledger.submit(Statement::new("again"), general_proof(1));
+        let cache = ledger.verification_cache().unwrap();
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+        let entries = ledger.entries();
+        assert_eq!(entries.len(), 3);
+        assert!(entries[1].accepted && entries[2].accepted);
+        assert_eq!(entries[1].hashes, entries[2].hashes);
+
+        let mut tampered = general_proof(1);
+        if let ProofKind::General { proof, .. } = &mut tampered.kind {
+            proof.final_evaluation += 1;
+        }
+        assert_ne!(
+            proof_digest(&tampered.kind),
+            proof_digest(&general_proof(1).kind)
+        );
+        ledger.submit(Statement::new("tampered"), tampered);
+        assert!(!ledger.entries()[3].accepted);
+        assert_eq!(ledger.verification_cache().unwrap().len(), 1);
+
+        let mut restarted = ProofLedger::new();
+        restarted
+            .enable_persistent_verification_cache(&path, 4)
+            .unwrap();
+        let mut tampered = general_proof(1);
+        if let ProofKind::General { proof, .. } = &mut tampered.kind {
+            proof.final_evaluation += 1;
+        }
+        restarted.submit(Statement::new("tampered"), tampered);
+        restarted.submit(Statement::new("first"), general_proof(1));
+        let cache = restarted.verification_cache().unwrap();
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+        assert!(!restarted.entries()[1].accepted && restarted.entries()[2].accepted);
+        assert_eq!(cache.len(), 2);
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "net")]
+
+use power_house::{transcript_digest, transcript_digest_to_hex};
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn temp_dir() -> PathBuf {
+    let suffix = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let path = std::env::temp_dir().join(format!("power-house-archive-{suffix}"));
+    fs::create_dir_all(&path).unwrap();
+    path
+}
+
+fn log_record(statement: &str, final_value: u64) -> String {
+    let hash = transcript_digest(&[3, 5], &[7, 11], final_value);
+    format!(
+        "statement:{statement}\ntranscript:3 5\nround_sums:7 11\nfinal:{final_value}\nhash:{}\n",
+        transcript_digest_to_hex(&hash)
+    )
+}
+
+#[test]
+fn archive_export_and_verify_roundtrip() {
+    let dir = temp_dir();
+    fs::write(dir.join("ledger_0000.txt"), log_record("Archived", 13)).unwrap();
+    let archive = dir.join("archive.json");
+
+    let julian = env!("CARGO_BIN_EXE_julian");
+    let output = Command::new(julian)
+        .args([
+            "node",
+            "archive",
+            "export",
+            dir.to_str().unwrap(),
+            "--node-id",
+            "auditor-test",
+            "--output",
+            archive.to_str().unwrap(),
+            "--key",
+            "ed25519://archive-cli",
+        ])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("1 epoch(s)"), "{stdout}");
+
+    let output = Command::new(julian)
+        .args(["node", "archive", "verify", archive.to_str().unwrap()])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.starts_with("PASS"), "{stdout}");
+
+    let mut bundle: Value = serde_json::from_slice(&fs::read(&archive).unwrap()).unwrap();
+    bundle["entries"][0]["epoch"] = Value::from(7u64);
+    fs::write(&archive, serde_json::to_vec(&bundle).unwrap()).unwrap();
+    let output = Command::new(julian)
+        .args(["node", "archive", "verify", archive.to_str().unwrap()])
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains("link hash mismatch"), "{stderr}");
+
+    fs::remove_dir_all(&dir).unwrap();
+}
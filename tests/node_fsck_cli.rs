@@ -0,0 +1,75 @@
+use power_house::{transcript_digest, transcript_digest_to_hex};
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn temp_dir() -> PathBuf {
+    let suffix = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let path = std::env::temp_dir().join(format!("power-house-fsck-{suffix}"));
+    fs::create_dir_all(&path).unwrap();
+    path
+}
+
+fn log_record(statement: &str, final_value: u64) -> String {
+    let hash = transcript_digest(&[3, 5], &[7, 11], final_value);
+    format!(
+        "statement:{statement}\ntranscript:3 5\nround_sums:7 11\nfinal:{final_value}\nhash:{}\n",
+        transcript_digest_to_hex(&hash)
+    )
+}
+
+#[test]
+fn fsck_classifies_and_quarantines_bad_logs() {
+    let dir = temp_dir();
+    fs::write(dir.join("ledger_0000.txt"), log_record("Good", 13)).unwrap();
+    fs::write(
+        dir.join("ledger_0001.txt"),
+        log_record("Edited", 13).replace("final:13", "final:14"),
+    )
+    .unwrap();
+    fs::write(
+        dir.join("ledger_0002.txt"),
+        log_record("Orphan", 13).replace("statement:Orphan\n", ""),
+    )
+    .unwrap();
+    fs::write(dir.join("ledger_0003.txt"), log_record("Wide", 200)).unwrap();
+
+    let julian = env!("CARGO_BIN_EXE_julian");
+    let dir_arg = dir.to_str().unwrap();
+    let output = Command::new(julian)
+        .args(["node", "fsck", dir_arg, "--modulus", "101"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!output.status.success(), "{stdout}");
+    assert!(stdout.contains("4 record(s), 3 fault(s)"), "{stdout}");
+    assert!(stdout.contains("bad-digest"), "{stdout}");
+    assert!(stdout.contains("missing-statement"), "{stdout}");
+    assert!(stdout.contains("stale-modulus"), "{stdout}");
+    assert!(stdout.contains("after:  2 entries"), "{stdout}");
+    assert!(dir.join("ledger_0001.txt").exists());
+
+    let output = Command::new(julian)
+        .args(["node", "fsck", dir_arg, "--modulus", "101", "--quarantine"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success(), "{stdout}");
+    assert!(!dir.join("ledger_0001.txt").exists());
+    assert!(dir.join("quarantine").join("ledger_0001.txt").exists());
+
+    let output = Command::new(julian)
+        .args(["node", "fsck", dir_arg, "--modulus", "101"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("1 record(s), 0 fault(s)"), "{stdout}");
+    assert!(stdout.contains("before: 2 entries"), "{stdout}");
+
+    fs::remove_dir_all(&dir).unwrap();
+}